@@ -0,0 +1,109 @@
+// src/admin/handlers/activity.rs
+//! Per-admin activity feed and the preferences behind it and the daily digest
+
+use axum::{
+    extract::{Extension, Query},
+    Json,
+};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::info;
+
+use crate::admin::models::{
+    ActivityEvent, ActivityPreferences, ActivityQuery, UpdateActivityPreferencesRequest,
+};
+use crate::auth::AuthedUser;
+use crate::common::{ApiError, AppState};
+use crate::services::activity::{self, ACTIVITY_SCOPES};
+use crate::services::notification_preferences;
+
+const DEFAULT_LIMIT: i64 = 100;
+const MAX_LIMIT: i64 = 500;
+
+/// GET /api/admin/activity/feed?since=&scope=&limit= - Notable events on the caller's jobs,
+/// newest first, filtered by their preferences
+pub async fn get_activity_feed(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Query(query): Query<ActivityQuery>,
+) -> Result<Json<Vec<ActivityEvent>>, ApiError> {
+    let state = state_lock.read().await.clone();
+    authed.require_admin("Activity feed")?;
+
+    let scope = query.scope.as_deref().unwrap_or("assigned");
+    if !ACTIVITY_SCOPES.contains(&scope) {
+        return Err(ApiError::BadRequest(format!(
+            "Invalid scope '{}'; expected one of {}",
+            scope,
+            ACTIVITY_SCOPES.join(", ")
+        )));
+    }
+    let since = match query.since.as_deref() {
+        Some(since) => activity::parse_since(since)?,
+        None => (chrono::Utc::now() - chrono::Duration::days(1))
+            .format("%Y-%m-%d %H:%M:%S")
+            .to_string(),
+    };
+    let limit = query.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+
+    let prefs = notification_preferences::activity(&state.db, &authed.id)
+        .await
+        .map_err(ApiError::DatabaseError)?;
+    let events = activity::feed(&state.db, &authed.id, &prefs, &since, scope == "all", limit)
+        .await
+        .map_err(ApiError::DatabaseError)?;
+
+    Ok(Json(events))
+}
+
+/// GET /api/admin/activity/preferences
+pub async fn get_activity_preferences(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+) -> Result<Json<ActivityPreferences>, ApiError> {
+    let state = state_lock.read().await.clone();
+    authed.require_admin("Activity feed")?;
+
+    Ok(Json(
+        notification_preferences::activity(&state.db, &authed.id)
+            .await
+            .map_err(ApiError::DatabaseError)?,
+    ))
+}
+
+/// PUT /api/admin/activity/preferences - Choose what the feed shows and whether, and when,
+/// the daily digest is emailed
+pub async fn update_activity_preferences(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Json(request): Json<UpdateActivityPreferencesRequest>,
+) -> Result<Json<ActivityPreferences>, ApiError> {
+    let state = state_lock.read().await.clone();
+    authed.require_admin("Activity feed")?;
+
+    let mut prefs = notification_preferences::activity(&state.db, &authed.id)
+        .await
+        .map_err(ApiError::DatabaseError)?;
+    if let Some(hour) = request.digest_hour {
+        activity::validate_digest_hour(hour)?;
+        prefs.digest_hour = hour;
+    }
+    prefs.digest_enabled = request.digest_enabled.unwrap_or(prefs.digest_enabled);
+    prefs.applications = request.applications.unwrap_or(prefs.applications);
+    prefs.stage_changes = request.stage_changes.unwrap_or(prefs.stage_changes);
+    prefs.interviews = request.interviews.unwrap_or(prefs.interviews);
+    prefs.offers = request.offers.unwrap_or(prefs.offers);
+
+    activity::save_preferences(&state.db, &authed.id, &prefs)
+        .await
+        .map_err(ApiError::DatabaseError)?;
+
+    info!(
+        user_id = %authed.id,
+        digest_enabled = prefs.digest_enabled,
+        digest_hour = prefs.digest_hour,
+        "Activity preferences updated"
+    );
+
+    Ok(Json(prefs))
+}
@@ -0,0 +1,151 @@
+// src/admin/handlers/ai_models.rs
+//! Which OpenAI model serves each AI purpose. Changes apply from the next request.
+
+use axum::{
+    extract::{Extension, Path},
+    Json,
+};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::{info, warn};
+
+use crate::admin::models::{AiModelsResponse, AiPurposeModel, UpdateAiModelRequest};
+use crate::auth::AuthedUser;
+use crate::common::{ApiError, AppState};
+use crate::services::openai::{ModelPurpose, OpenAIError, REASONING_EFFORTS};
+use crate::services::settings::SettingsError;
+
+fn settings_error(e: OpenAIError) -> ApiError {
+    ApiError::InternalServer(format!("Failed to read AI settings: {}", e))
+}
+
+async fn purpose_model(
+    state: &AppState,
+    purpose: ModelPurpose,
+) -> Result<AiPurposeModel, ApiError> {
+    Ok(AiPurposeModel {
+        purpose: purpose.as_str().to_string(),
+        model: state
+            .openai_service
+            .model_for(purpose)
+            .await
+            .map_err(settings_error)?,
+        default_model: purpose.default_model().to_string(),
+        reasoning_effort: state
+            .openai_service
+            .reasoning_effort_for(purpose)
+            .await
+            .map_err(settings_error)?,
+        default_reasoning_effort: purpose.default_reasoning_effort().map(str::to_string),
+    })
+}
+
+/// GET /api/admin/ai/models - The model per purpose, and the models the API key can use
+pub async fn get_ai_models(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+) -> Result<Json<AiModelsResponse>, ApiError> {
+    let state = state_lock.read().await.clone();
+    authed.require_admin("AI model configuration")?;
+
+    let mut purposes = Vec::new();
+    for purpose in ModelPurpose::ALL {
+        purposes.push(purpose_model(&state, purpose).await?);
+    }
+
+    let (available_models, available_models_error) = match state.openai_service.list_models().await
+    {
+        Ok(mut models) => {
+            models.sort();
+            (Some(models), None)
+        }
+        Err(e) => (None, Some(e.to_string())),
+    };
+
+    Ok(Json(AiModelsResponse {
+        purposes,
+        available_models,
+        available_models_error,
+    }))
+}
+
+/// PUT /api/admin/ai/models/:purpose - Set the model (and reasoning effort) for a purpose
+pub async fn update_ai_model(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Path(purpose): Path<String>,
+    Json(request): Json<UpdateAiModelRequest>,
+) -> Result<Json<AiPurposeModel>, ApiError> {
+    let state = state_lock.read().await.clone();
+    authed.require_admin("AI model configuration")?;
+
+    let purpose = ModelPurpose::parse(&purpose)
+        .ok_or_else(|| ApiError::NotFound(format!("Unknown AI purpose '{}'", purpose)))?;
+
+    let model = request.model.trim();
+    if model.is_empty() {
+        return Err(ApiError::ValidationError("model is required".to_string()));
+    }
+    if !purpose.accepts_model(model) {
+        return Err(ApiError::ValidationError(format!(
+            "'{}' cannot be used for {}",
+            model,
+            purpose.as_str()
+        )));
+    }
+
+    let reasoning_effort = request.reasoning_effort.as_deref().map(str::trim);
+    match (reasoning_effort, purpose.reasoning_setting_key()) {
+        (Some(_), None) => {
+            return Err(ApiError::ValidationError(format!(
+                "{} does not take a reasoning effort",
+                purpose.as_str()
+            )))
+        }
+        (Some(effort), Some(_)) if !REASONING_EFFORTS.contains(&effort) => {
+            return Err(ApiError::ValidationError(format!(
+                "reasoning_effort must be one of: {}",
+                REASONING_EFFORTS.join(", ")
+            )))
+        }
+        _ => {}
+    }
+
+    // Only accept models the configured key can actually call
+    let available = state.openai_service.list_models().await.map_err(|e| {
+        warn!(error = %e, "Could not verify model against OpenAI");
+        ApiError::ServiceUnavailable(format!("Could not verify the model with OpenAI: {}", e))
+    })?;
+    if !available.iter().any(|m| m == model) {
+        return Err(ApiError::ValidationError(format!(
+            "Model '{}' is not available to the configured OpenAI API key",
+            model
+        )));
+    }
+
+    let save_error = |e: SettingsError| {
+        ApiError::InternalServer(format!("Failed to save AI settings: {}", e))
+    };
+    state
+        .settings_service
+        .set_setting(&purpose.model_setting_key(), model, false, Some(&authed.id))
+        .await
+        .map_err(save_error)?;
+    if let (Some(effort), Some(key)) = (reasoning_effort, purpose.reasoning_setting_key()) {
+        state
+            .settings_service
+            .set_setting(&key, effort, false, Some(&authed.id))
+            .await
+            .map_err(save_error)?;
+    }
+
+    info!(
+        admin_id = %authed.id,
+        purpose = purpose.as_str(),
+        model = %model,
+        reasoning_effort = ?reasoning_effort,
+        "AI model updated"
+    );
+
+    Ok(Json(purpose_model(&state, purpose).await?))
+}
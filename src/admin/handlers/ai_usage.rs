@@ -0,0 +1,96 @@
+// src/admin/handlers/ai_usage.rs
+//! AI usage and estimated cost reports, for attributing the OpenAI bill
+
+use axum::{
+    extract::{Extension, Query},
+    http::{header, StatusCode},
+    response::{IntoResponse, Json, Response},
+};
+use serde_json::json;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::info;
+
+use crate::admin::models::{AiUsageMonthlyQuery, AiUsageQuery};
+use crate::auth::AuthedUser;
+use crate::common::{parse_date, ApiError, AppState};
+use crate::services::ai_usage::{self, MonthlyUsage, UsageGroup};
+
+const DEFAULT_MONTHS: i64 = 6;
+const MAX_MONTHS: i64 = 36;
+
+/// GET /api/admin/ai/usage?group_by=&from=&to=&format= - Usage and estimated cost by day, feature, user, model or route
+pub async fn get_ai_usage_report(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Query(query): Query<AiUsageQuery>,
+) -> Result<Response, ApiError> {
+    let state = state_lock.read().await.clone();
+    authed.require_admin("AI usage")?;
+
+    let group = UsageGroup::parse(query.group_by.as_deref().unwrap_or("day"))?;
+    parse_date(query.from.as_deref(), "from")?;
+    parse_date(query.to.as_deref(), "to")?;
+
+    let rows = ai_usage::summarize(&state.db_read, group, query.from.as_deref(), query.to.as_deref())
+        .await
+        .map_err(ApiError::DatabaseError)?;
+
+    info!(
+        admin_id = %authed.id,
+        group_by = group.as_str(),
+        rows = rows.len(),
+        "AI usage report generated"
+    );
+
+    match query.format.as_deref().unwrap_or("json") {
+        "csv" => Ok((
+            StatusCode::OK,
+            [
+                (header::CONTENT_TYPE, "text/csv".to_string()),
+                (
+                    header::CONTENT_DISPOSITION,
+                    format!(
+                        "attachment; filename=\"ai_usage_by_{}.csv\"",
+                        group.as_str()
+                    ),
+                ),
+            ],
+            ai_usage::to_csv(group, &rows),
+        )
+            .into_response()),
+        "json" => {
+            let total_cost = rows.iter().fold(0.0, |sum, r| sum + r.estimated_cost);
+            let total_requests: i64 = rows.iter().map(|r| r.requests).sum();
+            Ok(Json(json!({
+                "group_by": group.as_str(),
+                "from": query.from,
+                "to": query.to,
+                "total_requests": total_requests,
+                "total_estimated_cost": total_cost,
+                "rows": rows,
+            }))
+            .into_response())
+        }
+        _ => Err(ApiError::ValidationError(
+            "format must be 'json' or 'csv'".to_string(),
+        )),
+    }
+}
+
+/// GET /api/admin/ai/usage/monthly?months= - Monthly totals with month-over-month cost change
+pub async fn get_ai_usage_monthly(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Query(query): Query<AiUsageMonthlyQuery>,
+) -> Result<Json<Vec<MonthlyUsage>>, ApiError> {
+    let state = state_lock.read().await.clone();
+    authed.require_admin("AI usage")?;
+
+    let months = query.months.unwrap_or(DEFAULT_MONTHS).clamp(1, MAX_MONTHS);
+    let usage = ai_usage::monthly(&state.db_read, months)
+        .await
+        .map_err(ApiError::DatabaseError)?;
+
+    Ok(Json(usage))
+}
@@ -0,0 +1,42 @@
+// src/admin/handlers/analytics.rs
+//! On-demand refresh of the precomputed analytics tables
+
+use axum::{
+    extract::{Extension, Query},
+    Json,
+};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::{error, info};
+
+use crate::admin::models::AnalyticsRefreshQuery;
+use crate::auth::AuthedUser;
+use crate::common::{ApiError, AppState};
+use crate::services::analytics_facts::{self, RefreshSummary};
+
+/// POST /api/admin/analytics/refresh?full= - Bring the analytics tables up to date now
+/// rather than at the next scheduled refresh
+pub async fn refresh_analytics(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Query(query): Query<AnalyticsRefreshQuery>,
+) -> Result<Json<RefreshSummary>, ApiError> {
+    let state = state_lock.read().await.clone();
+    authed.require_admin("Analytics refresh")?;
+
+    let summary = analytics_facts::refresh(&state.db, query.full)
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Database error refreshing analytics");
+            ApiError::DatabaseError(e)
+        })?;
+
+    info!(
+        admin_user_id = %authed.id,
+        full = summary.full,
+        applications = summary.applications,
+        "Analytics refreshed"
+    );
+
+    Ok(Json(summary))
+}
@@ -0,0 +1,282 @@
+// src/admin/handlers/chat_webhooks.rs
+//! Slack and Teams webhooks for recruiting events
+
+use axum::{
+    extract::{Extension, Path, Query},
+    http::StatusCode,
+    Json,
+};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::{error, info};
+
+use crate::admin::models::{
+    ChatWebhook, ChatWebhookQuery, CreateChatWebhookRequest, MessageResponse,
+    UpdateChatWebhookRequest,
+};
+use crate::auth::AuthedUser;
+use crate::common::{generate_chat_webhook_id, ApiError, AppState};
+use crate::services::chat_webhooks::{self, ChatMessage};
+
+const MAX_NAME_LENGTH: usize = 100;
+
+async fn fetch_webhook(state: &AppState, id: &str) -> Result<ChatWebhook, ApiError> {
+    sqlx::query_as::<_, ChatWebhook>("SELECT * FROM chat_webhooks WHERE id = ?")
+        .bind(id)
+        .fetch_optional(&state.db)
+        .await
+        .map_err(ApiError::DatabaseError)?
+        .ok_or_else(|| ApiError::NotFound("Chat webhook not found".to_string()))
+}
+
+/// The webhook URL is a credential; responses only show its host
+fn masked(mut webhook: ChatWebhook) -> ChatWebhook {
+    webhook.webhook_url = chat_webhooks::mask_webhook_url(&webhook.webhook_url);
+    webhook
+}
+
+fn validate_name(name: &str) -> Result<(), ApiError> {
+    if name.is_empty() || name.chars().count() > MAX_NAME_LENGTH {
+        return Err(ApiError::ValidationError(format!(
+            "Name must be between 1 and {} characters",
+            MAX_NAME_LENGTH
+        )));
+    }
+    Ok(())
+}
+
+/// GET /api/admin/chat-webhooks?company_id=&job_id= - Webhooks, optionally of one company
+/// or job
+pub async fn list_chat_webhooks(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Query(query): Query<ChatWebhookQuery>,
+) -> Result<Json<Vec<ChatWebhook>>, ApiError> {
+    let state = state_lock.read().await.clone();
+    authed.require_admin("Chat webhook")?;
+
+    let webhooks = sqlx::query_as::<_, ChatWebhook>(
+        r#"
+        SELECT * FROM chat_webhooks
+        WHERE (?1 IS NULL OR company_id = ?1)
+          AND (?2 IS NULL OR job_id = ?2)
+        ORDER BY created_at DESC
+        "#,
+    )
+    .bind(query.company_id.as_deref())
+    .bind(query.job_id.as_deref())
+    .fetch_all(&state.db)
+    .await
+    .map_err(|e| {
+        error!(error = %e, "Database error listing chat webhooks");
+        ApiError::DatabaseError(e)
+    })?;
+
+    Ok(Json(webhooks.into_iter().map(masked).collect()))
+}
+
+/// POST /api/admin/chat-webhooks - Post a company's or a job's recruiting events to a
+/// Slack or Teams channel
+pub async fn create_chat_webhook(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Json(request): Json<CreateChatWebhookRequest>,
+) -> Result<(StatusCode, Json<ChatWebhook>), ApiError> {
+    let state = state_lock.read().await.clone();
+    authed.require_admin("Chat webhook")?;
+
+    let provider = request.provider.trim().to_lowercase();
+    let name = request.name.trim().to_string();
+    let webhook_url = request.webhook_url.trim().to_string();
+    validate_name(&name)?;
+    chat_webhooks::validate_webhook(&provider, &webhook_url).map_err(ApiError::ValidationError)?;
+
+    let (table, target_id) = match (request.company_id.as_deref(), request.job_id.as_deref()) {
+        (Some(company_id), None) => ("companies", company_id),
+        (None, Some(job_id)) => ("jobs", job_id),
+        _ => {
+            return Err(ApiError::ValidationError(
+                "Set exactly one of company_id and job_id".to_string(),
+            ))
+        }
+    };
+    let exists: bool = sqlx::query_scalar(&format!(
+        "SELECT EXISTS(SELECT 1 FROM {} WHERE id = ?)",
+        table
+    ))
+    .bind(target_id)
+    .fetch_one(&state.db)
+    .await
+    .map_err(ApiError::DatabaseError)?;
+    if !exists {
+        return Err(ApiError::NotFound(match table {
+            "companies" => "Company not found".to_string(),
+            _ => "Job not found".to_string(),
+        }));
+    }
+
+    let id = generate_chat_webhook_id();
+    sqlx::query(
+        r#"
+        INSERT INTO chat_webhooks (
+            id, provider, name, webhook_url, company_id, job_id, notify_new_application,
+            notify_interview_feedback, notify_offer_accepted, is_active, created_by
+        )
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+        "#,
+    )
+    .bind(&id)
+    .bind(&provider)
+    .bind(&name)
+    .bind(&webhook_url)
+    .bind(request.company_id.as_deref())
+    .bind(request.job_id.as_deref())
+    .bind(request.notify_new_application.unwrap_or(true))
+    .bind(request.notify_interview_feedback.unwrap_or(true))
+    .bind(request.notify_offer_accepted.unwrap_or(true))
+    .bind(request.is_active.unwrap_or(true))
+    .bind(&authed.id)
+    .execute(&state.db)
+    .await
+    .map_err(|e| {
+        error!(error = %e, "Database error creating chat webhook");
+        ApiError::DatabaseError(e)
+    })?;
+
+    info!(
+        admin_user_id = %authed.id,
+        webhook_id = %id,
+        provider = %provider,
+        "Chat webhook created"
+    );
+
+    Ok((
+        StatusCode::CREATED,
+        Json(masked(fetch_webhook(&state, &id).await?)),
+    ))
+}
+
+/// PUT /api/admin/chat-webhooks/:id - Rename, repoint or change the events of a webhook
+pub async fn update_chat_webhook(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Path(id): Path<String>,
+    Json(request): Json<UpdateChatWebhookRequest>,
+) -> Result<Json<ChatWebhook>, ApiError> {
+    let state = state_lock.read().await.clone();
+    authed.require_admin("Chat webhook")?;
+
+    let mut webhook = fetch_webhook(&state, &id).await?;
+    if let Some(name) = request.name {
+        webhook.name = name.trim().to_string();
+        validate_name(&webhook.name)?;
+    }
+    if let Some(webhook_url) = request.webhook_url {
+        webhook.webhook_url = webhook_url.trim().to_string();
+        chat_webhooks::validate_webhook(&webhook.provider, &webhook.webhook_url)
+            .map_err(ApiError::ValidationError)?;
+    }
+    for (field, value) in [
+        (
+            &mut webhook.notify_new_application,
+            request.notify_new_application,
+        ),
+        (
+            &mut webhook.notify_interview_feedback,
+            request.notify_interview_feedback,
+        ),
+        (
+            &mut webhook.notify_offer_accepted,
+            request.notify_offer_accepted,
+        ),
+        (&mut webhook.is_active, request.is_active),
+    ] {
+        if let Some(value) = value {
+            *field = value;
+        }
+    }
+
+    sqlx::query(
+        r#"
+        UPDATE chat_webhooks
+        SET name = ?, webhook_url = ?, notify_new_application = ?,
+            notify_interview_feedback = ?, notify_offer_accepted = ?, is_active = ?,
+            updated_at = datetime('now')
+        WHERE id = ?
+        "#,
+    )
+    .bind(&webhook.name)
+    .bind(&webhook.webhook_url)
+    .bind(webhook.notify_new_application)
+    .bind(webhook.notify_interview_feedback)
+    .bind(webhook.notify_offer_accepted)
+    .bind(webhook.is_active)
+    .bind(&id)
+    .execute(&state.db)
+    .await
+    .map_err(|e| {
+        error!(error = %e, webhook_id = %id, "Database error updating chat webhook");
+        ApiError::DatabaseError(e)
+    })?;
+
+    info!(
+        admin_user_id = %authed.id,
+        webhook_id = %id,
+        is_active = webhook.is_active,
+        "Chat webhook updated"
+    );
+
+    Ok(Json(masked(fetch_webhook(&state, &id).await?)))
+}
+
+/// DELETE /api/admin/chat-webhooks/:id - Stop posting to a channel
+pub async fn delete_chat_webhook(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Path(id): Path<String>,
+) -> Result<Json<MessageResponse>, ApiError> {
+    let state = state_lock.read().await.clone();
+    authed.require_admin("Chat webhook")?;
+
+    let result = sqlx::query("DELETE FROM chat_webhooks WHERE id = ?")
+        .bind(&id)
+        .execute(&state.db)
+        .await
+        .map_err(ApiError::DatabaseError)?;
+
+    if result.rows_affected() == 0 {
+        return Err(ApiError::NotFound("Chat webhook not found".to_string()));
+    }
+
+    info!(admin_user_id = %authed.id, webhook_id = %id, "Chat webhook deleted");
+
+    Ok(Json(MessageResponse {
+        message: "Chat webhook deleted".to_string(),
+    }))
+}
+
+/// POST /api/admin/chat-webhooks/:id/test - Post a test message to the channel now
+pub async fn test_chat_webhook(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Path(id): Path<String>,
+) -> Result<Json<ChatWebhook>, ApiError> {
+    let state = state_lock.read().await.clone();
+    authed.require_admin("Chat webhook")?;
+
+    let webhook = fetch_webhook(&state, &id).await?;
+    let message = ChatMessage {
+        title: format!("Test message for {}", webhook.name),
+        facts: vec![("Sent by", authed.email.clone())],
+        link: None,
+    };
+    let result = chat_webhooks::post(&state.http, &webhook, &message).await;
+    chat_webhooks::record_result(&state.db, &id, &result)
+        .await
+        .map_err(ApiError::DatabaseError)?;
+    result.map_err(ApiError::ProcessingError)?;
+
+    info!(admin_user_id = %authed.id, webhook_id = %id, "Chat webhook test sent");
+
+    Ok(Json(masked(fetch_webhook(&state, &id).await?)))
+}
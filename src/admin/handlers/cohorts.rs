@@ -0,0 +1,75 @@
+// src/admin/handlers/cohorts.rs
+//! Source cohort analytics: how applicants from each channel progress over the weeks after
+//! they apply
+
+use axum::{
+    extract::{Extension, Query},
+    Json,
+};
+use chrono::Utc;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::{error, info};
+
+use crate::admin::models::CohortQuery;
+use crate::auth::AuthedUser;
+use crate::common::{ApiError, AppState};
+use crate::services::analytics_facts;
+use crate::services::cohorts::{self, CohortReport};
+
+const DEFAULT_MONTHS: u32 = 6;
+const DEFAULT_WEEKS: usize = 12;
+const MAX_WEEKS: usize = 52;
+
+/// GET /api/admin/analytics/cohorts?from=&to=&weeks=&job_id=&source= - Funnel progress by
+/// application month and source, week by week
+pub async fn get_source_cohorts(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Query(query): Query<CohortQuery>,
+) -> Result<Json<CohortReport>, ApiError> {
+    let state = state_lock.read().await.clone();
+    authed.require_admin("Cohort analytics")?;
+
+    let now = Utc::now().naive_utc();
+    let to = cohorts::parse_month(query.to.as_deref(), "to")?
+        .unwrap_or_else(|| now.format("%Y-%m").to_string());
+    let from = cohorts::parse_month(query.from.as_deref(), "from")?
+        .unwrap_or_else(|| cohorts::months_before(now, DEFAULT_MONTHS - 1));
+    if from > to {
+        return Err(ApiError::ValidationError(
+            "from must not be after to".to_string(),
+        ));
+    }
+    let weeks = query.weeks.unwrap_or(DEFAULT_WEEKS).clamp(1, MAX_WEEKS);
+
+    // Building the facts for the first time writes, so it goes to the primary pool
+    analytics_facts::ensure_built(&state.db).await.map_err(|e| {
+        error!(error = %e, "Database error building analytics facts");
+        ApiError::DatabaseError(e)
+    })?;
+
+    let report = cohorts::source_cohorts(
+        &state.db_read,
+        &from,
+        &to,
+        weeks,
+        query.job_id.as_deref(),
+        query.source.as_deref(),
+    )
+    .await
+    .map_err(|e| {
+        error!(error = %e, "Database error computing source cohorts");
+        ApiError::DatabaseError(e)
+    })?;
+
+    info!(
+        admin_user_id = %authed.id,
+        from = %from,
+        to = %to,
+        cohorts = report.cohorts.len(),
+        "Source cohort report generated"
+    );
+
+    Ok(Json(report))
+}
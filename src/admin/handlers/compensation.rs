@@ -0,0 +1,240 @@
+// src/admin/handlers/compensation.rs
+//! Compensation band management and offer salary validation
+
+use axum::{
+    extract::{Extension, Path, Query},
+    http::StatusCode,
+    Json,
+};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::{error, info};
+
+use crate::admin::models::{
+    CompensationBand, CompensationBandQuery, CompensationOverride, CreateCompensationBandRequest,
+    MessageResponse, OfferSalaryCheck, UpdateCompensationBandRequest, ValidateOfferSalaryRequest,
+};
+use crate::auth::AuthedUser;
+use crate::common::{generate_compensation_band_id, ApiError, AppState};
+use crate::services::compensation;
+
+async fn fetch_band(state: &AppState, id: &str) -> Result<CompensationBand, ApiError> {
+    sqlx::query_as::<_, CompensationBand>("SELECT * FROM compensation_bands WHERE id = ?")
+        .bind(id)
+        .fetch_optional(&state.db)
+        .await
+        .map_err(ApiError::DatabaseError)?
+        .ok_or_else(|| ApiError::NotFound("Compensation band not found".to_string()))
+}
+
+/// GET /api/admin/compensation-bands - List bands, optionally filtered by company or level
+pub async fn list_compensation_bands(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Query(query): Query<CompensationBandQuery>,
+) -> Result<Json<Vec<CompensationBand>>, ApiError> {
+    let state = state_lock.read().await.clone();
+    authed.require_admin("Compensation")?;
+
+    let bands = sqlx::query_as::<_, CompensationBand>(
+        r#"
+        SELECT * FROM compensation_bands
+        WHERE (? IS NULL OR company_id = ?) AND (? IS NULL OR level = ?)
+        ORDER BY company_id IS NOT NULL, company_id, min_salary
+        "#,
+    )
+    .bind(&query.company_id)
+    .bind(&query.company_id)
+    .bind(&query.level)
+    .bind(&query.level)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|e| {
+        error!(error = %e, "Database error listing compensation bands");
+        ApiError::DatabaseError(e)
+    })?;
+
+    Ok(Json(bands))
+}
+
+/// POST /api/admin/compensation-bands - Create a band for a level (and optionally a company)
+pub async fn create_compensation_band(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Json(request): Json<CreateCompensationBandRequest>,
+) -> Result<(StatusCode, Json<CompensationBand>), ApiError> {
+    let state = state_lock.read().await.clone();
+    authed.require_admin("Compensation")?;
+
+    let level = request.level.trim().to_lowercase();
+    compensation::validate_band(&level, request.min_salary, request.max_salary)?;
+
+    let existing: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM compensation_bands WHERE COALESCE(company_id, '') = COALESCE(?, '') AND level = ?",
+    )
+    .bind(&request.company_id)
+    .bind(&level)
+    .fetch_one(&state.db)
+    .await
+    .map_err(ApiError::DatabaseError)?;
+
+    if existing > 0 {
+        return Err(ApiError::BadRequest(format!(
+            "A {} band already exists for this company",
+            level
+        )));
+    }
+
+    let id = generate_compensation_band_id();
+    let currency = request
+        .currency
+        .as_deref()
+        .map(|c| c.trim().to_uppercase())
+        .filter(|c| !c.is_empty())
+        .unwrap_or_else(|| "USD".to_string());
+
+    sqlx::query(
+        r#"
+        INSERT INTO compensation_bands (id, company_id, level, currency, min_salary, max_salary, created_by)
+        VALUES (?, ?, ?, ?, ?, ?, ?)
+        "#,
+    )
+    .bind(&id)
+    .bind(&request.company_id)
+    .bind(&level)
+    .bind(&currency)
+    .bind(request.min_salary)
+    .bind(request.max_salary)
+    .bind(&authed.id)
+    .execute(&state.db)
+    .await
+    .map_err(|e| {
+        error!(error = %e, "Database error creating compensation band");
+        ApiError::DatabaseError(e)
+    })?;
+
+    info!(
+        admin_user_id = %authed.id,
+        band_id = %id,
+        company_id = ?request.company_id,
+        level = %level,
+        min_salary = request.min_salary,
+        max_salary = request.max_salary,
+        "Compensation band created"
+    );
+
+    Ok((StatusCode::CREATED, Json(fetch_band(&state, &id).await?)))
+}
+
+/// PUT /api/admin/compensation-bands/:id - Adjust a band's range or currency
+pub async fn update_compensation_band(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Path(id): Path<String>,
+    Json(request): Json<UpdateCompensationBandRequest>,
+) -> Result<Json<CompensationBand>, ApiError> {
+    let state = state_lock.read().await.clone();
+    authed.require_admin("Compensation")?;
+
+    let band = fetch_band(&state, &id).await?;
+    let min_salary = request.min_salary.unwrap_or(band.min_salary);
+    let max_salary = request.max_salary.unwrap_or(band.max_salary);
+    compensation::validate_band(&band.level, min_salary, max_salary)?;
+
+    let currency = request
+        .currency
+        .as_deref()
+        .map(|c| c.trim().to_uppercase())
+        .filter(|c| !c.is_empty())
+        .unwrap_or(band.currency);
+
+    sqlx::query(
+        "UPDATE compensation_bands SET currency = ?, min_salary = ?, max_salary = ?, updated_at = datetime('now') WHERE id = ?",
+    )
+    .bind(&currency)
+    .bind(min_salary)
+    .bind(max_salary)
+    .bind(&id)
+    .execute(&state.db)
+    .await
+    .map_err(|e| {
+        error!(error = %e, band_id = %id, "Database error updating compensation band");
+        ApiError::DatabaseError(e)
+    })?;
+
+    info!(
+        admin_user_id = %authed.id,
+        band_id = %id,
+        previous_min = band.min_salary,
+        previous_max = band.max_salary,
+        min_salary = min_salary,
+        max_salary = max_salary,
+        "Compensation band updated"
+    );
+
+    Ok(Json(fetch_band(&state, &id).await?))
+}
+
+/// DELETE /api/admin/compensation-bands/:id - Remove a band
+pub async fn delete_compensation_band(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Path(id): Path<String>,
+) -> Result<Json<MessageResponse>, ApiError> {
+    let state = state_lock.read().await.clone();
+    authed.require_admin("Compensation")?;
+
+    let result = sqlx::query("DELETE FROM compensation_bands WHERE id = ?")
+        .bind(&id)
+        .execute(&state.db)
+        .await
+        .map_err(ApiError::DatabaseError)?;
+
+    if result.rows_affected() == 0 {
+        return Err(ApiError::NotFound(
+            "Compensation band not found".to_string(),
+        ));
+    }
+
+    info!(admin_user_id = %authed.id, band_id = %id, "Compensation band deleted");
+
+    Ok(Json(MessageResponse {
+        message: "Compensation band deleted".to_string(),
+    }))
+}
+
+/// POST /api/admin/compensation-bands/validate-offer - Check an offer salary against its band
+///
+/// Above-band salaries need `override_justification`; accepted overrides are audit-logged.
+pub async fn validate_offer_salary(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Json(request): Json<ValidateOfferSalaryRequest>,
+) -> Result<Json<OfferSalaryCheck>, ApiError> {
+    let state = state_lock.read().await.clone();
+    authed.require_admin("Compensation")?;
+
+    let check = compensation::check_offer_salary(&state.db, &request, &authed.id).await?;
+    Ok(Json(check))
+}
+
+/// GET /api/admin/compensation-bands/overrides - Audit log of above-band offers
+pub async fn list_compensation_overrides(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+) -> Result<Json<Vec<CompensationOverride>>, ApiError> {
+    let state = state_lock.read().await.clone();
+    authed.require_admin("Compensation")?;
+
+    let overrides = sqlx::query_as::<_, CompensationOverride>(
+        "SELECT * FROM compensation_overrides ORDER BY created_at DESC",
+    )
+    .fetch_all(&state.db)
+    .await
+    .map_err(|e| {
+        error!(error = %e, "Database error listing compensation overrides");
+        ApiError::DatabaseError(e)
+    })?;
+
+    Ok(Json(overrides))
+}
@@ -0,0 +1,20 @@
+// src/admin/handlers/config.rs
+//! The configuration the server started with, for debugging deployments
+
+use axum::{extract::Extension, Json};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+use crate::auth::AuthedUser;
+use crate::common::config::RedactedConfig;
+use crate::common::{ApiError, AppState};
+
+/// GET /api/admin/config - Startup configuration with secrets redacted, plus any warnings
+pub async fn get_config(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+) -> Result<Json<RedactedConfig>, ApiError> {
+    authed.require_admin("Config")?;
+    let config = state_lock.read().await.config.clone();
+    Ok(Json(config.redacted()))
+}
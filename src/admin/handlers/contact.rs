@@ -63,7 +63,7 @@ pub async fn submit_contact_form(
     // Try to send via AWS SES if configured
     if let Some(to_email) = admin_email {
         match state
-            .aws_service
+            .email
             .send_email(vec![to_email.clone()], &subject_line, &email_body, None)
             .await
         {
@@ -8,6 +8,7 @@ use tracing::{error, info, warn};
 use crate::admin::models::{ActivityLog, DashboardMetrics, SystemHealth};
 use crate::auth::AuthedUser;
 use crate::common::{ApiError, AppState};
+use crate::services::resilience::{self, BreakerState};
 
 /// GET /api/admin/dashboard/metrics - Get comprehensive dashboard metrics
 pub async fn get_dashboard_metrics(
@@ -30,8 +31,8 @@ pub async fn get_dashboard_metrics(
     );
 
     // Get total jobs count
-    let total_jobs = sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM jobs")
-        .fetch_one(&state.db)
+    let total_jobs = sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM jobs WHERE deleted_at IS NULL")
+        .fetch_one(&state.db_read)
         .await
         .map_err(|e| {
             error!(
@@ -43,9 +44,9 @@ pub async fn get_dashboard_metrics(
 
     // Get active jobs count
     let active_jobs = sqlx::query_scalar::<_, i64>(
-        "SELECT COUNT(*) FROM jobs WHERE status = 'active'"
+        "SELECT COUNT(*) FROM jobs WHERE status = 'active' AND deleted_at IS NULL"
     )
-        .fetch_one(&state.db)
+        .fetch_one(&state.db_read)
         .await
         .map_err(|e| {
             error!(
@@ -57,9 +58,9 @@ pub async fn get_dashboard_metrics(
 
     // Get draft jobs count
     let draft_jobs = sqlx::query_scalar::<_, i64>(
-        "SELECT COUNT(*) FROM jobs WHERE status = 'draft'"
+        "SELECT COUNT(*) FROM jobs WHERE status = 'draft' AND deleted_at IS NULL"
     )
-        .fetch_one(&state.db)
+        .fetch_one(&state.db_read)
         .await
         .map_err(|e| {
             error!(
@@ -71,9 +72,9 @@ pub async fn get_dashboard_metrics(
 
     // Get closed jobs count
     let closed_jobs = sqlx::query_scalar::<_, i64>(
-        "SELECT COUNT(*) FROM jobs WHERE status = 'closed'"
+        "SELECT COUNT(*) FROM jobs WHERE status = 'closed' AND deleted_at IS NULL"
     )
-        .fetch_one(&state.db)
+        .fetch_one(&state.db_read)
         .await
         .map_err(|e| {
             error!(
@@ -85,7 +86,7 @@ pub async fn get_dashboard_metrics(
 
     // Get total applications count
     let total_applications = sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM applications")
-        .fetch_one(&state.db)
+        .fetch_one(&state.db_read)
         .await
         .map_err(|e| {
             error!(
@@ -99,7 +100,7 @@ pub async fn get_dashboard_metrics(
     let pending_reviews = sqlx::query_scalar::<_, i64>(
         "SELECT COUNT(*) FROM applications WHERE status = 'submitted'",
     )
-    .fetch_one(&state.db)
+    .fetch_one(&state.db_read)
     .await
     .map_err(|e| {
         error!(
@@ -113,7 +114,7 @@ pub async fn get_dashboard_metrics(
     let new_messages = sqlx::query_scalar::<_, i64>(
         "SELECT COUNT(*) FROM conversation_messages WHERE created_at >= datetime('now', '-1 day')",
     )
-    .fetch_one(&state.db)
+    .fetch_one(&state.db_read)
     .await
     .map_err(|e| {
         error!(
@@ -133,7 +134,7 @@ pub async fn get_dashboard_metrics(
         WHERE p.user_id IS NOT NULL OR a.user_id IS NOT NULL
         "#,
     )
-    .fetch_one(&state.db)
+    .fetch_one(&state.db_read)
     .await
     .map_err(|e| {
         error!(
@@ -145,9 +146,9 @@ pub async fn get_dashboard_metrics(
 
     // Get jobs by status breakdown
     let jobs_by_status_rows = sqlx::query_as::<_, (String, i64)>(
-        "SELECT status, COUNT(*) as count FROM jobs GROUP BY status"
+        "SELECT status, COUNT(*) as count FROM jobs WHERE deleted_at IS NULL GROUP BY status"
     )
-        .fetch_all(&state.db)
+        .fetch_all(&state.db_read)
         .await
         .map_err(|e| {
             error!(error = %e, "Database error fetching jobs by status");
@@ -163,7 +164,7 @@ pub async fn get_dashboard_metrics(
     let apps_by_status_rows = sqlx::query_as::<_, (String, i64)>(
         "SELECT status, COUNT(*) as count FROM applications GROUP BY status"
     )
-        .fetch_all(&state.db)
+        .fetch_all(&state.db_read)
         .await
         .map_err(|e| {
             error!(error = %e, "Database error fetching applications by status");
@@ -181,12 +182,13 @@ pub async fn get_dashboard_metrics(
         SELECT j.id, j.title, j.company, COUNT(a.id) as app_count, j.status
         FROM jobs j
         LEFT JOIN applications a ON j.id = a.job_id
+        WHERE j.deleted_at IS NULL
         GROUP BY j.id, j.title, j.company, j.status
         ORDER BY app_count DESC
         LIMIT 5
         "#
     )
-        .fetch_all(&state.db)
+        .fetch_all(&state.db_read)
         .await
         .map_err(|e| {
             error!(error = %e, "Database error fetching top jobs");
@@ -216,14 +218,14 @@ pub async fn get_dashboard_metrics(
         FROM (
             SELECT DATE(applied_at) as date, 'application' as type FROM applications WHERE applied_at >= datetime('now', '-7 days')
             UNION ALL
-            SELECT DATE(created_at) as date, 'job' as type FROM jobs WHERE created_at >= datetime('now', '-7 days')
+            SELECT DATE(created_at) as date, 'job' as type FROM jobs WHERE created_at >= datetime('now', '-7 days') AND deleted_at IS NULL
         )
         GROUP BY DATE(date)
         ORDER BY trend_date DESC
         LIMIT 7
         "#
     )
-        .fetch_all(&state.db)
+        .fetch_all(&state.db_read)
         .await
         .map_err(|e| {
             error!(error = %e, "Database error fetching application trends");
@@ -370,9 +372,20 @@ pub async fn get_system_health(
     let api_status = "healthy";
     details.insert("api".to_string(), "API responding normally".to_string());
 
+    // An external service whose breaker isn't closed is failing fast or on probation
+    let dependencies = resilience::health();
+    let dependencies_status = if dependencies
+        .iter()
+        .all(|d| d.state == BreakerState::Closed)
+    {
+        "healthy"
+    } else {
+        "warning"
+    };
+
     // Determine overall health
     let overall_health = match (database_status, storage_status, api_status) {
-        ("healthy", "healthy", "healthy") => "healthy",
+        ("healthy", "healthy", "healthy") => dependencies_status,
         ("error", _, _) | (_, "error", _) | (_, _, "error") => "error",
         _ => "warning",
     };
@@ -384,6 +397,7 @@ pub async fn get_system_health(
         overall_health: overall_health.to_string(),
         last_check: chrono::Utc::now().to_rfc3339(),
         details,
+        dependencies,
     };
 
     info!(
@@ -445,7 +459,7 @@ async fn fetch_recent_activity_internal(state: &AppState, limit: i64) -> Result<
         "#,
     )
     .bind(limit / 3)
-    .fetch_all(&state.db)
+    .fetch_all(&state.db_read)
     .await
     .map_err(|e| {
         error!(error = %e, "Database error fetching recent resume activities");
@@ -464,7 +478,7 @@ async fn fetch_recent_activity_internal(state: &AppState, limit: i64) -> Result<
         "#,
         )
         .bind(limit / 3)
-        .fetch_all(&state.db)
+        .fetch_all(&state.db_read)
         .await
         .map_err(|e| {
             error!(error = %e, "Database error fetching recent application activities");
@@ -482,7 +496,7 @@ async fn fetch_recent_activity_internal(state: &AppState, limit: i64) -> Result<
         "#,
     )
     .bind(limit / 3)
-    .fetch_all(&state.db)
+    .fetch_all(&state.db_read)
     .await
     .map_err(|e| {
         error!(error = %e, "Database error fetching recent message activities");
@@ -0,0 +1,33 @@
+// src/admin/handlers/dev.rs
+//! Dev-mode tooling
+
+use axum::{extract::Extension, Json};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::{error, warn};
+
+use crate::auth::AuthedUser;
+use crate::common::{ApiError, AppState};
+use crate::services::seed::{self, SeedSummary};
+
+/// POST /api/admin/dev/seed - Fill the database with demo data (dev mode only)
+pub async fn seed_demo_data(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+) -> Result<Json<SeedSummary>, ApiError> {
+    let state = state_lock.read().await.clone();
+    // Outside dev mode the endpoint doesn't exist
+    if !state.dev_mode.is_enabled() {
+        return Err(ApiError::NotFound("Not found".to_string()));
+    }
+    if !authed.is_admin {
+        warn!(user_id = %authed.id, "Demo seeding denied: admin privileges required");
+        return Err(ApiError::Forbidden("Admin privileges required".to_string()));
+    }
+
+    let summary = seed::seed_demo_data(&state.db).await.map_err(|e| {
+        error!(error = %e, "Failed to seed demo data");
+        ApiError::DatabaseError(e)
+    })?;
+    Ok(Json(summary))
+}
@@ -0,0 +1,10 @@
+// src/admin/handlers/docs.rs
+
+use axum::Json;
+
+use crate::common::error_codes::openapi_error_spec;
+
+/// GET /api/openapi/errors.json - Error body schema and error code catalog (public)
+pub async fn get_error_catalog() -> Json<serde_json::Value> {
+    Json(openapi_error_spec())
+}
@@ -7,6 +7,9 @@ use tracing::{error, info, warn};
 
 use crate::auth::AuthedUser;
 use crate::common::{ApiError, AppState};
+use crate::services::masking;
+use crate::services::monitoring::{self, SecurityActivity};
+use crate::services::pii::Sealed;
 
 /// GET /api/admin/export/jobs - Export jobs data in CSV or JSON format
 pub async fn export_jobs(
@@ -31,8 +34,8 @@ pub async fn export_jobs(
 
     let format = params.get("format").map(|s| s.as_str()).unwrap_or("csv");
 
-    let jobs = sqlx::query_as::<_, crate::jobs::Job>("SELECT * FROM jobs")
-        .fetch_all(&state.db)
+    let jobs = sqlx::query_as::<_, crate::jobs::Job>("SELECT * FROM jobs WHERE deleted_at IS NULL")
+        .fetch_all(&state.db_read)
         .await
         .map_err(|e| {
             error!(
@@ -42,6 +45,16 @@ pub async fn export_jobs(
             ApiError::DatabaseError(e)
         })?;
 
+    monitoring::record_security_activity(
+        &state,
+        SecurityActivity::Export,
+        Some(&authed.id),
+        None,
+        jobs.len() as i64,
+        "/api/admin/export/jobs",
+    )
+    .await;
+
     match format {
         "csv" => {
             let mut csv_content = String::from("ID,Title,Description,Location\n");
@@ -173,7 +186,7 @@ pub async fn export_applications(
             Option<String>,
         ),
     >(applications_query)
-    .fetch_all(&state.db)
+    .fetch_all(&state.db_read)
     .await
     .map_err(|e| {
         error!(
@@ -183,6 +196,16 @@ pub async fn export_applications(
         ApiError::DatabaseError(e)
     })?;
 
+    monitoring::record_security_activity(
+        &state,
+        SecurityActivity::Export,
+        Some(&authed.id),
+        None,
+        applications.len() as i64,
+        "/api/admin/export/applications",
+    )
+    .await;
+
     match format {
         "csv" => {
             let mut csv_content = String::from("Application ID,User ID,Job ID,Job Title,Candidate Email,Candidate Name,Resume ID,Status,Applied At,Updated At\n");
@@ -207,7 +230,7 @@ pub async fn export_applications(
                     user_id,
                     job_id,
                     job_title.unwrap_or_default(),
-                    candidate_email.unwrap_or_default(),
+                    candidate_email.as_deref().map(masking::visible_email).unwrap_or_default(),
                     candidate_name.unwrap_or_default(),
                     resume_id.unwrap_or_default(),
                     status,
@@ -257,7 +280,7 @@ pub async fn export_applications(
                             "user_id": user_id,
                             "job_id": job_id,
                             "job_title": job_title,
-                            "candidate_email": candidate_email,
+                            "candidate_email": candidate_email.as_deref().map(masking::visible_email),
                             "candidate_name": candidate_name,
                             "resume_id": resume_id,
                             "status": status,
@@ -363,8 +386,8 @@ pub async fn export_candidates(
             Option<String>,
             Option<String>,
             Option<String>,
-            Option<String>,
-            Option<String>,
+            Sealed,
+            Sealed,
             Option<String>,
             Option<String>,
             Option<String>,
@@ -374,7 +397,7 @@ pub async fn export_candidates(
             i64,
         ),
     >(candidates_query)
-    .fetch_all(&state.db)
+    .fetch_all(&state.db_read)
     .await
     .map_err(|e| {
         error!(
@@ -384,6 +407,16 @@ pub async fn export_candidates(
         ApiError::DatabaseError(e)
     })?;
 
+    monitoring::record_security_activity(
+        &state,
+        SecurityActivity::Export,
+        Some(&authed.id),
+        None,
+        candidates.len() as i64,
+        "/api/admin/export/candidates",
+    )
+    .await;
+
     match format {
         "csv" => {
             let mut csv_content = String::from("User ID,Email,Name,First Name,Last Name,Phone,Location,Website,LinkedIn,GitHub,Resume Status,Application Count,Created At\n");
@@ -396,8 +429,8 @@ pub async fn export_candidates(
                     created_at,
                     first_name,
                     last_name,
-                    phone,
-                    location,
+                    Sealed(phone),
+                    Sealed(location),
                     _bio,
                     website,
                     linkedin_url,
@@ -409,11 +442,11 @@ pub async fn export_candidates(
                 csv_content.push_str(&format!(
                     "\"{}\",\"{}\",\"{}\",\"{}\",\"{}\",\"{}\",\"{}\",\"{}\",\"{}\",\"{}\",\"{}\",\"{}\",\"{}\"\n",
                     id,
-                    email,
+                    masking::visible_email(&email),
                     name.unwrap_or_default(),
                     first_name.unwrap_or_default(),
                     last_name.unwrap_or_default(),
-                    phone.unwrap_or_default(),
+                    phone.as_deref().map(masking::visible_phone).unwrap_or_default(),
                     location.unwrap_or_default(),
                     website.unwrap_or_default(),
                     linkedin_url.unwrap_or_default(),
@@ -454,8 +487,8 @@ pub async fn export_candidates(
                         created_at,
                         first_name,
                         last_name,
-                        phone,
-                        location,
+                        Sealed(phone),
+                        Sealed(location),
                         bio,
                         website,
                         linkedin_url,
@@ -466,11 +499,11 @@ pub async fn export_candidates(
                     )| {
                         serde_json::json!({
                             "id": id,
-                            "email": email,
+                            "email": masking::visible_email(&email),
                             "name": name,
                             "first_name": first_name,
                             "last_name": last_name,
-                            "phone": phone,
+                            "phone": phone.as_deref().map(masking::visible_phone),
                             "location": location,
                             "bio": bio,
                             "website": website,
@@ -15,6 +15,7 @@ use crate::admin::models::{
 };
 use crate::auth::AuthedUser;
 use crate::common::{ApiError, AppState};
+use crate::services::legal_hold;
 
 /// GET /api/admin/files - List files in storage
 pub async fn list_files_handler(
@@ -163,6 +164,29 @@ pub async fn list_files_handler(
     Ok(Json(ListFilesResponse { files, total }))
 }
 
+/// Stored resumes stay in place while their owner or application is under a legal hold
+async fn ensure_file_deletable(
+    state: &AppState,
+    file_path: &str,
+    admin_user_id: &str,
+) -> Result<(), ApiError> {
+    let Some(filename) = file_path.strip_prefix("resumes/") else {
+        return Ok(());
+    };
+    let resume_id: Option<String> =
+        sqlx::query_scalar("SELECT id FROM resumes WHERE filename = ?")
+            .bind(filename)
+            .fetch_optional(&state.db)
+            .await
+            .map_err(ApiError::DatabaseError)?;
+    match resume_id {
+        Some(resume_id) => {
+            legal_hold::ensure_resume_deletable(&state.db, &resume_id, Some(admin_user_id)).await
+        }
+        None => Ok(()),
+    }
+}
+
 /// DELETE /api/admin/files/:path - Delete a file
 pub async fn delete_file_handler(
     Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
@@ -174,6 +198,7 @@ pub async fn delete_file_handler(
     }
 
     let state = state_lock.read().await.clone();
+    ensure_file_deletable(&state, &file_path, &authed.id).await?;
 
     let storage_type = state
         .settings_service
@@ -184,7 +209,7 @@ pub async fn delete_file_handler(
         .unwrap_or_else(|| "local".to_string());
 
     if storage_type == "s3" || storage_type == "s3-cloudfront" {
-        match state.aws_service.delete_file(&file_path).await {
+        match state.object_store.delete_file(&file_path).await {
             Ok(_) => {
                 info!(
                     admin_user_id = %authed.id,
@@ -372,8 +397,13 @@ pub async fn delete_files_bulk_handler(
     let mut errors: Vec<String> = Vec::new();
 
     for file_path in &request.paths {
+        if let Err(e) = ensure_file_deletable(&state, file_path, &authed.id).await {
+            errors.push(format!("Failed to delete {}: {}", file_path, e));
+            continue;
+        }
+
         if storage_type == "s3" || storage_type == "s3-cloudfront" {
-            match state.aws_service.delete_file(file_path).await {
+            match state.object_store.delete_file(file_path).await {
                 Ok(_) => {
                     deleted_count += 1;
                     info!(
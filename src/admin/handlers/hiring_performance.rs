@@ -0,0 +1,67 @@
+// src/admin/handlers/hiring_performance.rs
+//! Time-to-hire and recruiter performance report
+
+use axum::{
+    extract::{Extension, Query},
+    Json,
+};
+use chrono::{Duration, Utc};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::{error, info};
+
+use crate::admin::models::HiringPerformanceQuery;
+use crate::auth::AuthedUser;
+use crate::common::{parse_date, ApiError, AppState};
+use crate::services::analytics_facts;
+use crate::services::hiring_metrics::{self, HiringPerformanceReport};
+
+const DEFAULT_RANGE_DAYS: i64 = 90;
+
+/// GET /api/admin/analytics/hiring-performance?from=&to=&job_id= - Time to review and hire,
+/// time in each stage, offer acceptance and interviews per hire, by job and by recruiter
+pub async fn get_hiring_performance(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Query(query): Query<HiringPerformanceQuery>,
+) -> Result<Json<HiringPerformanceReport>, ApiError> {
+    let state = state_lock.read().await.clone();
+    authed.require_admin("Hiring performance")?;
+
+    let to = parse_date(query.to.as_deref(), "to")?.unwrap_or_else(|| Utc::now().date_naive());
+    let from = parse_date(query.from.as_deref(), "from")?
+        .unwrap_or(to - Duration::days(DEFAULT_RANGE_DAYS));
+    if from > to {
+        return Err(ApiError::ValidationError(
+            "from must not be after to".to_string(),
+        ));
+    }
+
+    // Building the facts for the first time writes, so it goes to the primary pool
+    analytics_facts::ensure_built(&state.db).await.map_err(|e| {
+        error!(error = %e, "Database error building analytics facts");
+        ApiError::DatabaseError(e)
+    })?;
+
+    let report = hiring_metrics::hiring_performance(
+        &state.db_read,
+        &from.format("%Y-%m-%d").to_string(),
+        &to.format("%Y-%m-%d").to_string(),
+        query.job_id.as_deref(),
+    )
+    .await
+    .map_err(|e| {
+        error!(error = %e, "Database error computing hiring performance");
+        ApiError::DatabaseError(e)
+    })?;
+
+    info!(
+        admin_user_id = %authed.id,
+        from = %report.from,
+        to = %report.to,
+        applications = report.overall.applications,
+        "Hiring performance report generated"
+    );
+
+    Ok(Json(report))
+}
@@ -0,0 +1,261 @@
+// src/admin/handlers/knockout_rules.rs
+//! Per-job knockout screening rules and the log of rules that fired
+
+use axum::{
+    extract::{Extension, Path},
+    http::StatusCode,
+    Json,
+};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::{error, info};
+
+use crate::admin::models::{
+    ApplicationKnockout, CreateKnockoutRuleRequest, KnockoutRule, MessageResponse,
+    UpdateKnockoutRuleRequest,
+};
+use crate::auth::AuthedUser;
+use crate::common::{generate_knockout_rule_id, ApiError, AppState};
+use crate::services::knockout;
+
+async fn fetch_rule(state: &AppState, id: &str) -> Result<KnockoutRule, ApiError> {
+    sqlx::query_as::<_, KnockoutRule>("SELECT * FROM knockout_rules WHERE id = ?")
+        .bind(id)
+        .fetch_optional(&state.db)
+        .await
+        .map_err(ApiError::DatabaseError)?
+        .ok_or_else(|| ApiError::NotFound("Knockout rule not found".to_string()))
+}
+
+fn trimmed(value: Option<String>) -> Option<String> {
+    value
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+}
+
+/// GET /api/admin/jobs/:id/knockout-rules - A job's rules, active or not
+pub async fn list_knockout_rules(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Path(job_id): Path<String>,
+) -> Result<Json<Vec<KnockoutRule>>, ApiError> {
+    let state = state_lock.read().await.clone();
+    authed.require_admin("Knockout rule")?;
+
+    let rules = knockout::job_rules(&state.db, &job_id, false)
+        .await
+        .map_err(|e| {
+            error!(error = %e, job_id = %job_id, "Database error listing knockout rules");
+            ApiError::DatabaseError(e)
+        })?;
+
+    Ok(Json(rules))
+}
+
+/// POST /api/admin/jobs/:id/knockout-rules - Add a rule checked on every new application
+pub async fn create_knockout_rule(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Path(job_id): Path<String>,
+    Json(request): Json<CreateKnockoutRuleRequest>,
+) -> Result<(StatusCode, Json<KnockoutRule>), ApiError> {
+    let state = state_lock.read().await.clone();
+    authed.require_admin("Knockout rule")?;
+
+    let job_exists: bool = sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM jobs WHERE id = ?)")
+        .bind(&job_id)
+        .fetch_one(&state.db)
+        .await
+        .map_err(ApiError::DatabaseError)?;
+    if !job_exists {
+        return Err(ApiError::NotFound("Job not found".to_string()));
+    }
+
+    let rule = KnockoutRule {
+        id: generate_knockout_rule_id(),
+        job_id,
+        name: request.name.trim().to_string(),
+        rule_type: request.rule_type.trim().to_lowercase(),
+        country: trimmed(request.country),
+        min_years: request.min_years,
+        question: trimmed(request.question),
+        expected_answer: trimmed(request.expected_answer),
+        action: request.action.trim().to_lowercase(),
+        tag: trimmed(request.tag),
+        email_subject: trimmed(request.email_subject),
+        email_body: trimmed(request.email_body),
+        is_active: request.is_active.unwrap_or(true),
+        created_by: authed.id.clone(),
+        created_at: None,
+        updated_at: None,
+    };
+    knockout::validate_rule(&rule)?;
+
+    sqlx::query(
+        r#"
+        INSERT INTO knockout_rules (
+            id, job_id, name, rule_type, country, min_years, question, expected_answer,
+            action, tag, email_subject, email_body, is_active, created_by
+        )
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+        "#,
+    )
+    .bind(&rule.id)
+    .bind(&rule.job_id)
+    .bind(&rule.name)
+    .bind(&rule.rule_type)
+    .bind(&rule.country)
+    .bind(rule.min_years)
+    .bind(&rule.question)
+    .bind(&rule.expected_answer)
+    .bind(&rule.action)
+    .bind(&rule.tag)
+    .bind(&rule.email_subject)
+    .bind(&rule.email_body)
+    .bind(rule.is_active)
+    .bind(&rule.created_by)
+    .execute(&state.db)
+    .await
+    .map_err(|e| {
+        error!(error = %e, "Database error creating knockout rule");
+        ApiError::DatabaseError(e)
+    })?;
+
+    info!(
+        admin_user_id = %authed.id,
+        rule_id = %rule.id,
+        job_id = %rule.job_id,
+        rule_type = %rule.rule_type,
+        action = %rule.action,
+        "Knockout rule created"
+    );
+
+    Ok((
+        StatusCode::CREATED,
+        Json(fetch_rule(&state, &rule.id).await?),
+    ))
+}
+
+/// PUT /api/admin/knockout-rules/:id - Change a rule; empty strings clear optional fields
+pub async fn update_knockout_rule(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Path(id): Path<String>,
+    Json(request): Json<UpdateKnockoutRuleRequest>,
+) -> Result<Json<KnockoutRule>, ApiError> {
+    let state = state_lock.read().await.clone();
+    authed.require_admin("Knockout rule")?;
+
+    let mut rule = fetch_rule(&state, &id).await?;
+    if let Some(name) = request.name {
+        rule.name = name.trim().to_string();
+    }
+    if let Some(action) = request.action {
+        rule.action = action.trim().to_lowercase();
+    }
+    if request.min_years.is_some() {
+        rule.min_years = request.min_years;
+    }
+    for (field, value) in [
+        (&mut rule.country, request.country),
+        (&mut rule.question, request.question),
+        (&mut rule.expected_answer, request.expected_answer),
+        (&mut rule.tag, request.tag),
+        (&mut rule.email_subject, request.email_subject),
+        (&mut rule.email_body, request.email_body),
+    ] {
+        if value.is_some() {
+            *field = trimmed(value);
+        }
+    }
+    if let Some(is_active) = request.is_active {
+        rule.is_active = is_active;
+    }
+    knockout::validate_rule(&rule)?;
+
+    sqlx::query(
+        r#"
+        UPDATE knockout_rules
+        SET name = ?, country = ?, min_years = ?, question = ?, expected_answer = ?,
+            action = ?, tag = ?, email_subject = ?, email_body = ?, is_active = ?,
+            updated_at = datetime('now')
+        WHERE id = ?
+        "#,
+    )
+    .bind(&rule.name)
+    .bind(&rule.country)
+    .bind(rule.min_years)
+    .bind(&rule.question)
+    .bind(&rule.expected_answer)
+    .bind(&rule.action)
+    .bind(&rule.tag)
+    .bind(&rule.email_subject)
+    .bind(&rule.email_body)
+    .bind(rule.is_active)
+    .bind(&id)
+    .execute(&state.db)
+    .await
+    .map_err(|e| {
+        error!(error = %e, rule_id = %id, "Database error updating knockout rule");
+        ApiError::DatabaseError(e)
+    })?;
+
+    info!(
+        admin_user_id = %authed.id,
+        rule_id = %id,
+        action = %rule.action,
+        is_active = rule.is_active,
+        "Knockout rule updated"
+    );
+
+    Ok(Json(fetch_rule(&state, &id).await?))
+}
+
+/// DELETE /api/admin/knockout-rules/:id - Remove a rule; hits already logged are kept
+pub async fn delete_knockout_rule(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Path(id): Path<String>,
+) -> Result<Json<MessageResponse>, ApiError> {
+    let state = state_lock.read().await.clone();
+    authed.require_admin("Knockout rule")?;
+
+    let result = sqlx::query("DELETE FROM knockout_rules WHERE id = ?")
+        .bind(&id)
+        .execute(&state.db)
+        .await
+        .map_err(ApiError::DatabaseError)?;
+
+    if result.rows_affected() == 0 {
+        return Err(ApiError::NotFound("Knockout rule not found".to_string()));
+    }
+
+    info!(admin_user_id = %authed.id, rule_id = %id, "Knockout rule deleted");
+
+    Ok(Json(MessageResponse {
+        message: "Knockout rule deleted".to_string(),
+    }))
+}
+
+/// GET /api/admin/applications/:id/knockouts - Rules that fired for an application
+pub async fn list_application_knockouts(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Path(application_id): Path<String>,
+) -> Result<Json<Vec<ApplicationKnockout>>, ApiError> {
+    let state = state_lock.read().await.clone();
+    authed.require_admin("Knockout rule")?;
+
+    let knockouts = knockout::application_knockouts(&state.db, &application_id)
+        .await
+        .map_err(|e| {
+            error!(
+                error = %e,
+                application_id = %application_id,
+                "Database error listing application knockouts"
+            );
+            ApiError::DatabaseError(e)
+        })?;
+
+    Ok(Json(knockouts))
+}
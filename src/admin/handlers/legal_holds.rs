@@ -0,0 +1,251 @@
+// src/admin/handlers/legal_holds.rs
+//! Placing, releasing and auditing legal holds
+
+use axum::{
+    extract::{Extension, Path, Query},
+    http::StatusCode,
+    Json,
+};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::{error, info, warn};
+
+use crate::admin::models::{
+    CreateLegalHoldRequest, LegalHold, LegalHoldEvent, LegalHoldQuery, ReleaseLegalHoldRequest,
+};
+use crate::auth::AuthedUser;
+use crate::common::{generate_legal_hold_id, ApiError, AppState};
+use crate::services::legal_hold::{self, SUBJECT_TYPES};
+use crate::services::permissions;
+
+const MAX_REASON_LENGTH: usize = 2000;
+
+/// Legal holds need the `legal_hold` permission on top of admin access
+async fn require_legal_hold_permission(
+    state: &AppState,
+    authed: &AuthedUser,
+) -> Result<(), ApiError> {
+    if !authed.is_admin {
+        warn!(
+            user_id = %authed.id,
+            "Legal hold access denied: admin privileges required"
+        );
+        return Err(ApiError::Forbidden("Admin privileges required".to_string()));
+    }
+    let permitted =
+        permissions::has_admin_permission(&state.db, &authed.id, permissions::LEGAL_HOLD)
+            .await
+            .map_err(ApiError::DatabaseError)?;
+    if !permitted {
+        warn!(
+            user_id = %authed.id,
+            "Legal hold access denied: missing legal_hold permission"
+        );
+        return Err(ApiError::Forbidden(
+            "The legal_hold permission is required".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+fn validate_reason(reason: &str) -> Result<String, ApiError> {
+    let reason = reason.trim();
+    if reason.is_empty() {
+        return Err(ApiError::ValidationError("reason is required".to_string()));
+    }
+    if reason.chars().count() > MAX_REASON_LENGTH {
+        return Err(ApiError::ValidationError(format!(
+            "reason must be at most {} characters",
+            MAX_REASON_LENGTH
+        )));
+    }
+    Ok(reason.to_string())
+}
+
+async fn fetch_hold(state: &AppState, id: &str) -> Result<LegalHold, ApiError> {
+    sqlx::query_as::<_, LegalHold>("SELECT * FROM legal_holds WHERE id = ?")
+        .bind(id)
+        .fetch_optional(&state.db)
+        .await
+        .map_err(ApiError::DatabaseError)?
+        .ok_or_else(|| ApiError::NotFound("Legal hold not found".to_string()))
+}
+
+/// GET /api/admin/legal-holds - Holds, newest first
+pub async fn list_legal_holds(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Query(query): Query<LegalHoldQuery>,
+) -> Result<Json<Vec<LegalHold>>, ApiError> {
+    let state = state_lock.read().await.clone();
+    require_legal_hold_permission(&state, &authed).await?;
+
+    let holds = sqlx::query_as::<_, LegalHold>(
+        r#"
+        SELECT * FROM legal_holds
+        WHERE (? IS NULL OR (released_at IS NULL) = ?)
+          AND (? IS NULL OR user_id = ?)
+        ORDER BY placed_at DESC
+        "#,
+    )
+    .bind(query.active)
+    .bind(query.active)
+    .bind(&query.user_id)
+    .bind(&query.user_id)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|e| {
+        error!(error = %e, "Database error listing legal holds");
+        ApiError::DatabaseError(e)
+    })?;
+
+    Ok(Json(holds))
+}
+
+/// POST /api/admin/legal-holds - Place a hold on a user or an application
+pub async fn create_legal_hold(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Json(request): Json<CreateLegalHoldRequest>,
+) -> Result<(StatusCode, Json<LegalHold>), ApiError> {
+    let state = state_lock.read().await.clone();
+    require_legal_hold_permission(&state, &authed).await?;
+
+    let subject_type = request.subject_type.trim().to_lowercase();
+    if !SUBJECT_TYPES.contains(&subject_type.as_str()) {
+        return Err(ApiError::ValidationError(
+            "subject_type must be user or application".to_string(),
+        ));
+    }
+    let reason = validate_reason(&request.reason)?;
+    let matter_reference = request
+        .matter_reference
+        .as_deref()
+        .map(str::trim)
+        .filter(|r| !r.is_empty());
+
+    let user_id: Option<String> = match subject_type.as_str() {
+        "user" => sqlx::query_scalar("SELECT id FROM users WHERE id = ?"),
+        _ => sqlx::query_scalar("SELECT user_id FROM applications WHERE id = ?"),
+    }
+    .bind(&request.subject_id)
+    .fetch_optional(&state.db)
+    .await
+    .map_err(ApiError::DatabaseError)?;
+    let user_id = user_id.ok_or_else(|| {
+        ApiError::NotFound(format!("{} {} not found", subject_type, request.subject_id))
+    })?;
+
+    let existing: bool = sqlx::query_scalar(
+        "SELECT EXISTS(SELECT 1 FROM legal_holds WHERE subject_type = ? AND subject_id = ? AND released_at IS NULL)",
+    )
+    .bind(&subject_type)
+    .bind(&request.subject_id)
+    .fetch_one(&state.db)
+    .await
+    .map_err(ApiError::DatabaseError)?;
+    if existing {
+        return Err(ApiError::BadRequest(format!(
+            "This {} is already under a legal hold",
+            subject_type
+        )));
+    }
+
+    let id = generate_legal_hold_id();
+    sqlx::query(
+        r#"
+        INSERT INTO legal_holds (id, subject_type, subject_id, user_id, reason, matter_reference, placed_by)
+        VALUES (?, ?, ?, ?, ?, ?, ?)
+        "#,
+    )
+    .bind(&id)
+    .bind(&subject_type)
+    .bind(&request.subject_id)
+    .bind(&user_id)
+    .bind(&reason)
+    .bind(matter_reference)
+    .bind(&authed.id)
+    .execute(&state.db)
+    .await
+    .map_err(|e| {
+        error!(error = %e, "Database error placing legal hold");
+        ApiError::DatabaseError(e)
+    })?;
+    legal_hold::record_event(&state.db, &id, "placed", Some(&authed.id), Some(&reason))
+        .await
+        .map_err(ApiError::DatabaseError)?;
+
+    info!(
+        admin_user_id = %authed.id,
+        hold_id = %id,
+        subject_type = %subject_type,
+        subject_id = %request.subject_id,
+        "Legal hold placed"
+    );
+
+    Ok((StatusCode::CREATED, Json(fetch_hold(&state, &id).await?)))
+}
+
+/// POST /api/admin/legal-holds/:id/release - Lift a hold; the record and its audit trail remain
+pub async fn release_legal_hold(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Path(id): Path<String>,
+    Json(request): Json<ReleaseLegalHoldRequest>,
+) -> Result<Json<LegalHold>, ApiError> {
+    let state = state_lock.read().await.clone();
+    require_legal_hold_permission(&state, &authed).await?;
+
+    let reason = validate_reason(&request.reason)?;
+    fetch_hold(&state, &id).await?;
+
+    let result = sqlx::query(
+        r#"
+        UPDATE legal_holds
+        SET released_by = ?, released_at = datetime('now'), release_reason = ?
+        WHERE id = ? AND released_at IS NULL
+        "#,
+    )
+    .bind(&authed.id)
+    .bind(&reason)
+    .bind(&id)
+    .execute(&state.db)
+    .await
+    .map_err(|e| {
+        error!(error = %e, hold_id = %id, "Database error releasing legal hold");
+        ApiError::DatabaseError(e)
+    })?;
+    if result.rows_affected() == 0 {
+        return Err(ApiError::BadRequest(
+            "This legal hold has already been released".to_string(),
+        ));
+    }
+    legal_hold::record_event(&state.db, &id, "released", Some(&authed.id), Some(&reason))
+        .await
+        .map_err(ApiError::DatabaseError)?;
+
+    info!(admin_user_id = %authed.id, hold_id = %id, "Legal hold released");
+
+    Ok(Json(fetch_hold(&state, &id).await?))
+}
+
+/// GET /api/admin/legal-holds/:id/events - Audit trail of a hold
+pub async fn list_legal_hold_events(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Path(id): Path<String>,
+) -> Result<Json<Vec<LegalHoldEvent>>, ApiError> {
+    let state = state_lock.read().await.clone();
+    require_legal_hold_permission(&state, &authed).await?;
+
+    fetch_hold(&state, &id).await?;
+    let events = sqlx::query_as::<_, LegalHoldEvent>(
+        "SELECT * FROM legal_hold_events WHERE hold_id = ? ORDER BY created_at, rowid",
+    )
+    .bind(&id)
+    .fetch_all(&state.db)
+    .await
+    .map_err(ApiError::DatabaseError)?;
+
+    Ok(Json(events))
+}
@@ -1,10 +1,34 @@
 // src/admin/handlers/mod.rs
 
+pub mod activity;
+pub mod ai_models;
+pub mod ai_usage;
+pub mod analytics;
+pub mod chat_webhooks;
+pub mod cohorts;
+pub mod compensation;
+pub mod config;
 pub mod contact;
 pub mod dashboard;
+pub mod dev;
+pub mod docs;
 pub mod exports;
 pub mod files;
+pub mod hiring_performance;
+pub mod knockout_rules;
+pub mod legal_holds;
+pub mod moderation;
+pub mod orphaned_files;
+pub mod org;
+pub mod promotions;
+pub mod reports;
+pub mod search;
+pub mod security;
 pub mod settings;
+pub mod sla;
+pub mod sms;
+pub mod storage_usage;
 pub mod theme;
 pub mod users;
+pub mod whatsapp;
 
@@ -0,0 +1,130 @@
+// src/admin/handlers/moderation.rs
+//! Review queue for user-generated content flagged or held by moderation
+
+use axum::{
+    extract::{Extension, Path, Query},
+    Json,
+};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::{error, info};
+
+use crate::admin::models::{ModerationQueueQuery, ReviewModerationRequest};
+use crate::auth::AuthedUser;
+use crate::common::{ApiError, AppState};
+use crate::services::moderation::{self, ModeratedContent, ModerationItem};
+
+/// GET /api/admin/moderation - Moderation queue, held items first, oldest first
+pub async fn list_moderation_queue(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Query(query): Query<ModerationQueueQuery>,
+) -> Result<Json<Vec<ModerationItem>>, ApiError> {
+    let state = state_lock.read().await.clone();
+    authed.require_admin("Moderation queue")?;
+
+    let status = query.status.as_deref().unwrap_or("pending");
+    if !["pending", "approved", "removed"].contains(&status) {
+        return Err(ApiError::ValidationError(
+            "status must be one of: pending, approved, removed".to_string(),
+        ));
+    }
+    if let Some(content_type) = &query.content_type {
+        if ModeratedContent::parse(content_type).is_none() {
+            return Err(ApiError::ValidationError(
+                "content_type must be one of: bio, testimonial, message, video_title".to_string(),
+            ));
+        }
+    }
+
+    let limit = query.limit.unwrap_or(100).clamp(1, 500);
+    let items = sqlx::query_as::<_, ModerationItem>(
+        r#"
+        SELECT * FROM moderation_queue
+        WHERE status = ?
+          AND (? IS NULL OR content_type = ?)
+        ORDER BY CASE action WHEN 'hold' THEN 0 ELSE 1 END, created_at ASC
+        LIMIT ?
+        "#,
+    )
+    .bind(status)
+    .bind(&query.content_type)
+    .bind(&query.content_type)
+    .bind(limit)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|e| {
+        error!(error = %e, "Database error listing moderation queue");
+        ApiError::DatabaseError(e)
+    })?;
+
+    Ok(Json(items))
+}
+
+/// POST /api/admin/moderation/:id/review - Approve held/flagged content or take it down
+pub async fn review_moderation_item(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Path(id): Path<String>,
+    Json(request): Json<ReviewModerationRequest>,
+) -> Result<Json<ModerationItem>, ApiError> {
+    let state = state_lock.read().await.clone();
+    authed.require_admin("Moderation queue")?;
+
+    let status = match request.decision.as_str() {
+        "approve" => "approved",
+        "remove" => "removed",
+        _ => {
+            return Err(ApiError::ValidationError(
+                "decision must be 'approve' or 'remove'".to_string(),
+            ))
+        }
+    };
+
+    let item = sqlx::query_as::<_, ModerationItem>("SELECT * FROM moderation_queue WHERE id = ?")
+        .bind(&id)
+        .fetch_optional(&state.db)
+        .await
+        .map_err(ApiError::DatabaseError)?
+        .ok_or_else(|| ApiError::NotFound("Moderation item not found".to_string()))?;
+
+    if item.status != "pending" {
+        return Err(ApiError::BadRequest(format!(
+            "Moderation item was already {}",
+            item.status
+        )));
+    }
+
+    if status == "removed" {
+        moderation::remove_content(&state.db, &item).await?;
+    }
+
+    sqlx::query(
+        "UPDATE moderation_queue SET status = ?, reviewed_by = ?, reviewed_at = datetime('now'), review_notes = ? WHERE id = ?",
+    )
+    .bind(status)
+    .bind(&authed.id)
+    .bind(request.notes.as_deref())
+    .bind(&id)
+    .execute(&state.db)
+    .await
+    .map_err(ApiError::DatabaseError)?;
+
+    info!(
+        admin_user_id = %authed.id,
+        moderation_id = %id,
+        content_type = %item.content_type,
+        content_id = %item.content_id,
+        decision = %request.decision,
+        "Moderation item reviewed"
+    );
+
+    let reviewed =
+        sqlx::query_as::<_, ModerationItem>("SELECT * FROM moderation_queue WHERE id = ?")
+            .bind(&id)
+            .fetch_one(&state.db)
+            .await
+            .map_err(ApiError::DatabaseError)?;
+
+    Ok(Json(reviewed))
+}
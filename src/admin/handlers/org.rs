@@ -0,0 +1,630 @@
+// src/admin/handlers/org.rs
+//! Departments, teams and who is hiring for each job
+
+use axum::{
+    extract::{Extension, Path, Query},
+    http::StatusCode,
+    Json,
+};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::{error, info};
+
+use crate::admin::models::{
+    CreateDepartmentRequest, Department, DepartmentQuery, JobAssignment, MessageResponse, MyJob,
+    MyJobsQuery, Team, TeamRequest, UpdateDepartmentRequest, UpdateJobAssignmentRequest,
+};
+use crate::auth::AuthedUser;
+use crate::common::{generate_department_id, generate_team_id, ApiError, AppState};
+use crate::services::org;
+
+const MAX_NAME_LENGTH: usize = 100;
+
+fn validate_name(name: &str, what: &str) -> Result<String, ApiError> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(ApiError::ValidationError(format!(
+            "{} name is required",
+            what
+        )));
+    }
+    if name.chars().count() > MAX_NAME_LENGTH {
+        return Err(ApiError::ValidationError(format!(
+            "{} name must be at most {} characters",
+            what, MAX_NAME_LENGTH
+        )));
+    }
+    Ok(name.to_string())
+}
+
+/// Treat empty strings in optional ids as "not set"
+fn normalize_id(value: Option<&str>) -> Option<String> {
+    value
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .map(str::to_string)
+}
+
+fn map_unique_violation(e: sqlx::Error, message: &str) -> ApiError {
+    if e.to_string().contains("UNIQUE constraint failed") {
+        return ApiError::BadRequest(message.to_string());
+    }
+    error!(error = %e, "Database error saving org structure");
+    ApiError::DatabaseError(e)
+}
+
+async fn fetch_department(state: &AppState, id: &str) -> Result<Department, ApiError> {
+    let mut department = sqlx::query_as::<_, Department>("SELECT * FROM departments WHERE id = ?")
+        .bind(id)
+        .fetch_optional(&state.db)
+        .await
+        .map_err(ApiError::DatabaseError)?
+        .ok_or_else(|| ApiError::NotFound("Department not found".to_string()))?;
+
+    department.teams =
+        sqlx::query_as::<_, Team>("SELECT * FROM teams WHERE department_id = ? ORDER BY name")
+            .bind(id)
+            .fetch_all(&state.db)
+            .await
+            .map_err(ApiError::DatabaseError)?;
+    Ok(department)
+}
+
+async fn fetch_team(state: &AppState, id: &str) -> Result<Team, ApiError> {
+    sqlx::query_as::<_, Team>("SELECT * FROM teams WHERE id = ?")
+        .bind(id)
+        .fetch_optional(&state.db)
+        .await
+        .map_err(ApiError::DatabaseError)?
+        .ok_or_else(|| ApiError::NotFound("Team not found".to_string()))
+}
+
+/// A parent must exist, share the department's company, and not sit below it
+async fn validate_parent(
+    state: &AppState,
+    department_id: Option<&str>,
+    company_id: Option<&str>,
+    parent_id: &str,
+) -> Result<(), ApiError> {
+    let parent = fetch_department(state, parent_id)
+        .await
+        .map_err(|_| ApiError::ValidationError("Parent department not found".to_string()))?;
+    if parent.company_id.as_deref() != company_id {
+        return Err(ApiError::ValidationError(
+            "Parent department belongs to a different company".to_string(),
+        ));
+    }
+
+    let Some(department_id) = department_id else {
+        return Ok(());
+    };
+    let mut ancestor = Some(parent);
+    while let Some(current) = ancestor {
+        if current.id == department_id {
+            return Err(ApiError::ValidationError(
+                "A department cannot be nested under itself".to_string(),
+            ));
+        }
+        ancestor = match current.parent_id {
+            Some(next) => Some(fetch_department(state, &next).await?),
+            None => None,
+        };
+    }
+    Ok(())
+}
+
+/// GET /api/admin/departments - Departments with their teams, optionally for one company
+pub async fn list_departments(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Query(query): Query<DepartmentQuery>,
+) -> Result<Json<Vec<Department>>, ApiError> {
+    let state = state_lock.read().await.clone();
+    authed.require_admin("Org structure")?;
+
+    let mut departments = sqlx::query_as::<_, Department>(
+        "SELECT * FROM departments WHERE (? IS NULL OR company_id = ?) ORDER BY name",
+    )
+    .bind(&query.company_id)
+    .bind(&query.company_id)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|e| {
+        error!(error = %e, "Database error listing departments");
+        ApiError::DatabaseError(e)
+    })?;
+
+    let teams = sqlx::query_as::<_, Team>("SELECT * FROM teams ORDER BY name")
+        .fetch_all(&state.db)
+        .await
+        .map_err(ApiError::DatabaseError)?;
+    for department in &mut departments {
+        department.teams = teams
+            .iter()
+            .filter(|t| t.department_id == department.id)
+            .cloned()
+            .collect();
+    }
+
+    Ok(Json(departments))
+}
+
+/// POST /api/admin/departments - Create a department
+pub async fn create_department(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Json(request): Json<CreateDepartmentRequest>,
+) -> Result<(StatusCode, Json<Department>), ApiError> {
+    let state = state_lock.read().await.clone();
+    authed.require_admin("Org structure")?;
+
+    let name = validate_name(&request.name, "Department")?;
+    let company_id = normalize_id(request.company_id.as_deref());
+    let parent_id = normalize_id(request.parent_id.as_deref());
+
+    if let Some(company_id) = &company_id {
+        let exists: bool =
+            sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM companies WHERE id = ?)")
+                .bind(company_id)
+                .fetch_one(&state.db)
+                .await
+                .map_err(ApiError::DatabaseError)?;
+        if !exists {
+            return Err(ApiError::NotFound("Company not found".to_string()));
+        }
+    }
+    if let Some(parent_id) = &parent_id {
+        validate_parent(&state, None, company_id.as_deref(), parent_id).await?;
+    }
+
+    let id = generate_department_id();
+    sqlx::query("INSERT INTO departments (id, company_id, parent_id, name) VALUES (?, ?, ?, ?)")
+        .bind(&id)
+        .bind(&company_id)
+        .bind(&parent_id)
+        .bind(&name)
+        .execute(&state.db)
+        .await
+        .map_err(|e| map_unique_violation(e, "A department with this name already exists"))?;
+
+    info!(
+        admin_user_id = %authed.id,
+        department_id = %id,
+        company_id = ?company_id,
+        "Department created"
+    );
+
+    Ok((
+        StatusCode::CREATED,
+        Json(fetch_department(&state, &id).await?),
+    ))
+}
+
+/// PUT /api/admin/departments/:id - Rename or move a department
+pub async fn update_department(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Path(id): Path<String>,
+    Json(request): Json<UpdateDepartmentRequest>,
+) -> Result<Json<Department>, ApiError> {
+    let state = state_lock.read().await.clone();
+    authed.require_admin("Org structure")?;
+
+    let department = fetch_department(&state, &id).await?;
+    let name = match &request.name {
+        Some(name) => validate_name(name, "Department")?,
+        None => department.name,
+    };
+    let parent_id = match request.parent_id.as_deref() {
+        Some(value) => normalize_id(Some(value)),
+        None => department.parent_id,
+    };
+    if let Some(parent_id) = &parent_id {
+        validate_parent(
+            &state,
+            Some(&id),
+            department.company_id.as_deref(),
+            parent_id,
+        )
+        .await?;
+    }
+
+    sqlx::query(
+        "UPDATE departments SET name = ?, parent_id = ?, updated_at = datetime('now') WHERE id = ?",
+    )
+    .bind(&name)
+    .bind(&parent_id)
+    .bind(&id)
+    .execute(&state.db)
+    .await
+    .map_err(|e| map_unique_violation(e, "A department with this name already exists"))?;
+
+    info!(admin_user_id = %authed.id, department_id = %id, "Department updated");
+
+    Ok(Json(fetch_department(&state, &id).await?))
+}
+
+/// DELETE /api/admin/departments/:id - Delete a department and its teams
+///
+/// Jobs placed in it keep their assignees but lose their department and team.
+pub async fn delete_department(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Path(id): Path<String>,
+) -> Result<Json<MessageResponse>, ApiError> {
+    let state = state_lock.read().await.clone();
+    authed.require_admin("Org structure")?;
+
+    fetch_department(&state, &id).await?;
+
+    let mut tx = state.db.begin().await.map_err(ApiError::DatabaseError)?;
+    sqlx::query(
+        "UPDATE jobs SET department_id = NULL, team_id = NULL, updated_at = datetime('now') WHERE department_id = ?",
+    )
+    .bind(&id)
+    .execute(&mut *tx)
+    .await
+    .map_err(ApiError::DatabaseError)?;
+    sqlx::query("UPDATE departments SET parent_id = NULL WHERE parent_id = ?")
+        .bind(&id)
+        .execute(&mut *tx)
+        .await
+        .map_err(ApiError::DatabaseError)?;
+    sqlx::query("DELETE FROM teams WHERE department_id = ?")
+        .bind(&id)
+        .execute(&mut *tx)
+        .await
+        .map_err(ApiError::DatabaseError)?;
+    sqlx::query("DELETE FROM departments WHERE id = ?")
+        .bind(&id)
+        .execute(&mut *tx)
+        .await
+        .map_err(ApiError::DatabaseError)?;
+    tx.commit().await.map_err(|e| {
+        error!(error = %e, department_id = %id, "Database error deleting department");
+        ApiError::DatabaseError(e)
+    })?;
+
+    info!(admin_user_id = %authed.id, department_id = %id, "Department deleted");
+
+    Ok(Json(MessageResponse {
+        message: "Department deleted".to_string(),
+    }))
+}
+
+/// POST /api/admin/departments/:id/teams - Add a team to a department
+pub async fn create_team(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Path(department_id): Path<String>,
+    Json(request): Json<TeamRequest>,
+) -> Result<(StatusCode, Json<Team>), ApiError> {
+    let state = state_lock.read().await.clone();
+    authed.require_admin("Org structure")?;
+
+    let name = validate_name(&request.name, "Team")?;
+    fetch_department(&state, &department_id).await?;
+
+    let id = generate_team_id();
+    sqlx::query("INSERT INTO teams (id, department_id, name) VALUES (?, ?, ?)")
+        .bind(&id)
+        .bind(&department_id)
+        .bind(&name)
+        .execute(&state.db)
+        .await
+        .map_err(|e| {
+            map_unique_violation(e, "This department already has a team with this name")
+        })?;
+
+    info!(
+        admin_user_id = %authed.id,
+        department_id = %department_id,
+        team_id = %id,
+        "Team created"
+    );
+
+    Ok((StatusCode::CREATED, Json(fetch_team(&state, &id).await?)))
+}
+
+/// PUT /api/admin/teams/:id - Rename a team
+pub async fn update_team(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Path(id): Path<String>,
+    Json(request): Json<TeamRequest>,
+) -> Result<Json<Team>, ApiError> {
+    let state = state_lock.read().await.clone();
+    authed.require_admin("Org structure")?;
+
+    let name = validate_name(&request.name, "Team")?;
+    fetch_team(&state, &id).await?;
+
+    sqlx::query("UPDATE teams SET name = ?, updated_at = datetime('now') WHERE id = ?")
+        .bind(&name)
+        .bind(&id)
+        .execute(&state.db)
+        .await
+        .map_err(|e| {
+            map_unique_violation(e, "This department already has a team with this name")
+        })?;
+
+    info!(admin_user_id = %authed.id, team_id = %id, "Team updated");
+
+    Ok(Json(fetch_team(&state, &id).await?))
+}
+
+/// DELETE /api/admin/teams/:id - Delete a team; its jobs stay in the department
+pub async fn delete_team(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Path(id): Path<String>,
+) -> Result<Json<MessageResponse>, ApiError> {
+    let state = state_lock.read().await.clone();
+    authed.require_admin("Org structure")?;
+
+    let mut tx = state.db.begin().await.map_err(ApiError::DatabaseError)?;
+    sqlx::query("UPDATE jobs SET team_id = NULL, updated_at = datetime('now') WHERE team_id = ?")
+        .bind(&id)
+        .execute(&mut *tx)
+        .await
+        .map_err(ApiError::DatabaseError)?;
+    let result = sqlx::query("DELETE FROM teams WHERE id = ?")
+        .bind(&id)
+        .execute(&mut *tx)
+        .await
+        .map_err(ApiError::DatabaseError)?;
+    if result.rows_affected() == 0 {
+        return Err(ApiError::NotFound("Team not found".to_string()));
+    }
+    tx.commit().await.map_err(ApiError::DatabaseError)?;
+
+    info!(admin_user_id = %authed.id, team_id = %id, "Team deleted");
+
+    Ok(Json(MessageResponse {
+        message: "Team deleted".to_string(),
+    }))
+}
+
+/// Department id and name, then team id and name
+type JobPlacement = (
+    Option<String>,
+    Option<String>,
+    Option<String>,
+    Option<String>,
+);
+
+async fn load_assignment(state: &AppState, job_id: &str) -> Result<JobAssignment, ApiError> {
+    let placement: Option<JobPlacement> = sqlx::query_as(
+        r#"
+            SELECT j.department_id, d.name, j.team_id, t.name
+            FROM jobs j
+            LEFT JOIN departments d ON d.id = j.department_id
+            LEFT JOIN teams t ON t.id = j.team_id
+            WHERE j.id = ?
+            "#,
+    )
+    .bind(job_id)
+    .fetch_optional(&state.db)
+    .await
+    .map_err(ApiError::DatabaseError)?;
+    let (department_id, department_name, team_id, team_name) =
+        placement.ok_or_else(|| ApiError::NotFound("Job not found".to_string()))?;
+
+    let hiring_manager = sqlx::query_as(
+        "SELECT u.id, u.name, u.email FROM jobs j JOIN users u ON u.id = j.hiring_manager_id WHERE j.id = ?",
+    )
+    .bind(job_id)
+    .fetch_optional(&state.db)
+    .await
+    .map_err(ApiError::DatabaseError)?;
+
+    let recruiters = sqlx::query_as(
+        r#"
+        SELECT u.id, u.name, u.email FROM job_recruiters r
+        JOIN users u ON u.id = r.user_id
+        WHERE r.job_id = ?
+        ORDER BY r.assigned_at, u.name
+        "#,
+    )
+    .bind(job_id)
+    .fetch_all(&state.db)
+    .await
+    .map_err(ApiError::DatabaseError)?;
+
+    Ok(JobAssignment {
+        job_id: job_id.to_string(),
+        department_id,
+        department_name,
+        team_id,
+        team_name,
+        hiring_manager,
+        recruiters,
+    })
+}
+
+/// GET /api/admin/jobs/:id/assignment - A job's department, team, hiring manager and recruiters
+pub async fn get_job_assignment(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Path(job_id): Path<String>,
+) -> Result<Json<JobAssignment>, ApiError> {
+    let state = state_lock.read().await.clone();
+    authed.require_admin("Org structure")?;
+
+    Ok(Json(load_assignment(&state, &job_id).await?))
+}
+
+/// PUT /api/admin/jobs/:id/assignment - Replace a job's placement and assignees
+pub async fn update_job_assignment(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Path(job_id): Path<String>,
+    Json(request): Json<UpdateJobAssignmentRequest>,
+) -> Result<Json<JobAssignment>, ApiError> {
+    let state = state_lock.read().await.clone();
+    authed.require_admin("Org structure")?;
+
+    let job_exists: bool = sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM jobs WHERE id = ?)")
+        .bind(&job_id)
+        .fetch_one(&state.db)
+        .await
+        .map_err(ApiError::DatabaseError)?;
+    if !job_exists {
+        return Err(ApiError::NotFound("Job not found".to_string()));
+    }
+
+    let department_id = normalize_id(request.department_id.as_deref());
+    let team_id = normalize_id(request.team_id.as_deref());
+    if let Some(department_id) = &department_id {
+        fetch_department(&state, department_id).await?;
+    }
+    if let Some(team_id) = &team_id {
+        let team = fetch_team(&state, team_id).await?;
+        if department_id.as_deref() != Some(team.department_id.as_str()) {
+            return Err(ApiError::ValidationError(
+                "team_id must belong to department_id".to_string(),
+            ));
+        }
+    }
+
+    let hiring_manager_id = normalize_id(request.hiring_manager_id.as_deref());
+    if let Some(user_id) = &hiring_manager_id {
+        org::load_assignable_user(&state, user_id).await?;
+    }
+    let mut recruiter_ids: Vec<String> = Vec::new();
+    for user_id in &request.recruiter_ids {
+        let Some(user_id) = normalize_id(Some(user_id)) else {
+            continue;
+        };
+        if recruiter_ids.contains(&user_id) {
+            continue;
+        }
+        org::load_assignable_user(&state, &user_id).await?;
+        recruiter_ids.push(user_id);
+    }
+
+    let mut tx = state.db.begin().await.map_err(ApiError::DatabaseError)?;
+    sqlx::query(
+        r#"
+        UPDATE jobs
+        SET department_id = ?, team_id = ?, hiring_manager_id = ?, updated_at = datetime('now')
+        WHERE id = ?
+        "#,
+    )
+    .bind(&department_id)
+    .bind(&team_id)
+    .bind(&hiring_manager_id)
+    .bind(&job_id)
+    .execute(&mut *tx)
+    .await
+    .map_err(ApiError::DatabaseError)?;
+
+    // Keep the original assigned_at of recruiters who stay on the job
+    let placeholders = vec!["?"; recruiter_ids.len()].join(", ");
+    let remove_sql = if recruiter_ids.is_empty() {
+        "DELETE FROM job_recruiters WHERE job_id = ?".to_string()
+    } else {
+        format!(
+            "DELETE FROM job_recruiters WHERE job_id = ? AND user_id NOT IN ({})",
+            placeholders
+        )
+    };
+    let mut remove = sqlx::query(&remove_sql).bind(&job_id);
+    for user_id in &recruiter_ids {
+        remove = remove.bind(user_id);
+    }
+    remove
+        .execute(&mut *tx)
+        .await
+        .map_err(ApiError::DatabaseError)?;
+
+    for user_id in &recruiter_ids {
+        sqlx::query(
+            "INSERT OR IGNORE INTO job_recruiters (job_id, user_id, assigned_by) VALUES (?, ?, ?)",
+        )
+        .bind(&job_id)
+        .bind(user_id)
+        .bind(&authed.id)
+        .execute(&mut *tx)
+        .await
+        .map_err(ApiError::DatabaseError)?;
+    }
+    tx.commit().await.map_err(|e| {
+        error!(error = %e, job_id = %job_id, "Database error saving job assignment");
+        ApiError::DatabaseError(e)
+    })?;
+
+    info!(
+        admin_user_id = %authed.id,
+        job_id = %job_id,
+        hiring_manager_id = ?hiring_manager_id,
+        recruiters = recruiter_ids.len(),
+        "Job assignment updated"
+    );
+
+    Ok(Json(load_assignment(&state, &job_id).await?))
+}
+
+/// GET /api/admin/my-jobs - Jobs the current admin is hiring manager or recruiter for
+pub async fn list_my_jobs(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Query(query): Query<MyJobsQuery>,
+) -> Result<Json<Vec<MyJob>>, ApiError> {
+    let state = state_lock.read().await.clone();
+    authed.require_admin("Org structure")?;
+
+    let role = query
+        .role
+        .as_deref()
+        .map(str::trim)
+        .filter(|r| !r.is_empty());
+    if let Some(role) = role {
+        if !matches!(role, "hiring_manager" | "recruiter") {
+            return Err(ApiError::ValidationError(
+                "role must be hiring_manager or recruiter".to_string(),
+            ));
+        }
+    }
+
+    let jobs = sqlx::query_as::<_, MyJob>(
+        r#"
+        SELECT * FROM (
+            SELECT j.id, j.title, j.company, j.status,
+                   d.name AS department_name, t.name AS team_name,
+                   COALESCE(j.hiring_manager_id = ?, 0) AS is_hiring_manager,
+                   EXISTS(SELECT 1 FROM job_recruiters r
+                          WHERE r.job_id = j.id AND r.user_id = ?) AS is_recruiter,
+                   (SELECT COUNT(*) FROM applications a
+                    WHERE a.job_id = j.id AND a.archived_at IS NULL
+                      AND a.status NOT IN ('hired', 'rejected', 'withdrawn')) AS open_applications,
+                   (SELECT COUNT(*) FROM application_sla_breaches b
+                    WHERE b.job_id = j.id AND b.resolved_at IS NULL) AS stale_applications,
+                   j.created_at
+            FROM jobs j
+            LEFT JOIN departments d ON d.id = j.department_id
+            LEFT JOIN teams t ON t.id = j.team_id
+            WHERE (? IS NULL OR j.status = ?) AND j.deleted_at IS NULL
+        )
+        WHERE CASE ?
+            WHEN 'hiring_manager' THEN is_hiring_manager
+            WHEN 'recruiter' THEN is_recruiter
+            ELSE is_hiring_manager OR is_recruiter
+        END
+        ORDER BY stale_applications DESC, created_at DESC
+        "#,
+    )
+    .bind(&authed.id)
+    .bind(&authed.id)
+    .bind(&query.status)
+    .bind(&query.status)
+    .bind(role)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|e| {
+        error!(error = %e, user_id = %authed.id, "Database error listing assigned jobs");
+        ApiError::DatabaseError(e)
+    })?;
+
+    Ok(Json(jobs))
+}
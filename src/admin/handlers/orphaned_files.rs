@@ -0,0 +1,50 @@
+// src/admin/handlers/orphaned_files.rs
+//! Reconciliation of stored files against the database: orphans and missing files
+
+use axum::{extract::Extension, Json};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::info;
+
+use crate::admin::models::OrphanScanRequest;
+use crate::auth::AuthedUser;
+use crate::common::{ApiError, AppState};
+use crate::services::file_gc::{self, OrphanScanReport, OrphanedFile};
+
+/// GET /api/admin/storage/orphans - Orphans recorded by the last scan, oldest first
+pub async fn list_orphaned_files(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+) -> Result<Json<Vec<OrphanedFile>>, ApiError> {
+    let state = state_lock.read().await.clone();
+    authed.require_admin("Orphaned file")?;
+
+    let orphans = file_gc::list_orphans(&state.db)
+        .await
+        .map_err(ApiError::DatabaseError)?;
+
+    Ok(Json(orphans))
+}
+
+/// POST /api/admin/storage/orphans/scan - Run a scan now, optionally deleting orphans past the grace period
+pub async fn scan_orphaned_files(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    body: Option<Json<OrphanScanRequest>>,
+) -> Result<Json<OrphanScanReport>, ApiError> {
+    let state = state_lock.read().await.clone();
+    authed.require_admin("Orphaned file")?;
+
+    let request = body.map(|Json(b)| b).unwrap_or_default();
+    let report = file_gc::scan(&state, request.delete).await?;
+
+    info!(
+        admin_id = %authed.id,
+        delete = request.delete,
+        orphaned = report.orphaned.len(),
+        deleted = report.deleted,
+        "Admin ran orphaned file scan"
+    );
+
+    Ok(Json(report))
+}
@@ -0,0 +1,257 @@
+// src/admin/handlers/promotions.rs
+//! Paid job promotion tracking and cost-per-application analytics
+
+use axum::{
+    extract::{Extension, Path, Query},
+    http::StatusCode,
+    Json,
+};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::{error, info};
+
+use crate::admin::models::{
+    CreateJobPromotionRequest, JobPromotion, MessageResponse, PromotionReport,
+    PromotionReportQuery, UpdateJobPromotionRequest,
+};
+use crate::auth::AuthedUser;
+use crate::common::{generate_promotion_id, ApiError, AppState};
+use crate::services::promotions;
+
+async fn fetch_promotion(state: &AppState, id: &str) -> Result<JobPromotion, ApiError> {
+    sqlx::query_as::<_, JobPromotion>("SELECT * FROM job_promotions WHERE id = ?")
+        .bind(id)
+        .fetch_optional(&state.db)
+        .await
+        .map_err(ApiError::DatabaseError)?
+        .ok_or_else(|| ApiError::NotFound("Promotion not found".to_string()))
+}
+
+fn normalize_currency(currency: Option<&str>) -> Option<String> {
+    currency
+        .map(|c| c.trim().to_uppercase())
+        .filter(|c| !c.is_empty())
+}
+
+fn source_tag(source: Option<&str>, platform: &str) -> Result<String, ApiError> {
+    promotions::normalize_source(source.unwrap_or(platform)).ok_or_else(|| {
+        ApiError::ValidationError("source must contain letters, digits, '-' or '_'".to_string())
+    })
+}
+
+/// GET /api/admin/jobs/:id/promotions - A job's promotions, latest first
+pub async fn list_job_promotions(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Path(job_id): Path<String>,
+) -> Result<Json<Vec<JobPromotion>>, ApiError> {
+    let state = state_lock.read().await.clone();
+    authed.require_admin("Promotion")?;
+
+    let promotions = sqlx::query_as::<_, JobPromotion>(
+        "SELECT * FROM job_promotions WHERE job_id = ? ORDER BY start_date DESC, created_at DESC",
+    )
+    .bind(&job_id)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|e| {
+        error!(error = %e, job_id = %job_id, "Database error listing job promotions");
+        ApiError::DatabaseError(e)
+    })?;
+
+    Ok(Json(promotions))
+}
+
+/// POST /api/admin/jobs/:id/promotions - Record a paid promotion of a job
+pub async fn create_job_promotion(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Path(job_id): Path<String>,
+    Json(request): Json<CreateJobPromotionRequest>,
+) -> Result<(StatusCode, Json<JobPromotion>), ApiError> {
+    let state = state_lock.read().await.clone();
+    authed.require_admin("Promotion")?;
+
+    let platform = request.platform.trim();
+    if platform.is_empty() {
+        return Err(ApiError::ValidationError(
+            "platform is required".to_string(),
+        ));
+    }
+    let source = source_tag(request.source.as_deref(), platform)?;
+    let spend = request.spend.unwrap_or(0.0);
+    promotions::validate_promotion(
+        request.budget,
+        spend,
+        &request.start_date,
+        request.end_date.as_deref(),
+    )?;
+
+    let job_exists: bool = sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM jobs WHERE id = ?)")
+        .bind(&job_id)
+        .fetch_one(&state.db)
+        .await
+        .map_err(ApiError::DatabaseError)?;
+    if !job_exists {
+        return Err(ApiError::NotFound("Job not found".to_string()));
+    }
+
+    let id = generate_promotion_id();
+    let currency =
+        normalize_currency(request.currency.as_deref()).unwrap_or_else(|| "USD".to_string());
+
+    sqlx::query(
+        r#"
+        INSERT INTO job_promotions
+            (id, job_id, platform, source, currency, budget, spend, start_date, end_date, notes, created_by)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+        "#,
+    )
+    .bind(&id)
+    .bind(&job_id)
+    .bind(platform)
+    .bind(&source)
+    .bind(&currency)
+    .bind(request.budget)
+    .bind(spend)
+    .bind(request.start_date.trim())
+    .bind(request.end_date.as_deref().map(str::trim))
+    .bind(&request.notes)
+    .bind(&authed.id)
+    .execute(&state.db)
+    .await
+    .map_err(|e| {
+        error!(error = %e, "Database error creating job promotion");
+        ApiError::DatabaseError(e)
+    })?;
+
+    info!(
+        admin_user_id = %authed.id,
+        promotion_id = %id,
+        job_id = %job_id,
+        platform = %platform,
+        source = %source,
+        budget = request.budget,
+        "Job promotion created"
+    );
+
+    Ok((
+        StatusCode::CREATED,
+        Json(fetch_promotion(&state, &id).await?),
+    ))
+}
+
+/// PUT /api/admin/promotions/:id - Update a promotion, typically its spend or end date
+pub async fn update_job_promotion(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Path(id): Path<String>,
+    Json(request): Json<UpdateJobPromotionRequest>,
+) -> Result<Json<JobPromotion>, ApiError> {
+    let state = state_lock.read().await.clone();
+    authed.require_admin("Promotion")?;
+
+    let promotion = fetch_promotion(&state, &id).await?;
+    let platform = match request.platform.as_deref().map(str::trim) {
+        Some("") => {
+            return Err(ApiError::ValidationError(
+                "platform is required".to_string(),
+            ));
+        }
+        Some(platform) => platform.to_string(),
+        None => promotion.platform,
+    };
+    let source = match request.source.as_deref() {
+        Some(source) => source_tag(Some(source), &platform)?,
+        None => promotion.source,
+    };
+    let currency = normalize_currency(request.currency.as_deref()).unwrap_or(promotion.currency);
+    let budget = request.budget.unwrap_or(promotion.budget);
+    let spend = request.spend.unwrap_or(promotion.spend);
+    let start_date = request
+        .start_date
+        .map(|d| d.trim().to_string())
+        .unwrap_or(promotion.start_date);
+    // An empty end_date reopens the promotion
+    let end_date = match request.end_date.as_deref().map(str::trim) {
+        Some("") => None,
+        Some(end_date) => Some(end_date.to_string()),
+        None => promotion.end_date,
+    };
+    let notes = request.notes.or(promotion.notes);
+    promotions::validate_promotion(budget, spend, &start_date, end_date.as_deref())?;
+
+    sqlx::query(
+        r#"
+        UPDATE job_promotions
+        SET platform = ?, source = ?, currency = ?, budget = ?, spend = ?, start_date = ?,
+            end_date = ?, notes = ?, updated_at = datetime('now')
+        WHERE id = ?
+        "#,
+    )
+    .bind(&platform)
+    .bind(&source)
+    .bind(&currency)
+    .bind(budget)
+    .bind(spend)
+    .bind(&start_date)
+    .bind(&end_date)
+    .bind(&notes)
+    .bind(&id)
+    .execute(&state.db)
+    .await
+    .map_err(|e| {
+        error!(error = %e, promotion_id = %id, "Database error updating job promotion");
+        ApiError::DatabaseError(e)
+    })?;
+
+    info!(
+        admin_user_id = %authed.id,
+        promotion_id = %id,
+        previous_spend = promotion.spend,
+        spend = spend,
+        "Job promotion updated"
+    );
+
+    Ok(Json(fetch_promotion(&state, &id).await?))
+}
+
+/// DELETE /api/admin/promotions/:id - Remove a promotion
+pub async fn delete_job_promotion(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Path(id): Path<String>,
+) -> Result<Json<MessageResponse>, ApiError> {
+    let state = state_lock.read().await.clone();
+    authed.require_admin("Promotion")?;
+
+    let result = sqlx::query("DELETE FROM job_promotions WHERE id = ?")
+        .bind(&id)
+        .execute(&state.db)
+        .await
+        .map_err(ApiError::DatabaseError)?;
+
+    if result.rows_affected() == 0 {
+        return Err(ApiError::NotFound("Promotion not found".to_string()));
+    }
+
+    info!(admin_user_id = %authed.id, promotion_id = %id, "Job promotion deleted");
+
+    Ok(Json(MessageResponse {
+        message: "Promotion deleted".to_string(),
+    }))
+}
+
+/// GET /api/admin/analytics/promotions - Spend, clicks, applications and cost per
+/// application, per promotion and per channel
+pub async fn get_promotion_report(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Query(query): Query<PromotionReportQuery>,
+) -> Result<Json<PromotionReport>, ApiError> {
+    let state = state_lock.read().await.clone();
+    authed.require_admin("Promotion")?;
+
+    let report = promotions::promotion_report(&state.db_read, &query).await?;
+    Ok(Json(report))
+}
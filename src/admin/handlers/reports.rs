@@ -0,0 +1,381 @@
+// src/admin/handlers/reports.rs
+//! Report builder: ad-hoc reports and saved reports emailed on a schedule
+
+use axum::{
+    extract::{Extension, Path, Query},
+    http::{header, StatusCode},
+    response::{IntoResponse, Json, Response},
+};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::{error, info};
+use validator::ValidateEmail;
+
+use crate::admin::models::{
+    CreateSavedReportRequest, MessageResponse, ReportOutputQuery, SavedReport,
+    UpdateSavedReportRequest,
+};
+use crate::auth::AuthedUser;
+use crate::common::{generate_saved_report_id, ApiError, AppState};
+use crate::services::reports::{self, Entity, ReportDefinition, ReportResult};
+
+const DEFAULT_SCHEDULE_HOUR: i64 = 8;
+
+async fn fetch_report(state: &AppState, id: &str) -> Result<SavedReport, ApiError> {
+    sqlx::query_as::<_, SavedReport>("SELECT * FROM saved_reports WHERE id = ?")
+        .bind(id)
+        .fetch_optional(&state.db)
+        .await
+        .map_err(ApiError::DatabaseError)?
+        .ok_or_else(|| ApiError::NotFound("Report not found".to_string()))
+}
+
+fn non_empty(value: Option<&str>) -> Option<String> {
+    value
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .map(str::to_string)
+}
+
+/// Check the schedule fields together; returns the recipients normalized
+fn validate_schedule(
+    schedule: Option<&str>,
+    hour: i64,
+    weekday: Option<i64>,
+    recipients: Option<&str>,
+) -> Result<Option<String>, ApiError> {
+    if let Some(schedule) = schedule {
+        if !reports::REPORT_SCHEDULES.contains(&schedule) {
+            return Err(ApiError::ValidationError(format!(
+                "schedule must be one of: {}",
+                reports::REPORT_SCHEDULES.join(", ")
+            )));
+        }
+    }
+    if !(0..=23).contains(&hour) {
+        return Err(ApiError::ValidationError(
+            "schedule_hour must be between 0 and 23".to_string(),
+        ));
+    }
+    if weekday.is_some_and(|d| !(0..=6).contains(&d)) {
+        return Err(ApiError::ValidationError(
+            "schedule_weekday must be between 0 (Monday) and 6 (Sunday)".to_string(),
+        ));
+    }
+
+    let recipients = reports::parse_recipients(recipients);
+    if let Some(invalid) = recipients.iter().find(|r| !r.validate_email()) {
+        return Err(ApiError::ValidationError(format!(
+            "'{}' is not a valid email address",
+            invalid
+        )));
+    }
+    if schedule.is_some() && recipients.is_empty() {
+        return Err(ApiError::ValidationError(
+            "A scheduled report needs at least one recipient".to_string(),
+        ));
+    }
+    Ok(Some(recipients.join(", ")).filter(|r| !r.is_empty()))
+}
+
+fn report_response(
+    result: ReportResult,
+    format: Option<&str>,
+    filename: &str,
+) -> Result<Response, ApiError> {
+    match format.unwrap_or("json") {
+        "csv" => Ok((
+            StatusCode::OK,
+            [
+                (header::CONTENT_TYPE, "text/csv".to_string()),
+                (
+                    header::CONTENT_DISPOSITION,
+                    format!("attachment; filename=\"{}.csv\"", filename),
+                ),
+            ],
+            reports::to_csv(&result),
+        )
+            .into_response()),
+        "json" => Ok(Json(result).into_response()),
+        _ => Err(ApiError::ValidationError(
+            "format must be 'json' or 'csv'".to_string(),
+        )),
+    }
+}
+
+/// GET /api/admin/reports/catalog - Entities with the dimensions and metrics reports can use
+pub async fn get_report_catalog(authed: AuthedUser) -> Result<Json<&'static [Entity]>, ApiError> {
+    authed.require_admin("Report")?;
+    Ok(Json(reports::CATALOG))
+}
+
+/// POST /api/admin/reports/run?format= - Run a report definition without saving it
+pub async fn run_report(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Query(query): Query<ReportOutputQuery>,
+    Json(definition): Json<ReportDefinition>,
+) -> Result<Response, ApiError> {
+    let state = state_lock.read().await.clone();
+    authed.require_admin("Report")?;
+
+    let result = reports::run(&state.db_read, &definition).await?;
+
+    info!(
+        admin_user_id = %authed.id,
+        entity = %definition.entity,
+        rows = result.rows.len(),
+        "Report run"
+    );
+
+    report_response(
+        result,
+        query.format.as_deref(),
+        &format!("{}_report", definition.entity.trim()),
+    )
+}
+
+/// GET /api/admin/reports - Saved reports, by name
+pub async fn list_saved_reports(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+) -> Result<Json<Vec<SavedReport>>, ApiError> {
+    let state = state_lock.read().await.clone();
+    authed.require_admin("Report")?;
+
+    let saved = sqlx::query_as::<_, SavedReport>("SELECT * FROM saved_reports ORDER BY name")
+        .fetch_all(&state.db)
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Database error listing saved reports");
+            ApiError::DatabaseError(e)
+        })?;
+
+    Ok(Json(saved))
+}
+
+/// POST /api/admin/reports - Save a report definition, optionally with an email schedule
+pub async fn create_saved_report(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Json(request): Json<CreateSavedReportRequest>,
+) -> Result<(StatusCode, Json<SavedReport>), ApiError> {
+    let state = state_lock.read().await.clone();
+    authed.require_admin("Report")?;
+
+    let name = non_empty(Some(&request.name))
+        .ok_or_else(|| ApiError::ValidationError("name is required".to_string()))?;
+    reports::compile(&request.definition)?;
+    let schedule = non_empty(request.schedule.as_deref());
+    let schedule_hour = request.schedule_hour.unwrap_or(DEFAULT_SCHEDULE_HOUR);
+    let recipients = validate_schedule(
+        schedule.as_deref(),
+        schedule_hour,
+        request.schedule_weekday,
+        request.recipients.as_deref(),
+    )?;
+    let definition = serde_json::to_string(&request.definition)
+        .map_err(|e| ApiError::InternalServer(format!("Failed to store definition: {}", e)))?;
+
+    let id = generate_saved_report_id();
+    sqlx::query(
+        r#"
+        INSERT INTO saved_reports
+            (id, name, description, definition, schedule, schedule_hour, schedule_weekday,
+             recipients, created_by)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+        "#,
+    )
+    .bind(&id)
+    .bind(&name)
+    .bind(non_empty(request.description.as_deref()))
+    .bind(&definition)
+    .bind(&schedule)
+    .bind(schedule_hour)
+    .bind(request.schedule_weekday)
+    .bind(&recipients)
+    .bind(&authed.id)
+    .execute(&state.db)
+    .await
+    .map_err(|e| {
+        error!(error = %e, "Database error creating saved report");
+        ApiError::DatabaseError(e)
+    })?;
+
+    info!(
+        admin_user_id = %authed.id,
+        report_id = %id,
+        entity = %request.definition.entity,
+        schedule = ?schedule,
+        "Saved report created"
+    );
+
+    Ok((StatusCode::CREATED, Json(fetch_report(&state, &id).await?)))
+}
+
+/// GET /api/admin/reports/:id - A saved report's definition and schedule
+pub async fn get_saved_report(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Path(id): Path<String>,
+) -> Result<Json<SavedReport>, ApiError> {
+    let state = state_lock.read().await.clone();
+    authed.require_admin("Report")?;
+
+    Ok(Json(fetch_report(&state, &id).await?))
+}
+
+/// PUT /api/admin/reports/:id - Change a saved report's definition or schedule
+pub async fn update_saved_report(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Path(id): Path<String>,
+    Json(request): Json<UpdateSavedReportRequest>,
+) -> Result<Json<SavedReport>, ApiError> {
+    let state = state_lock.read().await.clone();
+    authed.require_admin("Report")?;
+
+    let report = fetch_report(&state, &id).await?;
+    let name = match request.name.as_deref() {
+        Some(value) => non_empty(Some(value))
+            .ok_or_else(|| ApiError::ValidationError("name is required".to_string()))?,
+        None => report.name,
+    };
+    let description = match request.description.as_deref() {
+        Some(value) => non_empty(Some(value)),
+        None => report.description,
+    };
+    let definition = match &request.definition {
+        Some(definition) => {
+            reports::compile(definition)?;
+            serde_json::to_string(definition).map_err(|e| {
+                ApiError::InternalServer(format!("Failed to store definition: {}", e))
+            })?
+        }
+        None => report.definition,
+    };
+    let schedule = match request.schedule.as_deref() {
+        Some(value) => non_empty(Some(value)),
+        None => report.schedule,
+    };
+    let schedule_hour = request.schedule_hour.unwrap_or(report.schedule_hour);
+    let schedule_weekday = request.schedule_weekday.or(report.schedule_weekday);
+    let recipients = validate_schedule(
+        schedule.as_deref(),
+        schedule_hour,
+        schedule_weekday,
+        match request.recipients.as_deref() {
+            Some(value) => Some(value),
+            None => report.recipients.as_deref(),
+        },
+    )?;
+
+    sqlx::query(
+        r#"
+        UPDATE saved_reports
+        SET name = ?, description = ?, definition = ?, schedule = ?, schedule_hour = ?,
+            schedule_weekday = ?, recipients = ?, updated_at = datetime('now')
+        WHERE id = ?
+        "#,
+    )
+    .bind(&name)
+    .bind(&description)
+    .bind(&definition)
+    .bind(&schedule)
+    .bind(schedule_hour)
+    .bind(schedule_weekday)
+    .bind(&recipients)
+    .bind(&id)
+    .execute(&state.db)
+    .await
+    .map_err(|e| {
+        error!(error = %e, report_id = %id, "Database error updating saved report");
+        ApiError::DatabaseError(e)
+    })?;
+
+    info!(
+        admin_user_id = %authed.id,
+        report_id = %id,
+        schedule = ?schedule,
+        "Saved report updated"
+    );
+
+    Ok(Json(fetch_report(&state, &id).await?))
+}
+
+/// DELETE /api/admin/reports/:id - Delete a saved report and stop its emails
+pub async fn delete_saved_report(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Path(id): Path<String>,
+) -> Result<Json<MessageResponse>, ApiError> {
+    let state = state_lock.read().await.clone();
+    authed.require_admin("Report")?;
+
+    let result = sqlx::query("DELETE FROM saved_reports WHERE id = ?")
+        .bind(&id)
+        .execute(&state.db)
+        .await
+        .map_err(ApiError::DatabaseError)?;
+
+    if result.rows_affected() == 0 {
+        return Err(ApiError::NotFound("Report not found".to_string()));
+    }
+
+    info!(admin_user_id = %authed.id, report_id = %id, "Saved report deleted");
+
+    Ok(Json(MessageResponse {
+        message: "Report deleted".to_string(),
+    }))
+}
+
+/// GET /api/admin/reports/:id/results?format= - Run a saved report
+pub async fn get_saved_report_results(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Path(id): Path<String>,
+    Query(query): Query<ReportOutputQuery>,
+) -> Result<Response, ApiError> {
+    let state = state_lock.read().await.clone();
+    authed.require_admin("Report")?;
+
+    let report = fetch_report(&state, &id).await?;
+    let definition: ReportDefinition = serde_json::from_str(&report.definition).map_err(|e| {
+        error!(error = %e, report_id = %id, "Stored report definition is unreadable");
+        ApiError::InternalServer("Stored report definition is unreadable".to_string())
+    })?;
+    let result = reports::run(&state.db_read, &definition).await?;
+
+    info!(
+        admin_user_id = %authed.id,
+        report_id = %id,
+        rows = result.rows.len(),
+        "Saved report run"
+    );
+
+    report_response(result, query.format.as_deref(), &id.to_lowercase())
+}
+
+/// POST /api/admin/reports/:id/send - Email a saved report to its recipients now
+pub async fn send_saved_report(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Path(id): Path<String>,
+) -> Result<Json<MessageResponse>, ApiError> {
+    let state = state_lock.read().await.clone();
+    authed.require_admin("Report")?;
+
+    let report = fetch_report(&state, &id).await?;
+    let result = reports::deliver(&state.db, state.email.as_ref(), &report).await?;
+
+    info!(
+        admin_user_id = %authed.id,
+        report_id = %id,
+        rows = result.rows.len(),
+        "Saved report sent"
+    );
+
+    Ok(Json(MessageResponse {
+        message: "Report sent".to_string(),
+    }))
+}
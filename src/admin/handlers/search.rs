@@ -0,0 +1,173 @@
+// src/admin/handlers/search.rs
+//! Global search across candidates, jobs, applications and companies
+
+use axum::{
+    extract::{Extension, Query},
+    Json,
+};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::error;
+
+use crate::admin::models::{AdminSearchQuery, AdminSearchResponse, SearchHit};
+use crate::auth::AuthedUser;
+use crate::common::{ApiError, AppState};
+use crate::services::search::{
+    application_id_pattern, phrase_query, DEFAULT_RESULTS_PER_GROUP, MAX_RESULTS_PER_GROUP,
+    MIN_QUERY_LENGTH,
+};
+
+/// Application ID, candidate name, job title and status
+type ApplicationRow = (String, Option<String>, Option<String>, Option<String>);
+
+fn search_error(group: &str, e: sqlx::Error) -> ApiError {
+    error!(error = %e, group = %group, "Database error in admin search");
+    ApiError::DatabaseError(e)
+}
+
+/// GET /api/admin/search?q= - Matches grouped by type, each with a link into the admin UI
+pub async fn admin_search(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Query(query): Query<AdminSearchQuery>,
+) -> Result<Json<AdminSearchResponse>, ApiError> {
+    authed.require_admin("Search")?;
+    let state = state_lock.read().await.clone();
+
+    let q = query.q.trim().to_string();
+    if q.chars().count() < MIN_QUERY_LENGTH {
+        return Err(ApiError::ValidationError(format!(
+            "q must be at least {} characters",
+            MIN_QUERY_LENGTH
+        )));
+    }
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_RESULTS_PER_GROUP)
+        .clamp(1, MAX_RESULTS_PER_GROUP);
+    let phrase = phrase_query(&q);
+
+    let candidates: Vec<(String, Option<String>, String)> = sqlx::query_as(
+        r#"
+        SELECT u.id, u.name, u.email
+        FROM users_fts
+        JOIN users u ON u.rowid = users_fts.rowid
+        WHERE users_fts MATCH ?
+        ORDER BY rank
+        LIMIT ?
+        "#,
+    )
+    .bind(&phrase)
+    .bind(limit)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|e| search_error("candidates", e))?;
+
+    let jobs: Vec<(String, String, Option<String>, Option<String>)> = sqlx::query_as(
+        r#"
+        SELECT j.id, j.title, j.company, j.status
+        FROM jobs_fts
+        JOIN jobs j ON j.rowid = jobs_fts.rowid
+        WHERE jobs_fts MATCH ? AND j.deleted_at IS NULL
+        ORDER BY rank
+        LIMIT ?
+        "#,
+    )
+    .bind(&phrase)
+    .bind(limit)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|e| search_error("jobs", e))?;
+
+    let companies: Vec<(String, String, Option<String>)> = sqlx::query_as(
+        r#"
+        SELECT c.id, c.name, c.industry
+        FROM companies_fts
+        JOIN companies c ON c.rowid = companies_fts.rowid
+        WHERE companies_fts MATCH ? AND c.archived_at IS NULL
+        ORDER BY rank
+        LIMIT ?
+        "#,
+    )
+    .bind(&phrase)
+    .bind(limit)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|e| search_error("companies", e))?;
+
+    let applications: Vec<ApplicationRow> = match application_id_pattern(&q) {
+        Some(pattern) => sqlx::query_as(
+            r#"
+                SELECT a.id, u.name, j.title, a.status
+                FROM applications a
+                LEFT JOIN users u ON u.id = a.user_id
+                LEFT JOIN jobs j ON j.id = a.job_id
+                WHERE a.id GLOB ?
+                ORDER BY a.id
+                LIMIT ?
+                "#,
+        )
+        .bind(pattern)
+        .bind(limit)
+        .fetch_all(&state.db)
+        .await
+        .map_err(|e| search_error("applications", e))?,
+        None => Vec::new(),
+    };
+
+    Ok(Json(AdminSearchResponse {
+        query: q,
+        candidates: candidates
+            .into_iter()
+            .map(|(id, name, email)| SearchHit {
+                entity_type: "candidate",
+                link: format!("/admin/candidates/{}", id),
+                title: name
+                    .filter(|n| !n.trim().is_empty())
+                    .unwrap_or_else(|| email.clone()),
+                subtitle: Some(email),
+                id,
+            })
+            .collect(),
+        jobs: jobs
+            .into_iter()
+            .map(|(id, title, company, status)| SearchHit {
+                entity_type: "job",
+                link: format!("/admin/jobs/{}", id),
+                title,
+                subtitle: join_parts(&[company, status]),
+                id,
+            })
+            .collect(),
+        applications: applications
+            .into_iter()
+            .map(|(id, candidate, job, status)| SearchHit {
+                entity_type: "application",
+                link: format!("/admin/applications/{}", id),
+                title: id.clone(),
+                subtitle: join_parts(&[candidate, job, status]),
+                id,
+            })
+            .collect(),
+        companies: companies
+            .into_iter()
+            .map(|(id, name, industry)| SearchHit {
+                entity_type: "company",
+                link: format!("/admin/companies/{}", id),
+                title: name,
+                subtitle: industry,
+                id,
+            })
+            .collect(),
+    }))
+}
+
+fn join_parts(parts: &[Option<String>]) -> Option<String> {
+    let parts: Vec<&str> = parts
+        .iter()
+        .flatten()
+        .map(|p| p.trim())
+        .filter(|p| !p.is_empty())
+        .collect();
+    (!parts.is_empty()).then(|| parts.join(" · "))
+}
@@ -0,0 +1,131 @@
+// src/admin/handlers/security.rs
+//! Security alerts raised by the monitoring service, and the keys session tokens are
+//! signed with
+
+use axum::{
+    extract::{Extension, Path, Query},
+    Json,
+};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::{error, info};
+
+use crate::admin::models::SecurityEventQuery;
+use crate::auth::keys::{self, SigningKeyInfo};
+use crate::auth::AuthedUser;
+use crate::common::{ApiError, AppState};
+use crate::services::monitoring::SecurityEvent;
+
+/// GET /api/admin/security-events - Recent security alerts, newest first
+pub async fn list_security_events(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Query(query): Query<SecurityEventQuery>,
+) -> Result<Json<Vec<SecurityEvent>>, ApiError> {
+    let state = state_lock.read().await.clone();
+    authed.require_admin("Security events")?;
+
+    let limit = query.limit.unwrap_or(100).clamp(1, 500);
+    let events = sqlx::query_as::<_, SecurityEvent>(
+        r#"
+        SELECT * FROM security_events
+        WHERE (? IS NULL OR severity = ?)
+          AND (? IS NULL OR (acknowledged_at IS NOT NULL) = ?)
+        ORDER BY created_at DESC
+        LIMIT ?
+        "#,
+    )
+    .bind(&query.severity)
+    .bind(&query.severity)
+    .bind(query.acknowledged)
+    .bind(query.acknowledged)
+    .bind(limit)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|e| {
+        error!(error = %e, "Database error listing security events");
+        ApiError::DatabaseError(e)
+    })?;
+
+    Ok(Json(events))
+}
+
+/// POST /api/admin/security-events/:id/acknowledge - Mark an alert as reviewed
+pub async fn acknowledge_security_event(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Path(id): Path<String>,
+) -> Result<Json<SecurityEvent>, ApiError> {
+    let state = state_lock.read().await.clone();
+    authed.require_admin("Security events")?;
+
+    let result = sqlx::query(
+        "UPDATE security_events SET acknowledged_by = ?, acknowledged_at = datetime('now') WHERE id = ? AND acknowledged_at IS NULL",
+    )
+    .bind(&authed.id)
+    .bind(&id)
+    .execute(&state.db)
+    .await
+    .map_err(ApiError::DatabaseError)?;
+
+    let event = sqlx::query_as::<_, SecurityEvent>("SELECT * FROM security_events WHERE id = ?")
+        .bind(&id)
+        .fetch_optional(&state.db)
+        .await
+        .map_err(ApiError::DatabaseError)?
+        .ok_or_else(|| ApiError::NotFound("Security event not found".to_string()))?;
+
+    if result.rows_affected() > 0 {
+        info!(
+            admin_user_id = %authed.id,
+            event_id = %id,
+            event_type = %event.event_type,
+            "Security event acknowledged"
+        );
+    }
+
+    Ok(Json(event))
+}
+
+async fn signing_keys(state: &AppState) -> Result<Vec<SigningKeyInfo>, ApiError> {
+    sqlx::query_as::<_, SigningKeyInfo>(
+        r#"
+        SELECT kid, algorithm, status, created_at, retire_at, retired_at
+        FROM jwt_signing_keys
+        ORDER BY created_at DESC
+        "#,
+    )
+    .fetch_all(&state.db)
+    .await
+    .map_err(ApiError::DatabaseError)
+}
+
+/// GET /api/admin/signing-keys - Session token signing keys, newest first
+pub async fn list_signing_keys(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+) -> Result<Json<Vec<SigningKeyInfo>>, ApiError> {
+    let state = state_lock.read().await.clone();
+    authed.require_admin("Security events")?;
+
+    Ok(Json(signing_keys(&state).await?))
+}
+
+/// POST /api/admin/signing-keys/rotate - Start signing with a new key now
+///
+/// Sessions signed with the previous key stay valid until they expire.
+pub async fn rotate_signing_key(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+) -> Result<Json<Vec<SigningKeyInfo>>, ApiError> {
+    let state = state_lock.read().await.clone();
+    authed.require_admin("Security events")?;
+
+    let kid = keys::rotate(&state.db, &state.jwt_keys).await.map_err(|e| {
+        error!(error = %e, "JWT signing key rotation failed");
+        ApiError::InternalServer("Signing key rotation failed".to_string())
+    })?;
+    info!(admin_user_id = %authed.id, kid = %kid, "JWT signing key rotated by admin");
+
+    Ok(Json(signing_keys(&state).await?))
+}
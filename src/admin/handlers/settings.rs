@@ -1,13 +1,22 @@
 // src/admin/handlers/settings.rs
 
-use axum::{extract::Extension, Json};
+use axum::{
+    extract::{Extension, Path},
+    Json,
+};
+use std::future::Future;
 use std::sync::Arc;
+use std::time::Instant;
 use tokio::sync::RwLock;
 use tracing::{debug, error, info, warn};
 
-use crate::admin::models::{SystemSetting, TestConnectionRequest, UpdateSystemSettingsRequestV2};
+use crate::admin::models::{
+    DiagnosticCheck, IntegrationDiagnostics, SystemSetting, TestConnectionRequest,
+    UpdateSystemSettingsRequestV2,
+};
 use crate::auth::AuthedUser;
 use crate::common::{ApiError, AppState};
+use crate::services::settings::is_sensitive_key;
 
 /// GET /api/admin/settings - Get all system settings
 pub async fn get_system_settings(
@@ -92,15 +101,6 @@ pub async fn update_system_settings(
     let mut updated_count = 0;
     let mut errors = Vec::new();
 
-    let sensitive_keys = vec![
-        "openai_api_key",
-        "aws_secret_access_key",
-        "google_client_secret",
-        "google_refresh_token",
-        "google_access_token",
-        "monitoring_sentry_dsn",
-    ];
-
     for (key, setting_update) in request.settings.iter() {
         if !key
             .chars()
@@ -112,7 +112,7 @@ pub async fn update_system_settings(
 
         let should_encrypt = setting_update
             .encrypt
-            .unwrap_or_else(|| sensitive_keys.contains(&key.as_str()));
+            .unwrap_or_else(|| is_sensitive_key(key));
 
         let result = state
             .settings_service
@@ -374,6 +374,244 @@ pub async fn test_service_connection(
     Ok(Json(result))
 }
 
+/// POST /api/admin/settings/test/:integration - Run live diagnostics against an integration
+///
+/// Supported integrations: `aws_s3` (HeadBucket), `aws_ses` (send-to-self),
+/// `openai` (model list) and `google` (token check). Checks run in order and
+/// later checks are skipped once one fails.
+pub async fn test_integration(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Path(integration): Path<String>,
+) -> Result<Json<IntegrationDiagnostics>, ApiError> {
+    let state = state_lock.read().await.clone();
+
+    if !authed.is_admin {
+        warn!(
+            user_id = %authed.id,
+            "Integration test denied: admin privileges required"
+        );
+        return Err(ApiError::Forbidden("Admin privileges required".to_string()));
+    }
+
+    info!(
+        admin_user_id = %authed.id,
+        integration = %integration,
+        "Running integration diagnostics"
+    );
+
+    let mut checks = Vec::new();
+
+    match integration.as_str() {
+        "aws_s3" => {
+            let aws = state.aws_service.clone();
+            checks.push(
+                run_check("configuration", async {
+                    let config = aws.get_config().await.map_err(|e| e.to_string())?;
+                    if config.s3_bucket_name.is_empty() {
+                        return Err("S3 bucket name not configured".to_string());
+                    }
+                    Ok((
+                        "AWS credentials and bucket are configured".to_string(),
+                        Some(serde_json::json!({
+                            "region": config.region,
+                            "bucket": config.s3_bucket_name,
+                        })),
+                    ))
+                })
+                .await,
+            );
+            if all_passed(&checks) {
+                checks.push(
+                    run_check("head_bucket", async {
+                        let bucket = aws.head_bucket().await.map_err(|e| e.to_string())?;
+                        Ok((format!("Bucket {} is reachable", bucket), None))
+                    })
+                    .await,
+                );
+            }
+        }
+        "aws_ses" => {
+            let aws = state.aws_service.clone();
+            checks.push(
+                run_check("configuration", async {
+                    let config = aws.get_config().await.map_err(|e| e.to_string())?;
+                    if config.ses_from_email.is_empty() {
+                        return Err("SES from email not configured".to_string());
+                    }
+                    Ok((
+                        "AWS credentials and sender address are configured".to_string(),
+                        Some(serde_json::json!({
+                            "region": config.ses_region,
+                            "from_email": config.ses_from_email,
+                        })),
+                    ))
+                })
+                .await,
+            );
+            if all_passed(&checks) {
+                checks.push(
+                    run_check("send_to_self", async {
+                        let recipient = aws.send_test_email().await.map_err(|e| e.to_string())?;
+                        Ok((format!("Test email sent to {}", recipient), None))
+                    })
+                    .await,
+                );
+            }
+        }
+        "openai" => {
+            let openai = state.openai_service.clone();
+            let mut configured_models = Vec::new();
+            checks.push(
+                run_check("configuration", async {
+                    let config = openai.get_config().await.map_err(|e| e.to_string())?;
+                    configured_models = vec![
+                        config.models.resume_scanning,
+                        config.models.email_generation,
+                        config.models.message_responses,
+                        config.models.job_description_generation,
+                        config.models.image_generation,
+                    ];
+                    configured_models.sort();
+                    configured_models.dedup();
+                    Ok((
+                        "API key is configured".to_string(),
+                        Some(serde_json::json!({ "base_url": config.base_url })),
+                    ))
+                })
+                .await,
+            );
+            if all_passed(&checks) {
+                let mut available = Vec::new();
+                checks.push(
+                    run_check("list_models", async {
+                        available = openai.list_models().await.map_err(|e| e.to_string())?;
+                        Ok((
+                            format!("{} models available to this API key", available.len()),
+                            None,
+                        ))
+                    })
+                    .await,
+                );
+                if all_passed(&checks) {
+                    checks.push(
+                        run_check("configured_models", async {
+                            let missing: Vec<&String> = configured_models
+                                .iter()
+                                .filter(|m| !available.contains(m))
+                                .collect();
+                            if missing.is_empty() {
+                                Ok(("All configured models are available".to_string(), None))
+                            } else {
+                                Err(format!(
+                                    "Configured models not available: {}",
+                                    missing
+                                        .iter()
+                                        .map(|m| m.as_str())
+                                        .collect::<Vec<_>>()
+                                        .join(", ")
+                                ))
+                            }
+                        })
+                        .await,
+                    );
+                }
+            }
+        }
+        "google" => {
+            let google = state.google_service.clone();
+            checks.push(
+                run_check("configuration", async {
+                    let config = google.get_config().await.map_err(|e| e.to_string())?;
+                    if config.refresh_token.is_none() {
+                        return Err(
+                            "No refresh token stored. Connect a Google account first.".to_string()
+                        );
+                    }
+                    Ok((
+                        "OAuth client and refresh token are configured".to_string(),
+                        Some(serde_json::json!({
+                            "connected_account": config.connected_account,
+                        })),
+                    ))
+                })
+                .await,
+            );
+            if all_passed(&checks) {
+                checks.push(
+                    run_check("token_check", async {
+                        let info = google.check_token().await.map_err(|e| e.to_string())?;
+                        Ok((
+                            "Access token is valid".to_string(),
+                            serde_json::to_value(info).ok(),
+                        ))
+                    })
+                    .await,
+                );
+            }
+        }
+        _ => {
+            warn!(
+                admin_user_id = %authed.id,
+                integration = %integration,
+                "Unknown integration for diagnostics"
+            );
+            return Err(ApiError::BadRequest(format!(
+                "Unknown integration: {}. Expected one of: aws_s3, aws_ses, openai, google",
+                integration
+            )));
+        }
+    }
+
+    let success = all_passed(&checks);
+
+    info!(
+        admin_user_id = %authed.id,
+        integration = %integration,
+        success = success,
+        check_count = checks.len(),
+        "Integration diagnostics completed"
+    );
+
+    Ok(Json(IntegrationDiagnostics {
+        integration,
+        success,
+        checks,
+        tested_at: chrono::Utc::now().to_rfc3339(),
+    }))
+}
+
+/// Time a single diagnostic step and convert its outcome into a `DiagnosticCheck`
+async fn run_check<F>(name: &str, check: F) -> DiagnosticCheck
+where
+    F: Future<Output = Result<(String, Option<serde_json::Value>), String>>,
+{
+    let started = Instant::now();
+    let outcome = check.await;
+    let duration_ms = started.elapsed().as_millis();
+
+    match outcome {
+        Ok((message, details)) => DiagnosticCheck {
+            name: name.to_string(),
+            success: true,
+            message,
+            duration_ms,
+            details,
+        },
+        Err(message) => DiagnosticCheck {
+            name: name.to_string(),
+            success: false,
+            message,
+            duration_ms,
+            details: None,
+        },
+    }
+}
+
+fn all_passed(checks: &[DiagnosticCheck]) -> bool {
+    checks.iter().all(|c| c.success)
+}
+
 /// GET /api/admin/settings/google/auth-url - Get Google OAuth authorization URL
 pub async fn get_google_auth_url(
     Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
@@ -395,8 +633,9 @@ pub async fn get_google_auth_url(
     );
 
     // Get the redirect URI - use the admin callback endpoint
-    let redirect_uri = std::env::var("GOOGLE_OAUTH_REDIRECT_URI")
-        .unwrap_or_else(|_| "http://localhost:8080/api/admin/settings/google/callback".to_string());
+    let redirect_uri = state.config.google_oauth_redirect_uri.clone().unwrap_or_else(|| {
+        format!("{}/api/admin/settings/google/callback", state.config.backend_url)
+    });
 
     let auth_url = state
         .google_service
@@ -465,8 +704,9 @@ pub async fn google_oauth_callback(
     info!("Received Google OAuth callback with authorization code");
 
     // Get the redirect URI (must match the one used in authorization)
-    let redirect_uri = std::env::var("GOOGLE_OAUTH_REDIRECT_URI")
-        .unwrap_or_else(|_| "http://localhost:8080/api/admin/settings/google/callback".to_string());
+    let redirect_uri = state.config.google_oauth_redirect_uri.clone().unwrap_or_else(|| {
+        format!("{}/api/admin/settings/google/callback", state.config.backend_url)
+    });
 
     // Exchange code for tokens
     match state.google_service.exchange_code(code, &redirect_uri).await {
@@ -0,0 +1,250 @@
+// src/admin/handlers/sla.rs
+//! Hiring SLA targets and the stale application list
+
+use axum::{
+    extract::{Extension, Path, Query},
+    http::StatusCode,
+    Json,
+};
+use chrono::Utc;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::{error, info};
+
+use crate::admin::models::{
+    CreateSlaPolicyRequest, MessageResponse, SlaPolicy, SlaPolicyQuery, StaleApplication,
+    StaleApplicationQuery, UpdateSlaPolicyRequest,
+};
+use crate::auth::AuthedUser;
+use crate::common::{generate_sla_policy_id, ApiError, AppState};
+use crate::services::sla;
+
+async fn fetch_policy(state: &AppState, id: &str) -> Result<SlaPolicy, ApiError> {
+    sqlx::query_as::<_, SlaPolicy>("SELECT * FROM sla_policies WHERE id = ?")
+        .bind(id)
+        .fetch_optional(&state.db)
+        .await
+        .map_err(ApiError::DatabaseError)?
+        .ok_or_else(|| ApiError::NotFound("SLA policy not found".to_string()))
+}
+
+fn normalize_notify_email(value: Option<&str>) -> Option<String> {
+    value
+        .map(str::trim)
+        .filter(|e| !e.is_empty())
+        .map(str::to_string)
+}
+
+/// GET /api/admin/sla-policies - Global targets and, with `job_id`, that job's overrides
+pub async fn list_sla_policies(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Query(query): Query<SlaPolicyQuery>,
+) -> Result<Json<Vec<SlaPolicy>>, ApiError> {
+    let state = state_lock.read().await.clone();
+    authed.require_admin("SLA")?;
+
+    let policies = sqlx::query_as::<_, SlaPolicy>(
+        r#"
+        SELECT * FROM sla_policies
+        WHERE job_id IS NULL OR job_id = ?
+        ORDER BY job_id IS NOT NULL, stage
+        "#,
+    )
+    .bind(&query.job_id)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|e| {
+        error!(error = %e, "Database error listing SLA policies");
+        ApiError::DatabaseError(e)
+    })?;
+
+    Ok(Json(policies))
+}
+
+/// POST /api/admin/sla-policies - Set a stage target globally or for one job
+pub async fn create_sla_policy(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Json(request): Json<CreateSlaPolicyRequest>,
+) -> Result<(StatusCode, Json<SlaPolicy>), ApiError> {
+    let state = state_lock.read().await.clone();
+    authed.require_admin("SLA")?;
+
+    let stage = request.stage.trim().to_lowercase();
+    sla::validate_policy(&stage, request.target_business_days)?;
+
+    if let Some(job_id) = &request.job_id {
+        let job_exists: bool = sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM jobs WHERE id = ?)")
+            .bind(job_id)
+            .fetch_one(&state.db)
+            .await
+            .map_err(ApiError::DatabaseError)?;
+        if !job_exists {
+            return Err(ApiError::NotFound("Job not found".to_string()));
+        }
+    }
+
+    let existing: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM sla_policies WHERE COALESCE(job_id, '') = COALESCE(?, '') AND stage = ?",
+    )
+    .bind(&request.job_id)
+    .bind(&stage)
+    .fetch_one(&state.db)
+    .await
+    .map_err(ApiError::DatabaseError)?;
+    if existing > 0 {
+        return Err(ApiError::BadRequest(format!(
+            "An SLA target for {} already exists for this scope",
+            stage
+        )));
+    }
+
+    let id = generate_sla_policy_id();
+    sqlx::query(
+        r#"
+        INSERT INTO sla_policies (id, job_id, stage, target_business_days, notify_email, created_by)
+        VALUES (?, ?, ?, ?, ?, ?)
+        "#,
+    )
+    .bind(&id)
+    .bind(&request.job_id)
+    .bind(&stage)
+    .bind(request.target_business_days)
+    .bind(normalize_notify_email(request.notify_email.as_deref()))
+    .bind(&authed.id)
+    .execute(&state.db)
+    .await
+    .map_err(|e| {
+        error!(error = %e, "Database error creating SLA policy");
+        ApiError::DatabaseError(e)
+    })?;
+
+    info!(
+        admin_user_id = %authed.id,
+        policy_id = %id,
+        job_id = ?request.job_id,
+        stage = %stage,
+        target_business_days = request.target_business_days,
+        "SLA policy created"
+    );
+
+    Ok((StatusCode::CREATED, Json(fetch_policy(&state, &id).await?)))
+}
+
+/// PUT /api/admin/sla-policies/:id - Change a target or its recruiter notification
+pub async fn update_sla_policy(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Path(id): Path<String>,
+    Json(request): Json<UpdateSlaPolicyRequest>,
+) -> Result<Json<SlaPolicy>, ApiError> {
+    let state = state_lock.read().await.clone();
+    authed.require_admin("SLA")?;
+
+    let policy = fetch_policy(&state, &id).await?;
+    let target_business_days = request
+        .target_business_days
+        .unwrap_or(policy.target_business_days);
+    sla::validate_policy(&policy.stage, target_business_days)?;
+    let notify_email = match request.notify_email.as_deref() {
+        Some(value) => normalize_notify_email(Some(value)),
+        None => policy.notify_email,
+    };
+
+    sqlx::query(
+        "UPDATE sla_policies SET target_business_days = ?, notify_email = ?, updated_at = datetime('now') WHERE id = ?",
+    )
+    .bind(target_business_days)
+    .bind(&notify_email)
+    .bind(&id)
+    .execute(&state.db)
+    .await
+    .map_err(|e| {
+        error!(error = %e, policy_id = %id, "Database error updating SLA policy");
+        ApiError::DatabaseError(e)
+    })?;
+
+    info!(
+        admin_user_id = %authed.id,
+        policy_id = %id,
+        previous_target = policy.target_business_days,
+        target_business_days = target_business_days,
+        "SLA policy updated"
+    );
+
+    Ok(Json(fetch_policy(&state, &id).await?))
+}
+
+/// DELETE /api/admin/sla-policies/:id - Remove a target
+pub async fn delete_sla_policy(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Path(id): Path<String>,
+) -> Result<Json<MessageResponse>, ApiError> {
+    let state = state_lock.read().await.clone();
+    authed.require_admin("SLA")?;
+
+    let result = sqlx::query("DELETE FROM sla_policies WHERE id = ?")
+        .bind(&id)
+        .execute(&state.db)
+        .await
+        .map_err(ApiError::DatabaseError)?;
+
+    if result.rows_affected() == 0 {
+        return Err(ApiError::NotFound("SLA policy not found".to_string()));
+    }
+
+    info!(admin_user_id = %authed.id, policy_id = %id, "SLA policy deleted");
+
+    Ok(Json(MessageResponse {
+        message: "SLA policy deleted".to_string(),
+    }))
+}
+
+/// GET /api/admin/applications/stale - Applications flagged as past their SLA, most overdue first
+pub async fn list_stale_applications(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Query(query): Query<StaleApplicationQuery>,
+) -> Result<Json<Vec<StaleApplication>>, ApiError> {
+    let state = state_lock.read().await.clone();
+    authed.require_admin("SLA")?;
+
+    let mut stale = sqlx::query_as::<_, StaleApplication>(
+        r#"
+        SELECT b.id AS breach_id, b.application_id, b.job_id, j.title AS job_title,
+               u.name AS candidate_name, u.email AS candidate_email,
+               b.stage, b.stage_entered_at, b.due_at, b.detected_at, b.notified_at
+        FROM application_sla_breaches b
+        JOIN applications a ON a.id = b.application_id
+        LEFT JOIN jobs j ON j.id = b.job_id
+        LEFT JOIN users u ON u.id = a.user_id
+        WHERE b.resolved_at IS NULL
+          AND (? IS NULL OR b.job_id = ?)
+          AND (? IS NULL OR b.stage = ?)
+        ORDER BY b.due_at
+        LIMIT ?
+        "#,
+    )
+    .bind(&query.job_id)
+    .bind(&query.job_id)
+    .bind(&query.stage)
+    .bind(&query.stage)
+    .bind(query.limit.unwrap_or(100).clamp(1, 500))
+    .fetch_all(&state.db)
+    .await
+    .map_err(|e| {
+        error!(error = %e, "Database error listing stale applications");
+        ApiError::DatabaseError(e)
+    })?;
+
+    let now = Utc::now().naive_utc();
+    for item in &mut stale {
+        item.business_days_overdue = sla::parse_timestamp(&item.due_at)
+            .map(|due| sla::business_days_between(due, now))
+            .unwrap_or(0);
+    }
+
+    Ok(Json(stale))
+}
@@ -0,0 +1,60 @@
+// src/admin/handlers/sms.rs
+//! SMS delivery log
+
+use axum::{
+    extract::{Extension, Query},
+    Json,
+};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+use crate::admin::models::{SmsDelivery, SmsDeliveryQuery};
+use crate::auth::AuthedUser;
+use crate::common::{ApiError, AppState};
+use crate::services::sms::{self, SMS_KINDS, SMS_STATUSES};
+
+const DEFAULT_LIMIT: i64 = 100;
+const MAX_LIMIT: i64 = 500;
+
+/// GET /api/admin/sms/deliveries?user_id=&kind=&status=&limit= - Texts sent, failed and
+/// skipped, newest first
+pub async fn list_sms_deliveries(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Query(query): Query<SmsDeliveryQuery>,
+) -> Result<Json<Vec<SmsDelivery>>, ApiError> {
+    let state = state_lock.read().await.clone();
+    authed.require_admin("SMS delivery log")?;
+
+    if let Some(kind) = query.kind.as_deref() {
+        if !SMS_KINDS.contains(&kind) {
+            return Err(ApiError::BadRequest(format!(
+                "Invalid kind '{}'; expected one of {}",
+                kind,
+                SMS_KINDS.join(", ")
+            )));
+        }
+    }
+    if let Some(status) = query.status.as_deref() {
+        if !SMS_STATUSES.contains(&status) {
+            return Err(ApiError::BadRequest(format!(
+                "Invalid status '{}'; expected one of {}",
+                status,
+                SMS_STATUSES.join(", ")
+            )));
+        }
+    }
+    let limit = query.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+
+    let deliveries = sms::list_deliveries(
+        &state.db,
+        query.user_id.as_deref(),
+        query.kind.as_deref(),
+        query.status.as_deref(),
+        limit,
+    )
+    .await
+    .map_err(ApiError::DatabaseError)?;
+
+    Ok(Json(deliveries))
+}
@@ -0,0 +1,54 @@
+// src/admin/handlers/storage_usage.rs
+//! Storage consumption per user and company, against the configured quotas
+
+use axum::{
+    extract::{Extension, Path, Query},
+    Json,
+};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+use crate::admin::models::StorageUsageQuery;
+use crate::auth::AuthedUser;
+use crate::common::{ApiError, AppState};
+use crate::services::storage_usage::{self, StorageConsumer, StorageUsage};
+
+const DEFAULT_LIMIT: i64 = 20;
+const MAX_LIMIT: i64 = 200;
+
+/// GET /api/admin/storage/usage?owner_type=&limit= - Largest consumers, biggest first
+pub async fn list_storage_consumers(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Query(query): Query<StorageUsageQuery>,
+) -> Result<Json<Vec<StorageConsumer>>, ApiError> {
+    let state = state_lock.read().await.clone();
+    authed.require_admin("Storage usage")?;
+
+    let owner_type = query
+        .owner_type
+        .unwrap_or_else(|| storage_usage::OWNER_USER.to_string());
+    storage_usage::validate_owner_type(&owner_type)?;
+    let limit = query.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+
+    let consumers = storage_usage::top_consumers(&state.db, &owner_type, limit)
+        .await
+        .map_err(ApiError::DatabaseError)?;
+
+    Ok(Json(consumers))
+}
+
+/// GET /api/admin/storage/usage/:owner_type/:owner_id - One user's or company's usage by category
+pub async fn get_owner_storage_usage(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Path((owner_type, owner_id)): Path<(String, String)>,
+) -> Result<Json<StorageUsage>, ApiError> {
+    let state = state_lock.read().await.clone();
+    authed.require_admin("Storage usage")?;
+    storage_usage::validate_owner_type(&owner_type)?;
+
+    Ok(Json(
+        storage_usage::usage(&state, &owner_type, &owner_id).await?,
+    ))
+}
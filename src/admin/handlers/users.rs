@@ -1,7 +1,7 @@
 // src/admin/handlers/users.rs
 
 use axum::{
-    extract::{Extension, Path},
+    extract::{Extension, Path, Query},
     http::StatusCode,
     Json,
 };
@@ -10,11 +10,26 @@ use tokio::sync::RwLock;
 use tracing::{error, info, warn};
 
 use crate::admin::models::{
-    AdminUser, CandidateProfile, CreateAdminUserRequest, UpdateAdminUserRequest,
+    AdminUser, CandidateListQuery, CandidateProfile, CreateAdminUserRequest,
+    UpdateAdminUserRequest,
 };
 use crate::auth::{AuthedUser, User};
+use crate::candidates::experience_years;
 use crate::common::{generate_user_id, ApiError, AppState};
 use crate::profile::models::Profile;
+use crate::services::consent;
+
+/// Latest resume's ID, filename, stored experience years and parsed content
+type ResumeInfo = (String, String, Option<f64>, Option<String>);
+
+/// Stored experience years, or computed from the parsed resume when it predates tracking
+fn resume_experience_years(resume: Option<&ResumeInfo>) -> Option<f64> {
+    let (_, _, stored, parsed_json) = resume?;
+    stored.or_else(|| {
+        experience_years::summarize(parsed_json.as_deref(), None, chrono::Utc::now().date_naive())
+            .map(|summary| summary.total_years)
+    })
+}
 
 /// GET /api/admin/users - Get admin user list
 pub async fn get_admin_users(
@@ -487,10 +502,11 @@ pub async fn toggle_admin_user_status(
     Ok(Json(updated_admin))
 }
 
-/// GET /api/admin/candidates - Get candidate list
+/// GET /api/admin/candidates?min_years= - Get candidate list
 pub async fn get_admin_candidates(
     Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
     authed: AuthedUser,
+    Query(params): Query<CandidateListQuery>,
 ) -> Result<Json<Vec<CandidateProfile>>, ApiError> {
     let state = state_lock.read().await.clone();
 
@@ -508,11 +524,12 @@ pub async fn get_admin_candidates(
     );
 
     let candidates_query = r#"
-        SELECT DISTINCT u.id, u.email, u.name, u.avatar, u.provider, u.provider_id, u.created_at
+        SELECT DISTINCT u.id, u.email, u.name, u.avatar, u.provider, u.provider_id, u.created_at,
+               u.merged_into
         FROM users u
         LEFT JOIN profiles p ON u.id = p.user_id
         LEFT JOIN applications a ON u.id = a.user_id
-        WHERE u.id != ?
+        WHERE u.id != ? AND u.merged_into IS NULL
         ORDER BY u.created_at DESC
         "#;
 
@@ -529,8 +546,39 @@ pub async fn get_admin_candidates(
         })?;
 
     let mut candidates = Vec::new();
+    let policy_version = consent::current_policy_version(&state.settings_service).await;
 
     for user in users {
+        // Get the latest resume for this candidate
+        let resume_info: Option<ResumeInfo> = sqlx::query_as(
+            "SELECT id, filename, experience_years, parsed_json FROM resumes WHERE user_id = ? AND (deleted_at IS NULL OR deleted_at = '') ORDER BY submitted_at DESC LIMIT 1"
+        )
+        .bind(&user.id)
+        .fetch_optional(&state.db)
+        .await
+        .map_err(|e| {
+            error!(
+                error = %e,
+                user_id = %user.id,
+                "Database error fetching resume info"
+            );
+            ApiError::DatabaseError(e)
+        })?;
+
+        let experience_years = resume_experience_years(resume_info.as_ref());
+        if params
+            .min_years
+            .is_some_and(|min| !experience_years.is_some_and(|years| years >= min))
+        {
+            continue;
+        }
+
+        let (resume_status, resume_id, resume_filename) = if let Some((id, filename, _, _)) = resume_info {
+            ("uploaded".to_string(), Some(id), Some(filename))
+        } else {
+            ("not_uploaded".to_string(), None, None)
+        };
+
         let profile = sqlx::query_as::<_, Profile>("SELECT * FROM profiles WHERE user_id = ?")
             .bind(&user.id)
             .fetch_optional(&state.db)
@@ -589,28 +637,6 @@ pub async fn get_admin_candidates(
             ApiError::DatabaseError(e)
         })?;
 
-        // Get the latest resume for this candidate
-        let resume_info: Option<(String, String)> = sqlx::query_as(
-            "SELECT id, filename FROM resumes WHERE user_id = ? AND (deleted_at IS NULL OR deleted_at = '') ORDER BY submitted_at DESC LIMIT 1"
-        )
-        .bind(&user.id)
-        .fetch_optional(&state.db)
-        .await
-        .map_err(|e| {
-            error!(
-                error = %e,
-                user_id = %user.id,
-                "Database error fetching resume info"
-            );
-            ApiError::DatabaseError(e)
-        })?;
-
-        let (resume_status, resume_id, resume_filename) = if let Some((id, filename)) = resume_info {
-            ("uploaded".to_string(), Some(id), Some(filename))
-        } else {
-            ("not_uploaded".to_string(), None, None)
-        };
-
         let last_activity = sqlx::query_scalar::<_, Option<String>>(
             r#"
             SELECT MAX(datetime) as last_activity FROM (
@@ -649,6 +675,17 @@ pub async fn get_admin_candidates(
             0.0
         };
 
+        let consent = consent::consent_status(&state.db, &user.id, policy_version.clone())
+            .await
+            .map_err(|e| {
+                error!(
+                    error = %e,
+                    user_id = %user.id,
+                    "Database error fetching candidate consent"
+                );
+                ApiError::DatabaseError(e)
+            })?;
+
         let candidate = CandidateProfile {
             user,
             profile,
@@ -658,9 +695,11 @@ pub async fn get_admin_candidates(
             resume_status,
             resume_id,
             resume_filename,
+            experience_years,
             last_activity,
             total_applications,
             application_success_rate,
+            consent,
         };
 
         candidates.push(candidate);
@@ -777,8 +816,8 @@ pub async fn get_admin_candidate_details(
     })?;
 
     // Get the latest resume for this candidate
-    let resume_info: Option<(String, String)> = sqlx::query_as(
-        "SELECT id, filename FROM resumes WHERE user_id = ? AND (deleted_at IS NULL OR deleted_at = '') ORDER BY submitted_at DESC LIMIT 1"
+    let resume_info: Option<ResumeInfo> = sqlx::query_as(
+        "SELECT id, filename, experience_years, parsed_json FROM resumes WHERE user_id = ? AND (deleted_at IS NULL OR deleted_at = '') ORDER BY submitted_at DESC LIMIT 1"
     )
     .bind(&candidate_id)
     .fetch_optional(&state.db)
@@ -792,7 +831,8 @@ pub async fn get_admin_candidate_details(
         ApiError::DatabaseError(e)
     })?;
 
-    let (resume_status, resume_id, resume_filename) = if let Some((id, filename)) = resume_info {
+    let experience_years = resume_experience_years(resume_info.as_ref());
+    let (resume_status, resume_id, resume_filename) = if let Some((id, filename, _, _)) = resume_info {
         ("uploaded".to_string(), Some(id), Some(filename))
     } else {
         ("not_uploaded".to_string(), None, None)
@@ -836,6 +876,18 @@ pub async fn get_admin_candidate_details(
         0.0
     };
 
+    let policy_version = consent::current_policy_version(&state.settings_service).await;
+    let consent = consent::consent_status(&state.db, &candidate_id, policy_version)
+        .await
+        .map_err(|e| {
+            error!(
+                error = %e,
+                candidate_id = %candidate_id,
+                "Database error fetching candidate consent"
+            );
+            ApiError::DatabaseError(e)
+        })?;
+
     let candidate = CandidateProfile {
         user,
         profile,
@@ -845,9 +897,11 @@ pub async fn get_admin_candidate_details(
         resume_status,
         resume_id,
         resume_filename,
+        experience_years,
         last_activity,
         total_applications,
         application_success_rate,
+        consent,
     };
 
     info!(
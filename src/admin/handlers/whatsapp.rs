@@ -0,0 +1,99 @@
+// src/admin/handlers/whatsapp.rs
+//! WhatsApp template configuration and message log
+
+use axum::{
+    extract::{Extension, Query},
+    Json,
+};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::info;
+
+use crate::admin::models::{WhatsAppMessage, WhatsAppMessageQuery};
+use crate::auth::AuthedUser;
+use crate::common::{ApiError, AppState};
+use crate::services::whatsapp::{self, WhatsAppTemplates, WHATSAPP_EVENTS, WHATSAPP_STATUSES};
+
+const DEFAULT_LIMIT: i64 = 100;
+const MAX_LIMIT: i64 = 500;
+
+/// GET /api/admin/whatsapp/templates - Template used for each event
+pub async fn get_whatsapp_templates(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+) -> Result<Json<WhatsAppTemplates>, ApiError> {
+    let state = state_lock.read().await.clone();
+    authed.require_admin("WhatsApp admin")?;
+
+    Ok(Json(state.whatsapp_service.templates().await))
+}
+
+/// PUT /api/admin/whatsapp/templates - Replace the event templates
+///
+/// Events left out are not sent.
+pub async fn update_whatsapp_templates(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Json(templates): Json<WhatsAppTemplates>,
+) -> Result<Json<WhatsAppTemplates>, ApiError> {
+    let state = state_lock.read().await.clone();
+    authed.require_admin("WhatsApp admin")?;
+
+    whatsapp::validate_templates(&templates).map_err(ApiError::ValidationError)?;
+    state
+        .whatsapp_service
+        .save_templates(&templates, &authed.id)
+        .await
+        .map_err(ApiError::InternalServer)?;
+
+    info!(
+        admin_id = %authed.id,
+        events = templates.len(),
+        "WhatsApp templates updated"
+    );
+
+    Ok(Json(templates))
+}
+
+/// GET /api/admin/whatsapp/messages?user_id=&event=&status=&limit= - Messages sent and
+/// failed, newest first
+pub async fn list_whatsapp_messages(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Query(query): Query<WhatsAppMessageQuery>,
+) -> Result<Json<Vec<WhatsAppMessage>>, ApiError> {
+    let state = state_lock.read().await.clone();
+    authed.require_admin("WhatsApp admin")?;
+
+    if let Some(event) = query.event.as_deref() {
+        if !WHATSAPP_EVENTS.contains(&event) {
+            return Err(ApiError::BadRequest(format!(
+                "Invalid event '{}'; expected one of {}",
+                event,
+                WHATSAPP_EVENTS.join(", ")
+            )));
+        }
+    }
+    if let Some(status) = query.status.as_deref() {
+        if !WHATSAPP_STATUSES.contains(&status) {
+            return Err(ApiError::BadRequest(format!(
+                "Invalid status '{}'; expected one of {}",
+                status,
+                WHATSAPP_STATUSES.join(", ")
+            )));
+        }
+    }
+    let limit = query.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+
+    let messages = whatsapp::list_messages(
+        &state.db,
+        query.user_id.as_deref(),
+        query.event.as_deref(),
+        query.status.as_deref(),
+        limit,
+    )
+    .await
+    .map_err(ApiError::DatabaseError)?;
+
+    Ok(Json(messages))
+}
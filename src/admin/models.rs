@@ -4,6 +4,10 @@ use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 use std::collections::HashMap;
 
+use crate::services::pii::Sealed;
+use crate::services::reports::ReportDefinition;
+use crate::services::resilience::DependencyHealth;
+
 // Dashboard models
 #[derive(Debug, Serialize)]
 pub struct DashboardMetrics {
@@ -57,6 +61,8 @@ pub struct SystemHealth {
     pub overall_health: String,
     pub last_check: String,
     pub details: HashMap<String, String>,
+    /// Circuit breaker state of each external service
+    pub dependencies: Vec<DependencyHealth>,
 }
 
 #[derive(Debug, Serialize)]
@@ -65,6 +71,7 @@ pub struct ActivityLog {
     pub activity_type: String,
     pub description: String,
     pub user_id: Option<String>,
+    #[serde(serialize_with = "crate::services::masking::email_opt")]
     pub user_email: Option<String>,
     pub metadata: Option<String>,
     pub timestamp: String,
@@ -121,6 +128,26 @@ pub struct TestConnectionRequest {
     pub credentials: Option<HashMap<String, String>>,
 }
 
+/// Outcome of a single step in an integration test
+#[derive(Debug, Serialize)]
+pub struct DiagnosticCheck {
+    pub name: String,
+    pub success: bool,
+    pub message: String,
+    pub duration_ms: u128,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub details: Option<serde_json::Value>,
+}
+
+/// Structured diagnostics returned by POST /api/admin/settings/test/:integration
+#[derive(Debug, Serialize)]
+pub struct IntegrationDiagnostics {
+    pub integration: String,
+    pub success: bool,
+    pub checks: Vec<DiagnosticCheck>,
+    pub tested_at: String,
+}
+
 // File management models
 #[derive(Deserialize)]
 pub struct ListFilesQuery {
@@ -160,6 +187,12 @@ pub struct MessageResponse {
     pub message: String,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct CandidateListQuery {
+    /// Only candidates whose latest resume shows at least this many years of experience
+    pub min_years: Option<f64>,
+}
+
 // Candidate profile model
 #[derive(Debug, Serialize)]
 pub struct CandidateProfile {
@@ -171,7 +204,831 @@ pub struct CandidateProfile {
     pub resume_status: String,
     pub resume_id: Option<String>,
     pub resume_filename: Option<String>,
+    /// Total years of experience on the latest resume
+    pub experience_years: Option<f64>,
     pub last_activity: Option<String>,
     pub total_applications: i64,
     pub application_success_rate: f64,
+    pub consent: crate::auth::models::ConsentStatus,
+}
+
+// Compensation models
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct CompensationBand {
+    pub id: String,
+    /// None for the default band that applies to every company
+    pub company_id: Option<String>,
+    pub level: String,
+    pub currency: String,
+    pub min_salary: f64,
+    pub max_salary: f64,
+    pub created_by: Option<String>,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CompensationBandQuery {
+    pub company_id: Option<String>,
+    pub level: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateCompensationBandRequest {
+    pub company_id: Option<String>,
+    pub level: String,
+    pub currency: Option<String>,
+    pub min_salary: f64,
+    pub max_salary: f64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateCompensationBandRequest {
+    pub currency: Option<String>,
+    pub min_salary: Option<f64>,
+    pub max_salary: Option<f64>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ValidateOfferSalaryRequest {
+    pub job_id: String,
+    pub candidate_id: Option<String>,
+    pub salary: f64,
+    /// Required when the salary is above the band maximum
+    pub override_justification: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct OfferSalaryCheck {
+    /// None when no band covers the job's level
+    pub band: Option<CompensationBand>,
+    pub within_band: bool,
+    pub below_band: bool,
+    pub above_band: bool,
+    /// Audit record created for an approved above-band offer
+    pub override_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct CompensationOverride {
+    pub id: String,
+    pub band_id: Option<String>,
+    pub job_id: String,
+    pub candidate_id: Option<String>,
+    #[sqlx(try_from = "Sealed")]
+    #[serde(serialize_with = "crate::services::masking::salary")]
+    pub salary: f64,
+    pub band_min: f64,
+    pub band_max: f64,
+    pub justification: String,
+    pub approved_by: String,
+    pub created_at: Option<String>,
+}
+
+// Security monitoring models
+#[derive(Debug, Deserialize)]
+pub struct SecurityEventQuery {
+    pub severity: Option<String>,
+    /// Only unacknowledged events when false, only acknowledged when true
+    pub acknowledged: Option<bool>,
+    pub limit: Option<i64>,
+}
+
+// Content moderation models
+#[derive(Debug, Deserialize)]
+pub struct ModerationQueueQuery {
+    /// `pending` (default), `approved` or `removed`
+    pub status: Option<String>,
+    pub content_type: Option<String>,
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ReviewModerationRequest {
+    /// `approve` to publish the content, `remove` to take it down
+    pub decision: String,
+    pub notes: Option<String>,
+}
+
+// Job promotion (paid sponsorship) models
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct JobPromotion {
+    pub id: String,
+    pub job_id: String,
+    /// Where the job is promoted, e.g. `LinkedIn` or `Indeed`
+    pub platform: String,
+    /// Attribution tag matched against short link clicks (`/j/:code?src=`) and application sources
+    pub source: String,
+    pub currency: String,
+    pub budget: f64,
+    pub spend: f64,
+    /// `YYYY-MM-DD`, inclusive
+    pub start_date: String,
+    /// `YYYY-MM-DD`, inclusive; None while the promotion is open-ended
+    pub end_date: Option<String>,
+    pub notes: Option<String>,
+    pub created_by: Option<String>,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateJobPromotionRequest {
+    pub platform: String,
+    /// Defaults to the platform name as a tag (`LinkedIn Ads` -> `linkedin_ads`)
+    pub source: Option<String>,
+    pub currency: Option<String>,
+    pub budget: f64,
+    pub spend: Option<f64>,
+    pub start_date: String,
+    pub end_date: Option<String>,
+    pub notes: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateJobPromotionRequest {
+    pub platform: Option<String>,
+    pub source: Option<String>,
+    pub currency: Option<String>,
+    pub budget: Option<f64>,
+    pub spend: Option<f64>,
+    pub start_date: Option<String>,
+    pub end_date: Option<String>,
+    pub notes: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PromotionReportQuery {
+    /// Only promotions running on or after this date (`YYYY-MM-DD`)
+    pub from: Option<String>,
+    /// Only promotions running on or before this date (`YYYY-MM-DD`)
+    pub to: Option<String>,
+    pub job_id: Option<String>,
+}
+
+/// A promotion with the clicks and applications attributed to it during its run
+#[derive(Debug, Serialize)]
+pub struct PromotionPerformance {
+    #[serde(flatten)]
+    pub promotion: JobPromotion,
+    pub clicks: i64,
+    pub applications: i64,
+    pub cost_per_click: Option<f64>,
+    pub cost_per_application: Option<f64>,
+}
+
+/// Promotion totals for one attribution source and currency
+#[derive(Debug, Serialize)]
+pub struct ChannelPerformance {
+    pub source: String,
+    pub currency: String,
+    pub promotions: i64,
+    pub budget: f64,
+    pub spend: f64,
+    pub clicks: i64,
+    pub applications: i64,
+    pub cost_per_click: Option<f64>,
+    pub cost_per_application: Option<f64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PromotionReport {
+    pub promotions: Vec<PromotionPerformance>,
+    pub channels: Vec<ChannelPerformance>,
+}
+
+// Hiring SLA models
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct SlaPolicy {
+    pub id: String,
+    /// None for the global default that applies to every job without its own target
+    pub job_id: Option<String>,
+    /// Application status the target applies to, e.g. `submitted` for "review within N days"
+    pub stage: String,
+    pub target_business_days: i64,
+    /// Recruiter address(es), comma-separated, emailed when an application breaches this target
+    pub notify_email: Option<String>,
+    pub created_by: Option<String>,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SlaPolicyQuery {
+    pub job_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateSlaPolicyRequest {
+    pub job_id: Option<String>,
+    pub stage: String,
+    pub target_business_days: i64,
+    pub notify_email: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateSlaPolicyRequest {
+    pub target_business_days: Option<i64>,
+    /// An empty string stops notifications for this target
+    pub notify_email: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct StaleApplicationQuery {
+    pub job_id: Option<String>,
+    pub stage: Option<String>,
+    pub limit: Option<i64>,
+}
+
+/// An application that has sat in its stage past the SLA target
+#[derive(Debug, Serialize, FromRow)]
+pub struct StaleApplication {
+    pub breach_id: String,
+    pub application_id: String,
+    pub job_id: String,
+    pub job_title: Option<String>,
+    pub candidate_name: Option<String>,
+    #[serde(serialize_with = "crate::services::masking::email_opt")]
+    pub candidate_email: Option<String>,
+    pub stage: String,
+    pub stage_entered_at: String,
+    pub due_at: String,
+    pub detected_at: Option<String>,
+    pub notified_at: Option<String>,
+    #[sqlx(skip)]
+    pub business_days_overdue: i64,
+}
+
+// Report builder models
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct SavedReport {
+    pub id: String,
+    pub name: String,
+    pub description: Option<String>,
+    /// A `services::reports::ReportDefinition`, stored as JSON text
+    #[serde(serialize_with = "crate::services::reports::definition_json")]
+    pub definition: String,
+    /// `daily` or `weekly`; None for reports that are only run on demand
+    pub schedule: Option<String>,
+    /// UTC hour the scheduled email goes out
+    pub schedule_hour: i64,
+    /// Day of a weekly report, 0 = Monday
+    pub schedule_weekday: Option<i64>,
+    /// Comma-separated addresses the scheduled report is emailed to
+    pub recipients: Option<String>,
+    pub last_sent_at: Option<String>,
+    pub created_by: Option<String>,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateSavedReportRequest {
+    pub name: String,
+    pub description: Option<String>,
+    pub definition: ReportDefinition,
+    pub schedule: Option<String>,
+    pub schedule_hour: Option<i64>,
+    pub schedule_weekday: Option<i64>,
+    pub recipients: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateSavedReportRequest {
+    pub name: Option<String>,
+    /// An empty string clears it
+    pub description: Option<String>,
+    pub definition: Option<ReportDefinition>,
+    /// An empty string unschedules the report
+    pub schedule: Option<String>,
+    pub schedule_hour: Option<i64>,
+    pub schedule_weekday: Option<i64>,
+    /// An empty string removes every recipient
+    pub recipients: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ReportOutputQuery {
+    /// `json` (default) or `csv`
+    pub format: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AnalyticsRefreshQuery {
+    /// Recompute every application rather than only those that changed
+    #[serde(default)]
+    pub full: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct HiringPerformanceQuery {
+    /// Inclusive `YYYY-MM-DD` bounds on when applications were submitted; the last 90 days
+    /// by default
+    pub from: Option<String>,
+    pub to: Option<String>,
+    pub job_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CohortQuery {
+    /// Inclusive `YYYY-MM` bounds on the month applications were submitted; the last six
+    /// months by default
+    pub from: Option<String>,
+    pub to: Option<String>,
+    /// Weeks each cohort is followed for, 12 by default
+    pub weeks: Option<usize>,
+    pub job_id: Option<String>,
+    /// Only this source tag; `direct` for applications without one
+    pub source: Option<String>,
+}
+
+// Org structure and job assignment models
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct Department {
+    pub id: String,
+    /// None for departments shared across companies
+    pub company_id: Option<String>,
+    pub parent_id: Option<String>,
+    pub name: String,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+    #[sqlx(skip)]
+    pub teams: Vec<Team>,
+}
+
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct Team {
+    pub id: String,
+    pub department_id: String,
+    pub name: String,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DepartmentQuery {
+    pub company_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateDepartmentRequest {
+    pub name: String,
+    pub company_id: Option<String>,
+    pub parent_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateDepartmentRequest {
+    pub name: Option<String>,
+    /// An empty string moves the department to the top level
+    pub parent_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TeamRequest {
+    pub name: String,
+}
+
+/// An admin assigned to a job as hiring manager or recruiter
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct AssignedUser {
+    pub id: String,
+    pub name: Option<String>,
+    pub email: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct JobAssignment {
+    pub job_id: String,
+    pub department_id: Option<String>,
+    pub department_name: Option<String>,
+    pub team_id: Option<String>,
+    pub team_name: Option<String>,
+    pub hiring_manager: Option<AssignedUser>,
+    pub recruiters: Vec<AssignedUser>,
+}
+
+/// Replaces a job's whole assignment; omitted fields are cleared
+#[derive(Debug, Deserialize)]
+pub struct UpdateJobAssignmentRequest {
+    pub department_id: Option<String>,
+    pub team_id: Option<String>,
+    pub hiring_manager_id: Option<String>,
+    #[serde(default)]
+    pub recruiter_ids: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MyJobsQuery {
+    /// `hiring_manager` or `recruiter`; both when omitted
+    pub role: Option<String>,
+    pub status: Option<String>,
+}
+
+/// A job the current admin is assigned to
+#[derive(Debug, Serialize, FromRow)]
+pub struct MyJob {
+    pub id: String,
+    pub title: String,
+    pub company: Option<String>,
+    pub status: Option<String>,
+    pub department_name: Option<String>,
+    pub team_name: Option<String>,
+    pub is_hiring_manager: bool,
+    pub is_recruiter: bool,
+    /// Applications not yet hired, rejected or withdrawn
+    pub open_applications: i64,
+    /// Open applications past their SLA target
+    pub stale_applications: i64,
+    pub created_at: Option<String>,
+}
+
+// Legal hold models
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct LegalHold {
+    pub id: String,
+    /// `user` or `application`
+    pub subject_type: String,
+    pub subject_id: String,
+    /// The user whose data is preserved; for application holds, the applicant
+    pub user_id: String,
+    pub reason: String,
+    pub matter_reference: Option<String>,
+    pub placed_by: String,
+    pub placed_at: Option<String>,
+    pub released_by: Option<String>,
+    pub released_at: Option<String>,
+    pub release_reason: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct LegalHoldEvent {
+    pub id: String,
+    pub hold_id: String,
+    /// `placed`, `released` or `blocked`
+    pub action: String,
+    pub actor_id: Option<String>,
+    pub details: Option<String>,
+    pub created_at: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct LegalHoldQuery {
+    /// Only unreleased holds when true, only released ones when false
+    pub active: Option<bool>,
+    pub user_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateLegalHoldRequest {
+    pub subject_type: String,
+    pub subject_id: String,
+    pub reason: String,
+    pub matter_reference: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ReleaseLegalHoldRequest {
+    pub reason: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AdminSearchQuery {
+    pub q: String,
+    /// Results per group, default 5
+    pub limit: Option<i64>,
+}
+
+/// One search result and where it opens in the admin UI
+#[derive(Debug, Serialize)]
+pub struct SearchHit {
+    pub entity_type: &'static str,
+    pub id: String,
+    pub title: String,
+    pub subtitle: Option<String>,
+    pub link: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AdminSearchResponse {
+    pub query: String,
+    pub candidates: Vec<SearchHit>,
+    pub jobs: Vec<SearchHit>,
+    pub applications: Vec<SearchHit>,
+    pub companies: Vec<SearchHit>,
+}
+
+// Knockout screening rule models
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct KnockoutRule {
+    pub id: String,
+    pub job_id: String,
+    pub name: String,
+    /// `work_authorization`, `min_years` or `question`
+    pub rule_type: String,
+    /// Country the candidate must be authorized to work in
+    pub country: Option<String>,
+    /// Fewest years of experience on the candidate's resume
+    pub min_years: Option<f64>,
+    /// Question shown on the application form
+    pub question: Option<String>,
+    /// Answer the question needs, compared case-insensitively
+    pub expected_answer: Option<String>,
+    /// `tag` flags the application; `reject` rejects it and emails the candidate
+    pub action: String,
+    pub tag: Option<String>,
+    /// Rejection email overrides; `{name}`, `{job}` and `{company}` are filled in
+    pub email_subject: Option<String>,
+    /// Markdown body of the rejection email
+    pub email_body: Option<String>,
+    pub is_active: bool,
+    pub created_by: String,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateKnockoutRuleRequest {
+    pub name: String,
+    pub rule_type: String,
+    pub country: Option<String>,
+    pub min_years: Option<f64>,
+    pub question: Option<String>,
+    pub expected_answer: Option<String>,
+    pub action: String,
+    pub tag: Option<String>,
+    pub email_subject: Option<String>,
+    pub email_body: Option<String>,
+    pub is_active: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateKnockoutRuleRequest {
+    pub name: Option<String>,
+    pub country: Option<String>,
+    pub min_years: Option<f64>,
+    pub question: Option<String>,
+    pub expected_answer: Option<String>,
+    pub action: Option<String>,
+    pub tag: Option<String>,
+    pub email_subject: Option<String>,
+    pub email_body: Option<String>,
+    pub is_active: Option<bool>,
+}
+
+/// A knockout rule that fired for an application
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct ApplicationKnockout {
+    pub id: String,
+    pub application_id: String,
+    pub job_id: String,
+    pub rule_id: String,
+    pub rule_name: String,
+    pub action: String,
+    pub tag: Option<String>,
+    /// Why the rule fired, e.g. "Not authorized to work in Canada"
+    pub reason: String,
+    pub email_sent: bool,
+    pub created_at: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct StorageUsageQuery {
+    /// `user` (default) or `company`
+    pub owner_type: Option<String>,
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AiUsageQuery {
+    /// `day` (default), `feature`, `user`, `model` or `route`
+    pub group_by: Option<String>,
+    /// Inclusive `YYYY-MM-DD` bounds
+    pub from: Option<String>,
+    pub to: Option<String>,
+    /// `json` (default) or `csv`
+    pub format: Option<String>,
+}
+
+/// The model (and reasoning effort) used for one AI purpose
+#[derive(Debug, Serialize)]
+pub struct AiPurposeModel {
+    pub purpose: String,
+    pub model: String,
+    pub default_model: String,
+    /// `None` for image generation
+    pub reasoning_effort: Option<String>,
+    pub default_reasoning_effort: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AiModelsResponse {
+    pub purposes: Vec<AiPurposeModel>,
+    /// Models the configured API key can use; `None` when the provider could not be reached
+    pub available_models: Option<Vec<String>>,
+    pub available_models_error: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateAiModelRequest {
+    pub model: String,
+    pub reasoning_effort: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AiUsageMonthlyQuery {
+    pub months: Option<i64>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct OrphanScanRequest {
+    /// Also delete orphans older than the grace period; the default only reports
+    #[serde(default)]
+    pub delete: bool,
+}
+
+/// Something that happened on a job the admin works on
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct ActivityEvent {
+    /// `application`, `stage_change`, `interview` or `offer`
+    pub kind: String,
+    /// When it happened; for interviews, when they start
+    pub occurred_at: Option<String>,
+    pub application_id: Option<String>,
+    pub job_id: Option<String>,
+    pub job_title: Option<String>,
+    pub candidate_name: Option<String>,
+    /// New status for stage changes, interview type for interviews, `sent` or `hired` for offers
+    pub detail: Option<String>,
+    #[sqlx(skip)]
+    pub summary: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ActivityQuery {
+    /// Only events after this time; defaults to the last 24 hours
+    pub since: Option<String>,
+    pub limit: Option<i64>,
+    /// `assigned` (default): jobs the admin is hiring manager or recruiter on, plus jobs
+    /// nobody is assigned to; `all`: every job
+    pub scope: Option<String>,
+}
+
+/// What an admin wants in the activity feed and the daily digest
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct ActivityPreferences {
+    pub digest_enabled: bool,
+    /// UTC hour (0-23) the digest is sent at
+    pub digest_hour: i64,
+    pub applications: bool,
+    pub stage_changes: bool,
+    pub interviews: bool,
+    pub offers: bool,
+    pub last_digest_at: Option<String>,
+}
+
+impl Default for ActivityPreferences {
+    fn default() -> Self {
+        Self {
+            digest_enabled: false,
+            digest_hour: 8,
+            applications: true,
+            stage_changes: true,
+            interviews: true,
+            offers: true,
+            last_digest_at: None,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateActivityPreferencesRequest {
+    pub digest_enabled: Option<bool>,
+    pub digest_hour: Option<i64>,
+    pub applications: Option<bool>,
+    pub stage_changes: Option<bool>,
+    pub interviews: Option<bool>,
+    pub offers: Option<bool>,
+}
+
+/// One SMS attempt from the delivery log
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct SmsDelivery {
+    pub id: String,
+    pub user_id: Option<String>,
+    #[sqlx(try_from = "Sealed")]
+    #[serde(serialize_with = "crate::services::masking::phone")]
+    pub phone: String,
+    /// verification, interview_reminder or offer_expiring
+    pub kind: String,
+    /// Interview or application the text was about
+    pub reference_id: Option<String>,
+    pub body: String,
+    pub provider: Option<String>,
+    /// sent, failed or skipped
+    pub status: String,
+    pub provider_message_id: Option<String>,
+    pub error: Option<String>,
+    pub created_at: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SmsDeliveryQuery {
+    pub user_id: Option<String>,
+    pub kind: Option<String>,
+    pub status: Option<String>,
+    pub limit: Option<i64>,
+}
+
+/// An approved WhatsApp Business message template
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct WhatsAppTemplate {
+    /// Template name as approved in WhatsApp Business Manager
+    pub name: String,
+    /// Template language code, e.g. `en_US`
+    pub language: String,
+}
+
+/// One WhatsApp message from the delivery log
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct WhatsAppMessage {
+    pub id: String,
+    pub user_id: Option<String>,
+    #[sqlx(try_from = "Sealed")]
+    #[serde(serialize_with = "crate::services::masking::phone")]
+    pub phone: String,
+    /// interview_confirmation or interview_reminder
+    pub event: String,
+    /// Interview the message was about
+    pub reference_id: Option<String>,
+    pub template: String,
+    /// sent or failed
+    pub status: String,
+    pub provider_message_id: Option<String>,
+    pub error: Option<String>,
+    pub created_at: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct WhatsAppMessageQuery {
+    pub user_id: Option<String>,
+    pub event: Option<String>,
+    pub status: Option<String>,
+    pub limit: Option<i64>,
+}
+
+/// A Slack or Teams channel that recruiting events for a company or job are posted to
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct ChatWebhook {
+    pub id: String,
+    /// `slack` or `teams`
+    pub provider: String,
+    /// Label for the channel, e.g. `#hiring-eng`
+    pub name: String,
+    /// Incoming webhook URL; masked in responses
+    pub webhook_url: String,
+    /// Set for company-wide webhooks, which cover every job of the company
+    pub company_id: Option<String>,
+    /// Set for webhooks of a single job
+    pub job_id: Option<String>,
+    pub notify_new_application: bool,
+    pub notify_interview_feedback: bool,
+    pub notify_offer_accepted: bool,
+    pub is_active: bool,
+    /// Error of the most recent failed post, cleared by the next successful one
+    pub last_error: Option<String>,
+    pub last_delivered_at: Option<String>,
+    pub created_by: String,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateChatWebhookRequest {
+    pub provider: String,
+    pub name: String,
+    pub webhook_url: String,
+    /// Exactly one of `company_id` and `job_id`
+    pub company_id: Option<String>,
+    pub job_id: Option<String>,
+    pub notify_new_application: Option<bool>,
+    pub notify_interview_feedback: Option<bool>,
+    pub notify_offer_accepted: Option<bool>,
+    pub is_active: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateChatWebhookRequest {
+    pub name: Option<String>,
+    pub webhook_url: Option<String>,
+    pub notify_new_application: Option<bool>,
+    pub notify_interview_feedback: Option<bool>,
+    pub notify_offer_accepted: Option<bool>,
+    pub is_active: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ChatWebhookQuery {
+    pub company_id: Option<String>,
+    pub job_id: Option<String>,
 }
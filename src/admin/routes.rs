@@ -14,11 +14,52 @@ pub fn admin_routes() -> Router {
             "/api/public/contact",
             post(handlers::contact::submit_contact_form),
         )
+        // Public error catalog (OpenAPI components)
+        .route(
+            "/api/openapi/errors.json",
+            get(handlers::docs::get_error_catalog),
+        )
         // Dashboard and analytics endpoints
         .route(
             "/api/admin/dashboard/metrics",
             get(handlers::dashboard::get_dashboard_metrics),
         )
+        .route(
+            "/api/admin/activity/feed",
+            get(handlers::activity::get_activity_feed),
+        )
+        .route(
+            "/api/admin/activity/preferences",
+            get(handlers::activity::get_activity_preferences)
+                .put(handlers::activity::update_activity_preferences),
+        )
+        .route(
+            "/api/admin/sms/deliveries",
+            get(handlers::sms::list_sms_deliveries),
+        )
+        .route(
+            "/api/admin/whatsapp/templates",
+            get(handlers::whatsapp::get_whatsapp_templates)
+                .put(handlers::whatsapp::update_whatsapp_templates),
+        )
+        .route(
+            "/api/admin/whatsapp/messages",
+            get(handlers::whatsapp::list_whatsapp_messages),
+        )
+        .route(
+            "/api/admin/chat-webhooks",
+            get(handlers::chat_webhooks::list_chat_webhooks)
+                .post(handlers::chat_webhooks::create_chat_webhook),
+        )
+        .route(
+            "/api/admin/chat-webhooks/:id",
+            put(handlers::chat_webhooks::update_chat_webhook)
+                .delete(handlers::chat_webhooks::delete_chat_webhook),
+        )
+        .route(
+            "/api/admin/chat-webhooks/:id/test",
+            post(handlers::chat_webhooks::test_chat_webhook),
+        )
         .route(
             "/api/admin/system/health",
             get(handlers::dashboard::get_system_health),
@@ -49,6 +90,168 @@ pub fn admin_routes() -> Router {
             "/api/admin/candidates/:id",
             get(handlers::users::get_admin_candidate_details),
         )
+        // Compensation band endpoints
+        .route(
+            "/api/admin/compensation-bands",
+            get(handlers::compensation::list_compensation_bands)
+                .post(handlers::compensation::create_compensation_band),
+        )
+        .route(
+            "/api/admin/compensation-bands/validate-offer",
+            post(handlers::compensation::validate_offer_salary),
+        )
+        .route(
+            "/api/admin/compensation-bands/overrides",
+            get(handlers::compensation::list_compensation_overrides),
+        )
+        .route(
+            "/api/admin/compensation-bands/:id",
+            put(handlers::compensation::update_compensation_band)
+                .delete(handlers::compensation::delete_compensation_band),
+        )
+        // Paid job promotion endpoints
+        .route(
+            "/api/admin/jobs/:id/promotions",
+            get(handlers::promotions::list_job_promotions)
+                .post(handlers::promotions::create_job_promotion),
+        )
+        .route(
+            "/api/admin/promotions/:id",
+            put(handlers::promotions::update_job_promotion)
+                .delete(handlers::promotions::delete_job_promotion),
+        )
+        .route(
+            "/api/admin/analytics/promotions",
+            get(handlers::promotions::get_promotion_report),
+        )
+        .route(
+            "/api/admin/analytics/hiring-performance",
+            get(handlers::hiring_performance::get_hiring_performance),
+        )
+        .route(
+            "/api/admin/analytics/cohorts",
+            get(handlers::cohorts::get_source_cohorts),
+        )
+        .route(
+            "/api/admin/analytics/refresh",
+            post(handlers::analytics::refresh_analytics),
+        )
+        // Knockout screening rule endpoints
+        .route(
+            "/api/admin/jobs/:id/knockout-rules",
+            get(handlers::knockout_rules::list_knockout_rules)
+                .post(handlers::knockout_rules::create_knockout_rule),
+        )
+        .route(
+            "/api/admin/knockout-rules/:id",
+            put(handlers::knockout_rules::update_knockout_rule)
+                .delete(handlers::knockout_rules::delete_knockout_rule),
+        )
+        .route(
+            "/api/admin/applications/:id/knockouts",
+            get(handlers::knockout_rules::list_application_knockouts),
+        )
+        // Hiring SLA endpoints
+        .route(
+            "/api/admin/sla-policies",
+            get(handlers::sla::list_sla_policies).post(handlers::sla::create_sla_policy),
+        )
+        .route(
+            "/api/admin/sla-policies/:id",
+            put(handlers::sla::update_sla_policy).delete(handlers::sla::delete_sla_policy),
+        )
+        .route(
+            "/api/admin/applications/stale",
+            get(handlers::sla::list_stale_applications),
+        )
+        // Report builder endpoints
+        .route(
+            "/api/admin/reports",
+            get(handlers::reports::list_saved_reports).post(handlers::reports::create_saved_report),
+        )
+        .route(
+            "/api/admin/reports/catalog",
+            get(handlers::reports::get_report_catalog),
+        )
+        .route("/api/admin/reports/run", post(handlers::reports::run_report))
+        .route(
+            "/api/admin/reports/:id",
+            get(handlers::reports::get_saved_report)
+                .put(handlers::reports::update_saved_report)
+                .delete(handlers::reports::delete_saved_report),
+        )
+        .route(
+            "/api/admin/reports/:id/results",
+            get(handlers::reports::get_saved_report_results),
+        )
+        .route(
+            "/api/admin/reports/:id/send",
+            post(handlers::reports::send_saved_report),
+        )
+        // Org structure and job assignment endpoints
+        .route(
+            "/api/admin/departments",
+            get(handlers::org::list_departments).post(handlers::org::create_department),
+        )
+        .route(
+            "/api/admin/departments/:id",
+            put(handlers::org::update_department).delete(handlers::org::delete_department),
+        )
+        .route(
+            "/api/admin/departments/:id/teams",
+            post(handlers::org::create_team),
+        )
+        .route(
+            "/api/admin/teams/:id",
+            put(handlers::org::update_team).delete(handlers::org::delete_team),
+        )
+        .route(
+            "/api/admin/jobs/:id/assignment",
+            get(handlers::org::get_job_assignment).put(handlers::org::update_job_assignment),
+        )
+        .route("/api/admin/my-jobs", get(handlers::org::list_my_jobs))
+        // Global search endpoint
+        .route("/api/admin/search", get(handlers::search::admin_search))
+        // Legal hold endpoints
+        .route(
+            "/api/admin/legal-holds",
+            get(handlers::legal_holds::list_legal_holds)
+                .post(handlers::legal_holds::create_legal_hold),
+        )
+        .route(
+            "/api/admin/legal-holds/:id/release",
+            post(handlers::legal_holds::release_legal_hold),
+        )
+        .route(
+            "/api/admin/legal-holds/:id/events",
+            get(handlers::legal_holds::list_legal_hold_events),
+        )
+        // Security monitoring endpoints
+        .route(
+            "/api/admin/security-events",
+            get(handlers::security::list_security_events),
+        )
+        .route(
+            "/api/admin/security-events/:id/acknowledge",
+            post(handlers::security::acknowledge_security_event),
+        )
+        .route(
+            "/api/admin/signing-keys",
+            get(handlers::security::list_signing_keys),
+        )
+        .route(
+            "/api/admin/signing-keys/rotate",
+            post(handlers::security::rotate_signing_key),
+        )
+        // Content moderation queue
+        .route(
+            "/api/admin/moderation",
+            get(handlers::moderation::list_moderation_queue),
+        )
+        .route(
+            "/api/admin/moderation/:id/review",
+            post(handlers::moderation::review_moderation_item),
+        )
         // Data export endpoints
         .route(
             "/api/admin/export/jobs",
@@ -67,6 +270,8 @@ pub fn admin_routes() -> Router {
             "/api/settings/public",
             get(handlers::settings::get_public_system_settings),
         )
+        .route("/api/admin/config", get(handlers::config::get_config))
+        .route("/api/admin/dev/seed", post(handlers::dev::seed_demo_data))
         .route(
             "/api/admin/settings",
             get(handlers::settings::get_system_settings)
@@ -76,6 +281,10 @@ pub fn admin_routes() -> Router {
             "/api/admin/settings/test-connection",
             post(handlers::settings::test_service_connection),
         )
+        .route(
+            "/api/admin/settings/test/:integration",
+            post(handlers::settings::test_integration),
+        )
         // Theme settings endpoints
         .route(
             "/api/settings/theme",
@@ -116,4 +325,33 @@ pub fn admin_routes() -> Router {
             "/api/admin/files/:path",
             delete(handlers::files::delete_file_handler),
         )
+        .route(
+            "/api/admin/storage/usage",
+            get(handlers::storage_usage::list_storage_consumers),
+        )
+        .route(
+            "/api/admin/storage/usage/:owner_type/:owner_id",
+            get(handlers::storage_usage::get_owner_storage_usage),
+        )
+        .route("/api/admin/ai/models", get(handlers::ai_models::get_ai_models))
+        .route(
+            "/api/admin/ai/models/:purpose",
+            put(handlers::ai_models::update_ai_model),
+        )
+        .route(
+            "/api/admin/ai/usage",
+            get(handlers::ai_usage::get_ai_usage_report),
+        )
+        .route(
+            "/api/admin/ai/usage/monthly",
+            get(handlers::ai_usage::get_ai_usage_monthly),
+        )
+        .route(
+            "/api/admin/storage/orphans",
+            get(handlers::orphaned_files::list_orphaned_files),
+        )
+        .route(
+            "/api/admin/storage/orphans/scan",
+            post(handlers::orphaned_files::scan_orphaned_files),
+        )
 }
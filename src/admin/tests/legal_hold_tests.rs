@@ -0,0 +1,139 @@
+// src/admin/tests/legal_hold_tests.rs
+
+#[cfg(test)]
+mod tests {
+    use crate::services::legal_hold;
+    use crate::test_support::TestApp;
+    use axum::http::Method;
+    use serde_json::json;
+
+    /// An admin allowed to manage legal holds, and a candidate with two stored resumes
+    async fn setup(app: &TestApp) -> (String, String, String) {
+        let admin_id = app.create_admin().await;
+        sqlx::query(
+            "INSERT INTO admin_users (id, user_id, permissions) VALUES ('AU_HOLD', ?, '[\"legal_hold\"]')",
+        )
+        .bind(&admin_id)
+        .execute(&app.state.db)
+        .await
+        .unwrap();
+        let candidate_id = app
+            .create_user("held@test.example.com", "Harper Held")
+            .await;
+
+        for (id, filename) in [("R_HELD_1", "held-1.pdf"), ("R_HELD_2", "held-2.pdf")] {
+            sqlx::query("INSERT INTO resumes (id, user_id, filename) VALUES (?, ?, ?)")
+                .bind(id)
+                .bind(&candidate_id)
+                .bind(filename)
+                .execute(&app.state.db)
+                .await
+                .unwrap();
+            std::fs::write(app.state.resumes_dir.join(filename), b"%PDF-1.4").unwrap();
+        }
+
+        let admin = app.token_for(&admin_id).await;
+        let candidate = app.token_for(&candidate_id).await;
+        (admin, candidate, candidate_id)
+    }
+
+    #[tokio::test]
+    async fn test_held_user_resumes_and_files_are_kept_until_release() {
+        let app = TestApp::new().await;
+        let (admin, candidate, candidate_id) = setup(&app).await;
+
+        let placed = app
+            .post(
+                "/api/admin/legal-holds",
+                &admin,
+                json!({
+                    "subject_type": "user",
+                    "subject_id": candidate_id,
+                    "reason": "Pending employment litigation",
+                }),
+            )
+            .await;
+        assert_eq!(placed.status, 201, "{:?}", placed.body);
+        let hold_id = placed.body["id"].as_str().unwrap().to_string();
+        let hold = legal_hold::hold_for_user(&app.state.db, &candidate_id)
+            .await
+            .unwrap();
+        assert_eq!(hold.map(|h| h.id), Some(hold_id.clone()));
+
+        let resume_delete = app
+            .request(
+                Method::DELETE,
+                "/api/resumes/R_HELD_1",
+                Some(&candidate),
+                None,
+            )
+            .await;
+        assert_eq!(resume_delete.status, 409, "{:?}", resume_delete.body);
+        assert_eq!(resume_delete.body["code"], "LEGAL_HOLD_ACTIVE");
+
+        let file_delete = app
+            .request(
+                Method::DELETE,
+                "/api/admin/files/resumes%2Fheld-2.pdf",
+                Some(&admin),
+                None,
+            )
+            .await;
+        assert_eq!(file_delete.status, 409, "{:?}", file_delete.body);
+        assert!(app.state.resumes_dir.join("held-1.pdf").exists());
+        assert!(app.state.resumes_dir.join("held-2.pdf").exists());
+
+        let events = app
+            .get(
+                &format!("/api/admin/legal-holds/{}/events", hold_id),
+                &admin,
+            )
+            .await;
+        let blocked = events
+            .body
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter(|event| event["action"] == "blocked")
+            .count();
+        assert_eq!(blocked, 2);
+
+        let released = app
+            .post(
+                &format!("/api/admin/legal-holds/{}/release", hold_id),
+                &admin,
+                json!({ "reason": "Matter settled" }),
+            )
+            .await;
+        assert!(released.status.is_success(), "{:?}", released.body);
+        let hold = legal_hold::hold_for_user(&app.state.db, &candidate_id)
+            .await
+            .unwrap();
+        assert!(hold.is_none());
+
+        let resume_delete = app
+            .request(
+                Method::DELETE,
+                "/api/resumes/R_HELD_1",
+                Some(&candidate),
+                None,
+            )
+            .await;
+        assert!(
+            resume_delete.status.is_success(),
+            "{:?}",
+            resume_delete.body
+        );
+        let file_delete = app
+            .request(
+                Method::DELETE,
+                "/api/admin/files/resumes%2Fheld-2.pdf",
+                Some(&admin),
+                None,
+            )
+            .await;
+        assert!(file_delete.status.is_success(), "{:?}", file_delete.body);
+        assert!(!app.state.resumes_dir.join("held-1.pdf").exists());
+        assert!(!app.state.resumes_dir.join("held-2.pdf").exists());
+    }
+}
@@ -2,3 +2,4 @@
 
 pub mod handlers_tests;
 pub mod services_tests;
+pub mod legal_hold_tests;
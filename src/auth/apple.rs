@@ -0,0 +1,228 @@
+//! Sign in with Apple
+//!
+//! Apple identity tokens are JWTs signed with one of the keys Apple publishes. They are
+//! checked locally against those keys, which are cached and refetched when a token names
+//! a key we haven't seen (Apple rotates them), at most once per `MIN_REFETCH_INTERVAL` so
+//! forged tokens can't make us hammer Apple. Apple only includes the email on the first
+//! sign-in for some flows, and it may be a private relay address that forwards to the
+//! user's real inbox.
+
+use jsonwebtoken::{decode, decode_header, Algorithm, DecodingKey, Validation};
+use reqwest::Client;
+use serde::{Deserialize, Deserializer};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::RwLock;
+use tracing::{debug, error, warn};
+
+use crate::common::ApiError;
+
+pub const APPLE_ISSUER: &str = "https://appleid.apple.com";
+
+const APPLE_KEYS_URL: &str = "https://appleid.apple.com/auth/keys";
+
+/// How long fetched keys are trusted before being refetched
+const KEYS_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Shortest gap between two fetches of the key set, whatever the tokens coming in
+const MIN_REFETCH_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Domain of the addresses Apple hands out when the user hides their email
+pub const PRIVATE_RELAY_DOMAIN: &str = "privaterelay.appleid.com";
+
+/// One of Apple's public signing keys, as published in its JWK set
+#[derive(Debug, Clone, Deserialize)]
+pub struct AppleKey {
+    pub kid: String,
+    pub n: String,
+    pub e: String,
+}
+
+#[derive(Deserialize)]
+struct AppleKeySet {
+    keys: Vec<AppleKey>,
+}
+
+struct FetchedKeys {
+    fetched_at: Instant,
+    keys: Vec<AppleKey>,
+}
+
+/// Apple's signing keys, shared across requests
+#[derive(Clone, Default)]
+pub struct AppleKeyCache {
+    keys: Arc<RwLock<Option<FetchedKeys>>>,
+    /// When a fetch was last started, successful or not
+    last_refresh: Arc<Mutex<Option<Instant>>>,
+}
+
+impl AppleKeyCache {
+    /// A cache holding `keys`, as if they were fetched `age` ago
+    #[cfg(test)]
+    pub fn with_keys(keys: Vec<AppleKey>, age: Duration) -> Self {
+        let fetched_at = Instant::now() - age;
+        Self {
+            keys: Arc::new(RwLock::new(Some(FetchedKeys { fetched_at, keys }))),
+            last_refresh: Arc::new(Mutex::new(Some(fetched_at))),
+        }
+    }
+
+    /// Whether a fetch may start now; if so, the attempt is recorded
+    fn claim_refresh(&self) -> bool {
+        let mut last_refresh = self
+            .last_refresh
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if last_refresh.is_some_and(|at| at.elapsed() < MIN_REFETCH_INTERVAL) {
+            return false;
+        }
+        *last_refresh = Some(Instant::now());
+        true
+    }
+
+    async fn cached(&self) -> Option<Vec<AppleKey>> {
+        self.keys
+            .read()
+            .await
+            .as_ref()
+            .filter(|fetched| fetched.fetched_at.elapsed() < KEYS_TTL)
+            .map(|fetched| fetched.keys.clone())
+    }
+
+    async fn refresh(&self, http: &Client) -> Result<Vec<AppleKey>, ApiError> {
+        let unavailable = || {
+            ApiError::ServiceUnavailable("apple token validation service unavailable".to_string())
+        };
+        let set = http
+            .get(APPLE_KEYS_URL)
+            .send()
+            .await
+            .and_then(|r| r.error_for_status())
+            .map_err(|e| {
+                error!(error = %e, endpoint = APPLE_KEYS_URL, "HTTP error fetching Apple signing keys");
+                unavailable()
+            })?
+            .json::<AppleKeySet>()
+            .await
+            .map_err(|e| {
+                error!(error = %e, "Failed to parse Apple signing keys");
+                unavailable()
+            })?;
+        debug!(key_count = set.keys.len(), "Fetched Apple signing keys");
+        *self.keys.write().await = Some(FetchedKeys {
+            fetched_at: Instant::now(),
+            keys: set.keys.clone(),
+        });
+        Ok(set.keys)
+    }
+
+    /// The key a token was signed with, refetching the set once if it isn't cached. An
+    /// unknown key within `MIN_REFETCH_INTERVAL` of the last fetch is treated as not found.
+    pub async fn key(&self, http: &Client, kid: &str) -> Result<Option<AppleKey>, ApiError> {
+        if let Some(key) = self
+            .cached()
+            .await
+            .and_then(|keys| keys.into_iter().find(|k| k.kid == kid))
+        {
+            return Ok(Some(key));
+        }
+        if !self.claim_refresh() {
+            debug!(kid = %kid, "Unknown Apple key id; keys were fetched too recently to refetch");
+            return Ok(None);
+        }
+        Ok(self.refresh(http).await?.into_iter().find(|k| k.kid == kid))
+    }
+}
+
+/// Apple sends its boolean claims either as JSON booleans or as `"true"`/`"false"`
+fn bool_or_string<'de, D: Deserializer<'de>>(deserializer: D) -> Result<bool, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Flag {
+        Bool(bool),
+        Text(String),
+    }
+    Ok(match Option::<Flag>::deserialize(deserializer)? {
+        Some(Flag::Bool(value)) => value,
+        Some(Flag::Text(value)) => value == "true",
+        None => false,
+    })
+}
+
+/// The claims of a verified Apple identity token that sign-in uses
+#[derive(Debug, Deserialize)]
+pub struct AppleIdentity {
+    pub sub: String,
+    #[serde(default)]
+    pub email: Option<String>,
+    #[serde(default, deserialize_with = "bool_or_string")]
+    pub email_verified: bool,
+    #[serde(default, deserialize_with = "bool_or_string")]
+    pub is_private_email: bool,
+}
+
+impl AppleIdentity {
+    /// Whether the email is an Apple relay address rather than the user's own
+    pub fn has_private_email(&self) -> bool {
+        self.is_private_email || self.email.as_deref().is_some_and(is_private_relay)
+    }
+}
+
+pub fn is_private_relay(email: &str) -> bool {
+    email
+        .rsplit_once('@')
+        .is_some_and(|(_, domain)| domain.eq_ignore_ascii_case(PRIVATE_RELAY_DOMAIN))
+}
+
+/// Check a token's signature, issuer, audience and expiry against the given key
+pub fn verify_with_key(
+    token: &str,
+    key: &AppleKey,
+    client_ids: &[String],
+) -> Result<AppleIdentity, ApiError> {
+    let decoding_key = DecodingKey::from_rsa_components(&key.n, &key.e).map_err(|e| {
+        error!(error = %e, kid = %key.kid, "Apple signing key is unusable");
+        ApiError::InternalServer("apple signing key error".to_string())
+    })?;
+    let mut validation = Validation::new(Algorithm::RS256);
+    validation.set_issuer(&[APPLE_ISSUER]);
+    validation.set_audience(client_ids);
+
+    decode::<AppleIdentity>(token, &decoding_key, &validation)
+        .map(|data| data.claims)
+        .map_err(|e| {
+            warn!(error = %e, "Apple identity token rejected");
+            ApiError::Unauthorized("expired or invalid id_token".to_string())
+        })
+}
+
+/// Verify an Apple identity token issued to one of our client IDs
+pub async fn verify_identity_token(
+    http: &Client,
+    cache: &AppleKeyCache,
+    client_ids: &[String],
+    token: &str,
+) -> Result<AppleIdentity, ApiError> {
+    if client_ids.is_empty() {
+        return Err(ApiError::ServiceUnavailable(
+            "Sign in with Apple is not configured".to_string(),
+        ));
+    }
+
+    let header =
+        decode_header(token).map_err(|_| ApiError::BadRequest("malformed id_token".to_string()))?;
+    if header.alg != Algorithm::RS256 {
+        return Err(ApiError::Unauthorized(
+            "expired or invalid id_token".to_string(),
+        ));
+    }
+    let kid = header
+        .kid
+        .ok_or_else(|| ApiError::BadRequest("id_token missing key id".to_string()))?;
+    let key = cache.key(http, &kid).await?.ok_or_else(|| {
+        warn!(kid = %kid, "Apple identity token signed with an unknown key");
+        ApiError::Unauthorized("expired or invalid id_token".to_string())
+    })?;
+
+    verify_with_key(token, &key, client_ids)
+}
@@ -5,13 +5,13 @@ use axum::{
     extract::{Extension, FromRequestParts},
     http::{header::AUTHORIZATION, request::Parts},
 };
-use jsonwebtoken::{decode, Algorithm, DecodingKey, Validation};
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use tracing::{debug, error, warn};
 
-use super::models::{Claims, User};
+use super::models::User;
 use crate::common::{safe_email_log, ApiError, AppState};
+use crate::services::{ai_usage, masking};
 
 /// Authenticated user extractor
 ///
@@ -24,6 +24,21 @@ pub struct AuthedUser {
     pub is_admin: bool,
 }
 
+impl AuthedUser {
+    /// Refuse non-admins; `area` names what was being accessed in the denial log
+    pub fn require_admin(&self, area: &str) -> Result<(), ApiError> {
+        if !self.is_admin {
+            warn!(
+                user_id = %self.id,
+                area = %area,
+                "Access denied: admin privileges required"
+            );
+            return Err(ApiError::Forbidden("Admin privileges required".to_string()));
+        }
+        Ok(())
+    }
+}
+
 #[async_trait]
 impl<S> FromRequestParts<S> for AuthedUser
 where
@@ -53,6 +68,8 @@ where
                 "DEV MODE: Authentication bypassed"
             );
             
+            ai_usage::set_current_user(&dev_user.id);
+            masking::resolve_viewer(&app_state.db, &dev_user.id, is_admin).await;
             return Ok(AuthedUser {
                 id: dev_user.id,
                 email: dev_user.email,
@@ -83,19 +100,19 @@ where
         };
 
         // Validate JWT token
-        let decoded = match decode::<Claims>(
-            &bare_token,
-            &DecodingKey::from_secret(app_state.jwt_secret.as_bytes()),
-            &Validation::new(Algorithm::HS256),
-        ) {
-            Ok(d) => d,
+        let claims = match app_state
+            .jwt_keys
+            .verify(&app_state.db, &bare_token, &app_state.jwt_secret)
+            .await
+        {
+            Ok(c) => c,
             Err(e) => {
                 warn!(error = %e, "JWT token validation failed");
                 return Err(ApiError::Unauthorized("invalid token".into()));
             }
         };
 
-        let user_id = decoded.claims.sub;
+        let user_id = claims.sub;
 
         // Look up user in database
         let user: Option<User> = sqlx::query_as::<_, User>("SELECT * FROM users WHERE id = ?")
@@ -112,6 +129,12 @@ where
             })?;
 
         match user {
+            Some(u) if u.merged_into.is_some() => {
+                warn!(user_id = %u.id, "Authentication failed: account was merged into another");
+                Err(ApiError::Unauthorized(
+                    "account merged; sign in again".into(),
+                ))
+            }
             Some(u) => {
                 let user_email_lower = u.email.to_lowercase();
                 let is_admin = app_state.admin_emails.contains(&user_email_lower);
@@ -123,6 +146,8 @@ where
                     is_admin = is_admin,
                     "User authentication successful via extractor"
                 );
+                ai_usage::set_current_user(&u.id);
+                masking::resolve_viewer(&app_state.db, &u.id, is_admin).await;
                 Ok(AuthedUser {
                     id: u.id,
                     email: u.email,
@@ -1,17 +1,24 @@
 //! Authentication handlers
 
 use axum::extract::{Extension, Json};
-use chrono::{Duration, Utc};
-use jsonwebtoken::{encode, Algorithm, EncodingKey, Header};
+use chrono::Utc;
 use sqlx::SqlitePool;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use tracing::{debug, error, info, warn};
 
+use super::{apple, keys};
 use super::extractors::AuthedUser;
-use super::models::{Claims, GoogleIdTokenPayload, User};
-use crate::common::{generate_raw_id, generate_user_id, safe_email_log, ApiError, AppState};
-use jsonwebtoken::{decode, DecodingKey, Validation};
+use super::models::{
+    AppleIdTokenPayload, ClaimGuestAccountRequest, Claims, ConfirmAccountMergeRequest, ConsentInput, ConsentStatus, DuplicateAccount,
+    GoogleIdTokenPayload, LocalePreference, RequestAccountMergeRequest, TimezonePreference,
+    UpdateLocaleRequest, UpdateTimezoneRequest, User,
+};
+use crate::common::i18n::{t, user_locale, validate_locale, Locale};
+use crate::common::timezone::{user_timezone, validate_timezone, DEFAULT_TIMEZONE};
+use crate::common::{generate_user_id, safe_email_log, storage, ApiError, AppState};
+use crate::profile::avatar_fallback;
+use crate::services::{account_linking, consent, guest_apply};
 
 /// POST /api/auth/google
 /// Authenticates a user via Google OAuth ID token
@@ -140,13 +147,15 @@ pub async fn google_auth(
         ));
     }
 
-    // Check if email is verified (optional but recommended)
-    if let Some(email_verified) = body.get("email_verified").and_then(|v| v.as_bool()) {
-        if !email_verified {
-            warn!("Google token contains unverified email address");
-        } else {
-            debug!("Google token email verification confirmed");
-        }
+    // Check if email is verified (optional but recommended); tokeninfo sends it as a string
+    let email_verified = body
+        .get("email_verified")
+        .and_then(|v| v.as_bool().or_else(|| v.as_str().map(|s| s == "true")))
+        .unwrap_or(false);
+    if !email_verified {
+        warn!("Google token contains unverified email address");
+    } else {
+        debug!("Google token email verification confirmed");
     }
 
     // Check token expiration
@@ -200,6 +209,14 @@ pub async fn google_auth(
     let email = email.unwrap();
     let sub = sub.unwrap();
 
+    // Reject an outdated policy version before any account is created
+    let policy_version = consent::current_policy_version(&state.settings_service).await;
+    let consent_version = payload
+        .consent
+        .as_ref()
+        .map(|input| consent::validate_consent(input, policy_version.as_deref(), false))
+        .transpose()?;
+
     debug!(
         email = %safe_email_log(&email),
         provider = "google",
@@ -207,14 +224,9 @@ pub async fn google_auth(
         "Google token validation successful, proceeding with user lookup"
     );
 
-    // Create or find user in DB
-    let existing: Option<User> = match sqlx::query_as::<_, User>(
-        "SELECT * FROM users WHERE provider = ? AND provider_id = ?",
-    )
-    .bind("google")
-    .bind(&sub)
-    .fetch_optional(&state.db)
-    .await
+    // Create or find user in DB, following linked identities and merged accounts
+    let existing: Option<User> = match account_linking::user_for_identity(&state.db, "google", &sub)
+        .await
     {
         Ok(row) => {
             if row.is_some() {
@@ -242,7 +254,16 @@ pub async fn google_auth(
             return Err(ApiError::DatabaseError(e));
         }
     };
+    // A different Google account for an email we already know signs in to that user
+    let existing = match existing {
+        Some(user) => Some(user),
+        None => {
+            account_linking::link_by_email(&state.db, "google", &sub, &email, email_verified)
+                .await?
+        }
+    };
 
+    let is_new_user = existing.is_none();
     let user = match existing {
         Some(mut u) => {
             // For existing users, download and store avatar if we don't have one locally
@@ -346,6 +367,21 @@ pub async fn google_auth(
         }
     };
 
+    if let (Some(input), Some(version)) = (&payload.consent, &consent_version) {
+        let context = if is_new_user { "signup" } else { "login" };
+        if let Err(e) =
+            consent::record_consent(&state.db, &user.id, input, version.as_deref(), context, None)
+                .await
+        {
+            error!(error = %e, user_id = %user.id, "Database error recording consent");
+            return Err(ApiError::DatabaseError(e));
+        }
+    }
+    let consent_required = consent::consent_status(&state.db, &user.id, policy_version)
+        .await
+        .map(|status| status.reconsent_required)
+        .unwrap_or(false);
+
     // Update profile status to pending - this is not critical for OAuth flow
     // so we log errors but don't fail the authentication
     if let Err(e) = update_profile_status(&state.db, &user.id, "pending", None).await {
@@ -353,26 +389,7 @@ pub async fn google_auth(
     }
 
     // create JWT
-    let exp = (Utc::now() + Duration::hours(24)).timestamp() as usize;
-    let claims = Claims {
-        sub: user.id.clone(),
-        exp,
-    };
-    let token = match encode(
-        &Header::new(Algorithm::HS256),
-        &claims,
-        &EncodingKey::from_secret(state.jwt_secret.as_bytes()),
-    ) {
-        Ok(t) => t,
-        Err(e) => {
-            error!(
-                error = %e,
-                user_id = %user.id,
-                "JWT encoding error during authentication"
-            );
-            return Err(ApiError::InternalServer("jwt error".to_string()));
-        }
-    };
+    let token = session_token(&state, &user.id).await?;
 
     info!(
         user_id = %user.id,
@@ -390,14 +407,179 @@ pub async fn google_auth(
             "id": user.id,
             "email": user.email,
             "name": user.name,
-            "avatar": user.avatar,
+            "avatar": avatar_fallback::avatar_url(&user.id, user.avatar.as_deref()),
             "is_admin": is_admin,
         },
+        "consent_required": consent_required,
     });
 
     Ok(Json(resp))
 }
 
+/// POST /api/auth/apple
+/// Authenticates a user via a Sign in with Apple identity token
+///
+/// A user who hides their email gets an Apple relay address, which is never matched
+/// against existing accounts; `private_email` in the response lets the client offer to
+/// link the account they already have.
+///
+/// # Request Body
+/// ```json
+/// {
+///   "id_token": "<apple identity token>",
+///   "name": "Ada Lovelace"
+/// }
+/// ```
+///
+/// # Response
+/// ```json
+/// {
+///   "token": "<jwt token>",
+///   "user": { ... },
+///   "private_email": false
+/// }
+/// ```
+pub async fn apple_auth(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    Json(payload): Json<AppleIdTokenPayload>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    info!("🔐 Received Apple auth request");
+    let state = state_lock.read().await.clone();
+
+    let identity = apple::verify_identity_token(
+        &state.http,
+        &state.apple_keys,
+        &state.apple_client_ids,
+        payload.id_token.trim(),
+    )
+    .await?;
+    let private_email = identity.has_private_email();
+    let email = identity.email.as_deref().map(|e| e.trim().to_lowercase());
+
+    let policy_version = consent::current_policy_version(&state.settings_service).await;
+    let consent_version = payload
+        .consent
+        .as_ref()
+        .map(|input| consent::validate_consent(input, policy_version.as_deref(), false))
+        .transpose()?;
+
+    debug!(
+        provider = "apple",
+        provider_id = %identity.sub,
+        private_email = private_email,
+        "Apple token validation successful, proceeding with user lookup"
+    );
+
+    let existing = account_linking::user_for_identity(&state.db, "apple", &identity.sub)
+        .await
+        .map_err(|e| {
+            error!(error = %e, provider = "apple", "Database error checking existing user");
+            ApiError::DatabaseError(e)
+        })?;
+    // A relay address is unique to this app, so it can only ever match its own account
+    let existing = match (existing, &email) {
+        (Some(user), _) => Some(user),
+        (None, Some(email)) if !private_email => {
+            account_linking::link_by_email(
+                &state.db,
+                "apple",
+                &identity.sub,
+                email,
+                identity.email_verified,
+            )
+            .await?
+        }
+        _ => None,
+    };
+
+    let is_new_user = existing.is_none();
+    let user = match existing {
+        Some(user) => user,
+        None => {
+            // Apple leaves the email out of later tokens; a first sign-in without one can't
+            // create an account
+            let email = email.ok_or_else(|| {
+                warn!(provider = "apple", "Apple token for a new user carries no email");
+                ApiError::BadRequest("token missing required fields".to_string())
+            })?;
+            let name = payload
+                .name
+                .as_deref()
+                .map(str::trim)
+                .filter(|n| !n.is_empty());
+            let id = generate_user_id();
+            info!(
+                user_id = %id,
+                email = %safe_email_log(&email),
+                provider = "apple",
+                private_email = private_email,
+                "Creating new user account via Sign in with Apple"
+            );
+            sqlx::query(
+                "INSERT INTO users (id, email, name, provider, provider_id) VALUES (?, ?, ?, 'apple', ?)",
+            )
+            .bind(&id)
+            .bind(&email)
+            .bind(name)
+            .bind(&identity.sub)
+            .execute(&state.db)
+            .await
+            .map_err(|e| {
+                error!(error = %e, user_id = %id, provider = "apple", "Database error inserting new user");
+                ApiError::DatabaseError(e)
+            })?;
+            sqlx::query_as::<_, User>("SELECT * FROM users WHERE id = ?")
+                .bind(&id)
+                .fetch_one(&state.db)
+                .await
+                .map_err(ApiError::DatabaseError)?
+        }
+    };
+
+    if let (Some(input), Some(version)) = (&payload.consent, &consent_version) {
+        let context = if is_new_user { "signup" } else { "login" };
+        if let Err(e) =
+            consent::record_consent(&state.db, &user.id, input, version.as_deref(), context, None)
+                .await
+        {
+            error!(error = %e, user_id = %user.id, "Database error recording consent");
+            return Err(ApiError::DatabaseError(e));
+        }
+    }
+    let consent_required = consent::consent_status(&state.db, &user.id, policy_version)
+        .await
+        .map(|status| status.reconsent_required)
+        .unwrap_or(false);
+
+    if let Err(e) = update_profile_status(&state.db, &user.id, "pending", None).await {
+        error!(error = %e, user_id = %user.id, "Failed to update profile status to pending");
+    }
+
+    let token = session_token(&state, &user.id).await?;
+
+    info!(
+        user_id = %user.id,
+        email = %safe_email_log(&user.email),
+        provider = "apple",
+        "User authentication successful via Sign in with Apple"
+    );
+
+    let is_admin = state.admin_emails.contains(&user.email);
+
+    Ok(Json(serde_json::json!({
+        "token": token,
+        "user": {
+            "id": user.id,
+            "email": user.email,
+            "name": user.name,
+            "avatar": avatar_fallback::avatar_url(&user.id, user.avatar.as_deref()),
+            "is_admin": is_admin,
+        },
+        "consent_required": consent_required,
+        "private_email": private_email,
+    })))
+}
+
 /// GET /api/me
 /// Returns the current authenticated user's information
 ///
@@ -426,19 +608,391 @@ pub async fn me_handler(
     }
     
     // Production mode: fetch user from database
-    let user = sqlx::query_as::<_, User>("SELECT * FROM users WHERE id = ?")
+    let mut user = sqlx::query_as::<_, User>("SELECT * FROM users WHERE id = ?")
         .bind(&authed.id)
         .fetch_one(&state.db)
         .await
         .map_err(ApiError::DatabaseError)?;
+    user.avatar = Some(avatar_fallback::avatar_url(&user.id, user.avatar.as_deref()));
+
+    let policy_version = consent::current_policy_version(&state.settings_service).await;
+    let consent_required = consent::consent_status(&state.db, &authed.id, policy_version)
+        .await
+        .map_err(ApiError::DatabaseError)?
+        .reconsent_required;
 
     let resp = serde_json::json!({
         "user": user,
-        "is_admin": authed.is_admin
+        "is_admin": authed.is_admin,
+        "consent_required": consent_required
     });
     Ok(Json(resp))
 }
 
+/// GET /api/me/timezone
+/// Returns the caller's time zone preference used to render dates in emails and responses
+///
+/// # Response
+/// ```json
+/// {
+///   "timezone": "America/New_York",
+///   "effective_timezone": "America/New_York"
+/// }
+/// ```
+pub async fn get_timezone_handler(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+) -> Result<Json<TimezonePreference>, ApiError> {
+    let state = state_lock.read().await.clone();
+
+    let timezone = user_timezone(&state.db, &authed.id).await;
+    Ok(Json(TimezonePreference {
+        effective_timezone: timezone
+            .clone()
+            .unwrap_or_else(|| DEFAULT_TIMEZONE.to_string()),
+        timezone,
+    }))
+}
+
+/// PUT /api/me/timezone
+/// Sets the caller's time zone preference
+///
+/// # Request Body
+/// ```json
+/// {
+///   "timezone": "Europe/Berlin"
+/// }
+/// ```
+pub async fn update_timezone_handler(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Json(payload): Json<UpdateTimezoneRequest>,
+) -> Result<Json<TimezonePreference>, ApiError> {
+    let state = state_lock.read().await.clone();
+
+    let timezone = validate_timezone(&payload.timezone).map_err(ApiError::BadRequest)?;
+
+    sqlx::query("UPDATE users SET timezone = ? WHERE id = ?")
+        .bind(&timezone)
+        .bind(&authed.id)
+        .execute(&state.db)
+        .await
+        .map_err(ApiError::DatabaseError)?;
+
+    info!(user_id = %authed.id, timezone = %timezone, "Updated time zone preference");
+
+    Ok(Json(TimezonePreference {
+        timezone: Some(timezone.clone()),
+        effective_timezone: timezone,
+    }))
+}
+
+/// GET /api/me/locale
+/// Returns the caller's language preference used for emails
+///
+/// # Response
+/// ```json
+/// {
+///   "locale": "es",
+///   "effective_locale": "es",
+///   "supported_locales": ["en", "es", "fr", "de"]
+/// }
+/// ```
+pub async fn get_locale_handler(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+) -> Result<Json<LocalePreference>, ApiError> {
+    let state = state_lock.read().await.clone();
+
+    let locale = user_locale(&state.db, &authed.id).await;
+    Ok(Json(locale_preference(locale)))
+}
+
+/// PUT /api/me/locale
+/// Sets the caller's language preference
+///
+/// # Request Body
+/// ```json
+/// {
+///   "locale": "fr"
+/// }
+/// ```
+pub async fn update_locale_handler(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Json(payload): Json<UpdateLocaleRequest>,
+) -> Result<Json<LocalePreference>, ApiError> {
+    let state = state_lock.read().await.clone();
+
+    let locale = validate_locale(&payload.locale).map_err(ApiError::BadRequest)?;
+
+    sqlx::query("UPDATE users SET locale = ? WHERE id = ?")
+        .bind(locale.as_str())
+        .bind(&authed.id)
+        .execute(&state.db)
+        .await
+        .map_err(ApiError::DatabaseError)?;
+
+    info!(user_id = %authed.id, locale = %locale.as_str(), "Updated language preference");
+
+    Ok(Json(locale_preference(Some(locale))))
+}
+
+/// GET /api/me/consents
+/// Returns the caller's current consents and whether the privacy policy must be accepted again
+///
+/// # Response
+/// ```json
+/// {
+///   "current_policy_version": "2024-05",
+///   "accepted_policy_version": "2023-01",
+///   "data_processing": true,
+///   "marketing": false,
+///   "reconsent_required": true,
+///   ...
+/// }
+/// ```
+pub async fn get_consents_handler(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+) -> Result<Json<ConsentStatus>, ApiError> {
+    let state = state_lock.read().await.clone();
+
+    let policy_version = consent::current_policy_version(&state.settings_service).await;
+    let status = consent::consent_status(&state.db, &authed.id, policy_version)
+        .await
+        .map_err(ApiError::DatabaseError)?;
+    Ok(Json(status))
+}
+
+/// POST /api/me/consents
+/// Records the caller's consent choices, e.g. accepting a new privacy policy or opting out of
+/// marketing
+///
+/// # Request Body
+/// ```json
+/// {
+///   "privacy_policy": true,
+///   "data_processing": true,
+///   "marketing": false,
+///   "policy_version": "2024-05"
+/// }
+/// ```
+pub async fn update_consents_handler(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Json(payload): Json<ConsentInput>,
+) -> Result<Json<ConsentStatus>, ApiError> {
+    let state = state_lock.read().await.clone();
+
+    let policy_version = consent::current_policy_version(&state.settings_service).await;
+    let version = consent::validate_consent(&payload, policy_version.as_deref(), false)?;
+
+    consent::record_consent(
+        &state.db,
+        &authed.id,
+        &payload,
+        version.as_deref(),
+        "settings",
+        None,
+    )
+    .await
+    .map_err(ApiError::DatabaseError)?;
+
+    info!(
+        user_id = %authed.id,
+        privacy_policy = payload.privacy_policy,
+        data_processing = payload.data_processing,
+        marketing = payload.marketing,
+        "Updated consent"
+    );
+
+    let status = consent::consent_status(&state.db, &authed.id, policy_version)
+        .await
+        .map_err(ApiError::DatabaseError)?;
+    Ok(Json(status))
+}
+
+/// GET /api/me/account-links
+/// Lists other accounts sharing the caller's email (ignoring case) or phone number, which
+/// can be merged into the caller's account
+pub async fn get_account_links_handler(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+) -> Result<Json<Vec<DuplicateAccount>>, ApiError> {
+    let state = state_lock.read().await.clone();
+
+    let duplicates = account_linking::find_duplicates(&state.db, &authed.id)
+        .await
+        .map_err(|e| {
+            error!(error = %e, user_id = %authed.id, "Database error finding duplicate accounts");
+            ApiError::DatabaseError(e)
+        })?;
+    Ok(Json(duplicates))
+}
+
+/// POST /api/me/account-links
+/// Asks to merge another account into the caller's; the other account's inbox gets a
+/// confirmation link and nothing moves until it is used
+///
+/// # Request Body
+/// ```json
+/// {
+///   "user_id": "<id from GET /api/me/account-links>"
+/// }
+/// ```
+pub async fn request_account_merge_handler(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Json(payload): Json<RequestAccountMergeRequest>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let state = state_lock.read().await.clone();
+
+    let request = account_linking::request_merge(&state.db, &authed.id, &payload.user_id).await?;
+
+    let (source_email, source_name) = sqlx::query_as::<_, (String, Option<String>)>(
+        "SELECT email, name FROM users WHERE id = ?",
+    )
+    .bind(&request.source_user_id)
+    .fetch_one(&state.db)
+    .await
+    .map_err(ApiError::DatabaseError)?;
+
+    let locale = user_locale(&state.db, &request.source_user_id)
+        .await
+        .unwrap_or_default();
+    let url = format!(
+        "{}/account/merge/{}",
+        state.config.frontend_url,
+        request.token
+    );
+    let name = source_name.unwrap_or_default();
+    let hours = account_linking::MERGE_TTL_HOURS.to_string();
+    let args = [
+        ("name", name.as_str()),
+        ("email", authed.email.as_str()),
+        ("url", url.as_str()),
+        ("hours", hours.as_str()),
+    ];
+    let body = format!(
+        r#"<html><body style="font-family: Arial, sans-serif; line-height: 1.6; color: #333;">
+<div style="max-width: 600px; margin: 0 auto; padding: 20px;">
+<p>{}</p>
+{}
+<p>{}</p>
+</div></body></html>"#,
+        t("email.greeting", locale, &args),
+        t("email.account_merge.body", locale, &args),
+        t("email.signoff", locale, &args),
+    );
+    state
+        .email
+        .send_email(
+            vec![source_email.clone()],
+            &t("email.account_merge.subject", locale, &args),
+            &body,
+            None,
+        )
+        .await
+        .map_err(|e| {
+            error!(error = %e, merge_id = %request.id, "Failed to send account merge confirmation");
+            ApiError::ServiceUnavailable("Could not send the confirmation email".to_string())
+        })?;
+
+    info!(
+        merge_id = %request.id,
+        target_user_id = %authed.id,
+        source_user_id = %request.source_user_id,
+        match_reason = %request.match_reason,
+        "Account merge requested"
+    );
+
+    Ok(Json(serde_json::json!({
+        "message": format!("Confirmation sent to {}", safe_email_log(&source_email)),
+        "expires_at": request.expires_at,
+    })))
+}
+
+/// POST /api/auth/account-links/confirm
+/// Merges the account a confirmation link was sent to into the account that asked; the
+/// token is the only credential
+///
+/// # Request Body
+/// ```json
+/// {
+///   "token": "<token from the confirmation email>"
+/// }
+/// ```
+pub async fn confirm_account_merge_handler(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    Json(payload): Json<ConfirmAccountMergeRequest>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let state = state_lock.read().await.clone();
+
+    let (request, summary) = account_linking::confirm_merge(&state.db, payload.token.trim()).await?;
+
+    Ok(Json(serde_json::json!({
+        "message": "Accounts merged",
+        "user_id": request.target_user_id,
+        "moved": summary,
+    })))
+}
+
+/// POST /api/auth/guest/claim
+/// Signs a guest applicant in from the link emailed after applying; their applications
+/// join any account that already uses the email
+///
+/// # Request Body
+/// ```json
+/// {
+///   "token": "<token from the claim email>"
+/// }
+/// ```
+pub async fn claim_guest_account_handler(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    Json(payload): Json<ClaimGuestAccountRequest>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let state = state_lock.read().await.clone();
+
+    let outcome = guest_apply::claim(&state.db, payload.token.trim()).await?;
+    let user = outcome.user;
+    let token = session_token(&state, &user.id).await?;
+
+    let is_admin = state.admin_emails.contains(&user.email);
+
+    Ok(Json(serde_json::json!({
+        "token": token,
+        "user": {
+            "id": user.id,
+            "email": user.email,
+            "name": user.name,
+            "avatar": avatar_fallback::avatar_url(&user.id, user.avatar.as_deref()),
+            "is_admin": is_admin,
+        },
+        "merged": outcome.merged,
+    })))
+}
+
+/// A session JWT for the user, signed with the active key
+async fn session_token(state: &AppState, user_id: &str) -> Result<String, ApiError> {
+    state
+        .jwt_keys
+        .sign(&keys::session_claims(user_id))
+        .await
+        .map_err(|e| {
+            error!(error = %e, user_id = %user_id, "JWT encoding error during authentication");
+            ApiError::InternalServer("jwt error".to_string())
+        })
+}
+
+fn locale_preference(locale: Option<Locale>) -> LocalePreference {
+    LocalePreference {
+        locale: locale.map(|l| l.as_str().to_string()),
+        effective_locale: locale.unwrap_or_default().as_str().to_string(),
+        supported_locales: Locale::ALL.iter().map(|l| l.as_str().to_string()).collect(),
+    }
+}
+
 /// POST /api/auth/logout
 /// Logout endpoint - since we're using JWT tokens, logout is handled client-side
 /// This endpoint just returns success to confirm the logout request
@@ -461,21 +1015,24 @@ pub async fn logout_handler(_authed: AuthedUser) -> Result<Json<serde_json::Valu
 
 /// Validate a JWT token and return the claims
 /// This is used by the WebSocket handler for authentication
-pub fn validate_jwt(token: &str) -> Result<Claims, ApiError> {
-    let jwt_secret = std::env::var("JWT_SECRET")
-        .unwrap_or_else(|_| "default_secret_key_change_in_production".to_string());
-
-    let token_data = decode::<Claims>(
-        token,
-        &DecodingKey::from_secret(jwt_secret.as_bytes()),
-        &Validation::new(Algorithm::HS256),
-    )
-    .map_err(|e| {
-        warn!(error = %e, "JWT validation failed");
-        ApiError::Unauthorized("Invalid token".to_string())
-    })?;
+pub async fn validate_jwt(state: &AppState, token: &str) -> Result<Claims, ApiError> {
+    state
+        .jwt_keys
+        .verify(&state.db, token, &state.jwt_secret)
+        .await
+        .map_err(|e| {
+            warn!(error = %e, "JWT validation failed");
+            ApiError::Unauthorized("Invalid token".to_string())
+        })
+}
 
-    Ok(token_data.claims)
+/// GET /.well-known/jwks.json
+/// Public keys session tokens are signed with, for services that validate them
+pub async fn jwks_handler(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+) -> Json<keys::JwkSet> {
+    let keys = state_lock.read().await.jwt_keys.clone();
+    Json(keys.jwks().await)
 }
 
 async fn download_and_store_avatar(
@@ -525,7 +1082,7 @@ async fn download_and_store_avatar(
 
     // Generate filename
     let extension = get_extension_from_url(external_url).unwrap_or("jpg");
-    let filename = format!("avatar_{}_{}.{}", user_id, generate_raw_id(8), extension);
+    let filename = format!("avatar_{}_{}.{}", user_id, storage::content_hash(&bytes), extension);
 
     // Check storage type setting
     let storage_type = state
@@ -540,7 +1097,7 @@ async fn download_and_store_avatar(
         // Upload to S3
         let s3_key = format!("avatars/{}", filename);
         match state
-            .aws_service
+            .object_store
             .upload_file(bytes.to_vec(), &s3_key, &content_type)
             .await
         {
@@ -645,8 +1202,11 @@ pub async fn google_oauth_start(
     let state = state_lock.read().await;
     
     // Get the redirect URI from environment or use default
-    let redirect_uri = std::env::var("GOOGLE_OAUTH_REDIRECT_URI")
-        .unwrap_or_else(|_| "http://localhost:8080/auth/google/callback".to_string());
+    let redirect_uri = state
+        .config
+        .google_oauth_redirect_uri
+        .clone()
+        .unwrap_or_else(|| format!("{}/auth/google/callback", state.config.backend_url));
     
     info!("Starting Google OAuth flow with redirect_uri: {}", redirect_uri);
     
@@ -708,8 +1268,11 @@ pub async fn google_oauth_callback(
     info!("Received OAuth callback with authorization code");
     
     // Get the redirect URI (must match the one used in authorization)
-    let redirect_uri = std::env::var("GOOGLE_OAUTH_REDIRECT_URI")
-        .unwrap_or_else(|_| "http://localhost:8080/auth/google/callback".to_string());
+    let redirect_uri = state
+        .config
+        .google_oauth_redirect_uri
+        .clone()
+        .unwrap_or_else(|| format!("{}/auth/google/callback", state.config.backend_url));
     
     // Exchange code for tokens
     let token_response = state
@@ -820,6 +1383,6 @@ pub async fn google_oauth_callback(
         </html>
         "#,
         connected_account,
-        std::env::var("FRONTEND_URL").unwrap_or_else(|_| "http://localhost:3000".to_string())
+        state.config.frontend_url
     )))
 }
@@ -0,0 +1,605 @@
+//! Signing keys for session tokens
+//!
+//! Session JWTs are signed with RS256 by the newest active key and name it in the `kid`
+//! header. The public halves are published at `/.well-known/jwks.json`, so other services
+//! can validate tokens without the HMAC secret. Keys rotate on a schedule: the new key
+//! signs from then on, the previous one keeps verifying until every token it signed has
+//! expired, and is then retired. HS256 tokens from before the first key, which carry no
+//! `kid`, are accepted only until those sessions would have expired anyway.
+//!
+//! With several instances sharing the database, each rotation is claimed there first so only
+//! one instance creates the new key, and an instance that sees a `kid` it hasn't loaded yet
+//! reloads its ring before rejecting the token.
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+use chrono::{NaiveDateTime, Utc};
+use jsonwebtoken::{
+    decode, decode_header, encode, Algorithm, DecodingKey, EncodingKey, Header, Validation,
+};
+use rsa::pkcs1::{EncodeRsaPrivateKey, LineEnding};
+use rsa::traits::PublicKeyParts;
+use rsa::RsaPrivateKey;
+use serde::Serialize;
+use sqlx::{FromRow, SqlitePool};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use thiserror::Error;
+use tokio::sync::RwLock;
+use tracing::{error, info, warn};
+
+use super::models::Claims;
+use crate::common::generate_raw_id;
+use crate::services::encryption::EncryptionService;
+
+/// How long a session token is valid
+pub const SESSION_TTL_HOURS: i64 = 24;
+
+const RSA_KEY_BITS: usize = 2048;
+
+const KID_LENGTH: usize = 16;
+
+const ROTATION_CHECK_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Least time between reloads triggered by tokens naming an unknown `kid`, so forged tokens
+/// can't turn every request into a database read
+const MIN_UNKNOWN_KID_RELOAD_INTERVAL: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Error)]
+pub enum KeyError {
+    #[error("database error: {0}")]
+    Database(#[from] sqlx::Error),
+
+    #[error("key generation failed: {0}")]
+    Generation(String),
+
+    #[error("stored key {kid} is unusable: {reason}")]
+    Unusable { kid: String, reason: String },
+}
+
+/// A signing key as listed to admins; the private half never leaves the database
+#[derive(Debug, Serialize, FromRow)]
+pub struct SigningKeyInfo {
+    pub kid: String,
+    pub algorithm: String,
+    /// `active` (signs and verifies), `retiring` (verifies only) or `retired`
+    pub status: String,
+    pub created_at: String,
+    /// When a retiring key stops verifying
+    pub retire_at: Option<String>,
+    pub retired_at: Option<String>,
+}
+
+#[derive(FromRow)]
+struct StoredKey {
+    kid: String,
+    private_key: String,
+    public_n: String,
+    public_e: String,
+    status: String,
+}
+
+/// A public key in JWK form
+#[derive(Debug, Clone, Serialize)]
+pub struct Jwk {
+    pub kty: &'static str,
+    pub kid: String,
+    pub alg: &'static str,
+    #[serde(rename = "use")]
+    pub key_use: &'static str,
+    pub n: String,
+    pub e: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct JwkSet {
+    pub keys: Vec<Jwk>,
+}
+
+#[derive(Default)]
+struct KeyRing {
+    signing: Option<(String, EncodingKey)>,
+    verifying: HashMap<String, DecodingKey>,
+    jwks: Vec<Jwk>,
+    /// Latest expiry accepted on a legacy HS256 token; `None` before any key exists
+    legacy_until: Option<i64>,
+}
+
+/// The loaded signing keys, shared across requests and reloaded after a rotation
+#[derive(Clone, Default)]
+pub struct JwtKeys {
+    ring: Arc<RwLock<KeyRing>>,
+    last_unknown_kid_reload: Arc<Mutex<Option<Instant>>>,
+}
+
+impl JwtKeys {
+    /// Sign a session token with the active key
+    pub async fn sign(&self, claims: &Claims) -> Result<String, jsonwebtoken::errors::Error> {
+        let ring = self.ring.read().await;
+        let (kid, key) =
+            ring.signing
+                .as_ref()
+                .ok_or(jsonwebtoken::errors::ErrorKind::InvalidRsaKey(
+                    "no active signing key",
+                ))?;
+        let mut header = Header::new(Algorithm::RS256);
+        header.kid = Some(kid.clone());
+        encode(&header, claims, key)
+    }
+
+    /// Whether a reload for an unknown `kid` may run now; if so, the attempt is recorded
+    fn claim_unknown_kid_reload(&self) -> bool {
+        let mut last_reload = self
+            .last_unknown_kid_reload
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if last_reload.is_some_and(|at| at.elapsed() < MIN_UNKNOWN_KID_RELOAD_INTERVAL) {
+            return false;
+        }
+        *last_reload = Some(Instant::now());
+        true
+    }
+
+    /// Verify a session token against the key it names, or the HMAC secret for a legacy
+    /// token that is still within its original lifetime
+    ///
+    /// A `kid` missing from the ring may belong to a key another instance just created, so
+    /// the ring is reloaded from `pool` before the token is rejected.
+    pub async fn verify(
+        &self,
+        pool: &SqlitePool,
+        token: &str,
+        legacy_secret: &str,
+    ) -> Result<Claims, jsonwebtoken::errors::Error> {
+        let header = decode_header(token)?;
+        if let Some(kid) = &header.kid {
+            let known = self.ring.read().await.verifying.contains_key(kid);
+            if !known && self.claim_unknown_kid_reload() {
+                match self.reload(pool).await {
+                    Ok(()) => info!(kid = %kid, "Reloaded JWT signing keys for an unknown key id"),
+                    Err(e) => error!(error = %e, "Failed to reload JWT signing keys"),
+                }
+            }
+        }
+
+        let ring = self.ring.read().await;
+        match header.kid {
+            Some(kid) => {
+                let key = ring
+                    .verifying
+                    .get(&kid)
+                    .ok_or(jsonwebtoken::errors::ErrorKind::InvalidToken)?;
+                decode::<Claims>(token, key, &Validation::new(Algorithm::RS256))
+                    .map(|data| data.claims)
+            }
+            None => {
+                let claims = decode::<Claims>(
+                    token,
+                    &DecodingKey::from_secret(legacy_secret.as_bytes()),
+                    &Validation::new(Algorithm::HS256),
+                )?
+                .claims;
+                match ring.legacy_until {
+                    Some(until) if claims.exp as i64 > until => {
+                        Err(jsonwebtoken::errors::ErrorKind::ExpiredSignature.into())
+                    }
+                    _ => Ok(claims),
+                }
+            }
+        }
+    }
+
+    /// Public keys that tokens may currently be signed with
+    pub async fn jwks(&self) -> JwkSet {
+        JwkSet {
+            keys: self.ring.read().await.jwks.clone(),
+        }
+    }
+
+    /// Load the active and retiring keys from the database
+    pub async fn reload(&self, pool: &SqlitePool) -> Result<(), KeyError> {
+        let stored = sqlx::query_as::<_, StoredKey>(
+            r#"
+            SELECT kid, private_key, public_n, public_e, status FROM jwt_signing_keys
+            WHERE status IN ('active', 'retiring')
+            ORDER BY created_at DESC
+            "#,
+        )
+        .fetch_all(pool)
+        .await?;
+        let first_created: Option<String> =
+            sqlx::query_scalar("SELECT MIN(created_at) FROM jwt_signing_keys")
+                .fetch_one(pool)
+                .await?;
+
+        let mut ring = KeyRing {
+            legacy_until: first_created
+                .as_deref()
+                .and_then(|created| {
+                    NaiveDateTime::parse_from_str(created, "%Y-%m-%d %H:%M:%S").ok()
+                })
+                .map(|created| created.and_utc().timestamp() + SESSION_TTL_HOURS * 3600),
+            ..KeyRing::default()
+        };
+        for key in stored {
+            let unusable = |reason: String| KeyError::Unusable {
+                kid: key.kid.clone(),
+                reason,
+            };
+            let decoding = DecodingKey::from_rsa_components(&key.public_n, &key.public_e)
+                .map_err(|e| unusable(e.to_string()))?;
+            if key.status == "active" && ring.signing.is_none() {
+                let pem = open_private_key(&key.private_key).map_err(unusable)?;
+                let encoding = EncodingKey::from_rsa_pem(pem.as_bytes())
+                    .map_err(|e| unusable(e.to_string()))?;
+                ring.signing = Some((key.kid.clone(), encoding));
+            }
+            ring.verifying.insert(key.kid.clone(), decoding);
+            ring.jwks.push(Jwk {
+                kty: "RSA",
+                kid: key.kid,
+                alg: "RS256",
+                key_use: "sig",
+                n: key.public_n,
+                e: key.public_e,
+            });
+        }
+
+        *self.ring.write().await = ring;
+        Ok(())
+    }
+}
+
+/// Private keys are envelope-encrypted at rest when a master key is configured
+fn seal_private_key(pem: &str) -> String {
+    match EncryptionService::from_env() {
+        Ok(service) => match service.encrypt_envelope(pem) {
+            Ok(sealed) => return sealed,
+            Err(e) => {
+                warn!(error = %e, "Failed to encrypt JWT signing key; storing it unencrypted")
+            }
+        },
+        Err(_) => warn!("Encryption service not configured; storing JWT signing key unencrypted"),
+    }
+    pem.to_string()
+}
+
+fn open_private_key(stored: &str) -> Result<String, String> {
+    if !EncryptionService::is_envelope(stored) {
+        return Ok(stored.to_string());
+    }
+    EncryptionService::from_env()
+        .map_err(|e| e.to_string())?
+        .decrypt(stored)
+        .map_err(|e| e.to_string())
+}
+
+/// A new RSA key as (PKCS#1 PEM, modulus, exponent), the public parts base64url-encoded
+fn generate_key() -> Result<(String, String, String), KeyError> {
+    let key = RsaPrivateKey::new(&mut rand::rngs::OsRng, RSA_KEY_BITS)
+        .map_err(|e| KeyError::Generation(e.to_string()))?;
+    let pem = key
+        .to_pkcs1_pem(LineEnding::LF)
+        .map_err(|e| KeyError::Generation(e.to_string()))?;
+    Ok((
+        pem.to_string(),
+        URL_SAFE_NO_PAD.encode(key.n().to_bytes_be()),
+        URL_SAFE_NO_PAD.encode(key.e().to_bytes_be()),
+    ))
+}
+
+/// Make a new key active; the previous active key keeps verifying for one session lifetime
+pub async fn rotate(pool: &SqlitePool, keys: &JwtKeys) -> Result<String, KeyError> {
+    claim_rotation(pool, keys, Retire::Active)
+        .await?
+        .ok_or_else(|| KeyError::Generation("another rotation claimed the new key".to_string()))
+}
+
+/// Which active key a rotation replaces
+enum Retire {
+    /// Whatever key is active
+    Active,
+    /// The active key only if it has signed for this many days
+    OlderThanDays(i64),
+    /// None: only create a key when there is no active one
+    Nothing,
+}
+
+/// Create a new active key, claiming the rotation in the database so concurrent instances
+/// can't both add one. The retire and the insert run in one transaction, and the insert only
+/// goes ahead while no key is active, so the instance that loses the race rolls back, loads
+/// the winner's key and returns `None`.
+async fn claim_rotation(
+    pool: &SqlitePool,
+    keys: &JwtKeys,
+    retire: Retire,
+) -> Result<Option<String>, KeyError> {
+    let (pem, n, e) = tokio::task::spawn_blocking(generate_key)
+        .await
+        .map_err(|e| KeyError::Generation(e.to_string()))??;
+    let kid = generate_raw_id(KID_LENGTH);
+
+    let mut tx = pool.begin().await?;
+    let retire_at = format!("+{} hours", SESSION_TTL_HOURS);
+    match retire {
+        Retire::Active => {
+            sqlx::query(
+                "UPDATE jwt_signing_keys SET status = 'retiring', retire_at = datetime('now', ?) WHERE status = 'active'",
+            )
+            .bind(&retire_at)
+            .execute(&mut *tx)
+            .await?;
+        }
+        Retire::OlderThanDays(days) => {
+            sqlx::query(
+                "UPDATE jwt_signing_keys SET status = 'retiring', retire_at = datetime('now', ?) WHERE status = 'active' AND created_at <= datetime('now', ?)",
+            )
+            .bind(&retire_at)
+            .bind(format!("-{} days", days))
+            .execute(&mut *tx)
+            .await?;
+        }
+        Retire::Nothing => {}
+    }
+    let inserted = sqlx::query(
+        r#"
+        INSERT INTO jwt_signing_keys (kid, algorithm, private_key, public_n, public_e, status)
+        SELECT ?, 'RS256', ?, ?, ?, 'active'
+        WHERE NOT EXISTS (SELECT 1 FROM jwt_signing_keys WHERE status = 'active')
+        "#,
+    )
+    .bind(&kid)
+    .bind(seal_private_key(&pem))
+    .bind(&n)
+    .bind(&e)
+    .execute(&mut *tx)
+    .await?
+    .rows_affected();
+
+    if inserted == 0 {
+        tx.rollback().await?;
+        keys.reload(pool).await?;
+        info!("JWT signing key already rotated by another instance");
+        return Ok(None);
+    }
+    tx.commit().await?;
+
+    keys.reload(pool).await?;
+    info!(kid = %kid, "JWT signing key rotated");
+    Ok(Some(kid))
+}
+
+/// Retire keys whose tokens have all expired. Returns how many were retired.
+pub async fn retire_expired(pool: &SqlitePool) -> Result<u64, sqlx::Error> {
+    Ok(sqlx::query(
+        r#"
+        UPDATE jwt_signing_keys SET status = 'retired', retired_at = datetime('now')
+        WHERE status = 'retiring' AND retire_at <= datetime('now')
+        "#,
+    )
+    .execute(pool)
+    .await?
+    .rows_affected())
+}
+
+/// Whether the active key is missing or has signed for `rotation_days`
+pub async fn rotation_due(pool: &SqlitePool, rotation_days: i64) -> Result<bool, sqlx::Error> {
+    let fresh: bool = sqlx::query_scalar(
+        "SELECT EXISTS(SELECT 1 FROM jwt_signing_keys WHERE status = 'active' AND created_at > datetime('now', ?))",
+    )
+    .bind(format!("-{} days", rotation_days))
+    .fetch_one(pool)
+    .await?;
+    Ok(!fresh)
+}
+
+/// Load the keys, creating the first one if there is none
+pub async fn init(pool: &SqlitePool, keys: &JwtKeys) -> Result<(), KeyError> {
+    let has_active: bool =
+        sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM jwt_signing_keys WHERE status = 'active')")
+            .fetch_one(pool)
+            .await?;
+    if has_active {
+        keys.reload(pool).await
+    } else {
+        claim_rotation(pool, keys, Retire::Nothing)
+            .await
+            .map(|_| ())
+    }
+}
+
+/// Rotate the signing key when it is due and retire keys past their last token
+pub fn start_key_rotation_task(pool: SqlitePool, keys: JwtKeys, rotation_days: i64) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(ROTATION_CHECK_INTERVAL);
+        loop {
+            tokio::select! {
+                _ = interval.tick() => {}
+                _ = crate::common::shutdown::requested() => break,
+            }
+            let _running = crate::common::shutdown::track();
+
+            match retire_expired(&pool).await {
+                Ok(0) => {}
+                Ok(count) => {
+                    info!(count = count, "Retired expired JWT signing keys");
+                    if let Err(e) = keys.reload(&pool).await {
+                        error!(error = %e, "Failed to reload JWT signing keys");
+                    }
+                }
+                Err(e) => error!(error = %e, "Failed to retire JWT signing keys"),
+            }
+
+            match rotation_due(&pool, rotation_days).await {
+                Ok(true) => {
+                    let retire = Retire::OlderThanDays(rotation_days);
+                    if let Err(e) = claim_rotation(&pool, &keys, retire).await {
+                        error!(error = %e, "Scheduled JWT signing key rotation failed");
+                    }
+                }
+                Ok(false) => {}
+                Err(e) => error!(error = %e, "Failed to check JWT signing key age"),
+            }
+        }
+    });
+}
+
+/// Claims for a new session token
+pub fn session_claims(user_id: &str) -> Claims {
+    Claims {
+        sub: user_id.to_string(),
+        exp: (Utc::now() + chrono::Duration::hours(SESSION_TTL_HOURS)).timestamp() as usize,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    async fn pool() -> SqlitePool {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        sqlx::query(
+            r#"
+            CREATE TABLE jwt_signing_keys (
+                kid TEXT PRIMARY KEY,
+                algorithm TEXT NOT NULL DEFAULT 'RS256',
+                private_key TEXT NOT NULL,
+                public_n TEXT NOT NULL,
+                public_e TEXT NOT NULL,
+                status TEXT NOT NULL DEFAULT 'active',
+                created_at TEXT DEFAULT (datetime('now')),
+                retire_at TEXT,
+                retired_at TEXT
+            )
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        pool
+    }
+
+    fn legacy_token(secret: &str, exp: i64) -> String {
+        let claims = Claims {
+            sub: "U_LEGACY".to_string(),
+            exp: exp as usize,
+        };
+        encode(
+            &Header::new(Algorithm::HS256),
+            &claims,
+            &EncodingKey::from_secret(secret.as_bytes()),
+        )
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_rotation_keeps_previous_tokens_valid() {
+        let pool = pool().await;
+        let keys = JwtKeys::default();
+        init(&pool, &keys).await.unwrap();
+
+        let first = keys.sign(&session_claims("U_1")).await.unwrap();
+        assert_eq!(
+            keys.verify(&pool, &first, "secret").await.unwrap().sub,
+            "U_1"
+        );
+
+        rotate(&pool, &keys).await.unwrap();
+        let second = keys.sign(&session_claims("U_2")).await.unwrap();
+        assert_ne!(
+            decode_header(&first).unwrap().kid,
+            decode_header(&second).unwrap().kid
+        );
+        assert_eq!(
+            keys.verify(&pool, &first, "secret").await.unwrap().sub,
+            "U_1"
+        );
+        assert_eq!(
+            keys.verify(&pool, &second, "secret").await.unwrap().sub,
+            "U_2"
+        );
+        assert_eq!(keys.jwks().await.keys.len(), 2);
+
+        // Once the retiring key is past its last token it no longer verifies
+        sqlx::query("UPDATE jwt_signing_keys SET retire_at = datetime('now', '-1 minute') WHERE status = 'retiring'")
+            .execute(&pool)
+            .await
+            .unwrap();
+        assert_eq!(retire_expired(&pool).await.unwrap(), 1);
+        keys.reload(&pool).await.unwrap();
+        assert!(keys.verify(&pool, &first, "secret").await.is_err());
+        assert_eq!(keys.jwks().await.keys.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_legacy_tokens_accepted_only_within_their_original_lifetime() {
+        let pool = pool().await;
+        let keys = JwtKeys::default();
+        init(&pool, &keys).await.unwrap();
+
+        let now = Utc::now().timestamp();
+        let existing = legacy_token("secret", now + 3600);
+        assert_eq!(
+            keys.verify(&pool, &existing, "secret").await.unwrap().sub,
+            "U_LEGACY"
+        );
+        assert!(keys.verify(&pool, &existing, "other").await.is_err());
+
+        let minted_later = legacy_token("secret", now + 2 * SESSION_TTL_HOURS * 3600);
+        assert!(keys.verify(&pool, &minted_later, "secret").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_due_rotation_is_claimed_by_one_instance() {
+        let pool = pool().await;
+        let first = JwtKeys::default();
+        let second = JwtKeys::default();
+        init(&pool, &first).await.unwrap();
+        init(&pool, &second).await.unwrap();
+        assert_eq!(first.jwks().await.keys.len(), 1);
+
+        sqlx::query("UPDATE jwt_signing_keys SET created_at = datetime('now', '-31 days')")
+            .execute(&pool)
+            .await
+            .unwrap();
+        let rotated = claim_rotation(&pool, &first, Retire::OlderThanDays(30))
+            .await
+            .unwrap();
+        assert!(rotated.is_some());
+        // The second instance saw the same stale key but finds the rotation already done
+        let again = claim_rotation(&pool, &second, Retire::OlderThanDays(30))
+            .await
+            .unwrap();
+        assert!(again.is_none());
+
+        let active: i64 =
+            sqlx::query_scalar("SELECT COUNT(*) FROM jwt_signing_keys WHERE status = 'active'")
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!(active, 1);
+        assert_eq!(second.jwks().await.keys.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_unknown_kid_reloads_keys_from_the_database() {
+        let pool = pool().await;
+        let signer = JwtKeys::default();
+        let verifier = JwtKeys::default();
+        init(&pool, &signer).await.unwrap();
+        init(&pool, &verifier).await.unwrap();
+
+        // Another instance rotates; this one hasn't reloaded yet
+        rotate(&pool, &signer).await.unwrap();
+        let token = signer.sign(&session_claims("U_1")).await.unwrap();
+        assert_eq!(
+            verifier.verify(&pool, &token, "secret").await.unwrap().sub,
+            "U_1"
+        );
+    }
+}
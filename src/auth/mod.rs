@@ -2,12 +2,15 @@
 //!
 //! This module handles all authentication-related functionality including:
 //! - Google OAuth authentication
-//! - JWT token generation and validation
+//! - Sign in with Apple
+//! - JWT token generation and validation, with rotating signing keys
 //! - User authentication and authorization
 //! - AuthedUser extractor for protected routes
 
+pub mod apple;
 pub mod extractors;
 pub mod handlers;
+pub mod keys;
 pub mod models;
 pub mod routes;
 
@@ -14,16 +14,143 @@ pub struct Claims {
 #[derive(FromRow, Serialize, Deserialize, Debug)]
 pub struct User {
     pub id: String,
+    #[serde(serialize_with = "crate::services::masking::email")]
     pub email: String,
     pub name: Option<String>,
     pub avatar: Option<String>,
     pub provider: Option<String>,
     pub provider_id: Option<String>,
     pub created_at: Option<String>,
+    /// The account this one was merged into, if any
+    #[serde(default)]
+    pub merged_into: Option<String>,
 }
 
 /// Google ID token payload for OAuth
 #[derive(Deserialize)]
 pub struct GoogleIdTokenPayload {
     pub id_token: String,
+    /// Consent given on the sign-up form, recorded alongside the account
+    #[serde(default)]
+    pub consent: Option<ConsentInput>,
+}
+
+/// Apple identity token payload for Sign in with Apple
+#[derive(Deserialize)]
+pub struct AppleIdTokenPayload {
+    pub id_token: String,
+    /// The user's name, which Apple only hands to the client on the first sign-in
+    #[serde(default)]
+    pub name: Option<String>,
+    /// Consent given on the sign-up form, recorded alongside the account
+    #[serde(default)]
+    pub consent: Option<ConsentInput>,
+}
+
+/// Request body for updating the caller's time zone preference
+#[derive(Deserialize)]
+pub struct UpdateTimezoneRequest {
+    /// IANA zone name, e.g. `America/New_York`
+    pub timezone: String,
+}
+
+/// The caller's time zone preference and the zone dates are rendered in
+#[derive(Serialize)]
+pub struct TimezonePreference {
+    pub timezone: Option<String>,
+    pub effective_timezone: String,
+}
+
+/// Request body for updating the caller's language preference
+#[derive(Deserialize)]
+pub struct UpdateLocaleRequest {
+    /// Language tag, e.g. `es` or `fr-CA`
+    pub locale: String,
+}
+
+/// The caller's language preference, the language emails are sent in and the locales
+/// available
+#[derive(Serialize)]
+pub struct LocalePreference {
+    pub locale: Option<String>,
+    pub effective_locale: String,
+    pub supported_locales: Vec<String>,
+}
+
+/// Consent choices submitted at sign-up, on an application, or from settings
+#[derive(Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct ConsentInput {
+    pub privacy_policy: bool,
+    pub data_processing: bool,
+    pub marketing: bool,
+    /// The privacy policy version the candidate was shown
+    pub policy_version: Option<String>,
+}
+
+/// One consent given or withdrawn
+#[derive(FromRow, Serialize, Deserialize, Debug, Clone)]
+pub struct ConsentRecord {
+    pub id: String,
+    pub user_id: String,
+    pub consent_type: String,
+    pub granted: bool,
+    pub policy_version: Option<String>,
+    pub context: String,
+    pub application_id: Option<String>,
+    pub created_at: Option<String>,
+}
+
+/// A user's current consents and whether they must accept a newer privacy policy
+#[derive(Serialize, Debug)]
+pub struct ConsentStatus {
+    pub current_policy_version: Option<String>,
+    /// Version of the privacy policy the user last accepted
+    pub accepted_policy_version: Option<String>,
+    pub privacy_policy_accepted_at: Option<String>,
+    pub data_processing: bool,
+    pub marketing: bool,
+    pub reconsent_required: bool,
+    /// Most recent changes first
+    pub history: Vec<ConsentRecord>,
+}
+
+/// Another account that looks like the caller's, offered for merging
+#[derive(Serialize, Debug)]
+pub struct DuplicateAccount {
+    pub user_id: String,
+    /// Masked, since the caller has not proven they own it yet
+    pub email: String,
+    pub name: Option<String>,
+    /// `email` or `phone`
+    pub match_reason: String,
+}
+
+/// Request body for asking to merge another account into the caller's
+#[derive(Deserialize)]
+pub struct RequestAccountMergeRequest {
+    pub user_id: String,
+}
+
+/// Request body for confirming a merge from the link emailed to the duplicate account
+#[derive(Deserialize)]
+pub struct ConfirmAccountMergeRequest {
+    pub token: String,
+}
+
+/// Request body for claiming a guest applicant's account from the emailed link
+#[derive(Deserialize)]
+pub struct ClaimGuestAccountRequest {
+    pub token: String,
+}
+
+/// A request to fold `source_user_id` into `target_user_id`
+#[derive(FromRow, Debug, Clone)]
+pub struct AccountMergeRequest {
+    pub id: String,
+    pub source_user_id: String,
+    pub target_user_id: String,
+    pub match_reason: String,
+    pub token: String,
+    pub expires_at: String,
 }
@@ -11,13 +11,47 @@ use super::handlers;
 ///
 /// # Routes
 /// - `POST /api/auth/google` - Google OAuth authentication
+/// - `POST /api/auth/apple` - Sign in with Apple
+/// - `GET /.well-known/jwks.json` - Public keys session tokens are signed with
 /// - `POST /api/auth/logout` - Logout (client-side token removal)
 /// - `GET /api/me` - Get current user information
+/// - `GET /api/me/timezone`, `PUT /api/me/timezone` - Time zone preference
+/// - `GET /api/me/locale`, `PUT /api/me/locale` - Language preference for emails
+/// - `GET /api/me/account-links`, `POST /api/me/account-links` - Duplicate accounts and merge requests
+/// - `POST /api/auth/account-links/confirm` - Confirm a merge from the emailed link
+/// - `POST /api/auth/guest/claim` - Claim a guest applicant's account from the emailed link
 pub fn auth_routes() -> Router {
     Router::new()
         .route("/api/auth/google", post(handlers::google_auth))
+        .route("/api/auth/apple", post(handlers::apple_auth))
+        .route("/.well-known/jwks.json", get(handlers::jwks_handler))
         .route("/auth/google", get(handlers::google_oauth_start))
         .route("/auth/google/callback", get(handlers::google_oauth_callback))
         .route("/api/auth/logout", post(handlers::logout_handler))
         .route("/api/me", get(handlers::me_handler))
+        .route(
+            "/api/me/timezone",
+            get(handlers::get_timezone_handler).put(handlers::update_timezone_handler),
+        )
+        .route(
+            "/api/me/locale",
+            get(handlers::get_locale_handler).put(handlers::update_locale_handler),
+        )
+        .route(
+            "/api/me/consents",
+            get(handlers::get_consents_handler).post(handlers::update_consents_handler),
+        )
+        .route(
+            "/api/me/account-links",
+            get(handlers::get_account_links_handler)
+                .post(handlers::request_account_merge_handler),
+        )
+        .route(
+            "/api/auth/account-links/confirm",
+            post(handlers::confirm_account_merge_handler),
+        )
+        .route(
+            "/api/auth/guest/claim",
+            post(handlers::claim_guest_account_handler),
+        )
 }
@@ -4,11 +4,13 @@
 //! - JWT token validation
 //! - User model structure
 //! - Claims structure
+//! - Apple identity token verification and signing key refetch limits
 
 #[cfg(test)]
 mod tests {
     use super::super::*;
     use jsonwebtoken::{decode, encode, Algorithm, DecodingKey, EncodingKey, Header, Validation};
+    use std::time::Duration;
 
     #[test]
     fn test_claims_structure() {
@@ -93,6 +95,7 @@ mod tests {
             provider: Some("google".to_string()),
             provider_id: Some("google-123".to_string()),
             created_at: Some("2024-01-01".to_string()),
+            merged_into: None,
         };
 
         assert_eq!(user.id, "user-123");
@@ -106,8 +109,90 @@ mod tests {
         // Test GoogleIdTokenPayload can be created
         let payload = models::GoogleIdTokenPayload {
             id_token: "test_token_string".to_string(),
+            consent: None,
         };
 
         assert_eq!(payload.id_token, "test_token_string");
     }
+
+    /// Public half of `testdata/apple_test_key.pem`, standing in for one of Apple's keys
+    fn apple_test_key() -> apple::AppleKey {
+        apple::AppleKey {
+            kid: "test-kid".to_string(),
+            n: "xnIvvt5tlLN94XfGP_mnVJbyRUHvNOtD8n96J4m2XIkGruaRXQ56hY6DoUkoG9L0Hu7dwf8s51wB_fnIITOvacfeRqkjfEdcp_t6z9udL4RUNu0qR7ITUqESG4WodaZGJqqvdVDGJbaXgHG8TVJ1Fts0MbWghINCcpSIwh9ENW_r9x_F1mjWGuQYzhqKWJjivl6Jf7pVYsd5sjwoJMdBM0LRDWv8u--giY8eFGyyfbSJVdqZXJeBtpH07VUrFyMvGwPNAyPuhqgB4LBFNvjyXqD_jiFXyGbUqxlkEQ3DZrWkKKK3UgEXi_3MTmOJKX-kc3WsXsL5bEkiOCYkyK5kwQ".to_string(),
+            e: "AQAB".to_string(),
+        }
+    }
+
+    fn apple_token(claims: serde_json::Value) -> String {
+        let mut header = Header::new(Algorithm::RS256);
+        header.kid = Some("test-kid".to_string());
+        encode(
+            &header,
+            &claims,
+            &EncodingKey::from_rsa_pem(include_bytes!("testdata/apple_test_key.pem")).unwrap(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_apple_identity_token_verification() {
+        let client_ids = vec!["com.example.jobs".to_string()];
+        let token = apple_token(serde_json::json!({
+            "iss": apple::APPLE_ISSUER,
+            "aud": "com.example.jobs",
+            "sub": "001234.abcd",
+            "exp": 9999999999u64,
+            "email": "xyz123@privaterelay.appleid.com",
+            "email_verified": "true",
+            "is_private_email": "true",
+        }));
+
+        let identity = apple::verify_with_key(&token, &apple_test_key(), &client_ids)
+            .expect("valid Apple token should verify");
+        assert_eq!(identity.sub, "001234.abcd");
+        assert!(identity.email_verified);
+        assert!(identity.has_private_email());
+
+        let other_app = vec!["com.example.other".to_string()];
+        assert!(apple::verify_with_key(&token, &apple_test_key(), &other_app).is_err());
+
+        let wrong_issuer = apple_token(serde_json::json!({
+            "iss": "https://accounts.google.com",
+            "aud": "com.example.jobs",
+            "sub": "001234.abcd",
+            "exp": 9999999999u64,
+        }));
+        assert!(apple::verify_with_key(&wrong_issuer, &apple_test_key(), &client_ids).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_apple_unknown_key_does_not_refetch_within_interval() {
+        // Any fetch fails fast, so `Ok(None)` means none was attempted
+        let offline = reqwest::Client::builder()
+            .proxy(reqwest::Proxy::all("http://127.0.0.1:9").unwrap())
+            .build()
+            .unwrap();
+
+        let fresh = apple::AppleKeyCache::with_keys(vec![apple_test_key()], Duration::ZERO);
+        let known = fresh.key(&offline, "test-kid").await.unwrap();
+        assert_eq!(known.map(|k| k.kid), Some("test-kid".to_string()));
+        for _ in 0..3 {
+            assert!(fresh.key(&offline, "forged").await.unwrap().is_none());
+        }
+
+        let stale =
+            apple::AppleKeyCache::with_keys(vec![apple_test_key()], Duration::from_secs(2 * 60));
+        assert!(stale.key(&offline, "forged").await.is_err());
+        // The failed attempt counts, so the next unknown key waits out the interval too
+        assert!(stale.key(&offline, "forged").await.unwrap().is_none());
+    }
+
+    #[test]
+    fn test_apple_private_relay_detection() {
+        assert!(apple::is_private_relay("xyz123@privaterelay.appleid.com"));
+        assert!(apple::is_private_relay("XYZ@PrivateRelay.AppleID.com"));
+        assert!(!apple::is_private_relay("ada@example.com"));
+        assert!(!apple::is_private_relay("privaterelay.appleid.com"));
+    }
 }
@@ -0,0 +1,424 @@
+// body_limit_middleware.rs
+//! Request body size limits and upload validation
+//!
+//! Every body is capped by route: JSON endpoints get a small cap, upload endpoints one sized
+//! to the files their handler accepts. A body over its cap gets a JSON 413, whether the
+//! Content-Length gives it away up front or it only shows while streaming. Multipart uploads
+//! are also checked file by file before the handler runs: the content is sniffed and has to
+//! agree with the filename's extension and the declared Content-Type, and executables are
+//! refused.
+
+use crate::candidates::handlers::video_uploads::CHUNK_SIZE;
+use crate::common::error::localized_message;
+use crate::common::request_id::current_request_id;
+use crate::common::ErrorCode;
+use crate::services::documents::MAX_DOCUMENT_SIZE;
+use crate::services::interview_artifacts::MAX_RECORDING_SIZE;
+use axum::{
+    body::{Body, Bytes},
+    extract::{FromRequest, Multipart, Request},
+    http::{header, HeaderMap, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+    Json,
+};
+use http_body_util::{LengthLimitError, Limited};
+use serde::Serialize;
+use std::path::Path;
+use tracing::{debug, warn};
+
+const MB: usize = 1024 * 1024;
+
+/// Room for multipart boundaries and the form fields sent alongside a file
+const MULTIPART_OVERHEAD: usize = MB;
+
+/// What a route's body holds, which decides how large it may be
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BodyClass {
+    Json,
+    Image,
+    Document,
+    Recording,
+    Video,
+    VideoChunk,
+}
+
+impl BodyClass {
+    /// Environment variable overriding the limit, in bytes
+    fn env_var(&self) -> &'static str {
+        match self {
+            BodyClass::Json => "BODY_LIMIT_JSON_BYTES",
+            BodyClass::Image => "BODY_LIMIT_IMAGE_BYTES",
+            BodyClass::Document => "BODY_LIMIT_DOCUMENT_BYTES",
+            BodyClass::Recording => "BODY_LIMIT_RECORDING_BYTES",
+            BodyClass::Video => "BODY_LIMIT_VIDEO_BYTES",
+            BodyClass::VideoChunk => "BODY_LIMIT_VIDEO_CHUNK_BYTES",
+        }
+    }
+
+    fn default_limit(&self) -> usize {
+        match self {
+            BodyClass::Json => MB,
+            BodyClass::Image => 10 * MB + MULTIPART_OVERHEAD,
+            BodyClass::Document => MAX_DOCUMENT_SIZE + MULTIPART_OVERHEAD,
+            BodyClass::Recording => MAX_RECORDING_SIZE + MULTIPART_OVERHEAD,
+            BodyClass::Video => 100 * MB + MULTIPART_OVERHEAD,
+            // Chunks are sent raw, so only the last one can fall short of a full chunk
+            BodyClass::VideoChunk => CHUNK_SIZE as usize + 1024,
+        }
+    }
+
+    fn limit(&self) -> usize {
+        std::env::var(self.env_var())
+            .ok()
+            .and_then(|v| v.parse::<usize>().ok())
+            .filter(|n| *n > 0)
+            .unwrap_or_else(|| self.default_limit())
+    }
+}
+
+/// Routes that take more than a JSON body; `:param` matches any one segment
+const UPLOAD_ROUTES: &[(&str, BodyClass)] = &[
+    ("/api/user/videos/uploads/:id", BodyClass::VideoChunk),
+    ("/api/user/videos", BodyClass::Video),
+    ("/api/applications/:id/video", BodyClass::Video),
+    ("/api/interviews/:id/artifacts", BodyClass::Recording),
+    ("/api/resumes", BodyClass::Document),
+    ("/api/applications/:id/resume", BodyClass::Document),
+    ("/api/jobs/:id/guest-apply", BodyClass::Document),
+    ("/api/me/document-requests/:id/upload", BodyClass::Document),
+    ("/api/admin/companies/import", BodyClass::Document),
+    ("/api/admin/job-templates/import", BodyClass::Document),
+    ("/api/user/avatar", BodyClass::Image),
+    ("/api/admin/logo/upload", BodyClass::Image),
+    ("/api/admin/companies/:id/assets", BodyClass::Image),
+    ("/api/admin/jobs/upload-image", BodyClass::Image),
+];
+
+/// Whether `path` matches a route `pattern`, where `:param` matches any one segment
+pub(crate) fn route_matches(pattern: &str, path: &str) -> bool {
+    let path = path.trim_end_matches('/');
+    let mut pattern_segments = pattern.split('/');
+    let mut path_segments = path.split('/');
+    loop {
+        match (pattern_segments.next(), path_segments.next()) {
+            (None, None) => return true,
+            (Some(p), Some(s)) if p.starts_with(':') && !s.is_empty() => {}
+            (Some(p), Some(s)) if p == s => {}
+            _ => return false,
+        }
+    }
+}
+
+fn classify(path: &str) -> BodyClass {
+    UPLOAD_ROUTES
+        .iter()
+        .find(|(pattern, _)| route_matches(pattern, path))
+        .map(|(_, class)| *class)
+        .unwrap_or(BodyClass::Json)
+}
+
+/// Accepted Content-Types for each file extension. Sniffed types are checked against the same
+/// list, so container formats a sniffer can't tell apart (docx and zip, webm audio and video)
+/// list every type they may come back as.
+const EXTENSION_TYPES: &[(&str, &[&str])] = &[
+    ("pdf", &["application/pdf"]),
+    ("doc", &["application/msword", "application/x-ole-storage"]),
+    (
+        "docx",
+        &[
+            "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+            "application/zip",
+        ],
+    ),
+    ("txt", &["text/plain"]),
+    ("md", &["text/markdown", "text/x-markdown", "text/plain"]),
+    ("vtt", &["text/vtt", "text/plain"]),
+    ("srt", &["application/x-subrip", "text/srt", "text/plain"]),
+    (
+        "csv",
+        &["text/csv", "text/plain", "application/vnd.ms-excel"],
+    ),
+    ("png", &["image/png"]),
+    ("jpg", &["image/jpeg", "image/jpg", "image/pjpeg"]),
+    ("jpeg", &["image/jpeg", "image/jpg", "image/pjpeg"]),
+    ("gif", &["image/gif"]),
+    ("webp", &["image/webp"]),
+    ("svg", &["image/svg+xml", "text/xml", "application/xml"]),
+    ("mp4", &["video/mp4", "audio/mp4", "video/x-m4v"]),
+    ("m4v", &["video/x-m4v", "video/mp4"]),
+    (
+        "m4a",
+        &["audio/mp4", "audio/m4a", "audio/x-m4a", "video/mp4"],
+    ),
+    ("mov", &["video/quicktime", "video/mp4"]),
+    ("webm", &["video/webm", "audio/webm", "video/x-matroska"]),
+    ("mkv", &["video/x-matroska", "video/webm"]),
+    ("avi", &["video/x-msvideo", "video/avi", "video/msvideo"]),
+    ("mp3", &["audio/mpeg", "audio/mp3"]),
+    (
+        "wav",
+        &["audio/wav", "audio/x-wav", "audio/wave", "audio/vnd.wave"],
+    ),
+    ("ogg", &["audio/ogg", "video/ogg", "application/ogg"]),
+];
+
+/// Declared types that say nothing about the content
+const GENERIC_TYPES: &[&str] = &["application/octet-stream", "binary/octet-stream"];
+
+const EXECUTABLE_EXTENSIONS: &[&str] = &[
+    "exe", "dll", "com", "bat", "cmd", "msi", "scr", "ps1", "sh", "vbs", "jar", "apk", "app",
+];
+
+fn extension_types(extension: &str) -> Option<&'static [&'static str]> {
+    EXTENSION_TYPES
+        .iter()
+        .find(|(ext, _)| *ext == extension)
+        .map(|(_, types)| *types)
+}
+
+/// Check one uploaded file's name, declared type and content against each other
+fn check_file(filename: &str, declared_type: Option<&str>, data: &[u8]) -> Result<(), String> {
+    let extension = Path::new(filename)
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_ascii_lowercase());
+    let sniffed = infer::get(data);
+
+    let is_executable_name = extension
+        .as_deref()
+        .is_some_and(|ext| EXECUTABLE_EXTENSIONS.contains(&ext));
+    let is_executable_content =
+        sniffed.is_some_and(|kind| kind.matcher_type() == infer::MatcherType::App);
+    if is_executable_name || is_executable_content {
+        return Err(format!(
+            "'{}' is an executable file, which is not accepted",
+            filename
+        ));
+    }
+
+    let Some(expected) = extension.as_deref().and_then(extension_types) else {
+        // Unfamiliar extensions are left to the handler's own allow-list
+        return Ok(());
+    };
+
+    if let Some(kind) = sniffed {
+        if !expected.contains(&kind.mime_type()) {
+            return Err(format!(
+                "The content of '{}' is {}, which does not match its extension",
+                filename,
+                kind.mime_type()
+            ));
+        }
+    }
+
+    let declared = declared_type
+        .map(|t| {
+            t.split(';')
+                .next()
+                .unwrap_or("")
+                .trim()
+                .to_ascii_lowercase()
+        })
+        .filter(|t| !t.is_empty() && !GENERIC_TYPES.contains(&t.as_str()));
+    if let Some(declared) = declared {
+        if !expected.contains(&declared.as_str()) {
+            return Err(format!(
+                "'{}' was sent as {}, which does not match its extension",
+                filename, declared
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Check every file in a buffered multipart body. A body that doesn't parse is let through
+/// for the handler to reject with its own message.
+async fn check_multipart(headers: &HeaderMap, body: Bytes) -> Result<(), String> {
+    let mut request = Request::new(Body::from(body));
+    if let Some(content_type) = headers.get(header::CONTENT_TYPE) {
+        request
+            .headers_mut()
+            .insert(header::CONTENT_TYPE, content_type.clone());
+    }
+    let mut multipart = match Multipart::from_request(request, &()).await {
+        Ok(multipart) => multipart,
+        Err(e) => {
+            debug!(error = %e, "Multipart body not inspected");
+            return Ok(());
+        }
+    };
+
+    loop {
+        let field = match multipart.next_field().await {
+            Ok(Some(field)) => field,
+            Ok(None) => return Ok(()),
+            Err(e) => {
+                debug!(error = %e, "Multipart body not inspected");
+                return Ok(());
+            }
+        };
+        let Some(filename) = field.file_name().map(str::to_string) else {
+            continue;
+        };
+        let declared_type = field.content_type().map(str::to_string);
+        let Ok(data) = field.bytes().await else {
+            return Ok(());
+        };
+        check_file(&filename, declared_type.as_deref(), &data)?;
+    }
+}
+
+#[derive(Serialize)]
+struct PayloadTooLargeResponse {
+    error: String,
+    code: String,
+    limit_bytes: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    request_id: Option<String>,
+}
+
+fn payload_too_large(limit: usize) -> Response {
+    let code = ErrorCode::PayloadTooLarge;
+    let message = format!(
+        "Request body exceeds the {} byte limit for this endpoint",
+        limit
+    );
+    let body = PayloadTooLargeResponse {
+        error: localized_message(code, message),
+        code: code.as_str().to_string(),
+        limit_bytes: limit,
+        request_id: current_request_id().filter(|id| !id.is_empty()),
+    };
+    (code.status(), Json(body)).into_response()
+}
+
+/// Whether reading a body failed because it ran past its limit
+pub(crate) fn is_length_limit(error: &axum::Error) -> bool {
+    let mut source: Option<&(dyn std::error::Error + 'static)> = Some(error);
+    while let Some(e) = source {
+        if e.is::<LengthLimitError>() {
+            return true;
+        }
+        source = e.source();
+    }
+    false
+}
+
+/// Body limit and upload validation middleware
+pub async fn body_limit_middleware(request: Request, next: Next) -> Response {
+    let path = request.uri().path().to_string();
+    let limit = classify(&path).limit();
+
+    let declared_length = request
+        .headers()
+        .get(header::CONTENT_LENGTH)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse::<usize>().ok());
+    if declared_length.is_some_and(|length| length > limit) {
+        warn!(path = %path, limit = limit, length = ?declared_length, "Request body too large");
+        return payload_too_large(limit);
+    }
+
+    let is_multipart = request
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.starts_with("multipart/form-data"));
+
+    let (parts, body) = request.into_parts();
+    let request = if is_multipart {
+        // Handlers buffer uploads anyway; reading the body here first lets the files be
+        // checked before any handler work, then the same bytes are handed on
+        let bytes = match axum::body::to_bytes(body, limit).await {
+            Ok(bytes) => bytes,
+            Err(e) if is_length_limit(&e) => {
+                warn!(path = %path, limit = limit, "Request body too large");
+                return payload_too_large(limit);
+            }
+            Err(e) => {
+                debug!(path = %path, error = %e, "Failed to read request body");
+                return crate::common::ApiError::BadRequest(
+                    "Failed to read request body".to_string(),
+                )
+                .into_response();
+            }
+        };
+        if let Err(message) = check_multipart(&parts.headers, bytes.clone()).await {
+            warn!(path = %path, reason = %message, "Upload rejected");
+            return crate::common::ApiError::Coded(ErrorCode::UnsupportedMediaType, message)
+                .into_response();
+        }
+        Request::from_parts(parts, Body::from(bytes))
+    } else {
+        Request::from_parts(parts, Body::new(Limited::new(body, limit)))
+    };
+
+    let response = next.run(request).await;
+
+    // Extractors that hit the limit while streaming answer with a plain-text 413
+    let is_json = response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.starts_with("application/json"));
+    if response.status() == StatusCode::PAYLOAD_TOO_LARGE && !is_json {
+        return payload_too_large(limit);
+    }
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PNG: &[u8] = &[
+        0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A, 0, 0, 0, 0x0D,
+    ];
+    const PDF: &[u8] = b"%PDF-1.7\n%\xe2\xe3\xcf\xd3\n";
+
+    #[test]
+    fn test_classify_routes() {
+        assert_eq!(classify("/api/jobs"), BodyClass::Json);
+        assert_eq!(classify("/api/user/videos"), BodyClass::Video);
+        assert_eq!(classify("/api/user/videos/uploads"), BodyClass::Json);
+        assert_eq!(
+            classify("/api/user/videos/uploads/VUP123"),
+            BodyClass::VideoChunk
+        );
+        assert_eq!(classify("/api/applications/A1/resume"), BodyClass::Document);
+        assert_eq!(
+            classify("/api/applications/A1/resume-history"),
+            BodyClass::Json
+        );
+        assert_eq!(classify("/api/resumes/"), BodyClass::Document);
+        assert_eq!(classify("/api/resumes/R1"), BodyClass::Json);
+        assert_eq!(classify("/api/admin/companies/C1/assets"), BodyClass::Image);
+    }
+
+    #[test]
+    fn test_check_file_consistency() {
+        assert!(check_file("resume.pdf", Some("application/pdf"), PDF).is_ok());
+        assert!(check_file("photo.PNG", Some("image/png"), PNG).is_ok());
+        // Generic declared types and unknown extensions defer to the handler
+        assert!(check_file("resume.pdf", Some("application/octet-stream"), PDF).is_ok());
+        assert!(check_file("notes.xyz", Some("text/plain"), b"hello").is_ok());
+        assert!(check_file("notes.txt", Some("text/plain; charset=utf-8"), b"hello").is_ok());
+
+        // Content disagrees with the extension
+        assert!(check_file("resume.pdf", Some("application/pdf"), PNG).is_err());
+        // Declared type disagrees with the extension
+        assert!(check_file("photo.png", Some("application/pdf"), PNG).is_err());
+    }
+
+    #[test]
+    fn test_check_file_rejects_executables() {
+        assert!(check_file("setup.exe", None, b"anything").is_err());
+        // A Windows executable renamed to look like a document
+        let mut exe = b"MZ".to_vec();
+        exe.resize(128, 0);
+        assert!(check_file("resume.pdf", Some("application/pdf"), &exe).is_err());
+        assert!(check_file("resume", None, &exe).is_err());
+    }
+}
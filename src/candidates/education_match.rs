@@ -0,0 +1,158 @@
+// src/candidates/education_match.rs
+
+use serde::Serialize;
+use serde_json::Value;
+
+/// Degree levels, lowest first; a higher level satisfies any lower requirement
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DegreeLevel {
+    HighSchool,
+    Diploma,
+    Graduate,
+    PostGraduate,
+    Doctorate,
+}
+
+/// Word sequences that identify each level, checked from the highest level down
+const LEVEL_TERMS: &[(DegreeLevel, &[&str])] = &[
+    (
+        DegreeLevel::Doctorate,
+        &["phd", "doctorate", "doctoral", "doctor of", "dphil", "edd"],
+    ),
+    (
+        DegreeLevel::PostGraduate,
+        &[
+            "post graduate",
+            "postgraduate",
+            "pg",
+            "master",
+            "masters",
+            "msc",
+            "ms",
+            "ma",
+            "mtech",
+            "me",
+            "mba",
+            "mca",
+            "mcom",
+            "mphil",
+            "md",
+        ],
+    ),
+    (
+        DegreeLevel::Graduate,
+        &[
+            "graduate",
+            "under graduate",
+            "undergraduate",
+            "ug",
+            "bachelor",
+            "bachelors",
+            "bsc",
+            "bs",
+            "ba",
+            "btech",
+            "be",
+            "bcom",
+            "bca",
+            "bba",
+            "mbbs",
+            "llb",
+        ],
+    ),
+    (
+        DegreeLevel::Diploma,
+        &["diploma", "associate", "associates", "polytechnic"],
+    ),
+    (
+        DegreeLevel::HighSchool,
+        &[
+            "high school",
+            "higher secondary",
+            "secondary",
+            "12th",
+            "hsc",
+            "ssc",
+            "ged",
+        ],
+    ),
+];
+
+/// Lowercased words with dots dropped, so "M.Sc." and "MSc" read the same
+fn normalize(text: &str) -> String {
+    let words: Vec<String> = text
+        .to_lowercase()
+        .replace(['.', '\''], "")
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(str::to_string)
+        .collect();
+    format!(" {} ", words.join(" "))
+}
+
+/// The level a degree name or requirement describes, if it names one
+pub fn classify_degree(text: &str) -> Option<DegreeLevel> {
+    let normalized = normalize(text);
+    LEVEL_TERMS.iter().find_map(|(level, terms)| {
+        terms
+            .iter()
+            .any(|term| normalized.contains(&format!(" {} ", term)))
+            .then_some(*level)
+    })
+}
+
+/// The minimum degree level a job asks for
+///
+/// `educational_qualifications` holds a list of `{ "degree_level": ... }` entries (plain
+/// strings are accepted too). Any listed level qualifies, so the lowest one is the bar.
+pub fn required_level(educational_qualifications: Option<&str>) -> Option<DegreeLevel> {
+    let value: Value = serde_json::from_str(educational_qualifications?).ok()?;
+    let entries = match value {
+        Value::Array(entries) => entries,
+        other => vec![other],
+    };
+    entries
+        .iter()
+        .filter_map(|entry| match entry {
+            Value::String(text) => Some(text.as_str()),
+            Value::Object(fields) => fields.get("degree_level").and_then(Value::as_str),
+            _ => None,
+        })
+        .filter_map(classify_degree)
+        .min()
+}
+
+/// Degree names from a scanned resume's `parsed_json`
+pub fn resume_degrees(parsed_json: Option<&str>) -> Vec<String> {
+    let Some(parsed) = parsed_json.and_then(|p| serde_json::from_str::<Value>(p).ok()) else {
+        return Vec::new();
+    };
+    let education = parsed
+        .get("extracted_data")
+        .unwrap_or(&parsed)
+        .get("education")
+        .and_then(Value::as_array);
+    education
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| entry.get("degree").and_then(Value::as_str))
+        .map(str::to_string)
+        .collect()
+}
+
+/// The highest level among a candidate's degrees
+pub fn highest_level<S: AsRef<str>>(degrees: &[S]) -> Option<DegreeLevel> {
+    degrees
+        .iter()
+        .filter_map(|d| classify_degree(d.as_ref()))
+        .max()
+}
+
+/// Whether a candidate meets a job's education bar; `None` when the job sets none
+pub fn education_match(
+    required: Option<DegreeLevel>,
+    candidate: Option<DegreeLevel>,
+) -> Option<bool> {
+    required.map(|required| candidate.is_some_and(|level| level >= required))
+}
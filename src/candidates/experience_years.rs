@@ -0,0 +1,252 @@
+// src/candidates/experience_years.rs
+
+use chrono::{Datelike, NaiveDate, Utc};
+use serde_json::Value;
+use sqlx::SqlitePool;
+use std::collections::HashSet;
+
+const MONTHS: &[&str] = &[
+    "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+];
+
+/// End dates that mean the role is still held
+const ONGOING: &[&str] = &["present", "current", "now", "ongoing", "today", "till date"];
+
+/// Title words that describe seniority or are filler rather than the kind of work
+const GENERIC_TITLE_WORDS: &[&str] = &[
+    "a",
+    "an",
+    "and",
+    "of",
+    "the",
+    "for",
+    "in",
+    "to",
+    "senior",
+    "junior",
+    "lead",
+    "principal",
+    "staff",
+    "head",
+    "associate",
+    "assistant",
+    "intern",
+    "trainee",
+    "sr",
+    "jr",
+    "i",
+    "ii",
+    "iii",
+    "iv",
+];
+
+/// Total and job-relevant experience from a scanned resume, in years to one decimal
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ExperienceSummary {
+    pub total_years: f64,
+    pub relevant_years: f64,
+}
+
+/// Months since year 0, so ranges can be compared and merged as integers
+fn month_index(year: i32, month: u32) -> i32 {
+    year * 12 + month as i32 - 1
+}
+
+fn parse_year(text: &str) -> Option<i32> {
+    let year: i32 = text.parse().ok()?;
+    (1900..=2200).contains(&year).then_some(year)
+}
+
+fn parse_month_name(text: &str) -> Option<u32> {
+    let prefix = text.get(..3)?;
+    MONTHS
+        .iter()
+        .position(|m| *m == prefix)
+        .map(|i| i as u32 + 1)
+}
+
+/// Parses `YYYY-MM`, `YYYY-MM-DD`, `MM/YYYY`, `Mar 2020`, `March 2020` or a bare `YYYY`
+///
+/// A bare year has no month, so `is_end` picks December for an end date and January for a
+/// start, counting the whole year the candidate says they were there.
+pub fn parse_month(text: &str, is_end: bool) -> Option<i32> {
+    let text = text.trim().to_lowercase();
+    let parts: Vec<&str> = text
+        .split(|c: char| c == '-' || c == '/' || c == '.' || c == ',' || c.is_whitespace())
+        .filter(|p| !p.is_empty())
+        .collect();
+
+    let (year, month) = match parts.as_slice() {
+        [year] => (parse_year(year)?, if is_end { 12 } else { 1 }),
+        [first, second, ..] => {
+            if let Some(year) = parse_year(first) {
+                (year, second.parse().ok()?)
+            } else if let Some(month) = parse_month_name(first) {
+                (parse_year(second)?, month)
+            } else {
+                (parse_year(parts.last()?)?, first.parse().ok()?)
+            }
+        }
+        [] => return None,
+    };
+
+    (1..=12).contains(&month).then(|| month_index(year, month))
+}
+
+fn is_ongoing(entry: &Value, end: Option<&str>) -> bool {
+    if entry.get("is_current").and_then(Value::as_bool) == Some(true) {
+        return true;
+    }
+    match end.map(|e| e.trim().to_lowercase()) {
+        None => true,
+        Some(end) => end.is_empty() || ONGOING.contains(&end.as_str()),
+    }
+}
+
+/// A role's months as a half-open range, or `None` when the dates cannot be read
+fn role_range(entry: &Value, today: NaiveDate) -> Option<(i32, i32)> {
+    let start = parse_month(entry.get("start_date").and_then(Value::as_str)?, false)?;
+    let end_text = entry.get("end_date").and_then(Value::as_str);
+    let current = month_index(today.year(), today.month());
+    let end = if is_ongoing(entry, end_text) {
+        current
+    } else {
+        parse_month(end_text?, true)?
+    };
+    let end = end.min(current) + 1;
+    (end > start).then_some((start, end))
+}
+
+/// Months covered by the ranges, with overlapping roles counted once
+fn covered_months(mut ranges: Vec<(i32, i32)>) -> i32 {
+    ranges.sort_unstable();
+    let mut total = 0;
+    let mut current: Option<(i32, i32)> = None;
+    for (start, end) in ranges {
+        match current {
+            Some((s, e)) if start <= e => current = Some((s, e.max(end))),
+            Some((s, e)) => {
+                total += e - s;
+                current = Some((start, end));
+            }
+            None => current = Some((start, end)),
+        }
+    }
+    total + current.map_or(0, |(s, e)| e - s)
+}
+
+fn to_years(months: i32) -> f64 {
+    (months as f64 / 12.0 * 10.0).round() / 10.0
+}
+
+fn title_words(title: &str) -> HashSet<String> {
+    title
+        .to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty() && !GENERIC_TITLE_WORDS.contains(w))
+        .map(str::to_string)
+        .collect()
+}
+
+/// Experience totals from a scanned resume's `parsed_json`
+///
+/// A role counts as relevant when its title shares a word with `job_title`, ignoring
+/// seniority words, so "Senior Backend Engineer" is relevant to "Backend Developer".
+/// Returns `None` when the resume has not been scanned.
+pub fn summarize(
+    parsed_json: Option<&str>,
+    job_title: Option<&str>,
+    today: NaiveDate,
+) -> Option<ExperienceSummary> {
+    let parsed: Value = serde_json::from_str(parsed_json?).ok()?;
+    let entries = parsed
+        .get("extracted_data")
+        .unwrap_or(&parsed)
+        .get("experience")?
+        .as_array()?;
+
+    let job_words = job_title.map(title_words).unwrap_or_default();
+    let mut all = Vec::new();
+    let mut relevant = Vec::new();
+    for entry in entries {
+        let Some(range) = role_range(entry, today) else {
+            continue;
+        };
+        all.push(range);
+        let title = entry.get("title").and_then(Value::as_str).unwrap_or("");
+        if !title_words(title).is_disjoint(&job_words) {
+            relevant.push(range);
+        }
+    }
+
+    Some(ExperienceSummary {
+        total_years: to_years(covered_months(all)),
+        relevant_years: to_years(covered_months(relevant)),
+    })
+}
+
+/// Recomputes a resume's total experience and that of every application it is attached to
+pub async fn refresh_resume(pool: &SqlitePool, resume_id: &str) -> Result<(), sqlx::Error> {
+    let parsed_json: Option<Option<String>> =
+        sqlx::query_scalar("SELECT parsed_json FROM resumes WHERE id = ?")
+            .bind(resume_id)
+            .fetch_optional(pool)
+            .await?;
+    let Some(parsed_json) = parsed_json else {
+        return Ok(());
+    };
+
+    let summary = summarize(parsed_json.as_deref(), None, Utc::now().date_naive());
+    sqlx::query("UPDATE resumes SET experience_years = ? WHERE id = ?")
+        .bind(summary.map(|s| s.total_years))
+        .bind(resume_id)
+        .execute(pool)
+        .await?;
+
+    let application_ids: Vec<String> =
+        sqlx::query_scalar("SELECT id FROM applications WHERE resume_id = ?")
+            .bind(resume_id)
+            .fetch_all(pool)
+            .await?;
+    for application_id in application_ids {
+        refresh_application(pool, &application_id).await?;
+    }
+    Ok(())
+}
+
+/// Recomputes an application's experience from its resume, measured against the job title
+pub async fn refresh_application(
+    pool: &SqlitePool,
+    application_id: &str,
+) -> Result<Option<ExperienceSummary>, sqlx::Error> {
+    let row: Option<(Option<String>, Option<String>)> = sqlx::query_as(
+        r#"
+        SELECT r.parsed_json, j.title
+        FROM applications a
+        LEFT JOIN resumes r ON r.id = a.resume_id
+        LEFT JOIN jobs j ON j.id = a.job_id
+        WHERE a.id = ?
+        "#,
+    )
+    .bind(application_id)
+    .fetch_optional(pool)
+    .await?;
+    let Some((parsed_json, job_title)) = row else {
+        return Ok(None);
+    };
+
+    let summary = summarize(
+        parsed_json.as_deref(),
+        job_title.as_deref(),
+        Utc::now().date_naive(),
+    );
+    sqlx::query(
+        "UPDATE applications SET experience_years = ?, relevant_experience_years = ? WHERE id = ?",
+    )
+    .bind(summary.map(|s| s.total_years))
+    .bind(summary.map(|s| s.relevant_years))
+    .bind(application_id)
+    .execute(pool)
+    .await?;
+    Ok(summary)
+}
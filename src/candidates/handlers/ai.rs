@@ -105,7 +105,7 @@ pub async fn generate_candidate_email(
     );
 
     let result = state
-        .openai_service
+        .llm
         .generate_text(
             TextGenerationPurpose::EmailGeneration,
             &prompt,
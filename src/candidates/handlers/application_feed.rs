@@ -0,0 +1,144 @@
+// src/candidates/handlers/application_feed.rs
+//! Tokenized feeds of the caller's application progress
+
+use axum::{
+    extract::{Extension, Path},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::{info, warn};
+
+use crate::auth::AuthedUser;
+use crate::candidates::models::{ApplicationFeedLinks, CalendarFeedStatus};
+use crate::common::{ApiError, AppState};
+use crate::services::{application_feed, calendar_feed};
+
+const FEED_CACHE_CONTROL: &str = "private, max-age=300";
+
+/// GET /api/me/application-feed - Whether a feed exists and when it was last fetched
+pub async fn get_application_feed(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+) -> Result<Json<CalendarFeedStatus>, ApiError> {
+    let state = state_lock.read().await.clone();
+
+    let row: Option<(Option<String>, Option<String>)> = sqlx::query_as(
+        "SELECT created_at, last_accessed_at FROM application_feed_tokens WHERE user_id = ?",
+    )
+    .bind(&authed.id)
+    .fetch_optional(&state.db)
+    .await
+    .map_err(ApiError::DatabaseError)?;
+
+    Ok(Json(match row {
+        Some((created_at, last_accessed_at)) => CalendarFeedStatus {
+            active: true,
+            created_at,
+            last_accessed_at,
+        },
+        None => CalendarFeedStatus {
+            active: false,
+            created_at: None,
+            last_accessed_at: None,
+        },
+    }))
+}
+
+/// POST /api/me/application-feed - Create the feed, or replace its URLs so old subscriptions stop
+pub async fn regenerate_application_feed(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+) -> Result<Json<ApplicationFeedLinks>, ApiError> {
+    let state = state_lock.read().await.clone();
+
+    let token = application_feed::regenerate_token(&state.db, &authed.id)
+        .await
+        .map_err(ApiError::DatabaseError)?;
+
+    info!(user_id = %authed.id, "Application feed token generated");
+
+    let base = format!("{}/feeds/applications/{}", state.config.backend_url, token);
+    let ics_url = format!("{}.ics", base);
+    let webcal_url = match ics_url.split_once("://") {
+        Some((_, rest)) => format!("webcal://{}", rest),
+        None => ics_url.clone(),
+    };
+    Ok(Json(ApplicationFeedLinks {
+        rss_url: format!("{}.rss", base),
+        ics_url,
+        webcal_url,
+    }))
+}
+
+/// DELETE /api/me/application-feed - Turn the feed off
+pub async fn revoke_application_feed(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+) -> Result<StatusCode, ApiError> {
+    let state = state_lock.read().await.clone();
+
+    sqlx::query("DELETE FROM application_feed_tokens WHERE user_id = ?")
+        .bind(&authed.id)
+        .execute(&state.db)
+        .await
+        .map_err(ApiError::DatabaseError)?;
+
+    info!(user_id = %authed.id, "Application feed revoked");
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// GET /feeds/applications/:file - `<token>.rss` for status changes, `<token>.ics` for interviews
+///
+/// The token is the only credential.
+pub async fn serve_application_feed(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    Path(file): Path<String>,
+) -> Result<Response, ApiError> {
+    let state = state_lock.read().await.clone();
+    let not_found = || ApiError::NotFound("Application feed not found".to_string());
+
+    let (token, format) = file.rsplit_once('.').ok_or_else(not_found)?;
+    if format != "rss" && format != "ics" {
+        return Err(not_found());
+    }
+    let Some(user_id) = application_feed::resolve_token(&state.db, token)
+        .await
+        .map_err(ApiError::DatabaseError)?
+    else {
+        warn!("Application feed requested with an unknown token");
+        return Err(not_found());
+    };
+
+    let (content_type, body) = if format == "rss" {
+        let events = application_feed::application_events(&state.db, &user_id)
+            .await
+            .map_err(ApiError::DatabaseError)?;
+        let frontend = &state.config.frontend_url;
+        let body = application_feed::render_rss(
+            &events,
+            &format!("{}/feeds/applications/{}", state.config.backend_url, file),
+            &format!("{}/applications", frontend),
+            |id| format!("{}/jobs/{}", frontend, id),
+        );
+        ("application/rss+xml; charset=utf-8", body)
+    } else {
+        let interviews = calendar_feed::candidate_interviews(&state.db, &user_id)
+            .await
+            .map_err(ApiError::DatabaseError)?;
+        let body = calendar_feed::render_candidate_ics(&interviews, chrono::Utc::now());
+        ("text/calendar; charset=utf-8", body)
+    };
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, content_type),
+            (header::CACHE_CONTROL, FEED_CACHE_CONTROL),
+        ],
+        body,
+    )
+        .into_response())
+}
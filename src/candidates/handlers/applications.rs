@@ -1,15 +1,35 @@
 // src/candidates/handlers/applications.rs
 
 use crate::auth::AuthedUser;
+use crate::candidates::education_match;
+use crate::candidates::experience_years;
 use crate::candidates::models::*;
-use crate::candidates::validators::ApplicationValidator;
-use crate::common::{generate_application_id, generate_history_id, ApiError, AppState, Validator};
-use axum::extract::{Extension, Json, Path};
+use crate::candidates::requirements_match::{
+    compare_requirements, parse_requirements, ApplicationRequirementsMatch,
+};
+use crate::candidates::validators::{evaluate_reapplication, ApplicationValidator};
+use crate::common::{generate_application_id, generate_history_id, ApiError, AppState, ErrorCode, Validator};
+use crate::common::timezone::parse_stored;
+use crate::jobs::services::capacity::{self, Admission};
+use crate::jobs::services::deadlines;
+use crate::messages::models::ScheduleInput;
+use crate::services::monitoring::{self, SecurityActivity};
+use crate::services::chat_webhooks::{self, ChatEvent};
+use crate::services::consent;
+use crate::services::interview_artifacts;
+use crate::services::knockout;
+use crate::services::org;
+use crate::services::promotions;
+use crate::services::rejection_feedback;
+use crate::services::sanitize::render_markdown;
+use crate::services::scheduled_messages::{self, NewScheduledMessage};
+use crate::services::snippets;
+use axum::extract::{Extension, Json, Path, Query};
 use serde::Serialize;
 use sqlx::Row;
 use std::sync::Arc;
 use tokio::sync::RwLock;
-use tracing::{info, warn};
+use tracing::{error, info, warn};
 
 #[derive(Debug, Serialize)]
 pub struct ApplicationAnalytics {
@@ -18,6 +38,14 @@ pub struct ApplicationAnalytics {
     pub applications_by_job: Vec<JobApplicationStats>,
     pub conversion_rates: ConversionRates,
     pub recent_applications: Vec<Application>,
+    pub withdrawal_reasons: Vec<WithdrawalReasonStats>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct WithdrawalReasonStats {
+    pub reason: String,
+    pub count: i64,
+    pub percentage: f64,
 }
 
 #[derive(Debug, Serialize)]
@@ -104,52 +132,86 @@ pub async fn create_application(
         return Err(ApiError::from(validation_result));
     }
 
-    let job_exists = sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM jobs WHERE id = ?")
-        .bind(&request.job_id)
-        .fetch_one(&state.db)
-        .await
-        .map_err(ApiError::DatabaseError)?;
+    let job_exists = sqlx::query_scalar::<_, i64>(
+        "SELECT COUNT(*) FROM jobs WHERE id = ? AND deleted_at IS NULL",
+    )
+    .bind(&request.job_id)
+    .fetch_one(&state.db)
+    .await
+    .map_err(ApiError::DatabaseError)?;
 
     if job_exists == 0 {
         return Err(ApiError::BadRequest("Job not found".to_string()));
     }
+    deadlines::ensure_accepting_applications(&state.db, &request.job_id).await?;
+    let admission = capacity::admit_application(&state.db, &request.job_id, &authed.id).await?;
+    let waitlisted_at = (admission == Admission::Waitlisted)
+        .then(|| chrono::Utc::now().format("%Y-%m-%d %H:%M:%S").to_string());
+
+    // Consent on the form is checked now and recorded once the application exists; without
+    // it the candidate must already have accepted the current privacy policy
+    let policy_version = consent::current_policy_version(&state.settings_service).await;
+    let accepted_version = match &request.consent {
+        Some(input) => Some(consent::validate_consent(
+            input,
+            policy_version.as_deref(),
+            true,
+        )?),
+        None => {
+            let status = consent::consent_status(&state.db, &authed.id, policy_version.clone())
+                .await
+                .map_err(ApiError::DatabaseError)?;
+            if status.reconsent_required {
+                return Err(ApiError::Coded(
+                    ErrorCode::ConsentRequired,
+                    format!(
+                        "Accept privacy policy version {} to apply",
+                        policy_version.unwrap_or_default()
+                    ),
+                ));
+            }
+            None
+        }
+    };
 
-    let existing_application = sqlx::query_scalar::<_, i64>(
-        "SELECT COUNT(*) FROM applications WHERE user_id = ? AND job_id = ?",
+    let existing_application = sqlx::query_as::<_, Application>(
+        "SELECT * FROM applications WHERE user_id = ? AND job_id = ? AND archived_at IS NULL",
     )
     .bind(&authed.id)
     .bind(&request.job_id)
-    .fetch_one(&state.db)
+    .fetch_optional(&state.db)
     .await
     .map_err(ApiError::DatabaseError)?;
 
-    if existing_application > 0 {
-        return Err(ApiError::BadRequest(
-            "You have already applied for this job".to_string(),
-        ));
+    if let Some(previous) = &existing_application {
+        enforce_reapplication_policy(&state, previous).await?;
     }
-
     if let Some(resume_id) = &request.resume_id {
-        let resume_exists = sqlx::query_scalar::<_, i64>(
-            "SELECT COUNT(*) FROM resumes WHERE id = ? AND user_id = ?",
-        )
-        .bind(resume_id)
-        .bind(&authed.id)
-        .fetch_one(&state.db)
-        .await
-        .map_err(ApiError::DatabaseError)?;
-
-        if resume_exists == 0 {
-            return Err(ApiError::BadRequest("Resume not found".to_string()));
-        }
+        ensure_resume_attachable(&state.db, resume_id, &authed.id).await?;
     }
 
     let application_id = generate_application_id();
+    let source = request
+        .source
+        .as_deref()
+        .and_then(promotions::normalize_source);
+
+    // Archiving the previous application and inserting its replacement happen together, so a
+    // failed insert never leaves the candidate without an active application
+    let mut tx = state.db.begin().await.map_err(ApiError::DatabaseError)?;
+
+    if let Some(previous) = &existing_application {
+        sqlx::query("UPDATE applications SET archived_at = datetime('now') WHERE id = ?")
+            .bind(&previous.id)
+            .execute(&mut *tx)
+            .await
+            .map_err(ApiError::DatabaseError)?;
+    }
 
     sqlx::query(
         r#"
-        INSERT INTO applications (id, user_id, job_id, resume_id, status, cover_letter, applied_at, updated_at)
-        VALUES (?, ?, ?, ?, 'submitted', ?, datetime('now'), datetime('now'))
+        INSERT INTO applications (id, user_id, job_id, resume_id, status, cover_letter, source, waitlisted_at, applied_at, updated_at)
+        VALUES (?, ?, ?, ?, 'submitted', ?, ?, ?, datetime('now'), datetime('now'))
         "#
     )
     .bind(&application_id)
@@ -157,24 +219,72 @@ pub async fn create_application(
     .bind(&request.job_id)
     .bind(request.resume_id.as_deref())
     .bind(request.cover_letter.as_deref())
-    .execute(&state.db)
+    .bind(&source)
+    .bind(waitlisted_at.as_deref())
+    .execute(&mut *tx)
     .await
     .map_err(ApiError::DatabaseError)?;
 
+    tx.commit().await.map_err(ApiError::DatabaseError)?;
+
+    if let Some(previous) = &existing_application {
+        info!(
+            user_id = %authed.id,
+            job_id = %request.job_id,
+            previous_application_id = %previous.id,
+            "Archived previous application for re-application"
+        );
+    }
+
+    let experience = match experience_years::refresh_application(&state.db, &application_id).await {
+        Ok(summary) => summary,
+        Err(e) => {
+            warn!(error = %e, application_id = %application_id, "Failed to store application experience years");
+            None
+        }
+    };
+
     let history_id = generate_history_id();
     sqlx::query(
         r#"
         INSERT INTO application_status_history (id, application_id, status, changed_by, notes, changed_at)
-        VALUES (?, ?, 'submitted', ?, 'Application submitted', datetime('now'))
+        VALUES (?, ?, 'submitted', ?, ?, datetime('now'))
         "#
     )
     .bind(&history_id)
     .bind(&application_id)
     .bind(&authed.id)
+    .bind(if waitlisted_at.is_some() {
+        "Application submitted and waitlisted"
+    } else {
+        "Application submitted"
+    })
     .execute(&state.db)
     .await
     .map_err(ApiError::DatabaseError)?;
 
+    if let (Some(input), Some(version)) = (&request.consent, &accepted_version) {
+        consent::record_consent(
+            &state.db,
+            &authed.id,
+            input,
+            version.as_deref(),
+            "application",
+            Some(&application_id),
+        )
+        .await
+        .map_err(ApiError::DatabaseError)?;
+    }
+
+    let knockout_input = knockout::KnockoutInput {
+        work_authorization: &request.work_authorization,
+        answers: &request.screening_answers,
+        experience_years: experience.map(|e| e.total_years),
+    };
+    if let Err(e) = knockout::screen_application(&state, &application_id, &knockout_input).await {
+        error!(error = %e, application_id = %application_id, "Failed to run knockout rules");
+    }
+
     let application = sqlx::query_as::<_, Application>("SELECT * FROM applications WHERE id = ?")
         .bind(&application_id)
         .fetch_one(&state.db)
@@ -185,12 +295,71 @@ pub async fn create_application(
         user_id = %authed.id,
         application_id = %application_id,
         job_id = %request.job_id,
+        waitlisted = waitlisted_at.is_some(),
         "Application created successfully"
     );
 
+    // A job that closes at its cap stops showing as soon as the last place is taken
+    match capacity::close_if_full(&state.db, &request.job_id).await {
+        Ok(true) => {
+            info!(job_id = %request.job_id, "Job closed at its application cap");
+            state.feed_cache.invalidate().await;
+        }
+        Ok(false) => {}
+        Err(e) => error!(
+            error = %e,
+            job_id = %request.job_id,
+            "Failed to check the job's application cap"
+        ),
+    }
+
+    // Let the job's hiring manager and recruiters know without delaying the candidate
+    let notify_state = state.clone();
+    let job_id = request.job_id.clone();
+    tokio::spawn(async move {
+        org::notify_new_application(&notify_state, &application_id, &job_id).await;
+    });
+    chat_webhooks::dispatch(&state, ChatEvent::NewApplication, &application.id);
+
     Ok(Json(application))
 }
 
+/// GET /api/jobs/:id/screening-questions - Questions to answer when applying to a job
+pub async fn get_job_screening_questions(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    Path(job_id): Path<String>,
+) -> Result<Json<ScreeningForm>, ApiError> {
+    let state = state_lock.read().await.clone();
+
+    let rules = knockout::job_rules(&state.db, &job_id, true)
+        .await
+        .map_err(ApiError::DatabaseError)?;
+
+    let mut work_authorization_countries: Vec<String> = Vec::new();
+    let mut questions = Vec::new();
+    for rule in rules {
+        match (rule.rule_type.as_str(), rule.country, rule.question) {
+            ("work_authorization", Some(country), _)
+                if !work_authorization_countries
+                    .iter()
+                    .any(|c| c.eq_ignore_ascii_case(&country)) =>
+            {
+                work_authorization_countries.push(country);
+            }
+            ("question", _, Some(question)) => questions.push(ScreeningQuestion {
+                id: rule.id,
+                question,
+            }),
+            _ => {}
+        }
+    }
+
+    Ok(Json(ScreeningForm {
+        work_authorization_countries,
+        questions,
+    }))
+}
+
 /// GET /api/applications - Get all applications for the authenticated user
 pub async fn get_user_applications(
     Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
@@ -293,7 +462,7 @@ pub async fn get_application_details(
 
     let (candidate_name, candidate_email) = if authed.is_admin {
         let user_details = sqlx::query_as::<_, (Option<String>, String)>(
-            "SELECT name, email FROM users WHERE id = ?",
+            "SELECT u.name, COALESCE(p.contact_email, u.email) FROM users u LEFT JOIN profiles p ON p.user_id = u.id WHERE u.id = ?",
         )
         .bind(&application.user_id)
         .fetch_optional(&state.db)
@@ -316,12 +485,19 @@ pub async fn get_application_details(
     .await
     .map_err(ApiError::DatabaseError)?;
 
+    let interview_records = if authed.is_admin {
+        interview_artifacts::timeline_entries(&state.db, &application_id, &authed).await?
+    } else {
+        Vec::new()
+    };
+
     Ok(Json(ApplicationWithDetails {
         application,
         job_title,
         candidate_name,
         candidate_email,
         status_history,
+        interview_records,
     }))
 }
 
@@ -362,7 +538,7 @@ pub async fn update_application_status(
 
     // Validate status transition
     if let Err(msg) = super::email_templates::validate_status_transition(&existing_application.status, &request.status) {
-        return Err(ApiError::BadRequest(msg));
+        return Err(ApiError::Coded(ErrorCode::StageTransitionInvalid, msg));
     }
 
     // Log the status change
@@ -375,16 +551,22 @@ pub async fn update_application_status(
     );
 
     let current_stage = status_to_stage(&request.status);
+    let offer_expires_at = request
+        .offer_expires_at
+        .as_deref()
+        .and_then(parse_stored)
+        .map(|at| at.to_rfc3339());
 
     sqlx::query(
         r#"
         UPDATE applications 
-        SET status = ?, current_stage = ?, updated_at = datetime('now')
+        SET status = ?, current_stage = ?, offer_expires_at = ?, updated_at = datetime('now')
         WHERE id = ?
         "#,
     )
     .bind(&request.status)
     .bind(current_stage)
+    .bind(offer_expires_at.as_deref())
     .bind(&application_id)
     .execute(&state.db)
     .await
@@ -393,8 +575,8 @@ pub async fn update_application_status(
     let history_id = generate_history_id();
     sqlx::query(
         r#"
-        INSERT INTO application_status_history (id, application_id, status, changed_by, notes, changed_at)
-        VALUES (?, ?, ?, ?, ?, datetime('now'))
+        INSERT INTO application_status_history (id, application_id, status, changed_by, notes, reason_code, reason_details, changed_at)
+        VALUES (?, ?, ?, ?, ?, ?, ?, datetime('now'))
         "#
     )
     .bind(&history_id)
@@ -402,6 +584,8 @@ pub async fn update_application_status(
     .bind(&request.status)
     .bind(&authed.id)
     .bind(request.notes.as_deref())
+    .bind(request.withdrawal_reason.as_deref())
+    .bind(request.withdrawal_details.as_deref())
     .execute(&state.db)
     .await
     .map_err(ApiError::DatabaseError)?;
@@ -412,6 +596,10 @@ pub async fn update_application_status(
         .await
         .map_err(ApiError::DatabaseError)?;
 
+    if request.status == "hired" {
+        chat_webhooks::dispatch(&state, ChatEvent::OfferAccepted, &application_id);
+    }
+
     info!(
         application_id = %application_id,
         new_status = %request.status,
@@ -421,11 +609,405 @@ pub async fn update_application_status(
     Ok(Json(application))
 }
 
+/// PUT /api/applications/:id/resume - Swap the resume attached to an application
+///
+/// Only allowed while the application is still `submitted`, i.e. before review starts.
+pub async fn swap_application_resume(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Path(application_id): Path<String>,
+    Json(request): Json<SwapApplicationResumeRequest>,
+) -> Result<Json<Application>, ApiError> {
+    let state = state_lock.read().await.clone();
+
+    let validator = ApplicationValidator;
+    let validation_result = validator.validate(&request);
+    if !validation_result.is_valid {
+        return Err(ApiError::from(validation_result));
+    }
+
+    let application = sqlx::query_as::<_, Application>(
+        "SELECT * FROM applications WHERE id = ? AND user_id = ?",
+    )
+    .bind(&application_id)
+    .bind(&authed.id)
+    .fetch_optional(&state.db)
+    .await
+    .map_err(ApiError::DatabaseError)?
+    .ok_or_else(|| ApiError::NotFound("Application not found".to_string()))?;
+
+    if application.status != "submitted" {
+        warn!(
+            application_id = %application_id,
+            status = %application.status,
+            "Resume swap rejected: application already under review"
+        );
+        return Err(ApiError::BadRequest(
+            "The resume can only be changed before the application is reviewed".to_string(),
+        ));
+    }
+
+    if application.resume_id.as_deref() == Some(request.resume_id.as_str()) {
+        return Ok(Json(application));
+    }
+
+    ensure_resume_attachable(&state.db, &request.resume_id, &authed.id).await?;
+
+    // The swap and its history row land together, and only over the resume read above
+    let mut tx = state.db.begin().await.map_err(ApiError::DatabaseError)?;
+
+    let swapped = sqlx::query(
+        "UPDATE applications SET resume_id = ?, updated_at = datetime('now') WHERE id = ? AND status = 'submitted' AND resume_id IS ?",
+    )
+    .bind(&request.resume_id)
+    .bind(&application_id)
+    .bind(application.resume_id.as_deref())
+    .execute(&mut *tx)
+    .await
+    .map_err(ApiError::DatabaseError)?;
+
+    if swapped.rows_affected() == 0 {
+        let still_exists: bool =
+            sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM applications WHERE id = ?)")
+                .bind(&application_id)
+                .fetch_one(&mut *tx)
+                .await
+                .map_err(ApiError::DatabaseError)?;
+        if !still_exists {
+            return Err(ApiError::NotFound("Application not found".to_string()));
+        }
+        warn!(
+            application_id = %application_id,
+            "Resume swap rejected: application changed concurrently"
+        );
+        return Err(ApiError::Coded(
+            ErrorCode::ResumeSwapConflict,
+            "The application changed while the resume was being swapped".to_string(),
+        ));
+    }
+
+    sqlx::query(
+        r#"
+        INSERT INTO application_resume_history (id, application_id, old_resume_id, new_resume_id, changed_by, changed_at)
+        VALUES (?, ?, ?, ?, ?, datetime('now'))
+        "#,
+    )
+    .bind(generate_history_id())
+    .bind(&application_id)
+    .bind(application.resume_id.as_deref())
+    .bind(&request.resume_id)
+    .bind(&authed.id)
+    .execute(&mut *tx)
+    .await
+    .map_err(ApiError::DatabaseError)?;
+
+    tx.commit().await.map_err(ApiError::DatabaseError)?;
+
+    if let Err(e) = experience_years::refresh_application(&state.db, &application_id).await {
+        warn!(error = %e, application_id = %application_id, "Failed to store application experience years");
+    }
+
+    let application = sqlx::query_as::<_, Application>("SELECT * FROM applications WHERE id = ?")
+        .bind(&application_id)
+        .fetch_one(&state.db)
+        .await
+        .map_err(ApiError::DatabaseError)?;
+
+    info!(
+        application_id = %application_id,
+        resume_id = %request.resume_id,
+        user_id = %authed.id,
+        "Application resume swapped"
+    );
+
+    Ok(Json(application))
+}
+
+/// GET /api/applications/:id/resume-history - Resume swaps for an application
+pub async fn get_application_resume_history(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Path(application_id): Path<String>,
+) -> Result<Json<Vec<ApplicationResumeHistory>>, ApiError> {
+    let state = state_lock.read().await.clone();
+
+    let owner: Option<String> =
+        sqlx::query_scalar("SELECT user_id FROM applications WHERE id = ?")
+            .bind(&application_id)
+            .fetch_optional(&state.db)
+            .await
+            .map_err(ApiError::DatabaseError)?;
+
+    match owner {
+        Some(owner) if authed.is_admin || owner == authed.id => {}
+        _ => return Err(ApiError::NotFound("Application not found".to_string())),
+    }
+
+    let history = sqlx::query_as::<_, ApplicationResumeHistory>(
+        "SELECT * FROM application_resume_history WHERE application_id = ? ORDER BY changed_at DESC",
+    )
+    .bind(&application_id)
+    .fetch_all(&state.db)
+    .await
+    .map_err(ApiError::DatabaseError)?;
+
+    Ok(Json(history))
+}
+
+/// GET /api/admin/applications/:id/requirements-match - Requirement-by-requirement resume comparison
+///
+/// The comparison is cached per application and regenerated when the attached resume or the
+/// job's requirements change, or when `?refresh=true` is passed.
+pub async fn get_application_requirements_match(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Path(application_id): Path<String>,
+    Query(query): Query<RequirementsMatchQuery>,
+) -> Result<Json<ApplicationRequirementsMatch>, ApiError> {
+    let state = state_lock.read().await.clone();
+
+    if !authed.is_admin {
+        return Err(ApiError::Forbidden("Admin privileges required".to_string()));
+    }
+
+    let application = sqlx::query_as::<_, Application>("SELECT * FROM applications WHERE id = ?")
+        .bind(&application_id)
+        .fetch_optional(&state.db)
+        .await
+        .map_err(ApiError::DatabaseError)?
+        .ok_or_else(|| ApiError::NotFound("Application not found".to_string()))?;
+
+    let resume_id = application.resume_id.clone().ok_or_else(|| {
+        ApiError::BadRequest("Application has no resume attached".to_string())
+    })?;
+
+    let requirements_raw: Option<String> =
+        sqlx::query_scalar("SELECT requirements FROM jobs WHERE id = ?")
+            .bind(&application.job_id)
+            .fetch_optional(&state.db)
+            .await
+            .map_err(ApiError::DatabaseError)?
+            .ok_or_else(|| ApiError::NotFound("Job not found".to_string()))?;
+    let requirements_snapshot = requirements_raw.clone().unwrap_or_default();
+
+    if !query.refresh {
+        let cached = sqlx::query_as::<_, (String, String, String, Option<String>)>(
+            "SELECT resume_id, requirements_snapshot, result_json, generated_at FROM application_requirement_matches WHERE application_id = ?",
+        )
+        .bind(&application_id)
+        .fetch_optional(&state.db)
+        .await
+        .map_err(ApiError::DatabaseError)?;
+
+        if let Some((cached_resume_id, cached_requirements, result_json, generated_at)) = cached {
+            if cached_resume_id == resume_id && cached_requirements == requirements_snapshot {
+                if let Ok(comparison) = serde_json::from_str(&result_json) {
+                    return Ok(Json(ApplicationRequirementsMatch {
+                        application_id,
+                        job_id: application.job_id,
+                        resume_id,
+                        generated_at: generated_at.unwrap_or_default(),
+                        cached: true,
+                        comparison,
+                    }));
+                }
+            }
+        }
+    }
+
+    let requirements = parse_requirements(requirements_raw.as_deref());
+    if requirements.is_empty() {
+        return Err(ApiError::BadRequest(
+            "Job has no requirements to compare against".to_string(),
+        ));
+    }
+
+    let parsed_json: Option<String> =
+        sqlx::query_scalar("SELECT parsed_json FROM resumes WHERE id = ?")
+            .bind(&resume_id)
+            .fetch_optional(&state.db)
+            .await
+            .map_err(ApiError::DatabaseError)?
+            .flatten();
+    let parsed_resume: serde_json::Value = parsed_json
+        .as_deref()
+        .and_then(|json| serde_json::from_str(json).ok())
+        .ok_or_else(|| {
+            ApiError::BadRequest(
+                "Resume has not been scanned yet; scan it before comparing".to_string(),
+            )
+        })?;
+
+    let comparison = compare_requirements(&requirements, &parsed_resume);
+    let generated_at = chrono::Utc::now().to_rfc3339();
+    let result_json = serde_json::to_string(&comparison)
+        .map_err(|e| ApiError::InternalServer(format!("Failed to serialize comparison: {}", e)))?;
+
+    sqlx::query(
+        r#"
+        INSERT INTO application_requirement_matches
+            (application_id, job_id, resume_id, requirements_snapshot, result_json, generated_at)
+        VALUES (?, ?, ?, ?, ?, ?)
+        ON CONFLICT(application_id) DO UPDATE SET
+            job_id = excluded.job_id,
+            resume_id = excluded.resume_id,
+            requirements_snapshot = excluded.requirements_snapshot,
+            result_json = excluded.result_json,
+            generated_at = excluded.generated_at
+        "#,
+    )
+    .bind(&application_id)
+    .bind(&application.job_id)
+    .bind(&resume_id)
+    .bind(&requirements_snapshot)
+    .bind(&result_json)
+    .bind(&generated_at)
+    .execute(&state.db)
+    .await
+    .map_err(|e| {
+        error!(error = %e, application_id = %application_id, "Failed to cache requirements match");
+        ApiError::DatabaseError(e)
+    })?;
+
+    info!(
+        admin_user_id = %authed.id,
+        application_id = %application_id,
+        met = comparison.met,
+        partially_met = comparison.partially_met,
+        missing = comparison.missing,
+        "Generated requirements match"
+    );
+
+    Ok(Json(ApplicationRequirementsMatch {
+        application_id,
+        job_id: application.job_id,
+        resume_id,
+        generated_at,
+        cached: false,
+        comparison,
+    }))
+}
+
+/// Default cooldowns (days) when neither the job nor system settings define one
+const DEFAULT_REAPPLY_DAYS_AFTER_REJECTION: i64 = 180;
+const DEFAULT_REAPPLY_DAYS_AFTER_WITHDRAWAL: i64 = 0;
+
+/// Decide whether a candidate may re-apply given their previous application for the job.
+///
+/// The job's `reapply_cooldown_days` overrides the global `reapply_cooldown_days_after_rejection`
+/// and `reapply_cooldown_days_after_withdrawal` settings.
+async fn enforce_reapplication_policy(
+    state: &AppState,
+    previous: &Application,
+) -> Result<(), ApiError> {
+    let job_cooldown: Option<i64> =
+        sqlx::query_scalar("SELECT reapply_cooldown_days FROM jobs WHERE id = ?")
+            .bind(&previous.job_id)
+            .fetch_optional(&state.db)
+            .await
+            .map_err(ApiError::DatabaseError)?
+            .flatten();
+
+    let (setting_key, default_days) = if previous.status == "withdrawn" {
+        (
+            "reapply_cooldown_days_after_withdrawal",
+            DEFAULT_REAPPLY_DAYS_AFTER_WITHDRAWAL,
+        )
+    } else {
+        (
+            "reapply_cooldown_days_after_rejection",
+            DEFAULT_REAPPLY_DAYS_AFTER_REJECTION,
+        )
+    };
+
+    let cooldown_days = match job_cooldown {
+        Some(days) => days,
+        None => state
+            .settings_service
+            .get_setting(setting_key)
+            .await
+            .ok()
+            .flatten()
+            .and_then(|v| v.trim().parse::<i64>().ok())
+            .unwrap_or(default_days),
+    };
+
+    // When the application reached its final status; fall back to its last update
+    let closed_at: Option<String> = sqlx::query_scalar(
+        "SELECT MAX(changed_at) FROM application_status_history WHERE application_id = ? AND status = ?",
+    )
+    .bind(&previous.id)
+    .bind(&previous.status)
+    .fetch_one(&state.db)
+    .await
+    .map_err(ApiError::DatabaseError)?;
+
+    let closed_at = closed_at
+        .or_else(|| previous.updated_at.clone())
+        .as_deref()
+        .and_then(parse_db_timestamp)
+        .unwrap_or_else(|| chrono::Utc::now().naive_utc());
+
+    evaluate_reapplication(
+        &previous.status,
+        closed_at,
+        cooldown_days,
+        chrono::Utc::now().naive_utc(),
+    )
+    .map_err(|msg| {
+        warn!(
+            user_id = %previous.user_id,
+            job_id = %previous.job_id,
+            previous_status = %previous.status,
+            cooldown_days = cooldown_days,
+            "Re-application blocked by policy"
+        );
+        match previous.status.as_str() {
+            "rejected" | "withdrawn" => ApiError::BadRequest(msg),
+            _ => ApiError::Coded(ErrorCode::ApplicationDuplicate, msg),
+        }
+    })
+}
+
+/// Parse timestamps written either by SQLite's datetime('now') or as RFC 3339
+fn parse_db_timestamp(value: &str) -> Option<chrono::NaiveDateTime> {
+    chrono::NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S")
+        .ok()
+        .or_else(|| {
+            chrono::DateTime::parse_from_rfc3339(value)
+                .ok()
+                .map(|dt| dt.naive_utc())
+        })
+}
+
+/// Check that a resume belongs to the candidate and has not been deleted
+async fn ensure_resume_attachable(
+    db: &sqlx::SqlitePool,
+    resume_id: &str,
+    user_id: &str,
+) -> Result<(), ApiError> {
+    let resume_exists = sqlx::query_scalar::<_, i64>(
+        "SELECT COUNT(*) FROM resumes WHERE id = ? AND user_id = ? AND deleted_at IS NULL",
+    )
+    .bind(resume_id)
+    .bind(user_id)
+    .fetch_one(db)
+    .await
+    .map_err(ApiError::DatabaseError)?;
+
+    if resume_exists == 0 {
+        return Err(ApiError::BadRequest("Resume not found".to_string()));
+    }
+
+    Ok(())
+}
+
 /// GET /api/admin/jobs/:id/applications - Get all applications for a specific job (admin only)
 pub async fn get_job_applications(
     Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
     authed: AuthedUser,
     Path(job_id): Path<String>,
+    Query(params): Query<JobApplicationsQuery>,
 ) -> Result<Json<Vec<JobApplicationDetails>>, ApiError> {
     let state = state_lock.read().await.clone();
 
@@ -433,14 +1015,47 @@ pub async fn get_job_applications(
         return Err(ApiError::Forbidden("Admin privileges required".to_string()));
     }
 
-    let job_exists = sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM jobs WHERE id = ?")
-        .bind(&job_id)
-        .fetch_one(&state.db)
-        .await
-        .map_err(ApiError::DatabaseError)?;
+    let educational_qualifications: Option<Option<String>> =
+        sqlx::query_scalar("SELECT educational_qualifications FROM jobs WHERE id = ?")
+            .bind(&job_id)
+            .fetch_optional(&state.db)
+            .await
+            .map_err(ApiError::DatabaseError)?;
 
-    if job_exists == 0 {
+    let Some(educational_qualifications) = educational_qualifications else {
         return Err(ApiError::BadRequest("Job not found".to_string()));
+    };
+    let required_level =
+        education_match::required_level(educational_qualifications.as_deref());
+
+    // Profile degrees of everyone who applied, grouped by candidate
+    let degree_rows: Vec<(String, String)> = sqlx::query_as(
+        r#"
+        SELECT e.user_id, e.degree FROM education e
+        WHERE e.user_id IN (SELECT user_id FROM applications WHERE job_id = ?)
+        "#,
+    )
+    .bind(&job_id)
+    .fetch_all(&state.db)
+    .await
+    .map_err(ApiError::DatabaseError)?;
+    let mut profile_degrees: std::collections::HashMap<String, Vec<String>> =
+        std::collections::HashMap::new();
+    for (user_id, degree) in degree_rows {
+        profile_degrees.entry(user_id).or_default().push(degree);
+    }
+
+    let tag_rows: Vec<(String, String)> = sqlx::query_as(
+        "SELECT application_id, tag FROM application_knockouts WHERE job_id = ? AND tag IS NOT NULL ORDER BY created_at",
+    )
+    .bind(&job_id)
+    .fetch_all(&state.db)
+    .await
+    .map_err(ApiError::DatabaseError)?;
+    let mut tags_by_application: std::collections::HashMap<String, Vec<String>> =
+        std::collections::HashMap::new();
+    for (application_id, tag) in tag_rows {
+        tags_by_application.entry(application_id).or_default().push(tag);
     }
 
     let query = r#"
@@ -448,7 +1063,15 @@ pub async fn get_job_applications(
             a.id as application_id, a.user_id as candidate_id,
             u.name as candidate_name, u.email as candidate_email,
             a.resume_id, r.filename as resume_filename,
-            a.status, a.applied_at, a.cover_letter
+            COALESCE(r.version, 1) as resume_version,
+            (
+                SELECT latest.id FROM resumes latest
+                WHERE COALESCE(latest.root_resume_id, latest.id) = COALESCE(r.root_resume_id, r.id)
+                AND latest.superseded_by IS NULL AND latest.id != r.id
+                LIMIT 1
+            ) as latest_resume_id,
+            a.status, a.applied_at, a.cover_letter, r.parsed_json as resume_parsed_json,
+            a.experience_years, a.relevant_experience_years, a.waitlisted_at
         FROM applications a
         INNER JOIN users u ON a.user_id = u.id
         LEFT JOIN resumes r ON a.resume_id = r.id
@@ -465,17 +1088,71 @@ pub async fn get_job_applications(
     let mut result = Vec::new();
 
     for row in rows {
+        let candidate_id: String = row.try_get("candidate_id").unwrap_or_default();
+        let resume_parsed_json: Option<String> =
+            row.try_get("resume_parsed_json").ok().flatten();
+        let has_parsed_resume = resume_parsed_json.is_some();
+        let mut degrees = education_match::resume_degrees(resume_parsed_json.as_deref());
+        degrees.extend(profile_degrees.get(&candidate_id).cloned().unwrap_or_default());
+        let highest_degree_level = education_match::highest_level(&degrees);
+        let meets_education =
+            education_match::education_match(required_level, highest_degree_level);
+
+        if params
+            .education_match
+            .is_some_and(|wanted| meets_education != Some(wanted))
+        {
+            continue;
+        }
+
+        let application_id: String = row.try_get("application_id").unwrap_or_default();
+        let mut experience_years: Option<f64> = row.try_get("experience_years").ok().flatten();
+        let mut relevant_experience_years: Option<f64> =
+            row.try_get("relevant_experience_years").ok().flatten();
+        // Applications made before experience was tracked are filled in on first view
+        if experience_years.is_none() && has_parsed_resume {
+            if let Some(summary) =
+                experience_years::refresh_application(&state.db, &application_id)
+                    .await
+                    .map_err(ApiError::DatabaseError)?
+            {
+                experience_years = Some(summary.total_years);
+                relevant_experience_years = Some(summary.relevant_years);
+            }
+        }
+
+        if params
+            .min_years
+            .is_some_and(|min| !experience_years.is_some_and(|years| years >= min))
+            || params
+                .min_relevant_years
+                .is_some_and(|min| !relevant_experience_years.is_some_and(|years| years >= min))
+        {
+            continue;
+        }
+
+        let knockout_tags = tags_by_application
+            .remove(&application_id)
+            .unwrap_or_default();
         result.push(JobApplicationDetails {
-            application_id: row.try_get("application_id").unwrap_or_default(),
-            candidate_id: row.try_get("candidate_id").unwrap_or_default(),
+            application_id,
+            candidate_id,
             candidate_name: row.try_get("candidate_name").unwrap_or_default(),
             candidate_email: row.try_get("candidate_email").unwrap_or_default(),
             resume_id: row.try_get("resume_id").ok(),
             resume_filename: row.try_get("resume_filename").ok(),
             resume_label: None, // Column doesn't exist in database
+            resume_version: row.try_get("resume_version").ok().flatten(),
+            latest_resume_id: row.try_get("latest_resume_id").ok().flatten(),
             status: row.try_get("status").unwrap_or_default(),
             applied_at: row.try_get("applied_at").ok(),
             cover_letter: row.try_get("cover_letter").ok(),
+            highest_degree_level,
+            education_match: meets_education,
+            experience_years,
+            relevant_experience_years,
+            knockout_tags,
+            waitlisted_at: row.try_get("waitlisted_at").ok().flatten(),
         });
     }
 
@@ -569,12 +1246,46 @@ pub async fn get_application_analytics(
     .await
     .map_err(ApiError::DatabaseError)?;
 
+    // Latest withdrawal entry per application; withdrawals without a reason show as "unspecified"
+    let reason_counts = sqlx::query_as::<_, (String, i64)>(
+        r#"
+        SELECT COALESCE(h.reason_code, 'unspecified') as reason, COUNT(*) as count
+        FROM application_status_history h
+        WHERE h.status = 'withdrawn'
+          AND h.id = (
+              SELECT h2.id FROM application_status_history h2
+              WHERE h2.application_id = h.application_id AND h2.status = 'withdrawn'
+              ORDER BY h2.changed_at DESC LIMIT 1
+          )
+        GROUP BY reason
+        ORDER BY count DESC
+        "#,
+    )
+    .fetch_all(&state.db)
+    .await
+    .map_err(ApiError::DatabaseError)?;
+
+    let total_withdrawals: i64 = reason_counts.iter().map(|(_, count)| count).sum();
+    let withdrawal_reasons = reason_counts
+        .into_iter()
+        .map(|(reason, count)| WithdrawalReasonStats {
+            reason,
+            count,
+            percentage: if total_withdrawals > 0 {
+                count as f64 * 100.0 / total_withdrawals as f64
+            } else {
+                0.0
+            },
+        })
+        .collect();
+
     Ok(Json(ApplicationAnalytics {
         total_applications,
         applications_by_status,
         applications_by_job,
         conversion_rates,
         recent_applications,
+        withdrawal_reasons,
     }))
 }
 
@@ -637,7 +1348,16 @@ pub async fn bulk_update_application_status(
                             .await;
 
                             match history_result {
-                                Ok(_) => success_count += 1,
+                                Ok(_) => {
+                                    success_count += 1;
+                                    if request.status == "hired" {
+                                        chat_webhooks::dispatch(
+                                            &state,
+                                            ChatEvent::OfferAccepted,
+                                            application_id,
+                                        );
+                                    }
+                                }
                                 Err(e) => {
                                     failed_count += 1;
                                     errors.push(format!(
@@ -677,6 +1397,18 @@ pub async fn bulk_update_application_status(
         }
     }
 
+    if request.status == "rejected" && success_count > 0 {
+        monitoring::record_security_activity(
+            &state,
+            SecurityActivity::BulkRejection,
+            Some(&authed.id),
+            None,
+            success_count as i64,
+            "/api/admin/applications/bulk-update-status",
+        )
+        .await;
+    }
+
     Ok(Json(BulkOperationResult {
         success_count,
         failed_count,
@@ -690,6 +1422,7 @@ pub async fn bulk_update_application_status(
 // ============================================================================
 
 use super::email_templates::{get_email_template, get_next_status, status_to_stage};
+use crate::common::i18n::user_locale;
 use serde::Deserialize;
 
 #[derive(Debug, Deserialize)]
@@ -733,7 +1466,12 @@ pub async fn advance_application_stage(
 
     // Get next status
     let next_status = get_next_status(&application.status)
-        .ok_or_else(|| ApiError::BadRequest("Application is already at final stage".to_string()))?;
+        .ok_or_else(|| {
+            ApiError::Coded(
+                ErrorCode::StageTransitionInvalid,
+                "Application is already at final stage".to_string(),
+            )
+        })?;
 
     let current_stage = status_to_stage(next_status);
 
@@ -769,6 +1507,10 @@ pub async fn advance_application_stage(
     .await
     .map_err(ApiError::DatabaseError)?;
 
+    if next_status == "hired" {
+        chat_webhooks::dispatch(&state, ChatEvent::OfferAccepted, &application_id);
+    }
+
     // Send email if requested
     if request.send_email.unwrap_or(false) {
         if let Err(e) = send_status_email(&state, &application, next_status).await {
@@ -886,6 +1628,21 @@ pub async fn bulk_application_action(
         "Bulk application action completed"
     );
 
+    if request.action == "update_status"
+        && request.status.as_deref() == Some("rejected")
+        && success_count > 0
+    {
+        monitoring::record_security_activity(
+            &state,
+            SecurityActivity::BulkRejection,
+            Some(&authed.id),
+            None,
+            success_count as i64,
+            "/api/admin/applications/bulk-action",
+        )
+        .await;
+    }
+
     Ok(Json(BulkOperationResult {
         success_count,
         failed_count,
@@ -904,7 +1661,7 @@ async fn send_status_email(
 ) -> Result<(), ApiError> {
     // Get candidate info
     let user: (String, String) = sqlx::query_as(
-        "SELECT name, email FROM users WHERE id = ?"
+        "SELECT u.name, COALESCE(p.contact_email, u.email) FROM users u LEFT JOIN profiles p ON p.user_id = u.id WHERE u.id = ?"
     )
     .bind(&application.user_id)
     .fetch_optional(&state.db)
@@ -927,10 +1684,24 @@ async fn send_status_email(
     let job_title = job.0;
     let company_name = job.1.unwrap_or_else(|| "Our Company".to_string());
 
-    let template = get_email_template(status, &candidate_name, &job_title, &company_name);
+    let locale = user_locale(&state.db, &application.user_id)
+        .await
+        .unwrap_or_default();
+    let mut template =
+        get_email_template(status, &candidate_name, &job_title, &company_name, locale);
+
+    // Feedback an admin picked in the AI editor replaces the standard rejection body
+    if status == "rejected" {
+        if let Some(feedback) = rejection_feedback::active_feedback(&state.db, &application.id)
+            .await
+            .map_err(ApiError::DatabaseError)?
+        {
+            template.body = render_markdown(&feedback);
+        }
+    }
 
     state
-        .aws_service
+        .email
         .send_email(vec![candidate_email], &template.subject, &template.body, None)
         .await
         .map_err(|e| ApiError::ProcessingError(format!("Failed to send email: {}", e)))?;
@@ -977,6 +1748,10 @@ async fn advance_single_application(
     .await
     .map_err(|e| e.to_string())?;
 
+    if next_status == "hired" {
+        chat_webhooks::dispatch(state, ChatEvent::OfferAccepted, application_id);
+    }
+
     if send_email {
         let updated_app = sqlx::query_as::<_, Application>("SELECT * FROM applications WHERE id = ?")
             .bind(application_id)
@@ -1038,6 +1813,10 @@ async fn update_single_application_status(
     .await
     .map_err(|e| e.to_string())?;
 
+    if status == "hired" {
+        chat_webhooks::dispatch(state, ChatEvent::OfferAccepted, application_id);
+    }
+
     if send_email {
         let application = sqlx::query_as::<_, Application>("SELECT * FROM applications WHERE id = ?")
             .bind(application_id)
@@ -1105,7 +1884,7 @@ pub async fn approve_candidate_for_job(
 
     // Find the application for this job and candidate
     let application = sqlx::query_as::<_, Application>(
-        "SELECT * FROM applications WHERE job_id = ? AND user_id = ?"
+        "SELECT * FROM applications WHERE job_id = ? AND user_id = ? AND archived_at IS NULL"
     )
     .bind(&job_id)
     .bind(&candidate_id)
@@ -1116,7 +1895,12 @@ pub async fn approve_candidate_for_job(
 
     // Get next status
     let next_status = get_next_status(&application.status)
-        .ok_or_else(|| ApiError::BadRequest("Application is already at final stage".to_string()))?;
+        .ok_or_else(|| {
+            ApiError::Coded(
+                ErrorCode::StageTransitionInvalid,
+                "Application is already at final stage".to_string(),
+            )
+        })?;
 
     let current_stage = status_to_stage(next_status);
 
@@ -1221,7 +2005,7 @@ pub async fn reject_candidate_for_job(
 
     // Find the application for this job and candidate
     let application = sqlx::query_as::<_, Application>(
-        "SELECT * FROM applications WHERE job_id = ? AND user_id = ?"
+        "SELECT * FROM applications WHERE job_id = ? AND user_id = ? AND archived_at IS NULL"
     )
     .bind(&job_id)
     .bind(&candidate_id)
@@ -1319,9 +2103,13 @@ pub struct SendCandidateEmailRequest {
     pub subject: String,
     pub content: String,
     pub cc: Option<Vec<String>>,
+    #[serde(flatten)]
+    pub schedule: ScheduleInput,
 }
 
 /// POST /api/admin/jobs/:job_id/candidates/:candidate_id/email - Send email to candidate
+///
+/// With `send_at` or `business_hours` the email is scheduled instead of sent.
 pub async fn send_candidate_email_for_job(
     Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
     authed: AuthedUser,
@@ -1336,7 +2124,7 @@ pub async fn send_candidate_email_for_job(
 
     // Find the application for this job and candidate
     let application = sqlx::query_as::<_, Application>(
-        "SELECT * FROM applications WHERE job_id = ? AND user_id = ?"
+        "SELECT * FROM applications WHERE job_id = ? AND user_id = ? AND archived_at IS NULL"
     )
     .bind(&job_id)
     .bind(&candidate_id)
@@ -1354,6 +2142,47 @@ pub async fn send_candidate_email_for_job(
     .await
     .map_err(ApiError::DatabaseError)?;
 
+    // Expand `/shortcut` snippets for this candidate and application
+    let (content, _) = snippets::expand(
+        &state.db,
+        &request.content,
+        Some(&candidate_id),
+        Some(&application.id),
+    )
+    .await?;
+
+    if let Some((send_at, timezone)) = scheduled_messages::resolve_send_at(
+        &state.db,
+        &state.settings_service,
+        &candidate_id,
+        &request.schedule,
+    )
+    .await?
+    {
+        let scheduled = scheduled_messages::schedule_message(
+            &state.db,
+            NewScheduledMessage {
+                kind: "email",
+                user_id: &candidate_id,
+                application_id: Some(&application.id),
+                job_id: Some(&job_id),
+                subject: Some(&request.subject),
+                body: &content,
+                cc: request.cc.as_deref(),
+                send_at,
+                timezone: &timezone,
+                business_hours: request.schedule.business_hours,
+                created_by: &authed.id,
+            },
+        )
+        .await?;
+        return Ok(Json(serde_json::json!({
+            "message": "Email scheduled",
+            "recipient": candidate_email,
+            "scheduled": scheduled_messages::to_response(scheduled)
+        })));
+    }
+
     // Build recipient list
     let mut recipients = vec![candidate_email.clone()];
     if let Some(cc_list) = &request.cc {
@@ -1362,8 +2191,8 @@ pub async fn send_candidate_email_for_job(
 
     // Send email via AWS SES
     state
-        .aws_service
-        .send_email(recipients.clone(), &request.subject, &request.content, None)
+        .email
+        .send_email(recipients.clone(), &request.subject, &content, None)
         .await
         .map_err(|e| ApiError::ProcessingError(format!("Failed to send email: {}", e)))?;
 
@@ -1382,7 +2211,7 @@ pub async fn send_candidate_email_for_job(
     .bind(&candidate_id)
     .bind(&job_id)
     .bind(&request.subject)
-    .bind(&request.content)
+    .bind(&content)
     .bind(&cc_json)
     .bind(&authed.id)
     .execute(&state.db)
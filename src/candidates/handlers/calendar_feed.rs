@@ -0,0 +1,119 @@
+// src/candidates/handlers/calendar_feed.rs
+//! Subscribable calendar of the interviews the caller runs or sits on
+
+use axum::{
+    extract::{Extension, Path},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::{info, warn};
+
+use crate::auth::AuthedUser;
+use crate::candidates::models::{CalendarFeedLink, CalendarFeedStatus};
+use crate::common::{ApiError, AppState};
+use crate::services::calendar_feed;
+
+const FEED_CACHE_CONTROL: &str = "private, max-age=300";
+
+/// GET /api/me/calendar-feed - Whether a feed exists and when it was last fetched
+pub async fn get_calendar_feed(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+) -> Result<Json<CalendarFeedStatus>, ApiError> {
+    let state = state_lock.read().await.clone();
+
+    let row: Option<(Option<String>, Option<String>)> = sqlx::query_as(
+        "SELECT created_at, last_accessed_at FROM calendar_feed_tokens WHERE user_id = ?",
+    )
+    .bind(&authed.id)
+    .fetch_optional(&state.db)
+    .await
+    .map_err(ApiError::DatabaseError)?;
+
+    Ok(Json(match row {
+        Some((created_at, last_accessed_at)) => CalendarFeedStatus {
+            active: true,
+            created_at,
+            last_accessed_at,
+        },
+        None => CalendarFeedStatus {
+            active: false,
+            created_at: None,
+            last_accessed_at: None,
+        },
+    }))
+}
+
+/// POST /api/me/calendar-feed - Create the feed, or replace its URL so old subscriptions stop
+pub async fn regenerate_calendar_feed(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+) -> Result<Json<CalendarFeedLink>, ApiError> {
+    let state = state_lock.read().await.clone();
+
+    let token = calendar_feed::regenerate_token(&state.db, &authed.id)
+        .await
+        .map_err(ApiError::DatabaseError)?;
+
+    info!(user_id = %authed.id, "Calendar feed token generated");
+
+    let url = format!("{}/calendar/{}.ics", state.config.backend_url, token);
+    let webcal_url = match url.split_once("://") {
+        Some((_, rest)) => format!("webcal://{}", rest),
+        None => url.clone(),
+    };
+    Ok(Json(CalendarFeedLink { url, webcal_url }))
+}
+
+/// DELETE /api/me/calendar-feed - Turn the feed off
+pub async fn revoke_calendar_feed(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+) -> Result<StatusCode, ApiError> {
+    let state = state_lock.read().await.clone();
+
+    sqlx::query("DELETE FROM calendar_feed_tokens WHERE user_id = ?")
+        .bind(&authed.id)
+        .execute(&state.db)
+        .await
+        .map_err(ApiError::DatabaseError)?;
+
+    info!(user_id = %authed.id, "Calendar feed revoked");
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// GET /calendar/:token.ics - The feed itself; the token is the only credential
+pub async fn serve_calendar_feed(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    Path(file): Path<String>,
+) -> Result<Response, ApiError> {
+    let state = state_lock.read().await.clone();
+    let not_found = || ApiError::NotFound("Calendar feed not found".to_string());
+
+    let token = file.strip_suffix(".ics").ok_or_else(not_found)?;
+    let Some((user_id, email)) = calendar_feed::resolve_token(&state.db, token)
+        .await
+        .map_err(ApiError::DatabaseError)?
+    else {
+        warn!("Calendar feed requested with an unknown token");
+        return Err(not_found());
+    };
+
+    let interviews = calendar_feed::feed_interviews(&state.db, &user_id, &email)
+        .await
+        .map_err(ApiError::DatabaseError)?;
+    let body = calendar_feed::render_ics(&interviews, chrono::Utc::now());
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "text/calendar; charset=utf-8"),
+            (header::CACHE_CONTROL, FEED_CACHE_CONTROL),
+        ],
+        body,
+    )
+        .into_response())
+}
@@ -0,0 +1,435 @@
+// src/candidates/handlers/documents.rs
+//! Candidate document collection (ID, certificates) with encrypted storage
+
+use axum::{
+    extract::{Extension, Multipart, Path, Query},
+    http::{header, StatusCode},
+    response::{IntoResponse, Json, Response},
+};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::{error, info, warn};
+
+use crate::auth::AuthedUser;
+use crate::candidates::models::{
+    CreateDocumentRequestsRequest, DocumentDownloadQuery, DocumentDownloadUrl, DocumentRequest,
+    ReviewDocumentRequest,
+};
+use crate::common::{generate_document_request_id, storage, ApiError, AppState};
+use crate::services::documents;
+use crate::services::text::escape_html;
+
+/// S3 prefix for stored documents; locally they live in `documents_dir`
+const DOCUMENTS_PREFIX: &str = "documents";
+
+async fn fetch_document(state: &AppState, id: &str) -> Result<DocumentRequest, ApiError> {
+    sqlx::query_as::<_, DocumentRequest>("SELECT * FROM document_requests WHERE id = ?")
+        .bind(id)
+        .fetch_optional(&state.db)
+        .await
+        .map_err(ApiError::DatabaseError)?
+        .ok_or_else(|| ApiError::NotFound("Document request not found".to_string()))
+}
+
+fn download_url(
+    state: &AppState,
+    document: &DocumentRequest,
+) -> Result<DocumentDownloadUrl, ApiError> {
+    if document.filename.is_none() {
+        return Err(ApiError::BadRequest(
+            "No file has been uploaded for this document yet".to_string(),
+        ));
+    }
+
+    let (token, expires_at) =
+        documents::issue_download_token(&state.jwt_secret, &document.id, chrono::Utc::now())?;
+
+    Ok(DocumentDownloadUrl {
+        url: format!("/api/documents/{}/download?token={}", document.id, token),
+        expires_at: expires_at.to_rfc3339(),
+    })
+}
+
+/// POST /api/admin/candidates/:id/document-requests - Request documents from a candidate
+pub async fn create_document_requests(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Path(candidate_id): Path<String>,
+    Json(request): Json<CreateDocumentRequestsRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    let state = state_lock.read().await.clone();
+    authed.require_admin("Document request")?;
+
+    if request.document_types.is_empty() {
+        return Err(ApiError::ValidationError(
+            "At least one document type is required".to_string(),
+        ));
+    }
+    for document_type in &request.document_types {
+        documents::validate_document_type(document_type)?;
+    }
+
+    let candidate: (Option<String>, String) =
+        sqlx::query_as("SELECT name, email FROM users WHERE id = ?")
+            .bind(&candidate_id)
+            .fetch_optional(&state.db)
+            .await
+            .map_err(ApiError::DatabaseError)?
+            .ok_or_else(|| ApiError::NotFound(format!("Candidate not found: {}", candidate_id)))?;
+
+    if let Some(application_id) = &request.application_id {
+        let owned: i64 =
+            sqlx::query_scalar("SELECT COUNT(*) FROM applications WHERE id = ? AND user_id = ?")
+                .bind(application_id)
+                .bind(&candidate_id)
+                .fetch_one(&state.db)
+                .await
+                .map_err(ApiError::DatabaseError)?;
+
+        if owned == 0 {
+            return Err(ApiError::BadRequest(
+                "Application does not belong to this candidate".to_string(),
+            ));
+        }
+    }
+
+    let mut created = Vec::with_capacity(request.document_types.len());
+    for document_type in &request.document_types {
+        let id = generate_document_request_id();
+        sqlx::query(
+            r#"
+            INSERT INTO document_requests (id, candidate_id, application_id, document_type, instructions, requested_by, due_date)
+            VALUES (?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(&id)
+        .bind(&candidate_id)
+        .bind(&request.application_id)
+        .bind(document_type)
+        .bind(&request.instructions)
+        .bind(&authed.id)
+        .bind(&request.due_date)
+        .execute(&state.db)
+        .await
+        .map_err(|e| {
+            error!(error = %e, candidate_id = %candidate_id, "Database error creating document request");
+            ApiError::DatabaseError(e)
+        })?;
+
+        created.push(fetch_document(&state, &id).await?);
+    }
+
+    info!(
+        admin_user_id = %authed.id,
+        candidate_id = %candidate_id,
+        count = created.len(),
+        "Documents requested from candidate"
+    );
+
+    // Let the candidate know; the requests stand even if the email can't be sent
+    let (name, email) = candidate;
+    let list = request
+        .document_types
+        .iter()
+        .map(|t| format!("<li>{}</li>", t.replace('_', " ")))
+        .collect::<String>();
+    let due = request
+        .due_date
+        .as_deref()
+        .map(|d| {
+            format!(
+                "<p>Please upload them by <strong>{}</strong>.</p>",
+                escape_html(d)
+            )
+        })
+        .unwrap_or_default();
+    let body = format!(
+        "<p>Dear {},</p><p>We need the following documents to continue with your application:</p><ul>{}</ul>{}<p>You can upload them securely from your candidate portal.</p><p>Best regards,<br>Hiring Team</p>",
+        escape_html(name.as_deref().unwrap_or("Candidate")),
+        list,
+        due
+    );
+    if let Err(e) = state
+        .email
+        .send_email(
+            vec![email],
+            "Documents requested for your application",
+            &body,
+            None,
+        )
+        .await
+    {
+        warn!(error = %e, candidate_id = %candidate_id, "Failed to send document request email");
+    }
+
+    Ok((StatusCode::CREATED, Json(created)))
+}
+
+/// GET /api/admin/candidates/:id/document-requests - A candidate's document requests
+pub async fn list_candidate_document_requests(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Path(candidate_id): Path<String>,
+) -> Result<Json<Vec<DocumentRequest>>, ApiError> {
+    let state = state_lock.read().await.clone();
+    authed.require_admin("Document request")?;
+
+    let requests = sqlx::query_as::<_, DocumentRequest>(
+        "SELECT * FROM document_requests WHERE candidate_id = ? ORDER BY created_at DESC",
+    )
+    .bind(&candidate_id)
+    .fetch_all(&state.db)
+    .await
+    .map_err(ApiError::DatabaseError)?;
+
+    Ok(Json(requests))
+}
+
+/// PUT /api/admin/document-requests/:id/review - Approve or reject an uploaded document
+pub async fn review_document(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Path(id): Path<String>,
+    Json(request): Json<ReviewDocumentRequest>,
+) -> Result<Json<DocumentRequest>, ApiError> {
+    let state = state_lock.read().await.clone();
+    authed.require_admin("Document request")?;
+
+    if request.status != "approved" && request.status != "rejected" {
+        return Err(ApiError::ValidationError(
+            "Review status must be 'approved' or 'rejected'".to_string(),
+        ));
+    }
+
+    let document = fetch_document(&state, &id).await?;
+    if document.filename.is_none() {
+        return Err(ApiError::BadRequest(
+            "Cannot review a document that hasn't been uploaded".to_string(),
+        ));
+    }
+
+    sqlx::query(
+        r#"
+        UPDATE document_requests
+        SET status = ?, review_notes = ?, reviewed_by = ?, reviewed_at = datetime('now'), updated_at = datetime('now')
+        WHERE id = ?
+        "#,
+    )
+    .bind(&request.status)
+    .bind(&request.notes)
+    .bind(&authed.id)
+    .bind(&id)
+    .execute(&state.db)
+    .await
+    .map_err(ApiError::DatabaseError)?;
+
+    info!(
+        admin_user_id = %authed.id,
+        document_id = %id,
+        status = %request.status,
+        "Candidate document reviewed"
+    );
+
+    Ok(Json(fetch_document(&state, &id).await?))
+}
+
+/// GET /api/admin/document-requests/:id/download-url - Short-lived link to an uploaded document
+pub async fn admin_document_download_url(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Path(id): Path<String>,
+) -> Result<Json<DocumentDownloadUrl>, ApiError> {
+    let state = state_lock.read().await.clone();
+    authed.require_admin("Document request")?;
+
+    let document = fetch_document(&state, &id).await?;
+    info!(admin_user_id = %authed.id, document_id = %id, "Issued document download link");
+
+    Ok(Json(download_url(&state, &document)?))
+}
+
+/// GET /api/me/document-requests - Documents requested from the caller
+pub async fn get_my_document_requests(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+) -> Result<Json<Vec<DocumentRequest>>, ApiError> {
+    let state = state_lock.read().await.clone();
+
+    let requests = sqlx::query_as::<_, DocumentRequest>(
+        "SELECT * FROM document_requests WHERE candidate_id = ? ORDER BY created_at DESC",
+    )
+    .bind(&authed.id)
+    .fetch_all(&state.db)
+    .await
+    .map_err(ApiError::DatabaseError)?;
+
+    Ok(Json(requests))
+}
+
+/// POST /api/me/document-requests/:id/upload - Upload a requested document (multipart `file`)
+///
+/// The file is encrypted before it reaches storage. Re-uploading replaces the previous file
+/// and sends the document back for review, unless it was already approved.
+pub async fn upload_requested_document(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Path(id): Path<String>,
+    mut multipart: Multipart,
+) -> Result<Json<DocumentRequest>, ApiError> {
+    let state = state_lock.read().await.clone();
+
+    let document = fetch_document(&state, &id).await?;
+    if document.candidate_id != authed.id {
+        return Err(ApiError::NotFound("Document request not found".to_string()));
+    }
+    if document.status == "approved" {
+        return Err(ApiError::BadRequest(
+            "This document has already been approved".to_string(),
+        ));
+    }
+
+    let encryption = state.settings_service.encryption().ok_or_else(|| {
+        error!("Document upload attempted without an encryption key configured");
+        ApiError::ServiceUnavailable("Secure document storage is not configured".to_string())
+    })?;
+
+    let mut upload: Option<(Option<String>, axum::body::Bytes)> = None;
+    while let Some(field) = multipart
+        .next_field()
+        .await
+        .map_err(|e| ApiError::BadRequest(format!("Invalid multipart data: {}", e)))?
+    {
+        if field.name() == Some("file") {
+            let original_filename = field.file_name().map(str::to_string);
+            let data = field
+                .bytes()
+                .await
+                .map_err(|e| ApiError::BadRequest(format!("Failed to read upload: {}", e)))?;
+            upload = Some((original_filename, data));
+        }
+    }
+    let (original_filename, data) =
+        upload.ok_or_else(|| ApiError::BadRequest("No file provided".to_string()))?;
+
+    let (content_type, extension) = documents::validate_document_upload(&data)?;
+    let encrypted = encryption.encrypt_bytes(&data).map_err(|e| {
+        error!(error = %e, document_id = %id, "Failed to encrypt document");
+        ApiError::InternalServer("Failed to encrypt document".to_string())
+    })?;
+
+    // A fresh name per upload so a replacement never serves the old file from a cache
+    let filename = format!(
+        "{}_{}.{}.enc",
+        id,
+        chrono::Utc::now().timestamp(),
+        extension
+    );
+    storage::save_file(
+        &state,
+        DOCUMENTS_PREFIX,
+        &state.documents_dir,
+        &filename,
+        encrypted,
+        "application/octet-stream",
+    )
+    .await?;
+
+    sqlx::query(
+        r#"
+        UPDATE document_requests
+        SET status = 'uploaded', filename = ?, original_filename = ?, content_type = ?, file_size = ?,
+            uploaded_at = datetime('now'), review_notes = NULL, reviewed_by = NULL, reviewed_at = NULL,
+            updated_at = datetime('now')
+        WHERE id = ?
+        "#,
+    )
+    .bind(&filename)
+    .bind(&original_filename)
+    .bind(content_type)
+    .bind(data.len() as i64)
+    .bind(&id)
+    .execute(&state.db)
+    .await
+    .map_err(ApiError::DatabaseError)?;
+
+    info!(
+        user_id = %authed.id,
+        document_id = %id,
+        document_type = %document.document_type,
+        file_size = data.len(),
+        "Candidate uploaded requested document"
+    );
+
+    Ok(Json(fetch_document(&state, &id).await?))
+}
+
+/// GET /api/me/document-requests/:id/download-url - Short-lived link to the caller's own upload
+pub async fn my_document_download_url(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Path(id): Path<String>,
+) -> Result<Json<DocumentDownloadUrl>, ApiError> {
+    let state = state_lock.read().await.clone();
+
+    let document = fetch_document(&state, &id).await?;
+    if document.candidate_id != authed.id {
+        return Err(ApiError::NotFound("Document request not found".to_string()));
+    }
+
+    Ok(Json(download_url(&state, &document)?))
+}
+
+/// GET /api/documents/:id/download?token= - Decrypt and serve a document via an expiring link
+pub async fn download_document(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    Path(id): Path<String>,
+    Query(query): Query<DocumentDownloadQuery>,
+) -> Result<Response, ApiError> {
+    let state = state_lock.read().await.clone();
+
+    documents::verify_download_token(&state.jwt_secret, &query.token, &id)?;
+
+    let document = fetch_document(&state, &id).await?;
+    let filename = document
+        .filename
+        .as_deref()
+        .ok_or_else(|| ApiError::NotFound("Document has not been uploaded".to_string()))?;
+
+    let encryption = state.settings_service.encryption().ok_or_else(|| {
+        ApiError::ServiceUnavailable("Secure document storage is not configured".to_string())
+    })?;
+
+    let encrypted =
+        storage::load_file(&state, DOCUMENTS_PREFIX, &state.documents_dir, filename).await?;
+    let data = encryption.decrypt_bytes(&encrypted).map_err(|e| {
+        error!(error = %e, document_id = %id, "Failed to decrypt document");
+        ApiError::InternalServer("Failed to decrypt document".to_string())
+    })?;
+
+    let download_name = document
+        .original_filename
+        .as_deref()
+        .map(|name| name.replace(['"', '\\', '\r', '\n'], ""))
+        .unwrap_or_else(|| format!("{}-{}", document.document_type, document.id));
+
+    info!(document_id = %id, "Serving candidate document");
+
+    Ok((
+        StatusCode::OK,
+        [
+            (
+                header::CONTENT_TYPE,
+                document
+                    .content_type
+                    .unwrap_or_else(|| "application/octet-stream".to_string()),
+            ),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", download_name),
+            ),
+            (header::CACHE_CONTROL, "private, no-store".to_string()),
+        ],
+        data,
+    )
+        .into_response())
+}
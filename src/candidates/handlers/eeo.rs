@@ -0,0 +1,258 @@
+// src/candidates/handlers/eeo.rs
+//! Voluntary self-identification: the candidate's own answers and aggregate-only reporting
+
+use axum::{
+    extract::{Extension, Path, Query},
+    Json,
+};
+use chrono::NaiveDate;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::{error, info, warn};
+
+use crate::auth::AuthedUser;
+use crate::candidates::models::*;
+use crate::common::{generate_history_id, ApiError, AppState};
+use crate::services::eeo::{self, QUESTIONS};
+use crate::services::encryption::EncryptionService;
+use crate::services::permissions;
+
+fn encryption(state: &AppState) -> Result<&EncryptionService, ApiError> {
+    state.settings_service.encryption().ok_or_else(|| {
+        error!("Self-identification used without an encryption key configured");
+        ApiError::ServiceUnavailable("Self-identification is not available".to_string())
+    })
+}
+
+/// The job of an application owned by the caller; answers are only ever the candidate's own
+async fn owned_application_job(
+    state: &AppState,
+    authed: &AuthedUser,
+    application_id: &str,
+) -> Result<String, ApiError> {
+    sqlx::query_scalar("SELECT job_id FROM applications WHERE id = ? AND user_id = ?")
+        .bind(application_id)
+        .bind(&authed.id)
+        .fetch_optional(&state.db)
+        .await
+        .map_err(ApiError::DatabaseError)?
+        .ok_or_else(|| ApiError::NotFound("Application not found".to_string()))
+}
+
+/// GET /api/self-identification/questions - The voluntary questions and their answer options
+pub async fn get_self_identification_questions() -> Json<&'static [SelfIdentificationQuestion]> {
+    Json(QUESTIONS)
+}
+
+/// GET /api/applications/:id/self-identification - The candidate's own answers
+pub async fn get_self_identification(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Path(application_id): Path<String>,
+) -> Result<Json<SelfIdentification>, ApiError> {
+    let state = state_lock.read().await.clone();
+    owned_application_job(&state, &authed, &application_id).await?;
+
+    let encrypted: String =
+        sqlx::query_scalar("SELECT encrypted_answers FROM eeo_responses WHERE application_id = ?")
+            .bind(&application_id)
+            .fetch_optional(&state.db)
+            .await
+            .map_err(ApiError::DatabaseError)?
+            .ok_or_else(|| ApiError::NotFound("No self-identification answers".to_string()))?;
+
+    let json = encryption(&state)?.decrypt(&encrypted).map_err(|e| {
+        error!(error = %e, application_id = %application_id, "Failed to decrypt self-identification");
+        ApiError::InternalServer("Failed to read self-identification".to_string())
+    })?;
+    let answers = serde_json::from_str(&json)
+        .map_err(|_| ApiError::InternalServer("Failed to read self-identification".to_string()))?;
+
+    Ok(Json(answers))
+}
+
+/// PUT /api/applications/:id/self-identification - Save or replace the candidate's answers
+pub async fn save_self_identification(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Path(application_id): Path<String>,
+    Json(request): Json<SelfIdentification>,
+) -> Result<Json<SelfIdentification>, ApiError> {
+    let state = state_lock.read().await.clone();
+    let job_id = owned_application_job(&state, &authed, &application_id).await?;
+
+    let answers = eeo::normalize_answers(request)?;
+    let json = serde_json::to_string(&answers)
+        .map_err(|e| ApiError::InternalServer(format!("Failed to encode answers: {}", e)))?;
+    let encrypted = encryption(&state)?.encrypt_envelope(&json).map_err(|e| {
+        error!(error = %e, application_id = %application_id, "Failed to encrypt self-identification");
+        ApiError::InternalServer("Failed to save self-identification".to_string())
+    })?;
+
+    sqlx::query(
+        r#"
+        INSERT INTO eeo_responses (id, application_id, job_id, encrypted_answers)
+        VALUES (?, ?, ?, ?)
+        ON CONFLICT(application_id) DO UPDATE SET
+            encrypted_answers = excluded.encrypted_answers,
+            submitted_at = datetime('now')
+        "#,
+    )
+    .bind(generate_history_id())
+    .bind(&application_id)
+    .bind(&job_id)
+    .bind(&encrypted)
+    .execute(&state.db)
+    .await
+    .map_err(|e| {
+        error!(error = %e, application_id = %application_id, "Database error saving self-identification");
+        ApiError::DatabaseError(e)
+    })?;
+
+    // The answers themselves are never logged
+    info!(user_id = %authed.id, application_id = %application_id, "Self-identification saved");
+
+    Ok(Json(answers))
+}
+
+/// DELETE /api/applications/:id/self-identification - Withdraw the candidate's answers
+pub async fn delete_self_identification(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Path(application_id): Path<String>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let state = state_lock.read().await.clone();
+    owned_application_job(&state, &authed, &application_id).await?;
+
+    sqlx::query("DELETE FROM eeo_responses WHERE application_id = ?")
+        .bind(&application_id)
+        .execute(&state.db)
+        .await
+        .map_err(ApiError::DatabaseError)?;
+
+    info!(user_id = %authed.id, application_id = %application_id, "Self-identification withdrawn");
+
+    Ok(Json(serde_json::json!({
+        "message": "Self-identification answers deleted"
+    })))
+}
+
+/// GET /api/admin/analytics/eeo - Aggregate self-identification figures with small groups hidden
+pub async fn get_eeo_report(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Query(query): Query<EeoReportQuery>,
+) -> Result<Json<EeoReport>, ApiError> {
+    if !authed.is_admin {
+        return Err(ApiError::Forbidden("Admin privileges required".to_string()));
+    }
+    let state = state_lock.read().await.clone();
+
+    let permitted =
+        permissions::has_admin_permission(&state.db, &authed.id, permissions::EEO_REPORTS)
+            .await
+            .map_err(ApiError::DatabaseError)?;
+    if !permitted {
+        warn!(user_id = %authed.id, "EEO report access denied: missing eeo_reports permission");
+        return Err(ApiError::Forbidden(
+            "The eeo_reports permission is required".to_string(),
+        ));
+    }
+
+    for (field, value) in [("from", &query.from), ("to", &query.to)] {
+        if let Some(value) = value {
+            NaiveDate::parse_from_str(value, "%Y-%m-%d").map_err(|_| {
+                ApiError::ValidationError(format!("{} must be a date in YYYY-MM-DD format", field))
+            })?;
+        }
+    }
+
+    let min_cohort_size = eeo::min_cohort_size(
+        state
+            .settings_service
+            .get_setting("eeo_min_cohort_size")
+            .await
+            .ok()
+            .flatten()
+            .as_deref(),
+    );
+
+    let scope = r#"
+        FROM applications a
+        LEFT JOIN eeo_responses e ON e.application_id = a.id
+        WHERE (? IS NULL OR a.job_id = ?)
+          AND (? IS NULL OR a.status = ?)
+          AND (? IS NULL OR date(a.applied_at) >= ?)
+          AND (? IS NULL OR date(a.applied_at) <= ?)
+    "#;
+    let (applications, respondents): (i64, i64) =
+        sqlx::query_as(&format!("SELECT COUNT(*), COUNT(e.id) {}", scope))
+            .bind(&query.job_id)
+            .bind(&query.job_id)
+            .bind(&query.status)
+            .bind(&query.status)
+            .bind(&query.from)
+            .bind(&query.from)
+            .bind(&query.to)
+            .bind(&query.to)
+            .fetch_one(&state.db)
+            .await
+            .map_err(ApiError::DatabaseError)?;
+
+    if respondents < min_cohort_size {
+        return Ok(Json(EeoReport {
+            applications,
+            respondents,
+            min_cohort_size,
+            withheld: true,
+            fields: Vec::new(),
+        }));
+    }
+
+    let encrypted: Vec<String> = sqlx::query_scalar(&format!(
+        "SELECT e.encrypted_answers {} AND e.id IS NOT NULL",
+        scope
+    ))
+    .bind(&query.job_id)
+    .bind(&query.job_id)
+    .bind(&query.status)
+    .bind(&query.status)
+    .bind(&query.from)
+    .bind(&query.from)
+    .bind(&query.to)
+    .bind(&query.to)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|e| {
+        error!(error = %e, "Database error building EEO report");
+        ApiError::DatabaseError(e)
+    })?;
+
+    let encryption = encryption(&state)?;
+    let answers: Vec<SelfIdentification> = encrypted
+        .iter()
+        .filter_map(|value| encryption.decrypt(value).ok())
+        .filter_map(|json| serde_json::from_str(&json).ok())
+        .collect();
+    if answers.len() < encrypted.len() {
+        warn!(
+            unreadable = encrypted.len() - answers.len(),
+            "Skipped unreadable self-identification answers"
+        );
+    }
+
+    info!(
+        admin_user_id = %authed.id,
+        job_id = ?query.job_id,
+        respondents = respondents,
+        "EEO report generated"
+    );
+
+    Ok(Json(EeoReport {
+        applications,
+        respondents,
+        min_cohort_size,
+        withheld: false,
+        fields: eeo::aggregate(&answers, min_cohort_size),
+    }))
+}
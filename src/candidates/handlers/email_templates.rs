@@ -2,114 +2,44 @@
 //! Email templates for application status updates
 
 use crate::candidates::models::Application;
+use crate::common::i18n::{t, Locale};
 
 pub struct EmailTemplate {
     pub subject: String,
     pub body: String,
 }
 
+/// Status update email for `status`, written in `locale`
 pub fn get_email_template(
     status: &str,
     candidate_name: &str,
     job_title: &str,
     company_name: &str,
+    locale: Locale,
 ) -> EmailTemplate {
-    match status {
-        "reviewed" => EmailTemplate {
-            subject: format!("Application Received - {}", job_title),
-            body: format!(
-                r#"<html><body style="font-family: Arial, sans-serif; line-height: 1.6; color: #333;">
-<div style="max-width: 600px; margin: 0 auto; padding: 20px;">
-<p>Hi {},</p>
-<p>Thanks for applying for <strong>{}</strong> at <strong>{}</strong>. We've received your application and will review it shortly.</p>
-<p>We'll be in touch with next steps soon.</p>
-<p>Best,<br>{} Team</p>
-</div></body></html>"#,
-                candidate_name, job_title, company_name, company_name
-            ),
-        },
-        "shortlisted" => EmailTemplate {
-            subject: format!("You're Shortlisted! - {}", job_title),
-            body: format!(
-                r#"<html><body style="font-family: Arial, sans-serif; line-height: 1.6; color: #333;">
-<div style="max-width: 600px; margin: 0 auto; padding: 20px;">
-<p>Hi {},</p>
-<p>Great news! You've been shortlisted for the <strong>{}</strong> position at <strong>{}</strong>.</p>
-<p>We'll contact you soon to discuss next steps.</p>
-<p>Best,<br>{} Team</p>
-</div></body></html>"#,
-                candidate_name, job_title, company_name, company_name
-            ),
-        },
-        "interviewed" => EmailTemplate {
-            subject: format!("Interview Invitation - {}", job_title),
-            body: format!(
-                r#"<html><body style="font-family: Arial, sans-serif; line-height: 1.6; color: #333;">
-<div style="max-width: 600px; margin: 0 auto; padding: 20px;">
-<p>Hi {},</p>
-<p>We'd like to invite you for an interview for <strong>{}</strong> at <strong>{}</strong>.</p>
-<p>We'll reach out shortly to schedule a time that works for you.</p>
-<p>Looking forward to speaking with you!</p>
-<p>Best,<br>{} Team</p>
-</div></body></html>"#,
-                candidate_name, job_title, company_name, company_name
-            ),
-        },
-        "offered" => EmailTemplate {
-            subject: format!("Job Offer - {}", job_title),
-            body: format!(
-                r#"<html><body style="font-family: Arial, sans-serif; line-height: 1.6; color: #333;">
-<div style="max-width: 600px; margin: 0 auto; padding: 20px;">
-<p>Hi {},</p>
-<p>Congratulations! We're pleased to offer you the <strong>{}</strong> position at <strong>{}</strong>.</p>
-<p>You'll receive a formal offer letter with compensation details shortly.</p>
-<p>Welcome to the team!</p>
-<p>Best,<br>{} Team</p>
-</div></body></html>"#,
-                candidate_name, job_title, company_name, company_name
-            ),
-        },
-        "hired" => EmailTemplate {
-            subject: format!("Welcome to {}!", company_name),
-            body: format!(
-                r#"<html><body style="font-family: Arial, sans-serif; line-height: 1.6; color: #333;">
-<div style="max-width: 600px; margin: 0 auto; padding: 20px;">
-<p>Hi {},</p>
-<p>Welcome to <strong>{}</strong>! We're excited to have you join us as <strong>{}</strong>.</p>
-<p>You'll receive onboarding details including your start date and first-day information soon.</p>
-<p>See you soon!</p>
-<p>Best,<br>{} Team</p>
-</div></body></html>"#,
-                candidate_name, company_name, job_title, company_name
-            ),
-        },
-        "rejected" => EmailTemplate {
-            subject: format!("Application Update - {}", job_title),
-            body: format!(
-                r#"<html><body style="font-family: Arial, sans-serif; line-height: 1.6; color: #333;">
-<div style="max-width: 600px; margin: 0 auto; padding: 20px;">
-<p>Hi {},</p>
-<p>Thank you for your interest in <strong>{}</strong> at <strong>{}</strong>.</p>
-<p>After careful review, we've decided to move forward with other candidates. We encourage you to apply for future openings that match your skills.</p>
-<p>Best of luck in your search!</p>
-<p>Best,<br>{} Team</p>
-</div></body></html>"#,
-                candidate_name, job_title, company_name, company_name
-            ),
-        },
-        _ => EmailTemplate {
-            subject: format!("Application Update - {}", job_title),
-            body: format!(
-                r#"<html><body style="font-family: Arial, sans-serif; line-height: 1.6; color: #333;">
+    let key = match status {
+        "reviewed" | "shortlisted" | "interviewed" | "offered" | "hired" | "rejected" => status,
+        _ => "other",
+    };
+    let args = [
+        ("name", candidate_name),
+        ("job", job_title),
+        ("company", company_name),
+    ];
+
+    EmailTemplate {
+        subject: t(&format!("email.status.{}.subject", key), locale, &args),
+        body: format!(
+            r#"<html><body style="font-family: Arial, sans-serif; line-height: 1.6; color: #333;">
 <div style="max-width: 600px; margin: 0 auto; padding: 20px;">
-<p>Hi {},</p>
-<p>There's an update on your application for <strong>{}</strong> at <strong>{}</strong>.</p>
-<p>We'll be in touch if we need anything further.</p>
-<p>Best,<br>{} Team</p>
+<p>{}</p>
+{}
+<p>{}</p>
 </div></body></html>"#,
-                candidate_name, job_title, company_name, company_name
-            ),
-        },
+            t("email.greeting", locale, &args),
+            t(&format!("email.status.{}.body", key), locale, &args),
+            t("email.signoff", locale, &args),
+        ),
     }
 }
 
@@ -196,3 +126,23 @@ pub fn validate_status_transition(current_status: &str, new_status: &str) -> Res
     
     Err(format!("Invalid status transition from '{}' to '{}'. Status can only move forward.", current_status, new_status))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_status_email_is_localized() {
+        let en = get_email_template("offered", "Ana", "Chef", "Acme", Locale::En);
+        assert_eq!(en.subject, "Job Offer - Chef");
+        assert!(en.body.contains("<p>Hi Ana,</p>"));
+        assert!(en.body.contains("<p>Best,<br>Acme Team</p>"));
+
+        let es = get_email_template("offered", "Ana", "Chef", "Acme", Locale::Es);
+        assert_eq!(es.subject, "Oferta de empleo - Chef");
+        assert!(es.body.contains("<strong>Chef</strong> en <strong>Acme</strong>"));
+
+        let other = get_email_template("withdrawn", "Ana", "Chef", "Acme", Locale::De);
+        assert_eq!(other.subject, "Neuigkeiten zu Ihrer Bewerbung - Chef");
+    }
+}
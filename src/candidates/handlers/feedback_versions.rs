@@ -0,0 +1,112 @@
+// src/candidates/handlers/feedback_versions.rs
+//! Handlers for rejection feedback versions (Inline AI Editor for candidate communications)
+
+use axum::{
+    extract::{Extension, Path},
+    Json,
+};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::info;
+
+use crate::auth::AuthedUser;
+use crate::candidates::models::{
+    ActivateFeedbackResponse, FeedbackVersionsResponse, GenerateFeedbackRequest,
+    GenerateFeedbackResponse,
+};
+use crate::common::{ApiError, AppState};
+use crate::jobs::models::DeleteVersionResponse;
+use crate::services::rejection_feedback;
+
+/// GET /api/admin/applications/:id/feedback/versions
+/// Get all feedback versions for an application
+pub async fn get_feedback_versions(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    user: AuthedUser,
+    Path(application_id): Path<String>,
+) -> Result<Json<FeedbackVersionsResponse>, ApiError> {
+    if !user.is_admin {
+        return Err(ApiError::Forbidden("Admin access required".to_string()));
+    }
+
+    let state = state_lock.read().await.clone();
+
+    Ok(Json(
+        rejection_feedback::list_versions(&state.db, &application_id).await?,
+    ))
+}
+
+/// POST /api/admin/applications/:id/feedback/generate
+/// Draft several phrasings of the rejection feedback using AI
+pub async fn generate_feedback(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    user: AuthedUser,
+    Path(application_id): Path<String>,
+    Json(request): Json<GenerateFeedbackRequest>,
+) -> Result<Json<GenerateFeedbackResponse>, ApiError> {
+    if !user.is_admin {
+        return Err(ApiError::Forbidden("Admin access required".to_string()));
+    }
+
+    info!(
+        application_id = %application_id,
+        user_id = %user.id,
+        "Generating rejection feedback with AI"
+    );
+
+    let state = state_lock.read().await.clone();
+    let versions = rejection_feedback::generate(&state, &application_id, request, &user.id).await?;
+
+    Ok(Json(GenerateFeedbackResponse { versions }))
+}
+
+/// POST /api/admin/applications/:id/feedback/versions/:version_id/activate
+/// Choose the version sent in the rejection email
+pub async fn activate_feedback_version(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    user: AuthedUser,
+    Path((application_id, version_id)): Path<(String, String)>,
+) -> Result<Json<ActivateFeedbackResponse>, ApiError> {
+    if !user.is_admin {
+        return Err(ApiError::Forbidden("Admin access required".to_string()));
+    }
+
+    info!(
+        application_id = %application_id,
+        version_id = %version_id,
+        user_id = %user.id,
+        "Activating rejection feedback version"
+    );
+
+    let state = state_lock.read().await.clone();
+    let version = rejection_feedback::activate(&state.db, &application_id, &version_id).await?;
+
+    Ok(Json(ActivateFeedbackResponse {
+        success: true,
+        version,
+    }))
+}
+
+/// DELETE /api/admin/applications/:id/feedback/versions/:version_id
+/// Delete a feedback version (cannot delete the active version)
+pub async fn delete_feedback_version(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    user: AuthedUser,
+    Path((application_id, version_id)): Path<(String, String)>,
+) -> Result<Json<DeleteVersionResponse>, ApiError> {
+    if !user.is_admin {
+        return Err(ApiError::Forbidden("Admin access required".to_string()));
+    }
+
+    info!(
+        application_id = %application_id,
+        version_id = %version_id,
+        user_id = %user.id,
+        "Deleting rejection feedback version"
+    );
+
+    let state = state_lock.read().await.clone();
+    rejection_feedback::delete_version(&state.db, &application_id, &version_id).await?;
+
+    Ok(Json(DeleteVersionResponse { success: true }))
+}
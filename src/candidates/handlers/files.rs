@@ -1,44 +1,453 @@
 // src/candidates/handlers/files.rs
-//! File serving for candidate resumes and assets
+//! File serving for candidate resumes and assets, behind per-file access checks and
+//! short-lived signed links
 
 use axum::{
-    extract::{Extension, Path},
-    http::StatusCode,
-    response::IntoResponse,
+    body::Body,
+    extract::{Extension, Path, Query},
+    http::{header, HeaderMap, HeaderValue, Request, StatusCode},
+    response::{IntoResponse, Json, Response},
 };
+use std::path::PathBuf;
 use std::sync::Arc;
 use tokio::sync::RwLock;
+use tower_http::services::ServeFile;
+use tracing::{error, info, warn};
 
-use crate::common::{ApiError, AppState};
+use crate::auth::AuthedUser;
+use crate::candidates::models::{DocumentDownloadQuery, DocumentDownloadUrl};
+use crate::common::{storage, ApiError, AppState};
+use crate::services::downloads::{self, FileKind};
+use crate::services::interview_artifacts;
+use crate::services::storage_usage::{self, StorageUsage};
 
-/// GET /uploads/resumes/*path - Serve resume files (PDFs and derived images)
+/// Where a stored file's bytes live
+enum FileLocation {
+    Local(PathBuf),
+    S3(String),
+}
+
+struct StoredFile {
+    owner_id: String,
+    location: FileLocation,
+    content_type: String,
+    download_name: String,
+}
+
+fn content_type_for(filename: &str) -> &'static str {
+    match filename
+        .rsplit('.')
+        .next()
+        .map(|ext| ext.to_lowercase())
+        .as_deref()
+    {
+        Some("pdf") => "application/pdf",
+        Some("png") => "image/png",
+        Some("jpg") | Some("jpeg") => "image/jpeg",
+        Some("mp4") => "video/mp4",
+        Some("webm") => "video/webm",
+        Some("mov") => "video/quicktime",
+        Some("doc") => "application/msword",
+        Some("docx") => "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+        Some("txt") => "text/plain",
+        _ => "application/octet-stream",
+    }
+}
+
+fn local_path(base: &std::path::Path, relative: &str) -> Result<PathBuf, ApiError> {
+    if !downloads::is_safe_relative_path(relative) {
+        warn!(path = %relative, "Refusing to serve file outside its storage directory");
+        return Err(ApiError::NotFound("File not found".to_string()));
+    }
+    Ok(base.join(relative))
+}
+
+/// Where a file recorded by URL lives: an S3 or CloudFront URL, or a local `/uploads/...` one
+fn location_from_url(url: &str) -> Result<FileLocation, ApiError> {
+    if let Some(key) = downloads::s3_key_from_url(url) {
+        return Ok(FileLocation::S3(key));
+    }
+    match url.strip_prefix(&format!("/{}/", storage::LOCAL_UPLOADS_DIR)) {
+        Some(relative) => Ok(FileLocation::Local(local_path(
+            std::path::Path::new(storage::LOCAL_UPLOADS_DIR),
+            relative,
+        )?)),
+        None => Err(ApiError::NotFound("File not found".to_string())),
+    }
+}
+
+/// Serve a local file with support for `Range` and conditional requests, so video players
+/// can seek and PDF viewers can fetch pages on demand without downloading the whole file
+async fn serve_local(
+    path: &std::path::Path,
+    content_type: &str,
+    headers: &HeaderMap,
+) -> Result<Response, ApiError> {
+    let mut request = Request::new(Body::empty());
+    for name in [
+        header::RANGE,
+        header::IF_RANGE,
+        header::IF_MODIFIED_SINCE,
+        header::IF_UNMODIFIED_SINCE,
+    ] {
+        if let Some(value) = headers.get(&name) {
+            request.headers_mut().insert(name, value.clone());
+        }
+    }
+
+    let response = ServeFile::new(path).try_call(request).await.map_err(|e| {
+        error!(error = %e, path = %path.display(), "Failed to read stored file");
+        ApiError::InternalServer("Failed to read file".to_string())
+    })?;
+    if response.status() == StatusCode::NOT_FOUND {
+        return Err(ApiError::NotFound("File not found".to_string()));
+    }
+
+    let mut response = response.map(Body::new);
+    // The type is guessed from the extension; the one recorded at upload wins
+    if let Ok(content_type) = HeaderValue::from_str(content_type) {
+        response
+            .headers_mut()
+            .insert(header::CONTENT_TYPE, content_type);
+    }
+    Ok(response)
+}
+
+/// Serve a located file from local disk or S3
+async fn serve_stored(
+    state: &AppState,
+    file: &StoredFile,
+    headers: &HeaderMap,
+) -> Result<Response, ApiError> {
+    match &file.location {
+        FileLocation::Local(path) => serve_local(path, &file.content_type, headers).await,
+        FileLocation::S3(key) => {
+            let content = state.object_store.download_file(key).await.map_err(|e| {
+                error!(error = %e, s3_key = %key, "Failed to fetch file from S3");
+                ApiError::NotFound("File not found".to_string())
+            })?;
+            Ok(([(header::CONTENT_TYPE, file.content_type.clone())], content).into_response())
+        }
+    }
+}
+
+/// Look up a file by kind and id, resolving its owner and where it is stored
+async fn locate_file(state: &AppState, kind: FileKind, id: &str) -> Result<StoredFile, ApiError> {
+    let not_found = || ApiError::NotFound("File not found".to_string());
+
+    match kind {
+        FileKind::Resume => {
+            let (user_id, filename): (Option<String>, Option<String>) = sqlx::query_as(
+                "SELECT user_id, filename FROM resumes WHERE id = ? AND deleted_at IS NULL",
+            )
+            .bind(id)
+            .fetch_optional(&state.db)
+            .await
+            .map_err(ApiError::DatabaseError)?
+            .ok_or_else(not_found)?;
+            let filename = filename.ok_or_else(not_found)?;
+
+            let path = local_path(&state.resumes_dir, &filename)?;
+            let location = if !path.exists() && storage::uses_s3(state).await {
+                FileLocation::S3(format!("resumes/{}", filename))
+            } else {
+                FileLocation::Local(path)
+            };
+
+            Ok(StoredFile {
+                owner_id: user_id.unwrap_or_default(),
+                location,
+                content_type: content_type_for(&filename).to_string(),
+                download_name: filename,
+            })
+        }
+        FileKind::Video => {
+            let (user_id, s3_url, filename, mime_type): (
+                String,
+                Option<String>,
+                Option<String>,
+                Option<String>,
+            ) = sqlx::query_as(
+                "SELECT user_id, s3_url, filename, mime_type FROM videos WHERE id = ? AND COALESCE(video_source, 'upload') = 'upload'",
+            )
+            .bind(id)
+            .fetch_optional(&state.db)
+            .await
+            .map_err(ApiError::DatabaseError)?
+            .ok_or_else(not_found)?;
+
+            let location = location_from_url(s3_url.as_deref().ok_or_else(not_found)?)?;
+            let download_name = filename.unwrap_or_else(|| format!("{}.mp4", id));
+
+            Ok(StoredFile {
+                owner_id: user_id,
+                location,
+                content_type: mime_type
+                    .unwrap_or_else(|| content_type_for(&download_name).to_string()),
+                download_name,
+            })
+        }
+        FileKind::Attachment => {
+            let (user_id, file_path, original_filename, mime_type): (
+                String,
+                String,
+                String,
+                String,
+            ) = sqlx::query_as(
+                r#"
+                SELECT m.user_id, a.file_path, a.original_filename, a.mime_type
+                FROM message_attachments a
+                JOIN conversation_messages m ON m.id = a.message_id
+                WHERE a.id = ?
+                "#,
+            )
+            .bind(id)
+            .fetch_optional(&state.db)
+            .await
+            .map_err(ApiError::DatabaseError)?
+            .ok_or_else(not_found)?;
+
+            Ok(StoredFile {
+                owner_id: user_id,
+                location: FileLocation::Local(local_path(&state.resumes_dir, &file_path)?),
+                content_type: mime_type,
+                download_name: original_filename,
+            })
+        }
+        FileKind::OfferLetter => {
+            let (candidate_id, pdf_url): (String, Option<String>) =
+                sqlx::query_as("SELECT candidate_id, pdf_url FROM offer_letters WHERE id = ?")
+                    .bind(id)
+                    .fetch_optional(&state.db)
+                    .await
+                    .map_err(ApiError::DatabaseError)?
+                    .ok_or_else(not_found)?;
+            let pdf_url = pdf_url.ok_or_else(not_found)?;
+
+            // The PDF service records an S3 URL, or `/uploads/offer-letters/...` when storing locally
+            let location = location_from_url(&pdf_url)?;
+
+            Ok(StoredFile {
+                owner_id: candidate_id,
+                location,
+                content_type: "application/pdf".to_string(),
+                download_name: format!("offer-letter-{}.pdf", id),
+            })
+        }
+        FileKind::InterviewArtifact => {
+            let (filename, original_filename, content_type): (String, Option<String>, String) =
+                sqlx::query_as(
+                    "SELECT filename, original_filename, content_type FROM interview_artifacts WHERE id = ?",
+                )
+                .bind(id)
+                .fetch_optional(&state.db)
+                .await
+                .map_err(ApiError::DatabaseError)?
+                .ok_or_else(not_found)?;
+
+            let path = local_path(&state.documents_dir, &filename)?;
+            let location = if !path.exists() && storage::uses_s3(state).await {
+                FileLocation::S3(format!(
+                    "{}/{}",
+                    interview_artifacts::ARTIFACTS_PREFIX,
+                    filename
+                ))
+            } else {
+                FileLocation::Local(path)
+            };
+
+            // Access goes by the hiring team rather than an owner
+            Ok(StoredFile {
+                owner_id: String::new(),
+                location,
+                content_type,
+                download_name: original_filename.unwrap_or(filename),
+            })
+        }
+    }
+}
+
+/// Owners and admins only; anyone else gets the same answer as for a missing file
+fn authorize(authed: &AuthedUser, owner_id: &str) -> Result<(), ApiError> {
+    if authed.is_admin || (!owner_id.is_empty() && owner_id == authed.id) {
+        return Ok(());
+    }
+    warn!(user_id = %authed.id, "File access denied");
+    Err(ApiError::NotFound("File not found".to_string()))
+}
+
+/// Presign S3 objects directly; local files (or a failed presign) go through the gateway
+async fn signed_url(
+    state: &AppState,
+    kind: FileKind,
+    id: &str,
+    file: &StoredFile,
+) -> Result<DocumentDownloadUrl, ApiError> {
+    let now = chrono::Utc::now();
+
+    if let FileLocation::S3(key) = &file.location {
+        let expires_in = std::time::Duration::from_secs(kind.link_minutes() as u64 * 60);
+        match state.aws_service.presign_download(key, expires_in).await {
+            Ok(url) => {
+                return Ok(DocumentDownloadUrl {
+                    url,
+                    expires_at: (now + chrono::Duration::minutes(kind.link_minutes()))
+                        .to_rfc3339(),
+                })
+            }
+            Err(e) => {
+                warn!(error = %e, s3_key = %key, "Failed to presign S3 download, using the gateway");
+            }
+        }
+    }
+
+    let (token, expires_at) = downloads::issue_file_token(&state.jwt_secret, kind, id, now)?;
+    Ok(DocumentDownloadUrl {
+        url: format!("/api/files/{}/{}?token={}", kind.as_str(), id, token),
+        expires_at: expires_at.to_rfc3339(),
+    })
+}
+
+/// Check the caller may download a file and issue a short-lived link to it
+pub async fn file_download_url(
+    state: &AppState,
+    authed: &AuthedUser,
+    kind: FileKind,
+    id: &str,
+) -> Result<DocumentDownloadUrl, ApiError> {
+    let file = locate_file(state, kind, id).await?;
+    if kind == FileKind::InterviewArtifact {
+        let artifact = interview_artifacts::load_artifact(&state.db, id).await?;
+        interview_artifacts::require_hiring_team(&state.db, &artifact.interview_id, authed)
+            .await
+            .map_err(|_| ApiError::NotFound("File not found".to_string()))?;
+    } else {
+        authorize(authed, &file.owner_id)?;
+    }
+    signed_url(state, kind, id, &file).await
+}
+
+/// GET /api/files/:kind/:id/download-url - Signed link to a resume, video, attachment, offer
+/// letter or interview artifact
+pub async fn get_file_download_url(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Path((kind, id)): Path<(String, String)>,
+) -> Result<Json<DocumentDownloadUrl>, ApiError> {
+    let state = state_lock.read().await.clone();
+    let kind = FileKind::parse(&kind)?;
+
+    Ok(Json(file_download_url(&state, &authed, kind, &id).await?))
+}
+
+/// GET /api/files/:kind/:id?token= - Serve a file via a signed link
+///
+/// Local files honour `Range` requests, for video playback and seeking.
+pub async fn download_file(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    Path((kind, id)): Path<(String, String)>,
+    Query(query): Query<DocumentDownloadQuery>,
+    headers: HeaderMap,
+) -> Result<Response, ApiError> {
+    let state = state_lock.read().await.clone();
+    let kind = FileKind::parse(&kind)?;
+
+    downloads::verify_file_token(&state.jwt_secret, &query.token, kind, &id)?;
+
+    let file = locate_file(&state, kind, &id).await?;
+    let mut response = serve_stored(&state, &file, &headers).await?;
+
+    info!(kind = kind.as_str(), file_id = %id, "Serving file via signed link");
+
+    let download_name = file.download_name.replace(['"', '\\', '\r', '\n'], "");
+    let response_headers = response.headers_mut();
+    if let Ok(disposition) =
+        HeaderValue::from_str(&format!("inline; filename=\"{}\"", download_name))
+    {
+        response_headers.insert(header::CONTENT_DISPOSITION, disposition);
+    }
+    response_headers.insert(header::CACHE_CONTROL, HeaderValue::from_static("private, no-store"));
+    Ok(response)
+}
+
+/// GET /api/me/storage - The caller's storage usage by category and remaining quota
+pub async fn get_my_storage_usage(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+) -> Result<Json<StorageUsage>, ApiError> {
+    let state = state_lock.read().await.clone();
+
+    Ok(Json(
+        storage_usage::usage(&state, storage_usage::OWNER_USER, &authed.id).await?,
+    ))
+}
+
+/// GET /uploads/resumes/*path - Serve a resume file to its owner
+///
+/// Resumes are stored as `<resume id>.<ext>`, so the path names the record; the file served is
+/// the one that record points at.
 pub async fn serve_resume_file(
     Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
     Path(path): Path<String>,
-) -> Result<impl IntoResponse, ApiError> {
-    let state = state_lock.read().await;
+    headers: HeaderMap,
+) -> Result<Response, ApiError> {
+    let state = state_lock.read().await.clone();
+
+    let resume_id = path.split_once('.').map_or(path.as_str(), |(id, _)| id);
+    let file = locate_file(&state, FileKind::Resume, resume_id).await?;
+    authorize(&authed, &file.owner_id)?;
+
+    let mut response = serve_stored(&state, &file, &headers).await?;
+    response
+        .headers_mut()
+        .insert(header::CACHE_CONTROL, HeaderValue::from_static("private, no-store"));
+    Ok(response)
+}
 
-    let file_path = state.resumes_dir.join(&path);
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    if !file_path.exists() {
-        return Err(ApiError::BadRequest("File not found".to_string()));
+    #[test]
+    fn test_location_from_url() {
+        assert!(matches!(
+            location_from_url("https://bucket.s3.amazonaws.com/videos/v1.mp4"),
+            Ok(FileLocation::S3(key)) if key == "videos/v1.mp4"
+        ));
+        assert!(matches!(
+            location_from_url("/uploads/videos/v1.mp4"),
+            Ok(FileLocation::Local(path)) if path == std::path::Path::new("uploads/videos/v1.mp4")
+        ));
+        assert!(location_from_url("/uploads/../secrets.txt").is_err());
+        assert!(location_from_url("https://example.com/videos/v1.mp4").is_err());
     }
 
-    let content = tokio::fs::read(&file_path)
-        .await
-        .map_err(|_| ApiError::InternalServer("Failed to read file".to_string()))?;
+    #[tokio::test]
+    async fn test_serve_local_honours_range_requests() {
+        let path = std::env::temp_dir().join(format!("serve-local-{}.bin", uuid::Uuid::new_v4()));
+        tokio::fs::write(&path, b"0123456789").await.unwrap();
 
-    let content_type = if path.ends_with(".pdf") {
-        "application/pdf"
-    } else if path.ends_with(".png") {
-        "image/png"
-    } else {
-        "application/octet-stream"
-    };
+        let full = serve_local(&path, "video/mp4", &HeaderMap::new())
+            .await
+            .unwrap();
+        assert_eq!(full.status(), StatusCode::OK);
+        assert_eq!(full.headers()[header::CONTENT_TYPE], "video/mp4");
 
-    Ok((
-        StatusCode::OK,
-        [(axum::http::header::CONTENT_TYPE, content_type)],
-        content,
-    ))
+        let mut headers = HeaderMap::new();
+        headers.insert(header::RANGE, HeaderValue::from_static("bytes=2-5"));
+        let partial = serve_local(&path, "video/mp4", &headers).await.unwrap();
+        assert_eq!(partial.status(), StatusCode::PARTIAL_CONTENT);
+        assert_eq!(partial.headers()[header::CONTENT_RANGE], "bytes 2-5/10");
+        let body = axum::body::to_bytes(partial.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(&body[..], b"2345");
+
+        tokio::fs::remove_file(&path).await.unwrap();
+        assert!(matches!(
+            serve_local(&path, "video/mp4", &HeaderMap::new()).await,
+            Err(ApiError::NotFound(_))
+        ));
+    }
 }
@@ -0,0 +1,263 @@
+// src/candidates/handlers/guest_apply.rs
+//! Applying to a job without signing in
+
+use crate::auth::AuthedUser;
+use crate::candidates::handlers::create_application;
+use crate::candidates::models::CreateApplicationRequest;
+use crate::common::i18n::{current_locale, t};
+use crate::common::{generate_resume_id, safe_email_log, storage, ApiError, AppState};
+use crate::services::{guest_apply, storage_usage};
+use axum::{
+    extract::{Extension, Multipart, Path},
+    http::StatusCode,
+    response::{IntoResponse, Json},
+};
+use serde::de::DeserializeOwned;
+use serde_json::json;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::{error, info};
+
+fn parse_field<T: DeserializeOwned>(key: &str, value: serde_json::Value) -> Result<T, ApiError> {
+    serde_json::from_value(value)
+        .map_err(|e| ApiError::BadRequest(format!("Invalid {}: {}", key, e)))
+}
+
+/// POST /api/jobs/:id/guest-apply - Apply with a name, an email and a resume
+///
+/// Multipart fields: `name`, `email` and `resume` (PDF) are required; `cover_letter` and
+/// `source` are text, `consent`, `work_authorization` and `screening_answers` are JSON as
+/// in `POST /api/applications`. The application is owned by a provisional account, and a
+/// link to claim it is emailed to the guest.
+pub async fn guest_apply(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    Path(job_id): Path<String>,
+    mut multipart: Multipart,
+) -> Result<impl IntoResponse, ApiError> {
+    let state = state_lock.read().await.clone();
+
+    let mut fields: HashMap<String, String> = HashMap::new();
+    let mut upload: Option<(String, axum::body::Bytes)> = None;
+    while let Some(field) = multipart
+        .next_field()
+        .await
+        .map_err(|_| ApiError::BadRequest("Invalid multipart body".to_string()))?
+    {
+        let Some(name) = field.name().map(|n| n.to_string()) else {
+            continue;
+        };
+        if name == "resume" {
+            let filename = field.file_name().unwrap_or("resume.pdf").to_string();
+            let data = field
+                .bytes()
+                .await
+                .map_err(|_| ApiError::BadRequest("Invalid file".to_string()))?;
+            upload = Some((filename, data));
+        } else {
+            let value = field
+                .text()
+                .await
+                .map_err(|_| ApiError::BadRequest(format!("Invalid {} field", name)))?;
+            fields.insert(name, value);
+        }
+    }
+
+    let (name, email) = guest_apply::validate_guest(
+        fields.get("name").map(String::as_str).unwrap_or_default(),
+        fields.get("email").map(String::as_str).unwrap_or_default(),
+    )
+    .map_err(ApiError::ValidationError)?;
+    let (filename, data) =
+        upload.ok_or_else(|| ApiError::BadRequest("No resume file provided".to_string()))?;
+    if !filename.ends_with(".pdf") {
+        return Err(ApiError::BadRequest(
+            "Only PDF files are allowed".to_string(),
+        ));
+    }
+
+    let text = |key: &str| {
+        fields
+            .get(key)
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty())
+    };
+    let json_field = |key: &str| -> Result<Option<serde_json::Value>, ApiError> {
+        text(key)
+            .map(|v| {
+                serde_json::from_str(&v)
+                    .map_err(|_| ApiError::BadRequest(format!("{} must be valid JSON", key)))
+            })
+            .transpose()
+    };
+    let consent = json_field("consent")?
+        .map(|v| parse_field("consent", v))
+        .transpose()?;
+    let work_authorization = json_field("work_authorization")?
+        .map(|v| parse_field("work_authorization", v))
+        .transpose()?
+        .unwrap_or_default();
+    let screening_answers = json_field("screening_answers")?
+        .map(|v| parse_field("screening_answers", v))
+        .transpose()?
+        .unwrap_or_default();
+
+    let job_title: String =
+        sqlx::query_scalar("SELECT title FROM jobs WHERE id = ? AND deleted_at IS NULL")
+            .bind(&job_id)
+            .fetch_optional(&state.db)
+            .await
+            .map_err(ApiError::DatabaseError)?
+            .ok_or_else(|| ApiError::NotFound("Job not found".to_string()))?;
+
+    let locale = current_locale();
+    let user_id = guest_apply::provisional_user(&state.db, &name, &email, locale.as_str())
+        .await
+        .map_err(ApiError::DatabaseError)?;
+
+    storage_usage::ensure_within_quota(
+        &state,
+        storage_usage::OWNER_USER,
+        &user_id,
+        data.len() as i64,
+    )
+    .await?;
+
+    let resume_id = generate_resume_id();
+    let safe_filename = format!("{}.pdf", resume_id);
+    storage::save_file(
+        &state,
+        "resumes",
+        &state.resumes_dir,
+        &safe_filename,
+        data.to_vec(),
+        "application/pdf",
+    )
+    .await?;
+
+    sqlx::query(
+        r#"
+        INSERT INTO resumes (id, user_id, filename, status, submitted_at, file_size)
+        VALUES (?, ?, ?, 'submitted', ?, ?)
+        "#,
+    )
+    .bind(&resume_id)
+    .bind(&user_id)
+    .bind(&safe_filename)
+    .bind(chrono::Utc::now().to_rfc3339())
+    .bind(data.len() as i64)
+    .execute(&state.db)
+    .await
+    .map_err(ApiError::DatabaseError)?;
+
+    storage_usage::record_file(
+        &state.db,
+        storage_usage::CATEGORY_RESUME,
+        &resume_id,
+        storage_usage::OWNER_USER,
+        &user_id,
+        data.len() as i64,
+    )
+    .await;
+
+    // Issued before applying so a guest retrying after a refused application keeps the
+    // same provisional account
+    let claim = guest_apply::issue_claim(&state.db, &user_id, &email)
+        .await
+        .map_err(ApiError::DatabaseError)?;
+
+    // The provisional account applies like any candidate, so every application check holds
+    let account_email: String = sqlx::query_scalar("SELECT email FROM users WHERE id = ?")
+        .bind(&user_id)
+        .fetch_one(&state.db)
+        .await
+        .map_err(ApiError::DatabaseError)?;
+    let applied = create_application(
+        Extension(state_lock.clone()),
+        AuthedUser {
+            id: user_id.clone(),
+            email: account_email,
+            is_admin: false,
+        },
+        Json(CreateApplicationRequest {
+            job_id: job_id.clone(),
+            resume_id: Some(resume_id.clone()),
+            cover_letter: text("cover_letter"),
+            source: text("source"),
+            consent,
+            work_authorization,
+            screening_answers,
+        }),
+    )
+    .await;
+    let application = match applied {
+        Ok(Json(application)) => application,
+        Err(e) => {
+            sqlx::query("UPDATE resumes SET deleted_at = datetime('now') WHERE id = ?")
+                .bind(&resume_id)
+                .execute(&state.db)
+                .await
+                .map_err(ApiError::DatabaseError)?;
+            return Err(e);
+        }
+    };
+
+    let url = format!(
+        "{}/guest/claim/{}",
+        state.config.frontend_url,
+        claim.token
+    );
+    let days = guest_apply::GUEST_CLAIM_TTL_DAYS.to_string();
+    let args = [
+        ("name", name.as_str()),
+        ("job", job_title.as_str()),
+        ("url", url.as_str()),
+        ("days", days.as_str()),
+    ];
+    let body = format!(
+        r#"<html><body style="font-family: Arial, sans-serif; line-height: 1.6; color: #333;">
+<div style="max-width: 600px; margin: 0 auto; padding: 20px;">
+<p>{}</p>
+{}
+<p>{}</p>
+</div></body></html>"#,
+        t("email.greeting", locale, &args),
+        t("email.guest_claim.body", locale, &args),
+        t("email.signoff", locale, &args),
+    );
+    // The application stands either way; the guest can apply again for a new link
+    let claim_email_sent = match state
+        .email
+        .send_email(
+            vec![email.clone()],
+            &t("email.guest_claim.subject", locale, &args),
+            &body,
+            None,
+        )
+        .await
+    {
+        Ok(_) => true,
+        Err(e) => {
+            error!(error = %e, user_id = %user_id, "Failed to send guest claim link");
+            false
+        }
+    };
+
+    info!(
+        user_id = %user_id,
+        job_id = %job_id,
+        application_id = %application.id,
+        email = %safe_email_log(&email),
+        "Guest application submitted"
+    );
+
+    Ok((
+        StatusCode::CREATED,
+        Json(json!({
+            "application": application,
+            "claim_email": email,
+            "claim_email_sent": claim_email_sent,
+            "claim_expires_at": claim.expires_at,
+        })),
+    ))
+}
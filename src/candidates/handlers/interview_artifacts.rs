@@ -0,0 +1,243 @@
+// src/candidates/handlers/interview_artifacts.rs
+//! Shared notes, recordings and transcripts for completed interviews, visible to the
+//! hiring team only
+
+use axum::{
+    extract::{Extension, Multipart, Path},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::{error, info, warn};
+
+use crate::auth::AuthedUser;
+use crate::candidates::models::{Interview, InterviewRecord, UpdateSharedNotesRequest};
+use crate::common::{generate_interview_artifact_id, storage, ApiError, AppState};
+use crate::services::chat_webhooks::{self, ChatEvent};
+use crate::services::interview_artifacts::{self, ARTIFACTS_PREFIX, MAX_SHARED_NOTES_LENGTH};
+
+fn require_completed(interview: &Interview) -> Result<(), ApiError> {
+    if !interview_artifacts::is_completed(interview, chrono::Utc::now()) {
+        return Err(ApiError::BadRequest(
+            "Notes and files can be added once the interview has ended".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// GET /api/interviews/:id/record - Shared notes and uploaded files of an interview
+pub async fn get_interview_record(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Path(id): Path<String>,
+) -> Result<Json<InterviewRecord>, ApiError> {
+    let state = state_lock.read().await.clone();
+    interview_artifacts::require_hiring_team(&state.db, &id, &authed).await?;
+
+    Ok(Json(
+        interview_artifacts::load_record(&state.db, &id).await?,
+    ))
+}
+
+/// PUT /api/interviews/:id/notes - Replace the notes the hiring team shares for an interview
+pub async fn update_shared_notes(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Path(id): Path<String>,
+    Json(request): Json<UpdateSharedNotesRequest>,
+) -> Result<Json<InterviewRecord>, ApiError> {
+    let state = state_lock.read().await.clone();
+    let interview = interview_artifacts::require_hiring_team(&state.db, &id, &authed).await?;
+    require_completed(&interview)?;
+
+    let notes = request.notes.trim();
+    if notes.chars().count() > MAX_SHARED_NOTES_LENGTH {
+        return Err(ApiError::ValidationError(format!(
+            "Notes exceed maximum length of {} characters",
+            MAX_SHARED_NOTES_LENGTH
+        )));
+    }
+    let notes = (!notes.is_empty()).then_some(notes);
+
+    let had_notes: bool = sqlx::query_scalar(
+        "SELECT COALESCE(shared_notes, '') != '' FROM interviews WHERE id = ?",
+    )
+    .bind(&id)
+    .fetch_one(&state.db)
+    .await
+    .map_err(ApiError::DatabaseError)?;
+
+    sqlx::query(
+        r#"
+        UPDATE interviews
+        SET shared_notes = ?, shared_notes_updated_by = ?, shared_notes_updated_at = datetime('now')
+        WHERE id = ?
+        "#,
+    )
+    .bind(notes)
+    .bind(&authed.id)
+    .bind(&id)
+    .execute(&state.db)
+    .await
+    .map_err(|e| {
+        error!(error = %e, interview_id = %id, "Database error saving interview notes");
+        ApiError::DatabaseError(e)
+    })?;
+
+    info!(user_id = %authed.id, interview_id = %id, "Interview notes updated");
+
+    // The first notes on an interview are the panel's feedback being submitted
+    if notes.is_some() && !had_notes {
+        chat_webhooks::dispatch(&state, ChatEvent::InterviewFeedback, &id);
+    }
+
+    Ok(Json(
+        interview_artifacts::load_record(&state.db, &id).await?,
+    ))
+}
+
+/// POST /api/interviews/:id/artifacts - Upload a recording or transcript (multipart `kind`
+/// and `file`)
+pub async fn upload_interview_artifact(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Path(id): Path<String>,
+    mut multipart: Multipart,
+) -> Result<impl IntoResponse, ApiError> {
+    let state = state_lock.read().await.clone();
+    let interview = interview_artifacts::require_hiring_team(&state.db, &id, &authed).await?;
+    require_completed(&interview)?;
+
+    let mut kind: Option<String> = None;
+    let mut upload: Option<(Option<String>, axum::body::Bytes)> = None;
+    while let Some(field) = multipart
+        .next_field()
+        .await
+        .map_err(|e| ApiError::BadRequest(format!("Invalid multipart data: {}", e)))?
+    {
+        match field.name() {
+            Some("kind") => {
+                let value = field
+                    .text()
+                    .await
+                    .map_err(|e| ApiError::BadRequest(format!("Invalid kind field: {}", e)))?;
+                kind = Some(value.trim().to_lowercase());
+            }
+            Some("file") => {
+                let original_filename = field.file_name().map(str::to_string);
+                let data = field
+                    .bytes()
+                    .await
+                    .map_err(|e| ApiError::BadRequest(format!("Failed to read upload: {}", e)))?;
+                upload = Some((original_filename, data));
+            }
+            _ => {}
+        }
+    }
+    let kind = kind.ok_or_else(|| {
+        ApiError::ValidationError("kind is required: recording or transcript".to_string())
+    })?;
+    let (original_filename, data) =
+        upload.ok_or_else(|| ApiError::BadRequest("No file provided".to_string()))?;
+
+    let (content_type, extension) =
+        interview_artifacts::validate_artifact_upload(&kind, &data, original_filename.as_deref())?;
+
+    let artifact_id = generate_interview_artifact_id();
+    let filename = format!("{}.{}", artifact_id, extension);
+    storage::save_file(
+        &state,
+        ARTIFACTS_PREFIX,
+        &state.documents_dir,
+        &filename,
+        data.to_vec(),
+        content_type,
+    )
+    .await?;
+
+    sqlx::query(
+        r#"
+        INSERT INTO interview_artifacts (id, interview_id, kind, filename, original_filename, content_type, file_size, uploaded_by)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+        "#,
+    )
+    .bind(&artifact_id)
+    .bind(&id)
+    .bind(&kind)
+    .bind(&filename)
+    .bind(&original_filename)
+    .bind(content_type)
+    .bind(data.len() as i64)
+    .bind(&authed.id)
+    .execute(&state.db)
+    .await
+    .map_err(|e| {
+        error!(error = %e, interview_id = %id, "Database error saving interview artifact");
+        ApiError::DatabaseError(e)
+    })?;
+
+    info!(
+        user_id = %authed.id,
+        interview_id = %id,
+        artifact_id = %artifact_id,
+        kind = %kind,
+        file_size = data.len(),
+        "Interview artifact uploaded"
+    );
+
+    Ok((
+        StatusCode::CREATED,
+        Json(interview_artifacts::load_artifact(&state.db, &artifact_id).await?),
+    ))
+}
+
+/// DELETE /api/interviews/:id/artifacts/:artifact_id - Remove a recording or transcript
+pub async fn delete_interview_artifact(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Path((id, artifact_id)): Path<(String, String)>,
+) -> Result<StatusCode, ApiError> {
+    let state = state_lock.read().await.clone();
+    interview_artifacts::require_hiring_team(&state.db, &id, &authed).await?;
+
+    let filename: String = sqlx::query_scalar(
+        "SELECT filename FROM interview_artifacts WHERE id = ? AND interview_id = ?",
+    )
+    .bind(&artifact_id)
+    .bind(&id)
+    .fetch_optional(&state.db)
+    .await
+    .map_err(ApiError::DatabaseError)?
+    .ok_or_else(|| ApiError::NotFound("File not found".to_string()))?;
+
+    sqlx::query("DELETE FROM interview_artifacts WHERE id = ?")
+        .bind(&artifact_id)
+        .execute(&state.db)
+        .await
+        .map_err(ApiError::DatabaseError)?;
+
+    // The row is gone either way; a file left behind is only logged
+    if storage::uses_s3(&state).await {
+        let s3_key = format!("{}/{}", ARTIFACTS_PREFIX, filename);
+        if let Err(e) = state.object_store.delete_file(&s3_key).await {
+            warn!(error = %e, s3_key = %s3_key, "Failed to delete interview artifact from S3");
+        }
+    }
+    let local = state.documents_dir.join(&filename);
+    if local.exists() {
+        if let Err(e) = tokio::fs::remove_file(&local).await {
+            warn!(error = %e, filename = %filename, "Failed to delete local interview artifact");
+        }
+    }
+
+    info!(
+        user_id = %authed.id,
+        interview_id = %id,
+        artifact_id = %artifact_id,
+        "Interview artifact deleted"
+    );
+
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -1,6 +1,8 @@
 // src/candidates/handlers/interview_email_templates.rs
 //! Email templates for interview scheduling and notifications
 
+use crate::services::sanitize::sanitize_html;
+
 pub struct InterviewEmailTemplate {
     pub subject: String,
     pub body: String,
@@ -51,7 +53,7 @@ pub fn get_interview_scheduled_template(
                     <p style="margin: 0; color: #6B7280; white-space: pre-wrap;">{}</p>
                 </div>
                 "#,
-                note_text
+                sanitize_html(note_text)
             )
         } else {
             String::new()
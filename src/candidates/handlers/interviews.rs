@@ -1,7 +1,7 @@
 // src/candidates/handlers/interviews.rs
 
 use axum::{
-    extract::{Extension, Path},
+    extract::{Extension, Path, Query},
     http::StatusCode,
     response::IntoResponse,
     Json,
@@ -12,9 +12,10 @@ use serde_json::json;
 use tracing::info;
 
 use crate::auth::AuthedUser;
+use crate::common::timezone::{user_timezone, validate_timezone, DEFAULT_TIMEZONE};
 use crate::common::{ApiError, AppState};
 use crate::candidates::models::*;
-use crate::services::interviews;
+use crate::services::{interviews, whatsapp};
 
 /// POST /api/admin/interviews/schedule - Schedule an interview
 pub async fn schedule_interview(
@@ -35,9 +36,9 @@ pub async fn schedule_interview(
         "Admin scheduling interview"
     );
 
-    let interview = interviews::schedule_interview(
+    let mut interview = interviews::schedule_interview(
         &state.db,
-        state.google_service.clone(),
+        state.calendar.clone(),
         body,
         &authed.id,
     )
@@ -46,7 +47,7 @@ pub async fn schedule_interview(
     // Send calendar invitations to all attendees
     if let Err(e) = interviews::send_calendar_invitations(
         &state.db,
-        &state.aws_service,
+        state.email.as_ref(),
         &interview.id,
     )
     .await
@@ -59,6 +60,24 @@ pub async fn schedule_interview(
         );
     }
 
+    // Confirm on WhatsApp if the candidate opted in
+    if let Err(e) = whatsapp::notify_interview(
+        &state.db,
+        &state.whatsapp_service,
+        "interview_confirmation",
+        &interview.id,
+    )
+    .await
+    {
+        tracing::warn!(
+            error = %e,
+            interview_id = %interview.id,
+            "Failed to send WhatsApp confirmation, but interview was created"
+        );
+    }
+
+    interview.localize(&viewer_timezone(&state, &authed.id).await);
+
     Ok((StatusCode::CREATED, Json(interview)))
 }
 
@@ -82,13 +101,14 @@ pub async fn update_interview(
         "Admin updating interview"
     );
 
-    let interview = interviews::update_interview(
+    let mut interview = interviews::update_interview(
         &state.db,
-        state.aws_service.clone(),
+        state.email.clone(),
         &id,
         body,
         &authed.id
     ).await?;
+    interview.localize(&viewer_timezone(&state, &authed.id).await);
 
     Ok(Json(interview))
 }
@@ -112,11 +132,87 @@ pub async fn cancel_interview(
         "Admin canceling interview"
     );
 
-    interviews::cancel_interview(&state.db, &state.aws_service, &id).await?;
+    interviews::cancel_interview(&state.db, state.email.as_ref(), &id).await?;
 
     Ok(Json(json!({ "message": "Interview cancelled successfully" })))
 }
 
+/// GET /api/admin/interviews/calendar - Interviews bucketed by day for calendar views
+pub async fn get_interview_calendar(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Query(query): Query<InterviewCalendarQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    let state = state_lock.read().await;
+    if !authed.is_admin {
+        return Err(ApiError::Forbidden(
+            "Only admins can view the interview calendar".to_string(),
+        ));
+    }
+
+    let timezone = match query.tz.as_deref() {
+        Some(tz) => validate_timezone(tz).map_err(ApiError::BadRequest)?,
+        None => viewer_timezone(&state, &authed.id).await,
+    };
+
+    let (from, to) = interviews::resolve_calendar_range(
+        query.from.as_deref(),
+        query.to.as_deref(),
+        crate::common::timezone::local_date(chrono::Utc::now(), &timezone),
+    )?;
+
+    let calendar = interviews::get_interview_calendar(
+        &state.db,
+        from,
+        to,
+        query.panelist.as_deref(),
+        &timezone,
+    )
+    .await?;
+
+    Ok(Json(calendar))
+}
+
+/// POST /api/admin/interviews/calendar-sync - Pull Google Calendar edits and deletions now
+pub async fn sync_interview_calendar(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+) -> Result<impl IntoResponse, ApiError> {
+    let state = state_lock.read().await;
+    if !authed.is_admin {
+        return Err(ApiError::Forbidden(
+            "Only admins can sync the interview calendar".to_string(),
+        ));
+    }
+
+    info!(admin_id = %authed.id, "Admin triggered Google Calendar sync");
+
+    let summary =
+        interviews::sync_calendar_changes(&state.db, state.calendar.as_ref(), state.email.as_ref())
+            .await?;
+
+    Ok(Json(summary))
+}
+
+/// GET /api/me/interviews - The caller's own upcoming and past interviews
+pub async fn get_my_interviews(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+) -> Result<impl IntoResponse, ApiError> {
+    let state = state_lock.read().await;
+
+    let timezone = viewer_timezone(&state, &authed.id).await;
+    let hub = interviews::get_candidate_interview_hub(
+        &state.db,
+        &authed.id,
+        &timezone,
+        chrono::Utc::now(),
+    )
+    .await?;
+
+    Ok(Json(hub))
+}
+
 /// GET /api/admin/interviews/:id - Get a single interview by ID
 pub async fn get_interview(
     Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
@@ -130,7 +226,8 @@ pub async fn get_interview(
         ));
     }
 
-    let interview = interviews::get_interview(&state.db, &id).await?;
+    let mut interview = interviews::get_interview(&state.db, &id).await?;
+    interview.localize(&viewer_timezone(&state, &authed.id).await);
 
     Ok(Json(interview))
 }
@@ -149,7 +246,11 @@ pub async fn get_candidate_interviews(
         ));
     }
 
-    let interviews = interviews::get_candidate_interviews(&state.db, &candidate_id).await?;
+    let mut interviews = interviews::get_candidate_interviews(&state.db, &candidate_id).await?;
+    let timezone = viewer_timezone(&state, &authed.id).await;
+    for details in &mut interviews {
+        details.interview.localize(&timezone);
+    }
 
     Ok(Json(interviews))
 }
@@ -184,7 +285,11 @@ pub async fn get_job_interviews(
         ));
     }
 
-    let interviews = interviews::get_job_interviews(&state.db, &job_id).await?;
+    let mut interviews = interviews::get_job_interviews(&state.db, &job_id).await?;
+    let timezone = viewer_timezone(&state, &authed.id).await;
+    for details in &mut interviews {
+        details.interview.localize(&timezone);
+    }
 
     Ok(Json(interviews))
 }
@@ -207,7 +312,14 @@ pub async fn create_google_meet_link(
         "Admin creating Google Meet link"
     );
 
-    let response = interviews::create_google_meet_link(state.google_service.clone(), body).await?;
+    let response = interviews::create_google_meet_link(state.calendar.clone(), body).await?;
 
     Ok(Json(response))
 }
+
+/// The caller's preferred time zone, or UTC when unset
+async fn viewer_timezone(state: &AppState, user_id: &str) -> String {
+    user_timezone(&state.db, user_id)
+        .await
+        .unwrap_or_else(|| DEFAULT_TIMEZONE.to_string())
+}
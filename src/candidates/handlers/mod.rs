@@ -1,13 +1,25 @@
 // src/candidates/handlers/mod.rs
 
 pub mod ai;
+pub mod application_feed;
 pub mod applications;
+pub mod calendar_feed;
+pub mod documents;
+pub mod eeo;
 pub mod email_templates;
+pub mod feedback_versions;
+pub mod guest_apply;
+pub mod interview_artifacts;
 pub mod interview_email_templates;
 pub mod files;
 pub mod interviews;
+pub mod recently_viewed;
+pub mod recommendations;
+pub mod resume_exports;
 pub mod resumes;
 pub mod saved_jobs;
+pub mod surveys;
+pub mod video_uploads;
 pub mod videos;
 pub mod youtube_videos;
 
@@ -0,0 +1,98 @@
+// src/candidates/handlers/recently_viewed.rs
+//! The caller's recently viewed jobs and the setting that turns tracking off
+
+use axum::{
+    extract::{Extension, Query},
+    http::StatusCode,
+    Json,
+};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::info;
+
+use crate::auth::AuthedUser;
+use crate::candidates::models::{
+    RecentlyViewedQuery, RecentlyViewedResponse, ViewTrackingSettings,
+};
+use crate::candidates::recently_viewed;
+use crate::common::{ApiError, AppState};
+
+const DEFAULT_LIMIT: i64 = 20;
+const MAX_LIMIT: i64 = 50;
+
+/// GET /api/me/recently-viewed - Jobs the caller viewed, newest first, and the one to resume
+pub async fn get_recently_viewed(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Query(query): Query<RecentlyViewedQuery>,
+) -> Result<Json<RecentlyViewedResponse>, ApiError> {
+    let state = state_lock.read().await.clone();
+    let limit = query.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+
+    let tracking_enabled = recently_viewed::tracking_enabled(&state.db, &authed.id)
+        .await
+        .map_err(ApiError::DatabaseError)?;
+    let jobs = if tracking_enabled {
+        recently_viewed::recently_viewed(&state.db, &authed.id, limit)
+            .await
+            .map_err(ApiError::DatabaseError)?
+    } else {
+        Vec::new()
+    };
+
+    Ok(Json(RecentlyViewedResponse {
+        tracking_enabled,
+        continue_where_left_off: recently_viewed::continue_where_left_off(&jobs),
+        jobs,
+    }))
+}
+
+/// DELETE /api/me/recently-viewed - Clear the caller's view history
+pub async fn clear_recently_viewed(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+) -> Result<StatusCode, ApiError> {
+    let state = state_lock.read().await.clone();
+
+    let cleared = recently_viewed::forget_views(&state.db, &authed.id)
+        .await
+        .map_err(ApiError::DatabaseError)?;
+    info!(user_id = %authed.id, cleared = cleared, "Cleared recently viewed jobs");
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// GET /api/me/recently-viewed/settings - Whether job views are being tracked
+pub async fn get_view_tracking_settings(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+) -> Result<Json<ViewTrackingSettings>, ApiError> {
+    let state = state_lock.read().await.clone();
+
+    let tracking_enabled = recently_viewed::tracking_enabled(&state.db, &authed.id)
+        .await
+        .map_err(ApiError::DatabaseError)?;
+    Ok(Json(ViewTrackingSettings { tracking_enabled }))
+}
+
+/// PUT /api/me/recently-viewed/settings - Turn view tracking on or off
+///
+/// Turning it off also clears the existing history.
+pub async fn update_view_tracking_settings(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Json(payload): Json<ViewTrackingSettings>,
+) -> Result<Json<ViewTrackingSettings>, ApiError> {
+    let state = state_lock.read().await.clone();
+
+    recently_viewed::set_tracking(&state.db, &authed.id, payload.tracking_enabled)
+        .await
+        .map_err(ApiError::DatabaseError)?;
+    info!(
+        user_id = %authed.id,
+        tracking_enabled = payload.tracking_enabled,
+        "Updated job view tracking"
+    );
+
+    Ok(Json(payload))
+}
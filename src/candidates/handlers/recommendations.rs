@@ -0,0 +1,252 @@
+// src/candidates/handlers/recommendations.rs
+//! Job recommendations for the signed-in candidate
+
+use axum::{
+    extract::{Extension, Path, Query},
+    http::StatusCode,
+    Json,
+};
+use sqlx::{FromRow, SqlitePool};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::info;
+
+use crate::auth::AuthedUser;
+use crate::candidates::models::{
+    DismissRecommendationRequest, RecommendedJob, RecommendedJobsQuery,
+};
+use crate::candidates::recommendations::{
+    parse_skills, resume_skills, score_job, CandidateSignals, JobFacts, DISMISS_REASONS,
+};
+use crate::common::{generate_recommendation_dismissal_id, ApiError, AppState};
+use crate::services::pii::Sealed;
+
+const DEFAULT_LIMIT: i64 = 10;
+const MAX_LIMIT: i64 = 50;
+
+/// Newest open jobs considered per request
+const CANDIDATE_POOL_SIZE: i64 = 500;
+
+/// Shown for the newest-jobs fallback when nothing is known about the candidate yet
+const FALLBACK_EXPLANATION: &str =
+    "Recently posted; add skills to your profile for more personal recommendations";
+
+#[derive(Debug, FromRow)]
+struct CandidateJob {
+    #[sqlx(flatten)]
+    facts: JobFacts,
+    company_logo_url: Option<String>,
+    salary_min: Option<i64>,
+    salary_max: Option<i64>,
+    published_at: Option<String>,
+}
+
+impl CandidateJob {
+    fn recommend(self, score: i64, explanation: String) -> RecommendedJob {
+        RecommendedJob {
+            job_id: self.facts.id,
+            title: self.facts.title,
+            company: self.facts.company,
+            company_logo_url: self.company_logo_url,
+            location: self.facts.location,
+            job_type: self.facts.job_type,
+            salary_min: self.salary_min,
+            salary_max: self.salary_max,
+            published_at: self.published_at,
+            score,
+            explanation,
+        }
+    }
+}
+
+/// Everything known about what `user_id` is looking for
+async fn load_signals(db: &SqlitePool, user_id: &str) -> Result<CandidateSignals, sqlx::Error> {
+    let mut signals = CandidateSignals::default();
+
+    let profile: Option<(Option<String>, Sealed)> =
+        sqlx::query_as("SELECT skills, location FROM profiles WHERE user_id = ?")
+            .bind(user_id)
+            .fetch_optional(db)
+            .await?;
+    if let Some((skills, location)) = profile {
+        signals.add_skills(parse_skills(skills.as_deref()));
+        signals.add_location(location.into_inner().as_deref());
+    }
+
+    let parsed_resume: Option<(Option<String>,)> = sqlx::query_as(
+        r#"
+        SELECT parsed_json FROM resumes
+        WHERE user_id = ? AND deleted_at IS NULL AND parsed_json IS NOT NULL
+        ORDER BY submitted_at DESC
+        LIMIT 1
+        "#,
+    )
+    .bind(user_id)
+    .fetch_optional(db)
+    .await?;
+    if let Some((parsed_json,)) = parsed_resume {
+        signals.add_skills(resume_skills(parsed_json.as_deref()));
+    }
+
+    let history = sqlx::query_as::<_, JobFacts>(
+        r#"
+        SELECT id, title, company, location, job_type, experience_level, requirements, description
+        FROM jobs
+        WHERE id IN (SELECT job_id FROM saved_jobs WHERE user_id = ?)
+           OR id IN (SELECT job_id FROM applications WHERE user_id = ?)
+        "#,
+    )
+    .bind(user_id)
+    .bind(user_id)
+    .fetch_all(db)
+    .await?;
+    for job in &history {
+        signals.learn_from(job);
+    }
+
+    Ok(signals)
+}
+
+/// GET /api/me/recommended-jobs - Open jobs that fit the caller, each with why it was picked
+///
+/// Jobs the caller already saved, applied to or dismissed are left out. With nothing to go
+/// on yet, the newest openings are returned instead.
+pub async fn get_recommended_jobs(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Query(query): Query<RecommendedJobsQuery>,
+) -> Result<Json<Vec<RecommendedJob>>, ApiError> {
+    let state = state_lock.read().await.clone();
+    let limit = query.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT) as usize;
+
+    let signals = load_signals(&state.db, &authed.id)
+        .await
+        .map_err(ApiError::DatabaseError)?;
+
+    let pool = sqlx::query_as::<_, CandidateJob>(
+        r#"
+        SELECT id, title, company, location, job_type, experience_level, requirements,
+               description, company_logo_url, salary_min, salary_max, published_at
+        FROM jobs
+        WHERE status = 'active' AND deleted_at IS NULL
+          AND id NOT IN (SELECT job_id FROM saved_jobs WHERE user_id = ?)
+          AND id NOT IN (SELECT job_id FROM applications WHERE user_id = ?)
+          AND id NOT IN (SELECT job_id FROM job_recommendation_dismissals WHERE user_id = ?)
+        ORDER BY COALESCE(published_at, created_at) DESC
+        LIMIT ?
+        "#,
+    )
+    .bind(&authed.id)
+    .bind(&authed.id)
+    .bind(&authed.id)
+    .bind(CANDIDATE_POOL_SIZE)
+    .fetch_all(&state.db)
+    .await
+    .map_err(ApiError::DatabaseError)?;
+
+    // The pool is newest first and the sort is stable, so ties go to the newer job
+    let mut scored: Vec<(CandidateJob, i64, String)> = Vec::new();
+    let mut unmatched = Vec::new();
+    for job in pool {
+        let fit = score_job(&signals, &job.facts);
+        if fit.score > 0 {
+            let explanation = fit.explanation();
+            scored.push((job, fit.score, explanation));
+        } else {
+            unmatched.push(job);
+        }
+    }
+    scored.sort_by_key(|(_, score, _)| std::cmp::Reverse(*score));
+
+    let recommendations: Vec<RecommendedJob> = if scored.is_empty() {
+        unmatched
+            .into_iter()
+            .take(limit)
+            .map(|job| job.recommend(0, FALLBACK_EXPLANATION.to_string()))
+            .collect()
+    } else {
+        scored
+            .into_iter()
+            .take(limit)
+            .map(|(job, score, explanation)| job.recommend(score, explanation))
+            .collect()
+    };
+
+    Ok(Json(recommendations))
+}
+
+/// POST /api/me/recommended-jobs/:job_id/dismiss - Stop recommending a job
+///
+/// The score and explanation the job would have been shown with are stored with the reason,
+/// so dismissals can be read back against the rules that produced them.
+pub async fn dismiss_recommended_job(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Path(job_id): Path<String>,
+    Json(payload): Json<DismissRecommendationRequest>,
+) -> Result<StatusCode, ApiError> {
+    let state = state_lock.read().await.clone();
+
+    let reason = payload
+        .reason
+        .map(|r| r.trim().to_lowercase())
+        .filter(|r| !r.is_empty());
+    if let Some(reason) = &reason {
+        if !DISMISS_REASONS.contains(&reason.as_str()) {
+            return Err(ApiError::ValidationError(format!(
+                "Invalid reason '{}'; expected one of: {}",
+                reason,
+                DISMISS_REASONS.join(", ")
+            )));
+        }
+    }
+
+    let job = sqlx::query_as::<_, JobFacts>(
+        r#"
+        SELECT id, title, company, location, job_type, experience_level, requirements, description
+        FROM jobs
+        WHERE id = ? AND deleted_at IS NULL
+        "#,
+    )
+    .bind(&job_id)
+    .fetch_optional(&state.db)
+    .await
+    .map_err(ApiError::DatabaseError)?
+    .ok_or_else(|| ApiError::NotFound("Job not found".to_string()))?;
+
+    let signals = load_signals(&state.db, &authed.id)
+        .await
+        .map_err(ApiError::DatabaseError)?;
+    let fit = score_job(&signals, &job);
+
+    sqlx::query(
+        r#"
+        INSERT INTO job_recommendation_dismissals (id, user_id, job_id, reason, score, explanation)
+        VALUES (?, ?, ?, ?, ?, ?)
+        ON CONFLICT(user_id, job_id) DO UPDATE SET
+            reason = excluded.reason,
+            score = excluded.score,
+            explanation = excluded.explanation,
+            dismissed_at = datetime('now')
+        "#,
+    )
+    .bind(generate_recommendation_dismissal_id())
+    .bind(&authed.id)
+    .bind(&job.id)
+    .bind(&reason)
+    .bind(fit.score)
+    .bind(fit.explanation())
+    .execute(&state.db)
+    .await
+    .map_err(ApiError::DatabaseError)?;
+
+    info!(
+        user_id = %authed.id,
+        job_id = %job.id,
+        reason = ?reason,
+        score = fit.score,
+        "Recommendation dismissed"
+    );
+
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,425 @@
+// src/candidates/handlers/resume_exports.rs
+//! Bulk resume download for a job as a ZIP archive
+
+use axum::{
+    body::Body,
+    extract::{Extension, Path, Query},
+    http::{header, StatusCode},
+    response::{IntoResponse, Json, Response},
+};
+use bytes::Bytes;
+use std::collections::HashSet;
+use std::io::{Seek, Write};
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::io::AsyncReadExt;
+use tokio::sync::RwLock;
+use tracing::{error, info, warn};
+use zip::{write::FileOptions, CompressionMethod, ZipWriter};
+
+use super::resumes::read_resume_file;
+use crate::auth::AuthedUser;
+use crate::candidates::models::{ResumeExport, ResumeZipQuery};
+use crate::common::{generate_export_id, ApiError, AppState};
+use crate::services::monitoring::{self, SecurityActivity};
+
+/// Jobs with more resumes than this are zipped in the background
+const SYNC_ZIP_MAX_RESUMES: usize = 20;
+
+/// Bytes read from the archive per chunk of the response body
+const ZIP_CHUNK_SIZE: usize = 64 * 1024;
+
+/// A resume to include in the archive and the name it gets inside the ZIP
+struct ZipEntry {
+    filename: String,
+    archive_name: String,
+}
+
+/// GET /api/admin/jobs/:id/resumes.zip - Download all applicants' resumes for a job
+///
+/// Small jobs get the ZIP directly. Large jobs (or `?async=true`) get `202 Accepted` with
+/// an export record to poll at `/api/admin/resume-exports/:id`.
+pub async fn download_job_resumes_zip(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Path(job_id): Path<String>,
+    Query(query): Query<ResumeZipQuery>,
+) -> Result<Response, ApiError> {
+    let state = state_lock.read().await.clone();
+
+    if !authed.is_admin {
+        warn!(
+            user_id = %authed.id,
+            "Resume ZIP download denied: admin privileges required"
+        );
+        return Err(ApiError::Forbidden("Admin privileges required".to_string()));
+    }
+
+    let job_exists: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM jobs WHERE id = ?")
+        .bind(&job_id)
+        .fetch_one(&state.db)
+        .await
+        .map_err(ApiError::DatabaseError)?;
+
+    if job_exists == 0 {
+        return Err(ApiError::NotFound(format!("Job not found: {}", job_id)));
+    }
+
+    let entries = collect_job_resumes(&state, &job_id).await?;
+    if entries.is_empty() {
+        return Err(ApiError::NotFound(
+            "No applicant resumes found for this job".to_string(),
+        ));
+    }
+
+    monitoring::record_security_activity(
+        &state,
+        SecurityActivity::Export,
+        Some(&authed.id),
+        None,
+        entries.len() as i64,
+        "/api/admin/jobs/:id/resumes.zip",
+    )
+    .await;
+
+    if !query.run_async && entries.len() <= SYNC_ZIP_MAX_RESUMES {
+        let file_path = exports_dir(&state)
+            .await?
+            .join(format!("{}.zip", generate_export_id()));
+        let written = match write_zip(&state, &entries, &file_path, None).await {
+            Ok(missing_count) => tokio::fs::File::open(&file_path)
+                .await
+                .map(|file| (missing_count, file))
+                .map_err(|e| ApiError::ExportError(format!("Failed to open export file: {}", e))),
+            Err(e) => Err(e),
+        };
+        // The open handle keeps the archive readable while the body streams out
+        if let Err(e) = tokio::fs::remove_file(&file_path).await {
+            warn!(error = %e, path = %file_path.display(), "Failed to remove temporary resume ZIP");
+        }
+        let (missing_count, file) = written?;
+
+        info!(
+            admin_user_id = %authed.id,
+            job_id = %job_id,
+            resume_count = entries.len() - missing_count,
+            missing_count = missing_count,
+            "Resume ZIP generated"
+        );
+
+        return Ok(zip_response(&job_id, file));
+    }
+
+    let export_id = generate_export_id();
+    sqlx::query(
+        r#"
+        INSERT INTO resume_exports (id, job_id, status, total, processed, requested_by, created_at)
+        VALUES (?, ?, 'pending', ?, 0, ?, datetime('now'))
+        "#,
+    )
+    .bind(&export_id)
+    .bind(&job_id)
+    .bind(entries.len() as i64)
+    .bind(&authed.id)
+    .execute(&state.db)
+    .await
+    .map_err(ApiError::DatabaseError)?;
+
+    info!(
+        admin_user_id = %authed.id,
+        job_id = %job_id,
+        export_id = %export_id,
+        resume_count = entries.len(),
+        "Queued background resume ZIP export"
+    );
+
+    let task_state = state.clone();
+    let task_export_id = export_id.clone();
+    tokio::spawn(async move {
+        if let Err(e) = run_resume_export(&task_state, &task_export_id, entries).await {
+            error!(export_id = %task_export_id, error = %e, "Resume ZIP export failed");
+            let _ = sqlx::query(
+                "UPDATE resume_exports SET status = 'failed', error = ?, completed_at = datetime('now') WHERE id = ?",
+            )
+            .bind(e.to_string())
+            .bind(&task_export_id)
+            .execute(&task_state.db)
+            .await;
+        }
+    });
+
+    let export = fetch_export(&state, &export_id).await?;
+    Ok((StatusCode::ACCEPTED, Json(export)).into_response())
+}
+
+/// GET /api/admin/resume-exports/:id - Poll the progress of a background resume export
+pub async fn get_resume_export(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Path(export_id): Path<String>,
+) -> Result<Json<ResumeExport>, ApiError> {
+    let state = state_lock.read().await.clone();
+
+    if !authed.is_admin {
+        return Err(ApiError::Forbidden("Admin privileges required".to_string()));
+    }
+
+    Ok(Json(fetch_export(&state, &export_id).await?))
+}
+
+/// GET /api/admin/resume-exports/:id/download - Download a completed resume export
+pub async fn download_resume_export(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Path(export_id): Path<String>,
+) -> Result<Response, ApiError> {
+    let state = state_lock.read().await.clone();
+
+    if !authed.is_admin {
+        return Err(ApiError::Forbidden("Admin privileges required".to_string()));
+    }
+
+    let export = fetch_export(&state, &export_id).await?;
+    let file_path = match (export.status.as_str(), export.file_path) {
+        ("completed", Some(path)) => path,
+        _ => {
+            return Err(ApiError::BadRequest(format!(
+                "Export is not ready (status: {})",
+                export.status
+            )))
+        }
+    };
+
+    let file = tokio::fs::File::open(&file_path).await.map_err(|e| {
+        error!(export_id = %export_id, error = %e, "Failed to read resume export file");
+        ApiError::ExportError("Export file is no longer available".to_string())
+    })?;
+
+    Ok(zip_response(&export.job_id, file))
+}
+
+async fn fetch_export(state: &AppState, export_id: &str) -> Result<ResumeExport, ApiError> {
+    sqlx::query_as::<_, ResumeExport>("SELECT * FROM resume_exports WHERE id = ?")
+        .bind(export_id)
+        .fetch_optional(&state.db)
+        .await
+        .map_err(ApiError::DatabaseError)?
+        .ok_or_else(|| ApiError::NotFound("Export not found".to_string()))
+}
+
+/// Resumes attached to the job's active applications, named after the candidate
+async fn collect_job_resumes(state: &AppState, job_id: &str) -> Result<Vec<ZipEntry>, ApiError> {
+    let rows = sqlx::query_as::<
+        _,
+        (
+            String,
+            Option<String>,
+            Option<String>,
+            Option<String>,
+            String,
+        ),
+    >(
+        r#"
+        SELECT r.filename, p.first_name, p.last_name, u.name, u.email
+        FROM applications a
+        JOIN resumes r ON a.resume_id = r.id
+        JOIN users u ON a.user_id = u.id
+        LEFT JOIN profiles p ON p.user_id = a.user_id
+        WHERE a.job_id = ? AND a.archived_at IS NULL
+        ORDER BY a.applied_at ASC
+        "#,
+    )
+    .bind(job_id)
+    .fetch_all(&state.db)
+    .await
+    .map_err(ApiError::DatabaseError)?;
+
+    let mut used = HashSet::new();
+    Ok(rows
+        .into_iter()
+        .map(|(filename, first_name, last_name, name, email)| {
+            let profile_name = match (first_name, last_name) {
+                (Some(first), Some(last)) => Some(format!("{} {}", first, last)),
+                (first, last) => first.or(last),
+            };
+            let display = profile_name
+                .or(name)
+                .unwrap_or_else(|| email.split('@').next().unwrap_or_default().to_string());
+            ZipEntry {
+                filename,
+                archive_name: unique_archive_name(&display, &mut used),
+            }
+        })
+        .collect())
+}
+
+/// Turn a candidate name into a safe, unique `First_Last.pdf` entry name
+pub(crate) fn unique_archive_name(display_name: &str, used: &mut HashSet<String>) -> String {
+    let sanitized: String = display_name
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("_");
+    let base = if sanitized.is_empty() {
+        "candidate".to_string()
+    } else {
+        sanitized
+    };
+
+    let mut name = format!("{}.pdf", base);
+    let mut counter = 2;
+    while !used.insert(name.clone()) {
+        name = format!("{}_{}.pdf", base, counter);
+        counter += 1;
+    }
+    name
+}
+
+async fn run_resume_export(
+    state: &AppState,
+    export_id: &str,
+    entries: Vec<ZipEntry>,
+) -> Result<(), ApiError> {
+    sqlx::query("UPDATE resume_exports SET status = 'processing' WHERE id = ?")
+        .bind(export_id)
+        .execute(&state.db)
+        .await
+        .map_err(ApiError::DatabaseError)?;
+
+    let file_path = exports_dir(state).await?.join(format!("{}.zip", export_id));
+    let missing_count = write_zip(state, &entries, &file_path, Some(export_id)).await?;
+
+    sqlx::query(
+        "UPDATE resume_exports SET status = 'completed', file_path = ?, completed_at = datetime('now') WHERE id = ?",
+    )
+    .bind(file_path.to_string_lossy().to_string())
+    .bind(export_id)
+    .execute(&state.db)
+    .await
+    .map_err(ApiError::DatabaseError)?;
+
+    info!(
+        export_id = %export_id,
+        resume_count = entries.len() - missing_count,
+        missing_count = missing_count,
+        "Background resume ZIP export completed"
+    );
+
+    Ok(())
+}
+
+/// Where finished and in-progress archives are written
+async fn exports_dir(state: &AppState) -> Result<PathBuf, ApiError> {
+    let dir = state.resumes_dir.join("exports");
+    tokio::fs::create_dir_all(&dir)
+        .await
+        .map_err(|e| ApiError::ExportError(format!("Failed to create export directory: {}", e)))?;
+    Ok(dir)
+}
+
+/// Run blocking ZIP work off the async runtime
+async fn blocking<T, F>(work: F) -> Result<T, ApiError>
+where
+    T: Send + 'static,
+    F: FnOnce() -> Result<T, ApiError> + Send + 'static,
+{
+    tokio::task::spawn_blocking(work)
+        .await
+        .map_err(|e| ApiError::ExportError(format!("ZIP task failed: {}", e)))?
+}
+
+/// Write the archive to `file_path` one resume at a time, recording progress on the export
+/// if there is one; returns how many resumes could not be read
+async fn write_zip(
+    state: &AppState,
+    entries: &[ZipEntry],
+    file_path: &std::path::Path,
+    export_id: Option<&str>,
+) -> Result<usize, ApiError> {
+    let path = file_path.to_path_buf();
+    let mut zip = blocking(move || {
+        let file = std::fs::File::create(&path)
+            .map_err(|e| ApiError::ExportError(format!("Failed to create export file: {}", e)))?;
+        Ok(ZipWriter::new(file))
+    })
+    .await?;
+    let mut missing = Vec::new();
+
+    for (index, entry) in entries.iter().enumerate() {
+        match read_resume_file(state, &entry.filename).await {
+            Ok(bytes) => {
+                let name = entry.archive_name.clone();
+                zip = blocking(move || {
+                    add_file(&mut zip, &name, &bytes)?;
+                    Ok(zip)
+                })
+                .await?;
+            }
+            Err(_) => missing.push(entry.archive_name.clone()),
+        }
+
+        if let Some(export_id) = export_id {
+            sqlx::query("UPDATE resume_exports SET processed = ? WHERE id = ?")
+                .bind((index + 1) as i64)
+                .bind(export_id)
+                .execute(&state.db)
+                .await
+                .map_err(ApiError::DatabaseError)?;
+        }
+    }
+
+    let missing_count = missing.len();
+    blocking(move || finish_zip(zip, &missing).map(|_| ())).await?;
+    Ok(missing_count)
+}
+
+fn add_file<W: Write + Seek>(
+    zip: &mut ZipWriter<W>,
+    name: &str,
+    bytes: &[u8],
+) -> Result<(), ApiError> {
+    // PDFs are already compressed, so store them as-is
+    let options = FileOptions::default().compression_method(CompressionMethod::Stored);
+    zip.start_file(name, options)
+        .map_err(|e| ApiError::ExportError(format!("Failed to add {} to ZIP: {}", name, e)))?;
+    zip.write_all(bytes)
+        .map_err(|e| ApiError::ExportError(format!("Failed to write {} to ZIP: {}", name, e)))
+}
+
+/// Close the archive, listing resumes whose files could not be read
+fn finish_zip<W: Write + Seek>(mut zip: ZipWriter<W>, missing: &[String]) -> Result<W, ApiError> {
+    if !missing.is_empty() {
+        let note = format!(
+            "The following resumes could not be retrieved:\n{}\n",
+            missing.join("\n")
+        );
+        add_file(&mut zip, "MISSING_RESUMES.txt", note.as_bytes())?;
+    }
+
+    zip.finish()
+        .map_err(|e| ApiError::ExportError(format!("Failed to finalize ZIP: {}", e)))
+}
+
+/// Stream an archive to the client in chunks rather than reading it into memory
+fn zip_response(job_id: &str, file: tokio::fs::File) -> Response {
+    let chunks = futures::stream::try_unfold(file, |mut file| async move {
+        let mut chunk = vec![0; ZIP_CHUNK_SIZE];
+        let read = file.read(&mut chunk).await?;
+        if read == 0 {
+            return Ok::<_, std::io::Error>(None);
+        }
+        chunk.truncate(read);
+        Ok(Some((Bytes::from(chunk), file)))
+    });
+
+    let disposition = format!("attachment; filename=\"{}_resumes.zip\"", job_id);
+    (
+        StatusCode::OK,
+        [
+            (header::CONTENT_TYPE, "application/zip".to_string()),
+            (header::CONTENT_DISPOSITION, disposition),
+        ],
+        Body::from_stream(chunks),
+    )
+        .into_response()
+}
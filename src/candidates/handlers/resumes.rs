@@ -1,8 +1,15 @@
 // src/candidates/handlers/resumes.rs
 
 use crate::auth::AuthedUser;
+use crate::candidates::experience_years;
+use crate::candidates::handlers::files;
 use crate::candidates::models::{AdminResumeFilters, BulkResumeStatusUpdate, Resume, UpdateResumeLabelRequest};
-use crate::common::{generate_resume_id, ApiError, AppState};
+use crate::common::{generate_resume_id, storage, ApiError, AppState, ErrorCode};
+use crate::profile::completeness::refresh_completeness;
+use crate::services::downloads::FileKind;
+use crate::services::legal_hold;
+use crate::services::pii;
+use crate::services::storage_usage;
 use axum::{
     extract::{Extension, Multipart, Path, Query},
     http::StatusCode,
@@ -23,118 +30,190 @@ pub async fn upload_resume(
 
     info!(user_id = %authed.id, "User uploading resume");
 
-    // Check resume limit (max 5 resumes per user)
-    const MAX_RESUMES: i64 = 5;
-    let resume_count: i64 = sqlx::query_scalar(
-        "SELECT COUNT(*) FROM resumes WHERE user_id = ?"
-    )
-    .bind(&authed.id)
-    .fetch_one(&state.db)
-    .await
-    .map_err(ApiError::DatabaseError)?;
+    // Collect the upload; an optional `replaces` field makes it a new version of an existing resume
+    let mut upload: Option<(String, axum::body::Bytes)> = None;
+    let mut replaces: Option<String> = None;
+    while let Some(field) = multipart.next_field().await.unwrap() {
+        match field.name() {
+            Some("resume") => {
+                let filename = field.file_name().unwrap_or("resume.pdf").to_string();
+                let data = field
+                    .bytes()
+                    .await
+                    .map_err(|_| ApiError::BadRequest("Invalid file".to_string()))?;
+                upload = Some((filename, data));
+            }
+            Some("replaces") => {
+                let value = field
+                    .text()
+                    .await
+                    .map_err(|_| ApiError::BadRequest("Invalid replaces field".to_string()))?;
+                if !value.trim().is_empty() {
+                    replaces = Some(value.trim().to_string());
+                }
+            }
+            _ => {}
+        }
+    }
 
-    if resume_count >= MAX_RESUMES {
-        warn!(
-            user_id = %authed.id,
-            current_count = resume_count,
-            "Resume upload limit reached"
-        );
+    let (filename, data) =
+        upload.ok_or_else(|| ApiError::BadRequest("No resume file provided".to_string()))?;
+
+    // Validate PDF
+    if !filename.ends_with(".pdf") {
         return Err(ApiError::BadRequest(
-            format!("Resume limit reached. You can upload a maximum of {} resumes. Please delete an existing resume before uploading a new one.", MAX_RESUMES)
+            "Only PDF files are allowed".to_string(),
         ));
     }
 
-    // Extract file from multipart
-    while let Some(field) = multipart.next_field().await.unwrap() {
-        if field.name() == Some("resume") {
-            let filename = field.file_name().unwrap_or("resume.pdf").to_string();
-
-            let data = field
-                .bytes()
-                .await
-                .map_err(|_| ApiError::BadRequest("Invalid file".to_string()))?;
+    let previous = match &replaces {
+        Some(previous_id) => {
+            let previous = sqlx::query_as::<_, Resume>(
+                "SELECT * FROM resumes WHERE id = ? AND user_id = ? AND deleted_at IS NULL",
+            )
+            .bind(previous_id)
+            .bind(&authed.id)
+            .fetch_optional(&state.db)
+            .await
+            .map_err(ApiError::DatabaseError)?
+            .ok_or_else(|| ApiError::BadRequest("Resume to replace not found".to_string()))?;
 
-            // Validate PDF
-            if !filename.ends_with(".pdf") {
+            if previous.superseded_by.is_some() {
                 return Err(ApiError::BadRequest(
-                    "Only PDF files are allowed".to_string(),
+                    "Only the latest version of a resume can be replaced".to_string(),
                 ));
             }
+            Some(previous)
+        }
+        None => None,
+    };
 
-            // Save file
-            let resume_id = generate_resume_id();
-            let safe_filename = format!("{}.pdf", resume_id);
+    // Check resume limit (max 5 resumes per user); older versions don't count
+    const MAX_RESUMES: i64 = 5;
+    if previous.is_none() {
+        let resume_count: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM resumes WHERE user_id = ? AND superseded_by IS NULL"
+        )
+        .bind(&authed.id)
+        .fetch_one(&state.db)
+        .await
+        .map_err(ApiError::DatabaseError)?;
 
-            // Check storage type setting
-            let storage_type = state
-                .settings_service
-                .get_setting("storage_type")
-                .await
-                .ok()
-                .flatten()
-                .unwrap_or_else(|| "local".to_string());
-
-            if storage_type.starts_with("s3") {
-                // Upload to S3
-                let s3_key = format!("resumes/{}", safe_filename);
-                match state
-                    .aws_service
-                    .upload_file(data.to_vec(), &s3_key, "application/pdf")
-                    .await
-                {
-                    Ok(_url) => {
-                        info!(user_id = %authed.id, s3_key = %s3_key, "Resume uploaded to S3 successfully");
-                    }
-                    Err(e) => {
-                        warn!(error = %e, user_id = %authed.id, "Failed to upload resume to S3, falling back to local storage");
-                        // Fall back to local storage
-                        let file_path = state.resumes_dir.join(&safe_filename);
-                        tokio::fs::write(&file_path, &data).await.map_err(|e| {
-                            error!(error = %e, "Failed to save resume locally");
-                            ApiError::InternalServer("Failed to save resume".to_string())
-                        })?;
-                    }
-                }
-            } else {
-                // Save to local storage
-                let file_path = state.resumes_dir.join(&safe_filename);
-                tokio::fs::write(&file_path, &data).await.map_err(|e| {
-                    error!(error = %e, "Failed to save resume");
-                    ApiError::InternalServer("Failed to save resume".to_string())
-                })?;
-            }
+        if resume_count >= MAX_RESUMES {
+            warn!(
+                user_id = %authed.id,
+                current_count = resume_count,
+                "Resume upload limit reached"
+            );
+            return Err(ApiError::Coded(
+                ErrorCode::ResumeLimitReached,
+                format!("Resume limit reached. You can keep a maximum of {} resumes. Upload a new version of an existing resume or delete one before uploading another.", MAX_RESUMES)
+            ));
+        }
+    }
 
-            // Create database record
-            let now = chrono::Utc::now().to_rfc3339();
-            sqlx::query(
-                r#"
-                INSERT INTO resumes (id, user_id, filename, status, submitted_at)
-                VALUES (?, ?, ?, 'submitted', ?)
-                "#,
-            )
+    storage_usage::ensure_within_quota(
+        &state,
+        storage_usage::OWNER_USER,
+        &authed.id,
+        data.len() as i64,
+    )
+    .await?;
+
+    // Save file
+    let resume_id = generate_resume_id();
+    let safe_filename = format!("{}.pdf", resume_id);
+
+    storage::save_file(
+        &state,
+        "resumes",
+        &state.resumes_dir,
+        &safe_filename,
+        data.to_vec(),
+        "application/pdf",
+    )
+    .await?;
+    info!(user_id = %authed.id, filename = %safe_filename, "Resume file stored");
+
+    // Create database record; a replacement joins its predecessor's version chain
+    let now = chrono::Utc::now().to_rfc3339();
+    let (version, root_resume_id) = chain_position(previous.as_ref());
+
+    let mut tx = state.db.begin().await.map_err(ApiError::DatabaseError)?;
+
+    sqlx::query(
+        r#"
+        INSERT INTO resumes (id, user_id, filename, status, submitted_at, label, version, supersedes_id, root_resume_id, file_size)
+        VALUES (?, ?, ?, 'submitted', ?, ?, ?, ?, ?, ?)
+        "#,
+    )
+    .bind(&resume_id)
+    .bind(&authed.id)
+    .bind(&safe_filename)
+    .bind(&now)
+    .bind(previous.as_ref().and_then(|p| p.label.clone()))
+    .bind(version)
+    .bind(previous.as_ref().map(|p| p.id.clone()))
+    .bind(&root_resume_id)
+    .bind(data.len() as i64)
+    .execute(&mut *tx)
+    .await
+    .map_err(ApiError::DatabaseError)?;
+
+    if let Some(previous) = &previous {
+        sqlx::query("UPDATE resumes SET superseded_by = ? WHERE id = ?")
             .bind(&resume_id)
-            .bind(&authed.id)
-            .bind(&safe_filename)
-            .bind(&now)
-            .execute(&state.db)
+            .bind(&previous.id)
+            .execute(&mut *tx)
             .await
             .map_err(ApiError::DatabaseError)?;
+    }
 
-            info!(user_id = %authed.id, resume_id = %resume_id, "Resume uploaded successfully");
+    tx.commit().await.map_err(ApiError::DatabaseError)?;
 
-            return Ok((
-                StatusCode::CREATED,
-                Json(json!({
-                    "id": resume_id,
-                    "filename": safe_filename,
-                    "status": "submitted",
-                    "message": "Resume uploaded successfully"
-                })),
-            ));
-        }
-    }
+    storage_usage::record_file(
+        &state.db,
+        storage_usage::CATEGORY_RESUME,
+        &resume_id,
+        storage_usage::OWNER_USER,
+        &authed.id,
+        data.len() as i64,
+    )
+    .await;
+
+    refresh_completeness(&state.db, &authed.id).await;
 
-    Err(ApiError::BadRequest("No resume file provided".to_string()))
+    info!(
+        user_id = %authed.id,
+        resume_id = %resume_id,
+        version = version,
+        supersedes = ?previous.as_ref().map(|p| &p.id),
+        "Resume uploaded successfully"
+    );
+
+    Ok((
+        StatusCode::CREATED,
+        Json(json!({
+            "id": resume_id,
+            "filename": safe_filename,
+            "status": "submitted",
+            "version": version,
+            "supersedes_id": previous.as_ref().map(|p| &p.id),
+            "message": "Resume uploaded successfully"
+        })),
+    ))
+}
+
+/// Version number and chain root for an upload replacing `previous`; a first upload starts a
+/// new chain at version 1
+fn chain_position(previous: Option<&Resume>) -> (i64, Option<String>) {
+    match previous {
+        Some(p) => (
+            p.version.unwrap_or(1) + 1,
+            Some(p.root_resume_id.clone().unwrap_or_else(|| p.id.clone())),
+        ),
+        None => (1, None),
+    }
 }
 
 /// GET /api/user/resumes - Get user's resumes
@@ -145,7 +224,7 @@ pub async fn get_user_resumes(
     let state = state_lock.read().await;
 
     let resumes = sqlx::query_as::<_, Resume>(
-        "SELECT * FROM resumes WHERE user_id = ? ORDER BY submitted_at DESC",
+        "SELECT * FROM resumes WHERE user_id = ? AND superseded_by IS NULL ORDER BY submitted_at DESC",
     )
     .bind(&authed.id)
     .fetch_all(&state.db)
@@ -155,6 +234,44 @@ pub async fn get_user_resumes(
     Ok(Json(resumes))
 }
 
+/// GET /api/resumes/:id/versions - List every version of a resume, newest first
+pub async fn get_resume_versions(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Path(resume_id): Path<String>,
+) -> Result<Json<Vec<Resume>>, ApiError> {
+    let state = state_lock.read().await;
+
+    // Owners see their own history; admins can inspect any candidate's
+    let resume = sqlx::query_as::<_, Resume>(
+        "SELECT * FROM resumes WHERE id = ? AND (user_id = ? OR ? = 1)"
+    )
+    .bind(&resume_id)
+    .bind(&authed.id)
+    .bind(authed.is_admin as i32)
+    .fetch_optional(&state.db)
+    .await
+    .map_err(ApiError::DatabaseError)?
+    .ok_or_else(|| ApiError::BadRequest("Resume not found".to_string()))?;
+
+    let root_id = resume.root_resume_id.clone().unwrap_or(resume.id);
+
+    let versions = sqlx::query_as::<_, Resume>(
+        r#"
+        SELECT * FROM resumes
+        WHERE COALESCE(root_resume_id, id) = ? AND user_id = ?
+        ORDER BY COALESCE(version, 1) DESC
+        "#,
+    )
+    .bind(&root_id)
+    .bind(&resume.user_id)
+    .fetch_all(&state.db)
+    .await
+    .map_err(ApiError::DatabaseError)?;
+
+    Ok(Json(versions))
+}
+
 /// DELETE /api/resumes/:id - Delete a resume
 pub async fn delete_resume(
     Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
@@ -172,6 +289,8 @@ pub async fn delete_resume(
         .map_err(ApiError::DatabaseError)?
         .ok_or_else(|| ApiError::BadRequest("Resume not found".to_string()))?;
 
+    legal_hold::ensure_resume_deletable(&state.db, &resume_id, Some(&authed.id)).await?;
+
     // Check if resume is used in any ACTIVE applications
     // Allow deletion if all applications using this resume are withdrawn or rejected
     let active_application_count: i64 = sqlx::query_scalar(
@@ -211,6 +330,21 @@ pub async fn delete_resume(
     .await
     .map_err(ApiError::DatabaseError)?;
 
+    // Close the gap in the version chain so deleting the latest version restores the previous one
+    sqlx::query("UPDATE resumes SET superseded_by = ? WHERE superseded_by = ?")
+        .bind(&resume.superseded_by)
+        .bind(&resume_id)
+        .execute(&state.db)
+        .await
+        .map_err(ApiError::DatabaseError)?;
+
+    sqlx::query("UPDATE resumes SET supersedes_id = ? WHERE supersedes_id = ?")
+        .bind(&resume.supersedes_id)
+        .bind(&resume_id)
+        .execute(&state.db)
+        .await
+        .map_err(ApiError::DatabaseError)?;
+
     // Delete file
     let file_path = state.resumes_dir.join(&resume.filename);
     let _ = tokio::fs::remove_file(file_path).await;
@@ -221,6 +355,9 @@ pub async fn delete_resume(
         .execute(&state.db)
         .await
         .map_err(ApiError::DatabaseError)?;
+    storage_usage::release_file(&state.db, storage_usage::CATEGORY_RESUME, &resume_id).await;
+
+    refresh_completeness(&state.db, &authed.id).await;
 
     info!(user_id = %authed.id, resume_id = %resume_id, "Resume deleted successfully");
 
@@ -319,7 +456,7 @@ pub async fn scan_resume(
         let s3_key = format!("resumes/{}", resume.filename);
         info!(resume_id = %resume_id, s3_key = %s3_key, "Downloading resume from S3");
         
-        match state.aws_service.download_file(&s3_key).await {
+        match state.object_store.download_file(&s3_key).await {
             Ok(bytes) => bytes,
             Err(e) => {
                 // Fallback to local storage if S3 fails
@@ -424,7 +561,7 @@ Resume text:
     );
 
     let ai_result = state
-        .openai_service
+        .llm
         .generate_text(
             crate::services::openai::TextGenerationPurpose::ResumeScanning,
             &ai_prompt,
@@ -508,6 +645,10 @@ Resume text:
     .await
     .map_err(ApiError::DatabaseError)?;
 
+    if let Err(e) = experience_years::refresh_resume(&state.db, &resume_id).await {
+        warn!(error = %e, resume_id = %resume_id, "Failed to store resume experience years");
+    }
+
     info!(
         user_id = %authed.id,
         resume_id = %resume_id,
@@ -564,6 +705,11 @@ pub async fn get_resume_review(
         json!({})
     };
 
+    let file_url = files::file_download_url(&state, &authed, FileKind::Resume, &resume.id)
+        .await
+        .ok()
+        .map(|link| link.url);
+
     // Build review response
     let review = json!({
         "id": resume.id,
@@ -572,7 +718,7 @@ pub async fn get_resume_review(
         "ai_model": extracted_data.get("ai_model").and_then(|v| v.as_str()).unwrap_or("gpt-5-mini"),
         "scanned_at": extracted_data.get("scanned_at").and_then(|v| v.as_str()),
         "extracted_data": extracted_data.get("extracted_data").unwrap_or(&json!({})),
-        "file_url": format!("/uploads/resumes/{}", resume.filename),
+        "file_url": file_url,
         "image_urls": []
     });
 
@@ -631,12 +777,20 @@ pub async fn propagate_resume_to_profile(
     // Update phone if available and profile phone is empty
     if let Some(phone) = extracted_data.get("phone").and_then(|p| p.as_str()) {
         if !phone.is_empty() {
-            sqlx::query("UPDATE profiles SET phone = COALESCE(NULLIF(phone, ''), ?) WHERE user_id = ?")
-                .bind(phone)
-                .bind(&resume.user_id)
-                .execute(&state.db)
-                .await
-                .ok();
+            sqlx::query(
+                r#"
+                UPDATE profiles
+                SET phone_index = CASE WHEN NULLIF(phone, '') IS NULL THEN ? ELSE phone_index END,
+                    phone = COALESCE(NULLIF(phone, ''), ?)
+                WHERE user_id = ?
+                "#,
+            )
+            .bind(pii::phone_index(phone))
+            .bind(pii::seal(phone)?)
+            .bind(&resume.user_id)
+            .execute(&state.db)
+            .await
+            .ok();
             updated_fields.push("phone");
         }
     }
@@ -645,7 +799,7 @@ pub async fn propagate_resume_to_profile(
     if let Some(location) = extracted_data.get("location").and_then(|l| l.as_str()) {
         if !location.is_empty() {
             sqlx::query("UPDATE profiles SET location = COALESCE(NULLIF(location, ''), ?) WHERE user_id = ?")
-                .bind(location)
+                .bind(pii::seal(location)?)
                 .bind(&resume.user_id)
                 .execute(&state.db)
                 .await
@@ -780,6 +934,8 @@ pub async fn propagate_resume_to_profile(
         updated_fields.push("education");
     }
 
+    refresh_completeness(&state.db, &resume.user_id).await;
+
     info!(
         user_id = %resume.user_id,
         resume_id = %resume_id,
@@ -999,44 +1155,7 @@ pub async fn download_resume(
     .map_err(ApiError::DatabaseError)?
     .ok_or_else(|| ApiError::BadRequest("Resume not found".to_string()))?;
 
-    // Check storage type and read file accordingly
-    let storage_type = state
-        .settings_service
-        .get_setting("storage_type")
-        .await
-        .ok()
-        .flatten()
-        .unwrap_or_else(|| "local".to_string());
-
-    let content: Vec<u8> = if storage_type.starts_with("s3") {
-        // Try to download from S3
-        let s3_key = format!("resumes/{}", resume.filename);
-        info!(resume_id = %resume_id, s3_key = %s3_key, "Downloading resume from S3 for preview");
-        
-        match state.aws_service.download_file(&s3_key).await {
-            Ok(bytes) => bytes,
-            Err(e) => {
-                // Fallback to local storage if S3 fails
-                warn!(error = %e, resume_id = %resume_id, "Failed to download from S3, trying local storage");
-                let file_path = state.resumes_dir.join(&resume.filename);
-                if !file_path.exists() {
-                    return Err(ApiError::BadRequest("Resume file not found".to_string()));
-                }
-                tokio::fs::read(&file_path)
-                    .await
-                    .map_err(|_| ApiError::InternalServer("Failed to read resume".to_string()))?
-            }
-        }
-    } else {
-        // Read from local storage
-        let file_path = state.resumes_dir.join(&resume.filename);
-        if !file_path.exists() {
-            return Err(ApiError::BadRequest("Resume file not found".to_string()));
-        }
-        tokio::fs::read(&file_path)
-            .await
-            .map_err(|_| ApiError::InternalServer("Failed to read resume".to_string()))?
-    };
+    let content = read_resume_file(&state, &resume.filename).await?;
 
     let disposition = format!("attachment; filename=\"{}\"", resume.filename);
     Ok((
@@ -1089,3 +1208,43 @@ pub async fn retry_resume_processing(
         "message": "Resume reset for reprocessing. You can now scan it again."
     })))
 }
+
+/// Read a stored resume PDF from S3 (when configured) or local disk
+pub(crate) async fn read_resume_file(state: &AppState, filename: &str) -> Result<Vec<u8>, ApiError> {
+    storage::load_file(state, "resumes", &state.resumes_dir, filename)
+        .await
+        .map_err(|e| match e {
+            ApiError::NotFound(_) => ApiError::BadRequest("Resume file not found".to_string()),
+            other => other,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn resume(id: &str, version: Option<i64>, root: Option<&str>) -> Resume {
+        let mut resume: Resume = serde_json::from_value(json!({
+            "id": id,
+            "user_id": "u1",
+            "filename": format!("{}.pdf", id),
+            "status": "submitted",
+            "version": version,
+        }))
+        .unwrap();
+        resume.root_resume_id = root.map(str::to_string);
+        resume
+    }
+
+    #[test]
+    fn test_chain_position_follows_the_root() {
+        assert_eq!(chain_position(None), (1, None));
+
+        // Resumes uploaded before versioning have no version and are their own root
+        let first = resume("r1", None, None);
+        assert_eq!(chain_position(Some(&first)), (2, Some("r1".to_string())));
+
+        let second = resume("r2", Some(2), Some("r1"));
+        assert_eq!(chain_position(Some(&second)), (3, Some("r1".to_string())));
+    }
+}
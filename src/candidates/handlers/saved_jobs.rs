@@ -54,7 +54,7 @@ pub async fn save_job(
     let job_id = &payload.job_id;
 
     // Check if job exists
-    let job_exists: Option<(String,)> = sqlx::query_as("SELECT id FROM jobs WHERE id = ?")
+    let job_exists: Option<(String,)> = sqlx::query_as("SELECT id FROM jobs WHERE id = ? AND deleted_at IS NULL")
         .bind(job_id)
         .fetch_optional(&state.db)
         .await
@@ -195,7 +195,7 @@ pub async fn get_saved_jobs(
             j.company_logo_url
         FROM saved_jobs sj
         LEFT JOIN jobs j ON sj.job_id = j.id
-        WHERE sj.user_id = ?
+        WHERE sj.user_id = ? AND j.deleted_at IS NULL
         ORDER BY sj.saved_at DESC
         "#
     )
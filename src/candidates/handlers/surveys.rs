@@ -0,0 +1,241 @@
+// src/candidates/handlers/surveys.rs
+//! Candidate satisfaction surveys: the public response page and NPS analytics
+
+use axum::{
+    extract::{Extension, Path, Query},
+    Json,
+};
+use chrono::NaiveDate;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::{error, info};
+
+use crate::auth::AuthedUser;
+use crate::candidates::models::*;
+use crate::common::{ApiError, AppState};
+use crate::services::surveys::{with_scores, MAX_COMMENT_LENGTH};
+
+async fn fetch_survey(state: &AppState, token: &str) -> Result<CandidateSurvey, ApiError> {
+    sqlx::query_as::<_, CandidateSurvey>("SELECT * FROM candidate_surveys WHERE token = ?")
+        .bind(token)
+        .fetch_optional(&state.db)
+        .await
+        .map_err(ApiError::DatabaseError)?
+        .ok_or_else(|| ApiError::NotFound("Survey not found".to_string()))
+}
+
+async fn is_expired(state: &AppState, survey: &CandidateSurvey) -> Result<bool, ApiError> {
+    sqlx::query_scalar("SELECT ? <= datetime('now')")
+        .bind(&survey.expires_at)
+        .fetch_one(&state.db)
+        .await
+        .map_err(ApiError::DatabaseError)
+}
+
+/// GET /api/public/surveys/:token - The survey a candidate was emailed
+pub async fn get_survey(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    Path(token): Path<String>,
+) -> Result<Json<PublicSurvey>, ApiError> {
+    let state = state_lock.read().await.clone();
+    let survey = fetch_survey(&state, &token).await?;
+
+    let job: Option<(String, Option<String>)> =
+        sqlx::query_as("SELECT title, company FROM jobs WHERE id = ?")
+            .bind(&survey.job_id)
+            .fetch_optional(&state.db)
+            .await
+            .map_err(ApiError::DatabaseError)?;
+    let (job_title, company) = match job {
+        Some((title, company)) => (Some(title), company),
+        None => (None, None),
+    };
+
+    Ok(Json(PublicSurvey {
+        job_title,
+        company,
+        responded: survey.responded_at.is_some(),
+        expired: is_expired(&state, &survey).await?,
+        expires_at: survey.expires_at,
+    }))
+}
+
+/// POST /api/public/surveys/:token - Record a candidate's score and comment (once)
+pub async fn submit_survey(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    Path(token): Path<String>,
+    Json(request): Json<SubmitSurveyRequest>,
+) -> Result<Json<PublicSurvey>, ApiError> {
+    let state = state_lock.read().await.clone();
+
+    if !(0..=10).contains(&request.score) {
+        return Err(ApiError::ValidationError(
+            "score must be between 0 and 10".to_string(),
+        ));
+    }
+    let comment = request
+        .comment
+        .as_deref()
+        .map(str::trim)
+        .filter(|c| !c.is_empty());
+    if comment.is_some_and(|c| c.chars().count() > MAX_COMMENT_LENGTH) {
+        return Err(ApiError::ValidationError(format!(
+            "comment must be at most {} characters",
+            MAX_COMMENT_LENGTH
+        )));
+    }
+
+    let result = sqlx::query(
+        r#"
+        UPDATE candidate_surveys
+        SET score = ?, comment = ?, responded_at = datetime('now')
+        WHERE token = ? AND responded_at IS NULL AND expires_at > datetime('now')
+        "#,
+    )
+    .bind(request.score)
+    .bind(comment)
+    .bind(&token)
+    .execute(&state.db)
+    .await
+    .map_err(ApiError::DatabaseError)?;
+
+    let survey = fetch_survey(&state, &token).await?;
+    if result.rows_affected() == 0 {
+        return Err(ApiError::BadRequest(if survey.responded_at.is_some() {
+            "This survey has already been answered".to_string()
+        } else {
+            "This survey has expired".to_string()
+        }));
+    }
+
+    info!(
+        survey_id = %survey.id,
+        job_id = %survey.job_id,
+        score = request.score,
+        "Candidate survey answered"
+    );
+
+    Ok(Json(PublicSurvey {
+        job_title: None,
+        company: None,
+        responded: true,
+        expired: false,
+        expires_at: survey.expires_at,
+    }))
+}
+
+/// GET /api/admin/analytics/nps - Net Promoter Score overall, per job and per recruiter
+pub async fn get_nps_report(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Query(query): Query<NpsQuery>,
+) -> Result<Json<NpsReport>, ApiError> {
+    if !authed.is_admin {
+        return Err(ApiError::Forbidden("Admin privileges required".to_string()));
+    }
+    let state = state_lock.read().await.clone();
+
+    for (field, value) in [("from", &query.from), ("to", &query.to)] {
+        if let Some(value) = value {
+            NaiveDate::parse_from_str(value, "%Y-%m-%d").map_err(|_| {
+                ApiError::ValidationError(format!("{} must be a date in YYYY-MM-DD format", field))
+            })?;
+        }
+    }
+
+    // `{group}` is one of a fixed set of column expressions, never user input
+    let breakdown_sql = |id: &str, label: &str, group: &str| {
+        format!(
+            r#"
+            SELECT {id} AS id, {label} AS label,
+                   COUNT(*) AS sent,
+                   COALESCE(SUM(s.responded_at IS NOT NULL), 0) AS responses,
+                   COALESCE(SUM(s.score >= 9), 0) AS promoters,
+                   COALESCE(SUM(s.score BETWEEN 7 AND 8), 0) AS passives,
+                   COALESCE(SUM(s.score <= 6), 0) AS detractors
+            FROM candidate_surveys s
+            LEFT JOIN jobs j ON j.id = s.job_id
+            LEFT JOIN users u ON u.id = s.recruiter_id
+            WHERE s.sent_at IS NOT NULL
+              AND (? IS NULL OR date(s.sent_at) >= ?)
+              AND (? IS NULL OR date(s.sent_at) <= ?)
+              AND (? IS NULL OR s.job_id = ?)
+            {group}
+            "#
+        )
+    };
+    let load = |sql: String| {
+        let query = &query;
+        let db = &state.db;
+        async move {
+            sqlx::query_as::<_, NpsBreakdown>(&sql)
+                .bind(&query.from)
+                .bind(&query.from)
+                .bind(&query.to)
+                .bind(&query.to)
+                .bind(&query.job_id)
+                .bind(&query.job_id)
+                .fetch_all(db)
+                .await
+                .map_err(|e| {
+                    error!(error = %e, "Database error building NPS report");
+                    ApiError::DatabaseError(e)
+                })
+        }
+    };
+
+    let overall = load(breakdown_sql("NULL", "NULL", ""))
+        .await?
+        .into_iter()
+        .next()
+        .map(with_scores)
+        .ok_or_else(|| ApiError::InternalServer("Empty NPS aggregate".to_string()))?;
+    let by_job = load(breakdown_sql(
+        "s.job_id",
+        "j.title",
+        "GROUP BY s.job_id ORDER BY sent DESC",
+    ))
+    .await?
+    .into_iter()
+    .map(with_scores)
+    .collect();
+    let by_recruiter = load(breakdown_sql(
+        "s.recruiter_id",
+        "COALESCE(u.name, u.email)",
+        "GROUP BY s.recruiter_id ORDER BY sent DESC",
+    ))
+    .await?
+    .into_iter()
+    .map(with_scores)
+    .collect();
+
+    let recent_comments = sqlx::query_as::<_, SurveyComment>(
+        r#"
+        SELECT s.job_id, j.title AS job_title, s.outcome, s.score, s.comment, s.responded_at
+        FROM candidate_surveys s
+        LEFT JOIN jobs j ON j.id = s.job_id
+        WHERE s.comment IS NOT NULL AND s.responded_at IS NOT NULL
+          AND (? IS NULL OR date(s.sent_at) >= ?)
+          AND (? IS NULL OR date(s.sent_at) <= ?)
+          AND (? IS NULL OR s.job_id = ?)
+        ORDER BY s.responded_at DESC
+        LIMIT 20
+        "#,
+    )
+    .bind(&query.from)
+    .bind(&query.from)
+    .bind(&query.to)
+    .bind(&query.to)
+    .bind(&query.job_id)
+    .bind(&query.job_id)
+    .fetch_all(&state.db)
+    .await
+    .map_err(ApiError::DatabaseError)?;
+
+    Ok(Json(NpsReport {
+        overall,
+        by_job,
+        by_recruiter,
+        recent_comments,
+    }))
+}
@@ -0,0 +1,436 @@
+// src/candidates/handlers/video_uploads.rs
+//! Resumable video uploads
+//!
+//! The client opens a session with the file's size, then sends the file as raw chunks
+//! with an `Upload-Offset` header. A dropped connection only loses the chunk in flight:
+//! `GET` on the session returns the offset to resume from. When the last byte lands the
+//! file is uploaded to storage and becomes a regular video.
+
+use axum::{
+    body::Bytes,
+    extract::{Extension, Path},
+    http::{HeaderMap, StatusCode},
+    response::IntoResponse,
+    Json,
+};
+use serde_json::json;
+use std::io::SeekFrom;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::fs as tokio_fs;
+use tokio::io::{AsyncSeekExt, AsyncWriteExt};
+use tokio::sync::RwLock;
+use tracing::{error, info, warn};
+
+use crate::auth::AuthedUser;
+use crate::candidates::models::{CreateUploadSessionRequest, UploadSession};
+use crate::common::{
+    generate_upload_session_id, generate_video_id, storage, ApiError, AppState, ErrorCode,
+};
+use crate::services::storage_usage;
+
+/// Largest video accepted through a resumable upload
+pub const MAX_RESUMABLE_VIDEO_SIZE: i64 = 200 * 1024 * 1024;
+
+/// Suggested chunk size; also the largest chunk accepted
+pub const CHUNK_SIZE: i64 = 5 * 1024 * 1024;
+
+/// An idle session expires this long after its last chunk
+const SESSION_TTL_HOURS: i64 = 24;
+
+/// Same limit as direct uploads
+const MAX_VIDEOS: i64 = 2;
+
+const SUPPORTED_FORMATS: &[&str] = &[
+    "video/mp4",
+    "video/quicktime",
+    "video/x-msvideo",
+    "video/webm",
+];
+
+/// Check a new session's metadata before anything is stored
+pub fn validate_upload(filename: &str, mime_type: &str, total_size: i64) -> Result<(), ApiError> {
+    if filename.trim().is_empty() {
+        return Err(ApiError::BadRequest("No filename provided".to_string()));
+    }
+    if !SUPPORTED_FORMATS.contains(&mime_type) {
+        return Err(ApiError::BadRequest(
+            "Unsupported video format. Supported formats: MP4, MOV, AVI, WebM".to_string(),
+        ));
+    }
+    if total_size <= 0 {
+        return Err(ApiError::BadRequest(
+            "Upload size must be positive".to_string(),
+        ));
+    }
+    if total_size > MAX_RESUMABLE_VIDEO_SIZE {
+        return Err(ApiError::BadRequest(format!(
+            "Video file too large. Maximum size is {}MB.",
+            MAX_RESUMABLE_VIDEO_SIZE / (1024 * 1024)
+        )));
+    }
+    Ok(())
+}
+
+/// Check that a chunk continues the upload exactly where it left off and fits the file
+pub fn check_chunk(session: &UploadSession, offset: i64, chunk_len: i64) -> Result<(), ApiError> {
+    if offset != session.received_bytes {
+        return Err(ApiError::Coded(
+            ErrorCode::UploadOffsetMismatch,
+            format!(
+                "Chunk starts at byte {} but the upload is at byte {}",
+                offset, session.received_bytes
+            ),
+        ));
+    }
+    if chunk_len == 0 {
+        return Err(ApiError::BadRequest("Empty chunk".to_string()));
+    }
+    if chunk_len > CHUNK_SIZE {
+        return Err(ApiError::BadRequest(format!(
+            "Chunk too large. Maximum chunk size is {} bytes.",
+            CHUNK_SIZE
+        )));
+    }
+    if offset + chunk_len > session.total_size {
+        return Err(ApiError::BadRequest(
+            "Chunk runs past the declared upload size".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+fn partial_path(state: &AppState, session_id: &str) -> PathBuf {
+    state
+        .upload_sessions_dir
+        .join(format!("{}.part", session_id))
+}
+
+fn session_response(session: &UploadSession) -> serde_json::Value {
+    json!({
+        "session": session,
+        "offset": session.received_bytes,
+        "chunk_size": CHUNK_SIZE,
+    })
+}
+
+async fn fetch_session(
+    state: &AppState,
+    id: &str,
+    user_id: &str,
+) -> Result<UploadSession, ApiError> {
+    sqlx::query_as::<_, UploadSession>("SELECT * FROM upload_sessions WHERE id = ? AND user_id = ?")
+        .bind(id)
+        .bind(user_id)
+        .fetch_optional(&state.db)
+        .await
+        .map_err(ApiError::DatabaseError)?
+        .ok_or_else(|| ApiError::NotFound("Upload session not found".to_string()))
+}
+
+async fn video_count(state: &AppState, user_id: &str) -> Result<i64, ApiError> {
+    sqlx::query_scalar("SELECT COUNT(*) FROM videos WHERE user_id = ?")
+        .bind(user_id)
+        .fetch_one(&state.db)
+        .await
+        .map_err(ApiError::DatabaseError)
+}
+
+/// Abort sessions nobody has touched within the TTL and delete their partial files
+async fn purge_expired_sessions(state: &AppState) {
+    let expired: Vec<String> = match sqlx::query_scalar(
+        "SELECT id FROM upload_sessions WHERE status = 'uploading' AND expires_at <= datetime('now')",
+    )
+    .fetch_all(&state.db)
+    .await
+    {
+        Ok(ids) => ids,
+        Err(e) => {
+            warn!(error = %e, "Failed to list expired upload sessions");
+            return;
+        }
+    };
+    for id in expired {
+        let _ = tokio_fs::remove_file(partial_path(state, &id)).await;
+        let _ = sqlx::query(
+            "UPDATE upload_sessions SET status = 'aborted', updated_at = datetime('now') WHERE id = ?",
+        )
+        .bind(&id)
+        .execute(&state.db)
+        .await;
+    }
+}
+
+/// POST /api/user/videos/uploads - Start a resumable video upload
+pub async fn create_upload_session(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Json(request): Json<CreateUploadSessionRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    let state = state_lock.read().await.clone();
+
+    validate_upload(&request.filename, &request.mime_type, request.total_size)?;
+    if video_count(&state, &authed.id).await? >= MAX_VIDEOS {
+        return Err(ApiError::BadRequest(format!(
+            "Video limit reached. You can upload a maximum of {} videos. Please delete an existing video before uploading a new one.",
+            MAX_VIDEOS
+        )));
+    }
+
+    purge_expired_sessions(&state).await;
+
+    // Bytes already promised to other in-flight uploads count against the quota too
+    let reserved: i64 = sqlx::query_scalar(
+        "SELECT COALESCE(SUM(total_size), 0) FROM upload_sessions WHERE user_id = ? AND status = 'uploading'",
+    )
+    .bind(&authed.id)
+    .fetch_one(&state.db)
+    .await
+    .map_err(ApiError::DatabaseError)?;
+    storage_usage::ensure_within_quota(
+        &state,
+        storage_usage::OWNER_USER,
+        &authed.id,
+        reserved + request.total_size,
+    )
+    .await?;
+
+    let id = generate_upload_session_id();
+    tokio_fs::write(partial_path(&state, &id), b"")
+        .await
+        .map_err(|e| {
+            error!(error = %e, session_id = %id, "Failed to create partial upload file");
+            ApiError::InternalServer("Failed to start upload".to_string())
+        })?;
+
+    sqlx::query(
+        r#"
+        INSERT INTO upload_sessions (id, user_id, filename, mime_type, total_size, expires_at)
+        VALUES (?, ?, ?, ?, ?, datetime('now', ?))
+        "#,
+    )
+    .bind(&id)
+    .bind(&authed.id)
+    .bind(request.filename.trim())
+    .bind(&request.mime_type)
+    .bind(request.total_size)
+    .bind(format!("+{} hours", SESSION_TTL_HOURS))
+    .execute(&state.db)
+    .await
+    .map_err(ApiError::DatabaseError)?;
+
+    info!(
+        session_id = %id,
+        user_id = %authed.id,
+        total_size = request.total_size,
+        "Resumable video upload started"
+    );
+
+    let session = fetch_session(&state, &id, &authed.id).await?;
+    Ok((StatusCode::CREATED, Json(session_response(&session))))
+}
+
+/// GET /api/user/videos/uploads/:id - Where to resume an upload from
+pub async fn get_upload_session(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Path(id): Path<String>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let state = state_lock.read().await.clone();
+    let session = fetch_session(&state, &id, &authed.id).await?;
+    Ok(Json(session_response(&session)))
+}
+
+/// PATCH /api/user/videos/uploads/:id - Append a chunk
+///
+/// The body is the raw chunk and `Upload-Offset` the byte it starts at, which must equal
+/// the session's current offset. The chunk that completes the file also creates the video.
+pub async fn upload_chunk(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Path(id): Path<String>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let state = state_lock.read().await.clone();
+
+    let offset: i64 = headers
+        .get("Upload-Offset")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.trim().parse().ok())
+        .ok_or_else(|| {
+            ApiError::BadRequest("Missing or invalid Upload-Offset header".to_string())
+        })?;
+
+    let session = fetch_session(&state, &id, &authed.id).await?;
+    if session.status != "uploading" {
+        return Err(ApiError::BadRequest(format!(
+            "Upload is {}",
+            session.status
+        )));
+    }
+    // Every byte arrived but moving it to storage failed; any PATCH retries that step
+    if session.received_bytes == session.total_size {
+        finalize_upload(&state, &session).await?;
+        let session = fetch_session(&state, &id, &authed.id).await?;
+        return Ok(Json(session_response(&session)));
+    }
+    check_chunk(&session, offset, body.len() as i64)?;
+
+    // Write at the offset rather than appending, so a retried chunk can't be stored twice
+    let path = partial_path(&state, &id);
+    let write = async {
+        let mut file = tokio_fs::OpenOptions::new().write(true).open(&path).await?;
+        file.seek(SeekFrom::Start(offset as u64)).await?;
+        file.write_all(&body).await?;
+        file.flush().await
+    };
+    write.await.map_err(|e| {
+        error!(error = %e, session_id = %id, "Failed to write upload chunk");
+        ApiError::InternalServer("Failed to store chunk".to_string())
+    })?;
+
+    let received = offset + body.len() as i64;
+    let updated = sqlx::query(
+        r#"
+        UPDATE upload_sessions
+        SET received_bytes = ?, updated_at = datetime('now'), expires_at = datetime('now', ?)
+        WHERE id = ? AND received_bytes = ? AND status = 'uploading'
+        "#,
+    )
+    .bind(received)
+    .bind(format!("+{} hours", SESSION_TTL_HOURS))
+    .bind(&id)
+    .bind(offset)
+    .execute(&state.db)
+    .await
+    .map_err(ApiError::DatabaseError)?;
+    if updated.rows_affected() == 0 {
+        // Another request for the same offset got there first
+        let current = fetch_session(&state, &id, &authed.id).await?;
+        return Err(ApiError::Coded(
+            ErrorCode::UploadOffsetMismatch,
+            format!("Upload is at byte {}", current.received_bytes),
+        ));
+    }
+
+    if received == session.total_size {
+        finalize_upload(&state, &session).await?;
+    }
+
+    let session = fetch_session(&state, &id, &authed.id).await?;
+    Ok(Json(session_response(&session)))
+}
+
+/// Move a complete upload to storage and record it as the user's video
+async fn finalize_upload(state: &AppState, session: &UploadSession) -> Result<(), ApiError> {
+    let path = partial_path(state, &session.id);
+    let data = tokio_fs::read(&path).await.map_err(|e| {
+        error!(error = %e, session_id = %session.id, "Failed to read completed upload");
+        ApiError::InternalServer("Failed to read uploaded video".to_string())
+    })?;
+
+    let detected = infer::get(&data).map(|kind| kind.mime_type());
+    if detected.is_some_and(|mime| !mime.starts_with("video/")) {
+        abort_session(state, &session.id).await;
+        return Err(ApiError::BadRequest(
+            "Uploaded file is not a video".to_string(),
+        ));
+    }
+    if video_count(state, &session.user_id).await? >= MAX_VIDEOS {
+        abort_session(state, &session.id).await;
+        return Err(ApiError::BadRequest(format!(
+            "Video limit reached. You can upload a maximum of {} videos.",
+            MAX_VIDEOS
+        )));
+    }
+
+    let video_id = generate_video_id();
+    let extension = session.filename.rsplit('.').next().unwrap_or("mp4");
+    let s3_key = format!("videos/user-{}/{}.{}", session.user_id, video_id, extension);
+    let file_size = data.len() as i64;
+
+    // On failure the session stays fully received, so the next PATCH retries from here
+    let s3_url = storage::store_upload(state, &s3_key, data, &session.mime_type).await?;
+
+    let mut tx = state.db.begin().await.map_err(ApiError::DatabaseError)?;
+    sqlx::query(
+        r#"
+        INSERT INTO videos (id, user_id, s3_url, filename, file_size, duration_seconds, mime_type, uploaded_at)
+        VALUES (?, ?, ?, ?, ?, 0, ?, datetime('now'))
+        "#,
+    )
+    .bind(&video_id)
+    .bind(&session.user_id)
+    .bind(&s3_url)
+    .bind(&session.filename)
+    .bind(file_size)
+    .bind(&session.mime_type)
+    .execute(&mut *tx)
+    .await
+    .map_err(ApiError::DatabaseError)?;
+    sqlx::query(
+        "UPDATE upload_sessions SET status = 'completed', video_id = ?, updated_at = datetime('now') WHERE id = ?",
+    )
+    .bind(&video_id)
+    .bind(&session.id)
+    .execute(&mut *tx)
+    .await
+    .map_err(ApiError::DatabaseError)?;
+    tx.commit().await.map_err(ApiError::DatabaseError)?;
+
+    storage_usage::record_file(
+        &state.db,
+        storage_usage::CATEGORY_VIDEO,
+        &video_id,
+        storage_usage::OWNER_USER,
+        &session.user_id,
+        file_size,
+    )
+    .await;
+
+    let _ = tokio_fs::remove_file(&path).await;
+
+    info!(
+        session_id = %session.id,
+        video_id = %video_id,
+        user_id = %session.user_id,
+        file_size = file_size,
+        "Resumable video upload completed"
+    );
+    Ok(())
+}
+
+async fn abort_session(state: &AppState, id: &str) {
+    let _ = tokio_fs::remove_file(partial_path(state, id)).await;
+    if let Err(e) = sqlx::query(
+        "UPDATE upload_sessions SET status = 'aborted', updated_at = datetime('now') WHERE id = ?",
+    )
+    .bind(id)
+    .execute(&state.db)
+    .await
+    {
+        error!(error = %e, session_id = %id, "Failed to abort upload session");
+    }
+}
+
+/// DELETE /api/user/videos/uploads/:id - Abandon an upload and discard what was sent
+pub async fn cancel_upload_session(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Path(id): Path<String>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let state = state_lock.read().await.clone();
+
+    let session = fetch_session(&state, &id, &authed.id).await?;
+    if session.status == "completed" {
+        return Err(ApiError::BadRequest(
+            "Upload already completed; delete the video instead".to_string(),
+        ));
+    }
+    abort_session(&state, &id).await;
+
+    info!(session_id = %id, user_id = %authed.id, "Resumable video upload cancelled");
+
+    Ok(Json(json!({ "message": "Upload cancelled" })))
+}
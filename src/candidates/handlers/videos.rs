@@ -9,11 +9,14 @@ use axum::{
 use serde_json::json;
 use std::sync::Arc;
 use tokio::sync::RwLock;
-use tracing::info;
+use tracing::{info, warn};
 
 use crate::auth::AuthedUser;
+use crate::candidates::handlers::files;
 use crate::candidates::models::*;
-use crate::common::{generate_video_id, ApiError, AppState};
+use crate::common::{generate_video_id, storage, ApiError, AppState};
+use crate::services::downloads::{self, FileKind};
+use crate::services::storage_usage;
 
 /// POST /api/user/videos - Upload a video
 pub async fn upload_video(
@@ -88,19 +91,22 @@ pub async fn upload_video(
         ));
     }
 
+    storage_usage::ensure_within_quota(
+        &state,
+        storage_usage::OWNER_USER,
+        &authed.id,
+        video_data.len() as i64,
+    )
+    .await?;
+
     // Generate unique video ID and S3 key
     let video_id = generate_video_id();
     let extension = filename.split('.').last().unwrap_or("mp4");
     let s3_key = format!("videos/user-{}/{}.{}", authed.id, video_id, extension);
 
-    // Upload to S3
-    let s3_url = state
-        .aws_service
-        .upload_file(video_data.clone(), &s3_key, &mime_type)
-        .await
-        .map_err(|e| ApiError::ProcessingError(format!("Failed to upload to S3: {}", e)))?;
-
+    // Upload to S3, or keep it on local disk when storage is local
     let file_size = video_data.len() as i64;
+    let s3_url = storage::store_upload(&state, &s3_key, video_data, &mime_type).await?;
 
     // Store in database
     sqlx::query(
@@ -119,6 +125,16 @@ pub async fn upload_video(
     .await
     .map_err(ApiError::DatabaseError)?;
 
+    storage_usage::record_file(
+        &state.db,
+        storage_usage::CATEGORY_VIDEO,
+        &video_id,
+        storage_usage::OWNER_USER,
+        &authed.id,
+        file_size,
+    )
+    .await;
+
     info!(video_id = %video_id, user_id = %authed.id, "Video uploaded successfully");
 
     Ok((StatusCode::CREATED, Json(json!({
@@ -181,8 +197,17 @@ pub async fn delete_video(
         .map_err(ApiError::DatabaseError)?
         .ok_or_else(|| ApiError::BadRequest("Video not found".to_string()))?;
 
-    // Extract S3 key from URL (skip for YouTube videos)
-    let s3_key = if let Some(url) = &video.s3_url {
+    // Locally stored videos just need their file removed
+    let local_path = video.s3_url.as_deref().and_then(|url| {
+        url.strip_prefix(&format!("/{}/", storage::LOCAL_UPLOADS_DIR))
+            .filter(|relative| downloads::is_safe_relative_path(relative))
+            .map(|relative| std::path::Path::new(storage::LOCAL_UPLOADS_DIR).join(relative))
+    });
+
+    // Extract S3 key from URL (skip for YouTube and local videos)
+    let s3_key = if local_path.is_some() {
+        String::new()
+    } else if let Some(url) = &video.s3_url {
         if let Some(key) = url.split(".amazonaws.com/").nth(1) {
             key.to_string()
         } else if let Some(key) = url.split("/").last() {
@@ -198,18 +223,25 @@ pub async fn delete_video(
     // Delete from S3 (only for uploaded videos)
     if !s3_key.is_empty() {
         state
-            .aws_service
+            .object_store
             .delete_file(&s3_key)
             .await
             .map_err(|e| ApiError::ProcessingError(format!("Failed to delete from S3: {}", e)))?;
     }
 
+    if let Some(path) = &local_path {
+        if let Err(e) = tokio::fs::remove_file(path).await {
+            warn!(error = %e, video_id = %id, "Failed to remove local video file");
+        }
+    }
+
     // Delete from database
     sqlx::query("DELETE FROM videos WHERE id = ?")
         .bind(&id)
         .execute(&state.db)
         .await
         .map_err(ApiError::DatabaseError)?;
+    storage_usage::release_file(&state.db, storage_usage::CATEGORY_VIDEO, &id).await;
 
     info!(video_id = %id, user_id = %authed.id, "Video deleted");
 
@@ -236,9 +268,12 @@ pub async fn download_video(
         .map_err(ApiError::DatabaseError)?
         .ok_or_else(|| ApiError::BadRequest("Video not found".to_string()))?;
 
-    // Return the S3 URL for download
+    // Hand out a short-lived presigned link rather than the raw bucket URL
+    let link = files::file_download_url(&state, &authed, FileKind::Video, &video.id).await?;
+
     Ok(Json(json!({
-        "download_url": video.s3_url,
+        "download_url": link.url,
+        "expires_at": link.expires_at,
         "filename": video.filename
     })))
 }
@@ -17,6 +17,7 @@ use crate::{
     auth::AuthedUser,
     candidates::models::{VideoSubmission, YouTubeVideoLinkRequest},
     common::{generate_token_id, generate_video_id, ApiError, AppState},
+    services::moderation::{self, ModeratedContent},
     services::youtube::{YouTubeService, YouTubeVideo},
 };
 
@@ -197,6 +198,15 @@ pub async fn link_youtube_video(
         ApiError::DatabaseError(e)
     })?;
 
+    moderation::moderate(
+        &state,
+        ModeratedContent::VideoTitle,
+        &video_id,
+        &authed.id,
+        request.title.as_deref().unwrap_or(&youtube_video.title),
+    )
+    .await;
+
     // Fetch the created video
     let video = sqlx::query_as::<_, VideoSubmission>(
         "SELECT * FROM videos WHERE id = ?"
@@ -233,19 +243,20 @@ pub async fn youtube_oauth_start(
     })?;
     
     // Validate the JWT token
-    let claims = crate::auth::handlers::validate_jwt(token)?;
+    let claims = crate::auth::handlers::validate_jwt(&state, token).await?;
     let user_id = claims.sub;
     
     info!(user_id = %user_id, "Starting YouTube OAuth flow for user");
     
     // Get Google client credentials from settings or env
-    let client_id = std::env::var("GOOGLE_CLIENT_ID")
-        .map_err(|_| ApiError::InternalServer("Google OAuth not configured".to_string()))?;
+    let client_id = state
+        .config
+        .google_client_id
+        .clone()
+        .ok_or_else(|| ApiError::InternalServer("Google OAuth not configured".to_string()))?;
     
-    // Get backend URL from env for redirect
-    let backend_url = std::env::var("BACKEND_URL")
-        .unwrap_or_else(|_| "http://localhost:8080".to_string());
-    let redirect_uri = format!("{}/api/auth/youtube/callback", backend_url);
+    // Google sends the user back to this API
+    let redirect_uri = format!("{}/api/auth/youtube/callback", state.config.backend_url);
     
     // YouTube readonly scope
     let scopes = vec![
@@ -277,8 +288,7 @@ pub async fn youtube_oauth_callback(
     // Check for error from Google
     if let Some(error) = params.get("error") {
         error!(oauth_error = %error, "YouTube OAuth returned error");
-        let frontend_url = std::env::var("FRONTEND_URL")
-            .unwrap_or_else(|_| "http://localhost:3000".to_string());
+        let frontend_url = &state.config.frontend_url;
         return Ok(Html(format!(
             r#"
             <!DOCTYPE html>
@@ -313,14 +323,18 @@ pub async fn youtube_oauth_callback(
     info!(user_id = %user_id, "Received YouTube OAuth callback");
     
     // Exchange code for tokens
-    let client_id = std::env::var("GOOGLE_CLIENT_ID")
-        .map_err(|_| ApiError::InternalServer("Google OAuth not configured".to_string()))?;
-    let client_secret = std::env::var("GOOGLE_CLIENT_SECRET")
-        .map_err(|_| ApiError::InternalServer("Google OAuth not configured".to_string()))?;
-    
-    let backend_url = std::env::var("BACKEND_URL")
-        .unwrap_or_else(|_| "http://localhost:8080".to_string());
-    let redirect_uri = format!("{}/api/auth/youtube/callback", backend_url);
+    let client_id = state
+        .config
+        .google_client_id
+        .clone()
+        .ok_or_else(|| ApiError::InternalServer("Google OAuth not configured".to_string()))?;
+    let client_secret = state
+        .config
+        .google_client_secret
+        .clone()
+        .ok_or_else(|| ApiError::InternalServer("Google OAuth not configured".to_string()))?;
+    
+    let redirect_uri = format!("{}/api/auth/youtube/callback", state.config.backend_url);
     
     let client = reqwest::Client::new();
     let token_response = client
@@ -387,8 +401,7 @@ pub async fn youtube_oauth_callback(
     
     info!(user_id = %user_id, "Successfully stored YouTube OAuth tokens");
     
-    let frontend_url = std::env::var("FRONTEND_URL")
-        .unwrap_or_else(|_| "http://localhost:3000".to_string());
+    let frontend_url = &state.config.frontend_url;
     
     // Redirect back to the videos page
     Ok(Html(format!(r#"
@@ -483,10 +496,16 @@ async fn refresh_user_youtube_token(
         .and_then(|t| t.refresh_token)
         .ok_or_else(|| ApiError::BadRequest("No refresh token available".to_string()))?;
     
-    let client_id = std::env::var("GOOGLE_CLIENT_ID")
-        .map_err(|_| ApiError::InternalServer("Google OAuth not configured".to_string()))?;
-    let client_secret = std::env::var("GOOGLE_CLIENT_SECRET")
-        .map_err(|_| ApiError::InternalServer("Google OAuth not configured".to_string()))?;
+    let client_id = state
+        .config
+        .google_client_id
+        .clone()
+        .ok_or_else(|| ApiError::InternalServer("Google OAuth not configured".to_string()))?;
+    let client_secret = state
+        .config
+        .google_client_secret
+        .clone()
+        .ok_or_else(|| ApiError::InternalServer("Google OAuth not configured".to_string()))?;
     
     let client = reqwest::Client::new();
     let response = client
@@ -1,7 +1,12 @@
 // src/candidates/mod.rs
 
+pub mod education_match;
+pub mod experience_years;
 pub mod handlers;
 pub mod models;
+pub mod recently_viewed;
+pub mod recommendations;
+pub mod requirements_match;
 pub mod routes;
 pub mod validators;
 
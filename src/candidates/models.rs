@@ -2,6 +2,10 @@
 
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
+use std::collections::HashMap;
+use validator::Validate;
+
+use crate::candidates::education_match::DegreeLevel;
 
 // ============================================================================
 // Resume Models
@@ -19,11 +23,26 @@ pub struct Resume {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[sqlx(default)]
     pub label: Option<String>,
+    // Version chain: each replacement upload supersedes the previous file
+    #[sqlx(default)]
+    pub version: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[sqlx(default)]
+    pub supersedes_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[sqlx(default)]
+    pub superseded_by: Option<String>,
+    #[serde(skip)]
+    #[sqlx(default)]
+    pub root_resume_id: Option<String>,
     // Candidate information (populated in admin queries)
     #[serde(skip_serializing_if = "Option::is_none")]
     #[sqlx(default)]
     pub candidate_name: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "crate::services::masking::email_opt"
+    )]
     #[sqlx(default)]
     pub candidate_email: Option<String>,
 }
@@ -85,15 +104,19 @@ pub struct DetailedResumeStatus {
     pub processing_metadata: ResumeProcessingMetadata,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Validate)]
 pub struct AdminResumeFilters {
     pub status: Option<String>,
     pub candidate_name: Option<String>,
     pub date_from: Option<String>,
     pub date_to: Option<String>,
+    #[validate(range(min = 0.0, max = 100.0, message = "Minimum score must be between 0 and 100"))]
     pub score_min: Option<f64>,
+    #[validate(range(min = 0.0, max = 100.0, message = "Maximum score must be between 0 and 100"))]
     pub score_max: Option<f64>,
+    #[validate(range(min = 1, message = "Page must be greater than 0"))]
     pub page: Option<i64>,
+    #[validate(range(min = 1, max = 100, message = "Limit must be between 1 and 100"))]
     pub limit: Option<i64>,
     pub sort_by: Option<String>,
     pub sort_order: Option<String>,
@@ -126,6 +149,9 @@ pub struct Application {
     pub cover_letter: Option<String>,
     pub applied_at: Option<String>,
     pub updated_at: Option<String>,
+    /// Set while the application waits past the job's application cap
+    #[sqlx(default)]
+    pub waitlisted_at: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
@@ -136,19 +162,109 @@ pub struct ApplicationStatusHistory {
     pub changed_by: String,
     pub notes: Option<String>,
     pub changed_at: Option<String>,
+    pub reason_code: Option<String>,
+    pub reason_details: Option<String>,
 }
 
+/// Structured reasons a candidate can give when withdrawing an application
+pub const WITHDRAWAL_REASONS: &[&str] = &[
+    "accepted_elsewhere",
+    "salary",
+    "timeline",
+    "location",
+    "role_mismatch",
+    "process_too_long",
+    "personal",
+    "other",
+];
+
 #[derive(Debug, Deserialize)]
 pub struct CreateApplicationRequest {
     pub job_id: String,
     pub resume_id: Option<String>,
     pub cover_letter: Option<String>,
+    /// Attribution tag from the link the candidate arrived by (the `utm_source` of a short link)
+    pub source: Option<String>,
+    /// Consent given on the application form; required when the privacy policy changed
+    #[serde(default)]
+    pub consent: Option<crate::auth::models::ConsentInput>,
+    /// Countries the candidate is authorized to work in, checked by knockout rules
+    #[serde(default)]
+    pub work_authorization: Vec<String>,
+    /// Answers to the job's screening questions, keyed by question ID
+    #[serde(default)]
+    pub screening_answers: HashMap<String, String>,
+}
+
+/// A screening question shown on a job's application form
+#[derive(Debug, Serialize)]
+pub struct ScreeningQuestion {
+    pub id: String,
+    pub question: String,
+}
+
+/// What a job's knockout rules ask of applicants; expected answers are not revealed
+#[derive(Debug, Serialize)]
+pub struct ScreeningForm {
+    pub work_authorization_countries: Vec<String>,
+    pub questions: Vec<ScreeningQuestion>,
+}
+
+/// Background job that bundles a job's applicant resumes into a ZIP
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ResumeExport {
+    pub id: String,
+    pub job_id: String,
+    pub status: String,
+    pub total: i64,
+    pub processed: i64,
+    #[serde(skip_serializing)]
+    pub file_path: Option<String>,
+    pub error: Option<String>,
+    pub requested_by: String,
+    pub created_at: Option<String>,
+    pub completed_at: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ResumeZipQuery {
+    /// Force background generation even for small jobs
+    #[serde(rename = "async", default)]
+    pub run_async: bool,
+}
+
+/// Record of a candidate swapping the resume attached to an application
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ApplicationResumeHistory {
+    pub id: String,
+    pub application_id: String,
+    pub old_resume_id: Option<String>,
+    pub new_resume_id: String,
+    pub changed_by: String,
+    pub changed_at: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SwapApplicationResumeRequest {
+    pub resume_id: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RequirementsMatchQuery {
+    /// Regenerate instead of serving the cached comparison
+    #[serde(default)]
+    pub refresh: bool,
 }
 
 #[derive(Debug, Deserialize)]
 pub struct UpdateApplicationStatusRequest {
     pub status: String,
     pub notes: Option<String>,
+    /// One of WITHDRAWAL_REASONS; only accepted when status is `withdrawn`
+    pub withdrawal_reason: Option<String>,
+    pub withdrawal_details: Option<String>,
+    /// RFC3339 deadline for the candidate to answer; only accepted when status is `offered`
+    pub offer_expires_at: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -157,8 +273,12 @@ pub struct ApplicationWithDetails {
     pub application: Application,
     pub job_title: Option<String>,
     pub candidate_name: Option<String>,
+    #[serde(serialize_with = "crate::services::masking::email_opt")]
     pub candidate_email: Option<String>,
     pub status_history: Vec<ApplicationStatusHistory>,
+    /// Interview notes, recordings and transcripts; only shown to the hiring team
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub interview_records: Vec<InterviewRecordEntry>,
 }
 
 #[derive(Debug, Serialize)]
@@ -189,13 +309,40 @@ pub struct JobApplicationDetails {
     pub application_id: String,
     pub candidate_id: String,
     pub candidate_name: String,
+    #[serde(serialize_with = "crate::services::masking::email")]
     pub candidate_email: String,
     pub resume_id: Option<String>,
     pub resume_filename: Option<String>,
     pub resume_label: Option<String>,
+    /// Version of the resume as attached when the candidate applied
+    pub resume_version: Option<i64>,
+    /// Newest version of the attached resume, when the candidate has since replaced it
+    pub latest_resume_id: Option<String>,
     pub status: String,
     pub applied_at: Option<String>,
     pub cover_letter: Option<String>,
+    /// Highest degree found on the candidate's profile or attached resume
+    pub highest_degree_level: Option<DegreeLevel>,
+    /// Whether that degree meets the job's educational qualifications; null when the job has none
+    pub education_match: Option<bool>,
+    /// Total years of experience on the attached resume, overlapping roles counted once
+    pub experience_years: Option<f64>,
+    /// Years in roles whose title matches the job's
+    pub relevant_experience_years: Option<f64>,
+    /// Tags added by knockout rules that fired when the candidate applied
+    pub knockout_tags: Vec<String>,
+    /// Set while the application waits past the job's application cap
+    pub waitlisted_at: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct JobApplicationsQuery {
+    /// Only applicants who do (true) or do not (false) meet the job's education bar
+    pub education_match: Option<bool>,
+    /// Only applicants with at least this many years of experience
+    pub min_years: Option<f64>,
+    /// Only applicants with at least this many years in roles matching the job title
+    pub min_relevant_years: Option<f64>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -247,6 +394,30 @@ pub struct VideoUploadResponse {
 // Type alias for backward compatibility
 pub type VideoSubmission = Video;
 
+/// A resumable video upload; `received_bytes` is the offset the next chunk must start at
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct UploadSession {
+    pub id: String,
+    pub user_id: String,
+    pub filename: String,
+    pub mime_type: String,
+    pub total_size: i64,
+    pub received_bytes: i64,
+    pub status: String,
+    /// Set once the last chunk is stored and the video is created
+    pub video_id: Option<String>,
+    pub expires_at: String,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateUploadSessionRequest {
+    pub filename: String,
+    pub mime_type: String,
+    pub total_size: i64,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct YouTubeVideoLinkRequest {
     pub youtube_video_id: String,
@@ -275,10 +446,40 @@ pub struct Interview {
     pub created_by: String,
     pub created_at: Option<String>,
     pub updated_at: Option<String>,
+    /// Zone the interview was booked in; `scheduled_date` is UTC
+    #[sqlx(default)]
+    pub scheduled_timezone: Option<String>,
+    /// Newest calendar change (local edit or Google update) already applied to this row
+    #[sqlx(default)]
+    pub calendar_synced_at: Option<String>,
+    /// Shown to the candidate, unlike `notes` which stay internal
+    #[sqlx(default)]
+    pub preparation_notes: Option<String>,
+    #[sqlx(default)]
+    pub feedback_released_at: Option<String>,
+    /// `scheduled_date` rendered in the viewer's zone, filled in per request
+    #[sqlx(skip)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub local_scheduled_date: Option<String>,
+    #[sqlx(skip)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub local_timezone: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+impl Interview {
+    /// Fill in the local time fields for a viewer in `timezone`
+    pub fn localize(&mut self, timezone: &str) {
+        if let Some(utc) = crate::common::timezone::parse_stored(&self.scheduled_date) {
+            self.local_scheduled_date =
+                Some(crate::common::timezone::to_zone_rfc3339(utc, timezone));
+            self.local_timezone = Some(timezone.to_string());
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate)]
 pub struct InterviewPanelMember {
+    #[validate(email(message = "Panel member email must be valid"))]
     pub email: String,
     pub name: Option<String>,
     pub role: Option<String>,
@@ -298,15 +499,22 @@ pub struct Panelist {
     pub updated_at: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Validate)]
 pub struct CreateInterviewRequest {
     pub application_id: String,
     pub scheduled_date: String,
+    #[validate(range(min = 1, max = 480, message = "Duration must be between 1 and 480 minutes"))]
     pub duration_minutes: i32,
     pub interview_type: String,
+    #[validate(nested)]
     pub panel_members: Vec<InterviewPanelMember>,
     pub notes: Option<String>,
     pub create_google_meet: bool,
+    /// IANA zone the date was entered in; required when `scheduled_date` has no offset
+    #[serde(default)]
+    pub timezone: Option<String>,
+    #[serde(default)]
+    pub preparation_notes: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -316,6 +524,13 @@ pub struct UpdateInterviewRequest {
     pub interview_type: Option<String>,
     pub panel_members: Option<Vec<InterviewPanelMember>>,
     pub notes: Option<String>,
+    #[serde(default)]
+    pub timezone: Option<String>,
+    #[serde(default)]
+    pub preparation_notes: Option<String>,
+    /// Let the candidate know their interview feedback is available
+    #[serde(default)]
+    pub feedback_released: Option<bool>,
 }
 
 #[derive(Debug, Serialize)]
@@ -323,11 +538,127 @@ pub struct InterviewWithDetails {
     #[serde(flatten)]
     pub interview: Interview,
     pub candidate_name: String,
+    #[serde(serialize_with = "crate::services::masking::email")]
     pub candidate_email: String,
     pub job_title: String,
     pub panel_members_parsed: Vec<InterviewPanelMember>,
 }
 
+/// Whether the caller has an interview calendar or application feed
+#[derive(Debug, Serialize)]
+pub struct CalendarFeedStatus {
+    pub active: bool,
+    pub created_at: Option<String>,
+    /// When a calendar app last fetched the feed
+    pub last_accessed_at: Option<String>,
+}
+
+/// A freshly generated feed URL; it can't be shown again, only regenerated
+#[derive(Debug, Serialize)]
+pub struct CalendarFeedLink {
+    pub url: String,
+    /// Same feed with the `webcal://` scheme, which opens the subscribe dialog directly
+    pub webcal_url: String,
+}
+
+/// Freshly generated application feed URLs; they can't be shown again, only regenerated
+#[derive(Debug, Serialize)]
+pub struct ApplicationFeedLinks {
+    /// Status changes, for feed readers
+    pub rss_url: String,
+    /// The candidate's interviews, for calendar apps
+    pub ics_url: String,
+    /// `ics_url` with the `webcal://` scheme, which opens the subscribe dialog directly
+    pub webcal_url: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct InterviewCalendarQuery {
+    /// First day shown (YYYY-MM-DD); defaults to today
+    pub from: Option<String>,
+    /// Last day shown, inclusive (YYYY-MM-DD); defaults to six days after `from`
+    pub to: Option<String>,
+    /// Panelist email or user id
+    pub panelist: Option<String>,
+    /// IANA zone used to split days; defaults to the viewer's preference
+    pub tz: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CalendarInterview {
+    pub id: String,
+    pub application_id: String,
+    pub scheduled_date: String,
+    pub end_time: String,
+    /// Zone the interview was booked in
+    pub scheduled_timezone: Option<String>,
+    pub duration_minutes: i32,
+    pub interview_type: String,
+    pub status: Option<String>,
+    pub google_meet_link: Option<String>,
+    pub google_calendar_event_id: Option<String>,
+    pub candidate_id: String,
+    pub candidate_name: Option<String>,
+    #[serde(serialize_with = "crate::services::masking::email_opt")]
+    pub candidate_email: Option<String>,
+    pub job_id: Option<String>,
+    pub job_title: Option<String>,
+    pub panel_members: Vec<InterviewPanelMember>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CalendarDay {
+    pub date: String,
+    pub interviews: Vec<CalendarInterview>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct InterviewCalendar {
+    pub from: String,
+    pub to: String,
+    pub timezone: String,
+    pub total: usize,
+    pub days: Vec<CalendarDay>,
+}
+
+/// An interview as the candidate sees it: no internal notes or panelist contact details
+#[derive(Debug, Serialize)]
+pub struct CandidateInterview {
+    pub id: String,
+    pub application_id: String,
+    pub job_id: Option<String>,
+    pub job_title: Option<String>,
+    pub company: Option<String>,
+    pub scheduled_date: String,
+    pub end_time: String,
+    pub duration_minutes: i32,
+    pub interview_type: String,
+    pub status: Option<String>,
+    pub google_meet_link: Option<String>,
+    /// Panelist names only
+    pub panel: Vec<String>,
+    pub preparation_notes: Option<String>,
+    pub feedback_released: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CandidateInterviewHub {
+    pub timezone: String,
+    /// Soonest first, including interviews still in progress
+    pub upcoming: Vec<CandidateInterview>,
+    /// Most recent first
+    pub past: Vec<CandidateInterview>,
+}
+
+/// Outcome of pulling Google Calendar changes into the interviews table
+#[derive(Debug, Default, Serialize)]
+pub struct CalendarSyncSummary {
+    pub checked: usize,
+    pub rescheduled: usize,
+    pub cancelled: usize,
+    pub failed: usize,
+}
+
 #[derive(Debug, Serialize)]
 pub struct GoogleMeetLinkResponse {
     pub meet_link: String,
@@ -344,6 +675,52 @@ pub struct CreateGoogleMeetRequest {
     pub attendees: Vec<String>,
 }
 
+/// A recording or transcript uploaded for a completed interview
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct InterviewArtifact {
+    pub id: String,
+    pub interview_id: String,
+    /// `recording` or `transcript`
+    pub kind: String,
+    pub original_filename: Option<String>,
+    pub content_type: String,
+    pub file_size: i64,
+    pub uploaded_by: String,
+    pub created_at: Option<String>,
+}
+
+/// What the hiring team kept from an interview; download artifacts through
+/// `/api/files/interview-artifact/:id/download-url`
+#[derive(Debug, Serialize)]
+pub struct InterviewRecord {
+    pub interview_id: String,
+    pub application_id: String,
+    pub shared_notes: Option<String>,
+    pub shared_notes_updated_by: Option<String>,
+    pub shared_notes_updated_at: Option<String>,
+    pub artifacts: Vec<InterviewArtifact>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateSharedNotesRequest {
+    /// Empty clears the notes
+    pub notes: String,
+}
+
+/// One interview note or file on an application's timeline
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct InterviewRecordEntry {
+    pub interview_id: String,
+    /// `notes`, `recording` or `transcript`
+    pub kind: String,
+    pub artifact_id: Option<String>,
+    pub added_by: Option<String>,
+    pub added_at: Option<String>,
+    /// The interview's record, with the notes and every artifact
+    #[sqlx(skip)]
+    pub link: String,
+}
+
 // ============================================================================
 // Candidate Stage Management Models
 // ============================================================================
@@ -432,3 +809,311 @@ pub struct CandidateWithApplications {
     pub candidate_avatar: Option<String>,
     pub applications: Vec<CandidateApplicationWithDetails>,
 }
+
+// ============================================================================
+// Document Collection Models
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct DocumentRequest {
+    pub id: String,
+    pub candidate_id: String,
+    pub application_id: Option<String>,
+    pub document_type: String,
+    pub instructions: Option<String>,
+    pub status: String,
+    pub requested_by: String,
+    pub due_date: Option<String>,
+    /// Storage name of the encrypted upload; never exposed to clients
+    #[serde(skip_serializing)]
+    pub filename: Option<String>,
+    pub original_filename: Option<String>,
+    pub content_type: Option<String>,
+    pub file_size: Option<i64>,
+    pub uploaded_at: Option<String>,
+    pub review_notes: Option<String>,
+    pub reviewed_by: Option<String>,
+    pub reviewed_at: Option<String>,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateDocumentRequestsRequest {
+    pub document_types: Vec<String>,
+    pub application_id: Option<String>,
+    pub instructions: Option<String>,
+    pub due_date: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ReviewDocumentRequest {
+    /// `approved` or `rejected`
+    pub status: String,
+    pub notes: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DocumentDownloadQuery {
+    pub token: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DocumentDownloadUrl {
+    pub url: String,
+    pub expires_at: String,
+}
+
+// ============================================================================
+// Candidate Satisfaction Survey Models
+// ============================================================================
+
+/// Satisfaction survey sent once an application is hired or rejected
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct CandidateSurvey {
+    pub id: String,
+    pub application_id: String,
+    pub job_id: String,
+    pub user_id: String,
+    /// Admin who made the final decision on the application
+    pub recruiter_id: Option<String>,
+    /// `hired` or `rejected`
+    pub outcome: String,
+    #[serde(skip_serializing)]
+    pub token: String,
+    /// 0-10 answer to "how likely are you to recommend applying here"
+    pub score: Option<i64>,
+    pub comment: Option<String>,
+    pub sent_at: Option<String>,
+    pub expires_at: String,
+    pub responded_at: Option<String>,
+    pub created_at: Option<String>,
+}
+
+/// What the public survey page shows; never includes candidate details
+#[derive(Debug, Serialize)]
+pub struct PublicSurvey {
+    pub job_title: Option<String>,
+    pub company: Option<String>,
+    pub responded: bool,
+    pub expired: bool,
+    pub expires_at: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SubmitSurveyRequest {
+    pub score: i64,
+    pub comment: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct NpsQuery {
+    /// Only surveys sent on or after this date (`YYYY-MM-DD`)
+    pub from: Option<String>,
+    /// Only surveys sent on or before this date (`YYYY-MM-DD`)
+    pub to: Option<String>,
+    pub job_id: Option<String>,
+}
+
+/// Survey counts for one group (a job, a recruiter, or everything)
+#[derive(Debug, Serialize, FromRow)]
+pub struct NpsBreakdown {
+    /// Job or recruiter ID; None for the overall figures
+    pub id: Option<String>,
+    /// Job title or recruiter name
+    pub label: Option<String>,
+    pub sent: i64,
+    pub responses: i64,
+    pub promoters: i64,
+    pub passives: i64,
+    pub detractors: i64,
+    /// Percentage of promoters minus percentage of detractors, -100 to 100
+    #[sqlx(skip)]
+    pub nps: Option<f64>,
+    #[sqlx(skip)]
+    pub response_rate: Option<f64>,
+}
+
+#[derive(Debug, Serialize, FromRow)]
+pub struct SurveyComment {
+    pub job_id: String,
+    pub job_title: Option<String>,
+    pub outcome: String,
+    pub score: i64,
+    pub comment: String,
+    pub responded_at: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct NpsReport {
+    pub overall: NpsBreakdown,
+    pub by_job: Vec<NpsBreakdown>,
+    pub by_recruiter: Vec<NpsBreakdown>,
+    pub recent_comments: Vec<SurveyComment>,
+}
+
+/// Voluntary self-identification answers; every field is optional and never affects the application
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SelfIdentification {
+    pub gender: Option<String>,
+    pub race_ethnicity: Option<String>,
+    pub veteran_status: Option<String>,
+    pub disability_status: Option<String>,
+}
+
+/// The choices offered for one self-identification question
+#[derive(Debug, Serialize)]
+pub struct SelfIdentificationQuestion {
+    pub field: &'static str,
+    pub options: &'static [&'static str],
+}
+
+#[derive(Debug, Deserialize)]
+pub struct EeoReportQuery {
+    pub job_id: Option<String>,
+    /// Only applications currently in this status, e.g. `hired`
+    pub status: Option<String>,
+    /// Inclusive YYYY-MM-DD bounds on when the application was submitted
+    pub from: Option<String>,
+    pub to: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct EeoCategoryCount {
+    pub category: String,
+    pub count: i64,
+}
+
+/// Answers to one question; groups smaller than the minimum cohort are folded into `suppressed`
+#[derive(Debug, Serialize)]
+pub struct EeoFieldBreakdown {
+    pub field: String,
+    pub categories: Vec<EeoCategoryCount>,
+    pub suppressed: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct EeoReport {
+    pub applications: i64,
+    pub respondents: i64,
+    pub min_cohort_size: i64,
+    /// True when there are too few respondents to report anything
+    pub withheld: bool,
+    pub fields: Vec<EeoFieldBreakdown>,
+}
+
+// ============================================================================
+// Rejection Feedback Versions (Inline AI Editor)
+// ============================================================================
+
+/// One phrasing of the feedback sent to a rejected candidate
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct FeedbackVersion {
+    pub id: String,
+    pub application_id: String,
+    pub content: String,
+    /// The rejection reason the phrasing was written from
+    pub reason: Option<String>,
+    pub tone: Option<String>,
+    pub is_active: i32,
+    pub version_number: i32,
+    pub created_by: Option<String>,
+    pub created_at: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FeedbackVersionsResponse {
+    pub active: Option<FeedbackVersion>,
+    pub history: Vec<FeedbackVersion>,
+    pub total: usize,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct GenerateFeedbackRequest {
+    /// Defaults to the note recorded when the application was rejected
+    pub reason: Option<String>,
+    pub tone: Option<String>,
+    /// Number of phrasings to draft, 1-5 (default 3)
+    pub count: Option<usize>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct GenerateFeedbackResponse {
+    pub versions: Vec<FeedbackVersion>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ActivateFeedbackResponse {
+    pub success: bool,
+    pub version: FeedbackVersion,
+}
+
+/// A job suggested to the caller, best match first
+#[derive(Debug, Serialize)]
+pub struct RecommendedJob {
+    pub job_id: String,
+    pub title: String,
+    pub company: Option<String>,
+    pub company_logo_url: Option<String>,
+    pub location: Option<String>,
+    pub job_type: Option<String>,
+    pub salary_min: Option<i64>,
+    pub salary_max: Option<i64>,
+    pub published_at: Option<String>,
+    /// 0-100
+    pub score: i64,
+    /// Why the job was suggested, for display
+    pub explanation: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RecommendedJobsQuery {
+    /// 1-50, default 10
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DismissRecommendationRequest {
+    /// One of `recommendations::DISMISS_REASONS`
+    pub reason: Option<String>,
+}
+
+/// A job the caller looked at, with where they got to with it
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct RecentlyViewedJob {
+    pub job_id: String,
+    pub title: String,
+    pub company: Option<String>,
+    pub company_logo_url: Option<String>,
+    pub location: Option<String>,
+    pub job_type: Option<String>,
+    pub salary_min: Option<i64>,
+    pub salary_max: Option<i64>,
+    /// Still accepting applications
+    pub is_open: bool,
+    pub last_viewed_at: String,
+    pub view_count: i64,
+    pub saved: bool,
+    /// Status of the caller's application, if they applied
+    pub application_status: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RecentlyViewedResponse {
+    /// False when the caller turned tracking off; `jobs` is then empty
+    pub tracking_enabled: bool,
+    /// The most recent open job the caller viewed but hasn't applied to
+    pub continue_where_left_off: Option<RecentlyViewedJob>,
+    pub jobs: Vec<RecentlyViewedJob>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RecentlyViewedQuery {
+    /// 1-50, default 20
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ViewTrackingSettings {
+    pub tracking_enabled: bool,
+}
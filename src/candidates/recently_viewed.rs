@@ -0,0 +1,91 @@
+// src/candidates/recently_viewed.rs
+//! Jobs a signed-in user has looked at, for their recently viewed list
+//!
+//! Views are the `job_views` rows the public view tracker already writes; for a signed-in
+//! user who hasn't turned tracking off the row carries their user ID. Turning tracking off
+//! or clearing the history unlinks past rows instead of deleting them, so job view counts
+//! stay intact.
+
+use sqlx::SqlitePool;
+
+use crate::candidates::models::RecentlyViewedJob;
+
+/// Whether `user_id`'s job views are linked to them; on unless they opted out
+pub async fn tracking_enabled(pool: &SqlitePool, user_id: &str) -> Result<bool, sqlx::Error> {
+    let track: Option<Option<i64>> =
+        sqlx::query_scalar("SELECT track_job_views FROM users WHERE id = ?")
+            .bind(user_id)
+            .fetch_optional(pool)
+            .await?;
+    Ok(track.flatten().unwrap_or(1) != 0)
+}
+
+/// Turn tracking on or off; turning it off also forgets past views
+pub async fn set_tracking(
+    pool: &SqlitePool,
+    user_id: &str,
+    enabled: bool,
+) -> Result<(), sqlx::Error> {
+    sqlx::query("UPDATE users SET track_job_views = ? WHERE id = ?")
+        .bind(enabled as i64)
+        .bind(user_id)
+        .execute(pool)
+        .await?;
+    if !enabled {
+        forget_views(pool, user_id).await?;
+    }
+    Ok(())
+}
+
+/// Unlink every view from `user_id`; returns how many were unlinked
+pub async fn forget_views(pool: &SqlitePool, user_id: &str) -> Result<u64, sqlx::Error> {
+    let result = sqlx::query("UPDATE job_views SET user_id = NULL WHERE user_id = ?")
+        .bind(user_id)
+        .execute(pool)
+        .await?;
+    Ok(result.rows_affected())
+}
+
+/// Distinct jobs `user_id` viewed, most recent first, with whether they saved or applied;
+/// deleted jobs are left out
+pub async fn recently_viewed(
+    pool: &SqlitePool,
+    user_id: &str,
+    limit: i64,
+) -> Result<Vec<RecentlyViewedJob>, sqlx::Error> {
+    sqlx::query_as::<_, RecentlyViewedJob>(
+        r#"
+        SELECT
+            j.id AS job_id, j.title, j.company, j.company_logo_url, j.location, j.job_type,
+            j.salary_min, j.salary_max,
+            (j.status = 'active') AS is_open,
+            v.last_viewed_at, v.view_count,
+            EXISTS (SELECT 1 FROM saved_jobs s WHERE s.user_id = ? AND s.job_id = j.id) AS saved,
+            (SELECT a.status FROM applications a
+             WHERE a.user_id = ? AND a.job_id = j.id
+             ORDER BY a.applied_at DESC LIMIT 1) AS application_status
+        FROM (
+            SELECT job_id, MAX(viewed_at) AS last_viewed_at, COUNT(*) AS view_count
+            FROM job_views
+            WHERE user_id = ?
+            GROUP BY job_id
+        ) v
+        JOIN jobs j ON j.id = v.job_id AND j.deleted_at IS NULL
+        ORDER BY v.last_viewed_at DESC
+        LIMIT ?
+        "#,
+    )
+    .bind(user_id)
+    .bind(user_id)
+    .bind(user_id)
+    .bind(limit)
+    .fetch_all(pool)
+    .await
+}
+
+/// The job to offer picking back up: the latest one still open and not yet applied to
+pub fn continue_where_left_off(jobs: &[RecentlyViewedJob]) -> Option<RecentlyViewedJob> {
+    jobs.iter()
+        .find(|job| job.is_open && job.application_status.is_none())
+        .cloned()
+}
@@ -0,0 +1,233 @@
+// src/candidates/recommendations.rs
+//! Rule-based job recommendations
+//!
+//! A candidate's signals are their skills (profile plus latest scanned resume) and what the
+//! jobs they saved or applied to have in common: title words, job types, locations,
+//! experience levels and companies. Each open job is scored against them out of 100 and every
+//! rule that fired adds a human-readable reason, which is what the candidate sees as the
+//! explanation.
+
+use serde_json::Value;
+use sqlx::FromRow;
+use std::collections::HashSet;
+
+use crate::candidates::requirements_match::keywords;
+
+/// Points per matched skill, and the most skills that count
+const SKILL_POINTS: i64 = 12;
+const MAX_SKILL_MATCHES: usize = 4;
+/// Points when the title shares every word with titles the candidate showed interest in
+const TITLE_POINTS: f64 = 20.0;
+const LOCATION_POINTS: i64 = 10;
+const JOB_TYPE_POINTS: i64 = 10;
+const EXPERIENCE_POINTS: i64 = 7;
+const COMPANY_POINTS: i64 = 5;
+
+/// Reasons a recommendation can be dismissed with
+pub const DISMISS_REASONS: &[&str] = &[
+    "not_relevant",
+    "wrong_location",
+    "wrong_seniority",
+    "wrong_salary",
+    "already_applied_elsewhere",
+    "other",
+];
+
+/// The parts of a job the rules look at
+#[derive(Debug, Clone, FromRow)]
+pub struct JobFacts {
+    pub id: String,
+    pub title: String,
+    pub company: Option<String>,
+    pub location: Option<String>,
+    pub job_type: Option<String>,
+    pub experience_level: Option<String>,
+    pub requirements: Option<String>,
+    pub description: Option<String>,
+}
+
+/// What a candidate is known to be after
+#[derive(Debug, Default)]
+pub struct CandidateSignals {
+    /// As the candidate wrote them, deduplicated case-insensitively
+    skills: Vec<String>,
+    title_keywords: HashSet<String>,
+    job_types: HashSet<String>,
+    locations: HashSet<String>,
+    experience_levels: HashSet<String>,
+    companies: HashSet<String>,
+}
+
+fn normalized(value: Option<&str>) -> Option<String> {
+    value
+        .map(|v| v.trim().to_lowercase())
+        .filter(|v| !v.is_empty())
+}
+
+/// Skills from a `skills` JSON array, as stored on profiles
+pub fn parse_skills(raw: Option<&str>) -> Vec<String> {
+    raw.and_then(|raw| serde_json::from_str::<Vec<Value>>(raw).ok())
+        .unwrap_or_default()
+        .iter()
+        .filter_map(Value::as_str)
+        .map(str::to_string)
+        .collect()
+}
+
+/// Skills from a scanned resume's `parsed_json`
+pub fn resume_skills(parsed_json: Option<&str>) -> Vec<String> {
+    let Some(parsed) = parsed_json.and_then(|p| serde_json::from_str::<Value>(p).ok()) else {
+        return Vec::new();
+    };
+    parsed
+        .get("extracted_data")
+        .unwrap_or(&parsed)
+        .get("skills")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str)
+        .map(str::to_string)
+        .collect()
+}
+
+impl CandidateSignals {
+    pub fn add_skills<I: IntoIterator<Item = String>>(&mut self, skills: I) {
+        for skill in skills {
+            let skill = skill.trim().to_string();
+            if !skill.is_empty() && !self.skills.iter().any(|s| s.eq_ignore_ascii_case(&skill)) {
+                self.skills.push(skill);
+            }
+        }
+    }
+
+    pub fn add_location(&mut self, location: Option<&str>) {
+        if let Some(location) = normalized(location) {
+            self.locations.insert(location);
+        }
+    }
+
+    /// Learn from a job the candidate saved or applied to
+    pub fn learn_from(&mut self, job: &JobFacts) {
+        self.title_keywords.extend(keywords(&job.title));
+        self.add_location(job.location.as_deref());
+        if let Some(job_type) = normalized(job.job_type.as_deref()) {
+            self.job_types.insert(job_type);
+        }
+        if let Some(level) = normalized(job.experience_level.as_deref()) {
+            self.experience_levels.insert(level);
+        }
+        if let Some(company) = normalized(job.company.as_deref()) {
+            self.companies.insert(company);
+        }
+    }
+}
+
+/// How well one job fits a candidate
+#[derive(Debug, Clone, PartialEq)]
+pub struct JobScore {
+    /// 0-100
+    pub score: i64,
+    pub reasons: Vec<String>,
+}
+
+impl JobScore {
+    /// The reasons as one sentence for display
+    pub fn explanation(&self) -> String {
+        self.reasons.join("; ")
+    }
+}
+
+/// Score `job` against `signals`
+pub fn score_job(signals: &CandidateSignals, job: &JobFacts) -> JobScore {
+    let mut score = 0;
+    let mut reasons = Vec::new();
+
+    let job_text = [
+        Some(job.title.as_str()),
+        job.requirements.as_deref(),
+        job.description.as_deref(),
+    ]
+    .into_iter()
+    .flatten()
+    .collect::<Vec<_>>()
+    .join(" ");
+    let job_keywords: HashSet<String> = keywords(&job_text).into_iter().collect();
+
+    let matched_skills: Vec<&String> = signals
+        .skills
+        .iter()
+        .filter(|skill| {
+            let wanted = keywords(skill);
+            !wanted.is_empty() && wanted.iter().all(|kw| job_keywords.contains(kw))
+        })
+        .take(MAX_SKILL_MATCHES)
+        .collect();
+    if !matched_skills.is_empty() {
+        score += SKILL_POINTS * matched_skills.len() as i64;
+        reasons.push(format!(
+            "Matches your skills: {}",
+            matched_skills
+                .iter()
+                .map(|s| s.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ));
+    }
+
+    let title_keywords = keywords(&job.title);
+    let shared = title_keywords
+        .iter()
+        .filter(|kw| signals.title_keywords.contains(*kw))
+        .count();
+    if shared > 0 {
+        score += (TITLE_POINTS * shared as f64 / title_keywords.len() as f64).round() as i64;
+        reasons.push("Similar to jobs you saved or applied to".to_string());
+    }
+
+    if let Some(location) = normalized(job.location.as_deref()) {
+        if signals
+            .locations
+            .iter()
+            .any(|wanted| location.contains(wanted.as_str()) || wanted.contains(&location))
+        {
+            score += LOCATION_POINTS;
+            reasons.push(format!(
+                "In {}",
+                job.location.as_deref().unwrap_or_default().trim()
+            ));
+        }
+    }
+
+    if let Some(job_type) = normalized(job.job_type.as_deref()) {
+        if signals.job_types.contains(&job_type) {
+            score += JOB_TYPE_POINTS;
+            reasons.push(format!(
+                "Same job type ({}) as roles you've looked at",
+                job.job_type.as_deref().unwrap_or_default().trim()
+            ));
+        }
+    }
+
+    if let Some(level) = normalized(job.experience_level.as_deref()) {
+        if signals.experience_levels.contains(&level) {
+            score += EXPERIENCE_POINTS;
+            reasons.push("At the experience level you've been targeting".to_string());
+        }
+    }
+
+    if let Some(company) = normalized(job.company.as_deref()) {
+        if signals.companies.contains(&company) {
+            score += COMPANY_POINTS;
+            reasons.push(format!(
+                "At {}, a company you've shown interest in",
+                job.company.as_deref().unwrap_or_default().trim()
+            ));
+        }
+    }
+
+    JobScore {
+        score: score.min(100),
+        reasons,
+    }
+}
@@ -0,0 +1,328 @@
+// src/candidates/requirements_match.rs
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashSet;
+
+/// Share of a requirement's keywords the resume must cover to count as met
+const MET_THRESHOLD: f64 = 0.75;
+const MAX_EVIDENCE: usize = 3;
+const MAX_SNIPPET_CHARS: usize = 200;
+
+/// Filler words that say nothing about whether a candidate qualifies
+const STOPWORDS: &[&str] = &[
+    "a",
+    "an",
+    "and",
+    "or",
+    "the",
+    "of",
+    "in",
+    "on",
+    "to",
+    "for",
+    "with",
+    "as",
+    "at",
+    "by",
+    "is",
+    "are",
+    "be",
+    "our",
+    "your",
+    "you",
+    "we",
+    "will",
+    "must",
+    "should",
+    "can",
+    "plus",
+    "etc",
+    "strong",
+    "good",
+    "excellent",
+    "solid",
+    "proven",
+    "working",
+    "knowledge",
+    "understanding",
+    "familiarity",
+    "familiar",
+    "ability",
+    "able",
+    "skills",
+    "skill",
+    "experience",
+    "experienced",
+    "years",
+    "year",
+    "yrs",
+    "least",
+    "minimum",
+    "preferred",
+    "required",
+    "requirement",
+    "bonus",
+    "nice",
+    "have",
+    "has",
+    "using",
+    "use",
+    "including",
+    "related",
+    "relevant",
+    "similar",
+    "field",
+    "equivalent",
+    "degree",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RequirementStatus {
+    Met,
+    PartiallyMet,
+    Missing,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RequirementMatch {
+    pub requirement: String,
+    pub status: RequirementStatus,
+    /// Requirement keywords found anywhere in the resume
+    pub matched_keywords: Vec<String>,
+    pub missing_keywords: Vec<String>,
+    /// Resume snippets that mention the requirement, best match first
+    pub evidence: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RequirementComparison {
+    pub met: usize,
+    pub partially_met: usize,
+    pub missing: usize,
+    /// 0-100, partially met requirements count for half
+    pub match_score: i64,
+    pub requirements: Vec<RequirementMatch>,
+}
+
+/// Cached comparison for one application, as served to reviewers
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApplicationRequirementsMatch {
+    pub application_id: String,
+    pub job_id: String,
+    pub resume_id: String,
+    pub generated_at: String,
+    pub cached: bool,
+    #[serde(flatten)]
+    pub comparison: RequirementComparison,
+}
+
+/// Parse a job's requirements column, which holds a JSON array but may be plain lines in old rows
+pub fn parse_requirements(raw: Option<&str>) -> Vec<String> {
+    let raw = match raw {
+        Some(raw) if !raw.trim().is_empty() => raw,
+        _ => return Vec::new(),
+    };
+
+    let items = serde_json::from_str::<Vec<String>>(raw).unwrap_or_else(|_| {
+        raw.lines()
+            .map(|line| line.trim_start_matches(['-', '*', '•', ' ']).to_string())
+            .collect()
+    });
+
+    items
+        .into_iter()
+        .map(|item| item.trim().to_string())
+        .filter(|item| !item.is_empty())
+        .collect()
+}
+
+/// Lowercased, deduplicated words of `text` that say something about a qualification
+pub(crate) fn keywords(text: &str) -> Vec<String> {
+    let mut seen = HashSet::new();
+    text.to_lowercase()
+        .split(|c: char| !(c.is_alphanumeric() || c == '+' || c == '#'))
+        .map(|token| token.trim_matches('+'))
+        .filter(|token| {
+            !token.is_empty()
+                && !token.chars().all(|c| c.is_ascii_digit())
+                && (token.chars().count() > 1 || matches!(*token, "c" | "r"))
+                && !STOPWORDS.contains(token)
+        })
+        .filter(|token| seen.insert(token.to_string()))
+        .map(str::to_string)
+        .collect()
+}
+
+/// Flatten the parsed resume into human-readable snippets to quote as evidence
+pub fn resume_snippets(parsed: &Value) -> Vec<String> {
+    let text = |value: &Value, key: &str| {
+        value
+            .get(key)
+            .and_then(Value::as_str)
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(str::to_string)
+    };
+    let list = |key: &str| -> Vec<String> {
+        parsed
+            .get(key)
+            .and_then(Value::as_array)
+            .map(|items| {
+                items
+                    .iter()
+                    .filter_map(Value::as_str)
+                    .map(|s| s.trim().to_string())
+                    .filter(|s| !s.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+
+    let mut snippets = Vec::new();
+
+    if let Some(summary) = text(parsed, "summary") {
+        snippets.push(summary);
+    }
+
+    let skills = list("skills");
+    if !skills.is_empty() {
+        snippets.push(format!("Skills: {}", skills.join(", ")));
+    }
+
+    for entry in parsed
+        .get("experience")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+    {
+        let heading = match (text(entry, "title"), text(entry, "company")) {
+            (Some(title), Some(company)) => format!("{} at {}", title, company),
+            (title, company) => title.or(company).unwrap_or_default(),
+        };
+        let snippet = match text(entry, "description") {
+            Some(description) if heading.is_empty() => description,
+            Some(description) => format!("{}: {}", heading, description),
+            None => heading,
+        };
+        if !snippet.is_empty() {
+            snippets.push(snippet);
+        }
+    }
+
+    for entry in parsed
+        .get("education")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+    {
+        let parts: Vec<String> = ["degree", "field_of_study", "institution"]
+            .iter()
+            .filter_map(|key| text(entry, key))
+            .collect();
+        if !parts.is_empty() {
+            snippets.push(parts.join(", "));
+        }
+    }
+
+    let certifications = list("certifications");
+    if !certifications.is_empty() {
+        snippets.push(format!("Certifications: {}", certifications.join(", ")));
+    }
+
+    let languages = list("languages");
+    if !languages.is_empty() {
+        snippets.push(format!("Languages: {}", languages.join(", ")));
+    }
+
+    snippets
+}
+
+fn truncate(snippet: &str) -> String {
+    if snippet.chars().count() <= MAX_SNIPPET_CHARS {
+        snippet.to_string()
+    } else {
+        let cut: String = snippet.chars().take(MAX_SNIPPET_CHARS).collect();
+        format!("{}…", cut.trim_end())
+    }
+}
+
+/// Compare each job requirement against the parsed resume
+pub fn compare_requirements(
+    requirements: &[String],
+    parsed_resume: &Value,
+) -> RequirementComparison {
+    let snippets = resume_snippets(parsed_resume);
+    let snippet_keywords: Vec<HashSet<String>> = snippets
+        .iter()
+        .map(|s| keywords(s).into_iter().collect())
+        .collect();
+
+    let matches: Vec<RequirementMatch> = requirements
+        .iter()
+        .map(|requirement| {
+            let wanted = keywords(requirement);
+            let (matched_keywords, missing_keywords): (Vec<String>, Vec<String>) = wanted
+                .iter()
+                .cloned()
+                .partition(|kw| snippet_keywords.iter().any(|set| set.contains(kw)));
+
+            let mut ranked: Vec<(usize, &String)> = snippets
+                .iter()
+                .zip(&snippet_keywords)
+                .map(|(snippet, set)| {
+                    (
+                        wanted.iter().filter(|kw| set.contains(*kw)).count(),
+                        snippet,
+                    )
+                })
+                .filter(|(hits, _)| *hits > 0)
+                .collect();
+            ranked.sort_by_key(|(hits, _)| std::cmp::Reverse(*hits));
+
+            let coverage = if wanted.is_empty() {
+                0.0
+            } else {
+                matched_keywords.len() as f64 / wanted.len() as f64
+            };
+            let status = if coverage >= MET_THRESHOLD {
+                RequirementStatus::Met
+            } else if coverage > 0.0 {
+                RequirementStatus::PartiallyMet
+            } else {
+                RequirementStatus::Missing
+            };
+
+            RequirementMatch {
+                requirement: requirement.clone(),
+                status,
+                matched_keywords,
+                missing_keywords,
+                evidence: ranked
+                    .into_iter()
+                    .take(MAX_EVIDENCE)
+                    .map(|(_, snippet)| truncate(snippet))
+                    .collect(),
+            }
+        })
+        .collect();
+
+    let count = |status: RequirementStatus| matches.iter().filter(|m| m.status == status).count();
+    let met = count(RequirementStatus::Met);
+    let partially_met = count(RequirementStatus::PartiallyMet);
+    let missing = count(RequirementStatus::Missing);
+    let match_score = if matches.is_empty() {
+        0
+    } else {
+        ((met as f64 + partially_met as f64 * 0.5) / matches.len() as f64 * 100.0).round() as i64
+    };
+
+    RequirementComparison {
+        met,
+        partially_met,
+        missing,
+        match_score,
+        requirements: matches,
+    }
+}
@@ -1,6 +1,9 @@
 // src/candidates/routes.rs
 
-use crate::candidates::handlers::{self, files};
+use crate::candidates::handlers::{
+    self, application_feed, calendar_feed, documents, eeo, feedback_versions, files, guest_apply, interview_artifacts,
+    recently_viewed, recommendations, resume_exports, surveys, video_uploads,
+};
 use axum::{
     routing::{delete, get, patch, post, put},
     Router,
@@ -10,6 +13,12 @@ pub fn candidates_routes() -> Router {
     Router::new()
         // File serving routes
         .route("/uploads/resumes/*path", get(files::serve_resume_file))
+        .route(
+            "/api/files/:kind/:id/download-url",
+            get(files::get_file_download_url),
+        )
+        .route("/api/files/:kind/:id", get(files::download_file))
+        .route("/api/me/storage", get(files::get_my_storage_usage))
         // Application routes
         .route(
             "/api/applications",
@@ -23,6 +32,19 @@ pub fn candidates_routes() -> Router {
             "/api/applications/:id/status",
             patch(handlers::update_application_status),
         )
+        .route(
+            "/api/applications/:id/resume",
+            put(handlers::swap_application_resume),
+        )
+        .route(
+            "/api/applications/:id/resume-history",
+            get(handlers::get_application_resume_history),
+        )
+        .route(
+            "/api/jobs/:id/screening-questions",
+            get(handlers::get_job_screening_questions),
+        )
+        .route("/api/jobs/:id/guest-apply", post(guest_apply::guest_apply))
         // Admin application routes
         .route(
             "/api/admin/jobs/:id/applications",
@@ -50,6 +72,10 @@ pub fn candidates_routes() -> Router {
             post(handlers::bulk_update_application_status),
         )
         // Enhanced application management routes
+        .route(
+            "/api/admin/applications/:id/requirements-match",
+            get(handlers::get_application_requirements_match),
+        )
         .route(
             "/api/admin/applications/:id/advance-stage",
             post(handlers::advance_application_stage),
@@ -58,6 +84,23 @@ pub fn candidates_routes() -> Router {
             "/api/admin/applications/:id/send-email",
             post(handlers::send_application_email),
         )
+        // Rejection feedback versions (Inline AI Editor)
+        .route(
+            "/api/admin/applications/:id/feedback/versions",
+            get(feedback_versions::get_feedback_versions),
+        )
+        .route(
+            "/api/admin/applications/:id/feedback/generate",
+            post(feedback_versions::generate_feedback),
+        )
+        .route(
+            "/api/admin/applications/:id/feedback/versions/:version_id/activate",
+            post(feedback_versions::activate_feedback_version),
+        )
+        .route(
+            "/api/admin/applications/:id/feedback/versions/:version_id",
+            delete(feedback_versions::delete_feedback_version),
+        )
         .route(
             "/api/admin/applications/bulk-action",
             post(handlers::bulk_application_action),
@@ -67,6 +110,10 @@ pub fn candidates_routes() -> Router {
         .route("/api/user/resumes", get(handlers::get_user_resumes))
         .route("/api/resumes/:id", delete(handlers::delete_resume))
         .route("/api/resumes/:id/label", put(handlers::update_resume_label))
+        .route(
+            "/api/resumes/:id/versions",
+            get(handlers::get_resume_versions),
+        )
         .route("/api/resumes/:id/scan", post(handlers::scan_resume))
         .route("/api/resumes/:id/review", get(handlers::get_resume_review))
         .route(
@@ -74,6 +121,18 @@ pub fn candidates_routes() -> Router {
             post(handlers::propagate_resume_to_profile),
         )
         .route("/api/admin/resumes", get(handlers::admin_list_resumes))
+        .route(
+            "/api/admin/jobs/:id/resumes.zip",
+            get(resume_exports::download_job_resumes_zip),
+        )
+        .route(
+            "/api/admin/resume-exports/:id",
+            get(resume_exports::get_resume_export),
+        )
+        .route(
+            "/api/admin/resume-exports/:id/download",
+            get(resume_exports::download_resume_export),
+        )
         .route(
             "/api/admin/resumes/bulk-update-status",
             post(handlers::bulk_update_resume_status),
@@ -87,12 +146,53 @@ pub fn candidates_routes() -> Router {
             "/api/resumes/:id/retry-processing",
             post(handlers::retry_resume_processing),
         )
+        // Candidate document collection routes
+        .route(
+            "/api/admin/candidates/:id/document-requests",
+            get(documents::list_candidate_document_requests)
+                .post(documents::create_document_requests),
+        )
+        .route(
+            "/api/admin/document-requests/:id/review",
+            put(documents::review_document),
+        )
+        .route(
+            "/api/admin/document-requests/:id/download-url",
+            get(documents::admin_document_download_url),
+        )
+        .route(
+            "/api/me/document-requests",
+            get(documents::get_my_document_requests),
+        )
+        .route(
+            "/api/me/document-requests/:id/upload",
+            post(documents::upload_requested_document),
+        )
+        .route(
+            "/api/me/document-requests/:id/download-url",
+            get(documents::my_document_download_url),
+        )
+        .route(
+            "/api/documents/:id/download",
+            get(documents::download_document),
+        )
         // Video routes
         .route(
             "/api/user/videos",
             get(handlers::list_user_videos).post(handlers::upload_video),
         )
         .route("/api/user/videos/:id", delete(handlers::delete_video))
+        // Resumable video uploads
+        .route(
+            "/api/user/videos/uploads",
+            post(video_uploads::create_upload_session),
+        )
+        .route(
+            "/api/user/videos/uploads/:id",
+            get(video_uploads::get_upload_session)
+                .patch(video_uploads::upload_chunk)
+                .delete(video_uploads::cancel_upload_session),
+        )
         .route(
             "/api/applications/:id/video",
             post(handlers::upload_video)
@@ -126,6 +226,32 @@ pub fn candidates_routes() -> Router {
             "/api/admin/interviews/schedule",
             post(handlers::schedule_interview),
         )
+        .route(
+            "/api/admin/interviews/calendar",
+            get(handlers::get_interview_calendar),
+        )
+        .route("/api/me/interviews", get(handlers::get_my_interviews))
+        .route(
+            "/api/me/calendar-feed",
+            get(calendar_feed::get_calendar_feed)
+                .post(calendar_feed::regenerate_calendar_feed)
+                .delete(calendar_feed::revoke_calendar_feed),
+        )
+        .route("/calendar/:file", get(calendar_feed::serve_calendar_feed))
+        .route(
+            "/api/me/application-feed",
+            get(application_feed::get_application_feed)
+                .post(application_feed::regenerate_application_feed)
+                .delete(application_feed::revoke_application_feed),
+        )
+        .route(
+            "/feeds/applications/:file",
+            get(application_feed::serve_application_feed),
+        )
+        .route(
+            "/api/admin/interviews/calendar-sync",
+            post(handlers::sync_interview_calendar),
+        )
         .route(
             "/api/admin/interviews/:id",
             get(handlers::get_interview)
@@ -144,6 +270,23 @@ pub fn candidates_routes() -> Router {
             "/api/admin/jobs/:jobId/interviews",
             get(handlers::get_job_interviews),
         )
+        // Interview notes, recordings and transcripts (hiring team)
+        .route(
+            "/api/interviews/:id/record",
+            get(interview_artifacts::get_interview_record),
+        )
+        .route(
+            "/api/interviews/:id/notes",
+            put(interview_artifacts::update_shared_notes),
+        )
+        .route(
+            "/api/interviews/:id/artifacts",
+            post(interview_artifacts::upload_interview_artifact),
+        )
+        .route(
+            "/api/interviews/:id/artifacts/:artifact_id",
+            delete(interview_artifacts::delete_interview_artifact),
+        )
         // Panelist routes
         .route(
             "/api/admin/panelists",
@@ -163,4 +306,42 @@ pub fn candidates_routes() -> Router {
             "/api/saved-jobs/:job_id",
             get(handlers::is_job_saved).delete(handlers::unsave_job),
         )
+        // Job recommendation routes
+        .route(
+            "/api/me/recommended-jobs",
+            get(recommendations::get_recommended_jobs),
+        )
+        .route(
+            "/api/me/recommended-jobs/:job_id/dismiss",
+            post(recommendations::dismiss_recommended_job),
+        )
+        // Recently viewed job routes
+        .route(
+            "/api/me/recently-viewed",
+            get(recently_viewed::get_recently_viewed)
+                .delete(recently_viewed::clear_recently_viewed),
+        )
+        .route(
+            "/api/me/recently-viewed/settings",
+            get(recently_viewed::get_view_tracking_settings)
+                .put(recently_viewed::update_view_tracking_settings),
+        )
+        // Candidate satisfaction survey routes
+        .route(
+            "/api/public/surveys/:token",
+            get(surveys::get_survey).post(surveys::submit_survey),
+        )
+        .route("/api/admin/analytics/nps", get(surveys::get_nps_report))
+        // Voluntary self-identification (EEO) routes
+        .route(
+            "/api/self-identification/questions",
+            get(eeo::get_self_identification_questions),
+        )
+        .route(
+            "/api/applications/:id/self-identification",
+            get(eeo::get_self_identification)
+                .put(eeo::save_self_identification)
+                .delete(eeo::delete_self_identification),
+        )
+        .route("/api/admin/analytics/eeo", get(eeo::get_eeo_report))
 }
@@ -0,0 +1,80 @@
+// src/candidates/tests/contract_tests.rs
+
+#[cfg(test)]
+mod tests {
+    use crate::test_support::schema::assert_compatible;
+    use crate::test_support::TestApp;
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn test_application_and_interview_response_contracts() {
+        let app = TestApp::new().await;
+        let admin = app.token_for(&app.create_admin().await).await;
+        let candidate_id = app
+            .create_user("candidate@test.example.com", "Casey Candidate")
+            .await;
+        let candidate = app.token_for(&candidate_id).await;
+        let job_id = app.create_job("Backend Engineer").await;
+
+        let applied = app
+            .post(
+                "/api/applications",
+                &candidate,
+                json!({ "job_id": job_id, "cover_letter": "I'd like to join." }),
+            )
+            .await;
+        assert!(applied.status.is_success(), "{:?}", applied.body);
+        let application_id = applied.body["id"].as_str().unwrap().to_string();
+
+        let application = app
+            .get(&format!("/api/applications/{}", application_id), &candidate)
+            .await;
+        assert!(application.status.is_success(), "{:?}", application.body);
+        assert_compatible("application", &application.body);
+
+        let interview = app
+            .post(
+                "/api/admin/interviews/schedule",
+                &admin,
+                json!({
+                    "application_id": application_id,
+                    "scheduled_date": "2030-03-04T10:00:00Z",
+                    "duration_minutes": 45,
+                    "interview_type": "video",
+                    "panel_members": [{ "email": "panel@test.example.com", "name": "Pat Panel", "role": "Lead" }],
+                    "notes": "Systems design",
+                    "create_google_meet": true,
+                    "preparation_notes": "Bring a laptop",
+                }),
+            )
+            .await;
+        assert!(interview.status.is_success(), "{:?}", interview.body);
+        assert_compatible("interview", &interview.body);
+    }
+
+    #[tokio::test]
+    async fn test_resume_review_response_contract() {
+        let app = TestApp::new().await;
+        let candidate_id = app
+            .create_user("candidate@test.example.com", "Casey Candidate")
+            .await;
+        let candidate = app.token_for(&candidate_id).await;
+        let parsed = json!({
+            "ai_model": "test-model",
+            "scanned_at": "2030-01-01T00:00:00Z",
+            "extracted_data": { "name": "Casey Candidate", "skills": ["Rust"] },
+        });
+        sqlx::query(
+            "INSERT INTO resumes (id, user_id, filename, status, score, parsed_json) VALUES ('R_CONTRACT', ?, 'cv.pdf', 'scanned', 82.5, ?)",
+        )
+        .bind(&candidate_id)
+        .bind(parsed.to_string())
+        .execute(&app.state.db)
+        .await
+        .unwrap();
+
+        let review = app.get("/api/resumes/R_CONTRACT/review", &candidate).await;
+        assert!(review.status.is_success(), "{:?}", review.body);
+        assert_compatible("resume_review", &review.body);
+    }
+}
@@ -0,0 +1,97 @@
+// src/candidates/tests/education_match_tests.rs
+
+#[cfg(test)]
+mod tests {
+    use crate::candidates::education_match::{
+        classify_degree, education_match, highest_level, required_level, resume_degrees,
+        DegreeLevel,
+    };
+    use serde_json::json;
+
+    #[test]
+    fn test_classify_degree_names() {
+        assert_eq!(
+            classify_degree("Ph.D. in Chemistry"),
+            Some(DegreeLevel::Doctorate)
+        );
+        assert_eq!(
+            classify_degree("M.Sc. Physics"),
+            Some(DegreeLevel::PostGraduate)
+        );
+        assert_eq!(
+            classify_degree("Post-Graduate"),
+            Some(DegreeLevel::PostGraduate)
+        );
+        assert_eq!(
+            classify_degree("Master of Business Administration"),
+            Some(DegreeLevel::PostGraduate)
+        );
+        assert_eq!(
+            classify_degree("B.Tech Computer Science"),
+            Some(DegreeLevel::Graduate)
+        );
+        assert_eq!(
+            classify_degree("Under-Graduate"),
+            Some(DegreeLevel::Graduate)
+        );
+        assert_eq!(
+            classify_degree("Diploma in Mechanical Engineering"),
+            Some(DegreeLevel::Diploma)
+        );
+        assert_eq!(
+            classify_degree("Higher Secondary (12th)"),
+            Some(DegreeLevel::HighSchool)
+        );
+        assert_eq!(classify_degree("Certificate in Welding"), None);
+    }
+
+    #[test]
+    fn test_required_level_takes_the_lowest_listed() {
+        let quals = json!([
+            { "degree_level": "Post-Graduate", "preferred_iitian": true },
+            { "degree_level": "Graduate" }
+        ])
+        .to_string();
+        assert_eq!(required_level(Some(&quals)), Some(DegreeLevel::Graduate));
+        assert_eq!(
+            required_level(Some(r#"["PhD"]"#)),
+            Some(DegreeLevel::Doctorate)
+        );
+        assert_eq!(required_level(Some("[]")), None);
+        assert_eq!(required_level(Some("not json")), None);
+        assert_eq!(required_level(None), None);
+    }
+
+    #[test]
+    fn test_resume_degrees_reads_extracted_data() {
+        let parsed = json!({
+            "scanned_at": "2024-01-01T00:00:00Z",
+            "extracted_data": {
+                "education": [
+                    { "degree": "BSc", "institution": "State University" },
+                    { "degree": "MSc", "institution": "Tech Institute" }
+                ]
+            }
+        })
+        .to_string();
+        let degrees = resume_degrees(Some(&parsed));
+        assert_eq!(degrees, vec!["BSc", "MSc"]);
+        assert_eq!(highest_level(&degrees), Some(DegreeLevel::PostGraduate));
+        assert!(resume_degrees(None).is_empty());
+    }
+
+    #[test]
+    fn test_education_match() {
+        use DegreeLevel::*;
+        assert_eq!(education_match(None, Some(Graduate)), None);
+        assert_eq!(
+            education_match(Some(Graduate), Some(PostGraduate)),
+            Some(true)
+        );
+        assert_eq!(
+            education_match(Some(PostGraduate), Some(Graduate)),
+            Some(false)
+        );
+        assert_eq!(education_match(Some(Graduate), None), Some(false));
+    }
+}
@@ -0,0 +1,86 @@
+// src/candidates/tests/experience_years_tests.rs
+
+#[cfg(test)]
+mod tests {
+    use crate::candidates::experience_years::{parse_month, summarize};
+    use chrono::NaiveDate;
+    use serde_json::json;
+
+    fn today() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 6, 15).unwrap()
+    }
+
+    fn resume(experience: serde_json::Value) -> String {
+        json!({ "extracted_data": { "experience": experience } }).to_string()
+    }
+
+    #[test]
+    fn test_parse_month_formats() {
+        let march_2020 = 2020 * 12 + 2;
+        assert_eq!(parse_month("2020-03", false), Some(march_2020));
+        assert_eq!(parse_month("2020-03-15", false), Some(march_2020));
+        assert_eq!(parse_month("03/2020", false), Some(march_2020));
+        assert_eq!(parse_month("Mar 2020", false), Some(march_2020));
+        assert_eq!(parse_month("March, 2020", false), Some(march_2020));
+        assert_eq!(parse_month("2020", false), Some(2020 * 12));
+        assert_eq!(parse_month("2020", true), Some(2020 * 12 + 11));
+        assert_eq!(parse_month("2020-13", false), None);
+        assert_eq!(parse_month("sometime", false), None);
+    }
+
+    #[test]
+    fn test_overlapping_roles_are_counted_once() {
+        let parsed = resume(json!([
+            { "title": "Backend Engineer", "start_date": "2018-01", "end_date": "2020-12" },
+            { "title": "Freelance Developer", "start_date": "2020-01", "end_date": "2021-12" }
+        ]));
+        let summary = summarize(Some(&parsed), None, today()).unwrap();
+        assert_eq!(summary.total_years, 4.0);
+        assert_eq!(summary.relevant_years, 0.0);
+    }
+
+    #[test]
+    fn test_current_role_runs_to_today() {
+        let parsed = resume(json!([
+            { "title": "Data Analyst", "start_date": "2025-01", "end_date": "Present" },
+            { "title": "Data Analyst", "start_date": "2024-01", "end_date": null, "is_current": false },
+            { "title": "Intern", "start_date": "2023-07", "end_date": "2023-12", "is_current": true }
+        ]));
+        let summary = summarize(Some(&parsed), Some("Data Analyst"), today()).unwrap();
+        // 2023-07 through 2026-06, with the open-ended roles merged
+        assert_eq!(summary.total_years, 3.0);
+        assert_eq!(summary.relevant_years, 2.5);
+    }
+
+    #[test]
+    fn test_relevance_ignores_seniority_words() {
+        let parsed = resume(json!([
+            { "title": "Senior Backend Engineer", "start_date": "2019", "end_date": "2021" },
+            { "title": "Senior Sales Associate", "start_date": "2016", "end_date": "2018" }
+        ]));
+        let summary = summarize(Some(&parsed), Some("Backend Developer"), today()).unwrap();
+        assert_eq!(summary.total_years, 6.0);
+        assert_eq!(summary.relevant_years, 3.0);
+    }
+
+    #[test]
+    fn test_unreadable_dates_are_skipped() {
+        let parsed = resume(json!([
+            { "title": "Engineer", "start_date": "unknown", "end_date": "2020" },
+            { "title": "Engineer", "start_date": "2022-06", "end_date": "2022-01" },
+            { "title": "Engineer", "start_date": "2021-01", "end_date": "2021-06" }
+        ]));
+        let summary = summarize(Some(&parsed), Some("Engineer"), today()).unwrap();
+        assert_eq!(summary.total_years, 0.5);
+    }
+
+    #[test]
+    fn test_unscanned_resume_has_no_summary() {
+        assert_eq!(summarize(None, None, today()), None);
+        assert_eq!(summarize(Some("not json"), None, today()), None);
+        assert_eq!(
+            summarize(Some(r#"{"scanned_at":"2026-01-01"}"#), None, today()),
+            None
+        );
+    }
+}
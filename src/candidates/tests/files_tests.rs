@@ -0,0 +1,131 @@
+// src/candidates/tests/files_tests.rs
+
+#[cfg(test)]
+mod tests {
+    use crate::test_support::TestApp;
+    use axum::body::Body;
+    use axum::http::{header, HeaderMap, Request, StatusCode};
+    use tower::ServiceExt;
+
+    const CONTENT: &[u8] = b"%PDF-1.4 0123456789";
+
+    /// A candidate with one resume stored on local disk
+    async fn setup(app: &TestApp) -> String {
+        let candidate_id = app
+            .create_user("files@test.example.com", "Frankie Files")
+            .await;
+        sqlx::query(
+            "INSERT INTO resumes (id, user_id, filename) VALUES ('R_FILES', ?, 'R_FILES.pdf')",
+        )
+        .bind(&candidate_id)
+        .execute(&app.state.db)
+        .await
+        .unwrap();
+        std::fs::write(app.state.resumes_dir.join("R_FILES.pdf"), CONTENT).unwrap();
+        app.token_for(&candidate_id).await
+    }
+
+    /// GET with raw headers and body, for checking partial responses
+    async fn fetch(
+        app: &TestApp,
+        uri: &str,
+        token: Option<&str>,
+        range: Option<&str>,
+    ) -> (StatusCode, HeaderMap, Vec<u8>) {
+        let mut builder = Request::builder().uri(uri);
+        if let Some(token) = token {
+            builder = builder.header(header::AUTHORIZATION, format!("Bearer {}", token));
+        }
+        if let Some(range) = range {
+            builder = builder.header(header::RANGE, range);
+        }
+        let response = app
+            .router
+            .clone()
+            .oneshot(builder.body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let status = response.status();
+        let headers = response.headers().clone();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, headers, bytes.to_vec())
+    }
+
+    #[tokio::test]
+    async fn test_local_resume_supports_range_requests() {
+        let app = TestApp::new().await;
+        let token = setup(&app).await;
+
+        let (status, headers, body) =
+            fetch(&app, "/uploads/resumes/R_FILES.pdf", Some(&token), None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body, CONTENT);
+        assert_eq!(headers[header::CONTENT_TYPE], "application/pdf");
+        assert_eq!(headers[header::CACHE_CONTROL], "private, no-store");
+        assert_eq!(headers[header::ACCEPT_RANGES], "bytes");
+
+        let (status, headers, body) = fetch(
+            &app,
+            "/uploads/resumes/R_FILES.pdf",
+            Some(&token),
+            Some("bytes=9-12"),
+        )
+        .await;
+        assert_eq!(status, StatusCode::PARTIAL_CONTENT);
+        assert_eq!(body, b"0123");
+        assert_eq!(
+            headers[header::CONTENT_RANGE],
+            format!("bytes 9-12/{}", CONTENT.len()).as_str()
+        );
+
+        let other = app
+            .token_for(&app.create_user("other@test.example.com", "Other").await)
+            .await;
+        // Someone else's file is indistinguishable from a missing one
+        let (status, _, _) = fetch(&app, "/uploads/resumes/R_FILES.pdf", Some(&other), None).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        let (status, _, _) = fetch(&app, "/uploads/resumes/missing.pdf", Some(&token), None).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        // The file stays on disk after a soft delete, but the record no longer serves it
+        sqlx::query("UPDATE resumes SET deleted_at = datetime('now') WHERE id = 'R_FILES'")
+            .execute(&app.state.db)
+            .await
+            .unwrap();
+        let (status, _, _) = fetch(&app, "/uploads/resumes/R_FILES.pdf", Some(&token), None).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_signed_link_serves_local_file_ranges() {
+        let app = TestApp::new().await;
+        let token = setup(&app).await;
+
+        let link = app
+            .get("/api/files/resume/R_FILES/download-url", &token)
+            .await;
+        assert!(link.status.is_success(), "{:?}", link.body);
+        let url = link.body["url"].as_str().unwrap().to_string();
+        let path = url.find("/api/files/").map(|i| &url[i..]).unwrap();
+
+        let (status, headers, body) = fetch(&app, path, None, Some("bytes=0-3")).await;
+        assert_eq!(status, StatusCode::PARTIAL_CONTENT);
+        assert_eq!(body, b"%PDF");
+        assert_eq!(headers[header::CONTENT_TYPE], "application/pdf");
+        assert!(headers[header::CONTENT_DISPOSITION]
+            .to_str()
+            .unwrap()
+            .starts_with("inline; filename="));
+
+        let (status, _, body) = fetch(&app, path, None, None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body, CONTENT);
+
+        let (status, _, _) =
+            fetch(&app, "/api/files/resume/R_FILES?token=forged", None, None).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+    }
+}
@@ -0,0 +1,576 @@
+// src/candidates/tests/flow_tests.rs
+
+#[cfg(test)]
+mod tests {
+    use crate::services::calendar_retries;
+    use crate::test_support::TestApp;
+    use serde_json::json;
+    use std::sync::atomic::Ordering;
+
+    #[tokio::test]
+    async fn test_apply_advance_interview_offer() {
+        let app = TestApp::new().await;
+        let admin = app.token_for(&app.create_admin().await).await;
+        let candidate_id = app
+            .create_user("candidate@test.example.com", "Casey Candidate")
+            .await;
+        let candidate = app.token_for(&candidate_id).await;
+        let job_id = app.create_job("Backend Engineer").await;
+
+        let applied = app
+            .post("/api/applications", &candidate, json!({ "job_id": job_id }))
+            .await;
+        assert!(applied.status.is_success(), "{:?}", applied.body);
+        assert_eq!(applied.body["status"], "submitted");
+        let application_id = applied.body["id"].as_str().unwrap().to_string();
+
+        let advance = format!("/api/admin/applications/{}/advance-stage", application_id);
+        let denied = app.post(&advance, &candidate, json!({})).await;
+        assert_eq!(denied.status, 403);
+        for expected in ["reviewed", "shortlisted"] {
+            let advanced = app.post(&advance, &admin, json!({})).await;
+            assert!(advanced.status.is_success(), "{:?}", advanced.body);
+            assert_eq!(advanced.body["status"], expected);
+        }
+
+        let scheduled = app
+            .post(
+                "/api/admin/interviews/schedule",
+                &admin,
+                json!({
+                    "application_id": application_id,
+                    "scheduled_date": "2030-03-04T10:00:00Z",
+                    "duration_minutes": 45,
+                    "interview_type": "video",
+                    "panel_members": [{ "email": "panel@test.example.com", "name": "Pat Panel" }],
+                    "create_google_meet": true,
+                }),
+            )
+            .await;
+        assert!(scheduled.status.is_success(), "{:?}", scheduled.body);
+        assert_eq!(
+            scheduled.body["google_meet_link"],
+            "https://meet.example.com/event-1"
+        );
+        let events = app.google.events.lock().unwrap().clone();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].attendees, vec!["panel@test.example.com"]);
+        assert!(!app.aws.emails_to("candidate@test.example.com").is_empty());
+        assert!(!app.aws.emails_to("panel@test.example.com").is_empty());
+
+        let status = format!("/api/applications/{}/status", application_id);
+        for next in ["interviewed", "offered"] {
+            let updated = app.patch(&status, &admin, json!({ "status": next })).await;
+            assert!(updated.status.is_success(), "{:?}", updated.body);
+        }
+
+        let viewed = app
+            .get(&format!("/api/applications/{}", application_id), &candidate)
+            .await;
+        assert!(viewed.status.is_success(), "{:?}", viewed.body);
+        assert_eq!(viewed.body["status"], "offered");
+
+        let history: Vec<String> = sqlx::query_scalar(
+            "SELECT status FROM application_status_history WHERE application_id = ? ORDER BY changed_at, rowid",
+        )
+        .bind(&application_id)
+        .fetch_all(&app.state.db)
+        .await
+        .unwrap();
+        assert_eq!(
+            history,
+            vec![
+                "submitted",
+                "reviewed",
+                "shortlisted",
+                "interview_scheduled",
+                "interviewed",
+                "offered"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_meet_link_retried_after_calendar_outage() {
+        let app = TestApp::new().await;
+        let admin = app.token_for(&app.create_admin().await).await;
+        let candidate_id = app
+            .create_user("candidate@test.example.com", "Casey Candidate")
+            .await;
+        let candidate = app.token_for(&candidate_id).await;
+        let job_id = app.create_job("Backend Engineer").await;
+        let applied = app
+            .post("/api/applications", &candidate, json!({ "job_id": job_id }))
+            .await;
+        let application_id = applied.body["id"].as_str().unwrap().to_string();
+
+        app.google.unavailable.store(true, Ordering::SeqCst);
+        let scheduled = app
+            .post(
+                "/api/admin/interviews/schedule",
+                &admin,
+                json!({
+                    "application_id": application_id,
+                    "scheduled_date": "2030-03-04T10:00:00Z",
+                    "duration_minutes": 45,
+                    "interview_type": "video",
+                    "panel_members": [{ "email": "panel@test.example.com", "name": "Pat Panel" }],
+                    "create_google_meet": true,
+                }),
+            )
+            .await;
+        assert!(scheduled.status.is_success(), "{:?}", scheduled.body);
+        assert!(scheduled.body["google_meet_link"].is_null());
+        let interview_id = scheduled.body["id"].as_str().unwrap().to_string();
+
+        // Still down when the retry comes due: it is pushed back
+        let make_due = "UPDATE calendar_retries SET next_attempt_at = '2000-01-01T00:00:00Z'";
+        sqlx::query(make_due).execute(&app.state.db).await.unwrap();
+        let attempted = calendar_retries::retry_due_operations(
+            &app.state.db,
+            app.google.clone(),
+            app.aws.as_ref(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(attempted, 1);
+        let (status, attempts): (String, i64) =
+            sqlx::query_as("SELECT status, attempts FROM calendar_retries WHERE interview_id = ?")
+                .bind(&interview_id)
+                .fetch_one(&app.state.db)
+                .await
+                .unwrap();
+        assert_eq!((status.as_str(), attempts), ("pending", 1));
+
+        app.google.unavailable.store(false, Ordering::SeqCst);
+        let emails_before = app.aws.emails_to("candidate@test.example.com").len();
+        sqlx::query(make_due).execute(&app.state.db).await.unwrap();
+        calendar_retries::retry_due_operations(&app.state.db, app.google.clone(), app.aws.as_ref())
+            .await
+            .unwrap();
+
+        let viewed = app
+            .get(&format!("/api/admin/interviews/{}", interview_id), &admin)
+            .await;
+        assert!(viewed.status.is_success(), "{:?}", viewed.body);
+        assert_eq!(
+            viewed.body["google_meet_link"],
+            "https://meet.example.com/event-1"
+        );
+        let status: String =
+            sqlx::query_scalar("SELECT status FROM calendar_retries WHERE interview_id = ?")
+                .bind(&interview_id)
+                .fetch_one(&app.state.db)
+                .await
+                .unwrap();
+        assert_eq!(status, "succeeded");
+        let candidate_emails = app.aws.emails_to("candidate@test.example.com");
+        assert!(candidate_emails.len() > emails_before);
+        assert!(candidate_emails
+            .last()
+            .unwrap()
+            .body
+            .contains("https://meet.example.com/event-1"));
+        assert!(!app.aws.emails_to("panel@test.example.com").is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_recommendations_follow_saved_jobs_and_dismissals() {
+        let app = TestApp::new().await;
+        let candidate_id = app
+            .create_user("candidate@test.example.com", "Casey Candidate")
+            .await;
+        let candidate = app.token_for(&candidate_id).await;
+        let saved_id = app.create_job("Rust Backend Engineer").await;
+        let similar_id = app.create_job("Backend Engineer").await;
+        let other_id = app.create_job("Backend Platform Engineer").await;
+
+        // Nothing known yet: newest openings
+        let fallback = app.get("/api/me/recommended-jobs", &candidate).await;
+        assert!(fallback.status.is_success(), "{:?}", fallback.body);
+        assert_eq!(fallback.body.as_array().unwrap().len(), 3);
+        assert_eq!(fallback.body[0]["score"], 0);
+
+        let saved = app
+            .post("/api/saved-jobs", &candidate, json!({ "job_id": saved_id }))
+            .await;
+        assert!(saved.status.is_success(), "{:?}", saved.body);
+
+        let recommended = app
+            .get("/api/me/recommended-jobs?limit=5", &candidate)
+            .await;
+        let jobs = recommended.body.as_array().unwrap();
+        assert_eq!(jobs.len(), 2);
+        assert!(jobs.iter().all(|j| j["job_id"] != saved_id.as_str()));
+        assert_eq!(jobs[0]["job_id"], similar_id.as_str());
+        assert!(jobs[0]["explanation"]
+            .as_str()
+            .unwrap()
+            .contains("Similar to jobs you saved or applied to"));
+
+        let invalid = app
+            .post(
+                &format!("/api/me/recommended-jobs/{}/dismiss", similar_id),
+                &candidate,
+                json!({ "reason": "boring" }),
+            )
+            .await;
+        assert_eq!(invalid.status.as_u16(), 400);
+        let dismissed = app
+            .post(
+                &format!("/api/me/recommended-jobs/{}/dismiss", similar_id),
+                &candidate,
+                json!({ "reason": "not_relevant" }),
+            )
+            .await;
+        assert_eq!(dismissed.status.as_u16(), 204, "{:?}", dismissed.body);
+
+        let recommended = app.get("/api/me/recommended-jobs", &candidate).await;
+        let jobs = recommended.body.as_array().unwrap();
+        assert_eq!(jobs.len(), 1);
+        assert_eq!(jobs[0]["job_id"], other_id.as_str());
+
+        let (reason, score): (String, i64) = sqlx::query_as(
+            "SELECT reason, score FROM job_recommendation_dismissals WHERE user_id = ? AND job_id = ?",
+        )
+        .bind(&candidate_id)
+        .bind(&similar_id)
+        .fetch_one(&app.state.db)
+        .await
+        .unwrap();
+        assert_eq!(reason, "not_relevant");
+        assert!(score > 0);
+    }
+
+    #[tokio::test]
+    async fn test_recently_viewed_and_tracking_opt_out() {
+        let app = TestApp::new().await;
+        let candidate_id = app
+            .create_user("candidate@test.example.com", "Casey Candidate")
+            .await;
+        let candidate = app.token_for(&candidate_id).await;
+        let first = app.create_job("Backend Engineer").await;
+        let second = app.create_job("Data Engineer").await;
+
+        let view = |job_id: &str| format!("/api/jobs/{}/view", job_id);
+        for job_id in [&first, &second, &first] {
+            let viewed = app.post(&view(job_id), &candidate, json!({})).await;
+            assert_eq!(viewed.status.as_u16(), 201, "{:?}", viewed.body);
+            // Views are timestamped to the second
+            sqlx::query("UPDATE job_views SET viewed_at = datetime(viewed_at, '-1 minute')")
+                .execute(&app.state.db)
+                .await
+                .unwrap();
+        }
+        // Anonymous views aren't anyone's history
+        app.request(
+            axum::http::Method::POST,
+            &view(&second),
+            None,
+            Some(json!({})),
+        )
+        .await;
+
+        let applied = app
+            .post("/api/applications", &candidate, json!({ "job_id": first }))
+            .await;
+        assert!(applied.status.is_success(), "{:?}", applied.body);
+
+        let recent = app.get("/api/me/recently-viewed", &candidate).await;
+        assert!(recent.status.is_success(), "{:?}", recent.body);
+        assert_eq!(recent.body["tracking_enabled"], true);
+        let jobs = recent.body["jobs"].as_array().unwrap();
+        assert_eq!(jobs.len(), 2);
+        assert_eq!(jobs[0]["job_id"], first.as_str());
+        assert_eq!(jobs[0]["view_count"], 2);
+        assert_eq!(jobs[0]["application_status"], "submitted");
+        assert_eq!(jobs[1]["view_count"], 1);
+        assert_eq!(
+            recent.body["continue_where_left_off"]["job_id"],
+            second.as_str()
+        );
+
+        let off = app
+            .request(
+                axum::http::Method::PUT,
+                "/api/me/recently-viewed/settings",
+                Some(&candidate),
+                Some(json!({ "tracking_enabled": false })),
+            )
+            .await;
+        assert!(off.status.is_success(), "{:?}", off.body);
+        app.post(&view(&second), &candidate, json!({})).await;
+
+        let recent = app.get("/api/me/recently-viewed", &candidate).await;
+        assert_eq!(recent.body["tracking_enabled"], false);
+        assert!(recent.body["jobs"].as_array().unwrap().is_empty());
+        let (linked, total): (i64, i64) =
+            sqlx::query_as("SELECT COUNT(user_id), COUNT(*) FROM job_views")
+                .fetch_one(&app.state.db)
+                .await
+                .unwrap();
+        assert_eq!((linked, total), (0, 5));
+    }
+
+    #[tokio::test]
+    async fn test_application_feed_follows_status_and_interviews() {
+        let app = TestApp::new().await;
+        let admin = app.token_for(&app.create_admin().await).await;
+        let candidate_id = app
+            .create_user("candidate@test.example.com", "Casey Candidate")
+            .await;
+        let candidate = app.token_for(&candidate_id).await;
+        let job_id = app.create_job("Backend Engineer").await;
+
+        let applied = app
+            .post("/api/applications", &candidate, json!({ "job_id": job_id }))
+            .await;
+        let application_id = applied.body["id"].as_str().unwrap().to_string();
+        let advance = format!("/api/admin/applications/{}/advance-stage", application_id);
+        for _ in ["reviewed", "shortlisted"] {
+            app.post(&advance, &admin, json!({})).await;
+        }
+        let scheduled = app
+            .post(
+                "/api/admin/interviews/schedule",
+                &admin,
+                json!({
+                    "application_id": application_id,
+                    "scheduled_date": "2030-03-04T10:00:00Z",
+                    "duration_minutes": 45,
+                    "interview_type": "video",
+                    "panel_members": [{ "email": "panel@test.example.com", "name": "Pat Panel" }],
+                    "create_google_meet": true,
+                }),
+            )
+            .await;
+        assert!(scheduled.status.is_success(), "{:?}", scheduled.body);
+
+        let status = app.get("/api/me/application-feed", &candidate).await;
+        assert_eq!(status.body["active"], false);
+        let links = app
+            .post("/api/me/application-feed", &candidate, json!({}))
+            .await;
+        assert!(links.status.is_success(), "{:?}", links.body);
+        let path = |url: &serde_json::Value| {
+            let url = url.as_str().unwrap();
+            url[url.find("/feeds/").unwrap()..].to_string()
+        };
+        let rss_path = path(&links.body["rss_url"]);
+        let ics_path = path(&links.body["ics_url"]);
+        assert!(links.body["webcal_url"]
+            .as_str()
+            .unwrap()
+            .starts_with("webcal://"));
+
+        let rss = app
+            .request(axum::http::Method::GET, &rss_path, None, None)
+            .await;
+        assert_eq!(rss.status.as_u16(), 200);
+        let rss = rss.body.as_str().unwrap().to_string();
+        assert!(rss.contains("<title>Backend Engineer: Application received</title>"));
+        assert!(rss.contains("<title>Backend Engineer: Resume Review</title>"));
+        assert!(rss.contains("<title>Backend Engineer: Interview Scheduled</title>"));
+        // Notes written by the hiring team stay internal
+        assert!(!rss.contains("Interview scheduled<"));
+
+        let ics = app
+            .request(axum::http::Method::GET, &ics_path, None, None)
+            .await;
+        assert_eq!(ics.status.as_u16(), 200);
+        let ics = ics.body.as_str().unwrap().replace("\r\n ", "");
+        assert!(ics.contains("SUMMARY:video interview for Backend Engineer"));
+        assert!(!ics.contains("Pat Panel"));
+
+        let status = app.get("/api/me/application-feed", &candidate).await;
+        assert_eq!(status.body["active"], true);
+        assert!(status.body["last_accessed_at"].is_string());
+
+        let revoked = app
+            .request(
+                axum::http::Method::DELETE,
+                "/api/me/application-feed",
+                Some(&candidate),
+                None,
+            )
+            .await;
+        assert_eq!(revoked.status.as_u16(), 204);
+        let gone = app
+            .request(axum::http::Method::GET, &rss_path, None, None)
+            .await;
+        assert_eq!(gone.status.as_u16(), 404);
+    }
+
+    #[tokio::test]
+    async fn test_failed_reapplication_keeps_previous_application() {
+        let app = TestApp::new().await;
+        let candidate_id = app
+            .create_user("reapply@test.example.com", "Riley Reapply")
+            .await;
+        let candidate = app.token_for(&candidate_id).await;
+        let job_id = app.create_job("Data Engineer").await;
+
+        let applied = app
+            .post("/api/applications", &candidate, json!({ "job_id": job_id }))
+            .await;
+        assert!(applied.status.is_success(), "{:?}", applied.body);
+        let first_id = applied.body["id"].as_str().unwrap().to_string();
+        sqlx::query("UPDATE applications SET status = 'withdrawn' WHERE id = ?")
+            .bind(&first_id)
+            .execute(&app.state.db)
+            .await
+            .unwrap();
+
+        let archived_at = |id: String| {
+            let db = app.state.db.clone();
+            async move {
+                sqlx::query_scalar::<_, Option<String>>(
+                    "SELECT archived_at FROM applications WHERE id = ?",
+                )
+                .bind(id)
+                .fetch_one(&db)
+                .await
+                .unwrap()
+            }
+        };
+
+        let rejected = app
+            .post(
+                "/api/applications",
+                &candidate,
+                json!({ "job_id": job_id, "resume_id": "R_missing" }),
+            )
+            .await;
+        assert_eq!(rejected.status, 400, "{:?}", rejected.body);
+        assert!(archived_at(first_id.clone()).await.is_none());
+
+        let reapplied = app
+            .post("/api/applications", &candidate, json!({ "job_id": job_id }))
+            .await;
+        assert!(reapplied.status.is_success(), "{:?}", reapplied.body);
+        assert_ne!(reapplied.body["id"], first_id.as_str());
+        assert!(archived_at(first_id).await.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_resume_versions_chain_and_attachment() {
+        let app = TestApp::new().await;
+        let admin = app.token_for(&app.create_admin().await).await;
+        let candidate_id = app
+            .create_user("versions@test.example.com", "Vic Versions")
+            .await;
+        let candidate = app.token_for(&candidate_id).await;
+        let first_job = app.create_job("Platform Engineer").await;
+        let second_job = app.create_job("Infrastructure Engineer").await;
+        let pdf: &[u8] = b"%PDF-1.4 resume";
+
+        let first = app
+            .post_multipart(
+                "/api/resumes",
+                &candidate,
+                &[("resume", Some("cv.pdf"), pdf)],
+            )
+            .await;
+        assert_eq!(first.status, 201, "{:?}", first.body);
+        assert_eq!(first.body["version"], 1);
+        let first_id = first.body["id"].as_str().unwrap().to_string();
+
+        let applied = app
+            .post(
+                "/api/applications",
+                &candidate,
+                json!({ "job_id": first_job, "resume_id": first_id }),
+            )
+            .await;
+        assert!(applied.status.is_success(), "{:?}", applied.body);
+
+        let second = app
+            .post_multipart(
+                "/api/resumes",
+                &candidate,
+                &[
+                    ("replaces", None, first_id.as_bytes()),
+                    ("resume", Some("cv-2.pdf"), pdf),
+                ],
+            )
+            .await;
+        assert_eq!(second.status, 201, "{:?}", second.body);
+        assert_eq!(second.body["version"], 2);
+        assert_eq!(second.body["supersedes_id"], first_id.as_str());
+        let second_id = second.body["id"].as_str().unwrap().to_string();
+
+        let (superseded_by, root): (Option<String>, Option<String>) = sqlx::query_as(
+            "SELECT (SELECT superseded_by FROM resumes WHERE id = ?), (SELECT root_resume_id FROM resumes WHERE id = ?)",
+        )
+        .bind(&first_id)
+        .bind(&second_id)
+        .fetch_one(&app.state.db)
+        .await
+        .unwrap();
+        assert_eq!(superseded_by.as_deref(), Some(second_id.as_str()));
+        assert_eq!(root.as_deref(), Some(first_id.as_str()));
+
+        // Only the head of a chain can be replaced
+        let stale = app
+            .post_multipart(
+                "/api/resumes",
+                &candidate,
+                &[
+                    ("replaces", None, first_id.as_bytes()),
+                    ("resume", Some("cv-3.pdf"), pdf),
+                ],
+            )
+            .await;
+        assert_eq!(stale.status, 400, "{:?}", stale.body);
+
+        // Candidates pick from the latest versions, and new applications attach that one
+        let current = app.get("/api/user/resumes", &candidate).await;
+        let ids: Vec<&str> = current
+            .body
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|resume| resume["id"].as_str().unwrap())
+            .collect();
+        assert_eq!(ids, vec![second_id.as_str()]);
+        let applied = app
+            .post(
+                "/api/applications",
+                &candidate,
+                json!({ "job_id": second_job, "resume_id": second_id }),
+            )
+            .await;
+        assert!(applied.status.is_success(), "{:?}", applied.body);
+        assert_eq!(applied.body["resume_id"], second_id.as_str());
+
+        // The earlier application keeps the version it was sent with
+        let listed = app
+            .get(
+                &format!("/api/admin/jobs/{}/applications", first_job),
+                &admin,
+            )
+            .await;
+        assert!(listed.status.is_success(), "{:?}", listed.body);
+        assert_eq!(listed.body[0]["resume_id"], first_id.as_str());
+        assert_eq!(listed.body[0]["resume_version"], 1);
+        assert_eq!(listed.body[0]["latest_resume_id"], second_id.as_str());
+
+        let versions = app
+            .get(&format!("/api/resumes/{}/versions", first_id), &candidate)
+            .await;
+        assert!(versions.status.is_success(), "{:?}", versions.body);
+        let chain: Vec<(&str, i64)> = versions
+            .body
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|resume| {
+                (
+                    resume["id"].as_str().unwrap(),
+                    resume["version"].as_i64().unwrap(),
+                )
+            })
+            .collect();
+        assert_eq!(chain, vec![(second_id.as_str(), 2), (first_id.as_str(), 1)]);
+    }
+}
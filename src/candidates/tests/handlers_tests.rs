@@ -12,4 +12,20 @@ mod tests {
         // Minimal test to ensure module compiles
         assert!(true);
     }
+
+    #[test]
+    fn test_resume_archive_names_are_sanitized_and_unique() {
+        use crate::candidates::handlers::resume_exports::unique_archive_name;
+        use std::collections::HashSet;
+
+        let mut used = HashSet::new();
+        assert_eq!(unique_archive_name("Jane Doe", &mut used), "Jane_Doe.pdf");
+        assert_eq!(unique_archive_name("Jane  Doe", &mut used), "Jane_Doe_2.pdf");
+        assert_eq!(
+            unique_archive_name("../../etc/passwd", &mut used),
+            "etc_passwd.pdf"
+        );
+        assert_eq!(unique_archive_name("José Ñúñez", &mut used), "José_Ñúñez.pdf");
+        assert_eq!(unique_archive_name("   ", &mut used), "candidate.pdf");
+    }
 }
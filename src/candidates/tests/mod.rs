@@ -5,3 +5,27 @@ mod handlers_tests;
 
 #[cfg(test)]
 mod validators_tests;
+
+#[cfg(test)]
+mod requirements_match_tests;
+
+#[cfg(test)]
+mod education_match_tests;
+
+#[cfg(test)]
+mod experience_years_tests;
+
+#[cfg(test)]
+mod video_uploads_tests;
+
+#[cfg(test)]
+mod flow_tests;
+
+#[cfg(test)]
+mod contract_tests;
+
+#[cfg(test)]
+mod recommendations_tests;
+
+#[cfg(test)]
+mod files_tests;
@@ -0,0 +1,86 @@
+// src/candidates/tests/recommendations_tests.rs
+
+#[cfg(test)]
+mod tests {
+    use crate::candidates::recommendations::{
+        parse_skills, resume_skills, score_job, CandidateSignals, JobFacts,
+    };
+
+    fn job(title: &str) -> JobFacts {
+        JobFacts {
+            id: "J1".to_string(),
+            title: title.to_string(),
+            company: Some("Acme".to_string()),
+            location: Some("Berlin, Germany".to_string()),
+            job_type: Some("full-time".to_string()),
+            experience_level: Some("senior".to_string()),
+            requirements: Some(r#"["Rust", "PostgreSQL"]"#.to_string()),
+            description: Some("Build services with Docker".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_skill_sources() {
+        assert_eq!(
+            parse_skills(Some(r#"["Rust", 3, "SQL"]"#)),
+            vec!["Rust".to_string(), "SQL".to_string()]
+        );
+        assert!(parse_skills(Some("not json")).is_empty());
+        assert_eq!(
+            resume_skills(Some(r#"{"extracted_data": {"skills": ["Docker"]}}"#)),
+            vec!["Docker".to_string()]
+        );
+        assert_eq!(
+            resume_skills(Some(r#"{"skills": ["Go"]}"#)),
+            vec!["Go".to_string()]
+        );
+        assert!(resume_skills(None).is_empty());
+    }
+
+    #[test]
+    fn test_no_signals_scores_zero() {
+        let fit = score_job(&CandidateSignals::default(), &job("Backend Engineer"));
+        assert_eq!(fit.score, 0);
+        assert!(fit.reasons.is_empty());
+    }
+
+    #[test]
+    fn test_skills_match_with_explanation() {
+        let mut signals = CandidateSignals::default();
+        signals.add_skills(vec![
+            "Rust".to_string(),
+            "rust".to_string(),
+            "Docker".to_string(),
+            "Kotlin".to_string(),
+        ]);
+        signals.add_location(Some("berlin"));
+
+        let fit = score_job(&signals, &job("Backend Engineer"));
+        assert_eq!(fit.score, 12 * 2 + 10);
+        assert_eq!(
+            fit.explanation(),
+            "Matches your skills: Rust, Docker; In Berlin, Germany"
+        );
+    }
+
+    #[test]
+    fn test_learns_from_saved_and_applied_jobs() {
+        let mut signals = CandidateSignals::default();
+        let mut history = job("Senior Backend Engineer");
+        history.location = None;
+        signals.learn_from(&history);
+
+        let fit = score_job(&signals, &job("Backend Engineer"));
+        // Every title word shared, same type, level and company
+        assert_eq!(fit.score, 20 + 10 + 7 + 5);
+        assert_eq!(fit.reasons.len(), 4);
+
+        let unrelated = JobFacts {
+            company: None,
+            job_type: None,
+            experience_level: None,
+            ..job("Sales Manager")
+        };
+        assert_eq!(score_job(&signals, &unrelated).score, 0);
+    }
+}
@@ -0,0 +1,69 @@
+// src/candidates/tests/requirements_match_tests.rs
+
+#[cfg(test)]
+mod tests {
+    use crate::candidates::requirements_match::{
+        compare_requirements, parse_requirements, RequirementStatus,
+    };
+    use serde_json::json;
+
+    fn parsed_resume() -> serde_json::Value {
+        json!({
+            "summary": "Backend engineer focused on distributed systems",
+            "skills": ["Rust", "PostgreSQL", "Docker"],
+            "experience": [{
+                "title": "Senior Engineer",
+                "company": "Acme",
+                "description": "Built Rust microservices deployed on Kubernetes"
+            }],
+            "education": [{
+                "degree": "BSc",
+                "field_of_study": "Computer Science",
+                "institution": "State University"
+            }],
+            "certifications": []
+        })
+    }
+
+    #[test]
+    fn test_parse_requirements_accepts_json_and_lines() {
+        assert_eq!(
+            parse_requirements(Some(r#"["Rust", " SQL "]"#)),
+            vec!["Rust".to_string(), "SQL".to_string()]
+        );
+        assert_eq!(
+            parse_requirements(Some("- Rust\n\n* Docker")),
+            vec!["Rust".to_string(), "Docker".to_string()]
+        );
+        assert!(parse_requirements(None).is_empty());
+    }
+
+    #[test]
+    fn test_compare_requirements_classifies_each_requirement() {
+        let requirements = vec![
+            "5+ years of Rust experience".to_string(),
+            "Experience with Kubernetes and Terraform".to_string(),
+            "Fluent in Japanese".to_string(),
+        ];
+
+        let comparison = compare_requirements(&requirements, &parsed_resume());
+
+        let rust = &comparison.requirements[0];
+        assert_eq!(rust.status, RequirementStatus::Met);
+        assert!(rust.evidence.iter().any(|e| e.contains("Rust microservices")));
+
+        let infra = &comparison.requirements[1];
+        assert_eq!(infra.status, RequirementStatus::PartiallyMet);
+        assert_eq!(infra.missing_keywords, vec!["terraform".to_string()]);
+
+        let language = &comparison.requirements[2];
+        assert_eq!(language.status, RequirementStatus::Missing);
+        assert!(language.evidence.is_empty());
+
+        assert_eq!(
+            (comparison.met, comparison.partially_met, comparison.missing),
+            (1, 1, 1)
+        );
+        assert_eq!(comparison.match_score, 50);
+    }
+}
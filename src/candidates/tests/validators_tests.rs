@@ -13,6 +13,10 @@ mod tests {
             job_id: "550e8400-e29b-41d4-a716-446655440000".to_string(),
             resume_id: None,
             cover_letter: Some("Test cover letter".to_string()),
+            source: None,
+            consent: None,
+            work_authorization: Vec::new(),
+            screening_answers: Default::default(),
         };
 
         let result = validator.validate(&request);
@@ -27,6 +31,10 @@ mod tests {
             job_id: "invalid-uuid".to_string(),
             resume_id: None,
             cover_letter: None,
+            source: None,
+            consent: None,
+            work_authorization: Vec::new(),
+            screening_answers: Default::default(),
         };
 
         let result = validator.validate(&request);
@@ -41,6 +49,10 @@ mod tests {
             job_id: "550e8400-e29b-41d4-a716-446655440000".to_string(),
             resume_id: None,
             cover_letter: Some("a".repeat(5001)),
+            source: None,
+            consent: None,
+            work_authorization: Vec::new(),
+            screening_answers: Default::default(),
         };
 
         let result = validator.validate(&request);
@@ -65,4 +77,63 @@ mod tests {
         let result = validate_stage_transition("Offer Extended", "Hired");
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn test_swap_resume_validator() {
+        let validator = ApplicationValidator;
+
+        let valid = SwapApplicationResumeRequest {
+            resume_id: "R_8MWQT2".to_string(),
+        };
+        assert!(validator.validate(&valid).is_valid);
+
+        let empty = SwapApplicationResumeRequest {
+            resume_id: "  ".to_string(),
+        };
+        assert!(!validator.validate(&empty).is_valid);
+    }
+
+    #[test]
+    fn test_withdrawal_reason_validation() {
+        let validator = ApplicationValidator;
+        let request = |status: &str, reason: Option<&str>, details: Option<&str>| {
+            UpdateApplicationStatusRequest {
+                status: status.to_string(),
+                notes: None,
+                withdrawal_reason: reason.map(str::to_string),
+                withdrawal_details: details.map(str::to_string),
+                offer_expires_at: None,
+            }
+        };
+
+        assert!(validator
+            .validate(&request("withdrawn", Some("accepted_elsewhere"), Some("Took another offer")))
+            .is_valid);
+        assert!(validator.validate(&request("withdrawn", None, None)).is_valid);
+        assert!(!validator.validate(&request("withdrawn", Some("bored"), None)).is_valid);
+        assert!(!validator.validate(&request("rejected", Some("salary"), None)).is_valid);
+        assert!(!validator.validate(&request("withdrawn", None, Some("details"))).is_valid);
+    }
+
+    #[test]
+    fn test_reapplication_policy() {
+        let closed = chrono::NaiveDate::from_ymd_opt(2024, 1, 1)
+            .unwrap()
+            .and_hms_opt(12, 0, 0)
+            .unwrap();
+        let days_later = |days| closed + chrono::Duration::days(days);
+
+        // Active and hired applications can never be re-submitted
+        assert!(evaluate_reapplication("submitted", closed, 0, days_later(400)).is_err());
+        assert!(evaluate_reapplication("hired", closed, 0, days_later(400)).is_err());
+
+        // Rejection cooldown reports the eligible date
+        let err = evaluate_reapplication("rejected", closed, 180, days_later(30)).unwrap_err();
+        assert!(err.contains("2024-06-29"), "unexpected message: {}", err);
+        assert!(evaluate_reapplication("rejected", closed, 180, days_later(180)).is_ok());
+
+        // Withdrawals with no cooldown may re-apply immediately; negative blocks forever
+        assert!(evaluate_reapplication("withdrawn", closed, 0, closed).is_ok());
+        assert!(evaluate_reapplication("withdrawn", closed, -1, days_later(3650)).is_err());
+    }
 }
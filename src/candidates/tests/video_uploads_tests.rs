@@ -0,0 +1,60 @@
+// src/candidates/tests/video_uploads_tests.rs
+
+#[cfg(test)]
+mod tests {
+    use crate::candidates::handlers::video_uploads::{
+        check_chunk, validate_upload, CHUNK_SIZE, MAX_RESUMABLE_VIDEO_SIZE,
+    };
+    use crate::candidates::models::UploadSession;
+    use crate::common::ApiError;
+
+    fn session(received_bytes: i64, total_size: i64) -> UploadSession {
+        UploadSession {
+            id: "US_TEST".to_string(),
+            user_id: "user-1".to_string(),
+            filename: "intro.mp4".to_string(),
+            mime_type: "video/mp4".to_string(),
+            total_size,
+            received_bytes,
+            status: "uploading".to_string(),
+            video_id: None,
+            expires_at: "2026-01-02 00:00:00".to_string(),
+            created_at: None,
+            updated_at: None,
+        }
+    }
+
+    #[test]
+    fn test_validate_upload_limits() {
+        assert!(validate_upload("intro.mp4", "video/mp4", 150 * 1024 * 1024).is_ok());
+        assert!(validate_upload("intro.mp4", "video/mp4", MAX_RESUMABLE_VIDEO_SIZE + 1).is_err());
+        assert!(validate_upload("intro.mp4", "video/mp4", 0).is_err());
+        assert!(validate_upload("intro.mkv", "video/x-matroska", 1024).is_err());
+        assert!(validate_upload("  ", "video/mp4", 1024).is_err());
+    }
+
+    #[test]
+    fn test_chunk_must_start_at_current_offset() {
+        let upload = session(CHUNK_SIZE, 3 * CHUNK_SIZE);
+        assert!(check_chunk(&upload, CHUNK_SIZE, CHUNK_SIZE).is_ok());
+        assert!(matches!(
+            check_chunk(&upload, 0, CHUNK_SIZE),
+            Err(ApiError::Coded(..))
+        ));
+        assert!(matches!(
+            check_chunk(&upload, 2 * CHUNK_SIZE, CHUNK_SIZE),
+            Err(ApiError::Coded(..))
+        ));
+    }
+
+    #[test]
+    fn test_chunk_size_and_bounds() {
+        let upload = session(0, 2 * CHUNK_SIZE);
+        assert!(check_chunk(&upload, 0, 0).is_err());
+        assert!(check_chunk(&upload, 0, CHUNK_SIZE + 1).is_err());
+
+        let last = session(CHUNK_SIZE, CHUNK_SIZE + 10);
+        assert!(check_chunk(&last, CHUNK_SIZE, 10).is_ok());
+        assert!(check_chunk(&last, CHUNK_SIZE, 11).is_err());
+    }
+}
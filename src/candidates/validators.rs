@@ -1,8 +1,8 @@
 // src/candidates/validators.rs
 
 use super::models::*;
-use crate::common::{ValidationResult, Validator};
-use chrono::NaiveDate;
+use crate::common::{validate_fields, ValidationResult, Validator};
+use chrono::{Duration, NaiveDate, NaiveDateTime};
 use std::collections::HashSet;
 
 // ============================================================================
@@ -68,6 +68,66 @@ impl Validator<UpdateApplicationStatusRequest> for ApplicationValidator {
             }
         }
 
+        if let Some(reason) = &data.withdrawal_reason {
+            if data.status != "withdrawn" {
+                result.add_error(
+                    "withdrawal_reason",
+                    "Withdrawal reason is only allowed when withdrawing",
+                );
+            } else if !WITHDRAWAL_REASONS.contains(&reason.as_str()) {
+                result.add_error("withdrawal_reason", "Invalid withdrawal reason");
+            }
+        }
+
+        if let Some(details) = &data.withdrawal_details {
+            if data.withdrawal_reason.is_none() {
+                result.add_error(
+                    "withdrawal_details",
+                    "Withdrawal details require a withdrawal reason",
+                );
+            } else if details.len() > 1000 {
+                result.add_error(
+                    "withdrawal_details",
+                    "Withdrawal details must be less than 1000 characters",
+                );
+            }
+        }
+
+        if let Some(expires_at) = &data.offer_expires_at {
+            if data.status != "offered" {
+                result.add_error(
+                    "offer_expires_at",
+                    "An offer deadline is only allowed when making an offer",
+                );
+            } else {
+                match crate::common::timezone::parse_stored(expires_at) {
+                    Some(at) if at > chrono::Utc::now() => {}
+                    Some(_) => result.add_error(
+                        "offer_expires_at",
+                        "Offer deadline must be in the future",
+                    ),
+                    None => result.add_error(
+                        "offer_expires_at",
+                        "Offer deadline must be an RFC3339 timestamp",
+                    ),
+                }
+            }
+        }
+
+        result
+    }
+}
+
+impl Validator<SwapApplicationResumeRequest> for ApplicationValidator {
+    fn validate(&self, data: &SwapApplicationResumeRequest) -> ValidationResult {
+        let mut result = ValidationResult::new();
+
+        if data.resume_id.trim().is_empty() {
+            result.add_error("resume_id", "Resume ID is required");
+        } else if !is_valid_uuid(&data.resume_id) {
+            result.add_error("resume_id", "Resume ID must be a valid UUID");
+        }
+
         result
     }
 }
@@ -80,7 +140,8 @@ pub struct ResumeProcessingValidator;
 
 impl Validator<AdminResumeFilters> for ResumeProcessingValidator {
     fn validate(&self, data: &AdminResumeFilters) -> ValidationResult {
-        let mut result = ValidationResult::new();
+        // Score bounds and paging limits are declared on AdminResumeFilters
+        let mut result = validate_fields(data);
 
         if let Some(status) = &data.status {
             let valid_statuses = HashSet::from([
@@ -119,18 +180,6 @@ impl Validator<AdminResumeFilters> for ResumeProcessingValidator {
             }
         }
 
-        if let Some(score_min) = data.score_min {
-            if score_min < 0.0 || score_min > 100.0 {
-                result.add_error("score_min", "Minimum score must be between 0 and 100");
-            }
-        }
-
-        if let Some(score_max) = data.score_max {
-            if score_max < 0.0 || score_max > 100.0 {
-                result.add_error("score_max", "Maximum score must be between 0 and 100");
-            }
-        }
-
         if let (Some(score_min), Some(score_max)) = (data.score_min, data.score_max) {
             if score_min > score_max {
                 result.add_error(
@@ -140,18 +189,6 @@ impl Validator<AdminResumeFilters> for ResumeProcessingValidator {
             }
         }
 
-        if let Some(page) = data.page {
-            if page < 1 {
-                result.add_error("page", "Page must be greater than 0");
-            }
-        }
-
-        if let Some(limit) = data.limit {
-            if limit < 1 || limit > 100 {
-                result.add_error("limit", "Limit must be between 1 and 100");
-            }
-        }
-
         if let Some(sort_by) = &data.sort_by {
             let valid_sort_fields = HashSet::from([
                 "submitted_at",
@@ -294,7 +331,8 @@ pub struct InterviewValidator;
 
 impl Validator<CreateInterviewRequest> for InterviewValidator {
     fn validate(&self, data: &CreateInterviewRequest) -> ValidationResult {
-        let mut result = ValidationResult::new();
+        // Duration bounds and panel member emails are declared on the request
+        let mut result = validate_fields(data);
 
         if data.application_id.trim().is_empty() {
             result.add_error("application_id", "Application ID is required");
@@ -304,36 +342,10 @@ impl Validator<CreateInterviewRequest> for InterviewValidator {
             result.add_error("scheduled_date", "Scheduled date is required");
         }
 
-        if data.duration_minutes <= 0 {
-            result.add_error("duration_minutes", "Duration must be greater than 0");
-        }
-
-        if data.duration_minutes > 480 {
-            result.add_error(
-                "duration_minutes",
-                "Duration must not exceed 480 minutes (8 hours)",
-            );
-        }
-
         if data.interview_type.trim().is_empty() {
             result.add_error("interview_type", "Interview type is required");
         }
 
-        // Panel members are optional, but if provided, validate them
-        for (idx, member) in data.panel_members.iter().enumerate() {
-            if member.email.trim().is_empty() {
-                result.add_error(
-                    &format!("panel_members[{}].email", idx),
-                    "Panel member email is required",
-                );
-            } else if !member.email.contains('@') {
-                result.add_error(
-                    &format!("panel_members[{}].email", idx),
-                    "Panel member email must be valid",
-                );
-            }
-        }
-
         result
     }
 }
@@ -397,3 +409,34 @@ pub fn validate_stage_transition(current_stage: &str, new_stage: &str) -> Result
 
     Ok(())
 }
+
+// Re-application policy
+//
+// `closed_at` is when the previous application reached its final status. A negative
+// cooldown means re-applying is never allowed after that outcome.
+pub fn evaluate_reapplication(
+    previous_status: &str,
+    closed_at: NaiveDateTime,
+    cooldown_days: i64,
+    now: NaiveDateTime,
+) -> Result<(), String> {
+    match previous_status {
+        "rejected" | "withdrawn" => {}
+        "hired" => return Err("You have already been hired for this job".to_string()),
+        _ => return Err("You have already applied for this job".to_string()),
+    }
+
+    if cooldown_days < 0 {
+        return Err("Re-applying to this job is not allowed".to_string());
+    }
+
+    let eligible_at = closed_at + Duration::days(cooldown_days);
+    if now < eligible_at {
+        return Err(format!(
+            "You can re-apply for this job on or after {}",
+            eligible_at.format("%Y-%m-%d")
+        ));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,66 @@
+// Database connection pools
+//
+// Writes go through the primary pool. Heavy read-only traffic (public listings, analytics,
+// exports) goes through a separate read pool so long scans don't hold the connections
+// writes need. With SQLite the read pool opens its own read-only connections to the same
+// file, which WAL journaling lets run alongside the writer; DATABASE_READ_URL points it at
+// a replica instead.
+
+use sqlx::sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePoolOptions};
+use sqlx::SqlitePool;
+use std::str::FromStr;
+use tracing::info;
+
+/// In-memory databases exist per connection, so a second pool would see an empty database
+fn is_in_memory(database_url: &str) -> bool {
+    database_url.contains(":memory:") || database_url.contains("mode=memory")
+}
+
+/// Open the primary pool used for writes and reads that must see them
+pub async fn connect_write_pool(database_url: &str) -> Result<SqlitePool, sqlx::Error> {
+    let connect_options = SqliteConnectOptions::from_str(database_url)?
+        .create_if_missing(true)
+        .journal_mode(SqliteJournalMode::Wal);
+    SqlitePoolOptions::new().connect_with(connect_options).await
+}
+
+/// Open the read pool, from `read_url` (DATABASE_READ_URL) or else the primary database.
+/// Call after migrations, since read-only connections can't create the database file.
+pub async fn connect_read_pool(
+    database_url: &str,
+    read_url: Option<&str>,
+    max_connections: u32,
+    write_pool: &SqlitePool,
+) -> Result<SqlitePool, sqlx::Error> {
+    let url = read_url.unwrap_or(database_url);
+
+    if read_url.is_none() && is_in_memory(url) {
+        info!("In-memory database; heavy reads share the primary pool");
+        return Ok(write_pool.clone());
+    }
+
+    let connect_options = SqliteConnectOptions::from_str(url)?.read_only(true);
+    let pool = SqlitePoolOptions::new()
+        .max_connections(max_connections)
+        .connect_with(connect_options)
+        .await?;
+    info!(
+        replica = read_url.is_some(),
+        max_connections = max_connections,
+        "Read pool connected"
+    );
+    Ok(pool)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_in_memory() {
+        assert!(is_in_memory("sqlite::memory:"));
+        assert!(is_in_memory("sqlite://file:jobs?mode=memory&cache=shared"));
+        assert!(!is_in_memory("sqlite://job_api.db"));
+        assert!(!is_in_memory("sqlite:///data/job_api.db?mode=rwc"));
+    }
+}
@@ -58,6 +58,7 @@ impl DevModeConfig {
             provider: Some("dev".to_string()),
             provider_id: Some(user_id),
             created_at: Some(Utc::now().to_rfc3339()),
+            merged_into: None,
         }
     }
 }
@@ -94,6 +95,14 @@ pub fn parse_dev_mode_args() -> Option<bool> {
     None
 }
 
+/// Whether demo data should be seeded at startup, via `--seed` or DEV_SEED=true
+pub fn seed_requested() -> bool {
+    env::args().any(|arg| arg == "--seed")
+        || env::var("DEV_SEED")
+            .map(|v| v.to_lowercase() == "true")
+            .unwrap_or(false)
+}
+
 /// Override dev mode from CLI args
 pub fn apply_cli_override(mut config: DevModeConfig) -> DevModeConfig {
     if let Some(cli_dev_mode) = parse_dev_mode_args() {
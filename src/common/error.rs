@@ -1,11 +1,14 @@
 // Error handling types for the API
 
-use axum::{http::StatusCode, response::IntoResponse, Json};
+use axum::{response::IntoResponse, Json};
 use serde::Serialize;
 use std::fmt;
 use tracing::error;
 
-use super::validation::ValidationResult;
+use super::i18n::{current_locale, lookup, Locale};
+use super::request_id::current_request_id;
+use super::error_codes::ErrorCode;
+use super::validation::{ValidationError, ValidationResult};
 
 /// API error types
 #[derive(Debug)]
@@ -23,6 +26,10 @@ pub enum ApiError {
     ProcessingError(String),
     AttachmentError(String),
     AnalyticsError(String),
+    /// Failure with a specific catalog code, e.g. `ErrorCode::ApplicationDuplicate`
+    Coded(ErrorCode, String),
+    /// Field-level validation failures, reported individually in `details`
+    InvalidFields(Vec<ValidationError>),
 }
 
 impl fmt::Display for ApiError {
@@ -41,6 +48,10 @@ impl fmt::Display for ApiError {
             ApiError::ProcessingError(msg) => write!(f, "Processing Error: {}", msg),
             ApiError::AttachmentError(msg) => write!(f, "Attachment Error: {}", msg),
             ApiError::AnalyticsError(msg) => write!(f, "Analytics Error: {}", msg),
+            ApiError::Coded(code, msg) => write!(f, "{}: {}", code.as_str(), msg),
+            ApiError::InvalidFields(errors) => {
+                write!(f, "Validation Error: {}", join_field_errors(errors))
+            }
         }
     }
 }
@@ -50,54 +61,89 @@ impl fmt::Display for ApiError {
 pub struct ErrorResponse {
     pub error: String,
     pub code: String,
+    /// Per-field messages for validation failures
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub details: Option<Vec<ValidationError>>,
+    /// ID of the failed request, for matching a client report to server logs
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+}
+
+impl ApiError {
+    /// The catalog code this error is reported with
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            ApiError::Unauthorized(_) => ErrorCode::Unauthorized,
+            ApiError::Forbidden(_) => ErrorCode::Forbidden,
+            ApiError::BadRequest(_) => ErrorCode::BadRequest,
+            ApiError::NotFound(_) => ErrorCode::NotFound,
+            ApiError::InternalServer(_) => ErrorCode::InternalServerError,
+            ApiError::ServiceUnavailable(_) => ErrorCode::ServiceUnavailable,
+            ApiError::DatabaseError(_) => ErrorCode::DatabaseError,
+            ApiError::ValidationError(_) | ApiError::InvalidFields(_) => ErrorCode::ValidationError,
+            ApiError::BulkOperationError(_) => ErrorCode::BulkOperationError,
+            ApiError::ExportError(_) => ErrorCode::ExportError,
+            ApiError::ProcessingError(_) => ErrorCode::ProcessingError,
+            ApiError::AttachmentError(_) => ErrorCode::AttachmentError,
+            ApiError::AnalyticsError(_) => ErrorCode::AnalyticsError,
+            ApiError::Coded(code, _) => *code,
+        }
+    }
 }
 
 impl IntoResponse for ApiError {
     fn into_response(self) -> axum::response::Response {
-        let (status, error_message, code) = match self {
-            ApiError::Unauthorized(msg) => (StatusCode::UNAUTHORIZED, msg, "UNAUTHORIZED"),
-            ApiError::Forbidden(msg) => (StatusCode::FORBIDDEN, msg, "FORBIDDEN"),
-            ApiError::BadRequest(msg) => (StatusCode::BAD_REQUEST, msg, "BAD_REQUEST"),
-            ApiError::NotFound(msg) => (StatusCode::NOT_FOUND, msg, "NOT_FOUND"),
-            ApiError::InternalServer(msg) => (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                msg,
-                "INTERNAL_SERVER_ERROR",
-            ),
-            ApiError::ServiceUnavailable(msg) => (
-                StatusCode::SERVICE_UNAVAILABLE,
-                msg,
-                "SERVICE_UNAVAILABLE",
-            ),
+        let code = self.code();
+        let (error_message, details) = match self {
+            ApiError::Unauthorized(msg)
+            | ApiError::Forbidden(msg)
+            | ApiError::BadRequest(msg)
+            | ApiError::NotFound(msg)
+            | ApiError::InternalServer(msg)
+            | ApiError::ServiceUnavailable(msg)
+            | ApiError::ValidationError(msg)
+            | ApiError::BulkOperationError(msg)
+            | ApiError::ExportError(msg)
+            | ApiError::ProcessingError(msg)
+            | ApiError::AttachmentError(msg)
+            | ApiError::AnalyticsError(msg)
+            | ApiError::Coded(_, msg) => (msg, None),
             ApiError::DatabaseError(e) => {
                 error!(error = %e, "Database error occurred");
-                (
-                    StatusCode::INTERNAL_SERVER_ERROR,
-                    "Database operation failed".to_string(),
-                    "DATABASE_ERROR",
-                )
-            }
-            ApiError::ValidationError(msg) => (StatusCode::BAD_REQUEST, msg, "VALIDATION_ERROR"),
-            ApiError::BulkOperationError(msg) => {
-                (StatusCode::BAD_REQUEST, msg, "BULK_OPERATION_ERROR")
-            }
-            ApiError::ExportError(msg) => (StatusCode::INTERNAL_SERVER_ERROR, msg, "EXPORT_ERROR"),
-            ApiError::ProcessingError(msg) => {
-                (StatusCode::INTERNAL_SERVER_ERROR, msg, "PROCESSING_ERROR")
-            }
-            ApiError::AttachmentError(msg) => (StatusCode::BAD_REQUEST, msg, "ATTACHMENT_ERROR"),
-            ApiError::AnalyticsError(msg) => {
-                (StatusCode::INTERNAL_SERVER_ERROR, msg, "ANALYTICS_ERROR")
+                ("Database operation failed".to_string(), None)
             }
+            ApiError::InvalidFields(errors) => (join_field_errors(&errors), Some(errors)),
         };
 
         let error_response = ErrorResponse {
-            error: error_message,
-            code: code.to_string(),
+            error: localized_message(code, error_message),
+            code: code.as_str().to_string(),
+            details,
+            request_id: current_request_id().filter(|id| !id.is_empty()),
         };
 
-        (status, Json(error_response)).into_response()
+        (code.status(), Json(error_response)).into_response()
+    }
+}
+
+/// Swap the message for the catalog's translation when the request negotiated a
+/// non-English locale; English keeps the specific message
+pub fn localized_message(code: ErrorCode, message: String) -> String {
+    let locale = current_locale();
+    if locale == Locale::En {
+        return message;
     }
+    lookup(&format!("error.{}", code.as_str()), locale)
+        .map(str::to_string)
+        .unwrap_or(message)
+}
+
+fn join_field_errors(errors: &[ValidationError]) -> String {
+    errors
+        .iter()
+        .map(|e| format!("{}: {}", e.field, e.message))
+        .collect::<Vec<_>>()
+        .join(", ")
 }
 
 /// Helper function to convert ValidationResult to ApiError
@@ -108,12 +154,50 @@ impl From<ValidationResult> for ApiError {
                 "Validation result was valid but converted to error".to_string(),
             )
         } else {
-            let error_messages: Vec<String> = result
-                .errors
-                .iter()
-                .map(|e| format!("{}: {}", e.field, e.message))
-                .collect();
-            ApiError::ValidationError(error_messages.join(", "))
+            ApiError::InvalidFields(result.errors)
         }
     }
 }
+
+/// Encryption failures are server faults; the details only go to the log
+impl From<crate::services::encryption::EncryptionError> for ApiError {
+    fn from(e: crate::services::encryption::EncryptionError) -> Self {
+        error!(error = %e, "Encryption error");
+        ApiError::InternalServer("Failed to encrypt or decrypt data".to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::StatusCode;
+
+    #[tokio::test]
+    async fn test_validation_result_reports_field_details() {
+        let mut result = ValidationResult::new();
+        result.add_error("email", "Email is required");
+        result.add_error("title", "Title is too long");
+
+        let response = ApiError::from(result).into_response();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["code"], "VALIDATION_ERROR");
+        assert_eq!(
+            json["error"],
+            "email: Email is required, title: Title is too long"
+        );
+        assert_eq!(json["details"][1]["field"], "title");
+        assert_eq!(json["details"][1]["message"], "Title is too long");
+    }
+
+    #[test]
+    fn test_coded_errors_keep_their_code() {
+        let err = ApiError::Coded(ErrorCode::ResumeLimitReached, "Too many".to_string());
+        assert_eq!(err.code().as_str(), "RESUME_LIMIT_REACHED");
+        assert_eq!(err.into_response().status(), StatusCode::BAD_REQUEST);
+    }
+}
@@ -0,0 +1,318 @@
+// src/common/error_codes.rs
+//! Error catalog: stable, machine-readable codes returned in every error body
+//!
+//! Clients should branch on `code`, never on the human-readable `error` message. Codes are
+//! part of the API contract: add new ones freely, but never rename or remove an existing one.
+
+use axum::http::StatusCode;
+use serde_json::{json, Value};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorCode {
+    Unauthorized,
+    Forbidden,
+    BadRequest,
+    NotFound,
+    InternalServerError,
+    ServiceUnavailable,
+    DatabaseError,
+    ValidationError,
+    BulkOperationError,
+    ExportError,
+    ProcessingError,
+    AttachmentError,
+    AnalyticsError,
+    RateLimitExceeded,
+    ApplicationDuplicate,
+    ResumeLimitReached,
+    StageTransitionInvalid,
+    ConsentRequired,
+    LegalHoldActive,
+    JobDuplicate,
+    JobLintFailed,
+    AccountExists,
+    UploadOffsetMismatch,
+    StorageQuotaExceeded,
+    DraftConflict,
+    VerificationCodeInvalid,
+    ApplicationDeadlinePassed,
+    ApplicationCapReached,
+    ResumeSwapConflict,
+    PayloadTooLarge,
+    UnsupportedMediaType,
+    ServerBusy,
+}
+
+impl ErrorCode {
+    /// Every code, in catalog order
+    pub const ALL: &'static [ErrorCode] = &[
+        ErrorCode::Unauthorized,
+        ErrorCode::Forbidden,
+        ErrorCode::BadRequest,
+        ErrorCode::NotFound,
+        ErrorCode::InternalServerError,
+        ErrorCode::ServiceUnavailable,
+        ErrorCode::DatabaseError,
+        ErrorCode::ValidationError,
+        ErrorCode::BulkOperationError,
+        ErrorCode::ExportError,
+        ErrorCode::ProcessingError,
+        ErrorCode::AttachmentError,
+        ErrorCode::AnalyticsError,
+        ErrorCode::RateLimitExceeded,
+        ErrorCode::ApplicationDuplicate,
+        ErrorCode::ResumeLimitReached,
+        ErrorCode::StageTransitionInvalid,
+        ErrorCode::ConsentRequired,
+        ErrorCode::LegalHoldActive,
+        ErrorCode::JobDuplicate,
+        ErrorCode::JobLintFailed,
+        ErrorCode::AccountExists,
+        ErrorCode::UploadOffsetMismatch,
+        ErrorCode::StorageQuotaExceeded,
+        ErrorCode::DraftConflict,
+        ErrorCode::VerificationCodeInvalid,
+        ErrorCode::ApplicationDeadlinePassed,
+        ErrorCode::ApplicationCapReached,
+        ErrorCode::ResumeSwapConflict,
+        ErrorCode::PayloadTooLarge,
+        ErrorCode::UnsupportedMediaType,
+        ErrorCode::ServerBusy,
+    ];
+
+    /// The wire value of the code
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ErrorCode::Unauthorized => "UNAUTHORIZED",
+            ErrorCode::Forbidden => "FORBIDDEN",
+            ErrorCode::BadRequest => "BAD_REQUEST",
+            ErrorCode::NotFound => "NOT_FOUND",
+            ErrorCode::InternalServerError => "INTERNAL_SERVER_ERROR",
+            ErrorCode::ServiceUnavailable => "SERVICE_UNAVAILABLE",
+            ErrorCode::DatabaseError => "DATABASE_ERROR",
+            ErrorCode::ValidationError => "VALIDATION_ERROR",
+            ErrorCode::BulkOperationError => "BULK_OPERATION_ERROR",
+            ErrorCode::ExportError => "EXPORT_ERROR",
+            ErrorCode::ProcessingError => "PROCESSING_ERROR",
+            ErrorCode::AttachmentError => "ATTACHMENT_ERROR",
+            ErrorCode::AnalyticsError => "ANALYTICS_ERROR",
+            ErrorCode::RateLimitExceeded => "RATE_LIMIT_EXCEEDED",
+            ErrorCode::ApplicationDuplicate => "APPLICATION_DUPLICATE",
+            ErrorCode::ResumeLimitReached => "RESUME_LIMIT_REACHED",
+            ErrorCode::StageTransitionInvalid => "STAGE_TRANSITION_INVALID",
+            ErrorCode::ConsentRequired => "CONSENT_REQUIRED",
+            ErrorCode::LegalHoldActive => "LEGAL_HOLD_ACTIVE",
+            ErrorCode::JobDuplicate => "JOB_DUPLICATE",
+            ErrorCode::JobLintFailed => "JOB_LINT_FAILED",
+            ErrorCode::AccountExists => "ACCOUNT_EXISTS",
+            ErrorCode::UploadOffsetMismatch => "UPLOAD_OFFSET_MISMATCH",
+            ErrorCode::StorageQuotaExceeded => "STORAGE_QUOTA_EXCEEDED",
+            ErrorCode::DraftConflict => "DRAFT_CONFLICT",
+            ErrorCode::VerificationCodeInvalid => "VERIFICATION_CODE_INVALID",
+            ErrorCode::ApplicationDeadlinePassed => "APPLICATION_DEADLINE_PASSED",
+            ErrorCode::ApplicationCapReached => "APPLICATION_CAP_REACHED",
+            ErrorCode::ResumeSwapConflict => "RESUME_SWAP_CONFLICT",
+            ErrorCode::PayloadTooLarge => "PAYLOAD_TOO_LARGE",
+            ErrorCode::UnsupportedMediaType => "UNSUPPORTED_MEDIA_TYPE",
+            ErrorCode::ServerBusy => "SERVER_BUSY",
+        }
+    }
+
+    /// HTTP status the code is returned with
+    pub fn status(&self) -> StatusCode {
+        match self {
+            ErrorCode::Unauthorized => StatusCode::UNAUTHORIZED,
+            ErrorCode::Forbidden | ErrorCode::ConsentRequired => StatusCode::FORBIDDEN,
+            ErrorCode::NotFound => StatusCode::NOT_FOUND,
+            ErrorCode::ServiceUnavailable | ErrorCode::ServerBusy => {
+                StatusCode::SERVICE_UNAVAILABLE
+            }
+            ErrorCode::RateLimitExceeded => StatusCode::TOO_MANY_REQUESTS,
+            ErrorCode::StorageQuotaExceeded | ErrorCode::PayloadTooLarge => {
+                StatusCode::PAYLOAD_TOO_LARGE
+            }
+            ErrorCode::UnsupportedMediaType => StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            ErrorCode::LegalHoldActive
+            | ErrorCode::JobDuplicate
+            | ErrorCode::AccountExists
+            | ErrorCode::UploadOffsetMismatch
+            | ErrorCode::DraftConflict
+            | ErrorCode::ApplicationDeadlinePassed
+            | ErrorCode::ApplicationCapReached
+            | ErrorCode::ResumeSwapConflict => StatusCode::CONFLICT,
+            ErrorCode::InternalServerError
+            | ErrorCode::DatabaseError
+            | ErrorCode::ExportError
+            | ErrorCode::ProcessingError
+            | ErrorCode::AnalyticsError => StatusCode::INTERNAL_SERVER_ERROR,
+            ErrorCode::BadRequest
+            | ErrorCode::ValidationError
+            | ErrorCode::BulkOperationError
+            | ErrorCode::AttachmentError
+            | ErrorCode::ApplicationDuplicate
+            | ErrorCode::ResumeLimitReached
+            | ErrorCode::StageTransitionInvalid
+            | ErrorCode::JobLintFailed
+            | ErrorCode::VerificationCodeInvalid => StatusCode::BAD_REQUEST,
+        }
+    }
+
+    /// What the code means, for the published catalog
+    pub fn description(&self) -> &'static str {
+        match self {
+            ErrorCode::Unauthorized => "Missing, invalid or expired credentials",
+            ErrorCode::Forbidden => "Authenticated, but not allowed to perform this action",
+            ErrorCode::BadRequest => "The request could not be processed as sent",
+            ErrorCode::NotFound => "The requested resource does not exist",
+            ErrorCode::InternalServerError => "Unexpected server failure",
+            ErrorCode::ServiceUnavailable => "A required service is temporarily unavailable",
+            ErrorCode::DatabaseError => "A database operation failed",
+            ErrorCode::ValidationError => {
+                "One or more fields are invalid; see `details` for per-field messages when present"
+            }
+            ErrorCode::BulkOperationError => "A bulk operation could not be applied",
+            ErrorCode::ExportError => "Generating an export failed",
+            ErrorCode::ProcessingError => "Processing the request failed",
+            ErrorCode::AttachmentError => "An attachment was rejected",
+            ErrorCode::AnalyticsError => "Computing analytics failed",
+            ErrorCode::RateLimitExceeded => "Too many requests; retry after the advertised delay",
+            ErrorCode::ApplicationDuplicate => {
+                "The candidate already has an active application for this job"
+            }
+            ErrorCode::ResumeLimitReached => {
+                "The candidate has reached the maximum number of stored resumes"
+            }
+            ErrorCode::StageTransitionInvalid => {
+                "The application cannot move from its current stage to the requested one"
+            }
+            ErrorCode::ConsentRequired => {
+                "The current privacy policy and data processing consent must be accepted first"
+            }
+            ErrorCode::LegalHoldActive => {
+                "The data is under a legal hold and cannot be deleted until the hold is released"
+            }
+            ErrorCode::JobDuplicate => {
+                "The job looks like a duplicate of an active posting; resend with `allow_duplicate` to create it anyway"
+            }
+            ErrorCode::JobLintFailed => {
+                "The job description does not pass enough quality checks to be published"
+            }
+            ErrorCode::AccountExists => {
+                "Another account already uses this email; sign in with it to link the new sign-in"
+            }
+            ErrorCode::UploadOffsetMismatch => {
+                "The chunk does not start where the upload left off; fetch the session and resume from its offset"
+            }
+            ErrorCode::StorageQuotaExceeded => {
+                "The upload would take the account over its storage quota; delete files to free space"
+            }
+            ErrorCode::DraftConflict => {
+                "Another editor changed the same draft fields since your last save; reload the draft and reapply your edits"
+            }
+            ErrorCode::VerificationCodeInvalid => {
+                "The verification code is wrong, expired or used up; request a new one"
+            }
+            ErrorCode::ApplicationDeadlinePassed => {
+                "The job stopped accepting applications at its application deadline"
+            }
+            ErrorCode::ApplicationCapReached => {
+                "The job has received as many applications as it accepts"
+            }
+            ErrorCode::ResumeSwapConflict => {
+                "The application's resume or status changed while swapping; reload the application and try again"
+            }
+            ErrorCode::PayloadTooLarge => {
+                "The request body is larger than this endpoint accepts; see `limit_bytes` for the cap"
+            }
+            ErrorCode::UnsupportedMediaType => {
+                "An uploaded file's content, extension and declared type disagree, or the file type is not accepted"
+            }
+            ErrorCode::ServerBusy => {
+                "Too many requests to this kind of endpoint are already running; retry after the advertised delay"
+            }
+        }
+    }
+}
+
+/// OpenAPI 3 document describing the error body and the code catalog
+///
+/// Generated from `ErrorCode` so the published spec cannot drift from what the API returns.
+pub fn openapi_error_spec() -> Value {
+    let codes: Vec<&str> = ErrorCode::ALL.iter().map(|c| c.as_str()).collect();
+    let catalog: Vec<Value> = ErrorCode::ALL
+        .iter()
+        .map(|c| {
+            json!({
+                "code": c.as_str(),
+                "status": c.status().as_u16(),
+                "description": c.description(),
+            })
+        })
+        .collect();
+
+    json!({
+        "openapi": "3.0.3",
+        "info": {
+            "title": "Job API error catalog",
+            "version": env!("CARGO_PKG_VERSION"),
+        },
+        "paths": {},
+        "components": {
+            "schemas": {
+                "ErrorCode": {
+                    "type": "string",
+                    "enum": codes,
+                    "x-error-catalog": catalog,
+                },
+                "FieldError": {
+                    "type": "object",
+                    "required": ["field", "message"],
+                    "properties": {
+                        "field": { "type": "string" },
+                        "message": { "type": "string" },
+                    },
+                },
+                "ErrorResponse": {
+                    "type": "object",
+                    "required": ["error", "code"],
+                    "properties": {
+                        "error": {
+                            "type": "string",
+                            "description": "Human-readable message; not stable, do not parse",
+                        },
+                        "code": { "$ref": "#/components/schemas/ErrorCode" },
+                        "details": {
+                            "type": "array",
+                            "items": { "$ref": "#/components/schemas/FieldError" },
+                        },
+                        "request_id": { "type": "string" },
+                    },
+                },
+            },
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_codes_are_unique() {
+        let codes: HashSet<&str> = ErrorCode::ALL.iter().map(|c| c.as_str()).collect();
+        assert_eq!(codes.len(), ErrorCode::ALL.len());
+    }
+
+    #[test]
+    fn test_openapi_spec_lists_every_code() {
+        let spec = openapi_error_spec();
+        let listed = spec["components"]["schemas"]["ErrorCode"]["enum"]
+            .as_array()
+            .unwrap();
+        assert_eq!(listed.len(), ErrorCode::ALL.len());
+        for code in ErrorCode::ALL {
+            assert!(listed.contains(&Value::from(code.as_str())));
+        }
+    }
+}
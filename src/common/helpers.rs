@@ -1,7 +1,10 @@
-// Helper functions for safe logging and serialization
+// Helper functions for safe logging, serialization and parsing request fields
 
+use chrono::NaiveDate;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
+use super::ApiError;
+
 /// Masks email addresses for safe logging
 /// Prevents sensitive data exposure while preserving debugging utility
 ///
@@ -23,6 +26,16 @@ pub fn safe_email_log(email: &str) -> String {
     }
 }
 
+/// Parses an optional `YYYY-MM-DD` request field; a blank value counts as absent
+pub fn parse_date(value: Option<&str>, field: &str) -> Result<Option<NaiveDate>, ApiError> {
+    match value.map(str::trim).filter(|v| !v.is_empty()) {
+        Some(v) => NaiveDate::parse_from_str(v, "%Y-%m-%d").map(Some).map_err(|_| {
+            ApiError::ValidationError(format!("{} must be a date in YYYY-MM-DD format", field))
+        }),
+        None => Ok(None),
+    }
+}
+
 /// Masks tokens for safe logging
 /// Shows only first and last 4 characters
 ///
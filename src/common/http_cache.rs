@@ -0,0 +1,123 @@
+// HTTP caching for public, cacheable responses
+//
+// Responses carry an ETag of their body and a Cache-Control policy; a client (or CDN)
+// revalidating with a matching If-None-Match gets `304 Not Modified` without the body.
+// ETags are weak because the compression layer may re-encode the body on the way out.
+
+use axum::{
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+use crate::common::ApiError;
+
+/// Job listings change as jobs are published, so browsers revalidate soon; shared caches may
+/// serve a stale copy while they do
+pub const LISTING_CACHE_CONTROL: &str = "public, max-age=60, stale-while-revalidate=300";
+
+/// Company profiles and single jobs change rarely
+pub const PROFILE_CACHE_CONTROL: &str = "public, max-age=300, stale-while-revalidate=3600";
+
+/// Uploaded files get a new name on every upload, so a URL's content never changes
+pub const IMMUTABLE_CACHE_CONTROL: &str = "public, max-age=31536000, immutable";
+
+/// Weak ETag of a response body
+pub fn etag(body: &[u8]) -> String {
+    let digest: String = Sha256::digest(body)[..16]
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect();
+    format!("W/\"{}\"", digest)
+}
+
+/// Whether the request's If-None-Match already names `etag`, compared weakly
+pub fn etag_matches(headers: &HeaderMap, etag: &str) -> bool {
+    let opaque = |tag: &str| tag.trim().trim_start_matches("W/").to_string();
+    headers
+        .get(header::IF_NONE_MATCH)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|tags| {
+            tags.split(',')
+                .any(|tag| tag.trim() == "*" || opaque(tag) == opaque(etag))
+        })
+}
+
+/// Serve `body` with an ETag and `cache_control`, or `304 Not Modified` if the client's
+/// copy is current
+pub fn cached(
+    headers: &HeaderMap,
+    content_type: &str,
+    body: Vec<u8>,
+    cache_control: &'static str,
+) -> Response {
+    let etag = etag(&body);
+    let mut response = if etag_matches(headers, &etag) {
+        StatusCode::NOT_MODIFIED.into_response()
+    } else {
+        let mut response = body.into_response();
+        if let Ok(content_type) = HeaderValue::from_str(content_type) {
+            response
+                .headers_mut()
+                .insert(header::CONTENT_TYPE, content_type);
+        }
+        response
+    };
+
+    let response_headers = response.headers_mut();
+    response_headers.insert(
+        header::CACHE_CONTROL,
+        HeaderValue::from_static(cache_control),
+    );
+    if let Ok(etag) = HeaderValue::from_str(&etag) {
+        response_headers.insert(header::ETAG, etag);
+    }
+    response
+}
+
+/// [`cached`] for a JSON body
+pub fn cached_json<T: Serialize>(
+    headers: &HeaderMap,
+    value: &T,
+    cache_control: &'static str,
+) -> Result<Response, ApiError> {
+    let body = serde_json::to_vec(value)
+        .map_err(|e| ApiError::InternalServer(format!("Failed to serialize response: {}", e)))?;
+    Ok(cached(headers, "application/json", body, cache_control))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cached_answers_not_modified_for_matching_etag() {
+        let body = br#"{"jobs":[]}"#.to_vec();
+        let tag = etag(&body);
+
+        let fresh = cached(
+            &HeaderMap::new(),
+            "application/json",
+            body.clone(),
+            LISTING_CACHE_CONTROL,
+        );
+        assert_eq!(fresh.status(), StatusCode::OK);
+        assert_eq!(fresh.headers()[header::ETAG], tag.as_str());
+        assert_eq!(
+            fresh.headers()[header::CACHE_CONTROL],
+            LISTING_CACHE_CONTROL
+        );
+
+        let mut headers = HeaderMap::new();
+        // Strong and weak forms of the same tag both match
+        let strong = tag.trim_start_matches("W/").to_string();
+        headers.insert(
+            header::IF_NONE_MATCH,
+            format!("\"other\", {}", strong).parse().unwrap(),
+        );
+        let revalidated = cached(&headers, "application/json", body, LISTING_CACHE_CONTROL);
+        assert_eq!(revalidated.status(), StatusCode::NOT_MODIFIED);
+        assert_eq!(revalidated.headers()[header::ETAG], tag.as_str());
+    }
+}
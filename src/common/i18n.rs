@@ -0,0 +1,500 @@
+// src/common/i18n.rs
+//! Localization: a key/locale translation catalog, `Accept-Language` negotiation and the
+//! per-request locale used for user-facing messages.
+//!
+//! Emails use the recipient's stored preference (`users.locale`); API responses use the
+//! locale negotiated from the request's `Accept-Language` header. Missing translations
+//! fall back to English.
+
+use axum::{
+    extract::Request,
+    http::{
+        header::{ACCEPT_LANGUAGE, CONTENT_LANGUAGE, VARY},
+        HeaderValue,
+    },
+    middleware::Next,
+    response::Response,
+};
+use sqlx::SqlitePool;
+use std::future::Future;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Locale {
+    #[default]
+    En,
+    Es,
+    Fr,
+    De,
+}
+
+impl Locale {
+    /// Every supported locale, default first
+    pub const ALL: &'static [Locale] = &[Locale::En, Locale::Es, Locale::Fr, Locale::De];
+
+    /// The language tag stored on users and sent in `Content-Language`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Locale::En => "en",
+            Locale::Es => "es",
+            Locale::Fr => "fr",
+            Locale::De => "de",
+        }
+    }
+
+    /// Match a language tag on its primary subtag, so `fr-CA` resolves to French
+    pub fn parse(tag: &str) -> Option<Locale> {
+        let primary = tag.trim().split(['-', '_']).next()?.to_ascii_lowercase();
+        Locale::ALL
+            .iter()
+            .copied()
+            .find(|locale| locale.as_str() == primary)
+    }
+}
+
+/// Check that a user-supplied locale is one we have translations for
+pub fn validate_locale(tag: &str) -> Result<Locale, String> {
+    Locale::parse(tag).ok_or_else(|| {
+        let supported: Vec<&str> = Locale::ALL.iter().map(|l| l.as_str()).collect();
+        format!(
+            "Unsupported locale '{}'; use one of: {}",
+            tag,
+            supported.join(", ")
+        )
+    })
+}
+
+/// Pick the best supported locale for an `Accept-Language` header value
+///
+/// Ranges are tried in descending `q` order (ties keep header order); `*` and an absent or
+/// unusable header resolve to the default locale.
+pub fn negotiate(accept_language: &str) -> Locale {
+    let mut ranges: Vec<(&str, f32)> = accept_language
+        .split(',')
+        .filter_map(|part| {
+            let mut pieces = part.split(';');
+            let tag = pieces.next()?.trim();
+            if tag.is_empty() {
+                return None;
+            }
+            let quality = pieces
+                .find_map(|p| p.trim().strip_prefix("q="))
+                .and_then(|q| q.trim().parse::<f32>().ok())
+                .unwrap_or(1.0);
+            (quality > 0.0).then_some((tag, quality))
+        })
+        .collect();
+    ranges.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+    for (tag, _) in ranges {
+        if tag == "*" {
+            return Locale::default();
+        }
+        if let Some(locale) = Locale::parse(tag) {
+            return locale;
+        }
+    }
+    Locale::default()
+}
+
+/// Translation catalog: (key, locale, text). Placeholders are written `{name}`.
+const CATALOG: &[(&str, Locale, &str)] = &[
+    // ---- Shared email parts ----
+    ("email.greeting", Locale::En, "Hi {name},"),
+    ("email.greeting", Locale::Es, "Hola {name}:"),
+    ("email.greeting", Locale::Fr, "Bonjour {name},"),
+    ("email.greeting", Locale::De, "Hallo {name},"),
+    ("email.signoff", Locale::En, "Best,<br>{company} Team"),
+    ("email.signoff", Locale::Es, "Saludos,<br>Equipo de {company}"),
+    ("email.signoff", Locale::Fr, "Cordialement,<br>L'équipe {company}"),
+    ("email.signoff", Locale::De, "Viele Grüße<br>Ihr {company} Team"),
+    // ---- Application status emails ----
+    ("email.status.reviewed.subject", Locale::En, "Application Received - {job}"),
+    ("email.status.reviewed.subject", Locale::Es, "Solicitud recibida - {job}"),
+    ("email.status.reviewed.subject", Locale::Fr, "Candidature reçue - {job}"),
+    ("email.status.reviewed.subject", Locale::De, "Bewerbung erhalten - {job}"),
+    (
+        "email.status.reviewed.body",
+        Locale::En,
+        "<p>Thanks for applying for <strong>{job}</strong> at <strong>{company}</strong>. We've received your application and will review it shortly.</p>\n<p>We'll be in touch with next steps soon.</p>",
+    ),
+    (
+        "email.status.reviewed.body",
+        Locale::Es,
+        "<p>Gracias por postularte a <strong>{job}</strong> en <strong>{company}</strong>. Hemos recibido tu solicitud y la revisaremos en breve.</p>\n<p>Pronto te contaremos los próximos pasos.</p>",
+    ),
+    (
+        "email.status.reviewed.body",
+        Locale::Fr,
+        "<p>Merci d'avoir postulé au poste <strong>{job}</strong> chez <strong>{company}</strong>. Nous avons bien reçu votre candidature et l'examinerons prochainement.</p>\n<p>Nous reviendrons vers vous rapidement pour la suite.</p>",
+    ),
+    (
+        "email.status.reviewed.body",
+        Locale::De,
+        "<p>Vielen Dank für Ihre Bewerbung als <strong>{job}</strong> bei <strong>{company}</strong>. Wir haben Ihre Bewerbung erhalten und prüfen sie in Kürze.</p>\n<p>Wir melden uns bald mit den nächsten Schritten.</p>",
+    ),
+    ("email.status.shortlisted.subject", Locale::En, "You're Shortlisted! - {job}"),
+    ("email.status.shortlisted.subject", Locale::Es, "¡Estás preseleccionado! - {job}"),
+    ("email.status.shortlisted.subject", Locale::Fr, "Vous êtes présélectionné ! - {job}"),
+    ("email.status.shortlisted.subject", Locale::De, "Sie sind in der engeren Auswahl! - {job}"),
+    (
+        "email.status.shortlisted.body",
+        Locale::En,
+        "<p>Great news! You've been shortlisted for the <strong>{job}</strong> position at <strong>{company}</strong>.</p>\n<p>We'll contact you soon to discuss next steps.</p>",
+    ),
+    (
+        "email.status.shortlisted.body",
+        Locale::Es,
+        "<p>¡Buenas noticias! Has sido preseleccionado para el puesto de <strong>{job}</strong> en <strong>{company}</strong>.</p>\n<p>Nos pondremos en contacto contigo pronto para hablar de los próximos pasos.</p>",
+    ),
+    (
+        "email.status.shortlisted.body",
+        Locale::Fr,
+        "<p>Bonne nouvelle ! Vous avez été présélectionné pour le poste <strong>{job}</strong> chez <strong>{company}</strong>.</p>\n<p>Nous vous contacterons bientôt pour discuter de la suite.</p>",
+    ),
+    (
+        "email.status.shortlisted.body",
+        Locale::De,
+        "<p>Gute Nachrichten! Sie sind in der engeren Auswahl für die Position <strong>{job}</strong> bei <strong>{company}</strong>.</p>\n<p>Wir melden uns bald, um die nächsten Schritte zu besprechen.</p>",
+    ),
+    ("email.status.interviewed.subject", Locale::En, "Interview Invitation - {job}"),
+    ("email.status.interviewed.subject", Locale::Es, "Invitación a entrevista - {job}"),
+    ("email.status.interviewed.subject", Locale::Fr, "Invitation à un entretien - {job}"),
+    ("email.status.interviewed.subject", Locale::De, "Einladung zum Vorstellungsgespräch - {job}"),
+    (
+        "email.status.interviewed.body",
+        Locale::En,
+        "<p>We'd like to invite you for an interview for <strong>{job}</strong> at <strong>{company}</strong>.</p>\n<p>We'll reach out shortly to schedule a time that works for you.</p>\n<p>Looking forward to speaking with you!</p>",
+    ),
+    (
+        "email.status.interviewed.body",
+        Locale::Es,
+        "<p>Nos gustaría invitarte a una entrevista para <strong>{job}</strong> en <strong>{company}</strong>.</p>\n<p>Te contactaremos en breve para acordar un horario que te venga bien.</p>\n<p>¡Esperamos hablar contigo!</p>",
+    ),
+    (
+        "email.status.interviewed.body",
+        Locale::Fr,
+        "<p>Nous aimerions vous inviter à un entretien pour le poste <strong>{job}</strong> chez <strong>{company}</strong>.</p>\n<p>Nous vous contacterons prochainement pour convenir d'un créneau.</p>\n<p>Au plaisir d'échanger avec vous !</p>",
+    ),
+    (
+        "email.status.interviewed.body",
+        Locale::De,
+        "<p>Wir möchten Sie zu einem Vorstellungsgespräch für <strong>{job}</strong> bei <strong>{company}</strong> einladen.</p>\n<p>Wir melden uns in Kürze, um einen passenden Termin zu vereinbaren.</p>\n<p>Wir freuen uns auf das Gespräch!</p>",
+    ),
+    ("email.status.offered.subject", Locale::En, "Job Offer - {job}"),
+    ("email.status.offered.subject", Locale::Es, "Oferta de empleo - {job}"),
+    ("email.status.offered.subject", Locale::Fr, "Offre d'emploi - {job}"),
+    ("email.status.offered.subject", Locale::De, "Stellenangebot - {job}"),
+    (
+        "email.status.offered.body",
+        Locale::En,
+        "<p>Congratulations! We're pleased to offer you the <strong>{job}</strong> position at <strong>{company}</strong>.</p>\n<p>You'll receive a formal offer letter with compensation details shortly.</p>\n<p>Welcome to the team!</p>",
+    ),
+    (
+        "email.status.offered.body",
+        Locale::Es,
+        "<p>¡Enhorabuena! Nos complace ofrecerte el puesto de <strong>{job}</strong> en <strong>{company}</strong>.</p>\n<p>En breve recibirás una carta de oferta formal con los detalles de la compensación.</p>\n<p>¡Bienvenido al equipo!</p>",
+    ),
+    (
+        "email.status.offered.body",
+        Locale::Fr,
+        "<p>Félicitations ! Nous avons le plaisir de vous proposer le poste <strong>{job}</strong> chez <strong>{company}</strong>.</p>\n<p>Vous recevrez prochainement une lettre d'offre officielle précisant la rémunération.</p>\n<p>Bienvenue dans l'équipe !</p>",
+    ),
+    (
+        "email.status.offered.body",
+        Locale::De,
+        "<p>Herzlichen Glückwunsch! Wir freuen uns, Ihnen die Position <strong>{job}</strong> bei <strong>{company}</strong> anzubieten.</p>\n<p>Sie erhalten in Kürze ein formelles Angebotsschreiben mit den Vergütungsdetails.</p>\n<p>Willkommen im Team!</p>",
+    ),
+    ("email.status.hired.subject", Locale::En, "Welcome to {company}!"),
+    ("email.status.hired.subject", Locale::Es, "¡Bienvenido a {company}!"),
+    ("email.status.hired.subject", Locale::Fr, "Bienvenue chez {company} !"),
+    ("email.status.hired.subject", Locale::De, "Willkommen bei {company}!"),
+    (
+        "email.status.hired.body",
+        Locale::En,
+        "<p>Welcome to <strong>{company}</strong>! We're excited to have you join us as <strong>{job}</strong>.</p>\n<p>You'll receive onboarding details including your start date and first-day information soon.</p>\n<p>See you soon!</p>",
+    ),
+    (
+        "email.status.hired.body",
+        Locale::Es,
+        "<p>¡Bienvenido a <strong>{company}</strong>! Estamos encantados de que te unas como <strong>{job}</strong>.</p>\n<p>Pronto recibirás la información de incorporación, incluida tu fecha de inicio y los detalles del primer día.</p>\n<p>¡Hasta pronto!</p>",
+    ),
+    (
+        "email.status.hired.body",
+        Locale::Fr,
+        "<p>Bienvenue chez <strong>{company}</strong> ! Nous sommes ravis que vous nous rejoigniez en tant que <strong>{job}</strong>.</p>\n<p>Vous recevrez bientôt les informations d'intégration, dont votre date de début et le programme du premier jour.</p>\n<p>À très bientôt !</p>",
+    ),
+    (
+        "email.status.hired.body",
+        Locale::De,
+        "<p>Willkommen bei <strong>{company}</strong>! Wir freuen uns, dass Sie als <strong>{job}</strong> zu uns kommen.</p>\n<p>Sie erhalten in Kürze Informationen zum Onboarding, einschließlich Ihres Starttermins und des ersten Arbeitstags.</p>\n<p>Bis bald!</p>",
+    ),
+    ("email.status.rejected.subject", Locale::En, "Application Update - {job}"),
+    ("email.status.rejected.subject", Locale::Es, "Actualización de tu solicitud - {job}"),
+    ("email.status.rejected.subject", Locale::Fr, "Suivi de votre candidature - {job}"),
+    ("email.status.rejected.subject", Locale::De, "Neuigkeiten zu Ihrer Bewerbung - {job}"),
+    (
+        "email.status.rejected.body",
+        Locale::En,
+        "<p>Thank you for your interest in <strong>{job}</strong> at <strong>{company}</strong>.</p>\n<p>After careful review, we've decided to move forward with other candidates. We encourage you to apply for future openings that match your skills.</p>\n<p>Best of luck in your search!</p>",
+    ),
+    (
+        "email.status.rejected.body",
+        Locale::Es,
+        "<p>Gracias por tu interés en <strong>{job}</strong> en <strong>{company}</strong>.</p>\n<p>Tras una revisión cuidadosa, hemos decidido continuar con otros candidatos. Te animamos a postularte a futuras vacantes que encajen con tu perfil.</p>\n<p>¡Mucha suerte en tu búsqueda!</p>",
+    ),
+    (
+        "email.status.rejected.body",
+        Locale::Fr,
+        "<p>Merci de l'intérêt que vous portez au poste <strong>{job}</strong> chez <strong>{company}</strong>.</p>\n<p>Après un examen attentif, nous avons décidé de poursuivre avec d'autres candidats. N'hésitez pas à postuler à nos futures offres correspondant à votre profil.</p>\n<p>Bonne continuation dans vos recherches !</p>",
+    ),
+    (
+        "email.status.rejected.body",
+        Locale::De,
+        "<p>Vielen Dank für Ihr Interesse an der Position <strong>{job}</strong> bei <strong>{company}</strong>.</p>\n<p>Nach sorgfältiger Prüfung haben wir uns für andere Kandidaten entschieden. Wir ermutigen Sie, sich auf künftige passende Stellen zu bewerben.</p>\n<p>Viel Erfolg bei Ihrer weiteren Suche!</p>",
+    ),
+    ("email.status.other.subject", Locale::En, "Application Update - {job}"),
+    ("email.status.other.subject", Locale::Es, "Actualización de tu solicitud - {job}"),
+    ("email.status.other.subject", Locale::Fr, "Suivi de votre candidature - {job}"),
+    ("email.status.other.subject", Locale::De, "Neuigkeiten zu Ihrer Bewerbung - {job}"),
+    (
+        "email.status.other.body",
+        Locale::En,
+        "<p>There's an update on your application for <strong>{job}</strong> at <strong>{company}</strong>.</p>\n<p>We'll be in touch if we need anything further.</p>",
+    ),
+    (
+        "email.status.other.body",
+        Locale::Es,
+        "<p>Hay novedades sobre tu solicitud para <strong>{job}</strong> en <strong>{company}</strong>.</p>\n<p>Te contactaremos si necesitamos algo más.</p>",
+    ),
+    (
+        "email.status.other.body",
+        Locale::Fr,
+        "<p>Votre candidature au poste <strong>{job}</strong> chez <strong>{company}</strong> a évolué.</p>\n<p>Nous vous contacterons si nous avons besoin d'autres informations.</p>",
+    ),
+    (
+        "email.status.other.body",
+        Locale::De,
+        "<p>Es gibt Neuigkeiten zu Ihrer Bewerbung als <strong>{job}</strong> bei <strong>{company}</strong>.</p>\n<p>Wir melden uns, falls wir weitere Informationen benötigen.</p>",
+    ),
+    ("email.survey.subject", Locale::En, "How was applying to {company}?"),
+    ("email.survey.subject", Locale::Es, "¿Qué tal fue tu experiencia con {company}?"),
+    ("email.survey.subject", Locale::Fr, "Comment s'est passée votre candidature chez {company} ?"),
+    ("email.survey.subject", Locale::De, "Wie war Ihre Bewerbung bei {company}?"),
+    (
+        "email.survey.body",
+        Locale::En,
+        "<p>Thank you for applying for <strong>{job}</strong> at <strong>{company}</strong>. Whatever the outcome, we'd like to hear how the process felt.</p>\n<p>How likely are you to recommend applying to {company} to a friend? It takes less than a minute:</p>\n<p><a href=\"{url}\">Share your feedback</a></p>",
+    ),
+    (
+        "email.survey.body",
+        Locale::Es,
+        "<p>Gracias por postularte a <strong>{job}</strong> en <strong>{company}</strong>. Sea cual sea el resultado, nos gustaría saber cómo viviste el proceso.</p>\n<p>¿Qué probabilidad hay de que recomiendes postularse a {company} a un amigo? Te llevará menos de un minuto:</p>\n<p><a href=\"{url}\">Danos tu opinión</a></p>",
+    ),
+    (
+        "email.survey.body",
+        Locale::Fr,
+        "<p>Merci d'avoir postulé au poste <strong>{job}</strong> chez <strong>{company}</strong>. Quelle qu'en soit l'issue, nous aimerions savoir comment vous avez vécu le processus.</p>\n<p>Recommanderiez-vous à un proche de postuler chez {company} ? Cela prend moins d'une minute :</p>\n<p><a href=\"{url}\">Donner mon avis</a></p>",
+    ),
+    (
+        "email.survey.body",
+        Locale::De,
+        "<p>Vielen Dank für Ihre Bewerbung als <strong>{job}</strong> bei <strong>{company}</strong>. Unabhängig vom Ergebnis möchten wir wissen, wie Sie den Prozess erlebt haben.</p>\n<p>Wie wahrscheinlich ist es, dass Sie einer Freundin oder einem Freund eine Bewerbung bei {company} empfehlen? Es dauert weniger als eine Minute:</p>\n<p><a href=\"{url}\">Feedback geben</a></p>",
+    ),
+    ("email.account_merge.subject", Locale::En, "Confirm merging your accounts"),
+    ("email.account_merge.subject", Locale::Es, "Confirma la fusión de tus cuentas"),
+    ("email.account_merge.subject", Locale::Fr, "Confirmez la fusion de vos comptes"),
+    ("email.account_merge.subject", Locale::De, "Bestätigen Sie die Zusammenführung Ihrer Konten"),
+    (
+        "email.account_merge.body",
+        Locale::En,
+        "<p>You asked to merge this account into the one you use as <strong>{email}</strong>. Your applications, resumes and profile details will move there, and signing in here will open that account.</p>\n<p><a href=\"{url}\">Confirm the merge</a></p>\n<p>If this wasn't you, ignore this email. The link expires in {hours} hours.</p>",
+    ),
+    (
+        "email.account_merge.body",
+        Locale::Es,
+        "<p>Has pedido fusionar esta cuenta con la que usas como <strong>{email}</strong>. Tus solicitudes, currículums y datos de perfil pasarán a esa cuenta, y al iniciar sesión aquí se abrirá esa cuenta.</p>\n<p><a href=\"{url}\">Confirmar la fusión</a></p>\n<p>Si no has sido tú, ignora este correo. El enlace caduca en {hours} horas.</p>",
+    ),
+    (
+        "email.account_merge.body",
+        Locale::Fr,
+        "<p>Vous avez demandé à fusionner ce compte avec celui que vous utilisez en tant que <strong>{email}</strong>. Vos candidatures, CV et informations de profil y seront transférés, et une connexion ici ouvrira ce compte.</p>\n<p><a href=\"{url}\">Confirmer la fusion</a></p>\n<p>Si vous n'êtes pas à l'origine de cette demande, ignorez cet e-mail. Le lien expire dans {hours} heures.</p>",
+    ),
+    (
+        "email.account_merge.body",
+        Locale::De,
+        "<p>Sie haben angefordert, dieses Konto mit dem Konto <strong>{email}</strong> zusammenzuführen. Ihre Bewerbungen, Lebensläufe und Profildaten werden dorthin übertragen, und eine Anmeldung hier öffnet künftig dieses Konto.</p>\n<p><a href=\"{url}\">Zusammenführung bestätigen</a></p>\n<p>Falls Sie das nicht waren, ignorieren Sie diese E-Mail. Der Link läuft in {hours} Stunden ab.</p>",
+    ),
+    ("email.guest_claim.subject", Locale::En, "Your application for {job} was received"),
+    ("email.guest_claim.subject", Locale::Es, "Hemos recibido tu solicitud para {job}"),
+    ("email.guest_claim.subject", Locale::Fr, "Votre candidature au poste {job} a bien été reçue"),
+    ("email.guest_claim.subject", Locale::De, "Ihre Bewerbung als {job} ist eingegangen"),
+    (
+        "email.guest_claim.body",
+        Locale::En,
+        "<p>Thanks for applying for <strong>{job}</strong>. To follow your application, upload more documents or apply again faster, open your account:</p>\n<p><a href=\"{url}\">Open my account</a></p>\n<p>If you already have an account with this email, your application will be added to it. The link expires in {days} days.</p>",
+    ),
+    (
+        "email.guest_claim.body",
+        Locale::Es,
+        "<p>Gracias por postularte a <strong>{job}</strong>. Para seguir tu solicitud, subir más documentos o postularte más rápido la próxima vez, abre tu cuenta:</p>\n<p><a href=\"{url}\">Abrir mi cuenta</a></p>\n<p>Si ya tienes una cuenta con este correo, la solicitud se añadirá a ella. El enlace caduca en {days} días.</p>",
+    ),
+    (
+        "email.guest_claim.body",
+        Locale::Fr,
+        "<p>Merci d'avoir postulé au poste <strong>{job}</strong>. Pour suivre votre candidature, ajouter des documents ou postuler plus vite la prochaine fois, ouvrez votre compte :</p>\n<p><a href=\"{url}\">Ouvrir mon compte</a></p>\n<p>Si vous avez déjà un compte avec cette adresse, la candidature y sera ajoutée. Le lien expire dans {days} jours.</p>",
+    ),
+    (
+        "email.guest_claim.body",
+        Locale::De,
+        "<p>Vielen Dank für Ihre Bewerbung als <strong>{job}</strong>. Um Ihre Bewerbung zu verfolgen, weitere Unterlagen hochzuladen oder sich künftig schneller zu bewerben, öffnen Sie Ihr Konto:</p>\n<p><a href=\"{url}\">Mein Konto öffnen</a></p>\n<p>Falls Sie bereits ein Konto mit dieser E-Mail-Adresse haben, wird die Bewerbung diesem hinzugefügt. Der Link läuft in {days} Tagen ab.</p>",
+    ),
+    // ---- API error messages, keyed by error code ----
+    // No English entries: English responses keep the specific message each error carries
+    ("error.UNAUTHORIZED", Locale::Es, "Credenciales ausentes, no válidas o caducadas"),
+    ("error.UNAUTHORIZED", Locale::Fr, "Identifiants absents, invalides ou expirés"),
+    ("error.UNAUTHORIZED", Locale::De, "Fehlende, ungültige oder abgelaufene Anmeldedaten"),
+    ("error.FORBIDDEN", Locale::Es, "No tienes permiso para realizar esta acción"),
+    ("error.FORBIDDEN", Locale::Fr, "Vous n'êtes pas autorisé à effectuer cette action"),
+    ("error.FORBIDDEN", Locale::De, "Sie sind nicht berechtigt, diese Aktion auszuführen"),
+    ("error.NOT_FOUND", Locale::Es, "El recurso solicitado no existe"),
+    ("error.NOT_FOUND", Locale::Fr, "La ressource demandée n'existe pas"),
+    ("error.NOT_FOUND", Locale::De, "Die angeforderte Ressource existiert nicht"),
+    ("error.VALIDATION_ERROR", Locale::Es, "Uno o más campos no son válidos"),
+    ("error.VALIDATION_ERROR", Locale::Fr, "Un ou plusieurs champs sont invalides"),
+    ("error.VALIDATION_ERROR", Locale::De, "Mindestens ein Feld ist ungültig"),
+    ("error.RATE_LIMIT_EXCEEDED", Locale::Es, "Demasiadas solicitudes. Inténtalo de nuevo más tarde."),
+    ("error.RATE_LIMIT_EXCEEDED", Locale::Fr, "Trop de requêtes. Veuillez réessayer plus tard."),
+    ("error.RATE_LIMIT_EXCEEDED", Locale::De, "Zu viele Anfragen. Bitte versuchen Sie es später erneut."),
+    ("error.APPLICATION_DUPLICATE", Locale::Es, "Ya tienes una solicitud activa para este empleo"),
+    ("error.APPLICATION_DUPLICATE", Locale::Fr, "Vous avez déjà une candidature active pour cette offre"),
+    ("error.APPLICATION_DUPLICATE", Locale::De, "Sie haben sich bereits auf diese Stelle beworben"),
+    ("error.RESUME_LIMIT_REACHED", Locale::Es, "Has alcanzado el número máximo de currículums"),
+    ("error.RESUME_LIMIT_REACHED", Locale::Fr, "Vous avez atteint le nombre maximal de CV"),
+    ("error.RESUME_LIMIT_REACHED", Locale::De, "Sie haben die maximale Anzahl an Lebensläufen erreicht"),
+    ("error.INTERNAL_SERVER_ERROR", Locale::Es, "Se ha producido un error inesperado"),
+    ("error.INTERNAL_SERVER_ERROR", Locale::Fr, "Une erreur inattendue s'est produite"),
+    ("error.INTERNAL_SERVER_ERROR", Locale::De, "Ein unerwarteter Fehler ist aufgetreten"),
+    ("error.SERVICE_UNAVAILABLE", Locale::Es, "El servicio no está disponible temporalmente"),
+    ("error.SERVICE_UNAVAILABLE", Locale::Fr, "Le service est temporairement indisponible"),
+    ("error.SERVICE_UNAVAILABLE", Locale::De, "Der Dienst ist vorübergehend nicht verfügbar"),
+    ("error.DATABASE_ERROR", Locale::Es, "Se ha producido un error inesperado"),
+    ("error.DATABASE_ERROR", Locale::Fr, "Une erreur inattendue s'est produite"),
+    ("error.DATABASE_ERROR", Locale::De, "Ein unerwarteter Fehler ist aufgetreten"),
+];
+
+/// Catalog text for `key` in exactly `locale`, without falling back
+pub fn lookup(key: &str, locale: Locale) -> Option<&'static str> {
+    CATALOG
+        .iter()
+        .find(|(k, l, _)| *k == key && *l == locale)
+        .map(|(_, _, text)| *text)
+}
+
+/// Translate `key`, substituting `{name}` placeholders from `args`
+///
+/// Falls back to English, then to the key itself so a missing entry is visible rather
+/// than blank.
+pub fn t(key: &str, locale: Locale, args: &[(&str, &str)]) -> String {
+    let template = lookup(key, locale)
+        .or_else(|| lookup(key, Locale::En))
+        .unwrap_or(key);
+
+    args.iter()
+        .fold(template.to_string(), |text, (name, value)| {
+            text.replace(&format!("{{{}}}", name), value)
+        })
+}
+
+/// Stored locale preference of a user, if they have set one
+pub async fn user_locale(pool: &SqlitePool, user_id: &str) -> Option<Locale> {
+    sqlx::query_scalar::<_, Option<String>>("SELECT locale FROM users WHERE id = ?")
+        .bind(user_id)
+        .fetch_optional(pool)
+        .await
+        .ok()
+        .flatten()
+        .flatten()
+        .and_then(|tag| Locale::parse(&tag))
+}
+
+tokio::task_local! {
+    static REQUEST_LOCALE: Locale;
+}
+
+/// Run `future` with `locale` as the current request's locale
+pub async fn with_locale<F: Future>(locale: Locale, future: F) -> F::Output {
+    REQUEST_LOCALE.scope(locale, future).await
+}
+
+/// Locale negotiated for the request being handled; the default outside a request
+pub fn current_locale() -> Locale {
+    REQUEST_LOCALE
+        .try_with(|locale| *locale)
+        .unwrap_or_default()
+}
+
+/// Middleware negotiating `Accept-Language` for the request and echoing the chosen
+/// locale in `Content-Language`
+pub async fn locale_scope(request: Request, next: Next) -> Response {
+    let locale = request
+        .headers()
+        .get(ACCEPT_LANGUAGE)
+        .and_then(|v| v.to_str().ok())
+        .map(negotiate)
+        .unwrap_or_default();
+
+    let mut response = with_locale(locale, next.run(request)).await;
+    let headers = response.headers_mut();
+    headers.insert(CONTENT_LANGUAGE, HeaderValue::from_static(locale.as_str()));
+    headers.append(VARY, HeaderValue::from_static("accept-language"));
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_negotiate_respects_quality_and_region() {
+        assert_eq!(negotiate("fr-CH, fr;q=0.9, en;q=0.8"), Locale::Fr);
+        assert_eq!(negotiate("ja, de;q=0.7, es;q=0.9"), Locale::Es);
+        assert_eq!(negotiate("es;q=0, de"), Locale::De);
+        assert_eq!(negotiate("*, fr;q=0.5"), Locale::En);
+        assert_eq!(negotiate("ja, zh"), Locale::En);
+        assert_eq!(negotiate(""), Locale::En);
+    }
+
+    #[test]
+    fn test_translate_falls_back_to_english_then_key() {
+        assert_eq!(
+            t("email.greeting", Locale::Fr, &[("name", "Ana")]),
+            "Bonjour Ana,"
+        );
+        assert_eq!(lookup("error.NOT_FOUND", Locale::En), None);
+        assert_eq!(
+            t("email.status.other.subject", Locale::En, &[("job", "Chef")]),
+            "Application Update - Chef"
+        );
+        assert_eq!(t("missing.key", Locale::De, &[]), "missing.key");
+    }
+
+    #[test]
+    fn test_every_key_has_an_english_entry() {
+        for (key, _, _) in CATALOG {
+            if !key.starts_with("error.") {
+                assert!(
+                    lookup(key, Locale::En).is_some(),
+                    "{} has no English text",
+                    key
+                );
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_locale_is_scoped_to_the_request() {
+        assert_eq!(current_locale(), Locale::En);
+        let inside = with_locale(Locale::De, async { current_locale() }).await;
+        assert_eq!(inside, Locale::De);
+    }
+}
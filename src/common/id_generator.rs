@@ -58,6 +58,52 @@ pub enum EntityPrefix {
     Connection,
     /// ContentVersion (CV_) - Content version for inline AI editor
     ContentVersion,
+    /// Export (EX_) - Background export job (e.g. resume ZIP)
+    Export,
+    /// CompensationBand (CB_) - Salary band for a job level
+    CompensationBand,
+    /// CompensationOverride (CO_) - Approved offer salary above its band maximum
+    CompensationOverride,
+    /// DocumentRequest (DR_) - Document requested from a candidate
+    DocumentRequest,
+    /// ShortLink (SL_) - Shortened, channel-attributed link to a job
+    ShortLink,
+    /// Promotion (PR_) - Paid promotion of a job on an external platform
+    Promotion,
+    /// SlaPolicy (SP_) - Hiring SLA target for an application stage
+    SlaPolicy,
+    /// Department (DP_) - Department in the org structure
+    Department,
+    /// Team (TM_) - Team within a department
+    Team,
+    /// LegalHold (LH_) - Legal hold preserving a user's or application's data
+    LegalHold,
+    /// KnockoutRule (KR_) - Automatic screening rule for a job's applications
+    KnockoutRule,
+    /// AccountMerge (AM_) - Request to fold a duplicate account into another
+    AccountMerge,
+    /// UploadSession (US_) - Resumable upload in progress
+    UploadSession,
+    /// FeedbackVersion (FV_) - AI-drafted rejection feedback for an application
+    FeedbackVersion,
+    /// Broadcast (BC_) - Message sent to a segment of candidates
+    Broadcast,
+    /// ScheduledMessage (SM_) - Admin message or candidate email waiting for its send time
+    ScheduledMessage,
+    /// Snippet (SN_) - Canned response recruiters expand into messages and emails
+    Snippet,
+    /// InterviewArtifact (IA_) - Recording or transcript uploaded for an interview
+    InterviewArtifact,
+    /// ChatWebhook (CW_) - Slack or Teams channel recruiting events are posted to
+    ChatWebhook,
+    /// GuestClaim (GC_) - Emailed link that turns a guest applicant into a full account
+    GuestClaim,
+    /// SavedReport (RP_) - Admin report definition, optionally emailed on a schedule
+    SavedReport,
+    /// CalendarRetry (CR_) - Failed Google Calendar operation queued for another attempt
+    CalendarRetry,
+    /// RecommendationDismissal (RD_) - Job a candidate hid from their recommendations
+    RecommendationDismissal,
 }
 
 impl EntityPrefix {
@@ -84,6 +130,29 @@ impl EntityPrefix {
             EntityPrefix::View => "W",
             EntityPrefix::Connection => "N",
             EntityPrefix::ContentVersion => "CV",
+            EntityPrefix::Export => "EX",
+            EntityPrefix::CompensationBand => "CB",
+            EntityPrefix::CompensationOverride => "CO",
+            EntityPrefix::DocumentRequest => "DR",
+            EntityPrefix::ShortLink => "SL",
+            EntityPrefix::Promotion => "PR",
+            EntityPrefix::SlaPolicy => "SP",
+            EntityPrefix::Department => "DP",
+            EntityPrefix::Team => "TM",
+            EntityPrefix::LegalHold => "LH",
+            EntityPrefix::KnockoutRule => "KR",
+            EntityPrefix::AccountMerge => "AM",
+            EntityPrefix::UploadSession => "US",
+            EntityPrefix::FeedbackVersion => "FV",
+            EntityPrefix::Broadcast => "BC",
+            EntityPrefix::ScheduledMessage => "SM",
+            EntityPrefix::Snippet => "SN",
+            EntityPrefix::InterviewArtifact => "IA",
+            EntityPrefix::ChatWebhook => "CW",
+            EntityPrefix::GuestClaim => "GC",
+            EntityPrefix::SavedReport => "RP",
+            EntityPrefix::CalendarRetry => "CR",
+            EntityPrefix::RecommendationDismissal => "RD",
         }
     }
 }
@@ -256,6 +325,121 @@ pub fn generate_content_version_id() -> String {
     generate_id(EntityPrefix::ContentVersion)
 }
 
+/// Generate an Export ID (EX_XXXXXX)
+pub fn generate_export_id() -> String {
+    generate_id(EntityPrefix::Export)
+}
+
+/// Generate a Compensation Band ID (CB_XXXXXX)
+pub fn generate_compensation_band_id() -> String {
+    generate_id(EntityPrefix::CompensationBand)
+}
+
+/// Generate a Compensation Override ID (CO_XXXXXX)
+pub fn generate_compensation_override_id() -> String {
+    generate_id(EntityPrefix::CompensationOverride)
+}
+
+/// Generate a Document Request ID (DR_XXXXXX)
+pub fn generate_document_request_id() -> String {
+    generate_id(EntityPrefix::DocumentRequest)
+}
+
+/// Generate a Short Link ID (SL_XXXXXX)
+pub fn generate_short_link_id() -> String {
+    generate_id(EntityPrefix::ShortLink)
+}
+
+/// Generate a Promotion ID (PR_XXXXXX)
+pub fn generate_promotion_id() -> String {
+    generate_id(EntityPrefix::Promotion)
+}
+
+/// Generate an SLA Policy ID (SP_XXXXXX)
+pub fn generate_sla_policy_id() -> String {
+    generate_id(EntityPrefix::SlaPolicy)
+}
+
+/// Generate a Department ID (DP_XXXXXX)
+pub fn generate_department_id() -> String {
+    generate_id(EntityPrefix::Department)
+}
+
+/// Generate a Team ID (TM_XXXXXX)
+pub fn generate_team_id() -> String {
+    generate_id(EntityPrefix::Team)
+}
+
+/// Generate a Legal Hold ID (LH_XXXXXX)
+pub fn generate_legal_hold_id() -> String {
+    generate_id(EntityPrefix::LegalHold)
+}
+
+/// Generate a Knockout Rule ID (KR_XXXXXX)
+pub fn generate_knockout_rule_id() -> String {
+    generate_id(EntityPrefix::KnockoutRule)
+}
+
+/// Generate an Account Merge ID (AM_XXXXXX)
+pub fn generate_account_merge_id() -> String {
+    generate_id(EntityPrefix::AccountMerge)
+}
+
+/// Generate an Upload Session ID (US_XXXXXX)
+pub fn generate_upload_session_id() -> String {
+    generate_id(EntityPrefix::UploadSession)
+}
+
+/// Generate a Feedback Version ID (FV_XXXXXX)
+pub fn generate_feedback_version_id() -> String {
+    generate_id(EntityPrefix::FeedbackVersion)
+}
+
+/// Generate a Broadcast ID (BC_XXXXXX)
+pub fn generate_broadcast_id() -> String {
+    generate_id(EntityPrefix::Broadcast)
+}
+
+/// Generate a Scheduled Message ID (SM_XXXXXX)
+pub fn generate_scheduled_message_id() -> String {
+    generate_id(EntityPrefix::ScheduledMessage)
+}
+
+/// Generate a Snippet ID (SN_XXXXXX)
+pub fn generate_snippet_id() -> String {
+    generate_id(EntityPrefix::Snippet)
+}
+
+/// Generate an Interview Artifact ID (IA_XXXXXX)
+pub fn generate_interview_artifact_id() -> String {
+    generate_id(EntityPrefix::InterviewArtifact)
+}
+
+/// Generate a Chat Webhook ID (CW_XXXXXX)
+pub fn generate_chat_webhook_id() -> String {
+    generate_id(EntityPrefix::ChatWebhook)
+}
+
+/// Generate a Guest Claim ID (GC_XXXXXX)
+pub fn generate_guest_claim_id() -> String {
+    generate_id(EntityPrefix::GuestClaim)
+}
+
+/// Generate a Saved Report ID (RP_XXXXXX)
+pub fn generate_saved_report_id() -> String {
+    generate_id(EntityPrefix::SavedReport)
+}
+
+/// Generate a Calendar Retry ID (CR_XXXXXX)
+pub fn generate_calendar_retry_id() -> String {
+    generate_id(EntityPrefix::CalendarRetry)
+}
+
+/// Generate a Recommendation Dismissal ID (RD_XXXXXX)
+pub fn generate_recommendation_dismissal_id() -> String {
+    generate_id(EntityPrefix::RecommendationDismissal)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -322,6 +506,7 @@ mod tests {
         assert!(generate_token_id().starts_with("K_"));
         assert!(generate_view_id().starts_with("W_"));
         assert!(generate_connection_id().starts_with("N_"));
+        assert!(generate_export_id().starts_with("EX_"));
     }
 
     #[test]
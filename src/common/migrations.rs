@@ -9,12 +9,10 @@ use tracing::{info, warn};
 /// 
 /// Per design requirements: "Drop and recreate tables as needed (no legacy preservation)"
 /// This ensures clean schema without migration conflicts
-pub async fn run_migrations(pool: &SqlitePool) -> Result<(), sqlx::Error> {
-    // Only drop tables if RESET_DB environment variable is set to "true"
+pub async fn run_migrations(pool: &SqlitePool, reset_db: bool) -> Result<(), sqlx::Error> {
+    // Only drop tables if RESET_DB is set to "true"
     // This prevents data loss on server restarts
-    let should_reset_db = env::var("RESET_DB").unwrap_or_else(|_| "false".to_string()) == "true";
-
-    if should_reset_db {
+    if reset_db {
         warn!("⚠️  RESET_DB=true - Dropping all tables and recreating schema...");
         drop_all_tables(pool).await?;
         info!("✅ Dropped old tables");
@@ -31,6 +29,7 @@ pub async fn run_migrations(pool: &SqlitePool) -> Result<(), sqlx::Error> {
     create_messaging_tables(pool).await?;
     create_system_tables(pool).await?;
     create_indexes(pool).await?;
+    create_search_index(pool).await?;
     
     // Initialize default settings from environment variables
     init_default_settings(pool).await?;
@@ -38,6 +37,9 @@ pub async fn run_migrations(pool: &SqlitePool) -> Result<(), sqlx::Error> {
     // Sync current_stage with status for existing applications
     sync_application_stages(pool).await?;
 
+    // Bring the storage ledger in line with the files that actually exist
+    sync_storage_usage(pool).await?;
+
     info!("✅ Database migration completed successfully!");
     info!("📊 Created all tables with performance indexes");
 
@@ -54,6 +56,7 @@ async fn init_default_settings(pool: &SqlitePool) -> Result<(), sqlx::Error> {
         ("aws_region", "AWS_REGION"),
         ("aws_s3_bucket_name", "AWS_S3_BUCKET_NAME"),
         ("aws_cloudfront_domain", "AWS_CLOUDFRONT_DOMAIN"),
+        ("aws_cloudfront_distribution_id", "AWS_CLOUDFRONT_DISTRIBUTION_ID"),
         ("aws_ses_from_email", "AWS_SES_FROM_EMAIL"),
         ("aws_ses_region", "AWS_SES_REGION"),
     ];
@@ -134,13 +137,53 @@ async fn init_default_settings(pool: &SqlitePool) -> Result<(), sqlx::Error> {
 async fn drop_all_tables(pool: &SqlitePool) -> Result<(), sqlx::Error> {
     // Drop tables in reverse dependency order
     let tables = vec![
+        "users_fts",
+        "jobs_fts",
+        "companies_fts",
+        "conversation_messages_fts",
+        "job_recommendation_dismissals",
+        "analytics_refresh_state",
+        "analytics_status_durations",
+        "analytics_application_facts",
+        "saved_reports",
+        "legal_hold_events",
+        "guest_claims",
+        "jwt_signing_keys",
+        "account_merge_requests",
+        "user_identities",
+        "legal_holds",
+        "application_feedback_versions",
+        "application_knockouts",
+        "knockout_rules",
+        "eeo_responses",
+        "candidate_surveys",
+        "application_sla_breaches",
+        "sla_policies",
         "job_content_versions",
+        "job_promotions",
+        "job_recruiters",
+        "scheduled_social_posts",
+        "job_short_link_clicks",
+        "job_short_links",
         "job_social_images",
+        "compensation_overrides",
+        "compensation_bands",
         "offer_letters",
+        "interview_artifacts",
+        "application_feed_tokens",
+        "calendar_feed_tokens",
+        "calendar_retries",
         "interview_interviewers",
         "interviews",
         "stage_history",
+        "application_resume_history",
+        "application_requirement_matches",
+        "resume_exports",
+        "document_requests",
         "video_submissions",
+        "upload_sessions",
+        "storage_usage",
+        "orphaned_files",
         "videos",
         "application_status_history",
         "applications",
@@ -148,20 +191,39 @@ async fn drop_all_tables(pool: &SqlitePool) -> Result<(), sqlx::Error> {
         "job_views",
         "jobs",
         "job_templates",
+        "teams",
+        "departments",
         "company_assets",
         "companies",
+        "message_routing_state",
+        "conversation_states",
+        "message_snippets",
+        "scheduled_messages",
+        "broadcast_recipients",
+        "broadcasts",
         "message_attachments",
         "conversation_messages",
         "resume_assets",
         "events",
         "resumes",
+        "consents",
         "testimonials",
         "education",
         "experiences",
+        "contact_verifications",
+        "sms_preferences",
+        "whatsapp_opt_ins",
         "profiles",
         "admin_users",
         "system_settings",
         "ai_usage_logs",
+        "security_events",
+        "security_activity",
+        "activity_preferences",
+        "sms_deliveries",
+        "whatsapp_messages",
+        "chat_webhooks",
+        "moderation_queue",
         "email_history",
         "users",
     ];
@@ -195,6 +257,115 @@ async fn create_core_tables(pool: &SqlitePool) -> Result<(), sqlx::Error> {
     .execute(pool)
     .await?;
 
+    // Preferred IANA time zone for rendering dates to the user
+    let _ = sqlx::query("ALTER TABLE users ADD COLUMN timezone TEXT")
+        .execute(pool)
+        .await;
+
+    // Preferred language for emails, e.g. "es"
+    let _ = sqlx::query("ALTER TABLE users ADD COLUMN locale TEXT")
+        .execute(pool)
+        .await;
+
+    // Whether the user's job views are kept for their recently viewed list (1) or stored
+    // anonymously (0)
+    let _ = sqlx::query("ALTER TABLE users ADD COLUMN track_job_views INTEGER DEFAULT 1")
+        .execute(pool)
+        .await;
+
+    // Set when the account was folded into another one; sign-ins resolve to that user
+    let _ = sqlx::query("ALTER TABLE users ADD COLUMN merged_into TEXT")
+        .execute(pool)
+        .await;
+    let _ = sqlx::query("ALTER TABLE users ADD COLUMN merged_at TEXT")
+        .execute(pool)
+        .await;
+
+    // Additional sign-in identities linked to a user besides the one on the users row
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS user_identities (
+            id TEXT PRIMARY KEY,
+            user_id TEXT NOT NULL,
+            provider TEXT NOT NULL,
+            provider_id TEXT NOT NULL,
+            email TEXT,
+            linked_at TEXT DEFAULT (datetime('now')),
+            UNIQUE(provider, provider_id),
+            FOREIGN KEY(user_id) REFERENCES users(id) ON DELETE CASCADE
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // RS256 keys that sign session tokens; the private key is envelope-encrypted when a
+    // master key is configured
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS jwt_signing_keys (
+            kid TEXT PRIMARY KEY,
+            algorithm TEXT NOT NULL DEFAULT 'RS256',
+            private_key TEXT NOT NULL,
+            public_n TEXT NOT NULL,
+            public_e TEXT NOT NULL,
+            status TEXT NOT NULL DEFAULT 'active' CHECK (status IN ('active', 'retiring', 'retired')),
+            created_at TEXT DEFAULT (datetime('now')),
+            retire_at TEXT,
+            retired_at TEXT
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // Guest applicants get a provisional account, which has no sign-in until claimed.
+    // When the email already belonged to someone, the provisional account uses a
+    // placeholder address and is merged into that account on claim.
+    let _ = sqlx::query("ALTER TABLE users ADD COLUMN is_provisional INTEGER DEFAULT 0")
+        .execute(pool)
+        .await;
+
+    // Emailed links that claim a provisional account; only the token's hash is stored
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS guest_claims (
+            id TEXT PRIMARY KEY,
+            user_id TEXT NOT NULL,
+            email TEXT NOT NULL,
+            token_hash TEXT NOT NULL UNIQUE,
+            expires_at TEXT NOT NULL,
+            claimed_at TEXT,
+            claimed_by TEXT,
+            created_at TEXT DEFAULT (datetime('now')),
+            FOREIGN KEY(user_id) REFERENCES users(id) ON DELETE CASCADE
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // Requests to fold a duplicate account into another, confirmed from the duplicate's inbox
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS account_merge_requests (
+            id TEXT PRIMARY KEY,
+            source_user_id TEXT NOT NULL,
+            target_user_id TEXT NOT NULL,
+            match_reason TEXT NOT NULL CHECK (match_reason IN ('email', 'phone')),
+            token TEXT NOT NULL UNIQUE,
+            status TEXT NOT NULL DEFAULT 'pending' CHECK (status IN ('pending', 'completed', 'cancelled')),
+            expires_at TEXT NOT NULL,
+            created_at TEXT DEFAULT (datetime('now')),
+            completed_at TEXT,
+            FOREIGN KEY(source_user_id) REFERENCES users(id),
+            FOREIGN KEY(target_user_id) REFERENCES users(id)
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
     // Profiles table
     sqlx::query(
         r#"
@@ -219,6 +390,101 @@ async fn create_core_tables(pool: &SqlitePool) -> Result<(), sqlx::Error> {
     .execute(pool)
     .await?;
 
+    // Cached profile completeness (recomputed on profile and resume changes)
+    let _ = sqlx::query("ALTER TABLE profiles ADD COLUMN completeness_score INTEGER")
+        .execute(pool)
+        .await;
+    let _ = sqlx::query("ALTER TABLE profiles ADD COLUMN completeness_missing TEXT")
+        .execute(pool)
+        .await;
+    let _ = sqlx::query("ALTER TABLE profiles ADD COLUMN completeness_updated_at TEXT")
+        .execute(pool)
+        .await;
+
+    // Public portfolio opt-in, slug and per-section visibility
+    let _ = sqlx::query("ALTER TABLE profiles ADD COLUMN public_slug TEXT")
+        .execute(pool)
+        .await;
+    let _ = sqlx::query("ALTER TABLE profiles ADD COLUMN public_enabled INTEGER DEFAULT 0")
+        .execute(pool)
+        .await;
+    let _ = sqlx::query("ALTER TABLE profiles ADD COLUMN public_sections TEXT")
+        .execute(pool)
+        .await;
+
+    // Verified contact details; edits wait in pending_* until the code sent to them is confirmed
+    let _ = sqlx::query("ALTER TABLE profiles ADD COLUMN contact_email TEXT")
+        .execute(pool)
+        .await;
+    let _ = sqlx::query("ALTER TABLE profiles ADD COLUMN contact_email_verified_at TEXT")
+        .execute(pool)
+        .await;
+    let _ = sqlx::query("ALTER TABLE profiles ADD COLUMN pending_contact_email TEXT")
+        .execute(pool)
+        .await;
+    let _ = sqlx::query("ALTER TABLE profiles ADD COLUMN phone_verified_at TEXT")
+        .execute(pool)
+        .await;
+    let _ = sqlx::query("ALTER TABLE profiles ADD COLUMN pending_phone TEXT")
+        .execute(pool)
+        .await;
+    // Keyed hash of the phone's last ten digits, for matching duplicates now that the
+    // phone itself is encrypted
+    let _ = sqlx::query("ALTER TABLE profiles ADD COLUMN phone_index TEXT")
+        .execute(pool)
+        .await;
+
+    // Codes sent to confirm a pending contact change
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS contact_verifications (
+            id TEXT PRIMARY KEY,
+            user_id TEXT NOT NULL,
+            channel TEXT NOT NULL CHECK(channel IN ('email', 'phone')),
+            value TEXT NOT NULL,
+            code_hash TEXT NOT NULL,
+            attempts INTEGER NOT NULL DEFAULT 0,
+            expires_at TEXT NOT NULL,
+            verified_at TEXT,
+            created_at TEXT DEFAULT (datetime('now')),
+            FOREIGN KEY(user_id) REFERENCES users(id) ON DELETE CASCADE
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // SMS opt-in and quiet hours (local hours in the user's time zone)
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS sms_preferences (
+            user_id TEXT PRIMARY KEY,
+            opted_in INTEGER NOT NULL DEFAULT 0,
+            quiet_hours_start INTEGER CHECK (quiet_hours_start BETWEEN 0 AND 23),
+            quiet_hours_end INTEGER CHECK (quiet_hours_end BETWEEN 0 AND 23),
+            updated_at TEXT DEFAULT (datetime('now')),
+            FOREIGN KEY(user_id) REFERENCES users(id) ON DELETE CASCADE
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // WhatsApp opt-in; opted_in_at is the record of consent WhatsApp requires
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS whatsapp_opt_ins (
+            user_id TEXT PRIMARY KEY,
+            opted_in INTEGER NOT NULL DEFAULT 0,
+            opted_in_at TEXT,
+            opted_out_at TEXT,
+            FOREIGN KEY(user_id) REFERENCES users(id) ON DELETE CASCADE
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
     // Experiences table
     sqlx::query(
         r#"
@@ -281,6 +547,25 @@ async fn create_core_tables(pool: &SqlitePool) -> Result<(), sqlx::Error> {
     .execute(pool)
     .await?;
 
+    // Append-only log of consent given or withdrawn; a user's latest row per type is current
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS consents (
+            id TEXT PRIMARY KEY,
+            user_id TEXT NOT NULL,
+            consent_type TEXT NOT NULL CHECK (consent_type IN ('privacy_policy', 'data_processing', 'marketing')),
+            granted INTEGER NOT NULL,
+            policy_version TEXT,
+            context TEXT NOT NULL CHECK (context IN ('signup', 'login', 'application', 'settings')),
+            application_id TEXT,
+            created_at TEXT DEFAULT (datetime('now')),
+            FOREIGN KEY(user_id) REFERENCES users(id) ON DELETE CASCADE
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
     Ok(())
 }
 
@@ -309,6 +594,14 @@ async fn create_company_tables(pool: &SqlitePool) -> Result<(), sqlx::Error> {
     .execute(pool)
     .await?;
 
+    // Set when a duplicate company is merged into another; archived companies are hidden from lists
+    let _ = sqlx::query("ALTER TABLE companies ADD COLUMN merged_into TEXT")
+        .execute(pool)
+        .await;
+    let _ = sqlx::query("ALTER TABLE companies ADD COLUMN archived_at TEXT")
+        .execute(pool)
+        .await;
+
     // Company assets table
     sqlx::query(
         r#"
@@ -329,6 +622,41 @@ async fn create_company_tables(pool: &SqlitePool) -> Result<(), sqlx::Error> {
     .execute(pool)
     .await?;
 
+    // Departments, optionally scoped to a company and nested under a parent department
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS departments (
+            id TEXT PRIMARY KEY,
+            company_id TEXT,
+            parent_id TEXT,
+            name TEXT NOT NULL,
+            created_at TEXT DEFAULT (datetime('now')),
+            updated_at TEXT DEFAULT (datetime('now')),
+            FOREIGN KEY(company_id) REFERENCES companies(id) ON DELETE CASCADE,
+            FOREIGN KEY(parent_id) REFERENCES departments(id) ON DELETE SET NULL
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // Teams within a department
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS teams (
+            id TEXT PRIMARY KEY,
+            department_id TEXT NOT NULL,
+            name TEXT NOT NULL,
+            created_at TEXT DEFAULT (datetime('now')),
+            updated_at TEXT DEFAULT (datetime('now')),
+            UNIQUE(department_id, name),
+            FOREIGN KEY(department_id) REFERENCES departments(id) ON DELETE CASCADE
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
     Ok(())
 }
 
@@ -454,6 +782,101 @@ async fn create_job_tables(pool: &SqlitePool) -> Result<(), sqlx::Error> {
     .execute(pool)
     .await?;
 
+    // Short links (/j/:code) to job pages, one per job and channel
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS job_short_links (
+            id TEXT PRIMARY KEY,
+            code TEXT NOT NULL UNIQUE,
+            job_id TEXT NOT NULL,
+            channel TEXT NOT NULL CHECK (channel IN ('poster', 'email', 'social', 'other')),
+            created_by TEXT,
+            created_at TEXT DEFAULT (datetime('now')),
+            UNIQUE(job_id, channel),
+            FOREIGN KEY(job_id) REFERENCES jobs(id) ON DELETE CASCADE,
+            FOREIGN KEY(created_by) REFERENCES users(id)
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // Clicks on short links, attributed to the link's channel or the `?src=` tag it was followed with
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS job_short_link_clicks (
+            id TEXT PRIMARY KEY,
+            short_link_id TEXT NOT NULL,
+            job_id TEXT NOT NULL,
+            channel TEXT NOT NULL,
+            ip_address TEXT,
+            user_agent TEXT,
+            referrer TEXT,
+            clicked_at TEXT DEFAULT (datetime('now')),
+            FOREIGN KEY(short_link_id) REFERENCES job_short_links(id) ON DELETE CASCADE,
+            FOREIGN KEY(job_id) REFERENCES jobs(id) ON DELETE CASCADE
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // Social posts queued for publishing, one row per network
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS scheduled_social_posts (
+            id TEXT PRIMARY KEY,
+            group_id TEXT NOT NULL,
+            job_id TEXT NOT NULL,
+            network TEXT NOT NULL CHECK (network IN ('linkedin', 'x', 'buffer')),
+            caption TEXT NOT NULL,
+            image_url TEXT,
+            link_url TEXT,
+            scheduled_for TEXT NOT NULL,
+            scheduled_timezone TEXT,
+            status TEXT NOT NULL DEFAULT 'scheduled' CHECK (status IN ('scheduled', 'publishing', 'published', 'failed', 'cancelled')),
+            attempts INTEGER NOT NULL DEFAULT 0,
+            external_id TEXT,
+            external_url TEXT,
+            last_error TEXT,
+            created_by TEXT NOT NULL,
+            created_at TEXT DEFAULT (datetime('now')),
+            updated_at TEXT DEFAULT (datetime('now')),
+            published_at TEXT,
+            FOREIGN KEY(job_id) REFERENCES jobs(id) ON DELETE CASCADE,
+            FOREIGN KEY(created_by) REFERENCES users(id)
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // Paid promotion of a job on an external platform; `source` is the attribution tag
+    // matched against short link click channels and application sources
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS job_promotions (
+            id TEXT PRIMARY KEY,
+            job_id TEXT NOT NULL,
+            platform TEXT NOT NULL,
+            source TEXT NOT NULL,
+            currency TEXT NOT NULL DEFAULT 'USD',
+            budget REAL NOT NULL CHECK (budget >= 0),
+            spend REAL NOT NULL DEFAULT 0 CHECK (spend >= 0),
+            start_date TEXT NOT NULL,
+            end_date TEXT,
+            notes TEXT,
+            created_by TEXT,
+            created_at TEXT DEFAULT (datetime('now')),
+            updated_at TEXT DEFAULT (datetime('now')),
+            FOREIGN KEY(job_id) REFERENCES jobs(id) ON DELETE CASCADE,
+            FOREIGN KEY(created_by) REFERENCES users(id)
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
     Ok(())
 }
 
@@ -473,6 +896,7 @@ async fn create_content_version_tables(pool: &SqlitePool) -> Result<(), sqlx::Er
             version_number INTEGER NOT NULL,
             created_by TEXT,
             created_at TEXT DEFAULT (datetime('now')),
+            source TEXT NOT NULL DEFAULT 'ai' CHECK (source IN ('ai', 'human')),
             FOREIGN KEY(job_id) REFERENCES jobs(id) ON DELETE CASCADE,
             FOREIGN KEY(created_by) REFERENCES users(id)
         )
@@ -505,24 +929,90 @@ async fn create_content_version_tables(pool: &SqlitePool) -> Result<(), sqlx::Er
         .execute(pool)
         .await;
 
-    // Migrate job_content_versions to include 'summary' in CHECK constraint
-    migrate_job_content_versions_check_constraint(pool).await?;
-
-    Ok(())
-}
+    // Per-job override (in days) for the re-application cooldown; NULL uses the global setting
+    let _ = sqlx::query("ALTER TABLE jobs ADD COLUMN reapply_cooldown_days INTEGER")
+        .execute(pool)
+        .await;
 
-/// Migration to update job_content_versions CHECK constraint to include 'summary'
-/// SQLite doesn't support modifying CHECK constraints, so we recreate the table
-async fn migrate_job_content_versions_check_constraint(pool: &SqlitePool) -> Result<(), sqlx::Error> {
-    use sqlx::Row;
+    // Org placement and hiring manager of a job; recruiters are in job_recruiters
+    let _ = sqlx::query("ALTER TABLE jobs ADD COLUMN department_id TEXT")
+        .execute(pool)
+        .await;
+    let _ = sqlx::query("ALTER TABLE jobs ADD COLUMN team_id TEXT")
+        .execute(pool)
+        .await;
+    let _ = sqlx::query("ALTER TABLE jobs ADD COLUMN hiring_manager_id TEXT")
+        .execute(pool)
+        .await;
 
-    // First, check if we have a leftover _new table from a failed migration
-    // If so, we need to recover by dropping the old table and renaming the new one
-    let has_new_table: Option<(String,)> = sqlx::query_as(
-        "SELECT name FROM sqlite_master WHERE type='table' AND name='job_content_versions_new'"
-    )
-    .fetch_optional(pool)
-    .await?;
+    // Soft delete: trashed jobs are hidden everywhere and purged after the retention window
+    let _ = sqlx::query("ALTER TABLE jobs ADD COLUMN deleted_at TEXT")
+        .execute(pool)
+        .await;
+    let _ = sqlx::query("ALTER TABLE jobs ADD COLUMN deleted_by TEXT")
+        .execute(pool)
+        .await;
+
+    // Autosaved edits in draft_data are versioned so concurrent editors can detect conflicts
+    let _ = sqlx::query("ALTER TABLE jobs ADD COLUMN draft_revision INTEGER NOT NULL DEFAULT 0")
+        .execute(pool)
+        .await;
+    let _ = sqlx::query("ALTER TABLE jobs ADD COLUMN draft_updated_by TEXT")
+        .execute(pool)
+        .await;
+    let _ = sqlx::query("ALTER TABLE jobs ADD COLUMN draft_updated_at TEXT")
+        .execute(pool)
+        .await;
+
+    // Applications are refused after the deadline and the scheduler closes the job
+    let _ = sqlx::query("ALTER TABLE jobs ADD COLUMN application_deadline TEXT")
+        .execute(pool)
+        .await;
+
+    // Most applications a job takes; past it the job closes or further applicants are
+    // waitlisted, per application_cap_action ('close' or 'waitlist')
+    let _ = sqlx::query("ALTER TABLE jobs ADD COLUMN application_cap INTEGER")
+        .execute(pool)
+        .await;
+    let _ = sqlx::query("ALTER TABLE jobs ADD COLUMN application_cap_action TEXT")
+        .execute(pool)
+        .await;
+
+    // Recruiters assigned to a job
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS job_recruiters (
+            job_id TEXT NOT NULL,
+            user_id TEXT NOT NULL,
+            assigned_by TEXT,
+            assigned_at TEXT DEFAULT (datetime('now')),
+            PRIMARY KEY(job_id, user_id),
+            FOREIGN KEY(job_id) REFERENCES jobs(id) ON DELETE CASCADE,
+            FOREIGN KEY(user_id) REFERENCES users(id) ON DELETE CASCADE
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // Migrate job_content_versions to include 'summary' in CHECK constraint
+    migrate_job_content_versions_check_constraint(pool).await?;
+
+    Ok(())
+}
+
+/// Migration to update job_content_versions CHECK constraint to include 'summary'
+/// SQLite doesn't support modifying CHECK constraints, so we recreate the table
+async fn migrate_job_content_versions_check_constraint(pool: &SqlitePool) -> Result<(), sqlx::Error> {
+    use sqlx::Row;
+
+    // First, check if we have a leftover _new table from a failed migration
+    // If so, we need to recover by dropping the old table and renaming the new one
+    let has_new_table: Option<(String,)> = sqlx::query_as(
+        "SELECT name FROM sqlite_master WHERE type='table' AND name='job_content_versions_new'"
+    )
+    .fetch_optional(pool)
+    .await?;
 
     if has_new_table.is_some() {
         tracing::info!("Recovering from failed migration - found job_content_versions_new table");
@@ -613,6 +1103,7 @@ async fn migrate_job_content_versions_check_constraint(pool: &SqlitePool) -> Res
             version_number INTEGER NOT NULL,
             created_by TEXT,
             created_at TEXT DEFAULT (datetime('now')),
+            source TEXT NOT NULL DEFAULT 'ai' CHECK (source IN ('ai', 'human')),
             FOREIGN KEY(job_id) REFERENCES jobs(id) ON DELETE CASCADE,
             FOREIGN KEY(created_by) REFERENCES users(id)
         )
@@ -776,6 +1267,45 @@ async fn create_application_tables(pool: &SqlitePool) -> Result<(), sqlx::Error>
         .execute(pool)
         .await;
 
+    // Version chain columns for replacement uploads
+    let _ = sqlx::query("ALTER TABLE resumes ADD COLUMN version INTEGER DEFAULT 1")
+        .execute(pool)
+        .await;
+    let _ = sqlx::query("ALTER TABLE resumes ADD COLUMN supersedes_id TEXT")
+        .execute(pool)
+        .await;
+    let _ = sqlx::query("ALTER TABLE resumes ADD COLUMN superseded_by TEXT")
+        .execute(pool)
+        .await;
+    let _ = sqlx::query("ALTER TABLE resumes ADD COLUMN root_resume_id TEXT")
+        .execute(pool)
+        .await;
+
+    // Total years of experience read from the scanned resume
+    let _ = sqlx::query("ALTER TABLE resumes ADD COLUMN experience_years REAL")
+        .execute(pool)
+        .await;
+
+    // Stored size in bytes, for storage accounting
+    let _ = sqlx::query("ALTER TABLE resumes ADD COLUMN file_size INTEGER")
+        .execute(pool)
+        .await;
+
+    // Input/output token split, since models price them differently
+    let _ = sqlx::query("ALTER TABLE ai_usage_logs ADD COLUMN prompt_tokens INTEGER")
+        .execute(pool)
+        .await;
+    let _ = sqlx::query("ALTER TABLE ai_usage_logs ADD COLUMN completion_tokens INTEGER")
+        .execute(pool)
+        .await;
+
+    // Whether a content version was AI-generated or a human edit
+    let _ = sqlx::query(
+        "ALTER TABLE job_content_versions ADD COLUMN source TEXT NOT NULL DEFAULT 'ai' CHECK (source IN ('ai', 'human'))",
+    )
+    .execute(pool)
+    .await;
+
     // Resume events table
     sqlx::query(
         r#"
@@ -829,7 +1359,7 @@ async fn create_application_tables(pool: &SqlitePool) -> Result<(), sqlx::Error>
             cover_letter TEXT,
             applied_at TEXT DEFAULT (datetime('now')),
             updated_at TEXT DEFAULT (datetime('now')),
-            UNIQUE(user_id, job_id),
+            archived_at TEXT,
             FOREIGN KEY(user_id) REFERENCES users(id) ON DELETE CASCADE,
             FOREIGN KEY(job_id) REFERENCES jobs(id) ON DELETE CASCADE,
             FOREIGN KEY(resume_id) REFERENCES resumes(id) ON DELETE SET NULL
@@ -843,6 +1373,32 @@ async fn create_application_tables(pool: &SqlitePool) -> Result<(), sqlx::Error>
     // SQLite doesn't support ALTER TABLE to modify constraints, so we need to handle this
     migrate_applications_status_constraint(pool).await?;
 
+    // One active application per candidate and job; archived ones are kept for history
+    migrate_applications_unique_constraint(pool).await?;
+
+    // Attribution tag (e.g. `linkedin_ads`) passed through from the link the candidate followed
+    let _ = sqlx::query("ALTER TABLE applications ADD COLUMN source TEXT")
+        .execute(pool)
+        .await;
+
+    // Experience from the attached resume; relevant years are measured against the job title
+    let _ = sqlx::query("ALTER TABLE applications ADD COLUMN experience_years REAL")
+        .execute(pool)
+        .await;
+    let _ = sqlx::query("ALTER TABLE applications ADD COLUMN relevant_experience_years REAL")
+        .execute(pool)
+        .await;
+
+    // Deadline for the candidate to answer an offer; drives the expiring-offer reminder
+    let _ = sqlx::query("ALTER TABLE applications ADD COLUMN offer_expires_at TEXT")
+        .execute(pool)
+        .await;
+
+    // Set while the application waits past the job's application cap; cleared on promotion
+    let _ = sqlx::query("ALTER TABLE applications ADD COLUMN waitlisted_at TEXT")
+        .execute(pool)
+        .await;
+
     // Application status history table
     sqlx::query(
         r#"
@@ -861,6 +1417,168 @@ async fn create_application_tables(pool: &SqlitePool) -> Result<(), sqlx::Error>
     .execute(pool)
     .await?;
 
+    // Structured withdrawal reasons recorded on the status-history entry
+    let _ = sqlx::query("ALTER TABLE application_status_history ADD COLUMN reason_code TEXT")
+        .execute(pool)
+        .await;
+    let _ = sqlx::query("ALTER TABLE application_status_history ADD COLUMN reason_details TEXT")
+        .execute(pool)
+        .await;
+
+    // Resume swaps on applications (candidates may change the attached resume before review)
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS application_resume_history (
+            id TEXT PRIMARY KEY,
+            application_id TEXT NOT NULL,
+            old_resume_id TEXT,
+            new_resume_id TEXT NOT NULL,
+            changed_by TEXT NOT NULL,
+            changed_at TEXT DEFAULT (datetime('now')),
+            FOREIGN KEY(application_id) REFERENCES applications(id) ON DELETE CASCADE,
+            FOREIGN KEY(changed_by) REFERENCES users(id)
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // Cached requirement-by-requirement resume comparisons for screening
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS application_requirement_matches (
+            application_id TEXT PRIMARY KEY,
+            job_id TEXT NOT NULL,
+            resume_id TEXT NOT NULL,
+            requirements_snapshot TEXT NOT NULL,
+            result_json TEXT NOT NULL,
+            generated_at TEXT DEFAULT (datetime('now')),
+            FOREIGN KEY(application_id) REFERENCES applications(id) ON DELETE CASCADE
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // Knockout rules checked when a candidate applies; a fired rule tags or rejects the application
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS knockout_rules (
+            id TEXT PRIMARY KEY,
+            job_id TEXT NOT NULL,
+            name TEXT NOT NULL,
+            rule_type TEXT NOT NULL CHECK (rule_type IN ('work_authorization', 'min_years', 'question')),
+            country TEXT,
+            min_years REAL,
+            question TEXT,
+            expected_answer TEXT,
+            action TEXT NOT NULL CHECK (action IN ('tag', 'reject')),
+            tag TEXT,
+            email_subject TEXT,
+            email_body TEXT,
+            is_active INTEGER NOT NULL DEFAULT 1,
+            created_by TEXT NOT NULL,
+            created_at TEXT DEFAULT (datetime('now')),
+            updated_at TEXT DEFAULT (datetime('now')),
+            FOREIGN KEY(job_id) REFERENCES jobs(id) ON DELETE CASCADE
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // Every knockout rule that fired, with what it did
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS application_knockouts (
+            id TEXT PRIMARY KEY,
+            application_id TEXT NOT NULL,
+            job_id TEXT NOT NULL,
+            rule_id TEXT NOT NULL,
+            rule_name TEXT NOT NULL,
+            action TEXT NOT NULL,
+            tag TEXT,
+            reason TEXT NOT NULL,
+            email_sent INTEGER NOT NULL DEFAULT 0,
+            created_at TEXT DEFAULT (datetime('now')),
+            FOREIGN KEY(application_id) REFERENCES applications(id) ON DELETE CASCADE
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // AI-drafted rejection feedback for an application; the active version is what gets sent
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS application_feedback_versions (
+            id TEXT PRIMARY KEY,
+            application_id TEXT NOT NULL,
+            content TEXT NOT NULL,
+            reason TEXT,
+            tone TEXT,
+            is_active INTEGER NOT NULL DEFAULT 0,
+            version_number INTEGER NOT NULL,
+            created_by TEXT,
+            created_at TEXT DEFAULT (datetime('now')),
+            FOREIGN KEY(application_id) REFERENCES applications(id) ON DELETE CASCADE
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // Background resume ZIP exports for jobs with many applicants
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS resume_exports (
+            id TEXT PRIMARY KEY,
+            job_id TEXT NOT NULL,
+            status TEXT NOT NULL DEFAULT 'pending' CHECK (status IN ('pending', 'processing', 'completed', 'failed')),
+            total INTEGER NOT NULL DEFAULT 0,
+            processed INTEGER NOT NULL DEFAULT 0,
+            file_path TEXT,
+            error TEXT,
+            requested_by TEXT NOT NULL,
+            created_at TEXT DEFAULT (datetime('now')),
+            completed_at TEXT,
+            FOREIGN KEY(job_id) REFERENCES jobs(id) ON DELETE CASCADE
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // Documents (ID, certificates) requested from candidates; files are stored encrypted
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS document_requests (
+            id TEXT PRIMARY KEY,
+            candidate_id TEXT NOT NULL,
+            application_id TEXT,
+            document_type TEXT NOT NULL,
+            instructions TEXT,
+            status TEXT NOT NULL DEFAULT 'requested' CHECK (status IN ('requested', 'uploaded', 'approved', 'rejected')),
+            requested_by TEXT NOT NULL,
+            due_date TEXT,
+            filename TEXT,
+            original_filename TEXT,
+            content_type TEXT,
+            file_size INTEGER,
+            uploaded_at TEXT,
+            review_notes TEXT,
+            reviewed_by TEXT,
+            reviewed_at TEXT,
+            created_at TEXT DEFAULT (datetime('now')),
+            updated_at TEXT DEFAULT (datetime('now')),
+            FOREIGN KEY(candidate_id) REFERENCES users(id) ON DELETE CASCADE,
+            FOREIGN KEY(application_id) REFERENCES applications(id) ON DELETE SET NULL
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
     // Stage history table
     sqlx::query(
         r#"
@@ -940,77 +1658,302 @@ async fn create_application_tables(pool: &SqlitePool) -> Result<(), sqlx::Error>
         .execute(pool)
         .await;
 
-    Ok(())
-}
-
-/// Migration to update applications CHECK constraint to include 'interview_scheduled' status
-/// SQLite doesn't support ALTER TABLE to modify CHECK constraints, so we recreate the table
-/// using the recommended SQLite approach: https://www.sqlite.org/lang_altertable.html#otheralter
-async fn migrate_applications_status_constraint(pool: &SqlitePool) -> Result<(), sqlx::Error> {
-    use sqlx::Row;
-
-    // Check if the constraint already includes 'interview_scheduled' by checking the table schema
-    let needs_migration = sqlx::query(
-        "SELECT sql FROM sqlite_master WHERE type='table' AND name='applications'",
-    )
-    .fetch_optional(pool)
-    .await?
-    .map(|row: sqlx::sqlite::SqliteRow| {
-        let sql: String = row.get("sql");
-        // Check if the CHECK constraint already includes 'interview_scheduled'
-        !sql.contains("interview_scheduled")
-    })
-    .unwrap_or(false);
-
-    if !needs_migration {
-        return Ok(());
-    }
-
-    tracing::info!("Migrating applications table to support 'interview_scheduled' status...");
-
-    // Get a dedicated connection from the pool for this migration
-    // This ensures PRAGMA foreign_keys = OFF stays in effect for all operations
-    let mut conn = pool.acquire().await?;
-
-    // Step 1: Disable foreign key enforcement for this connection
-    sqlx::query("PRAGMA foreign_keys = OFF")
-        .execute(&mut *conn)
-        .await?;
-
-    // Verify foreign keys are disabled
-    let fk_status: (i32,) = sqlx::query_as("PRAGMA foreign_keys")
-        .fetch_one(&mut *conn)
-        .await?;
-    tracing::info!(foreign_keys_enabled = fk_status.0, "Foreign key status");
-
-    // Clean up any leftover temp table from previous failed migrations
-    let _ = sqlx::query("DROP TABLE IF EXISTS applications_new")
-        .execute(&mut *conn)
-        .await;
-
-    // Step 2: Create new table with updated CHECK constraint
+    // Resumable video uploads; chunks are appended to a temp file until the last one lands
     sqlx::query(
         r#"
-        CREATE TABLE applications_new (
+        CREATE TABLE IF NOT EXISTS upload_sessions (
             id TEXT PRIMARY KEY,
             user_id TEXT NOT NULL,
-            job_id TEXT NOT NULL,
-            resume_id TEXT,
-            status TEXT DEFAULT 'submitted' CHECK (status IN (
-                'submitted', 'reviewed', 'shortlisted', 'interview_scheduled', 'interviewed', 
-                'offered', 'hired', 'rejected', 'withdrawn'
-            )),
-            current_stage TEXT DEFAULT 'Applied' CHECK (current_stage IN (
-                'Applied', 'Resume Review', 'Shortlisted', 'Interview Scheduled',
-                'Interview Completed', 'Offer Extended', 'Hired', 'Rejected'
-            )),
-            cover_letter TEXT,
-            applied_at TEXT DEFAULT (datetime('now')),
+            filename TEXT NOT NULL,
+            mime_type TEXT NOT NULL,
+            total_size INTEGER NOT NULL CHECK (total_size > 0),
+            received_bytes INTEGER NOT NULL DEFAULT 0,
+            status TEXT NOT NULL DEFAULT 'uploading' CHECK (status IN ('uploading', 'completed', 'aborted')),
+            video_id TEXT,
+            expires_at TEXT NOT NULL,
+            created_at TEXT DEFAULT (datetime('now')),
             updated_at TEXT DEFAULT (datetime('now')),
-            UNIQUE(user_id, job_id),
-            FOREIGN KEY(user_id) REFERENCES users(id) ON DELETE CASCADE,
-            FOREIGN KEY(job_id) REFERENCES jobs(id) ON DELETE CASCADE,
-            FOREIGN KEY(resume_id) REFERENCES resumes(id) ON DELETE SET NULL
+            FOREIGN KEY(user_id) REFERENCES users(id) ON DELETE CASCADE
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // Storage ledger: one row per stored file, charged to a user or a company
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS storage_usage (
+            category TEXT NOT NULL CHECK (category IN ('resume', 'video', 'attachment', 'company_asset')),
+            file_id TEXT NOT NULL,
+            owner_type TEXT NOT NULL CHECK (owner_type IN ('user', 'company')),
+            owner_id TEXT NOT NULL,
+            bytes INTEGER NOT NULL CHECK (bytes >= 0),
+            created_at TEXT DEFAULT (datetime('now')),
+            PRIMARY KEY (category, file_id)
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // Stored objects no row references, tracked across scans so they can be deleted after a grace period
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS orphaned_files (
+            storage TEXT NOT NULL CHECK (storage IN ('local', 's3')),
+            path TEXT NOT NULL,
+            category TEXT NOT NULL,
+            size_bytes INTEGER NOT NULL DEFAULT 0,
+            owner_id TEXT,
+            first_seen_at TEXT NOT NULL DEFAULT (datetime('now')),
+            last_seen_at TEXT NOT NULL DEFAULT (datetime('now')),
+            deleted_at TEXT,
+            PRIMARY KEY (storage, path)
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // Hiring SLA targets per application status; job_id NULL is the global default
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS sla_policies (
+            id TEXT PRIMARY KEY,
+            job_id TEXT,
+            stage TEXT NOT NULL CHECK (stage IN (
+                'submitted', 'reviewed', 'shortlisted', 'interview_scheduled', 'interviewed', 'offered'
+            )),
+            target_business_days INTEGER NOT NULL CHECK (target_business_days > 0),
+            notify_email TEXT,
+            created_by TEXT,
+            created_at TEXT DEFAULT (datetime('now')),
+            updated_at TEXT DEFAULT (datetime('now')),
+            FOREIGN KEY(job_id) REFERENCES jobs(id) ON DELETE CASCADE,
+            FOREIGN KEY(created_by) REFERENCES users(id)
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // Applications flagged by the SLA monitor; resolved once they leave the stage
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS application_sla_breaches (
+            id TEXT PRIMARY KEY,
+            application_id TEXT NOT NULL,
+            job_id TEXT NOT NULL,
+            policy_id TEXT,
+            stage TEXT NOT NULL,
+            stage_entered_at TEXT NOT NULL,
+            due_at TEXT NOT NULL,
+            detected_at TEXT DEFAULT (datetime('now')),
+            notified_at TEXT,
+            resolved_at TEXT,
+            UNIQUE(application_id, stage, stage_entered_at),
+            FOREIGN KEY(application_id) REFERENCES applications(id) ON DELETE CASCADE,
+            FOREIGN KEY(job_id) REFERENCES jobs(id) ON DELETE CASCADE,
+            FOREIGN KEY(policy_id) REFERENCES sla_policies(id) ON DELETE SET NULL
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // Admin report definitions (a services::reports::ReportDefinition as JSON), optionally
+    // emailed to recipients daily or weekly
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS saved_reports (
+            id TEXT PRIMARY KEY,
+            name TEXT NOT NULL,
+            description TEXT,
+            definition TEXT NOT NULL,
+            schedule TEXT CHECK (schedule IN ('daily', 'weekly')),
+            schedule_hour INTEGER NOT NULL DEFAULT 8 CHECK (schedule_hour BETWEEN 0 AND 23),
+            schedule_weekday INTEGER CHECK (schedule_weekday BETWEEN 0 AND 6),
+            recipients TEXT,
+            last_sent_at TEXT,
+            created_by TEXT,
+            created_at TEXT DEFAULT (datetime('now')),
+            updated_at TEXT DEFAULT (datetime('now')),
+            FOREIGN KEY(created_by) REFERENCES users(id) ON DELETE SET NULL
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // Per-application milestones precomputed for the analytics endpoints; derived from
+    // applications and their history by services::analytics_facts, so never the source of truth
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS analytics_application_facts (
+            application_id TEXT PRIMARY KEY,
+            job_id TEXT NOT NULL,
+            source TEXT NOT NULL,
+            applied_at TEXT NOT NULL,
+            days_to_first_review REAL,
+            days_to_hire REAL,
+            reviewed_at TEXT,
+            interview_at TEXT,
+            offered_at TEXT,
+            hired_at TEXT,
+            offer_accepted INTEGER NOT NULL DEFAULT 0,
+            interviews INTEGER NOT NULL DEFAULT 0
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // Completed stints in each status, one row per stint
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS analytics_status_durations (
+            application_id TEXT NOT NULL,
+            status TEXT NOT NULL,
+            days REAL NOT NULL
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // Where the last analytics refresh got to, by rowid of each source table
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS analytics_refresh_state (
+            id INTEGER PRIMARY KEY CHECK (id = 1),
+            application_rowid INTEGER NOT NULL DEFAULT 0,
+            history_rowid INTEGER NOT NULL DEFAULT 0,
+            interview_rowid INTEGER NOT NULL DEFAULT 0,
+            refreshed_at TEXT,
+            full_refreshed_at TEXT
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // Satisfaction surveys sent to candidates once their application is hired or rejected
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS candidate_surveys (
+            id TEXT PRIMARY KEY,
+            application_id TEXT NOT NULL UNIQUE,
+            job_id TEXT NOT NULL,
+            user_id TEXT NOT NULL,
+            recruiter_id TEXT,
+            outcome TEXT NOT NULL CHECK (outcome IN ('hired', 'rejected')),
+            token TEXT NOT NULL UNIQUE,
+            score INTEGER CHECK (score BETWEEN 0 AND 10),
+            comment TEXT,
+            sent_at TEXT,
+            expires_at TEXT NOT NULL,
+            responded_at TEXT,
+            created_at TEXT DEFAULT (datetime('now')),
+            FOREIGN KEY(application_id) REFERENCES applications(id) ON DELETE CASCADE,
+            FOREIGN KEY(job_id) REFERENCES jobs(id) ON DELETE CASCADE,
+            FOREIGN KEY(user_id) REFERENCES users(id) ON DELETE CASCADE,
+            FOREIGN KEY(recruiter_id) REFERENCES users(id) ON DELETE SET NULL
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // Voluntary self-identification (EEO) answers, kept apart from the application itself.
+    // The answers are one envelope-encrypted JSON document; only aggregates are ever reported.
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS eeo_responses (
+            id TEXT PRIMARY KEY,
+            application_id TEXT NOT NULL UNIQUE,
+            job_id TEXT NOT NULL,
+            encrypted_answers TEXT NOT NULL,
+            submitted_at TEXT DEFAULT (datetime('now')),
+            FOREIGN KEY(application_id) REFERENCES applications(id) ON DELETE CASCADE,
+            FOREIGN KEY(job_id) REFERENCES jobs(id) ON DELETE CASCADE
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Migration to update applications CHECK constraint to include 'interview_scheduled' status
+/// SQLite doesn't support ALTER TABLE to modify CHECK constraints, so we recreate the table
+/// using the recommended SQLite approach: https://www.sqlite.org/lang_altertable.html#otheralter
+async fn migrate_applications_status_constraint(pool: &SqlitePool) -> Result<(), sqlx::Error> {
+    use sqlx::Row;
+
+    // Check if the constraint already includes 'interview_scheduled' by checking the table schema
+    let needs_migration = sqlx::query(
+        "SELECT sql FROM sqlite_master WHERE type='table' AND name='applications'",
+    )
+    .fetch_optional(pool)
+    .await?
+    .map(|row: sqlx::sqlite::SqliteRow| {
+        let sql: String = row.get("sql");
+        // Check if the CHECK constraint already includes 'interview_scheduled'
+        !sql.contains("interview_scheduled")
+    })
+    .unwrap_or(false);
+
+    if !needs_migration {
+        return Ok(());
+    }
+
+    tracing::info!("Migrating applications table to support 'interview_scheduled' status...");
+
+    // Get a dedicated connection from the pool for this migration
+    // This ensures PRAGMA foreign_keys = OFF stays in effect for all operations
+    let mut conn = pool.acquire().await?;
+
+    // Step 1: Disable foreign key enforcement for this connection
+    sqlx::query("PRAGMA foreign_keys = OFF")
+        .execute(&mut *conn)
+        .await?;
+
+    // Verify foreign keys are disabled
+    let fk_status: (i32,) = sqlx::query_as("PRAGMA foreign_keys")
+        .fetch_one(&mut *conn)
+        .await?;
+    tracing::info!(foreign_keys_enabled = fk_status.0, "Foreign key status");
+
+    // Clean up any leftover temp table from previous failed migrations
+    let _ = sqlx::query("DROP TABLE IF EXISTS applications_new")
+        .execute(&mut *conn)
+        .await;
+
+    // Step 2: Create new table with updated CHECK constraint
+    sqlx::query(
+        r#"
+        CREATE TABLE applications_new (
+            id TEXT PRIMARY KEY,
+            user_id TEXT NOT NULL,
+            job_id TEXT NOT NULL,
+            resume_id TEXT,
+            status TEXT DEFAULT 'submitted' CHECK (status IN (
+                'submitted', 'reviewed', 'shortlisted', 'interview_scheduled', 'interviewed', 
+                'offered', 'hired', 'rejected', 'withdrawn'
+            )),
+            current_stage TEXT DEFAULT 'Applied' CHECK (current_stage IN (
+                'Applied', 'Resume Review', 'Shortlisted', 'Interview Scheduled',
+                'Interview Completed', 'Offer Extended', 'Hired', 'Rejected'
+            )),
+            cover_letter TEXT,
+            applied_at TEXT DEFAULT (datetime('now')),
+            updated_at TEXT DEFAULT (datetime('now')),
+            archived_at TEXT,
+            FOREIGN KEY(user_id) REFERENCES users(id) ON DELETE CASCADE,
+            FOREIGN KEY(job_id) REFERENCES jobs(id) ON DELETE CASCADE,
+            FOREIGN KEY(resume_id) REFERENCES resumes(id) ON DELETE SET NULL
         )
         "#,
     )
@@ -1106,6 +2049,125 @@ async fn migrate_applications_status_constraint(pool: &SqlitePool) -> Result<(),
     Ok(())
 }
 
+/// Migration replacing the table-level UNIQUE(user_id, job_id) on applications with a
+/// partial unique index over non-archived rows, so candidates can re-apply once their
+/// previous application is archived under the re-application policy
+async fn migrate_applications_unique_constraint(pool: &SqlitePool) -> Result<(), sqlx::Error> {
+    use sqlx::Row;
+
+    let needs_migration = sqlx::query(
+        "SELECT sql FROM sqlite_master WHERE type='table' AND name='applications'",
+    )
+    .fetch_optional(pool)
+    .await?
+    .map(|row: sqlx::sqlite::SqliteRow| {
+        let sql: String = row.get("sql");
+        sql.contains("UNIQUE(user_id, job_id)")
+    })
+    .unwrap_or(false);
+
+    if needs_migration {
+        tracing::info!("Migrating applications table to allow archived re-applications...");
+
+        // Dedicated connection so PRAGMA foreign_keys = OFF applies to every statement
+        let mut conn = pool.acquire().await?;
+
+        sqlx::query("PRAGMA foreign_keys = OFF")
+            .execute(&mut *conn)
+            .await?;
+
+        let _ = sqlx::query("DROP TABLE IF EXISTS applications_new")
+            .execute(&mut *conn)
+            .await;
+
+        sqlx::query(
+            r#"
+            CREATE TABLE applications_new (
+                id TEXT PRIMARY KEY,
+                user_id TEXT NOT NULL,
+                job_id TEXT NOT NULL,
+                resume_id TEXT,
+                status TEXT DEFAULT 'submitted' CHECK (status IN (
+                    'submitted', 'reviewed', 'shortlisted', 'interview_scheduled', 'interviewed', 
+                    'offered', 'hired', 'rejected', 'withdrawn'
+                )),
+                current_stage TEXT DEFAULT 'Applied' CHECK (current_stage IN (
+                    'Applied', 'Resume Review', 'Shortlisted', 'Interview Scheduled',
+                    'Interview Completed', 'Offer Extended', 'Hired', 'Rejected'
+                )),
+                cover_letter TEXT,
+                applied_at TEXT DEFAULT (datetime('now')),
+                updated_at TEXT DEFAULT (datetime('now')),
+                archived_at TEXT,
+                FOREIGN KEY(user_id) REFERENCES users(id) ON DELETE CASCADE,
+                FOREIGN KEY(job_id) REFERENCES jobs(id) ON DELETE CASCADE,
+                FOREIGN KEY(resume_id) REFERENCES resumes(id) ON DELETE SET NULL
+            )
+            "#,
+        )
+        .execute(&mut *conn)
+        .await?;
+
+        let copy_result = sqlx::query(
+            r#"
+            INSERT INTO applications_new (id, user_id, job_id, resume_id, status, current_stage, cover_letter, applied_at, updated_at)
+            SELECT id, user_id, job_id, resume_id, status, current_stage, cover_letter, applied_at, updated_at
+            FROM applications
+            "#,
+        )
+        .execute(&mut *conn)
+        .await;
+
+        let swap_result = match copy_result {
+            Ok(_) => match sqlx::query("DROP TABLE applications")
+                .execute(&mut *conn)
+                .await
+            {
+                Ok(_) => sqlx::query("ALTER TABLE applications_new RENAME TO applications")
+                    .execute(&mut *conn)
+                    .await
+                    .map(|_| ()),
+                Err(e) => Err(e),
+            },
+            Err(e) => Err(e),
+        };
+
+        if let Err(e) = swap_result {
+            tracing::error!(error = %e, "Failed to migrate applications unique constraint");
+            let _ = sqlx::query("DROP TABLE IF EXISTS applications_new")
+                .execute(&mut *conn)
+                .await;
+            sqlx::query("PRAGMA foreign_keys = ON")
+                .execute(&mut *conn)
+                .await?;
+            return Err(e);
+        }
+
+        for index in [
+            "CREATE INDEX IF NOT EXISTS idx_applications_user ON applications(user_id)",
+            "CREATE INDEX IF NOT EXISTS idx_applications_job ON applications(job_id)",
+            "CREATE INDEX IF NOT EXISTS idx_applications_status ON applications(status)",
+            "CREATE INDEX IF NOT EXISTS idx_applications_stage ON applications(current_stage)",
+        ] {
+            let _ = sqlx::query(index).execute(&mut *conn).await;
+        }
+
+        sqlx::query("PRAGMA foreign_keys = ON")
+            .execute(&mut *conn)
+            .await?;
+
+        tracing::info!("Successfully migrated applications unique constraint");
+    }
+
+    sqlx::query(
+        "CREATE UNIQUE INDEX IF NOT EXISTS idx_applications_user_job_active ON applications(user_id, job_id) WHERE archived_at IS NULL",
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
 async fn create_interview_tables(pool: &SqlitePool) -> Result<(), sqlx::Error> {
     // Interviews table
     sqlx::query(
@@ -1136,6 +2198,26 @@ async fn create_interview_tables(pool: &SqlitePool) -> Result<(), sqlx::Error> {
     .execute(pool)
     .await?;
 
+    // Zone the interview was booked in; scheduled_date itself is stored in UTC
+    let _ = sqlx::query("ALTER TABLE interviews ADD COLUMN scheduled_timezone TEXT")
+        .execute(pool)
+        .await;
+
+    normalize_interview_timezones(pool).await?;
+
+    // Newest change already reflected locally, compared against Google's `updated` on sync
+    let _ = sqlx::query("ALTER TABLE interviews ADD COLUMN calendar_synced_at TEXT")
+        .execute(pool)
+        .await;
+
+    // Candidate-facing preparation notes and when interview feedback was released to them
+    let _ = sqlx::query("ALTER TABLE interviews ADD COLUMN preparation_notes TEXT")
+        .execute(pool)
+        .await;
+    let _ = sqlx::query("ALTER TABLE interviews ADD COLUMN feedback_released_at TEXT")
+        .execute(pool)
+        .await;
+
     // Create panelists table for storing frequently used interview panelists
     sqlx::query(
         r#"
@@ -1176,28 +2258,154 @@ async fn create_interview_tables(pool: &SqlitePool) -> Result<(), sqlx::Error> {
     .execute(pool)
     .await?;
 
+    // Per-user token of the subscribable interview calendar; only its hash is kept
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS calendar_feed_tokens (
+            user_id TEXT PRIMARY KEY,
+            token_hash TEXT NOT NULL UNIQUE,
+            created_at TEXT DEFAULT (datetime('now')),
+            last_accessed_at TEXT,
+            FOREIGN KEY(user_id) REFERENCES users(id) ON DELETE CASCADE
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // Per-user token of the candidate's application status feed; only its hash is kept
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS application_feed_tokens (
+            user_id TEXT PRIMARY KEY,
+            token_hash TEXT NOT NULL UNIQUE,
+            created_at TEXT DEFAULT (datetime('now')),
+            last_accessed_at TEXT,
+            FOREIGN KEY(user_id) REFERENCES users(id) ON DELETE CASCADE
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // Notes shared by the hiring team after an interview
+    let _ = sqlx::query("ALTER TABLE interviews ADD COLUMN shared_notes TEXT")
+        .execute(pool)
+        .await;
+    let _ = sqlx::query("ALTER TABLE interviews ADD COLUMN shared_notes_updated_by TEXT")
+        .execute(pool)
+        .await;
+    let _ = sqlx::query("ALTER TABLE interviews ADD COLUMN shared_notes_updated_at TEXT")
+        .execute(pool)
+        .await;
+
+    // Recordings and transcripts uploaded for a completed interview
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS interview_artifacts (
+            id TEXT PRIMARY KEY,
+            interview_id TEXT NOT NULL,
+            kind TEXT NOT NULL CHECK (kind IN ('recording', 'transcript')),
+            filename TEXT NOT NULL,
+            original_filename TEXT,
+            content_type TEXT NOT NULL,
+            file_size INTEGER NOT NULL,
+            uploaded_by TEXT NOT NULL,
+            created_at TEXT DEFAULT (datetime('now')),
+            FOREIGN KEY(interview_id) REFERENCES interviews(id) ON DELETE CASCADE,
+            FOREIGN KEY(uploaded_by) REFERENCES users(id)
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
     // Offer letters table
     sqlx::query(
         r#"
-        CREATE TABLE IF NOT EXISTS offer_letters (
+        CREATE TABLE IF NOT EXISTS offer_letters (
+            id TEXT PRIMARY KEY,
+            candidate_id TEXT NOT NULL,
+            job_id TEXT,
+            job_title TEXT NOT NULL,
+            salary REAL,
+            start_date TEXT,
+            benefits TEXT,
+            additional_terms TEXT,
+            content TEXT NOT NULL,
+            pdf_url TEXT,
+            logo_url TEXT,
+            signature_url TEXT,
+            sent_at TEXT,
+            created_by TEXT,
+            created_at TEXT DEFAULT (datetime('now')),
+            FOREIGN KEY(candidate_id) REFERENCES users(id),
+            FOREIGN KEY(job_id) REFERENCES jobs(id),
+            FOREIGN KEY(created_by) REFERENCES users(id)
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // Salary bands per job level, optionally scoped to a company (NULL = default band)
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS compensation_bands (
+            id TEXT PRIMARY KEY,
+            company_id TEXT,
+            level TEXT NOT NULL,
+            currency TEXT NOT NULL DEFAULT 'USD',
+            min_salary REAL NOT NULL,
+            max_salary REAL NOT NULL,
+            created_by TEXT,
+            created_at TEXT DEFAULT (datetime('now')),
+            updated_at TEXT DEFAULT (datetime('now')),
+            FOREIGN KEY(company_id) REFERENCES companies(id) ON DELETE CASCADE,
+            FOREIGN KEY(created_by) REFERENCES users(id)
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // Audit trail of offers approved above their band
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS compensation_overrides (
+            id TEXT PRIMARY KEY,
+            band_id TEXT,
+            job_id TEXT NOT NULL,
+            candidate_id TEXT,
+            salary REAL NOT NULL,
+            band_min REAL NOT NULL,
+            band_max REAL NOT NULL,
+            justification TEXT NOT NULL,
+            approved_by TEXT NOT NULL,
+            created_at TEXT DEFAULT (datetime('now')),
+            FOREIGN KEY(band_id) REFERENCES compensation_bands(id) ON DELETE SET NULL,
+            FOREIGN KEY(job_id) REFERENCES jobs(id) ON DELETE CASCADE,
+            FOREIGN KEY(approved_by) REFERENCES users(id)
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // Calendar operations that failed and are retried in the background
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS calendar_retries (
             id TEXT PRIMARY KEY,
-            candidate_id TEXT NOT NULL,
-            job_id TEXT,
-            job_title TEXT NOT NULL,
-            salary REAL,
-            start_date TEXT,
-            benefits TEXT,
-            additional_terms TEXT,
-            content TEXT NOT NULL,
-            pdf_url TEXT,
-            logo_url TEXT,
-            signature_url TEXT,
-            sent_at TEXT,
-            created_by TEXT,
+            interview_id TEXT NOT NULL,
+            operation TEXT NOT NULL CHECK (operation IN ('create_meet_link')),
+            status TEXT NOT NULL DEFAULT 'pending' CHECK (status IN ('pending', 'running', 'succeeded', 'failed', 'skipped')),
+            attempts INTEGER NOT NULL DEFAULT 0,
+            next_attempt_at TEXT NOT NULL,
+            last_error TEXT,
             created_at TEXT DEFAULT (datetime('now')),
-            FOREIGN KEY(candidate_id) REFERENCES users(id),
-            FOREIGN KEY(job_id) REFERENCES jobs(id),
-            FOREIGN KEY(created_by) REFERENCES users(id)
+            updated_at TEXT DEFAULT (datetime('now')),
+            FOREIGN KEY(interview_id) REFERENCES interviews(id) ON DELETE CASCADE
         )
         "#,
     )
@@ -1231,6 +2439,40 @@ async fn create_interview_tables(pool: &SqlitePool) -> Result<(), sqlx::Error> {
     Ok(())
 }
 
+/// Convert interviews stored with their booking offset to UTC, keeping the offset as their zone
+async fn normalize_interview_timezones(pool: &SqlitePool) -> Result<(), sqlx::Error> {
+    let rows: Vec<(String, String)> = sqlx::query_as(
+        "SELECT id, scheduled_date FROM interviews WHERE scheduled_timezone IS NULL",
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let mut converted = 0;
+    for (id, scheduled_date) in rows {
+        let (utc, zone) = match crate::common::timezone::normalize_schedule(&scheduled_date, None) {
+            Ok(normalized) => normalized,
+            Err(e) => {
+                warn!(interview_id = %id, error = %e, "Leaving interview with unparseable date as-is");
+                continue;
+            }
+        };
+
+        sqlx::query("UPDATE interviews SET scheduled_date = ?, scheduled_timezone = ? WHERE id = ?")
+            .bind(utc.to_rfc3339())
+            .bind(zone)
+            .bind(&id)
+            .execute(pool)
+            .await?;
+        converted += 1;
+    }
+
+    if converted > 0 {
+        info!(count = converted, "Normalized interview dates to UTC");
+    }
+
+    Ok(())
+}
+
 async fn create_messaging_tables(pool: &SqlitePool) -> Result<(), sqlx::Error> {
     // Conversation messages table
     sqlx::query(
@@ -1268,6 +2510,139 @@ async fn create_messaging_tables(pool: &SqlitePool) -> Result<(), sqlx::Error> {
     .execute(pool)
     .await?;
 
+    // Messages sent to a segment of candidates, fanned out by the broadcast task
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS broadcasts (
+            id TEXT PRIMARY KEY,
+            segment_type TEXT NOT NULL CHECK (segment_type IN ('job_applicants', 'stage', 'talent_pool')),
+            job_id TEXT,
+            stage TEXT,
+            channel TEXT NOT NULL CHECK (channel IN ('message', 'email', 'both')),
+            subject TEXT,
+            body TEXT NOT NULL,
+            status TEXT NOT NULL DEFAULT 'queued' CHECK (status IN ('queued', 'sending', 'completed', 'cancelled')),
+            total_recipients INTEGER NOT NULL DEFAULT 0,
+            created_by TEXT NOT NULL,
+            created_at TEXT DEFAULT (datetime('now')),
+            completed_at TEXT,
+            FOREIGN KEY(job_id) REFERENCES jobs(id) ON DELETE SET NULL,
+            FOREIGN KEY(created_by) REFERENCES users(id)
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // Delivery status of a broadcast per candidate
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS broadcast_recipients (
+            broadcast_id TEXT NOT NULL,
+            user_id TEXT NOT NULL,
+            application_id TEXT,
+            status TEXT NOT NULL DEFAULT 'pending' CHECK (status IN ('pending', 'sent', 'failed', 'skipped')),
+            attempts INTEGER NOT NULL DEFAULT 0,
+            message_id TEXT,
+            error TEXT,
+            sent_at TEXT,
+            PRIMARY KEY (broadcast_id, user_id),
+            FOREIGN KEY(broadcast_id) REFERENCES broadcasts(id) ON DELETE CASCADE,
+            FOREIGN KEY(user_id) REFERENCES users(id) ON DELETE CASCADE,
+            FOREIGN KEY(application_id) REFERENCES applications(id) ON DELETE SET NULL
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // Admin messages and candidate emails held until send_at (UTC), then sent by the scheduler
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS scheduled_messages (
+            id TEXT PRIMARY KEY,
+            kind TEXT NOT NULL CHECK (kind IN ('message', 'email')),
+            user_id TEXT NOT NULL,
+            application_id TEXT,
+            job_id TEXT,
+            subject TEXT,
+            body TEXT NOT NULL,
+            cc TEXT,
+            send_at TEXT NOT NULL,
+            timezone TEXT NOT NULL DEFAULT 'UTC',
+            business_hours INTEGER NOT NULL DEFAULT 0,
+            status TEXT NOT NULL DEFAULT 'scheduled' CHECK (status IN ('scheduled', 'sending', 'sent', 'cancelled', 'failed')),
+            attempts INTEGER NOT NULL DEFAULT 0,
+            error TEXT,
+            message_id TEXT,
+            created_by TEXT NOT NULL,
+            created_at TEXT DEFAULT (datetime('now')),
+            updated_at TEXT DEFAULT (datetime('now')),
+            sent_at TEXT,
+            FOREIGN KEY(user_id) REFERENCES users(id) ON DELETE CASCADE,
+            FOREIGN KEY(application_id) REFERENCES applications(id) ON DELETE CASCADE,
+            FOREIGN KEY(job_id) REFERENCES jobs(id) ON DELETE CASCADE,
+            FOREIGN KEY(created_by) REFERENCES users(id)
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // Canned responses, expanded from `/shortcut` in admin messages and candidate emails
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS message_snippets (
+            id TEXT PRIMARY KEY,
+            shortcut TEXT NOT NULL UNIQUE,
+            title TEXT NOT NULL,
+            body TEXT NOT NULL,
+            created_by TEXT NOT NULL,
+            created_at TEXT DEFAULT (datetime('now')),
+            updated_at TEXT DEFAULT (datetime('now')),
+            FOREIGN KEY(created_by) REFERENCES users(id)
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // Admin inbox state of a candidate's conversation; no row means open and unassigned
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS conversation_states (
+            user_id TEXT PRIMARY KEY,
+            state TEXT NOT NULL DEFAULT 'open' CHECK (state IN ('open', 'snoozed', 'closed')),
+            assignee_id TEXT,
+            snoozed_until TEXT,
+            updated_by TEXT,
+            updated_at TEXT DEFAULT (datetime('now')),
+            FOREIGN KEY(user_id) REFERENCES users(id),
+            FOREIGN KEY(assignee_id) REFERENCES users(id)
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // Conversations archived by the retention policy drop out of the live inbox
+    let _ = sqlx::query("ALTER TABLE conversation_states ADD COLUMN archived_at TEXT")
+        .execute(pool)
+        .await;
+
+    // When each admin was last routed a conversation, for round robin between those online
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS message_routing_state (
+            admin_id TEXT PRIMARY KEY,
+            last_routed_at TEXT NOT NULL,
+            FOREIGN KEY(admin_id) REFERENCES users(id)
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
     Ok(())
 }
 
@@ -1305,6 +2680,27 @@ async fn create_system_tables(pool: &SqlitePool) -> Result<(), sqlx::Error> {
     .execute(pool)
     .await?;
 
+    // Recommended jobs a candidate dismissed, with the score and explanation they were shown,
+    // kept to tune the recommendation rules
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS job_recommendation_dismissals (
+            id TEXT PRIMARY KEY,
+            user_id TEXT NOT NULL,
+            job_id TEXT NOT NULL,
+            reason TEXT,
+            score INTEGER,
+            explanation TEXT,
+            dismissed_at TEXT DEFAULT (datetime('now')),
+            UNIQUE(user_id, job_id),
+            FOREIGN KEY(user_id) REFERENCES users(id) ON DELETE CASCADE,
+            FOREIGN KEY(job_id) REFERENCES jobs(id) ON DELETE CASCADE
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
     // User OAuth tokens table (for storing per-user OAuth tokens like YouTube)
     sqlx::query(
         r#"
@@ -1359,6 +2755,8 @@ async fn create_system_tables(pool: &SqlitePool) -> Result<(), sqlx::Error> {
             model TEXT NOT NULL,
             purpose TEXT,
             tokens_used INTEGER,
+            prompt_tokens INTEGER,
+            completion_tokens INTEGER,
             cost_estimate REAL,
             created_at TEXT DEFAULT (datetime('now')),
             FOREIGN KEY(user_id) REFERENCES users(id)
@@ -1368,6 +2766,198 @@ async fn create_system_tables(pool: &SqlitePool) -> Result<(), sqlx::Error> {
     .execute(pool)
     .await?;
 
+    // Sensitive activity (exports, bulk rejections, auth failures) for anomaly detection
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS security_activity (
+            id TEXT PRIMARY KEY,
+            activity TEXT NOT NULL,
+            actor_id TEXT,
+            ip_address TEXT,
+            item_count INTEGER NOT NULL DEFAULT 1,
+            path TEXT,
+            created_at TEXT NOT NULL
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // Security alerts raised by the monitoring service
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS security_events (
+            id TEXT PRIMARY KEY,
+            event_type TEXT NOT NULL,
+            severity TEXT NOT NULL CHECK (severity IN ('medium', 'high', 'critical')),
+            actor_id TEXT,
+            ip_address TEXT,
+            description TEXT NOT NULL,
+            details TEXT,
+            notified INTEGER NOT NULL DEFAULT 0,
+            acknowledged_by TEXT,
+            acknowledged_at TEXT,
+            created_at TEXT NOT NULL
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // User-generated content flagged or held by the moderation pipeline
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS moderation_queue (
+            id TEXT PRIMARY KEY,
+            content_type TEXT NOT NULL CHECK (content_type IN ('bio', 'testimonial', 'message', 'video_title')),
+            content_id TEXT NOT NULL,
+            user_id TEXT NOT NULL,
+            excerpt TEXT NOT NULL,
+            action TEXT NOT NULL CHECK (action IN ('flag', 'hold')),
+            source TEXT NOT NULL,
+            reasons TEXT NOT NULL DEFAULT '[]',
+            status TEXT NOT NULL DEFAULT 'pending' CHECK (status IN ('pending', 'approved', 'removed')),
+            reviewed_by TEXT,
+            reviewed_at TEXT,
+            review_notes TEXT,
+            created_at TEXT NOT NULL
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // Legal holds: while unreleased, the user's or application's data must not be deleted
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS legal_holds (
+            id TEXT PRIMARY KEY,
+            subject_type TEXT NOT NULL CHECK (subject_type IN ('user', 'application')),
+            subject_id TEXT NOT NULL,
+            user_id TEXT NOT NULL,
+            reason TEXT NOT NULL,
+            matter_reference TEXT,
+            placed_by TEXT NOT NULL,
+            placed_at TEXT DEFAULT (datetime('now')),
+            released_by TEXT,
+            released_at TEXT,
+            release_reason TEXT
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // Per-admin choices for the activity feed and the daily digest email
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS activity_preferences (
+            user_id TEXT PRIMARY KEY,
+            digest_enabled INTEGER NOT NULL DEFAULT 0,
+            digest_hour INTEGER NOT NULL DEFAULT 8 CHECK (digest_hour BETWEEN 0 AND 23),
+            applications INTEGER NOT NULL DEFAULT 1,
+            stage_changes INTEGER NOT NULL DEFAULT 1,
+            interviews INTEGER NOT NULL DEFAULT 1,
+            offers INTEGER NOT NULL DEFAULT 1,
+            last_digest_at TEXT,
+            updated_at TEXT DEFAULT (datetime('now')),
+            FOREIGN KEY(user_id) REFERENCES users(id)
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // Audit trail of holds placed and released, and of deletions they blocked
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS legal_hold_events (
+            id TEXT PRIMARY KEY,
+            hold_id TEXT NOT NULL,
+            action TEXT NOT NULL CHECK (action IN ('placed', 'released', 'blocked')),
+            actor_id TEXT,
+            details TEXT,
+            created_at TEXT DEFAULT (datetime('now')),
+            FOREIGN KEY(hold_id) REFERENCES legal_holds(id)
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+
+    // Every SMS attempt, including ones skipped for quiet hours
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS sms_deliveries (
+            id TEXT PRIMARY KEY,
+            user_id TEXT,
+            phone TEXT NOT NULL,
+            kind TEXT NOT NULL,
+            reference_id TEXT,
+            body TEXT NOT NULL,
+            provider TEXT,
+            status TEXT NOT NULL CHECK (status IN ('sent', 'failed', 'skipped')),
+            provider_message_id TEXT,
+            error TEXT,
+            created_at TEXT DEFAULT (datetime('now')),
+            FOREIGN KEY(user_id) REFERENCES users(id) ON DELETE SET NULL
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // Every WhatsApp template message sent or attempted
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS whatsapp_messages (
+            id TEXT PRIMARY KEY,
+            user_id TEXT,
+            phone TEXT NOT NULL,
+            event TEXT NOT NULL,
+            reference_id TEXT,
+            template TEXT NOT NULL,
+            status TEXT NOT NULL CHECK (status IN ('sent', 'failed')),
+            provider_message_id TEXT,
+            error TEXT,
+            created_at TEXT DEFAULT (datetime('now')),
+            FOREIGN KEY(user_id) REFERENCES users(id) ON DELETE SET NULL
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // Slack and Teams incoming webhooks that recruiting events for a company or job are
+    // posted to, with a toggle per event
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS chat_webhooks (
+            id TEXT PRIMARY KEY,
+            provider TEXT NOT NULL CHECK (provider IN ('slack', 'teams')),
+            name TEXT NOT NULL,
+            webhook_url TEXT NOT NULL,
+            company_id TEXT,
+            job_id TEXT,
+            notify_new_application INTEGER NOT NULL DEFAULT 1,
+            notify_interview_feedback INTEGER NOT NULL DEFAULT 1,
+            notify_offer_accepted INTEGER NOT NULL DEFAULT 1,
+            is_active INTEGER NOT NULL DEFAULT 1,
+            last_error TEXT,
+            last_delivered_at TEXT,
+            created_by TEXT NOT NULL,
+            created_at TEXT DEFAULT (datetime('now')),
+            updated_at TEXT DEFAULT (datetime('now')),
+            CHECK ((company_id IS NULL) != (job_id IS NULL)),
+            FOREIGN KEY(company_id) REFERENCES companies(id) ON DELETE CASCADE,
+            FOREIGN KEY(job_id) REFERENCES jobs(id) ON DELETE CASCADE
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
     Ok(())
 }
 
@@ -1378,6 +2968,14 @@ async fn create_indexes(pool: &SqlitePool) -> Result<(), sqlx::Error> {
         "CREATE INDEX IF NOT EXISTS idx_education_user_id ON education(user_id)",
         "CREATE INDEX IF NOT EXISTS idx_testimonials_user ON testimonials(user_id)",
         "CREATE INDEX IF NOT EXISTS idx_testimonials_featured ON testimonials(featured, approved)",
+        "CREATE UNIQUE INDEX IF NOT EXISTS idx_profiles_public_slug ON profiles(public_slug)",
+        "CREATE INDEX IF NOT EXISTS idx_profiles_phone_index ON profiles(phone_index)",
+        "CREATE INDEX IF NOT EXISTS idx_contact_verifications_user ON contact_verifications(user_id, channel)",
+        "CREATE INDEX IF NOT EXISTS idx_sms_deliveries_reference ON sms_deliveries(kind, reference_id)",
+        "CREATE INDEX IF NOT EXISTS idx_sms_deliveries_created ON sms_deliveries(created_at)",
+        "CREATE INDEX IF NOT EXISTS idx_whatsapp_messages_reference ON whatsapp_messages(event, reference_id)",
+        "CREATE INDEX IF NOT EXISTS idx_chat_webhooks_company ON chat_webhooks(company_id)",
+        "CREATE INDEX IF NOT EXISTS idx_chat_webhooks_job ON chat_webhooks(job_id)",
         
         // Company indexes
         "CREATE INDEX IF NOT EXISTS idx_companies_name ON companies(name)",
@@ -1399,17 +2997,60 @@ async fn create_indexes(pool: &SqlitePool) -> Result<(), sqlx::Error> {
         "CREATE INDEX IF NOT EXISTS idx_job_status_history_job_id ON job_status_history(job_id)",
         "CREATE INDEX IF NOT EXISTS idx_job_status_history_changed_at ON job_status_history(job_id, changed_at)",
         "CREATE INDEX IF NOT EXISTS idx_job_views_job_date ON job_views(job_id, viewed_at)",
+        "CREATE INDEX IF NOT EXISTS idx_job_views_user_date ON job_views(user_id, viewed_at)",
         "CREATE INDEX IF NOT EXISTS idx_job_social_images_job_id ON job_social_images(job_id)",
+        "CREATE INDEX IF NOT EXISTS idx_job_short_link_clicks_link ON job_short_link_clicks(short_link_id, clicked_at)",
+        "CREATE INDEX IF NOT EXISTS idx_job_short_link_clicks_job ON job_short_link_clicks(job_id, channel)",
+        "CREATE INDEX IF NOT EXISTS idx_scheduled_social_posts_due ON scheduled_social_posts(status, scheduled_for)",
+        "CREATE INDEX IF NOT EXISTS idx_scheduled_social_posts_job ON scheduled_social_posts(job_id)",
+        "CREATE INDEX IF NOT EXISTS idx_job_promotions_job ON job_promotions(job_id, start_date)",
+        "CREATE INDEX IF NOT EXISTS idx_applications_source ON applications(job_id, source)",
+        "CREATE INDEX IF NOT EXISTS idx_applications_waitlist ON applications(job_id, waitlisted_at)",
+        "CREATE UNIQUE INDEX IF NOT EXISTS idx_sla_policies_scope ON sla_policies(COALESCE(job_id, ''), stage)",
+        "CREATE INDEX IF NOT EXISTS idx_application_sla_breaches_open ON application_sla_breaches(resolved_at, job_id)",
+        "CREATE INDEX IF NOT EXISTS idx_candidate_surveys_job ON candidate_surveys(job_id, responded_at)",
+        "CREATE INDEX IF NOT EXISTS idx_eeo_responses_job ON eeo_responses(job_id)",
+        "CREATE INDEX IF NOT EXISTS idx_consents_user ON consents(user_id, consent_type, created_at)",
+        "CREATE INDEX IF NOT EXISTS idx_broadcast_recipients_pending ON broadcast_recipients(status, broadcast_id)",
+        "CREATE INDEX IF NOT EXISTS idx_scheduled_messages_due ON scheduled_messages(status, send_at)",
+        "CREATE INDEX IF NOT EXISTS idx_scheduled_messages_user ON scheduled_messages(user_id, created_at)",
+        "CREATE INDEX IF NOT EXISTS idx_conversation_states_assignee ON conversation_states(assignee_id, state)",
+        "CREATE UNIQUE INDEX IF NOT EXISTS idx_legal_holds_active ON legal_holds(subject_type, subject_id) WHERE released_at IS NULL",
+        "CREATE INDEX IF NOT EXISTS idx_legal_holds_user ON legal_holds(user_id, released_at)",
+        "CREATE INDEX IF NOT EXISTS idx_legal_hold_events_hold ON legal_hold_events(hold_id, created_at)",
+        "CREATE INDEX IF NOT EXISTS idx_knockout_rules_job ON knockout_rules(job_id, is_active)",
+        "CREATE INDEX IF NOT EXISTS idx_user_identities_user ON user_identities(user_id)",
+        "CREATE INDEX IF NOT EXISTS idx_guest_claims_email ON guest_claims(email, claimed_at)",
+        "CREATE INDEX IF NOT EXISTS idx_jwt_signing_keys_status ON jwt_signing_keys(status, created_at)",
+        "CREATE INDEX IF NOT EXISTS idx_saved_reports_schedule ON saved_reports(schedule)",
+        "CREATE INDEX IF NOT EXISTS idx_analytics_application_facts_applied ON analytics_application_facts(applied_at)",
+        "CREATE INDEX IF NOT EXISTS idx_analytics_application_facts_job ON analytics_application_facts(job_id, applied_at)",
+        "CREATE INDEX IF NOT EXISTS idx_analytics_status_durations_application ON analytics_status_durations(application_id)",
+        "CREATE INDEX IF NOT EXISTS idx_upload_sessions_user ON upload_sessions(user_id, status)",
+        "CREATE INDEX IF NOT EXISTS idx_upload_sessions_expires ON upload_sessions(status, expires_at)",
+        "CREATE INDEX IF NOT EXISTS idx_storage_usage_owner ON storage_usage(owner_type, owner_id)",
+        "CREATE INDEX IF NOT EXISTS idx_orphaned_files_first_seen ON orphaned_files(deleted_at, first_seen_at)",
+        "CREATE INDEX IF NOT EXISTS idx_account_merge_requests_source ON account_merge_requests(source_user_id, status)",
+        "CREATE INDEX IF NOT EXISTS idx_application_knockouts_application ON application_knockouts(application_id)",
+        "CREATE INDEX IF NOT EXISTS idx_application_feedback_versions_application ON application_feedback_versions(application_id, version_number)",
+        "CREATE INDEX IF NOT EXISTS idx_application_knockouts_job ON application_knockouts(job_id, created_at)",
+        "CREATE UNIQUE INDEX IF NOT EXISTS idx_departments_scope ON departments(COALESCE(company_id, ''), name)",
+        "CREATE INDEX IF NOT EXISTS idx_job_recruiters_user ON job_recruiters(user_id)",
+        "CREATE INDEX IF NOT EXISTS idx_jobs_hiring_manager ON jobs(hiring_manager_id)",
+        "CREATE INDEX IF NOT EXISTS idx_jobs_deleted_at ON jobs(deleted_at)",
         
         // Application indexes
         "CREATE INDEX IF NOT EXISTS idx_applications_user_job ON applications(user_id, job_id)",
         "CREATE INDEX IF NOT EXISTS idx_applications_status ON applications(status)",
         "CREATE INDEX IF NOT EXISTS idx_applications_current_stage ON applications(current_stage)",
         "CREATE INDEX IF NOT EXISTS idx_applications_job_stage ON applications(job_id, current_stage)",
+        "CREATE INDEX IF NOT EXISTS idx_application_resume_history_app ON application_resume_history(application_id, changed_at)",
+        "CREATE INDEX IF NOT EXISTS idx_resumes_root ON resumes(root_resume_id)",
         "CREATE INDEX IF NOT EXISTS idx_stage_history_application_id ON stage_history(application_id)",
         "CREATE INDEX IF NOT EXISTS idx_stage_history_changed_at ON stage_history(application_id, changed_at)",
         "CREATE INDEX IF NOT EXISTS idx_stage_history_stage ON stage_history(stage)",
         "CREATE INDEX IF NOT EXISTS idx_video_submissions_application_id ON video_submissions(application_id)",
+        "CREATE INDEX IF NOT EXISTS idx_document_requests_candidate ON document_requests(candidate_id, status)",
         
         // Interview indexes
         "CREATE INDEX IF NOT EXISTS idx_interviews_application_id ON interviews(application_id)",
@@ -1417,7 +3058,12 @@ async fn create_indexes(pool: &SqlitePool) -> Result<(), sqlx::Error> {
         "CREATE INDEX IF NOT EXISTS idx_interviews_job_id ON interviews(job_id)",
         "CREATE INDEX IF NOT EXISTS idx_interviews_scheduled_date ON interviews(scheduled_date)",
         "CREATE INDEX IF NOT EXISTS idx_interviews_status ON interviews(status)",
+        "CREATE INDEX IF NOT EXISTS idx_calendar_retries_due ON calendar_retries(status, next_attempt_at)",
+        "CREATE INDEX IF NOT EXISTS idx_interview_interviewers_user ON interview_interviewers(user_id)",
+        "CREATE INDEX IF NOT EXISTS idx_interview_artifacts_interview ON interview_artifacts(interview_id)",
         "CREATE INDEX IF NOT EXISTS idx_offer_letters_candidate_id ON offer_letters(candidate_id)",
+        "CREATE UNIQUE INDEX IF NOT EXISTS idx_compensation_bands_scope ON compensation_bands(COALESCE(company_id, ''), level)",
+        "CREATE INDEX IF NOT EXISTS idx_compensation_overrides_job_id ON compensation_overrides(job_id)",
         "CREATE INDEX IF NOT EXISTS idx_email_history_application_id ON email_history(application_id)",
         "CREATE INDEX IF NOT EXISTS idx_email_history_candidate_id ON email_history(candidate_id)",
         "CREATE INDEX IF NOT EXISTS idx_email_history_job_id ON email_history(job_id)",
@@ -1429,11 +3075,16 @@ async fn create_indexes(pool: &SqlitePool) -> Result<(), sqlx::Error> {
         // System indexes
         "CREATE INDEX IF NOT EXISTS idx_ai_usage_logs_user_id ON ai_usage_logs(user_id)",
         "CREATE INDEX IF NOT EXISTS idx_ai_usage_logs_created_at ON ai_usage_logs(created_at)",
+        "CREATE INDEX IF NOT EXISTS idx_security_activity_kind_time ON security_activity(activity, created_at)",
+        "CREATE INDEX IF NOT EXISTS idx_security_events_created_at ON security_events(created_at)",
+        "CREATE INDEX IF NOT EXISTS idx_moderation_queue_status ON moderation_queue(status, created_at)",
+        "CREATE INDEX IF NOT EXISTS idx_moderation_queue_content ON moderation_queue(content_type, content_id)",
         
         // Saved jobs indexes
         "CREATE INDEX IF NOT EXISTS idx_saved_jobs_user_id ON saved_jobs(user_id)",
         "CREATE INDEX IF NOT EXISTS idx_saved_jobs_job_id ON saved_jobs(job_id)",
         "CREATE INDEX IF NOT EXISTS idx_saved_jobs_user_job ON saved_jobs(user_id, job_id)",
+        "CREATE INDEX IF NOT EXISTS idx_job_recommendation_dismissals_user ON job_recommendation_dismissals(user_id)",
     ];
 
     for index_sql in indexes {
@@ -1443,6 +3094,62 @@ async fn create_indexes(pool: &SqlitePool) -> Result<(), sqlx::Error> {
     Ok(())
 }
 
+/// Full-text indexes behind the admin global search and message search
+///
+/// Trigram tokens give case-insensitive substring matches. Each index mirrors its source table
+/// by rowid and is kept current by triggers; it is rebuilt on every start so it also recovers
+/// from restores or a VACUUM renumbering rowids.
+async fn create_search_index(pool: &SqlitePool) -> Result<(), sqlx::Error> {
+    let sources = [
+        ("users", &["name", "email"][..]),
+        ("jobs", &["title", "company"][..]),
+        ("companies", &["name"][..]),
+        ("conversation_messages", &["message"][..]),
+    ];
+
+    for (table, columns) in sources {
+        let fts = format!("{}_fts", table);
+        let cols = columns.join(", ");
+        let new_values = columns
+            .iter()
+            .map(|c| format!("new.{}", c))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let old_values = columns
+            .iter()
+            .map(|c| format!("old.{}", c))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let insert_new = format!(
+            "INSERT INTO {fts} (rowid, {cols}) VALUES (new.rowid, {new_values});"
+        );
+        let delete_old = format!(
+            "INSERT INTO {fts} ({fts}, rowid, {cols}) VALUES ('delete', old.rowid, {old_values});"
+        );
+
+        let statements = [
+            format!(
+                "CREATE VIRTUAL TABLE IF NOT EXISTS {fts} USING fts5({cols}, content='{table}', content_rowid='rowid', tokenize='trigram')"
+            ),
+            format!(
+                "CREATE TRIGGER IF NOT EXISTS {fts}_insert AFTER INSERT ON {table} BEGIN {insert_new} END"
+            ),
+            format!(
+                "CREATE TRIGGER IF NOT EXISTS {fts}_delete AFTER DELETE ON {table} BEGIN {delete_old} END"
+            ),
+            format!(
+                "CREATE TRIGGER IF NOT EXISTS {fts}_update AFTER UPDATE OF {cols} ON {table} BEGIN {delete_old} {insert_new} END"
+            ),
+            format!("INSERT INTO {fts} ({fts}) VALUES ('rebuild')"),
+        ];
+        for sql in statements {
+            sqlx::query(&sql).execute(pool).await?;
+        }
+    }
+
+    Ok(())
+}
+
 /// Sync current_stage field with status for existing applications
 /// This ensures the Applications by Stage analytics shows correct data
 async fn sync_application_stages(pool: &SqlitePool) -> Result<(), sqlx::Error> {
@@ -1481,3 +3188,40 @@ async fn sync_application_stages(pool: &SqlitePool) -> Result<(), sqlx::Error> {
 
     Ok(())
 }
+
+/// Charge files stored before accounting existed (or missed by it) and drop ledger rows
+/// whose file has since been deleted
+async fn sync_storage_usage(pool: &SqlitePool) -> Result<(), sqlx::Error> {
+    let statements = [
+        r#"
+        INSERT OR IGNORE INTO storage_usage (category, file_id, owner_type, owner_id, bytes)
+        SELECT 'resume', id, 'user', user_id, file_size FROM resumes
+        WHERE user_id IS NOT NULL AND file_size IS NOT NULL AND deleted_at IS NULL
+        "#,
+        r#"
+        INSERT OR IGNORE INTO storage_usage (category, file_id, owner_type, owner_id, bytes)
+        SELECT 'video', id, 'user', user_id, file_size FROM videos
+        WHERE file_size IS NOT NULL AND COALESCE(video_source, 'upload') = 'upload'
+        "#,
+        r#"
+        INSERT OR IGNORE INTO storage_usage (category, file_id, owner_type, owner_id, bytes)
+        SELECT 'attachment', a.id, 'user', m.user_id, a.file_size
+        FROM message_attachments a JOIN conversation_messages m ON m.id = a.message_id
+        "#,
+        r#"
+        INSERT OR IGNORE INTO storage_usage (category, file_id, owner_type, owner_id, bytes)
+        SELECT 'company_asset', id, 'company', company_id, file_size FROM company_assets
+        WHERE file_size IS NOT NULL
+        "#,
+        "DELETE FROM storage_usage WHERE category = 'resume' AND file_id NOT IN (SELECT id FROM resumes WHERE deleted_at IS NULL)",
+        "DELETE FROM storage_usage WHERE category = 'video' AND file_id NOT IN (SELECT id FROM videos)",
+        "DELETE FROM storage_usage WHERE category = 'attachment' AND file_id NOT IN (SELECT id FROM message_attachments)",
+        "DELETE FROM storage_usage WHERE category = 'company_asset' AND file_id NOT IN (SELECT id FROM company_assets)",
+    ];
+
+    for statement in statements {
+        sqlx::query(statement).execute(pool).await?;
+    }
+
+    Ok(())
+}
@@ -1,16 +1,27 @@
 // Common module - shared types and utilities across all modules
 
+pub mod config;
+pub mod db;
 pub mod dev_mode;
 pub mod error;
+pub mod error_codes;
 pub mod helpers;
+pub mod http_cache;
+pub mod i18n;
 pub mod id_generator;
+pub mod log_format;
 pub mod migrations;
+pub mod request_id;
+pub mod shutdown;
 pub mod state;
+pub mod storage;
+pub mod timezone;
 pub mod validation;
 
 // Re-export commonly used types for convenience
 pub use error::ApiError;
-pub use helpers::safe_email_log;
+pub use error_codes::ErrorCode;
+pub use helpers::{parse_date, safe_email_log};
 pub use id_generator::*;
 pub use state::AppState;
-pub use validation::{ValidationError, ValidationResult, Validator};
+pub use validation::{validate_fields, ValidationError, ValidationResult, Validator};
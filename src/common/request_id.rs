@@ -0,0 +1,36 @@
+// src/common/request_id.rs
+//! Per-request correlation ID, available anywhere inside the request's task
+
+use std::future::Future;
+
+/// Header carrying the request ID in both directions
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+tokio::task_local! {
+    static REQUEST_ID: String;
+}
+
+/// Run `future` with `request_id` as the current request ID
+pub async fn with_request_id<F: Future>(request_id: String, future: F) -> F::Output {
+    REQUEST_ID.scope(request_id, future).await
+}
+
+/// The ID of the request being handled, if any
+pub fn current_request_id() -> Option<String> {
+    REQUEST_ID.try_with(|id| id.clone()).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_request_id_is_scoped_to_the_request() {
+        assert_eq!(current_request_id(), None);
+
+        let inside = with_request_id("req-123".to_string(), async { current_request_id() }).await;
+        assert_eq!(inside.as_deref(), Some("req-123"));
+
+        assert_eq!(current_request_id(), None);
+    }
+}
@@ -0,0 +1,136 @@
+// Graceful shutdown
+//
+// SIGTERM or Ctrl-C stops the server accepting connections. In-flight requests run to
+// completion, open WebSockets get a close frame, and background tasks finish the run they
+// are in instead of being cut off halfway through sending a batch of emails. Whatever is
+// still going after the drain timeout is dropped when the process exits.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::OnceLock;
+use std::time::Duration;
+use tokio::sync::{watch, Notify};
+use tracing::{info, warn};
+
+struct Shutdown {
+    requested: watch::Sender<bool>,
+    /// Work in progress that shutdown waits for
+    active: AtomicUsize,
+    idle: Notify,
+}
+
+fn shutdown() -> &'static Shutdown {
+    static SHUTDOWN: OnceLock<Shutdown> = OnceLock::new();
+    SHUTDOWN.get_or_init(|| Shutdown {
+        requested: watch::channel(false).0,
+        active: AtomicUsize::new(0),
+        idle: Notify::new(),
+    })
+}
+
+/// Start shutting down
+pub fn trigger() {
+    shutdown().requested.send_replace(true);
+}
+
+pub fn is_requested() -> bool {
+    *shutdown().requested.borrow()
+}
+
+/// Resolves once shutdown has been requested
+pub async fn requested() {
+    let mut rx = shutdown().requested.subscribe();
+    // The sender lives in a static, so this only fails if it is dropped, which it never is
+    let _ = rx.wait_for(|requested| *requested).await;
+}
+
+/// Sleep for `duration`, waking early on shutdown. Returns false if shutdown was requested,
+/// for background loops to stop instead of starting another run.
+pub async fn sleep(duration: Duration) -> bool {
+    tokio::select! {
+        _ = tokio::time::sleep(duration) => !is_requested(),
+        _ = requested() => false,
+    }
+}
+
+/// Marks work shutdown should wait for, until dropped
+pub struct TaskGuard(());
+
+impl Drop for TaskGuard {
+    fn drop(&mut self) {
+        if shutdown().active.fetch_sub(1, Ordering::AcqRel) == 1 {
+            shutdown().idle.notify_waiters();
+        }
+    }
+}
+
+/// Hold the returned guard while doing work shutdown should wait for
+pub fn track() -> TaskGuard {
+    shutdown().active.fetch_add(1, Ordering::AcqRel);
+    TaskGuard(())
+}
+
+/// Wait until no tracked work is left. Returns false if some was still running at the
+/// timeout.
+pub async fn drain(timeout: Duration) -> bool {
+    let wait = async {
+        loop {
+            let idle = shutdown().idle.notified();
+            if shutdown().active.load(Ordering::Acquire) == 0 {
+                return;
+            }
+            idle.await;
+        }
+    };
+    tokio::time::timeout(timeout, wait).await.is_ok()
+}
+
+pub fn active() -> usize {
+    shutdown().active.load(Ordering::Acquire)
+}
+
+/// Wait for SIGTERM or Ctrl-C, then request shutdown
+pub async fn listen_for_signals() {
+    let ctrl_c = async {
+        if let Err(e) = tokio::signal::ctrl_c().await {
+            warn!(error = %e, "Failed to listen for Ctrl-C");
+            std::future::pending::<()>().await;
+        }
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut signal) => {
+                signal.recv().await;
+            }
+            Err(e) => {
+                warn!(error = %e, "Failed to listen for SIGTERM");
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => info!("Received Ctrl-C, shutting down"),
+        _ = terminate => info!("Received SIGTERM, shutting down"),
+    }
+    trigger();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_drain_waits_for_tracked_work() {
+        let guard = track();
+        assert!(!drain(Duration::from_millis(20)).await);
+
+        let finished = tokio::spawn(drain(Duration::from_secs(5)));
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        drop(guard);
+        assert!(finished.await.unwrap());
+    }
+}
@@ -7,7 +7,8 @@ use std::path::PathBuf;
 use std::sync::Arc;
 
 use crate::services::{
-    AWSService, GoogleService, OpenAIService, PDFService, RateLimitService, SettingsService,
+    AWSService, CalendarClient, EmailSender, GoogleService, LlmClient, ObjectStore,
+    OpenAIService, PDFService, RateLimitService, SettingsService, SmsService, WhatsAppService,
 };
 use crate::common::dev_mode::DevModeConfig;
 
@@ -15,14 +16,25 @@ use crate::common::dev_mode::DevModeConfig;
 #[derive(Clone)]
 pub struct AppState {
     pub db: SqlitePool,
+    /// Read-only pool for heavy queries (public listings, analytics, exports). Anything that
+    /// writes, or must read its own writes, uses `db`.
+    pub db_read: SqlitePool,
     pub resumes_dir: PathBuf,
+    pub documents_dir: PathBuf,
+    /// Partial files of resumable uploads
+    pub upload_sessions_dir: PathBuf,
     pub avatars_dir: PathBuf,
     pub logos_dir: PathBuf,
     pub job_images_logos_dir: PathBuf,
     pub job_images_jobs_dir: PathBuf,
     pub http: Client,
+    /// Signs download links, and verifies session tokens from before signing keys existed
     pub jwt_secret: String,
+    pub jwt_keys: crate::auth::keys::JwtKeys,
     pub google_client_id: Option<String>,
+    /// Apple client IDs (app bundle IDs and web service IDs) identity tokens may be issued to
+    pub apple_client_ids: Vec<String>,
+    pub apple_keys: crate::auth::apple::AppleKeyCache,
     pub openai_api_key: Option<String>,
     pub openai_model: String,
     pub admin_emails: HashSet<String>,
@@ -31,7 +43,20 @@ pub struct AppState {
     pub openai_service: Arc<OpenAIService>,
     pub aws_service: Arc<AWSService>,
     pub google_service: Arc<GoogleService>,
+    /// Email, file storage, text generation and calendars, through the provider traits;
+    /// backed by the services above except in tests
+    pub email: Arc<dyn EmailSender>,
+    pub object_store: Arc<dyn ObjectStore>,
+    pub llm: Arc<dyn LlmClient>,
+    pub calendar: Arc<dyn CalendarClient>,
     pub rate_limit_service: Arc<RateLimitService>,
     pub pdf_service: Arc<PDFService>,
+    pub sms_service: Arc<SmsService>,
+    pub whatsapp_service: Arc<WhatsAppService>,
     pub connection_manager: crate::messages::services::ConnectionManager,
+    pub feed_cache: crate::jobs::services::FeedCache,
+    pub similar_jobs_cache: crate::jobs::services::SimilarJobsCache,
+    pub job_editors: crate::jobs::services::JobEditors,
+    /// Startup configuration, for the admin config endpoint
+    pub config: Arc<crate::common::config::Config>,
 }
@@ -0,0 +1,130 @@
+// src/common/storage.rs
+//! Shared file storage: S3 when the `storage_type` setting selects it, local disk otherwise
+
+use sha2::{Digest, Sha256};
+use std::path::Path;
+use tracing::{error, info, warn};
+
+use crate::common::{ApiError, AppState};
+
+/// Short content hash for public asset names, so replacing an asset always changes its URL
+/// and CDN or browser caches can never serve the old bytes under the new name
+pub fn content_hash(data: &[u8]) -> String {
+    Sha256::digest(data)
+        .iter()
+        .take(8)
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// Whether uploads should go to S3 according to the `storage_type` setting
+pub async fn uses_s3(state: &AppState) -> bool {
+    state
+        .settings_service
+        .get_setting("storage_type")
+        .await
+        .ok()
+        .flatten()
+        .map(|storage_type| storage_type.starts_with("s3"))
+        .unwrap_or(false)
+}
+
+/// Store a file under `<prefix>/<filename>` in S3, or in `local_dir` when S3 is not
+/// configured or the upload fails
+pub async fn save_file(
+    state: &AppState,
+    prefix: &str,
+    local_dir: &Path,
+    filename: &str,
+    data: Vec<u8>,
+    content_type: &str,
+) -> Result<(), ApiError> {
+    if uses_s3(state).await {
+        let s3_key = format!("{}/{}", prefix, filename);
+        match state
+            .object_store
+            .upload_file(data.clone(), &s3_key, content_type)
+            .await
+        {
+            Ok(_url) => {
+                info!(s3_key = %s3_key, "File uploaded to S3 successfully");
+                return Ok(());
+            }
+            Err(e) => {
+                warn!(error = %e, s3_key = %s3_key, "Failed to upload to S3, falling back to local storage");
+            }
+        }
+    }
+
+    tokio::fs::write(local_dir.join(filename), &data)
+        .await
+        .map_err(|e| {
+            error!(error = %e, filename = %filename, "Failed to save file locally");
+            ApiError::InternalServer("Failed to save file".to_string())
+        })
+}
+
+/// Read a file stored by `save_file`, trying S3 first when configured
+pub async fn load_file(
+    state: &AppState,
+    prefix: &str,
+    local_dir: &Path,
+    filename: &str,
+) -> Result<Vec<u8>, ApiError> {
+    if uses_s3(state).await {
+        let s3_key = format!("{}/{}", prefix, filename);
+        info!(s3_key = %s3_key, "Downloading file from S3");
+
+        match state.object_store.download_file(&s3_key).await {
+            Ok(bytes) => return Ok(bytes),
+            Err(e) => {
+                // Fallback to local storage if S3 fails
+                warn!(error = %e, filename = %filename, "Failed to download from S3, trying local storage");
+            }
+        }
+    }
+
+    let file_path = local_dir.join(filename);
+    if !file_path.exists() {
+        return Err(ApiError::NotFound("File not found".to_string()));
+    }
+    tokio::fs::read(&file_path).await.map_err(|e| {
+        error!(error = %e, filename = %filename, "Failed to read stored file");
+        ApiError::InternalServer("Failed to read file".to_string())
+    })
+}
+
+/// Directory local uploads live under, served back at `/uploads/...` URLs
+pub const LOCAL_UPLOADS_DIR: &str = "uploads";
+
+/// Store an upload at `key` in S3 when the storage type selects it, else on local disk under
+/// [`LOCAL_UPLOADS_DIR`]. Returns the URL to record: the S3 (or CloudFront) URL, or
+/// `/uploads/<key>` for a local file.
+pub async fn store_upload(
+    state: &AppState,
+    key: &str,
+    data: Vec<u8>,
+    content_type: &str,
+) -> Result<String, ApiError> {
+    if uses_s3(state).await {
+        return state
+            .object_store
+            .upload_file(data, key, content_type)
+            .await
+            .map_err(|e| ApiError::ProcessingError(format!("Failed to upload to S3: {}", e)));
+    }
+
+    let path = Path::new(LOCAL_UPLOADS_DIR).join(key);
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent).await.map_err(|e| {
+            error!(error = %e, key = %key, "Failed to create upload directory");
+            ApiError::InternalServer("Failed to save file".to_string())
+        })?;
+    }
+    tokio::fs::write(&path, &data).await.map_err(|e| {
+        error!(error = %e, key = %key, "Failed to save upload locally");
+        ApiError::InternalServer("Failed to save file".to_string())
+    })?;
+    info!(key = %key, "Upload stored locally");
+    Ok(format!("/{}/{}", LOCAL_UPLOADS_DIR, key))
+}
@@ -0,0 +1,298 @@
+// src/common/timezone.rs
+//! Time zone helpers: schedules are stored in UTC alongside the zone they were booked in,
+//! and rendered in each reader's preferred zone.
+
+use chrono::{DateTime, Datelike, FixedOffset, NaiveDateTime, TimeZone, Timelike, Utc, Weekday};
+use chrono_tz::Tz;
+use sqlx::SqlitePool;
+
+pub const DEFAULT_TIMEZONE: &str = "UTC";
+
+/// A zone label resolved to something chrono can convert into
+enum Zone {
+    Named(Tz),
+    Fixed(FixedOffset),
+}
+
+fn resolve_zone(label: &str) -> Option<Zone> {
+    let label = label.trim();
+    if let Ok(tz) = label.parse::<Tz>() {
+        return Some(Zone::Named(tz));
+    }
+
+    // Offsets recorded for interviews booked without a named zone, e.g. "+05:30"
+    DateTime::parse_from_rfc3339(&format!("2000-01-01T00:00:00{}", label))
+        .ok()
+        .map(|dt| Zone::Fixed(*dt.offset()))
+}
+
+/// Check that a user-supplied zone is a known IANA name such as `America/New_York`
+pub fn validate_timezone(name: &str) -> Result<String, String> {
+    name.trim()
+        .parse::<Tz>()
+        .map(|tz| tz.name().to_string())
+        .map_err(|_| {
+            format!(
+                "Unknown time zone '{}'; use an IANA name like Europe/London",
+                name
+            )
+        })
+}
+
+/// Parse a schedule into UTC and the zone it was entered in.
+///
+/// Accepts RFC3339 with an offset, or a local `YYYY-MM-DDTHH:MM[:SS]` time when `timezone`
+/// names the zone. When both are given the instant comes from the offset and `timezone` is
+/// kept as the original zone.
+pub fn normalize_schedule(
+    input: &str,
+    timezone: Option<&str>,
+) -> Result<(DateTime<Utc>, String), String> {
+    let named = match timezone.map(str::trim).filter(|tz| !tz.is_empty()) {
+        Some(tz) => Some(tz.parse::<Tz>().map_err(|_| {
+            format!(
+                "Unknown time zone '{}'; use an IANA name like Europe/London",
+                tz
+            )
+        })?),
+        None => None,
+    };
+
+    if let Ok(with_offset) = DateTime::parse_from_rfc3339(input.trim()) {
+        let zone = match named {
+            Some(tz) => tz.name().to_string(),
+            None => offset_label(with_offset.offset()),
+        };
+        return Ok((with_offset.with_timezone(&Utc), zone));
+    }
+
+    let tz = named.ok_or_else(|| {
+        format!(
+            "Invalid date '{}': include a UTC offset or provide a time zone",
+            input
+        )
+    })?;
+    let local = NaiveDateTime::parse_from_str(input.trim(), "%Y-%m-%dT%H:%M:%S")
+        .or_else(|_| NaiveDateTime::parse_from_str(input.trim(), "%Y-%m-%dT%H:%M"))
+        .map_err(|_| format!("Invalid date '{}': expected YYYY-MM-DDTHH:MM", input))?;
+    let resolved = tz
+        .from_local_datetime(&local)
+        .earliest()
+        .ok_or_else(|| format!("{} does not exist in {} (clock change)", input, tz.name()))?;
+
+    Ok((resolved.with_timezone(&Utc), tz.name().to_string()))
+}
+
+fn offset_label(offset: &FixedOffset) -> String {
+    if offset.local_minus_utc() == 0 {
+        DEFAULT_TIMEZONE.to_string()
+    } else {
+        offset.to_string()
+    }
+}
+
+/// Parse a stored schedule, which is UTC for new rows but may carry an offset in old ones
+pub fn parse_stored(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value)
+        .ok()
+        .map(|dt| dt.with_timezone(&Utc))
+}
+
+/// RFC3339 timestamp in the given zone, falling back to UTC for unknown zones
+pub fn to_zone_rfc3339(utc: DateTime<Utc>, zone: &str) -> String {
+    match resolve_zone(zone) {
+        Some(Zone::Named(tz)) => utc.with_timezone(&tz).to_rfc3339(),
+        Some(Zone::Fixed(offset)) => utc.with_timezone(&offset).to_rfc3339(),
+        None => utc.to_rfc3339(),
+    }
+}
+
+/// Calendar date of an instant as seen in the given zone
+pub fn local_date(utc: DateTime<Utc>, zone: &str) -> chrono::NaiveDate {
+    match resolve_zone(zone) {
+        Some(Zone::Named(tz)) => utc.with_timezone(&tz).date_naive(),
+        Some(Zone::Fixed(offset)) => utc.with_timezone(&offset).date_naive(),
+        None => utc.date_naive(),
+    }
+}
+
+/// Hour of the day (0-23) of an instant as seen in the given zone
+pub fn local_hour(utc: DateTime<Utc>, zone: &str) -> u32 {
+    match resolve_zone(zone) {
+        Some(Zone::Named(tz)) => utc.with_timezone(&tz).hour(),
+        Some(Zone::Fixed(offset)) => utc.with_timezone(&offset).hour(),
+        None => utc.hour(),
+    }
+}
+
+/// The first instant at or after `utc` that falls within business hours (`start_hour` to
+/// `end_hour`, Monday to Friday) in the given zone
+pub fn next_business_hours(
+    utc: DateTime<Utc>,
+    zone: &str,
+    start_hour: u32,
+    end_hour: u32,
+) -> DateTime<Utc> {
+    let resolved = resolve_zone(zone);
+    let local = match &resolved {
+        Some(Zone::Named(tz)) => utc.with_timezone(tz).naive_local(),
+        Some(Zone::Fixed(offset)) => utc.with_timezone(offset).naive_local(),
+        None => utc.naive_utc(),
+    };
+    let is_workday = |day: chrono::NaiveDate| !matches!(day.weekday(), Weekday::Sat | Weekday::Sun);
+
+    if is_workday(local.date()) && local.hour() >= start_hour && local.hour() < end_hour {
+        return utc;
+    }
+    let mut day = local.date();
+    if !(is_workday(day) && local.hour() < start_hour) {
+        day = day.succ_opt().unwrap_or(day);
+    }
+    while !is_workday(day) {
+        day = day.succ_opt().unwrap_or(day);
+    }
+    let Some(opening) = day.and_hms_opt(start_hour, 0, 0) else {
+        return utc;
+    };
+
+    let opening = match resolved {
+        Some(Zone::Named(tz)) => tz
+            .from_local_datetime(&opening)
+            .earliest()
+            .map(|dt| dt.with_timezone(&Utc)),
+        Some(Zone::Fixed(offset)) => offset
+            .from_local_datetime(&opening)
+            .single()
+            .map(|dt| dt.with_timezone(&Utc)),
+        None => Some(Utc.from_utc_datetime(&opening)),
+    };
+    opening.unwrap_or(utc)
+}
+
+/// Human-readable time with an explicit zone label, e.g.
+/// "Monday, March 2, 2026 at 10:00 AM EST (America/New_York)"
+pub fn format_for_display(utc: DateTime<Utc>, zone: &str) -> String {
+    const FORMAT: &str = "%A, %B %-d, %Y at %-I:%M %p";
+
+    match resolve_zone(zone) {
+        Some(Zone::Named(tz)) => {
+            let local = utc.with_timezone(&tz);
+            format!(
+                "{} {} ({})",
+                local.format(FORMAT),
+                local.format("%Z"),
+                tz.name()
+            )
+        }
+        Some(Zone::Fixed(offset)) => {
+            let local = utc.with_timezone(&offset);
+            format!("{} UTC{}", local.format(FORMAT), offset)
+        }
+        None => format!("{} UTC", utc.format(FORMAT)),
+    }
+}
+
+/// Render a stored schedule for display, leaving unparseable legacy values untouched
+pub fn display_stored(value: &str, zone: &str) -> String {
+    parse_stored(value)
+        .map(|utc| format_for_display(utc, zone))
+        .unwrap_or_else(|| value.to_string())
+}
+
+/// A user's preferred zone, if they have set one
+pub async fn user_timezone(pool: &SqlitePool, user_id: &str) -> Option<String> {
+    sqlx::query_scalar::<_, Option<String>>("SELECT timezone FROM users WHERE id = ?")
+        .bind(user_id)
+        .fetch_optional(pool)
+        .await
+        .ok()
+        .flatten()
+        .flatten()
+}
+
+/// Preferred zone of the account with this email, if any
+pub async fn email_timezone(pool: &SqlitePool, email: &str) -> Option<String> {
+    sqlx::query_scalar::<_, Option<String>>(
+        r#"
+        SELECT u.timezone FROM users u
+        LEFT JOIN profiles p ON p.user_id = u.id
+        WHERE LOWER(u.email) = LOWER(?1) OR LOWER(p.contact_email) = LOWER(?1)
+        LIMIT 1
+        "#,
+    )
+    .bind(email)
+    .fetch_optional(pool)
+    .await
+    .ok()
+    .flatten()
+    .flatten()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_schedule_keeps_offset_as_zone() {
+        let (utc, zone) = normalize_schedule("2026-03-02T10:00:00+05:30", None).unwrap();
+        assert_eq!(utc.to_rfc3339(), "2026-03-02T04:30:00+00:00");
+        assert_eq!(zone, "+05:30");
+
+        let (_, zone) = normalize_schedule("2026-03-02T10:00:00Z", None).unwrap();
+        assert_eq!(zone, "UTC");
+    }
+
+    #[test]
+    fn test_normalize_schedule_resolves_local_time_in_named_zone() {
+        let (utc, zone) = normalize_schedule("2026-07-01T09:00", Some("America/New_York")).unwrap();
+        assert_eq!(utc.to_rfc3339(), "2026-07-01T13:00:00+00:00");
+        assert_eq!(zone, "America/New_York");
+
+        assert!(normalize_schedule("2026-07-01T09:00", None).is_err());
+        assert!(normalize_schedule("2026-07-01T09:00", Some("Mars/Olympus")).is_err());
+    }
+
+    #[test]
+    fn test_next_business_hours() {
+        // Wednesday 10:00 in New York is already within hours
+        let open = parse_stored("2026-07-01T14:00:00Z").unwrap();
+        assert_eq!(next_business_hours(open, "America/New_York", 9, 17), open);
+
+        // Wednesday 20:00 in New York moves to Thursday 09:00
+        let evening = parse_stored("2026-07-02T00:00:00Z").unwrap();
+        assert_eq!(
+            next_business_hours(evening, "America/New_York", 9, 17).to_rfc3339(),
+            "2026-07-02T13:00:00+00:00"
+        );
+
+        // Early Friday morning waits for that day's opening, Saturday for Monday's
+        let early = parse_stored("2026-07-03T06:00:00Z").unwrap();
+        assert_eq!(
+            next_business_hours(early, "UTC", 9, 17).to_rfc3339(),
+            "2026-07-03T09:00:00+00:00"
+        );
+        let weekend = parse_stored("2026-07-04T12:00:00Z").unwrap();
+        assert_eq!(
+            next_business_hours(weekend, "UTC", 9, 17).to_rfc3339(),
+            "2026-07-06T09:00:00+00:00"
+        );
+    }
+
+    #[test]
+    fn test_format_for_display_labels_zone() {
+        let utc = parse_stored("2026-01-15T15:00:00Z").unwrap();
+        assert_eq!(
+            format_for_display(utc, "America/New_York"),
+            "Thursday, January 15, 2026 at 10:00 AM EST (America/New_York)"
+        );
+        assert_eq!(
+            format_for_display(utc, "+05:30"),
+            "Thursday, January 15, 2026 at 8:30 PM UTC+05:30"
+        );
+        assert_eq!(
+            to_zone_rfc3339(utc, "Asia/Tokyo"),
+            "2026-01-16T00:00:00+09:00"
+        );
+        assert_eq!(local_date(utc, "Asia/Tokyo").to_string(), "2026-01-16");
+    }
+}
@@ -1,6 +1,9 @@
 // Common validation types and traits
 
-#[derive(Debug)]
+use serde::Serialize;
+use validator::{Validate, ValidateUrl, ValidationErrors, ValidationErrorsKind};
+
+#[derive(Debug, Clone, Serialize)]
 pub struct ValidationError {
     pub field: String,
     pub message: String,
@@ -39,3 +42,165 @@ impl ValidationResult {
 pub trait Validator<T> {
     fn validate(&self, data: &T) -> ValidationResult;
 }
+
+// ============================================================================
+// Declarative Constraints
+// ============================================================================
+//
+// Request structs declare simple constraints (lengths, emails, URLs, ranges) with
+// `#[derive(validator::Validate)]`. Module validators start from `validate_fields` and add
+// the cross-field and business rules that attributes cannot express, so clients get one
+// consistent list of field errors either way.
+
+/// Run a request's declared `#[validate(...)]` constraints
+pub fn validate_fields<T: Validate>(data: &T) -> ValidationResult {
+    match data.validate() {
+        Ok(()) => ValidationResult::new(),
+        Err(errors) => errors.into(),
+    }
+}
+
+impl From<ValidationErrors> for ValidationResult {
+    fn from(errors: ValidationErrors) -> Self {
+        let mut result = ValidationResult::new();
+        collect_field_errors(&mut result, "", &errors);
+        result
+    }
+}
+
+/// Flatten nested and list errors into `parent.child` / `items[0].field` paths, sorted by
+/// path so responses are stable
+fn collect_field_errors(result: &mut ValidationResult, prefix: &str, errors: &ValidationErrors) {
+    let mut fields: Vec<_> = errors.errors().iter().collect();
+    fields.sort_by_key(|(field, _)| *field);
+
+    for (field, kind) in fields {
+        let path = if prefix.is_empty() {
+            field.to_string()
+        } else {
+            format!("{}.{}", prefix, field)
+        };
+
+        match kind {
+            ValidationErrorsKind::Field(field_errors) => {
+                for error in field_errors {
+                    result.add_error(&path, &constraint_message(error));
+                }
+            }
+            ValidationErrorsKind::Struct(nested) => collect_field_errors(result, &path, nested),
+            ValidationErrorsKind::List(items) => {
+                for (index, nested) in items {
+                    collect_field_errors(result, &format!("{}[{}]", path, index), nested);
+                }
+            }
+        }
+    }
+}
+
+/// The attribute's `message`, or a generic one built from the constraint's parameters
+fn constraint_message(error: &validator::ValidationError) -> String {
+    if let Some(message) = &error.message {
+        return message.to_string();
+    }
+
+    let param = |name: &str| error.params.get(name).map(|v| v.to_string());
+    match error.code.as_ref() {
+        "length" => match (param("min"), param("max"), param("equal")) {
+            (_, _, Some(equal)) => format!("Must be exactly {} characters", equal),
+            (Some(min), Some(max), _) => format!("Must be between {} and {} characters", min, max),
+            (Some(min), None, _) => format!("Must be at least {} characters", min),
+            (None, Some(max), _) => format!("Must be at most {} characters", max),
+            _ => "Invalid length".to_string(),
+        },
+        "range" => match (param("min"), param("max")) {
+            (Some(min), Some(max)) => format!("Must be between {} and {}", min, max),
+            (Some(min), None) => format!("Must be at least {}", min),
+            (None, Some(max)) => format!("Must be at most {}", max),
+            _ => "Out of range".to_string(),
+        },
+        "email" => "Must be a valid email address".to_string(),
+        "url" => "Must be a valid URL".to_string(),
+        code => format!("Failed {} validation", code),
+    }
+}
+
+/// Custom constraint for optional website fields: empty is allowed, anything else must be
+/// an absolute http(s) URL
+pub fn validate_http_url(value: &str) -> Result<(), validator::ValidationError> {
+    if value.is_empty()
+        || ((value.starts_with("http://") || value.starts_with("https://")) && value.validate_url())
+    {
+        Ok(())
+    } else {
+        Err(validator::ValidationError::new("url"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Validate)]
+    struct Contact {
+        #[validate(email(message = "Email must be valid"))]
+        email: String,
+    }
+
+    #[derive(Validate)]
+    struct Signup {
+        #[validate(length(min = 3, max = 20))]
+        username: String,
+        #[validate(range(min = 18))]
+        age: i32,
+        #[validate(custom(function = "validate_http_url"))]
+        website: Option<String>,
+        #[validate(nested)]
+        contacts: Vec<Contact>,
+    }
+
+    #[test]
+    fn test_declared_constraints_become_field_errors() {
+        let signup = Signup {
+            username: "ab".to_string(),
+            age: 16,
+            website: Some("ftp://example.com".to_string()),
+            contacts: vec![
+                Contact {
+                    email: "ok@example.com".to_string(),
+                },
+                Contact {
+                    email: "nope".to_string(),
+                },
+            ],
+        };
+
+        let result = validate_fields(&signup);
+        assert!(!result.is_valid);
+
+        let errors: Vec<(&str, &str)> = result
+            .errors
+            .iter()
+            .map(|e| (e.field.as_str(), e.message.as_str()))
+            .collect();
+        assert_eq!(
+            errors,
+            vec![
+                ("age", "Must be at least 18"),
+                ("contacts[1].email", "Email must be valid"),
+                ("username", "Must be between 3 and 20 characters"),
+                ("website", "Must be a valid URL"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_valid_request_passes() {
+        let signup = Signup {
+            username: "jordan".to_string(),
+            age: 30,
+            website: Some(String::new()),
+            contacts: vec![],
+        };
+        assert!(validate_fields(&signup).is_valid);
+    }
+}
@@ -3,8 +3,8 @@
 
 use axum::{
     extract::{Extension, Multipart, Path},
-    http::StatusCode,
-    response::IntoResponse,
+    http::{HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
     Json,
 };
 use serde_json::json;
@@ -12,7 +12,7 @@ use std::sync::Arc;
 use tokio::sync::RwLock;
 
 use crate::auth::AuthedUser;
-use crate::common::{generate_raw_id, ApiError, AppState};
+use crate::common::{http_cache, storage, ApiError, AppState};
 
 /// POST /api/admin/logo/upload - Upload company logo (admin only)
 pub async fn upload_logo(
@@ -61,7 +61,8 @@ pub async fn upload_logo(
 pub async fn serve_logo(
     Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
     Path(filename): Path<String>,
-) -> Result<impl IntoResponse, ApiError> {
+    headers: HeaderMap,
+) -> Result<Response, ApiError> {
     let state = state_lock.read().await;
 
     let file_path = state.logos_dir.join(&filename);
@@ -76,10 +77,12 @@ pub async fn serve_logo(
 
     let content_type = get_content_type_from_extension(&filename);
 
-    Ok((
-        StatusCode::OK,
-        [(axum::http::header::CONTENT_TYPE, content_type)],
+    // Logos are named by content hash
+    Ok(http_cache::cached(
+        &headers,
+        content_type,
         content,
+        http_cache::IMMUTABLE_CACHE_CONTROL,
     ))
 }
 
@@ -245,7 +248,7 @@ pub async fn delete_logo_file(
     // Try to delete from S3 first if using S3 storage
     if storage_type.starts_with("s3") {
         let s3_key = format!("logos/{}", filename);
-        match state.aws_service.delete_file(&s3_key).await {
+        match state.object_store.delete_file(&s3_key).await {
             Ok(_) => {
                 tracing::info!(s3_key = %s3_key, "Logo deleted from S3");
                 deleted = true;
@@ -284,7 +287,7 @@ pub async fn delete_logo_file(
 async fn save_logo_file(state: &AppState, data: &[u8]) -> Result<(String, Option<String>), ApiError> {
     use tracing::{info, warn};
     
-    let filename = format!("{}.png", generate_raw_id(8));
+    let filename = format!("{}.png", storage::content_hash(data));
 
     // Check storage type setting
     let storage_type = state
@@ -299,7 +302,7 @@ async fn save_logo_file(state: &AppState, data: &[u8]) -> Result<(String, Option
         // Upload to S3
         let s3_key = format!("logos/{}", filename);
         match state
-            .aws_service
+            .object_store
             .upload_file(data.to_vec(), &s3_key, "image/png")
             .await
         {
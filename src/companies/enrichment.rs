@@ -0,0 +1,466 @@
+// src/companies/enrichment.rs
+//! Company data enrichment from a website URL. The page's public metadata (name, description,
+//! logo, founding year, size hints) is turned into a draft for the company creation form, with
+//! an optional AI pass that summarizes the page and suggests an industry and size.
+
+use std::net::{IpAddr, SocketAddr};
+use std::time::Duration;
+
+use chrono::Datelike;
+use regex::Regex;
+use reqwest::{redirect, Url};
+use serde::Deserialize;
+use tracing::{info, warn};
+
+use super::models::{CompanyDraft, CompanyEnrichmentResponse};
+use crate::common::{ApiError, AppState};
+use crate::services::openai::TextGenerationPurpose;
+
+const FETCH_TIMEOUT: Duration = Duration::from_secs(10);
+const MAX_REDIRECTS: usize = 5;
+/// Pages are cut off here; the metadata lives in the head
+const MAX_PAGE_BYTES: usize = 1024 * 1024;
+/// Visible page text passed to the AI summary
+const MAX_AI_TEXT_CHARS: usize = 6000;
+const MAX_DESCRIPTION_CHARS: usize = 1000;
+
+/// The `company_size` buckets used across the admin UI
+const COMPANY_SIZES: [(u32, &str); 6] = [
+    (10, "1-10"),
+    (50, "11-50"),
+    (200, "51-200"),
+    (500, "201-500"),
+    (1000, "501-1000"),
+    (u32::MAX, "1000+"),
+];
+
+/// Addresses a server-side fetch must never reach
+fn is_public_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(v4) => {
+            !(v4.is_private()
+                || v4.is_loopback()
+                || v4.is_link_local()
+                || v4.is_unspecified()
+                || v4.is_broadcast()
+                || v4.is_multicast()
+                || v4.is_documentation()
+                // Carrier-grade NAT, 100.64.0.0/10
+                || (v4.octets()[0] == 100 && (v4.octets()[1] & 0xc0) == 64))
+        }
+        IpAddr::V6(v6) => match v6.to_ipv4_mapped() {
+            Some(v4) => is_public_ip(IpAddr::V4(v4)),
+            None => {
+                let first = v6.segments()[0];
+                !(v6.is_loopback()
+                    || v6.is_unspecified()
+                    || v6.is_multicast()
+                    // Unique local fc00::/7 and link-local fe80::/10
+                    || (first & 0xfe00) == 0xfc00
+                    || (first & 0xffc0) == 0xfe80)
+            }
+        },
+    }
+}
+
+/// Parse the admin-supplied website, adding `https://` when the scheme is missing
+pub fn normalize_website(website: &str) -> Result<Url, ApiError> {
+    let website = website.trim();
+    let with_scheme = if website.contains("://") {
+        website.to_string()
+    } else {
+        format!("https://{}", website)
+    };
+    let url = Url::parse(&with_scheme)
+        .map_err(|_| ApiError::ValidationError(format!("Invalid website URL: {}", website)))?;
+    if !matches!(url.scheme(), "http" | "https") || url.host_str().is_none() {
+        return Err(ApiError::ValidationError(
+            "Website must be an http:// or https:// URL".to_string(),
+        ));
+    }
+    Ok(url)
+}
+
+/// Resolve the URL's host and refuse private, loopback and other internal addresses
+async fn public_address(url: &Url) -> Result<SocketAddr, ApiError> {
+    // IPv6 literals come bracketed, as in the URL
+    let host = url
+        .host_str()
+        .unwrap_or_default()
+        .trim_start_matches('[')
+        .trim_end_matches(']');
+    let port = url.port_or_known_default().unwrap_or(443);
+    let addrs: Vec<SocketAddr> = tokio::net::lookup_host((host, port))
+        .await
+        .map_err(|e| ApiError::BadRequest(format!("Could not resolve {}: {}", host, e)))?
+        .collect();
+
+    match addrs.first() {
+        Some(_) if addrs.iter().all(|a| is_public_ip(a.ip())) => Ok(addrs[0]),
+        Some(_) => Err(ApiError::BadRequest(format!(
+            "{} resolves to a private address",
+            host
+        ))),
+        None => Err(ApiError::BadRequest(format!("Could not resolve {}", host))),
+    }
+}
+
+/// Fetch the page, following redirects one hop at a time so every hop is checked. Returns the
+/// final URL (for resolving relative links) and the start of the body.
+async fn fetch_page(mut url: Url) -> Result<(Url, String), ApiError> {
+    for _ in 0..=MAX_REDIRECTS {
+        let addr = public_address(&url).await?;
+        // A client per hop pins the checked address, so DNS cannot change between check and fetch.
+        // The shared client follows redirects itself and cannot be used for untrusted URLs.
+        let client = reqwest::Client::builder()
+            .no_proxy()
+            .timeout(FETCH_TIMEOUT)
+            .redirect(redirect::Policy::none())
+            .resolve(url.host_str().unwrap_or_default(), addr)
+            .build()
+            .map_err(|e| ApiError::InternalServer(format!("HTTP client error: {}", e)))?;
+
+        let mut response = client
+            .get(url.clone())
+            .header(reqwest::header::ACCEPT, "text/html")
+            .send()
+            .await
+            .map_err(|e| ApiError::BadRequest(format!("Could not fetch {}: {}", url, e)))?;
+
+        if response.status().is_redirection() {
+            let location = response
+                .headers()
+                .get(reqwest::header::LOCATION)
+                .and_then(|l| l.to_str().ok())
+                .ok_or_else(|| {
+                    ApiError::BadRequest(format!("{} redirected without a location", url))
+                })?;
+            url = url
+                .join(location)
+                .map_err(|_| ApiError::BadRequest(format!("Invalid redirect from {}", url)))?;
+            if !matches!(url.scheme(), "http" | "https") {
+                return Err(ApiError::BadRequest(format!(
+                    "Redirected to unsupported URL {}",
+                    url
+                )));
+            }
+            continue;
+        }
+        if !response.status().is_success() {
+            return Err(ApiError::BadRequest(format!(
+                "{} answered with {}",
+                url,
+                response.status()
+            )));
+        }
+
+        let mut body = Vec::new();
+        while let Some(chunk) = response
+            .chunk()
+            .await
+            .map_err(|e| ApiError::BadRequest(format!("Could not read {}: {}", url, e)))?
+        {
+            body.extend_from_slice(&chunk);
+            if body.len() >= MAX_PAGE_BYTES {
+                body.truncate(MAX_PAGE_BYTES);
+                break;
+            }
+        }
+        return Ok((url, String::from_utf8_lossy(&body).into_owned()));
+    }
+
+    Err(ApiError::BadRequest(format!(
+        "{} redirected more than {} times",
+        url, MAX_REDIRECTS
+    )))
+}
+
+fn decode_entities(text: &str) -> String {
+    text.replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&#x27;", "'")
+        .replace("&apos;", "'")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+}
+
+/// Attribute values of every `<tag ...>` in the page, keyed by lowercased attribute name
+fn tags(html: &str, tag: &str) -> Vec<Vec<(String, String)>> {
+    let tag_re = Regex::new(&format!(r"(?is)<{}\b[^>]*>", tag)).expect("valid regex");
+    let attr_re =
+        Regex::new(r#"(?s)([a-zA-Z_:-]+)\s*=\s*(?:"([^"]*)"|'([^']*)')"#).expect("valid regex");
+
+    tag_re
+        .find_iter(html)
+        .map(|m| {
+            attr_re
+                .captures_iter(m.as_str())
+                .map(|c| {
+                    let value = c.get(2).or_else(|| c.get(3)).map_or("", |v| v.as_str());
+                    (c[1].to_lowercase(), decode_entities(value.trim()))
+                })
+                .collect()
+        })
+        .collect()
+}
+
+fn attr<'a>(attrs: &'a [(String, String)], name: &str) -> Option<&'a str> {
+    attrs
+        .iter()
+        .find(|(n, _)| n == name)
+        .map(|(_, v)| v.as_str())
+}
+
+/// Content of the first `<meta>` whose `name` or `property` is one of `keys`, in key order
+fn meta(metas: &[Vec<(String, String)>], keys: &[&str]) -> Option<String> {
+    keys.iter().find_map(|key| {
+        metas.iter().find_map(|m| {
+            let named = attr(m, "property")
+                .or_else(|| attr(m, "name"))
+                .is_some_and(|n| n.eq_ignore_ascii_case(key));
+            attr(m, "content")
+                .filter(|c| named && !c.is_empty())
+                .map(str::to_string)
+        })
+    })
+}
+
+/// Logo URL from JSON-LD, touch icons, favicons or the share image, in that order
+fn logo_url(html: &str, links: &[Vec<(String, String)>], og_image: Option<&str>) -> Option<String> {
+    let json_ld = Regex::new(r#""logo"\s*:\s*(?:\{[^}]*?"url"\s*:\s*)?"([^"]+)""#)
+        .expect("valid regex")
+        .captures(html)
+        .map(|c| c[1].replace("\\/", "/"));
+
+    let icon = |rels: &[&str]| {
+        links.iter().find_map(|l| {
+            let rel = attr(l, "rel")?.to_lowercase();
+            rels.contains(&rel.as_str())
+                .then(|| attr(l, "href"))
+                .flatten()
+                .filter(|h| !h.is_empty())
+                .map(str::to_string)
+        })
+    };
+
+    json_ld
+        .or_else(|| icon(&["apple-touch-icon", "apple-touch-icon-precomposed"]))
+        .or_else(|| icon(&["icon", "shortcut icon"]))
+        .or_else(|| og_image.map(str::to_string))
+}
+
+/// Page text without markup, scripts or styles, whitespace collapsed
+fn visible_text(html: &str) -> String {
+    let without_code =
+        Regex::new(r"(?is)<(script|style|noscript|svg)\b.*?</(script|style|noscript|svg)>")
+            .expect("valid regex")
+            .replace_all(html, " ");
+    let without_tags = Regex::new(r"(?s)<[^>]*>")
+        .expect("valid regex")
+        .replace_all(&without_code, " ");
+    decode_entities(&without_tags)
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Bucket a headcount mentioned on the page, e.g. "250+ employees"
+fn size_hint(text: &str) -> Option<String> {
+    let headcount = Regex::new(
+        r"(?i)\b(\d{1,3}(?:,\d{3})+|\d+)\s*\+?\s*(?:employees|team members|people|staff)\b",
+    )
+    .expect("valid regex")
+    .captures(text)?[1]
+        .replace(',', "")
+        .parse::<u32>()
+        .ok()
+        .filter(|n| *n > 0)?;
+
+    COMPANY_SIZES
+        .iter()
+        .find(|(max, _)| headcount <= *max)
+        .map(|(_, bucket)| bucket.to_string())
+}
+
+/// "Founded in 2012", "Established 1998", "Since 2005"
+fn founded_year(text: &str) -> Option<i32> {
+    let year = Regex::new(r"(?i)\b(?:founded|established|since)\s+(?:in\s+)?((?:18|19|20)\d{2})\b")
+        .expect("valid regex")
+        .captures(text)?[1]
+        .parse::<i32>()
+        .ok()?;
+    (1800..=chrono::Utc::now().year())
+        .contains(&year)
+        .then_some(year)
+}
+
+fn truncate_chars(text: &str, max: usize) -> String {
+    match text.char_indices().nth(max) {
+        Some((end, _)) => text[..end].trim_end().to_string(),
+        None => text.to_string(),
+    }
+}
+
+/// Draft the company from the page's metadata alone
+pub fn draft_from_page(html: &str, page_url: &Url) -> CompanyDraft {
+    let metas = tags(html, "meta");
+    let links = tags(html, "link");
+    let text = visible_text(html);
+
+    let title = Regex::new(r"(?is)<title[^>]*>(.*?)</title>")
+        .expect("valid regex")
+        .captures(html)
+        .map(|c| decode_entities(c[1].trim()))
+        // "Acme | Home" or "Acme - Tutoring for JEE": the brand is usually first
+        .and_then(|t| {
+            Regex::new(r"\s+[|\-\u{2013}\u{2014}:]\s+")
+                .expect("valid regex")
+                .split(&t)
+                .next()
+                .map(|s| s.trim().to_string())
+        })
+        .filter(|t| !t.is_empty());
+    let name = meta(&metas, &["og:site_name", "application-name"]).or(title);
+
+    let description = meta(
+        &metas,
+        &["og:description", "description", "twitter:description"],
+    )
+    .map(|d| truncate_chars(&d, MAX_DESCRIPTION_CHARS));
+    let og_image = meta(&metas, &["og:image", "og:image:url", "twitter:image"]);
+    let default_logo_url = logo_url(html, &links, og_image.as_deref())
+        .and_then(|href| page_url.join(&href).ok())
+        .filter(|u| matches!(u.scheme(), "http" | "https"))
+        .map(String::from);
+
+    CompanyDraft {
+        name,
+        description,
+        website: page_url.origin().ascii_serialization(),
+        industry: None,
+        company_size: size_hint(&text),
+        founded_year: founded_year(&text),
+        default_logo_url,
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct AiCompanySummary {
+    description: Option<String>,
+    industry: Option<String>,
+    company_size: Option<String>,
+}
+
+fn parse_ai_summary(raw: &str) -> Option<AiCompanySummary> {
+    let start = raw.find('{')?;
+    let end = raw.rfind('}')?;
+    serde_json::from_str(raw.get(start..=end)?).ok()
+}
+
+fn build_prompt(draft: &CompanyDraft, page_text: &str) -> String {
+    format!(
+        "Below is the text of the website of {name} ({website}).\n\
+        Return ONLY a JSON object with these keys:\n\
+        - \"description\": a neutral 2-3 sentence summary of what the company does, for job seekers\n\
+        - \"industry\": the company's industry in 1-3 words, e.g. \"Education\" or \"Financial Services\"\n\
+        - \"company_size\": one of {sizes}, or null if the text gives no indication\n\
+        Use only facts stated in the text. Use null for anything you cannot tell.\n\n\
+        Website text:\n{page_text}",
+        name = draft.name.as_deref().unwrap_or("the company"),
+        website = draft.website,
+        sizes = COMPANY_SIZES
+            .iter()
+            .map(|(_, s)| format!("\"{}\"", s))
+            .collect::<Vec<_>>()
+            .join(", "),
+    )
+}
+
+/// Fetch `website` and draft a company from it. With `use_ai`, the page is also summarized by
+/// AI; AI failures only add a warning, since the metadata draft is still useful.
+pub async fn enrich(
+    state: &AppState,
+    website: &str,
+    use_ai: bool,
+) -> Result<CompanyEnrichmentResponse, ApiError> {
+    let url = normalize_website(website)?;
+    let (page_url, html) = fetch_page(url).await?;
+    let mut draft = draft_from_page(&html, &page_url);
+    let mut warnings = Vec::new();
+    let mut ai_summarized = false;
+
+    if use_ai {
+        let prompt = build_prompt(
+            &draft,
+            &truncate_chars(&visible_text(&html), MAX_AI_TEXT_CHARS),
+        );
+        // Company descriptions feed job descriptions, so they share that model
+        match state
+            .llm
+            .generate_text(
+                TextGenerationPurpose::JobDescriptionGeneration,
+                &prompt,
+                None,
+            )
+            .await
+        {
+            Ok(raw) => match parse_ai_summary(&raw) {
+                Some(summary) => {
+                    ai_summarized = true;
+                    if let Some(description) = summary.description.filter(|d| !d.trim().is_empty())
+                    {
+                        draft.description =
+                            Some(truncate_chars(description.trim(), MAX_DESCRIPTION_CHARS));
+                    }
+                    draft.industry = summary
+                        .industry
+                        .map(|i| i.trim().to_string())
+                        .filter(|i| !i.is_empty());
+                    // The page's own headcount beats the model's guess
+                    if draft.company_size.is_none() {
+                        draft.company_size = summary
+                            .company_size
+                            .filter(|s| COMPANY_SIZES.iter().any(|(_, b)| b == s));
+                    }
+                }
+                None => warnings.push("AI summary could not be read".to_string()),
+            },
+            Err(e) => {
+                warn!(error = %e, website = %page_url, "AI company summary failed");
+                warnings.push(format!("AI summary unavailable: {}", e));
+            }
+        }
+    }
+
+    if let Some(name) = &draft.name {
+        let existing: Option<String> =
+            sqlx::query_scalar("SELECT id FROM companies WHERE name = ? COLLATE NOCASE")
+                .bind(name)
+                .fetch_optional(&state.db)
+                .await
+                .map_err(ApiError::DatabaseError)?;
+        if let Some(id) = existing {
+            warnings.push(format!(
+                "A company named '{}' already exists ({})",
+                name, id
+            ));
+        }
+    }
+    if draft.name.is_none() {
+        warnings.push("No company name found on the page".to_string());
+    }
+
+    info!(
+        website = %page_url,
+        ai_summarized,
+        "Drafted company from website"
+    );
+
+    Ok(CompanyEnrichmentResponse {
+        draft,
+        ai_summarized,
+        warnings,
+    })
+}
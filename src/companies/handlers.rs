@@ -1,21 +1,24 @@
+use super::enrichment;
 use super::models::{
-    CreateCompanyRequest, MessageResponse, SaveUrlAsAssetRequest, UpdateCompanyRequest,
+    CreateCompanyRequest, EnrichCompanyRequest, MergeCompanyRequest, MessageResponse,
+    SaveUrlAsAssetRequest, UpdateCompanyRequest,
 };
 use super::services::CompaniesService;
 use super::validators;
 use crate::auth::AuthedUser;
-use crate::common::{ApiError, AppState};
+use crate::common::{http_cache, ApiError, AppState};
 use axum::{
     extract::{Extension, Multipart, Path},
-    http::StatusCode,
-    response::IntoResponse,
+    http::{HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
     Json,
 };
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use tracing::{error, info};
 
-use crate::common::generate_raw_id;
+use crate::common::storage;
+use crate::services::storage_usage;
 
 // ============================================================================
 // Company CRUD Handlers
@@ -56,6 +59,121 @@ pub async fn create_company(
     Ok((StatusCode::CREATED, Json(company)))
 }
 
+/// POST /api/admin/companies/enrich - Draft a company from its website
+pub async fn enrich_company(
+    Extension(state): Extension<Arc<RwLock<AppState>>>,
+    user: AuthedUser,
+    Json(request): Json<EnrichCompanyRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    if !user.is_admin {
+        return Err(ApiError::Forbidden("Admin access required".to_string()));
+    }
+
+    let app_state = state.read().await.clone();
+
+    let response = enrichment::enrich(&app_state, &request.website, request.use_ai).await?;
+
+    Ok(Json(response))
+}
+
+/// POST /api/admin/companies/import - Create companies from a CSV file (multipart `file`,
+/// optional `dry_run`)
+pub async fn import_companies(
+    Extension(state): Extension<Arc<RwLock<AppState>>>,
+    user: AuthedUser,
+    mut multipart: Multipart,
+) -> Result<impl IntoResponse, ApiError> {
+    if !user.is_admin {
+        return Err(ApiError::Forbidden("Admin access required".to_string()));
+    }
+
+    let mut file_data: Option<Vec<u8>> = None;
+    let mut dry_run = false;
+
+    while let Some(field) = multipart
+        .next_field()
+        .await
+        .map_err(|e| ApiError::BadRequest(format!("Failed to read multipart field: {}", e)))?
+    {
+        match field.name().unwrap_or("") {
+            "file" => {
+                file_data = Some(
+                    field
+                        .bytes()
+                        .await
+                        .map_err(|e| ApiError::BadRequest(format!("Failed to read file: {}", e)))?
+                        .to_vec(),
+                );
+            }
+            "dry_run" => {
+                let value = field
+                    .text()
+                    .await
+                    .map_err(|e| ApiError::BadRequest(format!("Failed to read dry_run: {}", e)))?;
+                dry_run = value == "true" || value == "1";
+            }
+            _ => {}
+        }
+    }
+
+    let file_data =
+        file_data.ok_or_else(|| ApiError::BadRequest("No file provided".to_string()))?;
+
+    let app_state = state.read().await;
+    let companies_service = CompaniesService::new(app_state.db.clone());
+
+    let response = companies_service
+        .import_companies(&file_data, dry_run)
+        .await?;
+
+    Ok(Json(response))
+}
+
+/// GET /api/admin/companies/duplicates - Companies sharing a name or website domain
+pub async fn get_duplicate_companies(
+    Extension(state): Extension<Arc<RwLock<AppState>>>,
+    user: AuthedUser,
+) -> Result<impl IntoResponse, ApiError> {
+    if !user.is_admin {
+        return Err(ApiError::Forbidden("Admin access required".to_string()));
+    }
+
+    let app_state = state.read().await;
+    let companies_service = CompaniesService::new(app_state.db.clone());
+
+    let duplicates = companies_service.find_duplicates().await?;
+
+    Ok(Json(duplicates))
+}
+
+/// POST /api/admin/companies/:id/merge - Merge a duplicate company into this one
+pub async fn merge_company(
+    Extension(state): Extension<Arc<RwLock<AppState>>>,
+    user: AuthedUser,
+    Path(company_id): Path<String>,
+    Json(request): Json<MergeCompanyRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    if !user.is_admin {
+        return Err(ApiError::Forbidden("Admin access required".to_string()));
+    }
+
+    let app_state = state.read().await;
+    let companies_service = CompaniesService::new(app_state.db.clone());
+
+    let response = companies_service
+        .merge_companies(&company_id, &request.duplicate_id)
+        .await?;
+    // Merged jobs may be active and now list under another company
+    app_state.feed_cache.invalidate().await;
+
+    info!(
+        "Company {} merged into {} by {}",
+        request.duplicate_id, company_id, user.id
+    );
+
+    Ok(Json(response))
+}
+
 /// GET /api/admin/companies/:id - Get company by ID
 pub async fn get_company_by_id(
     Extension(state): Extension<Arc<RwLock<AppState>>>,
@@ -219,12 +337,26 @@ pub async fn upload_company_asset(
     // Validate file type (images only)
     validators::validate_image_mime_type(&mime_type).map_err(ApiError::ValidationError)?;
 
+    storage_usage::ensure_within_quota(
+        &app_state,
+        storage_usage::OWNER_COMPANY,
+        &company_id,
+        file_data.len() as i64,
+    )
+    .await?;
+
     // Generate unique filename
     let extension = std::path::Path::new(&filename)
         .extension()
         .and_then(|e| e.to_str())
         .unwrap_or("png");
-    let unique_filename = format!("{}_{}.{}", asset_type, generate_raw_id(8), extension);
+    let unique_filename = format!(
+        "{}_{}_{}.{}",
+        asset_type,
+        company_id,
+        storage::content_hash(&file_data),
+        extension
+    );
 
     // Determine storage directory based on asset type
     let storage_dir = if asset_type == "logo" {
@@ -260,6 +392,16 @@ pub async fn upload_company_asset(
         )
         .await?;
 
+    storage_usage::record_file(
+        &app_state.db,
+        storage_usage::CATEGORY_COMPANY_ASSET,
+        &asset.id,
+        storage_usage::OWNER_COMPANY,
+        &company_id,
+        asset.file_size,
+    )
+    .await;
+
     info!(
         "Uploaded company asset: {} for company {}",
         asset.id, company_id
@@ -288,6 +430,12 @@ pub async fn delete_company_asset(
     companies_service
         .delete_company_asset(&company_id, &asset_id)
         .await?;
+    storage_usage::release_file(
+        &app_state.db,
+        storage_usage::CATEGORY_COMPANY_ASSET,
+        &asset_id,
+    )
+    .await;
 
     // Delete file from disk
     let storage_dir = if asset.asset_type == "logo" {
@@ -400,6 +548,16 @@ pub async fn save_url_as_company_asset(
         )
         .await?;
 
+    storage_usage::record_file(
+        &app_state.db,
+        storage_usage::CATEGORY_COMPANY_ASSET,
+        &asset.id,
+        storage_usage::OWNER_COMPANY,
+        &company_id,
+        asset.file_size,
+    )
+    .await;
+
     info!(
         "Saved URL as company asset: {} for company {}",
         asset.id, company_id
@@ -416,11 +574,12 @@ pub async fn save_url_as_company_asset(
 pub async fn get_company_public(
     Extension(state): Extension<Arc<RwLock<AppState>>>,
     Path(company_id): Path<String>,
-) -> Result<impl IntoResponse, ApiError> {
+    headers: HeaderMap,
+) -> Result<Response, ApiError> {
     let app_state = state.read().await;
-    let companies_service = CompaniesService::new(app_state.db.clone());
+    let companies_service = CompaniesService::new(app_state.db_read.clone());
 
     let company = companies_service.get_company_by_id(&company_id).await?;
 
-    Ok(Json(company))
+    http_cache::cached_json(&headers, &company, http_cache::PROFILE_CACHE_CONTROL)
 }
@@ -4,8 +4,10 @@
 //! - Company CRUD operations
 //! - Company asset management (logos and images)
 //! - Company information and metadata
+//! - Drafting companies from their website
 
 pub mod assets;
+pub mod enrichment;
 pub mod handlers;
 pub mod models;
 pub mod routes;
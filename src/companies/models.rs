@@ -1,5 +1,6 @@
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
+use validator::Validate;
 
 fn deserialize_bool_from_int<'de, D>(deserializer: D) -> Result<i64, D::Error>
 where
@@ -49,13 +50,19 @@ pub struct CompanyAsset {
     pub created_at: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Validate)]
 pub struct CreateCompanyRequest {
+    #[validate(length(max = 255, message = "Company name must not exceed 255 characters"))]
     pub name: String,
     pub description: Option<String>,
+    #[validate(custom(
+        function = "crate::common::validation::validate_http_url",
+        message = "Website must be a valid URL starting with http:// or https://"
+    ))]
     pub website: Option<String>,
     pub industry: Option<String>,
     pub company_size: Option<String>,
+    #[validate(range(min = 1800, max = 2100, message = "Founded year must be between 1800 and 2100"))]
     pub founded_year: Option<i32>,
     pub headquarters: Option<serde_json::Value>, // Will be stored as JSON string
     pub operating_locations: Option<Vec<serde_json::Value>>, // Will be stored as JSON array string
@@ -90,3 +97,92 @@ pub struct SaveUrlAsAssetRequest {
     pub asset_type: String,
     pub is_default: Option<bool>,
 }
+
+#[derive(Debug, Deserialize)]
+pub struct EnrichCompanyRequest {
+    pub website: String,
+    /// Also summarize the page with AI to suggest a description, industry and size
+    #[serde(default)]
+    pub use_ai: bool,
+}
+
+/// Pre-filled company creation form; fields match `CreateCompanyRequest`
+#[derive(Debug, Default, Serialize)]
+pub struct CompanyDraft {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub website: String,
+    pub industry: Option<String>,
+    pub company_size: Option<String>,
+    pub founded_year: Option<i32>,
+    pub default_logo_url: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CompanyEnrichmentResponse {
+    pub draft: CompanyDraft,
+    pub ai_summarized: bool,
+    pub warnings: Vec<String>,
+}
+
+/// Outcome of one data row of a company CSV import
+#[derive(Debug, Serialize)]
+pub struct ImportedCompanyRow {
+    /// Line in the file, counting the header as line 1
+    pub line: usize,
+    pub name: String,
+    /// `created`, `duplicate` or `invalid`
+    pub action: String,
+    /// The new company, or the existing one a duplicate matched
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub company_id: Option<String>,
+    /// `name` or `domain`, for duplicates
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub matched_by: Option<String>,
+    /// Earlier line in the same file a duplicate matched
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duplicate_of_line: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ImportCompaniesResponse {
+    pub dry_run: bool,
+    pub created: usize,
+    pub duplicates: usize,
+    pub invalid: usize,
+    pub rows: Vec<ImportedCompanyRow>,
+}
+
+/// Companies that look like the same organisation
+#[derive(Debug, Serialize)]
+pub struct CompanyDuplicateGroup {
+    /// `name` or `domain`
+    pub matched_by: String,
+    pub value: String,
+    pub companies: Vec<Company>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MergeCompanyRequest {
+    /// Company folded into the one in the path, then archived
+    pub duplicate_id: String,
+}
+
+/// What a merge moved to the surviving company
+#[derive(Debug, Default, Serialize)]
+pub struct CompanyMergeSummary {
+    pub jobs: u64,
+    pub assets: u64,
+    pub templates: u64,
+    /// Departments and compensation bands
+    pub other_records: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MergeCompanyResponse {
+    pub company: Company,
+    pub archived_id: String,
+    pub summary: CompanyMergeSummary,
+}
@@ -20,12 +20,28 @@ pub fn companies_routes() -> Router {
             "/api/admin/companies",
             get(handlers::get_companies).post(handlers::create_company),
         )
+        .route(
+            "/api/admin/companies/enrich",
+            post(handlers::enrich_company),
+        )
+        .route(
+            "/api/admin/companies/import",
+            post(handlers::import_companies),
+        )
+        .route(
+            "/api/admin/companies/duplicates",
+            get(handlers::get_duplicate_companies),
+        )
         .route(
             "/api/admin/companies/:id",
             get(handlers::get_company_by_id)
                 .put(handlers::update_company)
                 .delete(handlers::delete_company),
         )
+        .route(
+            "/api/admin/companies/:id/merge",
+            post(handlers::merge_company),
+        )
         // Company asset routes
         .route(
             "/api/admin/companies/:id/assets",
@@ -1,8 +1,107 @@
-use super::models::{Company, CompanyAsset, CreateCompanyRequest, UpdateCompanyRequest};
+use super::enrichment::normalize_website;
+use super::models::{
+    Company, CompanyAsset, CompanyDuplicateGroup, CompanyMergeSummary, CreateCompanyRequest,
+    ImportCompaniesResponse, ImportedCompanyRow, MergeCompanyResponse, UpdateCompanyRequest,
+};
 use crate::common::{generate_asset_id, generate_company_id, ApiError, Validator};
-use sqlx::SqlitePool;
+use sqlx::{Sqlite, SqlitePool, Transaction};
+use std::collections::{BTreeMap, HashMap};
 use tracing::info;
 
+/// Largest CSV import accepted in one request
+pub const MAX_IMPORT_ROWS: usize = 1000;
+
+/// Company fields filled from the duplicate where the surviving company has none
+const MERGE_FILL_FIELDS: &[&str] = &[
+    "description",
+    "website",
+    "industry",
+    "company_size",
+    "founded_year",
+    "headquarters",
+    "operating_locations",
+    "culture",
+    "benefits",
+    "default_logo_url",
+];
+
+/// Name compared when looking for duplicates: case, punctuation and spacing ignored
+pub fn name_key(name: &str) -> String {
+    name.split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+/// Host of a company website without `www.`, e.g. "acme.example"
+pub fn company_domain(website: &str) -> Option<String> {
+    let url = normalize_website(website).ok()?;
+    let host = url.host_str()?.to_lowercase();
+    Some(host.strip_prefix("www.").unwrap_or(&host).to_string())
+}
+
+/// A company creation request from one CSV row, using the columns the header names
+fn company_from_row(
+    columns: &HashMap<String, usize>,
+    record: &csv::StringRecord,
+) -> Result<CreateCompanyRequest, String> {
+    let field = |name: &str| {
+        columns
+            .get(name)
+            .and_then(|i| record.get(*i))
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+            .map(str::to_string)
+    };
+
+    let founded_year = field("founded_year")
+        .map(|y| {
+            y.parse::<i32>()
+                .map_err(|_| format!("founded_year '{}' is not a year", y))
+        })
+        .transpose()?;
+    // "City, State, Country", stored in the same shape the admin UI saves
+    let headquarters = field("headquarters").map(|h| {
+        let parts: Vec<&str> = h.split(',').map(str::trim).collect();
+        let mut place = serde_json::Map::new();
+        for (key, part) in ["city", "state", "country"].iter().zip(&parts) {
+            if !part.is_empty() {
+                place.insert(key.to_string(), serde_json::Value::from(*part));
+            }
+        }
+        serde_json::Value::Object(place)
+    });
+    let benefits = field("benefits").map(|b| {
+        b.split([';', '|'])
+            .map(str::trim)
+            .filter(|b| !b.is_empty())
+            .map(str::to_string)
+            .collect()
+    });
+
+    Ok(CreateCompanyRequest {
+        name: field("name").unwrap_or_default(),
+        description: field("description"),
+        // Spreadsheets usually hold bare domains
+        website: field("website").map(|w| {
+            if w.contains("://") {
+                w
+            } else {
+                format!("https://{}", w)
+            }
+        }),
+        industry: field("industry"),
+        company_size: field("company_size"),
+        founded_year,
+        headquarters,
+        operating_locations: None,
+        culture: None,
+        benefits,
+        default_logo_url: field("default_logo_url"),
+    })
+}
+
 pub struct CompaniesService {
     db: SqlitePool,
 }
@@ -24,6 +123,7 @@ impl CompaniesService {
                    headquarters, operating_locations, culture, benefits, default_logo_url,
                    created_at, updated_at
             FROM companies
+            WHERE archived_at IS NULL
             ORDER BY name ASC
             "#,
         )
@@ -486,4 +586,386 @@ impl CompaniesService {
 
         self.get_company_asset_by_id(asset_id).await
     }
+
+    // ============================================================================
+    // Bulk Import and Merge
+    // ============================================================================
+
+    /// Create companies from CSV rows, skipping rows that match an existing company (or an
+    /// earlier row) by name or website domain. Archived companies match their survivor.
+    pub async fn import_companies(
+        &self,
+        data: &[u8],
+        dry_run: bool,
+    ) -> Result<ImportCompaniesResponse, ApiError> {
+        let mut reader = csv::ReaderBuilder::new()
+            .flexible(true)
+            .trim(csv::Trim::All)
+            .from_reader(data);
+        let columns: HashMap<String, usize> = reader
+            .headers()
+            .map_err(|e| ApiError::ValidationError(format!("Invalid CSV header: {}", e)))?
+            .iter()
+            .enumerate()
+            .map(|(i, h)| (h.to_lowercase().replace(' ', "_"), i))
+            .collect();
+        if !columns.contains_key("name") {
+            return Err(ApiError::ValidationError(
+                "CSV must have a 'name' column".to_string(),
+            ));
+        }
+        let records = reader
+            .records()
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| ApiError::ValidationError(format!("Invalid CSV: {}", e)))?;
+        if records.is_empty() {
+            return Err(ApiError::ValidationError("CSV has no rows".to_string()));
+        }
+        if records.len() > MAX_IMPORT_ROWS {
+            return Err(ApiError::ValidationError(format!(
+                "CSV has {} rows; at most {} can be imported at once",
+                records.len(),
+                MAX_IMPORT_ROWS
+            )));
+        }
+
+        let existing: Vec<(String, String, Option<String>, Option<String>)> =
+            sqlx::query_as("SELECT id, name, website, merged_into FROM companies")
+                .fetch_all(&self.db)
+                .await
+                .map_err(ApiError::DatabaseError)?;
+        let mut known_names = HashMap::new();
+        let mut known_domains = HashMap::new();
+        for (id, name, website, merged_into) in existing {
+            let id = merged_into.unwrap_or(id);
+            known_names.insert(name_key(&name), id.clone());
+            if let Some(domain) = website.as_deref().and_then(company_domain) {
+                known_domains.insert(domain, id);
+            }
+        }
+
+        // Lines of this file already seen, by name and by domain
+        let mut seen_names: HashMap<String, usize> = HashMap::new();
+        let mut seen_domains: HashMap<String, usize> = HashMap::new();
+        let mut rows = Vec::with_capacity(records.len());
+
+        for (index, record) in records.iter().enumerate() {
+            let line = index + 2;
+            let mut row = ImportedCompanyRow {
+                line,
+                name: columns
+                    .get("name")
+                    .and_then(|i| record.get(*i))
+                    .unwrap_or_default()
+                    .to_string(),
+                action: "invalid".to_string(),
+                company_id: None,
+                matched_by: None,
+                duplicate_of_line: None,
+                error: None,
+            };
+
+            let request = match company_from_row(&columns, record) {
+                Ok(request) => request,
+                Err(e) => {
+                    row.error = Some(e);
+                    rows.push(row);
+                    continue;
+                }
+            };
+            if request.name.is_empty() {
+                row.error = Some("name is required".to_string());
+                rows.push(row);
+                continue;
+            }
+
+            let key = name_key(&request.name);
+            let domain = request.website.as_deref().and_then(company_domain);
+            let existing = known_names.get(&key).map(|id| ("name", id)).or_else(|| {
+                domain
+                    .as_ref()
+                    .and_then(|d| known_domains.get(d))
+                    .map(|id| ("domain", id))
+            });
+            let earlier = seen_names.get(&key).map(|l| ("name", *l)).or_else(|| {
+                domain
+                    .as_ref()
+                    .and_then(|d| seen_domains.get(d))
+                    .map(|l| ("domain", *l))
+            });
+            if let Some((matched_by, id)) = existing {
+                row.action = "duplicate".to_string();
+                row.matched_by = Some(matched_by.to_string());
+                row.company_id = Some(id.clone());
+                rows.push(row);
+                continue;
+            }
+            if let Some((matched_by, earlier_line)) = earlier {
+                row.action = "duplicate".to_string();
+                row.matched_by = Some(matched_by.to_string());
+                row.duplicate_of_line = Some(earlier_line);
+                rows.push(row);
+                continue;
+            }
+
+            let validation = request.validate(&request);
+            if !validation.is_valid {
+                row.error = Some(ApiError::from(validation).to_string());
+                rows.push(row);
+                continue;
+            }
+
+            if !dry_run {
+                match self.create_company(request).await {
+                    Ok(company) => row.company_id = Some(company.id),
+                    Err(e @ ApiError::DatabaseError(_)) => return Err(e),
+                    Err(e) => {
+                        row.error = Some(e.to_string());
+                        rows.push(row);
+                        continue;
+                    }
+                }
+            }
+            row.action = "created".to_string();
+            seen_names.insert(key, line);
+            if let Some(domain) = domain {
+                seen_domains.insert(domain, line);
+            }
+            rows.push(row);
+        }
+
+        let count = |action: &str| rows.iter().filter(|r| r.action == action).count();
+        let response = ImportCompaniesResponse {
+            dry_run,
+            created: count("created"),
+            duplicates: count("duplicate"),
+            invalid: count("invalid"),
+            rows,
+        };
+
+        info!(
+            "Company import{}: {} created, {} duplicates, {} invalid",
+            if dry_run { " (dry run)" } else { "" },
+            response.created,
+            response.duplicates,
+            response.invalid
+        );
+
+        Ok(response)
+    }
+
+    /// Groups of active companies sharing a name or website domain
+    pub async fn find_duplicates(&self) -> Result<Vec<CompanyDuplicateGroup>, ApiError> {
+        let companies = self.get_all_companies().await?;
+
+        let mut by_name: BTreeMap<String, Vec<&Company>> = BTreeMap::new();
+        let mut by_domain: BTreeMap<String, Vec<&Company>> = BTreeMap::new();
+        for company in &companies {
+            by_name
+                .entry(name_key(&company.name))
+                .or_default()
+                .push(company);
+            if let Some(domain) = company.website.as_deref().and_then(company_domain) {
+                by_domain.entry(domain).or_default().push(company);
+            }
+        }
+
+        let groups = |matched_by: &str, map: BTreeMap<String, Vec<&Company>>| {
+            map.into_iter()
+                .filter(|(_, companies)| companies.len() > 1)
+                .map(|(value, companies)| CompanyDuplicateGroup {
+                    matched_by: matched_by.to_string(),
+                    value,
+                    companies: companies.into_iter().cloned().collect(),
+                })
+                .collect::<Vec<_>>()
+        };
+        let mut duplicates = groups("name", by_name);
+        duplicates.extend(groups("domain", by_domain));
+
+        Ok(duplicates)
+    }
+
+    /// Fold `duplicate_id` into `company_id`: its jobs, assets, templates, departments and
+    /// compensation bands move to the survivor, empty survivor fields are filled from it, and it
+    /// is archived with `merged_into` pointing at the survivor.
+    pub async fn merge_companies(
+        &self,
+        company_id: &str,
+        duplicate_id: &str,
+    ) -> Result<MergeCompanyResponse, ApiError> {
+        if company_id == duplicate_id {
+            return Err(ApiError::ValidationError(
+                "A company cannot be merged into itself".to_string(),
+            ));
+        }
+
+        let mut names = Vec::with_capacity(2);
+        for id in [company_id, duplicate_id] {
+            let (name, archived_at): (String, Option<String>) =
+                sqlx::query_as("SELECT name, archived_at FROM companies WHERE id = ?")
+                    .bind(id)
+                    .fetch_optional(&self.db)
+                    .await
+                    .map_err(ApiError::DatabaseError)?
+                    .ok_or_else(|| ApiError::NotFound(format!("Company not found: {}", id)))?;
+            if archived_at.is_some() {
+                return Err(ApiError::BadRequest(format!(
+                    "Company {} has already been merged",
+                    id
+                )));
+            }
+            names.push(name);
+        }
+
+        let mut tx = self.db.begin().await.map_err(ApiError::DatabaseError)?;
+        let summary = merge_into(&mut tx, company_id, &names[0], duplicate_id, &names[1])
+            .await
+            .map_err(ApiError::DatabaseError)?;
+        tx.commit().await.map_err(ApiError::DatabaseError)?;
+
+        info!(
+            "Merged company {} into {}: {} jobs, {} assets, {} templates",
+            duplicate_id, company_id, summary.jobs, summary.assets, summary.templates
+        );
+
+        Ok(MergeCompanyResponse {
+            company: self.get_company_by_id(company_id).await?,
+            archived_id: duplicate_id.to_string(),
+            summary,
+        })
+    }
+}
+
+/// Move everything that belongs to `source` to `target` and archive `source`
+async fn merge_into(
+    tx: &mut Transaction<'_, Sqlite>,
+    target: &str,
+    target_name: &str,
+    source: &str,
+    source_name: &str,
+) -> Result<CompanyMergeSummary, sqlx::Error> {
+    let mut summary = CompanyMergeSummary::default();
+
+    let fills = MERGE_FILL_FIELDS
+        .iter()
+        .map(|f| {
+            format!("{f} = COALESCE(NULLIF({f}, ''), (SELECT {f} FROM companies WHERE id = ?))")
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    let sql = format!(
+        "UPDATE companies SET {}, updated_at = datetime('now') WHERE id = ?",
+        fills
+    );
+    let mut update = sqlx::query(&sql);
+    for _ in MERGE_FILL_FIELDS {
+        update = update.bind(source);
+    }
+    update.bind(target).execute(&mut **tx).await?;
+
+    // Jobs that named the duplicate take the survivor's name
+    summary.jobs = sqlx::query(
+        "UPDATE jobs SET company_id = ?, company = CASE WHEN company = ? THEN ? ELSE company END WHERE company_id = ?",
+    )
+    .bind(target)
+    .bind(source_name)
+    .bind(target_name)
+    .bind(source)
+    .execute(&mut **tx)
+    .await?
+    .rows_affected();
+
+    // The survivor's default logo and image stay the defaults
+    sqlx::query(
+        r#"
+        UPDATE company_assets SET is_default = 0
+        WHERE company_id = ? AND is_default = 1
+          AND asset_type IN (SELECT asset_type FROM company_assets WHERE company_id = ? AND is_default = 1)
+        "#,
+    )
+    .bind(source)
+    .bind(target)
+    .execute(&mut **tx)
+    .await?;
+    summary.assets = sqlx::query("UPDATE company_assets SET company_id = ? WHERE company_id = ?")
+        .bind(target)
+        .bind(source)
+        .execute(&mut **tx)
+        .await?
+        .rows_affected();
+    sqlx::query(
+        "UPDATE storage_usage SET owner_id = ? WHERE owner_type = 'company' AND owner_id = ?",
+    )
+    .bind(target)
+    .bind(source)
+    .execute(&mut **tx)
+    .await?;
+
+    // Templates named like one of the survivor's are told apart by the duplicate's name
+    sqlx::query(
+        r#"
+        UPDATE job_templates SET name = name || ' (' || ? || ')'
+        WHERE company_id = ?
+          AND LOWER(name) IN (SELECT LOWER(name) FROM job_templates WHERE company_id = ?)
+        "#,
+    )
+    .bind(source_name)
+    .bind(source)
+    .bind(target)
+    .execute(&mut **tx)
+    .await?;
+    summary.templates = sqlx::query("UPDATE job_templates SET company_id = ? WHERE company_id = ?")
+        .bind(target)
+        .bind(source)
+        .execute(&mut **tx)
+        .await?
+        .rows_affected();
+
+    // Departments are unique by name per company: jobs in a department the survivor also has
+    // move to the survivor's; the rest of the departments move with their teams
+    sqlx::query(
+        r#"
+        UPDATE jobs SET department_id = (
+            SELECT t.id FROM departments t
+            JOIN departments d ON d.name = t.name
+            WHERE d.id = jobs.department_id AND t.company_id = ?
+        )
+        WHERE department_id IN (
+            SELECT d.id FROM departments d
+            JOIN departments t ON t.name = d.name AND t.company_id = ?
+            WHERE d.company_id = ?
+        )
+        "#,
+    )
+    .bind(target)
+    .bind(target)
+    .bind(source)
+    .execute(&mut **tx)
+    .await?;
+    for table in ["departments", "compensation_bands"] {
+        summary.other_records += sqlx::query(&format!(
+            "UPDATE OR IGNORE {} SET company_id = ? WHERE company_id = ?",
+            table
+        ))
+        .bind(target)
+        .bind(source)
+        .execute(&mut **tx)
+        .await?
+        .rows_affected();
+    }
+
+    // Companies merged into the duplicate earlier now point straight at the survivor
+    sqlx::query("UPDATE companies SET merged_into = ? WHERE merged_into = ?")
+        .bind(target)
+        .bind(source)
+        .execute(&mut **tx)
+        .await?;
+    sqlx::query("UPDATE companies SET merged_into = ?, archived_at = datetime('now') WHERE id = ?")
+        .bind(target)
+        .bind(source)
+        .execute(&mut **tx)
+        .await?;
+
+    Ok(summary)
 }
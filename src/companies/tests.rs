@@ -214,4 +214,78 @@ mod tests {
 
         assert_eq!(response.message, "Success");
     }
+
+    #[test]
+    fn test_normalize_website_adds_scheme_and_rejects_other_schemes() {
+        let url = enrichment::normalize_website(" acme.example/about ").unwrap();
+        assert_eq!(url.as_str(), "https://acme.example/about");
+        assert!(enrichment::normalize_website("ftp://acme.example").is_err());
+        assert!(enrichment::normalize_website("https://").is_err());
+    }
+
+    #[test]
+    fn test_draft_from_page_reads_metadata() {
+        // Test that names, descriptions, logos and hints are pulled from the page
+        let html = r#"<html><head>
+            <title>Acme Academy | Home</title>
+            <meta name="description" content="Coaching for JEE &amp; NEET.">
+            <meta property="og:image" content="https://cdn.example/share.png">
+            <link rel="apple-touch-icon" href="/icons/touch.png">
+            <script>var x = "founded in 1901";</script>
+            </head><body>Founded in 2009. Our 120+ employees teach 5,000 students.</body></html>"#;
+        let page = reqwest::Url::parse("https://acme.example/en/home").unwrap();
+
+        let draft = enrichment::draft_from_page(html, &page);
+
+        assert_eq!(draft.name.as_deref(), Some("Acme Academy"));
+        assert_eq!(
+            draft.description.as_deref(),
+            Some("Coaching for JEE & NEET.")
+        );
+        assert_eq!(draft.website, "https://acme.example");
+        assert_eq!(
+            draft.default_logo_url.as_deref(),
+            Some("https://acme.example/icons/touch.png")
+        );
+        assert_eq!(draft.founded_year, Some(2009));
+        assert_eq!(draft.company_size.as_deref(), Some("51-200"));
+        assert_eq!(draft.industry, None);
+    }
+
+    #[test]
+    fn test_draft_from_page_prefers_site_name_and_json_ld_logo() {
+        let html = r#"<title>Welcome - Acme</title>
+            <meta property="og:site_name" content="Acme Learning">
+            <script type="application/ld+json">{"@type":"Organization","logo":{"@type":"ImageObject","url":"https:\/\/acme.example\/logo.svg"}}</script>"#;
+        let page = reqwest::Url::parse("https://acme.example").unwrap();
+
+        let draft = enrichment::draft_from_page(html, &page);
+
+        assert_eq!(draft.name.as_deref(), Some("Acme Learning"));
+        assert_eq!(
+            draft.default_logo_url.as_deref(),
+            Some("https://acme.example/logo.svg")
+        );
+        assert_eq!(draft.founded_year, None);
+        assert_eq!(draft.company_size, None);
+    }
+
+    #[test]
+    fn test_duplicate_keys_ignore_case_punctuation_and_www() {
+        // Test that names and domains compare the way people write them
+        assert_eq!(
+            services::name_key("  Acme, Inc. "),
+            services::name_key("ACME inc")
+        );
+        assert_ne!(services::name_key("Acme"), services::name_key("Acme Labs"));
+        assert_eq!(
+            services::company_domain("https://www.Acme.example/careers").as_deref(),
+            Some("acme.example")
+        );
+        assert_eq!(
+            services::company_domain("acme.example").as_deref(),
+            Some("acme.example")
+        );
+        assert_eq!(services::company_domain("not a url"), None);
+    }
 }
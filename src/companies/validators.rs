@@ -1,30 +1,15 @@
 use super::models::CreateCompanyRequest;
-use crate::common::{ValidationResult, Validator};
+use crate::common::{validate_fields, ValidationResult, Validator};
 
 impl Validator<CreateCompanyRequest> for CreateCompanyRequest {
     fn validate(&self, data: &CreateCompanyRequest) -> ValidationResult {
-        let mut result = ValidationResult::new();
+        // Name length, website and founded year are declared on the request
+        let mut result = validate_fields(data);
 
         if data.name.trim().is_empty() {
             result.add_error("name", "Company name is required");
         }
 
-        if data.name.len() > 255 {
-            result.add_error("name", "Company name must not exceed 255 characters");
-        }
-
-        if let Some(website) = &data.website {
-            if !website.is_empty()
-                && !website.starts_with("http://")
-                && !website.starts_with("https://")
-            {
-                result.add_error(
-                    "website",
-                    "Website must be a valid URL starting with http:// or https://",
-                );
-            }
-        }
-
         result
     }
 }
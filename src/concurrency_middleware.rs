@@ -0,0 +1,191 @@
+// concurrency_middleware.rs
+//! Per-endpoint concurrency limits with load shedding
+//!
+//! Routes are grouped into budgets, each allowed a fixed number of requests in flight. A
+//! request that finds its budget used up is shed at once with a 503 and Retry-After instead of
+//! waiting for a slot: AI endpoints can sit on an OpenAI call for minutes and exports scan whole
+//! tables, so letting them queue would tie up the runtime and the database pool for everyone
+//! else. Public listings get a large budget of their own so a burst of AI work can't starve
+//! them, and the general budget caps everything else.
+
+use crate::body_limit_middleware::route_matches;
+use crate::common::{ApiError, ErrorCode};
+use axum::{
+    extract::Request,
+    http::{header, HeaderValue},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use std::sync::{Arc, OnceLock};
+use tokio::sync::Semaphore;
+use tracing::warn;
+
+/// A group of routes sharing one concurrency limit
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Budget {
+    Ai,
+    Export,
+    PublicListing,
+    General,
+}
+
+impl Budget {
+    const ALL: [Budget; 4] = [
+        Budget::Ai,
+        Budget::Export,
+        Budget::PublicListing,
+        Budget::General,
+    ];
+
+    fn name(&self) -> &'static str {
+        match self {
+            Budget::Ai => "ai",
+            Budget::Export => "export",
+            Budget::PublicListing => "public_listing",
+            Budget::General => "general",
+        }
+    }
+
+    /// Environment variable overriding the limit
+    fn env_var(&self) -> &'static str {
+        match self {
+            Budget::Ai => "CONCURRENCY_LIMIT_AI",
+            Budget::Export => "CONCURRENCY_LIMIT_EXPORT",
+            Budget::PublicListing => "CONCURRENCY_LIMIT_PUBLIC_LISTING",
+            Budget::General => "CONCURRENCY_LIMIT_GENERAL",
+        }
+    }
+
+    fn default_limit(&self) -> usize {
+        match self {
+            Budget::Ai => 4,
+            Budget::Export => 2,
+            Budget::PublicListing => 256,
+            Budget::General => 512,
+        }
+    }
+
+    fn limit(&self) -> usize {
+        std::env::var(self.env_var())
+            .ok()
+            .and_then(|v| v.parse::<usize>().ok())
+            .filter(|n| *n > 0)
+            .unwrap_or_else(|| self.default_limit())
+    }
+
+    /// Seconds a shed client is told to wait, roughly how long a slot takes to free up
+    fn retry_after_secs(&self) -> u64 {
+        match self {
+            Budget::Ai => 10,
+            Budget::Export => 30,
+            Budget::PublicListing | Budget::General => 1,
+        }
+    }
+
+    fn semaphore(&self) -> Arc<Semaphore> {
+        static SEMAPHORES: OnceLock<Vec<Arc<Semaphore>>> = OnceLock::new();
+        let semaphores = SEMAPHORES.get_or_init(|| {
+            Budget::ALL
+                .iter()
+                .map(|budget| Arc::new(Semaphore::new(budget.limit())))
+                .collect()
+        });
+        let index = Budget::ALL.iter().position(|b| b == self).unwrap_or(0);
+        semaphores[index].clone()
+    }
+}
+
+/// Routes with a budget of their own; `:param` matches any one segment
+const BUDGET_ROUTES: &[(&str, Budget)] = &[
+    ("/api/admin/jobs/ai/:action", Budget::Ai),
+    (
+        "/api/admin/jobs/:job_id/content/:component_type/generate",
+        Budget::Ai,
+    ),
+    ("/api/admin/jobs/:job_id/generate-image", Budget::Ai),
+    ("/api/admin/job-templates/ai", Budget::Ai),
+    ("/api/admin/applications/:id/feedback/generate", Budget::Ai),
+    ("/api/admin/candidates/ai/generate-email", Budget::Ai),
+    ("/api/admin/companies/enrich", Budget::Ai),
+    ("/api/resumes/:id/scan", Budget::Ai),
+    ("/api/admin/export/:kind", Budget::Export),
+    ("/api/admin/job-templates/export", Budget::Export),
+    ("/api/admin/conversations/:user_id/export", Budget::Export),
+    ("/api/admin/jobs/:id/resumes.zip", Budget::Export),
+    ("/api/admin/resume-exports/:id/download", Budget::Export),
+    ("/api/admin/reports/run", Budget::Export),
+    ("/api/jobs", Budget::PublicListing),
+    ("/api/jobs/:id", Budget::PublicListing),
+    ("/api/jobs/:id/similar", Budget::PublicListing),
+    ("/feeds/jobs.rss", Budget::PublicListing),
+    (
+        "/feeds/companies/:company_id/jobs.rss",
+        Budget::PublicListing,
+    ),
+];
+
+fn classify(path: &str) -> Budget {
+    BUDGET_ROUTES
+        .iter()
+        .find(|(pattern, _)| route_matches(pattern, path))
+        .map(|(_, budget)| *budget)
+        .unwrap_or(Budget::General)
+}
+
+fn shed(budget: Budget) -> Response {
+    let mut response = ApiError::Coded(
+        ErrorCode::ServerBusy,
+        "The server is busy; please retry shortly".to_string(),
+    )
+    .into_response();
+    response.headers_mut().insert(
+        header::RETRY_AFTER,
+        HeaderValue::from(budget.retry_after_secs()),
+    );
+    response
+}
+
+/// Run the request if its budget has a free slot, otherwise shed it with a 503
+pub async fn concurrency_middleware(request: Request, next: Next) -> Response {
+    let budget = classify(request.uri().path());
+    let Ok(_permit) = budget.semaphore().try_acquire_owned() else {
+        warn!(
+            budget = budget.name(),
+            limit = budget.limit(),
+            path = %request.uri().path(),
+            "Concurrency budget exhausted; shedding request"
+        );
+        return shed(budget);
+    };
+    next.run(request).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::StatusCode;
+
+    #[test]
+    fn test_classify() {
+        assert_eq!(
+            classify("/api/admin/jobs/ai/generate-description"),
+            Budget::Ai
+        );
+        assert_eq!(
+            classify("/api/admin/jobs/J1/content/description/generate"),
+            Budget::Ai
+        );
+        assert_eq!(classify("/api/admin/export/applications"), Budget::Export);
+        assert_eq!(classify("/api/jobs"), Budget::PublicListing);
+        assert_eq!(classify("/api/jobs/J1/"), Budget::PublicListing);
+        assert_eq!(classify("/api/jobs/J1/stats"), Budget::General);
+        assert_eq!(classify("/api/applications"), Budget::General);
+    }
+
+    #[test]
+    fn test_shed_response() {
+        let response = shed(Budget::Ai);
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(response.headers()[header::RETRY_AFTER], "10");
+    }
+}
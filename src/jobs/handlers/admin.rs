@@ -10,8 +10,16 @@ use tokio::sync::RwLock;
 use tracing::{debug, error, info, warn};
 
 use crate::auth::AuthedUser;
-use crate::common::{generate_history_id, generate_job_id, ApiError, AppState};
+use crate::common::{generate_history_id, generate_job_id, ApiError, AppState, ErrorCode};
 use crate::jobs::models::*;
+use crate::jobs::services::capacity;
+use crate::jobs::services::content_versions;
+use crate::jobs::services::deadlines;
+use crate::jobs::services::duplicates::{self, DuplicatePolicy};
+use crate::jobs::services::lint;
+use crate::jobs::services::trash;
+use crate::services::job_templates::{self, JobTemplatesService};
+use crate::services::sanitize::sanitize_markdown;
 
 /// Query params for admin job listing
 #[derive(Debug, serde::Deserialize)]
@@ -40,7 +48,7 @@ pub async fn admin_list_jobs(
     // Build query based on status filter
     let (total, jobs) = if let Some(status) = &params.status {
         if status == "all" {
-            let total: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM jobs")
+            let total: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM jobs WHERE deleted_at IS NULL")
                 .fetch_one(&state.db)
                 .await
                 .map_err(ApiError::DatabaseError)?;
@@ -49,8 +57,9 @@ pub async fn admin_list_jobs(
                 r#"SELECT 
                     id, title, summary, description, location, company, company_id, company_logo_url, job_image_url,
                     salary_min, salary_max, job_type, experience_level, requirements, benefits,
-                    status, is_featured, created_at, updated_at, published_at
+                    status, is_featured, created_at, updated_at, published_at, application_deadline
                 FROM jobs 
+                WHERE deleted_at IS NULL
                 ORDER BY created_at DESC
                 LIMIT ? OFFSET ?"#,
             )
@@ -62,7 +71,9 @@ pub async fn admin_list_jobs(
 
             (total, jobs)
         } else {
-            let total: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM jobs WHERE status = ?")
+            let total: i64 = sqlx::query_scalar(
+                "SELECT COUNT(*) FROM jobs WHERE status = ? AND deleted_at IS NULL",
+            )
                 .bind(status)
                 .fetch_one(&state.db)
                 .await
@@ -72,9 +83,9 @@ pub async fn admin_list_jobs(
                 r#"SELECT 
                     id, title, summary, description, location, company, company_id, company_logo_url, job_image_url,
                     salary_min, salary_max, job_type, experience_level, requirements, benefits,
-                    status, is_featured, created_at, updated_at, published_at
+                    status, is_featured, created_at, updated_at, published_at, application_deadline
                 FROM jobs 
-                WHERE status = ?
+                WHERE status = ? AND deleted_at IS NULL
                 ORDER BY created_at DESC
                 LIMIT ? OFFSET ?"#,
             )
@@ -89,7 +100,7 @@ pub async fn admin_list_jobs(
         }
     } else {
         // Default: return all jobs
-        let total: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM jobs")
+        let total: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM jobs WHERE deleted_at IS NULL")
             .fetch_one(&state.db)
             .await
             .map_err(ApiError::DatabaseError)?;
@@ -98,8 +109,9 @@ pub async fn admin_list_jobs(
             r#"SELECT 
                 id, title, summary, description, location, company, company_id, company_logo_url, job_image_url,
                 salary_min, salary_max, job_type, experience_level, requirements, benefits,
-                status, is_featured, created_at, updated_at, published_at
+                status, is_featured, created_at, updated_at, published_at, application_deadline
             FROM jobs 
+            WHERE deleted_at IS NULL
             ORDER BY created_at DESC
             LIMIT ? OFFSET ?"#,
         )
@@ -146,7 +158,7 @@ pub async fn admin_get_job_by_id(
         r#"SELECT 
             id, title, summary, description, location, company, company_id, company_logo_url, job_image_url,
             salary_min, salary_max, job_type, experience_level, requirements, benefits,
-            status, is_featured, created_at, updated_at, published_at
+            status, is_featured, created_at, updated_at, published_at, application_deadline
         FROM jobs 
         WHERE id = ?"#,
     )
@@ -158,22 +170,296 @@ pub async fn admin_get_job_by_id(
 
     debug!(job_id = %job_id, job_title = %job.title, "Admin loaded job details");
 
-    Ok(Json(job.into()))
+    let mut job_response: JobResponse = job.into();
+    job_response.ai_provenance = Some(content_versions::job_provenance(&state.db, &job_id).await?);
+    Ok(Json(job_response))
+}
+
+/// GET /api/admin/jobs/:id/reapply-policy - Get the job's re-application cooldown override
+pub async fn admin_get_reapply_policy(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Path(job_id): Path<String>,
+) -> Result<Json<ReapplyPolicy>, ApiError> {
+    if !authed.is_admin {
+        return Err(ApiError::Forbidden("admin privileges required".to_string()));
+    }
+
+    let state = state_lock.read().await.clone();
+
+    let cooldown_days: Option<i64> =
+        sqlx::query_scalar("SELECT reapply_cooldown_days FROM jobs WHERE id = ?")
+            .bind(&job_id)
+            .fetch_optional(&state.db)
+            .await
+            .map_err(ApiError::DatabaseError)?
+            .ok_or_else(|| ApiError::NotFound(format!("Job not found: {}", job_id)))?;
+
+    Ok(Json(ReapplyPolicy { cooldown_days }))
+}
+
+/// PUT /api/admin/jobs/:id/reapply-policy - Set or clear the job's re-application cooldown
+pub async fn admin_update_reapply_policy(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Path(job_id): Path<String>,
+    Json(request): Json<ReapplyPolicy>,
+) -> Result<Json<ReapplyPolicy>, ApiError> {
+    if !authed.is_admin {
+        return Err(ApiError::Forbidden("admin privileges required".to_string()));
+    }
+
+    if let Some(days) = request.cooldown_days {
+        if days > 3650 {
+            return Err(ApiError::BadRequest(
+                "cooldown_days must be at most 3650".to_string(),
+            ));
+        }
+    }
+
+    let state = state_lock.read().await.clone();
+
+    let result = sqlx::query("UPDATE jobs SET reapply_cooldown_days = ? WHERE id = ?")
+        .bind(request.cooldown_days)
+        .bind(&job_id)
+        .execute(&state.db)
+        .await
+        .map_err(ApiError::DatabaseError)?;
+
+    if result.rows_affected() == 0 {
+        return Err(ApiError::NotFound(format!("Job not found: {}", job_id)));
+    }
+
+    info!(
+        job_id = %job_id,
+        cooldown_days = ?request.cooldown_days,
+        user_id = %authed.id,
+        "Job re-application policy updated"
+    );
+
+    Ok(Json(request))
+}
+
+async fn application_cap_status(
+    state: &AppState,
+    job_id: &str,
+) -> Result<ApplicationCapStatus, ApiError> {
+    let (cap, action) = capacity::job_cap(&state.db, job_id)
+        .await
+        .map_err(ApiError::DatabaseError)?
+        .ok_or_else(|| ApiError::NotFound(format!("Job not found: {}", job_id)))?;
+    let job_status: Option<String> = sqlx::query_scalar("SELECT status FROM jobs WHERE id = ?")
+        .bind(job_id)
+        .fetch_one(&state.db)
+        .await
+        .map_err(ApiError::DatabaseError)?;
+
+    Ok(ApplicationCapStatus {
+        cap,
+        action,
+        admitted: capacity::admitted_count(&state.db, job_id, None)
+            .await
+            .map_err(ApiError::DatabaseError)?,
+        waitlisted: capacity::waitlisted_count(&state.db, job_id)
+            .await
+            .map_err(ApiError::DatabaseError)?,
+        job_status: job_status.unwrap_or_default(),
+    })
+}
+
+/// GET /api/admin/jobs/:id/application-cap - Get the job's application cap and counts
+pub async fn admin_get_application_cap(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Path(job_id): Path<String>,
+) -> Result<Json<ApplicationCapStatus>, ApiError> {
+    if !authed.is_admin {
+        return Err(ApiError::Forbidden("admin privileges required".to_string()));
+    }
+
+    let state = state_lock.read().await.clone();
+
+    Ok(Json(application_cap_status(&state, &job_id).await?))
+}
+
+/// PUT /api/admin/jobs/:id/application-cap - Set or clear the job's application cap, and
+/// optionally reopen a closed job under it
+pub async fn admin_update_application_cap(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Path(job_id): Path<String>,
+    Json(request): Json<ApplicationCapRequest>,
+) -> Result<Json<ApplicationCapStatus>, ApiError> {
+    if !authed.is_admin {
+        return Err(ApiError::Forbidden("admin privileges required".to_string()));
+    }
+
+    let action = request
+        .action
+        .as_deref()
+        .unwrap_or(capacity::DEFAULT_CAP_ACTION);
+    capacity::validate_cap(request.cap, action).map_err(ApiError::BadRequest)?;
+
+    let state = state_lock.read().await.clone();
+    let job = fetch_job(&state, &job_id).await?;
+
+    let reopen = request.reopen && job.status.as_deref() == Some("closed");
+    if reopen {
+        deadlines::ensure_reopenable(job.application_deadline.as_deref())?;
+        let admitted = capacity::admitted_count(&state.db, &job_id, None)
+            .await
+            .map_err(ApiError::DatabaseError)?;
+        capacity::ensure_room(request.cap, action, admitted)?;
+    }
+
+    sqlx::query("UPDATE jobs SET application_cap = ?, application_cap_action = ? WHERE id = ?")
+        .bind(request.cap)
+        .bind(action)
+        .bind(&job_id)
+        .execute(&state.db)
+        .await
+        .map_err(ApiError::DatabaseError)?;
+
+    if reopen {
+        let now = chrono::Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
+        sqlx::query("UPDATE jobs SET status = 'active', updated_at = ? WHERE id = ?")
+            .bind(&now)
+            .bind(&job_id)
+            .execute(&state.db)
+            .await
+            .map_err(ApiError::DatabaseError)?;
+        sqlx::query(
+            r#"INSERT INTO job_status_history (
+                id, job_id, old_status, new_status, changed_by, notes, changed_at
+            ) VALUES (?, ?, 'closed', 'active', ?, 'Reopened under a new application cap', ?)"#,
+        )
+        .bind(generate_history_id())
+        .bind(&job_id)
+        .bind(&authed.id)
+        .bind(&now)
+        .execute(&state.db)
+        .await
+        .map_err(ApiError::DatabaseError)?;
+        state.feed_cache.invalidate().await;
+    } else if capacity::close_if_full(&state.db, &job_id)
+        .await
+        .map_err(ApiError::DatabaseError)?
+    {
+        state.feed_cache.invalidate().await;
+    }
+
+    info!(
+        job_id = %job_id,
+        cap = ?request.cap,
+        action = %action,
+        reopen = request.reopen,
+        user_id = %authed.id,
+        "Job application cap updated"
+    );
+
+    Ok(Json(application_cap_status(&state, &job_id).await?))
+}
+
+/// GET /api/admin/jobs/:id/waitlist - Waitlisted applications in promotion order
+pub async fn admin_get_waitlist(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Path(job_id): Path<String>,
+) -> Result<Json<Vec<WaitlistEntry>>, ApiError> {
+    if !authed.is_admin {
+        return Err(ApiError::Forbidden("admin privileges required".to_string()));
+    }
+
+    let state = state_lock.read().await.clone();
+
+    let entries = capacity::waitlist(&state.db, &job_id)
+        .await
+        .map_err(ApiError::DatabaseError)?;
+
+    Ok(Json(entries))
+}
+
+/// POST /api/admin/jobs/:id/waitlist/promote - Move the next waitlisted applications into
+/// the pipeline
+pub async fn admin_promote_waitlist(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Path(job_id): Path<String>,
+    Json(request): Json<PromoteWaitlistRequest>,
+) -> Result<Json<PromoteWaitlistResponse>, ApiError> {
+    if !authed.is_admin {
+        return Err(ApiError::Forbidden("admin privileges required".to_string()));
+    }
+
+    let count = request.count.unwrap_or(1);
+    if !(1..=capacity::MAX_PROMOTE).contains(&count) {
+        return Err(ApiError::BadRequest(format!(
+            "count must be between 1 and {}",
+            capacity::MAX_PROMOTE
+        )));
+    }
+
+    let state = state_lock.read().await.clone();
+
+    let promoted = capacity::promote(&state.db, &job_id, count, &authed.id)
+        .await
+        .map_err(|e| {
+            error!(error = %e, job_id = %job_id, "Database error promoting waitlist");
+            ApiError::DatabaseError(e)
+        })?;
+    let remaining = capacity::waitlisted_count(&state.db, &job_id)
+        .await
+        .map_err(ApiError::DatabaseError)?;
+
+    info!(
+        job_id = %job_id,
+        promoted = promoted.len(),
+        remaining,
+        user_id = %authed.id,
+        "Waitlisted applications promoted"
+    );
+
+    Ok(Json(PromoteWaitlistResponse {
+        promoted,
+        remaining,
+    }))
 }
 
 /// POST /api/admin/jobs - Create a new job
 pub async fn admin_create_job(
     Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
     authed: AuthedUser,
-    Json(body): Json<CreateJob>,
+    Json(mut body): Json<CreateJob>,
 ) -> Result<Json<JobResponse>, ApiError> {
     if !authed.is_admin {
         return Err(ApiError::Forbidden("admin privileges required".to_string()));
     }
+    content_versions::validate_ai_fields(&body.ai_generated_fields)?;
 
     let state = state_lock.read().await.clone();
     let id = generate_job_id();
 
+    // Fill template placeholders from the selected company and the submitted fields
+    if body.template_id.is_some() {
+        let values = JobTemplatesService::new(state.db.clone())
+            .placeholder_values(
+                body.company_id.as_deref(),
+                body.company.as_deref(),
+                body.location.as_deref(),
+                body.salary_min,
+                body.salary_max,
+            )
+            .await?;
+        let unresolved = job_templates::fill_job_placeholders(&mut body, &values);
+        if !unresolved.is_empty() && body.status.as_deref() == Some("active") {
+            return Err(ApiError::ValidationError(format!(
+                "Template placeholders without a value: {} (supported: {})",
+                unresolved.into_iter().collect::<Vec<_>>().join(", "),
+                TEMPLATE_PLACEHOLDERS.join(", ")
+            )));
+        }
+    }
+
     // Convert requirements and benefits arrays to JSON strings
     let requirements_json = body
         .requirements
@@ -197,6 +483,77 @@ pub async fn admin_create_job(
     // Set is_featured
     let is_featured = body.is_featured.unwrap_or(false) as i32;
 
+    let application_deadline = body
+        .application_deadline
+        .as_deref()
+        .map(|deadline| deadlines::normalize_deadline(deadline, chrono::Utc::now()))
+        .transpose()
+        .map_err(ApiError::ValidationError)?;
+
+    // Strip unsafe markup from the description before it is stored
+    let description = body.description.as_deref().map(sanitize_markdown);
+
+    if status == "active" {
+        let lint_input = JobLintRequest {
+            description: description.clone(),
+            salary_min: body.salary_min,
+            salary_max: body.salary_max,
+            requirements: body.requirements.clone().unwrap_or_default(),
+        };
+        lint::ensure_publishable(&state.settings_service, &lint_input).await?;
+    }
+
+    // Compare against active postings before anything is written
+    let company = match (body.company.clone(), body.company_id.as_deref()) {
+        (None, Some(company_id)) => sqlx::query_scalar("SELECT name FROM companies WHERE id = ?")
+            .bind(company_id)
+            .fetch_optional(&state.db)
+            .await
+            .map_err(ApiError::DatabaseError)?,
+        (company, _) => company,
+    };
+    let fingerprint = JobFingerprint {
+        id: id.clone(),
+        title: body.title.clone(),
+        company,
+        description: description.clone(),
+    };
+    let (duplicate_policy, duplicate_threshold) =
+        duplicates::settings(&state.settings_service).await;
+    let active_jobs = duplicates::active_jobs(&state.db)
+        .await
+        .map_err(ApiError::DatabaseError)?;
+    let possible_duplicates =
+        duplicates::find_matches(&fingerprint, &active_jobs, duplicate_threshold);
+    if !possible_duplicates.is_empty() {
+        let ids: Vec<&str> = possible_duplicates
+            .iter()
+            .map(|m| m.job_id.as_str())
+            .collect();
+        if duplicate_policy == DuplicatePolicy::Block && !body.allow_duplicate {
+            warn!(
+                user_id = %authed.id,
+                title = %body.title,
+                duplicates = ?ids,
+                "Job creation blocked as a likely duplicate"
+            );
+            return Err(ApiError::Coded(
+                ErrorCode::JobDuplicate,
+                format!(
+                    "This job looks like a duplicate of {}; set allow_duplicate to create it anyway",
+                    ids.join(", ")
+                ),
+            ));
+        }
+        info!(
+            user_id = %authed.id,
+            job_id = %id,
+            duplicates = ?ids,
+            overridden = body.allow_duplicate,
+            "Creating job despite likely duplicates"
+        );
+    }
+
     // Set timestamps
     let now = chrono::Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
 
@@ -211,12 +568,13 @@ pub async fn admin_create_job(
         r#"INSERT INTO jobs (
             id, title, description, location, company, company_id, company_logo_url, job_image_url,
             salary_min, salary_max, job_type, experience_level, requirements, benefits,
-            educational_qualifications, is_featured, template_id, status, created_at, updated_at, published_at
-        ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"#
+            educational_qualifications, is_featured, template_id, status, created_at, updated_at, published_at,
+            application_deadline
+        ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"#
     )
         .bind(&id)
         .bind(&body.title)
-        .bind(body.description.as_deref())
+        .bind(description.as_deref())
         .bind(body.location.as_deref())
         .bind(body.company.as_deref())
         .bind(body.company_id.as_deref())
@@ -235,6 +593,7 @@ pub async fn admin_create_job(
         .bind(&now)
         .bind(&now)
         .bind(published_at.as_deref())
+        .bind(application_deadline.as_deref())
         .execute(&state.db)
         .await
         .map_err(|e| {
@@ -253,7 +612,7 @@ pub async fn admin_create_job(
         r#"SELECT 
             id, title, summary, description, location, company, company_id, company_logo_url, job_image_url,
             salary_min, salary_max, job_type, experience_level, requirements, benefits,
-            status, is_featured, created_at, updated_at, published_at
+            status, is_featured, created_at, updated_at, published_at, application_deadline
         FROM jobs WHERE id = ?"#,
     )
     .bind(&id)
@@ -261,10 +620,151 @@ pub async fn admin_create_job(
     .await
     .map_err(ApiError::DatabaseError)?;
 
-    let job_response: JobResponse = job.into();
+    record_new_job_content(
+        &state,
+        &body,
+        &job,
+        requirements_json.as_deref(),
+        benefits_json.as_deref(),
+        &authed.id,
+    )
+    .await;
+
+    let mut job_response: JobResponse = job.into();
+    job_response.possible_duplicates = possible_duplicates;
+    if status == "active" {
+        state.feed_cache.invalidate().await;
+    }
     Ok(Json(job_response))
 }
 
+/// GET /api/admin/jobs/duplicates - Pairs of active jobs that look like the same posting
+pub async fn admin_list_duplicate_jobs(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Query(query): Query<DuplicateJobsQuery>,
+) -> Result<Json<Vec<DuplicateJobPair>>, ApiError> {
+    if !authed.is_admin {
+        return Err(ApiError::Forbidden("admin privileges required".to_string()));
+    }
+
+    let state = state_lock.read().await.clone();
+
+    let threshold = match query.threshold {
+        Some(threshold) => duplicates::clamp_threshold(threshold),
+        None => duplicates::settings(&state.settings_service).await.1,
+    };
+    let jobs = duplicates::active_jobs(&state.db).await.map_err(|e| {
+        error!(error = %e, "Database error loading jobs for duplicate detection");
+        ApiError::DatabaseError(e)
+    })?;
+
+    Ok(Json(duplicates::find_pairs(&jobs, threshold)))
+}
+
+/// Record the content of a newly created job as its first versions, for provenance
+async fn record_new_job_content(
+    state: &AppState,
+    body: &CreateJob,
+    job: &Job,
+    requirements_json: Option<&str>,
+    benefits_json: Option<&str>,
+    user_id: &str,
+) {
+    let changes = [
+        (ContentComponentType::Title, None, Some(job.title.as_str())),
+        (
+            ContentComponentType::Description,
+            None,
+            job.description.as_deref(),
+        ),
+        (ContentComponentType::Requirements, None, requirements_json),
+        (ContentComponentType::Benefits, None, benefits_json),
+        (
+            ContentComponentType::Image,
+            None,
+            job.job_image_url.as_deref(),
+        ),
+    ];
+    if let Err(e) = content_versions::record_job_edits(
+        &state.db,
+        &job.id,
+        &changes,
+        &body.ai_generated_fields,
+        user_id,
+    )
+    .await
+    {
+        error!(error = %e, job_id = %job.id, "Failed to record content provenance for new job");
+    }
+}
+
+async fn fetch_job(state: &AppState, id: &str) -> Result<Job, ApiError> {
+    sqlx::query_as::<_, Job>(
+        r#"SELECT 
+            id, title, summary, description, location, company, company_id, company_logo_url, job_image_url,
+            salary_min, salary_max, job_type, experience_level, requirements, benefits,
+            status, is_featured, created_at, updated_at, published_at, application_deadline
+        FROM jobs WHERE id = ?"#,
+    )
+    .bind(id)
+    .fetch_optional(&state.db)
+    .await
+    .map_err(ApiError::DatabaseError)?
+    .ok_or_else(|| ApiError::BadRequest("job not found".to_string()))
+}
+
+/// The lint view of a saved job
+fn lint_input(job: &Job) -> JobLintRequest {
+    JobLintRequest {
+        description: job.description.clone(),
+        salary_min: job.salary_min,
+        salary_max: job.salary_max,
+        requirements: job
+            .requirements
+            .as_deref()
+            .and_then(|r| serde_json::from_str(r).ok())
+            .unwrap_or_default(),
+    }
+}
+
+/// POST /api/admin/jobs/lint - Quality checklist for a job that has not been saved yet
+pub async fn admin_lint_job_draft(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Json(mut body): Json<JobLintRequest>,
+) -> Result<Json<JobLintReport>, ApiError> {
+    if !authed.is_admin {
+        return Err(ApiError::Forbidden("admin privileges required".to_string()));
+    }
+
+    let state = state_lock.read().await.clone();
+    body.description = body.description.as_deref().map(sanitize_markdown);
+    let settings = lint::settings(&state.settings_service).await;
+
+    Ok(Json(lint::lint(&body, &settings).await))
+}
+
+/// GET /api/admin/jobs/:id/lint - Quality checklist for a saved job
+pub async fn admin_lint_job(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Path(id): Path<String>,
+) -> Result<Json<JobLintReport>, ApiError> {
+    if !authed.is_admin {
+        return Err(ApiError::Forbidden("admin privileges required".to_string()));
+    }
+
+    let state = state_lock.read().await.clone();
+    let job = fetch_job(&state, &id).await?;
+    let settings = lint::settings(&state.settings_service).await;
+    let report = lint::lint(&lint_input(&job), &settings).await;
+
+    debug!(job_id = %id, score = report.score, "Job description linted");
+
+    Ok(Json(report))
+}
+
 /// PUT /api/admin/jobs/:id - Update a job
 pub async fn admin_update_job(
     Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
@@ -294,13 +794,33 @@ pub async fn admin_update_job(
         && body.is_featured.is_none()
         && body.template_id.is_none()
         && body.status.is_none()
+        && body.application_deadline.is_none()
     {
         return Err(ApiError::BadRequest(
             "at least one field must be provided".to_string(),
         ));
     }
+    content_versions::validate_ai_fields(&body.ai_generated_fields)?;
 
     let state = state_lock.read().await.clone();
+    let previous = fetch_job(&state, &id).await?;
+
+    // An empty deadline removes it
+    let application_deadline = match body.application_deadline.as_deref().map(str::trim) {
+        None => None,
+        Some("") => Some(None),
+        Some(deadline) => Some(Some(
+            deadlines::normalize_deadline(deadline, chrono::Utc::now())
+                .map_err(ApiError::ValidationError)?,
+        )),
+    };
+    if body.status.as_deref() == Some("active") {
+        deadlines::ensure_reopenable(match &application_deadline {
+            Some(deadline) => deadline.as_deref(),
+            None => previous.application_deadline.as_deref(),
+        })?;
+        capacity::ensure_reopenable(&state.db, &id).await?;
+    }
 
     // Convert requirements and benefits arrays to JSON strings if provided
     let requirements_json = body
@@ -346,6 +866,27 @@ pub async fn admin_update_job(
         }
     }
 
+    // Strip unsafe markup from the description before it is stored
+    let description = body.description.as_deref().map(sanitize_markdown);
+
+    // Publishing is checked against the job as it will be after this update
+    if body.status.as_deref() == Some("active") {
+        let mut lint_input = lint_input(&previous);
+        if description.is_some() {
+            lint_input.description = description.clone();
+        }
+        if body.salary_min.is_some() {
+            lint_input.salary_min = body.salary_min;
+        }
+        if body.salary_max.is_some() {
+            lint_input.salary_max = body.salary_max;
+        }
+        if let Some(requirements) = &body.requirements {
+            lint_input.requirements = requirements.clone();
+        }
+        lint::ensure_publishable(&state.settings_service, &lint_input).await?;
+    }
+
     let result = sqlx::query(
         r#"UPDATE jobs SET 
             title = COALESCE(?, title),
@@ -365,12 +906,13 @@ pub async fn admin_update_job(
             is_featured = COALESCE(?, is_featured),
             template_id = COALESCE(?, template_id),
             status = COALESCE(?, status),
+            application_deadline = CASE WHEN ? THEN ? ELSE application_deadline END,
             updated_at = ?,
             published_at = COALESCE(?, published_at)
         WHERE id = ?"#,
     )
     .bind(body.title.as_deref())
-    .bind(body.description.as_deref())
+    .bind(description.as_deref())
     .bind(body.location.as_deref())
     .bind(body.company.as_deref())
     .bind(body.company_id.as_deref())
@@ -386,6 +928,8 @@ pub async fn admin_update_job(
     .bind(is_featured_int)
     .bind(body.template_id.as_deref())
     .bind(body.status.as_deref())
+    .bind(application_deadline.is_some())
+    .bind(application_deadline.flatten())
     .bind(&now)
     .bind(published_at_update.as_deref())
     .bind(&id)
@@ -410,7 +954,7 @@ pub async fn admin_update_job(
         r#"SELECT 
             id, title, summary, description, location, company, company_id, company_logo_url, job_image_url,
             salary_min, salary_max, job_type, experience_level, requirements, benefits,
-            status, is_featured, created_at, updated_at, published_at
+            status, is_featured, created_at, updated_at, published_at, application_deadline
         FROM jobs WHERE id = ?"#,
     )
     .bind(&id)
@@ -418,11 +962,52 @@ pub async fn admin_update_job(
     .await
     .map_err(ApiError::DatabaseError)?;
 
+    // Fields saved through the form become versions; anything not flagged was written by a person
+    let changes = [
+        (
+            ContentComponentType::Title,
+            Some(previous.title.as_str()),
+            body.title.as_deref(),
+        ),
+        (
+            ContentComponentType::Description,
+            previous.description.as_deref(),
+            description.as_deref(),
+        ),
+        (
+            ContentComponentType::Requirements,
+            previous.requirements.as_deref(),
+            requirements_json.as_deref(),
+        ),
+        (
+            ContentComponentType::Benefits,
+            previous.benefits.as_deref(),
+            benefits_json.as_deref(),
+        ),
+        (
+            ContentComponentType::Image,
+            previous.job_image_url.as_deref(),
+            body.job_image_url.as_deref(),
+        ),
+    ];
+    if let Err(e) = content_versions::record_job_edits(
+        &state.db,
+        &id,
+        &changes,
+        &body.ai_generated_fields,
+        &authed.id,
+    )
+    .await
+    {
+        error!(error = %e, job_id = %id, "Failed to record content provenance for job update");
+    }
+
     let job_response: JobResponse = job.into();
+    state.feed_cache.invalidate().await;
     Ok(Json(job_response))
 }
 
-/// DELETE /api/admin/jobs/:id - Delete a job
+/// DELETE /api/admin/jobs/:id - Move a job to the trash
 pub async fn admin_delete_job(
     Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
     authed: AuthedUser,
@@ -433,27 +1018,97 @@ pub async fn admin_delete_job(
     }
 
     let state = state_lock.read().await.clone();
-    let result = sqlx::query("DELETE FROM jobs WHERE id = ?")
-        .bind(&id)
-        .execute(&state.db)
-        .await
-        .map_err(|e| {
-            error!(
-                error = %e,
-                job_id = %id,
-                user_id = %authed.id,
-                "Database error deleting job"
-            );
-            ApiError::DatabaseError(e)
-        })?;
+    let result = sqlx::query(
+        "UPDATE jobs SET deleted_at = datetime('now'), deleted_by = ? WHERE id = ? AND deleted_at IS NULL",
+    )
+    .bind(&authed.id)
+    .bind(&id)
+    .execute(&state.db)
+    .await
+    .map_err(|e| {
+        error!(
+            error = %e,
+            job_id = %id,
+            user_id = %authed.id,
+            "Database error deleting job"
+        );
+        ApiError::DatabaseError(e)
+    })?;
 
     if result.rows_affected() == 0 {
         return Err(ApiError::BadRequest("job not found".to_string()));
     }
 
+    info!(job_id = %id, user_id = %authed.id, "Job moved to trash");
+    state.feed_cache.invalidate().await;
     Ok(StatusCode::NO_CONTENT)
 }
 
+/// GET /api/admin/jobs/trash - Deleted jobs that can still be restored
+pub async fn admin_list_trashed_jobs(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+) -> Result<Json<Vec<TrashedJob>>, ApiError> {
+    if !authed.is_admin {
+        return Err(ApiError::Forbidden("admin privileges required".to_string()));
+    }
+
+    let state = state_lock.read().await.clone();
+    let jobs = trash::list_trashed(&state.db)
+        .await
+        .map_err(ApiError::DatabaseError)?;
+
+    Ok(Json(jobs))
+}
+
+/// POST /api/admin/jobs/:id/restore - Take a job out of the trash
+pub async fn admin_restore_job(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Path(id): Path<String>,
+) -> Result<Json<JobResponse>, ApiError> {
+    if !authed.is_admin {
+        return Err(ApiError::Forbidden("admin privileges required".to_string()));
+    }
+
+    let state = state_lock.read().await.clone();
+    let result = sqlx::query(
+        "UPDATE jobs SET deleted_at = NULL, deleted_by = NULL, updated_at = datetime('now') WHERE id = ? AND deleted_at IS NOT NULL",
+    )
+    .bind(&id)
+    .execute(&state.db)
+    .await
+    .map_err(|e| {
+        error!(
+            error = %e,
+            job_id = %id,
+            user_id = %authed.id,
+            "Database error restoring job"
+        );
+        ApiError::DatabaseError(e)
+    })?;
+
+    if result.rows_affected() == 0 {
+        return Err(ApiError::NotFound(format!("Job not in trash: {}", id)));
+    }
+
+    let job = sqlx::query_as::<_, Job>(
+        r#"SELECT 
+            id, title, summary, description, location, company, company_id, company_logo_url, job_image_url,
+            salary_min, salary_max, job_type, experience_level, requirements, benefits,
+            status, is_featured, created_at, updated_at, published_at, application_deadline
+        FROM jobs WHERE id = ?"#,
+    )
+    .bind(&id)
+    .fetch_one(&state.db)
+    .await
+    .map_err(ApiError::DatabaseError)?;
+
+    info!(job_id = %id, user_id = %authed.id, "Job restored from trash");
+    state.feed_cache.invalidate().await;
+    Ok(Json(job.into()))
+}
+
 /// PATCH /api/admin/jobs/:id/status - Update job status with history tracking
 pub async fn admin_update_job_status(
     Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
@@ -488,6 +1143,13 @@ pub async fn admin_update_job_status(
         None => return Err(ApiError::BadRequest("job not found".to_string())),
     };
 
+    if body.status == "active" {
+        let job = fetch_job(&state, &id).await?;
+        deadlines::ensure_reopenable(job.application_deadline.as_deref())?;
+        capacity::ensure_reopenable(&state.db, &id).await?;
+        lint::ensure_publishable(&state.settings_service, &lint_input(&job)).await?;
+    }
+
     // Update job status
     let now = chrono::Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
 
@@ -561,7 +1223,7 @@ pub async fn admin_update_job_status(
         r#"SELECT 
             id, title, summary, description, location, company, company_id, company_logo_url, job_image_url,
             salary_min, salary_max, job_type, experience_level, requirements, benefits,
-            status, is_featured, created_at, updated_at, published_at
+            status, is_featured, created_at, updated_at, published_at, application_deadline
         FROM jobs WHERE id = ?"#,
     )
     .bind(&id)
@@ -578,6 +1240,7 @@ pub async fn admin_update_job_status(
     );
 
     let job_response: JobResponse = job.into();
+    state.feed_cache.invalidate().await;
     Ok(Json(job_response))
 }
 
@@ -641,7 +1304,7 @@ pub async fn admin_toggle_featured_status(
         r#"SELECT 
             id, title, summary, description, location, company, company_id, company_logo_url, job_image_url,
             salary_min, salary_max, job_type, experience_level, requirements, benefits,
-            status, is_featured, created_at, updated_at, published_at
+            status, is_featured, created_at, updated_at, published_at, application_deadline
         FROM jobs WHERE id = ?"#,
     )
     .bind(&id)
@@ -669,6 +1332,7 @@ pub async fn admin_save_job_draft(
     if !authed.is_admin {
         return Err(ApiError::Forbidden("admin privileges required".to_string()));
     }
+    content_versions::validate_ai_fields(&body.ai_generated_fields)?;
 
     let state = state_lock.read().await.clone();
     let id = generate_job_id();
@@ -696,6 +1360,16 @@ pub async fn admin_save_job_draft(
     // Set is_featured
     let is_featured = body.is_featured.unwrap_or(false) as i32;
 
+    let application_deadline = body
+        .application_deadline
+        .as_deref()
+        .map(|deadline| deadlines::normalize_deadline(deadline, chrono::Utc::now()))
+        .transpose()
+        .map_err(ApiError::ValidationError)?;
+
+    // Strip unsafe markup from the description before it is stored
+    let description = body.description.as_deref().map(sanitize_markdown);
+
     // Set timestamps
     let now = chrono::Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
 
@@ -703,12 +1377,13 @@ pub async fn admin_save_job_draft(
         r#"INSERT INTO jobs (
             id, title, description, location, company, company_id, company_logo_url, job_image_url,
             salary_min, salary_max, job_type, experience_level, requirements, benefits,
-            educational_qualifications, is_featured, template_id, status, created_at, updated_at
-        ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"#,
+            educational_qualifications, is_featured, template_id, status, created_at, updated_at,
+            application_deadline
+        ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"#,
     )
     .bind(&id)
     .bind(&body.title)
-    .bind(body.description.as_deref())
+    .bind(description.as_deref())
     .bind(body.location.as_deref())
     .bind(body.company.as_deref())
     .bind(body.company_id.as_deref())
@@ -726,6 +1401,7 @@ pub async fn admin_save_job_draft(
     .bind(status)
     .bind(&now)
     .bind(&now)
+    .bind(application_deadline.as_deref())
     .execute(&state.db)
     .await
     .map_err(|e| {
@@ -744,7 +1420,7 @@ pub async fn admin_save_job_draft(
         r#"SELECT 
             id, title, summary, description, location, company, company_id, company_logo_url, job_image_url,
             salary_min, salary_max, job_type, experience_level, requirements, benefits,
-            status, is_featured, created_at, updated_at, published_at
+            status, is_featured, created_at, updated_at, published_at, application_deadline
         FROM jobs WHERE id = ?"#,
     )
     .bind(&id)
@@ -758,6 +1434,16 @@ pub async fn admin_save_job_draft(
         "Job draft saved successfully"
     );
 
+    record_new_job_content(
+        &state,
+        &body,
+        &job,
+        requirements_json.as_deref(),
+        benefits_json.as_deref(),
+        &authed.id,
+    )
+    .await;
+
     let job_response: JobResponse = job.into();
     Ok(Json(job_response))
 }
@@ -778,9 +1464,9 @@ pub async fn admin_load_job_draft(
         r#"SELECT 
             id, title, summary, description, location, company, company_id, company_logo_url, job_image_url,
             salary_min, salary_max, job_type, experience_level, requirements, benefits,
-            status, is_featured, created_at, updated_at, published_at
+            status, is_featured, created_at, updated_at, published_at, application_deadline
         FROM jobs 
-        WHERE id = ? AND status = 'draft'"#,
+        WHERE id = ? AND status = 'draft' AND deleted_at IS NULL"#,
     )
     .bind(&id)
     .fetch_optional(&state.db)
@@ -896,6 +1582,7 @@ pub async fn bulk_update_job_status(
         "Bulk job status update completed"
     );
 
+    state.feed_cache.invalidate().await;
     Ok(Json(result))
 }
 
@@ -950,86 +1637,41 @@ pub async fn bulk_delete_jobs(
 
     // Process each job individually to provide detailed error reporting
     for job_id in &request.job_ids {
-        // Check if job exists and has no applications (safety check)
-        let job_check = sqlx::query_as::<_, (String, i64)>(
-            r#"
-            SELECT j.id, COUNT(a.id) as application_count
-            FROM jobs j
-            LEFT JOIN applications a ON j.id = a.job_id
-            WHERE j.id = ?
-            GROUP BY j.id
-            "#,
+        // Move the job to the trash; it stays restorable until the purge task removes it
+        let delete_result = sqlx::query(
+            "UPDATE jobs SET deleted_at = datetime('now'), deleted_by = ? WHERE id = ? AND deleted_at IS NULL",
         )
+        .bind(&authed.id)
         .bind(job_id)
-        .fetch_optional(&state.db)
+        .execute(&state.db)
         .await;
 
-        match job_check {
-            Ok(Some((_, application_count))) => {
-                if application_count > 0 {
-                    failed_count += 1;
-                    let error_msg = format!(
-                        "Cannot delete job {} - it has {} applications",
-                        job_id, application_count
+        match delete_result {
+            Ok(result) => {
+                if result.rows_affected() > 0 {
+                    success_count += 1;
+                    debug!(
+                        job_id = %job_id,
+                        "Job moved to trash in bulk operation"
                     );
+                } else {
+                    failed_count += 1;
+                    let error_msg = format!("Job {} not found or already deleted", job_id);
                     errors.push(error_msg);
                     warn!(
                         job_id = %job_id,
-                        application_count = application_count,
-                        "Job deletion failed: job has applications"
+                        "Job not found in bulk deletion operation"
                     );
-                    continue;
                 }
-
-                // Delete the job
-                let delete_result = sqlx::query("DELETE FROM jobs WHERE id = ?")
-                    .bind(job_id)
-                    .execute(&state.db)
-                    .await;
-
-                match delete_result {
-                    Ok(result) => {
-                        if result.rows_affected() > 0 {
-                            success_count += 1;
-                            debug!(
-                                job_id = %job_id,
-                                "Job deleted successfully in bulk operation"
-                            );
-                        } else {
-                            failed_count += 1;
-                            let error_msg = format!("Job {} not found or already deleted", job_id);
-                            errors.push(error_msg);
-                        }
-                    }
-                    Err(e) => {
-                        failed_count += 1;
-                        let error_msg = format!("Failed to delete job {}: {}", job_id, e);
-                        errors.push(error_msg.clone());
-                        error!(
-                            error = %e,
-                            job_id = %job_id,
-                            "Database error deleting job in bulk operation"
-                        );
-                    }
-                }
-            }
-            Ok(None) => {
-                failed_count += 1;
-                let error_msg = format!("Job {} not found", job_id);
-                errors.push(error_msg);
-                warn!(
-                    job_id = %job_id,
-                    "Job not found in bulk deletion operation"
-                );
             }
             Err(e) => {
                 failed_count += 1;
-                let error_msg = format!("Database error checking job {}: {}", job_id, e);
+                let error_msg = format!("Failed to delete job {}: {}", job_id, e);
                 errors.push(error_msg.clone());
                 error!(
                     error = %e,
                     job_id = %job_id,
-                    "Database error checking job existence in bulk deletion"
+                    "Database error deleting job in bulk operation"
                 );
             }
         }
@@ -1049,5 +1691,6 @@ pub async fn bulk_delete_jobs(
         "Bulk job deletion completed"
     );
 
+    state.feed_cache.invalidate().await;
     Ok(Json(result))
 }
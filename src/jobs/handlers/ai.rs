@@ -13,6 +13,7 @@ use crate::common::state::AppState;
 use crate::companies::services::CompaniesService;
 use crate::services::job_templates::JobTemplatesService;
 use crate::services::openai::{ImageStyle, SocialPlatform, TextGenerationPurpose};
+use crate::services::sanitize::sanitize_markdown;
 
 // ============================================================================
 // Request/Response Types
@@ -173,7 +174,7 @@ pub async fn generate_job_description(
     );
 
     let result = state
-        .openai_service
+        .llm
         .generate_text(
             TextGenerationPurpose::JobDescriptionGeneration,
             &prompt,
@@ -186,7 +187,7 @@ pub async fn generate_job_description(
         })?;
 
     Ok(Json(AIGenerationResponse {
-        content: serde_json::json!(result),
+        content: serde_json::json!(sanitize_markdown(&result)),
         metadata: Some(AIGenerationMetadata {
             model: "gpt-5".to_string(),
             tokens_used: None,
@@ -254,7 +255,7 @@ pub async fn generate_job_benefits(
     );
 
     let result = state
-        .openai_service
+        .llm
         .generate_text(
             TextGenerationPurpose::JobDescriptionGeneration,
             &prompt,
@@ -308,7 +309,7 @@ pub async fn generate_job_requirements(
     );
 
     let result = state
-        .openai_service
+        .llm
         .generate_text(
             TextGenerationPurpose::JobDescriptionGeneration,
             &prompt,
@@ -359,7 +360,7 @@ pub async fn suggest_skills(
     );
 
     let result = state
-        .openai_service
+        .llm
         .generate_text(
             TextGenerationPurpose::JobDescriptionGeneration,
             &prompt,
@@ -403,7 +404,7 @@ pub async fn analyze_bias(
     );
 
     let result = state
-        .openai_service
+        .llm
         .generate_text(
             TextGenerationPurpose::JobDescriptionGeneration,
             &prompt,
@@ -449,7 +450,7 @@ pub async fn calculate_readability_score(
     );
 
     let result = state
-        .openai_service
+        .llm
         .generate_text(
             TextGenerationPurpose::JobDescriptionGeneration,
             &prompt,
@@ -643,7 +644,7 @@ pub async fn generate_all_job_content(
     );
 
     match state
-        .openai_service
+        .llm
         .generate_text(
             TextGenerationPurpose::JobDescriptionGeneration,
             &desc_prompt,
@@ -665,7 +666,7 @@ pub async fn generate_all_job_content(
     );
 
     match state
-        .openai_service
+        .llm
         .generate_text(
             TextGenerationPurpose::JobDescriptionGeneration,
             &req_prompt,
@@ -715,7 +716,7 @@ pub async fn generate_all_job_content(
         );
 
         match state
-            .openai_service
+            .llm
             .generate_text(
                 TextGenerationPurpose::JobDescriptionGeneration,
                 &benefits_prompt,
@@ -742,7 +743,7 @@ pub async fn generate_all_job_content(
     );
 
     match state
-        .openai_service
+        .llm
         .generate_text(
             TextGenerationPurpose::JobDescriptionGeneration,
             &skills_prompt,
@@ -881,7 +882,7 @@ pub async fn generate_from_ai_template(
             );
 
             match state
-                .openai_service
+                .llm
                 .generate_text(
                     TextGenerationPurpose::JobDescriptionGeneration,
                     &prompt,
@@ -915,7 +916,7 @@ pub async fn generate_from_ai_template(
             );
 
             match state
-                .openai_service
+                .llm
                 .generate_text(
                     TextGenerationPurpose::JobDescriptionGeneration,
                     &prompt,
@@ -950,7 +951,7 @@ pub async fn generate_from_ai_template(
             );
 
             match state
-                .openai_service
+                .llm
                 .generate_text(
                     TextGenerationPurpose::JobDescriptionGeneration,
                     &prompt,
@@ -1004,7 +1005,7 @@ pub async fn generate_from_ai_template(
                 );
 
                 match state
-                    .openai_service
+                    .llm
                     .generate_text(
                         TextGenerationPurpose::JobDescriptionGeneration,
                         &prompt,
@@ -1045,7 +1046,7 @@ pub async fn generate_from_ai_template(
             );
 
             match state
-                .openai_service
+                .llm
                 .generate_text(
                     TextGenerationPurpose::JobDescriptionGeneration,
                     &prompt,
@@ -34,8 +34,8 @@ pub async fn get_job_analytics(
     );
 
     // Get total active jobs count (only count jobs with status = 'active')
-    let total_jobs = sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM jobs WHERE status = 'active'")
-        .fetch_one(&state.db)
+    let total_jobs = sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM jobs WHERE status = 'active' AND deleted_at IS NULL")
+        .fetch_one(&state.db_read)
         .await
         .map_err(|e| {
             error!(
@@ -47,7 +47,7 @@ pub async fn get_job_analytics(
 
     // Get total views
     let total_views = sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM job_views")
-        .fetch_one(&state.db)
+        .fetch_one(&state.db_read)
         .await
         .map_err(|e| {
             error!(
@@ -61,7 +61,7 @@ pub async fn get_job_analytics(
     let total_applications_query = "SELECT COUNT(*) FROM applications".to_string();
 
     let total_applications = sqlx::query_scalar::<_, i64>(&total_applications_query)
-        .fetch_one(&state.db)
+        .fetch_one(&state.db_read)
         .await
         .map_err(|e| {
             error!(
@@ -94,6 +94,7 @@ pub async fn get_job_analytics(
         FROM jobs j
         LEFT JOIN job_views jv ON j.id = jv.job_id
         LEFT JOIN applications a ON j.id = a.job_id
+        WHERE j.deleted_at IS NULL
         GROUP BY j.id, j.title, j.created_at
         ORDER BY views DESC, applications DESC
         LIMIT 10
@@ -103,7 +104,7 @@ pub async fn get_job_analytics(
         sqlx::query_as::<_, (String, String, i64, i64, f64, Option<String>)>(&top_jobs_query);
 
     let top_jobs_data = top_jobs_query_builder
-        .fetch_all(&state.db)
+        .fetch_all(&state.db_read)
         .await
         .map_err(|e| {
             error!(
@@ -143,7 +144,7 @@ pub async fn get_job_analytics(
     let view_trends_query_builder = sqlx::query_as::<_, (String, i64)>(&view_trends_query);
 
     let view_trends_data = view_trends_query_builder
-        .fetch_all(&state.db)
+        .fetch_all(&state.db_read)
         .await
         .map_err(|e| {
             error!(
@@ -172,7 +173,7 @@ pub async fn get_job_analytics(
     let app_trends_query_builder = sqlx::query_as::<_, (String, i64)>(app_trends_query);
 
     let app_trends_data = app_trends_query_builder
-        .fetch_all(&state.db)
+        .fetch_all(&state.db_read)
         .await
         .map_err(|e| {
             error!(
@@ -234,7 +235,7 @@ pub async fn get_job_stats(
     // Check if job exists and get job title
     let job_info = sqlx::query_as::<_, (String, String)>("SELECT id, title FROM jobs WHERE id = ?")
         .bind(&job_id)
-        .fetch_optional(&state.db)
+        .fetch_optional(&state.db_read)
         .await
         .map_err(|e| {
             error!(
@@ -257,7 +258,7 @@ pub async fn get_job_stats(
     let total_views =
         sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM job_views WHERE job_id = ?")
             .bind(&job_id)
-            .fetch_one(&state.db)
+            .fetch_one(&state.db_read)
             .await
             .map_err(|e| {
                 error!(
@@ -277,7 +278,7 @@ pub async fn get_job_stats(
         "#,
     )
     .bind(&job_id)
-    .fetch_one(&state.db)
+    .fetch_one(&state.db_read)
     .await
     .map_err(|e| {
         error!(
@@ -292,7 +293,7 @@ pub async fn get_job_stats(
     let total_applications =
         sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM applications WHERE job_id = ?")
             .bind(&job_id)
-            .fetch_one(&state.db)
+            .fetch_one(&state.db_read)
             .await
             .map_err(|e| {
                 error!(
@@ -315,7 +316,7 @@ pub async fn get_job_stats(
         "SELECT * FROM job_views WHERE job_id = ? ORDER BY viewed_at DESC LIMIT 10",
     )
     .bind(&job_id)
-    .fetch_all(&state.db)
+    .fetch_all(&state.db_read)
     .await
     .map_err(|e| {
         error!(
@@ -339,7 +340,7 @@ pub async fn get_job_stats(
         "#,
     )
     .bind(&job_id)
-    .fetch_all(&state.db)
+    .fetch_all(&state.db_read)
     .await
     .map_err(|e| {
         error!(
@@ -366,7 +367,7 @@ pub async fn get_job_stats(
         "#,
     )
     .bind(&job_id)
-    .fetch_all(&state.db)
+    .fetch_all(&state.db_read)
     .await
     .map_err(|e| {
         error!(
@@ -420,7 +421,7 @@ pub async fn admin_get_job_detailed_analytics(
     // Get job title
     let job_title: Option<(String,)> = sqlx::query_as("SELECT title FROM jobs WHERE id = ?")
         .bind(&id)
-        .fetch_optional(&state.db)
+        .fetch_optional(&state.db_read)
         .await
         .map_err(ApiError::DatabaseError)?;
 
@@ -432,7 +433,7 @@ pub async fn admin_get_job_detailed_analytics(
     // Get view count
     let view_count: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM job_views WHERE job_id = ?")
         .bind(&id)
-        .fetch_one(&state.db)
+        .fetch_one(&state.db_read)
         .await
         .map_err(ApiError::DatabaseError)?;
 
@@ -440,7 +441,7 @@ pub async fn admin_get_job_detailed_analytics(
     let application_count: (i64,) =
         sqlx::query_as("SELECT COUNT(*) FROM applications WHERE job_id = ?")
             .bind(&id)
-            .fetch_one(&state.db)
+            .fetch_one(&state.db_read)
             .await
             .map_err(ApiError::DatabaseError)?;
 
@@ -462,7 +463,7 @@ pub async fn admin_get_job_detailed_analytics(
         ORDER BY date"#,
     )
     .bind(&id)
-    .fetch_all(&state.db)
+    .fetch_all(&state.db_read)
     .await
     .map_err(ApiError::DatabaseError)?;
 
@@ -477,7 +478,7 @@ pub async fn admin_get_job_detailed_analytics(
         ORDER BY date"#,
     )
     .bind(&id)
-    .fetch_all(&state.db)
+    .fetch_all(&state.db_read)
     .await
     .map_err(ApiError::DatabaseError)?;
 
@@ -493,7 +494,7 @@ pub async fn admin_get_job_detailed_analytics(
         LIMIT 5"#,
     )
     .bind(&id)
-    .fetch_all(&state.db)
+    .fetch_all(&state.db_read)
     .await
     .map_err(ApiError::DatabaseError)?;
 
@@ -505,7 +506,7 @@ pub async fn admin_get_job_detailed_analytics(
         GROUP BY current_stage"#,
     )
     .bind(&id)
-    .fetch_all(&state.db)
+    .fetch_all(&state.db_read)
     .await
     .map_err(ApiError::DatabaseError)?;
 
@@ -524,7 +525,7 @@ pub async fn admin_get_job_detailed_analytics(
         ORDER BY applied_at DESC"#,
     )
     .bind(&id)
-    .fetch_all(&state.db)
+    .fetch_all(&state.db_read)
     .await
     .map_err(ApiError::DatabaseError)?;
 
@@ -539,7 +540,7 @@ pub async fn admin_get_job_detailed_analytics(
         ORDER BY jsh.changed_at DESC"#,
     )
     .bind(&id)
-    .fetch_all(&state.db)
+    .fetch_all(&state.db_read)
     .await
     .map_err(ApiError::DatabaseError)?;
 
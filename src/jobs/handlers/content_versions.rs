@@ -36,7 +36,7 @@ pub async fn get_content_versions(
     let app_state = state.read().await;
     let service = ContentVersionsService::new(
         app_state.db.clone(),
-        app_state.openai_service.clone(),
+        app_state.llm.clone(),
     );
 
     let response = service.get_versions(&job_id, &component_type).await?;
@@ -71,7 +71,7 @@ pub async fn generate_content(
     let app_state = state.read().await;
     let service = ContentVersionsService::new(
         app_state.db.clone(),
-        app_state.openai_service.clone(),
+        app_state.llm.clone(),
     );
 
     let version = service
@@ -114,7 +114,7 @@ pub async fn activate_version(
     let app_state = state.read().await;
     let service = ContentVersionsService::new(
         app_state.db.clone(),
-        app_state.openai_service.clone(),
+        app_state.llm.clone(),
     );
 
     let version = service
@@ -154,7 +154,7 @@ pub async fn delete_version(
     let app_state = state.read().await;
     let service = ContentVersionsService::new(
         app_state.db.clone(),
-        app_state.openai_service.clone(),
+        app_state.llm.clone(),
     );
 
     service
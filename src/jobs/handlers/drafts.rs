@@ -0,0 +1,423 @@
+// src/jobs/handlers/drafts.rs
+//! Autosave for job editing: partial saves into the job's draft, and publishing it
+
+use axum::{
+    extract::{Extension, Path},
+    response::Json,
+};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::{debug, error, info};
+
+use crate::auth::AuthedUser;
+use crate::common::{generate_history_id, ApiError, AppState, ErrorCode};
+use crate::jobs::models::*;
+use crate::jobs::services::content_versions;
+use crate::jobs::services::drafts::{self, StoredDraft};
+use crate::jobs::services::editing;
+use crate::jobs::services::lint;
+use crate::messages::models::WebSocketMessage;
+use crate::services::sanitize::sanitize_markdown;
+
+fn draft_moved_on(revision: i64) -> ApiError {
+    ApiError::Coded(
+        ErrorCode::DraftConflict,
+        format!(
+            "Draft changed since revision {}; reload it and try again",
+            revision
+        ),
+    )
+}
+
+/// The saved draft, with who else has it open
+async fn current_draft(state: &AppState, id: &str) -> Result<JobDraft, ApiError> {
+    let mut draft = drafts::load(&state.db, id).await?.into_draft(id);
+    draft.editors = state.job_editors.editors(id).await;
+    Ok(draft)
+}
+
+/// Let the job's other editors know the draft moved on
+async fn announce(
+    state: &AppState,
+    id: &str,
+    action: &str,
+    revision: i64,
+    fields: Vec<String>,
+    authed: &AuthedUser,
+    forced: bool,
+) {
+    editing::notify_editors(
+        state,
+        id,
+        Some(&authed.id),
+        WebSocketMessage::JobDraftChanged {
+            job_id: id.to_string(),
+            action: action.to_string(),
+            revision,
+            fields,
+            changed_by: authed.id.clone(),
+            forced,
+        },
+    )
+    .await;
+}
+
+/// GET /api/admin/jobs/:id/draft - Pending edits and the current revision
+pub async fn get_job_draft(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Path(id): Path<String>,
+) -> Result<Json<JobDraft>, ApiError> {
+    authed.require_admin("Job draft")?;
+    let state = state_lock.read().await.clone();
+
+    Ok(Json(current_draft(&state, &id).await?))
+}
+
+/// PATCH /api/admin/jobs/:id/draft - Autosave the fields changed since the last save
+pub async fn save_job_draft(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Path(id): Path<String>,
+    Json(mut body): Json<SaveJobDraftRequest>,
+) -> Result<Json<JobDraft>, ApiError> {
+    authed.require_admin("Job draft")?;
+    let state = state_lock.read().await.clone();
+
+    if let Some(Some(description)) = body.changes.get("description").map(|d| d.as_str()) {
+        let sanitized = sanitize_markdown(description);
+        body.changes
+            .insert("description".to_string(), sanitized.into());
+    }
+
+    let row = drafts::load(&state.db, &id).await?;
+    let mut draft = StoredDraft::parse(row.draft_data.as_deref());
+    let changed = drafts::merge_changes(
+        &mut draft,
+        row.draft_revision,
+        body.base_revision,
+        &body.changes,
+        body.force,
+    )?;
+    if !changed {
+        return Ok(Json(current_draft(&state, &id).await?));
+    }
+    draft.as_update()?;
+
+    let draft_data = serde_json::to_string(&draft)
+        .map_err(|e| ApiError::InternalServer(format!("Failed to encode draft: {}", e)))?;
+    // Only lands if nobody saved between our read and this write
+    let result = sqlx::query(
+        r#"
+        UPDATE jobs
+        SET draft_data = ?, draft_revision = draft_revision + 1, draft_updated_by = ?,
+            draft_updated_at = datetime('now')
+        WHERE id = ? AND draft_revision = ? AND deleted_at IS NULL
+        "#,
+    )
+    .bind(&draft_data)
+    .bind(&authed.id)
+    .bind(&id)
+    .bind(row.draft_revision)
+    .execute(&state.db)
+    .await
+    .map_err(|e| {
+        error!(error = %e, job_id = %id, user_id = %authed.id, "Database error saving job draft");
+        ApiError::DatabaseError(e)
+    })?;
+    if result.rows_affected() == 0 {
+        return Err(draft_moved_on(row.draft_revision));
+    }
+
+    debug!(
+        job_id = %id,
+        user_id = %authed.id,
+        revision = row.draft_revision + 1,
+        fields = body.changes.len(),
+        "Job draft autosaved"
+    );
+    announce(
+        &state,
+        &id,
+        "saved",
+        row.draft_revision + 1,
+        body.changes.keys().cloned().collect(),
+        &authed,
+        body.force,
+    )
+    .await;
+
+    Ok(Json(current_draft(&state, &id).await?))
+}
+
+/// DELETE /api/admin/jobs/:id/draft - Throw away every pending edit
+pub async fn discard_job_draft(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Path(id): Path<String>,
+) -> Result<Json<JobDraft>, ApiError> {
+    authed.require_admin("Job draft")?;
+    let state = state_lock.read().await.clone();
+
+    let row = drafts::load(&state.db, &id).await?;
+    let draft = StoredDraft::parse(row.draft_data.as_deref());
+    if draft.fields.is_empty() {
+        return Ok(Json(current_draft(&state, &id).await?));
+    }
+
+    // Discarding counts as a change to every pending field
+    let cleared = drafts::after_publish(&draft, row.draft_revision + 1);
+    let draft_data = serde_json::to_string(&cleared)
+        .map_err(|e| ApiError::InternalServer(format!("Failed to encode draft: {}", e)))?;
+    let result = sqlx::query(
+        r#"
+        UPDATE jobs
+        SET draft_data = ?, draft_revision = draft_revision + 1, draft_updated_by = ?,
+            draft_updated_at = datetime('now')
+        WHERE id = ? AND draft_revision = ?
+        "#,
+    )
+    .bind(&draft_data)
+    .bind(&authed.id)
+    .bind(&id)
+    .bind(row.draft_revision)
+    .execute(&state.db)
+    .await
+    .map_err(ApiError::DatabaseError)?;
+    if result.rows_affected() == 0 {
+        return Err(draft_moved_on(row.draft_revision));
+    }
+
+    info!(job_id = %id, user_id = %authed.id, "Job draft discarded");
+    announce(
+        &state,
+        &id,
+        "discarded",
+        row.draft_revision + 1,
+        draft.fields.keys().cloned().collect(),
+        &authed,
+        false,
+    )
+    .await;
+
+    Ok(Json(current_draft(&state, &id).await?))
+}
+
+/// POST /api/admin/jobs/:id/draft/publish - Validate the draft and make it the live job
+///
+/// The edits, the status change to `active` and the cleared draft are written in one
+/// transaction, and only if the draft is still at the requested revision.
+pub async fn publish_job_draft(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Path(id): Path<String>,
+    Json(body): Json<PublishJobDraftRequest>,
+) -> Result<Json<JobResponse>, ApiError> {
+    authed.require_admin("Job draft")?;
+    content_versions::validate_ai_fields(&body.ai_generated_fields)?;
+    let state = state_lock.read().await.clone();
+
+    let row = drafts::load(&state.db, &id).await?;
+    if row.draft_revision != body.revision {
+        return Err(draft_moved_on(body.revision));
+    }
+    let draft = StoredDraft::parse(row.draft_data.as_deref());
+    let update = draft.as_update()?;
+
+    let previous = sqlx::query_as::<_, Job>(
+        r#"SELECT
+            id, title, summary, description, location, company, company_id, company_logo_url, job_image_url,
+            salary_min, salary_max, job_type, experience_level, requirements, benefits,
+            status, is_featured, created_at, updated_at, published_at, application_deadline
+        FROM jobs WHERE id = ?"#,
+    )
+    .bind(&id)
+    .fetch_one(&state.db)
+    .await
+    .map_err(ApiError::DatabaseError)?;
+
+    drafts::validate_for_publish(&update, (previous.salary_min, previous.salary_max))?;
+
+    let requirements_json = update
+        .requirements
+        .as_ref()
+        .map(|r| serde_json::to_string(r).unwrap_or_else(|_| "[]".to_string()));
+    let benefits_json = update
+        .benefits
+        .as_ref()
+        .map(|b| serde_json::to_string(b).unwrap_or_else(|_| "[]".to_string()));
+    let educational_qualifications_json = update
+        .educational_qualifications
+        .as_ref()
+        .map(|eq| serde_json::to_string(eq).unwrap_or_else(|_| "[]".to_string()));
+    let description = update.description.as_deref().map(sanitize_markdown);
+
+    let lint_input = JobLintRequest {
+        description: description.clone().or_else(|| previous.description.clone()),
+        salary_min: update.salary_min.or(previous.salary_min),
+        salary_max: update.salary_max.or(previous.salary_max),
+        requirements: update.requirements.clone().unwrap_or_else(|| {
+            previous
+                .requirements
+                .as_deref()
+                .and_then(|r| serde_json::from_str(r).ok())
+                .unwrap_or_default()
+        }),
+    };
+    lint::ensure_publishable(&state.settings_service, &lint_input).await?;
+
+    let publish_revision = row.draft_revision + 1;
+    let draft_data = serde_json::to_string(&drafts::after_publish(&draft, publish_revision))
+        .map_err(|e| ApiError::InternalServer(format!("Failed to encode draft: {}", e)))?;
+    let now = chrono::Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
+
+    let mut tx = state.db.begin().await.map_err(ApiError::DatabaseError)?;
+    let result = sqlx::query(
+        r#"UPDATE jobs SET
+            title = COALESCE(?, title),
+            description = COALESCE(?, description),
+            location = COALESCE(?, location),
+            company = COALESCE(?, company),
+            company_id = COALESCE(?, company_id),
+            company_logo_url = COALESCE(?, company_logo_url),
+            job_image_url = COALESCE(?, job_image_url),
+            salary_min = COALESCE(?, salary_min),
+            salary_max = COALESCE(?, salary_max),
+            job_type = COALESCE(?, job_type),
+            experience_level = COALESCE(?, experience_level),
+            requirements = COALESCE(?, requirements),
+            benefits = COALESCE(?, benefits),
+            educational_qualifications = COALESCE(?, educational_qualifications),
+            is_featured = COALESCE(?, is_featured),
+            status = 'active',
+            updated_at = ?,
+            published_at = COALESCE(published_at, ?),
+            draft_data = ?,
+            draft_revision = draft_revision + 1,
+            draft_updated_by = ?,
+            draft_updated_at = ?
+        WHERE id = ? AND draft_revision = ? AND deleted_at IS NULL"#,
+    )
+    .bind(update.title.as_deref())
+    .bind(description.as_deref())
+    .bind(update.location.as_deref())
+    .bind(update.company.as_deref())
+    .bind(update.company_id.as_deref())
+    .bind(update.company_logo_url.as_deref())
+    .bind(update.job_image_url.as_deref())
+    .bind(update.salary_min)
+    .bind(update.salary_max)
+    .bind(update.job_type.as_deref())
+    .bind(update.experience_level.as_deref())
+    .bind(requirements_json.as_deref())
+    .bind(benefits_json.as_deref())
+    .bind(educational_qualifications_json.as_deref())
+    .bind(update.is_featured.map(|f| f as i32))
+    .bind(&now)
+    .bind(&now)
+    .bind(&draft_data)
+    .bind(&authed.id)
+    .bind(&now)
+    .bind(&id)
+    .bind(body.revision)
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| {
+        error!(error = %e, job_id = %id, user_id = %authed.id, "Database error publishing job draft");
+        ApiError::DatabaseError(e)
+    })?;
+    if result.rows_affected() == 0 {
+        return Err(draft_moved_on(body.revision));
+    }
+
+    if previous.status.as_deref() != Some("active") {
+        sqlx::query(
+            r#"INSERT INTO job_status_history (
+                id, job_id, old_status, new_status, changed_by, notes, changed_at
+            ) VALUES (?, ?, ?, 'active', ?, ?, ?)"#,
+        )
+        .bind(generate_history_id())
+        .bind(&id)
+        .bind(previous.status.as_deref())
+        .bind(&authed.id)
+        .bind(body.notes.as_deref())
+        .bind(&now)
+        .execute(&mut *tx)
+        .await
+        .map_err(ApiError::DatabaseError)?;
+    }
+    tx.commit().await.map_err(ApiError::DatabaseError)?;
+
+    let changes = [
+        (
+            ContentComponentType::Title,
+            Some(previous.title.as_str()),
+            update.title.as_deref(),
+        ),
+        (
+            ContentComponentType::Description,
+            previous.description.as_deref(),
+            description.as_deref(),
+        ),
+        (
+            ContentComponentType::Requirements,
+            previous.requirements.as_deref(),
+            requirements_json.as_deref(),
+        ),
+        (
+            ContentComponentType::Benefits,
+            previous.benefits.as_deref(),
+            benefits_json.as_deref(),
+        ),
+        (
+            ContentComponentType::Image,
+            previous.job_image_url.as_deref(),
+            update.job_image_url.as_deref(),
+        ),
+    ];
+    if let Err(e) = content_versions::record_job_edits(
+        &state.db,
+        &id,
+        &changes,
+        &body.ai_generated_fields,
+        &authed.id,
+    )
+    .await
+    {
+        error!(error = %e, job_id = %id, "Failed to record content provenance for draft publish");
+    }
+
+    let job = sqlx::query_as::<_, Job>(
+        r#"SELECT
+            id, title, summary, description, location, company, company_id, company_logo_url, job_image_url,
+            salary_min, salary_max, job_type, experience_level, requirements, benefits,
+            status, is_featured, created_at, updated_at, published_at, application_deadline
+        FROM jobs WHERE id = ?"#,
+    )
+    .bind(&id)
+    .fetch_one(&state.db)
+    .await
+    .map_err(ApiError::DatabaseError)?;
+
+    info!(
+        job_id = %id,
+        user_id = %authed.id,
+        revision = publish_revision,
+        fields = draft.fields.len(),
+        "Job draft published"
+    );
+
+    announce(
+        &state,
+        &id,
+        "published",
+        publish_revision,
+        draft.fields.keys().cloned().collect(),
+        &authed,
+        false,
+    )
+    .await;
+
+    state.feed_cache.invalidate().await;
+    Ok(Json(job.into()))
+}
@@ -0,0 +1,152 @@
+// src/jobs/handlers/feeds.rs
+//! Public RSS feeds of active jobs, overall and per company
+
+use axum::{
+    extract::{Extension, Path},
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::debug;
+
+use crate::common::{http_cache, ApiError, AppState};
+use crate::jobs::models::Job;
+use crate::jobs::services::feeds::{
+    http_date, last_modified, parse_http_date, render_rss, CachedFeed, FeedChannel, FEED_ITEM_LIMIT,
+};
+
+const FEED_CACHE_CONTROL: &str = "public, max-age=300";
+
+/// GET /feeds/jobs.rss - All active jobs, newest first
+pub async fn jobs_feed(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    headers: HeaderMap,
+) -> Result<Response, ApiError> {
+    let state = state_lock.read().await.clone();
+
+    let feed = match state.feed_cache.get("all").await {
+        Some(feed) => feed,
+        None => {
+            let jobs = sqlx::query_as::<_, Job>(
+                r#"SELECT
+                    id, title, summary, description, location, company, company_id, company_logo_url, job_image_url,
+                    salary_min, salary_max, job_type, experience_level, requirements, benefits,
+                    status, is_featured, created_at, updated_at, published_at, application_deadline
+                FROM jobs
+                WHERE status = 'active' AND deleted_at IS NULL
+                ORDER BY COALESCE(published_at, created_at) DESC
+                LIMIT ?"#,
+            )
+            .bind(FEED_ITEM_LIMIT)
+            .fetch_all(&state.db_read)
+            .await
+            .map_err(ApiError::DatabaseError)?;
+
+            let frontend = &state.config.frontend_url;
+            let channel = FeedChannel {
+                title: "Latest jobs",
+                description: "Newly published job openings",
+                link: &format!("{}/jobs", frontend),
+                self_url: &format!("{}/feeds/jobs.rss", state.config.backend_url),
+            };
+            let body = render_rss(&channel, &jobs, |id| format!("{}/jobs/{}", frontend, id));
+            let feed = CachedFeed::new(body, last_modified(&jobs));
+            state.feed_cache.insert("all", feed.clone()).await;
+            debug!(job_count = jobs.len(), "Rendered jobs feed");
+            feed
+        }
+    };
+
+    Ok(feed_response(feed, &headers))
+}
+
+/// GET /feeds/companies/:company_id/jobs.rss - Active jobs of one company
+pub async fn company_jobs_feed(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    Path(company_id): Path<String>,
+    headers: HeaderMap,
+) -> Result<Response, ApiError> {
+    let state = state_lock.read().await.clone();
+    let cache_key = format!("company:{}", company_id);
+
+    let feed = match state.feed_cache.get(&cache_key).await {
+        Some(feed) => feed,
+        None => {
+            let company_name: String =
+                sqlx::query_scalar("SELECT name FROM companies WHERE id = ?")
+                    .bind(&company_id)
+                    .fetch_optional(&state.db_read)
+                    .await
+                    .map_err(ApiError::DatabaseError)?
+                    .ok_or_else(|| ApiError::NotFound("Company not found".to_string()))?;
+
+            let jobs = sqlx::query_as::<_, Job>(
+                r#"SELECT
+                    id, title, summary, description, location, company, company_id, company_logo_url, job_image_url,
+                    salary_min, salary_max, job_type, experience_level, requirements, benefits,
+                    status, is_featured, created_at, updated_at, published_at, application_deadline
+                FROM jobs
+                WHERE status = 'active' AND company_id = ? AND deleted_at IS NULL
+                ORDER BY COALESCE(published_at, created_at) DESC
+                LIMIT ?"#,
+            )
+            .bind(&company_id)
+            .bind(FEED_ITEM_LIMIT)
+            .fetch_all(&state.db_read)
+            .await
+            .map_err(ApiError::DatabaseError)?;
+
+            let frontend = &state.config.frontend_url;
+            let channel = FeedChannel {
+                title: &format!("Jobs at {}", company_name),
+                description: &format!("Newly published job openings at {}", company_name),
+                link: &format!("{}/companies/{}", frontend, company_id),
+                self_url: &format!("{}/feeds/companies/{}/jobs.rss", state.config.backend_url, company_id),
+            };
+            let body = render_rss(&channel, &jobs, |id| format!("{}/jobs/{}", frontend, id));
+            let feed = CachedFeed::new(body, last_modified(&jobs));
+            state.feed_cache.insert(&cache_key, feed.clone()).await;
+            debug!(company_id = %company_id, job_count = jobs.len(), "Rendered company jobs feed");
+            feed
+        }
+    };
+
+    Ok(feed_response(feed, &headers))
+}
+
+/// Serve a feed with validators, answering `304 Not Modified` when the client's copy is current
+fn feed_response(feed: CachedFeed, headers: &HeaderMap) -> Response {
+    let not_modified = if headers.contains_key(header::IF_NONE_MATCH) {
+        http_cache::etag_matches(headers, &feed.etag)
+    } else {
+        headers
+            .get(header::IF_MODIFIED_SINCE)
+            .and_then(|v| v.to_str().ok())
+            .and_then(parse_http_date)
+            .is_some_and(|since| feed.last_modified.timestamp() <= since.timestamp())
+    };
+
+    let mut response = if not_modified {
+        StatusCode::NOT_MODIFIED.into_response()
+    } else {
+        (
+            [(header::CONTENT_TYPE, "application/rss+xml; charset=utf-8")],
+            feed.body,
+        )
+            .into_response()
+    };
+
+    let response_headers = response.headers_mut();
+    response_headers.insert(
+        header::CACHE_CONTROL,
+        HeaderValue::from_static(FEED_CACHE_CONTROL),
+    );
+    if let Ok(etag) = HeaderValue::from_str(&feed.etag) {
+        response_headers.insert(header::ETAG, etag);
+    }
+    if let Ok(modified) = HeaderValue::from_str(&http_date(feed.last_modified)) {
+        response_headers.insert(header::LAST_MODIFIED, modified);
+    }
+    response
+}
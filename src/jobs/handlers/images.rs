@@ -3,8 +3,8 @@
 
 use axum::{
     extract::{Extension, Multipart, Path},
-    http::StatusCode,
-    response::IntoResponse,
+    http::{HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
     Json,
 };
 use serde::{Deserialize, Serialize};
@@ -14,7 +14,7 @@ use tokio::sync::RwLock;
 use tracing::{error, info};
 
 use crate::auth::AuthedUser;
-use crate::common::{generate_raw_id, ApiError, AppState};
+use crate::common::{http_cache, storage, ApiError, AppState};
 use crate::services::openai::{ImageSize, ImageStyle};
 
 /// POST /api/admin/jobs/upload-image - Upload job image or company logo (admin only)
@@ -55,7 +55,7 @@ pub async fn upload_job_image(
         return Err(ApiError::BadRequest("Invalid image type".to_string()));
     }
 
-    let filename = format!("{}.png", generate_raw_id(8));
+    let filename = format!("{}.png", storage::content_hash(&data));
 
     // Check storage type setting
     let storage_type = state
@@ -70,7 +70,7 @@ pub async fn upload_job_image(
         // Upload to S3
         let s3_key = format!("job-images/{}/{}", image_type, filename);
         match state
-            .aws_service
+            .object_store
             .upload_file(data.to_vec(), &s3_key, "image/png")
             .await
         {
@@ -127,7 +127,8 @@ pub async fn upload_job_image(
 pub async fn serve_job_image(
     Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
     Path((img_type, filename)): Path<(String, String)>,
-) -> Result<impl IntoResponse, ApiError> {
+    headers: HeaderMap,
+) -> Result<Response, ApiError> {
     let state = state_lock.read().await;
 
     let dir = if img_type == "logos" {
@@ -148,10 +149,12 @@ pub async fn serve_job_image(
 
     let content_type = get_content_type_from_extension(&filename);
 
-    Ok((
-        StatusCode::OK,
-        [(axum::http::header::CONTENT_TYPE, content_type)],
+    // Job images are named by content hash
+    Ok(http_cache::cached(
+        &headers,
+        content_type,
         content,
+        http_cache::IMMUTABLE_CACHE_CONTROL,
     ))
 }
 
@@ -673,7 +676,7 @@ async fn download_and_save_image(
             .to_vec();
     }
 
-    let filename = format!("ai_{}_{}.png", job_id, generate_raw_id(6));
+    let filename = format!("ai_{}_{}.png", job_id, storage::content_hash(&image_data));
 
     // Check storage type setting
     let storage_type = state
@@ -688,7 +691,7 @@ async fn download_and_save_image(
         // Upload to S3
         let s3_key = format!("job-images/jobs/{}", filename);
         match state
-            .aws_service
+            .object_store
             .upload_file(image_data.clone(), &s3_key, "image/png")
             .await
         {
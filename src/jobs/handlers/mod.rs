@@ -4,8 +4,12 @@ pub mod admin;
 pub mod ai;
 pub mod analytics;
 pub mod content_versions;
+pub mod drafts;
+pub mod feeds;
 pub mod images;
 pub mod public;
+pub mod short_links;
+pub mod social;
 pub mod templates;
 
 pub use admin::*;
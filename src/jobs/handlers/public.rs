@@ -2,22 +2,26 @@
 
 use axum::{
     extract::{Extension, Path, Query},
-    http::StatusCode,
-    response::Json,
+    http::{HeaderMap, StatusCode},
+    response::{Json, Response},
 };
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use tracing::{debug, error, info, warn};
 
-use crate::common::{generate_view_id, ApiError, AppState, Validator};
+use crate::auth::AuthedUser;
+use crate::candidates::recently_viewed;
+use crate::common::{generate_view_id, http_cache, ApiError, AppState, Validator};
 use crate::jobs::models::*;
+use crate::jobs::services::{content_versions, similar};
 use crate::jobs::validators::*;
 
 /// GET /api/jobs - List jobs (with optional featured filter and pagination)
 pub async fn list_jobs_or_featured(
     Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
     Query(params): Query<JobQueryParams>,
-) -> Result<Json<JobListResponse>, ApiError> {
+    headers: HeaderMap,
+) -> Result<Response, ApiError> {
     let state = state_lock.read().await.clone();
 
     // Parse pagination parameters with defaults
@@ -30,13 +34,13 @@ pub async fn list_jobs_or_featured(
 
     // Get total count
     let total: i64 = if is_featured_query {
-        sqlx::query_scalar("SELECT COUNT(*) FROM jobs WHERE status = 'active' AND is_featured = 1")
-            .fetch_one(&state.db)
+        sqlx::query_scalar("SELECT COUNT(*) FROM jobs WHERE status = 'active' AND is_featured = 1 AND deleted_at IS NULL")
+            .fetch_one(&state.db_read)
             .await
             .map_err(ApiError::DatabaseError)?
     } else {
-        sqlx::query_scalar("SELECT COUNT(*) FROM jobs WHERE status = 'active'")
-            .fetch_one(&state.db)
+        sqlx::query_scalar("SELECT COUNT(*) FROM jobs WHERE status = 'active' AND deleted_at IS NULL")
+            .fetch_one(&state.db_read)
             .await
             .map_err(ApiError::DatabaseError)?
     };
@@ -48,15 +52,15 @@ pub async fn list_jobs_or_featured(
             r#"SELECT 
                 id, title, summary, description, location, company, company_id, company_logo_url, job_image_url,
                 salary_min, salary_max, job_type, experience_level, requirements, benefits,
-                status, is_featured, created_at, updated_at, published_at
+                status, is_featured, created_at, updated_at, published_at, application_deadline
             FROM jobs 
-            WHERE status = 'active' AND is_featured = 1
+            WHERE status = 'active' AND is_featured = 1 AND deleted_at IS NULL
             ORDER BY created_at DESC
             LIMIT ? OFFSET ?"#,
         )
         .bind(limit as i64)
         .bind(offset as i64)
-        .fetch_all(&state.db)
+        .fetch_all(&state.db_read)
         .await
         .map_err(ApiError::DatabaseError)?
     } else {
@@ -65,15 +69,15 @@ pub async fn list_jobs_or_featured(
             r#"SELECT 
                 id, title, summary, description, location, company, company_id, company_logo_url, job_image_url,
                 salary_min, salary_max, job_type, experience_level, requirements, benefits,
-                status, is_featured, created_at, updated_at, published_at
+                status, is_featured, created_at, updated_at, published_at, application_deadline
             FROM jobs 
-            WHERE status = 'active'
+            WHERE status = 'active' AND deleted_at IS NULL
             ORDER BY created_at DESC
             LIMIT ? OFFSET ?"#,
         )
         .bind(limit as i64)
         .bind(offset as i64)
-        .fetch_all(&state.db)
+        .fetch_all(&state.db_read)
         .await
         .map_err(ApiError::DatabaseError)?
     };
@@ -90,19 +94,24 @@ pub async fn list_jobs_or_featured(
         "Successfully loaded paginated jobs list"
     );
 
-    Ok(Json(JobListResponse {
-        jobs: job_responses,
-        total: total as usize,
-        page,
-        page_size: limit,
-    }))
+    http_cache::cached_json(
+        &headers,
+        &JobListResponse {
+            jobs: job_responses,
+            total: total as usize,
+            page,
+            page_size: limit,
+        },
+        http_cache::LISTING_CACHE_CONTROL,
+    )
 }
 
 /// GET /api/jobs/:id - Get a specific job by ID (public endpoint)
 pub async fn get_job_by_id(
     Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
     Path(job_id): Path<String>,
-) -> Result<Json<JobResponse>, ApiError> {
+    headers: HeaderMap,
+) -> Result<Response, ApiError> {
     let state = state_lock.read().await.clone();
 
     // Fetch the job, but only if it's active (published)
@@ -110,9 +119,9 @@ pub async fn get_job_by_id(
         r#"SELECT 
             id, title, summary, description, location, company, company_id, company_logo_url, job_image_url,
             salary_min, salary_max, job_type, experience_level, requirements, benefits,
-            status, is_featured, created_at, updated_at, published_at
+            status, is_featured, created_at, updated_at, published_at, application_deadline
         FROM jobs 
-        WHERE id = ? AND status = 'active'"#,
+        WHERE id = ? AND status = 'active' AND deleted_at IS NULL"#,
     )
     .bind(&job_id)
     .fetch_optional(&state.db)
@@ -122,13 +131,67 @@ pub async fn get_job_by_id(
 
     debug!(job_id = %job_id, job_title = %job.title, "Successfully loaded job details");
 
-    Ok(Json(job.into()))
+    let mut job_response: JobResponse = job.into();
+    job_response.ai_disclosure =
+        content_versions::public_disclosure(&state.db, &state.settings_service, &job_id).await?;
+    http_cache::cached_json(&headers, &job_response, http_cache::PROFILE_CACHE_CONTROL)
+}
+
+/// GET /api/jobs/:id/similar - Other open jobs like this one, closest first
+pub async fn get_similar_jobs(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    Path(job_id): Path<String>,
+    Query(params): Query<SimilarJobsQuery>,
+    headers: HeaderMap,
+) -> Result<Response, ApiError> {
+    let state = state_lock.read().await.clone();
+    let limit = params
+        .limit
+        .unwrap_or(6)
+        .clamp(1, similar::MAX_SIMILAR_JOBS);
+
+    let ranked = similar::similar_job_ids(&state.db_read, &state.similar_jobs_cache, &job_id)
+        .await
+        .map_err(ApiError::DatabaseError)?
+        .ok_or_else(|| ApiError::NotFound(format!("Job not found: {}", job_id)))?;
+
+    // Re-read the ranked jobs so any that closed since the ranking was cached drop out
+    let mut jobs = Vec::new();
+    for (id, score) in &ranked {
+        if jobs.len() == limit {
+            break;
+        }
+        let job = sqlx::query_as::<_, SimilarJob>(
+            r#"SELECT
+                id, title, company, company_logo_url, location, job_type, experience_level,
+                salary_min, salary_max, published_at
+            FROM jobs
+            WHERE id = ? AND status = 'active' AND deleted_at IS NULL"#,
+        )
+        .bind(id)
+        .fetch_optional(&state.db_read)
+        .await
+        .map_err(ApiError::DatabaseError)?;
+        if let Some(job) = job {
+            jobs.push(SimilarJob {
+                score: *score,
+                ..job
+            });
+        }
+    }
+
+    debug!(job_id = %job_id, similar_count = jobs.len(), "Loaded similar jobs");
+    http_cache::cached_json(&headers, &jobs, http_cache::LISTING_CACHE_CONTROL)
 }
 
 /// POST /api/jobs/:id/view - Track a job view
+///
+/// Views by signed-in users are linked to them for their recently viewed list unless they
+/// turned tracking off.
 pub async fn track_job_view(
     Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
     Path(job_id): Path<String>,
+    authed: Option<AuthedUser>,
     Json(request): Json<JobViewRequest>,
 ) -> Result<StatusCode, ApiError> {
     let state = state_lock.read().await.clone();
@@ -151,7 +214,7 @@ pub async fn track_job_view(
     }
 
     // Check if job exists
-    let job_exists = sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM jobs WHERE id = ?")
+    let job_exists = sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM jobs WHERE id = ? AND deleted_at IS NULL")
         .bind(&job_id)
         .fetch_one(&state.db)
         .await
@@ -172,6 +235,14 @@ pub async fn track_job_view(
         return Err(ApiError::BadRequest("Job not found".to_string()));
     }
 
+    let viewer = match authed {
+        Some(authed) => recently_viewed::tracking_enabled(&state.db, &authed.id)
+            .await
+            .map_err(ApiError::DatabaseError)?
+            .then_some(authed.id),
+        None => None,
+    };
+
     // Create job view record
     let view_id = generate_view_id();
     sqlx::query(
@@ -182,7 +253,7 @@ pub async fn track_job_view(
     )
     .bind(&view_id)
     .bind(&job_id)
-    .bind(None::<String>) // user_id - not available without auth
+    .bind(&viewer)
     .bind("unknown") // ip_address - not available without ConnectInfo
     .bind(request.user_agent.as_deref())
     .execute(&state.db)
@@ -225,20 +296,20 @@ pub async fn get_public_stats(
     let state = state_lock.read().await.clone();
 
     // Get total active jobs
-    let active_jobs: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM jobs WHERE status = 'active'")
-        .fetch_one(&state.db)
+    let active_jobs: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM jobs WHERE status = 'active' AND deleted_at IS NULL")
+        .fetch_one(&state.db_read)
         .await
         .map_err(ApiError::DatabaseError)?;
 
     // Get total jobs (all statuses)
-    let total_jobs: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM jobs")
-        .fetch_one(&state.db)
+    let total_jobs: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM jobs WHERE deleted_at IS NULL")
+        .fetch_one(&state.db_read)
         .await
         .map_err(ApiError::DatabaseError)?;
 
     // Get unique companies from jobs
-    let total_companies: i64 = sqlx::query_scalar("SELECT COUNT(DISTINCT company) FROM jobs WHERE company IS NOT NULL AND company != ''")
-        .fetch_one(&state.db)
+    let total_companies: i64 = sqlx::query_scalar("SELECT COUNT(DISTINCT company) FROM jobs WHERE company IS NOT NULL AND company != '' AND deleted_at IS NULL")
+        .fetch_one(&state.db_read)
         .await
         .map_err(ApiError::DatabaseError)?;
 
@@ -246,7 +317,7 @@ pub async fn get_public_stats(
     let total_candidates: i64 = sqlx::query_scalar(
         "SELECT COUNT(*) FROM users WHERE id NOT IN (SELECT user_id FROM admin_users)"
     )
-    .fetch_one(&state.db)
+    .fetch_one(&state.db_read)
     .await
     .map_err(ApiError::DatabaseError)?;
 
@@ -254,13 +325,13 @@ pub async fn get_public_stats(
     let total_placements: i64 = sqlx::query_scalar(
         "SELECT COUNT(*) FROM applications WHERE status = 'hired'"
     )
-    .fetch_one(&state.db)
+    .fetch_one(&state.db_read)
     .await
     .map_err(ApiError::DatabaseError)?;
 
     // Calculate success rate (hired / total applications)
     let total_applications: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM applications")
-        .fetch_one(&state.db)
+        .fetch_one(&state.db_read)
         .await
         .map_err(ApiError::DatabaseError)?;
 
@@ -0,0 +1,314 @@
+// src/jobs/handlers/short_links.rs
+//! Short links (`/j/:code`) to job pages, with per-channel click tracking and QR codes
+//! for printed material
+
+use axum::{
+    extract::{ConnectInfo, Extension, Path, Query},
+    http::{header, HeaderMap},
+    response::{IntoResponse, Json, Redirect, Response},
+};
+use image::{DynamicImage, ImageFormat, Luma};
+use qrcode::QrCode;
+use std::io::Cursor;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::{debug, error, info};
+
+use crate::auth::AuthedUser;
+use crate::common::{
+    generate_raw_id, generate_short_link_id, generate_view_id, ApiError, AppState,
+};
+use crate::jobs::models::*;
+use crate::rate_limit_middleware::extract_ip_address;
+use crate::services::promotions::normalize_source;
+
+/// Length of the random part of a short link code
+const SHORT_CODE_LENGTH: usize = 7;
+
+const DEFAULT_QR_SIZE: u32 = 512;
+const MAX_QR_SIZE: u32 = 2048;
+
+/// Public URL of a short link served from `backend_url`
+pub fn short_url(backend_url: &str, code: &str) -> String {
+    format!("{}/j/{}", backend_url, code)
+}
+
+async fn to_response(state: &AppState, link: JobShortLink) -> Result<ShortLinkResponse, ApiError> {
+    let (clicks, last_clicked_at): (i64, Option<String>) = sqlx::query_as(
+        "SELECT COUNT(*), MAX(clicked_at) FROM job_short_link_clicks WHERE short_link_id = ?",
+    )
+    .bind(&link.id)
+    .fetch_one(&state.db)
+    .await
+    .map_err(ApiError::DatabaseError)?;
+
+    Ok(ShortLinkResponse {
+        url: short_url(&state.config.backend_url, &link.code),
+        qr_url: format!(
+            "{}/api/admin/short-links/{}/qr.png",
+            state.config.backend_url, link.code
+        ),
+        code: link.code,
+        job_id: link.job_id,
+        channel: link.channel,
+        clicks,
+        last_clicked_at,
+        created_at: link.created_at,
+    })
+}
+
+/// The job's short link for `channel`, creating it on first use
+pub async fn ensure_short_link(
+    state: &AppState,
+    job_id: &str,
+    channel: ShortLinkChannel,
+    created_by: &str,
+) -> Result<JobShortLink, ApiError> {
+    let channel = channel.as_str();
+
+    let existing = sqlx::query_as::<_, JobShortLink>(
+        "SELECT * FROM job_short_links WHERE job_id = ? AND channel = ?",
+    )
+    .bind(job_id)
+    .bind(channel)
+    .fetch_optional(&state.db)
+    .await
+    .map_err(ApiError::DatabaseError)?;
+
+    let link = match existing {
+        Some(link) => link,
+        None => {
+            let id = generate_short_link_id();
+            // Codes are random, so retry the rare collision with an existing code
+            let mut inserted = false;
+            for _ in 0..3 {
+                let result = sqlx::query(
+                    "INSERT OR IGNORE INTO job_short_links (id, code, job_id, channel, created_by) VALUES (?, ?, ?, ?, ?)",
+                )
+                .bind(&id)
+                .bind(generate_raw_id(SHORT_CODE_LENGTH))
+                .bind(job_id)
+                .bind(channel)
+                .bind(created_by)
+                .execute(&state.db)
+                .await
+                .map_err(ApiError::DatabaseError)?;
+                if result.rows_affected() == 1 {
+                    inserted = true;
+                    break;
+                }
+            }
+
+            // Either our insert or a concurrent one for the same job and channel won
+            let link = sqlx::query_as::<_, JobShortLink>(
+                "SELECT * FROM job_short_links WHERE job_id = ? AND channel = ?",
+            )
+            .bind(job_id)
+            .bind(channel)
+            .fetch_optional(&state.db)
+            .await
+            .map_err(ApiError::DatabaseError)?
+            .ok_or_else(|| {
+                ApiError::InternalServer("Could not allocate a short link code".to_string())
+            })?;
+
+            if inserted {
+                info!(
+                    job_id = %job_id,
+                    channel = %channel,
+                    code = %link.code,
+                    user_id = %created_by,
+                    "Created job short link"
+                );
+            }
+            link
+        }
+    };
+
+    Ok(link)
+}
+
+/// POST /api/admin/jobs/:id/short-links - Create the job's short link for a channel
+///
+/// Idempotent: a job has at most one link per channel, and asking again returns it.
+pub async fn create_short_link(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Path(job_id): Path<String>,
+    Json(request): Json<CreateShortLinkRequest>,
+) -> Result<Json<ShortLinkResponse>, ApiError> {
+    if !authed.is_admin {
+        return Err(ApiError::Forbidden("admin privileges required".to_string()));
+    }
+
+    let state = state_lock.read().await.clone();
+    let job_exists: bool = sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM jobs WHERE id = ?)")
+        .bind(&job_id)
+        .fetch_one(&state.db)
+        .await
+        .map_err(ApiError::DatabaseError)?;
+    if !job_exists {
+        return Err(ApiError::NotFound("Job not found".to_string()));
+    }
+
+    let link = ensure_short_link(&state, &job_id, request.channel, &authed.id).await?;
+
+    Ok(Json(to_response(&state, link).await?))
+}
+
+/// GET /api/admin/jobs/:id/short-links - A job's short links with click counts
+pub async fn list_short_links(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Path(job_id): Path<String>,
+) -> Result<Json<Vec<ShortLinkResponse>>, ApiError> {
+    if !authed.is_admin {
+        return Err(ApiError::Forbidden("admin privileges required".to_string()));
+    }
+
+    let state = state_lock.read().await.clone();
+    let links = sqlx::query_as::<_, JobShortLink>(
+        "SELECT * FROM job_short_links WHERE job_id = ? ORDER BY created_at",
+    )
+    .bind(&job_id)
+    .fetch_all(&state.db)
+    .await
+    .map_err(ApiError::DatabaseError)?;
+
+    let mut responses = Vec::with_capacity(links.len());
+    for link in links {
+        responses.push(to_response(&state, link).await?);
+    }
+
+    Ok(Json(responses))
+}
+
+/// GET /api/admin/short-links/:code/qr.png - QR code for a short link
+pub async fn short_link_qr(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Path(code): Path<String>,
+    Query(query): Query<ShortLinkQrQuery>,
+) -> Result<Response, ApiError> {
+    if !authed.is_admin {
+        return Err(ApiError::Forbidden("admin privileges required".to_string()));
+    }
+
+    let state = state_lock.read().await.clone();
+    let exists: bool =
+        sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM job_short_links WHERE code = ?)")
+            .bind(&code)
+            .fetch_one(&state.db)
+            .await
+            .map_err(ApiError::DatabaseError)?;
+    if !exists {
+        return Err(ApiError::NotFound("Short link not found".to_string()));
+    }
+
+    let size = query.size.unwrap_or(DEFAULT_QR_SIZE).clamp(64, MAX_QR_SIZE);
+    let png = render_qr_png(&short_url(&state.config.backend_url, &code), size)?;
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "image/png".to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("inline; filename=\"job-{}.png\"", code),
+            ),
+            (header::CACHE_CONTROL, "private, max-age=86400".to_string()),
+        ],
+        png,
+    )
+        .into_response())
+}
+
+/// Encode `url` as a QR code PNG at least `size` pixels wide
+fn render_qr_png(url: &str, size: u32) -> Result<Vec<u8>, ApiError> {
+    let code = QrCode::new(url.as_bytes())
+        .map_err(|e| ApiError::ProcessingError(format!("Failed to encode QR code: {}", e)))?;
+    let image = code.render::<Luma<u8>>().min_dimensions(size, size).build();
+
+    let mut png = Vec::new();
+    DynamicImage::ImageLuma8(image)
+        .write_to(&mut Cursor::new(&mut png), ImageFormat::Png)
+        .map_err(|e| ApiError::ProcessingError(format!("Failed to render QR code: {}", e)))?;
+    Ok(png)
+}
+
+/// GET /j/:code - Redirect to the job page, recording the click against the link's channel
+///
+/// `?src=<tag>` attributes the click to a more specific source than the channel, e.g. the
+/// tag of a paid promotion the link was placed in.
+pub async fn follow_short_link(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    connect_info: Option<ConnectInfo<SocketAddr>>,
+    headers: HeaderMap,
+    Path(code): Path<String>,
+    Query(query): Query<FollowShortLinkQuery>,
+) -> Result<Redirect, ApiError> {
+    let state = state_lock.read().await.clone();
+
+    let link = sqlx::query_as::<_, JobShortLink>("SELECT * FROM job_short_links WHERE code = ?")
+        .bind(&code)
+        .fetch_optional(&state.db)
+        .await
+        .map_err(ApiError::DatabaseError)?
+        .ok_or_else(|| {
+            debug!(code = %code, "Unknown short link code");
+            ApiError::NotFound("Link not found".to_string())
+        })?;
+
+    let source = query
+        .src
+        .as_deref()
+        .and_then(normalize_source)
+        .unwrap_or_else(|| link.channel.clone());
+    let ip_address = extract_ip_address(&headers, connect_info.as_ref());
+    let header_value = |name: header::HeaderName| {
+        headers
+            .get(name)
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string)
+    };
+
+    // A failed click insert must not break the redirect
+    if let Err(e) = sqlx::query(
+        r#"
+        INSERT INTO job_short_link_clicks (id, short_link_id, job_id, channel, ip_address, user_agent, referrer)
+        VALUES (?, ?, ?, ?, ?, ?, ?)
+        "#,
+    )
+    .bind(generate_view_id())
+    .bind(&link.id)
+    .bind(&link.job_id)
+    .bind(&source)
+    .bind(ip_address.as_deref())
+    .bind(header_value(header::USER_AGENT))
+    .bind(header_value(header::REFERER))
+    .execute(&state.db)
+    .await
+    {
+        error!(error = %e, code = %code, "Failed to record short link click");
+    }
+
+    Ok(Redirect::temporary(&format!(
+        "{}/jobs/{}?utm_source={}&utm_medium=shortlink",
+        state.config.frontend_url,
+        link.job_id,
+        source
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_qr_png_produces_a_png_of_at_least_the_requested_size() {
+        let png = render_qr_png("https://api.example.com/j/K7NP3XY", 256).unwrap();
+        let decoded = image::load_from_memory_with_format(&png, ImageFormat::Png).unwrap();
+        assert!(decoded.width() >= 256);
+        assert_eq!(decoded.width(), decoded.height());
+    }
+}
@@ -0,0 +1,226 @@
+// src/jobs/handlers/social.rs
+//! Scheduling generated social posts for publishing to LinkedIn, X and Buffer
+
+use axum::{
+    extract::{Extension, Path, Query},
+    response::Json,
+};
+use chrono::Utc;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::info;
+
+use crate::auth::AuthedUser;
+use crate::common::timezone::normalize_schedule;
+use crate::common::{generate_history_id, ApiError, AppState};
+use crate::jobs::handlers::short_links::{ensure_short_link, short_url};
+use crate::jobs::models::*;
+use crate::services::social::{schedule_timestamp, ScheduledSocialPost};
+
+const SOCIAL_POST_STATUSES: &[&str] = &[
+    "scheduled",
+    "publishing",
+    "published",
+    "failed",
+    "cancelled",
+];
+
+async fn load_post(state: &AppState, id: &str) -> Result<ScheduledSocialPost, ApiError> {
+    sqlx::query_as::<_, ScheduledSocialPost>("SELECT * FROM scheduled_social_posts WHERE id = ?")
+        .bind(id)
+        .fetch_optional(&state.db)
+        .await
+        .map_err(ApiError::DatabaseError)?
+        .ok_or_else(|| ApiError::NotFound("Scheduled post not found".to_string()))
+}
+
+/// POST /api/admin/jobs/:id/social-posts - Queue a post for one or more networks
+pub async fn schedule_social_post(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Path(job_id): Path<String>,
+    Json(request): Json<ScheduleSocialPostRequest>,
+) -> Result<Json<Vec<ScheduledSocialPost>>, ApiError> {
+    authed.require_admin("Social post")?;
+    let state = state_lock.read().await.clone();
+
+    let caption = request.caption.trim();
+    if caption.is_empty() {
+        return Err(ApiError::BadRequest("caption is required".to_string()));
+    }
+    let mut networks = Vec::new();
+    for network in &request.networks {
+        if !networks.contains(network) {
+            networks.push(*network);
+        }
+    }
+    if networks.is_empty() {
+        return Err(ApiError::BadRequest(
+            "at least one network is required".to_string(),
+        ));
+    }
+
+    let job_exists: bool = sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM jobs WHERE id = ?)")
+        .bind(&job_id)
+        .fetch_one(&state.db)
+        .await
+        .map_err(ApiError::DatabaseError)?;
+    if !job_exists {
+        return Err(ApiError::NotFound("Job not found".to_string()));
+    }
+
+    let (scheduled_for, scheduled_timezone) = match request.scheduled_for.as_deref() {
+        Some(input) => {
+            let (at, zone) = normalize_schedule(input, request.timezone.as_deref())
+                .map_err(ApiError::BadRequest)?;
+            (schedule_timestamp(at), Some(zone))
+        }
+        None => (schedule_timestamp(Utc::now()), None),
+    };
+
+    let link_url = match request.link_url.filter(|l| !l.trim().is_empty()) {
+        Some(link) => link,
+        None => {
+            let link =
+                ensure_short_link(&state, &job_id, ShortLinkChannel::Social, &authed.id).await?;
+            short_url(&state.config.backend_url, &link.code)
+        }
+    };
+
+    let group_id = generate_history_id();
+    let mut posts = Vec::with_capacity(networks.len());
+    for network in &networks {
+        let post = sqlx::query_as::<_, ScheduledSocialPost>(
+            r#"
+            INSERT INTO scheduled_social_posts
+                (id, group_id, job_id, network, caption, image_url, link_url, scheduled_for,
+                 scheduled_timezone, created_by)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            RETURNING *
+            "#,
+        )
+        .bind(generate_history_id())
+        .bind(&group_id)
+        .bind(&job_id)
+        .bind(network.as_str())
+        .bind(caption)
+        .bind(&request.image_url)
+        .bind(&link_url)
+        .bind(&scheduled_for)
+        .bind(&scheduled_timezone)
+        .bind(&authed.id)
+        .fetch_one(&state.db)
+        .await
+        .map_err(ApiError::DatabaseError)?;
+        posts.push(post);
+    }
+
+    info!(
+        job_id = %job_id,
+        group_id = %group_id,
+        networks = ?networks,
+        scheduled_for = %scheduled_for,
+        user_id = %authed.id,
+        "Scheduled social post"
+    );
+
+    Ok(Json(posts))
+}
+
+/// GET /api/admin/social-posts - Scheduled and published posts, newest schedule first
+pub async fn list_social_posts(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Query(query): Query<SocialPostQuery>,
+) -> Result<Json<Vec<ScheduledSocialPost>>, ApiError> {
+    authed.require_admin("Social post")?;
+    let state = state_lock.read().await.clone();
+
+    if let Some(status) = query.status.as_deref() {
+        if !SOCIAL_POST_STATUSES.contains(&status) {
+            return Err(ApiError::BadRequest(format!("Invalid status: {}", status)));
+        }
+    }
+
+    let posts = sqlx::query_as::<_, ScheduledSocialPost>(
+        r#"
+        SELECT * FROM scheduled_social_posts
+        WHERE (? IS NULL OR status = ?) AND (? IS NULL OR job_id = ?)
+        ORDER BY scheduled_for DESC
+        LIMIT ?
+        "#,
+    )
+    .bind(&query.status)
+    .bind(&query.status)
+    .bind(&query.job_id)
+    .bind(&query.job_id)
+    .bind(query.limit.unwrap_or(50).clamp(1, 200))
+    .fetch_all(&state.db)
+    .await
+    .map_err(ApiError::DatabaseError)?;
+
+    Ok(Json(posts))
+}
+
+/// POST /api/admin/social-posts/:id/cancel - Cancel a post that has not been published
+pub async fn cancel_social_post(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Path(id): Path<String>,
+) -> Result<Json<ScheduledSocialPost>, ApiError> {
+    authed.require_admin("Social post")?;
+    let state = state_lock.read().await.clone();
+
+    let result = sqlx::query(
+        "UPDATE scheduled_social_posts SET status = 'cancelled', updated_at = datetime('now') WHERE id = ? AND status = 'scheduled'",
+    )
+    .bind(&id)
+    .execute(&state.db)
+    .await
+    .map_err(ApiError::DatabaseError)?;
+
+    let post = load_post(&state, &id).await?;
+    if result.rows_affected() == 0 {
+        return Err(ApiError::BadRequest(format!(
+            "Only scheduled posts can be cancelled; this one is {}",
+            post.status
+        )));
+    }
+
+    info!(post_id = %id, user_id = %authed.id, "Cancelled social post");
+    Ok(Json(post))
+}
+
+/// POST /api/admin/social-posts/:id/retry - Requeue a failed or cancelled post now
+pub async fn retry_social_post(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Path(id): Path<String>,
+) -> Result<Json<ScheduledSocialPost>, ApiError> {
+    authed.require_admin("Social post")?;
+    let state = state_lock.read().await.clone();
+
+    let result = sqlx::query(
+        r#"
+        UPDATE scheduled_social_posts
+        SET status = 'scheduled', attempts = 0, scheduled_for = ?, updated_at = datetime('now')
+        WHERE id = ? AND status IN ('failed', 'cancelled')
+        "#,
+    )
+    .bind(schedule_timestamp(Utc::now()))
+    .bind(&id)
+    .execute(&state.db)
+    .await
+    .map_err(ApiError::DatabaseError)?;
+
+    let post = load_post(&state, &id).await?;
+    if result.rows_affected() == 0 {
+        return Err(ApiError::BadRequest(format!(
+            "Only failed or cancelled posts can be retried; this one is {}",
+            post.status
+        )));
+    }
+
+    info!(post_id = %id, user_id = %authed.id, "Requeued social post");
+    Ok(Json(post))
+}
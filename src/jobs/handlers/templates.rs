@@ -3,7 +3,7 @@
 
 use axum::{
     extract::{Extension, Path, Query},
-    http::StatusCode,
+    http::{header, StatusCode},
     response::IntoResponse,
     Json,
 };
@@ -14,8 +14,12 @@ use tokio::sync::RwLock;
 use tracing::info;
 
 use crate::auth::AuthedUser;
-use crate::common::{ApiError, AppState};
-use crate::jobs::models::{CreateAITemplateRequest, CreateJobTemplateRequest, UpdateJobTemplateRequest};
+use crate::common::{ApiError, AppState, Validator};
+use crate::jobs::models::{
+    CreateAITemplateRequest, CreateJobTemplateRequest, ImportTemplatesRequest, TemplateExportQuery,
+    TemplatePreviewRequest, UpdateJobTemplateRequest,
+};
+use crate::jobs::validators::TemplateImportValidator;
 use crate::services::job_templates::JobTemplatesService;
 
 #[derive(Debug, Deserialize)]
@@ -257,3 +261,79 @@ pub async fn get_job_composer_templates(
         "total": templates.len()
     })))
 }
+
+// ============================================================================
+// Template Bundle Handlers
+// ============================================================================
+
+/// GET /api/admin/job-templates/export - Download templates as a JSON bundle
+pub async fn export_templates(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Query(params): Query<TemplateExportQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    if !authed.is_admin {
+        return Err(ApiError::Forbidden("Admin access required".to_string()));
+    }
+
+    let state = state_lock.read().await;
+    let service = JobTemplatesService::new(state.db.clone());
+
+    let bundle = service.export_bundle(&params).await?;
+
+    info!(
+        templates = bundle.templates.len(),
+        user_id = %authed.id,
+        "Exported job templates"
+    );
+
+    Ok((
+        [(
+            header::CONTENT_DISPOSITION,
+            "attachment; filename=\"job-templates.json\"",
+        )],
+        Json(bundle),
+    ))
+}
+
+/// POST /api/admin/job-templates/import - Validate and import a JSON bundle of templates
+pub async fn import_templates(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Json(request): Json<ImportTemplatesRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    if !authed.is_admin {
+        return Err(ApiError::Forbidden("Admin access required".to_string()));
+    }
+
+    let validation = TemplateImportValidator.validate(&request);
+    if !validation.is_valid {
+        return Err(validation.into());
+    }
+
+    let state = state_lock.read().await;
+    let service = JobTemplatesService::new(state.db.clone());
+
+    let response = service.import_bundle(request, &authed.id).await?;
+
+    Ok(Json(response))
+}
+
+/// POST /api/admin/job-templates/:id/preview - Preview the job a template resolves to
+pub async fn preview_template(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Path(template_id): Path<String>,
+    Json(request): Json<TemplatePreviewRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    if !authed.is_admin {
+        return Err(ApiError::Forbidden("Admin access required".to_string()));
+    }
+
+    let state = state_lock.read().await;
+    let service = JobTemplatesService::new(state.db.clone());
+
+    let preview = service.preview_job(&template_id, &request).await?;
+
+    Ok(Json(preview))
+}
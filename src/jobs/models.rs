@@ -3,6 +3,9 @@
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 use std::collections::HashMap;
+use validator::Validate;
+
+use crate::services::sanitize::render_markdown;
 
 // ============================================================================
 // Job Models
@@ -32,6 +35,9 @@ pub struct Job {
     pub created_at: Option<String>,
     pub updated_at: Option<String>,
     pub published_at: Option<String>,
+    /// Applications are refused after this moment (RFC3339, UTC)
+    #[sqlx(default)]
+    pub application_deadline: Option<String>,
 }
 
 // Enhanced Job response with parsed arrays
@@ -41,6 +47,8 @@ pub struct JobResponse {
     pub title: String,
     pub summary: Option<String>,
     pub description: Option<String>,
+    /// `description` rendered from markdown to sanitized HTML
+    pub description_html: Option<String>,
     pub location: Option<String>,
     pub company: Option<String>,
     pub company_id: Option<String>,
@@ -57,6 +65,16 @@ pub struct JobResponse {
     pub created_at: Option<String>,
     pub updated_at: Option<String>,
     pub published_at: Option<String>,
+    pub application_deadline: Option<String>,
+    /// Active jobs this one closely resembles; only set when the job is created
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub possible_duplicates: Vec<DuplicateJobMatch>,
+    /// Which fields were AI-generated or human-edited; admin responses only
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ai_provenance: Option<Vec<FieldProvenance>>,
+    /// Public notice that the posting was drafted with AI, when enabled in settings
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ai_disclosure: Option<String>,
 }
 
 // Paginated job list response
@@ -84,6 +102,7 @@ impl From<Job> for JobResponse {
             id: job.id,
             title: job.title,
             summary: job.summary,
+            description_html: job.description.as_deref().map(render_markdown),
             description: job.description,
             location: job.location,
             company: job.company,
@@ -101,20 +120,30 @@ impl From<Job> for JobResponse {
             created_at: job.created_at,
             updated_at: job.updated_at,
             published_at: job.published_at,
+            application_deadline: job.application_deadline,
+            possible_duplicates: Vec::new(),
+            ai_provenance: None,
+            ai_disclosure: None,
         }
     }
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, Validate)]
 pub struct CreateJob {
+    #[validate(length(max = 255, message = "Job title must be less than 255 characters"))]
     pub title: String,
+    #[validate(length(max = 10000, message = "Description must be less than 10000 characters"))]
     pub description: Option<String>,
+    #[validate(length(max = 255, message = "Location must be less than 255 characters"))]
     pub location: Option<String>,
+    #[validate(length(max = 255, message = "Company name must be less than 255 characters"))]
     pub company: Option<String>,
     pub company_id: Option<String>,
     pub company_logo_url: Option<String>,
     pub job_image_url: Option<String>,
+    #[validate(range(min = 0, message = "Minimum salary cannot be negative"))]
     pub salary_min: Option<i64>,
+    #[validate(range(min = 0, message = "Maximum salary cannot be negative"))]
     pub salary_max: Option<i64>,
     pub job_type: Option<String>,
     pub experience_level: Option<String>,
@@ -124,6 +153,15 @@ pub struct CreateJob {
     pub is_featured: Option<bool>,
     pub template_id: Option<String>,
     pub status: Option<String>,
+    /// Last moment to apply (RFC3339); the job is closed automatically once it passes
+    pub application_deadline: Option<String>,
+    /// Create the job even when duplicate blocking is on and it matches an active posting
+    #[serde(default)]
+    pub allow_duplicate: bool,
+    /// Fields (`title`, `description`, `requirements`, `benefits`, `image`) whose submitted
+    /// content was produced by the AI assistants rather than written by hand
+    #[serde(default)]
+    pub ai_generated_fields: Vec<String>,
 }
 
 #[derive(Deserialize)]
@@ -145,6 +183,11 @@ pub struct UpdateJob {
     pub is_featured: Option<bool>,
     pub template_id: Option<String>,
     pub status: Option<String>,
+    /// New application deadline (RFC3339); an empty string removes it
+    pub application_deadline: Option<String>,
+    /// Fields in this update whose content came from the AI assistants; see `CreateJob`
+    #[serde(default)]
+    pub ai_generated_fields: Vec<String>,
 }
 
 #[derive(Deserialize)]
@@ -153,6 +196,62 @@ pub struct UpdateJobStatusRequest {
     pub notes: Option<String>,
 }
 
+/// Per-job re-application cooldown. `None` falls back to the global settings;
+/// a negative value blocks re-applying entirely.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ReapplyPolicy {
+    #[serde(default)]
+    pub cooldown_days: Option<i64>,
+}
+
+/// PUT body for a job's application cap. A `null` cap removes it.
+#[derive(Debug, Deserialize)]
+pub struct ApplicationCapRequest {
+    #[serde(default)]
+    pub cap: Option<i64>,
+    /// `close` (default) or `waitlist`
+    pub action: Option<String>,
+    /// Make a closed job active again under the new cap
+    #[serde(default)]
+    pub reopen: bool,
+}
+
+/// A job's application cap and how close it is
+#[derive(Debug, Serialize)]
+pub struct ApplicationCapStatus {
+    pub cap: Option<i64>,
+    pub action: String,
+    /// Applications counted against the cap
+    pub admitted: i64,
+    pub waitlisted: i64,
+    pub job_status: String,
+}
+
+/// A waitlisted application, in promotion order
+#[derive(Debug, Serialize, FromRow)]
+pub struct WaitlistEntry {
+    pub position: i64,
+    pub application_id: String,
+    pub candidate_id: String,
+    pub candidate_name: Option<String>,
+    #[serde(serialize_with = "crate::services::masking::email")]
+    pub candidate_email: String,
+    pub applied_at: Option<String>,
+    pub waitlisted_at: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PromoteWaitlistRequest {
+    /// How many to promote from the front of the waitlist; defaults to 1
+    pub count: Option<i64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PromoteWaitlistResponse {
+    pub promoted: Vec<String>,
+    pub remaining: i64,
+}
+
 // ============================================================================
 // Job Analytics Models
 // ============================================================================
@@ -295,6 +394,62 @@ pub struct BulkOperationResult {
     pub errors: Vec<String>,
 }
 
+/// Autosaved edits to a job that are not live yet. `fields` holds only the fields that
+/// differ from the published job; `revision` goes up with every save and publish.
+#[derive(Debug, Serialize)]
+pub struct JobDraft {
+    pub job_id: String,
+    pub revision: i64,
+    pub fields: serde_json::Map<String, serde_json::Value>,
+    pub updated_by: Option<String>,
+    pub updated_at: Option<String>,
+    /// Everyone with the job's editor open, so a save can warn about concurrent edits
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub editors: Vec<JobEditor>,
+}
+
+/// An admin with a job's edit draft open
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JobEditor {
+    pub user_id: String,
+    pub user_name: String,
+    pub since: String,
+}
+
+/// Partial autosave: only the fields changed since the last save, with `null` discarding
+/// a field's pending edit. `base_revision` is the revision the editor last saw.
+#[derive(Debug, Deserialize)]
+pub struct SaveJobDraftRequest {
+    pub base_revision: i64,
+    pub changes: serde_json::Map<String, serde_json::Value>,
+    /// Overwrite fields another editor changed since `base_revision`
+    #[serde(default)]
+    pub force: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PublishJobDraftRequest {
+    /// Revision being published; publishing fails if the draft has moved on since
+    pub revision: i64,
+    #[serde(default)]
+    pub notes: Option<String>,
+    /// Draft fields whose content came from the AI assistants; see `CreateJob`
+    #[serde(default)]
+    pub ai_generated_fields: Vec<String>,
+}
+
+/// A job in the trash; `purge_at` is when it is removed for good
+#[derive(Debug, Serialize, FromRow)]
+pub struct TrashedJob {
+    pub id: String,
+    pub title: String,
+    pub company: Option<String>,
+    pub status: Option<String>,
+    pub deleted_at: String,
+    pub deleted_by: Option<String>,
+    pub purge_at: String,
+}
+
 // ============================================================================
 // Job Template Models
 // ============================================================================
@@ -348,6 +503,110 @@ pub struct CreateAITemplateRequest {
     pub ai_context: AITemplateContext,
 }
 
+// ============================================================================
+// Template Bundle Models (import/export between deployments)
+// ============================================================================
+
+/// Identifies a template bundle file and the newest layout this build can read
+pub const TEMPLATE_BUNDLE_FORMAT: &str = "job_api.job_templates";
+pub const TEMPLATE_BUNDLE_VERSION: u32 = 1;
+
+/// A portable set of templates. Ids, owners and companies are left out since they only
+/// mean something in the deployment that exported them.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TemplateBundle {
+    pub format: String,
+    pub version: u32,
+    #[serde(default)]
+    pub exported_at: Option<String>,
+    pub templates: Vec<BundledTemplate>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundledTemplate {
+    pub name: String,
+    /// `custom` or `ai`; exported system templates are imported as `custom`
+    pub template_type: String,
+    #[serde(default)]
+    pub job_data: serde_json::Value,
+    #[serde(default)]
+    pub ai_context: Option<AITemplateContext>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TemplateExportQuery {
+    /// Comma-separated template ids; all non-system templates when omitted
+    pub ids: Option<String>,
+    pub company_id: Option<String>,
+    #[serde(default)]
+    pub include_system: bool,
+}
+
+/// What to do when an imported template's name is already used in the target company
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TemplateConflictPolicy {
+    #[default]
+    Skip,
+    Rename,
+    Overwrite,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ImportTemplatesRequest {
+    pub bundle: TemplateBundle,
+    /// Company the templates are imported into; required for AI templates
+    pub company_id: Option<String>,
+    #[serde(default)]
+    pub on_conflict: TemplateConflictPolicy,
+    /// Validate and report what would happen without writing anything
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ImportedTemplate {
+    pub name: String,
+    /// Name the template was saved under, when renamed to avoid a conflict
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub saved_as: Option<String>,
+    /// `created`, `renamed`, `overwritten` or `skipped`
+    pub action: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub template_id: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ImportTemplatesResponse {
+    pub dry_run: bool,
+    pub templates: Vec<ImportedTemplate>,
+}
+
+// ============================================================================
+// Template Placeholder Models
+// ============================================================================
+
+/// Placeholders (`{{company_name}}` etc.) a template's `job_data` strings may contain. They are
+/// filled in when a job is created from the template.
+pub const TEMPLATE_PLACEHOLDERS: [&str; 3] = ["company_name", "location", "salary_band"];
+
+/// Values to resolve a template against; the company defaults to the template's own
+#[derive(Debug, Default, Deserialize)]
+pub struct TemplatePreviewRequest {
+    pub company_id: Option<String>,
+    pub location: Option<String>,
+    pub salary_min: Option<i64>,
+    pub salary_max: Option<i64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TemplatePreviewResponse {
+    pub template_id: String,
+    pub job_data: serde_json::Value,
+    /// Placeholders left in place because no value was available
+    pub unresolved: Vec<String>,
+}
+
 // ============================================================================
 // Content Version Models (Inline AI Editor)
 // ============================================================================
@@ -407,6 +666,21 @@ pub struct ContentVersion {
     pub version_number: i32,
     pub created_by: Option<String>,
     pub created_at: String,
+    /// `ai` for generated content, `human` for edits saved through the job form
+    pub source: String,
+}
+
+/// Where the live content of one job field came from
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct FieldProvenance {
+    pub field: String,
+    /// `ai_generated`, `human_edited` (an AI draft a person has since changed) or `human`
+    pub origin: String,
+    pub active_version_id: String,
+    pub last_changed_by: Option<String>,
+    pub last_changed_at: String,
+    pub ai_versions: i64,
+    pub human_versions: i64,
 }
 
 /// Response for getting content versions
@@ -442,3 +716,195 @@ pub struct ActivateVersionResponse {
 pub struct DeleteVersionResponse {
     pub success: bool,
 }
+
+// ============================================================================
+// Short Link Models
+// ============================================================================
+
+/// Channel a short link is distributed through, used to attribute clicks
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ShortLinkChannel {
+    Poster,
+    Email,
+    Social,
+    Other,
+}
+
+impl ShortLinkChannel {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ShortLinkChannel::Poster => "poster",
+            ShortLinkChannel::Email => "email",
+            ShortLinkChannel::Social => "social",
+            ShortLinkChannel::Other => "other",
+        }
+    }
+}
+
+/// A stored short link
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct JobShortLink {
+    pub id: String,
+    pub code: String,
+    pub job_id: String,
+    pub channel: String,
+    pub created_by: Option<String>,
+    pub created_at: String,
+}
+
+/// Request to create (or fetch) a job's short link for a channel
+#[derive(Debug, Deserialize)]
+pub struct CreateShortLinkRequest {
+    pub channel: ShortLinkChannel,
+}
+
+/// A short link with its public URL and click count
+#[derive(Debug, Serialize)]
+pub struct ShortLinkResponse {
+    pub code: String,
+    pub job_id: String,
+    pub channel: String,
+    pub url: String,
+    pub qr_url: String,
+    pub clicks: i64,
+    pub last_clicked_at: Option<String>,
+    pub created_at: String,
+}
+
+/// Query parameters for short link QR codes
+#[derive(Debug, Deserialize)]
+pub struct ShortLinkQrQuery {
+    /// Minimum edge length of the PNG in pixels (default 512)
+    pub size: Option<u32>,
+}
+
+/// Query parameters for following a short link
+#[derive(Debug, Deserialize)]
+pub struct FollowShortLinkQuery {
+    /// Attribution tag overriding the link's channel, e.g. `linkedin_ads`
+    pub src: Option<String>,
+}
+
+// ============================================================================
+// Social Post Scheduling Models
+// ============================================================================
+
+/// Request to queue a post for one or more networks
+#[derive(Debug, Deserialize)]
+pub struct ScheduleSocialPostRequest {
+    pub networks: Vec<crate::services::social::SocialNetwork>,
+    pub caption: String,
+    /// Image from `generate-social-post`, attached where the network supports it
+    pub image_url: Option<String>,
+    /// Defaults to the job's `social` short link
+    pub link_url: Option<String>,
+    /// RFC3339, or local `YYYY-MM-DDTHH:MM` with `timezone`; omitted means as soon as possible
+    pub scheduled_for: Option<String>,
+    pub timezone: Option<String>,
+}
+
+/// Filters for listing scheduled posts
+#[derive(Debug, Deserialize)]
+pub struct SocialPostQuery {
+    pub status: Option<String>,
+    pub job_id: Option<String>,
+    pub limit: Option<i64>,
+}
+
+// ============================================================================
+// Duplicate Detection Models
+// ============================================================================
+
+/// The fields compared when looking for duplicate postings
+#[derive(Debug, Clone, FromRow)]
+pub struct JobFingerprint {
+    pub id: String,
+    pub title: String,
+    pub company: Option<String>,
+    pub description: Option<String>,
+}
+
+/// An active job that closely resembles another
+#[derive(Debug, Clone, Serialize)]
+pub struct DuplicateJobMatch {
+    pub job_id: String,
+    pub title: String,
+    pub company: Option<String>,
+    /// 0.0 to 1.0, weighted across title, company and description
+    pub similarity: f64,
+}
+
+/// Two active jobs suspected of being the same posting
+#[derive(Debug, Serialize)]
+pub struct DuplicateJobPair {
+    pub job_id: String,
+    pub title: String,
+    pub company: Option<String>,
+    pub duplicate: DuplicateJobMatch,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DuplicateJobsQuery {
+    /// Overrides the `duplicate_job_threshold` setting for this listing
+    pub threshold: Option<f64>,
+}
+
+// ============================================================================
+// Description Lint Models
+// ============================================================================
+
+/// A job as sent for linting before it is saved
+#[derive(Debug, Default, Deserialize)]
+pub struct JobLintRequest {
+    pub description: Option<String>,
+    pub salary_min: Option<i64>,
+    pub salary_max: Option<i64>,
+    #[serde(default)]
+    pub requirements: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct JobLintCheck {
+    pub id: &'static str,
+    pub passed: bool,
+    pub message: String,
+}
+
+/// Scored checklist for a job description
+#[derive(Debug, Clone, Serialize)]
+pub struct JobLintReport {
+    /// Percentage of checks passed
+    pub score: u32,
+    /// Score needed to publish when enforcement is on
+    pub min_score: u32,
+    /// Whether publishing is blocked below `min_score`
+    pub enforced: bool,
+    /// Whether the job may be published
+    pub publishable: bool,
+    pub checks: Vec<JobLintCheck>,
+}
+
+/// An open job related to the one being viewed
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct SimilarJob {
+    pub id: String,
+    pub title: String,
+    pub company: Option<String>,
+    pub company_logo_url: Option<String>,
+    pub location: Option<String>,
+    pub job_type: Option<String>,
+    pub experience_level: Option<String>,
+    pub salary_min: Option<i64>,
+    pub salary_max: Option<i64>,
+    pub published_at: Option<String>,
+    /// 0-100, how closely it matches
+    #[sqlx(default)]
+    pub score: i64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SimilarJobsQuery {
+    /// 1-20, default 6
+    pub limit: Option<usize>,
+}
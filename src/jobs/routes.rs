@@ -5,7 +5,7 @@ use axum::{
     Router,
 };
 
-use super::handlers::{self, ai, content_versions, images};
+use super::handlers::{self, ai, content_versions, drafts, feeds, images, short_links, social};
 
 /// Create the jobs router with all job-related routes
 pub fn jobs_routes() -> Router {
@@ -44,9 +44,13 @@ pub fn jobs_routes() -> Router {
         // Public routes
         .route("/api/jobs", get(handlers::list_jobs_or_featured))
         .route("/api/jobs/:id", get(handlers::get_job_by_id))
+        .route("/api/jobs/:id/similar", get(handlers::get_similar_jobs))
         .route("/api/jobs/:id/view", post(handlers::track_job_view))
         .route("/api/jobs/:id/stats", get(handlers::get_job_stats))
         .route("/api/public/stats", get(handlers::get_public_stats))
+        .route("/feeds/jobs.rss", get(feeds::jobs_feed))
+        .route("/feeds/companies/:company_id/jobs.rss", get(feeds::company_jobs_feed))
+        .route("/j/:code", get(short_links::follow_short_link))
         // Admin job management routes
         .route("/api/admin/jobs", get(handlers::admin_list_jobs).post(handlers::admin_create_job))
         // NOTE: Specific parameterized routes must come BEFORE generic :id routes
@@ -54,6 +58,27 @@ pub fn jobs_routes() -> Router {
             "/api/admin/jobs/:job_id/generate-image",
             post(images::generate_job_image),
         )
+        .route(
+            "/api/admin/jobs/:id/short-links",
+            get(short_links::list_short_links).post(short_links::create_short_link),
+        )
+        .route(
+            "/api/admin/short-links/:code/qr.png",
+            get(short_links::short_link_qr),
+        )
+        .route(
+            "/api/admin/jobs/:id/social-posts",
+            post(social::schedule_social_post),
+        )
+        .route("/api/admin/social-posts", get(social::list_social_posts))
+        .route(
+            "/api/admin/social-posts/:id/cancel",
+            post(social::cancel_social_post),
+        )
+        .route(
+            "/api/admin/social-posts/:id/retry",
+            post(social::retry_social_post),
+        )
         .route(
             "/api/admin/jobs/:id/status",
             patch(handlers::admin_update_job_status),
@@ -62,6 +87,21 @@ pub fn jobs_routes() -> Router {
             "/api/admin/jobs/:id/toggle-featured",
             patch(handlers::admin_toggle_featured_status),
         )
+        .route(
+            "/api/admin/jobs/:id/reapply-policy",
+            get(handlers::admin_get_reapply_policy).put(handlers::admin_update_reapply_policy),
+        )
+        .route(
+            "/api/admin/jobs/:id/application-cap",
+            get(handlers::admin_get_application_cap).put(handlers::admin_update_application_cap),
+        )
+        .route("/api/admin/jobs/:id/waitlist", get(handlers::admin_get_waitlist))
+        .route(
+            "/api/admin/jobs/:id/waitlist/promote",
+            post(handlers::admin_promote_waitlist),
+        )
+        .route("/api/admin/jobs/trash", get(handlers::admin_list_trashed_jobs))
+        .route("/api/admin/jobs/:id/restore", post(handlers::admin_restore_job))
         .route(
             "/api/admin/jobs/:id",
             get(handlers::admin_get_job_by_id)
@@ -76,10 +116,26 @@ pub fn jobs_routes() -> Router {
             "/api/admin/jobs/draft/:id",
             get(handlers::admin_load_job_draft),
         )
+        .route(
+            "/api/admin/jobs/:id/draft",
+            get(drafts::get_job_draft)
+                .patch(drafts::save_job_draft)
+                .delete(drafts::discard_job_draft),
+        )
+        .route(
+            "/api/admin/jobs/:id/draft/publish",
+            post(drafts::publish_job_draft),
+        )
         .route(
             "/api/admin/jobs/:id/detailed-analytics",
             get(handlers::admin_get_job_detailed_analytics),
         )
+        .route("/api/admin/jobs/lint", post(handlers::admin_lint_job_draft))
+        .route("/api/admin/jobs/:id/lint", get(handlers::admin_lint_job))
+        .route(
+            "/api/admin/jobs/duplicates",
+            get(handlers::admin_list_duplicate_jobs),
+        )
         // Job analytics endpoints
         .route(
             "/api/admin/jobs/analytics",
@@ -112,10 +168,22 @@ pub fn jobs_routes() -> Router {
             "/api/admin/job-templates/ai",
             post(handlers::create_ai_template),
         )
+        .route(
+            "/api/admin/job-templates/export",
+            get(handlers::export_templates),
+        )
+        .route(
+            "/api/admin/job-templates/import",
+            post(handlers::import_templates),
+        )
         .route(
             "/api/admin/job-templates/:id/ai-context",
             get(handlers::get_ai_template_context),
         )
+        .route(
+            "/api/admin/job-templates/:id/preview",
+            post(handlers::preview_template),
+        )
         .route(
             "/api/admin/job-templates/:id",
             get(handlers::get_template_by_id)
@@ -0,0 +1,275 @@
+// src/jobs/services/capacity.rs
+//! Application caps and waitlists
+//!
+//! A job can cap how many applications it takes. Applications count against the cap
+//! unless they are archived, withdrawn or waitlisted. With the `close` action the job is
+//! closed as the cap is reached and later applicants are refused; with `waitlist` it stays
+//! open and later applications are kept with `waitlisted_at` set, until an admin promotes
+//! them in the order they arrived.
+
+use sqlx::SqlitePool;
+
+use crate::common::{generate_history_id, ApiError, ErrorCode};
+use crate::jobs::models::WaitlistEntry;
+use crate::jobs::services::deadlines;
+
+pub const CAP_ACTIONS: &[&str] = &["close", "waitlist"];
+
+pub const DEFAULT_CAP_ACTION: &str = "close";
+
+pub const MAX_APPLICATION_CAP: i64 = 100_000;
+
+/// Most waitlisted applications promoted in one request
+pub const MAX_PROMOTE: i64 = 500;
+
+/// What happens to a new application
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Admission {
+    Admitted,
+    Waitlisted,
+    Refused,
+}
+
+/// Admission of the next application given the cap and the applications already counted
+pub fn admission(cap: Option<i64>, action: &str, admitted: i64) -> Admission {
+    match cap {
+        Some(cap) if admitted >= cap => {
+            if action == "waitlist" {
+                Admission::Waitlisted
+            } else {
+                Admission::Refused
+            }
+        }
+        _ => Admission::Admitted,
+    }
+}
+
+pub fn validate_cap(cap: Option<i64>, action: &str) -> Result<(), String> {
+    if let Some(cap) = cap {
+        if !(1..=MAX_APPLICATION_CAP).contains(&cap) {
+            return Err(format!("cap must be between 1 and {}", MAX_APPLICATION_CAP));
+        }
+    }
+    if !CAP_ACTIONS.contains(&action) {
+        return Err(format!("action must be one of: {}", CAP_ACTIONS.join(", ")));
+    }
+    Ok(())
+}
+
+/// The job's cap and action, or `None` when the job doesn't exist
+pub async fn job_cap(
+    pool: &SqlitePool,
+    job_id: &str,
+) -> Result<Option<(Option<i64>, String)>, sqlx::Error> {
+    let row: Option<(Option<i64>, Option<String>)> =
+        sqlx::query_as("SELECT application_cap, application_cap_action FROM jobs WHERE id = ?")
+            .bind(job_id)
+            .fetch_optional(pool)
+            .await?;
+    Ok(row.map(|(cap, action)| {
+        (
+            cap,
+            action.unwrap_or_else(|| DEFAULT_CAP_ACTION.to_string()),
+        )
+    }))
+}
+
+/// Applications counted against the cap, leaving out one candidate's (who is re-applying)
+pub async fn admitted_count(
+    pool: &SqlitePool,
+    job_id: &str,
+    except_user_id: Option<&str>,
+) -> Result<i64, sqlx::Error> {
+    sqlx::query_scalar(
+        r#"
+        SELECT COUNT(*) FROM applications
+        WHERE job_id = ?1
+          AND archived_at IS NULL
+          AND waitlisted_at IS NULL
+          AND status != 'withdrawn'
+          AND (?2 IS NULL OR user_id != ?2)
+        "#,
+    )
+    .bind(job_id)
+    .bind(except_user_id)
+    .fetch_one(pool)
+    .await
+}
+
+pub async fn waitlisted_count(pool: &SqlitePool, job_id: &str) -> Result<i64, sqlx::Error> {
+    sqlx::query_scalar(
+        r#"
+        SELECT COUNT(*) FROM applications
+        WHERE job_id = ? AND archived_at IS NULL AND waitlisted_at IS NOT NULL
+        "#,
+    )
+    .bind(job_id)
+    .fetch_one(pool)
+    .await
+}
+
+/// Whether a candidate's new application to the job is admitted or waitlisted; refused
+/// applications are an error
+pub async fn admit_application(
+    pool: &SqlitePool,
+    job_id: &str,
+    user_id: &str,
+) -> Result<Admission, ApiError> {
+    let Some((cap, action)) = job_cap(pool, job_id)
+        .await
+        .map_err(ApiError::DatabaseError)?
+    else {
+        return Ok(Admission::Admitted);
+    };
+    if cap.is_none() {
+        return Ok(Admission::Admitted);
+    }
+    let admitted = admitted_count(pool, job_id, Some(user_id))
+        .await
+        .map_err(ApiError::DatabaseError)?;
+
+    match admission(cap, &action, admitted) {
+        Admission::Refused => Err(ApiError::Coded(
+            ErrorCode::ApplicationCapReached,
+            "This job is no longer accepting applications".to_string(),
+        )),
+        other => Ok(other),
+    }
+}
+
+/// Close an active job with the `close` action once its cap is reached. Returns whether
+/// it was closed.
+pub async fn close_if_full(pool: &SqlitePool, job_id: &str) -> Result<bool, sqlx::Error> {
+    let row: Option<(Option<i64>, Option<String>, Option<String>)> = sqlx::query_as(
+        "SELECT application_cap, application_cap_action, status FROM jobs WHERE id = ?",
+    )
+    .bind(job_id)
+    .fetch_optional(pool)
+    .await?;
+    let Some((Some(cap), action, status)) = row else {
+        return Ok(false);
+    };
+    if status.as_deref() != Some("active")
+        || action.as_deref().unwrap_or(DEFAULT_CAP_ACTION) != "close"
+    {
+        return Ok(false);
+    }
+    if admitted_count(pool, job_id, None).await? < cap {
+        return Ok(false);
+    }
+
+    deadlines::close_job(pool, job_id, "Application cap reached").await?;
+    Ok(true)
+}
+
+/// Refuse to make a job active when its next application would be refused
+pub fn ensure_room(cap: Option<i64>, action: &str, admitted: i64) -> Result<(), ApiError> {
+    if admission(cap, action, admitted) == Admission::Refused {
+        return Err(ApiError::ValidationError(format!(
+            "The job already has {} applications against a cap of {}; raise or remove the cap before making it active",
+            admitted,
+            cap.unwrap_or_default()
+        )));
+    }
+    Ok(())
+}
+
+/// [`ensure_room`] under the job's saved cap
+pub async fn ensure_reopenable(pool: &SqlitePool, job_id: &str) -> Result<(), ApiError> {
+    let Some((cap, action)) = job_cap(pool, job_id)
+        .await
+        .map_err(ApiError::DatabaseError)?
+    else {
+        return Ok(());
+    };
+    let admitted = admitted_count(pool, job_id, None)
+        .await
+        .map_err(ApiError::DatabaseError)?;
+    ensure_room(cap, &action, admitted)
+}
+
+/// The job's waitlist, earliest first
+pub async fn waitlist(pool: &SqlitePool, job_id: &str) -> Result<Vec<WaitlistEntry>, sqlx::Error> {
+    sqlx::query_as::<_, WaitlistEntry>(
+        r#"
+        SELECT ROW_NUMBER() OVER (ORDER BY a.waitlisted_at, a.applied_at, a.id) AS position,
+               a.id AS application_id, a.user_id AS candidate_id, u.name AS candidate_name,
+               u.email AS candidate_email, a.applied_at, a.waitlisted_at
+        FROM applications a
+        JOIN users u ON u.id = a.user_id
+        WHERE a.job_id = ? AND a.archived_at IS NULL AND a.waitlisted_at IS NOT NULL
+        ORDER BY position
+        "#,
+    )
+    .bind(job_id)
+    .fetch_all(pool)
+    .await
+}
+
+/// Move the first `count` waitlisted applications into the pipeline, in order
+pub async fn promote(
+    pool: &SqlitePool,
+    job_id: &str,
+    count: i64,
+    changed_by: &str,
+) -> Result<Vec<String>, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    let ids: Vec<String> = sqlx::query_scalar(
+        r#"
+        SELECT id FROM applications
+        WHERE job_id = ? AND archived_at IS NULL AND waitlisted_at IS NOT NULL
+        ORDER BY waitlisted_at, applied_at, id
+        LIMIT ?
+        "#,
+    )
+    .bind(job_id)
+    .bind(count)
+    .fetch_all(&mut *tx)
+    .await?;
+
+    for id in &ids {
+        sqlx::query(
+            "UPDATE applications SET waitlisted_at = NULL, updated_at = datetime('now') WHERE id = ?",
+        )
+        .bind(id)
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query(
+            r#"
+            INSERT INTO application_status_history (id, application_id, status, changed_by, notes, changed_at)
+            SELECT ?, id, status, ?, 'Promoted from the waitlist', datetime('now')
+            FROM applications WHERE id = ?
+            "#,
+        )
+        .bind(generate_history_id())
+        .bind(changed_by)
+        .bind(id)
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    tx.commit().await?;
+    Ok(ids)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_admission() {
+        assert_eq!(admission(None, "close", 500), Admission::Admitted);
+        assert_eq!(admission(Some(200), "close", 199), Admission::Admitted);
+        assert_eq!(admission(Some(200), "close", 200), Admission::Refused);
+        assert_eq!(admission(Some(200), "waitlist", 200), Admission::Waitlisted);
+        assert_eq!(admission(Some(200), "waitlist", 150), Admission::Admitted);
+    }
+
+    #[test]
+    fn test_validate_cap() {
+        assert!(validate_cap(Some(200), "close").is_ok());
+        assert!(validate_cap(None, "waitlist").is_ok());
+        assert!(validate_cap(Some(0), "close").is_err());
+        assert!(validate_cap(Some(200), "pause").is_err());
+    }
+}
@@ -9,20 +9,31 @@ use std::sync::Arc;
 use tracing::{error, info};
 
 use crate::common::{generate_content_version_id, ApiError};
-use crate::jobs::models::{ContentComponentType, ContentVersion, ContentVersionsResponse};
-use crate::services::OpenAIService;
+use crate::jobs::models::{
+    ContentComponentType, ContentVersion, ContentVersionsResponse, FieldProvenance,
+};
+use crate::services::sanitize::sanitize_markdown;
+use crate::services::{LlmClient, SettingsService};
 
 /// Maximum number of versions to keep per job+component
 const MAX_VERSIONS_PER_COMPONENT: i32 = 10;
 
+/// Version sources: generated by the AI editor, or saved by a person through the job form
+pub const CONTENT_SOURCE_AI: &str = "ai";
+pub const CONTENT_SOURCE_HUMAN: &str = "human";
+
+/// Shown on public postings when `ai_provenance_notice_text` is unset
+const DEFAULT_DISCLOSURE: &str =
+    "Parts of this job posting were drafted with the help of AI and reviewed by our hiring team.";
+
 pub struct ContentVersionsService {
     db: SqlitePool,
-    openai_service: Arc<OpenAIService>,
+    llm: Arc<dyn LlmClient>,
 }
 
 impl ContentVersionsService {
-    pub fn new(db: SqlitePool, openai_service: Arc<OpenAIService>) -> Self {
-        Self { db, openai_service }
+    pub fn new(db: SqlitePool, llm: Arc<dyn LlmClient>) -> Self {
+        Self { db, llm }
     }
 
     /// Get all versions for a job component
@@ -38,7 +49,7 @@ impl ContentVersionsService {
         // Get active version
         let active = sqlx::query_as::<_, ContentVersion>(
             r#"
-            SELECT id, job_id, component_type, content, prompt_used, is_active, version_number, created_by, created_at
+            SELECT id, job_id, component_type, content, prompt_used, is_active, version_number, created_by, created_at, source
             FROM job_content_versions
             WHERE job_id = ? AND component_type = ? AND is_active = 1
             "#,
@@ -52,7 +63,7 @@ impl ContentVersionsService {
         // Get history (all versions, newest first)
         let history = sqlx::query_as::<_, ContentVersion>(
             r#"
-            SELECT id, job_id, component_type, content, prompt_used, is_active, version_number, created_by, created_at
+            SELECT id, job_id, component_type, content, prompt_used, is_active, version_number, created_by, created_at, source
             FROM job_content_versions
             WHERE job_id = ? AND component_type = ?
             ORDER BY version_number DESC
@@ -130,7 +141,7 @@ impl ContentVersionsService {
         );
 
         // Generate content using OpenAI
-        let generated_content = self.openai_service
+        let generated_content = self.llm
             .generate_text(
                 crate::services::openai::TextGenerationPurpose::JobDescriptionGeneration,
                 &ai_prompt,
@@ -204,7 +215,7 @@ impl ContentVersionsService {
 
         // Return the created version
         let version = sqlx::query_as::<_, ContentVersion>(
-            "SELECT id, job_id, component_type, content, prompt_used, is_active, version_number, created_by, created_at FROM job_content_versions WHERE id = ?"
+            "SELECT id, job_id, component_type, content, prompt_used, is_active, version_number, created_by, created_at, source FROM job_content_versions WHERE id = ?"
         )
         .bind(&version_id)
         .fetch_one(&self.db)
@@ -227,7 +238,7 @@ impl ContentVersionsService {
 
         // Get the version to activate
         let version = sqlx::query_as::<_, ContentVersion>(
-            "SELECT id, job_id, component_type, content, prompt_used, is_active, version_number, created_by, created_at FROM job_content_versions WHERE id = ? AND job_id = ? AND component_type = ?"
+            "SELECT id, job_id, component_type, content, prompt_used, is_active, version_number, created_by, created_at, source FROM job_content_versions WHERE id = ? AND job_id = ? AND component_type = ?"
         )
         .bind(version_id)
         .bind(job_id)
@@ -268,7 +279,7 @@ impl ContentVersionsService {
 
         // Return updated version
         let updated_version = sqlx::query_as::<_, ContentVersion>(
-            "SELECT id, job_id, component_type, content, prompt_used, is_active, version_number, created_by, created_at FROM job_content_versions WHERE id = ?"
+            "SELECT id, job_id, component_type, content, prompt_used, is_active, version_number, created_by, created_at, source FROM job_content_versions WHERE id = ?"
         )
         .bind(version_id)
         .fetch_one(&self.db)
@@ -287,7 +298,7 @@ impl ContentVersionsService {
     ) -> Result<(), ApiError> {
         // Check if version exists and is not active
         let version = sqlx::query_as::<_, ContentVersion>(
-            "SELECT id, job_id, component_type, content, prompt_used, is_active, version_number, created_by, created_at FROM job_content_versions WHERE id = ? AND job_id = ? AND component_type = ?"
+            "SELECT id, job_id, component_type, content, prompt_used, is_active, version_number, created_by, created_at, source FROM job_content_versions WHERE id = ? AND job_id = ? AND component_type = ?"
         )
         .bind(version_id)
         .bind(job_id)
@@ -353,29 +364,7 @@ impl ContentVersionsService {
         job_id: &str,
         component_type: &str,
     ) -> Result<(), ApiError> {
-        // Delete versions beyond the limit, but never delete active version
-        sqlx::query(
-            r#"
-            DELETE FROM job_content_versions
-            WHERE job_id = ? AND component_type = ? AND is_active = 0
-            AND id NOT IN (
-                SELECT id FROM job_content_versions
-                WHERE job_id = ? AND component_type = ?
-                ORDER BY version_number DESC
-                LIMIT ?
-            )
-            "#,
-        )
-        .bind(job_id)
-        .bind(component_type)
-        .bind(job_id)
-        .bind(component_type)
-        .bind(MAX_VERSIONS_PER_COMPONENT)
-        .execute(&self.db)
-        .await
-        .map_err(ApiError::DatabaseError)?;
-
-        Ok(())
+        prune_versions(&self.db, job_id, component_type).await
     }
 
     /// Build AI prompt based on component type
@@ -504,11 +493,11 @@ impl ContentVersionsService {
             }
             ContentComponentType::Summary => {
                 // Clean up summary - remove quotes, trim (no length limit)
-                content.trim().trim_matches('"').trim().to_string()
+                sanitize_markdown(content.trim().trim_matches('"').trim())
             }
             ContentComponentType::Description => {
-                // Keep markdown as-is
-                content.trim().to_string()
+                // Keep markdown, minus any unsafe HTML the model produced
+                sanitize_markdown(content.trim())
             }
             ContentComponentType::Requirements | ContentComponentType::Benefits => {
                 // Try to parse as JSON array, or extract from text
@@ -535,3 +524,211 @@ impl ContentVersionsService {
         }
     }
 }
+
+/// Delete versions beyond `MAX_VERSIONS_PER_COMPONENT`, never the active one
+async fn prune_versions(
+    db: &SqlitePool,
+    job_id: &str,
+    component_type: &str,
+) -> Result<(), ApiError> {
+    sqlx::query(
+        r#"
+        DELETE FROM job_content_versions
+        WHERE job_id = ? AND component_type = ? AND is_active = 0
+        AND id NOT IN (
+            SELECT id FROM job_content_versions
+            WHERE job_id = ? AND component_type = ?
+            ORDER BY version_number DESC
+            LIMIT ?
+        )
+        "#,
+    )
+    .bind(job_id)
+    .bind(component_type)
+    .bind(job_id)
+    .bind(component_type)
+    .bind(MAX_VERSIONS_PER_COMPONENT)
+    .execute(db)
+    .await
+    .map_err(ApiError::DatabaseError)?;
+
+    Ok(())
+}
+
+/// Check the `ai_generated_fields` of a job create/update name real content fields
+pub fn validate_ai_fields(fields: &[String]) -> Result<(), ApiError> {
+    for field in fields {
+        match ContentComponentType::from_str(field) {
+            Some(ContentComponentType::Summary) | None => {
+                return Err(ApiError::BadRequest(format!(
+                    "Unknown field in ai_generated_fields: {}",
+                    field
+                )))
+            }
+            Some(_) => {}
+        }
+    }
+    Ok(())
+}
+
+/// Record content saved through the job form as the active version of each changed field.
+/// `changes` pairs a component type with its previous and new values; unchanged fields are
+/// skipped, and fields listed in `ai_fields` are recorded as AI-generated.
+pub async fn record_job_edits(
+    db: &SqlitePool,
+    job_id: &str,
+    changes: &[(ContentComponentType, Option<&str>, Option<&str>)],
+    ai_fields: &[String],
+    user_id: &str,
+) -> Result<(), ApiError> {
+    for (component, previous, new) in changes {
+        let Some(content) = new else {
+            continue;
+        };
+        if previous == &Some(*content) {
+            continue;
+        }
+
+        let component_type = component.as_str();
+        let source = if ai_fields.iter().any(|f| f.eq_ignore_ascii_case(component_type)) {
+            CONTENT_SOURCE_AI
+        } else {
+            CONTENT_SOURCE_HUMAN
+        };
+
+        let next_version: i32 = sqlx::query_scalar(
+            "SELECT COALESCE(MAX(version_number), 0) + 1 FROM job_content_versions WHERE job_id = ? AND component_type = ?",
+        )
+        .bind(job_id)
+        .bind(component_type)
+        .fetch_one(db)
+        .await
+        .map_err(ApiError::DatabaseError)?;
+
+        sqlx::query(
+            "UPDATE job_content_versions SET is_active = 0 WHERE job_id = ? AND component_type = ?",
+        )
+        .bind(job_id)
+        .bind(component_type)
+        .execute(db)
+        .await
+        .map_err(ApiError::DatabaseError)?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO job_content_versions (id, job_id, component_type, content, prompt_used, is_active, version_number, created_by, created_at, source)
+            VALUES (?, ?, ?, ?, NULL, 1, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(generate_content_version_id())
+        .bind(job_id)
+        .bind(component_type)
+        .bind(content)
+        .bind(next_version)
+        .bind(user_id)
+        .bind(chrono::Utc::now().to_rfc3339())
+        .bind(source)
+        .execute(db)
+        .await
+        .map_err(ApiError::DatabaseError)?;
+
+        prune_versions(db, job_id, component_type).await?;
+    }
+
+    Ok(())
+}
+
+/// Per-field provenance from a job's versions, ordered by field then version number
+pub fn summarize_provenance(versions: &[ContentVersion]) -> Vec<FieldProvenance> {
+    let mut fields: Vec<FieldProvenance> = Vec::new();
+
+    for active in versions.iter().filter(|v| v.is_active == 1) {
+        let history = versions
+            .iter()
+            .filter(|v| v.component_type == active.component_type);
+        let ai_versions = history
+            .clone()
+            .filter(|v| v.source == CONTENT_SOURCE_AI)
+            .count() as i64;
+        let human_versions = history
+            .filter(|v| v.source == CONTENT_SOURCE_HUMAN)
+            .count() as i64;
+
+        let origin = if active.source == CONTENT_SOURCE_AI {
+            "ai_generated"
+        } else if ai_versions > 0 {
+            "human_edited"
+        } else {
+            "human"
+        };
+
+        fields.push(FieldProvenance {
+            field: active.component_type.clone(),
+            origin: origin.to_string(),
+            active_version_id: active.id.clone(),
+            last_changed_by: active.created_by.clone(),
+            last_changed_at: active.created_at.clone(),
+            ai_versions,
+            human_versions,
+        });
+    }
+
+    fields
+}
+
+/// Where each of a job's fields came from; fields never versioned are omitted
+pub async fn job_provenance(
+    db: &SqlitePool,
+    job_id: &str,
+) -> Result<Vec<FieldProvenance>, ApiError> {
+    let versions = sqlx::query_as::<_, ContentVersion>(
+        r#"
+        SELECT id, job_id, component_type, content, prompt_used, is_active, version_number, created_by, created_at, source
+        FROM job_content_versions
+        WHERE job_id = ?
+        ORDER BY component_type, version_number
+        "#,
+    )
+    .bind(job_id)
+    .fetch_all(db)
+    .await
+    .map_err(ApiError::DatabaseError)?;
+
+    Ok(summarize_provenance(&versions))
+}
+
+/// The notice to show on a public posting whose live content involved AI, when the
+/// `ai_provenance_notice_enabled` setting is on
+pub async fn public_disclosure(
+    db: &SqlitePool,
+    settings: &SettingsService,
+    job_id: &str,
+) -> Result<Option<String>, ApiError> {
+    let enabled = settings
+        .get_setting("ai_provenance_notice_enabled")
+        .await
+        .ok()
+        .flatten()
+        .map(|v| v == "true")
+        .unwrap_or(false);
+    if !enabled {
+        return Ok(None);
+    }
+
+    let uses_ai = job_provenance(db, job_id)
+        .await?
+        .iter()
+        .any(|field| field.origin != "human");
+    if !uses_ai {
+        return Ok(None);
+    }
+
+    let notice = settings
+        .get_setting("ai_provenance_notice_text")
+        .await
+        .ok()
+        .flatten()
+        .filter(|text| !text.trim().is_empty())
+        .unwrap_or_else(|| DEFAULT_DISCLOSURE.to_string());
+    Ok(Some(notice))
+}
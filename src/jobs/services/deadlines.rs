@@ -0,0 +1,290 @@
+// src/jobs/services/deadlines.rs
+//! Application deadlines
+//!
+//! A job's `application_deadline` is stored as a UTC RFC3339 timestamp. Once it passes,
+//! new applications are refused, and the close task moves the job from active to closed,
+//! records the change in its status history and emails the hiring manager a summary of
+//! the applications it received. Reopening a job requires moving or removing the deadline
+//! first, so the task doesn't close it again straight away.
+
+use chrono::{DateTime, Utc};
+use sqlx::SqlitePool;
+use std::collections::HashSet;
+use std::sync::Arc;
+use tracing::{debug, error, info};
+
+use crate::common::timezone::parse_stored;
+use crate::common::{generate_history_id, ApiError, ErrorCode};
+use crate::jobs::services::FeedCache;
+use crate::services::{org, EmailSender};
+
+const CLOSE_CHECK_INTERVAL_SECS: u64 = 5 * 60;
+
+/// `changed_by` of status history rows written by the close task
+const CLOSED_BY: &str = "system";
+
+/// Check a submitted deadline and return it as UTC RFC3339
+pub fn normalize_deadline(value: &str, now: DateTime<Utc>) -> Result<String, String> {
+    let deadline = parse_stored(value.trim()).ok_or_else(|| {
+        "Application deadline must be an RFC3339 timestamp, e.g. 2026-06-30T17:00:00Z".to_string()
+    })?;
+    if deadline <= now {
+        return Err("Application deadline must be in the future".to_string());
+    }
+    Ok(deadline.to_rfc3339())
+}
+
+/// Whether a stored deadline has passed; jobs without one never close
+pub fn deadline_passed(deadline: Option<&str>, now: DateTime<Utc>) -> bool {
+    deadline
+        .and_then(parse_stored)
+        .is_some_and(|deadline| deadline <= now)
+}
+
+/// Refuse an application to a job whose deadline has passed
+pub async fn ensure_accepting_applications(
+    pool: &SqlitePool,
+    job_id: &str,
+) -> Result<(), ApiError> {
+    let deadline: Option<String> =
+        sqlx::query_scalar("SELECT application_deadline FROM jobs WHERE id = ?")
+            .bind(job_id)
+            .fetch_optional(pool)
+            .await
+            .map_err(ApiError::DatabaseError)?
+            .flatten();
+
+    if deadline_passed(deadline.as_deref(), Utc::now()) {
+        return Err(ApiError::Coded(
+            ErrorCode::ApplicationDeadlinePassed,
+            format!(
+                "Applications for this job closed at {}",
+                deadline.unwrap_or_default()
+            ),
+        ));
+    }
+    Ok(())
+}
+
+/// Refuse to make a job active while its deadline is in the past
+pub fn ensure_reopenable(deadline: Option<&str>) -> Result<(), ApiError> {
+    if deadline_passed(deadline, Utc::now()) {
+        return Err(ApiError::ValidationError(
+            "The application deadline has passed; move or remove it before making the job active"
+                .to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Email body summarising the applications a job received before it closed
+pub fn summary_html(
+    title: &str,
+    deadline: &str,
+    counts: &[(String, i64)],
+    job_url: &str,
+) -> String {
+    let total: i64 = counts.iter().map(|(_, count)| count).sum();
+    let mut body = format!(
+        "<p><strong>{}</strong> stopped accepting applications at {} and has been closed.</p>",
+        title, deadline
+    );
+    if total == 0 {
+        body.push_str("<p>It received no applications.</p>");
+    } else {
+        body.push_str(&format!(
+            "<p>It received {} application{}:</p><ul>",
+            total,
+            if total == 1 { "" } else { "s" }
+        ));
+        for (status, count) in counts {
+            body.push_str(&format!("<li>{}: {}</li>", status, count));
+        }
+        body.push_str("</ul>");
+    }
+    body.push_str(&format!(
+        "<p><a href=\"{}\">Review the job</a></p>",
+        job_url
+    ));
+    body
+}
+
+/// Close an active job on the system's behalf, recording why in its status history
+pub async fn close_job(pool: &SqlitePool, job_id: &str, notes: &str) -> Result<(), sqlx::Error> {
+    let now = Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
+    let mut tx = pool.begin().await?;
+    sqlx::query("UPDATE jobs SET status = 'closed', updated_at = ? WHERE id = ?")
+        .bind(&now)
+        .bind(job_id)
+        .execute(&mut *tx)
+        .await?;
+    sqlx::query(
+        r#"INSERT INTO job_status_history (
+            id, job_id, old_status, new_status, changed_by, notes, changed_at
+        ) VALUES (?, ?, 'active', 'closed', ?, ?, ?)"#,
+    )
+    .bind(generate_history_id())
+    .bind(job_id)
+    .bind(CLOSED_BY)
+    .bind(notes)
+    .bind(&now)
+    .execute(&mut *tx)
+    .await?;
+    tx.commit().await
+}
+
+/// Close active jobs whose deadline has passed, returning how many were closed
+pub async fn close_expired_jobs(
+    pool: &SqlitePool,
+    email_sender: &dyn EmailSender,
+    admin_emails: &HashSet<String>,
+    frontend_url: &str,
+) -> Result<usize, sqlx::Error> {
+    let expired: Vec<(String, String, String)> = sqlx::query_as(
+        r#"
+        SELECT id, title, application_deadline FROM jobs
+        WHERE status = 'active'
+          AND deleted_at IS NULL
+          AND application_deadline IS NOT NULL
+          AND datetime(application_deadline) <= datetime('now')
+        "#,
+    )
+    .fetch_all(pool)
+    .await?;
+
+    for (job_id, title, deadline) in &expired {
+        close_job(pool, job_id, "Application deadline passed").await?;
+        info!(job_id = %job_id, deadline = %deadline, "Closed job at its application deadline");
+
+        let counts: Vec<(String, i64)> = sqlx::query_as(
+            r#"
+            SELECT status, COUNT(*) FROM applications
+            WHERE job_id = ?
+            GROUP BY status
+            ORDER BY COUNT(*) DESC, status
+            "#,
+        )
+        .bind(job_id)
+        .fetch_all(pool)
+        .await?;
+
+        // The hiring manager first; recruiters, then admins, when the job has none
+        let mut recipients: Vec<String> = sqlx::query_scalar(
+            "SELECT u.email FROM jobs j JOIN users u ON u.id = j.hiring_manager_id WHERE j.id = ?",
+        )
+        .bind(job_id)
+        .fetch_all(pool)
+        .await?;
+        if recipients.is_empty() {
+            recipients = org::job_assignee_emails(pool, job_id).await?;
+        }
+        if recipients.is_empty() {
+            recipients = admin_emails.iter().cloned().collect();
+            recipients.sort();
+        }
+        if recipients.is_empty() {
+            continue;
+        }
+
+        let subject = format!("Applications closed for {}", title);
+        let body = summary_html(
+            title,
+            deadline,
+            &counts,
+            &format!("{}/admin/jobs/{}", frontend_url, job_id),
+        );
+        if let Err(e) = email_sender
+            .send_email(recipients.clone(), &subject, &body, None)
+            .await
+        {
+            error!(error = %e, job_id = %job_id, "Failed to send job closing summary");
+        } else {
+            info!(
+                job_id = %job_id,
+                recipients = recipients.len(),
+                "Sent job closing summary"
+            );
+        }
+    }
+
+    Ok(expired.len())
+}
+
+/// Periodically close jobs whose application deadline has passed
+pub fn start_deadline_close_task(
+    pool: SqlitePool,
+    email_sender: Arc<dyn EmailSender>,
+    admin_emails: HashSet<String>,
+    frontend_url: String,
+    feed_cache: FeedCache,
+) {
+    tokio::spawn(async move {
+        loop {
+            if !crate::common::shutdown::sleep(std::time::Duration::from_secs(
+                CLOSE_CHECK_INTERVAL_SECS,
+            ))
+            .await
+            {
+                break;
+            }
+            let _running = crate::common::shutdown::track();
+
+            match close_expired_jobs(&pool, email_sender.as_ref(), &admin_emails, &frontend_url)
+                .await
+            {
+                Ok(0) => {}
+                Ok(closed) => {
+                    feed_cache.invalidate().await;
+                    info!(closed, "Closed jobs past their application deadline");
+                }
+                Err(e) => debug!(error = %e, "Skipped job deadline close run"),
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 5, 1, 12, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn test_normalize_deadline() {
+        assert_eq!(
+            normalize_deadline("2026-06-30T17:00:00+02:00", now()).unwrap(),
+            "2026-06-30T15:00:00+00:00"
+        );
+        assert!(normalize_deadline("2026-04-30T12:00:00Z", now()).is_err());
+        assert!(normalize_deadline("2026-06-30", now()).is_err());
+    }
+
+    #[test]
+    fn test_deadline_passed() {
+        assert!(deadline_passed(Some("2026-05-01T11:59:59Z"), now()));
+        assert!(deadline_passed(Some("2026-05-01T12:00:00Z"), now()));
+        assert!(!deadline_passed(Some("2026-05-01T12:00:01Z"), now()));
+        assert!(!deadline_passed(None, now()));
+        assert!(!deadline_passed(Some("not a date"), now()));
+    }
+
+    #[test]
+    fn test_summary_html() {
+        let url = "http://localhost:3000/admin/jobs/J_1";
+        let body = summary_html(
+            "Engineer",
+            "2026-05-01T12:00:00+00:00",
+            &[("pending".to_string(), 3), ("rejected".to_string(), 1)],
+            url,
+        );
+        assert!(body.contains("It received 4 applications:"));
+        assert!(body.contains("<li>pending: 3</li>"));
+        assert!(body.contains(url));
+
+        let empty = summary_html("Engineer", "2026-05-01T12:00:00+00:00", &[], url);
+        assert!(empty.contains("It received no applications."));
+    }
+}
@@ -0,0 +1,319 @@
+// src/jobs/services/drafts.rs
+//! Autosaved job drafts
+//!
+//! Editors send only the fields they changed since their last save, and the server keeps
+//! the pending values in `jobs.draft_data` next to the live job. Every save bumps
+//! `draft_revision` and stamps the changed fields with it, so a save based on an older
+//! revision only conflicts when it touches a field someone else saved in the meantime.
+//! Publishing applies the pending fields to the job and marks them as changed at the
+//! publish revision, so stale editors are told about it too.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use sqlx::SqlitePool;
+use std::collections::BTreeMap;
+
+use crate::common::{ApiError, ErrorCode};
+use crate::jobs::models::{JobDraft, UpdateJob};
+
+/// Job fields that can be edited through a draft
+pub const DRAFT_FIELDS: &[&str] = &[
+    "title",
+    "description",
+    "location",
+    "company",
+    "company_id",
+    "company_logo_url",
+    "job_image_url",
+    "salary_min",
+    "salary_max",
+    "job_type",
+    "experience_level",
+    "requirements",
+    "benefits",
+    "educational_qualifications",
+    "is_featured",
+];
+
+/// What `jobs.draft_data` holds
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct StoredDraft {
+    /// Pending values, keyed by field
+    #[serde(default)]
+    pub fields: Map<String, Value>,
+    /// Revision at which each field was last saved or published
+    #[serde(default)]
+    pub field_revisions: BTreeMap<String, i64>,
+}
+
+impl StoredDraft {
+    pub fn parse(draft_data: Option<&str>) -> Self {
+        draft_data
+            .and_then(|d| serde_json::from_str(d).ok())
+            .unwrap_or_default()
+    }
+
+    /// The pending fields as a job update, rejecting values of the wrong type
+    pub fn as_update(&self) -> Result<UpdateJob, ApiError> {
+        serde_json::from_value(Value::Object(self.fields.clone()))
+            .map_err(|e| ApiError::ValidationError(format!("Invalid draft value: {}", e)))
+    }
+}
+
+/// The draft columns of a job
+#[derive(Debug, sqlx::FromRow)]
+pub struct DraftRow {
+    pub draft_data: Option<String>,
+    pub draft_revision: i64,
+    pub draft_updated_by: Option<String>,
+    pub draft_updated_at: Option<String>,
+}
+
+impl DraftRow {
+    pub fn into_draft(self, job_id: &str) -> JobDraft {
+        JobDraft {
+            job_id: job_id.to_string(),
+            revision: self.draft_revision,
+            fields: StoredDraft::parse(self.draft_data.as_deref()).fields,
+            updated_by: self.draft_updated_by,
+            updated_at: self.draft_updated_at,
+            editors: Vec::new(),
+        }
+    }
+}
+
+pub async fn load(pool: &SqlitePool, job_id: &str) -> Result<DraftRow, ApiError> {
+    sqlx::query_as::<_, DraftRow>(
+        r#"
+        SELECT draft_data, draft_revision, draft_updated_by, draft_updated_at
+        FROM jobs
+        WHERE id = ? AND deleted_at IS NULL
+        "#,
+    )
+    .bind(job_id)
+    .fetch_optional(pool)
+    .await
+    .map_err(ApiError::DatabaseError)?
+    .ok_or_else(|| ApiError::NotFound(format!("Job not found: {}", job_id)))
+}
+
+/// Apply a partial save to the stored draft. Returns whether anything changed; a save
+/// that changes nothing does not need a new revision.
+pub fn merge_changes(
+    draft: &mut StoredDraft,
+    current_revision: i64,
+    base_revision: i64,
+    changes: &Map<String, Value>,
+    force: bool,
+) -> Result<bool, ApiError> {
+    if let Some(unknown) = changes.keys().find(|k| !DRAFT_FIELDS.contains(&k.as_str())) {
+        return Err(ApiError::ValidationError(format!(
+            "Field '{}' cannot be saved in a draft; expected one of {}",
+            unknown,
+            DRAFT_FIELDS.join(", ")
+        )));
+    }
+    if base_revision > current_revision {
+        return Err(ApiError::BadRequest(format!(
+            "Unknown draft revision {}; the latest is {}",
+            base_revision, current_revision
+        )));
+    }
+
+    if !force {
+        let conflicts: Vec<&str> = changes
+            .keys()
+            .filter(|field| {
+                draft
+                    .field_revisions
+                    .get(field.as_str())
+                    .is_some_and(|rev| *rev > base_revision)
+            })
+            .map(String::as_str)
+            .collect();
+        if !conflicts.is_empty() {
+            return Err(ApiError::Coded(
+                ErrorCode::DraftConflict,
+                format!(
+                    "Draft changed since revision {}: {}",
+                    base_revision,
+                    conflicts.join(", ")
+                ),
+            ));
+        }
+    }
+
+    let next_revision = current_revision + 1;
+    let mut changed = false;
+    for (field, value) in changes {
+        let previous = if value.is_null() {
+            draft.fields.remove(field)
+        } else {
+            draft.fields.insert(field.clone(), value.clone())
+        };
+        if previous.as_ref() != Some(value) && !(previous.is_none() && value.is_null()) {
+            draft.field_revisions.insert(field.clone(), next_revision);
+            changed = true;
+        }
+    }
+    Ok(changed)
+}
+
+/// Check the job as it will look once the draft is applied
+pub fn validate_for_publish(
+    update: &UpdateJob,
+    current_salary: (Option<i64>, Option<i64>),
+) -> Result<(), ApiError> {
+    if let Some(title) = &update.title {
+        if title.trim().is_empty() {
+            return Err(ApiError::ValidationError(
+                "Job title is required".to_string(),
+            ));
+        }
+        if title.chars().count() > 255 {
+            return Err(ApiError::ValidationError(
+                "Job title must be less than 255 characters".to_string(),
+            ));
+        }
+    }
+    if update
+        .description
+        .as_ref()
+        .is_some_and(|d| d.chars().count() > 10000)
+    {
+        return Err(ApiError::ValidationError(
+            "Description must be less than 10000 characters".to_string(),
+        ));
+    }
+    for (name, value) in [
+        ("Location", &update.location),
+        ("Company name", &update.company),
+    ] {
+        if value.as_ref().is_some_and(|v| v.chars().count() > 255) {
+            return Err(ApiError::ValidationError(format!(
+                "{} must be less than 255 characters",
+                name
+            )));
+        }
+    }
+
+    let salary_min = update.salary_min.or(current_salary.0);
+    let salary_max = update.salary_max.or(current_salary.1);
+    if salary_min.is_some_and(|s| s < 0) || salary_max.is_some_and(|s| s < 0) {
+        return Err(ApiError::ValidationError(
+            "Salary cannot be negative".to_string(),
+        ));
+    }
+    if let (Some(min), Some(max)) = (salary_min, salary_max) {
+        if min > max {
+            return Err(ApiError::ValidationError(
+                "Minimum salary cannot exceed maximum salary".to_string(),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// The draft left behind by a publish: no pending fields, with the published ones stamped
+/// at the publish revision
+pub fn after_publish(draft: &StoredDraft, publish_revision: i64) -> StoredDraft {
+    let mut field_revisions = draft.field_revisions.clone();
+    for field in draft.fields.keys() {
+        field_revisions.insert(field.clone(), publish_revision);
+    }
+    StoredDraft {
+        fields: Map::new(),
+        field_revisions,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn changes(value: Value) -> Map<String, Value> {
+        value.as_object().unwrap().clone()
+    }
+
+    #[test]
+    fn test_merge_changes_detects_conflicting_fields_only() {
+        let mut draft = StoredDraft::default();
+        assert!(merge_changes(&mut draft, 0, 0, &changes(json!({"title": "A"})), false).unwrap());
+        assert_eq!(draft.field_revisions["title"], 1);
+
+        // Another editor, still on revision 0, edits a different field
+        assert!(merge_changes(
+            &mut draft,
+            1,
+            0,
+            &changes(json!({"location": "Remote"})),
+            false
+        )
+        .unwrap());
+
+        // ...but the title moved on since revision 0
+        let err = merge_changes(&mut draft, 2, 0, &changes(json!({"title": "B"})), false);
+        assert!(matches!(
+            err,
+            Err(ApiError::Coded(ErrorCode::DraftConflict, _))
+        ));
+        assert!(merge_changes(&mut draft, 2, 0, &changes(json!({"title": "B"})), true).unwrap());
+        assert_eq!(draft.fields["title"], json!("B"));
+    }
+
+    #[test]
+    fn test_merge_changes_skips_no_ops_and_rejects_unknown_fields() {
+        let mut draft = StoredDraft::default();
+        merge_changes(&mut draft, 0, 0, &changes(json!({"title": "A"})), false).unwrap();
+        assert!(!merge_changes(&mut draft, 1, 1, &changes(json!({"title": "A"})), false).unwrap());
+        assert!(
+            !merge_changes(&mut draft, 1, 1, &changes(json!({"location": null})), false).unwrap()
+        );
+        assert!(merge_changes(&mut draft, 1, 1, &changes(json!({"title": null})), false).unwrap());
+        assert!(draft.fields.is_empty());
+
+        assert!(merge_changes(
+            &mut draft,
+            2,
+            2,
+            &changes(json!({"status": "active"})),
+            false
+        )
+        .is_err());
+        assert!(merge_changes(&mut draft, 2, 5, &changes(json!({"title": "C"})), false).is_err());
+    }
+
+    #[test]
+    fn test_after_publish_stamps_published_fields() {
+        let mut draft = StoredDraft::default();
+        merge_changes(&mut draft, 0, 0, &changes(json!({"title": "A"})), false).unwrap();
+        let published = after_publish(&draft, 4);
+        assert!(published.fields.is_empty());
+        assert_eq!(published.field_revisions["title"], 4);
+
+        let mut stale = published;
+        let err = merge_changes(&mut stale, 4, 1, &changes(json!({"title": "B"})), false);
+        assert!(matches!(
+            err,
+            Err(ApiError::Coded(ErrorCode::DraftConflict, _))
+        ));
+    }
+
+    #[test]
+    fn test_validate_for_publish() {
+        let draft = StoredDraft {
+            fields: changes(json!({"title": "Engineer", "salary_min": 90000})),
+            field_revisions: BTreeMap::new(),
+        };
+        let update = draft.as_update().unwrap();
+        assert!(validate_for_publish(&update, (None, Some(120000))).is_ok());
+        assert!(validate_for_publish(&update, (None, Some(50000))).is_err());
+
+        let bad_type = StoredDraft {
+            fields: changes(json!({"salary_min": "lots"})),
+            field_revisions: BTreeMap::new(),
+        };
+        assert!(bad_type.as_update().is_err());
+    }
+}
@@ -0,0 +1,289 @@
+// src/jobs/services/duplicates.rs
+//! Duplicate job posting detection
+//!
+//! New jobs are compared with every active job on title, company and description. Matches
+//! at or above `duplicate_job_threshold` (default 0.8) are returned with the created job as a
+//! warning, or refused outright when `duplicate_job_policy` is `block`, unless the request
+//! sets `allow_duplicate`.
+
+use std::collections::HashSet;
+
+use sqlx::SqlitePool;
+
+use crate::jobs::models::{DuplicateJobMatch, DuplicateJobPair, JobFingerprint};
+use crate::services::SettingsService;
+
+pub const DEFAULT_THRESHOLD: f64 = 0.8;
+
+/// Below this, unrelated jobs with a few shared words start to match
+const MIN_THRESHOLD: f64 = 0.5;
+
+const TITLE_WEIGHT: f64 = 0.45;
+const COMPANY_WEIGHT: f64 = 0.25;
+const DESCRIPTION_WEIGHT: f64 = 0.30;
+
+/// Legal-form suffixes ignored when comparing company names
+const COMPANY_SUFFIXES: &[&str] = &["inc", "llc", "ltd", "limited", "corp", "co", "gmbh", "plc"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DuplicatePolicy {
+    Warn,
+    Block,
+}
+
+/// A similarity threshold kept within a useful range
+pub fn clamp_threshold(threshold: f64) -> f64 {
+    if threshold.is_finite() {
+        threshold.clamp(MIN_THRESHOLD, 1.0)
+    } else {
+        DEFAULT_THRESHOLD
+    }
+}
+
+/// Configured similarity threshold
+pub fn threshold(setting: Option<&str>) -> f64 {
+    clamp_threshold(
+        setting
+            .and_then(|v| v.trim().parse::<f64>().ok())
+            .unwrap_or(DEFAULT_THRESHOLD),
+    )
+}
+
+pub async fn settings(settings_service: &SettingsService) -> (DuplicatePolicy, f64) {
+    let policy = match settings_service
+        .get_setting("duplicate_job_policy")
+        .await
+        .ok()
+        .flatten()
+        .as_deref()
+        .map(str::trim)
+    {
+        Some("block") => DuplicatePolicy::Block,
+        _ => DuplicatePolicy::Warn,
+    };
+    let threshold = threshold(
+        settings_service
+            .get_setting("duplicate_job_threshold")
+            .await
+            .ok()
+            .flatten()
+            .as_deref(),
+    );
+    (policy, threshold)
+}
+
+fn words(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+fn jaccard<T: Eq + std::hash::Hash>(a: &HashSet<T>, b: &HashSet<T>) -> f64 {
+    if a.is_empty() && b.is_empty() {
+        return 1.0;
+    }
+    let shared = a.intersection(b).count();
+    shared as f64 / (a.len() + b.len() - shared) as f64
+}
+
+fn title_similarity(a: &str, b: &str) -> f64 {
+    let a: HashSet<String> = words(a).into_iter().collect();
+    let b: HashSet<String> = words(b).into_iter().collect();
+    jaccard(&a, &b)
+}
+
+fn company_similarity(a: &str, b: &str) -> f64 {
+    let name = |s: &str| -> HashSet<String> {
+        words(s)
+            .into_iter()
+            .filter(|w| !COMPANY_SUFFIXES.contains(&w.as_str()))
+            .collect()
+    };
+    jaccard(&name(a), &name(b))
+}
+
+/// Overlap of three-word runs, so shared boilerplate phrases count but reordered text does not
+fn description_similarity(a: &str, b: &str) -> f64 {
+    let shingles = |s: &str| -> HashSet<String> {
+        let words = words(s);
+        if words.len() < 3 {
+            return words.into_iter().collect();
+        }
+        words.windows(3).map(|w| w.join(" ")).collect()
+    };
+    jaccard(&shingles(a), &shingles(b))
+}
+
+fn present(value: &Option<String>) -> Option<&str> {
+    value.as_deref().map(str::trim).filter(|v| !v.is_empty())
+}
+
+/// Weighted similarity of two jobs, from 0.0 to 1.0
+///
+/// A company or description missing on either side is left out and the remaining weights
+/// are scaled up, so two bare postings are judged on what they have.
+pub fn similarity(a: &JobFingerprint, b: &JobFingerprint) -> f64 {
+    let mut score = TITLE_WEIGHT * title_similarity(&a.title, &b.title);
+    let mut weight = TITLE_WEIGHT;
+
+    if let (Some(x), Some(y)) = (present(&a.company), present(&b.company)) {
+        score += COMPANY_WEIGHT * company_similarity(x, y);
+        weight += COMPANY_WEIGHT;
+    }
+    if let (Some(x), Some(y)) = (present(&a.description), present(&b.description)) {
+        score += DESCRIPTION_WEIGHT * description_similarity(x, y);
+        weight += DESCRIPTION_WEIGHT;
+    }
+
+    score / weight
+}
+
+/// Jobs in `existing` that `job` resembles, most similar first
+pub fn find_matches(
+    job: &JobFingerprint,
+    existing: &[JobFingerprint],
+    threshold: f64,
+) -> Vec<DuplicateJobMatch> {
+    let mut matches: Vec<DuplicateJobMatch> = existing
+        .iter()
+        .filter(|other| other.id != job.id)
+        .filter_map(|other| {
+            let similarity = similarity(job, other);
+            (similarity >= threshold).then(|| DuplicateJobMatch {
+                job_id: other.id.clone(),
+                title: other.title.clone(),
+                company: other.company.clone(),
+                similarity: (similarity * 1000.0).round() / 1000.0,
+            })
+        })
+        .collect();
+    matches.sort_by(|a, b| b.similarity.total_cmp(&a.similarity));
+    matches
+}
+
+/// Every pair of jobs at or above the threshold, most similar first
+pub fn find_pairs(jobs: &[JobFingerprint], threshold: f64) -> Vec<DuplicateJobPair> {
+    let mut pairs: Vec<DuplicateJobPair> = jobs
+        .iter()
+        .enumerate()
+        .flat_map(|(i, job)| {
+            find_matches(job, &jobs[i + 1..], threshold)
+                .into_iter()
+                .map(|duplicate| DuplicateJobPair {
+                    job_id: job.id.clone(),
+                    title: job.title.clone(),
+                    company: job.company.clone(),
+                    duplicate,
+                })
+        })
+        .collect();
+    pairs.sort_by(|a, b| b.duplicate.similarity.total_cmp(&a.duplicate.similarity));
+    pairs
+}
+
+pub async fn active_jobs(pool: &SqlitePool) -> Result<Vec<JobFingerprint>, sqlx::Error> {
+    sqlx::query_as::<_, JobFingerprint>(
+        "SELECT id, title, company, description FROM jobs WHERE status = 'active' AND deleted_at IS NULL ORDER BY created_at",
+    )
+    .fetch_all(pool)
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn job(
+        id: &str,
+        title: &str,
+        company: Option<&str>,
+        description: Option<&str>,
+    ) -> JobFingerprint {
+        JobFingerprint {
+            id: id.to_string(),
+            title: title.to_string(),
+            company: company.map(str::to_string),
+            description: description.map(str::to_string),
+        }
+    }
+
+    const DESCRIPTION: &str = "Build and operate the payments platform in Rust. You will own services end to end and work closely with product.";
+
+    #[test]
+    fn test_threshold_bounds() {
+        assert_eq!(threshold(None), DEFAULT_THRESHOLD);
+        assert_eq!(threshold(Some("0.9")), 0.9);
+        assert_eq!(threshold(Some("0.1")), MIN_THRESHOLD);
+        assert_eq!(threshold(Some("7")), 1.0);
+        assert_eq!(threshold(Some("NaN")), DEFAULT_THRESHOLD);
+    }
+
+    #[test]
+    fn test_reposted_job_is_a_duplicate() {
+        let a = job(
+            "J_1",
+            "Senior Rust Engineer",
+            Some("Acme Inc."),
+            Some(DESCRIPTION),
+        );
+        let b = job(
+            "J_2",
+            "Senior Rust Engineer",
+            Some("ACME"),
+            Some(DESCRIPTION),
+        );
+        assert!((similarity(&a, &b) - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_same_title_at_another_company_is_not() {
+        let a = job(
+            "J_1",
+            "Senior Rust Engineer",
+            Some("Acme"),
+            Some(DESCRIPTION),
+        );
+        let b = job(
+            "J_2",
+            "Senior Rust Engineer",
+            Some("Globex"),
+            Some("Help us scale our logistics APIs and mentor a small backend team."),
+        );
+        assert!(similarity(&a, &b) < DEFAULT_THRESHOLD);
+    }
+
+    #[test]
+    fn test_missing_fields_are_left_out() {
+        let a = job("J_1", "Data Analyst", None, None);
+        let b = job("J_2", "data analyst", Some("Acme"), Some(DESCRIPTION));
+        assert!((similarity(&a, &b) - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_find_matches_and_pairs() {
+        let jobs = vec![
+            job(
+                "J_1",
+                "Senior Rust Engineer",
+                Some("Acme"),
+                Some(DESCRIPTION),
+            ),
+            job("J_2", "Product Designer", Some("Acme"), None),
+            job(
+                "J_3",
+                "Senior Rust Engineer",
+                Some("Acme LLC"),
+                Some(DESCRIPTION),
+            ),
+        ];
+        let matches = find_matches(&jobs[0], &jobs, DEFAULT_THRESHOLD);
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].job_id, "J_3");
+
+        let pairs = find_pairs(&jobs, DEFAULT_THRESHOLD);
+        assert_eq!(pairs.len(), 1);
+        assert_eq!(pairs[0].job_id, "J_1");
+        assert_eq!(pairs[0].duplicate.job_id, "J_3");
+    }
+}
@@ -0,0 +1,172 @@
+// src/jobs/services/editing.rs
+//! Who has a job's edit draft open
+//!
+//! Admins announce over their WebSocket when they open and close a job's editor. Nothing is
+//! locked: everyone editing the job is told who else is there, and when one of them saves
+//! the draft the others hear which fields changed, so overwrites never go unnoticed.
+//! Entries are per connection and go away when the connection closes.
+
+use chrono::Utc;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+use crate::common::AppState;
+use crate::jobs::models::JobEditor;
+use crate::messages::models::WebSocketMessage;
+
+#[derive(Clone, Debug)]
+struct EditorSession {
+    connection_id: String,
+    editor: JobEditor,
+}
+
+/// Open job editors, keyed by job ID
+#[derive(Clone, Default)]
+pub struct JobEditors {
+    jobs: Arc<RwLock<HashMap<String, Vec<EditorSession>>>>,
+}
+
+/// One entry per person, earliest first, however many tabs they have open
+fn distinct_editors(sessions: &[EditorSession]) -> Vec<JobEditor> {
+    let mut editors: Vec<JobEditor> = Vec::new();
+    for session in sessions {
+        if !editors.iter().any(|e| e.user_id == session.editor.user_id) {
+            editors.push(session.editor.clone());
+        }
+    }
+    editors.sort_by(|a, b| a.since.cmp(&b.since));
+    editors
+}
+
+impl JobEditors {
+    /// Record that a connection opened the job's editor; returns everyone now editing it
+    pub async fn join(
+        &self,
+        job_id: &str,
+        connection_id: &str,
+        user_id: &str,
+        user_name: &str,
+    ) -> Vec<JobEditor> {
+        let mut jobs = self.jobs.write().await;
+        let sessions = jobs.entry(job_id.to_string()).or_default();
+        if !sessions.iter().any(|s| s.connection_id == connection_id) {
+            sessions.push(EditorSession {
+                connection_id: connection_id.to_string(),
+                editor: JobEditor {
+                    user_id: user_id.to_string(),
+                    user_name: user_name.to_string(),
+                    since: Utc::now().to_rfc3339(),
+                },
+            });
+        }
+        distinct_editors(sessions)
+    }
+
+    /// Record that a connection closed the job's editor; returns the remaining editors if
+    /// it was open
+    pub async fn leave(&self, job_id: &str, connection_id: &str) -> Option<Vec<JobEditor>> {
+        let mut jobs = self.jobs.write().await;
+        let sessions = jobs.get_mut(job_id)?;
+        let before = sessions.len();
+        sessions.retain(|s| s.connection_id != connection_id);
+        if sessions.len() == before {
+            return None;
+        }
+        let remaining = distinct_editors(sessions);
+        if sessions.is_empty() {
+            jobs.remove(job_id);
+        }
+        Some(remaining)
+    }
+
+    /// Close every editor a connection had open; returns each affected job with its
+    /// remaining editors
+    pub async fn leave_all(&self, connection_id: &str) -> Vec<(String, Vec<JobEditor>)> {
+        let mut jobs = self.jobs.write().await;
+        let mut affected = Vec::new();
+        for (job_id, sessions) in jobs.iter_mut() {
+            let before = sessions.len();
+            sessions.retain(|s| s.connection_id != connection_id);
+            if sessions.len() != before {
+                affected.push((job_id.clone(), distinct_editors(sessions)));
+            }
+        }
+        jobs.retain(|_, sessions| !sessions.is_empty());
+        affected
+    }
+
+    /// Everyone editing the job
+    pub async fn editors(&self, job_id: &str) -> Vec<JobEditor> {
+        self.jobs
+            .read()
+            .await
+            .get(job_id)
+            .map(|sessions| distinct_editors(sessions))
+            .unwrap_or_default()
+    }
+}
+
+/// Send an event to everyone editing the job, except `skip_user_id`
+pub async fn notify_editors(
+    state: &AppState,
+    job_id: &str,
+    skip_user_id: Option<&str>,
+    message: WebSocketMessage,
+) {
+    let recipients: Vec<String> = state
+        .job_editors
+        .editors(job_id)
+        .await
+        .into_iter()
+        .map(|e| e.user_id)
+        .filter(|id| Some(id.as_str()) != skip_user_id)
+        .collect();
+    state
+        .connection_manager
+        .broadcast_to_users(&recipients, message)
+        .await;
+}
+
+/// Tell everyone editing the job who is there now
+pub async fn broadcast_editors(state: &AppState, job_id: &str, editors: Vec<JobEditor>) {
+    let recipients: Vec<String> = editors.iter().map(|e| e.user_id.clone()).collect();
+    state
+        .connection_manager
+        .broadcast_to_users(
+            &recipients,
+            WebSocketMessage::JobEditorsChanged {
+                job_id: job_id.to_string(),
+                editors,
+            },
+        )
+        .await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_join_and_leave_track_people_not_tabs() {
+        let editors = JobEditors::default();
+        editors.join("J_1", "c1", "u1", "Ana").await;
+        editors.join("J_1", "c2", "u1", "Ana").await;
+        let all = editors.join("J_1", "c3", "u2", "Ben").await;
+        assert_eq!(all.len(), 2);
+
+        // Ana still has a tab open
+        let remaining = editors.leave("J_1", "c1").await.unwrap();
+        assert_eq!(remaining.len(), 2);
+        assert!(editors.leave("J_1", "c1").await.is_none());
+
+        let affected = editors.leave_all("c2").await;
+        assert_eq!(affected.len(), 1);
+        assert_eq!(affected[0].1.len(), 1);
+        assert_eq!(affected[0].1[0].user_id, "u2");
+
+        editors.leave("J_1", "c3").await;
+        assert!(editors.editors("J_1").await.is_empty());
+        assert!(editors.jobs.read().await.is_empty());
+    }
+}
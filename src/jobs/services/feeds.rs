@@ -0,0 +1,277 @@
+// src/jobs/services/feeds.rs
+//! RSS feeds of published jobs
+//!
+//! Feeds are rendered on first request and cached until a job is published, edited,
+//! archived or deleted, with a short TTL as a backstop for status changes made outside
+//! the admin handlers.
+
+use chrono::{DateTime, NaiveDateTime, Utc};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+use crate::jobs::models::Job;
+
+/// Jobs listed per feed, newest first
+pub const FEED_ITEM_LIMIT: i64 = 50;
+
+/// Longest a rendered feed is served before being rebuilt
+const FEED_TTL_SECONDS: i64 = 600;
+
+/// A rendered feed with the validators used for conditional requests
+#[derive(Clone, Debug)]
+pub struct CachedFeed {
+    pub body: String,
+    pub etag: String,
+    pub last_modified: DateTime<Utc>,
+    rendered_at: DateTime<Utc>,
+}
+
+impl CachedFeed {
+    pub fn new(body: String, last_modified: DateTime<Utc>) -> Self {
+        let mut hasher = DefaultHasher::new();
+        body.hash(&mut hasher);
+        Self {
+            etag: format!("\"{:x}\"", hasher.finish()),
+            body,
+            last_modified,
+            rendered_at: Utc::now(),
+        }
+    }
+
+    fn is_fresh(&self) -> bool {
+        (Utc::now() - self.rendered_at).num_seconds() < FEED_TTL_SECONDS
+    }
+}
+
+/// Rendered feeds keyed by scope (`all` or `company:<id>`)
+#[derive(Clone, Default)]
+pub struct FeedCache {
+    feeds: Arc<RwLock<HashMap<String, CachedFeed>>>,
+}
+
+impl FeedCache {
+    pub async fn get(&self, key: &str) -> Option<CachedFeed> {
+        self.feeds
+            .read()
+            .await
+            .get(key)
+            .filter(|feed| feed.is_fresh())
+            .cloned()
+    }
+
+    pub async fn insert(&self, key: &str, feed: CachedFeed) {
+        self.feeds.write().await.insert(key.to_string(), feed);
+    }
+
+    /// Drop every cached feed; called whenever a job's listing may have changed
+    pub async fn invalidate(&self) {
+        self.feeds.write().await.clear();
+    }
+}
+
+/// Channel-level details of a feed
+pub struct FeedChannel<'a> {
+    pub title: &'a str,
+    pub description: &'a str,
+    /// Public page the feed mirrors
+    pub link: &'a str,
+    /// URL the feed itself is served from
+    pub self_url: &'a str,
+}
+
+/// Parse a stored timestamp, either SQLite's `YYYY-MM-DD HH:MM:SS` or RFC3339
+pub fn parse_timestamp(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|dt| dt.with_timezone(&Utc))
+        .ok()
+        .or_else(|| {
+            NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S")
+                .ok()
+                .map(|dt| dt.and_utc())
+        })
+}
+
+/// When a job was published, falling back to its creation time
+pub fn job_published_at(job: &Job) -> Option<DateTime<Utc>> {
+    job.published_at
+        .as_deref()
+        .or(job.created_at.as_deref())
+        .and_then(parse_timestamp)
+}
+
+/// Most recent change across the feed's jobs, used for `Last-Modified`
+pub fn last_modified(jobs: &[Job]) -> DateTime<Utc> {
+    jobs.iter()
+        .filter_map(|job| {
+            job.updated_at
+                .as_deref()
+                .and_then(parse_timestamp)
+                .or_else(|| job_published_at(job))
+        })
+        .max()
+        .unwrap_or(DateTime::UNIX_EPOCH)
+}
+
+/// HTTP-date format used by `Last-Modified` and `If-Modified-Since`
+pub fn http_date(at: DateTime<Utc>) -> String {
+    at.format("%a, %d %b %Y %H:%M:%S GMT").to_string()
+}
+
+pub fn parse_http_date(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc2822(value.trim())
+        .ok()
+        .map(|dt| dt.with_timezone(&Utc))
+}
+
+/// Escape text for an XML element or attribute
+pub fn escape_xml(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+fn item_description(job: &Job) -> String {
+    let mut parts = Vec::new();
+    if let Some(summary) = job.summary.as_deref().filter(|s| !s.trim().is_empty()) {
+        parts.push(summary.trim().to_string());
+    }
+    let details: Vec<&str> = [
+        job.company.as_deref(),
+        job.location.as_deref(),
+        job.job_type.as_deref(),
+    ]
+    .into_iter()
+    .flatten()
+    .filter(|s| !s.trim().is_empty())
+    .collect();
+    if !details.is_empty() {
+        parts.push(details.join(" · "));
+    }
+    parts.join("\n\n")
+}
+
+/// Render an RSS 2.0 document; `job_url` maps a job ID to its public page
+pub fn render_rss(channel: &FeedChannel, jobs: &[Job], job_url: impl Fn(&str) -> String) -> String {
+    let mut xml = String::from(r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+    xml.push('\n');
+    xml.push_str(r#"<rss version="2.0" xmlns:atom="http://www.w3.org/2005/Atom">"#);
+    xml.push_str("\n<channel>\n");
+    xml.push_str(&format!("<title>{}</title>\n", escape_xml(channel.title)));
+    xml.push_str(&format!("<link>{}</link>\n", escape_xml(channel.link)));
+    xml.push_str(&format!(
+        "<description>{}</description>\n",
+        escape_xml(channel.description)
+    ));
+    xml.push_str(&format!(
+        "<atom:link href=\"{}\" rel=\"self\" type=\"application/rss+xml\"/>\n",
+        escape_xml(channel.self_url)
+    ));
+    if !jobs.is_empty() {
+        xml.push_str(&format!(
+            "<lastBuildDate>{}</lastBuildDate>\n",
+            last_modified(jobs).to_rfc2822()
+        ));
+    }
+
+    for job in jobs {
+        let link = job_url(&job.id);
+        xml.push_str("<item>\n");
+        xml.push_str(&format!("<title>{}</title>\n", escape_xml(&job.title)));
+        xml.push_str(&format!("<link>{}</link>\n", escape_xml(&link)));
+        xml.push_str(&format!(
+            "<guid isPermaLink=\"false\">{}</guid>\n",
+            escape_xml(&job.id)
+        ));
+        xml.push_str(&format!(
+            "<description>{}</description>\n",
+            escape_xml(&item_description(job))
+        ));
+        if let Some(company) = job.company.as_deref() {
+            xml.push_str(&format!("<category>{}</category>\n", escape_xml(company)));
+        }
+        if let Some(published) = job_published_at(job) {
+            xml.push_str(&format!("<pubDate>{}</pubDate>\n", published.to_rfc2822()));
+        }
+        xml.push_str("</item>\n");
+    }
+
+    xml.push_str("</channel>\n</rss>\n");
+    xml
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn job(id: &str, title: &str, published_at: &str) -> Job {
+        Job {
+            id: id.to_string(),
+            title: title.to_string(),
+            summary: Some("Cook & plate".to_string()),
+            description: None,
+            location: Some("Lyon".to_string()),
+            company: Some("Bistro <Co>".to_string()),
+            company_id: Some("CMP_1".to_string()),
+            company_logo_url: None,
+            job_image_url: None,
+            salary_min: None,
+            salary_max: None,
+            job_type: None,
+            experience_level: None,
+            requirements: None,
+            benefits: None,
+            status: Some("active".to_string()),
+            is_featured: Some(0),
+            created_at: Some("2026-01-01 09:00:00".to_string()),
+            updated_at: Some(published_at.to_string()),
+            published_at: Some(published_at.to_string()),
+            application_deadline: None,
+        }
+    }
+
+    #[test]
+    fn test_render_rss_escapes_and_dates_items() {
+        let jobs = vec![
+            job("JOB_2", "Sous Chef", "2026-03-02 10:00:00"),
+            job("JOB_1", "Line Cook", "2026-03-01 10:00:00"),
+        ];
+        let channel = FeedChannel {
+            title: "Jobs",
+            description: "Open roles",
+            link: "https://example.com/jobs",
+            self_url: "https://api.example.com/feeds/jobs.rss",
+        };
+        let xml = render_rss(&channel, &jobs, |id| {
+            format!("https://example.com/jobs/{}", id)
+        });
+
+        assert!(xml.contains("<title>Sous Chef</title>"));
+        assert!(xml.contains("<link>https://example.com/jobs/JOB_1</link>"));
+        assert!(xml.contains("Cook &amp; plate"));
+        assert!(xml.contains("<category>Bistro &lt;Co&gt;</category>"));
+        assert!(xml.contains("<pubDate>Mon, 2 Mar 2026 10:00:00 +0000</pubDate>"));
+        assert!(xml.contains("<lastBuildDate>Mon, 2 Mar 2026 10:00:00 +0000</lastBuildDate>"));
+        assert_eq!(
+            http_date(last_modified(&jobs)),
+            "Mon, 02 Mar 2026 10:00:00 GMT"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_cache_invalidation_drops_feeds() {
+        let cache = FeedCache::default();
+        cache
+            .insert("all", CachedFeed::new("<rss/>".to_string(), Utc::now()))
+            .await;
+        assert!(cache.get("all").await.is_some());
+
+        cache.invalidate().await;
+        assert!(cache.get("all").await.is_none());
+    }
+}
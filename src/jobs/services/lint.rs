@@ -0,0 +1,446 @@
+// src/jobs/services/lint.rs
+//! Deterministic quality checks for job descriptions
+//!
+//! Unlike the AI bias check, every rule here is fixed and explainable: description length,
+//! salary, requirements, buzzwords, reading level and broken links. Each check counts equally
+//! towards the score. With `job_lint_enforcement` set to `block`, a job cannot be published
+//! while its score is below `job_lint_min_score` (default 100, i.e. every check must pass).
+
+use std::collections::BTreeSet;
+use std::time::Duration;
+
+use futures::future::join_all;
+use regex::Regex;
+
+use crate::common::{ApiError, ErrorCode};
+use crate::jobs::models::{JobLintCheck, JobLintReport, JobLintRequest};
+use crate::services::SettingsService;
+
+const MIN_DESCRIPTION_WORDS: usize = 150;
+const MAX_DESCRIPTION_WORDS: usize = 1200;
+
+/// More than this many buzzwords reads as filler
+const MAX_BUZZWORDS: usize = 3;
+
+/// Flesch-Kincaid grade; above this the text is hard going for many readers
+const MAX_GRADE_LEVEL: f64 = 12.0;
+
+/// Only the first links are checked, so a long description cannot stall publishing
+const MAX_LINKS_CHECKED: usize = 10;
+const LINK_TIMEOUT: Duration = Duration::from_secs(5);
+
+const BUZZWORDS: &[&str] = &[
+    "rockstar",
+    "rock star",
+    "ninja",
+    "guru",
+    "wizard",
+    "unicorn",
+    "synergy",
+    "hustle",
+    "go-getter",
+    "self-starter",
+    "fast-paced",
+    "work hard play hard",
+    "thought leader",
+    "disruptive",
+    "world-class",
+    "best-in-class",
+    "game changer",
+    "game-changer",
+    "10x",
+    "crushing it",
+    "think outside the box",
+];
+
+pub struct LintSettings {
+    pub enforced: bool,
+    pub min_score: u32,
+}
+
+pub async fn settings(settings_service: &SettingsService) -> LintSettings {
+    let enforced = settings_service
+        .get_setting("job_lint_enforcement")
+        .await
+        .ok()
+        .flatten()
+        .is_some_and(|v| v.trim() == "block");
+    let min_score = settings_service
+        .get_setting("job_lint_min_score")
+        .await
+        .ok()
+        .flatten()
+        .and_then(|v| v.trim().parse::<u32>().ok())
+        .unwrap_or(100)
+        .min(100);
+    LintSettings {
+        enforced,
+        min_score,
+    }
+}
+
+fn words(text: &str) -> Vec<&str> {
+    text.split(|c: char| !(c.is_alphanumeric() || c == '\'' || c == '-'))
+        .filter(|w| w.chars().any(char::is_alphanumeric))
+        .collect()
+}
+
+/// Vowel groups, less a silent final e; close enough for a grade estimate
+fn syllables(word: &str) -> usize {
+    let word = word.to_lowercase();
+    let mut count = 0;
+    let mut previous_vowel = false;
+    for c in word.chars() {
+        let vowel = "aeiouy".contains(c);
+        if vowel && !previous_vowel {
+            count += 1;
+        }
+        previous_vowel = vowel;
+    }
+    if word.ends_with('e') && !word.ends_with("le") && count > 1 {
+        count -= 1;
+    }
+    count.max(1)
+}
+
+fn link_regex() -> Regex {
+    Regex::new(r#"https?://[^\s<>()\[\]"'`]+"#).expect("valid link pattern")
+}
+
+/// Text without links, markdown markers or code, as a reader sees it
+fn prose(description: &str) -> String {
+    link_regex()
+        .replace_all(description, " ")
+        .replace(['#', '*', '_', '`', '>', '|'], " ")
+}
+
+/// Flesch-Kincaid grade level
+///
+/// Line breaks end sentences too, so bullet lists are not read as one long sentence.
+pub fn grade_level(text: &str) -> Option<f64> {
+    let text = prose(text);
+    let sentences: Vec<&str> = text
+        .split(['.', '!', '?', '\n'])
+        .filter(|s| !words(s).is_empty())
+        .collect();
+    let words: Vec<&str> = sentences.iter().flat_map(|s| words(s)).collect();
+    if words.is_empty() {
+        return None;
+    }
+    let syllables: usize = words.iter().map(|w| syllables(w)).sum();
+    let grade = 0.39 * (words.len() as f64 / sentences.len() as f64)
+        + 11.8 * (syllables as f64 / words.len() as f64)
+        - 15.59;
+    Some((grade.max(0.0) * 10.0).round() / 10.0)
+}
+
+/// Buzzwords used in the text, each listed once
+pub fn buzzwords(text: &str) -> Vec<&'static str> {
+    let normalized = format!(" {} ", words(&text.to_lowercase()).join(" "));
+    BUZZWORDS
+        .iter()
+        .copied()
+        .filter(|b| normalized.contains(&format!(" {} ", b)))
+        .collect()
+}
+
+/// Distinct http(s) links in a description, trailing punctuation removed
+pub fn links(text: &str) -> Vec<String> {
+    let links: BTreeSet<String> = link_regex()
+        .find_iter(text)
+        .map(|m| {
+            m.as_str()
+                .trim_end_matches(['.', ',', ';', ':', '!', '?'])
+                .to_string()
+        })
+        .collect();
+    links.into_iter().collect()
+}
+
+fn check(id: &'static str, passed: bool, message: String) -> JobLintCheck {
+    JobLintCheck {
+        id,
+        passed,
+        message,
+    }
+}
+
+/// Score a job given the links in it that could not be reached
+pub fn report(
+    job: &JobLintRequest,
+    broken_links: &[String],
+    settings: &LintSettings,
+) -> JobLintReport {
+    let description = job.description.as_deref().unwrap_or("").trim();
+    let word_count = words(&prose(description)).len();
+
+    let length = if word_count < MIN_DESCRIPTION_WORDS {
+        check(
+            "description_length",
+            false,
+            format!(
+                "Description has {} words; aim for at least {}",
+                word_count, MIN_DESCRIPTION_WORDS
+            ),
+        )
+    } else if word_count > MAX_DESCRIPTION_WORDS {
+        check(
+            "description_length",
+            false,
+            format!(
+                "Description has {} words; keep it under {}",
+                word_count, MAX_DESCRIPTION_WORDS
+            ),
+        )
+    } else {
+        check(
+            "description_length",
+            true,
+            format!("Description has {} words", word_count),
+        )
+    };
+
+    let has_salary = job.salary_min.is_some() || job.salary_max.is_some();
+    let salary = check(
+        "salary",
+        has_salary,
+        if has_salary {
+            "A salary range is listed".to_string()
+        } else {
+            "No salary range; postings with pay ranges get more applicants".to_string()
+        },
+    );
+
+    let requirement_count = job
+        .requirements
+        .iter()
+        .filter(|r| !r.trim().is_empty())
+        .count();
+    let requirements = check(
+        "requirements",
+        requirement_count > 0,
+        if requirement_count > 0 {
+            format!("{} requirements listed", requirement_count)
+        } else {
+            "No requirements listed".to_string()
+        },
+    );
+
+    let found = buzzwords(description);
+    let buzzword_check = check(
+        "buzzwords",
+        found.len() <= MAX_BUZZWORDS,
+        if found.is_empty() {
+            "No buzzwords found".to_string()
+        } else {
+            format!(
+                "{} buzzwords found (at most {}): {}",
+                found.len(),
+                MAX_BUZZWORDS,
+                found.join(", ")
+            )
+        },
+    );
+
+    let reading_level = match grade_level(description) {
+        Some(grade) => check(
+            "reading_level",
+            grade <= MAX_GRADE_LEVEL,
+            format!(
+                "Reading level is grade {:.1} (at most {:.0})",
+                grade, MAX_GRADE_LEVEL
+            ),
+        ),
+        None => check(
+            "reading_level",
+            false,
+            "No description to assess".to_string(),
+        ),
+    };
+
+    let link_check = check(
+        "broken_links",
+        broken_links.is_empty(),
+        if broken_links.is_empty() {
+            "No broken links".to_string()
+        } else {
+            format!("Unreachable links: {}", broken_links.join(", "))
+        },
+    );
+
+    let checks = vec![
+        length,
+        salary,
+        requirements,
+        buzzword_check,
+        reading_level,
+        link_check,
+    ];
+    let passed = checks.iter().filter(|c| c.passed).count();
+    let score = (passed * 100 / checks.len()) as u32;
+
+    JobLintReport {
+        score,
+        min_score: settings.min_score,
+        enforced: settings.enforced,
+        publishable: !settings.enforced || score >= settings.min_score,
+        checks,
+    }
+}
+
+/// Links that fail to load or answer with an error status
+async fn broken_links(description: &str) -> Vec<String> {
+    let client = reqwest::Client::builder()
+        .timeout(LINK_TIMEOUT)
+        .redirect(reqwest::redirect::Policy::limited(5))
+        .build()
+        .unwrap_or_default();
+
+    let checks = links(description)
+        .into_iter()
+        .take(MAX_LINKS_CHECKED)
+        .map(|link| {
+            let client = client.clone();
+            async move {
+                let status = match client.head(&link).send().await {
+                    // Some servers refuse HEAD; ask again properly before calling it broken
+                    Ok(r) if r.status() == reqwest::StatusCode::METHOD_NOT_ALLOWED => {
+                        client.get(&link).send().await.map(|r| r.status())
+                    }
+                    other => other.map(|r| r.status()),
+                };
+                let ok = status.is_ok_and(|s| !s.is_client_error() && !s.is_server_error());
+                (!ok).then_some(link)
+            }
+        });
+
+    join_all(checks).await.into_iter().flatten().collect()
+}
+
+/// Run every check, including fetching the links in the description
+pub async fn lint(job: &JobLintRequest, settings: &LintSettings) -> JobLintReport {
+    let broken = broken_links(job.description.as_deref().unwrap_or("")).await;
+    report(job, &broken, settings)
+}
+
+/// Refuse to publish a job that does not clear the lint, when enforcement is on
+pub async fn ensure_publishable(
+    settings_service: &SettingsService,
+    job: &JobLintRequest,
+) -> Result<(), ApiError> {
+    let settings = settings(settings_service).await;
+    if !settings.enforced {
+        return Ok(());
+    }
+    let report = lint(job, &settings).await;
+    if !report.publishable {
+        let failing: Vec<&str> = report
+            .checks
+            .iter()
+            .filter(|c| !c.passed)
+            .map(|c| c.id)
+            .collect();
+        return Err(ApiError::Coded(
+            ErrorCode::JobLintFailed,
+            format!(
+                "Job description scores {} but {} is needed to publish; failing checks: {}",
+                report.score,
+                report.min_score,
+                failing.join(", ")
+            ),
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings(enforced: bool) -> LintSettings {
+        LintSettings {
+            enforced,
+            min_score: 100,
+        }
+    }
+
+    fn long_description() -> String {
+        "We are hiring a backend engineer to build our payments service. You will write clear code and review it with the team.\n"
+            .repeat(8)
+    }
+
+    #[test]
+    fn test_syllables() {
+        assert_eq!(syllables("cat"), 1);
+        assert_eq!(syllables("make"), 1);
+        assert_eq!(syllables("table"), 2);
+        assert_eq!(syllables("engineering"), 4);
+    }
+
+    #[test]
+    fn test_grade_level() {
+        assert!(grade_level("The cat sat on the mat. It was warm.").unwrap() < 3.0);
+        let dense = "Comprehensive organizational responsibilities necessitate extraordinarily sophisticated interdisciplinary communication capabilities";
+        assert!(grade_level(dense).unwrap() > MAX_GRADE_LEVEL);
+        assert_eq!(grade_level("   "), None);
+    }
+
+    #[test]
+    fn test_buzzwords() {
+        let found = buzzwords("Join our fast-paced team of Rockstar ninjas and ninja gurus");
+        assert_eq!(found, vec!["rockstar", "ninja", "fast-paced"]);
+        assert!(buzzwords("A steady, well-run team").is_empty());
+    }
+
+    #[test]
+    fn test_links() {
+        let found = links("See https://example.com/jobs. Or [apply](https://example.com/apply), https://example.com/jobs");
+        assert_eq!(
+            found,
+            vec!["https://example.com/apply", "https://example.com/jobs"]
+        );
+    }
+
+    #[test]
+    fn test_complete_job_scores_full_marks() {
+        let job = JobLintRequest {
+            description: Some(long_description()),
+            salary_min: Some(90_000),
+            salary_max: None,
+            requirements: vec!["Rust".to_string()],
+        };
+        let report = report(&job, &[], &settings(true));
+        assert_eq!(report.score, 100);
+        assert!(report.publishable);
+    }
+
+    #[test]
+    fn test_failing_checks_block_only_when_enforced() {
+        let job = JobLintRequest {
+            description: Some("Short.".to_string()),
+            ..Default::default()
+        };
+        let broken = vec!["https://example.com/gone".to_string()];
+
+        let enforced = report(&job, &broken, &settings(true));
+        let failing: Vec<&str> = enforced
+            .checks
+            .iter()
+            .filter(|c| !c.passed)
+            .map(|c| c.id)
+            .collect();
+        assert_eq!(
+            failing,
+            vec![
+                "description_length",
+                "salary",
+                "requirements",
+                "broken_links"
+            ]
+        );
+        assert_eq!(enforced.score, 33);
+        assert!(!enforced.publishable);
+
+        assert!(report(&job, &broken, &settings(false)).publishable);
+    }
+}
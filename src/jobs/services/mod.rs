@@ -1,6 +1,18 @@
 // src/jobs/services/mod.rs
 //! Job-related services
 
+pub mod capacity;
 pub mod content_versions;
+pub mod deadlines;
+pub mod drafts;
+pub mod duplicates;
+pub mod editing;
+pub mod feeds;
+pub mod lint;
+pub mod similar;
+pub mod trash;
 
 pub use content_versions::ContentVersionsService;
+pub use editing::JobEditors;
+pub use feeds::FeedCache;
+pub use similar::SimilarJobsCache;
@@ -0,0 +1,141 @@
+// src/jobs/services/similar.rs
+//! Related postings for the public job page
+//!
+//! A job is compared with other open jobs by the same rules that score candidate
+//! recommendations: its short requirements act as skills, and its title, type, level,
+//! company and location stand in for the candidate's history. Only the ranked IDs are
+//! cached per job, so a posting that closes drops out at once while the ranking is
+//! refreshed on a TTL.
+
+use chrono::{DateTime, Utc};
+use sqlx::SqlitePool;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+use crate::candidates::recommendations::{score_job, CandidateSignals, JobFacts};
+use crate::candidates::requirements_match::{keywords, parse_requirements};
+
+/// Most similar jobs kept per job
+pub const MAX_SIMILAR_JOBS: usize = 20;
+
+/// Newest open jobs compared against
+const COMPARISON_POOL_SIZE: i64 = 500;
+
+/// Requirements with more keywords than this are sentences, not skills
+const MAX_SKILL_KEYWORDS: usize = 3;
+
+/// Longest a ranking is served before being recomputed
+const SIMILAR_TTL_SECONDS: i64 = 900;
+
+#[derive(Clone, Debug)]
+struct CachedRanking {
+    /// Job IDs with their scores, best first
+    ranked: Vec<(String, i64)>,
+    computed_at: DateTime<Utc>,
+}
+
+impl CachedRanking {
+    fn is_fresh(&self) -> bool {
+        (Utc::now() - self.computed_at).num_seconds() < SIMILAR_TTL_SECONDS
+    }
+}
+
+/// Similar-job rankings keyed by job ID
+#[derive(Clone, Default)]
+pub struct SimilarJobsCache {
+    rankings: Arc<RwLock<HashMap<String, CachedRanking>>>,
+}
+
+impl SimilarJobsCache {
+    async fn get(&self, job_id: &str) -> Option<Vec<(String, i64)>> {
+        self.rankings
+            .read()
+            .await
+            .get(job_id)
+            .filter(|ranking| ranking.is_fresh())
+            .map(|ranking| ranking.ranked.clone())
+    }
+
+    async fn insert(&self, job_id: &str, ranked: Vec<(String, i64)>) {
+        let mut rankings = self.rankings.write().await;
+        rankings.retain(|_, ranking| ranking.is_fresh());
+        rankings.insert(
+            job_id.to_string(),
+            CachedRanking {
+                ranked,
+                computed_at: Utc::now(),
+            },
+        );
+    }
+}
+
+/// What the scoring rules look for when `job` is the reference
+pub fn job_signals(job: &JobFacts) -> CandidateSignals {
+    let mut signals = CandidateSignals::default();
+    signals.add_skills(
+        parse_requirements(job.requirements.as_deref())
+            .into_iter()
+            .filter(|item| keywords(item).len() <= MAX_SKILL_KEYWORDS),
+    );
+    signals.learn_from(job);
+    signals
+}
+
+/// Rank `others` by similarity to `job`, dropping `job` itself and anything unrelated
+pub fn rank_similar(job: &JobFacts, others: &[JobFacts]) -> Vec<(String, i64)> {
+    let signals = job_signals(job);
+    let mut ranked: Vec<(String, i64)> = others
+        .iter()
+        .filter(|other| other.id != job.id)
+        .map(|other| (other.id.clone(), score_job(&signals, other).score))
+        .filter(|(_, score)| *score > 0)
+        .collect();
+    // Stable, so ties keep the newest-first order of `others`
+    ranked.sort_by_key(|(_, score)| std::cmp::Reverse(*score));
+    ranked.truncate(MAX_SIMILAR_JOBS);
+    ranked
+}
+
+/// Ranked similar jobs for an open job, from the cache when fresh; `None` if the job isn't open
+pub async fn similar_job_ids(
+    db: &SqlitePool,
+    cache: &SimilarJobsCache,
+    job_id: &str,
+) -> Result<Option<Vec<(String, i64)>>, sqlx::Error> {
+    let job = sqlx::query_as::<_, JobFacts>(
+        r#"
+        SELECT id, title, company, location, job_type, experience_level, requirements, description
+        FROM jobs
+        WHERE id = ? AND status = 'active' AND deleted_at IS NULL
+        "#,
+    )
+    .bind(job_id)
+    .fetch_optional(db)
+    .await?;
+    let Some(job) = job else {
+        return Ok(None);
+    };
+
+    if let Some(ranked) = cache.get(job_id).await {
+        return Ok(Some(ranked));
+    }
+
+    let others = sqlx::query_as::<_, JobFacts>(
+        r#"
+        SELECT id, title, company, location, job_type, experience_level, requirements, description
+        FROM jobs
+        WHERE status = 'active' AND deleted_at IS NULL AND id != ?
+        ORDER BY COALESCE(published_at, created_at) DESC
+        LIMIT ?
+        "#,
+    )
+    .bind(job_id)
+    .bind(COMPARISON_POOL_SIZE)
+    .fetch_all(db)
+    .await?;
+
+    let ranked = rank_similar(&job, &others);
+    cache.insert(job_id, ranked.clone()).await;
+    Ok(Some(ranked))
+}
@@ -0,0 +1,182 @@
+// src/jobs/services/trash.rs
+//! Trash for deleted jobs
+//!
+//! Deleting a job sets `deleted_at`, which hides it from every listing, feed and search.
+//! It can be restored for `TRASH_RETENTION_DAYS` days; after that the purge task removes
+//! it with its job-only records. A job that received applications is never purged, so
+//! candidates' application history keeps pointing at a real job.
+
+use sqlx::SqlitePool;
+use tracing::{debug, info};
+
+use crate::jobs::models::TrashedJob;
+
+/// Days a deleted job stays restorable
+pub const TRASH_RETENTION_DAYS: i64 = 30;
+
+const PURGE_CHECK_INTERVAL_SECS: u64 = 6 * 60 * 60;
+
+/// Tables whose rows only describe a job and go with it when it is purged
+const JOB_OWNED_TABLES: &[&str] = &[
+    "job_status_history",
+    "job_views",
+    "job_social_images",
+    "job_short_link_clicks",
+    "job_short_links",
+    "scheduled_social_posts",
+    "job_promotions",
+    "job_content_versions",
+    "job_recruiters",
+    "knockout_rules",
+    "saved_jobs",
+];
+
+/// SQLite modifier for the retention window, e.g. `+30 days`
+fn retention_modifier() -> String {
+    format!("+{} days", TRASH_RETENTION_DAYS)
+}
+
+/// Jobs in the trash, most recently deleted first
+pub async fn list_trashed(pool: &SqlitePool) -> Result<Vec<TrashedJob>, sqlx::Error> {
+    sqlx::query_as::<_, TrashedJob>(
+        r#"
+        SELECT id, title, company, status, deleted_at, deleted_by,
+               datetime(deleted_at, ?) AS purge_at
+        FROM jobs
+        WHERE deleted_at IS NOT NULL
+        ORDER BY datetime(deleted_at) DESC
+        "#,
+    )
+    .bind(retention_modifier())
+    .fetch_all(pool)
+    .await
+}
+
+/// Permanently remove trashed jobs past the retention window that never had applications.
+/// Returns how many were removed.
+pub async fn purge_expired(pool: &SqlitePool) -> Result<u64, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    let expired: Vec<String> = sqlx::query_scalar(
+        r#"
+        SELECT id FROM jobs j
+        WHERE j.deleted_at IS NOT NULL
+          AND datetime(j.deleted_at, ?) <= datetime('now')
+          AND NOT EXISTS (SELECT 1 FROM applications a WHERE a.job_id = j.id)
+        "#,
+    )
+    .bind(retention_modifier())
+    .fetch_all(&mut *tx)
+    .await?;
+
+    for job_id in &expired {
+        for table in JOB_OWNED_TABLES {
+            sqlx::query(&format!("DELETE FROM {} WHERE job_id = ?", table))
+                .bind(job_id)
+                .execute(&mut *tx)
+                .await?;
+        }
+        sqlx::query("DELETE FROM jobs WHERE id = ?")
+            .bind(job_id)
+            .execute(&mut *tx)
+            .await?;
+    }
+
+    tx.commit().await?;
+    Ok(expired.len() as u64)
+}
+
+/// Periodically purge jobs that have been in the trash longer than the retention window
+pub fn start_trash_purge_task(pool: SqlitePool) {
+    tokio::spawn(async move {
+        loop {
+            if !crate::common::shutdown::sleep(std::time::Duration::from_secs(
+                PURGE_CHECK_INTERVAL_SECS,
+            ))
+            .await
+            {
+                break;
+            }
+            let _running = crate::common::shutdown::track();
+
+            match purge_expired(&pool).await {
+                Ok(0) => {}
+                Ok(purged) => info!(purged, "Purged expired jobs from trash"),
+                Err(e) => debug!(error = %e, "Skipped job trash purge run"),
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn setup_test_db() -> SqlitePool {
+        let pool = SqlitePool::connect(":memory:").await.unwrap();
+
+        sqlx::query(
+            r#"CREATE TABLE jobs (
+                id TEXT PRIMARY KEY, title TEXT NOT NULL, company TEXT NOT NULL, status TEXT,
+                deleted_at TEXT, deleted_by TEXT
+            )"#,
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query("CREATE TABLE applications (id TEXT PRIMARY KEY, job_id TEXT NOT NULL)")
+            .execute(&pool)
+            .await
+            .unwrap();
+        for table in JOB_OWNED_TABLES {
+            sqlx::query(&format!("CREATE TABLE {} (job_id TEXT NOT NULL)", table))
+                .execute(&pool)
+                .await
+                .unwrap();
+        }
+
+        for statement in [
+            "INSERT INTO jobs VALUES ('live', 'Live', 'Acme', 'active', NULL, NULL)",
+            "INSERT INTO jobs VALUES ('recent', 'Recent', 'Acme', 'active', datetime('now', '-1 day'), 'admin')",
+            "INSERT INTO jobs VALUES ('expired', 'Expired', 'Acme', 'active', datetime('now', '-31 days'), 'admin')",
+            "INSERT INTO jobs VALUES ('applied', 'Applied', 'Acme', 'closed', datetime('now', '-60 days'), 'admin')",
+            "INSERT INTO applications VALUES ('app', 'applied')",
+            "INSERT INTO job_views VALUES ('expired'), ('recent')",
+        ] {
+            sqlx::query(statement).execute(&pool).await.unwrap();
+        }
+
+        pool
+    }
+
+    #[tokio::test]
+    async fn test_list_trashed_excludes_live_jobs() {
+        let pool = setup_test_db().await;
+
+        let trashed = list_trashed(&pool).await.unwrap();
+        let ids: Vec<&str> = trashed.iter().map(|j| j.id.as_str()).collect();
+        assert_eq!(ids, vec!["recent", "expired", "applied"]);
+    }
+
+    #[tokio::test]
+    async fn test_purge_removes_only_expired_jobs_without_applications() {
+        let pool = setup_test_db().await;
+
+        assert_eq!(purge_expired(&pool).await.unwrap(), 1);
+
+        let remaining: Vec<String> = sqlx::query_scalar("SELECT id FROM jobs ORDER BY id")
+            .fetch_all(&pool)
+            .await
+            .unwrap();
+        assert_eq!(remaining, vec!["applied", "live", "recent"]);
+
+        let views: Vec<String> = sqlx::query_scalar("SELECT job_id FROM job_views")
+            .fetch_all(&pool)
+            .await
+            .unwrap();
+        assert_eq!(views, vec!["recent"]);
+
+        // A second run has nothing left to do
+        assert_eq!(purge_expired(&pool).await.unwrap(), 0);
+    }
+}
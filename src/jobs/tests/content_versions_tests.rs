@@ -0,0 +1,64 @@
+// src/jobs/tests/content_versions_tests.rs
+
+#[cfg(test)]
+mod tests {
+    use crate::jobs::models::*;
+    use crate::jobs::services::content_versions::*;
+
+    fn version(component: &str, number: i32, source: &str, active: bool) -> ContentVersion {
+        ContentVersion {
+            id: format!("{}-{}", component, number),
+            job_id: "J1".to_string(),
+            component_type: component.to_string(),
+            content: "content".to_string(),
+            prompt_used: None,
+            is_active: active as i32,
+            version_number: number,
+            created_by: Some("U1".to_string()),
+            created_at: "2026-10-01T00:00:00Z".to_string(),
+            source: source.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_provenance_distinguishes_ai_and_human_content() {
+        let versions = vec![
+            version("benefits", 1, CONTENT_SOURCE_HUMAN, true),
+            version("description", 1, CONTENT_SOURCE_AI, false),
+            version("description", 2, CONTENT_SOURCE_HUMAN, true),
+            version("title", 1, CONTENT_SOURCE_HUMAN, false),
+            version("title", 2, CONTENT_SOURCE_AI, true),
+            // No active version: nothing is live for this field
+            version("image", 1, CONTENT_SOURCE_AI, false),
+        ];
+
+        let provenance = summarize_provenance(&versions);
+        let origins: Vec<(&str, &str)> = provenance
+            .iter()
+            .map(|p| (p.field.as_str(), p.origin.as_str()))
+            .collect();
+        assert_eq!(
+            origins,
+            vec![
+                ("benefits", "human"),
+                ("description", "human_edited"),
+                ("title", "ai_generated"),
+            ]
+        );
+
+        let description = &provenance[1];
+        assert_eq!(description.active_version_id, "description-2");
+        assert_eq!(
+            (description.ai_versions, description.human_versions),
+            (1, 1)
+        );
+    }
+
+    #[test]
+    fn test_validate_ai_fields() {
+        assert!(validate_ai_fields(&["title".to_string(), "Description".to_string()]).is_ok());
+        assert!(validate_ai_fields(&["salary".to_string()]).is_err());
+        // Summaries are generated on their own and never saved through the job form
+        assert!(validate_ai_fields(&["summary".to_string()]).is_err());
+    }
+}
@@ -0,0 +1,31 @@
+// src/jobs/tests/contract_tests.rs
+
+#[cfg(test)]
+mod tests {
+    use crate::test_support::schema::assert_compatible;
+    use crate::test_support::TestApp;
+
+    #[tokio::test]
+    async fn test_job_response_contract() {
+        let app = TestApp::new().await;
+        let user = app
+            .create_user("viewer@test.example.com", "Vic Viewer")
+            .await;
+        let token = app.token_for(&user).await;
+        let job_id = app.create_job("Platform Engineer").await;
+        sqlx::query(
+            r#"UPDATE jobs SET summary = 'Run the platform', salary_min = 90000, salary_max = 120000,
+                experience_level = 'senior', requirements = '["Rust","SQL"]', benefits = '["Remote"]',
+                application_deadline = '2030-01-01T00:00:00Z'
+            WHERE id = ?"#,
+        )
+        .bind(&job_id)
+        .execute(&app.state.db)
+        .await
+        .unwrap();
+
+        let job = app.get(&format!("/api/jobs/{}", job_id), &token).await;
+        assert!(job.status.is_success(), "{:?}", job.body);
+        assert_compatible("job", &job.body);
+    }
+}
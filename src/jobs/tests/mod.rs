@@ -1,7 +1,19 @@
 // src/jobs/tests/mod.rs
 
+#[cfg(test)]
+mod content_versions_tests;
+
 #[cfg(test)]
 mod handlers_tests;
 
 #[cfg(test)]
 mod validators_tests;
+
+#[cfg(test)]
+mod contract_tests;
+
+#[cfg(test)]
+mod similar_tests;
+
+#[cfg(test)]
+mod trash_tests;
@@ -0,0 +1,76 @@
+// src/jobs/tests/similar_tests.rs
+
+#[cfg(test)]
+mod tests {
+    use crate::candidates::recommendations::JobFacts;
+    use crate::jobs::services::similar::rank_similar;
+    use crate::test_support::TestApp;
+
+    fn job(id: &str, title: &str, requirements: &str) -> JobFacts {
+        JobFacts {
+            id: id.to_string(),
+            title: title.to_string(),
+            company: Some("Acme".to_string()),
+            location: Some("Remote".to_string()),
+            job_type: Some("full-time".to_string()),
+            experience_level: None,
+            requirements: Some(requirements.to_string()),
+            description: None,
+        }
+    }
+
+    #[test]
+    fn test_rank_similar_prefers_shared_skills_and_title() {
+        let viewed = job(
+            "J1",
+            "Rust Backend Engineer",
+            r#"["Rust", "PostgreSQL", "5+ years building distributed systems at scale"]"#,
+        );
+        let close = job("J2", "Backend Engineer", r#"["Rust", "PostgreSQL"]"#);
+        let loose = job("J3", "Frontend Engineer", r#"["React"]"#);
+        let unrelated = JobFacts {
+            company: None,
+            location: None,
+            job_type: None,
+            ..job("J4", "Accountant", r#"["Excel"]"#)
+        };
+
+        let ranked = rank_similar(&viewed, &[unrelated, loose, viewed.clone(), close]);
+        let ids: Vec<&str> = ranked.iter().map(|(id, _)| id.as_str()).collect();
+        assert_eq!(ids, vec!["J2", "J3"]);
+        assert!(ranked[0].1 > ranked[1].1);
+    }
+
+    #[tokio::test]
+    async fn test_similar_jobs_endpoint() {
+        let app = TestApp::new().await;
+        let token = app.token_for(&app.create_admin().await).await;
+        let viewed = app.create_job("Rust Backend Engineer").await;
+        let close = app.create_job("Backend Engineer").await;
+        let closed = app.create_job("Senior Backend Engineer").await;
+
+        let response = app
+            .get(&format!("/api/jobs/{}/similar", viewed), &token)
+            .await;
+        assert!(response.status.is_success(), "{:?}", response.body);
+        let jobs = response.body.as_array().unwrap();
+        assert_eq!(jobs.len(), 2);
+        assert!(jobs.iter().all(|j| j["id"] != viewed.as_str()));
+
+        // Served from the cached ranking, but closed jobs still drop out
+        sqlx::query("UPDATE jobs SET status = 'closed' WHERE id = ?")
+            .bind(&closed)
+            .execute(&app.state.db)
+            .await
+            .unwrap();
+        let response = app
+            .get(&format!("/api/jobs/{}/similar?limit=5", viewed), &token)
+            .await;
+        let jobs = response.body.as_array().unwrap();
+        assert_eq!(jobs.len(), 1);
+        assert_eq!(jobs[0]["id"], close.as_str());
+
+        let missing = app.get("/api/jobs/nope/similar", &token).await;
+        assert_eq!(missing.status.as_u16(), 404);
+    }
+}
@@ -0,0 +1,132 @@
+// src/jobs/tests/trash_tests.rs
+
+#[cfg(test)]
+mod tests {
+    use crate::jobs::services::trash::{purge_expired, TRASH_RETENTION_DAYS};
+    use crate::test_support::TestApp;
+    use axum::http::Method;
+    use serde_json::{json, Value};
+
+    fn listed_ids(body: &Value) -> Vec<String> {
+        body["jobs"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|job| job["id"].as_str().unwrap().to_string())
+            .collect()
+    }
+
+    /// Backdate a job's deletion by `days`
+    async fn trash_days_ago(app: &TestApp, job_id: &str, days: i64) {
+        sqlx::query("UPDATE jobs SET deleted_at = datetime('now', ?) WHERE id = ?")
+            .bind(format!("-{} days", days))
+            .bind(job_id)
+            .execute(&app.state.db)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_trashed_job_is_hidden_and_restorable_within_window() {
+        let app = TestApp::new().await;
+        let admin = app.token_for(&app.create_admin().await).await;
+        let job_id = app.create_job("Site Reliability Engineer").await;
+
+        let listed = app.get("/api/jobs", &admin).await;
+        assert!(listed_ids(&listed.body).contains(&job_id));
+
+        let deleted = app
+            .request(
+                Method::DELETE,
+                &format!("/api/admin/jobs/{}", job_id),
+                Some(&admin),
+                None,
+            )
+            .await;
+        assert!(deleted.status.is_success(), "{:?}", deleted.body);
+
+        let listed = app.get("/api/jobs", &admin).await;
+        assert!(!listed_ids(&listed.body).contains(&job_id));
+        let detail = app.get(&format!("/api/jobs/{}", job_id), &admin).await;
+        assert_eq!(detail.status, 400);
+
+        let trash = app.get("/api/admin/jobs/trash", &admin).await;
+        assert!(trash.status.is_success(), "{:?}", trash.body);
+        assert_eq!(trash.body[0]["id"], job_id.as_str());
+        assert!(trash.body[0]["purge_at"].is_string());
+
+        // Still inside the retention window, so the purge leaves it alone
+        trash_days_ago(&app, &job_id, TRASH_RETENTION_DAYS - 1).await;
+        assert_eq!(purge_expired(&app.state.db).await.unwrap(), 0);
+
+        let restored = app
+            .post(
+                &format!("/api/admin/jobs/{}/restore", job_id),
+                &admin,
+                json!({}),
+            )
+            .await;
+        assert!(restored.status.is_success(), "{:?}", restored.body);
+
+        let listed = app.get("/api/jobs", &admin).await;
+        assert!(listed_ids(&listed.body).contains(&job_id));
+        let trash = app.get("/api/admin/jobs/trash", &admin).await;
+        assert_eq!(trash.body, json!([]));
+    }
+
+    #[tokio::test]
+    async fn test_purge_removes_jobs_past_retention_without_applications() {
+        let app = TestApp::new().await;
+        let admin = app.token_for(&app.create_admin().await).await;
+        let candidate_id = app
+            .create_user("trash@test.example.com", "Toby Trash")
+            .await;
+        let expired = app.create_job("Expired Posting").await;
+        let applied = app.create_job("Posting With Applicants").await;
+        let recent = app.create_job("Recently Deleted Posting").await;
+
+        sqlx::query("INSERT INTO job_views (id, job_id) VALUES ('V_TRASH', ?)")
+            .bind(&expired)
+            .execute(&app.state.db)
+            .await
+            .unwrap();
+        sqlx::query(
+            "INSERT INTO applications (id, user_id, job_id, status) VALUES ('A_TRASH', ?, ?, 'rejected')",
+        )
+        .bind(&candidate_id)
+        .bind(&applied)
+        .execute(&app.state.db)
+        .await
+        .unwrap();
+
+        trash_days_ago(&app, &expired, TRASH_RETENTION_DAYS + 1).await;
+        trash_days_ago(&app, &applied, TRASH_RETENTION_DAYS + 1).await;
+        trash_days_ago(&app, &recent, TRASH_RETENTION_DAYS - 1).await;
+
+        assert_eq!(purge_expired(&app.state.db).await.unwrap(), 1);
+
+        let remaining: Vec<String> = sqlx::query_scalar("SELECT id FROM jobs ORDER BY title")
+            .fetch_all(&app.state.db)
+            .await
+            .unwrap();
+        assert!(!remaining.contains(&expired));
+        assert!(remaining.contains(&applied));
+        assert!(remaining.contains(&recent));
+
+        let views: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM job_views WHERE job_id = ?")
+            .bind(&expired)
+            .fetch_one(&app.state.db)
+            .await
+            .unwrap();
+        assert_eq!(views, 0);
+
+        let restore = app
+            .post(
+                &format!("/api/admin/jobs/{}/restore", expired),
+                &admin,
+                json!({}),
+            )
+            .await;
+        assert_eq!(restore.status, 404);
+    }
+}
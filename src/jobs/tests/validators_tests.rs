@@ -27,6 +27,9 @@ mod tests {
             is_featured: Some(false),
             template_id: None,
             status: Some("draft".to_string()),
+            application_deadline: None,
+            allow_duplicate: false,
+            ai_generated_fields: Vec::new(),
         };
 
         let result = validator.validate(&request);
@@ -55,6 +58,9 @@ mod tests {
             is_featured: None,
             template_id: None,
             status: None,
+            application_deadline: None,
+            allow_duplicate: false,
+            ai_generated_fields: Vec::new(),
         };
 
         let result = validator.validate(&request);
@@ -62,6 +68,40 @@ mod tests {
         assert!(result.errors.iter().any(|e| e.field == "title"));
     }
 
+    #[test]
+    fn test_job_validator_past_application_deadline() {
+        let validator = JobValidator;
+        let request = CreateJob {
+            title: "Software Engineer".to_string(),
+            description: None,
+            location: None,
+            company: None,
+            company_id: None,
+            company_logo_url: None,
+            job_image_url: None,
+            salary_min: None,
+            salary_max: None,
+            job_type: None,
+            experience_level: None,
+            requirements: None,
+            benefits: None,
+            educational_qualifications: None,
+            is_featured: None,
+            template_id: None,
+            status: None,
+            application_deadline: Some("2020-01-01T00:00:00Z".to_string()),
+            allow_duplicate: false,
+            ai_generated_fields: Vec::new(),
+        };
+
+        let result = validator.validate(&request);
+        assert!(!result.is_valid);
+        assert!(result
+            .errors
+            .iter()
+            .any(|e| e.field == "application_deadline"));
+    }
+
     #[test]
     fn test_bulk_operation_validator_too_many_jobs() {
         let validator = BulkOperationValidator;
@@ -76,4 +116,76 @@ mod tests {
         assert!(!result.is_valid);
         assert!(result.errors.iter().any(|e| e.field == "job_ids"));
     }
+
+    fn bundle(templates: Vec<BundledTemplate>) -> TemplateBundle {
+        TemplateBundle {
+            format: TEMPLATE_BUNDLE_FORMAT.to_string(),
+            version: TEMPLATE_BUNDLE_VERSION,
+            exported_at: None,
+            templates,
+        }
+    }
+
+    fn template(name: &str, template_type: &str, job_data: serde_json::Value) -> BundledTemplate {
+        BundledTemplate {
+            name: name.to_string(),
+            template_type: template_type.to_string(),
+            job_data,
+            ai_context: None,
+        }
+    }
+
+    #[test]
+    fn test_template_import_validator_accepts_valid_bundle() {
+        let request = ImportTemplatesRequest {
+            bundle: bundle(vec![template(
+                "Physics Faculty",
+                "custom",
+                serde_json::json!({"title": "Physics Faculty", "requirements": ["M.Sc."]}),
+            )]),
+            company_id: None,
+            on_conflict: TemplateConflictPolicy::Skip,
+            dry_run: false,
+        };
+
+        assert!(TemplateImportValidator.validate(&request).is_valid);
+    }
+
+    #[test]
+    fn test_template_import_validator_reports_each_problem() {
+        let mut ai = template("AI Template", "ai", serde_json::json!({}));
+        ai.ai_context = Some(AITemplateContext {
+            title_context: Some("  ".to_string()),
+            description_context: None,
+            requirements_context: None,
+            benefits_context: None,
+            educational_qualifications_context: None,
+        });
+        let mut request = ImportTemplatesRequest {
+            bundle: bundle(vec![
+                template("Physics", "custom", serde_json::json!({"requirements": "M.Sc."})),
+                template("physics", "unknown", serde_json::json!([])),
+                ai,
+            ]),
+            company_id: None,
+            on_conflict: TemplateConflictPolicy::Rename,
+            dry_run: true,
+        };
+        request.bundle.version = TEMPLATE_BUNDLE_VERSION + 1;
+
+        let result = TemplateImportValidator.validate(&request);
+        let fields: Vec<&str> = result.errors.iter().map(|e| e.field.as_str()).collect();
+        assert_eq!(
+            fields,
+            vec![
+                "bundle.version",
+                "bundle.templates[0].job_data.requirements",
+                "bundle.templates[1].name",
+                "bundle.templates[1].template_type",
+                "bundle.templates[1].job_data",
+                "bundle.templates[2].ai_context",
+                "company_id",
+            ]
+        );
+    }
 }
@@ -1,7 +1,8 @@
 // src/jobs/validators.rs
 
 use super::models::*;
-use crate::common::{ValidationResult, Validator};
+use super::services::deadlines::normalize_deadline;
+use crate::common::{validate_fields, ValidationResult, Validator};
 use chrono::NaiveDate;
 use std::collections::HashSet;
 
@@ -13,47 +14,16 @@ pub struct JobValidator;
 
 impl Validator<CreateJob> for JobValidator {
     fn validate(&self, data: &CreateJob) -> ValidationResult {
-        let mut result = ValidationResult::new();
+        // Lengths and salary bounds are declared on CreateJob
+        let mut result = validate_fields(data);
 
         // Validate title
         if data.title.trim().is_empty() {
             result.add_error("title", "Job title is required");
-        } else if data.title.len() > 255 {
-            result.add_error("title", "Job title must be less than 255 characters");
-        }
-
-        // Validate description length if provided
-        if let Some(description) = &data.description {
-            if description.len() > 10000 {
-                result.add_error(
-                    "description",
-                    "Description must be less than 10000 characters",
-                );
-            }
-        }
-
-        // Validate location length if provided
-        if let Some(location) = &data.location {
-            if location.len() > 255 {
-                result.add_error("location", "Location must be less than 255 characters");
-            }
-        }
-
-        // Validate company length if provided
-        if let Some(company) = &data.company {
-            if company.len() > 255 {
-                result.add_error("company", "Company name must be less than 255 characters");
-            }
         }
 
         // Validate salary range
         if let (Some(min), Some(max)) = (data.salary_min, data.salary_max) {
-            if min < 0 {
-                result.add_error("salary_min", "Minimum salary cannot be negative");
-            }
-            if max < 0 {
-                result.add_error("salary_max", "Maximum salary cannot be negative");
-            }
             if min > max {
                 result.add_error(
                     "salary_range",
@@ -84,6 +54,13 @@ impl Validator<CreateJob> for JobValidator {
             }
         }
 
+        // Validate application_deadline if provided
+        if let Some(deadline) = &data.application_deadline {
+            if let Err(message) = normalize_deadline(deadline, chrono::Utc::now()) {
+                result.add_error("application_deadline", &message);
+            }
+        }
+
         result
     }
 }
@@ -121,6 +98,132 @@ impl Validator<BulkJobStatusUpdate> for BulkOperationValidator {
     }
 }
 
+// ============================================================================
+// Template Import Validators
+// ============================================================================
+
+pub struct TemplateImportValidator;
+
+impl Validator<ImportTemplatesRequest> for TemplateImportValidator {
+    fn validate(&self, data: &ImportTemplatesRequest) -> ValidationResult {
+        let mut result = ValidationResult::new();
+        let bundle = &data.bundle;
+
+        if bundle.format != TEMPLATE_BUNDLE_FORMAT {
+            result.add_error("bundle.format", "Not a job template bundle");
+        }
+        if bundle.version == 0 || bundle.version > TEMPLATE_BUNDLE_VERSION {
+            result.add_error("bundle.version", "Unsupported bundle version");
+        }
+        if bundle.templates.is_empty() {
+            result.add_error("bundle.templates", "Bundle contains no templates");
+        } else if bundle.templates.len() > 200 {
+            result.add_error(
+                "bundle.templates",
+                "Cannot import more than 200 templates at once",
+            );
+        }
+
+        let mut names = HashSet::new();
+        for (index, template) in bundle.templates.iter().enumerate() {
+            let field = |name: &str| format!("bundle.templates[{}].{}", index, name);
+
+            let name = template.name.trim();
+            if name.is_empty() {
+                result.add_error(&field("name"), "Template name cannot be empty");
+            } else if name.len() > 200 {
+                result.add_error(
+                    &field("name"),
+                    "Template name must be at most 200 characters",
+                );
+            } else if !names.insert(name.to_lowercase()) {
+                result.add_error(
+                    &field("name"),
+                    "Template name appears more than once in the bundle",
+                );
+            }
+
+            match template.template_type.as_str() {
+                "system" | "custom" => {}
+                "ai" => {
+                    let has_context = template.ai_context.as_ref().is_some_and(|c| {
+                        [
+                            &c.title_context,
+                            &c.description_context,
+                            &c.requirements_context,
+                            &c.benefits_context,
+                            &c.educational_qualifications_context,
+                        ]
+                        .iter()
+                        .any(|ctx| ctx.as_deref().is_some_and(|s| !s.trim().is_empty()))
+                    });
+                    if !has_context {
+                        result.add_error(
+                            &field("ai_context"),
+                            "AI templates need at least one context prompt",
+                        );
+                    }
+                    if data
+                        .company_id
+                        .as_deref()
+                        .is_none_or(|c| c.trim().is_empty())
+                    {
+                        result.add_error(
+                            "company_id",
+                            "A company is required to import AI templates",
+                        );
+                    }
+                }
+                _ => result.add_error(
+                    &field("template_type"),
+                    "Template type must be 'system', 'custom', or 'ai'",
+                ),
+            }
+
+            match &template.job_data {
+                serde_json::Value::Null => {}
+                serde_json::Value::Object(job_data) => {
+                    for key in [
+                        "title",
+                        "description",
+                        "location",
+                        "job_type",
+                        "experience_level",
+                    ] {
+                        if job_data
+                            .get(key)
+                            .is_some_and(|v| !v.is_string() && !v.is_null())
+                        {
+                            result.add_error(
+                                &field(&format!("job_data.{}", key)),
+                                "Must be a string",
+                            );
+                        }
+                    }
+                    for key in ["requirements", "benefits"] {
+                        let is_list = |v: &serde_json::Value| {
+                            v.as_array()
+                                .is_some_and(|items| items.iter().all(|i| i.is_string()))
+                        };
+                        if job_data
+                            .get(key)
+                            .is_some_and(|v| !v.is_null() && !is_list(v))
+                        {
+                            result.add_error(
+                                &field(&format!("job_data.{}", key)),
+                                "Must be a list of strings",
+                            );
+                        }
+                    }
+                }
+                _ => result.add_error(&field("job_data"), "Job data must be an object"),
+            }
+        }
+
+        result
+    }
+}
+
 // ============================================================================
 // Job Analytics Validators
 // ============================================================================
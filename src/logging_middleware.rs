@@ -9,16 +9,22 @@ use axum::{
     response::Response,
 };
 use axum::body::to_bytes;
-use tracing::debug;
+use tracing::{debug, info_span, Span};
+
+use crate::common::request_id::{with_request_id, REQUEST_ID_HEADER};
 
 /// Middleware to log request and response bodies in debug mode
 pub async fn log_request_response(request: Request, next: Next) -> Result<Response, StatusCode> {
     let (parts, body) = request.into_parts();
     
-    // Read request body
-    let bytes = to_bytes(body, usize::MAX)
-        .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    // Read request body; a body over its route's cap is answered with 413
+    let bytes = to_bytes(body, usize::MAX).await.map_err(|e| {
+        if crate::body_limit_middleware::is_length_limit(&e) {
+            StatusCode::PAYLOAD_TOO_LARGE
+        } else {
+            StatusCode::INTERNAL_SERVER_ERROR
+        }
+    })?;
     
     // Log request body if not empty
     if !bytes.is_empty() {
@@ -81,3 +87,31 @@ pub async fn log_request_response(request: Request, next: Next) -> Result<Respon
     
     Ok(response)
 }
+
+/// Span for each HTTP request, tagged with the ID set by the request-id layer
+pub fn request_span(request: &Request) -> Span {
+    let request_id = request
+        .headers()
+        .get(REQUEST_ID_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("-");
+
+    info_span!(
+        "request",
+        method = %request.method(),
+        uri = %request.uri(),
+        request_id = %request_id,
+    )
+}
+
+/// Middleware making the request ID available to handlers and error responses
+pub async fn request_id_scope(request: Request, next: Next) -> Response {
+    let request_id = request
+        .headers()
+        .get(REQUEST_ID_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or_default()
+        .to_string();
+
+    with_request_id(request_id, next.run(request)).await
+}
@@ -1,14 +1,23 @@
 // src/main.rs
-use axum::{extract::Extension, middleware, Router};
+use axum::{
+    extract::{DefaultBodyLimit, Extension},
+    middleware, Router,
+};
 use dotenv::dotenv;
 use reqwest::Client;
-use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
-use std::collections::HashSet;
-use std::env;
 use std::path::PathBuf;
-use std::{net::SocketAddr, str::FromStr, sync::Arc};
+use std::future::IntoFuture;
+use std::{net::SocketAddr, sync::Arc};
 use tokio::{net::TcpListener, sync::RwLock};
-use tower_http::{cors::CorsLayer, trace::TraceLayer};
+use tower_http::{
+    compression::{
+        predicate::{DefaultPredicate, NotForContentType, Predicate},
+        CompressionLayer,
+    },
+    cors::CorsLayer,
+    request_id::{MakeRequestUuid, PropagateRequestIdLayer, SetRequestIdLayer},
+    trace::TraceLayer,
+};
 use tracing::{info, warn};
 use tracing_subscriber::EnvFilter;
 
@@ -18,22 +27,27 @@ use tracing_subscriber::EnvFilter;
 
 mod admin;
 mod auth;
+mod body_limit_middleware;
 mod candidates;
 mod common;
+mod concurrency_middleware;
 mod companies;
 mod jobs;
 mod logging_middleware;
 mod messages;
 mod profile;
 mod rate_limit_middleware;
+mod security_middleware;
 mod services;
+#[cfg(test)]
+mod test_support;
 
 // ============================================================================
 // COMMON IMPORTS
 // ============================================================================
 
 use common::AppState;
-use common::dev_mode::{apply_cli_override, print_dev_mode_status, DevModeConfig};
+use common::dev_mode::print_dev_mode_status;
 use rate_limit_middleware::rate_limit_middleware;
 use services::{AWSService, GoogleService, OpenAIService, RateLimitService, SettingsService};
 
@@ -45,54 +59,46 @@ use services::{AWSService, GoogleService, OpenAIService, RateLimitService, Setti
 async fn main() -> anyhow::Result<()> {
     dotenv().ok();
 
-    let admin_emails_raw = env::var("ADMIN_EMAILS").unwrap_or_default();
-    info!("Raw ADMIN_EMAILS from env: '{}'", admin_emails_raw);
-
-    tracing_subscriber::fmt()
-        .with_env_filter(
-            EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info")),
-        )
-        .with_target(false)
-        .init();
-
     // ========================================================================
     // ENVIRONMENT CONFIGURATION
     // ========================================================================
 
-    let database_url =
-        env::var("DATABASE_URL").unwrap_or_else(|_| "sqlite://job_api.db".to_string());
-    let resumes_dir = env::var("RESUMES_DIR").unwrap_or_else(|_| "./resumes".to_string());
-    let avatars_dir = env::var("AVATARS_DIR").unwrap_or_else(|_| "./uploads/avatars".to_string());
-    let logos_dir = env::var("LOGOS_DIR").unwrap_or_else(|_| "./uploads/logos".to_string());
-    let jwt_secret =
-        env::var("JWT_SECRET").unwrap_or_else(|_| "replace_with_strong_secret".to_string());
-    let google_client_id = env::var("GOOGLE_CLIENT_ID").ok();
-    let openai_api_key = env::var("OPENAI_API_KEY").ok();
-    let openai_model = env::var("OPENAI_MODEL").unwrap_or_else(|_| "gpt-4".to_string());
-
-    // Parse admin emails from comma-separated env var
-    let admin_emails: HashSet<String> = admin_emails_raw
-        .split(',')
-        .map(|s| s.trim().to_lowercase())
-        .filter(|s| !s.is_empty())
-        .collect();
-
-    info!("Loaded admin emails: {:?}", admin_emails);
+    // Fails fast on unparseable values, and on insecure defaults outside dev mode. Loaded
+    // before logging starts, since it decides the log format.
+    let config = common::config::Config::load()?;
+
+    let env_filter =
+        EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
+    if config.json_logs {
+        tracing_subscriber::fmt()
+            .with_env_filter(env_filter)
+            .fmt_fields(common::log_format::JsonFields)
+            .event_format(common::log_format::JsonFormat)
+            .init();
+    } else {
+        tracing_subscriber::fmt()
+            .with_env_filter(env_filter)
+            .with_target(false)
+            .init();
+    }
 
-    // ========================================================================
-    // DEV MODE CONFIGURATION
-    // ========================================================================
+    for warning in &config.warnings {
+        warn!("Configuration: {}", warning);
+    }
+    info!("Loaded admin emails: {:?}", config.admin_emails);
+    print_dev_mode_status(&config.dev_mode);
 
-    let dev_mode = apply_cli_override(DevModeConfig::from_env());
-    print_dev_mode_status(&dev_mode);
+    let database_url = config.database_url.clone();
 
     // ========================================================================
     // DIRECTORY SETUP
     // ========================================================================
 
-    tokio::fs::create_dir_all(&resumes_dir).await?;
-    tokio::fs::create_dir_all(&avatars_dir).await?;
-    tokio::fs::create_dir_all(&logos_dir).await?;
+    tokio::fs::create_dir_all(&config.resumes_dir).await?;
+    tokio::fs::create_dir_all(&config.documents_dir).await?;
+    tokio::fs::create_dir_all(&config.upload_sessions_dir).await?;
+    tokio::fs::create_dir_all(&config.avatars_dir).await?;
+    tokio::fs::create_dir_all(&config.logos_dir).await?;
     tokio::fs::create_dir_all("./uploads/job-images/logos").await?;
     tokio::fs::create_dir_all("./uploads/job-images/jobs").await?;
 
@@ -112,13 +118,38 @@ async fn main() -> anyhow::Result<()> {
         }
     }
 
-    let connect_options = SqliteConnectOptions::from_str(&database_url)?.create_if_missing(true);
-    let pool = SqlitePoolOptions::new()
-        .connect_with(connect_options)
-        .await?;
+    let pool = common::db::connect_write_pool(&database_url).await?;
     
     // Run database migrations
-    common::migrations::run_migrations(&pool).await?;
+    common::migrations::run_migrations(&pool, config.reset_db).await?;
+
+    let read_pool = common::db::connect_read_pool(
+        &database_url,
+        config.database_read_url.as_deref(),
+        config.database_read_max_connections,
+        &pool,
+    )
+    .await?;
+
+    services::pii::init()?;
+    services::pii::reseal_all(&pool).await?;
+
+    if common::dev_mode::seed_requested() {
+        if config.dev_mode.is_enabled() {
+            services::seed::seed_demo_data(&pool).await?;
+        } else {
+            warn!("Demo data is only seeded in dev mode; ignoring --seed");
+        }
+    }
+
+    let jwt_keys = auth::keys::JwtKeys::default();
+    auth::keys::init(&pool, &jwt_keys).await?;
+    auth::keys::start_key_rotation_task(
+        pool.clone(),
+        jwt_keys.clone(),
+        config.jwt_key_rotation_days,
+    );
+    info!("JWT key rotation task started");
 
     // ========================================================================
     // SERVICE INITIALIZATION
@@ -129,7 +160,20 @@ async fn main() -> anyhow::Result<()> {
     let settings_service = Arc::new(SettingsService::new(pool.clone()));
     info!("SettingsService initialized");
 
-    let openai_service = Arc::new(OpenAIService::new(settings_service.clone()));
+    // Encrypt any secrets that were seeded or stored in plaintext
+    match settings_service.encrypt_plaintext_secrets().await {
+        Ok(migration) => {
+            if migration.rewritten > 0 {
+                info!(count = migration.rewritten, "Encrypted plaintext secrets at rest");
+            }
+            if !migration.skipped.is_empty() {
+                warn!(keys = ?migration.skipped, "Left settings that could not be decrypted as they were");
+            }
+        }
+        Err(e) => warn!("Failed to encrypt plaintext secrets: {}", e),
+    }
+
+    let openai_service = Arc::new(OpenAIService::new(settings_service.clone(), pool.clone()));
     info!("OpenAIService initialized");
 
     let aws_service = Arc::new(AWSService::new(settings_service.clone()));
@@ -139,10 +183,91 @@ async fn main() -> anyhow::Result<()> {
     info!("GoogleService initialized");
     
     // Sync Google OAuth credentials from environment to database
-    if let Err(e) = google_service.sync_env_to_settings().await {
+    if let Err(e) = google_service
+        .sync_env_to_settings(
+            config.google_client_id.as_deref(),
+            config.google_client_secret.as_deref(),
+        )
+        .await
+    {
         warn!("Failed to sync Google credentials to database: {}", e);
     }
 
+    services::interviews::start_calendar_sync_task(
+        pool.clone(),
+        settings_service.clone(),
+        google_service.clone(),
+        aws_service.clone(),
+    );
+    info!("Google Calendar sync task started");
+
+    services::calendar_retries::start_calendar_retry_task(
+        pool.clone(),
+        google_service.clone(),
+        aws_service.clone(),
+    );
+    info!("Calendar retry task started");
+
+    services::social::start_social_publish_task(
+        pool.clone(),
+        settings_service.clone(),
+        http_client.clone(),
+    );
+    info!("Social publishing task started");
+
+    services::sla::start_sla_monitor_task(
+        pool.clone(),
+        settings_service.clone(),
+        aws_service.clone(),
+        config.frontend_url.clone(),
+    );
+    info!("SLA monitor task started");
+
+    services::surveys::start_survey_task(
+        pool.clone(),
+        settings_service.clone(),
+        aws_service.clone(),
+        config.frontend_url.clone(),
+    );
+    info!("Candidate survey task started");
+
+    services::conversation_retention::start_conversation_retention_task(
+        pool.clone(),
+        settings_service.clone(),
+    );
+    info!("Conversation retention task started");
+
+    jobs::services::trash::start_trash_purge_task(pool.clone());
+    info!("Job trash purge task started");
+
+    let feed_cache = jobs::services::FeedCache::default();
+    jobs::services::deadlines::start_deadline_close_task(
+        pool.clone(),
+        aws_service.clone(),
+        config.admin_emails.clone(),
+        config.frontend_url.clone(),
+        feed_cache.clone(),
+    );
+    info!("Job deadline close task started");
+
+    services::activity::start_activity_digest_task(
+        pool.clone(),
+        settings_service.clone(),
+        aws_service.clone(),
+        config.admin_emails.clone(),
+    );
+    info!("Activity digest task started");
+
+    services::reports::start_report_schedule_task(
+        pool.clone(),
+        settings_service.clone(),
+        aws_service.clone(),
+    );
+    info!("Scheduled report task started");
+
+    services::analytics_facts::start_analytics_refresh_task(pool.clone(), settings_service.clone());
+    info!("Analytics refresh task started");
+
     let rate_limit_service = Arc::new(RateLimitService::new(settings_service.clone()));
     info!("RateLimitService initialized");
 
@@ -153,12 +278,46 @@ async fn main() -> anyhow::Result<()> {
     ));
     info!("PDFService initialized");
 
+    let sms_service = Arc::new(services::SmsService::new(
+        settings_service.clone(),
+        aws_service.clone(),
+        http_client.clone(),
+    ));
+    info!("SmsService initialized");
+
+    services::sms::start_sms_reminder_task(pool.clone(), sms_service.clone());
+    info!("SMS reminder task started");
+
+    let whatsapp_service = Arc::new(services::WhatsAppService::new(
+        settings_service.clone(),
+        http_client.clone(),
+    ));
+    info!("WhatsAppService initialized");
+
+    services::whatsapp::start_whatsapp_reminder_task(pool.clone(), whatsapp_service.clone());
+    info!("WhatsApp reminder task started");
+
     let connection_manager = messages::services::ConnectionManager::new();
     info!("ConnectionManager initialized");
 
     messages::services::WebSocketService::start_cleanup_task(connection_manager.clone());
     info!("WebSocket cleanup task started");
 
+    services::broadcasts::start_broadcast_task(
+        pool.clone(),
+        settings_service.clone(),
+        aws_service.clone(),
+        connection_manager.clone(),
+    );
+    info!("Broadcast delivery task started");
+
+    services::scheduled_messages::start_scheduled_message_task(
+        pool.clone(),
+        aws_service.clone(),
+        connection_manager.clone(),
+    );
+    info!("Scheduled message task started");
+
     // Initialize job templates
     let templates_service = services::job_templates::JobTemplatesService::new(pool.clone());
     if let Err(e) = templates_service.initialize_system_templates().await {
@@ -173,34 +332,109 @@ async fn main() -> anyhow::Result<()> {
 
     let app_state = AppState {
         db: pool,
-        resumes_dir: PathBuf::from(resumes_dir),
-        avatars_dir: PathBuf::from(avatars_dir),
-        logos_dir: PathBuf::from(logos_dir),
+        db_read: read_pool,
+        resumes_dir: config.resumes_dir.clone(),
+        documents_dir: config.documents_dir.clone(),
+        upload_sessions_dir: config.upload_sessions_dir.clone(),
+        avatars_dir: config.avatars_dir.clone(),
+        logos_dir: config.logos_dir.clone(),
         job_images_logos_dir: PathBuf::from("./uploads/job-images/logos"),
         job_images_jobs_dir: PathBuf::from("./uploads/job-images/jobs"),
         http: http_client,
-        jwt_secret,
-        google_client_id,
-        openai_api_key,
-        openai_model,
-        admin_emails,
-        dev_mode,
+        jwt_secret: config.jwt_secret.clone(),
+        jwt_keys,
+        google_client_id: config.google_client_id.clone(),
+        apple_client_ids: config.apple_client_ids.clone(),
+        apple_keys: auth::apple::AppleKeyCache::default(),
+        openai_api_key: config.openai_api_key.clone(),
+        openai_model: config.openai_model.clone(),
+        admin_emails: config.admin_emails.clone(),
+        dev_mode: config.dev_mode.clone(),
         settings_service,
+        email: aws_service.clone(),
+        object_store: aws_service.clone(),
+        llm: openai_service.clone(),
+        calendar: google_service.clone(),
         openai_service,
         aws_service,
         google_service,
         rate_limit_service: rate_limit_service.clone(),
         pdf_service,
+        sms_service,
+        whatsapp_service,
         connection_manager,
+        feed_cache,
+        similar_jobs_cache: jobs::services::SimilarJobsCache::default(),
+        job_editors: jobs::services::JobEditors::default(),
+        config: Arc::new(config.clone()),
     };
 
     let shared = Arc::new(RwLock::new(app_state));
 
+    services::file_gc::start_orphan_scan_task(shared.clone());
+    info!("Orphaned file scan task started");
+
     // ========================================================================
     // ROUTER COMPOSITION
     // ========================================================================
 
-    let app = Router::new()
+    let app = app_router(shared.clone(), rate_limit_service, &config.cors_origins);
+
+    // ========================================================================
+    // SERVER STARTUP
+    // ========================================================================
+
+    let addr = SocketAddr::from(([0, 0, 0, 0], config.port));
+    info!("Listening on {}", addr);
+    let listener = TcpListener::bind(addr).await?;
+
+    // On SIGTERM/Ctrl-C stop accepting connections and let in-flight requests finish
+    tokio::spawn(common::shutdown::listen_for_signals());
+    let mut server = tokio::spawn(
+        axum::serve(listener, app.into_make_service())
+            .with_graceful_shutdown(common::shutdown::requested())
+            .into_future(),
+    );
+    tokio::select! {
+        result = &mut server => return Ok(result??),
+        _ = common::shutdown::requested() => {}
+    }
+
+    // ========================================================================
+    // SHUTDOWN
+    // ========================================================================
+
+    let drain_timeout = std::time::Duration::from_secs(config.shutdown_drain_secs);
+    let deadline = tokio::time::Instant::now() + drain_timeout;
+    info!(drain_timeout_secs = drain_timeout.as_secs(), "Draining before shutdown");
+
+    // Upgraded WebSockets aren't tracked by the server, so close them explicitly
+    let connection_manager = shared.read().await.connection_manager.clone();
+    connection_manager.close_all("Server is shutting down").await;
+
+    match tokio::time::timeout_at(deadline, server).await {
+        Ok(result) => result??,
+        Err(_) => warn!("In-flight requests still running at the drain timeout; dropping them"),
+    }
+    let remaining = deadline.saturating_duration_since(tokio::time::Instant::now());
+    if !common::shutdown::drain(remaining).await {
+        warn!(
+            active = common::shutdown::active(),
+            "Background work still running at the drain timeout; dropping it"
+        );
+    }
+
+    info!("Shutdown complete");
+    Ok(())
+}
+
+/// All routes with their middleware, as served by `main` and exercised by the test harness
+fn app_router(
+    shared: Arc<RwLock<AppState>>,
+    rate_limit_service: Arc<RateLimitService>,
+    cors_origins: &[String],
+) -> Router {
+    Router::new()
         // ====================================================================
         // AUTHENTICATION ROUTES
         // ====================================================================
@@ -234,19 +468,26 @@ async fn main() -> anyhow::Result<()> {
         // ====================================================================
         // Add request/response body logging in debug mode
         .layer(middleware::from_fn(logging_middleware::log_request_response))
+        // Per-route body caps replace axum's blanket 2MB default
+        .layer(middleware::from_fn(body_limit_middleware::body_limit_middleware))
+        .layer(DefaultBodyLimit::disable())
+        // Shed requests once their route's concurrency budget is used up
+        .layer(middleware::from_fn(concurrency_middleware::concurrency_middleware))
         .layer(middleware::from_fn(rate_limit_middleware))
+        .layer(middleware::from_fn(services::ai_usage::attribution_scope))
+        .layer(middleware::from_fn(services::masking::masking_scope))
+        .layer(middleware::from_fn(
+            security_middleware::security_monitoring_middleware,
+        ))
         .layer(Extension(rate_limit_service))
-        .layer(Extension(shared.clone()))
+        .layer(Extension(shared))
         .layer({
-            // Get CORS origins from environment variable
-            let cors_origins = std::env::var("CORS_ORIGINS")
-                .unwrap_or_else(|_| "http://localhost:3000,http://localhost:3001,http://localhost:5173".to_string());
-            
+            // Origins were checked when the config loaded
             let origins: Vec<axum::http::HeaderValue> = cors_origins
-                .split(',')
-                .filter_map(|origin| origin.trim().parse().ok())
+                .iter()
+                .filter_map(|origin| origin.parse().ok())
                 .collect();
-            
+
             CorsLayer::new()
                 .allow_origin(origins)
                 .allow_methods([
@@ -263,22 +504,27 @@ async fn main() -> anyhow::Result<()> {
                     axum::http::HeaderName::from_static("x-request-id"),
                 ])
                 .allow_credentials(true)
+                .expose_headers([axum::http::HeaderName::from_static("x-request-id")])
         })
-        .layer(TraceLayer::new_for_http());
-
-    // ========================================================================
-    // SERVER STARTUP
-    // ========================================================================
-
-    let port = std::env::var("PORT")
-        .ok()
-        .and_then(|p| p.parse::<u16>().ok())
-        .unwrap_or(8080);
-    
-    let addr = SocketAddr::from(([0, 0, 0, 0], port));
-    info!("Listening on {}", addr);
-    let listener = TcpListener::bind(addr).await?;
-    axum::serve(listener, app.into_make_service()).await?;
-
-    Ok(())
+        .layer(middleware::from_fn(common::i18n::locale_scope))
+        .layer(middleware::from_fn(logging_middleware::request_id_scope))
+        // gzip/br for clients that accept it. Images, media and event streams are left alone,
+        // as are partial (range) responses, whose byte offsets refer to the raw file.
+        .layer(CompressionLayer::new().compress_when(
+            DefaultPredicate::new()
+                .and(NotForContentType::const_new("video/"))
+                .and(NotForContentType::const_new("audio/"))
+                .and(
+                    |status: axum::http::StatusCode,
+                     _: axum::http::Version,
+                     _: &axum::http::HeaderMap,
+                     _: &axum::http::Extensions| {
+                        status != axum::http::StatusCode::PARTIAL_CONTENT
+                    },
+                ),
+        ))
+        .layer(TraceLayer::new_for_http().make_span_with(logging_middleware::request_span))
+        // Outermost: reuse the caller's x-request-id or mint one, and echo it on the response
+        .layer(PropagateRequestIdLayer::x_request_id())
+        .layer(SetRequestIdLayer::x_request_id(MakeRequestUuid))
 }
@@ -5,8 +5,16 @@ use crate::auth::extractors::AuthedUser;
 use crate::common::error::ApiError;
 use crate::common::id_generator::generate_message_id;
 use crate::common::state::AppState;
-use crate::messages::models::{ConversationInput, ConversationMessage, EnhancedConversationMessage, MessageAttachment};
-use axum::{extract::Path, Extension, Json};
+use crate::messages::models::{AdminConversationInput, ConversationMessage, EnhancedConversationMessage, MessageAttachment};
+use crate::services::sanitize::sanitize_message;
+use crate::services::scheduled_messages::{self, NewScheduledMessage};
+use crate::services::snippets;
+use axum::{
+    extract::Path,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Extension, Json,
+};
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use tracing::error;
@@ -54,12 +62,14 @@ pub async fn admin_list_conversations(
     Ok(Json(enhanced_messages))
 }
 
+/// POST /api/admin/conversations/:user_id - Send a message now, or with `send_at` /
+/// `business_hours` schedule it and answer 202 with the scheduled message
 pub async fn admin_send_conversation(
     Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
     authed: AuthedUser,
     Path(user_id): Path<String>,
-    Json(input): Json<ConversationInput>,
-) -> Result<Json<ConversationMessage>, ApiError> {
+    Json(input): Json<AdminConversationInput>,
+) -> Result<Response, ApiError> {
     if !authed.is_admin {
         return Err(ApiError::Forbidden("admin privileges required".to_string()));
     }
@@ -69,9 +79,43 @@ pub async fn admin_send_conversation(
         return Err(ApiError::BadRequest("message cannot be empty".to_string()));
     }
 
-    let message = trimmed.to_owned();
-
     let state = state_lock.read().await.clone();
+    let (message, _) = snippets::expand(&state.db, trimmed, Some(&user_id), None).await?;
+
+    if let Some((send_at, timezone)) = scheduled_messages::resolve_send_at(
+        &state.db,
+        &state.settings_service,
+        &user_id,
+        &input.schedule,
+    )
+    .await?
+    {
+        let content = sanitize_message(&message);
+        crate::messages::validators::validate_message_content(&content)?;
+        let scheduled = scheduled_messages::schedule_message(
+            &state.db,
+            NewScheduledMessage {
+                kind: "message",
+                user_id: &user_id,
+                application_id: None,
+                job_id: None,
+                subject: None,
+                body: &content,
+                cc: None,
+                send_at,
+                timezone: &timezone,
+                business_hours: input.schedule.business_hours,
+                created_by: &authed.id,
+            },
+        )
+        .await?;
+        return Ok((
+            StatusCode::ACCEPTED,
+            Json(scheduled_messages::to_response(scheduled)),
+        )
+            .into_response());
+    }
+
     let message_id = generate_message_id();
     sqlx::query(
         "INSERT INTO conversation_messages (id, user_id, sender, message) VALUES (?, ?, ?, ?)",
@@ -101,7 +145,7 @@ pub async fn admin_send_conversation(
     .await
     .map_err(ApiError::DatabaseError)?;
 
-    Ok(Json(msg))
+    Ok(Json(msg).into_response())
 }
 
 pub async fn admin_get_all_conversations(
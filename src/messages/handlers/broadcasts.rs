@@ -0,0 +1,304 @@
+// src/messages/handlers/broadcasts.rs
+//! Admin broadcasts to candidate segments
+
+use axum::{
+    extract::{Extension, Path, Query},
+    Json,
+};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::{error, info};
+
+use crate::auth::AuthedUser;
+use crate::common::{generate_broadcast_id, ApiError, AppState};
+use crate::messages::models::{
+    Broadcast, BroadcastDeliveryCounts, BroadcastPreview, BroadcastRecipient,
+    BroadcastRecipientsQuery, BroadcastResponse, BroadcastSegment, CreateBroadcastRequest,
+    CreateBroadcastResponse,
+};
+use crate::services::{broadcasts, snippets};
+
+const RECIPIENT_STATUSES: &[&str] = &["pending", "sent", "failed", "skipped"];
+
+async fn fetch_broadcast(state: &AppState, id: &str) -> Result<Broadcast, ApiError> {
+    sqlx::query_as::<_, Broadcast>("SELECT * FROM broadcasts WHERE id = ?")
+        .bind(id)
+        .fetch_optional(&state.db)
+        .await
+        .map_err(ApiError::DatabaseError)?
+        .ok_or_else(|| ApiError::NotFound("Broadcast not found".to_string()))
+}
+
+async fn delivery_counts(state: &AppState, id: &str) -> Result<BroadcastDeliveryCounts, ApiError> {
+    sqlx::query_as::<_, BroadcastDeliveryCounts>(
+        r#"
+        SELECT
+            COALESCE(SUM(status = 'pending'), 0) AS pending,
+            COALESCE(SUM(status = 'sent'), 0) AS sent,
+            COALESCE(SUM(status = 'failed'), 0) AS failed,
+            COALESCE(SUM(status = 'skipped'), 0) AS skipped
+        FROM broadcast_recipients
+        WHERE broadcast_id = ?
+        "#,
+    )
+    .bind(id)
+    .fetch_one(&state.db)
+    .await
+    .map_err(ApiError::DatabaseError)
+}
+
+/// POST /api/admin/broadcasts - Queue a message or email to a candidate segment
+///
+/// With `dry_run`, only counts the segment and renders the first recipient's copy.
+pub async fn create_broadcast(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Json(request): Json<CreateBroadcastRequest>,
+) -> Result<Json<CreateBroadcastResponse>, ApiError> {
+    let state = state_lock.read().await.clone();
+    authed.require_admin("Broadcast")?;
+
+    let channel = request
+        .channel
+        .as_deref()
+        .unwrap_or("message")
+        .trim()
+        .to_string();
+    let subject = request
+        .subject
+        .as_deref()
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(str::to_string);
+    // Snippets keep their placeholders, which are filled per recipient at delivery
+    let (body, _) = snippets::expand(&state.db, request.body.trim(), None, None).await?;
+    broadcasts::validate_broadcast(&request.segment, &channel, subject.as_deref(), &body)?;
+
+    let job_id = match &request.segment {
+        BroadcastSegment::JobApplicants { job_id } => Some(job_id.clone()),
+        BroadcastSegment::Stage { job_id, .. } => job_id.clone(),
+        BroadcastSegment::TalentPool => None,
+    };
+    if let Some(job_id) = &job_id {
+        let exists = sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM jobs WHERE id = ?")
+            .bind(job_id)
+            .fetch_one(&state.db)
+            .await
+            .map_err(ApiError::DatabaseError)?;
+        if exists == 0 {
+            return Err(ApiError::NotFound("Job not found".to_string()));
+        }
+    }
+
+    let members = broadcasts::resolve_segment(&state.db, &request.segment).await?;
+
+    let preview = match members.first() {
+        Some(first) => broadcasts::recipient_context(
+            &state.db,
+            &first.user_id,
+            first.application_id.as_deref(),
+        )
+        .await
+        .map_err(ApiError::DatabaseError)?
+        .map(|context| BroadcastPreview {
+            user_id: first.user_id.clone(),
+            subject: subject
+                .as_deref()
+                .map(|s| broadcasts::fill_recipient_placeholders(s, &context)),
+            body: broadcasts::fill_recipient_placeholders(&body, &context),
+        }),
+        None => None,
+    };
+
+    if request.dry_run {
+        return Ok(Json(CreateBroadcastResponse {
+            dry_run: true,
+            total_recipients: members.len(),
+            preview,
+            broadcast: None,
+        }));
+    }
+    if members.is_empty() {
+        return Err(ApiError::BadRequest(
+            "No candidates match this segment".to_string(),
+        ));
+    }
+
+    let stage = match &request.segment {
+        BroadcastSegment::Stage { stage, .. } => Some(stage.clone()),
+        _ => None,
+    };
+    let broadcast_id = generate_broadcast_id();
+
+    let mut tx = state.db.begin().await.map_err(ApiError::DatabaseError)?;
+    sqlx::query(
+        r#"
+        INSERT INTO broadcasts
+            (id, segment_type, job_id, stage, channel, subject, body, total_recipients, created_by)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+        "#,
+    )
+    .bind(&broadcast_id)
+    .bind(request.segment.as_str())
+    .bind(&job_id)
+    .bind(&stage)
+    .bind(&channel)
+    .bind(&subject)
+    .bind(&body)
+    .bind(members.len() as i64)
+    .bind(&authed.id)
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| {
+        error!(error = %e, "Database error creating broadcast");
+        ApiError::DatabaseError(e)
+    })?;
+
+    for member in &members {
+        sqlx::query(
+            "INSERT OR IGNORE INTO broadcast_recipients (broadcast_id, user_id, application_id) VALUES (?, ?, ?)",
+        )
+        .bind(&broadcast_id)
+        .bind(&member.user_id)
+        .bind(&member.application_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(ApiError::DatabaseError)?;
+    }
+    tx.commit().await.map_err(ApiError::DatabaseError)?;
+
+    info!(
+        broadcast_id = %broadcast_id,
+        segment = request.segment.as_str(),
+        channel = %channel,
+        recipients = members.len(),
+        admin_user_id = %authed.id,
+        "Broadcast queued"
+    );
+
+    Ok(Json(CreateBroadcastResponse {
+        dry_run: false,
+        total_recipients: members.len(),
+        preview,
+        broadcast: Some(fetch_broadcast(&state, &broadcast_id).await?),
+    }))
+}
+
+/// GET /api/admin/broadcasts - Broadcasts, newest first
+pub async fn list_broadcasts(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+) -> Result<Json<Vec<Broadcast>>, ApiError> {
+    let state = state_lock.read().await.clone();
+    authed.require_admin("Broadcast")?;
+
+    let list = sqlx::query_as::<_, Broadcast>(
+        "SELECT * FROM broadcasts ORDER BY created_at DESC, rowid DESC LIMIT 200",
+    )
+    .fetch_all(&state.db)
+    .await
+    .map_err(ApiError::DatabaseError)?;
+
+    Ok(Json(list))
+}
+
+/// GET /api/admin/broadcasts/:id - A broadcast with its delivery progress
+pub async fn get_broadcast(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Path(id): Path<String>,
+) -> Result<Json<BroadcastResponse>, ApiError> {
+    let state = state_lock.read().await.clone();
+    authed.require_admin("Broadcast")?;
+
+    let broadcast = fetch_broadcast(&state, &id).await?;
+    let delivery = delivery_counts(&state, &id).await?;
+
+    Ok(Json(BroadcastResponse {
+        broadcast,
+        delivery,
+    }))
+}
+
+/// GET /api/admin/broadcasts/:id/recipients - Per-recipient delivery status
+pub async fn list_broadcast_recipients(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Path(id): Path<String>,
+    Query(query): Query<BroadcastRecipientsQuery>,
+) -> Result<Json<Vec<BroadcastRecipient>>, ApiError> {
+    let state = state_lock.read().await.clone();
+    authed.require_admin("Broadcast")?;
+
+    if let Some(status) = query.status.as_deref() {
+        if !RECIPIENT_STATUSES.contains(&status) {
+            return Err(ApiError::BadRequest(format!(
+                "Invalid status '{}'; expected one of {}",
+                status,
+                RECIPIENT_STATUSES.join(", ")
+            )));
+        }
+    }
+    fetch_broadcast(&state, &id).await?;
+
+    let recipients = sqlx::query_as::<_, BroadcastRecipient>(
+        r#"
+        SELECT r.user_id, u.name, u.email, r.application_id, r.status, r.attempts,
+               r.message_id, r.error, r.sent_at
+        FROM broadcast_recipients r
+        LEFT JOIN users u ON u.id = r.user_id
+        WHERE r.broadcast_id = ? AND (? IS NULL OR r.status = ?)
+        ORDER BY r.rowid
+        "#,
+    )
+    .bind(&id)
+    .bind(&query.status)
+    .bind(&query.status)
+    .fetch_all(&state.db)
+    .await
+    .map_err(ApiError::DatabaseError)?;
+
+    Ok(Json(recipients))
+}
+
+/// POST /api/admin/broadcasts/:id/cancel - Stop a broadcast; undelivered recipients are skipped
+pub async fn cancel_broadcast(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Path(id): Path<String>,
+) -> Result<Json<BroadcastResponse>, ApiError> {
+    let state = state_lock.read().await.clone();
+    authed.require_admin("Broadcast")?;
+
+    let broadcast = fetch_broadcast(&state, &id).await?;
+    if !matches!(broadcast.status.as_str(), "queued" | "sending") {
+        return Err(ApiError::BadRequest(format!(
+            "Broadcast is already {}",
+            broadcast.status
+        )));
+    }
+
+    let mut tx = state.db.begin().await.map_err(ApiError::DatabaseError)?;
+    sqlx::query(
+        "UPDATE broadcasts SET status = 'cancelled', completed_at = datetime('now') WHERE id = ?",
+    )
+    .bind(&id)
+    .execute(&mut *tx)
+    .await
+    .map_err(ApiError::DatabaseError)?;
+    sqlx::query(
+        "UPDATE broadcast_recipients SET status = 'skipped' WHERE broadcast_id = ? AND status = 'pending'",
+    )
+    .bind(&id)
+    .execute(&mut *tx)
+    .await
+    .map_err(ApiError::DatabaseError)?;
+    tx.commit().await.map_err(ApiError::DatabaseError)?;
+
+    info!(broadcast_id = %id, admin_user_id = %authed.id, "Broadcast cancelled");
+
+    Ok(Json(BroadcastResponse {
+        broadcast: fetch_broadcast(&state, &id).await?,
+        delivery: delivery_counts(&state, &id).await?,
+    }))
+}
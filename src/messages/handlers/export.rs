@@ -0,0 +1,98 @@
+// src/messages/handlers/export.rs
+//! Conversation export for compliance requests
+
+use axum::{
+    extract::{Extension, Path, Query},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::info;
+
+use crate::auth::AuthedUser;
+use crate::common::{ApiError, AppState};
+use crate::messages::models::ConversationExportQuery;
+use crate::services::conversation_retention::{build_export, transcript_paragraphs};
+use crate::services::monitoring::{self, SecurityActivity};
+
+fn download(content_type: &str, filename: String, body: Vec<u8>) -> Response {
+    (
+        StatusCode::OK,
+        [
+            (header::CONTENT_TYPE, content_type.to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", filename),
+            ),
+        ],
+        body,
+    )
+        .into_response()
+}
+
+/// GET /api/admin/conversations/:user_id/export?format=json|pdf - Every message and an
+/// attachment manifest, archived or not
+pub async fn export_conversation(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Path(user_id): Path<String>,
+    Query(query): Query<ConversationExportQuery>,
+) -> Result<Response, ApiError> {
+    let state = state_lock.read().await.clone();
+    authed.require_admin("Conversation export")?;
+
+    let format = query
+        .format
+        .as_deref()
+        .map(|f| f.trim().to_lowercase())
+        .unwrap_or_else(|| "json".to_string());
+    if format != "json" && format != "pdf" {
+        return Err(ApiError::BadRequest(format!(
+            "Invalid format '{}'; expected json or pdf",
+            format
+        )));
+    }
+
+    let export = build_export(&state.db, &user_id, &authed.id).await?;
+
+    monitoring::record_security_activity(
+        &state,
+        SecurityActivity::Export,
+        Some(&authed.id),
+        None,
+        export.messages.len() as i64,
+        "/api/admin/conversations/:user_id/export",
+    )
+    .await;
+
+    info!(
+        conversation_user_id = %user_id,
+        admin_user_id = %authed.id,
+        format = %format,
+        messages = export.messages.len(),
+        attachments = export.attachments.len(),
+        "Conversation exported"
+    );
+
+    if format == "pdf" {
+        let title = format!("Conversation with {}", export.candidate_email);
+        let bytes = state
+            .pdf_service
+            .render_text_pdf(&title, &transcript_paragraphs(&export))
+            .map_err(|e| ApiError::ExportError(format!("Failed to render transcript: {}", e)))?;
+        return Ok(download(
+            "application/pdf",
+            format!("conversation_{}.pdf", user_id),
+            bytes,
+        ));
+    }
+
+    let json = serde_json::to_string_pretty(&export)
+        .map_err(|e| ApiError::ExportError(format!("Failed to serialize export: {}", e)))?;
+    Ok(download(
+        "application/json",
+        format!("conversation_{}.json", user_id),
+        json.into_bytes(),
+    ))
+}
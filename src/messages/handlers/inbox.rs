@@ -0,0 +1,250 @@
+// src/messages/handlers/inbox.rs
+//! Admin inbox: assign candidate conversations and mark them open, snoozed or closed
+
+use axum::{
+    extract::{Extension, Path, Query},
+    Json,
+};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::info;
+
+use crate::auth::AuthedUser;
+use crate::common::{ApiError, AppState};
+use crate::messages::models::{
+    AssignConversationRequest, InboxConversation, InboxQuery, UpdateConversationStateRequest,
+    WebSocketMessage,
+};
+use crate::services::inbox::{self, EFFECTIVE_STATE_SQL, INBOX_STATES};
+use crate::services::org;
+
+/// Conversations with their latest message; `filter` is appended to the WHERE clause
+fn inbox_sql(filter: &str) -> String {
+    format!(
+        r#"
+        SELECT
+            cm.user_id,
+            u.name AS user_name,
+            u.email AS user_email,
+            u.avatar AS user_avatar,
+            cm.message AS last_message,
+            cm.created_at AS last_message_at,
+            (SELECT COUNT(*) FROM conversation_messages
+             WHERE user_id = cm.user_id AND sender = 'user' AND is_read = 0) AS unread_count,
+            {state} AS state,
+            cs.assignee_id,
+            a.name AS assignee_name,
+            CASE WHEN {state} = 'snoozed' THEN cs.snoozed_until END AS snoozed_until,
+            cs.updated_at,
+            cs.archived_at
+        FROM conversation_messages cm
+        INNER JOIN users u ON u.id = cm.user_id
+        LEFT JOIN conversation_states cs ON cs.user_id = cm.user_id
+        LEFT JOIN users a ON a.id = cs.assignee_id
+        WHERE cm.id = (
+            SELECT id FROM conversation_messages cm2
+            WHERE cm2.user_id = cm.user_id
+            ORDER BY datetime(cm2.created_at) DESC
+            LIMIT 1
+        )
+        {filter}
+        "#,
+        state = EFFECTIVE_STATE_SQL,
+        filter = filter
+    )
+}
+
+async fn fetch_conversation(
+    state: &AppState,
+    user_id: &str,
+) -> Result<InboxConversation, ApiError> {
+    sqlx::query_as::<_, InboxConversation>(&inbox_sql("AND cm.user_id = ?"))
+        .bind(user_id)
+        .fetch_optional(&state.db)
+        .await
+        .map_err(ApiError::DatabaseError)?
+        .ok_or_else(|| ApiError::NotFound("Conversation not found".to_string()))
+}
+
+/// GET /api/admin/inbox - Conversations by latest message, filtered by `state` and
+/// `assignee` (`me`, `unassigned` or a user ID); `archived=true` lists archived ones instead
+pub async fn list_inbox(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Query(query): Query<InboxQuery>,
+) -> Result<Json<Vec<InboxConversation>>, ApiError> {
+    let state = state_lock.read().await.clone();
+    authed.require_admin("Inbox")?;
+
+    if let Some(inbox_state) = query.state.as_deref() {
+        if !INBOX_STATES.contains(&inbox_state) {
+            return Err(ApiError::BadRequest(format!(
+                "Invalid state '{}'; expected one of {}",
+                inbox_state,
+                INBOX_STATES.join(", ")
+            )));
+        }
+    }
+
+    let (assignee_id, unassigned) = match query.assignee.as_deref().map(str::trim) {
+        None | Some("") => (None, false),
+        Some("me") => (Some(authed.id.clone()), false),
+        Some("unassigned") => (None, true),
+        Some(user_id) => (Some(user_id.to_string()), false),
+    };
+
+    let filter = format!(
+        r#"
+        AND (? IS NULL OR {state} = ?)
+        AND (? IS NULL OR cs.assignee_id = ?)
+        AND (? = 0 OR cs.assignee_id IS NULL)
+        AND (cs.archived_at IS NOT NULL) = ?
+        ORDER BY datetime(cm.created_at) DESC
+        LIMIT 500
+        "#,
+        state = EFFECTIVE_STATE_SQL
+    );
+    let conversations = sqlx::query_as::<_, InboxConversation>(&inbox_sql(&filter))
+        .bind(&query.state)
+        .bind(&query.state)
+        .bind(&assignee_id)
+        .bind(&assignee_id)
+        .bind(unassigned)
+        .bind(query.archived)
+        .fetch_all(&state.db)
+        .await
+        .map_err(ApiError::DatabaseError)?;
+
+    Ok(Json(conversations))
+}
+
+/// GET /api/admin/inbox/:user_id
+pub async fn get_inbox_conversation(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Path(user_id): Path<String>,
+) -> Result<Json<InboxConversation>, ApiError> {
+    let state = state_lock.read().await.clone();
+    authed.require_admin("Inbox")?;
+
+    Ok(Json(fetch_conversation(&state, &user_id).await?))
+}
+
+/// PUT /api/admin/inbox/:user_id/assignee - Hand a conversation to an admin, or unassign it
+pub async fn assign_conversation(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Path(user_id): Path<String>,
+    Json(request): Json<AssignConversationRequest>,
+) -> Result<Json<InboxConversation>, ApiError> {
+    let state = state_lock.read().await.clone();
+    authed.require_admin("Inbox")?;
+
+    let current = fetch_conversation(&state, &user_id).await?;
+    let assignee_id = match request.assignee_id.as_deref().map(str::trim) {
+        None | Some("") => None,
+        Some("me") => Some(authed.id.clone()),
+        Some(id) => Some(org::load_assignable_user(&state, id).await?.id),
+    };
+    if assignee_id == current.assignee_id {
+        return Ok(Json(current));
+    }
+
+    sqlx::query(
+        r#"
+        INSERT INTO conversation_states (user_id, assignee_id, updated_by)
+        VALUES (?, ?, ?)
+        ON CONFLICT(user_id) DO UPDATE SET
+            assignee_id = excluded.assignee_id,
+            updated_by = excluded.updated_by,
+            updated_at = datetime('now')
+        "#,
+    )
+    .bind(&user_id)
+    .bind(&assignee_id)
+    .bind(&authed.id)
+    .execute(&state.db)
+    .await
+    .map_err(ApiError::DatabaseError)?;
+
+    info!(
+        conversation_user_id = %user_id,
+        assignee_id = ?assignee_id,
+        previous_assignee_id = ?current.assignee_id,
+        admin_user_id = %authed.id,
+        "Conversation assigned"
+    );
+
+    inbox::notify_admins(
+        &state,
+        WebSocketMessage::ConversationAssigned {
+            conversation_id: user_id.clone(),
+            assignee_id,
+            previous_assignee_id: current.assignee_id,
+            assigned_by: authed.id.clone(),
+        },
+    )
+    .await;
+
+    Ok(Json(fetch_conversation(&state, &user_id).await?))
+}
+
+/// PUT /api/admin/inbox/:user_id/state - Open, snooze until a time, or close a conversation;
+/// an archived conversation comes back to the live inbox
+pub async fn update_conversation_state(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Path(user_id): Path<String>,
+    Json(request): Json<UpdateConversationStateRequest>,
+) -> Result<Json<InboxConversation>, ApiError> {
+    let state = state_lock.read().await.clone();
+    authed.require_admin("Inbox")?;
+
+    let inbox_state = request.state.trim().to_lowercase();
+    let snoozed_until = inbox::validate_state_change(
+        &inbox_state,
+        request.snoozed_until.as_deref(),
+        chrono::Utc::now(),
+    )?;
+    fetch_conversation(&state, &user_id).await?;
+
+    sqlx::query(
+        r#"
+        INSERT INTO conversation_states (user_id, state, snoozed_until, updated_by)
+        VALUES (?, ?, ?, ?)
+        ON CONFLICT(user_id) DO UPDATE SET
+            state = excluded.state,
+            snoozed_until = excluded.snoozed_until,
+            archived_at = NULL,
+            updated_by = excluded.updated_by,
+            updated_at = datetime('now')
+        "#,
+    )
+    .bind(&user_id)
+    .bind(&inbox_state)
+    .bind(&snoozed_until)
+    .bind(&authed.id)
+    .execute(&state.db)
+    .await
+    .map_err(ApiError::DatabaseError)?;
+
+    info!(
+        conversation_user_id = %user_id,
+        state = %inbox_state,
+        admin_user_id = %authed.id,
+        "Conversation state changed"
+    );
+
+    inbox::notify_admins(
+        &state,
+        WebSocketMessage::ConversationStateChanged {
+            conversation_id: user_id.clone(),
+            state: inbox_state,
+            snoozed_until,
+            changed_by: authed.id.clone(),
+        },
+    )
+    .await;
+
+    Ok(Json(fetch_conversation(&state, &user_id).await?))
+}
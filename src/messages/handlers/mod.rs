@@ -1,3 +1,9 @@
 pub mod admin;
+pub mod broadcasts;
+pub mod export;
+pub mod inbox;
+pub mod scheduled;
+pub mod search;
+pub mod snippets;
 pub mod user;
 pub mod websocket;
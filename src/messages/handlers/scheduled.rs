@@ -0,0 +1,271 @@
+// src/messages/handlers/scheduled.rs
+//! Admin view of scheduled messages and emails, with edit and cancel before they are sent
+
+use axum::{
+    extract::{Extension, Path, Query},
+    Json,
+};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::info;
+
+use crate::auth::AuthedUser;
+use crate::common::{ApiError, AppState};
+use crate::messages::models::{
+    ScheduleInput, ScheduledMessage, ScheduledMessageResponse, ScheduledMessagesQuery,
+    UpdateScheduledMessageRequest,
+};
+use crate::messages::validators;
+use crate::services::sanitize::sanitize_message;
+use crate::services::scheduled_messages::{self, SCHEDULED_STATUSES};
+use crate::services::snippets;
+
+async fn fetch_scheduled(state: &AppState, id: &str) -> Result<ScheduledMessage, ApiError> {
+    sqlx::query_as::<_, ScheduledMessage>("SELECT * FROM scheduled_messages WHERE id = ?")
+        .bind(id)
+        .fetch_optional(&state.db)
+        .await
+        .map_err(ApiError::DatabaseError)?
+        .ok_or_else(|| ApiError::NotFound("Scheduled message not found".to_string()))
+}
+
+fn require_pending(scheduled: &ScheduledMessage) -> Result<(), ApiError> {
+    if scheduled.status != "scheduled" {
+        return Err(ApiError::BadRequest(format!(
+            "Scheduled message is already {}",
+            scheduled.status
+        )));
+    }
+    Ok(())
+}
+
+/// GET /api/admin/scheduled-messages - Scheduled messages, soonest first
+pub async fn list_scheduled_messages(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Query(query): Query<ScheduledMessagesQuery>,
+) -> Result<Json<Vec<ScheduledMessageResponse>>, ApiError> {
+    let state = state_lock.read().await.clone();
+    authed.require_admin("Scheduled message")?;
+
+    if let Some(status) = query.status.as_deref() {
+        if !SCHEDULED_STATUSES.contains(&status) {
+            return Err(ApiError::BadRequest(format!(
+                "Invalid status '{}'; expected one of {}",
+                status,
+                SCHEDULED_STATUSES.join(", ")
+            )));
+        }
+    }
+
+    let list = sqlx::query_as::<_, ScheduledMessage>(
+        r#"
+        SELECT * FROM scheduled_messages
+        WHERE (? IS NULL OR status = ?)
+          AND (? IS NULL OR kind = ?)
+          AND (? IS NULL OR user_id = ?)
+        ORDER BY send_at
+        LIMIT 500
+        "#,
+    )
+    .bind(&query.status)
+    .bind(&query.status)
+    .bind(&query.kind)
+    .bind(&query.kind)
+    .bind(&query.user_id)
+    .bind(&query.user_id)
+    .fetch_all(&state.db)
+    .await
+    .map_err(ApiError::DatabaseError)?;
+
+    Ok(Json(
+        list.into_iter()
+            .map(scheduled_messages::to_response)
+            .collect(),
+    ))
+}
+
+/// GET /api/admin/scheduled-messages/:id
+pub async fn get_scheduled_message(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Path(id): Path<String>,
+) -> Result<Json<ScheduledMessageResponse>, ApiError> {
+    let state = state_lock.read().await.clone();
+    authed.require_admin("Scheduled message")?;
+
+    let scheduled = fetch_scheduled(&state, &id).await?;
+    Ok(Json(scheduled_messages::to_response(scheduled)))
+}
+
+/// PUT /api/admin/scheduled-messages/:id - Change the content or send time before it goes out
+pub async fn update_scheduled_message(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Path(id): Path<String>,
+    Json(request): Json<UpdateScheduledMessageRequest>,
+) -> Result<Json<ScheduledMessageResponse>, ApiError> {
+    let state = state_lock.read().await.clone();
+    authed.require_admin("Scheduled message")?;
+
+    let scheduled = fetch_scheduled(&state, &id).await?;
+    require_pending(&scheduled)?;
+
+    let is_email = scheduled.kind == "email";
+    if !is_email && (request.subject.is_some() || request.cc.is_some()) {
+        return Err(ApiError::BadRequest(
+            "Only scheduled emails have a subject or CC".to_string(),
+        ));
+    }
+
+    let expanded_body = match request.body.as_deref() {
+        Some(body) => Some(
+            snippets::expand(
+                &state.db,
+                body.trim(),
+                Some(&scheduled.user_id),
+                scheduled.application_id.as_deref(),
+            )
+            .await?
+            .0,
+        ),
+        None => None,
+    };
+    let body = match expanded_body.as_deref() {
+        Some(body) if is_email => {
+            let body = body.trim();
+            if body.is_empty() {
+                return Err(ApiError::ValidationError(
+                    "Email content cannot be empty".to_string(),
+                ));
+            }
+            body.to_string()
+        }
+        Some(body) => {
+            let body = sanitize_message(body.trim());
+            validators::validate_message_content(&body)?;
+            body
+        }
+        None => scheduled.body.clone(),
+    };
+    let subject = match request.subject.as_deref().map(str::trim) {
+        Some("") => {
+            return Err(ApiError::ValidationError(
+                "Email subject cannot be empty".to_string(),
+            ))
+        }
+        Some(subject) => Some(subject.to_string()),
+        None => scheduled.subject.clone(),
+    };
+    let cc = match &request.cc {
+        Some(cc) if cc.is_empty() => None,
+        Some(cc) => Some(serde_json::to_string(cc).unwrap_or_default()),
+        None => scheduled.cc.clone(),
+    };
+
+    let (send_at, timezone, business_hours) = if request.send_at.is_some()
+        || request.timezone.is_some()
+        || request.business_hours.is_some()
+    {
+        // Unchanged fields keep their current values; the stored instant is UTC
+        let schedule = ScheduleInput {
+            send_at: Some(
+                request
+                    .send_at
+                    .clone()
+                    .unwrap_or_else(|| format!("{}Z", scheduled.send_at.replace(' ', "T"))),
+            ),
+            timezone: Some(
+                request
+                    .timezone
+                    .clone()
+                    .unwrap_or_else(|| scheduled.timezone.clone()),
+            ),
+            business_hours: request.business_hours.unwrap_or(scheduled.business_hours),
+        };
+        let (send_at, timezone) = scheduled_messages::resolve_send_at(
+            &state.db,
+            &state.settings_service,
+            &scheduled.user_id,
+            &schedule,
+        )
+        .await?
+        .ok_or_else(|| ApiError::BadRequest("A send time is required".to_string()))?;
+        (
+            scheduled_messages::format_send_at(send_at),
+            timezone,
+            schedule.business_hours,
+        )
+    } else {
+        (
+            scheduled.send_at.clone(),
+            scheduled.timezone.clone(),
+            scheduled.business_hours,
+        )
+    };
+
+    let result = sqlx::query(
+        r#"
+        UPDATE scheduled_messages
+        SET subject = ?, body = ?, cc = ?, send_at = ?, timezone = ?, business_hours = ?,
+            updated_at = datetime('now')
+        WHERE id = ? AND status = 'scheduled'
+        "#,
+    )
+    .bind(&subject)
+    .bind(&body)
+    .bind(&cc)
+    .bind(&send_at)
+    .bind(&timezone)
+    .bind(business_hours)
+    .bind(&id)
+    .execute(&state.db)
+    .await
+    .map_err(ApiError::DatabaseError)?;
+    if result.rows_affected() == 0 {
+        return Err(ApiError::BadRequest(
+            "Scheduled message is already being sent".to_string(),
+        ));
+    }
+
+    info!(
+        scheduled_message_id = %id,
+        send_at = %send_at,
+        admin_user_id = %authed.id,
+        "Scheduled message updated"
+    );
+
+    let updated = fetch_scheduled(&state, &id).await?;
+    Ok(Json(scheduled_messages::to_response(updated)))
+}
+
+/// POST /api/admin/scheduled-messages/:id/cancel - Drop a message that hasn't been sent
+pub async fn cancel_scheduled_message(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Path(id): Path<String>,
+) -> Result<Json<ScheduledMessageResponse>, ApiError> {
+    let state = state_lock.read().await.clone();
+    authed.require_admin("Scheduled message")?;
+
+    let scheduled = fetch_scheduled(&state, &id).await?;
+    require_pending(&scheduled)?;
+
+    let result = sqlx::query(
+        "UPDATE scheduled_messages SET status = 'cancelled', updated_at = datetime('now') WHERE id = ? AND status = 'scheduled'",
+    )
+    .bind(&id)
+    .execute(&state.db)
+    .await
+    .map_err(ApiError::DatabaseError)?;
+    if result.rows_affected() == 0 {
+        return Err(ApiError::BadRequest(
+            "Scheduled message is already being sent".to_string(),
+        ));
+    }
+
+    info!(scheduled_message_id = %id, admin_user_id = %authed.id, "Scheduled message cancelled");
+
+    let cancelled = fetch_scheduled(&state, &id).await?;
+    Ok(Json(scheduled_messages::to_response(cancelled)))
+}
@@ -0,0 +1,102 @@
+// src/messages/handlers/search.rs
+//! Full-text search over conversation messages
+
+use axum::{
+    extract::{Extension, Query},
+    Json,
+};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::error;
+
+use crate::auth::AuthedUser;
+use crate::common::{ApiError, AppState};
+use crate::messages::models::{MessageSearchHit, MessageSearchQuery, MessageSearchResponse};
+use crate::services::search::{
+    message_link, phrase_query, DEFAULT_MESSAGE_RESULTS, MAX_MESSAGE_RESULTS, MIN_QUERY_LENGTH,
+};
+
+/// GET /api/messages/search?q= - Matching messages, newest first, with the surrounding text
+///
+/// Candidates search their own conversation; admins search every conversation, or one
+/// candidate's with `user_id`.
+pub async fn search_messages(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Query(query): Query<MessageSearchQuery>,
+) -> Result<Json<MessageSearchResponse>, ApiError> {
+    let state = state_lock.read().await.clone();
+
+    let q = query.q.trim().to_string();
+    if q.chars().count() < MIN_QUERY_LENGTH {
+        return Err(ApiError::ValidationError(format!(
+            "q must be at least {} characters",
+            MIN_QUERY_LENGTH
+        )));
+    }
+    let conversation_id = if authed.is_admin {
+        query.user_id.clone().filter(|id| !id.trim().is_empty())
+    } else {
+        Some(authed.id.clone())
+    };
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_MESSAGE_RESULTS)
+        .clamp(1, MAX_MESSAGE_RESULTS);
+    let offset = query.offset.unwrap_or(0).max(0);
+    let phrase = phrase_query(&q);
+
+    let mut results = sqlx::query_as::<_, MessageSearchHit>(
+        r#"
+        SELECT
+            m.id AS message_id,
+            m.user_id AS conversation_id,
+            u.name AS user_name,
+            m.sender,
+            snippet(conversation_messages_fts, 0, '<mark>', '</mark>', '…', 64) AS snippet,
+            m.created_at
+        FROM conversation_messages_fts
+        JOIN conversation_messages m ON m.rowid = conversation_messages_fts.rowid
+        LEFT JOIN users u ON u.id = m.user_id
+        WHERE conversation_messages_fts MATCH ? AND (? IS NULL OR m.user_id = ?)
+        ORDER BY datetime(m.created_at) DESC
+        LIMIT ? OFFSET ?
+        "#,
+    )
+    .bind(&phrase)
+    .bind(&conversation_id)
+    .bind(&conversation_id)
+    .bind(limit)
+    .bind(offset)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|e| {
+        error!(error = %e, user_id = %authed.id, "Database error searching messages");
+        ApiError::DatabaseError(e)
+    })?;
+
+    let total = sqlx::query_scalar::<_, i64>(
+        r#"
+        SELECT COUNT(*)
+        FROM conversation_messages_fts
+        JOIN conversation_messages m ON m.rowid = conversation_messages_fts.rowid
+        WHERE conversation_messages_fts MATCH ? AND (? IS NULL OR m.user_id = ?)
+        "#,
+    )
+    .bind(&phrase)
+    .bind(&conversation_id)
+    .bind(&conversation_id)
+    .fetch_one(&state.db)
+    .await
+    .map_err(ApiError::DatabaseError)?;
+
+    for hit in &mut results {
+        hit.link = message_link(authed.is_admin, &hit.conversation_id, &hit.message_id);
+    }
+
+    Ok(Json(MessageSearchResponse {
+        query: q,
+        results,
+        total,
+    }))
+}
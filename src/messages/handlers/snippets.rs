@@ -0,0 +1,215 @@
+// src/messages/handlers/snippets.rs
+//! Snippet library for recruiter messaging
+
+use axum::{
+    extract::{Extension, Path, Query},
+    http::StatusCode,
+    Json,
+};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::{error, info};
+
+use crate::auth::AuthedUser;
+use crate::common::{generate_snippet_id, ApiError, AppState};
+use crate::messages::models::{
+    CreateSnippetRequest, ExpandSnippetsRequest, ExpandSnippetsResponse, MessageResponse, Snippet,
+    SnippetsQuery, UpdateSnippetRequest,
+};
+use crate::services::snippets;
+
+async fn fetch_snippet(state: &AppState, id: &str) -> Result<Snippet, ApiError> {
+    sqlx::query_as::<_, Snippet>("SELECT * FROM message_snippets WHERE id = ?")
+        .bind(id)
+        .fetch_optional(&state.db)
+        .await
+        .map_err(ApiError::DatabaseError)?
+        .ok_or_else(|| ApiError::NotFound("Snippet not found".to_string()))
+}
+
+/// Refuse a shortcut another snippet already uses
+async fn ensure_shortcut_free(
+    state: &AppState,
+    shortcut: &str,
+    except_id: Option<&str>,
+) -> Result<(), ApiError> {
+    let taken = sqlx::query_scalar::<_, i64>(
+        "SELECT COUNT(*) FROM message_snippets WHERE shortcut = ? AND id != COALESCE(?, '')",
+    )
+    .bind(shortcut)
+    .bind(except_id)
+    .fetch_one(&state.db)
+    .await
+    .map_err(ApiError::DatabaseError)?;
+    if taken > 0 {
+        return Err(ApiError::BadRequest(format!(
+            "The shortcut /{} is already in use",
+            shortcut
+        )));
+    }
+    Ok(())
+}
+
+/// GET /api/admin/snippets - Snippets by shortcut, optionally filtered by `q`
+pub async fn list_snippets(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Query(query): Query<SnippetsQuery>,
+) -> Result<Json<Vec<Snippet>>, ApiError> {
+    let state = state_lock.read().await.clone();
+    authed.require_admin("Snippet")?;
+
+    let pattern = query
+        .q
+        .as_deref()
+        .map(|q| q.trim().trim_start_matches('/'))
+        .filter(|q| !q.is_empty())
+        .map(|q| format!("%{}%", q));
+
+    let list = sqlx::query_as::<_, Snippet>(
+        r#"
+        SELECT * FROM message_snippets
+        WHERE ? IS NULL OR shortcut LIKE ? OR title LIKE ?
+        ORDER BY shortcut
+        "#,
+    )
+    .bind(&pattern)
+    .bind(&pattern)
+    .bind(&pattern)
+    .fetch_all(&state.db)
+    .await
+    .map_err(ApiError::DatabaseError)?;
+
+    Ok(Json(list))
+}
+
+/// POST /api/admin/snippets - Add a snippet to the shared library
+pub async fn create_snippet(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Json(request): Json<CreateSnippetRequest>,
+) -> Result<(StatusCode, Json<Snippet>), ApiError> {
+    let state = state_lock.read().await.clone();
+    authed.require_admin("Snippet")?;
+
+    let shortcut = snippets::normalize_shortcut(&request.shortcut)?;
+    snippets::validate_snippet(&request.title, &request.body)?;
+    ensure_shortcut_free(&state, &shortcut, None).await?;
+
+    let id = generate_snippet_id();
+    sqlx::query(
+        "INSERT INTO message_snippets (id, shortcut, title, body, created_by) VALUES (?, ?, ?, ?, ?)",
+    )
+    .bind(&id)
+    .bind(&shortcut)
+    .bind(request.title.trim())
+    .bind(request.body.trim())
+    .bind(&authed.id)
+    .execute(&state.db)
+    .await
+    .map_err(|e| {
+        error!(error = %e, shortcut = %shortcut, "Database error creating snippet");
+        ApiError::DatabaseError(e)
+    })?;
+
+    info!(snippet_id = %id, shortcut = %shortcut, admin_user_id = %authed.id, "Snippet created");
+
+    Ok((StatusCode::CREATED, Json(fetch_snippet(&state, &id).await?)))
+}
+
+/// GET /api/admin/snippets/:id
+pub async fn get_snippet(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Path(id): Path<String>,
+) -> Result<Json<Snippet>, ApiError> {
+    let state = state_lock.read().await.clone();
+    authed.require_admin("Snippet")?;
+
+    Ok(Json(fetch_snippet(&state, &id).await?))
+}
+
+/// PUT /api/admin/snippets/:id
+pub async fn update_snippet(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Path(id): Path<String>,
+    Json(request): Json<UpdateSnippetRequest>,
+) -> Result<Json<Snippet>, ApiError> {
+    let state = state_lock.read().await.clone();
+    authed.require_admin("Snippet")?;
+
+    let existing = fetch_snippet(&state, &id).await?;
+    let shortcut = match request.shortcut.as_deref() {
+        Some(shortcut) => snippets::normalize_shortcut(shortcut)?,
+        None => existing.shortcut,
+    };
+    let title = request.title.unwrap_or(existing.title);
+    let body = request.body.unwrap_or(existing.body);
+    snippets::validate_snippet(&title, &body)?;
+    ensure_shortcut_free(&state, &shortcut, Some(&id)).await?;
+
+    sqlx::query(
+        r#"
+        UPDATE message_snippets
+        SET shortcut = ?, title = ?, body = ?, updated_at = datetime('now')
+        WHERE id = ?
+        "#,
+    )
+    .bind(&shortcut)
+    .bind(title.trim())
+    .bind(body.trim())
+    .bind(&id)
+    .execute(&state.db)
+    .await
+    .map_err(ApiError::DatabaseError)?;
+
+    info!(snippet_id = %id, shortcut = %shortcut, admin_user_id = %authed.id, "Snippet updated");
+
+    Ok(Json(fetch_snippet(&state, &id).await?))
+}
+
+/// DELETE /api/admin/snippets/:id
+pub async fn delete_snippet(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Path(id): Path<String>,
+) -> Result<Json<MessageResponse>, ApiError> {
+    let state = state_lock.read().await.clone();
+    authed.require_admin("Snippet")?;
+
+    let result = sqlx::query("DELETE FROM message_snippets WHERE id = ?")
+        .bind(&id)
+        .execute(&state.db)
+        .await
+        .map_err(ApiError::DatabaseError)?;
+    if result.rows_affected() == 0 {
+        return Err(ApiError::NotFound("Snippet not found".to_string()));
+    }
+
+    info!(snippet_id = %id, admin_user_id = %authed.id, "Snippet deleted");
+
+    Ok(Json(MessageResponse {
+        message: "Snippet deleted".to_string(),
+    }))
+}
+
+/// POST /api/admin/snippets/expand - Preview a draft with its shortcuts expanded
+pub async fn expand_snippets(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Json(request): Json<ExpandSnippetsRequest>,
+) -> Result<Json<ExpandSnippetsResponse>, ApiError> {
+    let state = state_lock.read().await.clone();
+    authed.require_admin("Snippet")?;
+
+    let (text, expanded) = snippets::expand(
+        &state.db,
+        &request.text,
+        request.user_id.as_deref(),
+        request.application_id.as_deref(),
+    )
+    .await?;
+
+    Ok(Json(ExpandSnippetsResponse { text, expanded }))
+}
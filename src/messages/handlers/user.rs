@@ -6,6 +6,7 @@ use crate::common::error::ApiError;
 use crate::common::id_generator::generate_message_id;
 use crate::common::state::AppState;
 use crate::messages::models::{CliMessage, ConversationInput, ConversationMessage, EnhancedConversationMessage, MessageAttachment};
+use crate::services::{inbox, message_routing};
 use axum::{
     extract::Path,
     http::{header, StatusCode},
@@ -102,6 +103,20 @@ pub async fn user_send_conversation(
         ApiError::DatabaseError(e)
     })?;
 
+    inbox::reopen_on_reply(&state, &authed.id).await;
+    message_routing::route_in_background(
+        &state,
+        EnhancedConversationMessage {
+            id: message_id_str.clone(),
+            user_id: authed.id.clone(),
+            sender: "user".to_string(),
+            message: message.clone(),
+            attachments: vec![],
+            is_read: false,
+            created_at: Some(created_at.clone()),
+        },
+    );
+
     // Return CLI-compatible format
     Ok(Json(CliMessage {
         id: message_id_str,
@@ -147,13 +162,30 @@ pub async fn mark_conversation_read(
 /// GET /api/attachments/:filename - Serve attachment file
 pub async fn serve_attachment(
     Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
-    _authed: AuthedUser,
+    authed: AuthedUser,
     Path(filename): Path<String>,
 ) -> Result<impl IntoResponse, ApiError> {
     let state = state_lock.read().await;
     
     // Sanitize filename to prevent directory traversal
     let safe_filename = filename.replace("..", "").replace("/", "").replace("\\", "");
+
+    // Only the candidate in the conversation (or an admin) may fetch its attachments
+    let owner: Option<String> = sqlx::query_scalar(
+        r#"
+        SELECT m.user_id
+        FROM message_attachments a
+        JOIN conversation_messages m ON m.id = a.message_id
+        WHERE a.filename = ?
+        "#,
+    )
+    .bind(&safe_filename)
+    .fetch_optional(&state.db)
+    .await
+    .map_err(ApiError::DatabaseError)?;
+    if !authed.is_admin && owner.as_deref() != Some(authed.id.as_str()) {
+        return Err(ApiError::NotFound("Attachment not found".to_string()));
+    }
     
     // Look for the file in the attachments directory
     let attachments_dir = state.resumes_dir.join("attachments");
@@ -191,7 +223,7 @@ pub async fn serve_attachment(
         StatusCode::OK,
         [
             (header::CONTENT_TYPE, content_type.to_string()),
-            (header::CACHE_CONTROL, "public, max-age=31536000".to_string()),
+            (header::CACHE_CONTROL, "private, max-age=3600".to_string()),
         ],
         content,
     ))
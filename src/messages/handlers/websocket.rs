@@ -1,15 +1,25 @@
 use crate::auth::extractors::AuthedUser;
+use crate::auth::models::User;
 use crate::common::error::ApiError;
 use crate::common::id_generator::{generate_connection_id, generate_raw_id};
+use crate::common::shutdown;
 use crate::common::state::AppState;
+use crate::jobs::services::editing;
 use crate::messages::models::{EnhancedConversationMessage, WebSocketMessage};
+use crate::messages::services::websocket_service::SEND_BUFFER_SIZE;
 use crate::messages::services::{ConnectionManager, MessageService, PresenceService};
 use crate::messages::validators;
+use crate::services::moderation::{self, ModeratedContent};
+use crate::services::sanitize::sanitize_message;
+use crate::services::snippets;
+use crate::services::storage_usage;
+use crate::services::{inbox, message_routing};
 use axum::{
     extract::{
         ws::{Message, WebSocket},
         Query, WebSocketUpgrade,
     },
+    http::{header::AUTHORIZATION, HeaderMap},
     response::IntoResponse,
     Extension,
 };
@@ -19,55 +29,108 @@ use std::sync::Arc;
 use tokio::sync::{mpsc, RwLock};
 use tracing::{debug, error, info, warn};
 
+/// Most messages replayed to a resuming connection; older ones are left to a full reload
+const MAX_REPLAYED_MESSAGES: i64 = 500;
+
+/// Check a JWT and load the account it belongs to, returning the user and when the token expires
+async fn authenticate_token(
+    state: &AppState,
+    token: &str,
+) -> Result<(AuthedUser, chrono::DateTime<chrono::Utc>), ApiError> {
+    let claims = state
+        .jwt_keys
+        .verify(&state.db, token, &state.jwt_secret)
+        .await
+        .map_err(|e| {
+            warn!(error = %e, "WebSocket token validation failed");
+            ApiError::Unauthorized("Invalid token".to_string())
+        })?;
+    let expires_at = chrono::DateTime::from_timestamp(claims.exp as i64, 0)
+        .ok_or_else(|| ApiError::Unauthorized("Invalid token".to_string()))?;
+
+    let user = sqlx::query_as::<_, User>("SELECT * FROM users WHERE id = ?")
+        .bind(&claims.sub)
+        .fetch_optional(&state.db)
+        .await
+        .map_err(ApiError::DatabaseError)?
+        .ok_or_else(|| ApiError::Unauthorized("User not found".to_string()))?;
+    if user.merged_into.is_some() {
+        return Err(ApiError::Unauthorized(
+            "account merged; sign in again".to_string(),
+        ));
+    }
+
+    // Check if user is admin based on admin_emails list
+    let is_admin = state.admin_emails.contains(&user.email.to_lowercase());
+
+    Ok((
+        AuthedUser {
+            id: user.id,
+            email: user.email,
+            is_admin,
+        },
+        expires_at,
+    ))
+}
+
 /// WebSocket upgrade handler
+///
+/// The token comes from an `Authorization: Bearer` header or, for browsers, the `token` query
+/// parameter. A reconnecting client passes the ID of the last message it received as
+/// `last_event_id` (or a `Last-Event-ID` header) to have what it missed replayed.
 pub async fn websocket_handler(
     ws: WebSocketUpgrade,
     Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    headers: HeaderMap,
     Query(params): Query<HashMap<String, String>>,
 ) -> Result<impl IntoResponse, ApiError> {
-    // Extract JWT token from query params or headers
-    let token = params
-        .get("token")
+    let header_token = headers
+        .get(AUTHORIZATION)
+        .and_then(|h| h.to_str().ok())
+        .map(|h| h.strip_prefix("Bearer ").unwrap_or(h).to_string());
+    let token = header_token
+        .or_else(|| params.get("token").cloned())
         .ok_or_else(|| ApiError::Unauthorized("Missing authentication token".to_string()))?;
 
-    // Validate JWT and extract user info
-    let state = state_lock.read().await.clone();
-    let claims = crate::auth::handlers::validate_jwt(token)?;
-
-    // Fetch user from database
-    let user = sqlx::query_as::<_, crate::auth::models::User>("SELECT * FROM users WHERE id = ?")
-        .bind(&claims.sub)
-        .fetch_optional(&state.db)
-        .await
-        .map_err(ApiError::DatabaseError)?
-        .ok_or_else(|| ApiError::Unauthorized("User not found".to_string()))?;
+    if shutdown::is_requested() {
+        return Err(ApiError::ServiceUnavailable(
+            "Server is shutting down; reconnect shortly".to_string(),
+        ));
+    }
 
-    // Check if user is admin based on admin_emails list
-    let user_email_lower = user.email.to_lowercase();
-    let is_admin = state.admin_emails.contains(&user_email_lower);
+    let state = state_lock.read().await.clone();
+    let (authed_user, expires_at) = authenticate_token(&state, &token).await?;
 
-    let authed_user = AuthedUser {
-        id: user.id.clone(),
-        email: user.email.clone(),
-        is_admin,
-    };
+    let last_event_id = params.get("last_event_id").cloned().or_else(|| {
+        headers
+            .get("last-event-id")
+            .and_then(|h| h.to_str().ok())
+            .map(str::to_string)
+    });
 
     info!(
         user_id = %authed_user.id,
         email = %authed_user.email,
+        resuming = last_event_id.is_some(),
         "WebSocket connection authenticated"
     );
 
     // Upgrade the connection
-    Ok(ws.on_upgrade(move |socket| handle_socket(socket, authed_user, state_lock)))
+    Ok(ws.on_upgrade(move |socket| {
+        handle_socket(socket, authed_user, expires_at, last_event_id, state_lock)
+    }))
 }
 
 /// Handle WebSocket connection
 async fn handle_socket(
     socket: WebSocket,
     authed_user: AuthedUser,
+    expires_at: chrono::DateTime<chrono::Utc>,
+    last_event_id: Option<String>,
     state_lock: Arc<RwLock<AppState>>,
 ) {
+    // Shutdown closes the socket and waits for the cleanup below
+    let _in_flight = shutdown::track();
     let connection_id = generate_connection_id();
     let user_id = authed_user.id.clone();
 
@@ -85,12 +148,16 @@ async fn handle_socket(
     // Split the socket into sender and receiver
     let (mut sender, mut receiver) = socket.split();
 
-    // Create a channel for sending messages to this connection
-    let (tx, mut rx) = mpsc::unbounded_channel::<Message>();
+    // Create a bounded channel for sending messages to this connection; the manager holds the
+    // only sender, so unregistering the connection ends the send task
+    let (tx, mut rx) = mpsc::channel::<Message>(SEND_BUFFER_SIZE);
 
     // Register the connection
     connection_manager
-        .register(user_id.clone(), connection_id.clone(), tx.clone())
+        .register(user_id.clone(), connection_id.clone(), tx)
+        .await;
+    connection_manager
+        .set_expiry(&connection_id, expires_at)
         .await;
 
     // Send connected message
@@ -101,21 +168,48 @@ async fn handle_socket(
         let _ = sender.send(Message::Text(json)).await;
     }
 
-    // Get missed messages (messages sent while user was offline)
-    let last_disconnect = presence_service.get_last_seen(&user_id).await;
-    if let Some(since) = last_disconnect {
-        match message_service.get_messages_since(&user_id, since).await {
-            Ok(missed) if !missed.is_empty() => {
-                let missed_msg = WebSocketMessage::MissedMessages {
-                    count: missed.len(),
-                    messages: missed,
-                };
-                if let Ok(json) = serde_json::to_string(&missed_msg) {
-                    let _ = sender.send(Message::Text(json)).await;
-                }
+    // Replay what a resuming client missed: its own conversation, or every conversation for admins
+    let replay = if let Some(last_event_id) = &last_event_id {
+        let scope = (!authed_user.is_admin).then_some(user_id.as_str());
+        match message_service
+            .get_messages_after(last_event_id, scope, MAX_REPLAYED_MESSAGES)
+            .await
+        {
+            Ok(Some(missed)) if !missed.is_empty() => Some(WebSocketMessage::MissedMessages {
+                count: missed.len(),
+                messages: missed,
+            }),
+            Ok(Some(_)) => None,
+            Ok(None) => Some(WebSocketMessage::Error {
+                code: "UNKNOWN_EVENT_ID".to_string(),
+                message: "Cannot resume from that event; reload the conversation".to_string(),
+            }),
+            Err(e) => {
+                warn!(user_id = %user_id, error = %e, "Failed to load messages to replay");
+                None
             }
-            _ => {}
         }
+    } else if let Some(since) = presence_service.get_last_seen(&user_id).await {
+        // Without an event ID, fall back to what arrived since the user was last seen
+        match message_service
+            .get_messages_since(&user_id, since, MAX_REPLAYED_MESSAGES)
+            .await
+        {
+            Ok(missed) if !missed.is_empty() => Some(WebSocketMessage::MissedMessages {
+                count: missed.len(),
+                messages: missed,
+            }),
+            Ok(_) => None,
+            Err(e) => {
+                warn!(user_id = %user_id, error = %e, "Failed to load messages to replay");
+                None
+            }
+        }
+    } else {
+        None
+    };
+    if let Some(json) = replay.and_then(|msg| serde_json::to_string(&msg).ok()) {
+        let _ = sender.send(Message::Text(json)).await;
     }
 
     // Mark user as online
@@ -199,6 +293,9 @@ async fn handle_socket(
 
     // Cleanup: unregister connection and mark user as offline
     connection_manager.unregister(&connection_id).await;
+    for (job_id, editors) in state.job_editors.leave_all(&connection_id).await {
+        editing::broadcast_editors(&state, &job_id, editors).await;
+    }
 
     // Only mark as offline if user has no other connections
     if connection_manager.get_user_connection_count(&user_id).await == 0 {
@@ -224,9 +321,11 @@ async fn handle_socket(
             .await;
     }
 
+    let total_connections = connection_manager.get_total_connections().await;
     info!(
         user_id = %user_id,
         connection_id = %connection_id,
+        total_connections,
         "WebSocket connection closed"
     );
 }
@@ -346,6 +445,37 @@ async fn handle_websocket_message(
         WebSocketMessage::MarkRead { message_id } => {
             handle_mark_read(&message_id, user_id, connection_manager, state_lock).await?;
         }
+        WebSocketMessage::Authenticate { token } => {
+            let state = state_lock.read().await.clone();
+            let (renewed, expires_at) = authenticate_token(&state, &token).await?;
+            if renewed.id != user_id {
+                return Err(ApiError::Unauthorized(
+                    "Token belongs to a different user".to_string(),
+                ));
+            }
+            connection_manager
+                .set_expiry(connection_id, expires_at)
+                .await;
+            connection_manager
+                .send_to_connection(
+                    connection_id,
+                    WebSocketMessage::Authenticated {
+                        expires_at: expires_at.to_rfc3339(),
+                    },
+                )
+                .await
+                .map_err(ApiError::InternalServer)?;
+        }
+        WebSocketMessage::EditJobStart { job_id } => {
+            handle_edit_job_start(&job_id, connection_id, authed_user, state_lock).await?;
+        }
+        WebSocketMessage::EditJobStop { job_id } => {
+            let state = state_lock.read().await.clone();
+            if let Some(editors) = state.job_editors.leave(&job_id, connection_id).await {
+                debug!(user_id = %user_id, job_id = %job_id, "Job editor closed");
+                editing::broadcast_editors(&state, &job_id, editors).await;
+            }
+        }
         WebSocketMessage::Ping => {
             connection_manager.update_heartbeat(connection_id).await;
             connection_manager
@@ -383,6 +513,44 @@ async fn handle_websocket_message(
     Ok(())
 }
 
+/// An admin opened a job's edit draft: record it and tell everyone editing the job, the
+/// newcomer included, who else is there
+async fn handle_edit_job_start(
+    job_id: &str,
+    connection_id: &str,
+    authed_user: &AuthedUser,
+    state_lock: &Arc<RwLock<AppState>>,
+) -> Result<(), ApiError> {
+    if !authed_user.is_admin {
+        return Err(ApiError::Forbidden("Admin privileges required".to_string()));
+    }
+    let state = state_lock.read().await.clone();
+
+    let user_name = sqlx::query_scalar::<_, Option<String>>(
+        "SELECT u.name FROM jobs j, users u WHERE j.id = ? AND j.deleted_at IS NULL AND u.id = ?",
+    )
+    .bind(job_id)
+    .bind(&authed_user.id)
+    .fetch_optional(&state.db)
+    .await
+    .map_err(ApiError::DatabaseError)?
+    .ok_or_else(|| ApiError::NotFound(format!("Job not found: {}", job_id)))?
+    .unwrap_or_else(|| authed_user.email.clone());
+
+    let editors = state
+        .job_editors
+        .join(job_id, connection_id, &authed_user.id, &user_name)
+        .await;
+    debug!(
+        user_id = %authed_user.id,
+        job_id = %job_id,
+        editors = editors.len(),
+        "Job editor opened"
+    );
+    editing::broadcast_editors(&state, job_id, editors).await;
+    Ok(())
+}
+
 /// Handle sending a message
 async fn handle_send_message(
     content: String,
@@ -392,9 +560,6 @@ async fn handle_send_message(
     connection_manager: &ConnectionManager,
     state_lock: &Arc<RwLock<AppState>>,
 ) -> Result<(), ApiError> {
-    // Validate message content
-    validators::validate_message_content(&content)?;
-
     let state = state_lock.read().await.clone();
     let message_service = MessageService::new(state.db.clone());
 
@@ -407,6 +572,19 @@ async fn handle_send_message(
         user_id.to_string()
     };
 
+    // Recruiters can insert `/shortcut` snippets
+    let content = if authed_user.is_admin {
+        snippets::expand(&state.db, &content, Some(&target_user_id), None)
+            .await?
+            .0
+    } else {
+        content
+    };
+
+    // Strip unsafe markup, then validate what will actually be stored
+    let content = sanitize_message(&content);
+    validators::validate_message_content(&content)?;
+
     let sender = if authed_user.is_admin {
         "admin"
     } else {
@@ -418,6 +596,24 @@ async fn handle_send_message(
         .create_message(&target_user_id, sender, &content)
         .await?;
 
+    // Candidate messages are moderated in the background so delivery is not delayed
+    if !authed_user.is_admin {
+        inbox::reopen_on_reply(&state, &target_user_id).await;
+        let moderation_state = state.clone();
+        let message_id = message.id.clone();
+        let author_id = authed_user.id.clone();
+        tokio::spawn(async move {
+            moderation::moderate(
+                &moderation_state,
+                ModeratedContent::Message,
+                &message_id,
+                &author_id,
+                &content,
+            )
+            .await;
+        });
+    }
+
     // Convert to enhanced message
     let enhanced_message = EnhancedConversationMessage {
         id: message.id.clone(),
@@ -429,6 +625,11 @@ async fn handle_send_message(
         created_at: message.created_at.clone(),
     };
 
+    // Candidate messages go to an online recruiter, or by email when nobody is online
+    if !authed_user.is_admin {
+        message_routing::route_in_background(&state, enhanced_message.clone());
+    }
+
     // Send to recipient
     let recipient_msg = WebSocketMessage::MessageReceived {
         message: enhanced_message.clone(),
@@ -566,10 +767,22 @@ async fn handle_file_upload(
         "user"
     };
 
+    // Attachments are charged to the candidate whose conversation they land in
+    storage_usage::ensure_within_quota(
+        &state,
+        storage_usage::OWNER_USER,
+        &target_user_id,
+        data.len() as i64,
+    )
+    .await?;
+
     // Create message for the attachment
     let message = message_service
         .create_message(&target_user_id, sender, &format!("Sent file: {}", filename))
         .await?;
+    if !authed_user.is_admin {
+        inbox::reopen_on_reply(&state, &target_user_id).await;
+    }
 
     // Save file
     let upload_id = generate_raw_id(8);
@@ -606,6 +819,16 @@ async fn handle_file_upload(
         )
         .await?;
 
+    storage_usage::record_file(
+        &state.db,
+        storage_usage::CATEGORY_ATTACHMENT,
+        &attachment.id,
+        storage_usage::OWNER_USER,
+        &target_user_id,
+        attachment.file_size,
+    )
+    .await;
+
     // Send upload complete message
     let complete_msg = WebSocketMessage::FileUploadComplete {
         upload_id: upload_id.clone(),
@@ -626,6 +849,10 @@ async fn handle_file_upload(
         created_at: message.created_at.clone(),
     };
 
+    if !authed_user.is_admin {
+        message_routing::route_in_background(&state, enhanced_message.clone());
+    }
+
     let recipient_msg = WebSocketMessage::MessageReceived {
         message: enhanced_message,
     };
@@ -83,6 +83,14 @@ pub struct ConversationInput {
     pub message: String,
 }
 
+/// Admin message, optionally held until a later time
+#[derive(Debug, Deserialize)]
+pub struct AdminConversationInput {
+    pub message: String,
+    #[serde(flatten)]
+    pub schedule: ScheduleInput,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct CreateMessageRequest {
     pub message: String,
@@ -125,6 +133,359 @@ pub struct MessageResponse {
     pub message: String,
 }
 
+// ============================================================================
+// Broadcast Models
+// ============================================================================
+
+/// Who a broadcast is sent to
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum BroadcastSegment {
+    /// Everyone with an active application to a job
+    JobApplicants { job_id: String },
+    /// Candidates whose active application is at a pipeline stage, optionally for one job
+    Stage {
+        stage: String,
+        job_id: Option<String>,
+    },
+    /// Candidates who agreed to hear about future roles (their latest marketing consent)
+    TalentPool,
+}
+
+impl BroadcastSegment {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BroadcastSegment::JobApplicants { .. } => "job_applicants",
+            BroadcastSegment::Stage { .. } => "stage",
+            BroadcastSegment::TalentPool => "talent_pool",
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateBroadcastRequest {
+    pub segment: BroadcastSegment,
+    /// `message`, `email` or `both` (default `message`)
+    pub channel: Option<String>,
+    /// Email subject; required when sending email
+    pub subject: Option<String>,
+    /// Markdown with `{name}`, `{job}`, `{company}` and `{stage}` placeholders
+    pub body: String,
+    /// Count the recipients and render a preview without queueing anything
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct Broadcast {
+    pub id: String,
+    pub segment_type: String,
+    pub job_id: Option<String>,
+    pub stage: Option<String>,
+    pub channel: String,
+    pub subject: Option<String>,
+    pub body: String,
+    pub status: String,
+    pub total_recipients: i64,
+    pub created_by: String,
+    pub created_at: Option<String>,
+    pub completed_at: Option<String>,
+}
+
+/// Recipients of a broadcast by delivery status
+#[derive(Debug, Default, Serialize, FromRow)]
+pub struct BroadcastDeliveryCounts {
+    pub pending: i64,
+    pub sent: i64,
+    pub failed: i64,
+    pub skipped: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BroadcastResponse {
+    #[serde(flatten)]
+    pub broadcast: Broadcast,
+    pub delivery: BroadcastDeliveryCounts,
+}
+
+/// The broadcast as the first recipient would receive it
+#[derive(Debug, Serialize)]
+pub struct BroadcastPreview {
+    pub user_id: String,
+    pub subject: Option<String>,
+    pub body: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CreateBroadcastResponse {
+    pub dry_run: bool,
+    pub total_recipients: usize,
+    pub preview: Option<BroadcastPreview>,
+    /// The queued broadcast; absent on a dry run
+    pub broadcast: Option<Broadcast>,
+}
+
+#[derive(Debug, Serialize, FromRow)]
+pub struct BroadcastRecipient {
+    pub user_id: String,
+    pub name: Option<String>,
+    pub email: Option<String>,
+    pub application_id: Option<String>,
+    pub status: String,
+    pub attempts: i64,
+    pub message_id: Option<String>,
+    pub error: Option<String>,
+    pub sent_at: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BroadcastRecipientsQuery {
+    pub status: Option<String>,
+}
+
+// ============================================================================
+// Scheduled Message Models
+// ============================================================================
+
+/// When to send an admin message or candidate email; with neither field set it goes now
+#[derive(Debug, Default, Deserialize)]
+pub struct ScheduleInput {
+    /// RFC3339 instant, or a local `YYYY-MM-DDTHH:MM` in `timezone`
+    pub send_at: Option<String>,
+    /// Zone for a local `send_at` and business hours; defaults to the recipient's
+    pub timezone: Option<String>,
+    /// Hold the message until the recipient's next business hours
+    #[serde(default)]
+    pub business_hours: bool,
+}
+
+impl ScheduleInput {
+    pub fn is_scheduled(&self) -> bool {
+        self.send_at.is_some() || self.business_hours
+    }
+}
+
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct ScheduledMessage {
+    pub id: String,
+    /// `message` or `email`
+    pub kind: String,
+    pub user_id: String,
+    pub application_id: Option<String>,
+    pub job_id: Option<String>,
+    pub subject: Option<String>,
+    pub body: String,
+    /// JSON array of CC addresses for emails
+    pub cc: Option<String>,
+    /// UTC, `YYYY-MM-DD HH:MM:SS`
+    pub send_at: String,
+    pub timezone: String,
+    pub business_hours: bool,
+    pub status: String,
+    pub attempts: i64,
+    pub error: Option<String>,
+    pub message_id: Option<String>,
+    pub created_by: String,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+    pub sent_at: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ScheduledMessageResponse {
+    #[serde(flatten)]
+    pub scheduled: ScheduledMessage,
+    /// `send_at` in the zone it was scheduled for
+    pub send_at_local: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ScheduledMessagesQuery {
+    pub status: Option<String>,
+    pub kind: Option<String>,
+    pub user_id: Option<String>,
+}
+
+/// Changes to a message that hasn't been sent yet
+#[derive(Debug, Deserialize)]
+pub struct UpdateScheduledMessageRequest {
+    pub subject: Option<String>,
+    pub body: Option<String>,
+    pub cc: Option<Vec<String>>,
+    pub send_at: Option<String>,
+    pub timezone: Option<String>,
+    pub business_hours: Option<bool>,
+}
+
+// ============================================================================
+// Snippet Models
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct Snippet {
+    pub id: String,
+    /// Typed as `/shortcut` to insert the body
+    pub shortcut: String,
+    pub title: String,
+    /// Text with `{name}`, `{job}`, `{company}` and `{stage}` placeholders
+    pub body: String,
+    pub created_by: String,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateSnippetRequest {
+    pub shortcut: String,
+    pub title: String,
+    pub body: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateSnippetRequest {
+    pub shortcut: Option<String>,
+    pub title: Option<String>,
+    pub body: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SnippetsQuery {
+    /// Matches the shortcut or title
+    pub q: Option<String>,
+}
+
+/// Expand the shortcuts in a draft, filling placeholders for a recipient when given
+#[derive(Debug, Deserialize)]
+pub struct ExpandSnippetsRequest {
+    pub text: String,
+    pub user_id: Option<String>,
+    pub application_id: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ExpandSnippetsResponse {
+    pub text: String,
+    /// Shortcuts that were expanded, in order of first use
+    pub expanded: Vec<String>,
+}
+
+// ============================================================================
+// Search Models
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+pub struct MessageSearchQuery {
+    pub q: String,
+    /// Admins only: limit the search to one candidate's conversation
+    pub user_id: Option<String>,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+/// A matching message with the text around the match, terms wrapped in `<mark>`
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct MessageSearchHit {
+    pub message_id: String,
+    /// The candidate's user ID, which identifies the conversation
+    pub conversation_id: String,
+    pub user_name: Option<String>,
+    pub sender: String,
+    pub snippet: String,
+    pub created_at: Option<String>,
+    /// Where the message opens in the UI
+    #[sqlx(skip)]
+    pub link: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MessageSearchResponse {
+    pub query: String,
+    pub results: Vec<MessageSearchHit>,
+    pub total: i64,
+}
+
+// ============================================================================
+// Inbox Models
+// ============================================================================
+
+/// A candidate's conversation as it appears in the admin inbox
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct InboxConversation {
+    pub user_id: String,
+    pub user_name: Option<String>,
+    pub user_email: String,
+    pub user_avatar: Option<String>,
+    pub last_message: String,
+    pub last_message_at: Option<String>,
+    pub unread_count: i64,
+    /// `open`, `snoozed` or `closed`; a snooze that has run out reads as `open`
+    pub state: String,
+    pub assignee_id: Option<String>,
+    pub assignee_name: Option<String>,
+    pub snoozed_until: Option<String>,
+    pub updated_at: Option<String>,
+    /// Set by the retention policy; cleared when the conversation is picked up again
+    pub archived_at: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct InboxQuery {
+    pub state: Option<String>,
+    /// `me`, `unassigned` or an admin's user ID
+    pub assignee: Option<String>,
+    /// Show archived conversations instead of live ones
+    #[serde(default)]
+    pub archived: bool,
+}
+
+/// Assign a conversation to an admin (`me` for yourself); null or empty unassigns it
+#[derive(Debug, Deserialize)]
+pub struct AssignConversationRequest {
+    pub assignee_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateConversationStateRequest {
+    pub state: String,
+    /// RFC 3339 time the conversation reopens; required when snoozing
+    pub snoozed_until: Option<String>,
+}
+
+// ============================================================================
+// Export Models
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+pub struct ConversationExportQuery {
+    /// `json` (default) or `pdf`
+    pub format: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct ExportedAttachment {
+    pub id: String,
+    pub message_id: String,
+    pub original_filename: String,
+    pub mime_type: String,
+    pub file_size: i64,
+    pub created_at: Option<String>,
+    pub url: String,
+}
+
+/// A conversation's full history for a compliance request
+#[derive(Debug, Clone, Serialize)]
+pub struct ConversationExport {
+    pub conversation_id: String,
+    pub candidate_name: Option<String>,
+    pub candidate_email: String,
+    pub exported_at: String,
+    pub exported_by: String,
+    pub archived_at: Option<String>,
+    pub messages: Vec<ConversationMessage>,
+    /// Every file shared in the conversation; the files themselves are not embedded
+    pub attachments: Vec<ExportedAttachment>,
+}
+
 // ============================================================================
 // WebSocket Message Models
 // ============================================================================
@@ -160,6 +521,18 @@ pub enum WebSocketMessage {
     MarkRead {
         message_id: String,
     },
+    /// Renew a connection's authentication with a fresh token before the current one expires
+    Authenticate {
+        token: String,
+    },
+    /// An admin opened a job's edit draft
+    EditJobStart {
+        job_id: String,
+    },
+    /// An admin closed a job's edit draft
+    EditJobStop {
+        job_id: String,
+    },
     Ping,
 
     // Server → Client
@@ -202,10 +575,42 @@ pub enum WebSocketMessage {
     Connected {
         user_id: String,
     },
+    Authenticated {
+        expires_at: String,
+    },
     MissedMessages {
         messages: Vec<EnhancedConversationMessage>,
         count: usize,
     },
+    /// Sent to online admins when a conversation changes hands
+    ConversationAssigned {
+        conversation_id: String,
+        assignee_id: Option<String>,
+        previous_assignee_id: Option<String>,
+        assigned_by: String,
+    },
+    /// Sent to online admins when a conversation is opened, snoozed or closed
+    ConversationStateChanged {
+        conversation_id: String,
+        state: String,
+        snoozed_until: Option<String>,
+        changed_by: String,
+    },
+    /// Sent to everyone editing a job when someone opens or closes its editor
+    JobEditorsChanged {
+        job_id: String,
+        editors: Vec<crate::jobs::models::JobEditor>,
+    },
+    /// Sent to a job's other editors when its draft is saved, discarded or published;
+    /// `forced` means the save overwrote fields someone else had changed
+    JobDraftChanged {
+        job_id: String,
+        action: String,
+        revision: i64,
+        fields: Vec<String>,
+        changed_by: String,
+        forced: bool,
+    },
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
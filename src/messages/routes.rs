@@ -1,6 +1,6 @@
 use crate::messages::handlers;
 use axum::{
-    routing::{get, post},
+    routing::{get, post, put},
     Router,
 };
 
@@ -21,6 +21,11 @@ pub fn messages_routes() -> Router {
             "/api/messages",
             get(handlers::user::list_conversations).post(handlers::user::user_send_conversation),
         )
+        // Full-text search over the conversations the caller can see
+        .route(
+            "/api/messages/search",
+            get(handlers::search::search_messages),
+        )
         .route(
             "/api/admin/conversations/:user_id",
             get(handlers::admin::admin_list_conversations)
@@ -35,11 +40,76 @@ pub fn messages_routes() -> Router {
             "/api/admin/conversations/:user_id/read",
             post(handlers::admin::admin_mark_conversation_read),
         )
+        // JSON or PDF transcript for compliance requests (admin)
+        .route(
+            "/api/admin/conversations/:user_id/export",
+            get(handlers::export::export_conversation),
+        )
         // Mark conversation as read (user)
         .route(
             "/api/conversations/read",
             post(handlers::user::mark_conversation_read),
         )
+        // Inbox assignment and states (admin)
+        .route("/api/admin/inbox", get(handlers::inbox::list_inbox))
+        .route(
+            "/api/admin/inbox/:user_id",
+            get(handlers::inbox::get_inbox_conversation),
+        )
+        .route(
+            "/api/admin/inbox/:user_id/assignee",
+            put(handlers::inbox::assign_conversation),
+        )
+        .route(
+            "/api/admin/inbox/:user_id/state",
+            put(handlers::inbox::update_conversation_state),
+        )
+        // Broadcasts to candidate segments (admin)
+        .route(
+            "/api/admin/broadcasts",
+            get(handlers::broadcasts::list_broadcasts).post(handlers::broadcasts::create_broadcast),
+        )
+        .route(
+            "/api/admin/broadcasts/:id",
+            get(handlers::broadcasts::get_broadcast),
+        )
+        .route(
+            "/api/admin/broadcasts/:id/recipients",
+            get(handlers::broadcasts::list_broadcast_recipients),
+        )
+        .route(
+            "/api/admin/broadcasts/:id/cancel",
+            post(handlers::broadcasts::cancel_broadcast),
+        )
+        // Scheduled messages and emails (admin)
+        .route(
+            "/api/admin/scheduled-messages",
+            get(handlers::scheduled::list_scheduled_messages),
+        )
+        .route(
+            "/api/admin/scheduled-messages/:id",
+            get(handlers::scheduled::get_scheduled_message)
+                .put(handlers::scheduled::update_scheduled_message),
+        )
+        .route(
+            "/api/admin/scheduled-messages/:id/cancel",
+            post(handlers::scheduled::cancel_scheduled_message),
+        )
+        // Snippet library for recruiter messaging (admin)
+        .route(
+            "/api/admin/snippets",
+            get(handlers::snippets::list_snippets).post(handlers::snippets::create_snippet),
+        )
+        .route(
+            "/api/admin/snippets/expand",
+            post(handlers::snippets::expand_snippets),
+        )
+        .route(
+            "/api/admin/snippets/:id",
+            get(handlers::snippets::get_snippet)
+                .put(handlers::snippets::update_snippet)
+                .delete(handlers::snippets::delete_snippet),
+        )
         // Attachment serving route
         .route(
             "/api/attachments/:filename",
@@ -2,7 +2,9 @@ use crate::common::error::ApiError;
 use crate::common::id_generator::{generate_attachment_id, generate_message_id};
 use crate::messages::models::{
     AttachmentData, ConversationMessage, EnhancedConversationMessage, MessageAttachment,
+    WebSocketMessage,
 };
+use crate::messages::services::ConnectionManager;
 use sqlx::SqlitePool;
 use tracing::{error, info};
 
@@ -61,6 +63,31 @@ impl MessageService {
         Ok(message)
     }
 
+    /// Post a message from the hiring team and push it to the candidate's open connections
+    pub async fn post_admin_message(
+        &self,
+        connection_manager: &ConnectionManager,
+        user_id: &str,
+        content: &str,
+    ) -> Result<ConversationMessage, ApiError> {
+        let message = self.create_message(user_id, "admin", content).await?;
+
+        let received = WebSocketMessage::MessageReceived {
+            message: EnhancedConversationMessage {
+                id: message.id.clone(),
+                user_id: message.user_id.clone(),
+                sender: message.sender.clone(),
+                message: message.message.clone(),
+                attachments: vec![],
+                is_read: false,
+                created_at: message.created_at.clone(),
+            },
+        };
+        let _ = connection_manager.send_to_user(user_id, received).await;
+
+        Ok(message)
+    }
+
     /// Get messages for a user
     pub async fn get_user_messages(
         &self,
@@ -297,19 +324,29 @@ impl MessageService {
         Ok(count)
     }
 
-    /// Get messages created after a specific timestamp (for missed messages)
+    /// Messages in a user's conversation created after `since`, oldest first, for a client
+    /// reconnecting without an event ID
+    ///
+    /// `created_at` only has second precision, so the second of `since` itself is included:
+    /// a message repeated is better than one lost.
     pub async fn get_messages_since(
         &self,
         user_id: &str,
         since: chrono::DateTime<chrono::Utc>,
+        limit: i64,
     ) -> Result<Vec<EnhancedConversationMessage>, ApiError> {
-        let since_str = since.to_rfc3339();
-
         let messages = sqlx::query_as::<_, ConversationMessage>(
-            "SELECT * FROM conversation_messages WHERE user_id = ? AND datetime(created_at) > datetime(?) ORDER BY datetime(created_at) ASC"
+            r#"
+            SELECT id, user_id, sender, message, is_read, created_at
+            FROM conversation_messages
+            WHERE user_id = ? AND datetime(created_at) >= datetime(?)
+            ORDER BY datetime(created_at), rowid
+            LIMIT ?
+            "#,
         )
         .bind(user_id)
-        .bind(&since_str)
+        .bind(since.to_rfc3339())
+        .bind(limit)
         .fetch_all(&self.db)
         .await
         .map_err(ApiError::DatabaseError)?;
@@ -330,4 +367,61 @@ impl MessageService {
 
         Ok(enhanced_messages)
     }
+
+    /// Messages stored after `last_event_id`, oldest first, for a client resuming its
+    /// connection; `user_id` limits them to one conversation
+    ///
+    /// Returns `None` when the event ID is unknown, so the client knows to reload instead.
+    pub async fn get_messages_after(
+        &self,
+        last_event_id: &str,
+        user_id: Option<&str>,
+        limit: i64,
+    ) -> Result<Option<Vec<EnhancedConversationMessage>>, ApiError> {
+        let after = sqlx::query_scalar::<_, i64>(
+            "SELECT rowid FROM conversation_messages WHERE id = ? AND (? IS NULL OR user_id = ?)",
+        )
+        .bind(last_event_id)
+        .bind(user_id)
+        .bind(user_id)
+        .fetch_optional(&self.db)
+        .await
+        .map_err(ApiError::DatabaseError)?;
+        let Some(after) = after else {
+            return Ok(None);
+        };
+
+        let messages = sqlx::query_as::<_, ConversationMessage>(
+            r#"
+            SELECT id, user_id, sender, message, is_read, created_at
+            FROM conversation_messages
+            WHERE rowid > ? AND (? IS NULL OR user_id = ?)
+            ORDER BY rowid
+            LIMIT ?
+            "#,
+        )
+        .bind(after)
+        .bind(user_id)
+        .bind(user_id)
+        .bind(limit)
+        .fetch_all(&self.db)
+        .await
+        .map_err(ApiError::DatabaseError)?;
+
+        let mut enhanced_messages = Vec::new();
+        for msg in messages {
+            let attachments = self.get_message_attachments(&msg.id).await?;
+            enhanced_messages.push(EnhancedConversationMessage {
+                id: msg.id,
+                user_id: msg.user_id,
+                sender: msg.sender,
+                message: msg.message,
+                attachments,
+                is_read: msg.is_read.unwrap_or(0) == 1,
+                created_at: msg.created_at,
+            });
+        }
+
+        Ok(Some(enhanced_messages))
+    }
 }
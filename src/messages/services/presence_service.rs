@@ -16,8 +16,8 @@ pub struct PresenceService {
 impl PresenceService {
     pub fn new(connection_manager: ConnectionManager) -> Self {
         Self {
+            last_seen: connection_manager.last_seen(),
             connection_manager,
-            last_seen: Arc::new(RwLock::new(HashMap::new())),
         }
     }
 
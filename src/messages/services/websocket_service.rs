@@ -1,5 +1,5 @@
 use crate::messages::models::{PresenceStatus, WebSocketMessage};
-use axum::extract::ws::Message;
+use axum::extract::ws::{close_code, CloseFrame, Message};
 use futures_util::stream::SplitSink;
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -8,6 +8,15 @@ use tracing::{debug, info, warn};
 
 pub type WsSender = SplitSink<axum::extract::ws::WebSocket, Message>;
 
+/// Frames queued for one connection before it counts as a slow consumer and is dropped
+pub const SEND_BUFFER_SIZE: usize = 256;
+
+/// How often each connection is pinged
+pub const HEARTBEAT_INTERVAL_SECS: u64 = 30;
+
+/// Connections silent for longer than this are removed
+pub const HEARTBEAT_TIMEOUT_SECS: i64 = 60;
+
 /// Connection information for a WebSocket client
 #[derive(Debug, Clone)]
 pub struct Connection {
@@ -15,6 +24,8 @@ pub struct Connection {
     pub connection_id: String,
     pub connected_at: chrono::DateTime<chrono::Utc>,
     pub last_heartbeat: chrono::DateTime<chrono::Utc>,
+    /// When the token the connection authenticated with runs out; renewed by `authenticate`
+    pub expires_at: Option<chrono::DateTime<chrono::Utc>>,
 }
 
 /// Manages active WebSocket connections
@@ -23,9 +34,11 @@ pub struct ConnectionManager {
     // Map of user_id -> list of connection_ids
     user_connections: Arc<RwLock<HashMap<String, Vec<String>>>>,
     // Map of connection_id -> sender channel
-    connections: Arc<RwLock<HashMap<String, mpsc::UnboundedSender<Message>>>>,
+    connections: Arc<RwLock<HashMap<String, mpsc::Sender<Message>>>>,
     // Map of connection_id -> Connection info
     connection_info: Arc<RwLock<HashMap<String, Connection>>>,
+    // Map of user_id -> last seen timestamp, shared by every PresenceService
+    last_seen: Arc<RwLock<HashMap<String, chrono::DateTime<chrono::Utc>>>>,
 }
 
 impl ConnectionManager {
@@ -34,15 +47,21 @@ impl ConnectionManager {
             user_connections: Arc::new(RwLock::new(HashMap::new())),
             connections: Arc::new(RwLock::new(HashMap::new())),
             connection_info: Arc::new(RwLock::new(HashMap::new())),
+            last_seen: Arc::new(RwLock::new(HashMap::new())),
         }
     }
 
+    /// Last seen timestamps, kept here so they outlive the connection that recorded them
+    pub fn last_seen(&self) -> Arc<RwLock<HashMap<String, chrono::DateTime<chrono::Utc>>>> {
+        self.last_seen.clone()
+    }
+
     /// Register a new WebSocket connection
     pub async fn register(
         &self,
         user_id: String,
         connection_id: String,
-        sender: mpsc::UnboundedSender<Message>,
+        sender: mpsc::Sender<Message>,
     ) {
         let now = chrono::Utc::now();
 
@@ -58,6 +77,7 @@ impl ConnectionManager {
             connection_id: connection_id.clone(),
             connected_at: now,
             last_heartbeat: now,
+            expires_at: None,
         };
         self.connection_info
             .write()
@@ -112,6 +132,13 @@ impl ConnectionManager {
         }
     }
 
+    /// Record when a connection's token expires, on connect and whenever it re-authenticates
+    pub async fn set_expiry(&self, connection_id: &str, expires_at: chrono::DateTime<chrono::Utc>) {
+        if let Some(conn) = self.connection_info.write().await.get_mut(connection_id) {
+            conn.expires_at = Some(expires_at);
+        }
+    }
+
     /// Get all connection IDs for a user
     pub async fn get_user_connections(&self, user_id: &str) -> Vec<String> {
         self.user_connections
@@ -138,6 +165,9 @@ impl ConnectionManager {
     }
 
     /// Send a message to a specific connection
+    ///
+    /// A connection whose send buffer is full isn't keeping up; it is dropped rather than
+    /// left to hold memory, and the client resumes from its last event ID when it reconnects.
     pub async fn send_to_connection(
         &self,
         connection_id: &str,
@@ -146,20 +176,33 @@ impl ConnectionManager {
         let json = serde_json::to_string(&message)
             .map_err(|e| format!("Failed to serialize message: {}", e))?;
 
-        let connections = self.connections.read().await;
-        if let Some(sender) = connections.get(connection_id) {
-            sender
-                .send(Message::Text(json))
-                .map_err(|e| format!("Failed to send message: {}", e))?;
+        self.send_frame(connection_id, Message::Text(json)).await?;
+        debug!(
+            connection_id = %connection_id,
+            message_type = ?message,
+            "Message sent to connection"
+        );
+        Ok(())
+    }
 
-            debug!(
-                connection_id = %connection_id,
-                message_type = ?message,
-                "Message sent to connection"
-            );
-            Ok(())
-        } else {
-            Err(format!("Connection {} not found", connection_id))
+    /// Queue a raw frame for a connection, dropping the connection if its buffer is full
+    async fn send_frame(&self, connection_id: &str, frame: Message) -> Result<(), String> {
+        let result = match self.connections.read().await.get(connection_id) {
+            Some(sender) => sender.try_send(frame),
+            None => return Err(format!("Connection {} not found", connection_id)),
+        };
+
+        match result {
+            Ok(()) => Ok(()),
+            Err(mpsc::error::TrySendError::Full(_)) => {
+                warn!(connection_id = %connection_id, "Send buffer full; dropping slow connection");
+                self.unregister(connection_id).await;
+                Err(format!("Connection {} is not keeping up", connection_id))
+            }
+            Err(mpsc::error::TrySendError::Closed(_)) => {
+                self.unregister(connection_id).await;
+                Err(format!("Connection {} is closed", connection_id))
+            }
         }
     }
 
@@ -207,24 +250,68 @@ impl ConnectionManager {
         }
     }
 
-    /// Remove stale connections (no heartbeat for more than 60 seconds)
+    /// Ping every connection; the client's pong refreshes its heartbeat
+    pub async fn ping_all(&self) {
+        let connection_ids: Vec<String> = self.connections.read().await.keys().cloned().collect();
+        for conn_id in connection_ids {
+            let _ = self.send_frame(&conn_id, Message::Ping(Vec::new())).await;
+        }
+    }
+
+    /// Remove stale connections (no heartbeat within the timeout) and close those whose
+    /// token has expired without being renewed
     pub async fn cleanup_stale_connections(&self) {
         let now = chrono::Utc::now();
-        let timeout = chrono::Duration::seconds(60);
-
-        let stale_connections: Vec<String> = self
-            .connection_info
-            .read()
-            .await
-            .iter()
-            .filter(|(_, conn)| now.signed_duration_since(conn.last_heartbeat) > timeout)
-            .map(|(id, _)| id.clone())
-            .collect();
+        let timeout = chrono::Duration::seconds(HEARTBEAT_TIMEOUT_SECS);
+
+        let (stale_connections, expired_connections): (Vec<String>, Vec<String>) = {
+            let info = self.connection_info.read().await;
+            let stale = info
+                .iter()
+                .filter(|(_, conn)| now.signed_duration_since(conn.last_heartbeat) > timeout)
+                .map(|(id, _)| id.clone())
+                .collect();
+            let expired = info
+                .iter()
+                .filter(|(_, conn)| conn.expires_at.is_some_and(|exp| exp <= now))
+                .map(|(id, _)| id.clone())
+                .collect();
+            (stale, expired)
+        };
 
         for conn_id in stale_connections {
             warn!(connection_id = %conn_id, "Removing stale connection");
             self.unregister(&conn_id).await;
         }
+        for conn_id in expired_connections {
+            info!(connection_id = %conn_id, "Closing connection with expired token");
+            let _ = self
+                .send_to_connection(
+                    &conn_id,
+                    WebSocketMessage::Error {
+                        code: "TOKEN_EXPIRED".to_string(),
+                        message: "Authentication expired; reconnect with a new token".to_string(),
+                    },
+                )
+                .await;
+            let _ = self.send_frame(&conn_id, Message::Close(None)).await;
+            self.unregister(&conn_id).await;
+        }
+    }
+
+    /// Close every connection with a "going away" frame so clients reconnect elsewhere,
+    /// e.g. when the server shuts down
+    pub async fn close_all(&self, reason: &str) {
+        let connection_ids: Vec<String> = self.connections.read().await.keys().cloned().collect();
+        info!(connections = connection_ids.len(), "Closing all WebSocket connections");
+        for conn_id in connection_ids {
+            let frame = CloseFrame {
+                code: close_code::AWAY,
+                reason: reason.to_string().into(),
+            };
+            let _ = self.send_frame(&conn_id, Message::Close(Some(frame))).await;
+            self.unregister(&conn_id).await;
+        }
     }
 
     /// Get connection count for a user
@@ -263,13 +350,15 @@ impl WebSocketService {
         &self.connection_manager
     }
 
-    /// Start background task for cleaning up stale connections
+    /// Start background task that pings every connection and cleans up stale or expired ones
     pub fn start_cleanup_task(connection_manager: ConnectionManager) {
         tokio::spawn(async move {
-            let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(30));
+            let mut interval =
+                tokio::time::interval(tokio::time::Duration::from_secs(HEARTBEAT_INTERVAL_SECS));
             loop {
                 interval.tick().await;
                 connection_manager.cleanup_stale_connections().await;
+                connection_manager.ping_all().await;
             }
         });
     }
@@ -99,7 +99,7 @@ mod tests {
         assert!(!manager.is_user_online("user1").await);
 
         // Register a connection
-        let (tx, _rx) = tokio::sync::mpsc::unbounded_channel();
+        let (tx, _rx) = tokio::sync::mpsc::channel(8);
         manager
             .register("user1".to_string(), "conn1".to_string(), tx)
             .await;
@@ -122,8 +122,8 @@ mod tests {
         let manager = ConnectionManager::new();
 
         // Register multiple connections for same user
-        let (tx1, _rx1) = tokio::sync::mpsc::unbounded_channel();
-        let (tx2, _rx2) = tokio::sync::mpsc::unbounded_channel();
+        let (tx1, _rx1) = tokio::sync::mpsc::channel(8);
+        let (tx2, _rx2) = tokio::sync::mpsc::channel(8);
 
         manager
             .register("user1".to_string(), "conn1".to_string(), tx1)
@@ -159,7 +159,7 @@ mod tests {
         assert_eq!(presence.get_status("user1").await, PresenceStatus::Offline);
 
         // Register connection
-        let (tx, _rx) = tokio::sync::mpsc::unbounded_channel();
+        let (tx, _rx) = tokio::sync::mpsc::channel(8);
         manager
             .register("user1".to_string(), "conn1".to_string(), tx)
             .await;
@@ -180,4 +180,152 @@ mod tests {
         assert_eq!(presence.get_status("user1").await, PresenceStatus::Offline);
         assert!(!presence.is_online("user1").await);
     }
+
+    #[tokio::test]
+    async fn test_connection_manager_drops_slow_connection() {
+        let manager = ConnectionManager::new();
+        let (tx, _rx) = tokio::sync::mpsc::channel(2);
+        manager
+            .register("user1".to_string(), "conn1".to_string(), tx)
+            .await;
+
+        // The client never reads, so the third message finds the buffer full
+        assert!(manager
+            .send_to_user("user1", WebSocketMessage::Pong)
+            .await
+            .is_ok());
+        assert!(manager
+            .send_to_user("user1", WebSocketMessage::Pong)
+            .await
+            .is_ok());
+        assert!(manager
+            .send_to_connection("conn1", WebSocketMessage::Pong)
+            .await
+            .is_err());
+
+        assert!(!manager.is_user_online("user1").await);
+        assert_eq!(manager.get_total_connections().await, 0);
+    }
+
+    #[tokio::test]
+    async fn test_cleanup_closes_connection_with_expired_token() {
+        let manager = ConnectionManager::new();
+        let (tx1, mut rx1) = tokio::sync::mpsc::channel(8);
+        let (tx2, _rx2) = tokio::sync::mpsc::channel(8);
+        manager
+            .register("user1".to_string(), "conn1".to_string(), tx1)
+            .await;
+        manager
+            .register("user2".to_string(), "conn2".to_string(), tx2)
+            .await;
+        let now = chrono::Utc::now();
+        manager
+            .set_expiry("conn1", now - chrono::Duration::seconds(1))
+            .await;
+        manager
+            .set_expiry("conn2", now + chrono::Duration::hours(1))
+            .await;
+
+        manager.cleanup_stale_connections().await;
+
+        assert!(!manager.is_user_online("user1").await);
+        assert!(manager.is_user_online("user2").await);
+
+        // The client is told why before the socket is closed
+        match rx1.recv().await {
+            Some(axum::extract::ws::Message::Text(json)) => assert!(json.contains("TOKEN_EXPIRED")),
+            other => panic!("expected an error message, got {:?}", other),
+        }
+        assert!(matches!(
+            rx1.recv().await,
+            Some(axum::extract::ws::Message::Close(None))
+        ));
+        assert!(rx1.recv().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_presence_last_seen_outlives_the_service() {
+        let manager = ConnectionManager::new();
+        PresenceService::new(manager.clone())
+            .update_last_seen("user1")
+            .await;
+
+        // Each connection builds its own PresenceService; they share the manager's record
+        let later = PresenceService::new(manager);
+        assert!(later.get_last_seen("user1").await.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_reconnect_without_last_event_id_replays_missed_messages() {
+        use futures_util::StreamExt;
+        use tokio_tungstenite::tungstenite::Message as WsMessage;
+
+        let app = crate::test_support::TestApp::new().await;
+        let user_id = app.create_user("ws@example.com", "Ws User").await;
+        let token = app.token_for(&user_id).await;
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!(
+            "ws://{}/ws/conversations?token={}",
+            listener.local_addr().unwrap(),
+            token
+        );
+        let router = app.router.clone();
+        tokio::spawn(async move { axum::serve(listener, router.into_make_service()).await });
+
+        async fn next_event(
+            socket: &mut tokio_tungstenite::WebSocketStream<
+                tokio_tungstenite::MaybeTlsStream<tokio::net::TcpStream>,
+            >,
+        ) -> WebSocketMessage {
+            loop {
+                let frame = tokio::time::timeout(std::time::Duration::from_secs(5), socket.next())
+                    .await
+                    .expect("timed out waiting for a message")
+                    .unwrap()
+                    .unwrap();
+                if let WsMessage::Text(json) = frame {
+                    return serde_json::from_str(&json).unwrap();
+                }
+            }
+        }
+
+        let (mut socket, _) = tokio_tungstenite::connect_async(url.as_str())
+            .await
+            .unwrap();
+        assert!(matches!(
+            next_event(&mut socket).await,
+            WebSocketMessage::Connected { .. }
+        ));
+        socket.close(None).await.unwrap();
+        while app
+            .state
+            .connection_manager
+            .get_user_connection_count(&user_id)
+            .await
+            > 0
+        {
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+
+        let missed = MessageService::new(app.state.db.clone())
+            .create_message(&user_id, "admin", "Sent while you were away")
+            .await
+            .unwrap();
+
+        // No last_event_id: the server falls back to when the user was last seen
+        let (mut socket, _) = tokio_tungstenite::connect_async(url.as_str())
+            .await
+            .unwrap();
+        assert!(matches!(
+            next_event(&mut socket).await,
+            WebSocketMessage::Connected { .. }
+        ));
+        match next_event(&mut socket).await {
+            WebSocketMessage::MissedMessages { messages, .. } => {
+                assert!(messages.iter().any(|m| m.id == missed.id));
+            }
+            other => panic!("expected missed messages, got {:?}", other),
+        }
+    }
 }
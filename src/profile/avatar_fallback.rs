@@ -0,0 +1,88 @@
+// src/profile/avatar_fallback.rs
+
+use sha2::{Digest, Sha256};
+
+/// Background colours for initials avatars, all dark enough for white text
+const PALETTE: &[&str] = &[
+    "#1E88E5", "#43A047", "#E53935", "#8E24AA", "#FB8C00", "#00897B", "#3949AB", "#D81B60",
+    "#6D4C41", "#546E7A", "#5E35B1", "#C0CA33",
+];
+
+/// Prefix of cached fallback files in the avatars directory, kept apart from uploads
+pub const FALLBACK_PREFIX: &str = "fallback_";
+
+/// The avatar a user's `avatar` field should point at, falling back to the generated one
+pub fn avatar_url(user_id: &str, avatar: Option<&str>) -> String {
+    match avatar.filter(|a| !a.trim().is_empty()) {
+        Some(avatar) => avatar.to_string(),
+        None => format!("/api/users/{}/avatar", user_id),
+    }
+}
+
+/// Up to two letters: first and last word of the name, else the start of the email
+pub fn initials(name: Option<&str>, email: &str) -> String {
+    let words: Vec<&str> = name
+        .unwrap_or("")
+        .split_whitespace()
+        .filter(|w| w.chars().next().is_some_and(char::is_alphanumeric))
+        .collect();
+    let letters: String = match words.as_slice() {
+        [] => email
+            .split('@')
+            .next()
+            .unwrap_or("")
+            .chars()
+            .filter(|c| c.is_alphanumeric())
+            .take(1)
+            .collect(),
+        [only] => only.chars().take(1).collect(),
+        [first, .., last] => first.chars().take(1).chain(last.chars().take(1)).collect(),
+    };
+    if letters.is_empty() {
+        "?".to_string()
+    } else {
+        letters.to_uppercase()
+    }
+}
+
+/// FNV-1a, so a user keeps the same colour across builds and restarts
+fn stable_hash(text: &str) -> u64 {
+    text.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+pub fn background_color(user_id: &str) -> &'static str {
+    PALETTE[(stable_hash(user_id) % PALETTE.len() as u64) as usize]
+}
+
+/// A 256px square SVG with the initials centred on the user's colour
+pub fn initials_svg(initials: &str, color: &str) -> String {
+    let escaped = initials
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;");
+    let font_size = if initials.chars().count() > 1 {
+        104
+    } else {
+        128
+    };
+    format!(
+        r##"<svg xmlns="http://www.w3.org/2000/svg" width="256" height="256" viewBox="0 0 256 256"><rect width="256" height="256" fill="{}"/><text x="50%" y="50%" dy=".35em" fill="#FFFFFF" font-family="Helvetica, Arial, sans-serif" font-size="{}" font-weight="600" text-anchor="middle">{}</text></svg>"##,
+        color, font_size, escaped
+    )
+}
+
+/// Gravatar's SHA-256 hash of the trimmed, lowercased email
+pub fn gravatar_hash(email: &str) -> String {
+    Sha256::digest(email.trim().to_lowercase().as_bytes())
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// Cache file stem for a user's fallback, which changes when the name or email does
+pub fn fallback_stem(user_id: &str, name: Option<&str>, email: &str) -> String {
+    let key = stable_hash(&format!("{}\n{}", name.unwrap_or(""), email.to_lowercase()));
+    format!("{}{}_{:016x}", FALLBACK_PREFIX, user_id, key)
+}
@@ -0,0 +1,205 @@
+// src/profile/completeness.rs
+
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use tracing::{debug, warn};
+
+use super::models::Profile;
+
+/// Weighted checklist of (key, weight, hint); weights add up to 100
+const ITEMS: &[(&str, i64, &str)] = &[
+    ("resume", 25, "Upload a resume"),
+    ("skills", 15, "Add your skills"),
+    ("experience", 15, "Add at least one work experience entry"),
+    ("education", 10, "Add your education history"),
+    ("name", 10, "Add your first and last name"),
+    ("bio", 10, "Write a short bio"),
+    ("phone", 5, "Add a phone number"),
+    ("location", 5, "Add your location"),
+    ("links", 5, "Add a LinkedIn, GitHub or website link"),
+];
+
+/// Facts about a candidate that the score is derived from
+#[derive(Debug, Default)]
+pub struct CompletenessInputs {
+    pub has_name: bool,
+    pub has_bio: bool,
+    pub has_phone: bool,
+    pub has_location: bool,
+    pub has_links: bool,
+    pub has_skills: bool,
+    pub experience_count: i64,
+    pub education_count: i64,
+    pub resume_count: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MissingItem {
+    pub key: String,
+    pub message: String,
+    pub weight: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ProfileCompleteness {
+    pub score: i64,
+    pub missing: Vec<MissingItem>,
+    pub computed_at: String,
+}
+
+fn is_filled(value: &Option<String>) -> bool {
+    value
+        .as_deref()
+        .map(|v| !v.trim().is_empty())
+        .unwrap_or(false)
+}
+
+fn has_skills(skills: &Option<String>) -> bool {
+    skills
+        .as_deref()
+        .and_then(|s| serde_json::from_str::<Vec<serde_json::Value>>(s).ok())
+        .map(|list| !list.is_empty())
+        .unwrap_or(false)
+}
+
+impl CompletenessInputs {
+    pub fn from_profile(
+        profile: Option<&Profile>,
+        experience_count: i64,
+        education_count: i64,
+        resume_count: i64,
+    ) -> Self {
+        let mut inputs = CompletenessInputs {
+            experience_count,
+            education_count,
+            resume_count,
+            ..Default::default()
+        };
+
+        if let Some(p) = profile {
+            inputs.has_name = is_filled(&p.first_name) && is_filled(&p.last_name);
+            inputs.has_bio = is_filled(&p.bio);
+            inputs.has_phone = is_filled(&p.phone);
+            inputs.has_location = is_filled(&p.location);
+            inputs.has_links =
+                is_filled(&p.linkedin_url) || is_filled(&p.github_url) || is_filled(&p.website);
+            inputs.has_skills = has_skills(&p.skills);
+        }
+
+        inputs
+    }
+
+    fn satisfies(&self, key: &str) -> bool {
+        match key {
+            "resume" => self.resume_count > 0,
+            "skills" => self.has_skills,
+            "experience" => self.experience_count > 0,
+            "education" => self.education_count > 0,
+            "name" => self.has_name,
+            "bio" => self.has_bio,
+            "phone" => self.has_phone,
+            "location" => self.has_location,
+            "links" => self.has_links,
+            _ => false,
+        }
+    }
+}
+
+/// Score a profile and list what is still missing, heaviest items first
+pub fn compute_completeness(inputs: &CompletenessInputs) -> ProfileCompleteness {
+    let mut score = 0;
+    let mut missing = Vec::new();
+
+    for &(key, weight, hint) in ITEMS {
+        if inputs.satisfies(key) {
+            score += weight;
+        } else {
+            missing.push(MissingItem {
+                key: key.to_string(),
+                message: hint.to_string(),
+                weight,
+            });
+        }
+    }
+
+    ProfileCompleteness {
+        score,
+        missing,
+        computed_at: chrono::Utc::now().to_rfc3339(),
+    }
+}
+
+/// Recompute the completeness score for a user and cache it on their profile row
+pub async fn recompute_completeness(
+    pool: &SqlitePool,
+    user_id: &str,
+) -> Result<ProfileCompleteness, sqlx::Error> {
+    let profile = sqlx::query_as::<_, Profile>("SELECT * FROM profiles WHERE user_id = ?")
+        .bind(user_id)
+        .fetch_optional(pool)
+        .await?;
+
+    let experience_count: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM experiences WHERE user_id = ?")
+            .bind(user_id)
+            .fetch_one(pool)
+            .await?;
+
+    let education_count: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM education WHERE user_id = ?")
+            .bind(user_id)
+            .fetch_one(pool)
+            .await?;
+
+    let resume_count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM resumes WHERE user_id = ?")
+        .bind(user_id)
+        .fetch_one(pool)
+        .await?;
+
+    let inputs = CompletenessInputs::from_profile(
+        profile.as_ref(),
+        experience_count,
+        education_count,
+        resume_count,
+    );
+    let completeness = compute_completeness(&inputs);
+
+    let missing_json =
+        serde_json::to_string(&completeness.missing).unwrap_or_else(|_| "[]".to_string());
+
+    sqlx::query(
+        r#"
+        INSERT INTO profiles (user_id, completeness_score, completeness_missing, completeness_updated_at)
+        VALUES (?, ?, ?, ?)
+        ON CONFLICT(user_id) DO UPDATE SET
+            completeness_score = excluded.completeness_score,
+            completeness_missing = excluded.completeness_missing,
+            completeness_updated_at = excluded.completeness_updated_at
+        "#,
+    )
+    .bind(user_id)
+    .bind(completeness.score)
+    .bind(&missing_json)
+    .bind(&completeness.computed_at)
+    .execute(pool)
+    .await?;
+
+    debug!(
+        user_id = %user_id,
+        score = completeness.score,
+        "Recomputed profile completeness"
+    );
+
+    Ok(completeness)
+}
+
+/// Best-effort refresh used after profile or resume changes; failures are only logged
+pub async fn refresh_completeness(pool: &SqlitePool, user_id: &str) {
+    if let Err(e) = recompute_completeness(pool, user_id).await {
+        warn!(
+            error = %e,
+            user_id = %user_id,
+            "Failed to recompute profile completeness"
+        );
+    }
+}
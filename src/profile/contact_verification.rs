@@ -0,0 +1,429 @@
+// src/profile/contact_verification.rs
+//! Confirming changes to a candidate's contact email and phone
+//!
+//! A new value waits in `pending_contact_email` / `pending_phone` while a six-digit code is
+//! sent to it, and only moves into `contact_email` / `phone` once the code is confirmed.
+//! Notifications and offers keep going to the previous value until then, so a typo or
+//! someone else's address never receives them. The account email is already verified by
+//! the sign-in provider, so choosing it as the contact email needs no code.
+
+use chrono::{Duration, Utc};
+use rand::Rng;
+use sha2::{Digest, Sha256};
+use sqlx::SqlitePool;
+use tracing::{info, warn};
+use uuid::Uuid;
+use validator::ValidateEmail;
+
+use super::models::PendingContactChange;
+use crate::common::{ApiError, AppState, ErrorCode};
+use crate::services::encryption::EncryptionError;
+use crate::services::pii::{self, Sealed};
+use crate::services::sms;
+
+/// Contact details that need verifying
+pub const CHANNELS: &[&str] = &["email", "phone"];
+
+/// Minutes a code stays valid
+pub const CODE_TTL_MINUTES: i64 = 15;
+
+/// Wrong guesses allowed before a code stops working
+pub const MAX_ATTEMPTS: i64 = 5;
+
+/// Seconds to wait before another code can be sent for the same change
+const RESEND_COOLDOWN_SECS: i64 = 60;
+
+pub fn validate_channel(channel: &str) -> Result<(), ApiError> {
+    if !CHANNELS.contains(&channel) {
+        return Err(ApiError::BadRequest(format!(
+            "Invalid channel '{}'; expected one of {}",
+            channel,
+            CHANNELS.join(", ")
+        )));
+    }
+    Ok(())
+}
+
+/// Trimmed, lowercased email, or a validation error
+pub fn normalize_email(email: &str) -> Result<String, ApiError> {
+    let email = email.trim().to_lowercase();
+    if !email.validate_email() {
+        return Err(ApiError::ValidationError(
+            "Contact email must be valid".to_string(),
+        ));
+    }
+    Ok(email)
+}
+
+/// Phone number in E.164 form (`+` and 8 to 15 digits), ignoring spaces, dashes, dots
+/// and brackets
+pub fn normalize_phone(phone: &str) -> Result<String, ApiError> {
+    let compact: String = phone
+        .trim()
+        .chars()
+        .filter(|c| !matches!(c, ' ' | '-' | '.' | '(' | ')'))
+        .collect();
+    let digits = compact.strip_prefix('+').unwrap_or("");
+    if !(8..=15).contains(&digits.len()) || !digits.chars().all(|c| c.is_ascii_digit()) {
+        return Err(ApiError::ValidationError(
+            "Phone must include the country code, e.g. +14155550123".to_string(),
+        ));
+    }
+    Ok(compact)
+}
+
+pub fn generate_code() -> String {
+    format!("{:06}", rand::thread_rng().gen_range(0..1_000_000))
+}
+
+/// Codes are stored hashed with their verification ID, so equal codes never share a hash
+pub fn hash_code(verification_id: &str, code: &str) -> String {
+    Sha256::digest(format!("{}:{}", verification_id, code.trim()).as_bytes())
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+fn pending_column(channel: &str) -> &'static str {
+    if channel == "email" {
+        "pending_contact_email"
+    } else {
+        "pending_phone"
+    }
+}
+
+/// A pending value as stored; phone numbers are encrypted, emails are not
+fn stored_value(channel: &str, value: &str) -> Result<String, EncryptionError> {
+    if channel == "phone" {
+        pii::seal(value)
+    } else {
+        Ok(value.to_string())
+    }
+}
+
+fn invalid_code(message: &str) -> ApiError {
+    ApiError::Coded(ErrorCode::VerificationCodeInvalid, message.to_string())
+}
+
+/// Send the code to the new address or number
+async fn deliver_code(
+    state: &AppState,
+    user_id: &str,
+    channel: &str,
+    value: &str,
+    code: &str,
+) -> Result<(), String> {
+    if channel == "phone" {
+        return sms::send_verification_code(&state.db, &state.sms_service, user_id, value, code)
+            .await;
+    }
+
+    let body = format!(
+        "<p>Your verification code is <strong>{}</strong>.</p>\
+         <p>Enter it in your profile within {} minutes to start receiving updates about your \
+         applications at this address. If you did not ask for this, you can ignore this email.</p>",
+        code, CODE_TTL_MINUTES
+    );
+    state
+        .email
+        .send_email(
+            vec![value.to_string()],
+            "Confirm your contact email",
+            &body,
+            None,
+        )
+        .await
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+}
+
+/// Park a new contact value and send it a code. Earlier unconfirmed codes for the channel
+/// stop working.
+pub async fn start(
+    state: &AppState,
+    user_id: &str,
+    channel: &str,
+    value: &str,
+) -> Result<PendingContactChange, ApiError> {
+    let verification_id = Uuid::new_v4().to_string();
+    let code = generate_code();
+    let expires_at = (Utc::now() + Duration::minutes(CODE_TTL_MINUTES))
+        .format("%Y-%m-%d %H:%M:%S")
+        .to_string();
+
+    let stored = stored_value(channel, value)?;
+
+    let mut tx = state.db.begin().await.map_err(ApiError::DatabaseError)?;
+    sqlx::query(&format!(
+        "UPDATE profiles SET {} = ?, updated_at = datetime('now') WHERE user_id = ?",
+        pending_column(channel)
+    ))
+    .bind(&stored)
+    .bind(user_id)
+    .execute(&mut *tx)
+    .await
+    .map_err(ApiError::DatabaseError)?;
+    sqlx::query(
+        "DELETE FROM contact_verifications WHERE user_id = ? AND channel = ? AND verified_at IS NULL",
+    )
+    .bind(user_id)
+    .bind(channel)
+    .execute(&mut *tx)
+    .await
+    .map_err(ApiError::DatabaseError)?;
+    sqlx::query(
+        r#"
+        INSERT INTO contact_verifications (id, user_id, channel, value, code_hash, expires_at)
+        VALUES (?, ?, ?, ?, ?, ?)
+        "#,
+    )
+    .bind(&verification_id)
+    .bind(user_id)
+    .bind(channel)
+    .bind(&stored)
+    .bind(hash_code(&verification_id, &code))
+    .bind(&expires_at)
+    .execute(&mut *tx)
+    .await
+    .map_err(ApiError::DatabaseError)?;
+    tx.commit().await.map_err(ApiError::DatabaseError)?;
+
+    let sent = match deliver_code(state, user_id, channel, value, &code).await {
+        Ok(()) => {
+            info!(user_id = %user_id, channel = %channel, "Contact verification code sent");
+            true
+        }
+        Err(e) => {
+            warn!(
+                user_id = %user_id,
+                channel = %channel,
+                error = %e,
+                "Failed to send contact verification code"
+            );
+            false
+        }
+    };
+
+    Ok(PendingContactChange {
+        channel: channel.to_string(),
+        value: value.to_string(),
+        expires_at,
+        sent,
+    })
+}
+
+/// Send a fresh code for the change already waiting on the channel
+pub async fn resend(
+    state: &AppState,
+    user_id: &str,
+    channel: &str,
+) -> Result<PendingContactChange, ApiError> {
+    let pending = sqlx::query_scalar::<_, Sealed>(&format!(
+        "SELECT {} FROM profiles WHERE user_id = ?",
+        pending_column(channel)
+    ))
+    .bind(user_id)
+    .fetch_optional(&state.db)
+    .await
+    .map_err(ApiError::DatabaseError)?
+    .and_then(Sealed::into_inner);
+    let value =
+        pending.ok_or_else(|| ApiError::NotFound(format!("No pending {} change", channel)))?;
+
+    let recently_sent: bool = sqlx::query_scalar(
+        r#"
+        SELECT EXISTS(
+            SELECT 1 FROM contact_verifications
+            WHERE user_id = ? AND channel = ? AND verified_at IS NULL
+              AND datetime(created_at, ?) > datetime('now')
+        )
+        "#,
+    )
+    .bind(user_id)
+    .bind(channel)
+    .bind(format!("+{} seconds", RESEND_COOLDOWN_SECS))
+    .fetch_one(&state.db)
+    .await
+    .map_err(ApiError::DatabaseError)?;
+    if recently_sent {
+        return Err(ApiError::Coded(
+            ErrorCode::RateLimitExceeded,
+            format!(
+                "A code was sent less than {} seconds ago",
+                RESEND_COOLDOWN_SECS
+            ),
+        ));
+    }
+
+    start(state, user_id, channel, &value).await
+}
+
+/// Check a code and, when it matches, make the pending value the one in use
+pub async fn confirm(
+    pool: &SqlitePool,
+    user_id: &str,
+    channel: &str,
+    code: &str,
+) -> Result<String, ApiError> {
+    let row: Option<(String, Sealed, Sealed, String, i64, bool)> = sqlx::query_as(&format!(
+        r#"
+        SELECT v.id, v.value, p.{}, v.code_hash, v.attempts,
+               datetime(v.expires_at) <= datetime('now')
+        FROM contact_verifications v
+        JOIN profiles p ON p.user_id = v.user_id
+        WHERE v.user_id = ? AND v.channel = ? AND v.verified_at IS NULL
+        ORDER BY v.created_at DESC
+        LIMIT 1
+        "#,
+        pending_column(channel)
+    ))
+    .bind(user_id)
+    .bind(channel)
+    .fetch_optional(pool)
+    .await
+    .map_err(ApiError::DatabaseError)?;
+
+    // Encrypted values differ on every write, so the code is matched to the pending change
+    // after decrypting both
+    let (verification_id, value, code_hash, attempts, expired) = row
+        .and_then(|(id, value, pending, code_hash, attempts, expired)| {
+            let value = value.into_inner()?;
+            (pending.into_inner().as_deref() == Some(value.as_str()))
+                .then_some((id, value, code_hash, attempts, expired))
+        })
+        .ok_or_else(|| invalid_code("No verification is waiting for this channel"))?;
+    if expired {
+        return Err(invalid_code("The verification code has expired"));
+    }
+    if attempts >= MAX_ATTEMPTS {
+        return Err(invalid_code(
+            "Too many wrong codes; request a new verification code",
+        ));
+    }
+    if hash_code(&verification_id, code) != code_hash {
+        sqlx::query("UPDATE contact_verifications SET attempts = attempts + 1 WHERE id = ?")
+            .bind(&verification_id)
+            .execute(pool)
+            .await
+            .map_err(ApiError::DatabaseError)?;
+        return Err(invalid_code("The verification code is incorrect"));
+    }
+
+    let mut tx = pool.begin().await.map_err(ApiError::DatabaseError)?;
+    sqlx::query("UPDATE contact_verifications SET verified_at = datetime('now') WHERE id = ?")
+        .bind(&verification_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(ApiError::DatabaseError)?;
+    let update = if channel == "email" {
+        sqlx::query(
+            r#"
+            UPDATE profiles
+            SET contact_email = ?, contact_email_verified_at = datetime('now'),
+                pending_contact_email = NULL, updated_at = datetime('now')
+            WHERE user_id = ?
+            "#,
+        )
+        .bind(&value)
+    } else {
+        sqlx::query(
+            r#"
+            UPDATE profiles
+            SET phone = ?, phone_index = ?, phone_verified_at = datetime('now'),
+                pending_phone = NULL, updated_at = datetime('now')
+            WHERE user_id = ?
+            "#,
+        )
+        .bind(pii::seal(&value)?)
+        .bind(pii::phone_index(&value))
+    };
+    update
+        .bind(user_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(ApiError::DatabaseError)?;
+    tx.commit().await.map_err(ApiError::DatabaseError)?;
+
+    info!(user_id = %user_id, channel = %channel, "Contact change verified");
+    Ok(value)
+}
+
+/// Set a contact value that needs no code, or clear it with `None`. Any pending change
+/// on the channel is dropped.
+pub async fn set_verified(
+    pool: &SqlitePool,
+    user_id: &str,
+    channel: &str,
+    value: Option<&str>,
+) -> Result<(), sqlx::Error> {
+    if channel == "email" {
+        sqlx::query(
+            r#"
+            UPDATE profiles
+            SET contact_email = ?1,
+                contact_email_verified_at = CASE WHEN ?1 IS NULL THEN NULL ELSE datetime('now') END,
+                pending_contact_email = NULL, updated_at = datetime('now')
+            WHERE user_id = ?2
+            "#,
+        )
+        .bind(value)
+        .bind(user_id)
+        .execute(pool)
+        .await?;
+    } else {
+        sqlx::query(
+            r#"
+            UPDATE profiles
+            SET phone = ?1, phone_index = ?2,
+                phone_verified_at = CASE WHEN ?1 IS NULL THEN NULL ELSE datetime('now') END,
+                pending_phone = NULL, updated_at = datetime('now')
+            WHERE user_id = ?3
+            "#,
+        )
+        .bind(pii::seal_opt(value).map_err(pii::db_error)?)
+        .bind(value.and_then(pii::phone_index))
+        .bind(user_id)
+        .execute(pool)
+        .await?;
+    }
+    Ok(())
+}
+
+/// Drop a pending change and its codes; the current value stays in use
+pub async fn cancel(pool: &SqlitePool, user_id: &str, channel: &str) -> Result<(), sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    sqlx::query(&format!(
+        "UPDATE profiles SET {} = NULL, updated_at = datetime('now') WHERE user_id = ?",
+        pending_column(channel)
+    ))
+    .bind(user_id)
+    .execute(&mut *tx)
+    .await?;
+    sqlx::query(
+        "DELETE FROM contact_verifications WHERE user_id = ? AND channel = ? AND verified_at IS NULL",
+    )
+    .bind(user_id)
+    .bind(channel)
+    .execute(&mut *tx)
+    .await?;
+    tx.commit().await
+}
+
+/// Where candidate notifications and offers go: the verified contact email, or the
+/// account email when none is set
+pub async fn notification_email(
+    pool: &SqlitePool,
+    user_id: &str,
+) -> Result<Option<String>, sqlx::Error> {
+    sqlx::query_scalar(
+        r#"
+        SELECT COALESCE(p.contact_email, u.email)
+        FROM users u
+        LEFT JOIN profiles p ON p.user_id = u.id
+        WHERE u.id = ?
+        "#,
+    )
+    .bind(user_id)
+    .fetch_optional(pool)
+    .await
+}
@@ -2,19 +2,20 @@
 
 use axum::{
     extract::{Extension, Json, Multipart, Path},
-    http::StatusCode,
-    response::IntoResponse,
+    http::{header, HeaderMap, HeaderValue},
+    response::{IntoResponse, Redirect, Response},
 };
 use infer::Infer;
 use sqlx::SqlitePool;
 use std::sync::Arc;
 use tokio::fs as tokio_fs;
 use tokio::sync::RwLock;
-use tracing::{error, info};
+use tracing::{error, info, warn};
 
 use super::super::models::{AvatarUpdateRequest, AvatarUploadResponse, MessageResponse};
 use crate::auth::{AuthedUser, User};
-use crate::common::{generate_raw_id, ApiError, AppState};
+use crate::profile::avatar_fallback;
+use crate::common::{http_cache, storage, ApiError, AppState};
 
 /// POST /api/user/avatar - Upload avatar
 pub async fn upload_avatar(
@@ -138,7 +139,8 @@ pub async fn remove_avatar(
 pub async fn serve_avatar(
     Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
     Path(filename): Path<String>,
-) -> Result<impl IntoResponse, ApiError> {
+    headers: HeaderMap,
+) -> Result<Response, ApiError> {
     let state = state_lock.read().await.clone();
 
     // Sanitize filename to prevent path traversal
@@ -155,16 +157,88 @@ pub async fn serve_avatar(
 
     let content_type = get_content_type_from_extension(&safe_filename);
 
-    Ok((
-        StatusCode::OK,
-        [
-            ("Content-Type", content_type),
-            ("Cache-Control", "public, max-age=31536000"), // 1 year cache
-        ],
+    // Uploads are named by content hash, and fallbacks by the name and email they show
+    Ok(http_cache::cached(
+        &headers,
+        content_type,
         file_content,
+        http_cache::IMMUTABLE_CACHE_CONTROL,
     ))
 }
 
+/// GET /api/users/:id/avatar - A user's avatar, or a generated fallback when none is set
+///
+/// Uploaded avatars are served as-is and external ones redirected to. Otherwise the
+/// user's Gravatar is used when `gravatar_enabled` is `true`, else an initials SVG; either
+/// is cached in the avatars directory until the user's name or email changes.
+pub async fn serve_user_avatar(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    Path(user_id): Path<String>,
+    headers: HeaderMap,
+) -> Result<Response, ApiError> {
+    let state = state_lock.read().await.clone();
+
+    let (email, name, avatar) = sqlx::query_as::<_, (String, Option<String>, Option<String>)>(
+        "SELECT email, name, avatar FROM users WHERE id = ?",
+    )
+    .bind(&user_id)
+    .fetch_optional(&state.db)
+    .await
+    .map_err(ApiError::DatabaseError)?
+    .ok_or_else(|| ApiError::NotFound("User not found".to_string()))?;
+
+    match avatar.as_deref().map(str::trim) {
+        Some(url) if url.starts_with("http") => {
+            return Ok(Redirect::temporary(url).into_response());
+        }
+        Some(url) if url.starts_with("/api/avatars/") => {
+            let filename = sanitize_filename(url.trim_start_matches("/api/avatars/"));
+            // A missing file falls through to the fallback rather than a broken image
+            if let Ok(content) = tokio_fs::read(state.avatars_dir.join(&filename)).await {
+                return Ok(avatar_response(&headers, get_content_type_from_extension(&filename), content));
+            }
+        }
+        _ => {}
+    }
+
+    let stem = avatar_fallback::fallback_stem(&user_id, name.as_deref(), &email);
+    for extension in FALLBACK_EXTENSIONS {
+        let filename = format!("{}.{}", stem, extension);
+        if let Ok(content) = tokio_fs::read(state.avatars_dir.join(&filename)).await {
+            return Ok(avatar_response(&headers, get_content_type_from_extension(&filename), content));
+        }
+    }
+
+    let gravatar_enabled = state
+        .settings_service
+        .get_setting("gravatar_enabled")
+        .await
+        .ok()
+        .flatten()
+        .is_some_and(|v| v == "true");
+    let gravatar = if gravatar_enabled {
+        fetch_gravatar(&state, &email).await
+    } else {
+        None
+    };
+    let (extension, content) = gravatar.unwrap_or_else(|| {
+        let initials = avatar_fallback::initials(name.as_deref(), &email);
+        let svg = avatar_fallback::initials_svg(&initials, avatar_fallback::background_color(&user_id));
+        ("svg", svg.into_bytes())
+    });
+
+    remove_stale_fallbacks(&state, &user_id).await;
+    let filename = format!("{}.{}", stem, extension);
+    if let Err(e) = tokio_fs::write(state.avatars_dir.join(&filename), &content).await {
+        // Still serve it; the next request regenerates
+        warn!(error = %e, user_id = %user_id, "Failed to cache fallback avatar");
+    } else {
+        info!(user_id = %user_id, filename = %filename, "Fallback avatar generated");
+    }
+
+    Ok(avatar_response(&headers, get_content_type_from_extension(&filename), content))
+}
+
 // ============================================================================
 // Helper Functions
 // ============================================================================
@@ -202,7 +276,7 @@ async fn download_and_store_avatar(
 
     // Generate filename and save
     let extension = get_extension_from_url(external_url).unwrap_or("jpg");
-    let filename = format!("avatar_{}_{}.{}", user_id, generate_raw_id(8), extension);
+    let filename = format!("avatar_{}.{}", user_id, extension);
 
     save_avatar_file(state, user_id, &bytes, &filename).await
 }
@@ -215,7 +289,7 @@ async fn save_avatar_file(
 ) -> Result<String, ApiError> {
     // Generate safe filename
     let extension = get_extension_from_filename(original_filename).unwrap_or("jpg");
-    let filename = format!("avatar_{}_{}.{}", user_id, generate_raw_id(8), extension);
+    let filename = format!("avatar_{}_{}.{}", user_id, storage::content_hash(data), extension);
     let file_path = state.avatars_dir.join(&filename);
 
     // Save file
@@ -252,6 +326,65 @@ async fn update_user_avatar(
     Ok(())
 }
 
+/// Extensions a cached fallback can have, Gravatar images first
+const FALLBACK_EXTENSIONS: &[&str] = &["jpg", "png", "gif", "webp", "svg"];
+
+fn avatar_response(headers: &HeaderMap, content_type: &'static str, content: Vec<u8>) -> Response {
+    // Short, since the URL stays the same when the user uploads a real avatar
+    let mut response = http_cache::cached(headers, content_type, content, "public, max-age=3600");
+    response.headers_mut().insert(
+        header::X_CONTENT_TYPE_OPTIONS,
+        HeaderValue::from_static("nosniff"),
+    );
+    response
+}
+
+/// The user's Gravatar, or `None` if they have none or it could not be fetched
+async fn fetch_gravatar(state: &AppState, email: &str) -> Option<(&'static str, Vec<u8>)> {
+    let url = format!(
+        "https://www.gravatar.com/avatar/{}?s=256&d=404",
+        avatar_fallback::gravatar_hash(email)
+    );
+    let response = match state.http.get(&url).send().await {
+        Ok(response) if response.status().is_success() => response,
+        Ok(_) => return None,
+        Err(e) => {
+            warn!(error = %e, "Failed to fetch Gravatar, using initials avatar");
+            return None;
+        }
+    };
+    let bytes = response.bytes().await.ok()?;
+    if !is_valid_image_type(&bytes) {
+        return None;
+    }
+    let extension = match Infer::new().get(&bytes)?.mime_type() {
+        "image/png" => "png",
+        "image/gif" => "gif",
+        "image/webp" => "webp",
+        _ => "jpg",
+    };
+    Some((extension, bytes.to_vec()))
+}
+
+/// Delete a user's cached fallbacks so a renamed user doesn't leave old files behind
+async fn remove_stale_fallbacks(state: &AppState, user_id: &str) {
+    let prefix = format!("{}{}_", avatar_fallback::FALLBACK_PREFIX, user_id);
+    let Ok(mut entries) = tokio_fs::read_dir(&state.avatars_dir).await else {
+        return;
+    };
+    while let Ok(Some(entry)) = entries.next_entry().await {
+        let name = entry.file_name().to_string_lossy().to_string();
+        // The rest must be exactly the key, so another user whose ID extends this one's is safe
+        let is_own = name
+            .strip_prefix(&prefix)
+            .and_then(|rest| rest.split_once('.'))
+            .is_some_and(|(key, _)| key.len() == 16 && key.chars().all(|c| c.is_ascii_hexdigit()));
+        if is_own {
+            let _ = tokio_fs::remove_file(entry.path()).await;
+        }
+    }
+}
+
 fn is_valid_image_type(data: &[u8]) -> bool {
     let infer = Infer::new();
     if let Some(info) = infer.get(data) {
@@ -269,6 +402,7 @@ fn get_content_type_from_extension(filename: &str) -> &'static str {
         Some("png") => "image/png",
         Some("gif") => "image/gif",
         Some("webp") => "image/webp",
+        Some("svg") => "image/svg+xml",
         Some("jpg") | Some("jpeg") => "image/jpeg",
         _ => "image/jpeg",
     }
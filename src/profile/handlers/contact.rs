@@ -0,0 +1,70 @@
+// src/profile/handlers/contact.rs
+
+use axum::{
+    extract::{Extension, Json, Path},
+    http::StatusCode,
+};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::info;
+
+use super::super::completeness::refresh_completeness;
+use super::super::contact_verification;
+use super::super::models::{
+    PendingContactChange, Profile, ResendContactCodeRequest, VerifyContactRequest,
+};
+use crate::auth::AuthedUser;
+use crate::common::{ApiError, AppState};
+
+/// POST /api/profile/contact/verify - Confirm a pending email or phone change with its code
+pub async fn verify_contact(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Json(request): Json<VerifyContactRequest>,
+) -> Result<Json<Profile>, ApiError> {
+    let state = state_lock.read().await.clone();
+    contact_verification::validate_channel(&request.channel)?;
+
+    contact_verification::confirm(&state.db, &authed.id, &request.channel, &request.code).await?;
+    refresh_completeness(&state.db, &authed.id).await;
+
+    let profile = sqlx::query_as::<_, Profile>("SELECT * FROM profiles WHERE user_id = ?")
+        .bind(&authed.id)
+        .fetch_one(&state.db)
+        .await
+        .map_err(ApiError::DatabaseError)?;
+
+    Ok(Json(profile))
+}
+
+/// POST /api/profile/contact/resend - Send a new code for a pending change
+pub async fn resend_contact_code(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Json(request): Json<ResendContactCodeRequest>,
+) -> Result<Json<PendingContactChange>, ApiError> {
+    let state = state_lock.read().await.clone();
+    contact_verification::validate_channel(&request.channel)?;
+
+    let pending = contact_verification::resend(&state, &authed.id, &request.channel).await?;
+    Ok(Json(pending))
+}
+
+/// DELETE /api/profile/contact/pending/:channel - Abandon a pending change and keep the
+/// current value
+pub async fn cancel_contact_change(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Path(channel): Path<String>,
+) -> Result<StatusCode, ApiError> {
+    let state = state_lock.read().await.clone();
+    contact_verification::validate_channel(&channel)?;
+
+    contact_verification::cancel(&state.db, &authed.id, &channel)
+        .await
+        .map_err(ApiError::DatabaseError)?;
+
+    info!(user_id = %authed.id, channel = %channel, "Pending contact change cancelled");
+
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -8,6 +8,7 @@ use std::sync::Arc;
 use tokio::sync::RwLock;
 use tracing::{debug, error, info, warn};
 
+use super::super::completeness::refresh_completeness;
 use super::super::models::{CreateEducationRequest, Education, UpdateEducationRequest};
 use super::super::validators::EducationValidator;
 use crate::auth::AuthedUser;
@@ -116,6 +117,8 @@ pub async fn create_education(
             ApiError::DatabaseError(e)
         })?;
 
+    refresh_completeness(&state.db, &authed.id).await;
+
     info!(
         user_id = %authed.id,
         education_id = %education_id,
@@ -286,6 +289,8 @@ pub async fn delete_education(
         ));
     }
 
+    refresh_completeness(&state.db, &authed.id).await;
+
     info!(
         user_id = %authed.id,
         education_id = %education_id,
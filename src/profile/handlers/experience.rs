@@ -8,6 +8,7 @@ use std::sync::Arc;
 use tokio::sync::RwLock;
 use tracing::{debug, error, info, warn};
 
+use super::super::completeness::refresh_completeness;
 use super::super::models::{CreateExperienceRequest, Experience, UpdateExperienceRequest};
 use super::super::validators::ExperienceValidator;
 use crate::auth::AuthedUser;
@@ -115,6 +116,8 @@ pub async fn create_experience(
             ApiError::DatabaseError(e)
         })?;
 
+    refresh_completeness(&state.db, &authed.id).await;
+
     info!(
         user_id = %authed.id,
         experience_id = %experience_id,
@@ -277,6 +280,8 @@ pub async fn delete_experience(
         return Err(ApiError::BadRequest("Experience not found".to_string()));
     }
 
+    refresh_completeness(&state.db, &authed.id).await;
+
     info!(
         user_id = %authed.id,
         experience_id = %experience_id,
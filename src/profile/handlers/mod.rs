@@ -1,7 +1,11 @@
 // src/profile/handlers/mod.rs
 
 pub mod avatar;
+pub mod contact;
 pub mod education;
 pub mod experience;
 pub mod profile;
+pub mod public_profile;
+pub mod sms;
 pub mod testimonials;
+pub mod whatsapp;
@@ -5,10 +5,17 @@ use std::sync::Arc;
 use tokio::sync::RwLock;
 use tracing::{error, info};
 
+use super::super::avatar_fallback;
+use super::super::contact_verification;
+use super::super::completeness::{
+    recompute_completeness, refresh_completeness, ProfileCompleteness,
+};
 use super::super::models::{Profile, UpdateProfileRequest};
 use crate::auth::{AuthedUser, User};
 use crate::candidates::models::Resume;
 use crate::common::{ApiError, AppState};
+use crate::services::moderation::{self, ModeratedContent};
+use crate::services::pii;
 
 /// GET /api/profile - Get user profile
 pub async fn profile_handler(
@@ -48,11 +55,12 @@ pub async fn profile_handler(
         .unwrap_or_else(|| "pending".to_string());
 
     // Fetch full user data for response
-    let user = sqlx::query_as::<_, User>("SELECT * FROM users WHERE id = ?")
+    let mut user = sqlx::query_as::<_, User>("SELECT * FROM users WHERE id = ?")
         .bind(&authed.id)
         .fetch_one(&state.db)
         .await
         .map_err(ApiError::DatabaseError)?;
+    user.avatar = Some(avatar_fallback::avatar_url(&user.id, user.avatar.as_deref()));
 
     let response = serde_json::json!({
         "user": user,
@@ -75,6 +83,41 @@ pub async fn update_profile_handler(
 
     info!(user_id = %authed.id, "Profile update request received");
 
+    let current = sqlx::query_as::<_, Profile>("SELECT * FROM profiles WHERE user_id = ?")
+        .bind(&authed.id)
+        .fetch_optional(&state.db)
+        .await
+        .map_err(ApiError::DatabaseError)?;
+
+    // A new phone number or contact email is only used once the code sent to it is
+    // confirmed; clearing one, or choosing the sign-in email, takes effect right away
+    let mut direct: Vec<(&str, Option<String>)> = Vec::new();
+    let mut needs_code: Vec<(&str, String)> = Vec::new();
+    if let Some(phone) = request.phone.as_deref() {
+        if phone.trim().is_empty() {
+            direct.push(("phone", None));
+        } else {
+            let phone = contact_verification::normalize_phone(phone)?;
+            if current.as_ref().and_then(|p| p.phone.as_deref()) != Some(phone.as_str()) {
+                needs_code.push(("phone", phone));
+            }
+        }
+    }
+    if let Some(email) = request.contact_email.as_deref() {
+        if email.trim().is_empty() {
+            direct.push(("email", None));
+        } else {
+            let email = contact_verification::normalize_email(email)?;
+            if email == authed.email.to_lowercase() {
+                direct.push(("email", Some(email)));
+            } else if current.as_ref().and_then(|p| p.contact_email.as_deref())
+                != Some(email.as_str())
+            {
+                needs_code.push(("email", email));
+            }
+        }
+    }
+
     // Convert skills array to JSON string if provided
     let skills_json = request
         .skills
@@ -104,8 +147,8 @@ pub async fn update_profile_handler(
     .bind(&authed.id)
     .bind(request.first_name.as_deref())
     .bind(request.last_name.as_deref())
-    .bind(request.phone.as_deref())
-    .bind(request.location.as_deref())
+    .bind(None::<&str>)
+    .bind(pii::seal_opt(request.location.as_deref())?)
     .bind(request.bio.as_deref())
     .bind(request.website.as_deref())
     .bind(request.linkedin_url.as_deref())
@@ -122,6 +165,21 @@ pub async fn update_profile_handler(
         ApiError::DatabaseError(e)
     })?;
 
+    for (channel, value) in direct {
+        contact_verification::set_verified(&state.db, &authed.id, channel, value.as_deref())
+            .await
+            .map_err(ApiError::DatabaseError)?;
+    }
+    for (channel, value) in needs_code {
+        contact_verification::start(&state, &authed.id, channel, &value).await?;
+    }
+
+    refresh_completeness(&state.db, &authed.id).await;
+
+    if let Some(bio) = request.bio.as_deref() {
+        moderation::moderate(&state, ModeratedContent::Bio, &authed.id, &authed.id, bio).await;
+    }
+
     // Fetch the updated profile
     let profile = sqlx::query_as::<_, Profile>("SELECT * FROM profiles WHERE user_id = ?")
         .bind(&authed.id)
@@ -140,3 +198,46 @@ pub async fn update_profile_handler(
 
     Ok(Json(profile))
 }
+
+/// GET /api/profile/completeness - Get the cached profile completeness score
+pub async fn get_profile_completeness(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+) -> Result<Json<ProfileCompleteness>, ApiError> {
+    let state = state_lock.read().await.clone();
+
+    let profile = sqlx::query_as::<_, Profile>("SELECT * FROM profiles WHERE user_id = ?")
+        .bind(&authed.id)
+        .fetch_optional(&state.db)
+        .await
+        .map_err(ApiError::DatabaseError)?;
+
+    // Serve the cached value when present; profiles created before scoring existed
+    // have no cache yet and get computed on first read
+    let cached = profile.and_then(|p| {
+        let score = p.completeness_score?;
+        let missing = serde_json::from_str(p.completeness_missing.as_deref()?).ok()?;
+        Some(ProfileCompleteness {
+            score,
+            missing,
+            computed_at: p.completeness_updated_at.unwrap_or_default(),
+        })
+    });
+
+    if let Some(completeness) = cached {
+        return Ok(Json(completeness));
+    }
+
+    let completeness = recompute_completeness(&state.db, &authed.id)
+        .await
+        .map_err(|e| {
+            error!(
+                error = %e,
+                user_id = %authed.id,
+                "Database error computing profile completeness"
+            );
+            ApiError::DatabaseError(e)
+        })?;
+
+    Ok(Json(completeness))
+}
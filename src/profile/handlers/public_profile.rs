@@ -0,0 +1,322 @@
+// src/profile/handlers/public_profile.rs
+
+use axum::extract::{Extension, Json, Path};
+use regex::Regex;
+use std::sync::{Arc, OnceLock};
+use tokio::sync::RwLock;
+use tracing::{error, info, warn};
+
+use super::super::models::{
+    Education, Experience, Profile, PublicCandidatePortfolio, PublicEducation, PublicExperience,
+    PublicProfileSettings, PublicSections, PublicTestimonial, PublicVideo, Testimonial,
+    UpdatePublicProfileRequest,
+};
+use super::super::validators::PublicProfileValidator;
+use crate::auth::AuthedUser;
+use crate::candidates::models::Video;
+use crate::common::{ApiError, AppState, Validator};
+use crate::services::moderation::{self, ModeratedContent, HELD_CONTENT_IDS};
+
+/// GET /api/profile/public - Get the caller's public portfolio settings
+pub async fn get_public_profile_settings(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+) -> Result<Json<PublicProfileSettings>, ApiError> {
+    let state = state_lock.read().await.clone();
+
+    let profile = sqlx::query_as::<_, Profile>("SELECT * FROM profiles WHERE user_id = ?")
+        .bind(&authed.id)
+        .fetch_optional(&state.db)
+        .await
+        .map_err(ApiError::DatabaseError)?;
+
+    Ok(Json(settings_from_profile(profile.as_ref())))
+}
+
+/// PUT /api/profile/public - Opt in/out of the public portfolio and set its slug and sections
+pub async fn update_public_profile_settings(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Json(request): Json<UpdatePublicProfileRequest>,
+) -> Result<Json<PublicProfileSettings>, ApiError> {
+    let state = state_lock.read().await.clone();
+
+    let validation_result = PublicProfileValidator.validate(&request);
+    if !validation_result.is_valid {
+        warn!(
+            user_id = %authed.id,
+            errors = ?validation_result.errors,
+            "Public profile update validation failed"
+        );
+        return Err(ApiError::from(validation_result));
+    }
+
+    let existing = sqlx::query_as::<_, Profile>("SELECT * FROM profiles WHERE user_id = ?")
+        .bind(&authed.id)
+        .fetch_optional(&state.db)
+        .await
+        .map_err(ApiError::DatabaseError)?;
+
+    if let Some(slug) = &request.slug {
+        let taken: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM profiles WHERE public_slug = ? AND user_id != ?",
+        )
+        .bind(slug)
+        .bind(&authed.id)
+        .fetch_one(&state.db)
+        .await
+        .map_err(ApiError::DatabaseError)?;
+
+        if taken > 0 {
+            return Err(ApiError::BadRequest(
+                "This profile URL is already taken".to_string(),
+            ));
+        }
+    }
+
+    let current = settings_from_profile(existing.as_ref());
+    let enabled = request.enabled.unwrap_or(current.enabled);
+    let slug = request.slug.clone().or(current.slug);
+    let sections = request.sections.unwrap_or(current.sections);
+
+    if enabled && slug.is_none() {
+        return Err(ApiError::BadRequest(
+            "Choose a profile URL before publishing your profile".to_string(),
+        ));
+    }
+
+    let sections_json = serde_json::to_string(&sections).unwrap_or_else(|_| "{}".to_string());
+
+    sqlx::query(
+        r#"
+        INSERT INTO profiles (user_id, public_slug, public_enabled, public_sections)
+        VALUES (?, ?, ?, ?)
+        ON CONFLICT(user_id) DO UPDATE SET
+            public_slug = excluded.public_slug,
+            public_enabled = excluded.public_enabled,
+            public_sections = excluded.public_sections,
+            updated_at = datetime('now')
+        "#,
+    )
+    .bind(&authed.id)
+    .bind(slug.as_deref())
+    .bind(enabled as i64)
+    .bind(&sections_json)
+    .execute(&state.db)
+    .await
+    .map_err(|e| {
+        error!(
+            error = %e,
+            user_id = %authed.id,
+            "Database error updating public profile settings"
+        );
+        ApiError::DatabaseError(e)
+    })?;
+
+    info!(
+        user_id = %authed.id,
+        enabled = enabled,
+        slug = ?slug,
+        "Public profile settings updated"
+    );
+
+    Ok(Json(PublicProfileSettings {
+        enabled,
+        url: slug.as_ref().map(|s| public_url(s)),
+        slug,
+        sections,
+    }))
+}
+
+/// GET /api/public/candidates/:slug - Public candidate portfolio (no auth)
+pub async fn get_public_portfolio(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    Path(slug): Path<String>,
+) -> Result<Json<PublicCandidatePortfolio>, ApiError> {
+    let state = state_lock.read().await.clone();
+
+    let profile = sqlx::query_as::<_, Profile>(
+        "SELECT * FROM profiles WHERE public_slug = ? AND public_enabled = 1",
+    )
+    .bind(&slug)
+    .fetch_optional(&state.db)
+    .await
+    .map_err(ApiError::DatabaseError)?
+    .ok_or_else(|| ApiError::NotFound("Profile not found".to_string()))?;
+
+    let sections = PublicSections::from_json(profile.public_sections.as_deref());
+
+    let avatar: Option<String> = sqlx::query_scalar("SELECT avatar FROM users WHERE id = ?")
+        .bind(&profile.user_id)
+        .fetch_optional(&state.db)
+        .await
+        .map_err(ApiError::DatabaseError)?
+        .flatten();
+
+    let experience = if sections.experience {
+        let rows = sqlx::query_as::<_, Experience>(
+            "SELECT * FROM experiences WHERE user_id = ? ORDER BY start_date DESC",
+        )
+        .bind(&profile.user_id)
+        .fetch_all(&state.db)
+        .await
+        .map_err(ApiError::DatabaseError)?;
+
+        Some(
+            rows.into_iter()
+                .map(|e| PublicExperience {
+                    company: e.company,
+                    title: e.title,
+                    start_date: e.start_date,
+                    end_date: e.end_date,
+                    description: e.description.map(|d| redact_contact_details(&d)),
+                })
+                .collect(),
+        )
+    } else {
+        None
+    };
+
+    let education = if sections.education {
+        let rows = sqlx::query_as::<_, Education>(
+            "SELECT * FROM education WHERE user_id = ? ORDER BY start_date DESC",
+        )
+        .bind(&profile.user_id)
+        .fetch_all(&state.db)
+        .await
+        .map_err(ApiError::DatabaseError)?;
+
+        Some(
+            rows.into_iter()
+                .map(|e| PublicEducation {
+                    institution: e.institution,
+                    degree: e.degree,
+                    field_of_study: e.field_of_study,
+                    start_date: e.start_date,
+                    end_date: e.end_date,
+                })
+                .collect(),
+        )
+    } else {
+        None
+    };
+
+    let testimonials = if sections.testimonials {
+        let rows = sqlx::query_as::<_, Testimonial>(
+            &format!(
+                "SELECT * FROM testimonials WHERE user_id = ? AND approved = 1 AND id NOT IN ({}) ORDER BY created_at DESC",
+                HELD_CONTENT_IDS
+            ),
+        )
+        .bind(&profile.user_id)
+        .bind(ModeratedContent::Testimonial.as_str())
+        .fetch_all(&state.db)
+        .await
+        .map_err(ApiError::DatabaseError)?;
+
+        Some(
+            rows.into_iter()
+                .map(|t| PublicTestimonial {
+                    content: redact_contact_details(&t.content),
+                    rating: t.rating,
+                    position: t.position,
+                    company: t.company,
+                    created_at: t.created_at,
+                })
+                .collect(),
+        )
+    } else {
+        None
+    };
+
+    // Only linked YouTube videos are public; uploaded files stay private
+    let videos = if sections.videos {
+        let rows = sqlx::query_as::<_, Video>(
+            "SELECT * FROM videos WHERE user_id = ? AND video_source = 'youtube' AND youtube_video_id IS NOT NULL ORDER BY uploaded_at DESC",
+        )
+        .bind(&profile.user_id)
+        .fetch_all(&state.db)
+        .await
+        .map_err(ApiError::DatabaseError)?;
+
+        let held_titles: Vec<String> = sqlx::query_scalar(HELD_CONTENT_IDS)
+            .bind(ModeratedContent::VideoTitle.as_str())
+            .fetch_all(&state.db)
+            .await
+            .map_err(ApiError::DatabaseError)?;
+
+        Some(
+            rows.into_iter()
+                .filter_map(|v| {
+                    Some(PublicVideo {
+                        youtube_video_id: v.youtube_video_id?,
+                        // Titles held for moderation are hidden, the video itself stays
+                        title: v.youtube_title.filter(|_| !held_titles.contains(&v.id)),
+                        description: v.youtube_description.map(|d| redact_contact_details(&d)),
+                        thumbnail_url: v.youtube_thumbnail_url,
+                    })
+                })
+                .collect(),
+        )
+    } else {
+        None
+    };
+
+    let bio_held = moderation::is_held(&state.db, ModeratedContent::Bio, &profile.user_id).await;
+
+    Ok(Json(PublicCandidatePortfolio {
+        slug,
+        first_name: profile.first_name,
+        last_name: profile.last_name,
+        avatar,
+        bio: if sections.bio && !bio_held {
+            profile.bio.map(|b| redact_contact_details(&b))
+        } else {
+            None
+        },
+        experience,
+        education,
+        testimonials,
+        videos,
+    }))
+}
+
+fn settings_from_profile(profile: Option<&Profile>) -> PublicProfileSettings {
+    let slug = profile.and_then(|p| p.public_slug.clone());
+    PublicProfileSettings {
+        enabled: profile.and_then(|p| p.public_enabled).unwrap_or(0) != 0,
+        url: slug.as_ref().map(|s| public_url(s)),
+        slug,
+        sections: PublicSections::from_json(profile.and_then(|p| p.public_sections.as_deref())),
+    }
+}
+
+fn public_url(slug: &str) -> String {
+    format!("/api/public/candidates/{}", slug)
+}
+
+/// Strip email addresses and phone numbers that candidates put in free text
+pub(crate) fn redact_contact_details(text: &str) -> String {
+    static EMAIL: OnceLock<Regex> = OnceLock::new();
+    static PHONE: OnceLock<Regex> = OnceLock::new();
+
+    let email = EMAIL.get_or_init(|| {
+        Regex::new(r"[A-Za-z0-9._%+-]+@[A-Za-z0-9.-]+\.[A-Za-z]{2,}").expect("valid email regex")
+    });
+    let phone =
+        PHONE.get_or_init(|| Regex::new(r"\+?\d[\d\s().-]{7,}\d").expect("valid phone regex"));
+
+    let without_emails = email.replace_all(text, "[redacted]");
+
+    // Require enough digits for a phone number so date ranges like 2019-2021 survive
+    phone
+        .replace_all(&without_emails, |caps: &regex::Captures| {
+            let matched = &caps[0];
+            if matched.chars().filter(|c| c.is_ascii_digit()).count() >= 10 {
+                "[redacted]".to_string()
+            } else {
+                matched.to_string()
+            }
+        })
+        .into_owned()
+}
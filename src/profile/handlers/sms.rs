@@ -0,0 +1,62 @@
+// src/profile/handlers/sms.rs
+
+use axum::extract::{Extension, Json};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::info;
+
+use super::super::models::{SmsPreferences, UpdateSmsPreferencesRequest};
+use crate::auth::AuthedUser;
+use crate::common::{ApiError, AppState};
+use crate::services::{notification_preferences, sms};
+
+/// GET /api/profile/sms - SMS opt-in, quiet hours and the number texts go to
+pub async fn get_sms_preferences(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+) -> Result<Json<SmsPreferences>, ApiError> {
+    let state = state_lock.read().await.clone();
+
+    let prefs = notification_preferences::sms(&state.db, &authed.id)
+        .await
+        .map_err(ApiError::DatabaseError)?;
+    Ok(Json(prefs))
+}
+
+/// PUT /api/profile/sms - Opt in or out of texts and set quiet hours
+///
+/// Opting in needs a verified phone number.
+pub async fn update_sms_preferences(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Json(request): Json<UpdateSmsPreferencesRequest>,
+) -> Result<Json<SmsPreferences>, ApiError> {
+    let state = state_lock.read().await.clone();
+
+    sms::validate_quiet_hours(request.quiet_hours_start, request.quiet_hours_end)
+        .map_err(ApiError::ValidationError)?;
+
+    let mut prefs = notification_preferences::sms(&state.db, &authed.id)
+        .await
+        .map_err(ApiError::DatabaseError)?;
+    if request.opted_in && prefs.phone.is_none() {
+        return Err(ApiError::ValidationError(
+            "Verify a phone number before opting in to text messages".to_string(),
+        ));
+    }
+    prefs.opted_in = request.opted_in;
+    prefs.quiet_hours_start = request.quiet_hours_start;
+    prefs.quiet_hours_end = request.quiet_hours_end;
+
+    sms::save_preferences(&state.db, &authed.id, &prefs)
+        .await
+        .map_err(ApiError::DatabaseError)?;
+
+    info!(
+        user_id = %authed.id,
+        opted_in = prefs.opted_in,
+        "SMS preferences updated"
+    );
+
+    Ok(Json(prefs))
+}
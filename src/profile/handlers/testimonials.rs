@@ -13,6 +13,7 @@ use super::super::models::{
 };
 use crate::auth::{AuthedUser, User};
 use crate::common::{generate_testimonial_id, ApiError, AppState};
+use crate::services::moderation::{self, ModeratedContent, HELD_CONTENT_IDS};
 
 /// GET /api/testimonials - Get approved and featured testimonials (public)
 pub async fn get_public_testimonials(
@@ -21,8 +22,12 @@ pub async fn get_public_testimonials(
     let state = state_lock.read().await.clone();
 
     let testimonials: Vec<Testimonial> = sqlx::query_as(
-        "SELECT * FROM testimonials WHERE approved = 1 AND featured = 1 ORDER BY created_at DESC LIMIT 10"
+        &format!(
+            "SELECT * FROM testimonials WHERE approved = 1 AND featured = 1 AND id NOT IN ({}) ORDER BY created_at DESC LIMIT 10",
+            HELD_CONTENT_IDS
+        )
     )
+    .bind(ModeratedContent::Testimonial.as_str())
     .fetch_all(&state.db)
     .await
     .map_err(ApiError::DatabaseError)?;
@@ -100,6 +105,15 @@ pub async fn create_testimonial(
 
     info!(testimonial_id = %id, user_id = %authed.id, "Testimonial created");
 
+    moderation::moderate(
+        &state,
+        ModeratedContent::Testimonial,
+        &id,
+        &authed.id,
+        &testimonial.content,
+    )
+    .await;
+
     Ok(Json(testimonial))
 }
 
@@ -130,6 +144,7 @@ pub async fn update_testimonial(
     let mut updates = Vec::new();
     let mut values: Vec<String> = Vec::new();
 
+    let new_content = req.content.clone();
     if let Some(content) = req.content {
         updates.push("content = ?");
         values.push(content);
@@ -182,6 +197,17 @@ pub async fn update_testimonial(
             .map_err(ApiError::DatabaseError)?;
     }
 
+    if let Some(content) = new_content {
+        moderation::moderate(
+            &state,
+            ModeratedContent::Testimonial,
+            &id,
+            &existing.user_id,
+            &content,
+        )
+        .await;
+    }
+
     let updated: Testimonial = sqlx::query_as("SELECT * FROM testimonials WHERE id = ?")
         .bind(&id)
         .fetch_one(&state.db)
@@ -0,0 +1,59 @@
+// src/profile/handlers/whatsapp.rs
+
+use axum::extract::{Extension, Json};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::info;
+
+use super::super::models::{UpdateWhatsAppPreferencesRequest, WhatsAppPreferences};
+use crate::auth::AuthedUser;
+use crate::common::{ApiError, AppState};
+use crate::services::{notification_preferences, whatsapp};
+
+/// GET /api/profile/whatsapp - WhatsApp opt-in and the number messages go to
+pub async fn get_whatsapp_preferences(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+) -> Result<Json<WhatsAppPreferences>, ApiError> {
+    let state = state_lock.read().await.clone();
+
+    let prefs = notification_preferences::whatsapp(&state.db, &authed.id)
+        .await
+        .map_err(ApiError::DatabaseError)?;
+    Ok(Json(prefs))
+}
+
+/// PUT /api/profile/whatsapp - Opt in or out of interview messages on WhatsApp
+///
+/// Opting in needs a verified phone number.
+pub async fn update_whatsapp_preferences(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    authed: AuthedUser,
+    Json(request): Json<UpdateWhatsAppPreferencesRequest>,
+) -> Result<Json<WhatsAppPreferences>, ApiError> {
+    let state = state_lock.read().await.clone();
+
+    let prefs = notification_preferences::whatsapp(&state.db, &authed.id)
+        .await
+        .map_err(ApiError::DatabaseError)?;
+    if request.opted_in && prefs.phone.is_none() {
+        return Err(ApiError::ValidationError(
+            "Verify a phone number before opting in to WhatsApp messages".to_string(),
+        ));
+    }
+
+    whatsapp::set_opt_in(&state.db, &authed.id, request.opted_in)
+        .await
+        .map_err(ApiError::DatabaseError)?;
+
+    info!(
+        user_id = %authed.id,
+        opted_in = request.opted_in,
+        "WhatsApp preferences updated"
+    );
+
+    let prefs = notification_preferences::whatsapp(&state.db, &authed.id)
+        .await
+        .map_err(ApiError::DatabaseError)?;
+    Ok(Json(prefs))
+}
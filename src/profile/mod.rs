@@ -1,5 +1,8 @@
 // src/profile/mod.rs
 
+pub mod avatar_fallback;
+pub mod completeness;
+pub mod contact_verification;
 pub mod handlers;
 pub mod models;
 pub mod routes;
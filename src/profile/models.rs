@@ -3,6 +3,9 @@
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 
+use crate::services::pii::Sealed;
+use validator::Validate;
+
 // ============================================================================
 // Profile Management Models
 // ============================================================================
@@ -35,41 +38,55 @@ pub struct Education {
 }
 
 // Request models for profile management
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Validate)]
 pub struct CreateExperienceRequest {
+    #[validate(length(max = 255, message = "Company name must be less than 255 characters"))]
     pub company: String,
+    #[validate(length(max = 255, message = "Job title must be less than 255 characters"))]
     pub title: String,
     pub start_date: String,
     pub end_date: Option<String>,
+    #[validate(length(max = 2000, message = "Description must be less than 2000 characters"))]
     pub description: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Validate)]
 pub struct UpdateExperienceRequest {
+    #[validate(length(max = 255, message = "Company name must be less than 255 characters"))]
     pub company: Option<String>,
+    #[validate(length(max = 255, message = "Job title must be less than 255 characters"))]
     pub title: Option<String>,
     pub start_date: Option<String>,
     pub end_date: Option<String>,
+    #[validate(length(max = 2000, message = "Description must be less than 2000 characters"))]
     pub description: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Validate)]
 pub struct CreateEducationRequest {
+    #[validate(length(max = 255, message = "Institution name must be less than 255 characters"))]
     pub institution: String,
+    #[validate(length(max = 255, message = "Degree must be less than 255 characters"))]
     pub degree: String,
+    #[validate(length(max = 255, message = "Field of study must be less than 255 characters"))]
     pub field_of_study: Option<String>,
     pub start_date: String,
     pub end_date: Option<String>,
+    #[validate(length(max = 2000, message = "Description must be less than 2000 characters"))]
     pub description: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Validate)]
 pub struct UpdateEducationRequest {
+    #[validate(length(max = 255, message = "Institution name must be less than 255 characters"))]
     pub institution: Option<String>,
+    #[validate(length(max = 255, message = "Degree must be less than 255 characters"))]
     pub degree: Option<String>,
+    #[validate(length(max = 255, message = "Field of study must be less than 255 characters"))]
     pub field_of_study: Option<String>,
     pub start_date: Option<String>,
     pub end_date: Option<String>,
+    #[validate(length(max = 2000, message = "Description must be less than 2000 characters"))]
     pub description: Option<String>,
 }
 
@@ -85,7 +102,10 @@ pub struct Profile {
     pub first_name: Option<String>,
     #[serde(rename = "lastName")]
     pub last_name: Option<String>,
+    #[sqlx(try_from = "Sealed")]
+    #[serde(serialize_with = "crate::services::masking::phone_opt")]
     pub phone: Option<String>,
+    #[sqlx(try_from = "Sealed")]
     pub location: Option<String>,
     pub bio: Option<String>,
     pub website: Option<String>,
@@ -105,6 +125,41 @@ pub struct Profile {
     pub resume_status: Option<String>,
     #[serde(rename = "lastResumeId")]
     pub last_resume_id: Option<String>,
+    #[serde(rename = "completenessScore")]
+    pub completeness_score: Option<i64>,
+    #[serde(skip)]
+    pub completeness_missing: Option<String>,
+    #[serde(skip)]
+    pub completeness_updated_at: Option<String>,
+    #[serde(rename = "publicSlug")]
+    pub public_slug: Option<String>,
+    #[serde(rename = "publicEnabled")]
+    pub public_enabled: Option<i64>,
+    #[serde(skip)]
+    pub public_sections: Option<String>,
+    /// Verified email for notifications and offers; the account email is used when unset
+    #[serde(
+        rename = "contactEmail",
+        serialize_with = "crate::services::masking::email_opt"
+    )]
+    pub contact_email: Option<String>,
+    #[serde(rename = "contactEmailVerifiedAt")]
+    pub contact_email_verified_at: Option<String>,
+    /// New contact email waiting for its code to be confirmed
+    #[serde(
+        rename = "pendingContactEmail",
+        serialize_with = "crate::services::masking::email_opt"
+    )]
+    pub pending_contact_email: Option<String>,
+    #[serde(rename = "phoneVerifiedAt")]
+    pub phone_verified_at: Option<String>,
+    /// New phone number waiting for its code to be confirmed
+    #[serde(
+        rename = "pendingPhone",
+        serialize_with = "crate::services::masking::phone_opt"
+    )]
+    #[sqlx(try_from = "Sealed")]
+    pub pending_phone: Option<String>,
 }
 
 #[derive(Deserialize)]
@@ -122,6 +177,200 @@ pub struct UpdateProfileRequest {
     #[serde(rename = "githubUrl")]
     pub github_url: Option<String>,
     pub skills: Option<Vec<String>>,
+    #[serde(rename = "contactEmail")]
+    pub contact_email: Option<String>,
+}
+
+// ============================================================================
+// Contact Verification Models
+// ============================================================================
+
+/// A contact change waiting to be confirmed
+#[derive(Debug, Serialize)]
+pub struct PendingContactChange {
+    pub channel: String,
+    pub value: String,
+    #[serde(rename = "expiresAt")]
+    pub expires_at: String,
+    /// False when the code could not be sent; request a new one to retry
+    pub sent: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct VerifyContactRequest {
+    pub channel: String,
+    pub code: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ResendContactCodeRequest {
+    pub channel: String,
+}
+
+// ============================================================================
+// SMS Preference Models
+// ============================================================================
+
+/// Whether time-critical updates are also texted, and when not to
+#[derive(Debug, Clone, Serialize)]
+pub struct SmsPreferences {
+    #[serde(rename = "optedIn")]
+    pub opted_in: bool,
+    /// Local hour (0-23) quiet hours begin; none when unset
+    #[serde(rename = "quietHoursStart")]
+    pub quiet_hours_start: Option<i64>,
+    /// Local hour (0-23) quiet hours end
+    #[serde(rename = "quietHoursEnd")]
+    pub quiet_hours_end: Option<i64>,
+    /// Verified number texts go to
+    pub phone: Option<String>,
+}
+
+/// Replaces the stored preferences; leave both hours out to have no quiet hours
+#[derive(Debug, Deserialize)]
+pub struct UpdateSmsPreferencesRequest {
+    #[serde(rename = "optedIn")]
+    pub opted_in: bool,
+    #[serde(rename = "quietHoursStart")]
+    pub quiet_hours_start: Option<i64>,
+    #[serde(rename = "quietHoursEnd")]
+    pub quiet_hours_end: Option<i64>,
+}
+
+// ============================================================================
+// WhatsApp Preference Models
+// ============================================================================
+
+/// Whether interview confirmations and reminders are also sent over WhatsApp
+#[derive(Debug, Clone, Serialize)]
+pub struct WhatsAppPreferences {
+    #[serde(rename = "optedIn")]
+    pub opted_in: bool,
+    /// When the candidate last opted in; kept as the record of consent
+    #[serde(rename = "optedInAt")]
+    pub opted_in_at: Option<String>,
+    /// Verified number messages go to
+    pub phone: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateWhatsAppPreferencesRequest {
+    #[serde(rename = "optedIn")]
+    pub opted_in: bool,
+}
+
+// ============================================================================
+// Public Portfolio Models
+// ============================================================================
+
+/// Which sections of an opted-in profile are shown on the public portfolio
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PublicSections {
+    #[serde(default = "default_visible")]
+    pub bio: bool,
+    #[serde(default = "default_visible")]
+    pub experience: bool,
+    #[serde(default = "default_visible")]
+    pub education: bool,
+    #[serde(default = "default_visible")]
+    pub testimonials: bool,
+    #[serde(default = "default_visible")]
+    pub videos: bool,
+}
+
+fn default_visible() -> bool {
+    true
+}
+
+impl Default for PublicSections {
+    fn default() -> Self {
+        Self {
+            bio: true,
+            experience: true,
+            education: true,
+            testimonials: true,
+            videos: true,
+        }
+    }
+}
+
+impl PublicSections {
+    /// Parse the JSON stored on the profile, falling back to all sections visible
+    pub fn from_json(value: Option<&str>) -> Self {
+        value
+            .and_then(|v| serde_json::from_str(v).ok())
+            .unwrap_or_default()
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct PublicProfileSettings {
+    pub enabled: bool,
+    pub slug: Option<String>,
+    pub sections: PublicSections,
+    pub url: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdatePublicProfileRequest {
+    pub enabled: Option<bool>,
+    pub slug: Option<String>,
+    pub sections: Option<PublicSections>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PublicExperience {
+    pub company: String,
+    pub title: String,
+    pub start_date: String,
+    pub end_date: Option<String>,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PublicEducation {
+    pub institution: String,
+    pub degree: String,
+    pub field_of_study: Option<String>,
+    pub start_date: String,
+    pub end_date: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PublicTestimonial {
+    pub content: String,
+    pub rating: Option<i32>,
+    pub position: Option<String>,
+    pub company: Option<String>,
+    pub created_at: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PublicVideo {
+    pub youtube_video_id: String,
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub thumbnail_url: Option<String>,
+}
+
+/// Public portfolio payload. Contact details (email, phone, location, links) are
+/// never part of this struct; hidden sections are omitted entirely.
+#[derive(Debug, Serialize)]
+pub struct PublicCandidatePortfolio {
+    pub slug: String,
+    pub first_name: Option<String>,
+    pub last_name: Option<String>,
+    pub avatar: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bio: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub experience: Option<Vec<PublicExperience>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub education: Option<Vec<PublicEducation>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub testimonials: Option<Vec<PublicTestimonial>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub videos: Option<Vec<PublicVideo>>,
 }
 
 // ============================================================================
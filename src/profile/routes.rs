@@ -1,11 +1,14 @@
 // src/profile/routes.rs
 
 use axum::{
-    routing::{get, post, put},
+    routing::{delete, get, post, put},
     Router,
 };
 
-use super::handlers::{avatar, education, experience, profile, testimonials};
+use super::handlers::{
+    avatar, contact, education, experience, profile, public_profile, sms, testimonials,
+    whatsapp,
+};
 
 pub fn profile_routes() -> Router {
     Router::new()
@@ -14,6 +17,41 @@ pub fn profile_routes() -> Router {
             "/api/profile",
             get(profile::profile_handler).put(profile::update_profile_handler),
         )
+        .route(
+            "/api/profile/completeness",
+            get(profile::get_profile_completeness),
+        )
+        // Contact verification routes
+        .route(
+            "/api/profile/contact/verify",
+            post(contact::verify_contact),
+        )
+        .route(
+            "/api/profile/contact/resend",
+            post(contact::resend_contact_code),
+        )
+        .route(
+            "/api/profile/contact/pending/:channel",
+            delete(contact::cancel_contact_change),
+        )
+        .route(
+            "/api/profile/sms",
+            get(sms::get_sms_preferences).put(sms::update_sms_preferences),
+        )
+        .route(
+            "/api/profile/whatsapp",
+            get(whatsapp::get_whatsapp_preferences).put(whatsapp::update_whatsapp_preferences),
+        )
+        // Public portfolio routes
+        .route(
+            "/api/profile/public",
+            get(public_profile::get_public_profile_settings)
+                .put(public_profile::update_public_profile_settings),
+        )
+        .route(
+            "/api/public/candidates/:slug",
+            get(public_profile::get_public_portfolio),
+        )
         // Experience routes
         .route(
             "/api/profile/experience",
@@ -40,6 +78,7 @@ pub fn profile_routes() -> Router {
                 .delete(avatar::remove_avatar),
         )
         .route("/api/avatars/:filename", get(avatar::serve_avatar))
+        .route("/api/users/:id/avatar", get(avatar::serve_user_avatar))
         // Testimonial routes
         .route(
             "/api/testimonials",
@@ -229,4 +229,170 @@ mod tests {
         assert_eq!(request.content, "Excellent service!");
         assert_eq!(request.rating, Some(5));
     }
+
+    // ============================================================================
+    // Completeness Tests
+    // ============================================================================
+
+    #[test]
+    fn test_completeness_empty_profile_lists_everything_missing() {
+        let inputs = completeness::CompletenessInputs::from_profile(None, 0, 0, 0);
+        let result = completeness::compute_completeness(&inputs);
+
+        assert_eq!(result.score, 0);
+        let keys: Vec<&str> = result.missing.iter().map(|m| m.key.as_str()).collect();
+        assert!(keys.contains(&"resume"));
+        assert!(keys.contains(&"skills"));
+        assert!(keys.contains(&"experience"));
+        assert_eq!(result.missing.iter().map(|m| m.weight).sum::<i64>(), 100);
+    }
+
+    #[test]
+    fn test_completeness_partial_profile() {
+        let profile = models::Profile {
+            user_id: "user-123".to_string(),
+            first_name: Some("Ada".to_string()),
+            last_name: Some("Lovelace".to_string()),
+            phone: Some("  ".to_string()),
+            location: None,
+            bio: None,
+            website: None,
+            linkedin_url: None,
+            github_url: Some("https://github.com/ada".to_string()),
+            skills: Some("[]".to_string()),
+            updated_at: None,
+            resume_status: None,
+            last_resume_id: None,
+            completeness_score: None,
+            completeness_missing: None,
+            completeness_updated_at: None,
+            public_slug: None,
+            public_enabled: None,
+            public_sections: None,
+            contact_email: None,
+            contact_email_verified_at: None,
+            pending_contact_email: None,
+            phone_verified_at: None,
+            pending_phone: None,
+        };
+
+        let inputs = completeness::CompletenessInputs::from_profile(Some(&profile), 2, 0, 1);
+        let result = completeness::compute_completeness(&inputs);
+
+        // resume + experience + name + links
+        assert_eq!(result.score, 25 + 15 + 10 + 5);
+        let keys: Vec<&str> = result.missing.iter().map(|m| m.key.as_str()).collect();
+        assert!(keys.contains(&"skills"), "empty skills array counts as missing");
+        assert!(keys.contains(&"phone"), "blank phone counts as missing");
+        assert!(!keys.contains(&"resume"));
+    }
+
+    // ============================================================================
+    // Public Portfolio Tests
+    // ============================================================================
+
+    #[test]
+    fn test_public_profile_validator_slug_rules() {
+        let validator = validators::PublicProfileValidator;
+        let request = |slug: &str| models::UpdatePublicProfileRequest {
+            enabled: Some(true),
+            slug: Some(slug.to_string()),
+            sections: None,
+        };
+
+        assert!(validator.validate(&request("jane-doe-42")).is_valid);
+        assert!(!validator.validate(&request("ab")).is_valid);
+        assert!(!validator.validate(&request("Jane_Doe")).is_valid);
+        assert!(!validator.validate(&request("-jane")).is_valid);
+        assert!(!validator.validate(&request("jane--doe")).is_valid);
+    }
+
+    #[test]
+    fn test_public_sections_default_to_visible() {
+        let sections = models::PublicSections::from_json(None);
+        assert!(sections.bio && sections.experience && sections.videos);
+
+        let sections = models::PublicSections::from_json(Some(r#"{"testimonials":false}"#));
+        assert!(!sections.testimonials);
+        assert!(sections.education);
+    }
+
+    #[test]
+    fn test_redact_contact_details_in_free_text() {
+        let text = "Reach me at jane.doe@example.com or +1 (555) 123-4567. Worked 2019-2021.";
+        let redacted = handlers::public_profile::redact_contact_details(text);
+
+        assert!(!redacted.contains("example.com"));
+        assert!(!redacted.contains("123-4567"));
+        assert!(redacted.contains("Worked 2019-2021."));
+    }
+
+    // ============================================================================
+    // Fallback Avatar Tests
+    // ============================================================================
+
+    #[test]
+    fn test_initials_from_name_or_email() {
+        assert_eq!(avatar_fallback::initials(Some("jane van der doe"), "x@y.com"), "JD");
+        assert_eq!(avatar_fallback::initials(Some("  Prince "), "x@y.com"), "P");
+        assert_eq!(avatar_fallback::initials(None, "sam.lee@example.com"), "S");
+        assert_eq!(avatar_fallback::initials(Some("- -"), "@example.com"), "?");
+    }
+
+    #[test]
+    fn test_fallback_is_deterministic_and_tracks_name() {
+        let color = avatar_fallback::background_color("USR_123");
+        assert_eq!(color, avatar_fallback::background_color("USR_123"));
+
+        let svg = avatar_fallback::initials_svg("JD", color);
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.contains(color) && svg.contains(">JD</text>"));
+
+        let stem = avatar_fallback::fallback_stem("USR_123", Some("Jane Doe"), "j@x.com");
+        assert_eq!(stem, avatar_fallback::fallback_stem("USR_123", Some("Jane Doe"), "J@X.com"));
+        assert_ne!(stem, avatar_fallback::fallback_stem("USR_123", Some("Jane Roe"), "j@x.com"));
+    }
+
+    #[test]
+    fn test_avatar_url_falls_back_to_generated_endpoint() {
+        assert_eq!(
+            avatar_fallback::avatar_url("USR_1", Some("/api/avatars/a.png")),
+            "/api/avatars/a.png"
+        );
+        assert_eq!(avatar_fallback::avatar_url("USR_1", None), "/api/users/USR_1/avatar");
+        assert_eq!(avatar_fallback::avatar_url("USR_1", Some(" ")), "/api/users/USR_1/avatar");
+        assert_eq!(
+            avatar_fallback::gravatar_hash(" MyEmailAddress@example.com "),
+            "84059b07d4be67b806386c0aad8070a23f18836bbaae342275dc0a83414c32ee"
+        );
+    }
+
+    #[test]
+    fn test_contact_values_are_normalized() {
+        assert_eq!(
+            contact_verification::normalize_phone("+1 (415) 555-0123").unwrap(),
+            "+14155550123"
+        );
+        assert!(contact_verification::normalize_phone("415 555 0123").is_err());
+        assert!(contact_verification::normalize_phone("+1 555").is_err());
+        assert!(contact_verification::normalize_phone("+1 415 CALL NOW").is_err());
+
+        assert_eq!(
+            contact_verification::normalize_email("  Ada@Example.COM ").unwrap(),
+            "ada@example.com"
+        );
+        assert!(contact_verification::normalize_email("not-an-email").is_err());
+        assert!(contact_verification::validate_channel("fax").is_err());
+    }
+
+    #[test]
+    fn test_verification_codes_are_hashed_per_verification() {
+        let code = contact_verification::generate_code();
+        assert_eq!(code.len(), 6);
+        assert!(code.chars().all(|c| c.is_ascii_digit()));
+
+        let hash = contact_verification::hash_code("CV_1", "012345");
+        assert_eq!(hash, contact_verification::hash_code("CV_1", " 012345 "));
+        assert_ne!(hash, contact_verification::hash_code("CV_2", "012345"));
+    }
 }
@@ -1,7 +1,7 @@
 // src/profile/validators.rs
 
 use super::models::*;
-use crate::common::{ValidationResult, Validator};
+use crate::common::{validate_fields, ValidationResult, Validator};
 use chrono::NaiveDate;
 
 // ============================================================================
@@ -12,20 +12,16 @@ pub struct ExperienceValidator;
 
 impl Validator<CreateExperienceRequest> for ExperienceValidator {
     fn validate(&self, data: &CreateExperienceRequest) -> ValidationResult {
-        let mut result = ValidationResult::new();
+        let mut result = validate_fields(data);
 
         // Validate company
         if data.company.trim().is_empty() {
             result.add_error("company", "Company name is required");
-        } else if data.company.len() > 255 {
-            result.add_error("company", "Company name must be less than 255 characters");
         }
 
         // Validate title
         if data.title.trim().is_empty() {
             result.add_error("title", "Job title is required");
-        } else if data.title.len() > 255 {
-            result.add_error("title", "Job title must be less than 255 characters");
         }
 
         // Validate start_date
@@ -50,23 +46,13 @@ impl Validator<CreateExperienceRequest> for ExperienceValidator {
             }
         }
 
-        // Validate description length if provided
-        if let Some(description) = &data.description {
-            if description.len() > 2000 {
-                result.add_error(
-                    "description",
-                    "Description must be less than 2000 characters",
-                );
-            }
-        }
-
         result
     }
 }
 
 impl Validator<UpdateExperienceRequest> for ExperienceValidator {
     fn validate(&self, data: &UpdateExperienceRequest) -> ValidationResult {
-        let mut result = ValidationResult::new();
+        let mut result = validate_fields(data);
 
         // Check if at least one field is provided
         if data.company.is_none()
@@ -83,8 +69,6 @@ impl Validator<UpdateExperienceRequest> for ExperienceValidator {
         if let Some(company) = &data.company {
             if company.trim().is_empty() {
                 result.add_error("company", "Company name cannot be empty");
-            } else if company.len() > 255 {
-                result.add_error("company", "Company name must be less than 255 characters");
             }
         }
 
@@ -92,8 +76,6 @@ impl Validator<UpdateExperienceRequest> for ExperienceValidator {
         if let Some(title) = &data.title {
             if title.trim().is_empty() {
                 result.add_error("title", "Job title cannot be empty");
-            } else if title.len() > 255 {
-                result.add_error("title", "Job title must be less than 255 characters");
             }
         }
 
@@ -111,16 +93,6 @@ impl Validator<UpdateExperienceRequest> for ExperienceValidator {
             }
         }
 
-        // Validate description length if provided
-        if let Some(description) = &data.description {
-            if description.len() > 2000 {
-                result.add_error(
-                    "description",
-                    "Description must be less than 2000 characters",
-                );
-            }
-        }
-
         result
     }
 }
@@ -129,33 +101,16 @@ pub struct EducationValidator;
 
 impl Validator<CreateEducationRequest> for EducationValidator {
     fn validate(&self, data: &CreateEducationRequest) -> ValidationResult {
-        let mut result = ValidationResult::new();
+        let mut result = validate_fields(data);
 
         // Validate institution
         if data.institution.trim().is_empty() {
             result.add_error("institution", "Institution name is required");
-        } else if data.institution.len() > 255 {
-            result.add_error(
-                "institution",
-                "Institution name must be less than 255 characters",
-            );
         }
 
         // Validate degree
         if data.degree.trim().is_empty() {
             result.add_error("degree", "Degree is required");
-        } else if data.degree.len() > 255 {
-            result.add_error("degree", "Degree must be less than 255 characters");
-        }
-
-        // Validate field_of_study if provided
-        if let Some(field) = &data.field_of_study {
-            if field.len() > 255 {
-                result.add_error(
-                    "field_of_study",
-                    "Field of study must be less than 255 characters",
-                );
-            }
         }
 
         // Validate start_date
@@ -180,23 +135,13 @@ impl Validator<CreateEducationRequest> for EducationValidator {
             }
         }
 
-        // Validate description length if provided
-        if let Some(description) = &data.description {
-            if description.len() > 2000 {
-                result.add_error(
-                    "description",
-                    "Description must be less than 2000 characters",
-                );
-            }
-        }
-
         result
     }
 }
 
 impl Validator<UpdateEducationRequest> for EducationValidator {
     fn validate(&self, data: &UpdateEducationRequest) -> ValidationResult {
-        let mut result = ValidationResult::new();
+        let mut result = validate_fields(data);
 
         // Check if at least one field is provided
         if data.institution.is_none()
@@ -214,11 +159,6 @@ impl Validator<UpdateEducationRequest> for EducationValidator {
         if let Some(institution) = &data.institution {
             if institution.trim().is_empty() {
                 result.add_error("institution", "Institution name cannot be empty");
-            } else if institution.len() > 255 {
-                result.add_error(
-                    "institution",
-                    "Institution name must be less than 255 characters",
-                );
             }
         }
 
@@ -226,18 +166,6 @@ impl Validator<UpdateEducationRequest> for EducationValidator {
         if let Some(degree) = &data.degree {
             if degree.trim().is_empty() {
                 result.add_error("degree", "Degree cannot be empty");
-            } else if degree.len() > 255 {
-                result.add_error("degree", "Degree must be less than 255 characters");
-            }
-        }
-
-        // Validate field_of_study if provided
-        if let Some(field) = &data.field_of_study {
-            if field.len() > 255 {
-                result.add_error(
-                    "field_of_study",
-                    "Field of study must be less than 255 characters",
-                );
             }
         }
 
@@ -255,12 +183,34 @@ impl Validator<UpdateEducationRequest> for EducationValidator {
             }
         }
 
-        // Validate description length if provided
-        if let Some(description) = &data.description {
-            if description.len() > 2000 {
+        result
+    }
+}
+
+// ============================================================================
+// Public Portfolio Validators
+// ============================================================================
+
+pub struct PublicProfileValidator;
+
+impl Validator<UpdatePublicProfileRequest> for PublicProfileValidator {
+    fn validate(&self, data: &UpdatePublicProfileRequest) -> ValidationResult {
+        let mut result = ValidationResult::new();
+
+        // Slugs appear in public URLs: lowercase letters, digits and single hyphens
+        if let Some(slug) = &data.slug {
+            if slug.len() < 3 || slug.len() > 50 {
+                result.add_error("slug", "Slug must be between 3 and 50 characters");
+            } else if !slug
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+                || slug.starts_with('-')
+                || slug.ends_with('-')
+                || slug.contains("--")
+            {
                 result.add_error(
-                    "description",
-                    "Description must be less than 2000 characters",
+                    "slug",
+                    "Slug may only contain lowercase letters, digits and single hyphens",
                 );
             }
         }
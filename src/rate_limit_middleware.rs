@@ -1,8 +1,11 @@
 // rate_limit_middleware.rs
+use crate::common::error::localized_message;
+use crate::common::ErrorCode;
+use crate::common::request_id::current_request_id;
 use crate::services::rate_limit::{RateLimitResult, RateLimitService};
 use axum::{
     extract::{ConnectInfo, Extension, Request},
-    http::{HeaderMap, HeaderValue, StatusCode},
+    http::{HeaderMap, HeaderValue},
     middleware::Next,
     response::{IntoResponse, Response},
     Json,
@@ -17,10 +20,12 @@ struct RateLimitErrorResponse {
     error: String,
     code: String,
     retry_after: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    request_id: Option<String>,
 }
 
 /// Extract IP address from request
-fn extract_ip_address(
+pub(crate) fn extract_ip_address(
     headers: &HeaderMap,
     connect_info: Option<&ConnectInfo<SocketAddr>>,
 ) -> Option<String> {
@@ -119,13 +124,17 @@ pub async fn rate_limit_middleware(
 
             // Return 429 Too Many Requests with retry-after header
             let error_response = RateLimitErrorResponse {
-                error: "Rate limit exceeded. Please try again later.".to_string(),
-                code: "RATE_LIMIT_EXCEEDED".to_string(),
+                error: localized_message(
+                    ErrorCode::RateLimitExceeded,
+                    "Rate limit exceeded. Please try again later.".to_string(),
+                ),
+                code: ErrorCode::RateLimitExceeded.as_str().to_string(),
                 retry_after,
+                request_id: current_request_id().filter(|id| !id.is_empty()),
             };
 
             let mut response =
-                (StatusCode::TOO_MANY_REQUESTS, Json(error_response)).into_response();
+                (ErrorCode::RateLimitExceeded.status(), Json(error_response)).into_response();
 
             // Add Retry-After header
             if let Ok(retry_header) = HeaderValue::from_str(&retry_after.to_string()) {
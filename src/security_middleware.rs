@@ -0,0 +1,59 @@
+// security_middleware.rs
+use crate::common::AppState;
+use crate::rate_limit_middleware::extract_ip_address;
+use crate::services::monitoring::{self, SecurityActivity};
+use axum::{
+    extract::{ConnectInfo, Extension, Request},
+    http::{header::AUTHORIZATION, StatusCode},
+    middleware::Next,
+    response::Response,
+};
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+/// Security monitoring middleware
+///
+/// Flags requests carrying a honeytoken and feeds authentication failures into
+/// anomaly detection. Requests are never blocked here, so a probing client gets
+/// no signal that it has been noticed.
+pub async fn security_monitoring_middleware(
+    Extension(state_lock): Extension<Arc<RwLock<AppState>>>,
+    connect_info: Option<ConnectInfo<SocketAddr>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let state = state_lock.read().await.clone();
+    let ip_address = extract_ip_address(request.headers(), connect_info.as_ref());
+    let path = request.uri().path().to_string();
+
+    let honeytokens = monitoring::load_honeytokens(&state.settings_service).await;
+    if !honeytokens.is_empty() {
+        let uri = request.uri().to_string();
+        let authorization = request
+            .headers()
+            .get(AUTHORIZATION)
+            .and_then(|h| h.to_str().ok())
+            .unwrap_or_default();
+
+        if let Some(token) = monitoring::matching_honeytoken(&honeytokens, &[&uri, authorization]) {
+            monitoring::record_honeytoken_access(&state, token, ip_address.as_deref(), &path).await;
+        }
+    }
+
+    let response = next.run(request).await;
+
+    if response.status() == StatusCode::UNAUTHORIZED {
+        monitoring::record_security_activity(
+            &state,
+            SecurityActivity::AuthFailure,
+            None,
+            ip_address.as_deref(),
+            1,
+            &path,
+        )
+        .await;
+    }
+
+    response
+}
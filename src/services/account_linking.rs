@@ -0,0 +1,538 @@
+// src/services/account_linking.rs
+//! Linking sign-in identities and merging duplicate candidate accounts
+//!
+//! A Google account whose verified email already belongs to a user signs in to that user
+//! and is recorded in `user_identities`. Accounts that only look alike (same phone number,
+//! or an email differing in case) are merged on request: the duplicate's owner confirms
+//! from its inbox, its candidate data moves to the account that asked, and the duplicate
+//! is marked `merged_into` so later sign-ins to it land on the surviving account.
+
+use serde::Serialize;
+use sqlx::{Sqlite, SqlitePool, Transaction};
+use tracing::info;
+
+use crate::auth::models::{AccountMergeRequest, DuplicateAccount, User};
+use crate::common::{
+    generate_account_merge_id, generate_history_id, generate_raw_id, safe_email_log, ApiError,
+    ErrorCode,
+};
+use crate::services::legal_hold;
+
+/// How long a merge confirmation link stays valid
+pub const MERGE_TTL_HOURS: i64 = 48;
+
+const MERGE_TOKEN_LENGTH: usize = 32;
+
+/// Digits compared when matching phone numbers, so country prefixes don't matter
+const PHONE_MATCH_DIGITS: usize = 10;
+
+/// Shortest number treated as a phone number rather than a typo
+const MIN_PHONE_DIGITS: usize = 7;
+
+/// Candidate data moved from the duplicate account, as (table, owner column)
+const CANDIDATE_TABLES: &[(&str, &str)] = &[
+    ("applications", "user_id"),
+    ("resumes", "user_id"),
+    ("experiences", "user_id"),
+    ("education", "user_id"),
+    ("testimonials", "user_id"),
+    ("consents", "user_id"),
+    ("videos", "user_id"),
+    ("job_views", "user_id"),
+    ("candidate_surveys", "user_id"),
+    ("conversation_messages", "user_id"),
+    ("ai_usage_logs", "user_id"),
+    ("user_identities", "user_id"),
+    ("document_requests", "candidate_id"),
+    ("interviews", "candidate_id"),
+    ("offer_letters", "candidate_id"),
+    ("compensation_overrides", "candidate_id"),
+    ("email_history", "candidate_id"),
+];
+
+/// Profile fields filled from the duplicate where the surviving account has none
+const PROFILE_FIELDS: &[&str] = &[
+    "first_name",
+    "last_name",
+    "phone",
+    "phone_index",
+    "location",
+    "bio",
+    "website",
+    "linkedin_url",
+    "github_url",
+    "skills",
+];
+
+
+/// What a completed merge moved to the surviving account
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct MergeSummary {
+    pub applications: u64,
+    pub resumes: u64,
+    /// Rows moved across every other table
+    pub other_records: u64,
+}
+
+/// The last ten digits of a phone number, or `None` if it has too few digits to compare
+pub fn normalize_phone(phone: &str) -> Option<String> {
+    let digits: String = phone.chars().filter(char::is_ascii_digit).collect();
+    if digits.len() < MIN_PHONE_DIGITS {
+        return None;
+    }
+    Some(digits[digits.len().saturating_sub(PHONE_MATCH_DIGITS)..].to_string())
+}
+
+pub fn same_email(a: &str, b: &str) -> bool {
+    a.trim().eq_ignore_ascii_case(b.trim())
+}
+
+async fn fetch_user(pool: &SqlitePool, user_id: &str) -> Result<Option<User>, sqlx::Error> {
+    sqlx::query_as::<_, User>("SELECT * FROM users WHERE id = ?")
+        .bind(user_id)
+        .fetch_optional(pool)
+        .await
+}
+
+/// Follow `merged_into` to the account a user now signs in as
+pub async fn resolve_merged(pool: &SqlitePool, mut user: User) -> Result<User, sqlx::Error> {
+    // Merges only ever point at live accounts, but don't trust that blindly
+    for _ in 0..5 {
+        let Some(target) = user.merged_into.clone() else {
+            break;
+        };
+        match fetch_user(pool, &target).await? {
+            Some(next) => user = next,
+            None => break,
+        }
+    }
+    Ok(user)
+}
+
+/// The user a provider identity signs in as, if it is already known
+///
+/// Checks the provider identity on the users row first, then linked identities, and
+/// follows merges to the surviving account.
+pub async fn user_for_identity(
+    pool: &SqlitePool,
+    provider: &str,
+    provider_id: &str,
+) -> Result<Option<User>, sqlx::Error> {
+    let mut user =
+        sqlx::query_as::<_, User>("SELECT * FROM users WHERE provider = ? AND provider_id = ?")
+            .bind(provider)
+            .bind(provider_id)
+            .fetch_optional(pool)
+            .await?;
+    if user.is_none() {
+        user = sqlx::query_as::<_, User>(
+            r#"
+            SELECT u.* FROM user_identities i
+            JOIN users u ON u.id = i.user_id
+            WHERE i.provider = ? AND i.provider_id = ?
+            "#,
+        )
+        .bind(provider)
+        .bind(provider_id)
+        .fetch_optional(pool)
+        .await?;
+    }
+    match user {
+        Some(user) => Ok(Some(resolve_merged(pool, user).await?)),
+        None => Ok(None),
+    }
+}
+
+/// Sign a new provider identity in to the account that already owns its email
+///
+/// Only for emails the provider has verified; an unverified match is refused rather than
+/// linked, since anyone can claim an address they don't control.
+pub async fn link_by_email(
+    pool: &SqlitePool,
+    provider: &str,
+    provider_id: &str,
+    email: &str,
+    email_verified: bool,
+) -> Result<Option<User>, ApiError> {
+    let Some(user) = sqlx::query_as::<_, User>("SELECT * FROM users WHERE email = ?")
+        .bind(email)
+        .fetch_optional(pool)
+        .await
+        .map_err(ApiError::DatabaseError)?
+    else {
+        return Ok(None);
+    };
+
+    if !email_verified {
+        return Err(ApiError::Coded(
+            ErrorCode::AccountExists,
+            "An account already uses this email; sign in with it to link this one".to_string(),
+        ));
+    }
+
+    let user = resolve_merged(pool, user)
+        .await
+        .map_err(ApiError::DatabaseError)?;
+    // A verified sign-in to a guest applicant's email claims the provisional account
+    sqlx::query("UPDATE users SET is_provisional = 0 WHERE id = ?")
+        .bind(&user.id)
+        .execute(pool)
+        .await
+        .map_err(ApiError::DatabaseError)?;
+    sqlx::query(
+        "INSERT OR IGNORE INTO user_identities (id, user_id, provider, provider_id, email) VALUES (?, ?, ?, ?, ?)",
+    )
+    .bind(generate_history_id())
+    .bind(&user.id)
+    .bind(provider)
+    .bind(provider_id)
+    .bind(email)
+    .execute(pool)
+    .await
+    .map_err(ApiError::DatabaseError)?;
+
+    info!(
+        user_id = %user.id,
+        provider = %provider,
+        "Linked sign-in identity to existing account by verified email"
+    );
+    Ok(Some(user))
+}
+
+/// Other live accounts sharing the user's phone number or email (ignoring case)
+pub async fn find_duplicates(
+    pool: &SqlitePool,
+    user_id: &str,
+) -> Result<Vec<DuplicateAccount>, sqlx::Error> {
+    // Phones are encrypted, so they are matched on their blind index (see `pii::phone_index`)
+    let Some((email, phone_index)) = sqlx::query_as::<_, (String, Option<String>)>(
+        "SELECT u.email, p.phone_index FROM users u LEFT JOIN profiles p ON p.user_id = u.id WHERE u.id = ?",
+    )
+    .bind(user_id)
+    .fetch_optional(pool)
+    .await?
+    else {
+        return Ok(Vec::new());
+    };
+
+    let rows = sqlx::query_as::<_, (String, String, Option<String>, Option<String>)>(
+        r#"
+        SELECT u.id, u.email, u.name, p.phone_index
+        FROM users u
+        LEFT JOIN profiles p ON p.user_id = u.id
+        WHERE u.id != ? AND u.merged_into IS NULL
+          AND (LOWER(u.email) = LOWER(?) OR (? IS NOT NULL AND p.phone_index = ?))
+        ORDER BY u.created_at
+        "#,
+    )
+    .bind(user_id)
+    .bind(&email)
+    .bind(&phone_index)
+    .bind(&phone_index)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .filter_map(|(id, other_email, name, other_index)| {
+            let match_reason = if same_email(&email, &other_email) {
+                "email"
+            } else if phone_index.is_some() && other_index == phone_index {
+                "phone"
+            } else {
+                return None;
+            };
+            Some(DuplicateAccount {
+                user_id: id,
+                email: safe_email_log(&other_email),
+                name,
+                match_reason: match_reason.to_string(),
+            })
+        })
+        .collect())
+}
+
+/// Record a request to fold `source_user_id` into `target_user_id`
+///
+/// Replaces any pending request for the same pair. The caller emails the returned token
+/// to the source account; nothing moves until it is confirmed.
+pub async fn request_merge(
+    pool: &SqlitePool,
+    target_user_id: &str,
+    source_user_id: &str,
+) -> Result<AccountMergeRequest, ApiError> {
+    if target_user_id == source_user_id {
+        return Err(ApiError::BadRequest(
+            "An account cannot be merged into itself".to_string(),
+        ));
+    }
+    let match_reason = find_duplicates(pool, target_user_id)
+        .await
+        .map_err(ApiError::DatabaseError)?
+        .into_iter()
+        .find(|d| d.user_id == source_user_id)
+        .map(|d| d.match_reason)
+        .ok_or_else(|| {
+            ApiError::BadRequest(
+                "That account does not share your email or phone number".to_string(),
+            )
+        })?;
+
+    sqlx::query(
+        "UPDATE account_merge_requests SET status = 'cancelled' WHERE source_user_id = ? AND target_user_id = ? AND status = 'pending'",
+    )
+    .bind(source_user_id)
+    .bind(target_user_id)
+    .execute(pool)
+    .await
+    .map_err(ApiError::DatabaseError)?;
+
+    let id = generate_account_merge_id();
+    sqlx::query(
+        r#"
+        INSERT INTO account_merge_requests
+            (id, source_user_id, target_user_id, match_reason, token, expires_at)
+        VALUES (?, ?, ?, ?, ?, datetime('now', ?))
+        "#,
+    )
+    .bind(&id)
+    .bind(source_user_id)
+    .bind(target_user_id)
+    .bind(&match_reason)
+    .bind(generate_raw_id(MERGE_TOKEN_LENGTH))
+    .bind(format!("+{} hours", MERGE_TTL_HOURS))
+    .execute(pool)
+    .await
+    .map_err(ApiError::DatabaseError)?;
+
+    sqlx::query_as::<_, AccountMergeRequest>("SELECT * FROM account_merge_requests WHERE id = ?")
+        .bind(&id)
+        .fetch_one(pool)
+        .await
+        .map_err(ApiError::DatabaseError)
+}
+
+/// Carry out the merge a confirmation token was issued for
+pub async fn confirm_merge(
+    pool: &SqlitePool,
+    token: &str,
+) -> Result<(AccountMergeRequest, MergeSummary), ApiError> {
+    let request = sqlx::query_as::<_, AccountMergeRequest>(
+        "SELECT * FROM account_merge_requests WHERE token = ? AND status = 'pending' AND expires_at > datetime('now')",
+    )
+    .bind(token)
+    .fetch_optional(pool)
+    .await
+    .map_err(ApiError::DatabaseError)?
+    .ok_or_else(|| ApiError::NotFound("Merge link is invalid or has expired".to_string()))?;
+
+    for user_id in [&request.source_user_id, &request.target_user_id] {
+        let live = fetch_user(pool, user_id)
+            .await
+            .map_err(ApiError::DatabaseError)?
+            .is_some_and(|u| u.merged_into.is_none());
+        if !live {
+            return Err(ApiError::BadRequest(
+                "One of these accounts has already been merged".to_string(),
+            ));
+        }
+    }
+    let is_staff: bool =
+        sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM admin_users WHERE user_id = ?)")
+            .bind(&request.source_user_id)
+            .fetch_one(pool)
+            .await
+            .map_err(ApiError::DatabaseError)?;
+    if is_staff {
+        return Err(ApiError::Forbidden(
+            "Staff accounts cannot be merged into another account".to_string(),
+        ));
+    }
+    // The hold covers data by owner, so moving it would take it out from under the hold
+    if let Some(hold) = legal_hold::hold_for_user(pool, &request.source_user_id)
+        .await
+        .map_err(ApiError::DatabaseError)?
+    {
+        return Err(ApiError::Coded(
+            ErrorCode::LegalHoldActive,
+            format!(
+                "This account is under a legal hold ({}) and cannot be merged",
+                hold.id
+            ),
+        ));
+    }
+
+    let mut tx = pool.begin().await.map_err(ApiError::DatabaseError)?;
+    let summary = merge_accounts(&mut tx, &request.source_user_id, &request.target_user_id)
+        .await
+        .map_err(ApiError::DatabaseError)?;
+    sqlx::query(
+        "UPDATE account_merge_requests SET status = 'completed', completed_at = datetime('now') WHERE id = ?",
+    )
+    .bind(&request.id)
+    .execute(&mut *tx)
+    .await
+    .map_err(ApiError::DatabaseError)?;
+    tx.commit().await.map_err(ApiError::DatabaseError)?;
+
+    info!(
+        merge_id = %request.id,
+        source_user_id = %request.source_user_id,
+        target_user_id = %request.target_user_id,
+        applications = summary.applications,
+        resumes = summary.resumes,
+        other_records = summary.other_records,
+        "Accounts merged"
+    );
+    Ok((request, summary))
+}
+
+/// Merge an account whose owner has already been verified, e.g. a claimed guest account
+pub async fn merge_verified(
+    pool: &SqlitePool,
+    source: &str,
+    target: &str,
+) -> Result<MergeSummary, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    let summary = merge_accounts(&mut tx, source, target).await?;
+    tx.commit().await?;
+
+    info!(
+        source_user_id = %source,
+        target_user_id = %target,
+        applications = summary.applications,
+        resumes = summary.resumes,
+        other_records = summary.other_records,
+        "Accounts merged"
+    );
+    Ok(summary)
+}
+
+/// Move the source account's candidate data to the target and retire the source
+async fn merge_accounts(
+    tx: &mut Transaction<'_, Sqlite>,
+    source: &str,
+    target: &str,
+) -> Result<MergeSummary, sqlx::Error> {
+    // One active application per job: keep the survivor's, archive the duplicate's
+    sqlx::query(
+        r#"
+        UPDATE applications SET archived_at = datetime('now')
+        WHERE user_id = ? AND archived_at IS NULL
+          AND job_id IN (SELECT job_id FROM applications WHERE user_id = ? AND archived_at IS NULL)
+        "#,
+    )
+    .bind(source)
+    .bind(target)
+    .execute(&mut **tx)
+    .await?;
+
+    let mut summary = MergeSummary::default();
+    for (table, column) in CANDIDATE_TABLES {
+        let moved = sqlx::query(&format!(
+            "UPDATE {} SET {} = ? WHERE {} = ?",
+            table, column, column
+        ))
+        .bind(target)
+        .bind(source)
+        .execute(&mut **tx)
+        .await?
+        .rows_affected();
+        match *table {
+            "applications" => summary.applications = moved,
+            "resumes" => summary.resumes = moved,
+            _ => summary.other_records += moved,
+        }
+    }
+
+    // Saved jobs are unique per user, so drop the ones the survivor already saved
+    summary.other_records +=
+        sqlx::query("UPDATE OR IGNORE saved_jobs SET user_id = ? WHERE user_id = ?")
+            .bind(target)
+            .bind(source)
+            .execute(&mut **tx)
+            .await?
+            .rows_affected();
+    sqlx::query("DELETE FROM saved_jobs WHERE user_id = ?")
+        .bind(source)
+        .execute(&mut **tx)
+        .await?;
+
+    let has_profile: bool =
+        sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM profiles WHERE user_id = ?)")
+            .bind(target)
+            .fetch_one(&mut **tx)
+            .await?;
+    if has_profile {
+        let fills = PROFILE_FIELDS
+            .iter()
+            .map(|f| {
+                format!(
+                    "{f} = COALESCE(NULLIF({f}, ''), (SELECT {f} FROM profiles WHERE user_id = ?))"
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        let sql = format!("UPDATE profiles SET {} WHERE user_id = ?", fills);
+        let mut update = sqlx::query(&sql);
+        for _ in PROFILE_FIELDS {
+            update = update.bind(source);
+        }
+        update.bind(target).execute(&mut **tx).await?;
+        sqlx::query("DELETE FROM profiles WHERE user_id = ?")
+            .bind(source)
+            .execute(&mut **tx)
+            .await?;
+    } else {
+        // Public slugs are unique and belong to the old account's URL, so don't carry them
+        sqlx::query("UPDATE profiles SET user_id = ?, public_slug = NULL, public_enabled = 0 WHERE user_id = ?")
+            .bind(target)
+            .bind(source)
+            .execute(&mut **tx)
+            .await?;
+    }
+
+    sqlx::query("UPDATE users SET merged_into = ?, merged_at = datetime('now') WHERE id = ?")
+        .bind(target)
+        .bind(source)
+        .execute(&mut **tx)
+        .await?;
+    sqlx::query(
+        "UPDATE account_merge_requests SET status = 'cancelled' WHERE status = 'pending' AND (source_user_id = ? OR target_user_id = ?)",
+    )
+    .bind(source)
+    .bind(source)
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_phone_ignores_formatting_and_country_code() {
+        assert_eq!(
+            normalize_phone("+1 (555) 123-4567"),
+            Some("5551234567".to_string())
+        );
+        assert_eq!(
+            normalize_phone("555.123.4567"),
+            normalize_phone("15551234567")
+        );
+        assert_eq!(
+            normalize_phone("+91 98765 43210"),
+            Some("9876543210".to_string())
+        );
+        assert_eq!(normalize_phone("123-45"), None);
+        assert_eq!(normalize_phone(""), None);
+    }
+
+    #[test]
+    fn test_same_email_ignores_case_and_whitespace() {
+        assert!(same_email("Jane.Doe@Example.com", " jane.doe@example.com"));
+        assert!(!same_email("jane@example.com", "jane@example.org"));
+    }
+}
@@ -0,0 +1,412 @@
+// src/services/activity.rs
+//! Admin activity feed and the daily digest email built from it
+//!
+//! The feed gathers new applications, stage changes, today's interviews and offers (letters
+//! sent, and applications moved to hired) on the jobs an admin works on: those they are the
+//! hiring manager or a recruiter of, plus jobs nobody is assigned to. Each admin chooses which
+//! kinds they see and whether they get the digest, and at which UTC hour.
+
+use chrono::{DateTime, Duration, Timelike, Utc};
+use sqlx::SqlitePool;
+use std::collections::HashSet;
+use std::sync::Arc;
+use tracing::{debug, error, info};
+
+use crate::admin::models::{ActivityEvent, ActivityPreferences};
+use crate::common::ApiError;
+use crate::services::{notification_preferences, EmailSender, SettingsService};
+use crate::services::text::escape_html;
+
+pub const ACTIVITY_SCOPES: &[&str] = &["assigned", "all"];
+
+const DIGEST_CHECK_INTERVAL_SECS: u64 = 15 * 60;
+
+/// Most events listed in one digest email
+const DIGEST_EVENT_LIMIT: i64 = 200;
+
+pub async fn save_preferences(
+    pool: &SqlitePool,
+    user_id: &str,
+    prefs: &ActivityPreferences,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        r#"
+        INSERT INTO activity_preferences
+            (user_id, digest_enabled, digest_hour, applications, stage_changes, interviews, offers)
+        VALUES (?, ?, ?, ?, ?, ?, ?)
+        ON CONFLICT(user_id) DO UPDATE SET
+            digest_enabled = excluded.digest_enabled,
+            digest_hour = excluded.digest_hour,
+            applications = excluded.applications,
+            stage_changes = excluded.stage_changes,
+            interviews = excluded.interviews,
+            offers = excluded.offers,
+            updated_at = datetime('now')
+        "#,
+    )
+    .bind(user_id)
+    .bind(prefs.digest_enabled)
+    .bind(prefs.digest_hour)
+    .bind(prefs.applications)
+    .bind(prefs.stage_changes)
+    .bind(prefs.interviews)
+    .bind(prefs.offers)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+pub fn validate_digest_hour(hour: i64) -> Result<(), ApiError> {
+    if !(0..=23).contains(&hour) {
+        return Err(ApiError::ValidationError(
+            "digest_hour must be between 0 and 23 (UTC)".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Parse a `since` timestamp into the stored UTC format
+pub fn parse_since(since: &str) -> Result<String, ApiError> {
+    crate::jobs::services::feeds::parse_timestamp(since.trim())
+        .map(|t| t.format("%Y-%m-%d %H:%M:%S").to_string())
+        .ok_or_else(|| {
+            ApiError::BadRequest(
+                "since must be an RFC 3339 date-time, e.g. 2026-01-15T09:00:00Z".to_string(),
+            )
+        })
+}
+
+/// One line describing the event
+pub fn describe(event: &ActivityEvent) -> String {
+    let candidate = event.candidate_name.as_deref().unwrap_or("A candidate");
+    let job = event.job_title.as_deref().unwrap_or("a job");
+    let detail = event.detail.as_deref().unwrap_or_default();
+    match event.kind.as_str() {
+        "application" => format!("{} applied to {}", candidate, job),
+        "stage_change" => format!(
+            "{} moved to {} for {}",
+            candidate,
+            detail.replace('_', " "),
+            job
+        ),
+        "interview" => format!(
+            "{} interview with {} for {} today",
+            detail.replace('_', " "),
+            candidate,
+            job
+        ),
+        "offer" if detail == "hired" => format!("{} accepted the offer for {}", candidate, job),
+        "offer" => format!("Offer sent to {} for {}", candidate, job),
+        _ => format!("{}: {}", event.kind, candidate),
+    }
+}
+
+/// Events on the admin's jobs since `since`, newest first, limited to the kinds they want.
+/// Interviews are the ones taking place today, whenever they were booked.
+pub async fn feed(
+    pool: &SqlitePool,
+    user_id: &str,
+    prefs: &ActivityPreferences,
+    since: &str,
+    all_jobs: bool,
+    limit: i64,
+) -> Result<Vec<ActivityEvent>, sqlx::Error> {
+    let mut events = sqlx::query_as::<_, ActivityEvent>(
+        r#"
+        WITH me(id, all_jobs, since) AS (SELECT ?, ?, datetime(?)),
+        my_jobs AS (
+            SELECT j.id, j.title FROM jobs j, me
+            WHERE j.deleted_at IS NULL AND (
+                me.all_jobs = 1
+                OR j.hiring_manager_id = me.id
+                OR EXISTS (SELECT 1 FROM job_recruiters r WHERE r.job_id = j.id AND r.user_id = me.id)
+                OR (j.hiring_manager_id IS NULL
+                    AND NOT EXISTS (SELECT 1 FROM job_recruiters r WHERE r.job_id = j.id))
+            )
+        )
+        SELECT kind, occurred_at, application_id, job_id, job_title, candidate_name, detail
+        FROM (
+            SELECT 'application' AS kind, a.applied_at AS occurred_at, a.id AS application_id,
+                   mj.id AS job_id, mj.title AS job_title,
+                   COALESCE(u.name, u.email) AS candidate_name, NULL AS detail
+            FROM applications a
+            JOIN my_jobs mj ON mj.id = a.job_id
+            JOIN users u ON u.id = a.user_id, me
+            WHERE datetime(a.applied_at) > me.since
+
+            UNION ALL
+            SELECT CASE WHEN h.status = 'hired' THEN 'offer' ELSE 'stage_change' END,
+                   h.changed_at, a.id, mj.id, mj.title, COALESCE(u.name, u.email), h.status
+            FROM application_status_history h
+            JOIN applications a ON a.id = h.application_id
+            JOIN my_jobs mj ON mj.id = a.job_id
+            JOIN users u ON u.id = a.user_id, me
+            WHERE h.status != 'submitted' AND datetime(h.changed_at) > me.since
+
+            UNION ALL
+            SELECT 'offer', o.sent_at, NULL, mj.id, mj.title, COALESCE(u.name, u.email), 'sent'
+            FROM offer_letters o
+            JOIN my_jobs mj ON mj.id = o.job_id
+            JOIN users u ON u.id = o.candidate_id, me
+            WHERE o.sent_at IS NOT NULL AND datetime(o.sent_at) > me.since
+
+            UNION ALL
+            SELECT 'interview', i.scheduled_date, i.application_id, mj.id, mj.title,
+                   COALESCE(u.name, u.email), i.interview_type
+            FROM interviews i
+            JOIN my_jobs mj ON mj.id = i.job_id
+            JOIN users u ON u.id = i.candidate_id
+            WHERE date(i.scheduled_date) = date('now') AND COALESCE(i.status, '') != 'cancelled'
+        )
+        WHERE (kind = 'application' AND ?)
+           OR (kind = 'stage_change' AND ?)
+           OR (kind = 'interview' AND ?)
+           OR (kind = 'offer' AND ?)
+        ORDER BY datetime(occurred_at) DESC
+        LIMIT ?
+        "#,
+    )
+    .bind(user_id)
+    .bind(all_jobs)
+    .bind(since)
+    .bind(prefs.applications)
+    .bind(prefs.stage_changes)
+    .bind(prefs.interviews)
+    .bind(prefs.offers)
+    .bind(limit)
+    .fetch_all(pool)
+    .await?;
+
+    for event in &mut events {
+        event.summary = describe(event);
+    }
+    Ok(events)
+}
+
+/// Whether the digest should go out now: enabled, at or past the chosen hour, and not
+/// already sent today
+pub fn digest_due(prefs: &ActivityPreferences, now: DateTime<Utc>) -> bool {
+    if !prefs.digest_enabled || i64::from(now.hour()) < prefs.digest_hour {
+        return false;
+    }
+    let today = now.format("%Y-%m-%d").to_string();
+    !prefs
+        .last_digest_at
+        .as_deref()
+        .is_some_and(|last| last.starts_with(&today))
+}
+
+/// Email body listing the events, grouped by kind
+pub fn digest_body(name: &str, events: &[ActivityEvent]) -> String {
+    let sections = [
+        ("interview", "Interviews today"),
+        ("application", "New applications"),
+        ("stage_change", "Stage changes"),
+        ("offer", "Offers"),
+    ];
+    let mut body = format!(
+        r#"<html><body style="font-family: Arial, sans-serif; line-height: 1.6; color: #333;">
+<div style="max-width: 600px; margin: 0 auto; padding: 20px;">
+<p>Hi {},</p>
+<p>Here is what happened on your jobs in the last day.</p>"#,
+        escape_html(name)
+    );
+    for (kind, heading) in sections {
+        let lines: Vec<String> = events
+            .iter()
+            .filter(|e| e.kind == kind)
+            .map(|e| format!("<li>{}</li>", escape_html(&e.summary)))
+            .collect();
+        if lines.is_empty() {
+            continue;
+        }
+        body.push_str(&format!(
+            "<h3>{} ({})</h3><ul>{}</ul>",
+            heading,
+            lines.len(),
+            lines.concat()
+        ));
+    }
+    body.push_str(
+        "<p>Change what the digest includes under Admin &rarr; Activity preferences.</p></div></body></html>",
+    );
+    body
+}
+
+/// Email the digest to every admin it is due for; returns how many were sent
+pub async fn send_digests(
+    pool: &SqlitePool,
+    email_sender: &dyn EmailSender,
+    admin_emails: &HashSet<String>,
+) -> Result<usize, sqlx::Error> {
+    let now = Utc::now();
+    let candidates: Vec<(String, String, Option<String>)> = sqlx::query_as(
+        r#"
+        SELECT u.id, u.email, u.name
+        FROM activity_preferences p
+        JOIN users u ON u.id = p.user_id
+        WHERE p.digest_enabled = 1
+        "#,
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let mut sent = 0;
+    for (user_id, email, name) in candidates {
+        if !admin_emails.contains(&email.to_lowercase()) {
+            continue;
+        }
+        let prefs = notification_preferences::activity(pool, &user_id).await?;
+        if !digest_due(&prefs, now) {
+            continue;
+        }
+
+        let since = prefs.last_digest_at.clone().unwrap_or_else(|| {
+            (now - Duration::days(1))
+                .format("%Y-%m-%d %H:%M:%S")
+                .to_string()
+        });
+        let events = feed(pool, &user_id, &prefs, &since, false, DIGEST_EVENT_LIMIT).await?;
+
+        // Quiet days are marked as done without an email
+        if !events.is_empty() {
+            let name = name.unwrap_or_else(|| email.clone());
+            let subject = format!("Your hiring activity for {}", now.format("%B %-d"));
+            if let Err(e) = email_sender
+                .send_email(
+                    vec![email.clone()],
+                    &subject,
+                    &digest_body(&name, &events),
+                    None,
+                )
+                .await
+            {
+                error!(error = %e, user_id = %user_id, "Failed to send activity digest");
+                continue;
+            }
+            sent += 1;
+        }
+
+        sqlx::query("UPDATE activity_preferences SET last_digest_at = ? WHERE user_id = ?")
+            .bind(now.format("%Y-%m-%d %H:%M:%S").to_string())
+            .bind(&user_id)
+            .execute(pool)
+            .await?;
+    }
+    Ok(sent)
+}
+
+/// Send due digests every 15 minutes unless `activity_digest_enabled` is `false`
+pub fn start_activity_digest_task(
+    pool: SqlitePool,
+    settings_service: Arc<SettingsService>,
+    email_sender: Arc<dyn EmailSender>,
+    admin_emails: HashSet<String>,
+) {
+    tokio::spawn(async move {
+        loop {
+            if !crate::common::shutdown::sleep(std::time::Duration::from_secs(
+                DIGEST_CHECK_INTERVAL_SECS,
+            ))
+            .await
+            {
+                break;
+            }
+            let _running = crate::common::shutdown::track();
+
+            let enabled = settings_service
+                .get_setting("activity_digest_enabled")
+                .await
+                .ok()
+                .flatten()
+                .map(|v| v != "false")
+                .unwrap_or(true);
+            if !enabled {
+                continue;
+            }
+
+            match send_digests(&pool, email_sender.as_ref(), &admin_emails).await {
+                Ok(0) => {}
+                Ok(sent) => info!(sent, "Sent activity digests"),
+                Err(e) => debug!(error = %e, "Skipped activity digest run"),
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn event(kind: &str, detail: Option<&str>) -> ActivityEvent {
+        let mut event = ActivityEvent {
+            kind: kind.to_string(),
+            occurred_at: Some("2026-03-02 10:00:00".to_string()),
+            application_id: Some("A_1".to_string()),
+            job_id: Some("J_1".to_string()),
+            job_title: Some("Line Cook".to_string()),
+            candidate_name: Some("Ana".to_string()),
+            detail: detail.map(str::to_string),
+            summary: String::new(),
+        };
+        event.summary = describe(&event);
+        event
+    }
+
+    #[test]
+    fn test_describe() {
+        assert_eq!(
+            event("application", None).summary,
+            "Ana applied to Line Cook"
+        );
+        assert_eq!(
+            event("stage_change", Some("interview_scheduled")).summary,
+            "Ana moved to interview scheduled for Line Cook"
+        );
+        assert_eq!(
+            event("offer", Some("hired")).summary,
+            "Ana accepted the offer for Line Cook"
+        );
+        assert_eq!(
+            event("offer", Some("sent")).summary,
+            "Offer sent to Ana for Line Cook"
+        );
+    }
+
+    #[test]
+    fn test_digest_due() {
+        let now = Utc.with_ymd_and_hms(2026, 3, 2, 9, 30, 0).unwrap();
+        let mut prefs = ActivityPreferences {
+            digest_enabled: true,
+            ..Default::default()
+        };
+        assert!(digest_due(&prefs, now));
+
+        prefs.last_digest_at = Some("2026-03-02 08:00:05".to_string());
+        assert!(!digest_due(&prefs, now));
+
+        prefs.last_digest_at = Some("2026-03-01 08:00:05".to_string());
+        prefs.digest_hour = 10;
+        assert!(!digest_due(&prefs, now));
+
+        prefs.digest_hour = 8;
+        prefs.digest_enabled = false;
+        assert!(!digest_due(&prefs, now));
+    }
+
+    #[test]
+    fn test_digest_body_groups_by_kind() {
+        let events = vec![
+            event("application", None),
+            event("interview", Some("video")),
+            event("application", None),
+        ];
+        let body = digest_body("Ben", &events);
+        assert!(body.contains("Hi Ben,"));
+        assert!(body.contains("<h3>New applications (2)</h3>"));
+        assert!(body.contains("<h3>Interviews today (1)</h3>"));
+        assert!(!body.contains("Stage changes"));
+        assert!(body.find("Interviews today") < body.find("New applications"));
+    }
+}
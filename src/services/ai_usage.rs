@@ -0,0 +1,466 @@
+// src/services/ai_usage.rs
+//! AI usage accounting: every OpenAI call is written to `ai_usage_logs` with an estimated
+//! cost, attributed to the request and user that triggered it, and rolled up for reporting
+
+use axum::{
+    extract::{MatchedPath, Request},
+    middleware::Next,
+    response::Response,
+};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use tracing::{error, warn};
+
+use crate::common::ApiError;
+use crate::services::settings::SettingsService;
+use crate::services::text::csv_field;
+
+/// Settings key holding per-model price overrides, e.g.
+/// `{"gpt-5-mini": {"input": 0.25, "output": 2.0}}` (USD per million tokens)
+const PRICING_SETTING: &str = "ai_model_pricing";
+
+/// Who and what an AI call is charged to
+#[derive(Debug)]
+struct Actor {
+    route: String,
+    user_id: Mutex<Option<String>>,
+}
+
+tokio::task_local! {
+    static ACTOR: Arc<Actor>;
+}
+
+/// Run `future` with AI calls attributed to `route`
+pub async fn with_actor<F: Future>(route: String, future: F) -> F::Output {
+    let actor = Arc::new(Actor {
+        route,
+        user_id: Mutex::new(None),
+    });
+    ACTOR.scope(actor, future).await
+}
+
+/// Middleware attributing AI calls made while handling a request to its route
+pub async fn attribution_scope(request: Request, next: Next) -> Response {
+    let path = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|p| p.as_str().to_string())
+        .unwrap_or_else(|| request.uri().path().to_string());
+    let route = format!("{} {}", request.method(), path);
+
+    with_actor(route, next.run(request)).await
+}
+
+/// Record the authenticated user for the current request; called by the auth extractor
+pub fn set_current_user(user_id: &str) {
+    let _ = ACTOR.try_with(|actor| {
+        if let Ok(mut current) = actor.user_id.lock() {
+            *current = Some(user_id.to_string());
+        }
+    });
+}
+
+/// The route and user of the request being handled; background work has neither
+fn current_actor() -> (Option<String>, Option<String>) {
+    ACTOR
+        .try_with(|actor| {
+            let user_id = actor.user_id.lock().ok().and_then(|u| u.clone());
+            (Some(actor.route.clone()), user_id)
+        })
+        .unwrap_or((None, None))
+}
+
+/// USD prices for a model
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+pub struct ModelPrice {
+    /// Per million input (prompt) tokens
+    #[serde(default)]
+    pub input: f64,
+    /// Per million output (completion) tokens
+    #[serde(default)]
+    pub output: f64,
+    /// Per generated image, used when the API reports no token usage
+    #[serde(default)]
+    pub image: f64,
+}
+
+impl ModelPrice {
+    const fn new(input: f64, output: f64, image: f64) -> Self {
+        Self {
+            input,
+            output,
+            image,
+        }
+    }
+}
+
+/// List prices at the time of writing, matched by longest model-name prefix
+const DEFAULT_PRICES: &[(&str, ModelPrice)] = &[
+    ("gpt-5-nano", ModelPrice::new(0.05, 0.40, 0.0)),
+    ("gpt-5-mini", ModelPrice::new(0.25, 2.00, 0.0)),
+    ("gpt-5", ModelPrice::new(1.25, 10.00, 0.0)),
+    ("gpt-4.1-nano", ModelPrice::new(0.10, 0.40, 0.0)),
+    ("gpt-4.1-mini", ModelPrice::new(0.40, 1.60, 0.0)),
+    ("gpt-4.1", ModelPrice::new(2.00, 8.00, 0.0)),
+    ("gpt-4o-mini", ModelPrice::new(0.15, 0.60, 0.0)),
+    ("gpt-4o", ModelPrice::new(2.50, 10.00, 0.0)),
+    ("o3-mini", ModelPrice::new(1.10, 4.40, 0.0)),
+    ("o3", ModelPrice::new(2.00, 8.00, 0.0)),
+    ("o1", ModelPrice::new(15.00, 60.00, 0.0)),
+    ("gpt-image-1", ModelPrice::new(5.00, 40.00, 0.04)),
+    ("dall-e-3", ModelPrice::new(0.0, 0.0, 0.04)),
+];
+
+/// Price for a model: an exact override from settings, else the longest matching default prefix
+pub fn price_for(model: &str, overrides: &HashMap<String, ModelPrice>) -> Option<ModelPrice> {
+    if let Some(price) = overrides.get(model) {
+        return Some(*price);
+    }
+    DEFAULT_PRICES
+        .iter()
+        .filter(|(prefix, _)| model.starts_with(prefix))
+        .max_by_key(|(prefix, _)| prefix.len())
+        .map(|(_, price)| *price)
+}
+
+/// Estimated USD cost of one call
+pub fn estimate_cost(
+    price: ModelPrice,
+    prompt_tokens: i64,
+    completion_tokens: i64,
+    images: i64,
+) -> f64 {
+    if prompt_tokens == 0 && completion_tokens == 0 {
+        return price.image * images as f64;
+    }
+    (prompt_tokens as f64 * price.input + completion_tokens as f64 * price.output) / 1_000_000.0
+}
+
+async fn price_overrides(settings: &SettingsService) -> HashMap<String, ModelPrice> {
+    let Some(raw) = settings.get_setting(PRICING_SETTING).await.ok().flatten() else {
+        return HashMap::new();
+    };
+    serde_json::from_str(&raw).unwrap_or_else(|e| {
+        warn!(error = %e, "Ignoring invalid ai_model_pricing setting");
+        HashMap::new()
+    })
+}
+
+/// Token counts reported for one call
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CallUsage {
+    pub prompt_tokens: i64,
+    pub completion_tokens: i64,
+    pub images: i64,
+}
+
+/// Log one AI call against the current request's user and route. Accounting never fails the call.
+pub async fn record(
+    pool: &SqlitePool,
+    settings: &SettingsService,
+    feature: &str,
+    model: &str,
+    usage: CallUsage,
+) {
+    let (route, user_id) = current_actor();
+    let cost = price_for(model, &price_overrides(settings).await).map(|price| {
+        estimate_cost(
+            price,
+            usage.prompt_tokens,
+            usage.completion_tokens,
+            usage.images,
+        )
+    });
+    if cost.is_none() {
+        warn!(model = %model, "No price known for AI model; usage logged without a cost");
+    }
+
+    let result = sqlx::query(
+        r#"
+        INSERT INTO ai_usage_logs (id, user_id, action, model, purpose, tokens_used, prompt_tokens, completion_tokens, cost_estimate)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+        "#,
+    )
+    .bind(uuid::Uuid::new_v4().to_string())
+    .bind(&user_id)
+    .bind(route.unwrap_or_else(|| "background".to_string()))
+    .bind(model)
+    .bind(feature)
+    .bind(usage.prompt_tokens + usage.completion_tokens)
+    .bind(usage.prompt_tokens)
+    .bind(usage.completion_tokens)
+    .bind(cost)
+    .execute(pool)
+    .await;
+
+    if let Err(e) = result {
+        error!(error = %e, feature = %feature, model = %model, "Failed to log AI usage");
+    }
+}
+
+/// Dimension a usage report is broken down by
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UsageGroup {
+    Day,
+    Feature,
+    User,
+    Model,
+    Route,
+}
+
+impl UsageGroup {
+    pub fn parse(value: &str) -> Result<Self, ApiError> {
+        match value {
+            "day" => Ok(UsageGroup::Day),
+            "feature" => Ok(UsageGroup::Feature),
+            "user" => Ok(UsageGroup::User),
+            "model" => Ok(UsageGroup::Model),
+            "route" => Ok(UsageGroup::Route),
+            _ => Err(ApiError::ValidationError(
+                "group_by must be one of day, feature, user, model, route".to_string(),
+            )),
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            UsageGroup::Day => "day",
+            UsageGroup::Feature => "feature",
+            UsageGroup::User => "user",
+            UsageGroup::Model => "model",
+            UsageGroup::Route => "route",
+        }
+    }
+
+    /// SQL for the group key and its display label
+    fn columns(&self) -> (&'static str, &'static str) {
+        match self {
+            UsageGroup::Day => ("date(l.created_at)", "NULL"),
+            UsageGroup::Feature => ("COALESCE(l.purpose, 'unknown')", "NULL"),
+            UsageGroup::User => (
+                "COALESCE(l.user_id, 'system')",
+                "(SELECT COALESCE(name, email) FROM users WHERE id = l.user_id)",
+            ),
+            UsageGroup::Model => ("l.model", "NULL"),
+            UsageGroup::Route => ("COALESCE(l.action, 'unknown')", "NULL"),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct UsageRow {
+    pub key: String,
+    pub label: Option<String>,
+    pub requests: i64,
+    pub prompt_tokens: i64,
+    pub completion_tokens: i64,
+    pub total_tokens: i64,
+    pub estimated_cost: f64,
+    /// Calls whose model had no known price and so add nothing to the cost
+    pub unpriced_requests: i64,
+}
+
+/// Usage between two dates (inclusive, `YYYY-MM-DD`), broken down by `group`, costliest first
+/// (chronological for days)
+pub async fn summarize(
+    pool: &SqlitePool,
+    group: UsageGroup,
+    from: Option<&str>,
+    to: Option<&str>,
+) -> Result<Vec<UsageRow>, sqlx::Error> {
+    let (key, label) = group.columns();
+    let order = if group == UsageGroup::Day {
+        "key"
+    } else {
+        "estimated_cost DESC, requests DESC"
+    };
+
+    sqlx::query_as::<_, UsageRow>(&format!(
+        r#"
+        SELECT {key} AS key,
+               {label} AS label,
+               COUNT(*) AS requests,
+               COALESCE(SUM(l.prompt_tokens), 0) AS prompt_tokens,
+               COALESCE(SUM(l.completion_tokens), 0) AS completion_tokens,
+               COALESCE(SUM(l.tokens_used), 0) AS total_tokens,
+               COALESCE(SUM(l.cost_estimate), 0.0) AS estimated_cost,
+               SUM(CASE WHEN l.cost_estimate IS NULL THEN 1 ELSE 0 END) AS unpriced_requests
+        FROM ai_usage_logs l
+        WHERE (?1 IS NULL OR l.created_at >= ?1)
+          AND (?2 IS NULL OR l.created_at < date(?2, '+1 day'))
+        GROUP BY key
+        ORDER BY {order}
+        "#,
+    ))
+    .bind(from)
+    .bind(to)
+    .fetch_all(pool)
+    .await
+}
+
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct MonthlyUsage {
+    pub month: String,
+    pub requests: i64,
+    pub total_tokens: i64,
+    pub estimated_cost: f64,
+    /// Percentage change in cost against the previous month; `None` for the first month
+    /// or when the previous month cost nothing
+    #[sqlx(default)]
+    pub cost_change_pct: Option<f64>,
+}
+
+/// Fill in month-over-month cost changes for months in chronological order
+pub fn with_month_over_month(mut months: Vec<MonthlyUsage>) -> Vec<MonthlyUsage> {
+    for i in 1..months.len() {
+        let previous = months[i - 1].estimated_cost;
+        months[i].cost_change_pct = (previous > 0.0).then(|| {
+            ((months[i].estimated_cost - previous) / previous * 100.0 * 10.0).round() / 10.0
+        });
+    }
+    months
+}
+
+/// Totals for the last `months` calendar months including the current one, oldest first
+pub async fn monthly(pool: &SqlitePool, months: i64) -> Result<Vec<MonthlyUsage>, sqlx::Error> {
+    let rows = sqlx::query_as::<_, MonthlyUsage>(
+        r#"
+        SELECT strftime('%Y-%m', created_at) AS month,
+               COUNT(*) AS requests,
+               COALESCE(SUM(tokens_used), 0) AS total_tokens,
+               COALESCE(SUM(cost_estimate), 0.0) AS estimated_cost
+        FROM ai_usage_logs
+        WHERE created_at >= date('now', 'start of month', ?)
+        GROUP BY month
+        ORDER BY month
+        "#,
+    )
+    .bind(format!("-{} months", months.max(1) - 1))
+    .fetch_all(pool)
+    .await?;
+
+    Ok(with_month_over_month(rows))
+}
+
+/// A usage report as CSV, one row per group
+pub fn to_csv(group: UsageGroup, rows: &[UsageRow]) -> String {
+    let mut csv = format!(
+        "{},label,requests,prompt_tokens,completion_tokens,total_tokens,estimated_cost_usd,unpriced_requests\n",
+        group.as_str()
+    );
+    for row in rows {
+        csv.push_str(&format!(
+            "{},{},{},{},{},{},{:.6},{}\n",
+            csv_field(&row.key),
+            csv_field(row.label.as_deref().unwrap_or("")),
+            row.requests,
+            row.prompt_tokens,
+            row.completion_tokens,
+            row.total_tokens,
+            row.estimated_cost,
+            row.unpriced_requests
+        ));
+    }
+    csv
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_price_for_uses_longest_prefix_and_overrides() {
+        let none = HashMap::new();
+        assert_eq!(
+            price_for("gpt-5-mini-2025-08-07", &none).unwrap().input,
+            0.25
+        );
+        assert_eq!(price_for("gpt-5", &none).unwrap().input, 1.25);
+        assert!(price_for("some-other-model", &none).is_none());
+
+        let mut overrides = HashMap::new();
+        overrides.insert(
+            "gpt-5".to_string(),
+            ModelPrice {
+                input: 1.0,
+                output: 2.0,
+                image: 0.0,
+            },
+        );
+        assert_eq!(price_for("gpt-5", &overrides).unwrap().output, 2.0);
+    }
+
+    #[test]
+    fn test_estimate_cost() {
+        let price = ModelPrice {
+            input: 1.0,
+            output: 10.0,
+            image: 0.04,
+        };
+        assert!((estimate_cost(price, 1_000_000, 100_000, 0) - 2.0).abs() < 1e-9);
+        // Images without token usage fall back to the per-image price
+        assert!((estimate_cost(price, 0, 0, 2) - 0.08).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_month_over_month() {
+        let month = |month: &str, cost: f64| MonthlyUsage {
+            month: month.to_string(),
+            requests: 1,
+            total_tokens: 1,
+            estimated_cost: cost,
+            cost_change_pct: None,
+        };
+        let months = with_month_over_month(vec![
+            month("2026-07", 0.0),
+            month("2026-08", 10.0),
+            month("2026-09", 15.0),
+        ]);
+        assert_eq!(months[0].cost_change_pct, None);
+        assert_eq!(months[1].cost_change_pct, None);
+        assert_eq!(months[2].cost_change_pct, Some(50.0));
+    }
+
+    #[tokio::test]
+    async fn test_actor_is_scoped_to_the_request() {
+        assert_eq!(current_actor(), (None, None));
+
+        let inside = with_actor("POST /api/jobs/ai".to_string(), async {
+            set_current_user("U1");
+            current_actor()
+        })
+        .await;
+        assert_eq!(
+            inside,
+            (
+                Some("POST /api/jobs/ai".to_string()),
+                Some("U1".to_string())
+            )
+        );
+
+        // Outside a request there is nothing to attribute to
+        set_current_user("U2");
+        assert_eq!(current_actor(), (None, None));
+    }
+
+    #[test]
+    fn test_csv_quotes_keys() {
+        let rows = vec![UsageRow {
+            key: "U1".to_string(),
+            label: Some("Ann \"A\" Lee".to_string()),
+            requests: 2,
+            prompt_tokens: 10,
+            completion_tokens: 5,
+            total_tokens: 15,
+            estimated_cost: 0.5,
+            unpriced_requests: 0,
+        }];
+        assert_eq!(
+            to_csv(UsageGroup::User, &rows),
+            "user,label,requests,prompt_tokens,completion_tokens,total_tokens,estimated_cost_usd,unpriced_requests\n\"U1\",\"Ann \"\"A\"\" Lee\",2,10,5,15,0.500000,0\n"
+        );
+    }
+}
@@ -0,0 +1,439 @@
+// src/services/analytics_facts.rs
+//! Pre-aggregated application facts for the analytics endpoints
+//!
+//! Reading status history for every application on each request gets slower as the history
+//! grows, so each application's milestones (when it was first reviewed, reached an interview,
+//! an offer, was hired; how long each status lasted) are computed once and stored in
+//! `analytics_application_facts` and `analytics_status_durations`. The analytics endpoints
+//! group over those tables and report when they were last refreshed.
+//!
+//! Refreshes are incremental: only applications that are new, or have new history entries or
+//! interviews since the previous refresh (tracked by rowid) are recomputed. Deleted
+//! interviews and applications leave no trace to follow, so every application is recomputed
+//! once a day.
+
+use chrono::{Duration, Utc};
+use sqlx::{SqlitePool, Transaction};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use tracing::{debug, info};
+
+use crate::services::cohorts;
+use crate::services::hiring_metrics::{self, StatusChange};
+use crate::services::SettingsService;
+
+const DEFAULT_REFRESH_MINUTES: u64 = 10;
+
+/// Applications recomputed per batch
+const REFRESH_BATCH: usize = 500;
+
+const TIMESTAMP_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+/// A full rebuild runs when the last one is older than this
+const FULL_REFRESH_HOURS: i64 = 24;
+
+/// Refreshes from the scheduler and from admins never overlap
+static REFRESH_LOCK: Mutex<()> = Mutex::const_new(());
+
+/// One application's milestones, as stored by the refresh
+#[derive(Debug, Clone, Default, sqlx::FromRow)]
+pub struct ApplicationFact {
+    pub application_id: String,
+    pub job_id: String,
+    /// Lowercased source tag, `direct` when there was none
+    pub source: String,
+    pub applied_at: String,
+    pub days_to_first_review: Option<f64>,
+    pub days_to_hire: Option<f64>,
+    pub reviewed_at: Option<String>,
+    pub interview_at: Option<String>,
+    pub offered_at: Option<String>,
+    pub hired_at: Option<String>,
+    pub offer_accepted: bool,
+    pub interviews: i64,
+}
+
+#[derive(sqlx::FromRow)]
+struct RefreshState {
+    application_rowid: i64,
+    history_rowid: i64,
+    interview_rowid: i64,
+    refreshed_at: Option<String>,
+    full_refreshed_at: Option<String>,
+}
+
+#[derive(sqlx::FromRow)]
+struct SourceApplication {
+    id: String,
+    job_id: String,
+    source: Option<String>,
+    applied_at: Option<String>,
+}
+
+/// Outcome of one refresh
+#[derive(Debug, Default, serde::Serialize)]
+pub struct RefreshSummary {
+    pub full: bool,
+    pub applications: usize,
+    pub refreshed_at: String,
+}
+
+async fn load_state(pool: &SqlitePool) -> Result<Option<RefreshState>, sqlx::Error> {
+    sqlx::query_as::<_, RefreshState>(
+        r#"
+        SELECT application_rowid, history_rowid, interview_rowid, refreshed_at, full_refreshed_at
+        FROM analytics_refresh_state WHERE id = 1
+        "#,
+    )
+    .fetch_optional(pool)
+    .await
+}
+
+/// When the facts were last refreshed; None until the first refresh
+pub async fn as_of(pool: &SqlitePool) -> Result<Option<String>, sqlx::Error> {
+    Ok(load_state(pool).await?.and_then(|s| s.refreshed_at))
+}
+
+/// Build the facts if they never have been, so a fresh install doesn't report nothing
+pub async fn ensure_built(pool: &SqlitePool) -> Result<(), sqlx::Error> {
+    if as_of(pool).await?.is_none() {
+        refresh(pool, true).await?;
+    }
+    Ok(())
+}
+
+async fn max_rowid(pool: &SqlitePool, table: &str) -> Result<i64, sqlx::Error> {
+    sqlx::query_scalar(&format!("SELECT COALESCE(MAX(rowid), 0) FROM {}", table))
+        .fetch_one(pool)
+        .await
+}
+
+fn placeholders(count: usize) -> String {
+    vec!["?"; count].join(", ")
+}
+
+/// Compute the facts for one application from its history, oldest entry first
+pub fn compute_fact(
+    application_id: &str,
+    job_id: &str,
+    source: Option<&str>,
+    applied_at: &str,
+    changes: &[StatusChange],
+    interviews: i64,
+) -> (ApplicationFact, Vec<(String, f64)>) {
+    let timeline = hiring_metrics::timeline(Some(applied_at), changes, interviews);
+    let reached: Vec<Option<String>> = cohorts::stage_reached(changes)
+        .into_iter()
+        .map(|at| at.map(|at| at.format(TIMESTAMP_FORMAT).to_string()))
+        .collect();
+    let reached_at = |index: usize| reached.get(index).cloned().flatten();
+
+    let fact = ApplicationFact {
+        application_id: application_id.to_string(),
+        job_id: job_id.to_string(),
+        source: cohorts::normalize_source(source),
+        applied_at: applied_at.to_string(),
+        days_to_first_review: timeline.days_to_first_review,
+        days_to_hire: timeline.days_to_hire,
+        reviewed_at: reached_at(0),
+        interview_at: reached_at(1),
+        offered_at: reached_at(2),
+        hired_at: reached_at(3),
+        offer_accepted: timeline.offer_accepted,
+        interviews,
+    };
+    (fact, timeline.days_in_status)
+}
+
+async fn store_batch(
+    tx: &mut Transaction<'_, sqlx::Sqlite>,
+    ids: &[String],
+    facts: &[(ApplicationFact, Vec<(String, f64)>)],
+) -> Result<(), sqlx::Error> {
+    for table in ["analytics_application_facts", "analytics_status_durations"] {
+        let sql = format!(
+            "DELETE FROM {} WHERE application_id IN ({})",
+            table,
+            placeholders(ids.len())
+        );
+        let mut query = sqlx::query(&sql);
+        for id in ids {
+            query = query.bind(id);
+        }
+        query.execute(&mut **tx).await?;
+    }
+
+    for (fact, durations) in facts {
+        sqlx::query(
+            r#"
+            INSERT INTO analytics_application_facts
+                (application_id, job_id, source, applied_at, days_to_first_review, days_to_hire,
+                 reviewed_at, interview_at, offered_at, hired_at, offer_accepted, interviews)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(&fact.application_id)
+        .bind(&fact.job_id)
+        .bind(&fact.source)
+        .bind(&fact.applied_at)
+        .bind(fact.days_to_first_review)
+        .bind(fact.days_to_hire)
+        .bind(&fact.reviewed_at)
+        .bind(&fact.interview_at)
+        .bind(&fact.offered_at)
+        .bind(&fact.hired_at)
+        .bind(fact.offer_accepted)
+        .bind(fact.interviews)
+        .execute(&mut **tx)
+        .await?;
+
+        for (status, days) in durations {
+            sqlx::query(
+                "INSERT INTO analytics_status_durations (application_id, status, days) VALUES (?, ?, ?)",
+            )
+            .bind(&fact.application_id)
+            .bind(status)
+            .bind(days)
+            .execute(&mut **tx)
+            .await?;
+        }
+    }
+    Ok(())
+}
+
+/// Recompute a batch of applications and replace their stored facts
+async fn refresh_batch(pool: &SqlitePool, ids: &[String]) -> Result<(), sqlx::Error> {
+    let list = placeholders(ids.len());
+
+    let sql = format!(
+        "SELECT id, job_id, source, applied_at FROM applications WHERE id IN ({})",
+        list
+    );
+    let mut query = sqlx::query_as::<_, SourceApplication>(&sql);
+    for id in ids {
+        query = query.bind(id);
+    }
+    let applications = query.fetch_all(pool).await?;
+
+    let sql = format!(
+        r#"
+        SELECT application_id, status, changed_at FROM application_status_history
+        WHERE application_id IN ({})
+        ORDER BY changed_at, rowid
+        "#,
+        list
+    );
+    let mut query = sqlx::query_as::<_, StatusChange>(&sql);
+    for id in ids {
+        query = query.bind(id);
+    }
+    let mut changes: HashMap<String, Vec<StatusChange>> = HashMap::new();
+    for change in query.fetch_all(pool).await? {
+        changes
+            .entry(change.application_id.clone())
+            .or_default()
+            .push(change);
+    }
+
+    let sql = format!(
+        "SELECT application_id, COUNT(*) FROM interviews WHERE application_id IN ({}) GROUP BY application_id",
+        list
+    );
+    let mut query = sqlx::query_as::<_, (String, i64)>(&sql);
+    for id in ids {
+        query = query.bind(id);
+    }
+    let interviews: HashMap<String, i64> = query.fetch_all(pool).await?.into_iter().collect();
+
+    let facts: Vec<_> = applications
+        .iter()
+        .map(|application| {
+            compute_fact(
+                &application.id,
+                &application.job_id,
+                application.source.as_deref(),
+                application.applied_at.as_deref().unwrap_or_default(),
+                changes
+                    .get(&application.id)
+                    .map(Vec::as_slice)
+                    .unwrap_or_default(),
+                interviews.get(&application.id).copied().unwrap_or(0),
+            )
+        })
+        .collect();
+
+    // Applications deleted since are dropped along with the rest of the batch
+    let mut tx = pool.begin().await?;
+    store_batch(&mut tx, ids, &facts).await?;
+    tx.commit().await
+}
+
+/// Bring the facts up to date; `full` recomputes every application
+pub async fn refresh(pool: &SqlitePool, full: bool) -> Result<RefreshSummary, sqlx::Error> {
+    let _guard = REFRESH_LOCK.lock().await;
+
+    let state = load_state(pool).await?;
+    let now = Utc::now();
+    let full = full
+        || state
+            .as_ref()
+            .and_then(|s| s.full_refreshed_at.as_deref())
+            .and_then(crate::services::sla::parse_timestamp)
+            .is_none_or(|at| now.naive_utc() - at >= Duration::hours(FULL_REFRESH_HOURS));
+
+    // Taken before reading so rows written during the refresh are picked up next time
+    let application_rowid = max_rowid(pool, "applications").await?;
+    let history_rowid = max_rowid(pool, "application_status_history").await?;
+    let interview_rowid = max_rowid(pool, "interviews").await?;
+
+    // A full rebuild replaces rows batch by batch, so readers never see the tables empty
+    let ids: Vec<String> = if full {
+        sqlx::query_scalar("SELECT id FROM applications WHERE rowid <= ?")
+            .bind(application_rowid)
+            .fetch_all(pool)
+            .await?
+    } else {
+        let state = state.as_ref();
+        let dirty: Vec<String> = sqlx::query_scalar(
+            r#"
+            SELECT id FROM applications WHERE rowid > ? AND rowid <= ?
+            UNION
+            SELECT application_id FROM application_status_history WHERE rowid > ? AND rowid <= ?
+            UNION
+            SELECT application_id FROM interviews WHERE rowid > ? AND rowid <= ?
+            "#,
+        )
+        .bind(state.map_or(0, |s| s.application_rowid))
+        .bind(application_rowid)
+        .bind(state.map_or(0, |s| s.history_rowid))
+        .bind(history_rowid)
+        .bind(state.map_or(0, |s| s.interview_rowid))
+        .bind(interview_rowid)
+        .fetch_all(pool)
+        .await?;
+        let unique: HashSet<String> = dirty.into_iter().collect();
+        unique.into_iter().collect()
+    };
+
+    for batch in ids.chunks(REFRESH_BATCH) {
+        refresh_batch(pool, batch).await?;
+    }
+    if full {
+        for table in ["analytics_application_facts", "analytics_status_durations"] {
+            sqlx::query(&format!(
+                "DELETE FROM {} WHERE application_id NOT IN (SELECT id FROM applications)",
+                table
+            ))
+            .execute(pool)
+            .await?;
+        }
+    }
+
+    let refreshed_at = now.format(TIMESTAMP_FORMAT).to_string();
+    sqlx::query(
+        r#"
+        INSERT INTO analytics_refresh_state
+            (id, application_rowid, history_rowid, interview_rowid, refreshed_at, full_refreshed_at)
+        VALUES (1, ?, ?, ?, ?, ?)
+        ON CONFLICT(id) DO UPDATE SET
+            application_rowid = excluded.application_rowid,
+            history_rowid = excluded.history_rowid,
+            interview_rowid = excluded.interview_rowid,
+            refreshed_at = excluded.refreshed_at,
+            full_refreshed_at = COALESCE(excluded.full_refreshed_at, full_refreshed_at)
+        "#,
+    )
+    .bind(application_rowid)
+    .bind(history_rowid)
+    .bind(interview_rowid)
+    .bind(&refreshed_at)
+    .bind(full.then(|| refreshed_at.clone()))
+    .execute(pool)
+    .await?;
+
+    Ok(RefreshSummary {
+        full,
+        applications: ids.len(),
+        refreshed_at,
+    })
+}
+
+/// Refresh the analytics tables every `analytics_refresh_minutes` (10 by default; 0 turns
+/// the refresh off)
+pub fn start_analytics_refresh_task(pool: SqlitePool, settings_service: Arc<SettingsService>) {
+    tokio::spawn(async move {
+        loop {
+            let minutes = settings_service
+                .get_setting("analytics_refresh_minutes")
+                .await
+                .ok()
+                .flatten()
+                .and_then(|v| v.parse::<u64>().ok())
+                .unwrap_or(DEFAULT_REFRESH_MINUTES);
+
+            if minutes > 0 {
+                let _running = crate::common::shutdown::track();
+                match refresh(&pool, false).await {
+                    Ok(summary) if summary.applications > 0 => info!(
+                        applications = summary.applications,
+                        full = summary.full,
+                        "Refreshed analytics facts"
+                    ),
+                    Ok(_) => {}
+                    Err(e) => debug!(error = %e, "Skipped analytics refresh"),
+                }
+            }
+
+            if !crate::common::shutdown::sleep(std::time::Duration::from_secs(
+                minutes.clamp(1, 24 * 60) * 60,
+            ))
+            .await
+            {
+                break;
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn change(status: &str, changed_at: &str) -> StatusChange {
+        StatusChange {
+            application_id: "A_1".to_string(),
+            status: status.to_string(),
+            changed_at: Some(changed_at.to_string()),
+        }
+    }
+
+    #[test]
+    fn test_compute_fact_records_milestones() {
+        let (fact, durations) = compute_fact(
+            "A_1",
+            "J_1",
+            Some(" LinkedIn "),
+            "2026-03-01 09:00:00",
+            &[
+                change("submitted", "2026-03-01 09:00:00"),
+                change("interview_scheduled", "2026-03-02 09:00:00"),
+                change("offered", "2026-03-05 09:00:00"),
+                change("hired", "2026-03-06 09:00:00"),
+            ],
+            2,
+        );
+
+        assert_eq!(fact.source, "linkedin");
+        assert_eq!(fact.days_to_first_review, Some(1.0));
+        assert_eq!(fact.days_to_hire, Some(5.0));
+        // Skipping straight to an interview still counts as reviewed
+        assert_eq!(fact.reviewed_at.as_deref(), Some("2026-03-02 09:00:00"));
+        assert_eq!(fact.interview_at.as_deref(), Some("2026-03-02 09:00:00"));
+        assert_eq!(fact.offered_at.as_deref(), Some("2026-03-05 09:00:00"));
+        assert_eq!(fact.hired_at.as_deref(), Some("2026-03-06 09:00:00"));
+        assert!(fact.offer_accepted);
+        assert_eq!(fact.interviews, 2);
+        assert_eq!(durations.len(), 3);
+    }
+}
@@ -0,0 +1,204 @@
+// src/services/application_feed.rs
+//! Personal feed of a candidate's application progress
+//!
+//! A candidate can follow their applications from a feed reader (RSS of status changes) or a
+//! calendar app (iCalendar of their interviews) without signing in. As with the interview
+//! calendar, the token in the URL is the only credential and only its hash is stored; it is
+//! separate from the interview calendar token so either can be revoked on its own. Internal
+//! notes on status changes are never included.
+
+use chrono::{DateTime, Utc};
+use sqlx::SqlitePool;
+
+use crate::candidates::handlers::email_templates::status_to_stage;
+use crate::jobs::services::feeds::{escape_xml, parse_timestamp};
+use crate::services::calendar_feed::{generate_token, hash_token};
+
+/// Status changes listed, newest first
+pub const FEED_ITEM_LIMIT: i64 = 100;
+
+/// Replace the user's token; the old feed URLs stop working
+pub async fn regenerate_token(pool: &SqlitePool, user_id: &str) -> Result<String, sqlx::Error> {
+    let token = generate_token();
+    sqlx::query(
+        r#"
+        INSERT INTO application_feed_tokens (user_id, token_hash, created_at, last_accessed_at)
+        VALUES (?, ?, datetime('now'), NULL)
+        ON CONFLICT(user_id) DO UPDATE SET
+            token_hash = excluded.token_hash,
+            created_at = excluded.created_at,
+            last_accessed_at = NULL
+        "#,
+    )
+    .bind(user_id)
+    .bind(hash_token(&token))
+    .execute(pool)
+    .await?;
+    Ok(token)
+}
+
+/// The user a feed token belongs to, noting the access
+pub async fn resolve_token(pool: &SqlitePool, token: &str) -> Result<Option<String>, sqlx::Error> {
+    let user_id: Option<String> =
+        sqlx::query_scalar("SELECT user_id FROM application_feed_tokens WHERE token_hash = ?")
+            .bind(hash_token(token))
+            .fetch_optional(pool)
+            .await?;
+
+    if let Some(user_id) = &user_id {
+        sqlx::query(
+            "UPDATE application_feed_tokens SET last_accessed_at = datetime('now') WHERE user_id = ?",
+        )
+        .bind(user_id)
+        .execute(pool)
+        .await?;
+    }
+    Ok(user_id)
+}
+
+/// One status change of one of the candidate's applications
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct ApplicationEvent {
+    pub id: String,
+    pub job_id: String,
+    pub job_title: Option<String>,
+    pub company: Option<String>,
+    pub status: String,
+    pub changed_at: Option<String>,
+}
+
+/// Status changes across all of `user_id`'s applications, newest first
+pub async fn application_events(
+    pool: &SqlitePool,
+    user_id: &str,
+) -> Result<Vec<ApplicationEvent>, sqlx::Error> {
+    sqlx::query_as::<_, ApplicationEvent>(
+        r#"
+        SELECT h.id, a.job_id, j.title AS job_title, j.company, h.status,
+               h.changed_at
+        FROM application_status_history h
+        JOIN applications a ON a.id = h.application_id
+        LEFT JOIN jobs j ON j.id = a.job_id
+        WHERE a.user_id = ?
+        ORDER BY h.changed_at DESC, h.id DESC
+        LIMIT ?
+        "#,
+    )
+    .bind(user_id)
+    .bind(FEED_ITEM_LIMIT)
+    .fetch_all(pool)
+    .await
+}
+
+/// How a status reads to the candidate
+pub fn status_label(status: &str) -> &'static str {
+    match status {
+        "submitted" => "Application received",
+        "withdrawn" => "Application withdrawn",
+        "rejected" => "Not moving forward",
+        other => status_to_stage(other),
+    }
+}
+
+/// Render the events as an RSS 2.0 document; `job_url` maps a job ID to its public page
+pub fn render_rss(
+    events: &[ApplicationEvent],
+    self_url: &str,
+    applications_url: &str,
+    job_url: impl Fn(&str) -> String,
+) -> String {
+    let mut xml = String::from(r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+    xml.push('\n');
+    xml.push_str(r#"<rss version="2.0" xmlns:atom="http://www.w3.org/2005/Atom">"#);
+    xml.push_str("\n<channel>\n");
+    xml.push_str("<title>My applications</title>\n");
+    xml.push_str(&format!("<link>{}</link>\n", escape_xml(applications_url)));
+    xml.push_str("<description>Status updates on your job applications</description>\n");
+    xml.push_str(&format!(
+        "<atom:link href=\"{}\" rel=\"self\" type=\"application/rss+xml\"/>\n",
+        escape_xml(self_url)
+    ));
+    let changed_at = |event: &ApplicationEvent| -> Option<DateTime<Utc>> {
+        event.changed_at.as_deref().and_then(parse_timestamp)
+    };
+    if let Some(latest) = events.iter().filter_map(changed_at).max() {
+        xml.push_str(&format!(
+            "<lastBuildDate>{}</lastBuildDate>\n",
+            latest.to_rfc2822()
+        ));
+    }
+
+    for event in events {
+        let job = event.job_title.as_deref().unwrap_or("Job application");
+        let label = status_label(&event.status);
+        let description = match event.company.as_deref() {
+            Some(company) => format!("{} at {}: {}", job, company, label),
+            None => format!("{}: {}", job, label),
+        };
+        xml.push_str("<item>\n");
+        xml.push_str(&format!(
+            "<title>{}</title>\n",
+            escape_xml(&format!("{}: {}", job, label))
+        ));
+        xml.push_str(&format!(
+            "<link>{}</link>\n",
+            escape_xml(&job_url(&event.job_id))
+        ));
+        xml.push_str(&format!(
+            "<guid isPermaLink=\"false\">{}</guid>\n",
+            escape_xml(&event.id)
+        ));
+        xml.push_str(&format!(
+            "<description>{}</description>\n",
+            escape_xml(&description)
+        ));
+        if let Some(at) = changed_at(event) {
+            xml.push_str(&format!("<pubDate>{}</pubDate>\n", at.to_rfc2822()));
+        }
+        xml.push_str("</item>\n");
+    }
+
+    xml.push_str("</channel>\n</rss>\n");
+    xml
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(id: &str, status: &str, changed_at: &str) -> ApplicationEvent {
+        ApplicationEvent {
+            id: id.to_string(),
+            job_id: "J_1".to_string(),
+            job_title: Some("Cook & Chef".to_string()),
+            company: Some("Bistro".to_string()),
+            status: status.to_string(),
+            changed_at: Some(changed_at.to_string()),
+        }
+    }
+
+    #[test]
+    fn test_status_label() {
+        assert_eq!(status_label("submitted"), "Application received");
+        assert_eq!(status_label("shortlisted"), "Shortlisted");
+        assert_eq!(status_label("rejected"), "Not moving forward");
+    }
+
+    #[test]
+    fn test_render_rss() {
+        let rss = render_rss(
+            &[
+                event("H_2", "shortlisted", "2026-03-02 10:00:00"),
+                event("H_1", "submitted", "2026-03-01 09:00:00"),
+            ],
+            "https://api.example.com/feeds/applications/t.rss",
+            "https://example.com/applications",
+            |id| format!("https://example.com/jobs/{}", id),
+        );
+        assert_eq!(rss.matches("<item>").count(), 2);
+        assert!(rss.contains("<title>Cook &amp; Chef: Shortlisted</title>"));
+        assert!(rss.contains("<guid isPermaLink=\"false\">H_1</guid>"));
+        assert!(rss.contains("<link>https://example.com/jobs/J_1</link>"));
+        assert!(rss.contains("<lastBuildDate>Mon, 2 Mar 2026 10:00:00 +0000</lastBuildDate>"));
+    }
+}
@@ -1,14 +1,21 @@
 // src/services/aws.rs
+use crate::services::resilience::{self, Dependency, ExternalError};
 use crate::services::settings::{SettingsError, SettingsService};
 use aws_config::BehaviorVersion;
+use aws_sdk_s3::config::http::HttpResponse;
 use aws_sdk_s3::config::{Credentials, Region};
+use aws_sdk_s3::error::SdkError;
+use aws_sdk_s3::presigning::PresigningConfig;
 use aws_sdk_s3::primitives::ByteStream;
 use aws_sdk_s3::Client as S3Client;
 use aws_sdk_sesv2::Client as SesClient;
+use aws_sigv4::http_request::{sign, SignableBody, SignableRequest, SigningSettings};
+use aws_sigv4::sign::v4;
 use bytes::Bytes;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
+use std::time::SystemTime;
 use thiserror::Error;
 use tracing::{debug, error, info, warn};
 
@@ -23,11 +30,79 @@ pub enum AWSError {
     #[error("SES operation failed: {0}")]
     SESError(String),
 
+    #[error("CloudFront operation failed: {0}")]
+    CloudFrontError(String),
+
+    #[error("SNS operation failed: {0}")]
+    SNSError(String),
+
     #[error("Settings error: {0}")]
     SettingsError(#[from] SettingsError),
 
     #[error("Invalid configuration: {0}")]
     InvalidConfig(String),
+
+    #[error("AWS rejected the request: {0}")]
+    Rejected(String),
+
+    #[error("AWS unavailable: {0}")]
+    Unavailable(String),
+}
+
+impl ExternalError for AWSError {
+    fn is_transient(&self) -> bool {
+        matches!(
+            self,
+            AWSError::S3Error(_) | AWSError::SESError(_) | AWSError::Unavailable(_)
+        )
+    }
+
+    fn unavailable(message: String) -> Self {
+        AWSError::Unavailable(message)
+    }
+}
+
+/// Map an SDK error, telling a 4xx other than throttling (a missing key, an unverified
+/// sender) apart from the service failing
+fn sdk_error<E>(
+    error: SdkError<E, HttpResponse>,
+    context: &str,
+    failed: fn(String) -> AWSError,
+) -> AWSError
+where
+    SdkError<E, HttpResponse>: std::fmt::Display,
+{
+    let rejected = matches!(error, SdkError::ServiceError(_))
+        && error.raw_response().is_some_and(|response| {
+            let status = response.status().as_u16();
+            (400..500).contains(&status) && status != 429
+        });
+    let message = format!("{}: {}", context, error);
+    if rejected {
+        AWSError::Rejected(message)
+    } else {
+        failed(message)
+    }
+}
+
+/// The `InvalidationBatch` document CloudFront expects, with keys turned into escaped paths
+fn invalidation_batch_xml(keys: &[String], caller_reference: &str) -> String {
+    let items: String = keys
+        .iter()
+        .map(|key| {
+            let path = format!("/{}", key.trim_start_matches('/'))
+                .replace('&', "&amp;")
+                .replace('<', "&lt;")
+                .replace('>', "&gt;");
+            format!("<Path>{}</Path>", path)
+        })
+        .collect();
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?><InvalidationBatch xmlns="http://cloudfront.amazonaws.com/doc/2020-05-31/"><Paths><Quantity>{}</Quantity><Items>{}</Items></Paths><CallerReference>{}</CallerReference></InvalidationBatch>"#,
+        keys.len(),
+        items,
+        caller_reference
+    )
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -37,6 +112,7 @@ pub struct AWSConfig {
     pub region: String,
     pub s3_bucket_name: String,
     pub cloudfront_domain: Option<String>,
+    pub cloudfront_distribution_id: Option<String>,
     pub ses_from_email: String,
     pub ses_region: String,
 }
@@ -82,6 +158,7 @@ impl AWSService {
             "aws_region",
             "aws_s3_bucket_name",
             "aws_cloudfront_domain",
+            "aws_cloudfront_distribution_id",
             "aws_ses_from_email",
             "aws_ses_region",
         ];
@@ -112,6 +189,11 @@ impl AWSService {
             .get("aws_cloudfront_domain")
             .and_then(|v| v.clone());
 
+        let cloudfront_distribution_id = settings
+            .get("aws_cloudfront_distribution_id")
+            .and_then(|v| v.clone())
+            .filter(|v| !v.trim().is_empty());
+
         let ses_from_email = settings
             .get("aws_ses_from_email")
             .and_then(|v| v.clone())
@@ -128,6 +210,7 @@ impl AWSService {
             region,
             s3_bucket_name,
             cloudfront_domain,
+            cloudfront_distribution_id,
             ses_from_email,
             ses_region,
         })
@@ -173,20 +256,20 @@ impl AWSService {
     ) -> Result<String, AWSError> {
         let (client, bucket) = self.get_s3_client().await?;
 
-        let body = ByteStream::from(Bytes::from(file_data));
-
-        client
-            .put_object()
-            .bucket(&bucket)
-            .key(file_name)
-            .body(body)
-            .content_type(content_type)
-            .send()
-            .await
-            .map_err(|e| {
-                error!(error = %e, key = %file_name, "Failed to upload file to S3");
-                AWSError::S3Error(format!("Upload failed: {}", e))
-            })?;
+        let file_data = Bytes::from(file_data);
+        resilience::call(Dependency::S3, || async {
+            client
+                .put_object()
+                .bucket(&bucket)
+                .key(file_name)
+                .body(ByteStream::from(file_data.clone()))
+                .content_type(content_type)
+                .send()
+                .await
+                .map_err(|e| sdk_error(e, "Upload failed", AWSError::S3Error))
+        })
+        .await
+        .inspect_err(|e| error!(error = %e, key = %file_name, "Failed to upload file to S3"))?;
 
         // Use CloudFront URL if configured, otherwise fall back to S3
         let url = self.get_file_url(file_name, true).await?;
@@ -201,30 +284,57 @@ impl AWSService {
 
         info!(key = %key, bucket = %bucket, "Downloading file from S3");
 
-        let response = client
+        let bytes = resilience::call(Dependency::S3, || async {
+            let response = client
+                .get_object()
+                .bucket(&bucket)
+                .key(key)
+                .send()
+                .await
+                .map_err(|e| {
+                    error!(error = %e, key = %key, "Failed to download file from S3");
+                    sdk_error(e, "Download failed", AWSError::S3Error)
+                })?;
+
+            response
+                .body
+                .collect()
+                .await
+                .map_err(|e| {
+                    error!(error = %e, key = %key, "Failed to read S3 response body");
+                    AWSError::S3Error(format!("Failed to read response: {}", e))
+                })
+                .map(|body| body.into_bytes().to_vec())
+        })
+        .await?;
+
+        info!(key = %key, size = bytes.len(), "File downloaded from S3 successfully");
+        Ok(bytes)
+    }
+
+    /// Presigned GET URL for a private object, valid for `expires_in`
+    pub async fn presign_download(
+        &self,
+        key: &str,
+        expires_in: std::time::Duration,
+    ) -> Result<String, AWSError> {
+        let (client, bucket) = self.get_s3_client().await?;
+
+        let presigning = PresigningConfig::expires_in(expires_in)
+            .map_err(|e| AWSError::InvalidConfig(format!("Invalid presign expiry: {}", e)))?;
+
+        let request = client
             .get_object()
             .bucket(&bucket)
             .key(key)
-            .send()
+            .presigned(presigning)
             .await
             .map_err(|e| {
-                error!(error = %e, key = %key, "Failed to download file from S3");
-                AWSError::S3Error(format!("Download failed: {}", e))
+                error!(error = %e, key = %key, "Failed to presign S3 download");
+                AWSError::S3Error(format!("Presign failed: {}", e))
             })?;
 
-        let bytes = response
-            .body
-            .collect()
-            .await
-            .map_err(|e| {
-                error!(error = %e, key = %key, "Failed to read S3 response body");
-                AWSError::S3Error(format!("Failed to read response: {}", e))
-            })?
-            .into_bytes()
-            .to_vec();
-
-        info!(key = %key, size = bytes.len(), "File downloaded from S3 successfully");
-        Ok(bytes)
+        Ok(request.uri().to_string())
     }
 
     /// List files in S3 bucket with optional prefix filtering
@@ -239,17 +349,24 @@ impl AWSService {
             request = request.prefix(p);
         }
 
-        let response = request.send().await.map_err(|e| {
-            // Extract more detailed error information
-            let error_details = format!("{:?}", e);
-            error!(
-                error = %e,
-                error_details = %error_details,
-                bucket = %bucket,
-                "Failed to list S3 objects"
-            );
-            AWSError::S3Error(format!("List failed for bucket '{}': {}", bucket, e))
-        })?;
+        let response = resilience::call(Dependency::S3, || async {
+            request.clone().send().await.map_err(|e| {
+                // Extract more detailed error information
+                let error_details = format!("{:?}", e);
+                error!(
+                    error = %e,
+                    error_details = %error_details,
+                    bucket = %bucket,
+                    "Failed to list S3 objects"
+                );
+                sdk_error(
+                    e,
+                    &format!("List failed for bucket '{}'", bucket),
+                    AWSError::S3Error,
+                )
+            })
+        })
+        .await?;
 
         let objects: Vec<S3Object> = response
             .contents()
@@ -272,21 +389,181 @@ impl AWSService {
     pub async fn delete_file(&self, key: &str) -> Result<(), AWSError> {
         let (client, bucket) = self.get_s3_client().await?;
 
-        client
-            .delete_object()
-            .bucket(&bucket)
-            .key(key)
-            .send()
-            .await
-            .map_err(|e| {
-                error!(error = %e, key = %key, "Failed to delete S3 object");
-                AWSError::S3Error(format!("Delete failed: {}", e))
-            })?;
+        resilience::call(Dependency::S3, || async {
+            client
+                .delete_object()
+                .bucket(&bucket)
+                .key(key)
+                .send()
+                .await
+                .map_err(|e| {
+                    error!(error = %e, key = %key, "Failed to delete S3 object");
+                    sdk_error(e, "Delete failed", AWSError::S3Error)
+                })
+        })
+        .await?;
 
         info!(key = %key, "File deleted from S3 successfully");
+
+        // A deleted object would otherwise keep being served from edge caches
+        if let Err(e) = self.invalidate_paths(&[key.to_string()]).await {
+            warn!(error = %e, key = %key, "Failed to invalidate CloudFront cache for deleted object");
+        }
+        Ok(())
+    }
+
+    /// Ask CloudFront to drop cached copies of these keys. A no-op when no
+    /// `aws_cloudfront_distribution_id` is configured.
+    pub async fn invalidate_paths(&self, keys: &[String]) -> Result<(), AWSError> {
+        if keys.is_empty() {
+            return Ok(());
+        }
+
+        let config = self.get_config().await?;
+        let Some(distribution_id) = config.cloudfront_distribution_id.as_deref() else {
+            debug!(count = keys.len(), "No CloudFront distribution configured, skipping invalidation");
+            return Ok(());
+        };
+
+        let url = format!(
+            "https://cloudfront.amazonaws.com/2020-05-31/distribution/{}/invalidation",
+            distribution_id
+        );
+        let caller_reference = format!(
+            "{}-{}",
+            Utc::now().timestamp_millis(),
+            crate::common::generate_raw_id(6)
+        );
+        let body = invalidation_batch_xml(keys, &caller_reference);
+
+        // CloudFront is a global service signed against us-east-1
+        let identity = Credentials::new(
+            &config.access_key_id,
+            &config.secret_access_key,
+            None,
+            None,
+            "settings",
+        )
+        .into();
+        let signing_params = v4::SigningParams::builder()
+            .identity(&identity)
+            .region("us-east-1")
+            .name("cloudfront")
+            .time(SystemTime::now())
+            .settings(SigningSettings::default())
+            .build()
+            .map_err(|e| AWSError::CloudFrontError(format!("Signing setup failed: {}", e)))?
+            .into();
+        let signable = SignableRequest::new(
+            "POST",
+            &url,
+            std::iter::once(("content-type", "application/xml")),
+            SignableBody::Bytes(body.as_bytes()),
+        )
+        .map_err(|e| AWSError::CloudFrontError(format!("Signing failed: {}", e)))?;
+        let (instructions, _signature) = sign(signable, &signing_params)
+            .map_err(|e| AWSError::CloudFrontError(format!("Signing failed: {}", e)))?
+            .into_parts();
+
+        let mut request = reqwest::Client::new()
+            .post(&url)
+            .header("content-type", "application/xml")
+            .body(body);
+        for (name, value) in instructions.headers() {
+            request = request.header(name, value);
+        }
+
+        let response = request.send().await.map_err(|e| {
+            error!(error = %e, distribution_id = %distribution_id, "CloudFront invalidation request failed");
+            AWSError::CloudFrontError(format!("Invalidation request failed: {}", e))
+        })?;
+        let status = response.status();
+        if !status.is_success() {
+            let detail = response.text().await.unwrap_or_default();
+            error!(status = %status, detail = %detail, "CloudFront rejected invalidation");
+            return Err(AWSError::CloudFrontError(format!(
+                "Invalidation rejected with HTTP {}",
+                status
+            )));
+        }
+
+        info!(distribution_id = %distribution_id, count = keys.len(), "CloudFront invalidation created");
         Ok(())
     }
 
+    /// Text a phone number (E.164) through SNS as a transactional message; returns the
+    /// SNS message ID
+    pub async fn publish_sms(&self, phone: &str, message: &str) -> Result<String, AWSError> {
+        let config = self.get_config().await?;
+        let url = format!("https://sns.{}.amazonaws.com/", config.region);
+        let body = format!(
+            "Action=Publish&Version=2010-03-31&PhoneNumber={}&Message={}\
+             &MessageAttributes.entry.1.Name=AWS.SNS.SMS.SMSType\
+             &MessageAttributes.entry.1.Value.DataType=String\
+             &MessageAttributes.entry.1.Value.StringValue=Transactional",
+            urlencoding::encode(phone),
+            urlencoding::encode(message)
+        );
+
+        let identity = Credentials::new(
+            &config.access_key_id,
+            &config.secret_access_key,
+            None,
+            None,
+            "settings",
+        )
+        .into();
+        let signing_params = v4::SigningParams::builder()
+            .identity(&identity)
+            .region(&config.region)
+            .name("sns")
+            .time(SystemTime::now())
+            .settings(SigningSettings::default())
+            .build()
+            .map_err(|e| AWSError::SNSError(format!("Signing setup failed: {}", e)))?
+            .into();
+        let signable = SignableRequest::new(
+            "POST",
+            &url,
+            std::iter::once(("content-type", "application/x-www-form-urlencoded")),
+            SignableBody::Bytes(body.as_bytes()),
+        )
+        .map_err(|e| AWSError::SNSError(format!("Signing failed: {}", e)))?;
+        let (instructions, _signature) = sign(signable, &signing_params)
+            .map_err(|e| AWSError::SNSError(format!("Signing failed: {}", e)))?
+            .into_parts();
+
+        let mut request = reqwest::Client::new()
+            .post(&url)
+            .header("content-type", "application/x-www-form-urlencoded")
+            .body(body);
+        for (name, value) in instructions.headers() {
+            request = request.header(name, value);
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| AWSError::SNSError(format!("Publish request failed: {}", e)))?;
+        let status = response.status();
+        let detail = response.text().await.unwrap_or_default();
+        if !status.is_success() {
+            error!(status = %status, detail = %detail, "SNS rejected SMS publish");
+            return Err(AWSError::SNSError(format!(
+                "Publish rejected with HTTP {}",
+                status
+            )));
+        }
+
+        let message_id = detail
+            .split("<MessageId>")
+            .nth(1)
+            .and_then(|rest| rest.split("</MessageId>").next())
+            .unwrap_or_default()
+            .to_string();
+        Ok(message_id)
+    }
+
     /// Delete multiple files from S3
     pub async fn delete_files(&self, keys: Vec<String>) -> Result<(), AWSError> {
         for key in keys {
@@ -355,6 +632,53 @@ impl AWSService {
         }
     }
 
+    /// Verify the configured bucket exists and is reachable with a HeadBucket call
+    /// Returns the bucket name on success
+    pub async fn head_bucket(&self) -> Result<String, AWSError> {
+        let (client, bucket) = self.get_s3_client().await?;
+
+        client
+            .head_bucket()
+            .bucket(&bucket)
+            .send()
+            .await
+            .map_err(|e| {
+                warn!(error = %e, bucket = %bucket, "S3 HeadBucket failed");
+                AWSError::S3Error(format!("HeadBucket failed: {}", e))
+            })?;
+
+        debug!(bucket = %bucket, "S3 HeadBucket succeeded");
+        Ok(bucket)
+    }
+
+    /// Send a test email from the configured sender address to itself
+    /// Returns the address the test email was delivered to
+    pub async fn send_test_email(&self) -> Result<String, AWSError> {
+        let config = self.get_config().await?;
+
+        if config.ses_from_email.is_empty() {
+            return Err(AWSError::InvalidConfig(
+                "SES from email not configured".to_string(),
+            ));
+        }
+
+        let body = format!(
+            "<p>This is a test email sent from the admin settings page at {}.</p>\
+             <p>If you received it, AWS SES is configured correctly.</p>",
+            Utc::now().to_rfc3339()
+        );
+
+        self.send_email(
+            vec![config.ses_from_email.clone()],
+            "SES configuration test",
+            &body,
+            None,
+        )
+        .await?;
+
+        Ok(config.ses_from_email)
+    }
+
     /// Initialize SES client with credentials from settings
     async fn get_ses_client(&self) -> Result<SesClient, AWSError> {
         let config = self.get_config().await?;
@@ -426,18 +750,20 @@ impl AWSService {
 
         let email_content = EmailContent::builder().simple(message).build();
 
-        // Send email
-        let result = client
+        // Send email; a send that timed out may still have gone out, so it isn't repeated
+        let request = client
             .send_email()
             .from_email_address(&config.ses_from_email)
             .destination(destination)
-            .content(email_content)
-            .send()
-            .await
-            .map_err(|e| {
-                error!(error = %e, to = ?to, "Failed to send email via SES");
-                AWSError::SESError(format!("Send failed: {}", e))
-            })?;
+            .content(email_content);
+        let result = resilience::call_once(Dependency::Ses, async {
+            request
+                .send()
+                .await
+                .map_err(|e| sdk_error(e, "Send failed", AWSError::SESError))
+        })
+        .await
+        .inspect_err(|e| error!(error = %e, to = ?to, "Failed to send email via SES"))?;
 
         info!(
             to = ?to,
@@ -569,6 +895,37 @@ mod tests {
         assert_eq!(config.s3_bucket_name, "test-bucket");
     }
 
+    #[test]
+    fn test_invalidation_batch_xml() {
+        let keys = vec!["logos/a.png".to_string(), "/avatars/b&c.png".to_string()];
+        let xml = invalidation_batch_xml(&keys, "ref-1");
+
+        assert!(xml.contains("<Quantity>2</Quantity>"));
+        assert!(xml.contains("<Path>/logos/a.png</Path>"));
+        assert!(xml.contains("<Path>/avatars/b&amp;c.png</Path>"));
+        assert!(xml.contains("<CallerReference>ref-1</CallerReference>"));
+    }
+
+    #[tokio::test]
+    async fn test_invalidation_without_distribution_is_a_noop() {
+        let pool = setup_test_db().await;
+        let settings_service = Arc::new(SettingsService::new(pool));
+        settings_service
+            .set_setting("aws_access_key_id", "test_key", false, Some("admin"))
+            .await
+            .unwrap();
+        settings_service
+            .set_setting("aws_secret_access_key", "test_secret", false, Some("admin"))
+            .await
+            .unwrap();
+
+        let aws_service = AWSService::new(settings_service);
+        assert!(aws_service
+            .invalidate_paths(&["logos/a.png".to_string()])
+            .await
+            .is_ok());
+    }
+
     #[tokio::test]
     async fn test_get_file_url_standard() {
         let pool = setup_test_db().await;
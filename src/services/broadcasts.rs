@@ -0,0 +1,529 @@
+// src/services/broadcasts.rs
+//! Messages and emails sent to a segment of candidates
+//!
+//! Creating a broadcast resolves its segment once and stores a `pending` row per recipient.
+//! A background task then works through pending rows at `broadcast_rate_per_minute`
+//! (default 60), rendering the body for each candidate, so a large segment never floods SES
+//! or the chat connections. Failed deliveries are retried on later runs until
+//! `MAX_DELIVERY_ATTEMPTS`.
+
+use sqlx::SqlitePool;
+use std::sync::Arc;
+use tracing::{debug, error, info, warn};
+
+use crate::candidates::handlers::email_templates::status_to_stage;
+use crate::common::ApiError;
+use crate::messages::models::{Broadcast, BroadcastSegment};
+use crate::messages::services::{ConnectionManager, MessageService};
+use crate::services::sanitize::{render_markdown, sanitize_message};
+use crate::services::{EmailSender, SettingsService};
+
+pub const BROADCAST_CHANNELS: &[&str] = &["message", "email", "both"];
+
+/// Application statuses a stage segment can target
+pub const BROADCAST_STAGES: &[&str] = &[
+    "submitted",
+    "reviewed",
+    "shortlisted",
+    "interview_scheduled",
+    "interviewed",
+    "offered",
+    "hired",
+];
+
+pub const MAX_SUBJECT_LENGTH: usize = 200;
+
+/// Deliveries to a recipient before it is marked `failed`
+pub const MAX_DELIVERY_ATTEMPTS: i64 = 3;
+
+/// Largest segment a single broadcast may target
+pub const MAX_BROADCAST_RECIPIENTS: usize = 5000;
+
+const DEFAULT_RATE_PER_MINUTE: i64 = 60;
+
+const BROADCAST_INTERVAL_SECONDS: u64 = 60;
+
+/// A candidate in a segment
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct SegmentMember {
+    pub user_id: String,
+    pub application_id: Option<String>,
+}
+
+/// Per-recipient values for the broadcast placeholders
+#[derive(Debug, Default, sqlx::FromRow)]
+pub struct RecipientContext {
+    pub email: String,
+    pub name: Option<String>,
+    pub job_title: Option<String>,
+    pub company: Option<String>,
+    pub status: Option<String>,
+}
+
+/// Replace `{name}`, `{job}`, `{company}` and `{stage}` with the recipient's values. Values
+/// the recipient doesn't have (a talent pool member has no job) render as empty.
+pub fn fill_recipient_placeholders(template: &str, context: &RecipientContext) -> String {
+    let stage = context.status.as_deref().map(status_to_stage).unwrap_or("");
+    let values = [
+        ("name", context.name.as_deref().unwrap_or("")),
+        ("job", context.job_title.as_deref().unwrap_or("")),
+        ("company", context.company.as_deref().unwrap_or("")),
+        ("stage", stage),
+    ];
+    values
+        .iter()
+        .fold(template.to_string(), |text, (key, value)| {
+            text.replace(&format!("{{{}}}", key), value)
+        })
+}
+
+/// Check a broadcast before anything is resolved or queued
+pub fn validate_broadcast(
+    segment: &BroadcastSegment,
+    channel: &str,
+    subject: Option<&str>,
+    body: &str,
+) -> Result<(), ApiError> {
+    if !BROADCAST_CHANNELS.contains(&channel) {
+        return Err(ApiError::ValidationError(format!(
+            "Invalid channel '{}'; expected one of {}",
+            channel,
+            BROADCAST_CHANNELS.join(", ")
+        )));
+    }
+    if let BroadcastSegment::Stage { stage, .. } = segment {
+        if !BROADCAST_STAGES.contains(&stage.as_str()) {
+            return Err(ApiError::ValidationError(format!(
+                "Invalid stage '{}'; expected one of {}",
+                stage,
+                BROADCAST_STAGES.join(", ")
+            )));
+        }
+    }
+    if channel != "message" {
+        match subject.map(str::trim) {
+            None | Some("") => {
+                return Err(ApiError::ValidationError(
+                    "A subject is required when sending email".to_string(),
+                ))
+            }
+            Some(subject) if subject.chars().count() > MAX_SUBJECT_LENGTH => {
+                return Err(ApiError::ValidationError(format!(
+                    "Subject exceeds maximum length of {} characters",
+                    MAX_SUBJECT_LENGTH
+                )))
+            }
+            Some(_) => {}
+        }
+    }
+    crate::messages::validators::validate_message_content(body)
+}
+
+/// Everyone in a segment, one row per candidate
+///
+/// Applications count while they are neither archived nor closed out as rejected or
+/// withdrawn. Merged accounts are left out; their applications moved to the surviving user.
+pub async fn resolve_segment(
+    pool: &SqlitePool,
+    segment: &BroadcastSegment,
+) -> Result<Vec<SegmentMember>, ApiError> {
+    let members = match segment {
+        BroadcastSegment::JobApplicants { job_id } => {
+            sqlx::query_as::<_, SegmentMember>(
+                r#"
+                SELECT a.user_id, MIN(a.id) AS application_id
+                FROM applications a
+                JOIN users u ON u.id = a.user_id
+                WHERE a.job_id = ?
+                  AND a.archived_at IS NULL
+                  AND a.status NOT IN ('rejected', 'withdrawn')
+                  AND u.merged_into IS NULL
+                GROUP BY a.user_id
+                ORDER BY MIN(a.applied_at)
+                "#,
+            )
+            .bind(job_id)
+            .fetch_all(pool)
+            .await
+        }
+        BroadcastSegment::Stage { stage, job_id } => {
+            sqlx::query_as::<_, SegmentMember>(
+                r#"
+                SELECT a.user_id, MIN(a.id) AS application_id
+                FROM applications a
+                JOIN users u ON u.id = a.user_id
+                WHERE a.status = ?
+                  AND (? IS NULL OR a.job_id = ?)
+                  AND a.archived_at IS NULL
+                  AND u.merged_into IS NULL
+                GROUP BY a.user_id
+                ORDER BY MIN(a.applied_at)
+                "#,
+            )
+            .bind(stage)
+            .bind(job_id)
+            .bind(job_id)
+            .fetch_all(pool)
+            .await
+        }
+        BroadcastSegment::TalentPool => {
+            sqlx::query_as::<_, SegmentMember>(
+                r#"
+                SELECT u.id AS user_id, NULL AS application_id
+                FROM users u
+                WHERE u.merged_into IS NULL
+                  AND (
+                      SELECT c.granted FROM consents c
+                      WHERE c.user_id = u.id AND c.consent_type = 'marketing'
+                      ORDER BY c.created_at DESC, c.rowid DESC
+                      LIMIT 1
+                  ) = 1
+                ORDER BY u.created_at
+                "#,
+            )
+            .fetch_all(pool)
+            .await
+        }
+    }
+    .map_err(|e| {
+        error!(error = %e, segment = segment.as_str(), "Database error resolving broadcast segment");
+        ApiError::DatabaseError(e)
+    })?;
+
+    if members.len() > MAX_BROADCAST_RECIPIENTS {
+        return Err(ApiError::ValidationError(format!(
+            "The segment has {} candidates; a broadcast can reach at most {}",
+            members.len(),
+            MAX_BROADCAST_RECIPIENTS
+        )));
+    }
+    Ok(members)
+}
+
+/// The placeholder values for one recipient
+pub async fn recipient_context(
+    pool: &SqlitePool,
+    user_id: &str,
+    application_id: Option<&str>,
+) -> Result<Option<RecipientContext>, sqlx::Error> {
+    sqlx::query_as::<_, RecipientContext>(
+        r#"
+        SELECT u.email, u.name, j.title AS job_title, j.company, a.status
+        FROM users u
+        LEFT JOIN applications a ON a.id = ? AND a.user_id = u.id
+        LEFT JOIN jobs j ON j.id = a.job_id
+        WHERE u.id = ?
+        "#,
+    )
+    .bind(application_id)
+    .bind(user_id)
+    .fetch_optional(pool)
+    .await
+}
+
+fn email_html(body_markdown: &str) -> String {
+    format!(
+        r#"<html><body style="font-family: Arial, sans-serif; line-height: 1.6; color: #333;">
+<div style="max-width: 600px; margin: 0 auto; padding: 20px;">
+{}
+</div></body></html>"#,
+        render_markdown(body_markdown)
+    )
+}
+
+#[derive(sqlx::FromRow)]
+struct PendingDelivery {
+    broadcast_id: String,
+    user_id: String,
+    application_id: Option<String>,
+    attempts: i64,
+}
+
+/// Deliver one pending recipient over the broadcast's channels. Returns the chat message id
+/// when one was posted.
+async fn deliver(
+    pool: &SqlitePool,
+    email_sender: &dyn EmailSender,
+    connection_manager: &ConnectionManager,
+    broadcast: &Broadcast,
+    recipient: &PendingDelivery,
+) -> Result<Option<String>, String> {
+    let context = recipient_context(
+        pool,
+        &recipient.user_id,
+        recipient.application_id.as_deref(),
+    )
+    .await
+    .map_err(|e| e.to_string())?
+    .ok_or_else(|| "Recipient no longer exists".to_string())?;
+    let body = fill_recipient_placeholders(&broadcast.body, &context);
+
+    let mut message_id = None;
+    if broadcast.channel != "email" {
+        let content = sanitize_message(&body);
+        let message = MessageService::new(pool.clone())
+            .post_admin_message(connection_manager, &recipient.user_id, &content)
+            .await
+            .map_err(|e| e.to_string())?;
+        message_id = Some(message.id);
+    }
+
+    if broadcast.channel != "message" {
+        let subject = fill_recipient_placeholders(broadcast.subject.as_deref().unwrap_or_default(), &context);
+        if let Err(e) = email_sender
+            .send_email(
+                vec![context.email.clone()],
+                &subject,
+                &email_html(&body),
+                None,
+            )
+            .await
+        {
+            // A posted chat message stays delivered; record it so a retry doesn't repeat it
+            if let Some(id) = &message_id {
+                let _ = sqlx::query(
+                    "UPDATE broadcast_recipients SET message_id = ? WHERE broadcast_id = ? AND user_id = ?",
+                )
+                .bind(id)
+                .bind(&broadcast.id)
+                .bind(&recipient.user_id)
+                .execute(pool)
+                .await;
+            }
+            return Err(e.to_string());
+        }
+    }
+
+    Ok(message_id)
+}
+
+/// Deliver up to `limit` pending recipients across queued broadcasts, oldest first, and mark
+/// broadcasts with nothing left pending as completed
+pub async fn dispatch_broadcasts(
+    pool: &SqlitePool,
+    email_sender: &dyn EmailSender,
+    connection_manager: &ConnectionManager,
+    limit: i64,
+) -> Result<usize, sqlx::Error> {
+    let pending = sqlx::query_as::<_, PendingDelivery>(
+        r#"
+        SELECT r.broadcast_id, r.user_id, r.application_id, r.attempts
+        FROM broadcast_recipients r
+        JOIN broadcasts b ON b.id = r.broadcast_id
+        WHERE r.status = 'pending' AND b.status IN ('queued', 'sending')
+        ORDER BY b.created_at, r.rowid
+        LIMIT ?
+        "#,
+    )
+    .bind(limit)
+    .fetch_all(pool)
+    .await?;
+
+    let mut sent = 0;
+    let mut current: Option<Broadcast> = None;
+    for recipient in pending {
+        if current.as_ref().map(|b| b.id.as_str()) != Some(recipient.broadcast_id.as_str()) {
+            sqlx::query(
+                "UPDATE broadcasts SET status = 'sending' WHERE id = ? AND status = 'queued'",
+            )
+            .bind(&recipient.broadcast_id)
+            .execute(pool)
+            .await?;
+            current = sqlx::query_as::<_, Broadcast>("SELECT * FROM broadcasts WHERE id = ?")
+                .bind(&recipient.broadcast_id)
+                .fetch_optional(pool)
+                .await?;
+        }
+        let Some(broadcast) = current.as_ref() else {
+            continue;
+        };
+
+        // A chat message posted before an email failure is not posted again
+        let already_posted = sqlx::query_scalar::<_, Option<String>>(
+            "SELECT message_id FROM broadcast_recipients WHERE broadcast_id = ? AND user_id = ?",
+        )
+        .bind(&broadcast.id)
+        .bind(&recipient.user_id)
+        .fetch_one(pool)
+        .await?;
+        let result = match (&already_posted, broadcast.channel.as_str()) {
+            (Some(_), "both") => {
+                let email_only = Broadcast {
+                    channel: "email".to_string(),
+                    ..broadcast.clone()
+                };
+                deliver(
+                    pool,
+                    email_sender,
+                    connection_manager,
+                    &email_only,
+                    &recipient,
+                )
+                .await
+                .map(|_| already_posted.clone())
+            }
+            _ => deliver(pool, email_sender, connection_manager, broadcast, &recipient).await,
+        };
+
+        match result {
+            Ok(message_id) => {
+                sqlx::query(
+                    r#"
+                    UPDATE broadcast_recipients
+                    SET status = 'sent', attempts = attempts + 1, message_id = ?, error = NULL,
+                        sent_at = datetime('now')
+                    WHERE broadcast_id = ? AND user_id = ?
+                    "#,
+                )
+                .bind(&message_id)
+                .bind(&broadcast.id)
+                .bind(&recipient.user_id)
+                .execute(pool)
+                .await?;
+                sent += 1;
+            }
+            Err(e) => {
+                let status = if recipient.attempts + 1 >= MAX_DELIVERY_ATTEMPTS {
+                    "failed"
+                } else {
+                    "pending"
+                };
+                warn!(
+                    error = %e,
+                    broadcast_id = %broadcast.id,
+                    user_id = %recipient.user_id,
+                    attempts = recipient.attempts + 1,
+                    "Broadcast delivery failed"
+                );
+                sqlx::query(
+                    r#"
+                    UPDATE broadcast_recipients
+                    SET status = ?, attempts = attempts + 1, error = ?
+                    WHERE broadcast_id = ? AND user_id = ?
+                    "#,
+                )
+                .bind(status)
+                .bind(&e)
+                .bind(&broadcast.id)
+                .bind(&recipient.user_id)
+                .execute(pool)
+                .await?;
+            }
+        }
+    }
+
+    let completed = sqlx::query(
+        r#"
+        UPDATE broadcasts SET status = 'completed', completed_at = datetime('now')
+        WHERE status IN ('queued', 'sending')
+          AND NOT EXISTS (
+              SELECT 1 FROM broadcast_recipients r
+              WHERE r.broadcast_id = broadcasts.id AND r.status = 'pending'
+          )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    if sent > 0 || completed.rows_affected() > 0 {
+        info!(
+            sent = sent,
+            completed = completed.rows_affected(),
+            "Delivered broadcast messages"
+        );
+    }
+    Ok(sent)
+}
+
+/// Deliver queued broadcasts every minute, at most `broadcast_rate_per_minute` recipients
+/// per run
+pub fn start_broadcast_task(
+    pool: SqlitePool,
+    settings_service: Arc<SettingsService>,
+    email_sender: Arc<dyn EmailSender>,
+    connection_manager: ConnectionManager,
+) {
+    tokio::spawn(async move {
+        loop {
+            if !crate::common::shutdown::sleep(std::time::Duration::from_secs(
+                BROADCAST_INTERVAL_SECONDS,
+            ))
+            .await
+            {
+                break;
+            }
+            let _running = crate::common::shutdown::track();
+
+            let rate = settings_service
+                .get_setting("broadcast_rate_per_minute")
+                .await
+                .ok()
+                .flatten()
+                .and_then(|v| v.parse::<i64>().ok())
+                .filter(|v| *v > 0)
+                .unwrap_or(DEFAULT_RATE_PER_MINUTE);
+
+            if let Err(e) =
+                dispatch_broadcasts(&pool, email_sender.as_ref(), &connection_manager, rate).await
+            {
+                debug!(error = %e, "Skipped broadcast dispatch");
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn context() -> RecipientContext {
+        RecipientContext {
+            email: "ana@example.com".to_string(),
+            name: Some("Ana".to_string()),
+            job_title: Some("Line Cook".to_string()),
+            company: Some("Acme".to_string()),
+            status: Some("interview_scheduled".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_fill_recipient_placeholders() {
+        assert_eq!(
+            fill_recipient_placeholders(
+                "Hi {name}, your {job} application at {company} is at {stage}.",
+                &context()
+            ),
+            "Hi Ana, your Line Cook application at Acme is at Interview Scheduled."
+        );
+    }
+
+    #[test]
+    fn test_fill_recipient_placeholders_blanks_missing_values() {
+        let pool_member = RecipientContext {
+            email: "ana@example.com".to_string(),
+            name: Some("Ana".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            fill_recipient_placeholders("Hi {name}, new roles{job} at {unknown}", &pool_member),
+            "Hi Ana, new roles at {unknown}"
+        );
+    }
+
+    #[test]
+    fn test_validate_broadcast() {
+        let segment = BroadcastSegment::TalentPool;
+        assert!(validate_broadcast(&segment, "message", None, "Hello").is_ok());
+        assert!(validate_broadcast(&segment, "email", Some("News"), "Hello").is_ok());
+        assert!(validate_broadcast(&segment, "sms", None, "Hello").is_err());
+        assert!(validate_broadcast(&segment, "both", Some("  "), "Hello").is_err());
+        assert!(validate_broadcast(&segment, "message", None, "   ").is_err());
+
+        let stage = |stage: &str| BroadcastSegment::Stage {
+            stage: stage.to_string(),
+            job_id: None,
+        };
+        assert!(validate_broadcast(&stage("offered"), "message", None, "Hello").is_ok());
+        assert!(validate_broadcast(&stage("rejected"), "message", None, "Hello").is_err());
+    }
+}
@@ -0,0 +1,388 @@
+// src/services/calendar_feed.rs
+//! Subscribable iCalendar feed of the interviews a user runs or sits on
+//!
+//! Each user has at most one feed token. Calendar apps can't send an Authorization header,
+//! so the token in the URL is the credential; only its hash is stored, and regenerating it
+//! cuts off every existing subscription. The feed covers interviews the user created, is
+//! an interviewer on or is listed on the panel of, from a month back onwards. Cancelled
+//! interviews stay in the feed as cancelled so subscribed calendars drop them.
+
+use chrono::{DateTime, Duration, NaiveDateTime, Utc};
+use rand::distributions::{Alphanumeric, DistString};
+use sha2::{Digest, Sha256};
+use sqlx::SqlitePool;
+
+use crate::candidates::models::InterviewPanelMember;
+use crate::common::timezone::parse_stored;
+
+/// How far back the feed reaches, so recent interviews don't vanish from calendars
+pub const FEED_LOOKBACK_DAYS: i64 = 30;
+
+pub const FEED_EVENT_LIMIT: i64 = 500;
+
+const TOKEN_LENGTH: usize = 40;
+
+/// A new random feed token
+pub fn generate_token() -> String {
+    Alphanumeric.sample_string(&mut rand::thread_rng(), TOKEN_LENGTH)
+}
+
+/// Hex SHA-256 of a URL token, stored in place of the token itself
+pub fn hash_token(token: &str) -> String {
+    Sha256::digest(token.as_bytes())
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// Replace the user's token; the old feed URL stops working
+pub async fn regenerate_token(pool: &SqlitePool, user_id: &str) -> Result<String, sqlx::Error> {
+    let token = generate_token();
+    sqlx::query(
+        r#"
+        INSERT INTO calendar_feed_tokens (user_id, token_hash, created_at, last_accessed_at)
+        VALUES (?, ?, datetime('now'), NULL)
+        ON CONFLICT(user_id) DO UPDATE SET
+            token_hash = excluded.token_hash,
+            created_at = excluded.created_at,
+            last_accessed_at = NULL
+        "#,
+    )
+    .bind(user_id)
+    .bind(hash_token(&token))
+    .execute(pool)
+    .await?;
+    Ok(token)
+}
+
+/// The user ID and email a feed token belongs to, noting the access
+pub async fn resolve_token(
+    pool: &SqlitePool,
+    token: &str,
+) -> Result<Option<(String, String)>, sqlx::Error> {
+    let owner: Option<(String, String)> = sqlx::query_as(
+        r#"
+        SELECT u.id, u.email FROM calendar_feed_tokens t
+        JOIN users u ON u.id = t.user_id
+        WHERE t.token_hash = ?
+        "#,
+    )
+    .bind(hash_token(token))
+    .fetch_optional(pool)
+    .await?;
+
+    if let Some((user_id, _)) = &owner {
+        sqlx::query(
+            "UPDATE calendar_feed_tokens SET last_accessed_at = datetime('now') WHERE user_id = ?",
+        )
+        .bind(user_id)
+        .execute(pool)
+        .await?;
+    }
+    Ok(owner)
+}
+
+/// An interview as shown in the feed
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct FeedInterview {
+    pub id: String,
+    pub scheduled_date: String,
+    pub duration_minutes: i32,
+    pub interview_type: String,
+    pub google_meet_link: Option<String>,
+    pub status: Option<String>,
+    pub panel_members: String,
+    pub updated_at: Option<String>,
+    pub candidate_name: Option<String>,
+    pub job_title: Option<String>,
+}
+
+/// Interviews the user created, interviews on or is a panelist of
+pub async fn feed_interviews(
+    pool: &SqlitePool,
+    user_id: &str,
+    email: &str,
+) -> Result<Vec<FeedInterview>, sqlx::Error> {
+    sqlx::query_as::<_, FeedInterview>(
+        r#"
+        SELECT i.id, i.scheduled_date, i.duration_minutes, i.interview_type, i.google_meet_link,
+               i.status, i.panel_members, i.updated_at, u.name AS candidate_name,
+               j.title AS job_title
+        FROM interviews i
+        LEFT JOIN users u ON u.id = i.candidate_id
+        LEFT JOIN jobs j ON j.id = i.job_id
+        WHERE datetime(i.scheduled_date) >= datetime('now', ?3)
+          AND (
+              i.created_by = ?1
+              OR EXISTS (
+                  SELECT 1 FROM interview_interviewers ii
+                  WHERE ii.interview_id = i.id AND ii.user_id = ?1
+              )
+              OR CASE WHEN json_valid(i.panel_members) THEN EXISTS (
+                  SELECT 1 FROM json_each(i.panel_members) p
+                  WHERE lower(json_extract(p.value, '$.email')) = lower(?2)
+              ) ELSE 0 END
+          )
+        ORDER BY datetime(i.scheduled_date)
+        LIMIT ?4
+        "#,
+    )
+    .bind(user_id)
+    .bind(email)
+    .bind(format!("-{} days", FEED_LOOKBACK_DAYS))
+    .bind(FEED_EVENT_LIMIT)
+    .fetch_all(pool)
+    .await
+}
+
+/// Interviews scheduled with `user_id` as the candidate
+pub async fn candidate_interviews(
+    pool: &SqlitePool,
+    user_id: &str,
+) -> Result<Vec<FeedInterview>, sqlx::Error> {
+    sqlx::query_as::<_, FeedInterview>(
+        r#"
+        SELECT i.id, i.scheduled_date, i.duration_minutes, i.interview_type, i.google_meet_link,
+               i.status, i.panel_members, i.updated_at, u.name AS candidate_name,
+               j.title AS job_title
+        FROM interviews i
+        LEFT JOIN users u ON u.id = i.candidate_id
+        LEFT JOIN jobs j ON j.id = i.job_id
+        WHERE i.candidate_id = ? AND datetime(i.scheduled_date) >= datetime('now', ?)
+        ORDER BY datetime(i.scheduled_date)
+        LIMIT ?
+        "#,
+    )
+    .bind(user_id)
+    .bind(format!("-{} days", FEED_LOOKBACK_DAYS))
+    .bind(FEED_EVENT_LIMIT)
+    .fetch_all(pool)
+    .await
+}
+
+/// Escape a TEXT value (RFC 5545 section 3.3.11)
+fn escape_text(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace("\r\n", "\\n")
+        .replace('\n', "\\n")
+        .replace('\r', "")
+}
+
+/// Fold a content line at 75 octets without splitting a character (RFC 5545 section 3.1)
+fn fold_line(line: &str) -> String {
+    let mut folded = String::with_capacity(line.len() + 8);
+    let mut width = 0;
+    for c in line.chars() {
+        if width + c.len_utf8() > 75 {
+            folded.push_str("\r\n ");
+            width = 1;
+        }
+        folded.push(c);
+        width += c.len_utf8();
+    }
+    folded.push_str("\r\n");
+    folded
+}
+
+fn format_utc(at: DateTime<Utc>) -> String {
+    at.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+/// `datetime('now')` column values, which are UTC without an offset
+fn parse_sqlite_utc(value: &str) -> Option<DateTime<Utc>> {
+    NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S")
+        .ok()
+        .map(|naive| naive.and_utc())
+        .or_else(|| parse_stored(value))
+}
+
+/// Whose calendar the feed goes into, which decides how events are described
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Audience {
+    /// Interviewers and panelists: events name the candidate and the panel
+    HiringTeam,
+    /// The candidate: events name the job only
+    Candidate,
+}
+
+fn render_event(
+    interview: &FeedInterview,
+    now: DateTime<Utc>,
+    audience: Audience,
+    lines: &mut Vec<String>,
+) {
+    let Some(start) = parse_stored(&interview.scheduled_date) else {
+        return;
+    };
+    let end = start + Duration::minutes(i64::from(interview.duration_minutes.max(1)));
+    let candidate = interview.candidate_name.as_deref().unwrap_or("Candidate");
+    let summary = match (audience, interview.job_title.as_deref()) {
+        (Audience::HiringTeam, Some(job)) => format!(
+            "{} interview: {} ({})",
+            interview.interview_type, candidate, job
+        ),
+        (Audience::HiringTeam, None) => {
+            format!("{} interview: {}", interview.interview_type, candidate)
+        }
+        (Audience::Candidate, Some(job)) => {
+            format!("{} interview for {}", interview.interview_type, job)
+        }
+        (Audience::Candidate, None) => format!("{} interview", interview.interview_type),
+    };
+
+    let mut description = Vec::new();
+    if audience == Audience::HiringTeam {
+        description.push(format!("Candidate: {}", candidate));
+    }
+    if let Some(job) = &interview.job_title {
+        description.push(format!("Job: {}", job));
+    }
+    let panel: Vec<InterviewPanelMember> =
+        serde_json::from_str(&interview.panel_members).unwrap_or_default();
+    if audience == Audience::HiringTeam && !panel.is_empty() {
+        let names: Vec<&str> = panel
+            .iter()
+            .map(|m| m.name.as_deref().unwrap_or(&m.email))
+            .collect();
+        description.push(format!("Panel: {}", names.join(", ")));
+    }
+    if let Some(link) = &interview.google_meet_link {
+        description.push(format!("Join: {}", link));
+    }
+    let cancelled = interview.status.as_deref() == Some("cancelled");
+
+    lines.push("BEGIN:VEVENT".to_string());
+    lines.push(format!("UID:interview-{}@job-api", interview.id));
+    lines.push(format!("DTSTAMP:{}", format_utc(now)));
+    lines.push(format!("DTSTART:{}", format_utc(start)));
+    lines.push(format!("DTEND:{}", format_utc(end)));
+    lines.push(format!("SUMMARY:{}", escape_text(&summary)));
+    lines.push(format!(
+        "DESCRIPTION:{}",
+        escape_text(&description.join("\n"))
+    ));
+    if let Some(link) = &interview.google_meet_link {
+        lines.push(format!("LOCATION:{}", escape_text(link)));
+    }
+    if let Some(modified) = interview.updated_at.as_deref().and_then(parse_sqlite_utc) {
+        lines.push(format!("LAST-MODIFIED:{}", format_utc(modified)));
+    }
+    lines.push(format!(
+        "STATUS:{}",
+        if cancelled { "CANCELLED" } else { "CONFIRMED" }
+    ));
+    lines.push("END:VEVENT".to_string());
+}
+
+fn render_calendar(
+    name: &str,
+    interviews: &[FeedInterview],
+    now: DateTime<Utc>,
+    audience: Audience,
+) -> String {
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//job_api//Interviews//EN".to_string(),
+        "CALSCALE:GREGORIAN".to_string(),
+        "METHOD:PUBLISH".to_string(),
+        format!("X-WR-CALNAME:{}", escape_text(name)),
+        "REFRESH-INTERVAL;VALUE=DURATION:PT1H".to_string(),
+        "X-PUBLISHED-TTL:PT1H".to_string(),
+    ];
+    for interview in interviews {
+        render_event(interview, now, audience, &mut lines);
+    }
+    lines.push("END:VCALENDAR".to_string());
+
+    lines.iter().map(|line| fold_line(line)).collect()
+}
+
+/// Render the feed as an iCalendar document
+pub fn render_ics(interviews: &[FeedInterview], now: DateTime<Utc>) -> String {
+    render_calendar("Interviews", interviews, now, Audience::HiringTeam)
+}
+
+/// Render a candidate's own interviews, without the panel or the candidate's name
+pub fn render_candidate_ics(interviews: &[FeedInterview], now: DateTime<Utc>) -> String {
+    render_calendar("My interviews", interviews, now, Audience::Candidate)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn interview() -> FeedInterview {
+        FeedInterview {
+            id: "I_1".to_string(),
+            scheduled_date: "2026-03-02T15:00:00+00:00".to_string(),
+            duration_minutes: 45,
+            interview_type: "technical".to_string(),
+            google_meet_link: Some("https://meet.google.com/abc-defg-hij".to_string()),
+            status: Some("scheduled".to_string()),
+            panel_members: r#"[{"email":"lee@example.com","name":"Lee, Sam"}]"#.to_string(),
+            updated_at: Some("2026-02-20 09:30:00".to_string()),
+            candidate_name: Some("Ada Lovelace".to_string()),
+            job_title: Some("Engineer; Platform".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_hash_token() {
+        let token = generate_token();
+        assert_eq!(token.len(), TOKEN_LENGTH);
+        assert_eq!(hash_token(&token), hash_token(&token));
+        assert_ne!(hash_token(&token), hash_token(&generate_token()));
+        assert_eq!(hash_token(&token).len(), 64);
+    }
+
+    #[test]
+    fn test_escape_and_fold() {
+        assert_eq!(escape_text("a,b;c\\d\ne"), "a\\,b\\;c\\\\d\\ne");
+
+        let folded = fold_line(&format!("SUMMARY:{}", "é".repeat(60)));
+        let lines: Vec<&str> = folded.trim_end_matches("\r\n").split("\r\n").collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines.iter().all(|l| l.len() <= 75));
+        assert!(lines[1].starts_with(' '));
+    }
+
+    #[test]
+    fn test_render_ics() {
+        let now = Utc.with_ymd_and_hms(2026, 2, 25, 8, 0, 0).unwrap();
+        let mut cancelled = interview();
+        cancelled.id = "I_2".to_string();
+        cancelled.status = Some("cancelled".to_string());
+        let mut unparseable = interview();
+        unparseable.scheduled_date = "soon".to_string();
+
+        let ics = render_ics(&[interview(), cancelled, unparseable], now);
+        let unfolded = ics.replace("\r\n ", "");
+        assert!(ics.starts_with("BEGIN:VCALENDAR\r\n"));
+        assert!(ics.ends_with("END:VCALENDAR\r\n"));
+        assert_eq!(ics.matches("BEGIN:VEVENT").count(), 2);
+        assert!(ics.contains("DTSTART:20260302T150000Z\r\n"));
+        assert!(ics.contains("DTEND:20260302T154500Z\r\n"));
+        assert!(
+            unfolded.contains("SUMMARY:technical interview: Ada Lovelace (Engineer\\; Platform)")
+        );
+        assert!(ics.contains("LAST-MODIFIED:20260220T093000Z\r\n"));
+        assert!(ics.contains("STATUS:CANCELLED\r\n"));
+        assert!(unfolded.contains("Panel: Lee\\, Sam\\nJoin: https://meet.google.com/"));
+    }
+
+    #[test]
+    fn test_render_candidate_ics() {
+        let now = Utc.with_ymd_and_hms(2026, 2, 25, 8, 0, 0).unwrap();
+        let ics = render_candidate_ics(&[interview()], now);
+        let unfolded = ics.replace("\r\n ", "");
+        assert!(ics.contains("X-WR-CALNAME:My interviews\r\n"));
+        assert!(unfolded.contains("SUMMARY:technical interview for Engineer\\; Platform"));
+        assert!(!unfolded.contains("Ada Lovelace"));
+        assert!(!unfolded.contains("Panel:"));
+        assert!(unfolded.contains("Join: https://meet.google.com/"));
+    }
+}
@@ -0,0 +1,326 @@
+// src/services/calendar_retries.rs
+//! Background retries for Google Calendar operations that failed
+//!
+//! Scheduling an interview doesn't wait for Google: when the Meet link can't be created the
+//! interview is saved without one and the operation is queued here. A background task retries
+//! due rows with backoff; once the link is created it is written to the interview and the
+//! candidate and panelists get the usual update email carrying it. Rows for interviews that
+//! were cancelled, already took place or got a link some other way are skipped.
+
+use chrono::{DateTime, Duration, SecondsFormat, Utc};
+use serde::Serialize;
+use sqlx::{FromRow, SqlitePool};
+use std::sync::Arc;
+use tracing::{debug, error, info, warn};
+
+use crate::candidates::models::InterviewPanelMember;
+use crate::common::generate_calendar_retry_id;
+use crate::common::timezone::parse_stored;
+use crate::services::interviews;
+use crate::services::{CalendarClient, EmailSender};
+
+/// Attempts before an operation is marked failed
+pub const MAX_CALENDAR_ATTEMPTS: i64 = 6;
+
+/// Operations claimed per worker pass
+const RETRY_BATCH_SIZE: i64 = 20;
+
+const RETRY_INTERVAL_SECONDS: u64 = 60;
+
+/// A queued calendar operation
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct CalendarRetry {
+    pub id: String,
+    pub interview_id: String,
+    /// Only `create_meet_link` so far
+    pub operation: String,
+    /// pending, running, succeeded, failed or skipped
+    pub status: String,
+    pub attempts: i64,
+    /// UTC, RFC3339
+    pub next_attempt_at: String,
+    pub last_error: Option<String>,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+}
+
+/// Timestamp format used for `next_attempt_at`, so due rows can be found by string comparison
+fn retry_timestamp(at: DateTime<Utc>) -> String {
+    at.to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+/// Wait before the next attempt: 1, 2, 4… minutes, capped at an hour
+pub fn retry_delay(attempts: i64) -> Duration {
+    Duration::minutes(2_i64.pow(attempts.clamp(1, 7) as u32 - 1).min(60))
+}
+
+/// Queue the Meet link for an interview whose link couldn't be created at scheduling time
+pub async fn enqueue_meet_link(
+    pool: &SqlitePool,
+    interview_id: &str,
+    reason: &str,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        r#"
+        INSERT INTO calendar_retries (id, interview_id, operation, next_attempt_at, last_error)
+        VALUES (?, ?, 'create_meet_link', ?, ?)
+        "#,
+    )
+    .bind(generate_calendar_retry_id())
+    .bind(interview_id)
+    .bind(retry_timestamp(Utc::now() + retry_delay(1)))
+    .bind(reason)
+    .execute(pool)
+    .await?;
+
+    info!(
+        interview_id = %interview_id,
+        reason = %reason,
+        "Queued Meet link creation for retry"
+    );
+    Ok(())
+}
+
+/// What a retry attempt came to
+enum Outcome {
+    Created,
+    /// Nothing left to do, with the reason
+    Skipped(String),
+    Failed(String),
+}
+
+/// Create the Meet link for one queued interview and tell everyone about it
+async fn create_meet_link(
+    pool: &SqlitePool,
+    calendar: Arc<dyn CalendarClient>,
+    email_sender: &dyn EmailSender,
+    retry: &CalendarRetry,
+) -> Outcome {
+    let interview = match interviews::get_interview(pool, &retry.interview_id).await {
+        Ok(interview) => interview,
+        Err(e) => return Outcome::Skipped(format!("Interview unavailable: {}", e)),
+    };
+
+    if interview.status.as_deref().unwrap_or("scheduled") != "scheduled" {
+        return Outcome::Skipped("Interview is no longer scheduled".to_string());
+    }
+    if interview.google_meet_link.is_some() {
+        return Outcome::Skipped("Interview already has a Meet link".to_string());
+    }
+    let start = match parse_stored(&interview.scheduled_date) {
+        Some(start) if start > Utc::now() => start,
+        _ => return Outcome::Skipped("Interview has already started".to_string()),
+    };
+    let panel_members: Vec<InterviewPanelMember> =
+        serde_json::from_str(&interview.panel_members).unwrap_or_default();
+
+    let (meet_link, event_id) = match interviews::create_google_meet_link_internal(
+        calendar,
+        start,
+        interview.duration_minutes,
+        &panel_members,
+        &interview.interview_type,
+    )
+    .await
+    {
+        Ok(created) => created,
+        Err(e) => return Outcome::Failed(e.to_string()),
+    };
+
+    if let Err(e) = sqlx::query(
+        "UPDATE interviews SET google_meet_link = ?, google_calendar_event_id = ?, updated_at = datetime('now') WHERE id = ?",
+    )
+    .bind(&meet_link)
+    .bind(&event_id)
+    .bind(&interview.id)
+    .execute(pool)
+    .await
+    {
+        // The event exists in Google now; retrying would create a second one
+        error!(error = %e, interview_id = %interview.id, event_id = %event_id, "Database error saving retried Meet link");
+        return Outcome::Skipped(format!("Meet link created but not saved: {}", e));
+    }
+
+    info!(
+        interview_id = %interview.id,
+        meet_link = %meet_link,
+        attempts = retry.attempts + 1,
+        "Meet link created on retry"
+    );
+
+    if let Err(e) =
+        interviews::send_interview_update_email_to_candidate(pool, email_sender, &interview.id)
+            .await
+    {
+        warn!(error = %e, interview_id = %interview.id, "Failed to send Meet link to candidate");
+    }
+    if let Err(e) =
+        interviews::send_interview_update_emails_to_panelists(pool, email_sender, &interview.id)
+            .await
+    {
+        warn!(error = %e, interview_id = %interview.id, "Failed to send Meet link to panelists");
+    }
+
+    Outcome::Created
+}
+
+/// Record the outcome of an attempt, scheduling another while attempts remain
+async fn record_outcome(
+    pool: &SqlitePool,
+    retry: &CalendarRetry,
+    outcome: Outcome,
+) -> Result<(), sqlx::Error> {
+    let attempts = retry.attempts + 1;
+    match outcome {
+        Outcome::Created => {
+            sqlx::query(
+                "UPDATE calendar_retries SET status = 'succeeded', attempts = ?, updated_at = datetime('now') WHERE id = ?",
+            )
+            .bind(attempts)
+            .bind(&retry.id)
+            .execute(pool)
+            .await?;
+        }
+        Outcome::Skipped(reason) => {
+            sqlx::query(
+                "UPDATE calendar_retries SET status = 'skipped', last_error = ?, updated_at = datetime('now') WHERE id = ?",
+            )
+            .bind(&reason)
+            .bind(&retry.id)
+            .execute(pool)
+            .await?;
+            debug!(retry_id = %retry.id, interview_id = %retry.interview_id, reason = %reason, "Skipped calendar retry");
+        }
+        Outcome::Failed(reason) if attempts < MAX_CALENDAR_ATTEMPTS => {
+            let next_attempt_at = retry_timestamp(Utc::now() + retry_delay(attempts + 1));
+            sqlx::query(
+                r#"
+                UPDATE calendar_retries
+                SET status = 'pending', attempts = ?, last_error = ?, next_attempt_at = ?,
+                    updated_at = datetime('now')
+                WHERE id = ?
+                "#,
+            )
+            .bind(attempts)
+            .bind(&reason)
+            .bind(&next_attempt_at)
+            .bind(&retry.id)
+            .execute(pool)
+            .await?;
+            warn!(
+                retry_id = %retry.id,
+                interview_id = %retry.interview_id,
+                attempts = attempts,
+                next_attempt_at = %next_attempt_at,
+                error = %reason,
+                "Calendar retry failed, will try again"
+            );
+        }
+        Outcome::Failed(reason) => {
+            sqlx::query(
+                "UPDATE calendar_retries SET status = 'failed', attempts = ?, last_error = ?, updated_at = datetime('now') WHERE id = ?",
+            )
+            .bind(attempts)
+            .bind(&reason)
+            .bind(&retry.id)
+            .execute(pool)
+            .await?;
+            error!(
+                retry_id = %retry.id,
+                interview_id = %retry.interview_id,
+                attempts = attempts,
+                error = %reason,
+                "Calendar retry failed permanently; the interview has no Meet link"
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Claim and retry every operation that is due; returns how many were attempted
+pub async fn retry_due_operations(
+    pool: &SqlitePool,
+    calendar: Arc<dyn CalendarClient>,
+    email_sender: &dyn EmailSender,
+) -> Result<usize, sqlx::Error> {
+    let now = retry_timestamp(Utc::now());
+    let due = sqlx::query_as::<_, CalendarRetry>(
+        r#"
+        UPDATE calendar_retries
+        SET status = 'running', updated_at = datetime('now')
+        WHERE id IN (
+            SELECT id FROM calendar_retries
+            WHERE status = 'pending' AND next_attempt_at <= ?
+            ORDER BY next_attempt_at
+            LIMIT ?
+        )
+        RETURNING *
+        "#,
+    )
+    .bind(&now)
+    .bind(RETRY_BATCH_SIZE)
+    .fetch_all(pool)
+    .await?;
+
+    for retry in &due {
+        let outcome = match retry.operation.as_str() {
+            "create_meet_link" => {
+                create_meet_link(pool, calendar.clone(), email_sender, retry).await
+            }
+            other => Outcome::Skipped(format!("Unknown operation '{}'", other)),
+        };
+        record_outcome(pool, retry, outcome).await?;
+    }
+
+    Ok(due.len())
+}
+
+/// Retry due calendar operations every minute
+///
+/// Rows left `running` by a crash are requeued on start; Google may then see the event
+/// twice, which is preferred over leaving the interview without a link.
+pub fn start_calendar_retry_task(
+    pool: SqlitePool,
+    calendar: Arc<dyn CalendarClient>,
+    email_sender: Arc<dyn EmailSender>,
+) {
+    tokio::spawn(async move {
+        if let Err(e) =
+            sqlx::query("UPDATE calendar_retries SET status = 'pending' WHERE status = 'running'")
+                .execute(&pool)
+                .await
+        {
+            warn!(error = %e, "Failed to requeue interrupted calendar retries");
+        }
+
+        loop {
+            if !crate::common::shutdown::sleep(std::time::Duration::from_secs(
+                RETRY_INTERVAL_SECONDS,
+            ))
+            .await
+            {
+                break;
+            }
+            let _running = crate::common::shutdown::track();
+
+            match retry_due_operations(&pool, calendar.clone(), email_sender.as_ref()).await {
+                Ok(0) => {}
+                Ok(count) => debug!(count = count, "Processed due calendar retries"),
+                Err(e) => error!(error = %e, "Calendar retry pass failed"),
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_retry_delay_backs_off() {
+        assert_eq!(retry_delay(1), Duration::minutes(1));
+        assert_eq!(retry_delay(2), Duration::minutes(2));
+        assert_eq!(retry_delay(4), Duration::minutes(8));
+        assert_eq!(retry_delay(20), Duration::minutes(60));
+    }
+}
@@ -0,0 +1,467 @@
+// src/services/chat_webhooks.rs
+//! Recruiting events posted to Slack and Microsoft Teams channels
+//!
+//! Admins point incoming webhooks at a company, covering all its jobs, or at a single job,
+//! and pick which events each channel receives: new applications, interview feedback
+//! being submitted and offers being accepted. Posts go out in the background so the
+//! request that triggered them is never held up; the outcome of the last post is kept on
+//! the webhook for admins to check.
+
+use reqwest::Client;
+use serde_json::{json, Value};
+use sqlx::SqlitePool;
+use tracing::{debug, warn};
+
+use crate::admin::models::ChatWebhook;
+use crate::common::AppState;
+
+pub const CHAT_PROVIDERS: &[&str] = &["slack", "teams"];
+
+/// Events a webhook can be toggled for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChatEvent {
+    NewApplication,
+    InterviewFeedback,
+    OfferAccepted,
+}
+
+impl ChatEvent {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ChatEvent::NewApplication => "new_application",
+            ChatEvent::InterviewFeedback => "interview_feedback",
+            ChatEvent::OfferAccepted => "offer_accepted",
+        }
+    }
+
+    /// The `chat_webhooks` toggle for this event
+    fn column(&self) -> &'static str {
+        match self {
+            ChatEvent::NewApplication => "notify_new_application",
+            ChatEvent::InterviewFeedback => "notify_interview_feedback",
+            ChatEvent::OfferAccepted => "notify_offer_accepted",
+        }
+    }
+}
+
+/// A provider-neutral message, rendered per provider when posted
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChatMessage {
+    pub title: String,
+    pub facts: Vec<(&'static str, String)>,
+    pub link: Option<String>,
+}
+
+/// Check a webhook URL is an HTTPS incoming webhook of the provider
+pub fn validate_webhook(provider: &str, url: &str) -> Result<(), String> {
+    if !CHAT_PROVIDERS.contains(&provider) {
+        return Err(format!(
+            "Invalid provider '{}'; expected one of {}",
+            provider,
+            CHAT_PROVIDERS.join(", ")
+        ));
+    }
+    let host = url
+        .strip_prefix("https://")
+        .and_then(|rest| rest.split(['/', '?', '#']).next())
+        // Power Automate URLs spell out the default port
+        .map(|host| host.strip_suffix(":443").unwrap_or(host))
+        .filter(|host| !host.is_empty() && !host.contains(['@', ':']))
+        .ok_or_else(|| "Webhook URL must be an https:// URL".to_string())?
+        .to_ascii_lowercase();
+
+    let allowed = match provider {
+        "slack" => host == "hooks.slack.com",
+        _ => {
+            host.ends_with(".webhook.office.com")
+                || host.ends_with(".logic.azure.com")
+                || host.ends_with(".powerplatform.com")
+        }
+    };
+    if !allowed {
+        return Err(match provider {
+            "slack" => "Slack webhook URLs start with https://hooks.slack.com/".to_string(),
+            _ => "Teams webhook URLs are on webhook.office.com or a Power Automate workflow"
+                .to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// Keep the host and hide the secret path of a webhook URL
+pub fn mask_webhook_url(url: &str) -> String {
+    let Some(rest) = url.strip_prefix("https://") else {
+        return "********".to_string();
+    };
+    let host = rest.split('/').next().unwrap_or_default();
+    let tail: String = {
+        let chars: Vec<char> = rest.chars().collect();
+        chars[chars.len().saturating_sub(4)..].iter().collect()
+    };
+    format!("https://{}/****{}", host, tail)
+}
+
+/// Slack's mrkdwn treats these three characters as control characters
+fn slack_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// Block Kit payload for a Slack incoming webhook
+pub fn slack_payload(message: &ChatMessage) -> Value {
+    let fields: Vec<Value> = message
+        .facts
+        .iter()
+        .map(|(label, value)| {
+            json!({
+                "type": "mrkdwn",
+                "text": format!("*{}*\n{}", label, slack_escape(value)),
+            })
+        })
+        .collect();
+    let mut blocks = vec![json!({
+        "type": "section",
+        "text": { "type": "mrkdwn", "text": format!("*{}*", slack_escape(&message.title)) },
+    })];
+    if !fields.is_empty() {
+        blocks.push(json!({ "type": "section", "fields": fields }));
+    }
+    if let Some(link) = &message.link {
+        blocks.push(json!({
+            "type": "actions",
+            "elements": [{
+                "type": "button",
+                "text": { "type": "plain_text", "text": "Open" },
+                "url": link,
+            }],
+        }));
+    }
+    json!({ "text": message.title, "blocks": blocks })
+}
+
+/// Adaptive Card payload, accepted by Teams incoming webhooks and Workflows alike
+pub fn teams_payload(message: &ChatMessage) -> Value {
+    let facts: Vec<Value> = message
+        .facts
+        .iter()
+        .map(|(label, value)| json!({ "title": label, "value": value }))
+        .collect();
+    let actions: Vec<Value> = message
+        .link
+        .iter()
+        .map(|link| json!({ "type": "Action.OpenUrl", "title": "Open", "url": link }))
+        .collect();
+    json!({
+        "type": "message",
+        "attachments": [{
+            "contentType": "application/vnd.microsoft.card.adaptive",
+            "content": {
+                "$schema": "http://adaptivecards.io/schemas/adaptive-card.json",
+                "type": "AdaptiveCard",
+                "version": "1.4",
+                "body": [
+                    {
+                        "type": "TextBlock",
+                        "text": message.title,
+                        "weight": "Bolder",
+                        "size": "Medium",
+                        "wrap": true,
+                    },
+                    { "type": "FactSet", "facts": facts },
+                ],
+                "actions": actions,
+            },
+        }],
+    })
+}
+
+/// Post a message to one webhook
+pub async fn post(
+    http: &Client,
+    webhook: &ChatWebhook,
+    message: &ChatMessage,
+) -> Result<(), String> {
+    let payload = match webhook.provider.as_str() {
+        "slack" => slack_payload(message),
+        _ => teams_payload(message),
+    };
+    let response = http
+        .post(&webhook.webhook_url)
+        .json(&payload)
+        .send()
+        .await
+        .map_err(|e| format!("Webhook request failed: {}", e))?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        return Err(format!(
+            "Webhook returned {}: {}",
+            status,
+            body.chars().take(200).collect::<String>()
+        ));
+    }
+    Ok(())
+}
+
+/// Keep the outcome of the latest post on the webhook
+pub async fn record_result(
+    pool: &SqlitePool,
+    webhook_id: &str,
+    result: &Result<(), String>,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        r#"
+        UPDATE chat_webhooks
+        SET last_error = ?2,
+            last_delivered_at = CASE WHEN ?2 IS NULL THEN datetime('now') ELSE last_delivered_at END
+        WHERE id = ?1
+        "#,
+    )
+    .bind(webhook_id)
+    .bind(result.as_ref().err())
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// Active webhooks of a job and its company that want the event
+pub async fn webhooks_for_job(
+    pool: &SqlitePool,
+    job_id: &str,
+    event: ChatEvent,
+) -> Result<Vec<ChatWebhook>, sqlx::Error> {
+    sqlx::query_as::<_, ChatWebhook>(&format!(
+        r#"
+        SELECT * FROM chat_webhooks
+        WHERE is_active = 1 AND {} = 1
+          AND (job_id = ?1 OR company_id = (SELECT company_id FROM jobs WHERE id = ?1))
+        ORDER BY created_at
+        "#,
+        event.column()
+    ))
+    .bind(job_id)
+    .fetch_all(pool)
+    .await
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct EventContext {
+    job_id: String,
+    candidate_name: Option<String>,
+    job_title: Option<String>,
+    company_name: Option<String>,
+    interview_type: Option<String>,
+    submitted_by: Option<String>,
+}
+
+/// Job and message for an event; `reference_id` is the application, or the interview for
+/// feedback
+async fn build_message(
+    pool: &SqlitePool,
+    frontend_url: &str,
+    event: ChatEvent,
+    reference_id: &str,
+) -> Result<Option<(String, ChatMessage)>, sqlx::Error> {
+    let context = match event {
+        ChatEvent::InterviewFeedback => {
+            sqlx::query_as::<_, EventContext>(
+                r#"
+                SELECT i.job_id, u.name AS candidate_name, j.title AS job_title,
+                       COALESCE(c.name, j.company) AS company_name, i.interview_type,
+                       COALESCE(s.name, s.email) AS submitted_by
+                FROM interviews i
+                JOIN jobs j ON j.id = i.job_id
+                LEFT JOIN users u ON u.id = i.candidate_id
+                LEFT JOIN companies c ON c.id = j.company_id
+                LEFT JOIN users s ON s.id = i.shared_notes_updated_by
+                WHERE i.id = ?
+                "#,
+            )
+            .bind(reference_id)
+            .fetch_optional(pool)
+            .await?
+        }
+        ChatEvent::NewApplication | ChatEvent::OfferAccepted => {
+            sqlx::query_as::<_, EventContext>(
+                r#"
+                SELECT a.job_id, u.name AS candidate_name, j.title AS job_title,
+                       COALESCE(c.name, j.company) AS company_name,
+                       NULL AS interview_type, NULL AS submitted_by
+                FROM applications a
+                JOIN jobs j ON j.id = a.job_id
+                LEFT JOIN users u ON u.id = a.user_id
+                LEFT JOIN companies c ON c.id = j.company_id
+                WHERE a.id = ?
+                "#,
+            )
+            .bind(reference_id)
+            .fetch_optional(pool)
+            .await?
+        }
+    };
+    let Some(context) = context else {
+        return Ok(None);
+    };
+
+    let candidate = context
+        .candidate_name
+        .clone()
+        .unwrap_or_else(|| "A candidate".to_string());
+    let job = context
+        .job_title
+        .clone()
+        .unwrap_or_else(|| "a job".to_string());
+    let (title, path) = match event {
+        ChatEvent::NewApplication => (
+            format!("New application from {} for {}", candidate, job),
+            format!("/admin/applications/{}", reference_id),
+        ),
+        ChatEvent::InterviewFeedback => (
+            format!("Interview feedback submitted for {} ({})", candidate, job),
+            format!("/admin/interviews/{}", reference_id),
+        ),
+        ChatEvent::OfferAccepted => (
+            format!("{} accepted the offer for {}", candidate, job),
+            format!("/admin/applications/{}", reference_id),
+        ),
+    };
+
+    let mut facts = vec![("Candidate", candidate), ("Job", job)];
+    if let Some(company) = context.company_name {
+        facts.push(("Company", company));
+    }
+    if let Some(interview_type) = context.interview_type {
+        facts.push(("Interview", interview_type));
+    }
+    if let Some(submitted_by) = context.submitted_by {
+        facts.push(("Submitted by", submitted_by));
+    }
+
+    Ok(Some((
+        context.job_id,
+        ChatMessage {
+            title,
+            facts,
+            link: Some(format!("{}{}", frontend_url, path)),
+        },
+    )))
+}
+
+/// Post an event to every webhook that wants it
+pub async fn notify(
+    pool: &SqlitePool,
+    http: &Client,
+    frontend_url: &str,
+    event: ChatEvent,
+    reference_id: &str,
+) -> Result<usize, sqlx::Error> {
+    let Some((job_id, message)) = build_message(pool, frontend_url, event, reference_id).await?
+    else {
+        return Ok(0);
+    };
+    let webhooks = webhooks_for_job(pool, &job_id, event).await?;
+
+    let mut delivered = 0;
+    for webhook in &webhooks {
+        let result = post(http, webhook, &message).await;
+        match &result {
+            Ok(()) => delivered += 1,
+            Err(e) => warn!(
+                error = %e,
+                webhook_id = %webhook.id,
+                event = event.as_str(),
+                "Chat webhook post failed"
+            ),
+        }
+        record_result(pool, &webhook.id, &result).await?;
+    }
+    Ok(delivered)
+}
+
+/// Post an event in the background
+pub fn dispatch(state: &AppState, event: ChatEvent, reference_id: &str) {
+    let pool = state.db.clone();
+    let http = state.http.clone();
+    let frontend_url = state.config.frontend_url.clone();
+    let reference_id = reference_id.to_string();
+    tokio::spawn(async move {
+        if let Err(e) = notify(&pool, &http, &frontend_url, event, &reference_id).await {
+            debug!(
+                error = %e,
+                event = event.as_str(),
+                reference_id = %reference_id,
+                "Skipped chat webhook notification"
+            );
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message() -> ChatMessage {
+        ChatMessage {
+            title: "New application from Ada <ada> for Engineer".to_string(),
+            facts: vec![("Candidate", "Ada & co".to_string())],
+            link: Some("https://app.example.com/admin/applications/A_1".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_validate_webhook() {
+        assert!(validate_webhook("slack", "https://hooks.slack.com/services/T0/B0/xyz").is_ok());
+        assert!(validate_webhook("slack", "http://hooks.slack.com/services/T0/B0/xyz").is_err());
+        assert!(validate_webhook("slack", "https://hooks.slack.com.evil.io/services").is_err());
+        assert!(validate_webhook(
+            "teams",
+            "https://contoso.webhook.office.com/webhookb2/abc/IncomingWebhook/def"
+        )
+        .is_ok());
+        assert!(validate_webhook(
+            "teams",
+            "https://prod-01.westus.logic.azure.com:443/workflows/abc"
+        )
+        .is_ok());
+        assert!(validate_webhook("teams", "https://evil.io:8443/x.webhook.office.com").is_err());
+        assert!(validate_webhook("teams", "https://hooks.slack.com/services/x").is_err());
+        assert!(validate_webhook("discord", "https://discord.com/api/webhooks/1").is_err());
+    }
+
+    #[test]
+    fn test_mask_webhook_url() {
+        assert_eq!(
+            mask_webhook_url("https://hooks.slack.com/services/T0/B0/secretXYZ1"),
+            "https://hooks.slack.com/****XYZ1"
+        );
+        assert_eq!(mask_webhook_url("not a url"), "********");
+    }
+
+    #[test]
+    fn test_slack_payload_escapes_text() {
+        let payload = slack_payload(&message());
+        assert_eq!(
+            payload["blocks"][0]["text"]["text"],
+            "*New application from Ada &lt;ada&gt; for Engineer*"
+        );
+        assert_eq!(
+            payload["blocks"][1]["fields"][0]["text"],
+            "*Candidate*\nAda &amp; co"
+        );
+        assert_eq!(
+            payload["blocks"][2]["elements"][0]["url"],
+            "https://app.example.com/admin/applications/A_1"
+        );
+    }
+
+    #[test]
+    fn test_teams_payload() {
+        let payload = teams_payload(&message());
+        let card = &payload["attachments"][0]["content"];
+        assert_eq!(card["type"], "AdaptiveCard");
+        assert_eq!(card["body"][1]["facts"][0]["value"], "Ada & co");
+        assert_eq!(card["actions"][0]["type"], "Action.OpenUrl");
+    }
+}
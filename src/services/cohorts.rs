@@ -0,0 +1,319 @@
+// src/services/cohorts.rs
+//! Source cohort analytics
+//!
+//! Applications are grouped by the month they were submitted in and their `source` tag, and
+//! each cohort's progress through the funnel is followed week by week from the date of each
+//! application. This separates channels that bring many applicants from channels that bring
+//! applicants who get hired. Progress comes from `application_status_history`; a stage is
+//! reached by the first status at or beyond it, so a candidate who skips straight to an
+//! interview still counts as reviewed.
+
+use chrono::{Months, NaiveDate, NaiveDateTime};
+use serde::Serialize;
+use sqlx::SqlitePool;
+use std::collections::BTreeMap;
+
+use crate::common::ApiError;
+use crate::services::analytics_facts::{self, ApplicationFact};
+use crate::services::hiring_metrics::StatusChange;
+use crate::services::sla::parse_timestamp;
+
+/// Funnel stages in order, with the statuses that mean an application reached each one
+pub const FUNNEL_STAGES: &[(&str, &[&str])] = &[
+    (
+        "reviewed",
+        &[
+            "reviewed",
+            "shortlisted",
+            "interview_scheduled",
+            "interviewed",
+            "offered",
+            "hired",
+        ],
+    ),
+    (
+        "interview",
+        &["interview_scheduled", "interviewed", "offered", "hired"],
+    ),
+    ("offered", &["offered", "hired"]),
+    ("hired", &["hired"]),
+];
+
+/// Applications without a source tag came to the site directly
+pub const DIRECT_SOURCE: &str = "direct";
+
+/// When an application first reached each of [`FUNNEL_STAGES`], from its status history
+pub fn stage_reached(changes: &[StatusChange]) -> Vec<Option<NaiveDateTime>> {
+    FUNNEL_STAGES
+        .iter()
+        .map(|(_, statuses)| {
+            changes
+                .iter()
+                .filter(|c| statuses.contains(&c.status.as_str()))
+                .filter_map(|c| c.changed_at.as_deref().and_then(parse_timestamp))
+                .min()
+        })
+        .collect()
+}
+
+/// How a cohort progressed to one funnel stage
+#[derive(Debug, Serialize, PartialEq)]
+pub struct StageProgress {
+    pub stage: &'static str,
+    /// Applications that have reached the stage so far
+    pub reached: i64,
+    pub rate: f64,
+    /// Applications that had reached the stage by the end of each week after applying,
+    /// cumulative; `null` for weeks the whole cohort hasn't lived through yet
+    pub by_week: Vec<Option<i64>>,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+pub struct SourceCohort {
+    /// `YYYY-MM` the applications were submitted in
+    pub month: String,
+    pub source: String,
+    pub applications: i64,
+    pub stages: Vec<StageProgress>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CohortReport {
+    /// When the analytics facts these numbers come from were last refreshed
+    pub as_of: Option<String>,
+    pub from: String,
+    pub to: String,
+    pub weeks: usize,
+    pub cohorts: Vec<SourceCohort>,
+}
+
+fn round4(value: f64) -> f64 {
+    (value * 10_000.0).round() / 10_000.0
+}
+
+pub fn normalize_source(source: Option<&str>) -> String {
+    source
+        .map(|s| s.trim().to_lowercase())
+        .filter(|s| !s.is_empty())
+        .unwrap_or_else(|| DIRECT_SOURCE.to_string())
+}
+
+/// Group applications into month/source cohorts and follow each for `weeks` weeks
+pub fn build_cohorts(
+    facts: &[ApplicationFact],
+    weeks: usize,
+    now: NaiveDateTime,
+) -> Vec<SourceCohort> {
+    struct Tally {
+        applications: i64,
+        newest: NaiveDateTime,
+        reached: Vec<i64>,
+        by_week: Vec<Vec<i64>>,
+    }
+
+    let mut cohorts: BTreeMap<(String, String), Tally> = BTreeMap::new();
+    for fact in facts {
+        let Some(applied_at) = parse_timestamp(&fact.applied_at) else {
+            continue;
+        };
+        let key = (applied_at.format("%Y-%m").to_string(), fact.source.clone());
+        let tally = cohorts.entry(key).or_insert_with(|| Tally {
+            applications: 0,
+            newest: applied_at,
+            reached: vec![0; FUNNEL_STAGES.len()],
+            by_week: vec![vec![0; weeks]; FUNNEL_STAGES.len()],
+        });
+        tally.applications += 1;
+        tally.newest = tally.newest.max(applied_at);
+
+        // In the order of FUNNEL_STAGES
+        let reached = [
+            &fact.reviewed_at,
+            &fact.interview_at,
+            &fact.offered_at,
+            &fact.hired_at,
+        ];
+        for (index, reached_at) in reached.iter().enumerate() {
+            let Some(reached_at) = reached_at.as_deref().and_then(parse_timestamp) else {
+                continue;
+            };
+            tally.reached[index] += 1;
+            let week = ((reached_at - applied_at).num_days().max(0) / 7) as usize;
+            for count in tally.by_week[index].iter_mut().skip(week) {
+                *count += 1;
+            }
+        }
+    }
+
+    cohorts
+        .into_iter()
+        .map(|((month, source), tally)| {
+            // Week n is complete once even the newest application is n + 1 weeks old
+            let observed_weeks = ((now - tally.newest).num_days().max(0) / 7) as usize;
+            let stages = FUNNEL_STAGES
+                .iter()
+                .enumerate()
+                .map(|(index, (stage, _))| StageProgress {
+                    stage,
+                    reached: tally.reached[index],
+                    rate: round4(tally.reached[index] as f64 / tally.applications as f64),
+                    by_week: tally.by_week[index]
+                        .iter()
+                        .enumerate()
+                        .map(|(week, count)| (week < observed_weeks).then_some(*count))
+                        .collect(),
+                })
+                .collect();
+            SourceCohort {
+                month,
+                source,
+                applications: tally.applications,
+                stages,
+            }
+        })
+        .collect()
+}
+
+/// Cohorts for applications submitted from the start of `from` to the end of `to` (both
+/// `YYYY-MM`), optionally for one job or one source. Reads the analytics facts, which the
+/// caller makes sure exist with [`analytics_facts::ensure_built`] on the primary pool.
+pub async fn source_cohorts(
+    pool: &SqlitePool,
+    from: &str,
+    to: &str,
+    weeks: usize,
+    job_id: Option<&str>,
+    source: Option<&str>,
+) -> Result<CohortReport, sqlx::Error> {
+    // Stored sources are normalized, so `direct` matches untagged applications
+    let source = source.map(|s| normalize_source(Some(s)));
+    let facts = sqlx::query_as::<_, ApplicationFact>(
+        r#"
+        SELECT * FROM analytics_application_facts
+        WHERE strftime('%Y-%m', applied_at) BETWEEN ? AND ?
+          AND (? IS NULL OR job_id = ?)
+          AND (? IS NULL OR source = ?)
+        "#,
+    )
+    .bind(from)
+    .bind(to)
+    .bind(job_id)
+    .bind(job_id)
+    .bind(&source)
+    .bind(&source)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(CohortReport {
+        as_of: analytics_facts::as_of(pool).await?,
+        from: from.to_string(),
+        to: to.to_string(),
+        weeks,
+        cohorts: build_cohorts(&facts, weeks, chrono::Utc::now().naive_utc()),
+    })
+}
+
+/// A `YYYY-MM` query bound, normalized; None when absent
+pub fn parse_month(value: Option<&str>, field: &str) -> Result<Option<String>, ApiError> {
+    match value {
+        Some(v) => NaiveDate::parse_from_str(&format!("{}-01", v), "%Y-%m-%d")
+            .map(|d| Some(d.format("%Y-%m").to_string()))
+            .map_err(|_| {
+                ApiError::ValidationError(format!("{} must be a month in YYYY-MM format", field))
+            }),
+        None => Ok(None),
+    }
+}
+
+/// `YYYY-MM` of the month `months` before the one containing `at`
+pub fn months_before(at: NaiveDateTime, months: u32) -> String {
+    let date = at.date();
+    date.checked_sub_months(Months::new(months))
+        .unwrap_or(date)
+        .format("%Y-%m")
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(value: &str) -> NaiveDateTime {
+        parse_timestamp(value).unwrap()
+    }
+
+    fn fact(
+        id: &str,
+        source: Option<&str>,
+        applied_at: &str,
+        changes: &[(&str, &str)],
+    ) -> ApplicationFact {
+        let changes: Vec<StatusChange> = changes
+            .iter()
+            .map(|(status, changed_at)| StatusChange {
+                application_id: id.to_string(),
+                status: status.to_string(),
+                changed_at: Some(changed_at.to_string()),
+            })
+            .collect();
+        analytics_facts::compute_fact(id, "J_1", source, applied_at, &changes, 0).0
+    }
+
+    #[test]
+    fn test_cohorts_follow_progress_by_week() {
+        let facts = vec![
+            fact(
+                "A_1",
+                Some("LinkedIn"),
+                "2026-03-02 09:00:00",
+                &[
+                    ("submitted", "2026-03-02 09:00:00"),
+                    // Skipping review still counts as reviewed
+                    ("interview_scheduled", "2026-03-04 09:00:00"),
+                    ("hired", "2026-03-20 09:00:00"),
+                ],
+            ),
+            fact(
+                "A_2",
+                Some("linkedin"),
+                "2026-03-05 09:00:00",
+                &[
+                    ("reviewed", "2026-03-15 09:00:00"),
+                    ("rejected", "2026-03-16 09:00:00"),
+                ],
+            ),
+            fact("A_3", None, "2026-03-10 09:00:00", &[]),
+        ];
+
+        let cohorts = build_cohorts(&facts, 4, at("2026-03-30 09:00:00"));
+        assert_eq!(cohorts.len(), 2);
+
+        let direct = &cohorts[0];
+        assert_eq!(
+            (direct.month.as_str(), direct.source.as_str()),
+            ("2026-03", "direct")
+        );
+        assert_eq!(direct.stages[0].reached, 0);
+
+        let linkedin = &cohorts[1];
+        assert_eq!(linkedin.source, "linkedin");
+        assert_eq!(linkedin.applications, 2);
+        let reviewed = &linkedin.stages[0];
+        assert_eq!(reviewed.reached, 2);
+        assert_eq!(reviewed.rate, 1.0);
+        // A_2 was reviewed 10 days in; week 3 hasn't passed for A_2 yet
+        assert_eq!(reviewed.by_week, vec![Some(1), Some(2), Some(2), None]);
+        let hired = &linkedin.stages[3];
+        assert_eq!(hired.reached, 1);
+        assert_eq!(hired.rate, 0.5);
+        assert_eq!(hired.by_week, vec![Some(0), Some(0), Some(1), None]);
+    }
+
+    #[test]
+    fn test_month_helpers() {
+        assert_eq!(parse_month(Some("2026-02"), "from").unwrap().as_deref(), Some("2026-02"));
+        assert!(parse_month(Some("2026-13"), "from").is_err());
+        assert_eq!(parse_month(None, "from").unwrap(), None);
+        assert_eq!(months_before(at("2026-03-31 10:00:00"), 5), "2025-10");
+    }
+}
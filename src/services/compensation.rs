@@ -0,0 +1,294 @@
+// src/services/compensation.rs
+//! Salary bands and offer validation against them
+
+use sqlx::SqlitePool;
+use tracing::{error, info, warn};
+
+use crate::admin::models::{CompensationBand, OfferSalaryCheck, ValidateOfferSalaryRequest};
+use crate::common::{generate_compensation_override_id, ApiError};
+use crate::services::pii;
+
+/// Levels a band can be defined for; matches the job `experience_level` values
+pub const BAND_LEVELS: &[&str] = &["entry", "mid", "senior", "lead", "executive"];
+
+/// Shortest override justification accepted, so "ok" doesn't pass as a reason
+const MIN_JUSTIFICATION_CHARS: usize = 20;
+
+/// Where a salary falls relative to a band
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BandPosition {
+    Below,
+    Within,
+    Above,
+}
+
+pub fn band_position(band: &CompensationBand, salary: f64) -> BandPosition {
+    if salary > band.max_salary {
+        BandPosition::Above
+    } else if salary < band.min_salary {
+        BandPosition::Below
+    } else {
+        BandPosition::Within
+    }
+}
+
+/// Check a band's level and range before it is stored
+pub fn validate_band(level: &str, min_salary: f64, max_salary: f64) -> Result<(), ApiError> {
+    if !BAND_LEVELS.contains(&level) {
+        return Err(ApiError::ValidationError(format!(
+            "Invalid level '{}'; expected one of {}",
+            level,
+            BAND_LEVELS.join(", ")
+        )));
+    }
+    if !min_salary.is_finite() || !max_salary.is_finite() || min_salary < 0.0 {
+        return Err(ApiError::ValidationError(
+            "Salaries must be non-negative numbers".to_string(),
+        ));
+    }
+    if min_salary > max_salary {
+        return Err(ApiError::ValidationError(
+            "min_salary cannot be greater than max_salary".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// The band for a job: the company's own band for the job's level, else the default band
+pub async fn find_band_for_job(
+    pool: &SqlitePool,
+    job_id: &str,
+) -> Result<Option<CompensationBand>, ApiError> {
+    let job: Option<(Option<String>, Option<String>)> =
+        sqlx::query_as("SELECT company_id, experience_level FROM jobs WHERE id = ?")
+            .bind(job_id)
+            .fetch_optional(pool)
+            .await
+            .map_err(ApiError::DatabaseError)?;
+
+    let (company_id, level) = match job {
+        Some(job) => job,
+        None => return Err(ApiError::NotFound(format!("Job not found: {}", job_id))),
+    };
+    let level = match level {
+        Some(level) => level,
+        None => return Ok(None),
+    };
+
+    sqlx::query_as::<_, CompensationBand>(
+        r#"
+        SELECT * FROM compensation_bands
+        WHERE level = ? AND (company_id = ? OR company_id IS NULL)
+        ORDER BY company_id IS NULL ASC
+        LIMIT 1
+        "#,
+    )
+    .bind(&level)
+    .bind(&company_id)
+    .fetch_optional(pool)
+    .await
+    .map_err(ApiError::DatabaseError)
+}
+
+/// Validate an offer salary against the job's band.
+///
+/// Salaries above the band are rejected unless a justification is given, in which case the
+/// override is written to `compensation_overrides` for audit.
+pub async fn check_offer_salary(
+    pool: &SqlitePool,
+    request: &ValidateOfferSalaryRequest,
+    actor_id: &str,
+) -> Result<OfferSalaryCheck, ApiError> {
+    if !request.salary.is_finite() || request.salary <= 0.0 {
+        return Err(ApiError::ValidationError(
+            "Salary must be a positive number".to_string(),
+        ));
+    }
+
+    let band = match find_band_for_job(pool, &request.job_id).await? {
+        Some(band) => band,
+        None => {
+            return Ok(OfferSalaryCheck {
+                band: None,
+                within_band: true,
+                below_band: false,
+                above_band: false,
+                override_id: None,
+            })
+        }
+    };
+
+    let position = band_position(&band, request.salary);
+    let mut override_id = None;
+
+    if position == BandPosition::Above {
+        let justification = request
+            .override_justification
+            .as_deref()
+            .map(str::trim)
+            .filter(|j| j.chars().count() >= MIN_JUSTIFICATION_CHARS)
+            .ok_or_else(|| {
+                warn!(
+                    job_id = %request.job_id,
+                    salary = request.salary,
+                    band_max = band.max_salary,
+                    "Offer salary above band without justification"
+                );
+                ApiError::ValidationError(format!(
+                    "Salary {:.0} exceeds the {} band maximum of {:.0} {}; provide an override_justification of at least {} characters",
+                    request.salary, band.level, band.max_salary, band.currency, MIN_JUSTIFICATION_CHARS
+                ))
+            })?;
+
+        let id = generate_compensation_override_id();
+        sqlx::query(
+            r#"
+            INSERT INTO compensation_overrides (
+                id, band_id, job_id, candidate_id, salary, band_min, band_max,
+                justification, approved_by, created_at
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, datetime('now'))
+            "#,
+        )
+        .bind(&id)
+        .bind(&band.id)
+        .bind(&request.job_id)
+        .bind(&request.candidate_id)
+        .bind(pii::seal_number(request.salary)?)
+        .bind(band.min_salary)
+        .bind(band.max_salary)
+        .bind(justification)
+        .bind(actor_id)
+        .execute(pool)
+        .await
+        .map_err(|e| {
+            error!(error = %e, job_id = %request.job_id, "Database error recording compensation override");
+            ApiError::DatabaseError(e)
+        })?;
+
+        info!(
+            override_id = %id,
+            job_id = %request.job_id,
+            candidate_id = ?request.candidate_id,
+            salary = request.salary,
+            band_max = band.max_salary,
+            approved_by = %actor_id,
+            "Above-band offer approved with override"
+        );
+        override_id = Some(id);
+    }
+
+    Ok(OfferSalaryCheck {
+        within_band: position == BandPosition::Within,
+        below_band: position == BandPosition::Below,
+        above_band: position == BandPosition::Above,
+        band: Some(band),
+        override_id,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn setup_test_db() -> SqlitePool {
+        let pool = SqlitePool::connect(":memory:").await.unwrap();
+
+        for statement in [
+            "CREATE TABLE jobs (id TEXT PRIMARY KEY, company_id TEXT, experience_level TEXT)",
+            r#"CREATE TABLE compensation_bands (
+                id TEXT PRIMARY KEY, company_id TEXT, level TEXT NOT NULL, currency TEXT NOT NULL,
+                min_salary REAL NOT NULL, max_salary REAL NOT NULL, created_by TEXT,
+                created_at TEXT, updated_at TEXT
+            )"#,
+            r#"CREATE TABLE compensation_overrides (
+                id TEXT PRIMARY KEY, band_id TEXT, job_id TEXT NOT NULL, candidate_id TEXT,
+                salary REAL NOT NULL, band_min REAL NOT NULL, band_max REAL NOT NULL,
+                justification TEXT NOT NULL, approved_by TEXT NOT NULL, created_at TEXT
+            )"#,
+            "INSERT INTO jobs VALUES ('acme-job', 'acme', 'senior'), ('other-job', 'other', 'senior'), ('no-level', 'acme', NULL)",
+            "INSERT INTO compensation_bands (id, company_id, level, currency, min_salary, max_salary) VALUES ('default', NULL, 'senior', 'USD', 100000, 150000)",
+            "INSERT INTO compensation_bands (id, company_id, level, currency, min_salary, max_salary) VALUES ('acme', 'acme', 'senior', 'USD', 120000, 180000)",
+        ] {
+            sqlx::query(statement).execute(&pool).await.unwrap();
+        }
+
+        pool
+    }
+
+    fn offer(job_id: &str, salary: f64, justification: Option<&str>) -> ValidateOfferSalaryRequest {
+        ValidateOfferSalaryRequest {
+            job_id: job_id.to_string(),
+            candidate_id: Some("cand".to_string()),
+            salary,
+            override_justification: justification.map(str::to_string),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_company_band_takes_precedence_over_default() {
+        let pool = setup_test_db().await;
+
+        let acme = find_band_for_job(&pool, "acme-job").await.unwrap().unwrap();
+        assert_eq!(acme.id, "acme");
+        let other = find_band_for_job(&pool, "other-job")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(other.id, "default");
+        assert!(find_band_for_job(&pool, "no-level")
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_above_band_offer_requires_justification_and_is_audited() {
+        let pool = setup_test_db().await;
+
+        let check = check_offer_salary(&pool, &offer("acme-job", 130000.0, None), "admin")
+            .await
+            .unwrap();
+        assert!(check.within_band);
+        assert!(check.override_id.is_none());
+
+        assert!(check_offer_salary(
+            &pool,
+            &offer("other-job", 160000.0, Some("worth it")),
+            "admin"
+        )
+        .await
+        .is_err());
+
+        let check = check_offer_salary(
+            &pool,
+            &offer(
+                "other-job",
+                160000.0,
+                Some("Competing offer from another company at 165k"),
+            ),
+            "admin",
+        )
+        .await
+        .unwrap();
+        assert!(check.above_band);
+        let override_id = check.override_id.unwrap();
+
+        let (approved_by, band_max): (String, f64) =
+            sqlx::query_as("SELECT approved_by, band_max FROM compensation_overrides WHERE id = ?")
+                .bind(&override_id)
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!(approved_by, "admin");
+        assert_eq!(band_max, 150000.0);
+    }
+
+    #[test]
+    fn test_validate_band() {
+        assert!(validate_band("senior", 100.0, 200.0).is_ok());
+        assert!(validate_band("principal", 100.0, 200.0).is_err());
+        assert!(validate_band("senior", 300.0, 200.0).is_err());
+        assert!(validate_band("senior", -1.0, 200.0).is_err());
+    }
+}
@@ -0,0 +1,193 @@
+// src/services/consent.rs
+//! Privacy policy and data processing consent
+//!
+//! Every choice is appended to `consents`; a user's latest row per type is their current
+//! consent. When `privacy_policy_version` is set, candidates must have accepted that exact
+//! version, plus data processing, before they can apply.
+
+use sqlx::SqlitePool;
+
+use crate::auth::models::{ConsentInput, ConsentRecord, ConsentStatus};
+use crate::common::{generate_history_id, ApiError, ErrorCode};
+use crate::services::SettingsService;
+
+/// How many past consent changes are returned with a status
+const CONSENT_HISTORY_LIMIT: i64 = 50;
+
+/// The privacy policy version candidates must accept, if one is configured
+pub async fn current_policy_version(settings_service: &SettingsService) -> Option<String> {
+    settings_service
+        .get_setting("privacy_policy_version")
+        .await
+        .ok()
+        .flatten()
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+}
+
+/// Whether a user's accepted consents fall short of the current policy
+pub fn needs_reconsent(
+    current_version: Option<&str>,
+    accepted_version: Option<&str>,
+    data_processing: bool,
+) -> bool {
+    match current_version {
+        Some(current) => accepted_version != Some(current) || !data_processing,
+        None => false,
+    }
+}
+
+/// Check a consent submission and resolve the policy version it accepts
+///
+/// With `required`, the privacy policy and data processing must both be accepted.
+pub fn validate_consent(
+    input: &ConsentInput,
+    current_version: Option<&str>,
+    required: bool,
+) -> Result<Option<String>, ApiError> {
+    let version = input
+        .policy_version
+        .as_deref()
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .or(current_version)
+        .map(str::to_string);
+
+    if input.privacy_policy {
+        if let (Some(current), Some(version)) = (current_version, version.as_deref()) {
+            if version != current {
+                return Err(ApiError::Coded(
+                    ErrorCode::ConsentRequired,
+                    format!(
+                        "Privacy policy version {} is out of date; the current version is {}",
+                        version, current
+                    ),
+                ));
+            }
+        }
+    }
+    if required && !(input.privacy_policy && input.data_processing) {
+        return Err(ApiError::Coded(
+            ErrorCode::ConsentRequired,
+            "The privacy policy and data processing must be accepted".to_string(),
+        ));
+    }
+    Ok(version)
+}
+
+/// Append a consent submission to a user's history
+///
+/// The privacy policy is only recorded when accepted; declining it is not a state a user can
+/// be in once they have an account.
+pub async fn record_consent(
+    pool: &SqlitePool,
+    user_id: &str,
+    input: &ConsentInput,
+    policy_version: Option<&str>,
+    context: &str,
+    application_id: Option<&str>,
+) -> Result<(), sqlx::Error> {
+    let mut choices = vec![
+        ("data_processing", input.data_processing, None),
+        ("marketing", input.marketing, None),
+    ];
+    if input.privacy_policy {
+        choices.insert(0, ("privacy_policy", true, policy_version));
+    }
+
+    for (consent_type, granted, version) in choices {
+        sqlx::query(
+            r#"
+            INSERT INTO consents (id, user_id, consent_type, granted, policy_version, context, application_id)
+            VALUES (?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(generate_history_id())
+        .bind(user_id)
+        .bind(consent_type)
+        .bind(granted)
+        .bind(version)
+        .bind(context)
+        .bind(application_id)
+        .execute(pool)
+        .await?;
+    }
+    Ok(())
+}
+
+/// A user's current consents
+pub async fn consent_status(
+    pool: &SqlitePool,
+    user_id: &str,
+    current_version: Option<String>,
+) -> Result<ConsentStatus, sqlx::Error> {
+    let history = sqlx::query_as::<_, ConsentRecord>(
+        "SELECT * FROM consents WHERE user_id = ? ORDER BY created_at DESC, rowid DESC LIMIT ?",
+    )
+    .bind(user_id)
+    .bind(CONSENT_HISTORY_LIMIT)
+    .fetch_all(pool)
+    .await?;
+
+    let latest = |consent_type: &str| history.iter().find(|r| r.consent_type == consent_type);
+    let privacy_policy = latest("privacy_policy").filter(|r| r.granted);
+    let data_processing = latest("data_processing").is_some_and(|r| r.granted);
+    let marketing = latest("marketing").is_some_and(|r| r.granted);
+    let accepted_policy_version = privacy_policy.and_then(|r| r.policy_version.clone());
+
+    Ok(ConsentStatus {
+        reconsent_required: needs_reconsent(
+            current_version.as_deref(),
+            accepted_policy_version.as_deref(),
+            data_processing,
+        ),
+        privacy_policy_accepted_at: privacy_policy.and_then(|r| r.created_at.clone()),
+        current_policy_version: current_version,
+        accepted_policy_version,
+        data_processing,
+        marketing,
+        history,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn input(privacy_policy: bool, data_processing: bool, version: Option<&str>) -> ConsentInput {
+        ConsentInput {
+            privacy_policy,
+            data_processing,
+            marketing: false,
+            policy_version: version.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_needs_reconsent() {
+        assert!(!needs_reconsent(None, None, false));
+        assert!(needs_reconsent(Some("2"), None, true));
+        assert!(needs_reconsent(Some("2"), Some("1"), true));
+        assert!(needs_reconsent(Some("2"), Some("2"), false));
+        assert!(!needs_reconsent(Some("2"), Some("2"), true));
+    }
+
+    #[test]
+    fn test_validate_consent_defaults_to_current_version() {
+        let version = validate_consent(&input(true, true, None), Some("2024-05"), true).unwrap();
+        assert_eq!(version.as_deref(), Some("2024-05"));
+    }
+
+    #[test]
+    fn test_validate_consent_rejects_stale_version() {
+        assert!(validate_consent(&input(true, true, Some("1")), Some("2"), false).is_err());
+        assert!(validate_consent(&input(true, true, Some("1")), None, false).is_ok());
+    }
+
+    #[test]
+    fn test_required_consent_needs_policy_and_processing() {
+        assert!(validate_consent(&input(true, false, None), Some("2"), true).is_err());
+        assert!(validate_consent(&input(false, true, None), Some("2"), true).is_err());
+        assert!(validate_consent(&input(false, false, None), Some("2"), false).is_ok());
+    }
+}
@@ -0,0 +1,266 @@
+// src/services/conversation_retention.rs
+//! Conversation export for compliance requests, and archiving of finished conversations
+//!
+//! A conversation is archived once every application the candidate made is closed (hired,
+//! rejected, withdrawn or archived) and neither the applications nor the conversation have
+//! changed for `conversation_retention_days` days. Archiving closes it in the inbox and hides it
+//! from the live list; nothing is deleted, and a new candidate message brings it back.
+
+use std::sync::Arc;
+
+use chrono::Utc;
+use sqlx::SqlitePool;
+use tracing::{debug, info};
+
+use crate::common::ApiError;
+use crate::messages::models::{ConversationExport, ConversationMessage, ExportedAttachment};
+use crate::services::SettingsService;
+
+/// Recorded as the actor when the retention policy archives a conversation
+pub const RETENTION_ACTOR: &str = "retention";
+
+const RETENTION_CHECK_INTERVAL_SECS: u64 = 60 * 60;
+
+/// Everything a candidate and the hiring team said to each other, with an attachment manifest
+pub async fn build_export(
+    pool: &SqlitePool,
+    user_id: &str,
+    exported_by: &str,
+) -> Result<ConversationExport, ApiError> {
+    let (candidate_name, candidate_email): (Option<String>, String) =
+        sqlx::query_as("SELECT name, email FROM users WHERE id = ?")
+            .bind(user_id)
+            .fetch_optional(pool)
+            .await
+            .map_err(ApiError::DatabaseError)?
+            .ok_or_else(|| ApiError::NotFound("User not found".to_string()))?;
+
+    let messages = sqlx::query_as::<_, ConversationMessage>(
+        r#"
+        SELECT id, user_id, sender, message, is_read, created_at
+        FROM conversation_messages
+        WHERE user_id = ?
+        ORDER BY datetime(created_at) ASC, rowid ASC
+        "#,
+    )
+    .bind(user_id)
+    .fetch_all(pool)
+    .await
+    .map_err(ApiError::DatabaseError)?;
+
+    let attachments = sqlx::query_as::<_, ExportedAttachment>(
+        r#"
+        SELECT a.id, a.message_id, a.original_filename, a.mime_type, a.file_size, a.created_at,
+               '/api/attachments/' || a.filename AS url
+        FROM message_attachments a
+        INNER JOIN conversation_messages m ON m.id = a.message_id
+        WHERE m.user_id = ?
+        ORDER BY datetime(a.created_at) ASC
+        "#,
+    )
+    .bind(user_id)
+    .fetch_all(pool)
+    .await
+    .map_err(ApiError::DatabaseError)?;
+
+    let archived_at = sqlx::query_scalar::<_, Option<String>>(
+        "SELECT archived_at FROM conversation_states WHERE user_id = ?",
+    )
+    .bind(user_id)
+    .fetch_optional(pool)
+    .await
+    .map_err(ApiError::DatabaseError)?
+    .flatten();
+
+    Ok(ConversationExport {
+        conversation_id: user_id.to_string(),
+        candidate_name,
+        candidate_email,
+        exported_at: Utc::now().to_rfc3339(),
+        exported_by: exported_by.to_string(),
+        archived_at,
+        messages,
+        attachments,
+    })
+}
+
+/// The export as transcript lines for a PDF, followed by the attachment manifest
+pub fn transcript_paragraphs(export: &ConversationExport) -> Vec<String> {
+    let candidate = export
+        .candidate_name
+        .clone()
+        .filter(|n| !n.trim().is_empty())
+        .unwrap_or_else(|| export.candidate_email.clone());
+
+    let mut paragraphs = vec![
+        format!("Candidate: {} <{}>", candidate, export.candidate_email),
+        format!("Exported {} by {}", export.exported_at, export.exported_by),
+    ];
+    if let Some(archived_at) = &export.archived_at {
+        paragraphs.push(format!("Archived {}", archived_at));
+    }
+    paragraphs.push(String::new());
+
+    if export.messages.is_empty() {
+        paragraphs.push("No messages.".to_string());
+    }
+    for message in &export.messages {
+        let sender = if message.sender == "admin" {
+            "Hiring team"
+        } else {
+            candidate.as_str()
+        };
+        paragraphs.push(format!(
+            "[{}] {}: {}",
+            message.created_at.as_deref().unwrap_or("unknown time"),
+            sender,
+            message.message
+        ));
+    }
+
+    if !export.attachments.is_empty() {
+        paragraphs.push(String::new());
+        paragraphs.push(format!("Attachments ({})", export.attachments.len()));
+        for attachment in &export.attachments {
+            paragraphs.push(format!(
+                "- {} ({}, {} bytes) in message {}",
+                attachment.original_filename,
+                attachment.mime_type,
+                attachment.file_size,
+                attachment.message_id
+            ));
+        }
+    }
+    paragraphs
+}
+
+/// Archive conversations whose candidate has no open application and that have been quiet
+/// for `days` days. Returns how many were archived.
+pub async fn archive_closed_conversations(
+    pool: &SqlitePool,
+    days: i64,
+) -> Result<u64, sqlx::Error> {
+    let cutoff = format!("-{} days", days);
+    let result = sqlx::query(
+        r#"
+        INSERT INTO conversation_states (user_id, state, archived_at, updated_by)
+        SELECT u.id, 'closed', datetime('now'), ?
+        FROM users u
+        WHERE EXISTS (SELECT 1 FROM conversation_messages WHERE user_id = u.id)
+          AND EXISTS (SELECT 1 FROM applications WHERE user_id = u.id)
+          AND NOT EXISTS (
+              SELECT 1 FROM applications
+              WHERE user_id = u.id AND archived_at IS NULL
+                AND status NOT IN ('hired', 'rejected', 'withdrawn')
+          )
+          AND NOT EXISTS (
+              SELECT 1 FROM applications
+              WHERE user_id = u.id
+                AND datetime(COALESCE(archived_at, updated_at, applied_at)) > datetime('now', ?)
+          )
+          AND NOT EXISTS (
+              SELECT 1 FROM conversation_messages
+              WHERE user_id = u.id AND datetime(created_at) > datetime('now', ?)
+          )
+          AND NOT EXISTS (
+              SELECT 1 FROM conversation_states
+              WHERE user_id = u.id
+                AND (archived_at IS NOT NULL OR datetime(updated_at) > datetime('now', ?))
+          )
+        ON CONFLICT(user_id) DO UPDATE SET
+            state = 'closed',
+            snoozed_until = NULL,
+            archived_at = excluded.archived_at,
+            updated_by = excluded.updated_by,
+            updated_at = datetime('now')
+        "#,
+    )
+    .bind(RETENTION_ACTOR)
+    .bind(&cutoff)
+    .bind(&cutoff)
+    .bind(&cutoff)
+    .execute(pool)
+    .await?;
+    Ok(result.rows_affected())
+}
+
+/// Periodically archive finished conversations when `conversation_retention_days` is set
+pub fn start_conversation_retention_task(pool: SqlitePool, settings_service: Arc<SettingsService>) {
+    tokio::spawn(async move {
+        loop {
+            if !crate::common::shutdown::sleep(std::time::Duration::from_secs(
+                RETENTION_CHECK_INTERVAL_SECS,
+            ))
+            .await
+            {
+                break;
+            }
+            let _running = crate::common::shutdown::track();
+
+            let days = settings_service
+                .get_setting("conversation_retention_days")
+                .await
+                .ok()
+                .flatten()
+                .and_then(|v| v.trim().parse::<i64>().ok())
+                .unwrap_or(0);
+            if days <= 0 {
+                continue;
+            }
+
+            match archive_closed_conversations(&pool, days).await {
+                Ok(0) => {}
+                Ok(archived) => info!(archived, days, "Archived finished conversations"),
+                Err(e) => debug!(error = %e, "Skipped conversation retention run"),
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(sender: &str, text: &str) -> ConversationMessage {
+        ConversationMessage {
+            id: format!("m-{}", text.len()),
+            user_id: "u1".to_string(),
+            sender: sender.to_string(),
+            message: text.to_string(),
+            is_read: Some(1),
+            created_at: Some("2026-03-02 10:00:00".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_transcript_paragraphs_labels_senders_and_lists_attachments() {
+        let export = ConversationExport {
+            conversation_id: "u1".to_string(),
+            candidate_name: Some("Ana".to_string()),
+            candidate_email: "ana@example.com".to_string(),
+            exported_at: "2026-03-05T09:00:00+00:00".to_string(),
+            exported_by: "admin-1".to_string(),
+            archived_at: None,
+            messages: vec![message("user", "Hello"), message("admin", "Hi Ana")],
+            attachments: vec![ExportedAttachment {
+                id: "a1".to_string(),
+                message_id: "m-5".to_string(),
+                original_filename: "cv.pdf".to_string(),
+                mime_type: "application/pdf".to_string(),
+                file_size: 1024,
+                created_at: None,
+                url: "/api/attachments/x.pdf".to_string(),
+            }],
+        };
+
+        let paragraphs = transcript_paragraphs(&export);
+        assert_eq!(paragraphs[0], "Candidate: Ana <ana@example.com>");
+        assert!(paragraphs.contains(&"[2026-03-02 10:00:00] Ana: Hello".to_string()));
+        assert!(paragraphs.contains(&"[2026-03-02 10:00:00] Hiring team: Hi Ana".to_string()));
+        assert!(paragraphs.contains(&"Attachments (1)".to_string()));
+        assert_eq!(
+            paragraphs.last().unwrap(),
+            "- cv.pdf (application/pdf, 1024 bytes) in message m-5"
+        );
+    }
+}
@@ -0,0 +1,151 @@
+// src/services/documents.rs
+//! Candidate document collection: allowed types, upload checks and expiring download tokens
+
+use chrono::{DateTime, Duration, Utc};
+
+use crate::common::ApiError;
+use crate::services::signed_links::{issue_link_token, verify_link_token, LinkError};
+
+/// Document types admins can request from a candidate
+pub const DOCUMENT_TYPES: &[&str] = &[
+    "government_id",
+    "passport",
+    "certificate",
+    "degree",
+    "work_authorization",
+    "other",
+];
+
+/// Largest document a candidate may upload (10MB)
+pub const MAX_DOCUMENT_SIZE: usize = 10 * 1024 * 1024;
+
+/// File types accepted for documents, detected from content rather than the client's header
+const ALLOWED_MIME_TYPES: &[&str] = &["application/pdf", "image/jpeg", "image/png"];
+
+/// How long a download link stays valid
+pub const DOWNLOAD_TOKEN_MINUTES: i64 = 10;
+
+/// Purpose claim distinguishing download tokens from any other JWT
+const DOWNLOAD_PURPOSE: &str = "document_download";
+
+pub fn validate_document_type(document_type: &str) -> Result<(), ApiError> {
+    if DOCUMENT_TYPES.contains(&document_type) {
+        Ok(())
+    } else {
+        Err(ApiError::ValidationError(format!(
+            "Invalid document type '{}'; expected one of {}",
+            document_type,
+            DOCUMENT_TYPES.join(", ")
+        )))
+    }
+}
+
+/// Check an upload's size and sniffed type, returning its MIME type and file extension
+pub fn validate_document_upload(data: &[u8]) -> Result<(&'static str, &'static str), ApiError> {
+    if data.is_empty() {
+        return Err(ApiError::ValidationError(
+            "Uploaded file is empty".to_string(),
+        ));
+    }
+    if data.len() > MAX_DOCUMENT_SIZE {
+        return Err(ApiError::ValidationError(format!(
+            "File too large. Maximum size is {}MB",
+            MAX_DOCUMENT_SIZE / 1024 / 1024
+        )));
+    }
+
+    match infer::get(data) {
+        Some(kind) if ALLOWED_MIME_TYPES.contains(&kind.mime_type()) => {
+            Ok((kind.mime_type(), kind.extension()))
+        }
+        _ => Err(ApiError::ValidationError(
+            "Unsupported file type. Upload a PDF, JPEG or PNG".to_string(),
+        )),
+    }
+}
+
+/// Issue a short-lived token granting download of one document
+pub fn issue_download_token(
+    jwt_secret: &str,
+    document_id: &str,
+    now: DateTime<Utc>,
+) -> Result<(String, DateTime<Utc>), ApiError> {
+    let expires_at = now + Duration::minutes(DOWNLOAD_TOKEN_MINUTES);
+    let token = issue_link_token(jwt_secret, DOWNLOAD_PURPOSE, document_id, expires_at)?;
+    Ok((token, expires_at))
+}
+
+/// Check a download token is valid, unexpired and issued for this document
+pub fn verify_download_token(
+    jwt_secret: &str,
+    token: &str,
+    document_id: &str,
+) -> Result<(), ApiError> {
+    verify_link_token(jwt_secret, DOWNLOAD_PURPOSE, token, document_id).map_err(|e| match e {
+        LinkError::Invalid => {
+            ApiError::Unauthorized("Download link is invalid or has expired".to_string())
+        }
+        LinkError::WrongSubject => {
+            ApiError::Unauthorized("Download link is not valid for this document".to_string())
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use jsonwebtoken::{encode, Algorithm, EncodingKey, Header};
+
+    #[test]
+    fn test_download_token_roundtrip() {
+        let (token, expires_at) = issue_download_token("secret", "DR_ABC123", Utc::now()).unwrap();
+        assert!(expires_at > Utc::now());
+
+        assert!(verify_download_token("secret", &token, "DR_ABC123").is_ok());
+        assert!(verify_download_token("secret", &token, "DR_OTHER1").is_err());
+        assert!(verify_download_token("other-secret", &token, "DR_ABC123").is_err());
+    }
+
+    #[test]
+    fn test_expired_download_token_is_rejected() {
+        let issued = Utc::now() - Duration::minutes(DOWNLOAD_TOKEN_MINUTES + 1);
+        let (token, _) = issue_download_token("secret", "DR_ABC123", issued).unwrap();
+        assert!(verify_download_token("secret", &token, "DR_ABC123").is_err());
+    }
+
+    #[test]
+    fn test_session_token_is_not_a_download_token() {
+        let claims = crate::auth::models::Claims {
+            sub: "DR_ABC123".to_string(),
+            exp: (Utc::now() + Duration::hours(1)).timestamp() as usize,
+        };
+        let session = encode(
+            &Header::new(Algorithm::HS256),
+            &claims,
+            &EncodingKey::from_secret(b"secret"),
+        )
+        .unwrap();
+        assert!(verify_download_token("secret", &session, "DR_ABC123").is_err());
+    }
+
+    #[test]
+    fn test_validate_document_upload() {
+        let pdf = b"%PDF-1.4\n%test document".to_vec();
+        assert_eq!(
+            validate_document_upload(&pdf).unwrap(),
+            ("application/pdf", "pdf")
+        );
+        assert!(validate_document_upload(b"just some text").is_err());
+        assert!(validate_document_upload(&[]).is_err());
+
+        let mut oversized = pdf.clone();
+        oversized.resize(MAX_DOCUMENT_SIZE + 1, 0);
+        assert!(validate_document_upload(&oversized).is_err());
+    }
+
+    #[test]
+    fn test_validate_document_type() {
+        assert!(validate_document_type("passport").is_ok());
+        assert!(validate_document_type("selfie").is_err());
+    }
+}
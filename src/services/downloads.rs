@@ -0,0 +1,186 @@
+// src/services/downloads.rs
+//! Download gateway for stored files: resource kinds and short-lived signed links
+
+use chrono::{DateTime, Duration, Utc};
+
+use crate::common::ApiError;
+use crate::services::signed_links::{issue_link_token, verify_link_token, LinkError};
+
+/// How long a signed link (local or S3) stays valid
+pub const DOWNLOAD_LINK_MINUTES: i64 = 5;
+
+/// Video links last longer: a player keeps issuing range requests against the same link for
+/// as long as the video is open
+pub const PLAYBACK_LINK_MINUTES: i64 = 60;
+
+/// Purpose claim distinguishing file links from sessions and document links
+const FILE_DOWNLOAD_PURPOSE: &str = "file_download";
+
+/// The kinds of stored file the gateway will hand out
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileKind {
+    Resume,
+    Video,
+    Attachment,
+    OfferLetter,
+    InterviewArtifact,
+}
+
+impl FileKind {
+    pub fn parse(kind: &str) -> Result<Self, ApiError> {
+        match kind {
+            "resume" => Ok(FileKind::Resume),
+            "video" => Ok(FileKind::Video),
+            "attachment" => Ok(FileKind::Attachment),
+            "offer-letter" => Ok(FileKind::OfferLetter),
+            "interview-artifact" => Ok(FileKind::InterviewArtifact),
+            _ => Err(ApiError::NotFound(format!("Unknown file type '{}'", kind))),
+        }
+    }
+
+    /// How long a signed link to this kind of file stays valid
+    pub fn link_minutes(&self) -> i64 {
+        match self {
+            FileKind::Video => PLAYBACK_LINK_MINUTES,
+            _ => DOWNLOAD_LINK_MINUTES,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FileKind::Resume => "resume",
+            FileKind::Video => "video",
+            FileKind::Attachment => "attachment",
+            FileKind::OfferLetter => "offer-letter",
+            FileKind::InterviewArtifact => "interview-artifact",
+        }
+    }
+}
+
+fn subject(kind: FileKind, id: &str) -> String {
+    format!("{}:{}", kind.as_str(), id)
+}
+
+/// Issue a short-lived token granting download of one stored file
+pub fn issue_file_token(
+    jwt_secret: &str,
+    kind: FileKind,
+    id: &str,
+    now: DateTime<Utc>,
+) -> Result<(String, DateTime<Utc>), ApiError> {
+    let expires_at = now + Duration::minutes(kind.link_minutes());
+    let token = issue_link_token(
+        jwt_secret,
+        FILE_DOWNLOAD_PURPOSE,
+        &subject(kind, id),
+        expires_at,
+    )?;
+    Ok((token, expires_at))
+}
+
+/// Check a file token is valid, unexpired and issued for exactly this file
+pub fn verify_file_token(
+    jwt_secret: &str,
+    token: &str,
+    kind: FileKind,
+    id: &str,
+) -> Result<(), ApiError> {
+    verify_link_token(jwt_secret, FILE_DOWNLOAD_PURPOSE, token, &subject(kind, id)).map_err(|e| {
+        match e {
+            LinkError::Invalid => {
+                ApiError::Unauthorized("Download link is invalid or has expired".to_string())
+            }
+            LinkError::WrongSubject => {
+                ApiError::Unauthorized("Download link is not valid for this file".to_string())
+            }
+        }
+    })
+}
+
+/// The object key inside an S3 or CloudFront URL, if the URL points at either
+pub fn s3_key_from_url(url: &str) -> Option<String> {
+    let rest = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))?;
+    let (host, key) = rest.split_once('/')?;
+    let key = key.split(['?', '#']).next().unwrap_or("");
+    if key.is_empty() || !(host.contains(".amazonaws.com") || host.contains("cloudfront")) {
+        return None;
+    }
+    Some(key.to_string())
+}
+
+/// Reject paths that could escape the storage directory they are joined onto
+pub fn is_safe_relative_path(path: &str) -> bool {
+    !path.is_empty()
+        && !path.starts_with('/')
+        && !path.contains('\\')
+        && path
+            .split('/')
+            .all(|part| !part.is_empty() && part != "." && part != "..")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_file_token_is_bound_to_kind_and_id() {
+        let (token, expires_at) =
+            issue_file_token("secret", FileKind::Resume, "R1", Utc::now()).unwrap();
+        assert!(expires_at > Utc::now());
+
+        assert!(verify_file_token("secret", &token, FileKind::Resume, "R1").is_ok());
+        assert!(verify_file_token("secret", &token, FileKind::Resume, "R2").is_err());
+        assert!(verify_file_token("secret", &token, FileKind::Video, "R1").is_err());
+        assert!(verify_file_token("other-secret", &token, FileKind::Resume, "R1").is_err());
+    }
+
+    #[test]
+    fn test_expired_file_token_is_rejected() {
+        let issued = Utc::now() - Duration::minutes(DOWNLOAD_LINK_MINUTES + 1);
+        let (token, _) = issue_file_token("secret", FileKind::Attachment, "A1", issued).unwrap();
+        assert!(verify_file_token("secret", &token, FileKind::Attachment, "A1").is_err());
+    }
+
+    #[test]
+    fn test_video_links_outlive_download_links() {
+        let issued = Utc::now() - Duration::minutes(DOWNLOAD_LINK_MINUTES + 1);
+        let (video, _) = issue_file_token("secret", FileKind::Video, "V1", issued).unwrap();
+        let (resume, _) = issue_file_token("secret", FileKind::Resume, "V1", issued).unwrap();
+        assert!(verify_file_token("secret", &video, FileKind::Video, "V1").is_ok());
+        assert!(verify_file_token("secret", &resume, FileKind::Resume, "V1").is_err());
+    }
+
+    #[test]
+    fn test_document_token_is_not_a_file_token() {
+        let (token, _) =
+            crate::services::documents::issue_download_token("secret", "R1", Utc::now()).unwrap();
+        assert!(verify_file_token("secret", &token, FileKind::Resume, "R1").is_err());
+    }
+
+    #[test]
+    fn test_s3_key_from_url() {
+        assert_eq!(
+            s3_key_from_url("https://bucket.s3.us-east-1.amazonaws.com/videos/user-1/v.mp4"),
+            Some("videos/user-1/v.mp4".to_string())
+        );
+        assert_eq!(
+            s3_key_from_url("https://d123.cloudfront.net/offer-letters/o.pdf?x=1"),
+            Some("offer-letters/o.pdf".to_string())
+        );
+        assert_eq!(s3_key_from_url("/uploads/offer-letters/o.pdf"), None);
+        assert_eq!(s3_key_from_url("https://example.com/o.pdf"), None);
+    }
+
+    #[test]
+    fn test_is_safe_relative_path() {
+        assert!(is_safe_relative_path("resume_1.pdf"));
+        assert!(is_safe_relative_path("attachments/abc_file.pdf"));
+        assert!(!is_safe_relative_path("../secrets.env"));
+        assert!(!is_safe_relative_path("attachments/../../x"));
+        assert!(!is_safe_relative_path("/etc/passwd"));
+        assert!(!is_safe_relative_path("a//b"));
+        assert!(!is_safe_relative_path(""));
+    }
+}
@@ -0,0 +1,251 @@
+// src/services/eeo.rs
+//! Voluntary self-identification (EEO) answers
+//!
+//! Answers are stored envelope-encrypted and apart from the application, are never shown to
+//! reviewers, and are reported only as aggregates. Reports need the `eeo_reports` admin
+//! permission and hide any group smaller than `eeo_min_cohort_size` (default 5).
+
+use std::collections::BTreeMap;
+
+use crate::candidates::models::{
+    EeoCategoryCount, EeoFieldBreakdown, SelfIdentification, SelfIdentificationQuestion,
+};
+use crate::common::ApiError;
+
+pub const DEFAULT_MIN_COHORT_SIZE: i64 = 5;
+
+/// A smaller configured cohort would let individual answers be inferred
+const MIN_ALLOWED_COHORT_SIZE: i64 = 3;
+
+pub const DECLINE_TO_ANSWER: &str = "decline_to_answer";
+
+/// Reported for respondents who left a question blank
+const NOT_PROVIDED: &str = "not_provided";
+
+pub const QUESTIONS: &[SelfIdentificationQuestion] = &[
+    SelfIdentificationQuestion {
+        field: "gender",
+        options: &["female", "male", "non_binary", DECLINE_TO_ANSWER],
+    },
+    SelfIdentificationQuestion {
+        field: "race_ethnicity",
+        options: &[
+            "american_indian_or_alaska_native",
+            "asian",
+            "black_or_african_american",
+            "hispanic_or_latino",
+            "native_hawaiian_or_pacific_islander",
+            "white",
+            "two_or_more_races",
+            DECLINE_TO_ANSWER,
+        ],
+    },
+    SelfIdentificationQuestion {
+        field: "veteran_status",
+        options: &[
+            "protected_veteran",
+            "not_a_protected_veteran",
+            DECLINE_TO_ANSWER,
+        ],
+    },
+    SelfIdentificationQuestion {
+        field: "disability_status",
+        options: &["yes", "no", DECLINE_TO_ANSWER],
+    },
+];
+
+fn answer<'a>(answers: &'a SelfIdentification, field: &str) -> Option<&'a String> {
+    match field {
+        "gender" => answers.gender.as_ref(),
+        "race_ethnicity" => answers.race_ethnicity.as_ref(),
+        "veteran_status" => answers.veteran_status.as_ref(),
+        "disability_status" => answers.disability_status.as_ref(),
+        _ => None,
+    }
+}
+
+/// Lowercase and trim each answer, treat blanks as unanswered, and reject unknown options
+pub fn normalize_answers(answers: SelfIdentification) -> Result<SelfIdentification, ApiError> {
+    let normalize = |field: &str, value: Option<String>| -> Result<Option<String>, ApiError> {
+        let Some(value) = value
+            .map(|v| v.trim().to_lowercase())
+            .filter(|v| !v.is_empty())
+        else {
+            return Ok(None);
+        };
+        let question = QUESTIONS.iter().find(|q| q.field == field);
+        if question.is_some_and(|q| q.options.contains(&value.as_str())) {
+            Ok(Some(value))
+        } else {
+            Err(ApiError::ValidationError(format!(
+                "{} is not a valid answer for {}",
+                value, field
+            )))
+        }
+    };
+
+    Ok(SelfIdentification {
+        gender: normalize("gender", answers.gender)?,
+        race_ethnicity: normalize("race_ethnicity", answers.race_ethnicity)?,
+        veteran_status: normalize("veteran_status", answers.veteran_status)?,
+        disability_status: normalize("disability_status", answers.disability_status)?,
+    })
+}
+
+/// Configured minimum cohort, never below the built-in floor
+pub fn min_cohort_size(setting: Option<&str>) -> i64 {
+    setting
+        .and_then(|v| v.trim().parse::<i64>().ok())
+        .unwrap_or(DEFAULT_MIN_COHORT_SIZE)
+        .max(MIN_ALLOWED_COHORT_SIZE)
+}
+
+/// Fold groups smaller than `min_cohort` into a suppressed total
+///
+/// If the suppressed total would itself be small, the next smallest groups are folded in too,
+/// so no group can be worked out by subtracting the others from the total.
+pub fn suppress_small_cohorts(
+    field: &str,
+    counts: BTreeMap<String, i64>,
+    min_cohort: i64,
+) -> EeoFieldBreakdown {
+    let mut groups: Vec<(String, i64)> = counts.into_iter().filter(|(_, c)| *c > 0).collect();
+    groups.sort_by(|a, b| a.1.cmp(&b.1).then_with(|| a.0.cmp(&b.0)));
+
+    let mut suppressed = 0;
+    let mut hidden = 0;
+    for (_, count) in &groups {
+        if *count < min_cohort || (suppressed > 0 && suppressed < min_cohort) {
+            suppressed += count;
+            hidden += 1;
+        } else {
+            break;
+        }
+    }
+
+    let mut categories: Vec<EeoCategoryCount> = groups
+        .into_iter()
+        .skip(hidden)
+        .map(|(category, count)| EeoCategoryCount { category, count })
+        .collect();
+    categories.sort_by(|a, b| {
+        b.count
+            .cmp(&a.count)
+            .then_with(|| a.category.cmp(&b.category))
+    });
+
+    EeoFieldBreakdown {
+        field: field.to_string(),
+        categories,
+        suppressed,
+    }
+}
+
+/// Per-question aggregates of decrypted answers
+pub fn aggregate(answers: &[SelfIdentification], min_cohort: i64) -> Vec<EeoFieldBreakdown> {
+    QUESTIONS
+        .iter()
+        .map(|question| {
+            let mut counts = BTreeMap::new();
+            for respondent in answers {
+                let category = answer(respondent, question.field)
+                    .cloned()
+                    .unwrap_or_else(|| NOT_PROVIDED.to_string());
+                *counts.entry(category).or_insert(0) += 1;
+            }
+            suppress_small_cohorts(question.field, counts, min_cohort)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn counts(pairs: &[(&str, i64)]) -> BTreeMap<String, i64> {
+        pairs.iter().map(|(k, v)| (k.to_string(), *v)).collect()
+    }
+
+    #[test]
+    fn test_normalize_answers() {
+        let answers = normalize_answers(SelfIdentification {
+            gender: Some(" Female ".to_string()),
+            race_ethnicity: Some(String::new()),
+            veteran_status: None,
+            disability_status: Some("decline_to_answer".to_string()),
+        })
+        .unwrap();
+        assert_eq!(answers.gender.as_deref(), Some("female"));
+        assert_eq!(answers.race_ethnicity, None);
+        assert_eq!(
+            answers.disability_status.as_deref(),
+            Some(DECLINE_TO_ANSWER)
+        );
+
+        assert!(normalize_answers(SelfIdentification {
+            gender: Some("unknown".to_string()),
+            ..Default::default()
+        })
+        .is_err());
+    }
+
+    #[test]
+    fn test_min_cohort_size_has_a_floor() {
+        assert_eq!(min_cohort_size(None), DEFAULT_MIN_COHORT_SIZE);
+        assert_eq!(min_cohort_size(Some("10")), 10);
+        assert_eq!(min_cohort_size(Some("1")), MIN_ALLOWED_COHORT_SIZE);
+        assert_eq!(min_cohort_size(Some("abc")), DEFAULT_MIN_COHORT_SIZE);
+    }
+
+    #[test]
+    fn test_small_groups_are_suppressed() {
+        let breakdown = suppress_small_cohorts(
+            "gender",
+            counts(&[
+                ("female", 12),
+                ("male", 9),
+                ("non_binary", 2),
+                ("decline_to_answer", 4),
+            ]),
+            5,
+        );
+        assert_eq!(breakdown.suppressed, 6);
+        let shown: Vec<_> = breakdown
+            .categories
+            .iter()
+            .map(|c| c.category.as_str())
+            .collect();
+        assert_eq!(shown, vec!["female", "male"]);
+    }
+
+    #[test]
+    fn test_single_small_group_takes_next_smallest_with_it() {
+        // Hiding only non_binary would leave it recoverable from the total
+        let breakdown = suppress_small_cohorts(
+            "gender",
+            counts(&[("female", 12), ("male", 9), ("non_binary", 2)]),
+            5,
+        );
+        assert_eq!(breakdown.suppressed, 11);
+        assert_eq!(breakdown.categories.len(), 1);
+        assert_eq!(breakdown.categories[0].category, "female");
+    }
+
+    #[test]
+    fn test_aggregate_counts_blank_answers() {
+        let answers: Vec<SelfIdentification> = (0..6)
+            .map(|i| SelfIdentification {
+                veteran_status: (i < 5).then(|| "not_a_protected_veteran".to_string()),
+                ..Default::default()
+            })
+            .collect();
+        let fields = aggregate(&answers, 5);
+        assert_eq!(fields.len(), QUESTIONS.len());
+        let gender = &fields[0];
+        assert_eq!(gender.categories[0].category, NOT_PROVIDED);
+        assert_eq!(gender.categories[0].count, 6);
+        let veteran = fields.iter().find(|f| f.field == "veteran_status").unwrap();
+        assert!(veteran.categories.is_empty());
+        assert_eq!(veteran.suppressed, 6);
+    }
+}
@@ -39,13 +39,28 @@ impl std::fmt::Debug for EncryptionService {
 }
 
 impl EncryptionService {
-    /// Initialize encryption service from environment variable
+    /// Initialize encryption service from environment
+    ///
+    /// Reads the master key from `ENCRYPTION_MASTER_KEY`, or from the file named by
+    /// `ENCRYPTION_MASTER_KEY_FILE` (e.g. a key decrypted by KMS or mounted by a secrets manager)
     #[allow(dead_code)]
     pub fn from_env() -> Result<Self, EncryptionError> {
+        Self::from_key(&Self::master_key_from_env()?)
+    }
+
+    /// The base64 master key configured through `ENCRYPTION_MASTER_KEY` or
+    /// `ENCRYPTION_MASTER_KEY_FILE`
+    pub fn master_key_from_env() -> Result<String, EncryptionError> {
+        if let Ok(key_str) = env::var("ENCRYPTION_MASTER_KEY") {
+            return Ok(key_str);
+        }
+
+        let key_file =
+            env::var("ENCRYPTION_MASTER_KEY_FILE").map_err(|_| EncryptionError::KeyNotConfigured)?;
         let key_str =
-            env::var("ENCRYPTION_MASTER_KEY").map_err(|_| EncryptionError::KeyNotConfigured)?;
+            std::fs::read_to_string(&key_file).map_err(|_| EncryptionError::KeyNotConfigured)?;
 
-        Self::from_key(&key_str)
+        Ok(key_str.trim().to_string())
     }
 
     /// Initialize encryption service from a base64-encoded key string
@@ -75,52 +90,140 @@ impl EncryptionService {
     }
 
     /// Encrypt a plaintext string and return base64-encoded ciphertext with nonce
-    #[allow(deprecated)]
+    #[allow(dead_code)]
     pub fn encrypt(&self, plaintext: &str) -> Result<String, EncryptionError> {
-        // Generate random nonce (12 bytes for GCM)
-        let mut nonce_bytes = [0u8; 12];
-        OsRng.fill_bytes(&mut nonce_bytes);
-        let nonce = Nonce::from_slice(&nonce_bytes);
+        let sealed = seal(&self.cipher, plaintext.as_bytes())?;
+        Ok(BASE64.encode(sealed))
+    }
 
-        // Encrypt the plaintext
-        let ciphertext = self
-            .cipher
-            .encrypt(nonce, plaintext.as_bytes())
-            .map_err(|e| EncryptionError::EncryptionFailed(e.to_string()))?;
+    /// Encrypt a plaintext string using envelope encryption
+    ///
+    /// A fresh 256-bit data key encrypts the value and the master key only
+    /// wraps that data key. Output format: `env1:<wrapped_key>:<ciphertext>`
+    #[allow(deprecated)]
+    pub fn encrypt_envelope(&self, plaintext: &str) -> Result<String, EncryptionError> {
+        let mut data_key = [0u8; 32];
+        OsRng.fill_bytes(&mut data_key);
+        let data_cipher = Aes256Gcm::new(aes_gcm::Key::<Aes256Gcm>::from_slice(&data_key));
+
+        let sealed_value = seal(&data_cipher, plaintext.as_bytes())?;
+        let wrapped_key = seal(&self.cipher, &data_key)?;
+
+        Ok(format!(
+            "{}{}:{}",
+            ENVELOPE_PREFIX,
+            BASE64.encode(wrapped_key),
+            BASE64.encode(sealed_value)
+        ))
+    }
 
-        // Combine nonce + ciphertext and encode as base64
-        let mut combined = nonce_bytes.to_vec();
-        combined.extend_from_slice(&ciphertext);
+    /// Decrypt a value produced by either `encrypt` or `encrypt_envelope`
+    #[allow(deprecated)]
+    pub fn decrypt(&self, encrypted: &str) -> Result<String, EncryptionError> {
+        let plaintext_bytes = match encrypted.strip_prefix(ENVELOPE_PREFIX) {
+            Some(envelope) => {
+                let (wrapped_key, sealed_value) = envelope
+                    .split_once(':')
+                    .ok_or(EncryptionError::InvalidDataFormat)?;
+
+                let wrapped_key = BASE64
+                    .decode(wrapped_key.as_bytes())
+                    .map_err(|_| EncryptionError::InvalidDataFormat)?;
+                let data_key = open(&self.cipher, &wrapped_key)?;
+                if data_key.len() != 32 {
+                    return Err(EncryptionError::InvalidDataFormat);
+                }
+                let data_cipher =
+                    Aes256Gcm::new(aes_gcm::Key::<Aes256Gcm>::from_slice(&data_key));
+
+                let sealed_value = BASE64
+                    .decode(sealed_value.as_bytes())
+                    .map_err(|_| EncryptionError::InvalidDataFormat)?;
+                open(&data_cipher, &sealed_value)?
+            }
+            None => {
+                let combined = BASE64
+                    .decode(encrypted.as_bytes())
+                    .map_err(|_| EncryptionError::InvalidDataFormat)?;
+                open(&self.cipher, &combined)?
+            }
+        };
 
-        Ok(BASE64.encode(combined))
+        // Convert to string
+        String::from_utf8(plaintext_bytes)
+            .map_err(|_| EncryptionError::DecryptionFailed("invalid UTF-8".to_string()))
     }
 
-    /// Decrypt a base64-encoded ciphertext (with nonce) and return plaintext
+    /// Envelope-encrypt binary data such as uploaded files
+    ///
+    /// Output layout: wrapped data key (fixed length) followed by the sealed payload
     #[allow(deprecated)]
-    pub fn decrypt(&self, encrypted: &str) -> Result<String, EncryptionError> {
-        // Decode base64
-        let combined = BASE64
-            .decode(encrypted.as_bytes())
-            .map_err(|_| EncryptionError::InvalidDataFormat)?;
+    pub fn encrypt_bytes(&self, plaintext: &[u8]) -> Result<Vec<u8>, EncryptionError> {
+        let mut data_key = [0u8; 32];
+        OsRng.fill_bytes(&mut data_key);
+        let data_cipher = Aes256Gcm::new(aes_gcm::Key::<Aes256Gcm>::from_slice(&data_key));
+
+        let mut combined = seal(&self.cipher, &data_key)?;
+        combined.extend_from_slice(&seal(&data_cipher, plaintext)?);
+        Ok(combined)
+    }
 
-        // Split nonce and ciphertext
-        if combined.len() < 12 {
+    /// Decrypt data produced by `encrypt_bytes`
+    #[allow(deprecated)]
+    pub fn decrypt_bytes(&self, encrypted: &[u8]) -> Result<Vec<u8>, EncryptionError> {
+        if encrypted.len() < WRAPPED_KEY_LEN {
             return Err(EncryptionError::InvalidDataFormat);
         }
 
-        let (nonce_bytes, ciphertext) = combined.split_at(12);
-        let nonce = Nonce::from_slice(nonce_bytes);
+        let (wrapped_key, sealed_value) = encrypted.split_at(WRAPPED_KEY_LEN);
+        let data_key = open(&self.cipher, wrapped_key)?;
+        let data_cipher = Aes256Gcm::new(aes_gcm::Key::<Aes256Gcm>::from_slice(&data_key));
 
-        // Decrypt
-        let plaintext_bytes = self
-            .cipher
-            .decrypt(nonce, ciphertext)
-            .map_err(|e| EncryptionError::DecryptionFailed(e.to_string()))?;
+        open(&data_cipher, sealed_value)
+    }
 
-        // Convert to string
-        String::from_utf8(plaintext_bytes)
-            .map_err(|_| EncryptionError::DecryptionFailed("invalid UTF-8".to_string()))
+    /// Check whether a stored value already uses envelope encryption
+    pub fn is_envelope(encrypted: &str) -> bool {
+        encrypted.starts_with(ENVELOPE_PREFIX)
+    }
+}
+
+/// Prefix marking values written by `encrypt_envelope`
+const ENVELOPE_PREFIX: &str = "env1:";
+
+/// Size of a sealed 32-byte data key: 12-byte nonce + key + 16-byte GCM tag
+const WRAPPED_KEY_LEN: usize = 12 + 32 + 16;
+
+/// Encrypt bytes with a random nonce, returning nonce + ciphertext
+#[allow(deprecated)]
+fn seal(cipher: &Aes256Gcm, plaintext: &[u8]) -> Result<Vec<u8>, EncryptionError> {
+    // Generate random nonce (12 bytes for GCM)
+    let mut nonce_bytes = [0u8; 12];
+    OsRng.fill_bytes(&mut nonce_bytes);
+    let nonce = Nonce::from_slice(&nonce_bytes);
+
+    let ciphertext = cipher
+        .encrypt(nonce, plaintext)
+        .map_err(|e| EncryptionError::EncryptionFailed(e.to_string()))?;
+
+    let mut combined = nonce_bytes.to_vec();
+    combined.extend_from_slice(&ciphertext);
+    Ok(combined)
+}
+
+/// Split nonce + ciphertext and decrypt
+#[allow(deprecated)]
+fn open(cipher: &Aes256Gcm, combined: &[u8]) -> Result<Vec<u8>, EncryptionError> {
+    if combined.len() < 12 {
+        return Err(EncryptionError::InvalidDataFormat);
     }
+
+    let (nonce_bytes, ciphertext) = combined.split_at(12);
+    let nonce = Nonce::from_slice(nonce_bytes);
+
+    cipher
+        .decrypt(nonce, ciphertext)
+        .map_err(|e| EncryptionError::DecryptionFailed(e.to_string()))
 }
 
 #[cfg(test)]
@@ -184,4 +287,42 @@ mod tests {
         let result = service.decrypt("invalid_encrypted_data");
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_envelope_encrypt_decrypt() {
+        let key = EncryptionService::generate_key();
+        let service = EncryptionService::from_key(&key).unwrap();
+
+        let encrypted = service.encrypt_envelope("aws_secret_value").unwrap();
+        assert!(EncryptionService::is_envelope(&encrypted));
+        assert!(!encrypted.contains("aws_secret_value"));
+        assert_eq!(service.decrypt(&encrypted).unwrap(), "aws_secret_value");
+
+        // Legacy values encrypted directly with the master key still decrypt
+        let legacy = service.encrypt("legacy_value").unwrap();
+        assert!(!EncryptionService::is_envelope(&legacy));
+        assert_eq!(service.decrypt(&legacy).unwrap(), "legacy_value");
+    }
+
+    #[test]
+    fn test_encrypt_bytes_roundtrip() {
+        let service = EncryptionService::from_key(&EncryptionService::generate_key()).unwrap();
+        let other = EncryptionService::from_key(&EncryptionService::generate_key()).unwrap();
+
+        let file = b"%PDF-1.4 binary \x00\xff content".to_vec();
+        let encrypted = service.encrypt_bytes(&file).unwrap();
+        assert_ne!(encrypted, file);
+        assert_eq!(service.decrypt_bytes(&encrypted).unwrap(), file);
+        assert!(other.decrypt_bytes(&encrypted).is_err());
+        assert!(service.decrypt_bytes(&encrypted[..20]).is_err());
+    }
+
+    #[test]
+    fn test_envelope_rejects_wrong_master_key() {
+        let service = EncryptionService::from_key(&EncryptionService::generate_key()).unwrap();
+        let other = EncryptionService::from_key(&EncryptionService::generate_key()).unwrap();
+
+        let encrypted = service.encrypt_envelope("secret").unwrap();
+        assert!(other.decrypt(&encrypted).is_err());
+    }
 }
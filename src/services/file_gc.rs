@@ -0,0 +1,581 @@
+// src/services/file_gc.rs
+//! Orphaned file reconciliation: cross-check stored objects against the rows that reference
+//! them, report both directions, and delete orphans once they have outlived a grace period
+//!
+//! A file is only deleted after it has been seen orphaned for `orphan_gc_grace_days`
+//! (default 7), which also protects uploads caught between writing the file and inserting
+//! its row. Orphans whose owner can be read from the key are kept while that owner is under
+//! a legal hold.
+
+use serde::Serialize;
+use sqlx::SqlitePool;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::{error, info, warn};
+
+use crate::common::{storage, ApiError, AppState};
+use crate::services::downloads;
+use crate::services::legal_hold;
+
+const DEFAULT_GRACE_DAYS: i64 = 7;
+const DEFAULT_INTERVAL_HOURS: u64 = 24;
+
+pub const STORAGE_LOCAL: &str = "local";
+pub const STORAGE_S3: &str = "s3";
+
+/// An object found in storage that no row references
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct OrphanedFile {
+    pub storage: String,
+    pub path: String,
+    pub category: String,
+    pub size_bytes: i64,
+    pub owner_id: Option<String>,
+    pub first_seen_at: String,
+    pub last_seen_at: String,
+    pub deleted_at: Option<String>,
+}
+
+/// A row whose file is nowhere to be found
+#[derive(Debug, Clone, Serialize)]
+pub struct MissingFile {
+    pub category: String,
+    pub id: String,
+    pub path: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct OrphanScanReport {
+    pub scanned_files: usize,
+    pub orphaned: Vec<OrphanedFile>,
+    pub missing: Vec<MissingFile>,
+    pub deleted: usize,
+    pub kept_for_legal_hold: usize,
+    /// False when S3 is in use but could not be listed, so only local storage was checked
+    pub s3_checked: bool,
+}
+
+/// Names and keys that rows currently point at
+#[derive(Debug, Default)]
+struct References {
+    resume_files: HashSet<String>,
+    attachment_paths: HashSet<String>,
+    video_keys: HashSet<String>,
+    asset_names: HashSet<String>,
+}
+
+/// One object seen in storage
+struct FoundObject {
+    storage: &'static str,
+    path: String,
+    category: &'static str,
+    size: i64,
+}
+
+fn basename(path: &str) -> &str {
+    path.rsplit('/').next().unwrap_or(path)
+}
+
+/// The uploading user of a video key such as `videos/user-<id>/<video>.mp4`
+pub fn owner_from_key(path: &str) -> Option<String> {
+    path.strip_prefix("videos/user-")
+        .and_then(|rest| rest.split_once('/'))
+        .map(|(owner, _)| owner.to_string())
+        .filter(|owner| !owner.is_empty())
+}
+
+/// Which ledger category a stored path belongs to, if the scan covers it at all
+fn category_for(path: &str) -> Option<&'static str> {
+    if path.starts_with("attachments/") {
+        Some("attachment")
+    } else if path.starts_with("resumes/") {
+        Some("resume")
+    } else if path.starts_with("videos/") {
+        Some("video")
+    } else if path.starts_with("job-images/") {
+        Some("company_asset")
+    } else {
+        None
+    }
+}
+
+impl References {
+    fn is_referenced(&self, path: &str) -> bool {
+        match category_for(path) {
+            Some("attachment") => self.attachment_paths.contains(path),
+            Some("resume") => self.resume_files.contains(basename(path)),
+            Some("video") => self.video_keys.contains(path),
+            Some("company_asset") => self.asset_names.contains(basename(path)),
+            _ => true,
+        }
+    }
+}
+
+async fn load_references(pool: &SqlitePool) -> Result<References, sqlx::Error> {
+    let mut refs = References::default();
+
+    let resumes: Vec<Option<String>> = sqlx::query_scalar("SELECT filename FROM resumes")
+        .fetch_all(pool)
+        .await?;
+    refs.resume_files = resumes.into_iter().flatten().collect();
+
+    let attachments: Vec<String> = sqlx::query_scalar("SELECT file_path FROM message_attachments")
+        .fetch_all(pool)
+        .await?;
+    refs.attachment_paths = attachments.into_iter().collect();
+
+    let videos: Vec<(String, Option<String>)> =
+        sqlx::query_as("SELECT user_id, s3_url FROM videos WHERE s3_url IS NOT NULL")
+            .fetch_all(pool)
+            .await?;
+    for (user_id, url) in videos {
+        match url.as_deref().and_then(downloads::s3_key_from_url) {
+            Some(key) => {
+                refs.video_keys.insert(key);
+            }
+            // Same fallback the video delete handler uses for non-S3 URLs
+            None => {
+                if let Some(name) = url.as_deref().map(basename) {
+                    refs.video_keys
+                        .insert(format!("videos/user-{}/{}", user_id, name));
+                }
+            }
+        }
+    }
+
+    // Job images can be referenced from several places, so collect every URL that might
+    // point at one and match on the file name
+    let asset_urls: Vec<Option<String>> = sqlx::query_scalar(
+        r#"
+        SELECT filename FROM company_assets
+        UNION SELECT url FROM company_assets
+        UNION SELECT company_logo_url FROM jobs
+        UNION SELECT job_image_url FROM jobs
+        UNION SELECT image_url FROM job_social_images
+        UNION SELECT image_url FROM scheduled_social_posts
+        UNION SELECT default_logo_url FROM companies
+        "#,
+    )
+    .fetch_all(pool)
+    .await?;
+    refs.asset_names = asset_urls
+        .into_iter()
+        .flatten()
+        .map(|url| basename(url.split(['?', '#']).next().unwrap_or("")).to_string())
+        .filter(|name| !name.is_empty())
+        .collect();
+
+    // Templates embed image URLs inside their JSON
+    let templates: Vec<String> = sqlx::query_scalar("SELECT job_data FROM job_templates")
+        .fetch_all(pool)
+        .await?;
+    for data in templates {
+        for part in data.split('"') {
+            if part.contains("/job-images/") {
+                refs.asset_names.insert(basename(part).to_string());
+            }
+        }
+    }
+
+    Ok(refs)
+}
+
+async fn list_local_dir(dir: &Path, label: &str, found: &mut Vec<FoundObject>) {
+    let Ok(mut entries) = tokio::fs::read_dir(dir).await else {
+        return;
+    };
+    while let Ok(Some(entry)) = entries.next_entry().await {
+        let Ok(metadata) = entry.metadata().await else {
+            continue;
+        };
+        if !metadata.is_file() {
+            continue;
+        }
+        let path = format!("{}/{}", label, entry.file_name().to_string_lossy());
+        if let Some(category) = category_for(&path) {
+            found.push(FoundObject {
+                storage: STORAGE_LOCAL,
+                path,
+                category,
+                size: metadata.len() as i64,
+            });
+        }
+    }
+}
+
+/// Local directories the scan covers, keyed by the label used in reported paths
+fn local_dirs(state: &AppState) -> Vec<(&'static str, PathBuf)> {
+    vec![
+        ("resumes", state.resumes_dir.clone()),
+        ("attachments", state.resumes_dir.join("attachments")),
+        ("job-images/logos", state.job_images_logos_dir.clone()),
+        ("job-images/jobs", state.job_images_jobs_dir.clone()),
+    ]
+}
+
+fn local_path(state: &AppState, path: &str) -> Option<PathBuf> {
+    local_dirs(state).into_iter().find_map(|(label, dir)| {
+        path.strip_prefix(label)
+            .and_then(|rest| rest.strip_prefix('/'))
+            .filter(|name| downloads::is_safe_relative_path(name) && !name.contains('/'))
+            .map(|name| dir.join(name))
+    })
+}
+
+/// List S3 objects under the scanned prefixes; false if any listing failed
+async fn list_s3(state: &AppState, found: &mut Vec<FoundObject>) -> bool {
+    for prefix in ["resumes/", "videos/", "job-images/"] {
+        match state.aws_service.list_files(Some(prefix)).await {
+            Ok(objects) => {
+                for object in objects {
+                    if let Some(category) = category_for(&object.key) {
+                        found.push(FoundObject {
+                            storage: STORAGE_S3,
+                            path: object.key,
+                            category,
+                            size: object.size,
+                        });
+                    }
+                }
+            }
+            Err(e) => {
+                warn!(error = %e, prefix = %prefix, "Could not list S3 objects for orphan scan");
+                return false;
+            }
+        }
+    }
+    true
+}
+
+async fn find_missing(
+    state: &AppState,
+    found: &[FoundObject],
+    s3_checked: bool,
+) -> Result<Vec<MissingFile>, sqlx::Error> {
+    let present: HashSet<&str> = found.iter().map(|f| f.path.as_str()).collect();
+    let uses_s3 = storage::uses_s3(state).await;
+    // Without a trustworthy S3 listing, only objects that can only be local are judged
+    let s3_unknown = uses_s3 && !s3_checked;
+    let mut missing = Vec::new();
+
+    let resumes: Vec<(String, String)> = sqlx::query_as(
+        "SELECT id, filename FROM resumes WHERE filename IS NOT NULL AND deleted_at IS NULL",
+    )
+    .fetch_all(&state.db)
+    .await?;
+    if !s3_unknown {
+        for (id, filename) in resumes {
+            let path = format!("resumes/{}", filename);
+            if !present.contains(path.as_str()) {
+                missing.push(MissingFile {
+                    category: "resume".to_string(),
+                    id,
+                    path,
+                });
+            }
+        }
+    }
+
+    let attachments: Vec<(String, String)> =
+        sqlx::query_as("SELECT id, file_path FROM message_attachments")
+            .fetch_all(&state.db)
+            .await?;
+    for (id, path) in attachments {
+        if !present.contains(path.as_str()) {
+            missing.push(MissingFile {
+                category: "attachment".to_string(),
+                id,
+                path,
+            });
+        }
+    }
+
+    if s3_checked && uses_s3 {
+        let videos: Vec<(String, Option<String>)> = sqlx::query_as(
+            "SELECT id, s3_url FROM videos WHERE COALESCE(video_source, 'upload') = 'upload'",
+        )
+        .fetch_all(&state.db)
+        .await?;
+        for (id, url) in videos {
+            if let Some(key) = url.as_deref().and_then(downloads::s3_key_from_url) {
+                if !present.contains(key.as_str()) {
+                    missing.push(MissingFile {
+                        category: "video".to_string(),
+                        id,
+                        path: key,
+                    });
+                }
+            }
+        }
+    }
+
+    let assets: Vec<(String, String, String)> =
+        sqlx::query_as("SELECT id, asset_type, filename FROM company_assets")
+            .fetch_all(&state.db)
+            .await?;
+    for (id, asset_type, filename) in assets {
+        let dir = if asset_type == "logo" {
+            "logos"
+        } else {
+            "jobs"
+        };
+        let path = format!("job-images/{}/{}", dir, filename);
+        if !present.contains(path.as_str()) {
+            missing.push(MissingFile {
+                category: "company_asset".to_string(),
+                id,
+                path,
+            });
+        }
+    }
+
+    Ok(missing)
+}
+
+async fn delete_object(state: &AppState, orphan: &OrphanedFile) -> Result<(), String> {
+    if orphan.storage == STORAGE_S3 {
+        return state
+            .object_store
+            .delete_file(&orphan.path)
+            .await
+            .map_err(|e| e.to_string());
+    }
+    let path = local_path(state, &orphan.path).ok_or("unrecognised local path")?;
+    match tokio::fs::remove_file(&path).await {
+        Ok(()) => Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+/// Orphans still in storage, oldest first
+pub async fn list_orphans(pool: &SqlitePool) -> Result<Vec<OrphanedFile>, sqlx::Error> {
+    sqlx::query_as::<_, OrphanedFile>(
+        "SELECT * FROM orphaned_files WHERE deleted_at IS NULL ORDER BY first_seen_at, path",
+    )
+    .fetch_all(pool)
+    .await
+}
+
+pub async fn grace_days(state: &AppState) -> i64 {
+    state
+        .settings_service
+        .get_setting("orphan_gc_grace_days")
+        .await
+        .ok()
+        .flatten()
+        .and_then(|v| v.trim().parse::<i64>().ok())
+        .map(|days| days.max(1))
+        .unwrap_or(DEFAULT_GRACE_DAYS)
+}
+
+/// Reconcile storage with the database, recording orphans; with `delete`, remove orphans
+/// first seen more than the grace period ago
+pub async fn scan(state: &AppState, delete: bool) -> Result<OrphanScanReport, ApiError> {
+    let refs = load_references(&state.db)
+        .await
+        .map_err(ApiError::DatabaseError)?;
+
+    let mut found = Vec::new();
+    for (label, dir) in local_dirs(state) {
+        list_local_dir(&dir, label, &mut found).await;
+    }
+    let uses_s3 = storage::uses_s3(state).await;
+    let s3_checked = if uses_s3 {
+        list_s3(state, &mut found).await
+    } else {
+        false
+    };
+
+    let missing = find_missing(state, &found, s3_checked)
+        .await
+        .map_err(ApiError::DatabaseError)?;
+
+    // Record this pass's orphans; a path that was deleted and has reappeared starts a new grace period
+    let mut orphan_paths: HashSet<(&str, &str)> = HashSet::new();
+    for object in found.iter().filter(|f| !refs.is_referenced(&f.path)) {
+        orphan_paths.insert((object.storage, object.path.as_str()));
+        sqlx::query(
+            r#"
+            INSERT INTO orphaned_files (storage, path, category, size_bytes, owner_id)
+            VALUES (?, ?, ?, ?, ?)
+            ON CONFLICT(storage, path) DO UPDATE SET
+                size_bytes = excluded.size_bytes,
+                last_seen_at = datetime('now'),
+                first_seen_at = CASE WHEN deleted_at IS NULL THEN first_seen_at ELSE datetime('now') END,
+                deleted_at = NULL
+            "#,
+        )
+        .bind(object.storage)
+        .bind(&object.path)
+        .bind(object.category)
+        .bind(object.size)
+        .bind(owner_from_key(&object.path))
+        .execute(&state.db)
+        .await
+        .map_err(ApiError::DatabaseError)?;
+    }
+
+    // Forget orphans that are gone or referenced again, but only in storage we could see
+    let tracked: Vec<(String, String)> =
+        sqlx::query_as("SELECT storage, path FROM orphaned_files WHERE deleted_at IS NULL")
+            .fetch_all(&state.db)
+            .await
+            .map_err(ApiError::DatabaseError)?;
+    for (storage_kind, path) in tracked {
+        let visible = storage_kind == STORAGE_LOCAL || s3_checked;
+        if visible && !orphan_paths.contains(&(storage_kind.as_str(), path.as_str())) {
+            sqlx::query("DELETE FROM orphaned_files WHERE storage = ? AND path = ?")
+                .bind(&storage_kind)
+                .bind(&path)
+                .execute(&state.db)
+                .await
+                .map_err(ApiError::DatabaseError)?;
+        }
+    }
+
+    let mut deleted = 0;
+    let mut kept_for_legal_hold = 0;
+    if delete {
+        let due = sqlx::query_as::<_, OrphanedFile>(
+            "SELECT * FROM orphaned_files WHERE deleted_at IS NULL AND first_seen_at <= datetime('now', ?)",
+        )
+        .bind(format!("-{} days", grace_days(state).await))
+        .fetch_all(&state.db)
+        .await
+        .map_err(ApiError::DatabaseError)?;
+
+        for orphan in due {
+            if let Some(owner_id) = &orphan.owner_id {
+                let hold = legal_hold::hold_for_user(&state.db, owner_id)
+                    .await
+                    .map_err(ApiError::DatabaseError)?;
+                if hold.is_some() {
+                    kept_for_legal_hold += 1;
+                    continue;
+                }
+            }
+
+            match delete_object(state, &orphan).await {
+                Ok(()) => {
+                    sqlx::query(
+                        "UPDATE orphaned_files SET deleted_at = datetime('now') WHERE storage = ? AND path = ?",
+                    )
+                    .bind(&orphan.storage)
+                    .bind(&orphan.path)
+                    .execute(&state.db)
+                    .await
+                    .map_err(ApiError::DatabaseError)?;
+                    deleted += 1;
+                }
+                Err(e) => {
+                    error!(error = %e, storage = %orphan.storage, path = %orphan.path, "Failed to delete orphaned file");
+                }
+            }
+        }
+    }
+
+    let orphaned = list_orphans(&state.db)
+        .await
+        .map_err(ApiError::DatabaseError)?;
+
+    info!(
+        scanned = found.len(),
+        orphaned = orphaned.len(),
+        missing = missing.len(),
+        deleted = deleted,
+        kept_for_legal_hold = kept_for_legal_hold,
+        "Orphaned file scan complete"
+    );
+
+    Ok(OrphanScanReport {
+        scanned_files: found.len(),
+        orphaned,
+        missing,
+        deleted,
+        kept_for_legal_hold,
+        s3_checked,
+    })
+}
+
+/// Scan every `orphan_gc_interval_hours` (default 24; 0 disables). Orphans are only
+/// deleted when `orphan_gc_auto_delete` is `true`; otherwise they are just reported.
+pub fn start_orphan_scan_task(state_lock: Arc<RwLock<AppState>>) {
+    tokio::spawn(async move {
+        loop {
+            let state = state_lock.read().await.clone();
+            let hours = state
+                .settings_service
+                .get_setting("orphan_gc_interval_hours")
+                .await
+                .ok()
+                .flatten()
+                .and_then(|v| v.parse::<u64>().ok())
+                .unwrap_or(DEFAULT_INTERVAL_HOURS);
+
+            if !crate::common::shutdown::sleep(std::time::Duration::from_secs(
+                hours.clamp(1, 24 * 30) * 3600,
+            ))
+            .await
+            {
+                break;
+            }
+            let _running = crate::common::shutdown::track();
+
+            if hours == 0 {
+                continue;
+            }
+
+            let auto_delete = state
+                .settings_service
+                .get_setting("orphan_gc_auto_delete")
+                .await
+                .ok()
+                .flatten()
+                .map(|v| v == "true")
+                .unwrap_or(false);
+
+            if let Err(e) = scan(&state, auto_delete).await {
+                warn!(error = %e, "Orphaned file scan failed");
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_owner_from_key() {
+        assert_eq!(
+            owner_from_key("videos/user-U_1/V_1.mp4"),
+            Some("U_1".to_string())
+        );
+        assert_eq!(owner_from_key("resumes/R_1.pdf"), None);
+        assert_eq!(owner_from_key("videos/user-/V_1.mp4"), None);
+    }
+
+    #[test]
+    fn test_is_referenced() {
+        let mut refs = References::default();
+        refs.resume_files.insert("R_1.pdf".to_string());
+        refs.attachment_paths
+            .insert("attachments/abc_cv.pdf".to_string());
+        refs.video_keys.insert("videos/user-U/V.mp4".to_string());
+        refs.asset_names.insert("logo_C_1_ab12.png".to_string());
+
+        assert!(refs.is_referenced("resumes/R_1.pdf"));
+        assert!(!refs.is_referenced("resumes/R_2.pdf"));
+        assert!(refs.is_referenced("attachments/abc_cv.pdf"));
+        assert!(!refs.is_referenced("attachments/zzz_cv.pdf"));
+        assert!(refs.is_referenced("videos/user-U/V.mp4"));
+        assert!(!refs.is_referenced("videos/user-U/W.mp4"));
+        assert!(refs.is_referenced("job-images/logos/logo_C_1_ab12.png"));
+        assert!(!refs.is_referenced("job-images/jobs/other.png"));
+        // Paths outside the scanned areas are never treated as orphans
+        assert!(refs.is_referenced("documents/D_1.bin"));
+    }
+}
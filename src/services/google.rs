@@ -1,4 +1,5 @@
 // src/services/google.rs
+use crate::services::resilience::{self, Dependency, ExternalError};
 use crate::services::settings::{SettingsError, SettingsService};
 use chrono::{DateTime, Utc};
 use reqwest::Client;
@@ -32,6 +33,34 @@ pub enum GoogleError {
 
     #[error("Serialization error: {0}")]
     SerializationError(String),
+
+    #[error("Google unavailable: {0}")]
+    Unavailable(String),
+}
+
+impl GoogleError {
+    /// The error for a failed Calendar API response: 5xx and 429 mean Google is struggling
+    fn from_calendar_status(status: reqwest::StatusCode, error_text: String) -> Self {
+        let message = format!("HTTP {}: {}", status, error_text);
+        if status.is_server_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            GoogleError::Unavailable(message)
+        } else {
+            GoogleError::CalendarError(message)
+        }
+    }
+}
+
+impl ExternalError for GoogleError {
+    fn is_transient(&self) -> bool {
+        matches!(
+            self,
+            GoogleError::RequestFailed(_) | GoogleError::Unavailable(_)
+        )
+    }
+
+    fn unavailable(message: String) -> Self {
+        GoogleError::Unavailable(message)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -61,6 +90,16 @@ pub struct CalendarEventResponse {
     pub hangout_link: Option<String>, // Google Meet link
 }
 
+/// Current state of an event as it exists in Google Calendar
+#[derive(Debug, Clone, Serialize)]
+pub struct ExternalCalendarEvent {
+    pub id: String,
+    pub start: Option<DateTime<Utc>>,
+    pub end: Option<DateTime<Utc>>,
+    /// Last modification time reported by Google
+    pub updated: Option<DateTime<Utc>>,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct TokenResponse {
     pub access_token: String,
@@ -77,6 +116,28 @@ pub struct TestResult {
     pub service: String,
 }
 
+/// Access token details as reported by Google's tokeninfo endpoint
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TokenInfo {
+    pub email: Option<String>,
+    pub scope: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_expires_in")]
+    pub expires_in: Option<i64>,
+}
+
+/// tokeninfo returns `expires_in` as a string, so accept either form
+fn deserialize_expires_in<'de, D>(deserializer: D) -> Result<Option<i64>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let value: Option<serde_json::Value> = Option::deserialize(deserializer)?;
+    Ok(match value {
+        Some(serde_json::Value::Number(n)) => n.as_i64(),
+        Some(serde_json::Value::String(s)) => s.parse().ok(),
+        _ => None,
+    })
+}
+
 // Google Calendar API request/response types
 #[derive(Debug, Serialize)]
 struct CalendarEventRequest {
@@ -151,6 +212,29 @@ struct EntryPoint {
     label: Option<String>,
 }
 
+#[derive(Debug, Deserialize)]
+struct EventLookupResponse {
+    id: String,
+    status: Option<String>,
+    start: Option<EventTimeResponse>,
+    end: Option<EventTimeResponse>,
+    updated: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct EventTimeResponse {
+    #[serde(rename = "dateTime")]
+    date_time: Option<String>,
+}
+
+impl EventTimeResponse {
+    fn parse(time: Option<&EventTimeResponse>) -> Option<DateTime<Utc>> {
+        time.and_then(|t| t.date_time.as_deref())
+            .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+            .map(|dt| dt.with_timezone(&Utc))
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct GoogleService {
     settings_service: Arc<SettingsService>,
@@ -170,16 +254,19 @@ impl GoogleService {
         }
     }
 
-    /// Sync Google OAuth credentials from environment variables to database
+    /// Sync Google OAuth credentials from the configuration (GOOGLE_CLIENT_ID and
+    /// GOOGLE_CLIENT_SECRET) to database
     /// This is called on startup to ensure env vars are available in the settings UI
-    pub async fn sync_env_to_settings(&self) -> Result<(), GoogleError> {
-        use std::env;
-
+    pub async fn sync_env_to_settings(
+        &self,
+        client_id: Option<&str>,
+        client_secret: Option<&str>,
+    ) -> Result<(), GoogleError> {
         // Sync GOOGLE_CLIENT_ID
-        if let Ok(client_id) = env::var("GOOGLE_CLIENT_ID") {
+        if let Some(client_id) = client_id {
             if let Err(e) = self
                 .settings_service
-                .set_setting("google_client_id", &client_id, false, None)
+                .set_setting("google_client_id", client_id, false, None)
                 .await
             {
                 warn!("Failed to sync GOOGLE_CLIENT_ID to database: {}", e);
@@ -189,10 +276,10 @@ impl GoogleService {
         }
 
         // Sync GOOGLE_CLIENT_SECRET (encrypted)
-        if let Ok(client_secret) = env::var("GOOGLE_CLIENT_SECRET") {
+        if let Some(client_secret) = client_secret {
             if let Err(e) = self
                 .settings_service
-                .set_setting("google_client_secret", &client_secret, true, None)
+                .set_setting("google_client_secret", client_secret, true, None)
                 .await
             {
                 warn!("Failed to sync GOOGLE_CLIENT_SECRET to database: {}", e);
@@ -561,6 +648,14 @@ impl GoogleService {
     pub async fn create_calendar_event(
         &self,
         event: CalendarEvent,
+    ) -> Result<CalendarEventResponse, GoogleError> {
+        // A timed-out insert may still have created the event and invited everyone
+        resilience::call_once(Dependency::Google, self.create_calendar_event_once(event)).await
+    }
+
+    async fn create_calendar_event_once(
+        &self,
+        event: CalendarEvent,
     ) -> Result<CalendarEventResponse, GoogleError> {
         let access_token = self.get_valid_access_token().await?;
 
@@ -680,10 +775,7 @@ impl GoogleService {
                 error = %error_text,
                 "Calendar event creation failed - check OAuth scopes and permissions"
             );
-            return Err(GoogleError::CalendarError(format!(
-                "HTTP {}: {}",
-                status, error_text
-            )));
+            return Err(GoogleError::from_calendar_status(status, error_text));
         }
 
         // Get response text for logging before parsing
@@ -734,6 +826,110 @@ impl GoogleService {
         Ok(result)
     }
 
+    /// Fetch an event from the primary calendar.
+    ///
+    /// Returns `Ok(None)` when the event was deleted or cancelled in Google Calendar.
+    pub async fn get_calendar_event(
+        &self,
+        event_id: &str,
+    ) -> Result<Option<ExternalCalendarEvent>, GoogleError> {
+        resilience::call(Dependency::Google, || self.get_calendar_event_once(event_id)).await
+    }
+
+    async fn get_calendar_event_once(
+        &self,
+        event_id: &str,
+    ) -> Result<Option<ExternalCalendarEvent>, GoogleError> {
+        let access_token = self.get_valid_access_token().await?;
+
+        let url = format!(
+            "https://www.googleapis.com/calendar/v3/calendars/primary/events/{}",
+            event_id
+        );
+        let response = self
+            .client
+            .get(&url)
+            .bearer_auth(&access_token)
+            .send()
+            .await
+            .map_err(|e| GoogleError::RequestFailed(e.to_string()))?;
+
+        let status = response.status();
+        if status == reqwest::StatusCode::NOT_FOUND || status == reqwest::StatusCode::GONE {
+            debug!(event_id = %event_id, status = %status, "Calendar event no longer exists");
+            return Ok(None);
+        }
+
+        if !status.is_success() {
+            let error_text = response
+                .text()
+                .await
+                .unwrap_or_else(|_| "Unknown error".to_string());
+            warn!(
+                event_id = %event_id,
+                status = %status,
+                error = %error_text,
+                "Calendar event lookup failed"
+            );
+            return Err(GoogleError::from_calendar_status(status, error_text));
+        }
+
+        let event = response
+            .json::<EventLookupResponse>()
+            .await
+            .map_err(|e| GoogleError::SerializationError(e.to_string()))?;
+
+        if event.status.as_deref() == Some("cancelled") {
+            debug!(event_id = %event_id, "Calendar event was cancelled");
+            return Ok(None);
+        }
+
+        Ok(Some(ExternalCalendarEvent {
+            id: event.id,
+            start: EventTimeResponse::parse(event.start.as_ref()),
+            end: EventTimeResponse::parse(event.end.as_ref()),
+            updated: event
+                .updated
+                .as_deref()
+                .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+                .map(|dt| dt.with_timezone(&Utc)),
+        }))
+    }
+
+    /// Validate the stored access token (refreshing if necessary) against Google's tokeninfo endpoint
+    pub async fn check_token(&self) -> Result<TokenInfo, GoogleError> {
+        let access_token = self.get_valid_access_token().await?;
+
+        let response = self
+            .client
+            .get("https://oauth2.googleapis.com/tokeninfo")
+            .query(&[("access_token", access_token.as_str())])
+            .send()
+            .await
+            .map_err(|e| GoogleError::RequestFailed(e.to_string()))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let error_text = response
+                .text()
+                .await
+                .unwrap_or_else(|_| "Unknown error".to_string());
+            warn!(status = %status, error = %error_text, "Google token check failed");
+            return Err(GoogleError::OAuthFailed(format!(
+                "HTTP {}: {}",
+                status, error_text
+            )));
+        }
+
+        let info = response
+            .json::<TokenInfo>()
+            .await
+            .map_err(|e| GoogleError::SerializationError(e.to_string()))?;
+
+        debug!(email = ?info.email, expires_in = ?info.expires_in, "Google token check succeeded");
+        Ok(info)
+    }
+
     /// Test Google Calendar API connection
     pub async fn test_connection(&self) -> Result<TestResult, GoogleError> {
         match self.get_valid_access_token().await {
@@ -847,4 +1043,18 @@ mod tests {
         assert!(auth_url.contains("redirect_uri=http"));
         assert!(auth_url.contains("scope="));
     }
+
+    #[test]
+    fn test_token_info_accepts_string_expires_in() {
+        let info: TokenInfo = serde_json::from_str(
+            r#"{"email": "admin@example.com", "scope": "openid email", "expires_in": "3599"}"#,
+        )
+        .unwrap();
+        assert_eq!(info.expires_in, Some(3599));
+        assert_eq!(info.email.as_deref(), Some("admin@example.com"));
+
+        let info: TokenInfo = serde_json::from_str(r#"{"expires_in": 120}"#).unwrap();
+        assert_eq!(info.expires_in, Some(120));
+        assert!(info.scope.is_none());
+    }
 }
@@ -0,0 +1,250 @@
+// src/services/guest_apply.rs
+//! Applying without an account
+//!
+//! A guest gives a name, an email and a resume. They get a provisional user that owns the
+//! application like any other, but has no way to sign in; an emailed link claims it. If
+//! the email already belongs to an account by then, the provisional account is merged
+//! into it, otherwise it becomes a regular account with that email. A guest applying
+//! again before claiming reuses the same provisional account.
+
+use sqlx::SqlitePool;
+use tracing::info;
+
+use crate::auth::models::User;
+use crate::common::{generate_guest_claim_id, generate_raw_id, generate_user_id, ApiError};
+use crate::services::account_linking::{self, MergeSummary};
+use crate::services::calendar_feed::hash_token;
+
+/// How long a claim link stays valid
+pub const GUEST_CLAIM_TTL_DAYS: i64 = 7;
+
+pub const MAX_GUEST_NAME_LENGTH: usize = 100;
+
+const CLAIM_TOKEN_LENGTH: usize = 40;
+
+/// Domain of the addresses provisional accounts use when the real one is taken; `.invalid`
+/// never resolves, so nothing is ever delivered there
+const PLACEHOLDER_EMAIL_DOMAIN: &str = "guest.invalid";
+
+/// A claim link, as emailed to the guest
+pub struct GuestClaim {
+    pub token: String,
+    pub expires_at: String,
+}
+
+/// The account a claim link signed in to
+pub struct ClaimOutcome {
+    pub user: User,
+    /// Set when the guest's applications moved into an existing account
+    pub merged: Option<MergeSummary>,
+}
+
+/// Trimmed name and lowercased email, or why they can't be used
+pub fn validate_guest(name: &str, email: &str) -> Result<(String, String), String> {
+    let name = name.trim();
+    if name.is_empty() || name.chars().count() > MAX_GUEST_NAME_LENGTH {
+        return Err(format!(
+            "Name must be between 1 and {} characters",
+            MAX_GUEST_NAME_LENGTH
+        ));
+    }
+    let email = email.trim().to_lowercase();
+    let valid = match email.split_once('@') {
+        Some((local, domain)) => {
+            !local.is_empty()
+                && domain.contains('.')
+                && !domain.starts_with('.')
+                && !domain.ends_with('.')
+                && !email.contains(char::is_whitespace)
+                && !domain.ends_with(PLACEHOLDER_EMAIL_DOMAIN)
+        }
+        None => false,
+    };
+    if !valid {
+        return Err("A valid email address is required".to_string());
+    }
+    Ok((name.to_string(), email))
+}
+
+pub fn placeholder_email(user_id: &str) -> String {
+    format!("{}@{}", user_id.to_lowercase(), PLACEHOLDER_EMAIL_DOMAIN)
+}
+
+/// The guest's unclaimed provisional account, created if they don't have one yet
+pub async fn provisional_user(
+    pool: &SqlitePool,
+    name: &str,
+    email: &str,
+    locale: &str,
+) -> Result<String, sqlx::Error> {
+    let existing: Option<String> = sqlx::query_scalar(
+        r#"
+        SELECT u.id FROM guest_claims c
+        JOIN users u ON u.id = c.user_id
+        WHERE c.email = ? AND u.is_provisional = 1 AND u.merged_into IS NULL
+        ORDER BY c.created_at DESC
+        LIMIT 1
+        "#,
+    )
+    .bind(email)
+    .fetch_optional(pool)
+    .await?;
+    if let Some(user_id) = existing {
+        return Ok(user_id);
+    }
+
+    let id = generate_user_id();
+    let taken: bool =
+        sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM users WHERE lower(email) = ?)")
+            .bind(email)
+            .fetch_one(pool)
+            .await?;
+    let account_email = if taken {
+        placeholder_email(&id)
+    } else {
+        email.to_string()
+    };
+    sqlx::query(
+        r#"
+        INSERT INTO users (id, email, name, provider, locale, is_provisional)
+        VALUES (?, ?, ?, 'guest', ?, 1)
+        "#,
+    )
+    .bind(&id)
+    .bind(&account_email)
+    .bind(name)
+    .bind(locale)
+    .execute(pool)
+    .await?;
+
+    info!(user_id = %id, email_taken = taken, "Provisional guest account created");
+    Ok(id)
+}
+
+/// A new claim link for the provisional account
+pub async fn issue_claim(
+    pool: &SqlitePool,
+    user_id: &str,
+    email: &str,
+) -> Result<GuestClaim, sqlx::Error> {
+    let token = generate_raw_id(CLAIM_TOKEN_LENGTH);
+    let expires_at: String = sqlx::query_scalar(
+        r#"
+        INSERT INTO guest_claims (id, user_id, email, token_hash, expires_at)
+        VALUES (?, ?, ?, ?, datetime('now', ?))
+        RETURNING expires_at
+        "#,
+    )
+    .bind(generate_guest_claim_id())
+    .bind(user_id)
+    .bind(email)
+    .bind(hash_token(&token))
+    .bind(format!("+{} days", GUEST_CLAIM_TTL_DAYS))
+    .fetch_one(pool)
+    .await?;
+    Ok(GuestClaim { token, expires_at })
+}
+
+/// Claim the provisional account a link was sent for, merging it into an existing account
+/// with the same email
+pub async fn claim(pool: &SqlitePool, token: &str) -> Result<ClaimOutcome, ApiError> {
+    let invalid = || ApiError::NotFound("Claim link is invalid or has expired".to_string());
+
+    let (claim_id, user_id, email): (String, String, String) = sqlx::query_as(
+        r#"
+        SELECT c.id, c.user_id, c.email FROM guest_claims c
+        JOIN users u ON u.id = c.user_id
+        WHERE c.token_hash = ? AND c.claimed_at IS NULL AND c.expires_at > datetime('now')
+          AND u.is_provisional = 1 AND u.merged_into IS NULL
+        "#,
+    )
+    .bind(hash_token(token))
+    .fetch_optional(pool)
+    .await
+    .map_err(ApiError::DatabaseError)?
+    .ok_or_else(invalid)?;
+
+    let existing = sqlx::query_as::<_, User>(
+        r#"
+        SELECT * FROM users
+        WHERE lower(email) = ? AND id != ? AND merged_into IS NULL
+        ORDER BY created_at
+        LIMIT 1
+        "#,
+    )
+    .bind(&email)
+    .bind(&user_id)
+    .fetch_optional(pool)
+    .await
+    .map_err(ApiError::DatabaseError)?;
+
+    let (claimed_by, merged) = match existing {
+        Some(account) => {
+            let summary = account_linking::merge_verified(pool, &user_id, &account.id)
+                .await
+                .map_err(ApiError::DatabaseError)?;
+            (account.id, Some(summary))
+        }
+        None => {
+            sqlx::query(
+                "UPDATE users SET email = ?, provider = 'email', is_provisional = 0 WHERE id = ?",
+            )
+            .bind(&email)
+            .bind(&user_id)
+            .execute(pool)
+            .await
+            .map_err(ApiError::DatabaseError)?;
+            (user_id.clone(), None)
+        }
+    };
+
+    sqlx::query(
+        "UPDATE guest_claims SET claimed_at = datetime('now'), claimed_by = ? WHERE user_id = ? AND claimed_at IS NULL",
+    )
+    .bind(&claimed_by)
+    .bind(&user_id)
+    .execute(pool)
+    .await
+    .map_err(ApiError::DatabaseError)?;
+
+    let user = sqlx::query_as::<_, User>("SELECT * FROM users WHERE id = ?")
+        .bind(&claimed_by)
+        .fetch_one(pool)
+        .await
+        .map_err(ApiError::DatabaseError)?;
+
+    info!(
+        claim_id = %claim_id,
+        provisional_user_id = %user_id,
+        user_id = %user.id,
+        merged = merged.is_some(),
+        "Guest account claimed"
+    );
+    Ok(ClaimOutcome { user, merged })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_guest() {
+        assert_eq!(
+            validate_guest("  Ada Lovelace ", " Ada@Example.com "),
+            Ok(("Ada Lovelace".to_string(), "ada@example.com".to_string()))
+        );
+        assert!(validate_guest("", "ada@example.com").is_err());
+        assert!(validate_guest(&"a".repeat(101), "ada@example.com").is_err());
+        assert!(validate_guest("Ada", "ada.example.com").is_err());
+        assert!(validate_guest("Ada", "ada@localhost").is_err());
+        assert!(validate_guest("Ada", "ada @example.com").is_err());
+        assert!(validate_guest("Ada", "u_1@guest.invalid").is_err());
+    }
+
+    #[test]
+    fn test_placeholder_email() {
+        let email = placeholder_email("U_01ABC");
+        assert_eq!(email, "u_01abc@guest.invalid");
+        assert!(validate_guest("Ada", &email).is_err());
+    }
+}
@@ -0,0 +1,423 @@
+// src/services/hiring_metrics.rs
+//! Time-to-hire and recruiter performance metrics
+//!
+//! Everything is derived from `application_status_history`: a status lasts from its history
+//! entry until the next one. Timelines are computed ahead of time by the analytics refresh
+//! (see `analytics_facts`) and grouped here. The cohort is the applications submitted in the
+//! requested date range, so a slow month shows up in that month's numbers even once it has
+//! caught up.
+//! Recruiters are credited with the jobs they are assigned to.
+
+use chrono::NaiveDateTime;
+use serde::Serialize;
+use sqlx::SqlitePool;
+use std::collections::{BTreeMap, HashMap};
+
+use crate::services::analytics_facts::{self, ApplicationFact};
+use crate::services::sla::parse_timestamp;
+
+/// The status an application is created with, before anyone has reviewed it
+const INITIAL_STATUS: &str = "submitted";
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct StatusChange {
+    pub application_id: String,
+    pub status: String,
+    pub changed_at: Option<String>,
+}
+
+/// What one application's history says about how it moved through the pipeline
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ApplicationTimeline {
+    pub days_to_first_review: Option<f64>,
+    pub days_to_hire: Option<f64>,
+    /// Completed stints only; the status an application is still in is not counted
+    pub days_in_status: Vec<(String, f64)>,
+    pub offered: bool,
+    /// Hired after being offered
+    pub offer_accepted: bool,
+    pub hired: bool,
+    pub interviews: i64,
+}
+
+impl ApplicationTimeline {
+    /// The timeline as stored by the analytics refresh
+    pub fn from_fact(fact: &ApplicationFact, days_in_status: Vec<(String, f64)>) -> Self {
+        ApplicationTimeline {
+            days_to_first_review: fact.days_to_first_review,
+            days_to_hire: fact.days_to_hire,
+            days_in_status,
+            offered: fact.offered_at.is_some(),
+            offer_accepted: fact.offer_accepted,
+            hired: fact.hired_at.is_some(),
+            interviews: fact.interviews,
+        }
+    }
+}
+
+fn days_between(from: NaiveDateTime, to: NaiveDateTime) -> f64 {
+    (to - from).num_seconds().max(0) as f64 / 86_400.0
+}
+
+/// Read an application's timeline from its status changes, oldest first
+pub fn timeline(
+    applied_at: Option<&str>,
+    changes: &[StatusChange],
+    interviews: i64,
+) -> ApplicationTimeline {
+    let applied_at = applied_at.and_then(parse_timestamp);
+    let changes: Vec<(&str, NaiveDateTime)> = changes
+        .iter()
+        .filter_map(|c| {
+            Some((
+                c.status.as_str(),
+                parse_timestamp(c.changed_at.as_deref()?)?,
+            ))
+        })
+        .collect();
+
+    let mut result = ApplicationTimeline {
+        interviews,
+        ..Default::default()
+    };
+
+    if let Some(applied_at) = applied_at {
+        result.days_to_first_review = changes
+            .iter()
+            .find(|(status, _)| *status != INITIAL_STATUS)
+            .map(|(_, at)| days_between(applied_at, *at));
+        result.days_to_hire = changes
+            .iter()
+            .find(|(status, _)| *status == "hired")
+            .map(|(_, at)| days_between(applied_at, *at));
+    }
+
+    for pair in changes.windows(2) {
+        let (status, entered) = pair[0];
+        let (_, left) = pair[1];
+        // Repeated entries for the same status (e.g. a re-save) are one stint
+        if status != pair[1].0 {
+            result
+                .days_in_status
+                .push((status.to_string(), days_between(entered, left)));
+        }
+    }
+
+    let offered_at = changes.iter().position(|(status, _)| *status == "offered");
+    result.offered = offered_at.is_some();
+    result.hired = changes.iter().any(|(status, _)| *status == "hired");
+    result.offer_accepted = offered_at.is_some_and(|offered| {
+        changes[offered..]
+            .iter()
+            .any(|(status, _)| *status == "hired")
+    });
+    result
+}
+
+/// Metrics for a group of applications: one job, one recruiter, or all of them
+#[derive(Debug, Default, Clone, Serialize, PartialEq)]
+pub struct HiringMetrics {
+    pub applications: i64,
+    pub reviewed: i64,
+    pub avg_days_to_first_review: Option<f64>,
+    pub hires: i64,
+    pub avg_days_to_hire: Option<f64>,
+    pub offers_extended: i64,
+    pub offers_accepted: i64,
+    pub offer_acceptance_rate: Option<f64>,
+    pub interviews: i64,
+    pub interviews_per_hire: Option<f64>,
+    /// Average completed days spent in each status
+    pub avg_days_in_status: BTreeMap<String, f64>,
+}
+
+fn round2(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
+
+fn average(values: &[f64]) -> Option<f64> {
+    (!values.is_empty()).then(|| round2(values.iter().sum::<f64>() / values.len() as f64))
+}
+
+/// Combine application timelines into a group's metrics
+pub fn aggregate<'a>(
+    timelines: impl IntoIterator<Item = &'a ApplicationTimeline>,
+) -> HiringMetrics {
+    let mut metrics = HiringMetrics::default();
+    let mut review_days = Vec::new();
+    let mut hire_days = Vec::new();
+    let mut status_days: BTreeMap<String, Vec<f64>> = BTreeMap::new();
+
+    for timeline in timelines {
+        metrics.applications += 1;
+        metrics.interviews += timeline.interviews;
+        if let Some(days) = timeline.days_to_first_review {
+            metrics.reviewed += 1;
+            review_days.push(days);
+        }
+        if timeline.hired {
+            metrics.hires += 1;
+        }
+        if let Some(days) = timeline.days_to_hire {
+            hire_days.push(days);
+        }
+        if timeline.offered {
+            metrics.offers_extended += 1;
+        }
+        if timeline.offer_accepted {
+            metrics.offers_accepted += 1;
+        }
+        for (status, days) in &timeline.days_in_status {
+            status_days.entry(status.clone()).or_default().push(*days);
+        }
+    }
+
+    metrics.avg_days_to_first_review = average(&review_days);
+    metrics.avg_days_to_hire = average(&hire_days);
+    metrics.offer_acceptance_rate = (metrics.offers_extended > 0)
+        .then(|| round2(metrics.offers_accepted as f64 / metrics.offers_extended as f64));
+    metrics.interviews_per_hire =
+        (metrics.hires > 0).then(|| round2(metrics.interviews as f64 / metrics.hires as f64));
+    metrics.avg_days_in_status = status_days
+        .into_iter()
+        .filter_map(|(status, days)| Some((status, average(&days)?)))
+        .collect();
+    metrics
+}
+
+#[derive(Debug, Serialize)]
+pub struct JobPerformance {
+    pub job_id: String,
+    pub job_title: Option<String>,
+    #[serde(flatten)]
+    pub metrics: HiringMetrics,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RecruiterPerformance {
+    pub user_id: String,
+    pub name: Option<String>,
+    pub jobs: i64,
+    #[serde(flatten)]
+    pub metrics: HiringMetrics,
+}
+
+#[derive(Debug, Serialize)]
+pub struct HiringPerformanceReport {
+    /// When the analytics facts these numbers come from were last refreshed
+    pub as_of: Option<String>,
+    pub from: String,
+    pub to: String,
+    pub overall: HiringMetrics,
+    pub jobs: Vec<JobPerformance>,
+    pub recruiters: Vec<RecruiterPerformance>,
+}
+
+/// Metrics for applications submitted between two dates (inclusive, `YYYY-MM-DD`),
+/// optionally for one job. Reads the analytics facts, which the caller makes sure exist with
+/// [`analytics_facts::ensure_built`] on the primary pool.
+pub async fn hiring_performance(
+    pool: &SqlitePool,
+    from: &str,
+    to: &str,
+    job_id: Option<&str>,
+) -> Result<HiringPerformanceReport, sqlx::Error> {
+    let facts = sqlx::query_as::<_, ApplicationFact>(
+        r#"
+        SELECT * FROM analytics_application_facts
+        WHERE date(applied_at) BETWEEN ? AND ?
+          AND (? IS NULL OR job_id = ?)
+        "#,
+    )
+    .bind(from)
+    .bind(to)
+    .bind(job_id)
+    .bind(job_id)
+    .fetch_all(pool)
+    .await?;
+
+    let mut durations: HashMap<String, Vec<(String, f64)>> = HashMap::new();
+    for (application_id, status, days) in sqlx::query_as::<_, (String, String, f64)>(
+        r#"
+        SELECT d.application_id, d.status, d.days
+        FROM analytics_status_durations d
+        JOIN analytics_application_facts f ON f.application_id = d.application_id
+        WHERE date(f.applied_at) BETWEEN ? AND ?
+          AND (? IS NULL OR f.job_id = ?)
+        "#,
+    )
+    .bind(from)
+    .bind(to)
+    .bind(job_id)
+    .bind(job_id)
+    .fetch_all(pool)
+    .await?
+    {
+        durations
+            .entry(application_id)
+            .or_default()
+            .push((status, days));
+    }
+
+    let mut by_job: BTreeMap<String, Vec<ApplicationTimeline>> = BTreeMap::new();
+    for fact in facts {
+        let days_in_status = durations.remove(&fact.application_id).unwrap_or_default();
+        by_job
+            .entry(fact.job_id.clone())
+            .or_default()
+            .push(ApplicationTimeline::from_fact(&fact, days_in_status));
+    }
+
+    let titles: HashMap<String, String> =
+        sqlx::query_as::<_, (String, String)>("SELECT id, title FROM jobs")
+            .fetch_all(pool)
+            .await?
+            .into_iter()
+            .collect();
+
+    let overall = aggregate(by_job.values().flatten());
+    let mut jobs: Vec<JobPerformance> = by_job
+        .iter()
+        .map(|(job_id, timelines)| JobPerformance {
+            job_id: job_id.clone(),
+            job_title: titles.get(job_id).cloned(),
+            metrics: aggregate(timelines),
+        })
+        .collect();
+    jobs.sort_by(|a, b| {
+        b.metrics
+            .applications
+            .cmp(&a.metrics.applications)
+            .then_with(|| a.job_id.cmp(&b.job_id))
+    });
+
+    let assignments = sqlx::query_as::<_, (String, String, Option<String>)>(
+        r#"
+        SELECT r.user_id, r.job_id, u.name
+        FROM job_recruiters r
+        JOIN users u ON u.id = r.user_id
+        ORDER BY r.user_id
+        "#,
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let mut recruiter_jobs: BTreeMap<String, (Option<String>, Vec<String>)> = BTreeMap::new();
+    for (user_id, job_id, name) in assignments {
+        let entry = recruiter_jobs.entry(user_id).or_insert((name, Vec::new()));
+        entry.1.push(job_id);
+    }
+    let mut recruiters: Vec<RecruiterPerformance> = recruiter_jobs
+        .into_iter()
+        .map(|(user_id, (name, job_ids))| RecruiterPerformance {
+            user_id,
+            name,
+            jobs: job_ids.len() as i64,
+            metrics: aggregate(
+                job_ids
+                    .iter()
+                    .filter_map(|job_id| by_job.get(job_id))
+                    .flatten(),
+            ),
+        })
+        .filter(|r| r.metrics.applications > 0)
+        .collect();
+    recruiters.sort_by(|a, b| {
+        b.metrics
+            .hires
+            .cmp(&a.metrics.hires)
+            .then_with(|| a.user_id.cmp(&b.user_id))
+    });
+
+    Ok(HiringPerformanceReport {
+        as_of: analytics_facts::as_of(pool).await?,
+        from: from.to_string(),
+        to: to.to_string(),
+        overall,
+        jobs,
+        recruiters,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn change(status: &str, changed_at: &str) -> StatusChange {
+        StatusChange {
+            application_id: "A_1".to_string(),
+            status: status.to_string(),
+            changed_at: Some(changed_at.to_string()),
+        }
+    }
+
+    #[test]
+    fn test_timeline_from_status_history() {
+        let hired = timeline(
+            Some("2026-03-01 09:00:00"),
+            &[
+                change("submitted", "2026-03-01 09:00:00"),
+                change("reviewed", "2026-03-03 09:00:00"),
+                change("interview_scheduled", "2026-03-04 21:00:00"),
+                change("offered", "2026-03-10 09:00:00"),
+                change("hired", "2026-03-11 09:00:00"),
+            ],
+            2,
+        );
+        assert_eq!(hired.days_to_first_review, Some(2.0));
+        assert_eq!(hired.days_to_hire, Some(10.0));
+        assert_eq!(
+            hired.days_in_status,
+            vec![
+                ("submitted".to_string(), 2.0),
+                ("reviewed".to_string(), 1.5),
+                ("interview_scheduled".to_string(), 5.5),
+                ("offered".to_string(), 1.0),
+            ]
+        );
+        assert!(hired.offered && hired.offer_accepted && hired.hired);
+
+        let waiting = timeline(
+            Some("2026-03-01 09:00:00"),
+            &[change("submitted", "2026-03-01 09:00:00")],
+            0,
+        );
+        assert_eq!(waiting.days_to_first_review, None);
+        assert!(waiting.days_in_status.is_empty());
+    }
+
+    #[test]
+    fn test_aggregate_rates_and_averages() {
+        let hired = ApplicationTimeline {
+            days_to_first_review: Some(1.0),
+            days_to_hire: Some(10.0),
+            days_in_status: vec![("submitted".to_string(), 1.0)],
+            offered: true,
+            offer_accepted: true,
+            hired: true,
+            interviews: 3,
+        };
+        let declined = ApplicationTimeline {
+            days_to_first_review: Some(2.0),
+            days_in_status: vec![("submitted".to_string(), 2.0)],
+            offered: true,
+            interviews: 2,
+            ..Default::default()
+        };
+        let unreviewed = ApplicationTimeline::default();
+
+        let metrics = aggregate([&hired, &declined, &unreviewed]);
+        assert_eq!(metrics.applications, 3);
+        assert_eq!(metrics.reviewed, 2);
+        assert_eq!(metrics.avg_days_to_first_review, Some(1.5));
+        assert_eq!(metrics.avg_days_to_hire, Some(10.0));
+        assert_eq!(metrics.offers_extended, 2);
+        assert_eq!(metrics.offer_acceptance_rate, Some(0.5));
+        assert_eq!(metrics.interviews_per_hire, Some(5.0));
+        assert_eq!(metrics.avg_days_in_status.get("submitted"), Some(&1.5));
+
+        let empty = aggregate(std::iter::empty());
+        assert_eq!(empty.offer_acceptance_rate, None);
+        assert_eq!(empty.interviews_per_hire, None);
+    }
+}
@@ -0,0 +1,149 @@
+// src/services/inbox.rs
+//! Admin inbox: who owns a candidate's conversation and whether it needs attention
+//!
+//! Conversations are keyed by the candidate's user ID. One without a `conversation_states`
+//! row is open and unassigned. A snoozed conversation reads as open once its snooze runs
+//! out, and any new message from the candidate reopens a snoozed, closed or archived one.
+
+use chrono::{DateTime, Utc};
+use tracing::{debug, info};
+
+use crate::common::{ApiError, AppState};
+use crate::messages::models::WebSocketMessage;
+
+pub const INBOX_STATES: &[&str] = &["open", "snoozed", "closed"];
+
+/// Stored state, with an expired snooze read as `open`
+pub const EFFECTIVE_STATE_SQL: &str = "CASE WHEN cs.state = 'snoozed' AND cs.snoozed_until <= datetime('now') THEN 'open' ELSE COALESCE(cs.state, 'open') END";
+
+/// Check a state and, for `snoozed`, parse the time it ends into the stored UTC format
+pub fn validate_state_change(
+    state: &str,
+    snoozed_until: Option<&str>,
+    now: DateTime<Utc>,
+) -> Result<Option<String>, ApiError> {
+    if !INBOX_STATES.contains(&state) {
+        return Err(ApiError::BadRequest(format!(
+            "Invalid state '{}'; expected one of {}",
+            state,
+            INBOX_STATES.join(", ")
+        )));
+    }
+    if state != "snoozed" {
+        if snoozed_until.is_some() {
+            return Err(ApiError::BadRequest(
+                "snoozed_until only applies when snoozing".to_string(),
+            ));
+        }
+        return Ok(None);
+    }
+
+    let until = snoozed_until.ok_or_else(|| {
+        ApiError::BadRequest("snoozed_until is required when snoozing".to_string())
+    })?;
+    let until = DateTime::parse_from_rfc3339(until.trim())
+        .map_err(|_| {
+            ApiError::BadRequest(
+                "snoozed_until must be an RFC 3339 date-time, e.g. 2026-01-15T09:00:00Z"
+                    .to_string(),
+            )
+        })?
+        .with_timezone(&Utc);
+    if until <= now {
+        return Err(ApiError::BadRequest(
+            "snoozed_until must be in the future".to_string(),
+        ));
+    }
+    Ok(Some(until.format("%Y-%m-%d %H:%M:%S").to_string()))
+}
+
+/// Push an inbox event to every admin with an open connection
+pub async fn notify_admins(state: &AppState, message: WebSocketMessage) {
+    let online = state.connection_manager.get_online_users().await;
+    if online.is_empty() {
+        return;
+    }
+
+    let placeholders = vec!["?"; online.len()].join(", ");
+    let sql = format!("SELECT id, email FROM users WHERE id IN ({})", placeholders);
+    let mut query = sqlx::query_as::<_, (String, String)>(&sql);
+    for user_id in &online {
+        query = query.bind(user_id);
+    }
+    let users = match query.fetch_all(&state.db).await {
+        Ok(users) => users,
+        Err(e) => {
+            debug!(error = %e, "Failed to look up online admins for inbox event");
+            return;
+        }
+    };
+
+    let admins: Vec<String> = users
+        .into_iter()
+        .filter(|(_, email)| state.admin_emails.contains(&email.to_lowercase()))
+        .map(|(id, _)| id)
+        .collect();
+    state
+        .connection_manager
+        .broadcast_to_users(&admins, message)
+        .await;
+}
+
+/// Reopen a snoozed, closed or archived conversation when the candidate writes in
+///
+/// Never fails the caller.
+pub async fn reopen_on_reply(state: &AppState, user_id: &str) {
+    let result = sqlx::query(
+        r#"
+        UPDATE conversation_states
+        SET state = 'open', snoozed_until = NULL, archived_at = NULL, updated_by = ?,
+            updated_at = datetime('now')
+        WHERE user_id = ? AND (state != 'open' OR archived_at IS NOT NULL)
+        "#,
+    )
+    .bind(user_id)
+    .bind(user_id)
+    .execute(&state.db)
+    .await;
+
+    match result {
+        Ok(result) if result.rows_affected() > 0 => {
+            info!(conversation_user_id = %user_id, "Conversation reopened by candidate reply");
+            notify_admins(
+                state,
+                WebSocketMessage::ConversationStateChanged {
+                    conversation_id: user_id.to_string(),
+                    state: "open".to_string(),
+                    snoozed_until: None,
+                    changed_by: user_id.to_string(),
+                },
+            )
+            .await;
+        }
+        Ok(_) => {}
+        Err(e) => {
+            debug!(error = %e, conversation_user_id = %user_id, "Failed to reopen conversation")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_validate_state_change() {
+        let now = Utc.with_ymd_and_hms(2026, 3, 2, 12, 0, 0).unwrap();
+
+        assert_eq!(validate_state_change("open", None, now).unwrap(), None);
+        assert_eq!(
+            validate_state_change("snoozed", Some("2026-03-03T09:00:00+05:30"), now).unwrap(),
+            Some("2026-03-03 03:30:00".to_string())
+        );
+        assert!(validate_state_change("snoozed", None, now).is_err());
+        assert!(validate_state_change("snoozed", Some("2026-03-01T09:00:00Z"), now).is_err());
+        assert!(validate_state_change("closed", Some("2026-03-03T09:00:00Z"), now).is_err());
+        assert!(validate_state_change("archived", None, now).is_err());
+    }
+}
@@ -0,0 +1,367 @@
+// src/services/interview_artifacts.rs
+//! Shared notes, recordings and transcripts kept for a completed interview
+//!
+//! Only the hiring team sees them: the interview's panelists and creator, and the job's
+//! hiring manager and recruiters. When a job has nobody assigned, every admin counts as
+//! its hiring team. Files go through the storage backend and are downloaded with signed
+//! links from the file gateway.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Duration, Utc};
+use sqlx::SqlitePool;
+use tracing::warn;
+
+use crate::auth::AuthedUser;
+use crate::candidates::models::{
+    Interview, InterviewArtifact, InterviewPanelMember, InterviewRecord, InterviewRecordEntry,
+};
+use crate::common::timezone::parse_stored;
+use crate::common::ApiError;
+use crate::services::org;
+
+pub const ARTIFACT_KINDS: &[&str] = &["recording", "transcript"];
+
+/// S3 prefix for artifacts; locally they live in `documents_dir`
+pub const ARTIFACTS_PREFIX: &str = "interview-artifacts";
+
+pub const MAX_RECORDING_SIZE: usize = 100 * 1024 * 1024;
+
+pub const MAX_TRANSCRIPT_SIZE: usize = 10 * 1024 * 1024;
+
+pub const MAX_SHARED_NOTES_LENGTH: usize = 20_000;
+
+/// Plain-text transcript formats, recognised by file extension
+const TEXT_TRANSCRIPTS: &[(&str, &str)] = &[
+    ("txt", "text/plain"),
+    ("md", "text/markdown"),
+    ("vtt", "text/vtt"),
+    ("srt", "application/x-subrip"),
+];
+
+const DOCUMENT_TRANSCRIPTS: &[&str] = &[
+    "application/pdf",
+    "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+];
+
+/// Whether an interview has ended; cancelled interviews are deleted, so any other has happened
+pub fn is_completed(interview: &Interview, now: DateTime<Utc>) -> bool {
+    parse_stored(&interview.scheduled_date)
+        .map(|start| start + Duration::minutes(interview.duration_minutes as i64) <= now)
+        .unwrap_or(false)
+}
+
+/// Whether `email` is on an interview's panel, given the stored `panel_members` JSON
+pub fn is_panelist(panel_members: &str, email: &str) -> bool {
+    serde_json::from_str::<Vec<InterviewPanelMember>>(panel_members)
+        .unwrap_or_default()
+        .iter()
+        .any(|member| member.email.trim().eq_ignore_ascii_case(email.trim()))
+}
+
+/// Check an uploaded recording or transcript, returning its content type and file extension
+pub fn validate_artifact_upload(
+    kind: &str,
+    data: &[u8],
+    filename: Option<&str>,
+) -> Result<(&'static str, &'static str), ApiError> {
+    if !ARTIFACT_KINDS.contains(&kind) {
+        return Err(ApiError::ValidationError(format!(
+            "Invalid kind '{}'; expected one of {}",
+            kind,
+            ARTIFACT_KINDS.join(", ")
+        )));
+    }
+    if data.is_empty() {
+        return Err(ApiError::ValidationError(
+            "Uploaded file is empty".to_string(),
+        ));
+    }
+    let max_size = if kind == "recording" {
+        MAX_RECORDING_SIZE
+    } else {
+        MAX_TRANSCRIPT_SIZE
+    };
+    if data.len() > max_size {
+        return Err(ApiError::ValidationError(format!(
+            "File too large. Maximum size is {}MB",
+            max_size / 1024 / 1024
+        )));
+    }
+
+    let detected = infer::get(data);
+    if kind == "recording" {
+        return match detected {
+            Some(kind)
+                if kind.mime_type().starts_with("audio/")
+                    || kind.mime_type().starts_with("video/") =>
+            {
+                Ok((kind.mime_type(), kind.extension()))
+            }
+            _ => Err(ApiError::ValidationError(
+                "Unsupported recording. Upload an audio or video file".to_string(),
+            )),
+        };
+    }
+
+    if let Some(kind) = detected.filter(|k| DOCUMENT_TRANSCRIPTS.contains(&k.mime_type())) {
+        return Ok((kind.mime_type(), kind.extension()));
+    }
+    let extension = filename
+        .and_then(|name| name.rsplit_once('.'))
+        .map(|(_, ext)| ext.to_lowercase())
+        .unwrap_or_else(|| "txt".to_string());
+    match TEXT_TRANSCRIPTS.iter().find(|(ext, _)| *ext == extension) {
+        Some((ext, content_type))
+            if detected.is_none() && std::str::from_utf8(data).is_ok() && !data.contains(&0) =>
+        {
+            Ok((content_type, ext))
+        }
+        _ => Err(ApiError::ValidationError(
+            "Unsupported transcript. Upload a PDF, DOCX, TXT, VTT or SRT file".to_string(),
+        )),
+    }
+}
+
+pub async fn load_interview(pool: &SqlitePool, interview_id: &str) -> Result<Interview, ApiError> {
+    sqlx::query_as::<_, Interview>("SELECT * FROM interviews WHERE id = ?")
+        .bind(interview_id)
+        .fetch_optional(pool)
+        .await
+        .map_err(ApiError::DatabaseError)?
+        .ok_or_else(|| ApiError::NotFound("Interview not found".to_string()))
+}
+
+/// Whether the caller is on the interview's hiring team
+pub async fn is_hiring_team(
+    pool: &SqlitePool,
+    interview: &Interview,
+    authed: &AuthedUser,
+) -> Result<bool, sqlx::Error> {
+    if interview.created_by == authed.id || is_panelist(&interview.panel_members, &authed.email) {
+        return Ok(true);
+    }
+
+    let interviewer: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM interview_interviewers WHERE interview_id = ? AND user_id = ?",
+    )
+    .bind(&interview.id)
+    .bind(&authed.id)
+    .fetch_one(pool)
+    .await?;
+    if interviewer > 0 {
+        return Ok(true);
+    }
+
+    let Some(job_id) = interview.job_id.as_deref() else {
+        return Ok(authed.is_admin);
+    };
+    let assignees = org::job_assignees(pool, job_id).await?;
+    if assignees.is_empty() {
+        return Ok(authed.is_admin);
+    }
+    Ok(assignees.iter().any(|a| a.id == authed.id))
+}
+
+/// Load an interview the caller may see the record of; anyone else is told it doesn't exist
+pub async fn require_hiring_team(
+    pool: &SqlitePool,
+    interview_id: &str,
+    authed: &AuthedUser,
+) -> Result<Interview, ApiError> {
+    let interview = load_interview(pool, interview_id).await?;
+    if !is_hiring_team(pool, &interview, authed)
+        .await
+        .map_err(ApiError::DatabaseError)?
+    {
+        warn!(
+            user_id = %authed.id,
+            interview_id = %interview_id,
+            "Interview record access denied: not on the hiring team"
+        );
+        return Err(ApiError::NotFound("Interview not found".to_string()));
+    }
+    Ok(interview)
+}
+
+pub async fn load_artifact(
+    pool: &SqlitePool,
+    artifact_id: &str,
+) -> Result<InterviewArtifact, ApiError> {
+    sqlx::query_as::<_, InterviewArtifact>(
+        r#"
+        SELECT id, interview_id, kind, original_filename, content_type, file_size, uploaded_by,
+               created_at
+        FROM interview_artifacts WHERE id = ?
+        "#,
+    )
+    .bind(artifact_id)
+    .fetch_optional(pool)
+    .await
+    .map_err(ApiError::DatabaseError)?
+    .ok_or_else(|| ApiError::NotFound("File not found".to_string()))
+}
+
+/// The shared notes and every artifact of an interview
+pub async fn load_record(
+    pool: &SqlitePool,
+    interview_id: &str,
+) -> Result<InterviewRecord, ApiError> {
+    let (application_id, shared_notes, shared_notes_updated_by, shared_notes_updated_at): (
+        String,
+        Option<String>,
+        Option<String>,
+        Option<String>,
+    ) = sqlx::query_as(
+        r#"
+        SELECT application_id, shared_notes, shared_notes_updated_by, shared_notes_updated_at
+        FROM interviews WHERE id = ?
+        "#,
+    )
+    .bind(interview_id)
+    .fetch_optional(pool)
+    .await
+    .map_err(ApiError::DatabaseError)?
+    .ok_or_else(|| ApiError::NotFound("Interview not found".to_string()))?;
+
+    let artifacts = sqlx::query_as::<_, InterviewArtifact>(
+        r#"
+        SELECT id, interview_id, kind, original_filename, content_type, file_size, uploaded_by,
+               created_at
+        FROM interview_artifacts
+        WHERE interview_id = ?
+        ORDER BY created_at ASC
+        "#,
+    )
+    .bind(interview_id)
+    .fetch_all(pool)
+    .await
+    .map_err(ApiError::DatabaseError)?;
+
+    Ok(InterviewRecord {
+        interview_id: interview_id.to_string(),
+        application_id,
+        shared_notes,
+        shared_notes_updated_by,
+        shared_notes_updated_at,
+        artifacts,
+    })
+}
+
+/// Interview notes and files on an application's timeline, newest first, limited to the
+/// interviews the caller is on the hiring team of
+pub async fn timeline_entries(
+    pool: &SqlitePool,
+    application_id: &str,
+    authed: &AuthedUser,
+) -> Result<Vec<InterviewRecordEntry>, ApiError> {
+    let entries = sqlx::query_as::<_, InterviewRecordEntry>(
+        r#"
+        SELECT id AS interview_id, 'notes' AS kind, NULL AS artifact_id,
+               shared_notes_updated_by AS added_by, shared_notes_updated_at AS added_at
+        FROM interviews
+        WHERE application_id = ? AND COALESCE(shared_notes, '') != ''
+        UNION ALL
+        SELECT a.interview_id, a.kind, a.id, a.uploaded_by, a.created_at
+        FROM interview_artifacts a
+        JOIN interviews i ON i.id = a.interview_id
+        WHERE i.application_id = ?
+        ORDER BY added_at DESC
+        "#,
+    )
+    .bind(application_id)
+    .bind(application_id)
+    .fetch_all(pool)
+    .await
+    .map_err(ApiError::DatabaseError)?;
+
+    let mut allowed: HashMap<String, bool> = HashMap::new();
+    let mut visible = Vec::with_capacity(entries.len());
+    for mut entry in entries {
+        if !allowed.contains_key(&entry.interview_id) {
+            let interview = load_interview(pool, &entry.interview_id).await?;
+            let on_team = is_hiring_team(pool, &interview, authed)
+                .await
+                .map_err(ApiError::DatabaseError)?;
+            allowed.insert(entry.interview_id.clone(), on_team);
+        }
+        if allowed[&entry.interview_id] {
+            entry.link = format!("/api/interviews/{}/record", entry.interview_id);
+            visible.push(entry);
+        }
+    }
+    Ok(visible)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn interview(scheduled_date: &str, duration_minutes: i32) -> Interview {
+        Interview {
+            id: "I_1".to_string(),
+            application_id: "A_1".to_string(),
+            candidate_id: "U_1".to_string(),
+            job_id: None,
+            scheduled_date: scheduled_date.to_string(),
+            duration_minutes,
+            interview_type: "video".to_string(),
+            google_meet_link: None,
+            google_calendar_event_id: None,
+            panel_members: r#"[{"email":"Lee@Example.com","name":"Lee","role":null}]"#.to_string(),
+            notes: None,
+            status: Some("scheduled".to_string()),
+            created_by: "U_admin".to_string(),
+            created_at: None,
+            updated_at: None,
+            scheduled_timezone: None,
+            calendar_synced_at: None,
+            preparation_notes: None,
+            feedback_released_at: None,
+            local_scheduled_date: None,
+            local_timezone: None,
+        }
+    }
+
+    #[test]
+    fn test_is_completed_after_the_interview_ends() {
+        let interview = interview("2026-03-02T10:00:00Z", 45);
+        let during = Utc.with_ymd_and_hms(2026, 3, 2, 10, 30, 0).unwrap();
+        let after = Utc.with_ymd_and_hms(2026, 3, 2, 10, 45, 0).unwrap();
+
+        assert!(!is_completed(&interview, during));
+        assert!(is_completed(&interview, after));
+    }
+
+    #[test]
+    fn test_is_panelist_ignores_case() {
+        let interview = interview("2026-03-02T10:00:00Z", 45);
+        assert!(is_panelist(&interview.panel_members, "lee@example.com"));
+        assert!(!is_panelist(&interview.panel_members, "kim@example.com"));
+        assert!(!is_panelist("not json", "lee@example.com"));
+    }
+
+    #[test]
+    fn test_validate_artifact_upload() {
+        let pdf = b"%PDF-1.4\n1 0 obj\n<<>>\nendobj\n";
+        assert_eq!(
+            validate_artifact_upload("transcript", pdf, Some("notes.pdf")).unwrap(),
+            ("application/pdf", "pdf")
+        );
+        assert_eq!(
+            validate_artifact_upload(
+                "transcript",
+                b"WEBVTT\n\n00:00.000 --> 00:02.000\nHi",
+                Some("call.vtt")
+            )
+            .unwrap(),
+            ("text/vtt", "vtt")
+        );
+        assert!(validate_artifact_upload("transcript", b"hello", Some("call.exe")).is_err());
+        assert!(validate_artifact_upload("recording", b"just text", None).is_err());
+        assert!(validate_artifact_upload("recording", pdf, None).is_err());
+        assert!(validate_artifact_upload("notes", b"hello", None).is_err());
+        assert!(validate_artifact_upload("transcript", b"", None).is_err());
+    }
+}
@@ -2,22 +2,31 @@
 
 use crate::common::{ApiError, Validator};
 use crate::candidates::models::{
-    Application, CreateInterviewRequest, GoogleMeetLinkResponse, Interview,
-    InterviewPanelMember, InterviewWithDetails, UpdateInterviewRequest,
+    Application, CalendarDay, CalendarInterview, CalendarSyncSummary, CandidateInterview,
+    CandidateInterviewHub, CreateInterviewRequest, GoogleMeetLinkResponse,
+    Interview, InterviewCalendar, InterviewPanelMember, InterviewWithDetails,
+    UpdateInterviewRequest,
 };
 use crate::candidates::handlers::interview_email_templates::get_interview_scheduled_template;
-use crate::services::google::{CalendarEvent, GoogleService};
-use chrono::{DateTime, Duration, Utc};
+use crate::services::google::{CalendarEvent, ExternalCalendarEvent, GoogleError};
+use crate::services::{CalendarClient, EmailSender};
+use crate::services::SettingsService;
+use chrono::{DateTime, Duration, NaiveDate, Utc};
 use sqlx::SqlitePool;
 use std::sync::Arc;
 use tracing::{debug, error, info, warn};
 
 use crate::common::generate_interview_id;
+use crate::profile::contact_verification;
+use crate::common::timezone::{
+    display_stored, email_timezone, local_date, normalize_schedule, parse_stored,
+    to_zone_rfc3339, DEFAULT_TIMEZONE,
+};
 
 /// Schedule an interview with validation
 pub async fn schedule_interview(
     pool: &SqlitePool,
-    google_service: Arc<GoogleService>,
+    calendar: Arc<dyn CalendarClient>,
     request: CreateInterviewRequest,
     created_by: &str,
 ) -> Result<Interview, ApiError> {
@@ -65,31 +74,28 @@ pub async fn schedule_interview(
 
     let (candidate_id, job_id) = application;
 
-    // Parse scheduled date (keep original timezone, don't convert to UTC)
-    let scheduled_datetime = DateTime::parse_from_rfc3339(&request.scheduled_date)
-        .map_err(|e| {
+    // Store the instant in UTC and remember the zone it was booked in
+    let (scheduled_utc, scheduled_timezone) =
+        normalize_schedule(&request.scheduled_date, request.timezone.as_deref()).map_err(|e| {
             warn!(
                 error = %e,
                 scheduled_date = %request.scheduled_date,
                 "Invalid scheduled date format"
             );
-            ApiError::BadRequest(format!("Invalid date format: {}", e))
+            ApiError::BadRequest(e)
         })?;
 
-    // Validate that scheduled date is in the future (compare in UTC)
-    if scheduled_datetime.with_timezone(&Utc) <= Utc::now() {
+    if scheduled_utc <= Utc::now() {
         return Err(ApiError::BadRequest(
             "Scheduled date must be in the future".to_string(),
         ));
     }
-    
-    // Store the date as-is with original timezone
-    let scheduled_date_str = request.scheduled_date.clone();
-    
+
     info!(
         scheduled_date_input = %request.scheduled_date,
-        scheduled_date_parsed = %scheduled_datetime,
-        "Storing interview with timezone"
+        scheduled_utc = %scheduled_utc,
+        scheduled_timezone = %scheduled_timezone,
+        "Storing interview in UTC"
     );
 
     // Serialize panel members to JSON
@@ -105,12 +111,13 @@ pub async fn schedule_interview(
         "Processing interview creation request"
     );
 
-    // Create Google Meet link if requested
+    // Create Google Meet link if requested; a failure is retried in the background
+    let mut meet_link_error = None;
     let (google_meet_link, google_calendar_event_id) = if request.create_google_meet {
         info!("Creating Google Meet link for interview");
         match create_google_meet_link_internal(
-            google_service,
-            scheduled_datetime.with_timezone(&Utc),
+            calendar,
+            scheduled_utc,
             request.duration_minutes,
             &request.panel_members,
             &request.interview_type,
@@ -122,7 +129,8 @@ pub async fn schedule_interview(
                 (Some(meet_link), Some(calendar_id))
             }
             Err(e) => {
-                warn!(error = %e, "Failed to create Google Meet link, continuing without it");
+                warn!(error = %e, "Failed to create Google Meet link, will retry in the background");
+                meet_link_error = Some(e.to_string());
                 (None, None)
             }
         }
@@ -135,22 +143,25 @@ pub async fn schedule_interview(
     sqlx::query(
         r#"
         INSERT INTO interviews (
-            id, application_id, candidate_id, job_id, scheduled_date, duration_minutes, interview_type,
-            google_meet_link, google_calendar_event_id, panel_members, notes, created_by
-        ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            id, application_id, candidate_id, job_id, scheduled_date, scheduled_timezone,
+            duration_minutes, interview_type, google_meet_link, google_calendar_event_id,
+            panel_members, notes, preparation_notes, created_by
+        ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
         "#,
     )
     .bind(&interview_id)
     .bind(&request.application_id)
     .bind(&candidate_id)
     .bind(&job_id)
-    .bind(&request.scheduled_date)
+    .bind(scheduled_utc.to_rfc3339())
+    .bind(&scheduled_timezone)
     .bind(request.duration_minutes)
     .bind(&request.interview_type)
     .bind(&google_meet_link)
     .bind(&google_calendar_event_id)
     .bind(&panel_members_json)
     .bind(&request.notes)
+    .bind(&request.preparation_notes)
     .bind(created_by)
     .execute(pool)
     .await
@@ -177,6 +188,14 @@ pub async fn schedule_interview(
         "Interview scheduled successfully"
     );
 
+    if let Some(reason) = meet_link_error {
+        if let Err(e) =
+            crate::services::calendar_retries::enqueue_meet_link(pool, &interview_id, &reason).await
+        {
+            error!(error = %e, interview_id = %interview_id, "Failed to queue Meet link retry");
+        }
+    }
+
     // Update application status to "interview_scheduled" (maps to "Interview Scheduled" stage)
     // Only update if current status is "shortlisted" to maintain proper flow
     // This is non-blocking - interview is created even if status update fails
@@ -238,7 +257,7 @@ pub async fn schedule_interview(
 
 /// Create Google Meet link with Google Calendar API integration
 pub async fn create_google_meet_link(
-    google_service: Arc<GoogleService>,
+    calendar: Arc<dyn CalendarClient>,
     request: crate::candidates::models::CreateGoogleMeetRequest,
 ) -> Result<GoogleMeetLinkResponse, ApiError> {
     let scheduled_date = request.start_time.clone();
@@ -285,7 +304,7 @@ pub async fn create_google_meet_link(
         create_meet_link: true,
     };
 
-    let calendar_response = google_service
+    let calendar_response = calendar
         .create_calendar_event(event)
         .await
         .map_err(|e| {
@@ -312,8 +331,8 @@ pub async fn create_google_meet_link(
 }
 
 /// Internal helper to create Google Meet link
-async fn create_google_meet_link_internal(
-    google_service: Arc<GoogleService>,
+pub(crate) async fn create_google_meet_link_internal(
+    calendar: Arc<dyn CalendarClient>,
     start_time: DateTime<Utc>,
     duration_minutes: i32,
     panel_members: &[InterviewPanelMember],
@@ -335,7 +354,7 @@ async fn create_google_meet_link_internal(
         create_meet_link: true,
     };
 
-    let calendar_response = google_service
+    let calendar_response = calendar
         .create_calendar_event(event)
         .await
         .map_err(|e| {
@@ -354,7 +373,7 @@ async fn create_google_meet_link_internal(
 /// Send calendar invitations to attendees
 pub async fn send_calendar_invitations(
     pool: &SqlitePool,
-    aws_service: &crate::services::aws::AWSService,
+    email_sender: &dyn EmailSender,
     interview_id: &str,
 ) -> Result<(), ApiError> {
     debug!(
@@ -408,37 +427,38 @@ pub async fn send_calendar_invitations(
     let candidate_name = candidate.name.unwrap_or_else(|| "Candidate".to_string());
     let company_name = job.company.unwrap_or_else(|| "Our Company".to_string());
     
-    let email_template = get_interview_scheduled_template(
-        &candidate_name,
-        &job.title,
-        &company_name,
-        &interview.scheduled_date,
-        interview.duration_minutes,
-        &interview.interview_type,
-        interview.google_meet_link.as_deref(),
-        interview.notes.as_deref(),
-    );
-
-    let subject = email_template.subject;
-    let content = email_template.body;
-
-    // Send email to candidate
-    let mut recipients = vec![candidate.email.clone()];
-
-    // Add panel members to recipients
+    // Send email to candidate and panel members, one message per recipient time zone
+    let candidate_email = contact_verification::notification_email(pool, &candidate.id)
+        .await
+        .map_err(ApiError::DatabaseError)?
+        .unwrap_or_else(|| candidate.email.clone());
+    let mut recipients = vec![candidate_email];
     for member in &panel_members {
         recipients.push(member.email.clone());
     }
 
     let recipient_count = recipients.len();
 
-    aws_service
-        .send_email(recipients, &subject, &content, None)
-        .await
-        .map_err(|e| {
-            error!(error = %e, "Failed to send calendar invitation emails");
-            ApiError::InternalServer(format!("Failed to send calendar invitations: {}", e))
-        })?;
+    for (timezone, group) in group_recipients_by_timezone(pool, recipients, &interview).await {
+        let email_template = get_interview_scheduled_template(
+            &candidate_name,
+            &job.title,
+            &company_name,
+            &display_stored(&interview.scheduled_date, &timezone),
+            interview.duration_minutes,
+            &interview.interview_type,
+            interview.google_meet_link.as_deref(),
+            interview.notes.as_deref(),
+        );
+
+        email_sender
+            .send_email(group, &email_template.subject, &email_template.body, None)
+            .await
+            .map_err(|e| {
+                error!(error = %e, "Failed to send calendar invitation emails");
+                ApiError::InternalServer(format!("Failed to send calendar invitations: {}", e))
+            })?;
+    }
 
     info!(
         interview_id = %interview_id,
@@ -573,6 +593,280 @@ pub async fn get_job_interviews(
     Ok(result)
 }
 
+/// Longest date range the calendar view serves in one request
+const MAX_CALENDAR_DAYS: i64 = 92;
+
+#[derive(sqlx::FromRow)]
+struct CalendarRow {
+    #[sqlx(flatten)]
+    interview: Interview,
+    candidate_name: Option<String>,
+    candidate_email: Option<String>,
+    job_title: Option<String>,
+}
+
+/// Resolve the calendar's `from`/`to` query values (YYYY-MM-DD) into an inclusive day range
+pub fn resolve_calendar_range(
+    from: Option<&str>,
+    to: Option<&str>,
+    today: NaiveDate,
+) -> Result<(NaiveDate, NaiveDate), ApiError> {
+    let parse = |value: &str, name: &str| {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d").map_err(|_| {
+            ApiError::BadRequest(format!("Invalid '{}' date, expected YYYY-MM-DD", name))
+        })
+    };
+
+    let from = match from {
+        Some(value) => parse(value, "from")?,
+        None => today,
+    };
+    let to = match to {
+        Some(value) => parse(value, "to")?,
+        None => from + Duration::days(6),
+    };
+
+    if to < from {
+        return Err(ApiError::BadRequest(
+            "'to' must not be before 'from'".to_string(),
+        ));
+    }
+    if (to - from).num_days() >= MAX_CALENDAR_DAYS {
+        return Err(ApiError::BadRequest(format!(
+            "Calendar range cannot exceed {} days",
+            MAX_CALENDAR_DAYS
+        )));
+    }
+
+    Ok((from, to))
+}
+
+/// Group interviews into one entry per day of the range, keeping empty days for the UI grid
+pub fn bucket_by_day(
+    from: NaiveDate,
+    to: NaiveDate,
+    interviews: Vec<(NaiveDate, CalendarInterview)>,
+) -> Vec<CalendarDay> {
+    let mut days: Vec<CalendarDay> = from
+        .iter_days()
+        .take_while(|day| *day <= to)
+        .map(|day| CalendarDay {
+            date: day.format("%Y-%m-%d").to_string(),
+            interviews: Vec::new(),
+        })
+        .collect();
+
+    for (day, interview) in interviews {
+        if day < from || day > to {
+            continue;
+        }
+        let index = (day - from).num_days() as usize;
+        days[index].interviews.push(interview);
+    }
+
+    for day in &mut days {
+        day.interviews
+            .sort_by(|a, b| a.scheduled_date.cmp(&b.scheduled_date));
+    }
+
+    days
+}
+
+/// Interviews between two dates (inclusive, in `timezone`), optionally limited to one panelist
+pub async fn get_interview_calendar(
+    pool: &SqlitePool,
+    from: NaiveDate,
+    to: NaiveDate,
+    panelist: Option<&str>,
+    timezone: &str,
+) -> Result<InterviewCalendar, ApiError> {
+    // scheduled_date is UTC while days are split in the viewer's zone, so widen the indexed
+    // string range by a day on each side and filter precisely after converting
+    let lower = (from - Duration::days(1)).format("%Y-%m-%d").to_string();
+    let upper = (to + Duration::days(2)).format("%Y-%m-%d").to_string();
+    let panelist = panelist.map(|p| p.trim().to_lowercase()).filter(|p| !p.is_empty());
+    let panel_needle = panelist
+        .as_ref()
+        .map(|p| format!("\"email\":\"{}\"", p));
+
+    let rows = sqlx::query_as::<_, CalendarRow>(
+        r#"
+        SELECT i.*, u.name AS candidate_name, u.email AS candidate_email, j.title AS job_title
+        FROM interviews i
+        LEFT JOIN users u ON i.candidate_id = u.id
+        LEFT JOIN jobs j ON i.job_id = j.id
+        WHERE i.scheduled_date >= ? AND i.scheduled_date < ?
+        AND (
+            ? IS NULL
+            OR instr(LOWER(i.panel_members), ?) > 0
+            OR EXISTS (
+                SELECT 1 FROM interview_interviewers ii
+                JOIN users pu ON pu.id = ii.user_id
+                WHERE ii.interview_id = i.id AND (ii.user_id = ? OR LOWER(pu.email) = ?)
+            )
+        )
+        ORDER BY i.scheduled_date ASC
+        "#,
+    )
+    .bind(&lower)
+    .bind(&upper)
+    .bind(&panelist)
+    .bind(&panel_needle)
+    .bind(&panelist)
+    .bind(&panelist)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| {
+        error!(error = %e, "Database error fetching interview calendar");
+        ApiError::DatabaseError(e)
+    })?;
+
+    let interviews: Vec<(NaiveDate, CalendarInterview)> = rows
+        .into_iter()
+        .filter_map(|row| {
+            let start = match parse_stored(&row.interview.scheduled_date) {
+                Some(start) => start,
+                None => {
+                    warn!(
+                        interview_id = %row.interview.id,
+                        "Skipping interview with unparseable scheduled_date"
+                    );
+                    return None;
+                }
+            };
+            let end = start + Duration::minutes(row.interview.duration_minutes as i64);
+            let panel_members =
+                serde_json::from_str(&row.interview.panel_members).unwrap_or_default();
+            let interview = row.interview;
+
+            Some((
+                local_date(start, timezone),
+                CalendarInterview {
+                    id: interview.id,
+                    application_id: interview.application_id,
+                    scheduled_date: to_zone_rfc3339(start, timezone),
+                    end_time: to_zone_rfc3339(end, timezone),
+                    scheduled_timezone: interview.scheduled_timezone,
+                    duration_minutes: interview.duration_minutes,
+                    interview_type: interview.interview_type,
+                    status: interview.status,
+                    google_meet_link: interview.google_meet_link,
+                    google_calendar_event_id: interview.google_calendar_event_id,
+                    candidate_id: interview.candidate_id,
+                    candidate_name: row.candidate_name,
+                    candidate_email: row.candidate_email,
+                    job_id: interview.job_id,
+                    job_title: row.job_title,
+                    panel_members,
+                },
+            ))
+        })
+        .filter(|(day, _)| *day >= from && *day <= to)
+        .collect();
+
+    let total = interviews.len();
+    debug!(from = %from, to = %to, total = total, "Built interview calendar");
+
+    Ok(InterviewCalendar {
+        from: from.format("%Y-%m-%d").to_string(),
+        to: to.format("%Y-%m-%d").to_string(),
+        timezone: timezone.to_string(),
+        total,
+        days: bucket_by_day(from, to, interviews),
+    })
+}
+
+#[derive(sqlx::FromRow)]
+struct CandidateInterviewRow {
+    #[sqlx(flatten)]
+    interview: Interview,
+    job_title: Option<String>,
+    company: Option<String>,
+}
+
+/// A candidate's own interviews split into upcoming and past, with times in `timezone`
+pub async fn get_candidate_interview_hub(
+    pool: &SqlitePool,
+    candidate_id: &str,
+    timezone: &str,
+    now: DateTime<Utc>,
+) -> Result<CandidateInterviewHub, ApiError> {
+    let rows = sqlx::query_as::<_, CandidateInterviewRow>(
+        r#"
+        SELECT i.*, j.title AS job_title, j.company AS company
+        FROM interviews i
+        LEFT JOIN jobs j ON i.job_id = j.id
+        WHERE i.candidate_id = ?
+        ORDER BY i.scheduled_date ASC
+        "#,
+    )
+    .bind(candidate_id)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| {
+        error!(error = %e, "Database error fetching candidate interviews");
+        ApiError::DatabaseError(e)
+    })?;
+
+    let mut upcoming = Vec::new();
+    let mut past = Vec::new();
+
+    for row in rows {
+        let interview = row.interview;
+        let start = match parse_stored(&interview.scheduled_date) {
+            Some(start) => start,
+            None => {
+                warn!(
+                    interview_id = %interview.id,
+                    "Skipping interview with unparseable scheduled_date"
+                );
+                continue;
+            }
+        };
+        let end = start + Duration::minutes(interview.duration_minutes as i64);
+        let panel = serde_json::from_str::<Vec<InterviewPanelMember>>(&interview.panel_members)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|member| {
+                member
+                    .name
+                    .filter(|name| !name.trim().is_empty())
+                    .unwrap_or_else(|| "Interviewer".to_string())
+            })
+            .collect();
+
+        let entry = CandidateInterview {
+            id: interview.id,
+            application_id: interview.application_id,
+            job_id: interview.job_id,
+            job_title: row.job_title,
+            company: row.company,
+            scheduled_date: to_zone_rfc3339(start, timezone),
+            end_time: to_zone_rfc3339(end, timezone),
+            duration_minutes: interview.duration_minutes,
+            interview_type: interview.interview_type,
+            status: interview.status,
+            google_meet_link: interview.google_meet_link,
+            panel,
+            preparation_notes: interview.preparation_notes,
+            feedback_released: interview.feedback_released_at.is_some(),
+        };
+
+        if end > now {
+            upcoming.push(entry);
+        } else {
+            past.push(entry);
+        }
+    }
+    past.reverse();
+
+    Ok(CandidateInterviewHub {
+        timezone: timezone.to_string(),
+        upcoming,
+        past,
+    })
+}
+
 /// Get interview by ID
 pub async fn get_interview(pool: &SqlitePool, interview_id: &str) -> Result<Interview, ApiError> {
     debug!(
@@ -604,7 +898,7 @@ pub async fn get_interview(pool: &SqlitePool, interview_id: &str) -> Result<Inte
 /// Update interview
 pub async fn update_interview(
     pool: &SqlitePool,
-    aws_service: Arc<crate::services::aws::AWSService>,
+    email_sender: Arc<dyn EmailSender>,
     interview_id: &str,
     request: UpdateInterviewRequest,
     _user_id: &str,
@@ -622,6 +916,7 @@ pub async fn update_interview(
 
     if request.scheduled_date.is_some() {
         query_parts.push("scheduled_date");
+        query_parts.push("scheduled_timezone");
     }
     if request.duration_minutes.is_some() {
         query_parts.push("duration_minutes");
@@ -635,22 +930,42 @@ pub async fn update_interview(
     if request.notes.is_some() {
         query_parts.push("notes");
     }
+    if request.preparation_notes.is_some() {
+        query_parts.push("preparation_notes");
+    }
+    if request.feedback_released.is_some() {
+        query_parts.push("feedback_released_at");
+    }
+
+    // A local reschedule is newer than whatever Google Calendar currently holds
+    let reschedules = request.scheduled_date.is_some() || request.duration_minutes.is_some();
+    if reschedules {
+        query_parts.push("calendar_synced_at");
+    }
 
     if query_parts.is_empty() {
         return Ok(existing);
     }
 
-    // Validate scheduled_date if provided
-    if let Some(ref scheduled_date) = request.scheduled_date {
-        DateTime::parse_from_rfc3339(scheduled_date).map_err(|e| {
-            warn!(
-                error = %e,
-                scheduled_date = %scheduled_date,
-                "Invalid scheduled date format"
-            );
-            ApiError::BadRequest(format!("Invalid date format: {}", e))
-        })?;
-    }
+    // Normalize scheduled_date to UTC; local times without an offset use the given or booked zone
+    let normalized_schedule = match request.scheduled_date {
+        Some(ref scheduled_date) => {
+            let zone = request
+                .timezone
+                .as_deref()
+                .or(existing.scheduled_timezone.as_deref())
+                .filter(|zone| zone.parse::<chrono_tz::Tz>().is_ok());
+            Some(normalize_schedule(scheduled_date, zone).map_err(|e| {
+                warn!(
+                    error = %e,
+                    scheduled_date = %scheduled_date,
+                    "Invalid scheduled date format"
+                );
+                ApiError::BadRequest(e)
+            })?)
+        }
+        None => None,
+    };
 
     // Validate duration_minutes if provided
     if let Some(duration_minutes) = request.duration_minutes {
@@ -683,8 +998,10 @@ pub async fn update_interview(
 
     let mut query_builder = sqlx::query(&query);
 
-    if let Some(ref scheduled_date) = request.scheduled_date {
-        query_builder = query_builder.bind(scheduled_date);
+    if let Some((scheduled_utc, ref scheduled_timezone)) = normalized_schedule {
+        query_builder = query_builder
+            .bind(scheduled_utc.to_rfc3339())
+            .bind(scheduled_timezone);
     }
     if let Some(duration_minutes) = request.duration_minutes {
         query_builder = query_builder.bind(duration_minutes);
@@ -698,6 +1015,26 @@ pub async fn update_interview(
     if let Some(ref notes) = request.notes {
         query_builder = query_builder.bind(notes);
     }
+    if let Some(ref preparation_notes) = request.preparation_notes {
+        query_builder = query_builder.bind(preparation_notes);
+    }
+    if let Some(released) = request.feedback_released {
+        // Keep the original release time when re-releasing
+        let released_at = if released {
+            Some(
+                existing
+                    .feedback_released_at
+                    .clone()
+                    .unwrap_or_else(|| Utc::now().to_rfc3339()),
+            )
+        } else {
+            None
+        };
+        query_builder = query_builder.bind(released_at);
+    }
+    if reschedules {
+        query_builder = query_builder.bind(Utc::now().to_rfc3339());
+    }
     query_builder = query_builder.bind(interview_id);
 
     query_builder.execute(pool).await.map_err(|e| {
@@ -715,7 +1052,7 @@ pub async fn update_interview(
     );
 
     // Send update emails to candidate and panelists
-    if let Err(e) = send_interview_update_email_to_candidate(pool, &aws_service, interview_id).await {
+    if let Err(e) = send_interview_update_email_to_candidate(pool, email_sender.as_ref(), interview_id).await {
         warn!(
             error = %e,
             interview_id = %interview_id,
@@ -723,7 +1060,7 @@ pub async fn update_interview(
         );
     }
 
-    if let Err(e) = send_interview_update_emails_to_panelists(pool, &aws_service, interview_id).await {
+    if let Err(e) = send_interview_update_emails_to_panelists(pool, email_sender.as_ref(), interview_id).await {
         warn!(
             error = %e,
             interview_id = %interview_id,
@@ -737,7 +1074,7 @@ pub async fn update_interview(
 /// Cancel interview with notification
 pub async fn cancel_interview(
     pool: &SqlitePool,
-    aws_service: &crate::services::aws::AWSService,
+    email_sender: &dyn EmailSender,
     interview_id: &str,
 ) -> Result<(), ApiError> {
     debug!(
@@ -797,9 +1134,21 @@ pub async fn cancel_interview(
             ApiError::DatabaseError(e)
         })?;
 
-    // Send cancellation notification
+    // Send cancellation notification, rendering the date in each recipient's zone
     let subject = format!("Interview Cancelled - {}", job.title);
-    let content = format!(
+    let candidate_name = candidate.name.unwrap_or_else(|| "Candidate".to_string());
+
+    let candidate_email = contact_verification::notification_email(pool, &candidate.id)
+        .await
+        .map_err(ApiError::DatabaseError)?
+        .unwrap_or_else(|| candidate.email.clone());
+    let mut recipients = vec![candidate_email];
+    for member in &panel_members {
+        recipients.push(member.email.clone());
+    }
+
+    for (timezone, group) in group_recipients_by_timezone(pool, recipients, &interview).await {
+        let content = format!(
         r#"<!DOCTYPE html>
 <html>
 <head>
@@ -829,34 +1178,281 @@ pub async fn cancel_interview(
     </div>
 </body>
 </html>"#,
-        candidate.name.unwrap_or_else(|| "Candidate".to_string()),
-        job.title,
-        interview.scheduled_date
+            candidate_name,
+            job.title,
+            display_stored(&interview.scheduled_date, &timezone)
+        );
+
+        // Send cancellation email (don't fail if email fails)
+        if let Err(e) = email_sender
+            .send_email(group, &subject, &content, None)
+            .await
+        {
+            warn!(
+                error = %e,
+                interview_id = %interview_id,
+                "Failed to send cancellation notification, but interview was deleted"
+            );
+        }
+    }
+
+    info!(
+        interview_id = %interview_id,
+        "Interview cancelled successfully"
     );
 
-    let mut recipients = vec![candidate.email.clone()];
-    for member in &panel_members {
-        recipients.push(member.email.clone());
+    Ok(())
+}
+
+/// Minutes between Google Calendar polls when `google_calendar_sync_minutes` is unset
+const DEFAULT_CALENDAR_SYNC_MINUTES: u64 = 15;
+
+/// What Google Calendar says happened to an interview's event since we last looked
+#[derive(Debug, PartialEq)]
+enum ExternalChange {
+    Unchanged,
+    Rescheduled {
+        start: DateTime<Utc>,
+        duration_minutes: i32,
+    },
+    Cancelled,
+}
+
+fn classify_external_change(
+    interview: &Interview,
+    event: Option<&ExternalCalendarEvent>,
+) -> ExternalChange {
+    let event = match event {
+        Some(event) => event,
+        None => return ExternalChange::Cancelled,
+    };
+
+    // Anything older than the last change we applied (including local edits) is stale
+    let synced_at = interview.calendar_synced_at.as_deref().and_then(parse_stored);
+    if let (Some(updated), Some(synced_at)) = (event.updated, synced_at) {
+        if updated <= synced_at {
+            return ExternalChange::Unchanged;
+        }
     }
 
-    // Send cancellation email (don't fail if email fails)
-    if let Err(e) = aws_service
-        .send_email(recipients, &subject, &content, None)
-        .await
+    // All-day events have no start time; leave the interview alone
+    let start = match event.start {
+        Some(start) => start,
+        None => return ExternalChange::Unchanged,
+    };
+    let duration_minutes = event
+        .end
+        .map(|end| (end - start).num_minutes())
+        .filter(|minutes| (1..=480).contains(minutes))
+        .map(|minutes| minutes as i32)
+        .unwrap_or(interview.duration_minutes);
+
+    if parse_stored(&interview.scheduled_date) == Some(start)
+        && duration_minutes == interview.duration_minutes
     {
-        warn!(
-            error = %e,
-            interview_id = %interview_id,
-            "Failed to send cancellation notification, but interview was deleted"
-        );
+        ExternalChange::Unchanged
+    } else {
+        ExternalChange::Rescheduled {
+            start,
+            duration_minutes,
+        }
+    }
+}
+
+/// Pull edits and deletions made directly in Google Calendar into upcoming interviews
+///
+/// Moved events reschedule the interview and re-send update emails; deleted or cancelled
+/// events cancel it with the usual notification.
+pub async fn sync_calendar_changes(
+    pool: &SqlitePool,
+    calendar: &dyn CalendarClient,
+    email_sender: &dyn EmailSender,
+) -> Result<CalendarSyncSummary, ApiError> {
+    let since = (Utc::now() - Duration::days(1)).to_rfc3339();
+    let interviews = sqlx::query_as::<_, Interview>(
+        "SELECT * FROM interviews WHERE google_calendar_event_id IS NOT NULL AND scheduled_date >= ? ORDER BY scheduled_date ASC",
+    )
+    .bind(&since)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| {
+        error!(error = %e, "Database error fetching interviews for calendar sync");
+        ApiError::DatabaseError(e)
+    })?;
+
+    let mut summary = CalendarSyncSummary::default();
+
+    for interview in interviews {
+        let event_id = match interview.google_calendar_event_id.as_deref() {
+            Some(event_id) => event_id,
+            None => continue,
+        };
+        summary.checked += 1;
+
+        let event = match calendar.get_calendar_event(event_id).await {
+            Ok(event) => event,
+            Err(GoogleError::NotConfigured) | Err(GoogleError::InvalidConfig(_)) => {
+                return Err(ApiError::BadRequest(
+                    "Google Calendar is not connected".to_string(),
+                ));
+            }
+            Err(e) => {
+                warn!(
+                    error = %e,
+                    interview_id = %interview.id,
+                    event_id = %event_id,
+                    "Failed to fetch calendar event during sync"
+                );
+                summary.failed += 1;
+                continue;
+            }
+        };
+
+        match classify_external_change(&interview, event.as_ref()) {
+            ExternalChange::Cancelled => {
+                info!(
+                    interview_id = %interview.id,
+                    event_id = %event_id,
+                    "Calendar event removed in Google Calendar, cancelling interview"
+                );
+                match cancel_interview(pool, email_sender, &interview.id).await {
+                    Ok(()) => summary.cancelled += 1,
+                    Err(e) => {
+                        warn!(error = %e, interview_id = %interview.id, "Failed to cancel interview during calendar sync");
+                        summary.failed += 1;
+                    }
+                }
+            }
+            ExternalChange::Rescheduled {
+                start,
+                duration_minutes,
+            } => {
+                let synced_at = event
+                    .as_ref()
+                    .and_then(|event| event.updated)
+                    .unwrap_or_else(Utc::now);
+                let result = sqlx::query(
+                    "UPDATE interviews SET scheduled_date = ?, duration_minutes = ?, calendar_synced_at = ?, updated_at = datetime('now') WHERE id = ?",
+                )
+                .bind(start.to_rfc3339())
+                .bind(duration_minutes)
+                .bind(synced_at.to_rfc3339())
+                .bind(&interview.id)
+                .execute(pool)
+                .await;
+
+                if let Err(e) = result {
+                    error!(error = %e, interview_id = %interview.id, "Database error applying calendar change");
+                    summary.failed += 1;
+                    continue;
+                }
+
+                info!(
+                    interview_id = %interview.id,
+                    previous_date = %interview.scheduled_date,
+                    scheduled_date = %start.to_rfc3339(),
+                    duration_minutes = duration_minutes,
+                    "Interview rescheduled from Google Calendar"
+                );
+                summary.rescheduled += 1;
+
+                if let Err(e) =
+                    send_interview_update_email_to_candidate(pool, email_sender, &interview.id).await
+                {
+                    warn!(error = %e, interview_id = %interview.id, "Failed to notify candidate of calendar change");
+                }
+                if let Err(e) =
+                    send_interview_update_emails_to_panelists(pool, email_sender, &interview.id).await
+                {
+                    warn!(error = %e, interview_id = %interview.id, "Failed to notify panelists of calendar change");
+                }
+            }
+            ExternalChange::Unchanged => {
+                // Remember edits that didn't touch the time so they aren't re-examined
+                let updated = event.as_ref().and_then(|event| event.updated);
+                let synced_at = interview.calendar_synced_at.as_deref().and_then(parse_stored);
+                if let Some(updated) = updated.filter(|updated| Some(*updated) > synced_at) {
+                    let _ = sqlx::query("UPDATE interviews SET calendar_synced_at = ? WHERE id = ?")
+                        .bind(updated.to_rfc3339())
+                        .bind(&interview.id)
+                        .execute(pool)
+                        .await;
+                }
+            }
+        }
     }
 
     info!(
-        interview_id = %interview_id,
-        "Interview cancelled successfully"
+        checked = summary.checked,
+        rescheduled = summary.rescheduled,
+        cancelled = summary.cancelled,
+        failed = summary.failed,
+        "Google Calendar sync finished"
     );
 
-    Ok(())
+    Ok(summary)
+}
+
+/// Poll Google Calendar every `google_calendar_sync_minutes` (default 15; 0 disables)
+pub fn start_calendar_sync_task(
+    pool: SqlitePool,
+    settings_service: Arc<SettingsService>,
+    calendar: Arc<dyn CalendarClient>,
+    email_sender: Arc<dyn EmailSender>,
+) {
+    tokio::spawn(async move {
+        loop {
+            let minutes = settings_service
+                .get_setting("google_calendar_sync_minutes")
+                .await
+                .ok()
+                .flatten()
+                .and_then(|v| v.parse::<u64>().ok())
+                .unwrap_or(DEFAULT_CALENDAR_SYNC_MINUTES);
+
+            if !crate::common::shutdown::sleep(std::time::Duration::from_secs(
+                minutes.clamp(1, 24 * 60) * 60,
+            ))
+            .await
+            {
+                break;
+            }
+            let _running = crate::common::shutdown::track();
+
+            if minutes == 0 {
+                continue;
+            }
+
+            if let Err(e) = sync_calendar_changes(&pool, calendar.as_ref(), email_sender.as_ref()).await {
+                debug!(error = %e, "Skipped Google Calendar sync");
+            }
+        }
+    });
+}
+
+/// Zone to render an interview in for one recipient: their own preference, else the booking zone
+async fn recipient_timezone(pool: &SqlitePool, email: &str, interview: &Interview) -> String {
+    email_timezone(pool, email)
+        .await
+        .or_else(|| interview.scheduled_timezone.clone())
+        .unwrap_or_else(|| DEFAULT_TIMEZONE.to_string())
+}
+
+/// Group recipients that share a time zone so each group gets one correctly rendered email
+async fn group_recipients_by_timezone(
+    pool: &SqlitePool,
+    recipients: Vec<String>,
+    interview: &Interview,
+) -> Vec<(String, Vec<String>)> {
+    let mut groups: Vec<(String, Vec<String>)> = Vec::new();
+    for email in recipients {
+        let timezone = recipient_timezone(pool, &email, interview).await;
+        match groups.iter_mut().find(|(zone, _)| *zone == timezone) {
+            Some((_, group)) => group.push(email),
+            None => groups.push((timezone, vec![email])),
+        }
+    }
+    groups
 }
 
 /// Helper function to build interview details with related data
@@ -914,9 +1510,9 @@ async fn build_interview_details(
 
 
 /// Send interview update email to candidate
-async fn send_interview_update_email_to_candidate(
+pub(crate) async fn send_interview_update_email_to_candidate(
     pool: &SqlitePool,
-    aws_service: &Arc<crate::services::AWSService>,
+    email_sender: &dyn EmailSender,
     interview_id: &str,
 ) -> Result<(), ApiError> {
     use crate::candidates::handlers::interview_email_templates::get_interview_updated_template;
@@ -942,7 +1538,7 @@ async fn send_interview_update_email_to_candidate(
 
     // Fetch candidate
     let candidate: (String, String) = sqlx::query_as(
-        "SELECT name, email FROM users WHERE id = ?"
+        "SELECT u.name, COALESCE(p.contact_email, u.email) FROM users u LEFT JOIN profiles p ON p.user_id = u.id WHERE u.id = ?"
     )
     .bind(&candidate_id)
     .fetch_one(pool)
@@ -986,11 +1582,12 @@ async fn send_interview_update_email_to_candidate(
     };
 
     // Generate email template
+    let timezone = recipient_timezone(pool, &candidate.1, &interview).await;
     let template = get_interview_updated_template(
         &candidate.0,
         &job.0,
         "Company", // TODO: Get from settings
-        &interview.scheduled_date,
+        &display_stored(&interview.scheduled_date, &timezone),
         interview.duration_minutes,
         &interview.interview_type,
         interview.google_meet_link.as_deref(),
@@ -998,7 +1595,7 @@ async fn send_interview_update_email_to_candidate(
     );
 
     // Send email via AWS SES
-    aws_service
+    email_sender
         .send_email(vec![candidate.1.clone()], &template.subject, &template.body, None)
         .await
         .map_err(|e| {
@@ -1016,9 +1613,9 @@ async fn send_interview_update_email_to_candidate(
 }
 
 /// Send interview update emails to all panelists
-async fn send_interview_update_emails_to_panelists(
+pub(crate) async fn send_interview_update_emails_to_panelists(
     pool: &SqlitePool,
-    aws_service: &Arc<crate::services::AWSService>,
+    email_sender: &dyn EmailSender,
     interview_id: &str,
 ) -> Result<(), ApiError> {
     use crate::candidates::handlers::interview_email_templates::get_panelist_interview_updated_template;
@@ -1044,7 +1641,7 @@ async fn send_interview_update_emails_to_panelists(
 
     // Fetch candidate
     let candidate: (String, String) = sqlx::query_as(
-        "SELECT name, email FROM users WHERE id = ?"
+        "SELECT u.name, COALESCE(p.contact_email, u.email) FROM users u LEFT JOIN profiles p ON p.user_id = u.id WHERE u.id = ?"
     )
     .bind(&candidate_id)
     .fetch_one(pool)
@@ -1076,19 +1673,20 @@ async fn send_interview_update_emails_to_panelists(
     // Send email to each panelist
     for panel_member in panel_members {
         let panelist_name = panel_member.name.as_deref().unwrap_or(&panel_member.email);
-        
+        let timezone = recipient_timezone(pool, &panel_member.email, &interview).await;
+
         let template = get_panelist_interview_updated_template(
             panelist_name,
             &candidate.0,
             &candidate.1,
             &job.0,
             "Company", // TODO: Get from settings
-            &interview.scheduled_date,
+            &display_stored(&interview.scheduled_date, &timezone),
             interview.duration_minutes,
             interview.google_meet_link.as_deref(),
         );
 
-        match aws_service
+        match email_sender
             .send_email(vec![panel_member.email.clone()], &template.subject, &template.body, None)
             .await
         {
@@ -1111,3 +1709,249 @@ async fn send_interview_update_emails_to_panelists(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day(value: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d").unwrap()
+    }
+
+    async fn setup_test_db() -> SqlitePool {
+        let pool = SqlitePool::connect(":memory:").await.unwrap();
+
+        for statement in [
+            "CREATE TABLE users (id TEXT PRIMARY KEY, email TEXT, name TEXT)",
+            "CREATE TABLE jobs (id TEXT PRIMARY KEY, title TEXT, company TEXT)",
+            r#"CREATE TABLE interviews (
+                id TEXT PRIMARY KEY, application_id TEXT NOT NULL, candidate_id TEXT NOT NULL,
+                job_id TEXT, scheduled_date TEXT NOT NULL, duration_minutes INTEGER NOT NULL,
+                interview_type TEXT NOT NULL, google_meet_link TEXT, google_calendar_event_id TEXT,
+                panel_members TEXT NOT NULL, notes TEXT, status TEXT DEFAULT 'scheduled',
+                created_by TEXT NOT NULL, created_at TEXT, updated_at TEXT,
+                preparation_notes TEXT, feedback_released_at TEXT
+            )"#,
+            "CREATE TABLE interview_interviewers (interview_id TEXT NOT NULL, user_id TEXT NOT NULL)",
+            "INSERT INTO users (id, email, name) VALUES ('cand', 'cand@example.com', 'Casey Candidate')",
+            "INSERT INTO jobs (id, title) VALUES ('job', 'Backend Engineer')",
+        ] {
+            sqlx::query(statement).execute(&pool).await.unwrap();
+        }
+
+        let interviews = [
+            // 23:30 in New York is already the next day in UTC
+            ("int1", "2026-03-02T23:30:00-05:00", r#"[{"email":"lead@example.com","name":null,"role":null}]"#),
+            ("int2", "2026-03-02T09:00:00Z", r#"[{"email":"other@example.com","name":null,"role":null}]"#),
+            ("int3", "2026-03-10T09:00:00Z", r#"[{"email":"lead@example.com","name":null,"role":null}]"#),
+        ];
+        for (id, scheduled, panel) in interviews {
+            sqlx::query(
+                "INSERT INTO interviews (id, application_id, candidate_id, job_id, scheduled_date, duration_minutes, interview_type, google_meet_link, panel_members, created_by) VALUES (?, 'app', 'cand', 'job', ?, 45, 'technical', 'https://meet.google.com/abc', ?, 'admin')",
+            )
+            .bind(id)
+            .bind(scheduled)
+            .bind(panel)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+
+        pool
+    }
+
+    #[test]
+    fn test_resolve_calendar_range_defaults_and_limits() {
+        let today = day("2026-03-02");
+        assert_eq!(
+            resolve_calendar_range(None, None, today).unwrap(),
+            (today, day("2026-03-08"))
+        );
+        assert!(resolve_calendar_range(Some("2026-03-05"), Some("2026-03-01"), today).is_err());
+        assert!(resolve_calendar_range(Some("2026-01-01"), Some("2026-12-31"), today).is_err());
+        assert!(resolve_calendar_range(Some("03/02/2026"), None, today).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_interview_calendar_buckets_by_utc_day_and_filters_panelist() {
+        let pool = setup_test_db().await;
+
+        let calendar =
+            get_interview_calendar(&pool, day("2026-03-02"), day("2026-03-03"), None, "UTC")
+                .await
+                .unwrap();
+        assert_eq!(calendar.total, 2);
+        assert_eq!(calendar.days.len(), 2);
+        assert_eq!(calendar.days[0].interviews[0].id, "int2");
+        assert_eq!(calendar.days[1].interviews[0].id, "int1");
+        assert_eq!(
+            calendar.days[1].interviews[0].job_title.as_deref(),
+            Some("Backend Engineer")
+        );
+        assert_eq!(
+            calendar.days[1].interviews[0].end_time,
+            "2026-03-03T05:15:00+00:00"
+        );
+
+        let filtered = get_interview_calendar(
+            &pool,
+            day("2026-03-01"),
+            day("2026-03-31"),
+            Some("Lead@Example.com"),
+            "UTC",
+        )
+        .await
+        .unwrap();
+        let ids: Vec<&str> = filtered
+            .days
+            .iter()
+            .flat_map(|d| d.interviews.iter().map(|i| i.id.as_str()))
+            .collect();
+        assert_eq!(ids, vec!["int1", "int3"]);
+    }
+
+    #[tokio::test]
+    async fn test_interview_calendar_splits_days_in_viewer_timezone() {
+        let pool = setup_test_db().await;
+
+        let calendar = get_interview_calendar(
+            &pool,
+            day("2026-03-02"),
+            day("2026-03-02"),
+            None,
+            "America/New_York",
+        )
+        .await
+        .unwrap();
+
+        let ids: Vec<&str> = calendar.days[0]
+            .interviews
+            .iter()
+            .map(|i| i.id.as_str())
+            .collect();
+        assert_eq!(ids, vec!["int2", "int1"]);
+        assert_eq!(
+            calendar.days[0].interviews[1].scheduled_date,
+            "2026-03-02T23:30:00-05:00"
+        );
+    }
+
+    fn synced_interview(calendar_synced_at: Option<&str>) -> Interview {
+        serde_json::from_value(serde_json::json!({
+            "id": "int1",
+            "application_id": "app",
+            "candidate_id": "cand",
+            "job_id": "job",
+            "scheduled_date": "2026-03-02T15:00:00+00:00",
+            "duration_minutes": 45,
+            "interview_type": "technical",
+            "google_meet_link": null,
+            "google_calendar_event_id": "evt1",
+            "panel_members": "[]",
+            "notes": null,
+            "status": "scheduled",
+            "created_by": "admin",
+            "created_at": null,
+            "updated_at": null,
+            "calendar_synced_at": calendar_synced_at,
+        }))
+        .unwrap()
+    }
+
+    fn event(start: &str, end: &str, updated: &str) -> ExternalCalendarEvent {
+        ExternalCalendarEvent {
+            id: "evt1".to_string(),
+            start: parse_stored(start),
+            end: parse_stored(end),
+            updated: parse_stored(updated),
+        }
+    }
+
+    #[test]
+    fn test_classify_external_change() {
+        let interview = synced_interview(None);
+
+        assert_eq!(
+            classify_external_change(&interview, None),
+            ExternalChange::Cancelled
+        );
+        assert_eq!(
+            classify_external_change(
+                &interview,
+                Some(&event(
+                    "2026-03-02T10:00:00-05:00",
+                    "2026-03-02T10:45:00-05:00",
+                    "2026-03-01T09:00:00Z"
+                ))
+            ),
+            ExternalChange::Unchanged
+        );
+        assert_eq!(
+            classify_external_change(
+                &interview,
+                Some(&event(
+                    "2026-03-03T16:00:00Z",
+                    "2026-03-03T17:00:00Z",
+                    "2026-03-01T09:00:00Z"
+                ))
+            ),
+            ExternalChange::Rescheduled {
+                start: parse_stored("2026-03-03T16:00:00Z").unwrap(),
+                duration_minutes: 60,
+            }
+        );
+    }
+
+    #[test]
+    fn test_classify_external_change_ignores_edits_older_than_local_changes() {
+        // Rescheduled in the app after the Google event was last touched
+        let interview = synced_interview(Some("2026-03-01T12:00:00+00:00"));
+
+        assert_eq!(
+            classify_external_change(
+                &interview,
+                Some(&event(
+                    "2026-03-03T16:00:00Z",
+                    "2026-03-03T17:00:00Z",
+                    "2026-03-01T09:00:00Z"
+                ))
+            ),
+            ExternalChange::Unchanged
+        );
+    }
+
+    #[tokio::test]
+    async fn test_candidate_interview_hub_splits_upcoming_and_hides_contacts() {
+        let pool = setup_test_db().await;
+        sqlx::query(
+            "UPDATE interviews SET preparation_notes = 'Bring a laptop', feedback_released_at = '2026-03-03T10:00:00+00:00', panel_members = ? WHERE id = 'int2'",
+        )
+        .bind(r#"[{"email":"lead@example.com","name":"Lee Lead","role":"Manager"},{"email":"other@example.com","name":null,"role":null}]"#)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let now = parse_stored("2026-03-05T00:00:00Z").unwrap();
+        let hub = get_candidate_interview_hub(&pool, "cand", "UTC", now)
+            .await
+            .unwrap();
+
+        let upcoming: Vec<&str> = hub.upcoming.iter().map(|i| i.id.as_str()).collect();
+        let past: Vec<&str> = hub.past.iter().map(|i| i.id.as_str()).collect();
+        assert_eq!(upcoming, vec!["int3"]);
+        assert_eq!(past, vec!["int1", "int2"]);
+
+        let int2 = &hub.past[1];
+        assert_eq!(int2.job_title.as_deref(), Some("Backend Engineer"));
+        assert_eq!(int2.panel, vec!["Lee Lead", "Interviewer"]);
+        assert_eq!(int2.preparation_notes.as_deref(), Some("Bring a laptop"));
+        assert!(int2.feedback_released);
+        assert!(!hub.upcoming[0].feedback_released);
+
+        assert!(get_candidate_interview_hub(&pool, "someone-else", "UTC", now)
+            .await
+            .unwrap()
+            .upcoming
+            .is_empty());
+    }
+}
@@ -1,10 +1,14 @@
 // src/job_templates_service.rs
 use crate::common::ApiError;
 use crate::jobs::models::{
-    AITemplateContext, CreateAITemplateRequest, CreateJobTemplateRequest, JobTemplate,
-    UpdateJobTemplateRequest,
+    AITemplateContext, BundledTemplate, CreateAITemplateRequest, CreateJob,
+    CreateJobTemplateRequest, ImportTemplatesRequest, ImportTemplatesResponse, ImportedTemplate,
+    JobTemplate, TemplateBundle, TemplateConflictPolicy, TemplateExportQuery,
+    TemplatePreviewRequest, TemplatePreviewResponse, UpdateJobTemplateRequest,
+    TEMPLATE_BUNDLE_FORMAT, TEMPLATE_BUNDLE_VERSION,
 };
 use sqlx::SqlitePool;
+use std::collections::{BTreeSet, HashMap, HashSet};
 use tracing::info;
 
 use crate::common::generate_template_id;
@@ -597,6 +601,315 @@ impl JobTemplatesService {
         Ok(templates)
     }
 
+    // ============================================================================
+    // Template Placeholders
+    // ============================================================================
+
+    /// Values for the template placeholders. The company name comes from `company_id` (or the
+    /// free-text `company`), and the location falls back to the company's headquarters.
+    pub async fn placeholder_values(
+        &self,
+        company_id: Option<&str>,
+        company: Option<&str>,
+        location: Option<&str>,
+        salary_min: Option<i64>,
+        salary_max: Option<i64>,
+    ) -> Result<HashMap<&'static str, String>, ApiError> {
+        let company_row = match company_id {
+            Some(company_id) => Some(
+                sqlx::query_as::<_, (String, Option<String>)>(
+                    "SELECT name, headquarters FROM companies WHERE id = ?",
+                )
+                .bind(company_id)
+                .fetch_optional(&self.db)
+                .await
+                .map_err(ApiError::DatabaseError)?
+                .ok_or_else(|| ApiError::NotFound(format!("Company not found: {}", company_id)))?,
+            ),
+            None => None,
+        };
+        let (company_name, headquarters) = match company_row {
+            Some((name, headquarters)) => (Some(name), headquarters.and_then(|h| place_name(&h))),
+            None => (company.map(str::to_string), None),
+        };
+
+        // A location that is itself a placeholder says nothing about where the job is
+        let location = location
+            .filter(|l| !l.trim().is_empty() && !l.contains("{{"))
+            .map(str::to_string)
+            .or(headquarters);
+
+        let mut values = HashMap::new();
+        for (key, value) in [
+            ("company_name", company_name),
+            ("location", location),
+            ("salary_band", salary_band(salary_min, salary_max)),
+        ] {
+            if let Some(value) = value.filter(|v| !v.trim().is_empty()) {
+                values.insert(key, value);
+            }
+        }
+        Ok(values)
+    }
+
+    /// The template's `job_data` with its placeholders resolved, as a job created from it would be
+    pub async fn preview_job(
+        &self,
+        template_id: &str,
+        request: &TemplatePreviewRequest,
+    ) -> Result<TemplatePreviewResponse, ApiError> {
+        let template = self.get_template_by_id(template_id).await?;
+        let mut job_data: serde_json::Value = serde_json::from_str(&template.job_data)
+            .map_err(|e| ApiError::InternalServer(format!("Invalid template job data: {}", e)))?;
+
+        let company_id = request
+            .company_id
+            .as_deref()
+            .or(template.company_id.as_deref());
+        let values = self
+            .placeholder_values(
+                company_id,
+                None,
+                request.location.as_deref(),
+                request.salary_min,
+                request.salary_max,
+            )
+            .await?;
+
+        let mut unresolved = BTreeSet::new();
+        fill_value(&mut job_data, &values, &mut unresolved);
+
+        Ok(TemplatePreviewResponse {
+            template_id: template.id,
+            job_data,
+            unresolved: unresolved.into_iter().collect(),
+        })
+    }
+
+    // ============================================================================
+    // Template Bundle Import/Export
+    // ============================================================================
+
+    /// Export templates as a bundle another deployment can import
+    pub async fn export_bundle(
+        &self,
+        query: &TemplateExportQuery,
+    ) -> Result<TemplateBundle, ApiError> {
+        let mut templates = self.get_all_templates().await?;
+
+        if let Some(ids) = &query.ids {
+            let ids: Vec<&str> = ids
+                .split(',')
+                .map(str::trim)
+                .filter(|id| !id.is_empty())
+                .collect();
+            if let Some(missing) = ids
+                .iter()
+                .find(|id| !templates.iter().any(|t| t.id == **id))
+            {
+                return Err(ApiError::NotFound(format!(
+                    "Template not found: {}",
+                    missing
+                )));
+            }
+            templates.retain(|t| ids.contains(&t.id.as_str()));
+        } else {
+            templates.retain(|t| {
+                let in_company = match &query.company_id {
+                    Some(company_id) => t.company_id.as_deref() == Some(company_id.as_str()),
+                    None => true,
+                };
+                if t.template_type == "system" {
+                    query.include_system
+                } else {
+                    in_company
+                }
+            });
+        }
+
+        let templates = templates
+            .into_iter()
+            .map(|t| BundledTemplate {
+                job_data: serde_json::from_str(&t.job_data)
+                    .unwrap_or_else(|_| serde_json::json!({})),
+                ai_context: t
+                    .ai_context
+                    .as_deref()
+                    .and_then(|c| serde_json::from_str(c).ok()),
+                name: t.name,
+                template_type: t.template_type,
+            })
+            .collect();
+
+        Ok(TemplateBundle {
+            format: TEMPLATE_BUNDLE_FORMAT.to_string(),
+            version: TEMPLATE_BUNDLE_VERSION,
+            exported_at: Some(chrono::Utc::now().to_rfc3339()),
+            templates,
+        })
+    }
+
+    /// Import a validated bundle into a company (or as global templates), resolving name
+    /// conflicts per `on_conflict`. Everything is written in one transaction.
+    pub async fn import_bundle(
+        &self,
+        request: ImportTemplatesRequest,
+        user_id: &str,
+    ) -> Result<ImportTemplatesResponse, ApiError> {
+        let company_id = request
+            .company_id
+            .as_deref()
+            .map(str::trim)
+            .filter(|c| !c.is_empty());
+
+        if let Some(company_id) = company_id {
+            let exists: Option<i64> = sqlx::query_scalar("SELECT 1 FROM companies WHERE id = ?")
+                .bind(company_id)
+                .fetch_optional(&self.db)
+                .await
+                .map_err(ApiError::DatabaseError)?;
+            if exists.is_none() {
+                return Err(ApiError::NotFound(format!(
+                    "Company not found: {}",
+                    company_id
+                )));
+            }
+        }
+
+        let mut tx = self.db.begin().await.map_err(ApiError::DatabaseError)?;
+
+        let existing: Vec<(String, String, String)> = sqlx::query_as(
+            "SELECT id, name, template_type FROM job_templates WHERE company_id IS ?",
+        )
+        .bind(company_id)
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(ApiError::DatabaseError)?;
+        let existing: HashMap<String, (String, String)> = existing
+            .into_iter()
+            .map(|(id, name, template_type)| (name.to_lowercase(), (id, template_type)))
+            .collect();
+        let mut taken: HashSet<String> = existing.keys().cloned().collect();
+
+        let now = chrono::Utc::now().to_rfc3339();
+        let mut results = Vec::with_capacity(request.bundle.templates.len());
+
+        for template in request.bundle.templates {
+            let name = template.name.trim().to_string();
+            // Only seeding creates system templates; imported ones become editable copies
+            let template_type = if template.template_type == "ai" {
+                "ai"
+            } else {
+                "custom"
+            };
+            let job_data = match template.job_data {
+                serde_json::Value::Null => serde_json::json!({}),
+                job_data => job_data,
+            }
+            .to_string();
+            let ai_context = template
+                .ai_context
+                .as_ref()
+                .map(serde_json::to_string)
+                .transpose()
+                .map_err(|e| ApiError::ValidationError(format!("Invalid AI context: {}", e)))?;
+
+            let conflict = existing.get(&name.to_lowercase());
+            // System templates are never overwritten; the import is renamed instead
+            let policy = match conflict {
+                Some((_, existing_type))
+                    if existing_type == "system"
+                        && request.on_conflict == TemplateConflictPolicy::Overwrite =>
+                {
+                    TemplateConflictPolicy::Rename
+                }
+                _ => request.on_conflict,
+            };
+
+            let (saved_name, action, overwrite_id) = match (conflict, policy) {
+                (None, _) => (name.clone(), "created", None),
+                (Some(_), TemplateConflictPolicy::Skip) => {
+                    results.push(ImportedTemplate {
+                        name,
+                        saved_as: None,
+                        action: "skipped".to_string(),
+                        template_id: conflict.map(|(id, _)| id.clone()),
+                    });
+                    continue;
+                }
+                (Some(_), TemplateConflictPolicy::Rename) => {
+                    (unique_name(&name, &taken), "renamed", None)
+                }
+                (Some((id, _)), TemplateConflictPolicy::Overwrite) => {
+                    (name.clone(), "overwritten", Some(id.clone()))
+                }
+            };
+            taken.insert(saved_name.to_lowercase());
+
+            let template_id = if request.dry_run {
+                overwrite_id.clone()
+            } else if let Some(id) = &overwrite_id {
+                sqlx::query(
+                    "UPDATE job_templates SET template_type = ?, job_data = ?, ai_context = ?, updated_at = ? WHERE id = ?",
+                )
+                .bind(template_type)
+                .bind(&job_data)
+                .bind(&ai_context)
+                .bind(&now)
+                .bind(id)
+                .execute(&mut *tx)
+                .await
+                .map_err(ApiError::DatabaseError)?;
+                Some(id.clone())
+            } else {
+                let id = generate_template_id();
+                sqlx::query(
+                    r#"
+                    INSERT INTO job_templates (id, name, company_id, template_type, job_data, ai_context, created_by, created_at, updated_at)
+                    VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+                    "#,
+                )
+                .bind(&id)
+                .bind(&saved_name)
+                .bind(company_id)
+                .bind(template_type)
+                .bind(&job_data)
+                .bind(&ai_context)
+                .bind(user_id)
+                .bind(&now)
+                .bind(&now)
+                .execute(&mut *tx)
+                .await
+                .map_err(ApiError::DatabaseError)?;
+                Some(id)
+            };
+
+            results.push(ImportedTemplate {
+                saved_as: (saved_name != name).then_some(saved_name),
+                name,
+                action: action.to_string(),
+                template_id,
+            });
+        }
+
+        if request.dry_run {
+            tx.rollback().await.map_err(ApiError::DatabaseError)?;
+        } else {
+            tx.commit().await.map_err(ApiError::DatabaseError)?;
+            info!(
+                company_id = ?company_id,
+                imported = results.iter().filter(|r| r.action != "skipped").count(),
+                skipped = results.iter().filter(|r| r.action == "skipped").count(),
+                "Imported job template bundle"
+            );
+        }
+
+        Ok(ImportTemplatesResponse {
+            dry_run: request.dry_run,
+            templates: results,
+        })
+    }
+
     // ============================================================================
     // System Template Definitions
     // ============================================================================
@@ -771,3 +1084,187 @@ impl JobTemplatesService {
         (name, job_data.to_string())
     }
 }
+
+/// Human-readable salary range used for `{{salary_band}}`
+fn salary_band(salary_min: Option<i64>, salary_max: Option<i64>) -> Option<String> {
+    match (salary_min, salary_max) {
+        (Some(min), Some(max)) if min == max => Some(min.to_string()),
+        (Some(min), Some(max)) => Some(format!("{} - {}", min, max)),
+        (Some(min), None) => Some(format!("From {}", min)),
+        (None, Some(max)) => Some(format!("Up to {}", max)),
+        (None, None) => None,
+    }
+}
+
+/// "City, State, Country" from a company's stored headquarters (`{"city": .., "state": ..}`)
+fn place_name(headquarters: &str) -> Option<String> {
+    match serde_json::from_str::<serde_json::Value>(headquarters) {
+        Ok(serde_json::Value::Object(place)) => {
+            let parts: Vec<&str> = ["city", "state", "country"]
+                .iter()
+                .filter_map(|key| place.get(*key)?.as_str())
+                .map(str::trim)
+                .filter(|part| !part.is_empty())
+                .collect();
+            (!parts.is_empty()).then(|| parts.join(", "))
+        }
+        Ok(serde_json::Value::String(place)) => Some(place),
+        Ok(_) => None,
+        // Older rows hold plain text
+        Err(_) => Some(headquarters.to_string()),
+    }
+}
+
+/// Replace `{{name}}` placeholders in `text`. Placeholders without a value are kept as written
+/// and their names added to `unresolved`.
+pub fn fill_placeholders(
+    text: &str,
+    values: &HashMap<&'static str, String>,
+    unresolved: &mut BTreeSet<String>,
+) -> String {
+    let mut filled = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("{{") {
+        let Some(len) = rest[start + 2..].find("}}") else {
+            break;
+        };
+        let key = rest[start + 2..start + 2 + len].trim();
+        let placeholder_end = start + 2 + len + 2;
+        filled.push_str(&rest[..start]);
+        match values.get(key) {
+            Some(value) => filled.push_str(value),
+            None => {
+                if !key.is_empty() && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+                    unresolved.insert(key.to_string());
+                }
+                filled.push_str(&rest[start..placeholder_end]);
+            }
+        }
+        rest = &rest[placeholder_end..];
+    }
+    filled.push_str(rest);
+    filled
+}
+
+/// Fill placeholders in every string within `value`
+fn fill_value(
+    value: &mut serde_json::Value,
+    values: &HashMap<&'static str, String>,
+    unresolved: &mut BTreeSet<String>,
+) {
+    match value {
+        serde_json::Value::String(text) => *text = fill_placeholders(text, values, unresolved),
+        serde_json::Value::Array(items) => {
+            for item in items {
+                fill_value(item, values, unresolved);
+            }
+        }
+        serde_json::Value::Object(fields) => {
+            for field in fields.values_mut() {
+                fill_value(field, values, unresolved);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Fill placeholders in the text fields of a job being created from a template. Returns the
+/// placeholders that had no value.
+pub fn fill_job_placeholders(
+    job: &mut CreateJob,
+    values: &HashMap<&'static str, String>,
+) -> BTreeSet<String> {
+    let mut unresolved = BTreeSet::new();
+    let mut fill = |text: &mut String| *text = fill_placeholders(text, values, &mut unresolved);
+
+    fill(&mut job.title);
+    for text in [job.description.as_mut(), job.location.as_mut()]
+        .into_iter()
+        .flatten()
+    {
+        fill(text);
+    }
+    for list in [job.requirements.as_mut(), job.benefits.as_mut()]
+        .into_iter()
+        .flatten()
+    {
+        list.iter_mut().for_each(&mut fill);
+    }
+    if let Some(qualifications) = job.educational_qualifications.as_mut() {
+        fill_value(qualifications, values, &mut unresolved);
+    }
+
+    unresolved
+}
+
+/// `name` with the lowest ` (n)` suffix not already in `taken` (lowercased names)
+fn unique_name(name: &str, taken: &HashSet<String>) -> String {
+    (2..)
+        .map(|n| format!("{} ({})", name, n))
+        .find(|candidate| !taken.contains(&candidate.to_lowercase()))
+        .unwrap_or_else(|| name.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unique_name_skips_taken_suffixes() {
+        let taken: HashSet<String> = ["physics faculty", "physics faculty (2)"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+        assert_eq!(
+            unique_name("Physics Faculty", &taken),
+            "Physics Faculty (3)"
+        );
+        assert_eq!(unique_name("Chemistry", &taken), "Chemistry (2)");
+    }
+
+    #[test]
+    fn test_fill_placeholders_keeps_unknown_and_missing() {
+        let values = HashMap::from([("company_name", "Acme Academy".to_string())]);
+        let mut unresolved = BTreeSet::new();
+        let filled = fill_placeholders(
+            "Join {{ company_name }} in {{location}} for {{salary_band}} {{not a key}} {{",
+            &values,
+            &mut unresolved,
+        );
+        assert_eq!(
+            filled,
+            "Join Acme Academy in {{location}} for {{salary_band}} {{not a key}} {{"
+        );
+        assert_eq!(
+            unresolved.into_iter().collect::<Vec<_>>(),
+            vec!["location".to_string(), "salary_band".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_place_name_from_headquarters() {
+        assert_eq!(
+            place_name(r#"{"city": "Kota", "state": "Rajasthan", "country": ""}"#).as_deref(),
+            Some("Kota, Rajasthan")
+        );
+        assert_eq!(place_name("Pune").as_deref(), Some("Pune"));
+        assert_eq!(place_name("{}"), None);
+    }
+
+    #[test]
+    fn test_salary_band_formats() {
+        assert_eq!(
+            salary_band(Some(50000), Some(80000)).as_deref(),
+            Some("50000 - 80000")
+        );
+        assert_eq!(
+            salary_band(Some(50000), None).as_deref(),
+            Some("From 50000")
+        );
+        assert_eq!(
+            salary_band(None, Some(80000)).as_deref(),
+            Some("Up to 80000")
+        );
+        assert_eq!(salary_band(None, None), None);
+    }
+}
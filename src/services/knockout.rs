@@ -0,0 +1,487 @@
+// src/services/knockout.rs
+//! Knockout screening: per-job rules checked the moment a candidate applies
+//!
+//! A rule asks for work authorization in a country, a minimum number of years of experience,
+//! or a set answer to a screening question. When it fires, the application is tagged or
+//! rejected (with a rejection email), and the hit is logged in `application_knockouts`.
+//! Experience comes from the attached resume; while it is unknown a `min_years` rule does
+//! not fire, so unscanned resumes are never rejected on a guess.
+
+use std::collections::HashMap;
+
+use tracing::{error, info, warn};
+
+use crate::admin::models::{ApplicationKnockout, KnockoutRule};
+use crate::candidates::handlers::email_templates::{
+    get_email_template, status_to_stage, EmailTemplate,
+};
+use crate::common::i18n::{user_locale, Locale};
+use crate::common::{generate_history_id, ApiError, AppState};
+use crate::services::sanitize::render_markdown;
+
+pub const RULE_TYPES: &[&str] = &["work_authorization", "min_years", "question"];
+pub const ACTIONS: &[&str] = &["tag", "reject"];
+
+const MAX_NAME_LENGTH: usize = 200;
+const MAX_QUESTION_LENGTH: usize = 500;
+const MAX_TAG_LENGTH: usize = 50;
+
+/// What the candidate told us on the application form
+pub struct KnockoutInput<'a> {
+    /// Countries the candidate is authorized to work in
+    pub work_authorization: &'a [String],
+    /// Screening answers keyed by rule ID
+    pub answers: &'a HashMap<String, String>,
+    pub experience_years: Option<f64>,
+}
+
+fn present(value: &Option<String>) -> Option<&str> {
+    value.as_deref().map(str::trim).filter(|v| !v.is_empty())
+}
+
+/// Check a rule has what its type and action need
+pub fn validate_rule(rule: &KnockoutRule) -> Result<(), ApiError> {
+    let name = rule.name.trim();
+    if name.is_empty() || name.chars().count() > MAX_NAME_LENGTH {
+        return Err(ApiError::ValidationError(format!(
+            "name must be 1-{} characters",
+            MAX_NAME_LENGTH
+        )));
+    }
+    if !RULE_TYPES.contains(&rule.rule_type.as_str()) {
+        return Err(ApiError::ValidationError(format!(
+            "rule_type must be one of: {}",
+            RULE_TYPES.join(", ")
+        )));
+    }
+    if !ACTIONS.contains(&rule.action.as_str()) {
+        return Err(ApiError::ValidationError(format!(
+            "action must be one of: {}",
+            ACTIONS.join(", ")
+        )));
+    }
+
+    match rule.rule_type.as_str() {
+        "work_authorization" if present(&rule.country).is_none() => {
+            return Err(ApiError::ValidationError(
+                "country is required for work_authorization rules".to_string(),
+            ));
+        }
+        "min_years" if !rule.min_years.is_some_and(|y| y.is_finite() && y > 0.0) => {
+            return Err(ApiError::ValidationError(
+                "min_years must be greater than 0 for min_years rules".to_string(),
+            ));
+        }
+        "question" => {
+            let question = present(&rule.question).ok_or_else(|| {
+                ApiError::ValidationError("question is required for question rules".to_string())
+            })?;
+            if question.chars().count() > MAX_QUESTION_LENGTH {
+                return Err(ApiError::ValidationError(format!(
+                    "question must be at most {} characters",
+                    MAX_QUESTION_LENGTH
+                )));
+            }
+            if present(&rule.expected_answer).is_none() {
+                return Err(ApiError::ValidationError(
+                    "expected_answer is required for question rules".to_string(),
+                ));
+            }
+        }
+        _ => {}
+    }
+
+    if rule.action == "tag" {
+        let tag = present(&rule.tag).ok_or_else(|| {
+            ApiError::ValidationError("tag is required when action is tag".to_string())
+        })?;
+        if tag.chars().count() > MAX_TAG_LENGTH {
+            return Err(ApiError::ValidationError(format!(
+                "tag must be at most {} characters",
+                MAX_TAG_LENGTH
+            )));
+        }
+    }
+    Ok(())
+}
+
+fn same_text(a: &str, b: &str) -> bool {
+    a.trim().eq_ignore_ascii_case(b.trim())
+}
+
+/// Why `rule` knocks the candidate out, or `None` when they pass it
+pub fn fired_reason(rule: &KnockoutRule, input: &KnockoutInput) -> Option<String> {
+    match rule.rule_type.as_str() {
+        "work_authorization" => {
+            let country = present(&rule.country)?;
+            let authorized = input
+                .work_authorization
+                .iter()
+                .any(|c| same_text(c, country));
+            (!authorized).then(|| format!("Not authorized to work in {}", country))
+        }
+        "min_years" => {
+            let min = rule.min_years?;
+            let years = input.experience_years?;
+            (years < min).then(|| format!("{} years of experience, {} required", years, min))
+        }
+        "question" => {
+            let question = present(&rule.question)?;
+            let expected = present(&rule.expected_answer)?;
+            match input.answers.get(&rule.id).map(|a| a.trim()) {
+                Some(answer) if same_text(answer, expected) => None,
+                Some("") | None => Some(format!("No answer to \"{}\"", question)),
+                Some(answer) => Some(format!("Answered \"{}\" to \"{}\"", answer, question)),
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Active rules that fire for `input`, each with its reason
+pub fn evaluate<'a>(
+    rules: &'a [KnockoutRule],
+    input: &KnockoutInput,
+) -> Vec<(&'a KnockoutRule, String)> {
+    rules
+        .iter()
+        .filter(|rule| rule.is_active)
+        .filter_map(|rule| fired_reason(rule, input).map(|reason| (rule, reason)))
+        .collect()
+}
+
+fn fill(template: &str, args: &[(&str, &str)]) -> String {
+    args.iter()
+        .fold(template.to_string(), |text, (name, value)| {
+            text.replace(&format!("{{{}}}", name), value)
+        })
+}
+
+/// The rejection email for a rule: its own subject and body where set, the standard
+/// rejection email otherwise
+pub fn rejection_email(
+    rule: &KnockoutRule,
+    candidate_name: &str,
+    job_title: &str,
+    company_name: &str,
+    locale: Locale,
+) -> EmailTemplate {
+    let standard = get_email_template("rejected", candidate_name, job_title, company_name, locale);
+    let args = [
+        ("name", candidate_name),
+        ("job", job_title),
+        ("company", company_name),
+    ];
+    EmailTemplate {
+        subject: present(&rule.email_subject)
+            .map(|subject| fill(subject, &args))
+            .unwrap_or(standard.subject),
+        body: present(&rule.email_body)
+            .map(|body| render_markdown(&fill(body, &args)))
+            .unwrap_or(standard.body),
+    }
+}
+
+pub async fn job_rules(
+    pool: &sqlx::SqlitePool,
+    job_id: &str,
+    active_only: bool,
+) -> Result<Vec<KnockoutRule>, sqlx::Error> {
+    sqlx::query_as::<_, KnockoutRule>(
+        r#"
+        SELECT * FROM knockout_rules
+        WHERE job_id = ? AND (is_active = 1 OR ? = 0)
+        ORDER BY created_at, id
+        "#,
+    )
+    .bind(job_id)
+    .bind(active_only)
+    .fetch_all(pool)
+    .await
+}
+
+/// Run a job's knockout rules against a new application and act on the ones that fire
+///
+/// Tags are recorded with the hit. The first rejecting rule rejects the application and
+/// emails the candidate; a failed email is logged and does not undo the rejection.
+pub async fn screen_application(
+    state: &AppState,
+    application_id: &str,
+    input: &KnockoutInput<'_>,
+) -> Result<Vec<ApplicationKnockout>, sqlx::Error> {
+    let application: Option<(String, String)> =
+        sqlx::query_as("SELECT job_id, user_id FROM applications WHERE id = ?")
+            .bind(application_id)
+            .fetch_optional(&state.db)
+            .await?;
+    let Some((job_id, user_id)) = application else {
+        return Ok(Vec::new());
+    };
+
+    let rules = job_rules(&state.db, &job_id, true).await?;
+    let fired = evaluate(&rules, input);
+    if fired.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let rejecting_rule = fired
+        .iter()
+        .map(|(rule, _)| *rule)
+        .find(|rule| rule.action == "reject");
+    let email_sent = match rejecting_rule {
+        Some(rule) => reject(state, application_id, &user_id, &job_id, rule).await?,
+        None => false,
+    };
+
+    for (rule, reason) in &fired {
+        sqlx::query(
+            r#"
+            INSERT INTO application_knockouts
+                (id, application_id, job_id, rule_id, rule_name, action, tag, reason, email_sent)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(generate_history_id())
+        .bind(application_id)
+        .bind(&job_id)
+        .bind(&rule.id)
+        .bind(&rule.name)
+        .bind(&rule.action)
+        .bind(present(&rule.tag))
+        .bind(reason)
+        .bind(email_sent && rejecting_rule.is_some_and(|r| r.id == rule.id))
+        .execute(&state.db)
+        .await?;
+
+        info!(
+            application_id = %application_id,
+            job_id = %job_id,
+            rule_id = %rule.id,
+            action = %rule.action,
+            reason = %reason,
+            "Knockout rule fired"
+        );
+    }
+
+    application_knockouts(&state.db, application_id).await
+}
+
+/// Reject the application on behalf of the rule's author; returns whether the email went out
+async fn reject(
+    state: &AppState,
+    application_id: &str,
+    user_id: &str,
+    job_id: &str,
+    rule: &KnockoutRule,
+) -> Result<bool, sqlx::Error> {
+    sqlx::query(
+        "UPDATE applications SET status = 'rejected', current_stage = ?, updated_at = datetime('now') WHERE id = ?",
+    )
+    .bind(status_to_stage("rejected"))
+    .bind(application_id)
+    .execute(&state.db)
+    .await?;
+
+    sqlx::query(
+        r#"
+        INSERT INTO application_status_history (id, application_id, status, changed_by, notes, changed_at)
+        VALUES (?, ?, 'rejected', ?, ?, datetime('now'))
+        "#,
+    )
+    .bind(generate_history_id())
+    .bind(application_id)
+    .bind(&rule.created_by)
+    .bind(format!("Knocked out by rule: {}", rule.name))
+    .execute(&state.db)
+    .await?;
+
+    let details: Option<(Option<String>, String, String, Option<String>)> = sqlx::query_as(
+        r#"
+        SELECT u.name, u.email, j.title, j.company FROM users u, jobs j
+        WHERE u.id = ? AND j.id = ?
+        "#,
+    )
+    .bind(user_id)
+    .bind(job_id)
+    .fetch_optional(&state.db)
+    .await?;
+    let Some((name, email, job_title, company)) = details else {
+        warn!(application_id = %application_id, "Knockout rejection email skipped: candidate or job missing");
+        return Ok(false);
+    };
+
+    let locale = user_locale(&state.db, user_id).await.unwrap_or_default();
+    let template = rejection_email(
+        rule,
+        name.as_deref().unwrap_or("Candidate"),
+        &job_title,
+        company.as_deref().unwrap_or("Our Company"),
+        locale,
+    );
+    match state
+        .email
+        .send_email(vec![email], &template.subject, &template.body, None)
+        .await
+    {
+        Ok(_) => Ok(true),
+        Err(e) => {
+            error!(error = %e, application_id = %application_id, "Failed to send knockout rejection email");
+            Ok(false)
+        }
+    }
+}
+
+pub async fn application_knockouts(
+    pool: &sqlx::SqlitePool,
+    application_id: &str,
+) -> Result<Vec<ApplicationKnockout>, sqlx::Error> {
+    sqlx::query_as::<_, ApplicationKnockout>(
+        "SELECT * FROM application_knockouts WHERE application_id = ? ORDER BY created_at, rule_name",
+    )
+    .bind(application_id)
+    .fetch_all(pool)
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(id: &str, rule_type: &str, action: &str) -> KnockoutRule {
+        KnockoutRule {
+            id: id.to_string(),
+            job_id: "J_1".to_string(),
+            name: format!("{} rule", rule_type),
+            rule_type: rule_type.to_string(),
+            country: None,
+            min_years: None,
+            question: None,
+            expected_answer: None,
+            action: action.to_string(),
+            tag: None,
+            email_subject: None,
+            email_body: None,
+            is_active: true,
+            created_by: "U_ADMIN".to_string(),
+            created_at: None,
+            updated_at: None,
+        }
+    }
+
+    fn input<'a>(
+        work_authorization: &'a [String],
+        answers: &'a HashMap<String, String>,
+        experience_years: Option<f64>,
+    ) -> KnockoutInput<'a> {
+        KnockoutInput {
+            work_authorization,
+            answers,
+            experience_years,
+        }
+    }
+
+    #[test]
+    fn test_validate_rule_requires_type_fields() {
+        let mut work = rule("KR_1", "work_authorization", "reject");
+        assert!(validate_rule(&work).is_err());
+        work.country = Some("Canada".to_string());
+        assert!(validate_rule(&work).is_ok());
+
+        let mut years = rule("KR_2", "min_years", "reject");
+        years.min_years = Some(0.0);
+        assert!(validate_rule(&years).is_err());
+        years.min_years = Some(3.0);
+        assert!(validate_rule(&years).is_ok());
+
+        let mut question = rule("KR_3", "question", "tag");
+        question.question = Some("Can you travel?".to_string());
+        question.expected_answer = Some("yes".to_string());
+        assert!(validate_rule(&question).is_err(), "tag action needs a tag");
+        question.tag = Some("no-travel".to_string());
+        assert!(validate_rule(&question).is_ok());
+
+        assert!(validate_rule(&rule("KR_4", "age", "reject")).is_err());
+        assert!(validate_rule(&rule("KR_5", "min_years", "hold")).is_err());
+    }
+
+    #[test]
+    fn test_work_authorization_rule() {
+        let mut work = rule("KR_1", "work_authorization", "reject");
+        work.country = Some("Canada".to_string());
+        let answers = HashMap::new();
+
+        let authorized = vec!["United States".to_string(), "canada".to_string()];
+        assert_eq!(
+            fired_reason(&work, &input(&authorized, &answers, None)),
+            None
+        );
+        assert_eq!(
+            fired_reason(&work, &input(&[], &answers, None)).as_deref(),
+            Some("Not authorized to work in Canada")
+        );
+    }
+
+    #[test]
+    fn test_min_years_rule_skips_unknown_experience() {
+        let mut years = rule("KR_1", "min_years", "reject");
+        years.min_years = Some(5.0);
+        let answers = HashMap::new();
+
+        assert!(fired_reason(&years, &input(&[], &answers, Some(3.5))).is_some());
+        assert_eq!(fired_reason(&years, &input(&[], &answers, Some(5.0))), None);
+        assert_eq!(fired_reason(&years, &input(&[], &answers, None)), None);
+    }
+
+    #[test]
+    fn test_question_rule_compares_answers() {
+        let mut question = rule("KR_1", "question", "reject");
+        question.question = Some("Do you hold a driving licence?".to_string());
+        question.expected_answer = Some("Yes".to_string());
+
+        let mut answers = HashMap::new();
+        assert!(fired_reason(&question, &input(&[], &answers, None)).is_some());
+        answers.insert("KR_1".to_string(), " yes ".to_string());
+        assert_eq!(fired_reason(&question, &input(&[], &answers, None)), None);
+        answers.insert("KR_1".to_string(), "no".to_string());
+        assert_eq!(
+            fired_reason(&question, &input(&[], &answers, None)).as_deref(),
+            Some("Answered \"no\" to \"Do you hold a driving licence?\"")
+        );
+    }
+
+    #[test]
+    fn test_evaluate_skips_inactive_rules() {
+        let mut active = rule("KR_1", "work_authorization", "tag");
+        active.country = Some("Germany".to_string());
+        let mut inactive = active.clone();
+        inactive.id = "KR_2".to_string();
+        inactive.is_active = false;
+
+        let rules = vec![active, inactive];
+        let answers = HashMap::new();
+        let fired = evaluate(&rules, &input(&[], &answers, None));
+        assert_eq!(fired.len(), 1);
+        assert_eq!(fired[0].0.id, "KR_1");
+    }
+
+    #[test]
+    fn test_rejection_email_fills_placeholders() {
+        let mut custom = rule("KR_1", "min_years", "reject");
+        custom.email_subject = Some("Your application for {job}".to_string());
+        custom.email_body =
+            Some("Hi {name},\n\n{company} needs **5+ years** for this role.".to_string());
+        let email = rejection_email(&custom, "Ada", "Rust Engineer", "Acme", Locale::En);
+        assert_eq!(email.subject, "Your application for Rust Engineer");
+        assert!(email.body.contains("Hi Ada,"));
+        assert!(email.body.contains("<strong>5+ years</strong>"));
+
+        let standard = rejection_email(
+            &rule("KR_2", "min_years", "reject"),
+            "Ada",
+            "Rust Engineer",
+            "Acme",
+            Locale::En,
+        );
+        assert!(standard.subject.contains("Rust Engineer"));
+    }
+}
@@ -0,0 +1,234 @@
+// src/services/legal_hold.rs
+//! Legal holds: preserve a user's or an application's data regardless of deletion requests
+//!
+//! A hold on a user covers everything they own; a hold on an application covers that
+//! application and the resume attached to it. Any deletion path for candidate data (resume
+//! deletion, GDPR purges, retention cleanup) must check for an active hold first, and every
+//! deletion a hold stops is written to `legal_hold_events`.
+
+use sqlx::SqlitePool;
+use tracing::warn;
+
+use crate::admin::models::LegalHold;
+use crate::common::{generate_history_id, ApiError, ErrorCode};
+
+pub const SUBJECT_TYPES: &[&str] = &["user", "application"];
+
+/// Append an entry to a hold's audit trail
+pub async fn record_event(
+    pool: &SqlitePool,
+    hold_id: &str,
+    action: &str,
+    actor_id: Option<&str>,
+    details: Option<&str>,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "INSERT INTO legal_hold_events (id, hold_id, action, actor_id, details) VALUES (?, ?, ?, ?, ?)",
+    )
+    .bind(generate_history_id())
+    .bind(hold_id)
+    .bind(action)
+    .bind(actor_id)
+    .bind(details)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// An active hold covering any of a user's data
+pub async fn hold_for_user(
+    pool: &SqlitePool,
+    user_id: &str,
+) -> Result<Option<LegalHold>, sqlx::Error> {
+    sqlx::query_as::<_, LegalHold>(
+        "SELECT * FROM legal_holds WHERE user_id = ? AND released_at IS NULL ORDER BY placed_at LIMIT 1",
+    )
+    .bind(user_id)
+    .fetch_optional(pool)
+    .await
+}
+
+/// An active hold on a resume's owner or on an application the resume is attached to
+pub async fn hold_for_resume(
+    pool: &SqlitePool,
+    resume_id: &str,
+) -> Result<Option<LegalHold>, sqlx::Error> {
+    sqlx::query_as::<_, LegalHold>(
+        r#"
+        SELECT h.* FROM legal_holds h
+        WHERE h.released_at IS NULL
+          AND (
+              (h.subject_type = 'user'
+               AND h.subject_id = (SELECT user_id FROM resumes WHERE id = ?))
+              OR (h.subject_type = 'application'
+                  AND h.subject_id IN (SELECT id FROM applications WHERE resume_id = ?))
+          )
+        ORDER BY h.placed_at
+        LIMIT 1
+        "#,
+    )
+    .bind(resume_id)
+    .bind(resume_id)
+    .fetch_optional(pool)
+    .await
+}
+
+/// Audit a deletion stopped by a hold and build the error returned to the caller
+async fn blocked(
+    pool: &SqlitePool,
+    hold: &LegalHold,
+    actor_id: Option<&str>,
+    what: &str,
+) -> ApiError {
+    warn!(
+        hold_id = %hold.id,
+        actor_id = ?actor_id,
+        what = %what,
+        "Deletion blocked by legal hold"
+    );
+    if let Err(e) = record_event(pool, &hold.id, "blocked", actor_id, Some(what)).await {
+        return ApiError::DatabaseError(e);
+    }
+    ApiError::Coded(
+        ErrorCode::LegalHoldActive,
+        format!("{} is under a legal hold and cannot be deleted", what),
+    )
+}
+
+/// Refuse to delete a resume that is under a hold
+pub async fn ensure_resume_deletable(
+    pool: &SqlitePool,
+    resume_id: &str,
+    actor_id: Option<&str>,
+) -> Result<(), ApiError> {
+    match hold_for_resume(pool, resume_id)
+        .await
+        .map_err(ApiError::DatabaseError)?
+    {
+        Some(hold) => Err(blocked(pool, &hold, actor_id, &format!("Resume {}", resume_id)).await),
+        None => Ok(()),
+    }
+}
+
+/// Refuse to purge or clean up a user's data while any of it is under a hold
+///
+/// For GDPR erasure and retention jobs; pass `None` as the actor for automated cleanup.
+#[allow(dead_code)]
+pub async fn ensure_user_deletable(
+    pool: &SqlitePool,
+    user_id: &str,
+    actor_id: Option<&str>,
+) -> Result<(), ApiError> {
+    match hold_for_user(pool, user_id)
+        .await
+        .map_err(ApiError::DatabaseError)?
+    {
+        Some(hold) => Err(blocked(pool, &hold, actor_id, &format!("User {}", user_id)).await),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn setup_test_db() -> SqlitePool {
+        let pool = SqlitePool::connect(":memory:").await.unwrap();
+
+        for statement in [
+            "CREATE TABLE resumes (id TEXT PRIMARY KEY, user_id TEXT NOT NULL)",
+            "CREATE TABLE applications (id TEXT PRIMARY KEY, user_id TEXT NOT NULL, resume_id TEXT)",
+            r#"CREATE TABLE legal_holds (
+                id TEXT PRIMARY KEY, subject_type TEXT NOT NULL, subject_id TEXT NOT NULL,
+                user_id TEXT NOT NULL, reason TEXT NOT NULL, matter_reference TEXT,
+                placed_by TEXT NOT NULL, placed_at TEXT DEFAULT (datetime('now')),
+                released_by TEXT, released_at TEXT, release_reason TEXT
+            )"#,
+            r#"CREATE TABLE legal_hold_events (
+                id TEXT PRIMARY KEY, hold_id TEXT NOT NULL, action TEXT NOT NULL, actor_id TEXT,
+                details TEXT, created_at TEXT DEFAULT (datetime('now'))
+            )"#,
+            "INSERT INTO resumes VALUES ('r1', 'u1'), ('r2', 'u1'), ('r3', 'u2')",
+            "INSERT INTO applications VALUES ('a1', 'u1', 'r2')",
+        ] {
+            sqlx::query(statement).execute(&pool).await.unwrap();
+        }
+
+        pool
+    }
+
+    async fn place(
+        pool: &SqlitePool,
+        id: &str,
+        subject_type: &str,
+        subject_id: &str,
+        user_id: &str,
+    ) {
+        sqlx::query(
+            "INSERT INTO legal_holds (id, subject_type, subject_id, user_id, reason, placed_by) VALUES (?, ?, ?, ?, 'litigation', 'admin')",
+        )
+        .bind(id)
+        .bind(subject_type)
+        .bind(subject_id)
+        .bind(user_id)
+        .execute(pool)
+        .await
+        .unwrap();
+    }
+
+    async fn blocked_events(pool: &SqlitePool, hold_id: &str) -> i64 {
+        sqlx::query_scalar(
+            "SELECT COUNT(*) FROM legal_hold_events WHERE hold_id = ? AND action = 'blocked'",
+        )
+        .bind(hold_id)
+        .fetch_one(pool)
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_user_hold_blocks_deletion_until_released() {
+        let pool = setup_test_db().await;
+        place(&pool, "h1", "user", "u1", "u1").await;
+
+        assert_eq!(hold_for_user(&pool, "u1").await.unwrap().unwrap().id, "h1");
+        let err = ensure_resume_deletable(&pool, "r1", Some("u1"))
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            ApiError::Coded(ErrorCode::LegalHoldActive, _)
+        ));
+        assert!(ensure_user_deletable(&pool, "u1", None).await.is_err());
+        assert_eq!(blocked_events(&pool, "h1").await, 2);
+
+        // Other users' data is unaffected
+        assert!(ensure_resume_deletable(&pool, "r3", Some("u2"))
+            .await
+            .is_ok());
+
+        sqlx::query("UPDATE legal_holds SET released_at = datetime('now') WHERE id = 'h1'")
+            .execute(&pool)
+            .await
+            .unwrap();
+        assert!(hold_for_user(&pool, "u1").await.unwrap().is_none());
+        assert!(ensure_resume_deletable(&pool, "r1", Some("u1"))
+            .await
+            .is_ok());
+        assert_eq!(blocked_events(&pool, "h1").await, 2);
+    }
+
+    #[tokio::test]
+    async fn test_application_hold_covers_only_its_resume() {
+        let pool = setup_test_db().await;
+        place(&pool, "h1", "application", "a1", "u1").await;
+
+        assert!(ensure_resume_deletable(&pool, "r2", Some("u1"))
+            .await
+            .is_err());
+        assert!(ensure_resume_deletable(&pool, "r1", Some("u1"))
+            .await
+            .is_ok());
+        assert_eq!(blocked_events(&pool, "h1").await, 1);
+    }
+}
@@ -0,0 +1,192 @@
+// src/services/masking.rs
+//! Masking of personal fields in admin API responses
+//!
+//! Emails, phone numbers and offer salaries are masked in responses to `/api/admin/`
+//! requests unless the admin holds the `view_pii` permission. Response models opt fields
+//! in with `#[serde(serialize_with = "...")]` using the functions below, so no handler
+//! checks anything itself: [`masking_scope`] masks every admin request up front, and the
+//! auth extractor lifts the mask for callers with the permission.
+
+use axum::{extract::Request, middleware::Next, response::Response};
+use serde::Serializer;
+use sqlx::SqlitePool;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tracing::warn;
+
+use crate::common::safe_email_log;
+use crate::services::permissions;
+
+/// Requests under this path are masked
+const ADMIN_PATH_PREFIX: &str = "/api/admin/";
+
+/// Trailing digits of a phone number left visible
+const VISIBLE_PHONE_DIGITS: usize = 4;
+
+struct MaskScope {
+    admin_route: bool,
+    masked: AtomicBool,
+}
+
+tokio::task_local! {
+    static SCOPE: MaskScope;
+}
+
+/// Middleware masking personal fields in admin responses until the caller is known to
+/// hold `view_pii`
+pub async fn masking_scope(request: Request, next: Next) -> Response {
+    let admin_route = request.uri().path().starts_with(ADMIN_PATH_PREFIX);
+    let scope = MaskScope {
+        admin_route,
+        masked: AtomicBool::new(admin_route),
+    };
+    SCOPE.scope(scope, next.run(request)).await
+}
+
+/// Record whether the authenticated caller may see full values; called by the auth extractor
+pub async fn resolve_viewer(pool: &SqlitePool, user_id: &str, is_admin: bool) {
+    let admin_route = SCOPE.try_with(|scope| scope.admin_route).unwrap_or(false);
+    if !admin_route || !is_admin {
+        return;
+    }
+    let reveal = permissions::has_admin_permission(pool, user_id, permissions::VIEW_PII)
+        .await
+        .unwrap_or_else(|e| {
+            warn!(error = %e, user_id = %user_id, "Failed to check view_pii permission; masking");
+            false
+        });
+    let _ = SCOPE.try_with(|scope| scope.masked.store(!reveal, Ordering::Relaxed));
+}
+
+/// Whether personal fields are masked for the current request; never outside a request
+pub fn is_masked() -> bool {
+    SCOPE
+        .try_with(|scope| scope.masked.load(Ordering::Relaxed))
+        .unwrap_or(false)
+}
+
+/// Run `future` as a request with personal fields masked or not
+#[cfg(test)]
+async fn with_masking<F: std::future::Future>(masked: bool, future: F) -> F::Output {
+    let scope = MaskScope {
+        admin_route: true,
+        masked: AtomicBool::new(masked),
+    };
+    SCOPE.scope(scope, future).await
+}
+
+/// `j***@example.com`
+pub fn mask_email(email: &str) -> String {
+    safe_email_log(email)
+}
+
+/// `***0123`; numbers too short to keep any digits are masked entirely
+pub fn mask_phone(phone: &str) -> String {
+    let digits: Vec<char> = phone.chars().filter(char::is_ascii_digit).collect();
+    if digits.len() <= VISIBLE_PHONE_DIGITS * 2 {
+        return "***".to_string();
+    }
+    let tail: String = digits[digits.len() - VISIBLE_PHONE_DIGITS..]
+        .iter()
+        .collect();
+    format!("***{}", tail)
+}
+
+/// The email as the current request may see it
+pub fn visible_email(email: &str) -> String {
+    if is_masked() {
+        mask_email(email)
+    } else {
+        email.to_string()
+    }
+}
+
+/// The phone number as the current request may see it
+pub fn visible_phone(phone: &str) -> String {
+    if is_masked() {
+        mask_phone(phone)
+    } else {
+        phone.to_string()
+    }
+}
+
+pub fn email<S: Serializer>(value: &str, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&visible_email(value))
+}
+
+pub fn email_opt<S: Serializer>(value: &Option<String>, serializer: S) -> Result<S::Ok, S::Error> {
+    match value {
+        Some(value) => email(value, serializer),
+        None => serializer.serialize_none(),
+    }
+}
+
+pub fn phone<S: Serializer>(value: &str, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&visible_phone(value))
+}
+
+pub fn phone_opt<S: Serializer>(value: &Option<String>, serializer: S) -> Result<S::Ok, S::Error> {
+    match value {
+        Some(value) => phone(value, serializer),
+        None => serializer.serialize_none(),
+    }
+}
+
+/// Salaries are left out (`null`) rather than masked
+pub fn salary<S: Serializer>(value: &f64, serializer: S) -> Result<S::Ok, S::Error> {
+    if is_masked() {
+        serializer.serialize_none()
+    } else {
+        serializer.serialize_f64(*value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Serialize;
+
+    #[derive(Serialize)]
+    struct Row {
+        #[serde(serialize_with = "email")]
+        email: String,
+        #[serde(serialize_with = "phone_opt")]
+        phone: Option<String>,
+        #[serde(serialize_with = "salary")]
+        salary: f64,
+    }
+
+    fn row() -> Row {
+        Row {
+            email: "jane@example.com".to_string(),
+            phone: Some("+1 (415) 555-0123".to_string()),
+            salary: 125000.0,
+        }
+    }
+
+    #[test]
+    fn test_mask_phone_keeps_last_digits() {
+        assert_eq!(mask_phone("+1 (415) 555-0123"), "***0123");
+        assert_eq!(mask_phone("555-0123"), "***");
+        assert_eq!(mask_phone(""), "***");
+    }
+
+    #[tokio::test]
+    async fn test_fields_masked_only_in_masked_scope() {
+        let masked = with_masking(true, async { serde_json::to_value(row()).unwrap() }).await;
+        assert_eq!(
+            masked,
+            serde_json::json!({"email": "j***@example.com", "phone": "***0123", "salary": null})
+        );
+
+        let full = with_masking(false, async { serde_json::to_value(row()).unwrap() }).await;
+        assert_eq!(full["email"], "jane@example.com");
+        assert_eq!(full["phone"], "+1 (415) 555-0123");
+        assert_eq!(full["salary"], 125000.0);
+
+        // Background work and non-admin requests see full values
+        assert_eq!(
+            serde_json::to_value(row()).unwrap()["email"],
+            "jane@example.com"
+        );
+    }
+}
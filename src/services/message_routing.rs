@@ -0,0 +1,340 @@
+// src/services/message_routing.rs
+//! Route new candidate messages to a recruiter who is online
+//!
+//! A conversation whose assignee is online goes to them. Otherwise it is assigned round robin
+//! to an online admin with access: the hiring manager and recruiters of the candidate's open
+//! applications, or every admin when none are assigned (or `message_routing_scope` is
+//! `all_admins`). With nobody online the assignee, or everyone with access, is emailed once
+//! per batch of unread messages.
+//!
+//! Settings: `message_routing_enabled` (default on), `message_routing_scope` (`job_team` or
+//! `all_admins`), `message_routing_reassign_offline` (move conversations away from an offline
+//! assignee; default off) and `message_routing_email_fallback` (default on).
+
+use std::collections::HashMap;
+
+use chrono::Utc;
+use tracing::{debug, info, warn};
+
+use crate::admin::models::AssignedUser;
+use crate::common::AppState;
+use crate::messages::models::{EnhancedConversationMessage, WebSocketMessage};
+use crate::messages::services::PresenceService;
+use crate::services::sanitize::render_markdown;
+use crate::services::{inbox, org};
+
+/// Recorded as the admin who assigned a conversation when routing does it
+pub const ROUTING_ACTOR: &str = "routing";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RoutingRules {
+    pub enabled: bool,
+    /// Route only among the candidate's job team, falling back to every admin
+    pub job_team_only: bool,
+    pub reassign_offline: bool,
+    pub email_fallback: bool,
+}
+
+impl RoutingRules {
+    pub async fn load(state: &AppState) -> Self {
+        let setting = |key: &'static str| async move {
+            state
+                .settings_service
+                .get_setting(key)
+                .await
+                .ok()
+                .flatten()
+                .map(|v| v.trim().to_lowercase())
+        };
+        Self {
+            enabled: setting("message_routing_enabled").await.as_deref() != Some("false"),
+            job_team_only: setting("message_routing_scope").await.as_deref() != Some("all_admins"),
+            reassign_offline: setting("message_routing_reassign_offline").await.as_deref()
+                == Some("true"),
+            email_fallback: setting("message_routing_email_fallback").await.as_deref()
+                != Some("false"),
+        }
+    }
+}
+
+/// The online admin routed to least recently; those never routed to come first, in order
+pub fn pick_round_robin(
+    online: &[String],
+    last_routed: &HashMap<String, String>,
+) -> Option<String> {
+    online
+        .iter()
+        .min_by(|a, b| last_routed.get(*a).cmp(&last_routed.get(*b)))
+        .cloned()
+}
+
+/// Every account listed in `admin_emails`
+async fn all_admins(state: &AppState) -> Result<Vec<AssignedUser>, sqlx::Error> {
+    if state.admin_emails.is_empty() {
+        return Ok(Vec::new());
+    }
+    let mut emails: Vec<&String> = state.admin_emails.iter().collect();
+    emails.sort();
+    let placeholders = vec!["?"; emails.len()].join(", ");
+    let sql = format!(
+        "SELECT id, name, email FROM users WHERE lower(email) IN ({}) AND merged_into IS NULL ORDER BY id",
+        placeholders
+    );
+    let mut query = sqlx::query_as::<_, AssignedUser>(&sql);
+    for email in emails {
+        query = query.bind(email);
+    }
+    query.fetch_all(&state.db).await
+}
+
+/// Admins who may pick up a candidate's conversation under `rules`
+async fn eligible_admins(
+    state: &AppState,
+    user_id: &str,
+    rules: &RoutingRules,
+) -> Result<Vec<AssignedUser>, sqlx::Error> {
+    if rules.job_team_only {
+        let job_ids = sqlx::query_scalar::<_, String>(
+            r#"
+            SELECT DISTINCT job_id FROM applications
+            WHERE user_id = ? AND archived_at IS NULL
+              AND status NOT IN ('rejected', 'withdrawn', 'hired')
+            "#,
+        )
+        .bind(user_id)
+        .fetch_all(&state.db)
+        .await?;
+
+        let mut team: Vec<AssignedUser> = Vec::new();
+        for job_id in job_ids {
+            for assignee in org::job_assignees(&state.db, &job_id).await? {
+                let is_admin = state.admin_emails.contains(&assignee.email.to_lowercase());
+                if is_admin && !team.iter().any(|t| t.id == assignee.id) {
+                    team.push(assignee);
+                }
+            }
+        }
+        if !team.is_empty() {
+            return Ok(team);
+        }
+    }
+    all_admins(state).await
+}
+
+fn received(message: &EnhancedConversationMessage) -> WebSocketMessage {
+    WebSocketMessage::MessageReceived {
+        message: message.clone(),
+    }
+}
+
+/// Email recruiters about a message nobody online could take
+///
+/// Only the first unread message of a run sends, so a burst of messages is one email.
+async fn email_fallback(
+    state: &AppState,
+    message: &EnhancedConversationMessage,
+    recipients: Vec<String>,
+) {
+    if recipients.is_empty() {
+        return;
+    }
+    let unread = sqlx::query_scalar::<_, i64>(
+        "SELECT COUNT(*) FROM conversation_messages WHERE user_id = ? AND sender = 'user' AND is_read = 0",
+    )
+    .bind(&message.user_id)
+    .fetch_one(&state.db)
+    .await
+    .unwrap_or(0);
+    if unread > 1 {
+        debug!(conversation_user_id = %message.user_id, "Recruiters already emailed about unread messages");
+        return;
+    }
+
+    let candidate: Option<(Option<String>, String)> =
+        sqlx::query_as("SELECT name, email FROM users WHERE id = ?")
+            .bind(&message.user_id)
+            .fetch_optional(&state.db)
+            .await
+            .ok()
+            .flatten();
+    let candidate = candidate
+        .map(|(name, email)| name.filter(|n| !n.trim().is_empty()).unwrap_or(email))
+        .unwrap_or_else(|| "A candidate".to_string());
+
+    let subject = format!("New message from {}", candidate);
+    let body = format!(
+        "<p>{} sent a message while no recruiter was online:</p><blockquote>{}</blockquote><p>Reply from Admin &rarr; Inbox.</p>",
+        render_markdown(&candidate),
+        render_markdown(&message.message)
+    );
+    match state
+        .email
+        .send_email(recipients, &subject, &body, None)
+        .await
+    {
+        Ok(_) => {
+            info!(conversation_user_id = %message.user_id, "Emailed recruiters about candidate message")
+        }
+        Err(e) => {
+            warn!(error = %e, conversation_user_id = %message.user_id, "Failed to email recruiters about candidate message")
+        }
+    }
+}
+
+/// Deliver a new candidate message to a recruiter, assigning the conversation if needed
+///
+/// Never fails the caller.
+pub async fn route_candidate_message(state: &AppState, message: &EnhancedConversationMessage) {
+    let rules = RoutingRules::load(state).await;
+    if !rules.enabled {
+        return;
+    }
+    let user_id = message.user_id.as_str();
+    let presence = PresenceService::new(state.connection_manager.clone());
+
+    let assignee: Option<(String, String)> = sqlx::query_as(
+        r#"
+        SELECT u.id, u.email FROM conversation_states cs
+        JOIN users u ON u.id = cs.assignee_id
+        WHERE cs.user_id = ?
+        "#,
+    )
+    .bind(user_id)
+    .fetch_optional(&state.db)
+    .await
+    .ok()
+    .flatten();
+
+    if let Some((assignee_id, assignee_email)) = &assignee {
+        if presence.is_online(assignee_id).await {
+            let _ = state
+                .connection_manager
+                .send_to_user(assignee_id, received(message))
+                .await;
+            return;
+        }
+        if !rules.reassign_offline {
+            if rules.email_fallback {
+                email_fallback(state, message, vec![assignee_email.clone()]).await;
+            }
+            return;
+        }
+    }
+
+    let pool = match eligible_admins(state, user_id, &rules).await {
+        Ok(pool) => pool,
+        Err(e) => {
+            debug!(error = %e, conversation_user_id = %user_id, "Failed to load admins to route to");
+            return;
+        }
+    };
+    let online_users = presence.get_online_users().await;
+    let online: Vec<String> = pool
+        .iter()
+        .filter(|a| online_users.contains(&a.id))
+        .map(|a| a.id.clone())
+        .collect();
+
+    let last_routed: HashMap<String, String> = sqlx::query_as::<_, (String, String)>(
+        "SELECT admin_id, last_routed_at FROM message_routing_state",
+    )
+    .fetch_all(&state.db)
+    .await
+    .unwrap_or_default()
+    .into_iter()
+    .collect();
+
+    let Some(admin_id) = pick_round_robin(&online, &last_routed) else {
+        if rules.email_fallback {
+            let recipients = match &assignee {
+                Some((_, email)) => vec![email.clone()],
+                None => pool.into_iter().map(|a| a.email).collect(),
+            };
+            email_fallback(state, message, recipients).await;
+        }
+        return;
+    };
+
+    let assigned = sqlx::query(
+        r#"
+        INSERT INTO conversation_states (user_id, assignee_id, updated_by)
+        VALUES (?, ?, ?)
+        ON CONFLICT(user_id) DO UPDATE SET
+            assignee_id = excluded.assignee_id,
+            updated_by = excluded.updated_by,
+            updated_at = datetime('now')
+        "#,
+    )
+    .bind(user_id)
+    .bind(&admin_id)
+    .bind(ROUTING_ACTOR)
+    .execute(&state.db)
+    .await;
+    if let Err(e) = assigned {
+        debug!(error = %e, conversation_user_id = %user_id, "Failed to assign routed conversation");
+        return;
+    }
+    let _ = sqlx::query(
+        r#"
+        INSERT INTO message_routing_state (admin_id, last_routed_at) VALUES (?, ?)
+        ON CONFLICT(admin_id) DO UPDATE SET last_routed_at = excluded.last_routed_at
+        "#,
+    )
+    .bind(&admin_id)
+    .bind(Utc::now().format("%Y-%m-%d %H:%M:%S%.6f").to_string())
+    .execute(&state.db)
+    .await;
+
+    info!(conversation_user_id = %user_id, admin_user_id = %admin_id, "Candidate message routed");
+
+    let _ = state
+        .connection_manager
+        .send_to_user(&admin_id, received(message))
+        .await;
+    inbox::notify_admins(
+        state,
+        WebSocketMessage::ConversationAssigned {
+            conversation_id: user_id.to_string(),
+            assignee_id: Some(admin_id),
+            previous_assignee_id: assignee.map(|(id, _)| id),
+            assigned_by: ROUTING_ACTOR.to_string(),
+        },
+    )
+    .await;
+}
+
+/// Route a candidate message without holding up the request that stored it
+pub fn route_in_background(state: &AppState, message: EnhancedConversationMessage) {
+    let state = state.clone();
+    tokio::spawn(async move {
+        route_candidate_message(&state, &message).await;
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pick_round_robin_prefers_least_recently_routed() {
+        let online = vec!["A".to_string(), "B".to_string(), "C".to_string()];
+        let mut last_routed = HashMap::from([
+            ("A".to_string(), "2026-03-02 10:00:00.000000".to_string()),
+            ("B".to_string(), "2026-03-02 09:00:00.000000".to_string()),
+        ]);
+
+        // C has never been routed to
+        assert_eq!(
+            pick_round_robin(&online, &last_routed),
+            Some("C".to_string())
+        );
+
+        last_routed.insert("C".to_string(), "2026-03-02 11:00:00.000000".to_string());
+        assert_eq!(
+            pick_round_robin(&online, &last_routed),
+            Some("B".to_string())
+        );
+
+        assert_eq!(pick_round_robin(&[], &last_routed), None);
+    }
+}
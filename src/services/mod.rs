@@ -3,19 +3,67 @@
 // Shared services module containing business logic services
 // that can be used across different domain modules
 
+pub mod account_linking;
+pub mod application_feed;
+pub mod activity;
+pub mod ai_usage;
+pub mod analytics_facts;
 pub mod aws;
+pub mod broadcasts;
+pub mod calendar_feed;
+pub mod calendar_retries;
+pub mod chat_webhooks;
+pub mod cohorts;
+pub mod compensation;
+pub mod consent;
+pub mod conversation_retention;
+pub mod documents;
+pub mod downloads;
+pub mod eeo;
 pub mod email;
 pub mod encryption;
+pub mod file_gc;
 pub mod google;
+pub mod guest_apply;
+pub mod hiring_metrics;
+pub mod inbox;
+pub mod interview_artifacts;
 pub mod interviews;
 pub mod job_templates;
+pub mod knockout;
+pub mod legal_hold;
+pub mod masking;
+pub mod message_routing;
+pub mod moderation;
+pub mod notification_preferences;
 pub mod monitoring;
 pub mod openai;
+pub mod org;
 pub mod panelists;
 pub mod pdf;
+pub mod permissions;
+pub mod pii;
+pub mod promotions;
+pub mod providers;
 pub mod rate_limit;
+pub mod rejection_feedback;
+pub mod reports;
+pub mod resilience;
+pub mod sanitize;
+pub mod scheduled_messages;
+pub mod search;
+pub mod seed;
 pub mod settings;
+pub mod signed_links;
+pub mod sla;
+pub mod sms;
+pub mod snippets;
+pub mod social;
+pub mod storage_usage;
+pub mod surveys;
+pub mod text;
 pub mod video;
+pub mod whatsapp;
 pub mod youtube;
 
 // Re-export commonly used types for convenience
@@ -23,5 +71,8 @@ pub use aws::AWSService;
 pub use google::GoogleService;
 pub use openai::OpenAIService;
 pub use pdf::PDFService;
+pub use providers::{CalendarClient, EmailSender, LlmClient, ObjectStore};
 pub use rate_limit::RateLimitService;
 pub use settings::SettingsService;
+pub use sms::SmsService;
+pub use whatsapp::WhatsAppService;
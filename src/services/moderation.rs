@@ -0,0 +1,427 @@
+// src/services/moderation.rs
+//! Moderation of user-generated content (bios, testimonials, messages, video titles)
+//!
+//! Text is checked against the OpenAI moderation endpoint when it is configured and against
+//! keyword lists from settings. Violations land in `moderation_queue`:
+//! - `hold`: content stays out of public surfaces until an admin approves it
+//! - `flag`: content stays visible and is queued for review
+//!
+//! Messages are delivered in real time, so they are only ever flagged.
+
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use tracing::{error, info, warn};
+
+use crate::common::{generate_history_id, ApiError, AppState};
+use crate::services::openai::OpenAIError;
+use crate::services::settings::SettingsService;
+
+/// Kinds of content that go through moderation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModeratedContent {
+    Bio,
+    Testimonial,
+    Message,
+    VideoTitle,
+}
+
+impl ModeratedContent {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ModeratedContent::Bio => "bio",
+            ModeratedContent::Testimonial => "testimonial",
+            ModeratedContent::Message => "message",
+            ModeratedContent::VideoTitle => "video_title",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "bio" => Some(ModeratedContent::Bio),
+            "testimonial" => Some(ModeratedContent::Testimonial),
+            "message" => Some(ModeratedContent::Message),
+            "video_title" => Some(ModeratedContent::VideoTitle),
+            _ => None,
+        }
+    }
+
+    /// Whether violating content of this kind can be held back from display
+    fn can_hold(&self) -> bool {
+        !matches!(self, ModeratedContent::Message)
+    }
+}
+
+/// Outcome of a moderation check, ordered by severity
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ModerationAction {
+    Allow,
+    Flag,
+    Hold,
+}
+
+impl ModerationAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ModerationAction::Allow => "allow",
+            ModerationAction::Flag => "flag",
+            ModerationAction::Hold => "hold",
+        }
+    }
+}
+
+/// Result of checking one piece of text
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModerationVerdict {
+    pub action: ModerationAction,
+    /// `openai`, `keywords` or both, comma separated
+    pub source: String,
+    pub reasons: Vec<String>,
+}
+
+/// An item awaiting or past admin review
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct ModerationItem {
+    pub id: String,
+    pub content_type: String,
+    pub content_id: String,
+    pub user_id: String,
+    pub excerpt: String,
+    pub action: String,
+    pub source: String,
+    /// JSON array of matched categories or keywords
+    pub reasons: String,
+    pub status: String,
+    pub reviewed_by: Option<String>,
+    pub reviewed_at: Option<String>,
+    pub review_notes: Option<String>,
+    pub created_at: String,
+}
+
+/// Longest excerpt stored with a queue item
+const EXCERPT_CHARS: usize = 500;
+
+/// Keyword rules from settings: `moderation_blocked_keywords` hold content,
+/// `moderation_flagged_keywords` only flag it
+#[derive(Debug, Clone, Default)]
+pub struct KeywordRules {
+    pub blocked: Vec<String>,
+    pub flagged: Vec<String>,
+}
+
+impl KeywordRules {
+    pub async fn load(settings: &SettingsService) -> Self {
+        Self {
+            blocked: keyword_setting(settings, "moderation_blocked_keywords").await,
+            flagged: keyword_setting(settings, "moderation_flagged_keywords").await,
+        }
+    }
+
+    /// Check text against the rules
+    pub fn check(&self, text: &str) -> ModerationVerdict {
+        let blocked = matching_keywords(text, &self.blocked);
+        let flagged = matching_keywords(text, &self.flagged);
+
+        let action = if !blocked.is_empty() {
+            ModerationAction::Hold
+        } else if !flagged.is_empty() {
+            ModerationAction::Flag
+        } else {
+            ModerationAction::Allow
+        };
+
+        ModerationVerdict {
+            action,
+            source: "keywords".to_string(),
+            reasons: blocked.into_iter().chain(flagged).collect(),
+        }
+    }
+}
+
+async fn keyword_setting(settings: &SettingsService, key: &str) -> Vec<String> {
+    settings
+        .get_setting(key)
+        .await
+        .ok()
+        .flatten()
+        .map(|value| {
+            value
+                .split(',')
+                .map(|k| k.trim().to_lowercase())
+                .filter(|k| !k.is_empty())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Keywords that appear in `text` as whole words (case-insensitive)
+pub fn matching_keywords(text: &str, keywords: &[String]) -> Vec<String> {
+    let words: Vec<String> = text
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(|w| w.to_lowercase())
+        .collect();
+    let normalized = words.join(" ");
+
+    keywords
+        .iter()
+        .filter(|keyword| {
+            // Multi-word phrases match against the normalized word sequence
+            let phrase: Vec<&str> = keyword.split_whitespace().collect();
+            match phrase.len() {
+                0 => false,
+                1 => words.iter().any(|w| w == phrase[0]),
+                _ => format!(" {} ", normalized).contains(&format!(" {} ", phrase.join(" "))),
+            }
+        })
+        .cloned()
+        .collect()
+}
+
+/// Check text with every configured provider and combine the verdicts
+pub async fn check_text(
+    state: &AppState,
+    content: ModeratedContent,
+    text: &str,
+) -> ModerationVerdict {
+    let mut verdict = KeywordRules::load(&state.settings_service)
+        .await
+        .check(text);
+    let mut sources = Vec::new();
+    if verdict.action != ModerationAction::Allow {
+        sources.push("keywords");
+    }
+
+    let use_openai = state
+        .settings_service
+        .get_setting("moderation_use_openai")
+        .await
+        .ok()
+        .flatten()
+        .map(|v| v != "false")
+        .unwrap_or(true);
+
+    if use_openai {
+        match state.llm.moderate(text).await {
+            Ok(categories) if !categories.is_empty() => {
+                verdict.action = ModerationAction::Hold;
+                verdict.reasons.extend(categories);
+                sources.push("openai");
+            }
+            Ok(_) | Err(OpenAIError::NotConfigured) => {}
+            Err(e) => {
+                // Fail open: keyword rules still apply and content is never lost
+                warn!(error = %e, content_type = content.as_str(), "Moderation API unavailable, using keyword rules only");
+            }
+        }
+    }
+
+    if verdict.action == ModerationAction::Hold && !content.can_hold() {
+        verdict.action = ModerationAction::Flag;
+    }
+    verdict.source = sources.join(",");
+    verdict
+}
+
+/// Moderate a piece of content and queue it for review when it violates policy
+///
+/// Replaces any pending queue entry for the same content, so an edit is judged on its
+/// latest text. Never fails the caller's write: errors are logged and treated as allowed.
+pub async fn moderate(
+    state: &AppState,
+    content: ModeratedContent,
+    content_id: &str,
+    user_id: &str,
+    text: &str,
+) -> ModerationAction {
+    if text.trim().is_empty() {
+        return ModerationAction::Allow;
+    }
+
+    let verdict = check_text(state, content, text).await;
+    if let Err(e) = record_verdict(&state.db, content, content_id, user_id, text, &verdict).await {
+        error!(error = %e, content_type = content.as_str(), content_id = %content_id, "Failed to record moderation result");
+        return ModerationAction::Allow;
+    }
+
+    if verdict.action != ModerationAction::Allow {
+        info!(
+            content_type = content.as_str(),
+            content_id = %content_id,
+            user_id = %user_id,
+            action = verdict.action.as_str(),
+            source = %verdict.source,
+            "Content queued for moderation"
+        );
+    }
+    verdict.action
+}
+
+async fn record_verdict(
+    pool: &SqlitePool,
+    content: ModeratedContent,
+    content_id: &str,
+    user_id: &str,
+    text: &str,
+    verdict: &ModerationVerdict,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "DELETE FROM moderation_queue WHERE content_type = ? AND content_id = ? AND status = 'pending'",
+    )
+    .bind(content.as_str())
+    .bind(content_id)
+    .execute(pool)
+    .await?;
+
+    if verdict.action == ModerationAction::Allow {
+        return Ok(());
+    }
+
+    let excerpt: String = text.chars().take(EXCERPT_CHARS).collect();
+    sqlx::query(
+        r#"
+        INSERT INTO moderation_queue (id, content_type, content_id, user_id, excerpt, action, source, reasons, status, created_at)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, 'pending', datetime('now'))
+        "#,
+    )
+    .bind(generate_history_id())
+    .bind(content.as_str())
+    .bind(content_id)
+    .bind(user_id)
+    .bind(excerpt)
+    .bind(verdict.action.as_str())
+    .bind(&verdict.source)
+    .bind(serde_json::to_string(&verdict.reasons).unwrap_or_else(|_| "[]".to_string()))
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Subquery selecting the ids of held content of one type (bound as its only parameter),
+/// for use in `NOT IN (...)` filters on public listings
+pub const HELD_CONTENT_IDS: &str = "SELECT content_id FROM moderation_queue WHERE content_type = ? AND action = 'hold' AND status = 'pending'";
+
+/// Whether a piece of content is currently held back from display
+pub async fn is_held(pool: &SqlitePool, content: ModeratedContent, content_id: &str) -> bool {
+    sqlx::query_scalar::<_, i64>(&format!(
+        "SELECT COUNT(*) FROM ({}) WHERE content_id = ?",
+        HELD_CONTENT_IDS
+    ))
+    .bind(content.as_str())
+    .bind(content_id)
+    .fetch_one(pool)
+    .await
+    .map(|count| count > 0)
+    .unwrap_or(false)
+}
+
+/// Take violating content down after an admin rejects it
+pub async fn remove_content(pool: &SqlitePool, item: &ModerationItem) -> Result<(), ApiError> {
+    let content = ModeratedContent::parse(&item.content_type).ok_or_else(|| {
+        ApiError::InternalServer(format!(
+            "Unknown moderated content type '{}'",
+            item.content_type
+        ))
+    })?;
+
+    let query = match content {
+        ModeratedContent::Bio => {
+            "UPDATE profiles SET bio = NULL, updated_at = datetime('now') WHERE user_id = ?"
+        }
+        ModeratedContent::Testimonial => "DELETE FROM testimonials WHERE id = ?",
+        ModeratedContent::Message => {
+            "UPDATE conversation_messages SET message = '[Message removed by a moderator]' WHERE id = ?"
+        }
+        ModeratedContent::VideoTitle => "UPDATE videos SET youtube_title = NULL WHERE id = ?",
+    };
+
+    sqlx::query(query)
+        .bind(&item.content_id)
+        .execute(pool)
+        .await
+        .map_err(ApiError::DatabaseError)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules(blocked: &[&str], flagged: &[&str]) -> KeywordRules {
+        KeywordRules {
+            blocked: blocked.iter().map(|k| k.to_string()).collect(),
+            flagged: flagged.iter().map(|k| k.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_keywords_match_whole_words_only() {
+        let keywords = vec!["scam".to_string(), "wire transfer".to_string()];
+        assert_eq!(
+            matching_keywords("Send a WIRE   transfer now, not a scam!", &keywords),
+            vec!["scam".to_string(), "wire transfer".to_string()]
+        );
+        assert!(matching_keywords("Scampi for lunch", &keywords).is_empty());
+    }
+
+    #[test]
+    fn test_blocked_keywords_hold_and_flagged_keywords_flag() {
+        let rules = rules(&["slur"], &["crypto"]);
+        assert_eq!(
+            rules.check("Contains a slur").action,
+            ModerationAction::Hold
+        );
+        assert_eq!(
+            rules.check("Crypto enthusiast").action,
+            ModerationAction::Flag
+        );
+
+        let clean = rules.check("Backend engineer");
+        assert_eq!(clean.action, ModerationAction::Allow);
+        assert!(clean.reasons.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_pending_entry_is_replaced_on_edit() {
+        let pool = SqlitePool::connect(":memory:").await.unwrap();
+        sqlx::query(
+            r#"CREATE TABLE moderation_queue (
+                id TEXT PRIMARY KEY, content_type TEXT NOT NULL, content_id TEXT NOT NULL,
+                user_id TEXT NOT NULL, excerpt TEXT NOT NULL, action TEXT NOT NULL,
+                source TEXT NOT NULL, reasons TEXT NOT NULL, status TEXT NOT NULL,
+                reviewed_by TEXT, reviewed_at TEXT, review_notes TEXT, created_at TEXT NOT NULL
+            )"#,
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let held = ModerationVerdict {
+            action: ModerationAction::Hold,
+            source: "keywords".to_string(),
+            reasons: vec!["slur".to_string()],
+        };
+        record_verdict(&pool, ModeratedContent::Bio, "U_1", "U_1", "bad bio", &held)
+            .await
+            .unwrap();
+        assert!(is_held(&pool, ModeratedContent::Bio, "U_1").await);
+        assert!(!is_held(&pool, ModeratedContent::Testimonial, "U_1").await);
+
+        let allowed = ModerationVerdict {
+            action: ModerationAction::Allow,
+            source: String::new(),
+            reasons: vec![],
+        };
+        record_verdict(
+            &pool,
+            ModeratedContent::Bio,
+            "U_1",
+            "U_1",
+            "good bio",
+            &allowed,
+        )
+        .await
+        .unwrap();
+        assert!(!is_held(&pool, ModeratedContent::Bio, "U_1").await);
+    }
+}
@@ -1,11 +1,15 @@
 // Monitoring Service with Sentry integration
 use anyhow::Result;
+use chrono::{DateTime, Duration, Timelike, Utc};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use sqlx::SqlitePool;
 use std::sync::Arc;
 use tracing::{error, info, warn};
 
+use crate::common::{generate_history_id, AppState};
+use crate::services::SettingsService;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MonitoringConfig {
     pub sentry_dsn: Option<String>,
@@ -282,6 +286,465 @@ impl Drop for MonitoringService {
     }
 }
 
+// ============================================================================
+// Security monitoring: honeytokens and admin activity anomalies
+// ============================================================================
+
+/// Sensitive activity tracked for anomaly detection
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SecurityActivity {
+    /// Admin data export; the item count is the number of records exported
+    Export,
+    /// Bulk change of applications to `rejected`; the item count is the batch size
+    BulkRejection,
+    /// Request rejected as unauthenticated
+    AuthFailure,
+}
+
+impl SecurityActivity {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SecurityActivity::Export => "export",
+            SecurityActivity::BulkRejection => "bulk_rejection",
+            SecurityActivity::AuthFailure => "auth_failure",
+        }
+    }
+}
+
+/// Detection thresholds, overridable through `security_*` system settings
+#[derive(Debug, Clone)]
+pub struct SecurityThresholds {
+    /// Records one admin may export within an hour before it is flagged
+    pub export_items_per_hour: i64,
+    /// Smallest bulk rejection flagged when made during quiet hours
+    pub bulk_rejection_min: i64,
+    /// UTC hours `[start, end)` outside normal working time; may wrap midnight
+    pub quiet_hours: (u32, u32),
+    /// Failed authentications from one IP within ten minutes that count as a spike
+    pub auth_failures_per_window: i64,
+}
+
+impl Default for SecurityThresholds {
+    fn default() -> Self {
+        Self {
+            export_items_per_hour: 500,
+            bulk_rejection_min: 20,
+            quiet_hours: (22, 6),
+            auth_failures_per_window: 20,
+        }
+    }
+}
+
+impl SecurityThresholds {
+    pub async fn load(settings: &SettingsService) -> Self {
+        let defaults = Self::default();
+        let number = |value: Option<String>, default: i64| {
+            value
+                .and_then(|v| v.trim().parse::<i64>().ok())
+                .filter(|v| *v > 0)
+                .unwrap_or(default)
+        };
+
+        Self {
+            export_items_per_hour: number(
+                settings
+                    .get_setting("security_export_items_per_hour")
+                    .await
+                    .ok()
+                    .flatten(),
+                defaults.export_items_per_hour,
+            ),
+            bulk_rejection_min: number(
+                settings
+                    .get_setting("security_bulk_rejection_min")
+                    .await
+                    .ok()
+                    .flatten(),
+                defaults.bulk_rejection_min,
+            ),
+            quiet_hours: settings
+                .get_setting("security_quiet_hours")
+                .await
+                .ok()
+                .flatten()
+                .and_then(|v| parse_quiet_hours(&v))
+                .unwrap_or(defaults.quiet_hours),
+            auth_failures_per_window: number(
+                settings
+                    .get_setting("security_auth_failures_per_window")
+                    .await
+                    .ok()
+                    .flatten(),
+                defaults.auth_failures_per_window,
+            ),
+        }
+    }
+}
+
+/// A security alert recorded in `security_events`
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct SecurityEvent {
+    pub id: String,
+    pub event_type: String,
+    pub severity: String,
+    pub actor_id: Option<String>,
+    pub ip_address: Option<String>,
+    pub description: String,
+    pub details: Option<String>,
+    pub notified: bool,
+    pub acknowledged_by: Option<String>,
+    pub acknowledged_at: Option<String>,
+    pub created_at: String,
+}
+
+/// Parse quiet hours written as `"22-6"`
+fn parse_quiet_hours(value: &str) -> Option<(u32, u32)> {
+    let (start, end) = value.split_once('-')?;
+    let start = start.trim().parse::<u32>().ok().filter(|h| *h < 24)?;
+    let end = end.trim().parse::<u32>().ok().filter(|h| *h < 24)?;
+    Some((start, end))
+}
+
+pub fn is_quiet_hour(hour: u32, (start, end): (u32, u32)) -> bool {
+    if start <= end {
+        hour >= start && hour < end
+    } else {
+        hour >= start || hour < end
+    }
+}
+
+/// The first honeytoken appearing in a request's URI or credentials
+pub fn matching_honeytoken<'a>(honeytokens: &'a [String], haystacks: &[&str]) -> Option<&'a str> {
+    honeytokens
+        .iter()
+        .map(String::as_str)
+        .find(|token| haystacks.iter().any(|h| h.contains(token)))
+}
+
+/// Decoy values from the `security_honeytokens` setting (comma-separated).
+///
+/// Plant these as IDs, API keys or credentials that no legitimate client ever uses; any
+/// request carrying one raises a critical alert.
+pub async fn load_honeytokens(settings: &SettingsService) -> Vec<String> {
+    settings
+        .get_setting("security_honeytokens")
+        .await
+        .ok()
+        .flatten()
+        .map(|value| {
+            value
+                .split(',')
+                .map(|t| t.trim().to_string())
+                // Short values would match unrelated URLs
+                .filter(|t| t.len() >= 8)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn db_timestamp(time: DateTime<Utc>) -> String {
+    time.format("%Y-%m-%d %H:%M:%S").to_string()
+}
+
+/// Store an alert unless the same one was raised for this actor/IP within the last hour
+#[allow(clippy::too_many_arguments)]
+async fn raise_security_event(
+    pool: &SqlitePool,
+    event_type: &str,
+    severity: &str,
+    actor_id: Option<&str>,
+    ip_address: Option<&str>,
+    description: String,
+    details: Value,
+    now: DateTime<Utc>,
+) -> Result<Option<SecurityEvent>, sqlx::Error> {
+    let recent: i64 = sqlx::query_scalar(
+        r#"
+        SELECT COUNT(*) FROM security_events
+        WHERE event_type = ? AND COALESCE(actor_id, '') = COALESCE(?, '')
+          AND COALESCE(ip_address, '') = COALESCE(?, '') AND created_at >= ?
+        "#,
+    )
+    .bind(event_type)
+    .bind(actor_id)
+    .bind(ip_address)
+    .bind(db_timestamp(now - Duration::hours(1)))
+    .fetch_one(pool)
+    .await?;
+
+    if recent > 0 {
+        return Ok(None);
+    }
+
+    let event = SecurityEvent {
+        id: generate_history_id(),
+        event_type: event_type.to_string(),
+        severity: severity.to_string(),
+        actor_id: actor_id.map(str::to_string),
+        ip_address: ip_address.map(str::to_string),
+        description,
+        details: Some(details.to_string()),
+        notified: false,
+        acknowledged_by: None,
+        acknowledged_at: None,
+        created_at: db_timestamp(now),
+    };
+
+    sqlx::query(
+        r#"
+        INSERT INTO security_events (id, event_type, severity, actor_id, ip_address, description, details, created_at)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+        "#,
+    )
+    .bind(&event.id)
+    .bind(&event.event_type)
+    .bind(&event.severity)
+    .bind(&event.actor_id)
+    .bind(&event.ip_address)
+    .bind(&event.description)
+    .bind(&event.details)
+    .bind(&event.created_at)
+    .execute(pool)
+    .await?;
+
+    warn!(
+        event_id = %event.id,
+        event_type = %event.event_type,
+        severity = %event.severity,
+        actor_id = ?event.actor_id,
+        ip_address = ?event.ip_address,
+        "Security event raised: {}",
+        event.description
+    );
+
+    Ok(Some(event))
+}
+
+/// Record an activity and return any alerts it triggers
+#[allow(clippy::too_many_arguments)]
+pub async fn detect_anomalies(
+    pool: &SqlitePool,
+    activity: SecurityActivity,
+    actor_id: Option<&str>,
+    ip_address: Option<&str>,
+    item_count: i64,
+    path: &str,
+    thresholds: &SecurityThresholds,
+    now: DateTime<Utc>,
+) -> Result<Vec<SecurityEvent>, sqlx::Error> {
+    sqlx::query(
+        r#"
+        INSERT INTO security_activity (id, activity, actor_id, ip_address, item_count, path, created_at)
+        VALUES (?, ?, ?, ?, ?, ?, ?)
+        "#,
+    )
+    .bind(generate_history_id())
+    .bind(activity.as_str())
+    .bind(actor_id)
+    .bind(ip_address)
+    .bind(item_count)
+    .bind(path)
+    .bind(db_timestamp(now))
+    .execute(pool)
+    .await?;
+
+    let event = match activity {
+        SecurityActivity::Export => {
+            let exported: i64 = sqlx::query_scalar(
+                r#"
+                SELECT COALESCE(SUM(item_count), 0) FROM security_activity
+                WHERE activity = 'export' AND COALESCE(actor_id, '') = COALESCE(?, '') AND created_at >= ?
+                "#,
+            )
+            .bind(actor_id)
+            .bind(db_timestamp(now - Duration::hours(1)))
+            .fetch_one(pool)
+            .await?;
+
+            if exported < thresholds.export_items_per_hour {
+                return Ok(Vec::new());
+            }
+            raise_security_event(
+                pool,
+                "mass_export",
+                "high",
+                actor_id,
+                ip_address,
+                format!("{} records exported within an hour", exported),
+                serde_json::json!({ "exported_last_hour": exported, "last_path": path }),
+                now,
+            )
+            .await?
+        }
+        SecurityActivity::BulkRejection => {
+            if item_count < thresholds.bulk_rejection_min
+                || !is_quiet_hour(now.hour(), thresholds.quiet_hours)
+            {
+                return Ok(Vec::new());
+            }
+            raise_security_event(
+                pool,
+                "off_hours_bulk_rejection",
+                "medium",
+                actor_id,
+                ip_address,
+                format!(
+                    "{} applications rejected in bulk at {} UTC",
+                    item_count,
+                    now.format("%H:%M")
+                ),
+                serde_json::json!({ "rejected": item_count, "path": path }),
+                now,
+            )
+            .await?
+        }
+        SecurityActivity::AuthFailure => {
+            let failures: i64 = sqlx::query_scalar(
+                r#"
+                SELECT COUNT(*) FROM security_activity
+                WHERE activity = 'auth_failure' AND COALESCE(ip_address, '') = COALESCE(?, '') AND created_at >= ?
+                "#,
+            )
+            .bind(ip_address)
+            .bind(db_timestamp(now - Duration::minutes(10)))
+            .fetch_one(pool)
+            .await?;
+
+            if failures < thresholds.auth_failures_per_window {
+                return Ok(Vec::new());
+            }
+            raise_security_event(
+                pool,
+                "auth_failure_spike",
+                "high",
+                actor_id,
+                ip_address,
+                format!("{} failed authentications within ten minutes", failures),
+                serde_json::json!({ "failures_last_10m": failures, "last_path": path }),
+                now,
+            )
+            .await?
+        }
+    };
+
+    Ok(event.into_iter().collect())
+}
+
+/// Email alerts to `security_alert_emails` (comma-separated), or the admin emails when unset
+pub async fn notify_security_events(state: &AppState, events: &[SecurityEvent]) {
+    if events.is_empty() {
+        return;
+    }
+
+    let recipients: Vec<String> = match state
+        .settings_service
+        .get_setting("security_alert_emails")
+        .await
+        .ok()
+        .flatten()
+    {
+        Some(list) => list
+            .split(',')
+            .map(|e| e.trim().to_string())
+            .filter(|e| !e.is_empty())
+            .collect(),
+        None => state.admin_emails.iter().cloned().collect(),
+    };
+    if recipients.is_empty() {
+        warn!("No recipients configured for security alerts");
+        return;
+    }
+
+    for event in events {
+        let subject = format!(
+            "[Security] {} alert: {}",
+            event.severity,
+            event.event_type.replace('_', " ")
+        );
+        let body = format!(
+            "<p><strong>{}</strong></p><ul><li>Severity: {}</li><li>Admin: {}</li><li>IP address: {}</li><li>Time: {} UTC</li></ul><p>Review and acknowledge this alert under Admin &rarr; Security events.</p>",
+            event.description,
+            event.severity,
+            event.actor_id.as_deref().unwrap_or("unknown"),
+            event.ip_address.as_deref().unwrap_or("unknown"),
+            event.created_at
+        );
+
+        match state
+            .email
+            .send_email(recipients.clone(), &subject, &body, None)
+            .await
+        {
+            Ok(_) => {
+                let _ = sqlx::query("UPDATE security_events SET notified = 1 WHERE id = ?")
+                    .bind(&event.id)
+                    .execute(&state.db)
+                    .await;
+            }
+            Err(e) => {
+                error!(error = %e, event_id = %event.id, "Failed to send security alert email");
+            }
+        }
+    }
+}
+
+/// Track sensitive activity, raising and emailing alerts for anomalies.
+///
+/// Never fails the caller: monitoring problems are logged and swallowed.
+pub async fn record_security_activity(
+    state: &AppState,
+    activity: SecurityActivity,
+    actor_id: Option<&str>,
+    ip_address: Option<&str>,
+    item_count: i64,
+    path: &str,
+) {
+    let thresholds = SecurityThresholds::load(&state.settings_service).await;
+    match detect_anomalies(
+        &state.db,
+        activity,
+        actor_id,
+        ip_address,
+        item_count,
+        path,
+        &thresholds,
+        Utc::now(),
+    )
+    .await
+    {
+        Ok(events) => notify_security_events(state, &events).await,
+        Err(e) => {
+            error!(error = %e, activity = activity.as_str(), "Failed to record security activity");
+        }
+    }
+}
+
+/// Raise a critical alert for a request carrying a honeytoken
+pub async fn record_honeytoken_access(
+    state: &AppState,
+    honeytoken: &str,
+    ip_address: Option<&str>,
+    path: &str,
+) {
+    // Only a prefix goes in the alert so the decoy itself isn't spread through email
+    let token_hint: String = honeytoken.chars().take(4).collect();
+    match raise_security_event(
+        &state.db,
+        "honeytoken_access",
+        "critical",
+        None,
+        ip_address,
+        format!("Honeytoken {}… used on {}", token_hint, path),
+        serde_json::json!({ "path": path, "token_prefix": token_hint }),
+        Utc::now(),
+    )
+    .await
+    {
+        Ok(event) => notify_security_events(state, &event.into_iter().collect::<Vec<_>>()).await,
+        Err(e) => error!(error = %e, "Failed to record honeytoken access"),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -294,4 +757,167 @@ mod tests {
         assert!(config.enable_error_tracking);
         assert!(!config.enable_performance_monitoring);
     }
+
+    async fn setup_security_db() -> SqlitePool {
+        let pool = SqlitePool::connect(":memory:").await.unwrap();
+        for statement in [
+            r#"CREATE TABLE security_activity (
+                id TEXT PRIMARY KEY, activity TEXT NOT NULL, actor_id TEXT, ip_address TEXT,
+                item_count INTEGER NOT NULL DEFAULT 1, path TEXT, created_at TEXT NOT NULL
+            )"#,
+            r#"CREATE TABLE security_events (
+                id TEXT PRIMARY KEY, event_type TEXT NOT NULL, severity TEXT NOT NULL, actor_id TEXT,
+                ip_address TEXT, description TEXT NOT NULL, details TEXT, notified INTEGER NOT NULL DEFAULT 0,
+                acknowledged_by TEXT, acknowledged_at TEXT, created_at TEXT NOT NULL
+            )"#,
+        ] {
+            sqlx::query(statement).execute(&pool).await.unwrap();
+        }
+        pool
+    }
+
+    fn at(hour: u32) -> DateTime<Utc> {
+        use chrono::TimeZone;
+        Utc.with_ymd_and_hms(2026, 3, 10, hour, 15, 0).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_mass_export_alerts_once_per_hour() {
+        let pool = setup_security_db().await;
+        let thresholds = SecurityThresholds::default();
+        let export = |count, now| {
+            let pool = pool.clone();
+            let thresholds = thresholds.clone();
+            async move {
+                detect_anomalies(
+                    &pool,
+                    SecurityActivity::Export,
+                    Some("admin"),
+                    None,
+                    count,
+                    "/api/admin/export/candidates",
+                    &thresholds,
+                    now,
+                )
+                .await
+                .unwrap()
+            }
+        };
+
+        assert!(export(300, at(10)).await.is_empty());
+        let events = export(300, at(10) + Duration::minutes(5)).await;
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].event_type, "mass_export");
+
+        // Further exports in the same hour don't raise duplicate alerts
+        assert!(export(300, at(10) + Duration::minutes(10)).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_bulk_rejection_only_flagged_in_quiet_hours() {
+        let pool = setup_security_db().await;
+        let thresholds = SecurityThresholds::default();
+
+        let daytime = detect_anomalies(
+            &pool,
+            SecurityActivity::BulkRejection,
+            Some("admin"),
+            None,
+            50,
+            "/bulk",
+            &thresholds,
+            at(14),
+        )
+        .await
+        .unwrap();
+        assert!(daytime.is_empty());
+
+        let small = detect_anomalies(
+            &pool,
+            SecurityActivity::BulkRejection,
+            Some("admin"),
+            None,
+            5,
+            "/bulk",
+            &thresholds,
+            at(3),
+        )
+        .await
+        .unwrap();
+        assert!(small.is_empty());
+
+        let night = detect_anomalies(
+            &pool,
+            SecurityActivity::BulkRejection,
+            Some("admin"),
+            None,
+            50,
+            "/bulk",
+            &thresholds,
+            at(3),
+        )
+        .await
+        .unwrap();
+        assert_eq!(night[0].event_type, "off_hours_bulk_rejection");
+    }
+
+    #[tokio::test]
+    async fn test_auth_failure_spike_per_ip() {
+        let pool = setup_security_db().await;
+        let thresholds = SecurityThresholds {
+            auth_failures_per_window: 3,
+            ..Default::default()
+        };
+
+        let mut raised = Vec::new();
+        for i in 0..3 {
+            raised.extend(
+                detect_anomalies(
+                    &pool,
+                    SecurityActivity::AuthFailure,
+                    None,
+                    Some("203.0.113.9"),
+                    1,
+                    "/api/admin/users",
+                    &thresholds,
+                    at(12) + Duration::seconds(i),
+                )
+                .await
+                .unwrap(),
+            );
+        }
+        let other_ip = detect_anomalies(
+            &pool,
+            SecurityActivity::AuthFailure,
+            None,
+            Some("198.51.100.1"),
+            1,
+            "/api/admin/users",
+            &thresholds,
+            at(12),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(raised.len(), 1);
+        assert_eq!(raised[0].ip_address.as_deref(), Some("203.0.113.9"));
+        assert!(other_ip.is_empty());
+    }
+
+    #[test]
+    fn test_quiet_hours_and_honeytokens() {
+        assert!(is_quiet_hour(23, (22, 6)));
+        assert!(is_quiet_hour(2, (22, 6)));
+        assert!(!is_quiet_hour(12, (22, 6)));
+        assert!(is_quiet_hour(13, (12, 14)));
+        assert_eq!(parse_quiet_hours("21-5"), Some((21, 5)));
+        assert_eq!(parse_quiet_hours("25-5"), None);
+
+        let tokens = vec!["U_DECOY1".to_string()];
+        assert_eq!(
+            matching_honeytoken(&tokens, &["/api/admin/candidates/U_DECOY1", ""]),
+            Some("U_DECOY1")
+        );
+        assert!(matching_honeytoken(&tokens, &["/api/admin/candidates/U_REAL01"]).is_none());
+    }
 }
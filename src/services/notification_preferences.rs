@@ -0,0 +1,122 @@
+// src/services/notification_preferences.rs
+//! What each user has chosen to be notified about, per channel
+//!
+//! Every channel's preferences load from here, so SMS and WhatsApp agree on which phone
+//! number may be messaged: only a verified one.
+
+use sqlx::SqlitePool;
+
+use crate::admin::models::ActivityPreferences;
+use crate::profile::models::{SmsPreferences, WhatsAppPreferences};
+use crate::services::pii::Sealed;
+
+/// The user's phone number, if they have verified it
+async fn verified_phone(pool: &SqlitePool, user_id: &str) -> Result<Option<String>, sqlx::Error> {
+    Ok(sqlx::query_scalar::<_, Sealed>(
+        "SELECT phone FROM profiles WHERE user_id = ? AND phone_verified_at IS NOT NULL",
+    )
+    .bind(user_id)
+    .fetch_optional(pool)
+    .await?
+    .and_then(Sealed::into_inner))
+}
+
+/// SMS opt-in and quiet hours; users who never chose are opted out
+pub async fn sms(pool: &SqlitePool, user_id: &str) -> Result<SmsPreferences, sqlx::Error> {
+    let row: Option<(bool, Option<i64>, Option<i64>)> = sqlx::query_as(
+        "SELECT opted_in, quiet_hours_start, quiet_hours_end FROM sms_preferences WHERE user_id = ?",
+    )
+    .bind(user_id)
+    .fetch_optional(pool)
+    .await?;
+
+    let (opted_in, quiet_hours_start, quiet_hours_end) = row.unwrap_or((false, None, None));
+    Ok(SmsPreferences {
+        opted_in,
+        quiet_hours_start,
+        quiet_hours_end,
+        phone: verified_phone(pool, user_id).await?,
+    })
+}
+
+/// WhatsApp opt-in; users who never chose are opted out
+pub async fn whatsapp(
+    pool: &SqlitePool,
+    user_id: &str,
+) -> Result<WhatsAppPreferences, sqlx::Error> {
+    let row: Option<(bool, Option<String>)> =
+        sqlx::query_as("SELECT opted_in, opted_in_at FROM whatsapp_opt_ins WHERE user_id = ?")
+            .bind(user_id)
+            .fetch_optional(pool)
+            .await?;
+
+    let (opted_in, opted_in_at) = row.unwrap_or((false, None));
+    Ok(WhatsAppPreferences {
+        opted_in,
+        opted_in_at,
+        phone: verified_phone(pool, user_id).await?,
+    })
+}
+
+/// An admin's activity feed and digest choices, or the defaults if they never saved any
+pub async fn activity(
+    pool: &SqlitePool,
+    user_id: &str,
+) -> Result<ActivityPreferences, sqlx::Error> {
+    Ok(sqlx::query_as::<_, ActivityPreferences>(
+        r#"
+        SELECT digest_enabled, digest_hour, applications, stage_changes, interviews, offers,
+               last_digest_at
+        FROM activity_preferences WHERE user_id = ?
+        "#,
+    )
+    .bind(user_id)
+    .fetch_optional(pool)
+    .await?
+    .unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn setup_test_db() -> SqlitePool {
+        let pool = SqlitePool::connect(":memory:").await.unwrap();
+
+        for statement in [
+            "CREATE TABLE profiles (user_id TEXT PRIMARY KEY, phone TEXT, phone_verified_at TEXT)",
+            r#"CREATE TABLE sms_preferences (
+                user_id TEXT PRIMARY KEY, opted_in BOOLEAN NOT NULL,
+                quiet_hours_start INTEGER, quiet_hours_end INTEGER
+            )"#,
+            r#"CREATE TABLE whatsapp_opt_ins (
+                user_id TEXT PRIMARY KEY, opted_in BOOLEAN NOT NULL, opted_in_at TEXT
+            )"#,
+            r#"INSERT INTO profiles VALUES
+                ('verified', '+15550100', '2026-01-01 00:00:00'), ('unverified', '+15550101', NULL)"#,
+            "INSERT INTO sms_preferences VALUES ('verified', 1, 22, 7), ('unverified', 1, NULL, NULL)",
+        ] {
+            sqlx::query(statement).execute(&pool).await.unwrap();
+        }
+
+        pool
+    }
+
+    #[tokio::test]
+    async fn test_channels_only_see_a_verified_phone() {
+        let pool = setup_test_db().await;
+
+        let prefs = sms(&pool, "verified").await.unwrap();
+        assert!(prefs.opted_in);
+        assert_eq!(prefs.quiet_hours_start, Some(22));
+        assert_eq!(prefs.phone.as_deref(), Some("+15550100"));
+
+        let prefs = sms(&pool, "unverified").await.unwrap();
+        assert!(prefs.opted_in);
+        assert_eq!(prefs.phone, None);
+
+        let prefs = whatsapp(&pool, "verified").await.unwrap();
+        assert!(!prefs.opted_in);
+        assert_eq!(prefs.phone.as_deref(), Some("+15550100"));
+    }
+}
@@ -1,7 +1,10 @@
 // src/services/openai.rs
+use crate::services::ai_usage::{self, CallUsage};
+use crate::services::resilience::{self, Dependency, ExternalError};
 use crate::services::settings::SettingsService;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
 use std::sync::Arc;
 use tracing::{debug, error, info, warn};
 
@@ -24,6 +27,39 @@ pub enum OpenAIError {
 
     #[error("Serialization error: {0}")]
     SerializationError(String),
+
+    #[error("API request rejected: {0}")]
+    Rejected(String),
+
+    #[error("OpenAI unavailable: {0}")]
+    Unavailable(String),
+}
+
+impl OpenAIError {
+    /// The error for a non-success response other than 429
+    fn from_status(status: reqwest::StatusCode, error_text: String) -> Self {
+        let message = format!("HTTP {}: {}", status, error_text);
+        if status.is_client_error() {
+            OpenAIError::Rejected(message)
+        } else {
+            OpenAIError::RequestFailed(message)
+        }
+    }
+}
+
+impl ExternalError for OpenAIError {
+    fn is_transient(&self) -> bool {
+        matches!(
+            self,
+            OpenAIError::RequestFailed(_)
+                | OpenAIError::RateLimitExceeded
+                | OpenAIError::Unavailable(_)
+        )
+    }
+
+    fn unavailable(message: String) -> Self {
+        OpenAIError::Unavailable(message)
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -74,6 +110,75 @@ impl Default for ReasoningEffortConfig {
     }
 }
 
+/// Reasoning effort levels accepted by reasoning models
+pub const REASONING_EFFORTS: [&str; 4] = ["minimal", "low", "medium", "high"];
+
+/// A configurable model slot; each has its own `openai_model_*` setting
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModelPurpose {
+    ResumeScanning,
+    EmailGeneration,
+    MessageResponses,
+    JobDescription,
+    ImageGeneration,
+}
+
+impl ModelPurpose {
+    pub const ALL: [ModelPurpose; 5] = [
+        ModelPurpose::ResumeScanning,
+        ModelPurpose::EmailGeneration,
+        ModelPurpose::MessageResponses,
+        ModelPurpose::JobDescription,
+        ModelPurpose::ImageGeneration,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ModelPurpose::ResumeScanning => "resume_scanning",
+            ModelPurpose::EmailGeneration => "email_generation",
+            ModelPurpose::MessageResponses => "message_responses",
+            ModelPurpose::JobDescription => "job_description",
+            ModelPurpose::ImageGeneration => "image_generation",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|p| p.as_str() == value)
+    }
+
+    pub fn model_setting_key(&self) -> String {
+        format!("openai_model_{}", self.as_str())
+    }
+
+    /// Image generation has no reasoning effort
+    pub fn reasoning_setting_key(&self) -> Option<String> {
+        (*self != ModelPurpose::ImageGeneration)
+            .then(|| format!("openai_reasoning_effort_{}", self.as_str()))
+    }
+
+    pub fn default_model(&self) -> &'static str {
+        match self {
+            ModelPurpose::JobDescription => "gpt-5",
+            ModelPurpose::ImageGeneration => "gpt-image-1",
+            _ => "gpt-5-mini",
+        }
+    }
+
+    pub fn default_reasoning_effort(&self) -> Option<&'static str> {
+        match self {
+            ModelPurpose::ResumeScanning | ModelPurpose::JobDescription => Some("medium"),
+            ModelPurpose::EmailGeneration | ModelPurpose::MessageResponses => Some("low"),
+            ModelPurpose::ImageGeneration => None,
+        }
+    }
+
+    /// Whether `model` can serve this purpose: image models only for image generation
+    pub fn accepts_model(&self, model: &str) -> bool {
+        let is_image_model = model.starts_with("gpt-image") || model.starts_with("dall-e");
+        is_image_model == (*self == ModelPurpose::ImageGeneration)
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 pub enum TextGenerationPurpose {
     ResumeScanning,
@@ -84,6 +189,20 @@ pub enum TextGenerationPurpose {
     EmailComposition,
 }
 
+impl TextGenerationPurpose {
+    /// Feature name used when logging usage
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TextGenerationPurpose::ResumeScanning => "resume_scanning",
+            TextGenerationPurpose::EmailGeneration => "email_generation",
+            TextGenerationPurpose::MessageResponses => "message_responses",
+            TextGenerationPurpose::JobDescriptionGeneration => "job_description_generation",
+            TextGenerationPurpose::JobDescription => "job_description",
+            TextGenerationPurpose::EmailComposition => "email_composition",
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 pub enum ImageSize {
     LinkedIn,  // 1200x627
@@ -236,15 +355,26 @@ struct ContentItem {
     text: Option<String>,
 }
 
+/// Token usage; the Responses and Images APIs name the counts input/output tokens
 #[derive(Debug, Deserialize)]
 struct Usage {
-    #[serde(default)]
+    #[serde(default, alias = "input_tokens")]
     prompt_tokens: u32,
-    #[serde(default)]
+    #[serde(default, alias = "output_tokens")]
     completion_tokens: u32,
     total_tokens: u32,
 }
 
+impl Usage {
+    fn to_call_usage(&self) -> CallUsage {
+        CallUsage {
+            prompt_tokens: self.prompt_tokens as i64,
+            completion_tokens: self.completion_tokens as i64,
+            images: 0,
+        }
+    }
+}
+
 #[derive(Debug, Serialize)]
 struct ImageGenerationRequest {
     model: String,
@@ -265,6 +395,7 @@ struct ImageGenerationRequest {
 struct ImageGenerationResponse {
     created: u64,
     data: Vec<ImageData>,
+    usage: Option<Usage>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -277,11 +408,12 @@ struct ImageData {
 #[derive(Debug)]
 pub struct OpenAIService {
     settings_service: Arc<SettingsService>,
+    db: SqlitePool,
     client: Client,
 }
 
 impl OpenAIService {
-    pub fn new(settings_service: Arc<SettingsService>) -> Self {
+    pub fn new(settings_service: Arc<SettingsService>, db: SqlitePool) -> Self {
         let client = Client::builder()
             .timeout(std::time::Duration::from_secs(180)) // Increased to 3 minutes for AI generation
             .build()
@@ -289,6 +421,7 @@ impl OpenAIService {
 
         Self {
             settings_service,
+            db,
             client,
         }
     }
@@ -313,37 +446,31 @@ impl OpenAIService {
 
         // Get model configurations (with defaults)
         let models = ModelConfig {
-            resume_scanning: self
-                .get_model_setting("openai_model_resume_scanning", "gpt-5-mini")
-                .await?,
-            email_generation: self
-                .get_model_setting("openai_model_email_generation", "gpt-5-mini")
-                .await?,
-            message_responses: self
-                .get_model_setting("openai_model_message_responses", "gpt-5-mini")
-                .await?,
-            job_description_generation: self
-                .get_model_setting("openai_model_job_description", "gpt-5")
-                .await?,
-            image_generation: self
-                .get_model_setting("openai_model_image_generation", "gpt-image-1")
-                .await?,
+            resume_scanning: self.model_for(ModelPurpose::ResumeScanning).await?,
+            email_generation: self.model_for(ModelPurpose::EmailGeneration).await?,
+            message_responses: self.model_for(ModelPurpose::MessageResponses).await?,
+            job_description_generation: self.model_for(ModelPurpose::JobDescription).await?,
+            image_generation: self.model_for(ModelPurpose::ImageGeneration).await?,
         };
 
         // Get reasoning effort configurations (with defaults)
         let reasoning_effort = ReasoningEffortConfig {
             resume_scanning: self
-                .get_reasoning_effort_setting("openai_reasoning_effort_resume_scanning", "medium")
-                .await?,
+                .reasoning_effort_for(ModelPurpose::ResumeScanning)
+                .await?
+                .unwrap_or_default(),
             email_generation: self
-                .get_reasoning_effort_setting("openai_reasoning_effort_email_generation", "low")
-                .await?,
+                .reasoning_effort_for(ModelPurpose::EmailGeneration)
+                .await?
+                .unwrap_or_default(),
             message_responses: self
-                .get_reasoning_effort_setting("openai_reasoning_effort_message_responses", "low")
-                .await?,
+                .reasoning_effort_for(ModelPurpose::MessageResponses)
+                .await?
+                .unwrap_or_default(),
             job_description_generation: self
-                .get_reasoning_effort_setting("openai_reasoning_effort_job_description", "medium")
-                .await?,
+                .reasoning_effort_for(ModelPurpose::JobDescription)
+                .await?
+                .unwrap_or_default(),
         };
 
         Ok(OpenAIConfig {
@@ -354,6 +481,30 @@ impl OpenAIService {
         })
     }
 
+    /// The model configured for a purpose. Settings are read on every call, so a change
+    /// takes effect on the next request.
+    pub async fn model_for(&self, purpose: ModelPurpose) -> Result<String, OpenAIError> {
+        self.get_model_setting(&purpose.model_setting_key(), purpose.default_model())
+            .await
+    }
+
+    /// The reasoning effort configured for a purpose; `None` for image generation
+    pub async fn reasoning_effort_for(
+        &self,
+        purpose: ModelPurpose,
+    ) -> Result<Option<String>, OpenAIError> {
+        match (
+            purpose.reasoning_setting_key(),
+            purpose.default_reasoning_effort(),
+        ) {
+            (Some(key), Some(default)) => self
+                .get_reasoning_effort_setting(&key, default)
+                .await
+                .map(Some),
+            _ => Ok(None),
+        }
+    }
+
     async fn get_model_setting(&self, key: &str, default: &str) -> Result<String, OpenAIError> {
         Ok(self
             .settings_service
@@ -477,8 +628,10 @@ impl OpenAIService {
             "Sending OpenAI text generation request"
         );
 
-        // Make API request with retry logic
-        let response = self.make_request_with_retry(&config, request).await?;
+        let response = resilience::call(Dependency::OpenAI, || {
+            self.make_request(&config, &request)
+        })
+        .await?;
 
         // Extract generated text - handle both GPT-4 (choices) and GPT-5 (output) formats
         let generated_text = if !response.output.is_empty() {
@@ -520,44 +673,19 @@ impl OpenAIService {
                 tokens_used = usage.total_tokens,
                 "OpenAI text generation completed"
             );
+            ai_usage::record(
+                &self.db,
+                &self.settings_service,
+                purpose.as_str(),
+                model,
+                usage.to_call_usage(),
+            )
+            .await;
         }
 
         Ok(generated_text)
     }
 
-    /// Make API request with retry logic
-    async fn make_request_with_retry(
-        &self,
-        config: &OpenAIConfig,
-        request: ChatCompletionRequest,
-    ) -> Result<ChatCompletionResponse, OpenAIError> {
-        let max_retries = 3;
-        let mut last_error = None;
-
-        for attempt in 1..=max_retries {
-            match self.make_request(config, &request).await {
-                Ok(response) => return Ok(response),
-                Err(e) => {
-                    warn!(
-                        attempt = attempt,
-                        max_retries = max_retries,
-                        error = %e,
-                        "OpenAI API request failed, retrying..."
-                    );
-                    last_error = Some(e);
-
-                    // Exponential backoff
-                    if attempt < max_retries {
-                        let delay = std::time::Duration::from_millis(1000 * 2_u64.pow(attempt - 1));
-                        tokio::time::sleep(delay).await;
-                    }
-                }
-            }
-        }
-
-        Err(last_error.unwrap_or_else(|| OpenAIError::RequestFailed("Unknown error".to_string())))
-    }
-
     /// Make a single API request
     async fn make_request(
         &self,
@@ -597,10 +725,7 @@ impl OpenAIService {
                 .await
                 .unwrap_or_else(|_| "Unknown error".to_string());
             error!(status = %status, error = %error_text, "OpenAI API request failed");
-            return Err(OpenAIError::RequestFailed(format!(
-                "HTTP {}: {}",
-                status, error_text
-            )));
+            return Err(OpenAIError::from_status(status, error_text));
         }
 
         response
@@ -639,6 +764,20 @@ impl OpenAIService {
         prompt: &str,
         size: ImageSize,
         style: ImageStyle,
+    ) -> Result<String, OpenAIError> {
+        // Every generation is billed, so a timed-out one isn't repeated
+        resilience::call_once(
+            Dependency::OpenAI,
+            self.generate_image_once(prompt, size, style),
+        )
+        .await
+    }
+
+    async fn generate_image_once(
+        &self,
+        prompt: &str,
+        size: ImageSize,
+        style: ImageStyle,
     ) -> Result<String, OpenAIError> {
         let config = self.get_config().await?;
 
@@ -692,10 +831,7 @@ impl OpenAIService {
                 .await
                 .unwrap_or_else(|_| "Unknown error".to_string());
             error!(status = %status, error = %error_text, "OpenAI image generation failed");
-            return Err(OpenAIError::RequestFailed(format!(
-                "HTTP {}: {}",
-                status, error_text
-            )));
+            return Err(OpenAIError::from_status(status, error_text));
         }
 
         let image_response = response
@@ -726,6 +862,19 @@ impl OpenAIService {
             style = ?style,
             "OpenAI image generation completed"
         );
+        let usage = image_response
+            .usage
+            .as_ref()
+            .map(Usage::to_call_usage)
+            .unwrap_or_default();
+        ai_usage::record(
+            &self.db,
+            &self.settings_service,
+            "image_generation",
+            &config.models.image_generation,
+            CallUsage { images: 1, ..usage },
+        )
+        .await;
 
         Ok(result)
     }
@@ -805,6 +954,128 @@ impl OpenAIService {
             .await
     }
 
+    /// List the model IDs available to the configured API key
+    pub async fn list_models(&self) -> Result<Vec<String>, OpenAIError> {
+        let config = self.get_config().await?;
+        let url = format!("{}/v1/models", config.base_url.trim_end_matches('/'));
+
+        let response = self
+            .client
+            .get(&url)
+            .header("Authorization", format!("Bearer {}", config.api_key))
+            .send()
+            .await
+            .map_err(|e| OpenAIError::RequestFailed(e.to_string()))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let error_text = response
+                .text()
+                .await
+                .unwrap_or_else(|_| "Unknown error".to_string());
+            warn!(status = %status, error = %error_text, "OpenAI model list request failed");
+            return Err(OpenAIError::RequestFailed(format!(
+                "HTTP {}: {}",
+                status, error_text
+            )));
+        }
+
+        #[derive(Deserialize)]
+        struct ModelList {
+            data: Vec<ModelEntry>,
+        }
+
+        #[derive(Deserialize)]
+        struct ModelEntry {
+            id: String,
+        }
+
+        let list: ModelList = response
+            .json()
+            .await
+            .map_err(|e| OpenAIError::InvalidResponse(e.to_string()))?;
+
+        debug!(count = list.data.len(), "Retrieved OpenAI model list");
+        Ok(list.data.into_iter().map(|m| m.id).collect())
+    }
+
+    /// Run text through the moderation endpoint, returning the categories it was flagged
+    /// for (empty when the text is acceptable)
+    pub async fn moderate(&self, input: &str) -> Result<Vec<String>, OpenAIError> {
+        resilience::call(Dependency::OpenAI, || self.moderate_once(input)).await
+    }
+
+    async fn moderate_once(&self, input: &str) -> Result<Vec<String>, OpenAIError> {
+        let config = self.get_config().await?;
+        let url = format!("{}/v1/moderations", config.base_url.trim_end_matches('/'));
+
+        let response = self
+            .client
+            .post(&url)
+            .header("Authorization", format!("Bearer {}", config.api_key))
+            .json(&serde_json::json!({
+                "model": "omni-moderation-latest",
+                "input": input,
+            }))
+            .send()
+            .await
+            .map_err(|e| OpenAIError::RequestFailed(e.to_string()))?;
+
+        let status = response.status();
+        if status.as_u16() == 429 {
+            return Err(OpenAIError::RateLimitExceeded);
+        }
+        if !status.is_success() {
+            let error_text = response
+                .text()
+                .await
+                .unwrap_or_else(|_| "Unknown error".to_string());
+            warn!(status = %status, error = %error_text, "OpenAI moderation request failed");
+            return Err(OpenAIError::from_status(status, error_text));
+        }
+
+        #[derive(Deserialize)]
+        struct ModerationResponse {
+            results: Vec<ModerationResult>,
+        }
+
+        #[derive(Deserialize)]
+        struct ModerationResult {
+            flagged: bool,
+            #[serde(default)]
+            categories: std::collections::HashMap<String, bool>,
+        }
+
+        let moderation: ModerationResponse = response
+            .json()
+            .await
+            .map_err(|e| OpenAIError::InvalidResponse(e.to_string()))?;
+
+        let mut categories: Vec<String> = moderation
+            .results
+            .into_iter()
+            .filter(|r| r.flagged)
+            .flat_map(|r| {
+                let hits: Vec<String> = r
+                    .categories
+                    .into_iter()
+                    .filter(|(_, hit)| *hit)
+                    .map(|(category, _)| category)
+                    .collect();
+                if hits.is_empty() {
+                    vec!["flagged".to_string()]
+                } else {
+                    hits
+                }
+            })
+            .collect();
+        categories.sort();
+        categories.dedup();
+
+        debug!(flagged_categories = categories.len(), "Moderation check completed");
+        Ok(categories)
+    }
+
     /// Test OpenAI connection
     pub async fn test_connection(&self) -> Result<String, OpenAIError> {
         let config = self.get_config().await?;
@@ -847,6 +1118,41 @@ mod tests {
         assert_eq!(config.image_generation, "gpt-image-1");
     }
 
+    #[test]
+    fn test_model_purposes_match_settings_and_defaults() {
+        let defaults = ModelConfig::default();
+        assert_eq!(
+            ModelPurpose::JobDescription.model_setting_key(),
+            "openai_model_job_description"
+        );
+        assert_eq!(
+            ModelPurpose::JobDescription.default_model(),
+            defaults.job_description_generation
+        );
+        assert_eq!(
+            ModelPurpose::ImageGeneration.default_model(),
+            defaults.image_generation
+        );
+        assert_eq!(
+            ModelPurpose::ResumeScanning.reasoning_setting_key().as_deref(),
+            Some("openai_reasoning_effort_resume_scanning")
+        );
+        assert_eq!(ModelPurpose::ImageGeneration.reasoning_setting_key(), None);
+        assert_eq!(
+            ModelPurpose::parse("email_generation"),
+            Some(ModelPurpose::EmailGeneration)
+        );
+        assert_eq!(ModelPurpose::parse("email"), None);
+    }
+
+    #[test]
+    fn test_model_purpose_accepts_model() {
+        assert!(ModelPurpose::ImageGeneration.accepts_model("gpt-image-1"));
+        assert!(!ModelPurpose::ImageGeneration.accepts_model("gpt-5"));
+        assert!(ModelPurpose::ResumeScanning.accepts_model("gpt-5-mini"));
+        assert!(!ModelPurpose::ResumeScanning.accepts_model("dall-e-3"));
+    }
+
     #[test]
     fn test_default_reasoning_effort_config() {
         let config = ReasoningEffortConfig::default();
@@ -0,0 +1,172 @@
+// src/services/org.rs
+//! Job assignment lookups and notifications scoped to a job's hiring manager and recruiters
+
+use sqlx::SqlitePool;
+use tracing::{debug, error, info};
+
+use crate::admin::models::AssignedUser;
+use crate::common::{ApiError, AppState};
+
+/// Hiring manager and recruiters of a job, hiring manager first, without duplicates
+pub async fn job_assignees(
+    pool: &SqlitePool,
+    job_id: &str,
+) -> Result<Vec<AssignedUser>, sqlx::Error> {
+    let mut assignees = sqlx::query_as::<_, AssignedUser>(
+        r#"
+        SELECT u.id, u.name, u.email FROM jobs j
+        JOIN users u ON u.id = j.hiring_manager_id
+        WHERE j.id = ?
+        "#,
+    )
+    .bind(job_id)
+    .fetch_all(pool)
+    .await?;
+
+    let recruiters = sqlx::query_as::<_, AssignedUser>(
+        r#"
+        SELECT u.id, u.name, u.email FROM job_recruiters r
+        JOIN users u ON u.id = r.user_id
+        WHERE r.job_id = ?
+        ORDER BY r.assigned_at
+        "#,
+    )
+    .bind(job_id)
+    .fetch_all(pool)
+    .await?;
+
+    for recruiter in recruiters {
+        if !assignees.iter().any(|a| a.id == recruiter.id) {
+            assignees.push(recruiter);
+        }
+    }
+    Ok(assignees)
+}
+
+/// Email addresses to notify about a job
+pub async fn job_assignee_emails(
+    pool: &SqlitePool,
+    job_id: &str,
+) -> Result<Vec<String>, sqlx::Error> {
+    Ok(job_assignees(pool, job_id)
+        .await?
+        .into_iter()
+        .map(|a| a.email)
+        .collect())
+}
+
+/// Combine a comma-separated notification list with a job's assignees, dropping blanks and
+/// case-insensitive duplicates; sorted so the same people always form the same list
+pub fn merge_recipients(notify_email: Option<&str>, assignees: &[String]) -> Vec<String> {
+    let mut recipients: Vec<String> = Vec::new();
+    let listed = notify_email.unwrap_or_default().split(',');
+    for email in listed.chain(assignees.iter().map(String::as_str)) {
+        let email = email.trim();
+        if !email.is_empty() && !recipients.iter().any(|r| r.eq_ignore_ascii_case(email)) {
+            recipients.push(email.to_string());
+        }
+    }
+    recipients.sort_by_key(|r| r.to_lowercase());
+    recipients
+}
+
+/// Check a user can be assigned to a job: they must exist and be an admin
+pub async fn load_assignable_user(
+    state: &AppState,
+    user_id: &str,
+) -> Result<AssignedUser, ApiError> {
+    let user = sqlx::query_as::<_, AssignedUser>("SELECT id, name, email FROM users WHERE id = ?")
+        .bind(user_id)
+        .fetch_optional(&state.db)
+        .await
+        .map_err(ApiError::DatabaseError)?
+        .ok_or_else(|| ApiError::NotFound(format!("User {} not found", user_id)))?;
+
+    if !state.admin_emails.contains(&user.email.to_lowercase()) {
+        return Err(ApiError::ValidationError(format!(
+            "{} is not an admin and cannot be assigned to jobs",
+            user.email
+        )));
+    }
+    Ok(user)
+}
+
+/// Email a job's hiring manager and recruiters about a new application
+///
+/// Jobs without assignees send nothing. Never fails the caller.
+pub async fn notify_new_application(state: &AppState, application_id: &str, job_id: &str) {
+    let recipients = match job_assignee_emails(&state.db, job_id).await {
+        Ok(recipients) => recipients,
+        Err(e) => {
+            error!(error = %e, job_id = %job_id, "Failed to load job assignees");
+            return;
+        }
+    };
+    if recipients.is_empty() {
+        debug!(job_id = %job_id, "No one assigned to job; skipping new application email");
+        return;
+    }
+
+    let details: Option<(String, Option<String>)> = sqlx::query_as(
+        r#"
+        SELECT j.title, u.name FROM applications a
+        JOIN jobs j ON j.id = a.job_id
+        LEFT JOIN users u ON u.id = a.user_id
+        WHERE a.id = ?
+        "#,
+    )
+    .bind(application_id)
+    .fetch_optional(&state.db)
+    .await
+    .ok()
+    .flatten();
+    let Some((job_title, candidate_name)) = details else {
+        return;
+    };
+
+    let subject = format!("New application for {}", job_title);
+    let body = format!(
+        "<p><strong>{}</strong> applied for <strong>{}</strong>.</p><p><a href=\"{}/admin/applications/{}\">Review the application</a></p><p>You are receiving this because you are assigned to this job.</p>",
+        candidate_name.as_deref().unwrap_or("A candidate"),
+        job_title,
+        state.config.frontend_url,
+        application_id
+    );
+
+    match state
+        .email
+        .send_email(recipients.clone(), &subject, &body, None)
+        .await
+    {
+        Ok(_) => info!(
+            application_id = %application_id,
+            job_id = %job_id,
+            recipients = recipients.len(),
+            "Sent new application notification"
+        ),
+        Err(e) => error!(
+            error = %e,
+            application_id = %application_id,
+            "Failed to send new application notification"
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merge_recipients() {
+        let assignees = vec!["hm@example.com".to_string(), "Rec@example.com".to_string()];
+        assert_eq!(
+            merge_recipients(Some(" rec@example.com, lead@example.com ,"), &assignees),
+            vec!["hm@example.com", "lead@example.com", "rec@example.com"]
+        );
+        assert_eq!(
+            merge_recipients(None, &assignees),
+            vec!["hm@example.com", "Rec@example.com"]
+        );
+        assert!(merge_recipients(Some(""), &[]).is_empty());
+    }
+}
@@ -8,7 +8,8 @@ use std::io::BufWriter;
 use std::sync::Arc;
 
 use crate::common::generate_raw_id;
-use crate::services::aws::AWSService;
+use crate::services::ObjectStore;
+use crate::services::pii::{self, Sealed};
 use crate::services::settings::SettingsService;
 
 /// Offer letter data structure
@@ -29,19 +30,19 @@ pub struct OfferLetterData {
 pub struct PDFService {
     pool: SqlitePool,
     settings_service: Arc<SettingsService>,
-    aws_service: Arc<AWSService>,
+    object_store: Arc<dyn ObjectStore>,
 }
 
 impl PDFService {
     pub fn new(
         pool: SqlitePool,
         settings_service: Arc<SettingsService>,
-        aws_service: Arc<AWSService>,
+        object_store: Arc<dyn ObjectStore>,
     ) -> Self {
         Self {
             pool,
             settings_service,
-            aws_service,
+            object_store,
         }
     }
 
@@ -251,7 +252,7 @@ impl PDFService {
             let file_data = std::fs::read(temp_path)?;
             let s3_key = format!("offer-letters/{}", filename);
 
-            self.aws_service
+            self.object_store
                 .upload_file(file_data, &s3_key, "application/pdf")
                 .await?
         } else {
@@ -266,6 +267,42 @@ impl PDFService {
         Ok(pdf_url)
     }
 
+    /// Render a plain-text document such as a conversation transcript, wrapping each paragraph
+    /// and starting new pages as needed. Empty paragraphs leave a blank line.
+    pub fn render_text_pdf(&self, title: &str, paragraphs: &[String]) -> Result<Vec<u8>> {
+        let (doc, page1, layer1) = PdfDocument::new(title, Mm(210.0), Mm(297.0), "Layer 1");
+        let font_bold = doc.add_builtin_font(BuiltinFont::HelveticaBold)?;
+        let font_regular = doc.add_builtin_font(BuiltinFont::Helvetica)?;
+
+        let left_margin = Mm(20.0);
+        let top_margin = Mm(277.0);
+        let bottom_margin = Mm(20.0);
+        let mut current_layer = doc.get_page(page1).get_layer(layer1);
+        let mut current_y = top_margin;
+
+        current_layer.use_text(title, 14.0, left_margin, current_y, &font_bold);
+        current_y -= Mm(10.0);
+
+        for paragraph in paragraphs {
+            let lines = self.wrap_text(paragraph, 95);
+            if lines.is_empty() {
+                current_y -= Mm(3.0);
+                continue;
+            }
+            for line in lines {
+                if current_y < bottom_margin {
+                    let (page, layer) = doc.add_page(Mm(210.0), Mm(297.0), "Layer 1");
+                    current_layer = doc.get_page(page).get_layer(layer);
+                    current_y = top_margin;
+                }
+                current_layer.use_text(&line, 10.0, left_margin, current_y, &font_regular);
+                current_y -= Mm(5.0);
+            }
+        }
+
+        Ok(doc.save_to_bytes()?)
+    }
+
     /// Wrap text to fit within specified character width
     fn wrap_text(&self, text: &str, max_chars: usize) -> Vec<String> {
         let mut lines = Vec::new();
@@ -319,7 +356,7 @@ impl PDFService {
         .bind(candidate_id)
         .bind(job_id)
         .bind(&data.job_title)
-        .bind(data.salary)
+        .bind(pii::seal_number(data.salary)?)
         .bind(&data.start_date)
         .bind(&data.benefits)
         .bind(&data.additional_terms)
@@ -376,6 +413,7 @@ pub struct OfferLetterRecord {
     pub candidate_id: String,
     pub job_id: String,
     pub job_title: String,
+    #[sqlx(try_from = "Sealed")]
     pub salary: f64,
     pub start_date: String,
     pub benefits: String,
@@ -392,6 +430,7 @@ pub struct OfferLetterRecord {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::services::aws::AWSService;
     use sqlx::SqlitePool;
 
     async fn setup_test_db() -> SqlitePool {
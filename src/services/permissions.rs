@@ -0,0 +1,34 @@
+// src/services/permissions.rs
+//! Named permissions granted to admins in `admin_users.permissions`
+//!
+//! Being an admin is enough for most of the admin API. A few sensitive areas additionally
+//! need one of these permissions.
+
+use sqlx::SqlitePool;
+
+/// Aggregate EEO (self-identification) reports
+pub const EEO_REPORTS: &str = "eeo_reports";
+
+/// Placing and releasing legal holds
+pub const LEGAL_HOLD: &str = "legal_hold";
+
+/// Seeing full emails, phone numbers and salaries in admin responses (see `masking`)
+pub const VIEW_PII: &str = "view_pii";
+
+/// Whether an admin has been granted a named permission
+pub async fn has_admin_permission(
+    pool: &SqlitePool,
+    user_id: &str,
+    permission: &str,
+) -> Result<bool, sqlx::Error> {
+    let permissions: Option<Option<String>> =
+        sqlx::query_scalar("SELECT permissions FROM admin_users WHERE user_id = ?")
+            .bind(user_id)
+            .fetch_optional(pool)
+            .await?;
+
+    Ok(permissions
+        .flatten()
+        .and_then(|json| serde_json::from_str::<Vec<String>>(&json).ok())
+        .is_some_and(|granted| granted.iter().any(|p| p == permission)))
+}
@@ -0,0 +1,524 @@
+// src/services/pii.rs
+//! Encryption of personal data columns at rest
+//!
+//! Phone numbers, candidate locations and offer salaries are sealed with the master key
+//! before they are written, and opened again as rows are decoded through [`Sealed`].
+//! Stored values look like `pii1:<key id>:<envelope>`, so values written under an older
+//! key still open while `ENCRYPTION_MASTER_KEY_PREVIOUS` lists that key, and
+//! [`reseal_all`] rewrites them under the current key at startup. Without a master key
+//! values are written as plaintext, as settings are.
+//!
+//! Sealed phone numbers can't be compared in SQL, so `profiles.phone_index` keeps a keyed
+//! hash of each number's last ten digits for matching duplicate accounts.
+
+use hmac::{Hmac, Mac};
+use sha2::{Digest, Sha256};
+use sqlx::error::BoxDynError;
+use sqlx::sqlite::{Sqlite, SqliteTypeInfo, SqliteValueRef};
+use sqlx::{Decode, SqlitePool, Type, TypeInfo, ValueRef};
+use std::sync::OnceLock;
+use thiserror::Error;
+use tracing::{info, warn};
+
+use crate::services::account_linking::normalize_phone;
+use crate::services::encryption::{EncryptionError, EncryptionService};
+
+/// Prefix marking sealed values
+pub const PII_PREFIX: &str = "pii1:";
+
+/// Rows re-encrypted per query during [`reseal_all`]
+const RESEAL_BATCH_SIZE: i64 = 500;
+
+/// Columns holding personal data, as (table, column, rows the column is personal in)
+const PII_COLUMNS: &[(&str, &str, &str)] = &[
+    ("profiles", "phone", "1"),
+    ("profiles", "pending_phone", "1"),
+    ("profiles", "location", "1"),
+    ("contact_verifications", "value", "channel = 'phone'"),
+    ("sms_deliveries", "phone", "1"),
+    ("whatsapp_messages", "phone", "1"),
+    ("offer_letters", "salary", "1"),
+    ("compensation_overrides", "salary", "1"),
+];
+
+static KEYS: OnceLock<PiiKeys> = OnceLock::new();
+
+struct PiiKey {
+    id: String,
+    cipher: EncryptionService,
+    index_key: Vec<u8>,
+}
+
+impl PiiKey {
+    fn new(key: &str) -> Result<Self, EncryptionError> {
+        let key = key.trim();
+        let id = Sha256::digest(key.as_bytes())
+            .iter()
+            .take(4)
+            .map(|b| format!("{:02x}", b))
+            .collect();
+        Ok(Self {
+            id,
+            cipher: EncryptionService::from_key(key)?,
+            index_key: Sha256::digest(format!("pii-index:{}", key).as_bytes()).to_vec(),
+        })
+    }
+}
+
+/// The master key values are sealed with, and retired keys they may still be sealed with
+pub struct PiiKeys {
+    current: Option<PiiKey>,
+    previous: Vec<PiiKey>,
+}
+
+impl PiiKeys {
+    pub fn new(current: Option<&str>, previous: &[&str]) -> Result<Self, EncryptionError> {
+        Ok(Self {
+            current: current.map(PiiKey::new).transpose()?,
+            previous: previous
+                .iter()
+                .filter(|key| !key.trim().is_empty())
+                .map(|key| PiiKey::new(key))
+                .collect::<Result<_, _>>()?,
+        })
+    }
+
+    /// Keys from `ENCRYPTION_MASTER_KEY` (or `_FILE`) and the comma-separated
+    /// `ENCRYPTION_MASTER_KEY_PREVIOUS`
+    pub fn from_env() -> Result<Self, EncryptionError> {
+        let current = match EncryptionService::master_key_from_env() {
+            Ok(key) => Some(key),
+            Err(EncryptionError::KeyNotConfigured) => None,
+            Err(e) => return Err(e),
+        };
+        let previous = std::env::var("ENCRYPTION_MASTER_KEY_PREVIOUS").unwrap_or_default();
+        Self::new(current.as_deref(), &previous.split(',').collect::<Vec<_>>())
+    }
+
+    pub fn is_configured(&self) -> bool {
+        self.current.is_some()
+    }
+
+    /// Seal a value under the current key; blank values, and every value when no key is
+    /// configured, are kept as they are
+    pub fn seal(&self, value: &str) -> Result<String, EncryptionError> {
+        match &self.current {
+            Some(key) if !value.trim().is_empty() => Ok(format!(
+                "{}{}:{}",
+                PII_PREFIX,
+                key.id,
+                key.cipher.encrypt_envelope(value)?
+            )),
+            _ => Ok(value.to_string()),
+        }
+    }
+
+    /// Open a stored value; plaintext from before encryption passes through unchanged
+    pub fn open(&self, stored: &str) -> Result<String, EncryptionError> {
+        let Some(sealed) = stored.strip_prefix(PII_PREFIX) else {
+            return Ok(stored.to_string());
+        };
+        let (key_id, envelope) = sealed
+            .split_once(':')
+            .ok_or(EncryptionError::InvalidDataFormat)?;
+        self.current
+            .iter()
+            .chain(&self.previous)
+            .find(|key| key.id == key_id)
+            .ok_or(EncryptionError::KeyNotConfigured)?
+            .cipher
+            .decrypt(envelope)
+    }
+
+    /// Whether a stored value is already in the form [`PiiKeys::seal`] writes
+    pub fn is_current(&self, stored: &str) -> bool {
+        match &self.current {
+            Some(key) => {
+                stored.trim().is_empty()
+                    || stored
+                        .strip_prefix(PII_PREFIX)
+                        .and_then(|sealed| sealed.split_once(':'))
+                        .is_some_and(|(key_id, _)| key_id == key.id)
+            }
+            None => !stored.starts_with(PII_PREFIX),
+        }
+    }
+
+    /// Blind index of a phone number, equal for numbers that match as duplicates
+    ///
+    /// Without a key this is the normalized number itself, as the phone is stored in
+    /// plaintext anyway.
+    pub fn phone_index(&self, phone: &str) -> Option<String> {
+        let digits = normalize_phone(phone)?;
+        let Some(key) = &self.current else {
+            return Some(digits);
+        };
+        let mut mac = Hmac::<Sha256>::new_from_slice(&key.index_key).ok()?;
+        mac.update(digits.as_bytes());
+        Some(
+            mac.finalize()
+                .into_bytes()
+                .iter()
+                .map(|b| format!("{:02x}", b))
+                .collect(),
+        )
+    }
+}
+
+fn keys() -> &'static PiiKeys {
+    KEYS.get_or_init(|| {
+        PiiKeys::from_env().unwrap_or_else(|e| {
+            warn!(error = %e, "Invalid encryption key; personal data will not be encrypted");
+            PiiKeys {
+                current: None,
+                previous: Vec::new(),
+            }
+        })
+    })
+}
+
+/// Load the keys from the environment, failing on a malformed key
+pub fn init() -> Result<(), EncryptionError> {
+    let loaded = PiiKeys::from_env()?;
+    if !loaded.is_configured() {
+        warn!("Encryption key not configured; phone numbers, locations and salaries are stored in plaintext");
+    }
+    let _ = KEYS.set(loaded);
+    Ok(())
+}
+
+pub fn seal(value: &str) -> Result<String, EncryptionError> {
+    keys().seal(value)
+}
+
+pub fn seal_opt(value: Option<&str>) -> Result<Option<String>, EncryptionError> {
+    value.map(seal).transpose()
+}
+
+pub fn seal_number(value: f64) -> Result<String, EncryptionError> {
+    seal(&value.to_string())
+}
+
+pub fn phone_index(phone: &str) -> Option<String> {
+    keys().phone_index(phone)
+}
+
+/// For callers reporting `sqlx::Error`; sealing only fails when the key is unusable
+pub fn db_error(e: EncryptionError) -> sqlx::Error {
+    sqlx::Error::Configuration(Box::new(e))
+}
+
+/// Why a sealed column couldn't become the model field's type
+#[derive(Debug, Error)]
+pub enum SealedError {
+    #[error("value is NULL")]
+    Null,
+
+    #[error("value is not a number")]
+    NotANumber,
+}
+
+/// A personal data column, opened as it is decoded
+///
+/// Model fields keep their own type with `#[sqlx(try_from = "Sealed")]`. Numbers written
+/// before the column was encrypted decode as their text form.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Sealed(pub Option<String>);
+
+impl Sealed {
+    pub fn into_inner(self) -> Option<String> {
+        self.0
+    }
+}
+
+impl Type<Sqlite> for Sealed {
+    fn type_info() -> SqliteTypeInfo {
+        <String as Type<Sqlite>>::type_info()
+    }
+
+    fn compatible(ty: &SqliteTypeInfo) -> bool {
+        <String as Type<Sqlite>>::compatible(ty)
+            || <f64 as Type<Sqlite>>::compatible(ty)
+            || <i64 as Type<Sqlite>>::compatible(ty)
+    }
+}
+
+impl<'r> Decode<'r, Sqlite> for Sealed {
+    fn decode(value: SqliteValueRef<'r>) -> Result<Self, BoxDynError> {
+        if value.is_null() {
+            return Ok(Self(None));
+        }
+        let stored = match value.type_info().name() {
+            "REAL" => <f64 as Decode<Sqlite>>::decode(value)?.to_string(),
+            "INTEGER" => <i64 as Decode<Sqlite>>::decode(value)?.to_string(),
+            _ => <String as Decode<Sqlite>>::decode(value)?,
+        };
+        Ok(Self(Some(keys().open(&stored)?)))
+    }
+}
+
+impl From<Sealed> for Option<String> {
+    fn from(sealed: Sealed) -> Self {
+        sealed.0
+    }
+}
+
+impl TryFrom<Sealed> for String {
+    type Error = SealedError;
+
+    fn try_from(sealed: Sealed) -> Result<Self, Self::Error> {
+        sealed.0.ok_or(SealedError::Null)
+    }
+}
+
+impl TryFrom<Sealed> for f64 {
+    type Error = SealedError;
+
+    fn try_from(sealed: Sealed) -> Result<Self, Self::Error> {
+        sealed
+            .0
+            .ok_or(SealedError::Null)?
+            .trim()
+            .parse()
+            .map_err(|_| SealedError::NotANumber)
+    }
+}
+
+/// Counts from a [`reseal_all`] pass
+#[derive(Debug, Default)]
+pub struct ResealSummary {
+    pub resealed: u64,
+    pub indexed: u64,
+    pub failed: u64,
+}
+
+/// Encrypt plaintext values and move values sealed under a previous key to the current
+/// one, filling in missing phone indexes along the way
+///
+/// Values that can't be opened (their key is no longer configured) are left alone and
+/// counted as failed.
+pub async fn reseal_all(pool: &SqlitePool) -> Result<ResealSummary, sqlx::Error> {
+    reseal_with(pool, keys()).await
+}
+
+async fn reseal_with(pool: &SqlitePool, keys: &PiiKeys) -> Result<ResealSummary, sqlx::Error> {
+    let mut summary = ResealSummary::default();
+    for (table, column, filter) in PII_COLUMNS {
+        let indexed = *table == "profiles" && *column == "phone";
+        let select = format!(
+            "SELECT rowid, CAST({column} AS TEXT), {} FROM {table} WHERE rowid > ? AND {column} IS NOT NULL AND {filter} ORDER BY rowid LIMIT ?",
+            if indexed {
+                "phone_index IS NULL AND trim(phone) != ''"
+            } else {
+                "0"
+            },
+        );
+        let mut after = 0i64;
+        loop {
+            let rows: Vec<(i64, String, bool)> = sqlx::query_as(&select)
+                .bind(after)
+                .bind(RESEAL_BATCH_SIZE)
+                .fetch_all(pool)
+                .await?;
+            let Some((last, _, _)) = rows.last() else {
+                break;
+            };
+            after = *last;
+
+            for (rowid, stored, missing_index) in rows {
+                let current = keys.is_current(&stored);
+                if current && !missing_index {
+                    continue;
+                }
+                let value = match keys.open(&stored) {
+                    Ok(value) => value,
+                    Err(e) => {
+                        warn!(table = %table, column = %column, rowid = rowid, error = %e, "Could not open personal data for re-encryption");
+                        summary.failed += 1;
+                        continue;
+                    }
+                };
+                let sealed = if current {
+                    stored
+                } else {
+                    match keys.seal(&value) {
+                        Ok(sealed) => sealed,
+                        Err(e) => {
+                            warn!(table = %table, column = %column, rowid = rowid, error = %e, "Could not re-encrypt personal data");
+                            summary.failed += 1;
+                            continue;
+                        }
+                    }
+                };
+
+                if indexed {
+                    let index = keys.phone_index(&value);
+                    if index.is_some() {
+                        summary.indexed += 1;
+                    }
+                    sqlx::query("UPDATE profiles SET phone = ?, phone_index = ? WHERE rowid = ?")
+                        .bind(&sealed)
+                        .bind(index)
+                        .bind(rowid)
+                        .execute(pool)
+                        .await?;
+                } else {
+                    sqlx::query(&format!("UPDATE {table} SET {column} = ? WHERE rowid = ?"))
+                        .bind(&sealed)
+                        .bind(rowid)
+                        .execute(pool)
+                        .await?;
+                }
+                if !current {
+                    summary.resealed += 1;
+                }
+            }
+        }
+    }
+
+    if summary.resealed > 0 || summary.indexed > 0 || summary.failed > 0 {
+        info!(
+            resealed = summary.resealed,
+            indexed = summary.indexed,
+            failed = summary.failed,
+            "Personal data encryption pass finished"
+        );
+    }
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    fn key() -> String {
+        EncryptionService::generate_key()
+    }
+
+    #[test]
+    fn test_seal_and_open() {
+        let keys = PiiKeys::new(Some(&key()), &[]).unwrap();
+        let sealed = keys.seal("+14155550123").unwrap();
+        assert!(sealed.starts_with(PII_PREFIX));
+        assert!(keys.is_current(&sealed));
+        assert_eq!(keys.open(&sealed).unwrap(), "+14155550123");
+        assert_ne!(keys.seal("+14155550123").unwrap(), sealed);
+
+        assert_eq!(keys.seal("  ").unwrap(), "  ");
+        assert_eq!(keys.open("Berlin").unwrap(), "Berlin");
+        assert!(!keys.is_current("Berlin"));
+    }
+
+    #[test]
+    fn test_previous_keys_still_open() {
+        let (old, new) = (key(), key());
+        let sealed = PiiKeys::new(Some(&old), &[])
+            .unwrap()
+            .seal("Paris")
+            .unwrap();
+
+        let rotated = PiiKeys::new(Some(&new), &[&old]).unwrap();
+        assert!(!rotated.is_current(&sealed));
+        assert_eq!(rotated.open(&sealed).unwrap(), "Paris");
+
+        let forgotten = PiiKeys::new(Some(&new), &[]).unwrap();
+        assert!(forgotten.open(&sealed).is_err());
+    }
+
+    #[test]
+    fn test_without_key_values_stay_plaintext() {
+        let keys = PiiKeys::new(None, &[]).unwrap();
+        assert_eq!(keys.seal("Paris").unwrap(), "Paris");
+        assert!(keys.is_current("Paris"));
+        assert_eq!(
+            keys.phone_index("+1 (415) 555-0123").as_deref(),
+            Some("4155550123")
+        );
+    }
+
+    #[test]
+    fn test_phone_index_matches_duplicates_only() {
+        let keys = PiiKeys::new(Some(&key()), &[]).unwrap();
+        let index = keys.phone_index("+1 (415) 555-0123").unwrap();
+        assert_eq!(keys.phone_index("415.555.0123"), Some(index.clone()));
+        assert_ne!(keys.phone_index("+1 415 555 0124"), Some(index.clone()));
+        assert!(!index.contains("4155550123"));
+        assert_eq!(keys.phone_index("123"), None);
+    }
+
+    #[test]
+    fn test_sealed_numbers() {
+        assert_eq!(
+            f64::try_from(Sealed(Some("85000".to_string()))).unwrap(),
+            85000.0
+        );
+        assert!(f64::try_from(Sealed(Some("n/a".to_string()))).is_err());
+        assert!(f64::try_from(Sealed(None)).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_reseal_encrypts_and_rotates() {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        for statement in [
+            "CREATE TABLE profiles (user_id TEXT PRIMARY KEY, phone TEXT, phone_index TEXT, pending_phone TEXT, location TEXT)",
+            "CREATE TABLE contact_verifications (id TEXT PRIMARY KEY, channel TEXT, value TEXT)",
+            "CREATE TABLE sms_deliveries (id TEXT PRIMARY KEY, phone TEXT)",
+            "CREATE TABLE whatsapp_messages (id TEXT PRIMARY KEY, phone TEXT)",
+            "CREATE TABLE offer_letters (id TEXT PRIMARY KEY, salary REAL)",
+            "CREATE TABLE compensation_overrides (id TEXT PRIMARY KEY, salary REAL NOT NULL)",
+            "INSERT INTO profiles (user_id, phone, location) VALUES ('u1', '+14155550123', 'Berlin'), ('u2', NULL, '')",
+            "INSERT INTO contact_verifications VALUES ('v1', 'phone', '+14155550199'), ('v2', 'email', 'a@example.com')",
+            "INSERT INTO offer_letters VALUES ('o1', 85000.5)",
+        ] {
+            sqlx::query(statement).execute(&pool).await.unwrap();
+        }
+
+        let (old, new) = (key(), key());
+        let first = PiiKeys::new(Some(&old), &[]).unwrap();
+        let summary = reseal_with(&pool, &first).await.unwrap();
+        assert_eq!(summary.resealed, 4);
+        assert_eq!(summary.failed, 0);
+
+        let (phone, index, location): (String, Option<String>, String) = sqlx::query_as(
+            "SELECT phone, phone_index, location FROM profiles WHERE user_id = 'u1'",
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert!(first.is_current(&phone) && first.is_current(&location));
+        assert_eq!(index, first.phone_index("4155550123"));
+        let email: String =
+            sqlx::query_scalar("SELECT value FROM contact_verifications WHERE id = 'v2'")
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!(email, "a@example.com");
+
+        // Nothing left to do under the same key
+        let again = reseal_with(&pool, &first).await.unwrap();
+        assert_eq!((again.resealed, again.indexed), (0, 0));
+
+        let rotated = PiiKeys::new(Some(&new), &[&old]).unwrap();
+        assert_eq!(reseal_with(&pool, &rotated).await.unwrap().resealed, 4);
+        let (phone, index): (String, Option<String>) =
+            sqlx::query_as("SELECT phone, phone_index FROM profiles WHERE user_id = 'u1'")
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!(rotated.open(&phone).unwrap(), "+14155550123");
+        assert_eq!(index, rotated.phone_index("4155550123"));
+        let salary: String = sqlx::query_scalar("SELECT salary FROM offer_letters")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(
+            rotated.open(&salary).unwrap().parse::<f64>().unwrap(),
+            85000.5
+        );
+    }
+}
@@ -0,0 +1,283 @@
+// src/services/promotions.rs
+//! Paid job promotion tracking and cost-per-application reporting
+//!
+//! A promotion's `source` tag ties it to the traffic it bought: clicks on the job's short
+//! links followed with `?src=<source>`, and applications submitted with that source. Both
+//! are counted only while the promotion runs (`start_date` through `end_date`, or today
+//! for open-ended promotions).
+
+use sqlx::SqlitePool;
+use std::collections::BTreeMap;
+
+use crate::admin::models::{
+    ChannelPerformance, JobPromotion, PromotionPerformance, PromotionReport, PromotionReportQuery,
+};
+use crate::common::{parse_date, ApiError};
+
+/// Longest attribution tag kept; longer tags are truncated
+const MAX_SOURCE_LENGTH: usize = 50;
+
+/// Normalize an attribution tag to lowercase `[a-z0-9_-]`, e.g. `LinkedIn Ads` -> `linkedin_ads`
+///
+/// Returns None when nothing usable is left.
+pub fn normalize_source(raw: &str) -> Option<String> {
+    let mut tag = String::new();
+    for c in raw.trim().chars() {
+        match c {
+            'a'..='z' | '0'..='9' | '_' | '-' => tag.push(c),
+            'A'..='Z' => tag.push(c.to_ascii_lowercase()),
+            ' ' | '.' | '/' if !tag.ends_with('_') => tag.push('_'),
+            _ => {}
+        }
+        if tag.len() >= MAX_SOURCE_LENGTH {
+            break;
+        }
+    }
+    let tag = tag.trim_matches('_').to_string();
+    (!tag.is_empty()).then_some(tag)
+}
+
+/// Check a promotion's amounts and run dates before it is stored
+pub fn validate_promotion(
+    budget: f64,
+    spend: f64,
+    start_date: &str,
+    end_date: Option<&str>,
+) -> Result<(), ApiError> {
+    if !budget.is_finite() || budget < 0.0 {
+        return Err(ApiError::ValidationError(
+            "budget must be zero or more".to_string(),
+        ));
+    }
+    if !spend.is_finite() || spend < 0.0 {
+        return Err(ApiError::ValidationError(
+            "spend must be zero or more".to_string(),
+        ));
+    }
+    let start = parse_date(Some(start_date), "start_date")?
+        .ok_or_else(|| ApiError::ValidationError("start_date is required".to_string()))?;
+    if let Some(end) = parse_date(end_date, "end_date")? {
+        if end < start {
+            return Err(ApiError::ValidationError(
+                "end_date must not be before start_date".to_string(),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Spend divided by `count`, rounded to cents; None when nothing was attributed
+pub fn cost_per(spend: f64, count: i64) -> Option<f64> {
+    (count > 0).then(|| (spend / count as f64 * 100.0).round() / 100.0)
+}
+
+/// Clicks and applications attributed to a promotion during its run
+async fn attribute(pool: &SqlitePool, promotion: &JobPromotion) -> Result<(i64, i64), ApiError> {
+    let clicks: i64 = sqlx::query_scalar(
+        r#"
+        SELECT COUNT(*) FROM job_short_link_clicks
+        WHERE job_id = ? AND channel = ?
+          AND date(clicked_at) BETWEEN ? AND COALESCE(?, date('now'))
+        "#,
+    )
+    .bind(&promotion.job_id)
+    .bind(&promotion.source)
+    .bind(&promotion.start_date)
+    .bind(&promotion.end_date)
+    .fetch_one(pool)
+    .await
+    .map_err(ApiError::DatabaseError)?;
+
+    let applications: i64 = sqlx::query_scalar(
+        r#"
+        SELECT COUNT(*) FROM applications
+        WHERE job_id = ? AND source = ?
+          AND date(applied_at) BETWEEN ? AND COALESCE(?, date('now'))
+        "#,
+    )
+    .bind(&promotion.job_id)
+    .bind(&promotion.source)
+    .bind(&promotion.start_date)
+    .bind(&promotion.end_date)
+    .fetch_one(pool)
+    .await
+    .map_err(ApiError::DatabaseError)?;
+
+    Ok((clicks, applications))
+}
+
+/// Roll promotions up per source and currency; amounts in different currencies are never summed
+pub fn summarize_channels(promotions: &[PromotionPerformance]) -> Vec<ChannelPerformance> {
+    let mut channels: BTreeMap<(String, String), ChannelPerformance> = BTreeMap::new();
+    for item in promotions {
+        let key = (
+            item.promotion.source.clone(),
+            item.promotion.currency.clone(),
+        );
+        let channel = channels
+            .entry(key)
+            .or_insert_with_key(|(source, currency)| ChannelPerformance {
+                source: source.clone(),
+                currency: currency.clone(),
+                promotions: 0,
+                budget: 0.0,
+                spend: 0.0,
+                clicks: 0,
+                applications: 0,
+                cost_per_click: None,
+                cost_per_application: None,
+            });
+        channel.promotions += 1;
+        channel.budget += item.promotion.budget;
+        channel.spend += item.promotion.spend;
+        channel.clicks += item.clicks;
+        channel.applications += item.applications;
+    }
+
+    channels
+        .into_values()
+        .map(|mut channel| {
+            channel.cost_per_click = cost_per(channel.spend, channel.clicks);
+            channel.cost_per_application = cost_per(channel.spend, channel.applications);
+            channel
+        })
+        .collect()
+}
+
+/// Promotions overlapping the requested window, with per-promotion and per-channel results
+pub async fn promotion_report(
+    pool: &SqlitePool,
+    query: &PromotionReportQuery,
+) -> Result<PromotionReport, ApiError> {
+    parse_date(query.from.as_deref(), "from")?;
+    parse_date(query.to.as_deref(), "to")?;
+
+    let promotions = sqlx::query_as::<_, JobPromotion>(
+        r#"
+        SELECT * FROM job_promotions
+        WHERE (? IS NULL OR job_id = ?)
+          AND (? IS NULL OR end_date IS NULL OR end_date >= ?)
+          AND (? IS NULL OR start_date <= ?)
+        ORDER BY start_date DESC, created_at DESC
+        "#,
+    )
+    .bind(&query.job_id)
+    .bind(&query.job_id)
+    .bind(&query.from)
+    .bind(&query.from)
+    .bind(&query.to)
+    .bind(&query.to)
+    .fetch_all(pool)
+    .await
+    .map_err(ApiError::DatabaseError)?;
+
+    let mut results = Vec::with_capacity(promotions.len());
+    for promotion in promotions {
+        let (clicks, applications) = attribute(pool, &promotion).await?;
+        results.push(PromotionPerformance {
+            cost_per_click: cost_per(promotion.spend, clicks),
+            cost_per_application: cost_per(promotion.spend, applications),
+            promotion,
+            clicks,
+            applications,
+        });
+    }
+
+    Ok(PromotionReport {
+        channels: summarize_channels(&results),
+        promotions: results,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn performance(
+        source: &str,
+        currency: &str,
+        spend: f64,
+        clicks: i64,
+        applications: i64,
+    ) -> PromotionPerformance {
+        PromotionPerformance {
+            promotion: JobPromotion {
+                id: format!("PR_{}", source),
+                job_id: "J_1".to_string(),
+                platform: source.to_string(),
+                source: source.to_string(),
+                currency: currency.to_string(),
+                budget: 500.0,
+                spend,
+                start_date: "2026-03-01".to_string(),
+                end_date: None,
+                notes: None,
+                created_by: None,
+                created_at: None,
+                updated_at: None,
+            },
+            clicks,
+            applications,
+            cost_per_click: cost_per(spend, clicks),
+            cost_per_application: cost_per(spend, applications),
+        }
+    }
+
+    #[test]
+    fn test_normalize_source() {
+        assert_eq!(
+            normalize_source(" LinkedIn Ads ").as_deref(),
+            Some("linkedin_ads")
+        );
+        assert_eq!(
+            normalize_source("indeed.com").as_deref(),
+            Some("indeed_com")
+        );
+        assert_eq!(normalize_source("x-promo_2").as_deref(), Some("x-promo_2"));
+        assert_eq!(normalize_source("<script>").as_deref(), Some("script"));
+        assert_eq!(normalize_source(" !! "), None);
+        assert_eq!(normalize_source(&"a".repeat(80)).unwrap().len(), 50);
+    }
+
+    #[test]
+    fn test_validate_promotion() {
+        assert!(validate_promotion(500.0, 120.5, "2026-03-01", Some("2026-03-31")).is_ok());
+        assert!(validate_promotion(500.0, 0.0, "2026-03-01", None).is_ok());
+        assert!(validate_promotion(-1.0, 0.0, "2026-03-01", None).is_err());
+        assert!(validate_promotion(500.0, f64::NAN, "2026-03-01", None).is_err());
+        assert!(validate_promotion(500.0, 0.0, "03/01/2026", None).is_err());
+        assert!(validate_promotion(500.0, 0.0, "2026-03-10", Some("2026-03-01")).is_err());
+    }
+
+    #[test]
+    fn test_cost_per_rounds_and_skips_zero_counts() {
+        assert_eq!(cost_per(100.0, 3), Some(33.33));
+        assert_eq!(cost_per(100.0, 0), None);
+    }
+
+    #[test]
+    fn test_summarize_channels_groups_by_source_and_currency() {
+        let channels = summarize_channels(&[
+            performance("linkedin_ads", "USD", 300.0, 150, 6),
+            performance("linkedin_ads", "USD", 100.0, 50, 2),
+            performance("linkedin_ads", "EUR", 90.0, 30, 0),
+            performance("indeed", "USD", 50.0, 0, 0),
+        ]);
+
+        assert_eq!(channels.len(), 3);
+        assert_eq!(channels[0].source, "indeed");
+        assert_eq!(channels[0].cost_per_application, None);
+
+        let linkedin_eur = &channels[1];
+        assert_eq!(linkedin_eur.currency, "EUR");
+        assert_eq!(linkedin_eur.cost_per_click, Some(3.0));
+
+        let linkedin_usd = &channels[2];
+        assert_eq!(linkedin_usd.promotions, 2);
+        assert_eq!(linkedin_usd.spend, 400.0);
+        assert_eq!(linkedin_usd.clicks, 200);
+        assert_eq!(linkedin_usd.applications, 8);
+        assert_eq!(linkedin_usd.cost_per_click, Some(2.0));
+        assert_eq!(linkedin_usd.cost_per_application, Some(50.0));
+    }
+}
@@ -0,0 +1,135 @@
+// Provider traits for external services
+//
+// Handlers and services send email, store files, generate text and book calendar events
+// through these traits rather than through the AWS, OpenAI and Google services directly.
+// AppState holds one trait object for each, backed by the real service in production and by
+// a fake in tests. Provider-specific features (presigned URLs, connection tests, OAuth) stay
+// on the concrete services.
+
+use async_trait::async_trait;
+use serde_json::Value;
+
+use crate::services::aws::{AWSError, AWSService, EmailAttachment};
+use crate::services::google::{
+    CalendarEvent, CalendarEventResponse, ExternalCalendarEvent, GoogleError, GoogleService,
+};
+use crate::services::openai::{OpenAIError, OpenAIService, TextGenerationPurpose};
+
+/// Sends transactional email
+#[async_trait]
+pub trait EmailSender: Send + Sync + std::fmt::Debug {
+    async fn send_email(
+        &self,
+        to: Vec<String>,
+        subject: &str,
+        body: &str,
+        attachments: Option<Vec<EmailAttachment>>,
+    ) -> Result<(), AWSError>;
+}
+
+/// Stores files under a key
+#[async_trait]
+pub trait ObjectStore: Send + Sync + std::fmt::Debug {
+    /// Store `file_data` under `file_name`, returning the key it was stored under
+    async fn upload_file(
+        &self,
+        file_data: Vec<u8>,
+        file_name: &str,
+        content_type: &str,
+    ) -> Result<String, AWSError>;
+    async fn download_file(&self, key: &str) -> Result<Vec<u8>, AWSError>;
+    async fn delete_file(&self, key: &str) -> Result<(), AWSError>;
+}
+
+/// Generates and moderates text with a language model
+#[async_trait]
+pub trait LlmClient: Send + Sync + std::fmt::Debug {
+    async fn generate_text(
+        &self,
+        purpose: TextGenerationPurpose,
+        prompt: &str,
+        context: Option<Value>,
+    ) -> Result<String, OpenAIError>;
+    /// Categories `input` was flagged for; empty when it is acceptable
+    async fn moderate(&self, input: &str) -> Result<Vec<String>, OpenAIError>;
+}
+
+/// Creates and reads calendar events
+#[async_trait]
+pub trait CalendarClient: Send + Sync + std::fmt::Debug {
+    async fn create_calendar_event(
+        &self,
+        event: CalendarEvent,
+    ) -> Result<CalendarEventResponse, GoogleError>;
+    /// The event as the calendar has it now, or `None` when it was deleted or cancelled
+    async fn get_calendar_event(
+        &self,
+        event_id: &str,
+    ) -> Result<Option<ExternalCalendarEvent>, GoogleError>;
+}
+
+#[async_trait]
+impl EmailSender for AWSService {
+    async fn send_email(
+        &self,
+        to: Vec<String>,
+        subject: &str,
+        body: &str,
+        attachments: Option<Vec<EmailAttachment>>,
+    ) -> Result<(), AWSError> {
+        AWSService::send_email(self, to, subject, body, attachments).await
+    }
+}
+
+#[async_trait]
+impl ObjectStore for AWSService {
+    async fn upload_file(
+        &self,
+        file_data: Vec<u8>,
+        file_name: &str,
+        content_type: &str,
+    ) -> Result<String, AWSError> {
+        AWSService::upload_file(self, file_data, file_name, content_type).await
+    }
+
+    async fn download_file(&self, key: &str) -> Result<Vec<u8>, AWSError> {
+        AWSService::download_file(self, key).await
+    }
+
+    async fn delete_file(&self, key: &str) -> Result<(), AWSError> {
+        AWSService::delete_file(self, key).await
+    }
+}
+
+#[async_trait]
+impl LlmClient for OpenAIService {
+    async fn generate_text(
+        &self,
+        purpose: TextGenerationPurpose,
+        prompt: &str,
+        context: Option<Value>,
+    ) -> Result<String, OpenAIError> {
+        OpenAIService::generate_text(self, purpose, prompt, context).await
+    }
+
+    async fn moderate(&self, input: &str) -> Result<Vec<String>, OpenAIError> {
+        OpenAIService::moderate(self, input).await
+    }
+}
+
+#[async_trait]
+impl CalendarClient for GoogleService {
+    async fn create_calendar_event(
+        &self,
+        event: CalendarEvent,
+    ) -> Result<CalendarEventResponse, GoogleError> {
+        GoogleService::create_calendar_event(self, event).await
+    }
+
+    async fn get_calendar_event(
+        &self,
+        event_id: &str,
+    ) -> Result<Option<ExternalCalendarEvent>, GoogleError> {
+        GoogleService::get_calendar_event(self, event_id).await
+    }
+}
@@ -0,0 +1,358 @@
+// src/services/rejection_feedback.rs
+//! AI-drafted rejection feedback for applications. Each generation adds several phrasings as
+//! versions; an admin activates one, which becomes the body of the rejection email. Versions are
+//! kept so the tone of what candidates were sent can be audited later.
+
+use sqlx::SqlitePool;
+use tracing::{error, info};
+
+use crate::candidates::models::{
+    FeedbackVersion, FeedbackVersionsResponse, GenerateFeedbackRequest,
+};
+use crate::common::{generate_feedback_version_id, ApiError, AppState};
+use crate::services::openai::TextGenerationPurpose;
+use crate::services::sanitize::sanitize_markdown;
+
+/// Versions kept per application; the active one is never pruned
+const MAX_VERSIONS_PER_APPLICATION: i64 = 20;
+
+const DEFAULT_PHRASINGS: usize = 3;
+const MAX_PHRASINGS: usize = 5;
+
+const VERSION_COLUMNS: &str =
+    "id, application_id, content, reason, tone, is_active, version_number, created_by, created_at";
+
+/// What the prompt needs to know about the application
+#[derive(Debug, sqlx::FromRow)]
+struct FeedbackContext {
+    candidate_name: Option<String>,
+    job_title: String,
+    company_name: Option<String>,
+    /// Note recorded with the most recent move to `rejected`
+    rejection_note: Option<String>,
+}
+
+async fn context(pool: &SqlitePool, application_id: &str) -> Result<FeedbackContext, ApiError> {
+    sqlx::query_as::<_, FeedbackContext>(
+        r#"
+        SELECT u.name AS candidate_name,
+               j.title AS job_title,
+               COALESCE(c.name, j.company) AS company_name,
+               (SELECT h.notes FROM application_status_history h
+                WHERE h.application_id = a.id AND h.status = 'rejected'
+                ORDER BY h.changed_at DESC LIMIT 1) AS rejection_note
+        FROM applications a
+        JOIN jobs j ON j.id = a.job_id
+        LEFT JOIN users u ON u.id = a.user_id
+        LEFT JOIN companies c ON c.id = j.company_id
+        WHERE a.id = ?
+        "#,
+    )
+    .bind(application_id)
+    .fetch_optional(pool)
+    .await
+    .map_err(ApiError::DatabaseError)?
+    .ok_or_else(|| ApiError::NotFound(format!("Application {} not found", application_id)))
+}
+
+fn build_prompt(
+    candidate_name: &str,
+    job_title: &str,
+    company_name: &str,
+    reason: Option<&str>,
+    tone: &str,
+    count: usize,
+) -> String {
+    format!(
+        "Write {count} alternative versions of an email telling {candidate_name} that their application \
+        for '{job_title}' at {company_name} will not move forward.\n\
+        Internal reason for the decision: {reason}\n\
+        Tone: {tone}\n\n\
+        Each version must:\n\
+        - Greet {candidate_name} by name and sign off from the {company_name} hiring team\n\
+        - Give honest, constructive feedback based on the reason, without quoting internal notes verbatim\n\
+        - Never mention age, gender, ethnicity, religion, disability, family status or any other protected characteristic\n\
+        - Thank them and, where it fits, encourage them to apply for future roles\n\
+        - Be under 150 words, in markdown with no subject line and no placeholders\n\
+        Vary the wording and structure between versions.\n\n\
+        Return ONLY a JSON array of {count} strings, one per version.",
+        reason = reason.unwrap_or("No specific reason was recorded"),
+    )
+}
+
+/// Split the model's reply into at most `count` phrasings, tolerating prose around the JSON
+fn parse_phrasings(raw: &str, count: usize) -> Vec<String> {
+    let trimmed = raw.trim();
+    let parsed = serde_json::from_str::<Vec<String>>(trimmed)
+        .ok()
+        .or_else(|| {
+            let start = trimmed.find('[')?;
+            let end = trimmed.rfind(']')?;
+            serde_json::from_str::<Vec<String>>(trimmed.get(start..=end)?).ok()
+        });
+
+    // A reply that is not a list is taken as a single phrasing
+    let phrasings = parsed.unwrap_or_else(|| vec![trimmed.to_string()]);
+    phrasings
+        .iter()
+        .map(|p| sanitize_markdown(p.trim()))
+        .filter(|p| !p.is_empty())
+        .take(count)
+        .collect()
+}
+
+async fn fetch_version(
+    pool: &SqlitePool,
+    application_id: &str,
+    version_id: &str,
+) -> Result<FeedbackVersion, ApiError> {
+    sqlx::query_as::<_, FeedbackVersion>(&format!(
+        "SELECT {} FROM application_feedback_versions WHERE id = ? AND application_id = ?",
+        VERSION_COLUMNS
+    ))
+    .bind(version_id)
+    .bind(application_id)
+    .fetch_optional(pool)
+    .await
+    .map_err(ApiError::DatabaseError)?
+    .ok_or_else(|| ApiError::NotFound(format!("Version {} not found", version_id)))
+}
+
+/// The active version and the history of an application's feedback, newest first
+pub async fn list_versions(
+    pool: &SqlitePool,
+    application_id: &str,
+) -> Result<FeedbackVersionsResponse, ApiError> {
+    let history = sqlx::query_as::<_, FeedbackVersion>(&format!(
+        "SELECT {} FROM application_feedback_versions WHERE application_id = ? ORDER BY version_number DESC",
+        VERSION_COLUMNS
+    ))
+    .bind(application_id)
+    .fetch_all(pool)
+    .await
+    .map_err(ApiError::DatabaseError)?;
+
+    let active = history.iter().find(|v| v.is_active == 1).cloned();
+    let total = history.len();
+
+    Ok(FeedbackVersionsResponse {
+        active,
+        history,
+        total,
+    })
+}
+
+/// Draft new phrasings with AI. They are stored inactive until an admin picks one.
+pub async fn generate(
+    state: &AppState,
+    application_id: &str,
+    request: GenerateFeedbackRequest,
+    user_id: &str,
+) -> Result<Vec<FeedbackVersion>, ApiError> {
+    let count = request
+        .count
+        .unwrap_or(DEFAULT_PHRASINGS)
+        .clamp(1, MAX_PHRASINGS);
+    let tone = request
+        .tone
+        .filter(|t| !t.trim().is_empty())
+        .unwrap_or_else(|| "empathetic".to_string());
+
+    let context = context(&state.db, application_id).await?;
+    let reason = request
+        .reason
+        .filter(|r| !r.trim().is_empty())
+        .or(context.rejection_note);
+    let candidate_name = context
+        .candidate_name
+        .unwrap_or_else(|| "the candidate".to_string());
+    let company_name = context
+        .company_name
+        .unwrap_or_else(|| "our company".to_string());
+
+    let prompt = build_prompt(
+        &candidate_name,
+        &context.job_title,
+        &company_name,
+        reason.as_deref(),
+        &tone,
+        count,
+    );
+    let raw = state
+        .llm
+        .generate_text(TextGenerationPurpose::EmailGeneration, &prompt, None)
+        .await
+        .map_err(|e| {
+            error!(error = %e, application_id = %application_id, "Failed to generate rejection feedback");
+            ApiError::ServiceUnavailable(format!("AI service error: {}", e))
+        })?;
+
+    let phrasings = parse_phrasings(&raw, count);
+    if phrasings.is_empty() {
+        return Err(ApiError::ServiceUnavailable(
+            "AI service returned no feedback".to_string(),
+        ));
+    }
+
+    let mut next_version: i32 = sqlx::query_scalar(
+        "SELECT COALESCE(MAX(version_number), 0) + 1 FROM application_feedback_versions WHERE application_id = ?",
+    )
+    .bind(application_id)
+    .fetch_one(&state.db)
+    .await
+    .map_err(ApiError::DatabaseError)?;
+
+    let mut ids = Vec::with_capacity(phrasings.len());
+    for content in &phrasings {
+        let id = generate_feedback_version_id();
+        sqlx::query(
+            r#"
+            INSERT INTO application_feedback_versions (id, application_id, content, reason, tone, is_active, version_number, created_by, created_at)
+            VALUES (?, ?, ?, ?, ?, 0, ?, ?, ?)
+            "#,
+        )
+        .bind(&id)
+        .bind(application_id)
+        .bind(content)
+        .bind(reason.as_deref())
+        .bind(&tone)
+        .bind(next_version)
+        .bind(user_id)
+        .bind(chrono::Utc::now().to_rfc3339())
+        .execute(&state.db)
+        .await
+        .map_err(ApiError::DatabaseError)?;
+        ids.push(id);
+        next_version += 1;
+    }
+
+    sqlx::query(
+        r#"
+        DELETE FROM application_feedback_versions
+        WHERE application_id = ? AND is_active = 0
+        AND id NOT IN (
+            SELECT id FROM application_feedback_versions
+            WHERE application_id = ?
+            ORDER BY version_number DESC
+            LIMIT ?
+        )
+        "#,
+    )
+    .bind(application_id)
+    .bind(application_id)
+    .bind(MAX_VERSIONS_PER_APPLICATION)
+    .execute(&state.db)
+    .await
+    .map_err(ApiError::DatabaseError)?;
+
+    info!(
+        application_id = %application_id,
+        versions = ids.len(),
+        user_id = %user_id,
+        "Generated rejection feedback versions"
+    );
+
+    let mut versions = Vec::with_capacity(ids.len());
+    for id in &ids {
+        versions.push(fetch_version(&state.db, application_id, id).await?);
+    }
+    Ok(versions)
+}
+
+/// Make `version_id` the feedback sent to the candidate
+pub async fn activate(
+    pool: &SqlitePool,
+    application_id: &str,
+    version_id: &str,
+) -> Result<FeedbackVersion, ApiError> {
+    fetch_version(pool, application_id, version_id).await?;
+
+    sqlx::query(
+        "UPDATE application_feedback_versions SET is_active = CASE WHEN id = ? THEN 1 ELSE 0 END WHERE application_id = ?",
+    )
+    .bind(version_id)
+    .bind(application_id)
+    .execute(pool)
+    .await
+    .map_err(ApiError::DatabaseError)?;
+
+    fetch_version(pool, application_id, version_id).await
+}
+
+/// Delete a version that is not active
+pub async fn delete_version(
+    pool: &SqlitePool,
+    application_id: &str,
+    version_id: &str,
+) -> Result<(), ApiError> {
+    let version = fetch_version(pool, application_id, version_id).await?;
+    if version.is_active == 1 {
+        return Err(ApiError::BadRequest(
+            "Cannot delete the active version".to_string(),
+        ));
+    }
+
+    sqlx::query("DELETE FROM application_feedback_versions WHERE id = ?")
+        .bind(version_id)
+        .execute(pool)
+        .await
+        .map_err(ApiError::DatabaseError)?;
+
+    Ok(())
+}
+
+/// The chosen feedback for an application, if an admin has activated one
+pub async fn active_feedback(
+    pool: &SqlitePool,
+    application_id: &str,
+) -> Result<Option<String>, sqlx::Error> {
+    sqlx::query_scalar(
+        "SELECT content FROM application_feedback_versions WHERE application_id = ? AND is_active = 1",
+    )
+    .bind(application_id)
+    .fetch_optional(pool)
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_phrasings_from_json_with_surrounding_prose() {
+        let raw = "Here you go:\n[\"Hi Ann, thank you...\", \"  \", \"Dear Ann, ...\", \"Hello Ann\"]\nGood luck!";
+        assert_eq!(
+            parse_phrasings(raw, 2),
+            vec![
+                "Hi Ann, thank you...".to_string(),
+                "Dear Ann, ...".to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_phrasings_falls_back_to_whole_reply() {
+        assert_eq!(
+            parse_phrasings("  Hi Ann, thanks for applying.  ", 3),
+            vec!["Hi Ann, thanks for applying.".to_string()]
+        );
+        assert!(parse_phrasings("   ", 3).is_empty());
+    }
+
+    #[test]
+    fn test_prompt_includes_reason_or_says_none() {
+        let with_reason = build_prompt(
+            "Ann",
+            "Rust Dev",
+            "Acme",
+            Some("Needs more Rust"),
+            "warm",
+            3,
+        );
+        assert!(with_reason.contains("Needs more Rust"));
+        assert!(with_reason.contains("JSON array of 3 strings"));
+
+        let without = build_prompt("Ann", "Rust Dev", "Acme", None, "warm", 1);
+        assert!(without.contains("No specific reason was recorded"));
+    }
+}
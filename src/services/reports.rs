@@ -0,0 +1,844 @@
+// src/services/reports.rs
+//! Admin report builder
+//!
+//! A report picks an entity, the dimensions to group by, the metrics to compute, filters and
+//! a date range. Every column and aggregate comes from the fixed [`CATALOG`] below and every
+//! value the caller supplies is bound as a parameter, so a definition can only ever compile
+//! to one of the queries the catalog allows. Saved reports can be emailed as CSV daily or
+//! weekly.
+
+use chrono::{DateTime, Datelike, Timelike, Utc};
+use serde::{Deserialize, Serialize, Serializer};
+use sqlx::{Row, SqlitePool};
+use std::sync::Arc;
+use tracing::{debug, error, info};
+
+use crate::admin::models::SavedReport;
+use crate::common::{parse_date, ApiError};
+use crate::services::aws::EmailAttachment;
+use crate::services::{EmailSender, SettingsService};
+use crate::services::text::{csv_field, escape_html};
+
+pub const REPORT_SCHEDULES: &[&str] = &["daily", "weekly"];
+
+pub const FILTER_OPS: &[&str] = &["eq", "ne", "gt", "gte", "lt", "lte", "in", "contains"];
+
+const DEFAULT_ROW_LIMIT: i64 = 500;
+const MAX_ROW_LIMIT: i64 = 5000;
+const MAX_DIMENSIONS: usize = 4;
+const MAX_METRICS: usize = 8;
+const MAX_FILTERS: usize = 20;
+
+/// Rows shown in the body of a scheduled email; the attached CSV has all of them
+const EMAIL_PREVIEW_ROWS: usize = 20;
+
+const SCHEDULE_CHECK_INTERVAL_SECS: u64 = 15 * 60;
+
+/// A column a report can group and filter by
+#[derive(Debug, Serialize)]
+pub struct Dimension {
+    pub name: &'static str,
+    #[serde(skip)]
+    sql: &'static str,
+}
+
+/// An aggregate a report can compute
+#[derive(Debug, Serialize)]
+pub struct Metric {
+    pub name: &'static str,
+    #[serde(skip)]
+    sql: &'static str,
+    /// Counts and sums come back as integers, averages as decimals
+    #[serde(skip)]
+    integer: bool,
+}
+
+/// Something a report can be run over
+#[derive(Debug, Serialize)]
+pub struct Entity {
+    pub name: &'static str,
+    pub description: &'static str,
+    /// The date range applies to this column
+    pub date_field: &'static str,
+    pub dimensions: &'static [Dimension],
+    pub metrics: &'static [Metric],
+    #[serde(skip)]
+    from: &'static str,
+    #[serde(skip)]
+    scope: Option<&'static str>,
+    #[serde(skip)]
+    date_sql: &'static str,
+}
+
+const fn dim(name: &'static str, sql: &'static str) -> Dimension {
+    Dimension { name, sql }
+}
+
+const fn count(name: &'static str, sql: &'static str) -> Metric {
+    Metric {
+        name,
+        sql,
+        integer: true,
+    }
+}
+
+const fn avg(name: &'static str, sql: &'static str) -> Metric {
+    Metric {
+        name,
+        sql,
+        integer: false,
+    }
+}
+
+/// Everything reports can see. Offer salaries and candidate contact details are encrypted
+/// or personal, so they are deliberately absent.
+pub static CATALOG: &[Entity] = &[
+    Entity {
+        name: "applications",
+        description: "Applications, with their job and department",
+        date_field: "applied_at",
+        from: "applications a JOIN jobs j ON j.id = a.job_id LEFT JOIN departments d ON d.id = j.department_id",
+        scope: None,
+        date_sql: "a.applied_at",
+        dimensions: &[
+            dim("status", "a.status"),
+            dim("stage", "a.current_stage"),
+            dim("source", "COALESCE(a.source, 'direct')"),
+            dim("job_id", "a.job_id"),
+            dim("job_title", "j.title"),
+            dim("company", "j.company"),
+            dim("department", "d.name"),
+            dim("applied_day", "date(a.applied_at)"),
+            dim("applied_week", "strftime('%Y-W%W', a.applied_at)"),
+            dim("applied_month", "strftime('%Y-%m', a.applied_at)"),
+        ],
+        metrics: &[
+            count("count", "COUNT(*)"),
+            count("candidates", "COUNT(DISTINCT a.user_id)"),
+            count("hired", "SUM(a.status = 'hired')"),
+            count("rejected", "SUM(a.status = 'rejected')"),
+            count("withdrawn", "SUM(a.status = 'withdrawn')"),
+            avg("avg_experience_years", "AVG(a.experience_years)"),
+        ],
+    },
+    Entity {
+        name: "jobs",
+        description: "Job postings, excluding those in the trash",
+        date_field: "created_at",
+        from: "jobs j LEFT JOIN departments d ON d.id = j.department_id",
+        scope: Some("j.deleted_at IS NULL"),
+        date_sql: "j.created_at",
+        dimensions: &[
+            dim("status", "j.status"),
+            dim("job_type", "j.job_type"),
+            dim("experience_level", "j.experience_level"),
+            dim("location", "j.location"),
+            dim("company", "j.company"),
+            dim("department", "d.name"),
+            dim("created_month", "strftime('%Y-%m', j.created_at)"),
+        ],
+        metrics: &[
+            count("count", "COUNT(*)"),
+            count("featured", "SUM(j.is_featured = 1)"),
+            avg("avg_salary_min", "AVG(j.salary_min)"),
+            avg("avg_salary_max", "AVG(j.salary_max)"),
+        ],
+    },
+    Entity {
+        name: "interviews",
+        description: "Scheduled interviews, with their job",
+        date_field: "scheduled_date",
+        from: "interviews i LEFT JOIN jobs j ON j.id = i.job_id",
+        scope: None,
+        date_sql: "i.scheduled_date",
+        dimensions: &[
+            dim("status", "i.status"),
+            dim("interview_type", "i.interview_type"),
+            dim("job_id", "i.job_id"),
+            dim("job_title", "j.title"),
+            dim("scheduled_day", "date(i.scheduled_date)"),
+            dim("scheduled_month", "strftime('%Y-%m', i.scheduled_date)"),
+        ],
+        metrics: &[
+            count("count", "COUNT(*)"),
+            count("candidates", "COUNT(DISTINCT i.candidate_id)"),
+            count("total_minutes", "SUM(i.duration_minutes)"),
+            avg("avg_duration_minutes", "AVG(i.duration_minutes)"),
+        ],
+    },
+    Entity {
+        name: "offers",
+        description: "Offer letters generated for candidates",
+        date_field: "created_at",
+        from: "offer_letters o",
+        scope: None,
+        date_sql: "o.created_at",
+        dimensions: &[
+            dim("job_id", "o.job_id"),
+            dim("job_title", "o.job_title"),
+            dim(
+                "sent",
+                "CASE WHEN o.sent_at IS NULL THEN 'no' ELSE 'yes' END",
+            ),
+            dim("created_month", "strftime('%Y-%m', o.created_at)"),
+        ],
+        metrics: &[
+            count("count", "COUNT(*)"),
+            count("candidates", "COUNT(DISTINCT o.candidate_id)"),
+        ],
+    },
+];
+
+/// One filter condition; `in` takes an array of values, the other operators one value
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReportFilter {
+    pub field: String,
+    #[serde(default = "default_op")]
+    pub op: String,
+    pub value: serde_json::Value,
+}
+
+fn default_op() -> String {
+    "eq".to_string()
+}
+
+/// What to report on, as sent by the admin UI and stored with saved reports
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReportDefinition {
+    pub entity: String,
+    #[serde(default)]
+    pub dimensions: Vec<String>,
+    /// Defaults to `count`
+    #[serde(default)]
+    pub metrics: Vec<String>,
+    #[serde(default)]
+    pub filters: Vec<ReportFilter>,
+    /// Inclusive, YYYY-MM-DD
+    pub date_from: Option<String>,
+    /// Inclusive, YYYY-MM-DD
+    pub date_to: Option<String>,
+    /// A selected column to sort by, descending when prefixed with `-`; defaults to the
+    /// dimensions in order
+    pub sort: Option<String>,
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum BindValue {
+    Text(String),
+    Number(f64),
+}
+
+/// A definition checked against the catalog and turned into SQL
+#[derive(Debug)]
+pub struct CompiledReport {
+    pub sql: String,
+    pub columns: Vec<String>,
+    binds: Vec<BindValue>,
+    /// Whether each metric column is an integer, in column order after the dimensions
+    integer_metrics: Vec<bool>,
+    dimension_count: usize,
+    limit: i64,
+}
+
+/// Report output: one row per group, values in column order
+#[derive(Debug, Serialize)]
+pub struct ReportResult {
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<serde_json::Value>>,
+    /// More rows matched than the limit allowed
+    pub truncated: bool,
+}
+
+fn find_entity(name: &str) -> Result<&'static Entity, ApiError> {
+    CATALOG.iter().find(|e| e.name == name).ok_or_else(|| {
+        ApiError::ValidationError(format!(
+            "Unknown entity '{}'; expected one of: {}",
+            name,
+            CATALOG
+                .iter()
+                .map(|e| e.name)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ))
+    })
+}
+
+fn find_dimension(entity: &'static Entity, name: &str) -> Result<&'static Dimension, ApiError> {
+    entity
+        .dimensions
+        .iter()
+        .find(|d| d.name == name)
+        .ok_or_else(|| {
+            ApiError::ValidationError(format!("Unknown field '{}' for {}", name, entity.name))
+        })
+}
+
+fn bind_value(field: &str, value: &serde_json::Value) -> Result<BindValue, ApiError> {
+    match value {
+        serde_json::Value::String(s) => Ok(BindValue::Text(s.clone())),
+        serde_json::Value::Number(n) => n.as_f64().map(BindValue::Number).ok_or_else(|| {
+            ApiError::ValidationError(format!("Filter value for '{}' is out of range", field))
+        }),
+        serde_json::Value::Bool(b) => Ok(BindValue::Number(if *b { 1.0 } else { 0.0 })),
+        _ => Err(ApiError::ValidationError(format!(
+            "Filter value for '{}' must be a string, number or boolean",
+            field
+        ))),
+    }
+}
+
+/// `%` and `_` in a `contains` value match themselves
+fn escape_like(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
+
+/// Check a definition against the catalog and build its query
+pub fn compile(definition: &ReportDefinition) -> Result<CompiledReport, ApiError> {
+    let entity = find_entity(definition.entity.trim())?;
+
+    if definition.dimensions.len() > MAX_DIMENSIONS {
+        return Err(ApiError::ValidationError(format!(
+            "A report can group by at most {} dimensions",
+            MAX_DIMENSIONS
+        )));
+    }
+    if definition.metrics.len() > MAX_METRICS {
+        return Err(ApiError::ValidationError(format!(
+            "A report can compute at most {} metrics",
+            MAX_METRICS
+        )));
+    }
+    if definition.filters.len() > MAX_FILTERS {
+        return Err(ApiError::ValidationError(format!(
+            "A report can have at most {} filters",
+            MAX_FILTERS
+        )));
+    }
+
+    let mut columns: Vec<String> = Vec::new();
+    let mut select: Vec<String> = Vec::new();
+    for name in &definition.dimensions {
+        let dimension = find_dimension(entity, name.trim())?;
+        if columns.iter().any(|c| c == dimension.name) {
+            return Err(ApiError::ValidationError(format!(
+                "Dimension '{}' is listed twice",
+                dimension.name
+            )));
+        }
+        select.push(format!("{} AS \"{}\"", dimension.sql, dimension.name));
+        columns.push(dimension.name.to_string());
+    }
+    let dimension_count = columns.len();
+
+    let metric_names: Vec<&str> = if definition.metrics.is_empty() {
+        vec!["count"]
+    } else {
+        definition.metrics.iter().map(|m| m.trim()).collect()
+    };
+    let mut integer_metrics = Vec::new();
+    for name in metric_names {
+        let metric = entity
+            .metrics
+            .iter()
+            .find(|m| m.name == name)
+            .ok_or_else(|| {
+                ApiError::ValidationError(format!("Unknown metric '{}' for {}", name, entity.name))
+            })?;
+        if columns.iter().any(|c| c == metric.name) {
+            return Err(ApiError::ValidationError(format!(
+                "Metric '{}' is listed twice",
+                metric.name
+            )));
+        }
+        select.push(format!("{} AS \"{}\"", metric.sql, metric.name));
+        columns.push(metric.name.to_string());
+        integer_metrics.push(metric.integer);
+    }
+
+    let mut conditions: Vec<String> = entity.scope.iter().map(|s| s.to_string()).collect();
+    let mut binds = Vec::new();
+    if let Some(from) = parse_date(definition.date_from.as_deref(), "date_from")? {
+        conditions.push(format!("date({}) >= ?", entity.date_sql));
+        binds.push(BindValue::Text(from.format("%Y-%m-%d").to_string()));
+    }
+    if let Some(to) = parse_date(definition.date_to.as_deref(), "date_to")? {
+        conditions.push(format!("date({}) <= ?", entity.date_sql));
+        binds.push(BindValue::Text(to.format("%Y-%m-%d").to_string()));
+    }
+
+    for filter in &definition.filters {
+        let dimension = find_dimension(entity, filter.field.trim())?;
+        let op = filter.op.trim().to_lowercase();
+        let comparison = match op.as_str() {
+            "eq" => "=",
+            "ne" => "!=",
+            "gt" => ">",
+            "gte" => ">=",
+            "lt" => "<",
+            "lte" => "<=",
+            "in" => {
+                let values = filter
+                    .value
+                    .as_array()
+                    .filter(|values| !values.is_empty())
+                    .ok_or_else(|| {
+                        ApiError::ValidationError(format!(
+                            "Filter 'in' on '{}' needs a non-empty array of values",
+                            dimension.name
+                        ))
+                    })?;
+                for value in values {
+                    binds.push(bind_value(dimension.name, value)?);
+                }
+                conditions.push(format!(
+                    "{} IN ({})",
+                    dimension.sql,
+                    vec!["?"; values.len()].join(", ")
+                ));
+                continue;
+            }
+            "contains" => {
+                let text = filter.value.as_str().ok_or_else(|| {
+                    ApiError::ValidationError(format!(
+                        "Filter 'contains' on '{}' needs a string value",
+                        dimension.name
+                    ))
+                })?;
+                conditions.push(format!("{} LIKE ? ESCAPE '\\'", dimension.sql));
+                binds.push(BindValue::Text(format!("%{}%", escape_like(text))));
+                continue;
+            }
+            _ => {
+                return Err(ApiError::ValidationError(format!(
+                    "Unknown filter operator '{}'; expected one of: {}",
+                    filter.op,
+                    FILTER_OPS.join(", ")
+                )))
+            }
+        };
+        conditions.push(format!("{} {} ?", dimension.sql, comparison));
+        binds.push(bind_value(dimension.name, &filter.value)?);
+    }
+
+    let mut sql = format!("SELECT {} FROM {}", select.join(", "), entity.from);
+    if !conditions.is_empty() {
+        sql.push_str(&format!(" WHERE {}", conditions.join(" AND ")));
+    }
+    if dimension_count > 0 {
+        let groups: Vec<String> = (1..=dimension_count).map(|i| i.to_string()).collect();
+        sql.push_str(&format!(" GROUP BY {}", groups.join(", ")));
+    }
+
+    match definition
+        .sort
+        .as_deref()
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+    {
+        Some(sort) => {
+            let (name, direction) = match sort.strip_prefix('-') {
+                Some(name) => (name, "DESC"),
+                None => (sort, "ASC"),
+            };
+            let position = columns.iter().position(|c| c == name).ok_or_else(|| {
+                ApiError::ValidationError(format!(
+                    "Can only sort by a selected dimension or metric, not '{}'",
+                    name
+                ))
+            })?;
+            sql.push_str(&format!(" ORDER BY {} {}", position + 1, direction));
+        }
+        None if dimension_count > 0 => {
+            let order: Vec<String> = (1..=dimension_count).map(|i| i.to_string()).collect();
+            sql.push_str(&format!(" ORDER BY {}", order.join(", ")));
+        }
+        None => {}
+    }
+
+    let limit = definition
+        .limit
+        .unwrap_or(DEFAULT_ROW_LIMIT)
+        .clamp(1, MAX_ROW_LIMIT);
+    sql.push_str(" LIMIT ?");
+
+    Ok(CompiledReport {
+        sql,
+        columns,
+        binds,
+        integer_metrics,
+        dimension_count,
+        limit,
+    })
+}
+
+/// Compile and run a definition
+pub async fn run(
+    pool: &SqlitePool,
+    definition: &ReportDefinition,
+) -> Result<ReportResult, ApiError> {
+    let report = compile(definition)?;
+
+    let mut query = sqlx::query(&report.sql);
+    for bind in &report.binds {
+        query = match bind {
+            BindValue::Text(text) => query.bind(text.clone()),
+            BindValue::Number(number) => query.bind(*number),
+        };
+    }
+    // One extra row tells us the result was cut off
+    let fetched = query
+        .bind(report.limit + 1)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| {
+            error!(error = %e, entity = %definition.entity, "Database error running report");
+            ApiError::DatabaseError(e)
+        })?;
+
+    let truncated = fetched.len() as i64 > report.limit;
+    let mut rows = Vec::with_capacity(fetched.len());
+    for row in fetched.iter().take(report.limit as usize) {
+        let mut values = Vec::with_capacity(report.columns.len());
+        for i in 0..report.dimension_count {
+            let value: Option<String> = row.try_get(i).map_err(ApiError::DatabaseError)?;
+            values.push(value.map_or(serde_json::Value::Null, serde_json::Value::from));
+        }
+        for (offset, integer) in report.integer_metrics.iter().enumerate() {
+            let i = report.dimension_count + offset;
+            let value = if *integer {
+                let value: Option<i64> = row.try_get(i).map_err(ApiError::DatabaseError)?;
+                value.map(serde_json::Value::from)
+            } else {
+                let value: Option<f64> = row.try_get(i).map_err(ApiError::DatabaseError)?;
+                value.map(|v| serde_json::Value::from((v * 100.0).round() / 100.0))
+            };
+            values.push(value.unwrap_or(serde_json::Value::Null));
+        }
+        rows.push(values);
+    }
+
+    Ok(ReportResult {
+        columns: report.columns,
+        rows,
+        truncated,
+    })
+}
+
+fn cell_text(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::Null => String::new(),
+        serde_json::Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// A report result as CSV with a header row
+pub fn to_csv(result: &ReportResult) -> String {
+    let mut csv = result
+        .columns
+        .iter()
+        .map(|c| csv_field(c))
+        .collect::<Vec<_>>()
+        .join(",");
+    csv.push('\n');
+    for row in &result.rows {
+        let line: Vec<String> = row.iter().map(|v| csv_field(&cell_text(v))).collect();
+        csv.push_str(&line.join(","));
+        csv.push('\n');
+    }
+    csv
+}
+
+fn email_body(report: &SavedReport, result: &ReportResult) -> String {
+    let header: String = result
+        .columns
+        .iter()
+        .map(|c| {
+            format!(
+                "<th style=\"text-align: left; padding: 4px 8px;\">{}</th>",
+                escape_html(c)
+            )
+        })
+        .collect();
+    let rows: String = result
+        .rows
+        .iter()
+        .take(EMAIL_PREVIEW_ROWS)
+        .map(|row| {
+            let cells: String = row
+                .iter()
+                .map(|v| {
+                    format!(
+                        "<td style=\"padding: 4px 8px;\">{}</td>",
+                        escape_html(&cell_text(v))
+                    )
+                })
+                .collect();
+            format!("<tr>{}</tr>", cells)
+        })
+        .collect();
+    let more = if result.rows.len() > EMAIL_PREVIEW_ROWS || result.truncated {
+        "<p>The attached CSV has the full report.</p>"
+    } else {
+        ""
+    };
+    let description = report
+        .description
+        .as_deref()
+        .map(|d| format!("<p>{}</p>", escape_html(d)))
+        .unwrap_or_default();
+    format!(
+        r#"<html><body style="font-family: Arial, sans-serif; line-height: 1.6; color: #333;">
+<div style="max-width: 800px; margin: 0 auto; padding: 20px;">
+<h2>{}</h2>
+{}
+<table style="border-collapse: collapse;"><thead><tr>{}</tr></thead><tbody>{}</tbody></table>
+{}
+<p>Change or stop this report under Admin &rarr; Reports.</p>
+</div></body></html>"#,
+        escape_html(&report.name),
+        description,
+        header,
+        rows,
+        more
+    )
+}
+
+/// Whether a scheduled report should go out now: at or past its hour on a scheduled day,
+/// and not already sent today
+pub fn report_due(report: &SavedReport, now: DateTime<Utc>) -> bool {
+    let on_schedule_day = match report.schedule.as_deref() {
+        Some("daily") => true,
+        Some("weekly") => {
+            i64::from(now.weekday().num_days_from_monday()) == report.schedule_weekday.unwrap_or(0)
+        }
+        _ => false,
+    };
+    if !on_schedule_day || i64::from(now.hour()) < report.schedule_hour {
+        return false;
+    }
+    let today = now.format("%Y-%m-%d").to_string();
+    !report
+        .last_sent_at
+        .as_deref()
+        .is_some_and(|last| last.starts_with(&today))
+}
+
+pub fn parse_recipients(recipients: Option<&str>) -> Vec<String> {
+    crate::services::org::merge_recipients(recipients, &[])
+}
+
+/// Run a saved report and email it, with the full result attached as CSV
+pub async fn deliver(
+    pool: &SqlitePool,
+    email_sender: &dyn EmailSender,
+    report: &SavedReport,
+) -> Result<ReportResult, ApiError> {
+    let recipients = parse_recipients(report.recipients.as_deref());
+    if recipients.is_empty() {
+        return Err(ApiError::ValidationError(
+            "This report has no recipients".to_string(),
+        ));
+    }
+    let definition: ReportDefinition = serde_json::from_str(&report.definition).map_err(|e| {
+        error!(error = %e, report_id = %report.id, "Stored report definition is unreadable");
+        ApiError::InternalServer("Stored report definition is unreadable".to_string())
+    })?;
+    let result = run(pool, &definition).await?;
+
+    let now = Utc::now();
+    let subject = format!("{} ({})", report.name, now.format("%B %-d"));
+    let attachment = EmailAttachment {
+        filename: format!("{}_{}.csv", report.id.to_lowercase(), now.format("%Y%m%d")),
+        content: to_csv(&result).into_bytes(),
+        content_type: "text/csv".to_string(),
+    };
+    email_sender
+        .send_email(
+            recipients,
+            &subject,
+            &email_body(report, &result),
+            Some(vec![attachment]),
+        )
+        .await
+        .map_err(|e| {
+            error!(error = %e, report_id = %report.id, "Failed to send scheduled report");
+            ApiError::ServiceUnavailable("Failed to send report email".to_string())
+        })?;
+
+    sqlx::query("UPDATE saved_reports SET last_sent_at = ? WHERE id = ?")
+        .bind(now.format("%Y-%m-%d %H:%M:%S").to_string())
+        .bind(&report.id)
+        .execute(pool)
+        .await
+        .map_err(ApiError::DatabaseError)?;
+
+    Ok(result)
+}
+
+/// Email every scheduled report that is due; returns how many were sent
+pub async fn send_due_reports(
+    pool: &SqlitePool,
+    email_sender: &dyn EmailSender,
+) -> Result<usize, sqlx::Error> {
+    let now = Utc::now();
+    let reports = sqlx::query_as::<_, SavedReport>(
+        "SELECT * FROM saved_reports WHERE schedule IS NOT NULL AND recipients IS NOT NULL",
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let mut sent = 0;
+    for report in reports.iter().filter(|r| report_due(r, now)) {
+        match deliver(pool, email_sender, report).await {
+            Ok(_) => sent += 1,
+            Err(e) => debug!(error = %e, report_id = %report.id, "Skipped scheduled report"),
+        }
+    }
+    Ok(sent)
+}
+
+/// Send due reports every 15 minutes unless `scheduled_reports_enabled` is `false`
+pub fn start_report_schedule_task(
+    pool: SqlitePool,
+    settings_service: Arc<SettingsService>,
+    email_sender: Arc<dyn EmailSender>,
+) {
+    tokio::spawn(async move {
+        loop {
+            if !crate::common::shutdown::sleep(std::time::Duration::from_secs(
+                SCHEDULE_CHECK_INTERVAL_SECS,
+            ))
+            .await
+            {
+                break;
+            }
+            let _running = crate::common::shutdown::track();
+
+            let enabled = settings_service
+                .get_setting("scheduled_reports_enabled")
+                .await
+                .ok()
+                .flatten()
+                .map(|v| v != "false")
+                .unwrap_or(true);
+            if !enabled {
+                continue;
+            }
+
+            match send_due_reports(&pool, email_sender.as_ref()).await {
+                Ok(0) => {}
+                Ok(sent) => info!(sent, "Sent scheduled reports"),
+                Err(e) => debug!(error = %e, "Skipped scheduled report run"),
+            }
+        }
+    });
+}
+
+/// Saved definitions are stored as JSON text and returned as JSON
+pub fn definition_json<S: Serializer>(value: &str, serializer: S) -> Result<S::Ok, S::Error> {
+    serde_json::from_str::<serde_json::Value>(value)
+        .unwrap_or(serde_json::Value::Null)
+        .serialize(serializer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn definition(value: serde_json::Value) -> ReportDefinition {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn test_compile_binds_every_value() {
+        let report = compile(&definition(serde_json::json!({
+            "entity": "applications",
+            "dimensions": ["status", "applied_month"],
+            "metrics": ["count", "hired"],
+            "filters": [
+                {"field": "source", "op": "in", "value": ["linkedin", "referral"]},
+                {"field": "job_title", "op": "contains", "value": "50%'; DROP TABLE jobs; --"}
+            ],
+            "date_from": "2026-01-01",
+            "sort": "-count"
+        })))
+        .unwrap();
+
+        assert_eq!(
+            report.columns,
+            vec!["status", "applied_month", "count", "hired"]
+        );
+        assert!(!report.sql.contains("DROP"));
+        assert!(report.sql.contains("GROUP BY 1, 2"));
+        assert!(report.sql.contains("ORDER BY 3 DESC"));
+        assert_eq!(
+            report.binds,
+            vec![
+                BindValue::Text("2026-01-01".to_string()),
+                BindValue::Text("linkedin".to_string()),
+                BindValue::Text("referral".to_string()),
+                BindValue::Text("%50\\%'; DROP TABLE jobs; --%".to_string()),
+            ]
+        );
+        assert_eq!(report.limit, DEFAULT_ROW_LIMIT);
+    }
+
+    #[test]
+    fn test_compile_rejects_anything_outside_the_catalog() {
+        let cases = [
+            serde_json::json!({"entity": "users"}),
+            serde_json::json!({"entity": "jobs", "dimensions": ["title; DROP TABLE jobs"]}),
+            serde_json::json!({"entity": "jobs", "metrics": ["SUM(salary)"]}),
+            serde_json::json!({"entity": "jobs", "filters": [{"field": "status", "op": "like", "value": "x"}]}),
+            serde_json::json!({"entity": "jobs", "filters": [{"field": "status", "op": "in", "value": "x"}]}),
+            serde_json::json!({"entity": "jobs", "date_to": "yesterday"}),
+            serde_json::json!({"entity": "jobs", "dimensions": ["status"], "sort": "location"}),
+            serde_json::json!({"entity": "jobs", "dimensions": ["status", "status"]}),
+        ];
+        for case in cases {
+            assert!(compile(&definition(case.clone())).is_err(), "{}", case);
+        }
+    }
+
+    #[test]
+    fn test_report_due_follows_schedule() {
+        let mut report = SavedReport {
+            id: "RP_1".to_string(),
+            name: "Weekly applications".to_string(),
+            description: None,
+            definition: "{}".to_string(),
+            schedule: Some("weekly".to_string()),
+            schedule_hour: 8,
+            schedule_weekday: Some(0),
+            recipients: Some("ops@example.com".to_string()),
+            last_sent_at: None,
+            created_by: None,
+            created_at: None,
+            updated_at: None,
+        };
+        // 2026-03-02 is a Monday
+        let monday = Utc.with_ymd_and_hms(2026, 3, 2, 9, 0, 0).unwrap();
+        assert!(report_due(&report, monday));
+        assert!(!report_due(
+            &report,
+            Utc.with_ymd_and_hms(2026, 3, 2, 7, 0, 0).unwrap()
+        ));
+        assert!(!report_due(
+            &report,
+            Utc.with_ymd_and_hms(2026, 3, 3, 9, 0, 0).unwrap()
+        ));
+
+        report.last_sent_at = Some("2026-03-02 08:00:00".to_string());
+        assert!(!report_due(&report, monday));
+
+        report.schedule = None;
+        report.last_sent_at = None;
+        assert!(!report_due(&report, monday));
+    }
+}
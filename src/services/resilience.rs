@@ -0,0 +1,422 @@
+// src/services/resilience.rs
+//
+// Timeouts, retries and circuit breakers for external dependencies
+//
+// Every call the AWS, OpenAI and Google services make goes through `call` (or `call_once` for
+// requests that must not be repeated, like sending an email). Each attempt is bounded by the
+// dependency's timeout, transient failures are retried with jittered exponential backoff, and
+// a circuit breaker per dependency stops calling a dependency that keeps failing: after
+// FAILURE_THRESHOLD consecutive failures it opens and calls fail fast with `unavailable` for
+// OPEN_FOR, then a single trial call is let through and its outcome closes or reopens it.
+// Breaker state is reported by the admin system health endpoint.
+
+use chrono::{DateTime, Utc};
+use rand::Rng;
+use serde::Serialize;
+use std::fmt::Display;
+use std::future::Future;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+use tracing::warn;
+
+/// Consecutive failures that open a breaker
+const FAILURE_THRESHOLD: u32 = 5;
+
+/// How long an open breaker rejects calls before letting a trial call through
+const OPEN_FOR: Duration = Duration::from_secs(30);
+
+/// An external service the app depends on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Dependency {
+    OpenAI,
+    Ses,
+    S3,
+    Google,
+}
+
+impl Dependency {
+    pub const ALL: [Dependency; 4] = [
+        Dependency::OpenAI,
+        Dependency::Ses,
+        Dependency::S3,
+        Dependency::Google,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Dependency::OpenAI => "openai",
+            Dependency::Ses => "ses",
+            Dependency::S3 => "s3",
+            Dependency::Google => "google",
+        }
+    }
+
+    fn policy(&self) -> Policy {
+        match self {
+            // Long generations legitimately take minutes
+            Dependency::OpenAI => Policy {
+                timeout: Duration::from_secs(180),
+                max_attempts: 3,
+                base_delay: Duration::from_secs(1),
+            },
+            Dependency::Ses => Policy {
+                timeout: Duration::from_secs(15),
+                max_attempts: 3,
+                base_delay: Duration::from_millis(500),
+            },
+            // Uploads of recordings and videos run to 100MB
+            Dependency::S3 => Policy {
+                timeout: Duration::from_secs(120),
+                max_attempts: 3,
+                base_delay: Duration::from_millis(200),
+            },
+            Dependency::Google => Policy {
+                timeout: Duration::from_secs(30),
+                max_attempts: 3,
+                base_delay: Duration::from_millis(500),
+            },
+        }
+    }
+
+    fn breaker(&self) -> &'static CircuitBreaker {
+        static BREAKERS: OnceLock<Vec<CircuitBreaker>> = OnceLock::new();
+        let breakers = BREAKERS.get_or_init(|| {
+            Dependency::ALL
+                .iter()
+                .map(|_| CircuitBreaker::new(FAILURE_THRESHOLD, OPEN_FOR))
+                .collect()
+        });
+        let index = Dependency::ALL.iter().position(|d| d == self).unwrap_or(0);
+        &breakers[index]
+    }
+}
+
+/// Errors returned by a dependency's client
+pub trait ExternalError: Display {
+    /// Whether the failure says the dependency is struggling (a timeout, a dropped connection,
+    /// a 5xx or 429) rather than that the request itself was wrong or unconfigured. Only
+    /// transient failures are retried and count against the breaker.
+    fn is_transient(&self) -> bool;
+
+    /// The error for a call that timed out or was refused by an open breaker
+    fn unavailable(message: String) -> Self;
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Policy {
+    timeout: Duration,
+    max_attempts: u32,
+    base_delay: Duration,
+}
+
+impl Policy {
+    /// Full jitter: a random delay up to `base_delay * 2^(attempt - 1)`
+    fn backoff(&self, attempt: u32) -> Duration {
+        let cap = self
+            .base_delay
+            .saturating_mul(2u32.saturating_pow(attempt - 1));
+        let millis = cap.as_millis() as u64;
+        Duration::from_millis(rand::thread_rng().gen_range(0..=millis))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BreakerState {
+    Closed,
+    Open,
+    HalfOpen,
+}
+
+#[derive(Debug, Default)]
+struct BreakerInner {
+    consecutive_failures: u32,
+    /// When the breaker opened, or when the latest trial call was let through
+    opened_at: Option<Instant>,
+    half_open: bool,
+    last_failure: Option<String>,
+    last_failure_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug)]
+struct CircuitBreaker {
+    failure_threshold: u32,
+    open_for: Duration,
+    inner: Mutex<BreakerInner>,
+}
+
+impl CircuitBreaker {
+    fn new(failure_threshold: u32, open_for: Duration) -> Self {
+        Self {
+            failure_threshold,
+            open_for,
+            inner: Mutex::new(BreakerInner::default()),
+        }
+    }
+
+    /// Whether a call may go ahead. Once an open breaker's wait is over, one caller gets
+    /// through as the trial and the wait restarts, so a trial that never reports back only
+    /// holds things up for another `open_for`.
+    fn allow(&self) -> bool {
+        let mut inner = self.inner.lock().unwrap();
+        match inner.opened_at {
+            None => true,
+            Some(opened_at) if opened_at.elapsed() >= self.open_for => {
+                inner.opened_at = Some(Instant::now());
+                inner.half_open = true;
+                true
+            }
+            Some(_) => false,
+        }
+    }
+
+    fn record_success(&self) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.consecutive_failures = 0;
+        inner.opened_at = None;
+        inner.half_open = false;
+    }
+
+    /// Record a failure, returning true when it opened the breaker
+    fn record_failure(&self, error: String) -> bool {
+        let mut inner = self.inner.lock().unwrap();
+        inner.consecutive_failures += 1;
+        inner.last_failure = Some(error);
+        inner.last_failure_at = Some(Utc::now());
+        let was_open = inner.opened_at.is_some() && !inner.half_open;
+        if inner.half_open || inner.consecutive_failures >= self.failure_threshold {
+            inner.opened_at = Some(Instant::now());
+            inner.half_open = false;
+        }
+        !was_open && inner.opened_at.is_some()
+    }
+
+    fn state(&self) -> BreakerState {
+        let inner = self.inner.lock().unwrap();
+        match inner.opened_at {
+            None => BreakerState::Closed,
+            Some(_) if inner.half_open => BreakerState::HalfOpen,
+            Some(opened_at) if opened_at.elapsed() >= self.open_for => BreakerState::HalfOpen,
+            Some(_) => BreakerState::Open,
+        }
+    }
+}
+
+/// A dependency's breaker as shown on the system health endpoint
+#[derive(Debug, Clone, Serialize)]
+pub struct DependencyHealth {
+    pub dependency: &'static str,
+    pub state: BreakerState,
+    pub consecutive_failures: u32,
+    pub last_failure: Option<String>,
+    pub last_failure_at: Option<DateTime<Utc>>,
+}
+
+/// Breaker state of every dependency
+pub fn health() -> Vec<DependencyHealth> {
+    Dependency::ALL
+        .iter()
+        .map(|dependency| {
+            let breaker = dependency.breaker();
+            let state = breaker.state();
+            let inner = breaker.inner.lock().unwrap();
+            DependencyHealth {
+                dependency: dependency.name(),
+                state,
+                consecutive_failures: inner.consecutive_failures,
+                last_failure: inner.last_failure.clone(),
+                last_failure_at: inner.last_failure_at,
+            }
+        })
+        .collect()
+}
+
+/// Run `operation` against `dependency`, retrying transient failures
+pub async fn call<T, E, F, Fut>(dependency: Dependency, operation: F) -> Result<T, E>
+where
+    E: ExternalError,
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+{
+    let policy = dependency.policy();
+    run(dependency, dependency.breaker(), policy, operation).await
+}
+
+/// Run `operation` against `dependency` without retrying, for requests that may have taken
+/// effect even when they appear to fail
+pub async fn call_once<T, E, Fut>(dependency: Dependency, operation: Fut) -> Result<T, E>
+where
+    E: ExternalError,
+    Fut: Future<Output = Result<T, E>>,
+{
+    let policy = Policy {
+        max_attempts: 1,
+        ..dependency.policy()
+    };
+    let mut operation = Some(operation);
+    run(dependency, dependency.breaker(), policy, || {
+        operation.take().expect("call_once runs its operation once")
+    })
+    .await
+}
+
+async fn run<T, E, F, Fut>(
+    dependency: Dependency,
+    breaker: &CircuitBreaker,
+    policy: Policy,
+    mut operation: F,
+) -> Result<T, E>
+where
+    E: ExternalError,
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+{
+    let mut attempt = 1;
+    loop {
+        if !breaker.allow() {
+            return Err(E::unavailable(format!(
+                "{} is unavailable after repeated failures; retry shortly",
+                dependency.name()
+            )));
+        }
+
+        let error = match tokio::time::timeout(policy.timeout, operation()).await {
+            Ok(Ok(value)) => {
+                breaker.record_success();
+                return Ok(value);
+            }
+            // The dependency answered, so it is up even though the request failed
+            Ok(Err(e)) if !e.is_transient() => {
+                breaker.record_success();
+                return Err(e);
+            }
+            Ok(Err(e)) => e,
+            Err(_) => E::unavailable(format!(
+                "{} did not respond within {}s",
+                dependency.name(),
+                policy.timeout.as_secs()
+            )),
+        };
+
+        if breaker.record_failure(error.to_string()) {
+            warn!(
+                dependency = dependency.name(),
+                error = %error,
+                "Circuit breaker opened"
+            );
+        }
+        if attempt >= policy.max_attempts {
+            return Err(error);
+        }
+        warn!(
+            dependency = dependency.name(),
+            attempt = attempt,
+            max_attempts = policy.max_attempts,
+            error = %error,
+            "External call failed, retrying"
+        );
+        tokio::time::sleep(policy.backoff(attempt)).await;
+        attempt += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    #[derive(Debug, PartialEq)]
+    enum TestError {
+        Transient,
+        Rejected,
+        Unavailable(String),
+    }
+
+    impl Display for TestError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{:?}", self)
+        }
+    }
+
+    impl ExternalError for TestError {
+        fn is_transient(&self) -> bool {
+            !matches!(self, TestError::Rejected)
+        }
+
+        fn unavailable(message: String) -> Self {
+            TestError::Unavailable(message)
+        }
+    }
+
+    fn policy(max_attempts: u32) -> Policy {
+        Policy {
+            timeout: Duration::from_millis(50),
+            max_attempts,
+            base_delay: Duration::from_millis(1),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_retries_transient_failures_only() {
+        let breaker = CircuitBreaker::new(10, OPEN_FOR);
+        let calls = AtomicU32::new(0);
+        let result = run(Dependency::OpenAI, &breaker, policy(3), || async {
+            match calls.fetch_add(1, Ordering::SeqCst) {
+                0 | 1 => Err(TestError::Transient),
+                _ => Ok("done"),
+            }
+        })
+        .await;
+        assert_eq!(result, Ok("done"));
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+
+        calls.store(0, Ordering::SeqCst);
+        let result: Result<(), _> = run(Dependency::OpenAI, &breaker, policy(3), || async {
+            calls.fetch_add(1, Ordering::SeqCst);
+            Err(TestError::Rejected)
+        })
+        .await;
+        assert_eq!(result, Err(TestError::Rejected));
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_times_out_slow_calls() {
+        let breaker = CircuitBreaker::new(10, OPEN_FOR);
+        let result: Result<(), _> = run(Dependency::S3, &breaker, policy(1), || async {
+            tokio::time::sleep(Duration::from_secs(5)).await;
+            Ok(())
+        })
+        .await;
+        assert!(matches!(result, Err(TestError::Unavailable(_))));
+        assert_eq!(breaker.state(), BreakerState::Closed);
+    }
+
+    #[tokio::test]
+    async fn test_breaker_opens_and_recovers() {
+        let breaker = CircuitBreaker::new(2, Duration::from_millis(20));
+        let calls = AtomicU32::new(0);
+        let failing = || async {
+            calls.fetch_add(1, Ordering::SeqCst);
+            Err::<(), _>(TestError::Transient)
+        };
+
+        let _ = run(Dependency::Google, &breaker, policy(5), failing).await;
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+        assert_eq!(breaker.state(), BreakerState::Open);
+
+        let rejected = run(Dependency::Google, &breaker, policy(1), || async { Ok(()) }).await;
+        assert!(matches!(rejected, Err(TestError::Unavailable(_))));
+
+        tokio::time::sleep(Duration::from_millis(25)).await;
+        assert_eq!(breaker.state(), BreakerState::HalfOpen);
+        let _ = run(Dependency::Google, &breaker, policy(1), failing).await;
+        assert_eq!(breaker.state(), BreakerState::Open);
+
+        tokio::time::sleep(Duration::from_millis(25)).await;
+        let trial: Result<(), TestError> =
+            run(Dependency::Google, &breaker, policy(1), || async { Ok(()) }).await;
+        assert_eq!(trial, Ok(()));
+        assert_eq!(breaker.state(), BreakerState::Closed);
+    }
+}
@@ -0,0 +1,113 @@
+// src/services/sanitize.rs
+//! HTML sanitization for user- and AI-authored content
+//!
+//! Job descriptions, messages and the free-text parts of emails are cleaned when they are
+//! written, so nothing stored can carry scripts, event handlers or `javascript:` links to
+//! the careers site or an inbox.
+
+use ammonia::Builder;
+use pulldown_cmark::{html, Options, Parser};
+use std::collections::HashSet;
+
+/// Tags allowed in chat messages: inline formatting, links and simple lists
+const MESSAGE_TAGS: &[&str] = &[
+    "a",
+    "b",
+    "strong",
+    "i",
+    "em",
+    "u",
+    "code",
+    "pre",
+    "br",
+    "p",
+    "ul",
+    "ol",
+    "li",
+    "blockquote",
+];
+
+/// Clean rich HTML (email fragments, rendered markdown) down to ammonia's safe allowlist
+pub fn sanitize_html(input: &str) -> String {
+    ammonia::clean(input)
+}
+
+/// Clean markdown source before storage
+///
+/// Markdown with no markup at all is stored untouched, so quotes (`>`) and ampersands keep
+/// their literal form. Anything carrying HTML is cleaned like `sanitize_html`.
+pub fn sanitize_markdown(markdown: &str) -> String {
+    if markdown.contains('<') {
+        sanitize_html(markdown)
+    } else {
+        markdown.to_string()
+    }
+}
+
+/// Clean a chat message, keeping only basic inline formatting
+pub fn sanitize_message(content: &str) -> String {
+    if !content.contains('<') {
+        return content.to_string();
+    }
+
+    Builder::default()
+        .tags(MESSAGE_TAGS.iter().copied().collect::<HashSet<_>>())
+        .clean(content)
+        .to_string()
+}
+
+/// Render markdown to HTML that is safe to embed in a page or email
+pub fn render_markdown(markdown: &str) -> String {
+    let mut options = Options::empty();
+    options.insert(Options::ENABLE_TABLES);
+    options.insert(Options::ENABLE_STRIKETHROUGH);
+
+    let mut rendered = String::new();
+    html::push_html(&mut rendered, Parser::new_ext(markdown, options));
+    sanitize_html(&rendered)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sanitize_html_strips_scripts_and_handlers() {
+        let cleaned = sanitize_html(
+            r#"<p onclick="steal()">Hi<script>alert(1)</script> <a href="javascript:alert(1)">x</a></p>"#,
+        );
+        assert!(!cleaned.contains("script"));
+        assert!(!cleaned.contains("onclick"));
+        assert!(!cleaned.contains("javascript:"));
+        assert!(cleaned.contains("<p>Hi"));
+    }
+
+    #[test]
+    fn test_plain_markdown_is_untouched() {
+        let markdown = "## Overview\n> Quoted & kept\n\n- R&D role";
+        assert_eq!(sanitize_markdown(markdown), markdown);
+
+        let with_html = "## Overview\n<img src=x onerror=alert(1)>";
+        assert!(!sanitize_markdown(with_html).contains("onerror"));
+    }
+
+    #[test]
+    fn test_sanitize_message_keeps_inline_formatting_only() {
+        assert_eq!(
+            sanitize_message("See you at 3 & bring CV"),
+            "See you at 3 & bring CV"
+        );
+
+        let cleaned = sanitize_message(r#"<b>Hello</b><img src="x"><iframe src="evil"></iframe>"#);
+        assert_eq!(cleaned, "<b>Hello</b>");
+    }
+
+    #[test]
+    fn test_render_markdown_is_sanitized() {
+        let rendered =
+            render_markdown("**Bold** [link](javascript:alert(1))\n\n<script>x</script>");
+        assert!(rendered.contains("<strong>Bold</strong>"));
+        assert!(!rendered.contains("javascript:"));
+        assert!(!rendered.contains("<script>"));
+    }
+}
@@ -0,0 +1,393 @@
+// src/services/scheduled_messages.rs
+//! Admin messages and candidate emails held for a later send time
+//!
+//! `send_at` is stored in UTC next to the zone it was chosen in, which defaults to the
+//! recipient's preferred zone. With `business_hours`, the time moves forward to the next
+//! weekday window between `business_hours_start` and `business_hours_end` (default 9 to 17)
+//! in that zone. A background task sends whatever is due every minute; until then a message
+//! can be edited or cancelled.
+
+use chrono::{DateTime, Duration, NaiveDateTime, Utc};
+use sqlx::SqlitePool;
+use std::sync::Arc;
+use tracing::{debug, error, info, warn};
+
+use crate::common::timezone::{
+    next_business_hours, normalize_schedule, to_zone_rfc3339, user_timezone, validate_timezone,
+    DEFAULT_TIMEZONE,
+};
+use crate::common::{generate_history_id, generate_scheduled_message_id, ApiError};
+use crate::messages::models::{ScheduleInput, ScheduledMessage, ScheduledMessageResponse};
+use crate::messages::services::{ConnectionManager, MessageService};
+use crate::services::{EmailSender, SettingsService};
+
+pub const SCHEDULED_STATUSES: &[&str] = &["scheduled", "sending", "sent", "cancelled", "failed"];
+
+/// Sends of a scheduled message before it is marked `failed`
+pub const MAX_SEND_ATTEMPTS: i64 = 3;
+
+/// How far in the past a `send_at` may be, to absorb clock skew between client and server
+const SEND_AT_GRACE_SECONDS: i64 = 60;
+
+const DEFAULT_BUSINESS_HOURS: (u32, u32) = (9, 17);
+
+/// Messages sent per run
+const SCHEDULED_BATCH_SIZE: i64 = 100;
+
+const SCHEDULED_INTERVAL_SECONDS: u64 = 60;
+
+const TIMESTAMP_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+/// The configured business hours, falling back to 9 to 17 when unset or inconsistent
+pub async fn business_hours(settings_service: &SettingsService) -> (u32, u32) {
+    let hour = |key: &'static str| async move {
+        settings_service
+            .get_setting(key)
+            .await
+            .ok()
+            .flatten()
+            .and_then(|v| v.trim().parse::<u32>().ok())
+    };
+    match (
+        hour("business_hours_start").await,
+        hour("business_hours_end").await,
+    ) {
+        (Some(start), Some(end)) if start < end && end <= 24 => (start, end),
+        (Some(start), None) if start < DEFAULT_BUSINESS_HOURS.1 => {
+            (start, DEFAULT_BUSINESS_HOURS.1)
+        }
+        (None, Some(end)) if end > DEFAULT_BUSINESS_HOURS.0 && end <= 24 => {
+            (DEFAULT_BUSINESS_HOURS.0, end)
+        }
+        _ => DEFAULT_BUSINESS_HOURS,
+    }
+}
+
+/// Resolve when a message to `user_id` should go out and the zone that was chosen in.
+/// `None` means send now.
+pub async fn resolve_send_at(
+    pool: &SqlitePool,
+    settings_service: &SettingsService,
+    user_id: &str,
+    schedule: &ScheduleInput,
+) -> Result<Option<(DateTime<Utc>, String)>, ApiError> {
+    if !schedule.is_scheduled() {
+        return Ok(None);
+    }
+
+    let zone = match schedule
+        .timezone
+        .as_deref()
+        .map(str::trim)
+        .filter(|tz| !tz.is_empty())
+    {
+        Some(tz) => validate_timezone(tz).map_err(ApiError::ValidationError)?,
+        None => user_timezone(pool, user_id)
+            .await
+            .unwrap_or_else(|| DEFAULT_TIMEZONE.to_string()),
+    };
+
+    let now = Utc::now();
+    let mut send_at = match schedule.send_at.as_deref() {
+        Some(input) => {
+            let (utc, _) =
+                normalize_schedule(input, Some(&zone)).map_err(ApiError::ValidationError)?;
+            if utc < now - Duration::seconds(SEND_AT_GRACE_SECONDS) {
+                return Err(ApiError::ValidationError(
+                    "send_at must be in the future".to_string(),
+                ));
+            }
+            utc
+        }
+        None => now,
+    };
+    if schedule.business_hours {
+        let (start, end) = business_hours(settings_service).await;
+        send_at = next_business_hours(send_at, &zone, start, end);
+    }
+
+    Ok(Some((send_at.max(now), zone)))
+}
+
+/// A message or email to hold until `send_at`
+pub struct NewScheduledMessage<'a> {
+    pub kind: &'a str,
+    pub user_id: &'a str,
+    pub application_id: Option<&'a str>,
+    pub job_id: Option<&'a str>,
+    pub subject: Option<&'a str>,
+    pub body: &'a str,
+    pub cc: Option<&'a [String]>,
+    pub send_at: DateTime<Utc>,
+    pub timezone: &'a str,
+    pub business_hours: bool,
+    pub created_by: &'a str,
+}
+
+pub async fn schedule_message(
+    pool: &SqlitePool,
+    new: NewScheduledMessage<'_>,
+) -> Result<ScheduledMessage, ApiError> {
+    let id = generate_scheduled_message_id();
+    let cc = new
+        .cc
+        .filter(|cc| !cc.is_empty())
+        .map(|cc| serde_json::to_string(cc).unwrap_or_default());
+
+    sqlx::query(
+        r#"
+        INSERT INTO scheduled_messages
+            (id, kind, user_id, application_id, job_id, subject, body, cc, send_at, timezone,
+             business_hours, created_by)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+        "#,
+    )
+    .bind(&id)
+    .bind(new.kind)
+    .bind(new.user_id)
+    .bind(new.application_id)
+    .bind(new.job_id)
+    .bind(new.subject)
+    .bind(new.body)
+    .bind(&cc)
+    .bind(format_send_at(new.send_at))
+    .bind(new.timezone)
+    .bind(new.business_hours)
+    .bind(new.created_by)
+    .execute(pool)
+    .await
+    .map_err(|e| {
+        error!(error = %e, user_id = %new.user_id, "Database error scheduling message");
+        ApiError::DatabaseError(e)
+    })?;
+
+    info!(
+        scheduled_message_id = %id,
+        kind = %new.kind,
+        user_id = %new.user_id,
+        send_at = %new.send_at.to_rfc3339(),
+        timezone = %new.timezone,
+        "Message scheduled"
+    );
+
+    sqlx::query_as::<_, ScheduledMessage>("SELECT * FROM scheduled_messages WHERE id = ?")
+        .bind(&id)
+        .fetch_one(pool)
+        .await
+        .map_err(ApiError::DatabaseError)
+}
+
+/// `send_at` as stored, formatted for the scheduling columns
+pub fn format_send_at(send_at: DateTime<Utc>) -> String {
+    send_at.format(TIMESTAMP_FORMAT).to_string()
+}
+
+pub fn to_response(scheduled: ScheduledMessage) -> ScheduledMessageResponse {
+    let send_at_local = NaiveDateTime::parse_from_str(&scheduled.send_at, TIMESTAMP_FORMAT)
+        .map(|naive| to_zone_rfc3339(naive.and_utc(), &scheduled.timezone))
+        .unwrap_or_else(|_| scheduled.send_at.clone());
+    ScheduledMessageResponse {
+        scheduled,
+        send_at_local,
+    }
+}
+
+/// Send one claimed message. Returns the chat message id for messages.
+async fn deliver(
+    pool: &SqlitePool,
+    email_sender: &dyn EmailSender,
+    connection_manager: &ConnectionManager,
+    scheduled: &ScheduledMessage,
+) -> Result<Option<String>, String> {
+    if scheduled.kind == "message" {
+        let message = MessageService::new(pool.clone())
+            .post_admin_message(connection_manager, &scheduled.user_id, &scheduled.body)
+            .await
+            .map_err(|e| e.to_string())?;
+        return Ok(Some(message.id));
+    }
+
+    let email = sqlx::query_scalar::<_, String>("SELECT email FROM users WHERE id = ?")
+        .bind(&scheduled.user_id)
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Recipient no longer exists".to_string())?;
+    let mut recipients = vec![email];
+    if let Some(cc) = scheduled.cc.as_deref() {
+        recipients.extend(serde_json::from_str::<Vec<String>>(cc).unwrap_or_default());
+    }
+    let subject = scheduled.subject.clone().unwrap_or_default();
+
+    email_sender
+        .send_email(recipients, &subject, &scheduled.body, None)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if let (Some(application_id), Some(job_id)) = (&scheduled.application_id, &scheduled.job_id) {
+        let _ = sqlx::query(
+            r#"
+            INSERT INTO email_history (id, application_id, candidate_id, job_id, subject, content, cc, sent_by, sent_at, email_type)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, datetime('now'), 'manual')
+            "#,
+        )
+        .bind(generate_history_id())
+        .bind(application_id)
+        .bind(&scheduled.user_id)
+        .bind(job_id)
+        .bind(&subject)
+        .bind(&scheduled.body)
+        .bind(&scheduled.cc)
+        .bind(&scheduled.created_by)
+        .execute(pool)
+        .await;
+    }
+    Ok(None)
+}
+
+/// Send every scheduled message whose time has come
+pub async fn dispatch_due(
+    pool: &SqlitePool,
+    email_sender: &dyn EmailSender,
+    connection_manager: &ConnectionManager,
+) -> Result<usize, sqlx::Error> {
+    let due = sqlx::query_as::<_, ScheduledMessage>(
+        r#"
+        SELECT * FROM scheduled_messages
+        WHERE status = 'scheduled' AND send_at <= datetime('now')
+        ORDER BY send_at
+        LIMIT ?
+        "#,
+    )
+    .bind(SCHEDULED_BATCH_SIZE)
+    .fetch_all(pool)
+    .await?;
+
+    let mut sent = 0;
+    for scheduled in due {
+        // Claim it, so an edit or cancel racing with the send is refused rather than lost
+        let claimed = sqlx::query(
+            "UPDATE scheduled_messages SET status = 'sending' WHERE id = ? AND status = 'scheduled'",
+        )
+        .bind(&scheduled.id)
+        .execute(pool)
+        .await?;
+        if claimed.rows_affected() == 0 {
+            continue;
+        }
+
+        match deliver(pool, email_sender, connection_manager, &scheduled).await {
+            Ok(message_id) => {
+                sqlx::query(
+                    r#"
+                    UPDATE scheduled_messages
+                    SET status = 'sent', attempts = attempts + 1, message_id = ?, error = NULL,
+                        sent_at = datetime('now'), updated_at = datetime('now')
+                    WHERE id = ?
+                    "#,
+                )
+                .bind(&message_id)
+                .bind(&scheduled.id)
+                .execute(pool)
+                .await?;
+                sent += 1;
+            }
+            Err(e) => {
+                let status = if scheduled.attempts + 1 >= MAX_SEND_ATTEMPTS {
+                    "failed"
+                } else {
+                    "scheduled"
+                };
+                warn!(
+                    error = %e,
+                    scheduled_message_id = %scheduled.id,
+                    attempts = scheduled.attempts + 1,
+                    "Scheduled message send failed"
+                );
+                sqlx::query(
+                    r#"
+                    UPDATE scheduled_messages
+                    SET status = ?, attempts = attempts + 1, error = ?, updated_at = datetime('now')
+                    WHERE id = ?
+                    "#,
+                )
+                .bind(status)
+                .bind(&e)
+                .bind(&scheduled.id)
+                .execute(pool)
+                .await?;
+            }
+        }
+    }
+
+    if sent > 0 {
+        info!(sent = sent, "Sent scheduled messages");
+    }
+    Ok(sent)
+}
+
+/// Send due messages every minute
+pub fn start_scheduled_message_task(
+    pool: SqlitePool,
+    email_sender: Arc<dyn EmailSender>,
+    connection_manager: ConnectionManager,
+) {
+    tokio::spawn(async move {
+        // Messages claimed when the server last stopped never finished sending
+        if let Err(e) = sqlx::query(
+            "UPDATE scheduled_messages SET status = 'scheduled' WHERE status = 'sending'",
+        )
+        .execute(&pool)
+        .await
+        {
+            debug!(error = %e, "Skipped releasing interrupted scheduled messages");
+        }
+
+        loop {
+            if !crate::common::shutdown::sleep(std::time::Duration::from_secs(
+                SCHEDULED_INTERVAL_SECONDS,
+            ))
+            .await
+            {
+                break;
+            }
+            let _running = crate::common::shutdown::track();
+
+            if let Err(e) = dispatch_due(&pool, email_sender.as_ref(), &connection_manager).await {
+                debug!(error = %e, "Skipped scheduled message dispatch");
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_response_renders_send_at_in_scheduled_zone() {
+        let scheduled = ScheduledMessage {
+            id: "SM_1".to_string(),
+            kind: "email".to_string(),
+            user_id: "U_1".to_string(),
+            application_id: None,
+            job_id: None,
+            subject: Some("Next steps".to_string()),
+            body: "Hello".to_string(),
+            cc: None,
+            send_at: "2026-07-01 13:00:00".to_string(),
+            timezone: "America/New_York".to_string(),
+            business_hours: true,
+            status: "scheduled".to_string(),
+            attempts: 0,
+            error: None,
+            message_id: None,
+            created_by: "U_2".to_string(),
+            created_at: None,
+            updated_at: None,
+            sent_at: None,
+        };
+        let response = to_response(scheduled);
+        assert_eq!(response.send_at_local, "2026-07-01T09:00:00-04:00");
+    }
+}
@@ -0,0 +1,70 @@
+// src/services/search.rs
+//! Query helpers for the admin global search and message search
+//!
+//! Names, emails, job titles and companies are matched through the trigram FTS5 tables
+//! (`users_fts`, `jobs_fts`, `companies_fts`); application IDs by prefix on the primary key.
+//! Conversation messages are matched through `conversation_messages_fts`.
+
+/// Trigram matching needs at least three characters
+pub const MIN_QUERY_LENGTH: usize = 3;
+
+pub const DEFAULT_RESULTS_PER_GROUP: i64 = 5;
+pub const MAX_RESULTS_PER_GROUP: i64 = 25;
+
+/// Quote a user query as a single FTS5 phrase, so it matches as a substring and operators
+/// or punctuation in the input are taken literally
+pub fn phrase_query(query: &str) -> String {
+    format!("\"{}\"", query.replace('"', "\"\""))
+}
+
+/// A GLOB pattern matching application IDs that start with the query, if it could be one
+///
+/// IDs are Crockford Base32 with a type prefix, e.g. `A_7K2M9Q`, so anything with other
+/// characters is skipped rather than scanned for.
+pub fn application_id_pattern(query: &str) -> Option<String> {
+    let query = query.to_uppercase();
+    let is_id_like =
+        query.starts_with("A_") && query.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    is_id_like.then(|| format!("{}*", query))
+}
+
+pub const DEFAULT_MESSAGE_RESULTS: i64 = 20;
+pub const MAX_MESSAGE_RESULTS: i64 = 100;
+
+/// Where a message opens: the admin conversation view, or the candidate's own messages
+pub fn message_link(is_admin: bool, conversation_id: &str, message_id: &str) -> String {
+    if is_admin {
+        format!("/admin/conversations/{}#{}", conversation_id, message_id)
+    } else {
+        format!("/messages#{}", message_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_phrase_query_escapes_quotes() {
+        assert_eq!(phrase_query("rust dev"), "\"rust dev\"");
+        assert_eq!(phrase_query("say \"hi\" OR"), "\"say \"\"hi\"\" OR\"");
+    }
+
+    #[test]
+    fn test_application_id_pattern() {
+        assert_eq!(application_id_pattern("a_7k2"), Some("A_7K2*".to_string()));
+        assert_eq!(application_id_pattern("A_"), Some("A_*".to_string()));
+        assert_eq!(application_id_pattern("alice"), None);
+        assert_eq!(application_id_pattern("A_7K*"), None);
+        assert_eq!(application_id_pattern("J_7K2M9Q"), None);
+    }
+
+    #[test]
+    fn test_message_link() {
+        assert_eq!(
+            message_link(true, "U_1", "M_2"),
+            "/admin/conversations/U_1#M_2"
+        );
+        assert_eq!(message_link(false, "U_1", "M_2"), "/messages#M_2");
+    }
+}
@@ -0,0 +1,633 @@
+// src/services/seed.rs
+//! Demo data for dev mode
+//!
+//! Fills an empty database with companies, jobs, candidates, applications in every stage with
+//! their history, interviews and message threads, so frontend work and demos have something
+//! to show. Seeded candidates have `@seed.example.com` addresses; seeding again once they
+//! exist does nothing.
+
+use chrono::{Duration, Utc};
+use serde::Serialize;
+use sqlx::{Sqlite, SqlitePool, Transaction};
+use tracing::info;
+
+use crate::candidates::handlers::email_templates::status_to_stage;
+use crate::common::{
+    generate_application_id, generate_company_id, generate_history_id, generate_interview_id,
+    generate_job_id, generate_message_id, generate_user_id,
+};
+use crate::services::pii;
+
+pub const SEED_EMAIL_DOMAIN: &str = "seed.example.com";
+
+/// Who seeded rows are recorded as changed by
+const SEED_ACTOR: &str = "seed";
+
+#[derive(Debug, Default, Serialize)]
+pub struct SeedSummary {
+    pub already_seeded: bool,
+    pub companies: usize,
+    pub jobs: usize,
+    pub candidates: usize,
+    pub applications: usize,
+    pub interviews: usize,
+    pub messages: usize,
+}
+
+struct SeedCompany {
+    name: &'static str,
+    industry: &'static str,
+    size: &'static str,
+    headquarters: &'static str,
+    website: &'static str,
+    description: &'static str,
+}
+
+const COMPANIES: &[SeedCompany] = &[
+    SeedCompany {
+        name: "Northwind Analytics",
+        industry: "Data & Analytics",
+        size: "51-200",
+        headquarters: "Bengaluru, India",
+        website: "https://northwind.example.com",
+        description: "Dashboards and forecasting for retail supply chains.",
+    },
+    SeedCompany {
+        name: "Bluepeak Health",
+        industry: "Healthcare",
+        size: "201-500",
+        headquarters: "Pune, India",
+        website: "https://bluepeak.example.com",
+        description: "Clinic management software used by over 2,000 practices.",
+    },
+    SeedCompany {
+        name: "Lumen Robotics",
+        industry: "Manufacturing",
+        size: "11-50",
+        headquarters: "Hyderabad, India",
+        website: "https://lumen.example.com",
+        description: "Vision-guided robots for small-batch assembly lines.",
+    },
+    SeedCompany {
+        name: "Quillstack",
+        industry: "Software",
+        size: "501-1000",
+        headquarters: "Remote",
+        website: "https://quillstack.example.com",
+        description: "Collaborative writing tools for documentation teams.",
+    },
+];
+
+struct SeedJob {
+    company: usize,
+    title: &'static str,
+    location: &'static str,
+    job_type: &'static str,
+    experience_level: &'static str,
+    salary: (i64, i64),
+    status: &'static str,
+    featured: bool,
+    requirements: &'static [&'static str],
+}
+
+const JOBS: &[SeedJob] = &[
+    SeedJob {
+        company: 0,
+        title: "Senior Data Engineer",
+        location: "Bengaluru, India",
+        job_type: "full-time",
+        experience_level: "senior",
+        salary: (2_800_000, 3_600_000),
+        status: "active",
+        featured: true,
+        requirements: &[
+            "5+ years building data pipelines",
+            "SQL and Python",
+            "Airflow or similar",
+        ],
+    },
+    SeedJob {
+        company: 0,
+        title: "Analytics Intern",
+        location: "Bengaluru, India",
+        job_type: "internship",
+        experience_level: "entry",
+        salary: (30_000, 40_000),
+        status: "active",
+        featured: false,
+        requirements: &["Statistics coursework", "Spreadsheet skills"],
+    },
+    SeedJob {
+        company: 1,
+        title: "Backend Engineer (Rust)",
+        location: "Pune, India",
+        job_type: "full-time",
+        experience_level: "mid",
+        salary: (1_800_000, 2_600_000),
+        status: "active",
+        featured: true,
+        requirements: &["3+ years backend development", "Rust or Go", "PostgreSQL"],
+    },
+    SeedJob {
+        company: 1,
+        title: "Clinical Product Manager",
+        location: "Pune, India",
+        job_type: "full-time",
+        experience_level: "senior",
+        salary: (2_500_000, 3_200_000),
+        status: "active",
+        featured: false,
+        requirements: &["Healthcare domain experience", "Shipped B2B products"],
+    },
+    SeedJob {
+        company: 1,
+        title: "QA Analyst",
+        location: "Pune, India",
+        job_type: "contract",
+        experience_level: "entry",
+        salary: (600_000, 900_000),
+        status: "closed",
+        featured: false,
+        requirements: &["Manual and exploratory testing", "Bug tracking tools"],
+    },
+    SeedJob {
+        company: 2,
+        title: "Robotics Software Engineer",
+        location: "Hyderabad, India",
+        job_type: "full-time",
+        experience_level: "mid",
+        salary: (2_000_000, 2_800_000),
+        status: "active",
+        featured: false,
+        requirements: &["C++ and ROS", "Computer vision basics"],
+    },
+    SeedJob {
+        company: 2,
+        title: "Field Service Technician",
+        location: "Hyderabad, India",
+        job_type: "full-time",
+        experience_level: "entry",
+        salary: (500_000, 700_000),
+        status: "draft",
+        featured: false,
+        requirements: &["Diploma in mechatronics", "Willing to travel"],
+    },
+    SeedJob {
+        company: 3,
+        title: "Frontend Engineer",
+        location: "Remote",
+        job_type: "full-time",
+        experience_level: "mid",
+        salary: (1_600_000, 2_400_000),
+        status: "active",
+        featured: true,
+        requirements: &["React and TypeScript", "Accessibility know-how"],
+    },
+    SeedJob {
+        company: 3,
+        title: "Technical Writer",
+        location: "Remote",
+        job_type: "part-time",
+        experience_level: "mid",
+        salary: (900_000, 1_200_000),
+        status: "active",
+        featured: false,
+        requirements: &["Portfolio of developer docs", "Markdown and Git"],
+    },
+    SeedJob {
+        company: 3,
+        title: "Engineering Manager",
+        location: "Remote",
+        job_type: "full-time",
+        experience_level: "lead",
+        salary: (4_000_000, 5_000_000),
+        status: "draft",
+        featured: false,
+        requirements: &["Managed teams of 6+", "Hiring experience"],
+    },
+];
+
+struct SeedCandidate {
+    first_name: &'static str,
+    last_name: &'static str,
+    location: &'static str,
+    skills: &'static [&'static str],
+    bio: &'static str,
+}
+
+const CANDIDATES: &[SeedCandidate] = &[
+    SeedCandidate {
+        first_name: "Aarav",
+        last_name: "Sharma",
+        location: "Bengaluru",
+        skills: &["Python", "SQL", "Airflow"],
+        bio: "Data engineer who likes tidy pipelines.",
+    },
+    SeedCandidate {
+        first_name: "Diya",
+        last_name: "Patel",
+        location: "Pune",
+        skills: &["Rust", "PostgreSQL", "Docker"],
+        bio: "Backend developer focused on reliability.",
+    },
+    SeedCandidate {
+        first_name: "Kabir",
+        last_name: "Iyer",
+        location: "Chennai",
+        skills: &["React", "TypeScript", "CSS"],
+        bio: "Frontend engineer with an eye for accessibility.",
+    },
+    SeedCandidate {
+        first_name: "Meera",
+        last_name: "Nair",
+        location: "Kochi",
+        skills: &["Product management", "Healthcare"],
+        bio: "Product manager from clinical software.",
+    },
+    SeedCandidate {
+        first_name: "Rohan",
+        last_name: "Gupta",
+        location: "Delhi",
+        skills: &["C++", "ROS", "OpenCV"],
+        bio: "Robotics engineer building pick-and-place systems.",
+    },
+    SeedCandidate {
+        first_name: "Ananya",
+        last_name: "Reddy",
+        location: "Hyderabad",
+        skills: &["Technical writing", "Markdown"],
+        bio: "Writes docs developers actually read.",
+    },
+    SeedCandidate {
+        first_name: "Vikram",
+        last_name: "Singh",
+        location: "Jaipur",
+        skills: &["Go", "Kubernetes"],
+        bio: "Platform engineer, formerly SRE.",
+    },
+    SeedCandidate {
+        first_name: "Isha",
+        last_name: "Kapoor",
+        location: "Mumbai",
+        skills: &["Statistics", "Excel", "Tableau"],
+        bio: "Final-year statistics student.",
+    },
+    SeedCandidate {
+        first_name: "Arjun",
+        last_name: "Menon",
+        location: "Bengaluru",
+        skills: &["Leadership", "Java", "Hiring"],
+        bio: "Engineering manager of two product teams.",
+    },
+    SeedCandidate {
+        first_name: "Sana",
+        last_name: "Qureshi",
+        location: "Lucknow",
+        skills: &["Testing", "Selenium"],
+        bio: "QA analyst who enjoys breaking things.",
+    },
+    SeedCandidate {
+        first_name: "Nikhil",
+        last_name: "Joshi",
+        location: "Ahmedabad",
+        skills: &["Rust", "Embedded"],
+        bio: "Firmware developer moving to backend.",
+    },
+    SeedCandidate {
+        first_name: "Priya",
+        last_name: "Das",
+        location: "Kolkata",
+        skills: &["Vue", "Node.js"],
+        bio: "Full-stack developer at an agency.",
+    },
+];
+
+/// Statuses applications are spread across; each candidate's applications take the next ones
+const STATUSES: &[&str] = &[
+    "submitted",
+    "reviewed",
+    "shortlisted",
+    "interview_scheduled",
+    "interviewed",
+    "offered",
+    "hired",
+    "rejected",
+    "withdrawn",
+];
+
+/// Statuses an application passed through to reach `status`, in order
+fn status_path(status: &str) -> Vec<&'static str> {
+    const FORWARD: &[&str] = &[
+        "submitted",
+        "reviewed",
+        "shortlisted",
+        "interview_scheduled",
+        "interviewed",
+        "offered",
+        "hired",
+    ];
+    match status {
+        "rejected" => vec!["submitted", "reviewed", "rejected"],
+        "withdrawn" => vec!["submitted", "withdrawn"],
+        _ => {
+            let end = FORWARD.iter().position(|s| *s == status).unwrap_or(0);
+            FORWARD[..=end].to_vec()
+        }
+    }
+}
+
+const MESSAGE_THREAD: &[(&str, &str)] = &[
+    (
+        "user",
+        "Hi! Could you tell me when I should expect to hear back?",
+    ),
+    (
+        "admin",
+        "Thanks for reaching out. The team is reviewing applications this week.",
+    ),
+    ("user", "Great, thank you for the update."),
+];
+
+fn timestamp(at: chrono::DateTime<Utc>) -> String {
+    at.to_rfc3339()
+}
+
+/// Fill the database with demo data unless it has been seeded already
+pub async fn seed_demo_data(pool: &SqlitePool) -> Result<SeedSummary, sqlx::Error> {
+    let existing: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM users WHERE email LIKE ?")
+        .bind(format!("%@{}", SEED_EMAIL_DOMAIN))
+        .fetch_one(pool)
+        .await?;
+    if existing > 0 {
+        info!("Demo data already seeded");
+        return Ok(SeedSummary {
+            already_seeded: true,
+            ..SeedSummary::default()
+        });
+    }
+
+    let mut summary = SeedSummary::default();
+    let mut tx = pool.begin().await?;
+    let now = Utc::now();
+
+    let recruiter_id = generate_user_id();
+    let recruiter_email = format!("recruiter@{}", SEED_EMAIL_DOMAIN);
+    sqlx::query("INSERT INTO users (id, email, name, provider) VALUES (?, ?, 'Riya Recruiter', ?)")
+        .bind(&recruiter_id)
+        .bind(&recruiter_email)
+        .bind(SEED_ACTOR)
+        .execute(&mut *tx)
+        .await?;
+
+    let mut company_ids = Vec::with_capacity(COMPANIES.len());
+    for company in COMPANIES {
+        let id = generate_company_id();
+        sqlx::query(
+            "INSERT INTO companies (id, name, description, website, industry, company_size, headquarters) VALUES (?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(&id)
+        .bind(company.name)
+        .bind(company.description)
+        .bind(company.website)
+        .bind(company.industry)
+        .bind(company.size)
+        .bind(company.headquarters)
+        .execute(&mut *tx)
+        .await?;
+        company_ids.push(id);
+        summary.companies += 1;
+    }
+
+    let mut job_ids = Vec::with_capacity(JOBS.len());
+    for (index, job) in JOBS.iter().enumerate() {
+        let id = generate_job_id();
+        let created_at = timestamp(now - Duration::days(40 - index as i64 * 3));
+        let published_at = (job.status != "draft").then(|| created_at.clone());
+        sqlx::query(
+            r#"INSERT INTO jobs (id, title, description, location, company, company_id, salary_min, salary_max,
+                job_type, experience_level, requirements, status, is_featured, created_at, updated_at, published_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"#,
+        )
+        .bind(&id)
+        .bind(job.title)
+        .bind(format!(
+            "{} is hiring a {} to join the team. Location: {}.",
+            COMPANIES[job.company].name, job.title, job.location
+        ))
+        .bind(job.location)
+        .bind(COMPANIES[job.company].name)
+        .bind(&company_ids[job.company])
+        .bind(job.salary.0)
+        .bind(job.salary.1)
+        .bind(job.job_type)
+        .bind(job.experience_level)
+        .bind(serde_json::to_string(job.requirements).unwrap_or_default())
+        .bind(job.status)
+        .bind(job.featured)
+        .bind(&created_at)
+        .bind(&created_at)
+        .bind(&published_at)
+        .execute(&mut *tx)
+        .await?;
+        job_ids.push(id);
+        summary.jobs += 1;
+    }
+
+    // Candidates apply to jobs that are or were open
+    let open_jobs: Vec<usize> = (0..JOBS.len())
+        .filter(|i| JOBS[*i].status != "draft")
+        .collect();
+    let mut status_index = 0;
+    for (index, candidate) in CANDIDATES.iter().enumerate() {
+        let user_id = generate_user_id();
+        let email = format!(
+            "{}.{}@{}",
+            candidate.first_name.to_lowercase(),
+            candidate.last_name.to_lowercase(),
+            SEED_EMAIL_DOMAIN
+        );
+        sqlx::query("INSERT INTO users (id, email, name, provider) VALUES (?, ?, ?, ?)")
+            .bind(&user_id)
+            .bind(&email)
+            .bind(format!("{} {}", candidate.first_name, candidate.last_name))
+            .bind(SEED_ACTOR)
+            .execute(&mut *tx)
+            .await?;
+        sqlx::query(
+            "INSERT INTO profiles (user_id, first_name, last_name, location, bio, skills) VALUES (?, ?, ?, ?, ?, ?)",
+        )
+        .bind(&user_id)
+        .bind(candidate.first_name)
+        .bind(candidate.last_name)
+        .bind(pii::seal(candidate.location).map_err(pii::db_error)?)
+        .bind(candidate.bio)
+        .bind(serde_json::to_string(candidate.skills).unwrap_or_default())
+        .execute(&mut *tx)
+        .await?;
+        summary.candidates += 1;
+
+        for offset in 0..2 {
+            let job_index = open_jobs[(index * 2 + offset) % open_jobs.len()];
+            let status = STATUSES[status_index % STATUSES.len()];
+            status_index += 1;
+            let applied_at = now - Duration::days(30 - (index as i64 * 2 + offset as i64));
+            seed_application(
+                &mut tx,
+                &user_id,
+                &job_ids[job_index],
+                status,
+                applied_at,
+                &recruiter_id,
+                &recruiter_email,
+                &mut summary,
+            )
+            .await?;
+        }
+
+        if index < 5 {
+            for (position, (sender, message)) in MESSAGE_THREAD.iter().enumerate() {
+                let sent_at = now - Duration::hours(48 - position as i64 * 6 - index as i64);
+                // The candidate's last message is left unread for the inbox
+                let is_read = position + 1 < MESSAGE_THREAD.len();
+                sqlx::query(
+                    "INSERT INTO conversation_messages (id, user_id, sender, message, is_read, created_at) VALUES (?, ?, ?, ?, ?, ?)",
+                )
+                .bind(generate_message_id())
+                .bind(&user_id)
+                .bind(sender)
+                .bind(message)
+                .bind(is_read)
+                .bind(timestamp(sent_at))
+                .execute(&mut *tx)
+                .await?;
+                summary.messages += 1;
+            }
+        }
+    }
+
+    tx.commit().await?;
+    info!(
+        companies = summary.companies,
+        jobs = summary.jobs,
+        candidates = summary.candidates,
+        applications = summary.applications,
+        interviews = summary.interviews,
+        messages = summary.messages,
+        "Demo data seeded"
+    );
+    Ok(summary)
+}
+
+/// One application with its status and stage history, and an interview once it got that far
+#[allow(clippy::too_many_arguments)]
+async fn seed_application(
+    tx: &mut Transaction<'_, Sqlite>,
+    user_id: &str,
+    job_id: &str,
+    status: &str,
+    applied_at: chrono::DateTime<Utc>,
+    recruiter_id: &str,
+    recruiter_email: &str,
+    summary: &mut SeedSummary,
+) -> Result<(), sqlx::Error> {
+    let application_id = generate_application_id();
+    let path = status_path(status);
+    let updated_at = applied_at + Duration::days(path.len() as i64 - 1);
+    sqlx::query(
+        "INSERT INTO applications (id, user_id, job_id, status, current_stage, applied_at, updated_at, source) VALUES (?, ?, ?, ?, ?, ?, ?, 'direct')",
+    )
+    .bind(&application_id)
+    .bind(user_id)
+    .bind(job_id)
+    .bind(status)
+    .bind(status_to_stage(status))
+    .bind(timestamp(applied_at))
+    .bind(timestamp(updated_at))
+    .execute(&mut **tx)
+    .await?;
+    summary.applications += 1;
+
+    for (day, step) in path.iter().enumerate() {
+        let changed_at = timestamp(applied_at + Duration::days(day as i64));
+        let changed_by = if day == 0 { user_id } else { recruiter_id };
+        sqlx::query(
+            "INSERT INTO application_status_history (id, application_id, status, changed_by, changed_at) VALUES (?, ?, ?, ?, ?)",
+        )
+        .bind(generate_history_id())
+        .bind(&application_id)
+        .bind(step)
+        .bind(changed_by)
+        .bind(&changed_at)
+        .execute(&mut **tx)
+        .await?;
+        sqlx::query(
+            "INSERT INTO stage_history (id, application_id, stage, changed_by, changed_by_name, changed_at) VALUES (?, ?, ?, ?, ?, ?)",
+        )
+        .bind(generate_history_id())
+        .bind(&application_id)
+        .bind(status_to_stage(step))
+        .bind(changed_by)
+        .bind(if day == 0 { "Candidate" } else { "Riya Recruiter" })
+        .bind(&changed_at)
+        .execute(&mut **tx)
+        .await?;
+    }
+
+    if path.contains(&"interview_scheduled") {
+        // Upcoming for applications waiting on it, otherwise held when it was scheduled for
+        let scheduled_date = if status == "interview_scheduled" {
+            Utc::now() + Duration::days(3)
+        } else {
+            applied_at + Duration::days(4)
+        };
+        let panel = serde_json::json!([{
+            "email": recruiter_email,
+            "name": "Riya Recruiter",
+            "role": "Recruiter",
+        }]);
+        sqlx::query(
+            r#"INSERT INTO interviews (id, application_id, candidate_id, job_id, scheduled_date, duration_minutes,
+                interview_type, panel_members, created_by, scheduled_timezone)
+            VALUES (?, ?, ?, ?, ?, 45, 'video', ?, ?, 'Asia/Kolkata')"#,
+        )
+        .bind(generate_interview_id())
+        .bind(&application_id)
+        .bind(user_id)
+        .bind(job_id)
+        .bind(timestamp(scheduled_date))
+        .bind(panel.to_string())
+        .bind(recruiter_id)
+        .execute(&mut **tx)
+        .await?;
+        summary.interviews += 1;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_status_path_ends_at_status() {
+        assert_eq!(status_path("submitted"), vec!["submitted"]);
+        assert_eq!(
+            status_path("interviewed"),
+            vec![
+                "submitted",
+                "reviewed",
+                "shortlisted",
+                "interview_scheduled",
+                "interviewed"
+            ]
+        );
+        assert_eq!(
+            status_path("rejected"),
+            vec!["submitted", "reviewed", "rejected"]
+        );
+        for status in STATUSES {
+            assert_eq!(status_path(status).last(), Some(status));
+        }
+    }
+}
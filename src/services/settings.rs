@@ -27,6 +27,27 @@ pub enum SettingsError {
     SerializationError(String),
 }
 
+/// Settings that hold credentials and are always encrypted at rest when a master key is configured
+pub const SENSITIVE_KEYS: &[&str] = &[
+    "openai_api_key",
+    "aws_access_key_id",
+    "aws_secret_access_key",
+    "google_client_secret",
+    "google_refresh_token",
+    "google_access_token",
+    "monitoring_sentry_dsn",
+    "social_linkedin_access_token",
+    "social_x_access_token",
+    "social_buffer_webhook_url",
+    "twilio_auth_token",
+    "whatsapp_access_token",
+];
+
+/// Check whether a setting key holds a secret
+pub fn is_sensitive_key(key: &str) -> bool {
+    SENSITIVE_KEYS.contains(&key)
+}
+
 #[derive(Debug, Clone)]
 struct CachedSetting {
     value: String,
@@ -39,6 +60,15 @@ pub struct SettingValue {
     pub encrypted: bool,
 }
 
+/// Outcome of encrypting stored secrets at rest
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct SecretsMigration {
+    /// Rows rewritten
+    pub rewritten: usize,
+    /// Keys whose stored value could not be decrypted and were left as they were
+    pub skipped: Vec<String>,
+}
+
 #[derive(Debug)]
 pub struct SettingsService {
     db_pool: SqlitePool,
@@ -62,6 +92,11 @@ impl SettingsService {
             }
         };
 
+        Self::with_encryption(db_pool, encryption_service)
+    }
+
+    /// Create a SettingsService with an explicit (optional) encryption service
+    pub fn with_encryption(db_pool: SqlitePool, encryption_service: Option<EncryptionService>) -> Self {
         Self {
             db_pool,
             cache: Arc::new(RwLock::new(HashMap::new())),
@@ -70,6 +105,11 @@ impl SettingsService {
         }
     }
 
+    /// The master-key encryption service, when one is configured
+    pub fn encryption(&self) -> Option<&EncryptionService> {
+        self.encryption_service.as_ref()
+    }
+
     /// Get a setting value by key
     /// Falls back to environment variable if not found in database
     pub async fn get_setting(&self, key: &str) -> Result<Option<String>, SettingsError> {
@@ -138,6 +178,9 @@ impl SettingsService {
     }
 
     /// Set a setting value
+    ///
+    /// Sensitive keys are encrypted whenever a master key is configured,
+    /// regardless of the `encrypt` flag passed by the caller
     pub async fn set_setting(
         &self,
         key: &str,
@@ -145,6 +188,8 @@ impl SettingsService {
         encrypt: bool,
         updated_by: Option<&str>,
     ) -> Result<(), SettingsError> {
+        let encrypt = encrypt || (is_sensitive_key(key) && self.encryption_service.is_some());
+
         // Validate encryption requirement
         if encrypt && self.encryption_service.is_none() {
             return Err(SettingsError::InvalidConfig(
@@ -155,7 +200,7 @@ impl SettingsService {
         // Encrypt value if requested
         let stored_value = if encrypt {
             match &self.encryption_service {
-                Some(service) => service.encrypt(value).map_err(|e| {
+                Some(service) => service.encrypt_envelope(value).map_err(|e| {
                     error!(key = %key, error = %e, "Failed to encrypt setting");
                     SettingsError::EncryptionError(e)
                 })?,
@@ -265,6 +310,68 @@ impl SettingsService {
         Ok(result)
     }
 
+    /// Encrypt sensitive settings that are still stored in plaintext and
+    /// re-wrap values encrypted before envelope encryption was introduced
+    ///
+    /// A row that can't be decrypted is logged and skipped so the rest are still migrated.
+    /// Safe to run on every startup.
+    pub async fn encrypt_plaintext_secrets(&self) -> Result<SecretsMigration, SettingsError> {
+        let service = match &self.encryption_service {
+            Some(service) => service,
+            None => {
+                warn!("Skipping secrets encryption: encryption service not configured");
+                return Ok(SecretsMigration::default());
+            }
+        };
+
+        let rows = sqlx::query_as::<_, (String, String, Option<i64>)>(
+            "SELECT key, value, encrypted FROM system_settings",
+        )
+        .fetch_all(&self.db_pool)
+        .await?;
+
+        let mut migration = SecretsMigration::default();
+
+        for (key, value, encrypted) in rows {
+            let is_encrypted = encrypted.unwrap_or(0) == 1;
+
+            let plaintext = if is_encrypted {
+                if EncryptionService::is_envelope(&value) {
+                    continue;
+                }
+                match service.decrypt(&value) {
+                    Ok(plaintext) => plaintext,
+                    Err(e) => {
+                        warn!(key = %key, error = %e, "Skipping setting that could not be decrypted");
+                        migration.skipped.push(key);
+                        continue;
+                    }
+                }
+            } else if is_sensitive_key(&key) {
+                value
+            } else {
+                continue;
+            };
+
+            let stored_value = service.encrypt_envelope(&plaintext)?;
+
+            sqlx::query("UPDATE system_settings SET value = ?, encrypted = 1 WHERE key = ?")
+                .bind(&stored_value)
+                .bind(&key)
+                .execute(&self.db_pool)
+                .await?;
+
+            info!(key = %key, "Encrypted setting at rest");
+            migration.rewritten += 1;
+        }
+
+        if migration.rewritten > 0 {
+            self.invalidate_cache().await;
+        }
+
+        Ok(migration)
+    }
+
     /// Delete a setting
     pub async fn delete_setting(&self, key: &str) -> Result<(), SettingsError> {
         sqlx::query("DELETE FROM system_settings WHERE key = ?")
@@ -403,4 +510,90 @@ mod tests {
         let value = service.get_setting("delete_me").await.unwrap();
         assert_eq!(value, None);
     }
+
+    #[tokio::test]
+    async fn test_sensitive_keys_encrypted_transparently() {
+        let pool = setup_test_db().await;
+        let encryption = EncryptionService::from_key(&EncryptionService::generate_key()).unwrap();
+        let service = SettingsService::with_encryption(pool.clone(), Some(encryption));
+
+        // Caller does not ask for encryption, but the key is sensitive
+        service
+            .set_setting("aws_secret_access_key", "super_secret", false, Some("admin"))
+            .await
+            .unwrap();
+
+        let (stored, encrypted): (String, i64) = sqlx::query_as(
+            "SELECT value, encrypted FROM system_settings WHERE key = 'aws_secret_access_key'",
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert_eq!(encrypted, 1);
+        assert!(EncryptionService::is_envelope(&stored));
+
+        let value = service.get_setting("aws_secret_access_key").await.unwrap();
+        assert_eq!(value, Some("super_secret".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_encrypt_plaintext_secrets_migration() {
+        let pool = setup_test_db().await;
+
+        sqlx::query(
+            "INSERT INTO system_settings (key, value, encrypted) VALUES ('openai_api_key', 'sk-plain', 0), ('timezone', 'UTC', 0)",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let encryption = EncryptionService::from_key(&EncryptionService::generate_key()).unwrap();
+        let service = SettingsService::with_encryption(pool.clone(), Some(encryption));
+
+        assert_eq!(service.encrypt_plaintext_secrets().await.unwrap().rewritten, 1);
+        // Second run is a no-op
+        assert_eq!(
+            service.encrypt_plaintext_secrets().await.unwrap(),
+            SecretsMigration::default()
+        );
+
+        let (timezone_encrypted,): (i64,) =
+            sqlx::query_as("SELECT encrypted FROM system_settings WHERE key = 'timezone'")
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!(timezone_encrypted, 0);
+
+        let value = service.get_setting("openai_api_key").await.unwrap();
+        assert_eq!(value, Some("sk-plain".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_encrypt_plaintext_secrets_skips_unreadable_rows() {
+        let pool = setup_test_db().await;
+
+        sqlx::query(
+            "INSERT INTO system_settings (key, value, encrypted) VALUES ('aws_secret_access_key', 'not-ciphertext', 1), ('openai_api_key', 'sk-plain', 0)",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let encryption = EncryptionService::from_key(&EncryptionService::generate_key()).unwrap();
+        let service = SettingsService::with_encryption(pool.clone(), Some(encryption));
+
+        let migration = service.encrypt_plaintext_secrets().await.unwrap();
+        assert_eq!(migration.rewritten, 1);
+        assert_eq!(migration.skipped, vec!["aws_secret_access_key".to_string()]);
+
+        let value = service.get_setting("openai_api_key").await.unwrap();
+        assert_eq!(value, Some("sk-plain".to_string()));
+        let (untouched,): (String,) = sqlx::query_as(
+            "SELECT value FROM system_settings WHERE key = 'aws_secret_access_key'",
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert_eq!(untouched, "not-ciphertext");
+    }
 }
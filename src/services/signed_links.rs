@@ -0,0 +1,115 @@
+// src/services/signed_links.rs
+//! Short-lived signed links bound to one purpose and one subject
+//!
+//! Each purpose signs with its own key derived from the JWT secret, so a link can never be
+//! replayed as a session token or as a link issued for another purpose. Expiry is exact.
+
+use chrono::{DateTime, Utc};
+use jsonwebtoken::{decode, encode, Algorithm, DecodingKey, EncodingKey, Header, Validation};
+use serde::{Deserialize, Serialize};
+
+use crate::common::ApiError;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct LinkClaims {
+    sub: String,
+    purpose: String,
+    exp: usize,
+}
+
+/// Why a link was refused
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LinkError {
+    /// Forged, signed for another purpose, or expired
+    Invalid,
+    /// Genuine, but issued for a different subject
+    WrongSubject,
+}
+
+fn signing_key(jwt_secret: &str, purpose: &str) -> Vec<u8> {
+    format!("{}:{}", jwt_secret, purpose).into_bytes()
+}
+
+/// Sign a link for `subject` under `purpose`, valid until `expires_at`
+pub fn issue_link_token(
+    jwt_secret: &str,
+    purpose: &str,
+    subject: &str,
+    expires_at: DateTime<Utc>,
+) -> Result<String, ApiError> {
+    let claims = LinkClaims {
+        sub: subject.to_string(),
+        purpose: purpose.to_string(),
+        exp: expires_at.timestamp() as usize,
+    };
+
+    encode(
+        &Header::new(Algorithm::HS256),
+        &claims,
+        &EncodingKey::from_secret(&signing_key(jwt_secret, purpose)),
+    )
+    .map_err(|e| ApiError::InternalServer(format!("Failed to sign download link: {}", e)))
+}
+
+/// Check a link is genuine, unexpired and issued for exactly this purpose and subject
+pub fn verify_link_token(
+    jwt_secret: &str,
+    purpose: &str,
+    token: &str,
+    subject: &str,
+) -> Result<(), LinkError> {
+    let mut validation = Validation::new(Algorithm::HS256);
+    validation.leeway = 0;
+
+    let claims = decode::<LinkClaims>(
+        token,
+        &DecodingKey::from_secret(&signing_key(jwt_secret, purpose)),
+        &validation,
+    )
+    .map_err(|_| LinkError::Invalid)?
+    .claims;
+
+    if claims.purpose != purpose {
+        return Err(LinkError::Invalid);
+    }
+    if claims.sub != subject {
+        return Err(LinkError::WrongSubject);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn test_link_is_bound_to_purpose_and_subject() {
+        let token =
+            issue_link_token("secret", "a", "S1", Utc::now() + Duration::minutes(1)).unwrap();
+
+        assert_eq!(verify_link_token("secret", "a", &token, "S1"), Ok(()));
+        assert_eq!(
+            verify_link_token("secret", "a", &token, "S2"),
+            Err(LinkError::WrongSubject)
+        );
+        assert_eq!(
+            verify_link_token("secret", "b", &token, "S1"),
+            Err(LinkError::Invalid)
+        );
+        assert_eq!(
+            verify_link_token("other-secret", "a", &token, "S1"),
+            Err(LinkError::Invalid)
+        );
+    }
+
+    #[test]
+    fn test_expired_link_is_rejected() {
+        let token =
+            issue_link_token("secret", "a", "S1", Utc::now() - Duration::seconds(1)).unwrap();
+        assert_eq!(
+            verify_link_token("secret", "a", &token, "S1"),
+            Err(LinkError::Invalid)
+        );
+    }
+}
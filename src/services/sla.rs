@@ -0,0 +1,460 @@
+// src/services/sla.rs
+//! Hiring SLA monitoring: flags applications that sit in a stage longer than its target
+//!
+//! Targets are counted in business days (Monday to Friday, UTC) from when the application
+//! entered its current status. A job's own target for a stage takes precedence over the
+//! global one.
+
+use chrono::{Datelike, Duration, NaiveDateTime, Utc, Weekday};
+use sqlx::SqlitePool;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use tracing::{debug, error, info};
+
+use crate::admin::models::SlaPolicy;
+use crate::common::{generate_history_id, ApiError};
+use crate::services::{org, EmailSender, SettingsService};
+
+/// Application statuses an SLA can be set for; final statuses have nothing left to wait on
+pub const SLA_STAGES: &[&str] = &[
+    "submitted",
+    "reviewed",
+    "shortlisted",
+    "interview_scheduled",
+    "interviewed",
+    "offered",
+];
+
+/// Longest SLA target accepted, roughly a quarter
+pub const MAX_TARGET_BUSINESS_DAYS: i64 = 60;
+
+const DEFAULT_SLA_CHECK_MINUTES: u64 = 60;
+
+const TIMESTAMP_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+fn is_business_day(at: NaiveDateTime) -> bool {
+    !matches!(at.weekday(), Weekday::Sat | Weekday::Sun)
+}
+
+/// `start` moved forward by `days` business days, keeping the time of day
+pub fn add_business_days(start: NaiveDateTime, days: i64) -> NaiveDateTime {
+    let mut at = start;
+    let mut remaining = days;
+    while remaining > 0 {
+        at += Duration::days(1);
+        if is_business_day(at) {
+            remaining -= 1;
+        }
+    }
+    at
+}
+
+/// Whole business days from `from` to `to`; zero when `to` is not later
+pub fn business_days_between(from: NaiveDateTime, to: NaiveDateTime) -> i64 {
+    let mut days = 0;
+    let mut at = from + Duration::days(1);
+    while at <= to {
+        if is_business_day(at) {
+            days += 1;
+        }
+        at += Duration::days(1);
+    }
+    days
+}
+
+pub fn parse_timestamp(value: &str) -> Option<NaiveDateTime> {
+    NaiveDateTime::parse_from_str(value, TIMESTAMP_FORMAT)
+        .ok()
+        .or_else(|| {
+            chrono::DateTime::parse_from_rfc3339(value)
+                .ok()
+                .map(|dt| dt.naive_utc())
+        })
+}
+
+/// Validate a target before it is stored
+pub fn validate_policy(stage: &str, target_business_days: i64) -> Result<(), ApiError> {
+    if !SLA_STAGES.contains(&stage) {
+        return Err(ApiError::ValidationError(format!(
+            "Invalid stage '{}'; expected one of {}",
+            stage,
+            SLA_STAGES.join(", ")
+        )));
+    }
+    if !(1..=MAX_TARGET_BUSINESS_DAYS).contains(&target_business_days) {
+        return Err(ApiError::ValidationError(format!(
+            "target_business_days must be between 1 and {}",
+            MAX_TARGET_BUSINESS_DAYS
+        )));
+    }
+    Ok(())
+}
+
+/// The target that applies to an application of `job_id` in `stage`
+pub fn resolve_policy<'a>(
+    policies: &'a [SlaPolicy],
+    job_id: &str,
+    stage: &str,
+) -> Option<&'a SlaPolicy> {
+    policies
+        .iter()
+        .find(|p| p.stage == stage && p.job_id.as_deref() == Some(job_id))
+        .or_else(|| {
+            policies
+                .iter()
+                .find(|p| p.stage == stage && p.job_id.is_none())
+        })
+}
+
+#[derive(sqlx::FromRow)]
+struct OpenApplication {
+    id: String,
+    job_id: String,
+    status: String,
+    stage_entered_at: Option<String>,
+}
+
+#[derive(sqlx::FromRow)]
+struct OpenBreach {
+    id: String,
+    application_id: String,
+    stage: String,
+    stage_entered_at: String,
+}
+
+/// A breach flagged in this run, pending a recruiter email
+struct NewBreach {
+    id: String,
+    application_id: String,
+    job_title: String,
+    stage: String,
+    due_at: String,
+    recipients: Vec<String>,
+}
+
+/// Outcome of one SLA check
+#[derive(Debug, Default)]
+pub struct SlaCheckSummary {
+    pub flagged: usize,
+    pub resolved: usize,
+    pub notified: usize,
+}
+
+/// Flag applications past their stage target, resolve breaches that no longer apply, and
+/// email the recruiters named on the breached targets plus the job's assigned team
+pub async fn check_sla_breaches(
+    pool: &SqlitePool,
+    email_sender: &dyn EmailSender,
+    frontend_url: &str,
+) -> Result<SlaCheckSummary, sqlx::Error> {
+    let policies = sqlx::query_as::<_, SlaPolicy>("SELECT * FROM sla_policies")
+        .fetch_all(pool)
+        .await?;
+
+    // The status was entered at its most recent history entry, or on application
+    let applications = sqlx::query_as::<_, OpenApplication>(
+        r#"
+        SELECT a.id, a.job_id, a.status,
+               COALESCE(
+                   (SELECT MAX(h.changed_at) FROM application_status_history h
+                    WHERE h.application_id = a.id AND h.status = a.status),
+                   a.applied_at
+               ) AS stage_entered_at
+        FROM applications a
+        WHERE a.archived_at IS NULL
+          AND a.status IN ('submitted', 'reviewed', 'shortlisted', 'interview_scheduled', 'interviewed', 'offered')
+        "#,
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let now = Utc::now().naive_utc();
+    let mut summary = SlaCheckSummary::default();
+    let mut breaching = HashSet::new();
+    let mut new_breaches = Vec::new();
+
+    for application in &applications {
+        let Some(policy) = resolve_policy(&policies, &application.job_id, &application.status)
+        else {
+            continue;
+        };
+        let Some(entered) = application
+            .stage_entered_at
+            .as_deref()
+            .and_then(parse_timestamp)
+        else {
+            continue;
+        };
+        let due = add_business_days(entered, policy.target_business_days);
+        if now <= due {
+            continue;
+        }
+
+        let entered_at = entered.format(TIMESTAMP_FORMAT).to_string();
+        breaching.insert((
+            application.id.clone(),
+            application.status.clone(),
+            entered_at.clone(),
+        ));
+
+        let id = generate_history_id();
+        let due_at = due.format(TIMESTAMP_FORMAT).to_string();
+        let result = sqlx::query(
+            r#"
+            INSERT OR IGNORE INTO application_sla_breaches
+                (id, application_id, job_id, policy_id, stage, stage_entered_at, due_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(&id)
+        .bind(&application.id)
+        .bind(&application.job_id)
+        .bind(&policy.id)
+        .bind(&application.status)
+        .bind(&entered_at)
+        .bind(&due_at)
+        .execute(pool)
+        .await?;
+
+        if result.rows_affected() == 1 {
+            summary.flagged += 1;
+            let assignees = org::job_assignee_emails(pool, &application.job_id).await?;
+            let recipients = org::merge_recipients(policy.notify_email.as_deref(), &assignees);
+            if !recipients.is_empty() {
+                let job_title: Option<String> =
+                    sqlx::query_scalar("SELECT title FROM jobs WHERE id = ?")
+                        .bind(&application.job_id)
+                        .fetch_optional(pool)
+                        .await?;
+                new_breaches.push(NewBreach {
+                    id,
+                    application_id: application.id.clone(),
+                    job_title: job_title.unwrap_or_else(|| application.job_id.clone()),
+                    stage: application.status.clone(),
+                    due_at,
+                    recipients,
+                });
+            }
+        }
+    }
+
+    // Applications that moved on, were archived, or lost their target are no longer stale
+    let open = sqlx::query_as::<_, OpenBreach>(
+        "SELECT id, application_id, stage, stage_entered_at FROM application_sla_breaches WHERE resolved_at IS NULL",
+    )
+    .fetch_all(pool)
+    .await?;
+    for breach in open {
+        let key = (breach.application_id, breach.stage, breach.stage_entered_at);
+        if breaching.contains(&key) {
+            continue;
+        }
+        sqlx::query(
+            "UPDATE application_sla_breaches SET resolved_at = datetime('now') WHERE id = ?",
+        )
+        .bind(&breach.id)
+        .execute(pool)
+        .await?;
+        summary.resolved += 1;
+    }
+
+    summary.notified = notify_recruiters(pool, email_sender, frontend_url, new_breaches).await;
+
+    if summary.flagged > 0 || summary.resolved > 0 {
+        info!(
+            flagged = summary.flagged,
+            resolved = summary.resolved,
+            notified = summary.notified,
+            "SLA check finished"
+        );
+    }
+
+    Ok(summary)
+}
+
+/// One email per recipient list covering all of its new breaches; returns breaches notified
+async fn notify_recruiters(
+    pool: &SqlitePool,
+    email_sender: &dyn EmailSender,
+    frontend_url: &str,
+    breaches: Vec<NewBreach>,
+) -> usize {
+    let mut by_recipients: HashMap<String, Vec<NewBreach>> = HashMap::new();
+    for breach in breaches {
+        by_recipients
+            .entry(breach.recipients.join(","))
+            .or_default()
+            .push(breach);
+    }
+
+    let mut notified = 0;
+
+    for (notify_email, breaches) in by_recipients {
+        let recipients = breaches[0].recipients.clone();
+
+        let subject = format!(
+            "{} application{} past the hiring SLA",
+            breaches.len(),
+            if breaches.len() == 1 { "" } else { "s" }
+        );
+        let items: String = breaches
+            .iter()
+            .map(|b| {
+                format!(
+                    "<li><a href=\"{}/admin/applications/{}\">{}</a>: {} since before {} UTC</li>",
+                    frontend_url,
+                    b.application_id,
+                    b.job_title,
+                    b.stage.replace('_', " "),
+                    b.due_at
+                )
+            })
+            .collect();
+        let body = format!(
+            "<p>These applications have waited in their stage longer than the SLA target:</p><ul>{}</ul><p>See all stale applications under Admin &rarr; Applications.</p>",
+            items
+        );
+
+        match email_sender
+            .send_email(recipients, &subject, &body, None)
+            .await
+        {
+            Ok(_) => {
+                for breach in &breaches {
+                    let _ = sqlx::query(
+                        "UPDATE application_sla_breaches SET notified_at = datetime('now') WHERE id = ?",
+                    )
+                    .bind(&breach.id)
+                    .execute(pool)
+                    .await;
+                }
+                notified += breaches.len();
+            }
+            Err(e) => {
+                error!(error = %e, recipients = %notify_email, "Failed to send SLA breach email");
+            }
+        }
+    }
+
+    notified
+}
+
+/// Check SLAs every `sla_check_minutes` (default 60; 0 disables)
+pub fn start_sla_monitor_task(
+    pool: SqlitePool,
+    settings_service: Arc<SettingsService>,
+    email_sender: Arc<dyn EmailSender>,
+    frontend_url: String,
+) {
+    tokio::spawn(async move {
+        loop {
+            let minutes = settings_service
+                .get_setting("sla_check_minutes")
+                .await
+                .ok()
+                .flatten()
+                .and_then(|v| v.parse::<u64>().ok())
+                .unwrap_or(DEFAULT_SLA_CHECK_MINUTES);
+
+            if !crate::common::shutdown::sleep(std::time::Duration::from_secs(
+                minutes.clamp(1, 24 * 60) * 60,
+            ))
+            .await
+            {
+                break;
+            }
+            let _running = crate::common::shutdown::track();
+
+            if minutes == 0 {
+                continue;
+            }
+
+            if let Err(e) = check_sla_breaches(&pool, email_sender.as_ref(), &frontend_url).await {
+                debug!(error = %e, "Skipped SLA check");
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(value: &str) -> NaiveDateTime {
+        parse_timestamp(value).unwrap()
+    }
+
+    fn policy(id: &str, job_id: Option<&str>, stage: &str) -> SlaPolicy {
+        SlaPolicy {
+            id: id.to_string(),
+            job_id: job_id.map(str::to_string),
+            stage: stage.to_string(),
+            target_business_days: 5,
+            notify_email: None,
+            created_by: None,
+            created_at: None,
+            updated_at: None,
+        }
+    }
+
+    #[test]
+    fn test_add_business_days_skips_weekends() {
+        // Thursday + 5 business days is the following Thursday
+        assert_eq!(
+            add_business_days(at("2026-03-05 14:00:00"), 5),
+            at("2026-03-12 14:00:00")
+        );
+        // Saturday counts from the next Monday
+        assert_eq!(
+            add_business_days(at("2026-03-07 09:00:00"), 1),
+            at("2026-03-09 09:00:00")
+        );
+        assert_eq!(
+            add_business_days(at("2026-03-06 09:00:00"), 1),
+            at("2026-03-09 09:00:00")
+        );
+    }
+
+    #[test]
+    fn test_business_days_between() {
+        assert_eq!(
+            business_days_between(at("2026-03-06 09:00:00"), at("2026-03-10 10:00:00")),
+            2
+        );
+        assert_eq!(
+            business_days_between(at("2026-03-10 10:00:00"), at("2026-03-06 09:00:00")),
+            0
+        );
+    }
+
+    #[test]
+    fn test_resolve_policy_prefers_job_target() {
+        let policies = vec![
+            policy("global", None, "submitted"),
+            policy("job", Some("J_1"), "submitted"),
+            policy("global-review", None, "reviewed"),
+        ];
+
+        assert_eq!(
+            resolve_policy(&policies, "J_1", "submitted").unwrap().id,
+            "job"
+        );
+        assert_eq!(
+            resolve_policy(&policies, "J_2", "submitted").unwrap().id,
+            "global"
+        );
+        assert_eq!(
+            resolve_policy(&policies, "J_1", "reviewed").unwrap().id,
+            "global-review"
+        );
+        assert!(resolve_policy(&policies, "J_1", "offered").is_none());
+    }
+
+    #[test]
+    fn test_validate_policy() {
+        assert!(validate_policy("submitted", 5).is_ok());
+        assert!(validate_policy("hired", 5).is_err());
+        assert!(validate_policy("submitted", 0).is_err());
+        assert!(validate_policy("submitted", MAX_TARGET_BUSINESS_DAYS + 1).is_err());
+    }
+}
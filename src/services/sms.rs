@@ -0,0 +1,557 @@
+// src/services/sms.rs
+//! SMS for time-critical candidate notifications
+//!
+//! Texts go through Twilio or AWS SNS, whichever the `sms_provider` setting selects; with
+//! neither configured nothing is texted. Candidates opt in from their profile, and only a
+//! verified phone number is ever texted. Reminders that fall in a candidate's quiet hours
+//! (local time) are skipped rather than held back, since "your interview starts in an
+//! hour" is no use the next morning. Every attempt, skipped ones included, is written to
+//! `sms_deliveries`.
+
+use chrono::Utc;
+use reqwest::Client;
+use serde_json::Value;
+use sqlx::SqlitePool;
+use std::sync::Arc;
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+use crate::admin::models::SmsDelivery;
+use crate::common::timezone::{display_stored, local_hour, user_timezone, DEFAULT_TIMEZONE};
+use crate::profile::models::SmsPreferences;
+use crate::services::pii;
+use crate::services::{notification_preferences, AWSService, SettingsService};
+
+/// What a text can be about
+pub const SMS_KINDS: &[&str] = &["verification", "interview_reminder", "offer_expiring"];
+
+/// Delivery log statuses
+pub const SMS_STATUSES: &[&str] = &["sent", "failed", "skipped"];
+
+/// How far ahead of an interview the reminder goes out
+pub const INTERVIEW_REMINDER_MINUTES: i64 = 60;
+
+/// How far ahead of an offer's deadline the reminder goes out
+pub const OFFER_EXPIRY_WARNING_HOURS: i64 = 24;
+
+const REMINDER_CHECK_INTERVAL_SECS: u64 = 5 * 60;
+
+/// Longest text sent; two concatenated GSM-7 segments
+const MAX_SMS_CHARS: usize = 306;
+
+/// Service texts are sent through
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SmsProvider {
+    Twilio,
+    Sns,
+}
+
+impl SmsProvider {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SmsProvider::Twilio => "twilio",
+            SmsProvider::Sns => "sns",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "twilio" => Some(SmsProvider::Twilio),
+            "sns" => Some(SmsProvider::Sns),
+            _ => None,
+        }
+    }
+}
+
+/// Sends texts through the configured provider
+#[derive(Debug)]
+pub struct SmsService {
+    settings_service: Arc<SettingsService>,
+    aws_service: Arc<AWSService>,
+    http: Client,
+}
+
+impl SmsService {
+    pub fn new(
+        settings_service: Arc<SettingsService>,
+        aws_service: Arc<AWSService>,
+        http: Client,
+    ) -> Self {
+        Self {
+            settings_service,
+            aws_service,
+            http,
+        }
+    }
+
+    /// The provider selected by the `sms_provider` setting, if any
+    pub async fn provider(&self) -> Option<SmsProvider> {
+        self.settings_service
+            .get_setting("sms_provider")
+            .await
+            .ok()
+            .flatten()
+            .and_then(|v| SmsProvider::parse(&v))
+    }
+
+    /// Send a text; returns the provider used and its message ID
+    pub async fn send(
+        &self,
+        phone: &str,
+        body: &str,
+    ) -> Result<(SmsProvider, Option<String>), String> {
+        let provider = self
+            .provider()
+            .await
+            .ok_or_else(|| "SMS delivery is not configured".to_string())?;
+        let body = truncate(body);
+        let message_id = match provider {
+            SmsProvider::Twilio => self.send_twilio(phone, &body).await?,
+            SmsProvider::Sns => Some(
+                self.aws_service
+                    .publish_sms(phone, &body)
+                    .await
+                    .map_err(|e| e.to_string())?,
+            )
+            .filter(|id| !id.is_empty()),
+        };
+        Ok((provider, message_id))
+    }
+
+    async fn send_twilio(&self, phone: &str, body: &str) -> Result<Option<String>, String> {
+        let settings = self
+            .settings_service
+            .get_settings(&[
+                "twilio_account_sid",
+                "twilio_auth_token",
+                "twilio_from_number",
+            ])
+            .await
+            .map_err(|e| e.to_string())?;
+        let setting = |key: &str| {
+            settings
+                .get(key)
+                .cloned()
+                .flatten()
+                .filter(|v| !v.trim().is_empty())
+                .ok_or_else(|| format!("{} is not configured", key))
+        };
+        let account_sid = setting("twilio_account_sid")?;
+        let auth_token = setting("twilio_auth_token")?;
+        let from = setting("twilio_from_number")?;
+
+        let response = self
+            .http
+            .post(format!(
+                "https://api.twilio.com/2010-04-01/Accounts/{}/Messages.json",
+                account_sid
+            ))
+            .basic_auth(&account_sid, Some(&auth_token))
+            .form(&[("To", phone), ("From", from.as_str()), ("Body", body)])
+            .send()
+            .await
+            .map_err(|e| format!("Twilio request failed: {}", e))?;
+
+        let status = response.status();
+        let payload: Value = response.json().await.unwrap_or(Value::Null);
+        if !status.is_success() {
+            return Err(format!(
+                "Twilio returned {}: {}",
+                status,
+                payload["message"].as_str().unwrap_or("unknown error")
+            ));
+        }
+        Ok(payload["sid"].as_str().map(str::to_string))
+    }
+}
+
+fn truncate(body: &str) -> String {
+    if body.chars().count() <= MAX_SMS_CHARS {
+        return body.to_string();
+    }
+    let mut text: String = body.chars().take(MAX_SMS_CHARS - 1).collect();
+    text.push('…');
+    text
+}
+
+/// Whether a local hour falls in quiet hours running from `start` up to `end`, which may
+/// wrap past midnight. Equal or missing hours mean no quiet hours.
+pub fn in_quiet_hours(start: Option<i64>, end: Option<i64>, hour: u32) -> bool {
+    let (Some(start), Some(end)) = (start, end) else {
+        return false;
+    };
+    let hour = i64::from(hour);
+    if start < end {
+        (start..end).contains(&hour)
+    } else if start > end {
+        hour >= start || hour < end
+    } else {
+        false
+    }
+}
+
+pub fn validate_quiet_hours(start: Option<i64>, end: Option<i64>) -> Result<(), String> {
+    match (start, end) {
+        (None, None) => Ok(()),
+        (Some(start), Some(end)) if (0..24).contains(&start) && (0..24).contains(&end) => Ok(()),
+        (Some(_), Some(_)) => Err("Quiet hours must be between 0 and 23".to_string()),
+        _ => Err("Set both the start and end of quiet hours, or neither".to_string()),
+    }
+}
+
+pub async fn save_preferences(
+    pool: &SqlitePool,
+    user_id: &str,
+    prefs: &SmsPreferences,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        r#"
+        INSERT INTO sms_preferences (user_id, opted_in, quiet_hours_start, quiet_hours_end, updated_at)
+        VALUES (?, ?, ?, ?, datetime('now'))
+        ON CONFLICT(user_id) DO UPDATE SET
+            opted_in = excluded.opted_in,
+            quiet_hours_start = excluded.quiet_hours_start,
+            quiet_hours_end = excluded.quiet_hours_end,
+            updated_at = datetime('now')
+        "#,
+    )
+    .bind(user_id)
+    .bind(prefs.opted_in)
+    .bind(prefs.quiet_hours_start)
+    .bind(prefs.quiet_hours_end)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+struct Delivery<'a> {
+    user_id: Option<&'a str>,
+    phone: &'a str,
+    kind: &'a str,
+    reference_id: Option<&'a str>,
+    body: &'a str,
+}
+
+async fn log_delivery(
+    pool: &SqlitePool,
+    delivery: &Delivery<'_>,
+    provider: Option<SmsProvider>,
+    status: &str,
+    provider_message_id: Option<&str>,
+    error: Option<&str>,
+) {
+    let phone = match pii::seal(delivery.phone) {
+        Ok(phone) => phone,
+        Err(e) => {
+            warn!(error = %e, kind = %delivery.kind, "Failed to log SMS delivery");
+            return;
+        }
+    };
+    let result = sqlx::query(
+        r#"
+        INSERT INTO sms_deliveries
+            (id, user_id, phone, kind, reference_id, body, provider, status, provider_message_id, error)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+        "#,
+    )
+    .bind(Uuid::new_v4().to_string())
+    .bind(delivery.user_id)
+    .bind(&phone)
+    .bind(delivery.kind)
+    .bind(delivery.reference_id)
+    .bind(delivery.body)
+    .bind(provider.map(|p| p.as_str()))
+    .bind(status)
+    .bind(provider_message_id)
+    .bind(error)
+    .execute(pool)
+    .await;
+    if let Err(e) = result {
+        warn!(error = %e, kind = %delivery.kind, "Failed to log SMS delivery");
+    }
+}
+
+async fn deliver(pool: &SqlitePool, sms: &SmsService, delivery: Delivery<'_>) -> bool {
+    match sms.send(delivery.phone, delivery.body).await {
+        Ok((provider, message_id)) => {
+            log_delivery(
+                pool,
+                &delivery,
+                Some(provider),
+                "sent",
+                message_id.as_deref(),
+                None,
+            )
+            .await;
+            true
+        }
+        Err(e) => {
+            warn!(error = %e, kind = %delivery.kind, "SMS delivery failed");
+            log_delivery(
+                pool,
+                &delivery,
+                sms.provider().await,
+                "failed",
+                None,
+                Some(&e),
+            )
+            .await;
+            false
+        }
+    }
+}
+
+/// Text a verification code to a number being added to a profile. Opt-in and quiet hours
+/// do not apply, since the candidate just asked for it; the logged body hides the code.
+pub async fn send_verification_code(
+    pool: &SqlitePool,
+    sms: &SmsService,
+    user_id: &str,
+    phone: &str,
+    code: &str,
+) -> Result<(), String> {
+    let delivery = Delivery {
+        user_id: Some(user_id),
+        phone,
+        kind: "verification",
+        reference_id: None,
+        body: "Your verification code is ******",
+    };
+    match sms
+        .send(phone, &format!("Your verification code is {}", code))
+        .await
+    {
+        Ok((provider, message_id)) => {
+            log_delivery(
+                pool,
+                &delivery,
+                Some(provider),
+                "sent",
+                message_id.as_deref(),
+                None,
+            )
+            .await;
+            Ok(())
+        }
+        Err(e) => {
+            log_delivery(
+                pool,
+                &delivery,
+                sms.provider().await,
+                "failed",
+                None,
+                Some(&e),
+            )
+            .await;
+            Err(e)
+        }
+    }
+}
+
+/// Text a candidate who opted in, unless it is their quiet hours. Returns whether a text
+/// went out.
+pub async fn notify(
+    pool: &SqlitePool,
+    sms: &SmsService,
+    user_id: &str,
+    kind: &str,
+    reference_id: &str,
+    body: &str,
+) -> Result<bool, sqlx::Error> {
+    let prefs = notification_preferences::sms(pool, user_id).await?;
+    let Some(phone) = prefs.phone.as_deref().filter(|_| prefs.opted_in) else {
+        return Ok(false);
+    };
+    let delivery = Delivery {
+        user_id: Some(user_id),
+        phone,
+        kind,
+        reference_id: Some(reference_id),
+        body,
+    };
+
+    let zone = user_timezone(pool, user_id)
+        .await
+        .unwrap_or_else(|| DEFAULT_TIMEZONE.to_string());
+    if in_quiet_hours(
+        prefs.quiet_hours_start,
+        prefs.quiet_hours_end,
+        local_hour(Utc::now(), &zone),
+    ) {
+        log_delivery(pool, &delivery, None, "skipped", None, Some("quiet hours")).await;
+        return Ok(false);
+    }
+
+    Ok(deliver(pool, sms, delivery).await)
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct DueReminder {
+    reference_id: String,
+    user_id: String,
+    at: String,
+    job_title: Option<String>,
+}
+
+/// Text reminders for interviews starting within the hour and offers expiring within a
+/// day. Each interview or offer is handled once, whether it was texted or skipped.
+pub async fn send_due_reminders(pool: &SqlitePool, sms: &SmsService) -> Result<usize, sqlx::Error> {
+    let interviews = sqlx::query_as::<_, DueReminder>(
+        r#"
+        SELECT i.id AS reference_id, i.candidate_id AS user_id, i.scheduled_date AS at,
+               j.title AS job_title
+        FROM interviews i
+        LEFT JOIN jobs j ON j.id = i.job_id
+        WHERE COALESCE(i.status, 'scheduled') = 'scheduled'
+          AND datetime(i.scheduled_date) > datetime('now')
+          AND datetime(i.scheduled_date) <= datetime('now', ?)
+          AND NOT EXISTS (
+              SELECT 1 FROM sms_deliveries d
+              WHERE d.kind = 'interview_reminder' AND d.reference_id = i.id
+          )
+        "#,
+    )
+    .bind(format!("+{} minutes", INTERVIEW_REMINDER_MINUTES))
+    .fetch_all(pool)
+    .await?;
+
+    let offers = sqlx::query_as::<_, DueReminder>(
+        r#"
+        SELECT a.id AS reference_id, a.user_id, a.offer_expires_at AS at, j.title AS job_title
+        FROM applications a
+        LEFT JOIN jobs j ON j.id = a.job_id
+        WHERE a.status = 'offered'
+          AND a.offer_expires_at IS NOT NULL
+          AND datetime(a.offer_expires_at) > datetime('now')
+          AND datetime(a.offer_expires_at) <= datetime('now', ?)
+          AND NOT EXISTS (
+              SELECT 1 FROM sms_deliveries d
+              WHERE d.kind = 'offer_expiring' AND d.reference_id = a.id
+          )
+        "#,
+    )
+    .bind(format!("+{} hours", OFFER_EXPIRY_WARNING_HOURS))
+    .fetch_all(pool)
+    .await?;
+
+    let mut sent = 0;
+    for (kind, reminders) in [
+        ("interview_reminder", interviews),
+        ("offer_expiring", offers),
+    ] {
+        for reminder in reminders {
+            let zone = user_timezone(pool, &reminder.user_id)
+                .await
+                .unwrap_or_else(|| DEFAULT_TIMEZONE.to_string());
+            let at = display_stored(&reminder.at, &zone);
+            let job = reminder.job_title.as_deref().unwrap_or("your application");
+            let body = if kind == "interview_reminder" {
+                format!("Reminder: your interview for {} starts at {}.", job, at)
+            } else {
+                format!(
+                    "Your offer for {} expires at {}. Reply from your candidate portal before then.",
+                    job, at
+                )
+            };
+            if notify(
+                pool,
+                sms,
+                &reminder.user_id,
+                kind,
+                &reminder.reference_id,
+                &body,
+            )
+            .await?
+            {
+                sent += 1;
+            }
+        }
+    }
+    Ok(sent)
+}
+
+/// The delivery log, newest first
+pub async fn list_deliveries(
+    pool: &SqlitePool,
+    user_id: Option<&str>,
+    kind: Option<&str>,
+    status: Option<&str>,
+    limit: i64,
+) -> Result<Vec<SmsDelivery>, sqlx::Error> {
+    sqlx::query_as::<_, SmsDelivery>(
+        r#"
+        SELECT * FROM sms_deliveries
+        WHERE (?1 IS NULL OR user_id = ?1)
+          AND (?2 IS NULL OR kind = ?2)
+          AND (?3 IS NULL OR status = ?3)
+        ORDER BY created_at DESC
+        LIMIT ?4
+        "#,
+    )
+    .bind(user_id)
+    .bind(kind)
+    .bind(status)
+    .bind(limit)
+    .fetch_all(pool)
+    .await
+}
+
+/// Periodically text interview and expiring-offer reminders while a provider is configured
+pub fn start_sms_reminder_task(pool: SqlitePool, sms_service: Arc<SmsService>) {
+    tokio::spawn(async move {
+        loop {
+            if !crate::common::shutdown::sleep(std::time::Duration::from_secs(
+                REMINDER_CHECK_INTERVAL_SECS,
+            ))
+            .await
+            {
+                break;
+            }
+            let _running = crate::common::shutdown::track();
+
+            if sms_service.provider().await.is_none() {
+                continue;
+            }
+            match send_due_reminders(&pool, &sms_service).await {
+                Ok(0) => {}
+                Ok(sent) => info!(sent, "SMS reminders sent"),
+                Err(e) => debug!(error = %e, "Skipped SMS reminder run"),
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quiet_hours_wrap_past_midnight() {
+        assert!(in_quiet_hours(Some(22), Some(7), 23));
+        assert!(in_quiet_hours(Some(22), Some(7), 3));
+        assert!(!in_quiet_hours(Some(22), Some(7), 7));
+        assert!(!in_quiet_hours(Some(22), Some(7), 12));
+
+        assert!(in_quiet_hours(Some(13), Some(14), 13));
+        assert!(!in_quiet_hours(Some(13), Some(14), 14));
+        assert!(!in_quiet_hours(Some(9), Some(9), 9));
+        assert!(!in_quiet_hours(None, Some(7), 3));
+    }
+
+    #[test]
+    fn test_validate_quiet_hours_needs_both_ends() {
+        assert!(validate_quiet_hours(None, None).is_ok());
+        assert!(validate_quiet_hours(Some(22), Some(7)).is_ok());
+        assert!(validate_quiet_hours(Some(22), None).is_err());
+        assert!(validate_quiet_hours(Some(24), Some(7)).is_err());
+    }
+
+    #[test]
+    fn test_long_texts_are_truncated() {
+        let long = "a".repeat(400);
+        assert_eq!(truncate(&long).chars().count(), MAX_SMS_CHARS);
+        assert_eq!(truncate("short"), "short");
+        assert_eq!(SmsProvider::parse(" Twilio "), Some(SmsProvider::Twilio));
+        assert_eq!(SmsProvider::parse("carrier-pigeon"), None);
+    }
+}
@@ -0,0 +1,226 @@
+// src/services/snippets.rs
+//! Canned responses for recruiter messaging
+//!
+//! A snippet is typed as `/shortcut` at the start of a line or after a space, and expanded
+//! when an admin message, candidate email or broadcast is sent. Placeholders in the snippet
+//! body are filled for the recipient when one is known; broadcasts keep them so they can be
+//! filled per candidate at delivery.
+
+use std::collections::HashMap;
+
+use sqlx::SqlitePool;
+
+use crate::common::ApiError;
+use crate::services::broadcasts::{
+    fill_recipient_placeholders, recipient_context, RecipientContext,
+};
+
+pub const MAX_SHORTCUT_LENGTH: usize = 32;
+
+pub const MAX_TITLE_LENGTH: usize = 100;
+
+pub const MAX_SNIPPET_LENGTH: usize = 5000;
+
+fn is_shortcut_char(c: char) -> bool {
+    c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_' || c == '-'
+}
+
+/// Lowercase a shortcut, dropping a leading `/`, and check its characters
+pub fn normalize_shortcut(input: &str) -> Result<String, ApiError> {
+    let shortcut = input.trim().trim_start_matches('/').to_lowercase();
+    if shortcut.is_empty() {
+        return Err(ApiError::ValidationError(
+            "Shortcut cannot be empty".to_string(),
+        ));
+    }
+    if shortcut.len() > MAX_SHORTCUT_LENGTH {
+        return Err(ApiError::ValidationError(format!(
+            "Shortcut exceeds maximum length of {} characters",
+            MAX_SHORTCUT_LENGTH
+        )));
+    }
+    if !shortcut.chars().all(is_shortcut_char) {
+        return Err(ApiError::ValidationError(
+            "Shortcut may only contain letters, digits, '-' and '_'".to_string(),
+        ));
+    }
+    Ok(shortcut)
+}
+
+/// Check a snippet's title and body
+pub fn validate_snippet(title: &str, body: &str) -> Result<(), ApiError> {
+    if title.trim().is_empty() {
+        return Err(ApiError::ValidationError(
+            "Title cannot be empty".to_string(),
+        ));
+    }
+    if title.chars().count() > MAX_TITLE_LENGTH {
+        return Err(ApiError::ValidationError(format!(
+            "Title exceeds maximum length of {} characters",
+            MAX_TITLE_LENGTH
+        )));
+    }
+    if body.trim().is_empty() {
+        return Err(ApiError::ValidationError(
+            "Body cannot be empty".to_string(),
+        ));
+    }
+    if body.chars().count() > MAX_SNIPPET_LENGTH {
+        return Err(ApiError::ValidationError(format!(
+            "Body exceeds maximum length of {} characters",
+            MAX_SNIPPET_LENGTH
+        )));
+    }
+    Ok(())
+}
+
+/// Replace every known `/shortcut` in `text` with its snippet body, filling placeholders in
+/// the inserted text when `context` is given. Unknown shortcuts, and slashes inside words or
+/// URLs, are left alone. Returns the expanded text and the shortcuts used.
+pub fn expand_shortcuts(
+    text: &str,
+    snippets: &HashMap<String, String>,
+    context: Option<&RecipientContext>,
+) -> (String, Vec<String>) {
+    let mut expanded = String::with_capacity(text.len());
+    let mut used: Vec<String> = Vec::new();
+    let mut previous: Option<char> = None;
+    let mut rest = text;
+
+    while let Some(c) = rest.chars().next() {
+        if c == '/' && previous.is_none_or(char::is_whitespace) {
+            let name_len = rest[1..]
+                .find(|ch: char| !is_shortcut_char(ch))
+                .unwrap_or(rest.len() - 1);
+            let name = &rest[1..1 + name_len];
+            if let Some(body) = snippets.get(name) {
+                match context {
+                    Some(context) => expanded.push_str(&fill_recipient_placeholders(body, context)),
+                    None => expanded.push_str(body),
+                }
+                if !used.iter().any(|u| u == name) {
+                    used.push(name.to_string());
+                }
+                rest = &rest[1 + name_len..];
+                previous = name.chars().last();
+                continue;
+            }
+        }
+        expanded.push(c);
+        previous = Some(c);
+        rest = &rest[c.len_utf8()..];
+    }
+    (expanded, used)
+}
+
+/// Every snippet body by shortcut
+pub async fn load_snippets(pool: &SqlitePool) -> Result<HashMap<String, String>, sqlx::Error> {
+    let rows = sqlx::query_as::<_, (String, String)>("SELECT shortcut, body FROM message_snippets")
+        .fetch_all(pool)
+        .await?;
+    Ok(rows.into_iter().collect())
+}
+
+/// Expand the shortcuts in a draft to `user_id`, or in a template when no recipient is given
+///
+/// Without `application_id`, placeholders are filled from the candidate's most recent open
+/// application.
+pub async fn expand(
+    pool: &SqlitePool,
+    text: &str,
+    user_id: Option<&str>,
+    application_id: Option<&str>,
+) -> Result<(String, Vec<String>), ApiError> {
+    if !text.contains('/') {
+        return Ok((text.to_string(), Vec::new()));
+    }
+    let snippets = load_snippets(pool).await.map_err(ApiError::DatabaseError)?;
+    if snippets.is_empty() {
+        return Ok((text.to_string(), Vec::new()));
+    }
+
+    let context = match user_id {
+        Some(user_id) => {
+            let application_id = match application_id {
+                Some(id) => Some(id.to_string()),
+                None => sqlx::query_scalar::<_, String>(
+                    r#"
+                    SELECT id FROM applications
+                    WHERE user_id = ? AND archived_at IS NULL
+                      AND status NOT IN ('rejected', 'withdrawn')
+                    ORDER BY applied_at DESC
+                    LIMIT 1
+                    "#,
+                )
+                .bind(user_id)
+                .fetch_optional(pool)
+                .await
+                .map_err(ApiError::DatabaseError)?,
+            };
+            recipient_context(pool, user_id, application_id.as_deref())
+                .await
+                .map_err(ApiError::DatabaseError)?
+        }
+        None => None,
+    };
+
+    Ok(expand_shortcuts(text, &snippets, context.as_ref()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snippets() -> HashMap<String, String> {
+        HashMap::from([
+            (
+                "thanks".to_string(),
+                "Thanks for applying, {name}!".to_string(),
+            ),
+            (
+                "next-steps".to_string(),
+                "We'll be in touch about {job}.".to_string(),
+            ),
+        ])
+    }
+
+    #[test]
+    fn test_normalize_shortcut() {
+        assert_eq!(normalize_shortcut("/Thanks").unwrap(), "thanks");
+        assert_eq!(normalize_shortcut("next-steps_2").unwrap(), "next-steps_2");
+        assert!(normalize_shortcut("/").is_err());
+        assert!(normalize_shortcut("two words").is_err());
+        assert!(normalize_shortcut(&"a".repeat(MAX_SHORTCUT_LENGTH + 1)).is_err());
+    }
+
+    #[test]
+    fn test_expand_shortcuts_fills_placeholders_for_recipient() {
+        let context = RecipientContext {
+            email: "ana@example.com".to_string(),
+            name: Some("Ana".to_string()),
+            job_title: Some("Line Cook".to_string()),
+            ..Default::default()
+        };
+        let (text, used) =
+            expand_shortcuts("/thanks\n/next-steps /thanks", &snippets(), Some(&context));
+        assert_eq!(
+            text,
+            "Thanks for applying, Ana!\nWe'll be in touch about Line Cook. Thanks for applying, Ana!"
+        );
+        assert_eq!(used, vec!["thanks", "next-steps"]);
+    }
+
+    #[test]
+    fn test_expand_shortcuts_leaves_urls_and_unknown_shortcuts() {
+        let (text, used) = expand_shortcuts(
+            "See https://example.com/thanks and/or /unknown. /thanks",
+            &snippets(),
+            None,
+        );
+        assert_eq!(
+            text,
+            "See https://example.com/thanks and/or /unknown. Thanks for applying, {name}!"
+        );
+        assert_eq!(used, vec!["thanks"]);
+    }
+}
@@ -0,0 +1,457 @@
+// src/services/social.rs
+//! Scheduled publishing of job posts to social networks
+//!
+//! Admins queue a post (caption, generated image, link) for a time and a set of networks;
+//! each network gets its own row so publish status is tracked per platform. A background
+//! task publishes due rows through the LinkedIn API, the X API or a Buffer webhook and
+//! retries failures with backoff.
+
+use chrono::{DateTime, Duration, SecondsFormat, Utc};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sqlx::{FromRow, SqlitePool};
+use std::sync::Arc;
+use tracing::{debug, error, info, warn};
+
+use crate::services::SettingsService;
+
+/// Attempts before a post is marked failed
+pub const MAX_PUBLISH_ATTEMPTS: i64 = 3;
+
+/// Posts claimed per worker pass
+const PUBLISH_BATCH_SIZE: i64 = 20;
+
+const PUBLISH_INTERVAL_SECONDS: u64 = 60;
+
+/// Network a post is published to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SocialNetwork {
+    Linkedin,
+    X,
+    Buffer,
+}
+
+impl SocialNetwork {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SocialNetwork::Linkedin => "linkedin",
+            SocialNetwork::X => "x",
+            SocialNetwork::Buffer => "buffer",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "linkedin" => Some(SocialNetwork::Linkedin),
+            "x" => Some(SocialNetwork::X),
+            "buffer" => Some(SocialNetwork::Buffer),
+            _ => None,
+        }
+    }
+
+    /// Longest text the network accepts
+    fn text_limit(&self) -> usize {
+        match self {
+            SocialNetwork::Linkedin => 3000,
+            SocialNetwork::X => 280,
+            SocialNetwork::Buffer => 3000,
+        }
+    }
+}
+
+/// One network's copy of a scheduled post
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ScheduledSocialPost {
+    pub id: String,
+    /// Shared by the rows created from one scheduling request
+    pub group_id: String,
+    pub job_id: String,
+    pub network: String,
+    pub caption: String,
+    pub image_url: Option<String>,
+    pub link_url: Option<String>,
+    /// UTC, RFC3339
+    pub scheduled_for: String,
+    pub scheduled_timezone: Option<String>,
+    /// scheduled, publishing, published, failed or cancelled
+    pub status: String,
+    pub attempts: i64,
+    pub external_id: Option<String>,
+    pub external_url: Option<String>,
+    pub last_error: Option<String>,
+    pub created_by: String,
+    pub created_at: String,
+    pub updated_at: String,
+    pub published_at: Option<String>,
+}
+
+/// Where a published post landed
+#[derive(Debug, Default)]
+pub struct PublishedPost {
+    pub external_id: Option<String>,
+    pub external_url: Option<String>,
+}
+
+/// Timestamp format used for `scheduled_for`, so due posts can be found by string comparison
+pub fn schedule_timestamp(at: DateTime<Utc>) -> String {
+    at.to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+/// Caption and link joined, with the caption shortened to fit the network's limit
+pub fn compose_text(network: SocialNetwork, caption: &str, link: Option<&str>) -> String {
+    let caption = caption.trim();
+    let suffix = link.map(|l| format!("\n\n{}", l)).unwrap_or_default();
+    let limit = network.text_limit();
+
+    if caption.chars().count() + suffix.chars().count() <= limit {
+        return format!("{}{}", caption, suffix);
+    }
+
+    let room = limit.saturating_sub(suffix.chars().count() + 1);
+    let shortened: String = caption.chars().take(room).collect();
+    format!("{}…{}", shortened.trim_end(), suffix)
+}
+
+/// Wait before the next attempt: 5, 10, 20… minutes
+pub fn retry_delay(attempts: i64) -> Duration {
+    Duration::minutes(5 * 2_i64.pow(attempts.clamp(1, 6) as u32 - 1))
+}
+
+async fn required_setting(settings: &SettingsService, key: &str) -> Result<String, String> {
+    settings
+        .get_setting(key)
+        .await
+        .ok()
+        .flatten()
+        .filter(|v| !v.trim().is_empty())
+        .ok_or_else(|| format!("{} is not configured", key))
+}
+
+async fn publish_linkedin(
+    http: &Client,
+    settings: &SettingsService,
+    text: &str,
+    post: &ScheduledSocialPost,
+) -> Result<PublishedPost, String> {
+    let token = required_setting(settings, "social_linkedin_access_token").await?;
+    let author = required_setting(settings, "social_linkedin_author_urn").await?;
+
+    let media = match post.link_url.as_deref() {
+        Some(link) => json!([{ "status": "READY", "originalUrl": link }]),
+        None => json!([]),
+    };
+    let category = if post.link_url.is_some() {
+        "ARTICLE"
+    } else {
+        "NONE"
+    };
+
+    let response = http
+        .post("https://api.linkedin.com/v2/ugcPosts")
+        .bearer_auth(token)
+        .header("X-Restli-Protocol-Version", "2.0.0")
+        .json(&json!({
+            "author": author,
+            "lifecycleState": "PUBLISHED",
+            "specificContent": {
+                "com.linkedin.ugc.ShareContent": {
+                    "shareCommentary": { "text": text },
+                    "shareMediaCategory": category,
+                    "media": media,
+                }
+            },
+            "visibility": { "com.linkedin.ugc.MemberNetworkVisibility": "PUBLIC" },
+        }))
+        .send()
+        .await
+        .map_err(|e| format!("LinkedIn request failed: {}", e))?;
+
+    let status = response.status();
+    let header_id = response
+        .headers()
+        .get("x-restli-id")
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string);
+    let body: Value = response.json().await.unwrap_or(Value::Null);
+    if !status.is_success() {
+        return Err(format!("LinkedIn returned {}: {}", status, body));
+    }
+
+    let external_id = header_id.or_else(|| body["id"].as_str().map(str::to_string));
+    Ok(PublishedPost {
+        external_url: external_id
+            .as_ref()
+            .map(|id| format!("https://www.linkedin.com/feed/update/{}", id)),
+        external_id,
+    })
+}
+
+async fn publish_x(
+    http: &Client,
+    settings: &SettingsService,
+    text: &str,
+) -> Result<PublishedPost, String> {
+    let token = required_setting(settings, "social_x_access_token").await?;
+
+    let response = http
+        .post("https://api.twitter.com/2/tweets")
+        .bearer_auth(token)
+        .json(&json!({ "text": text }))
+        .send()
+        .await
+        .map_err(|e| format!("X request failed: {}", e))?;
+
+    let status = response.status();
+    let body: Value = response.json().await.unwrap_or(Value::Null);
+    if !status.is_success() {
+        return Err(format!("X returned {}: {}", status, body));
+    }
+
+    let external_id = body["data"]["id"].as_str().map(str::to_string);
+    Ok(PublishedPost {
+        external_url: external_id
+            .as_ref()
+            .map(|id| format!("https://x.com/i/web/status/{}", id)),
+        external_id,
+    })
+}
+
+async fn publish_buffer(
+    http: &Client,
+    settings: &SettingsService,
+    text: &str,
+    post: &ScheduledSocialPost,
+) -> Result<PublishedPost, String> {
+    let webhook = required_setting(settings, "social_buffer_webhook_url").await?;
+
+    let response = http
+        .post(&webhook)
+        .json(&json!({
+            "text": text,
+            "link": post.link_url,
+            "image_url": post.image_url,
+            "job_id": post.job_id,
+            "post_id": post.id,
+        }))
+        .send()
+        .await
+        .map_err(|e| format!("Buffer webhook failed: {}", e))?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        return Err(format!("Buffer webhook returned {}: {}", status, body));
+    }
+
+    let body: Value = response.json().await.unwrap_or(Value::Null);
+    Ok(PublishedPost {
+        external_id: body["id"].as_str().map(str::to_string),
+        external_url: None,
+    })
+}
+
+/// Publish one post through its network's connector
+pub async fn publish(
+    http: &Client,
+    settings: &SettingsService,
+    post: &ScheduledSocialPost,
+) -> Result<PublishedPost, String> {
+    let network = SocialNetwork::parse(&post.network)
+        .ok_or_else(|| format!("Unknown network '{}'", post.network))?;
+    let text = compose_text(network, &post.caption, post.link_url.as_deref());
+
+    match network {
+        SocialNetwork::Linkedin => publish_linkedin(http, settings, &text, post).await,
+        SocialNetwork::X => publish_x(http, settings, &text).await,
+        SocialNetwork::Buffer => publish_buffer(http, settings, &text, post).await,
+    }
+}
+
+/// Record the outcome of a publish attempt, scheduling a retry while attempts remain
+async fn record_outcome(
+    pool: &SqlitePool,
+    post: &ScheduledSocialPost,
+    outcome: Result<PublishedPost, String>,
+) -> Result<(), sqlx::Error> {
+    let attempts = post.attempts + 1;
+    match outcome {
+        Ok(published) => {
+            sqlx::query(
+                r#"
+                UPDATE scheduled_social_posts
+                SET status = 'published', attempts = ?, external_id = ?, external_url = ?,
+                    last_error = NULL, published_at = datetime('now'), updated_at = datetime('now')
+                WHERE id = ?
+                "#,
+            )
+            .bind(attempts)
+            .bind(&published.external_id)
+            .bind(&published.external_url)
+            .bind(&post.id)
+            .execute(pool)
+            .await?;
+            info!(
+                post_id = %post.id,
+                job_id = %post.job_id,
+                network = %post.network,
+                external_id = ?published.external_id,
+                "Published social post"
+            );
+        }
+        Err(reason) if attempts < MAX_PUBLISH_ATTEMPTS => {
+            let retry_at = schedule_timestamp(Utc::now() + retry_delay(attempts));
+            sqlx::query(
+                r#"
+                UPDATE scheduled_social_posts
+                SET status = 'scheduled', attempts = ?, last_error = ?, scheduled_for = ?,
+                    updated_at = datetime('now')
+                WHERE id = ?
+                "#,
+            )
+            .bind(attempts)
+            .bind(&reason)
+            .bind(&retry_at)
+            .bind(&post.id)
+            .execute(pool)
+            .await?;
+            warn!(
+                post_id = %post.id,
+                network = %post.network,
+                attempts = attempts,
+                retry_at = %retry_at,
+                error = %reason,
+                "Social post publish failed, will retry"
+            );
+        }
+        Err(reason) => {
+            sqlx::query(
+                r#"
+                UPDATE scheduled_social_posts
+                SET status = 'failed', attempts = ?, last_error = ?, updated_at = datetime('now')
+                WHERE id = ?
+                "#,
+            )
+            .bind(attempts)
+            .bind(&reason)
+            .bind(&post.id)
+            .execute(pool)
+            .await?;
+            error!(
+                post_id = %post.id,
+                network = %post.network,
+                attempts = attempts,
+                error = %reason,
+                "Social post publish failed permanently"
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Claim and publish every post that is due; returns how many were attempted
+pub async fn publish_due_posts(
+    pool: &SqlitePool,
+    settings: &SettingsService,
+    http: &Client,
+) -> Result<usize, sqlx::Error> {
+    let now = schedule_timestamp(Utc::now());
+    let due = sqlx::query_as::<_, ScheduledSocialPost>(
+        r#"
+        UPDATE scheduled_social_posts
+        SET status = 'publishing', updated_at = datetime('now')
+        WHERE id IN (
+            SELECT id FROM scheduled_social_posts
+            WHERE status = 'scheduled' AND scheduled_for <= ?
+            ORDER BY scheduled_for
+            LIMIT ?
+        )
+        RETURNING *
+        "#,
+    )
+    .bind(&now)
+    .bind(PUBLISH_BATCH_SIZE)
+    .fetch_all(pool)
+    .await?;
+
+    for post in &due {
+        let outcome = publish(http, settings, post).await;
+        record_outcome(pool, post, outcome).await?;
+    }
+
+    Ok(due.len())
+}
+
+/// Publish due posts every minute
+///
+/// Rows left in `publishing` by a crash are requeued on start; the network may then see
+/// the post twice, which is preferred over silently dropping it.
+pub fn start_social_publish_task(pool: SqlitePool, settings: Arc<SettingsService>, http: Client) {
+    tokio::spawn(async move {
+        if let Err(e) = sqlx::query(
+            "UPDATE scheduled_social_posts SET status = 'scheduled' WHERE status = 'publishing'",
+        )
+        .execute(&pool)
+        .await
+        {
+            warn!(error = %e, "Failed to requeue interrupted social posts");
+        }
+
+        loop {
+            if !crate::common::shutdown::sleep(std::time::Duration::from_secs(
+                PUBLISH_INTERVAL_SECONDS,
+            ))
+            .await
+            {
+                break;
+            }
+            let _running = crate::common::shutdown::track();
+
+            match publish_due_posts(&pool, &settings, &http).await {
+                Ok(0) => {}
+                Ok(count) => debug!(count = count, "Processed due social posts"),
+                Err(e) => error!(error = %e, "Social publishing pass failed"),
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compose_text_fits_network_limit() {
+        let link = "https://api.example.com/j/K7NP3XY";
+        let caption = "We're hiring! ".repeat(40);
+
+        let x = compose_text(SocialNetwork::X, &caption, Some(link));
+        assert!(x.chars().count() <= 280);
+        assert!(x.ends_with(&format!("…\n\n{}", link)));
+
+        let linkedin = compose_text(SocialNetwork::Linkedin, &caption, Some(link));
+        assert_eq!(linkedin, format!("{}\n\n{}", caption.trim(), link));
+
+        assert_eq!(compose_text(SocialNetwork::X, " Join us ", None), "Join us");
+    }
+
+    #[test]
+    fn test_retry_delay_backs_off() {
+        assert_eq!(retry_delay(1), Duration::minutes(5));
+        assert_eq!(retry_delay(2), Duration::minutes(10));
+        assert_eq!(retry_delay(3), Duration::minutes(20));
+    }
+
+    #[test]
+    fn test_network_round_trip() {
+        for network in [
+            SocialNetwork::Linkedin,
+            SocialNetwork::X,
+            SocialNetwork::Buffer,
+        ] {
+            assert_eq!(SocialNetwork::parse(network.as_str()), Some(network));
+        }
+        assert_eq!(SocialNetwork::parse("myspace"), None);
+    }
+}
@@ -0,0 +1,313 @@
+// src/services/storage_usage.rs
+//! Per-file storage accounting for users and companies, and the quotas enforced on upload
+
+use serde::Serialize;
+use sqlx::SqlitePool;
+use tracing::{error, warn};
+
+use crate::common::{ApiError, AppState, ErrorCode};
+
+/// What a ledger row is charged to
+pub const OWNER_USER: &str = "user";
+pub const OWNER_COMPANY: &str = "company";
+
+/// Ledger categories, each keyed by the id of the row that owns the file
+pub const CATEGORY_RESUME: &str = "resume";
+pub const CATEGORY_VIDEO: &str = "video";
+pub const CATEGORY_ATTACHMENT: &str = "attachment";
+pub const CATEGORY_COMPANY_ASSET: &str = "company_asset";
+
+/// Quotas when the `storage_quota_user_mb` / `storage_quota_company_mb` settings are unset
+const DEFAULT_USER_QUOTA_MB: i64 = 500;
+const DEFAULT_COMPANY_QUOTA_MB: i64 = 2048;
+
+const BYTES_PER_MB: i64 = 1024 * 1024;
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct CategoryUsage {
+    pub category: String,
+    pub bytes: i64,
+    pub files: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct StorageUsage {
+    pub owner_type: String,
+    pub owner_id: String,
+    pub used_bytes: i64,
+    /// `None` when the quota is disabled
+    pub quota_bytes: Option<i64>,
+    pub remaining_bytes: Option<i64>,
+    pub categories: Vec<CategoryUsage>,
+}
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct StorageConsumer {
+    pub owner_id: String,
+    pub name: Option<String>,
+    pub used_bytes: i64,
+    pub files: i64,
+}
+
+pub fn validate_owner_type(owner_type: &str) -> Result<(), ApiError> {
+    if owner_type == OWNER_USER || owner_type == OWNER_COMPANY {
+        Ok(())
+    } else {
+        Err(ApiError::ValidationError(format!(
+            "owner_type must be '{}' or '{}'",
+            OWNER_USER, OWNER_COMPANY
+        )))
+    }
+}
+
+/// The configured quota for an owner type in bytes; `None` means unlimited (setting `0`)
+pub async fn quota_bytes(state: &AppState, owner_type: &str) -> Option<i64> {
+    let (key, default_mb) = if owner_type == OWNER_COMPANY {
+        ("storage_quota_company_mb", DEFAULT_COMPANY_QUOTA_MB)
+    } else {
+        ("storage_quota_user_mb", DEFAULT_USER_QUOTA_MB)
+    };
+
+    let mb = state
+        .settings_service
+        .get_setting(key)
+        .await
+        .ok()
+        .flatten()
+        .and_then(|v| v.trim().parse::<i64>().ok())
+        .unwrap_or(default_mb);
+
+    (mb > 0).then(|| mb * BYTES_PER_MB)
+}
+
+pub async fn used_bytes(
+    pool: &SqlitePool,
+    owner_type: &str,
+    owner_id: &str,
+) -> Result<i64, sqlx::Error> {
+    sqlx::query_scalar(
+        "SELECT COALESCE(SUM(bytes), 0) FROM storage_usage WHERE owner_type = ? AND owner_id = ?",
+    )
+    .bind(owner_type)
+    .bind(owner_id)
+    .fetch_one(pool)
+    .await
+}
+
+fn format_mb(bytes: i64) -> String {
+    format!("{:.1}MB", bytes as f64 / BYTES_PER_MB as f64)
+}
+
+/// Whether `additional` more bytes fit under the quota
+pub fn check_quota(used: i64, quota: Option<i64>, additional: i64) -> Result<(), ApiError> {
+    match quota {
+        Some(quota) if used.saturating_add(additional) > quota => Err(ApiError::Coded(
+            ErrorCode::StorageQuotaExceeded,
+            format!(
+                "Storage quota exceeded: this upload needs {} but only {} of the {} quota is left. Delete some files and try again.",
+                format_mb(additional),
+                format_mb((quota - used).max(0)),
+                format_mb(quota)
+            ),
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Refuse an upload of `additional` bytes that would take the owner over quota.
+/// Call this before the file is written anywhere.
+pub async fn ensure_within_quota(
+    state: &AppState,
+    owner_type: &str,
+    owner_id: &str,
+    additional: i64,
+) -> Result<(), ApiError> {
+    let quota = quota_bytes(state, owner_type).await;
+    if quota.is_none() {
+        return Ok(());
+    }
+    let used = used_bytes(&state.db, owner_type, owner_id)
+        .await
+        .map_err(ApiError::DatabaseError)?;
+
+    let result = check_quota(used, quota, additional);
+    if result.is_err() {
+        warn!(
+            owner_type = %owner_type,
+            owner_id = %owner_id,
+            used_bytes = used,
+            additional_bytes = additional,
+            "Upload refused: storage quota exceeded"
+        );
+    }
+    result
+}
+
+/// Charge a stored file to its owner. Accounting never fails the upload it follows.
+pub async fn record_file(
+    pool: &SqlitePool,
+    category: &str,
+    file_id: &str,
+    owner_type: &str,
+    owner_id: &str,
+    bytes: i64,
+) {
+    let result = sqlx::query(
+        r#"
+        INSERT INTO storage_usage (category, file_id, owner_type, owner_id, bytes)
+        VALUES (?, ?, ?, ?, ?)
+        ON CONFLICT(category, file_id) DO UPDATE SET
+            owner_type = excluded.owner_type,
+            owner_id = excluded.owner_id,
+            bytes = excluded.bytes
+        "#,
+    )
+    .bind(category)
+    .bind(file_id)
+    .bind(owner_type)
+    .bind(owner_id)
+    .bind(bytes.max(0))
+    .execute(pool)
+    .await;
+
+    if let Err(e) = result {
+        error!(error = %e, category = %category, file_id = %file_id, "Failed to record storage usage");
+    }
+}
+
+/// Stop charging for a file once it has been deleted
+pub async fn release_file(pool: &SqlitePool, category: &str, file_id: &str) {
+    let result = sqlx::query("DELETE FROM storage_usage WHERE category = ? AND file_id = ?")
+        .bind(category)
+        .bind(file_id)
+        .execute(pool)
+        .await;
+
+    if let Err(e) = result {
+        error!(error = %e, category = %category, file_id = %file_id, "Failed to release storage usage");
+    }
+}
+
+pub async fn usage(
+    state: &AppState,
+    owner_type: &str,
+    owner_id: &str,
+) -> Result<StorageUsage, ApiError> {
+    let categories = sqlx::query_as::<_, CategoryUsage>(
+        r#"
+        SELECT category, COALESCE(SUM(bytes), 0) AS bytes, COUNT(*) AS files
+        FROM storage_usage
+        WHERE owner_type = ? AND owner_id = ?
+        GROUP BY category
+        ORDER BY category
+        "#,
+    )
+    .bind(owner_type)
+    .bind(owner_id)
+    .fetch_all(&state.db)
+    .await
+    .map_err(ApiError::DatabaseError)?;
+
+    let used_bytes: i64 = categories.iter().map(|c| c.bytes).sum();
+    let quota_bytes = quota_bytes(state, owner_type).await;
+
+    Ok(StorageUsage {
+        owner_type: owner_type.to_string(),
+        owner_id: owner_id.to_string(),
+        used_bytes,
+        quota_bytes,
+        remaining_bytes: quota_bytes.map(|q| (q - used_bytes).max(0)),
+        categories,
+    })
+}
+
+/// Largest consumers of an owner type, biggest first
+pub async fn top_consumers(
+    pool: &SqlitePool,
+    owner_type: &str,
+    limit: i64,
+) -> Result<Vec<StorageConsumer>, sqlx::Error> {
+    let name_source = if owner_type == OWNER_COMPANY {
+        "(SELECT name FROM companies WHERE id = s.owner_id)"
+    } else {
+        "(SELECT COALESCE(name, email) FROM users WHERE id = s.owner_id)"
+    };
+
+    sqlx::query_as::<_, StorageConsumer>(&format!(
+        r#"
+        SELECT s.owner_id, {} AS name, SUM(s.bytes) AS used_bytes, COUNT(*) AS files
+        FROM storage_usage s
+        WHERE s.owner_type = ?
+        GROUP BY s.owner_id
+        ORDER BY used_bytes DESC
+        LIMIT ?
+        "#,
+        name_source
+    ))
+    .bind(owner_type)
+    .bind(limit)
+    .fetch_all(pool)
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_quota() {
+        let quota = Some(10 * BYTES_PER_MB);
+
+        assert!(check_quota(0, quota, 10 * BYTES_PER_MB).is_ok());
+        assert!(check_quota(9 * BYTES_PER_MB, quota, BYTES_PER_MB).is_ok());
+        assert!(check_quota(9 * BYTES_PER_MB, None, i64::MAX).is_ok());
+
+        match check_quota(9 * BYTES_PER_MB, quota, 2 * BYTES_PER_MB) {
+            Err(ApiError::Coded(code, message)) => {
+                assert_eq!(code, ErrorCode::StorageQuotaExceeded);
+                assert!(message.contains("only 1.0MB of the 10.0MB quota is left"));
+            }
+            other => panic!("expected a quota error, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_record_and_release_files() {
+        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+        sqlx::query(
+            r#"
+            CREATE TABLE storage_usage (
+                category TEXT NOT NULL,
+                file_id TEXT NOT NULL,
+                owner_type TEXT NOT NULL,
+                owner_id TEXT NOT NULL,
+                bytes INTEGER NOT NULL,
+                created_at TEXT DEFAULT (datetime('now')),
+                PRIMARY KEY (category, file_id)
+            )
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query("CREATE TABLE users (id TEXT PRIMARY KEY, email TEXT, name TEXT)")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        record_file(&pool, CATEGORY_RESUME, "R1", OWNER_USER, "U1", 100).await;
+        record_file(&pool, CATEGORY_VIDEO, "V1", OWNER_USER, "U1", 400).await;
+        // Re-recording the same file replaces its size rather than double counting
+        record_file(&pool, CATEGORY_RESUME, "R1", OWNER_USER, "U1", 150).await;
+        record_file(&pool, CATEGORY_RESUME, "R2", OWNER_USER, "U2", 999).await;
+
+        assert_eq!(used_bytes(&pool, OWNER_USER, "U1").await.unwrap(), 550);
+
+        release_file(&pool, CATEGORY_VIDEO, "V1").await;
+        assert_eq!(used_bytes(&pool, OWNER_USER, "U1").await.unwrap(), 150);
+
+        let top = top_consumers(&pool, OWNER_USER, 10).await.unwrap();
+        assert_eq!(top[0].owner_id, "U2");
+        assert_eq!(top[1].used_bytes, 150);
+    }
+}
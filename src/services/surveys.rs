@@ -0,0 +1,268 @@
+// src/services/surveys.rs
+//! Candidate satisfaction (NPS) surveys
+//!
+//! A survey link is emailed once an application has been hired or rejected for a while
+//! (`nps_survey_delay_hours`, default 24), so the decision email lands first. Candidates
+//! answer through a public tokenized page; the token is the only credential.
+
+use sqlx::SqlitePool;
+use std::sync::Arc;
+use tracing::{debug, error, info};
+
+use crate::candidates::models::{CandidateSurvey, NpsBreakdown};
+use crate::common::i18n::{t, user_locale};
+use crate::common::{generate_history_id, generate_raw_id};
+use crate::services::{EmailSender, SettingsService};
+
+/// How long a survey link stays open
+pub const SURVEY_TTL_DAYS: i64 = 30;
+
+/// Longest comment accepted with a score
+pub const MAX_COMMENT_LENGTH: usize = 2000;
+
+/// Decisions older than this never get a survey, so enabling surveys doesn't email
+/// every past candidate
+const SURVEY_LOOKBACK_DAYS: i64 = 14;
+
+const DEFAULT_SURVEY_DELAY_HOURS: i64 = 24;
+
+const SURVEY_TOKEN_LENGTH: usize = 32;
+
+/// Surveys created or emailed per run
+const SURVEY_BATCH_SIZE: i64 = 100;
+
+const SURVEY_INTERVAL_SECONDS: u64 = 15 * 60;
+
+pub fn survey_url(frontend_url: &str, token: &str) -> String {
+    format!("{}/surveys/{}", frontend_url, token)
+}
+
+/// Net Promoter Score: % promoters (9-10) minus % detractors (0-6), to one decimal
+pub fn net_promoter_score(promoters: i64, passives: i64, detractors: i64) -> Option<f64> {
+    let responses = promoters + passives + detractors;
+    (responses > 0)
+        .then(|| ((promoters - detractors) as f64 * 1000.0 / responses as f64).round() / 10.0)
+}
+
+/// Fill in the derived figures of a breakdown loaded from the database
+pub fn with_scores(mut breakdown: NpsBreakdown) -> NpsBreakdown {
+    breakdown.nps = net_promoter_score(
+        breakdown.promoters,
+        breakdown.passives,
+        breakdown.detractors,
+    );
+    breakdown.response_rate = (breakdown.sent > 0)
+        .then(|| (breakdown.responses as f64 * 1000.0 / breakdown.sent as f64).round() / 10.0);
+    breakdown
+}
+
+#[derive(sqlx::FromRow)]
+struct Decision {
+    application_id: String,
+    job_id: String,
+    user_id: String,
+    outcome: String,
+    recruiter_id: Option<String>,
+}
+
+/// Create surveys for recent final decisions and email any that haven't been sent
+pub async fn dispatch_surveys(
+    pool: &SqlitePool,
+    email_sender: &dyn EmailSender,
+    frontend_url: &str,
+    delay_hours: i64,
+) -> Result<usize, sqlx::Error> {
+    // The decision time is the latest history entry for the final status
+    let decisions = sqlx::query_as::<_, Decision>(
+        r#"
+        SELECT a.id AS application_id, a.job_id, a.user_id, a.status AS outcome,
+               h.changed_by AS recruiter_id
+        FROM applications a
+        JOIN application_status_history h ON h.id = (
+            SELECT h2.id FROM application_status_history h2
+            WHERE h2.application_id = a.id AND h2.status = a.status
+            ORDER BY h2.changed_at DESC
+            LIMIT 1
+        )
+        WHERE a.status IN ('hired', 'rejected')
+          AND a.archived_at IS NULL
+          AND h.changed_at <= datetime('now', ?)
+          AND h.changed_at >= datetime('now', ?)
+          AND NOT EXISTS (SELECT 1 FROM candidate_surveys s WHERE s.application_id = a.id)
+        LIMIT ?
+        "#,
+    )
+    .bind(format!("-{} hours", delay_hours.max(0)))
+    .bind(format!("-{} days", SURVEY_LOOKBACK_DAYS))
+    .bind(SURVEY_BATCH_SIZE)
+    .fetch_all(pool)
+    .await?;
+
+    for decision in decisions {
+        sqlx::query(
+            r#"
+            INSERT OR IGNORE INTO candidate_surveys
+                (id, application_id, job_id, user_id, recruiter_id, outcome, token, expires_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, datetime('now', ?))
+            "#,
+        )
+        .bind(generate_history_id())
+        .bind(&decision.application_id)
+        .bind(&decision.job_id)
+        .bind(&decision.user_id)
+        .bind(&decision.recruiter_id)
+        .bind(&decision.outcome)
+        .bind(generate_raw_id(SURVEY_TOKEN_LENGTH))
+        .bind(format!("+{} days", SURVEY_TTL_DAYS))
+        .execute(pool)
+        .await?;
+    }
+
+    // Includes surveys whose email failed on an earlier run
+    let unsent = sqlx::query_as::<_, CandidateSurvey>(
+        "SELECT * FROM candidate_surveys WHERE sent_at IS NULL AND expires_at > datetime('now') LIMIT ?",
+    )
+    .bind(SURVEY_BATCH_SIZE)
+    .fetch_all(pool)
+    .await?;
+
+    let mut sent = 0;
+    for survey in unsent {
+        let Some((email, name)) = sqlx::query_as::<_, (String, Option<String>)>(
+            "SELECT email, name FROM users WHERE id = ?",
+        )
+        .bind(&survey.user_id)
+        .fetch_optional(pool)
+        .await?
+        else {
+            continue;
+        };
+        let (job_title, company) = sqlx::query_as::<_, (String, Option<String>)>(
+            "SELECT title, company FROM jobs WHERE id = ?",
+        )
+        .bind(&survey.job_id)
+        .fetch_optional(pool)
+        .await?
+        .unwrap_or_default();
+
+        let locale = user_locale(pool, &survey.user_id).await.unwrap_or_default();
+        let name = name.unwrap_or_default();
+        let company = company.unwrap_or_else(|| "Our Company".to_string());
+        let url = survey_url(frontend_url, &survey.token);
+        let args = [
+            ("name", name.as_str()),
+            ("job", job_title.as_str()),
+            ("company", company.as_str()),
+            ("url", url.as_str()),
+        ];
+        let subject = t("email.survey.subject", locale, &args);
+        let body = format!(
+            r#"<html><body style="font-family: Arial, sans-serif; line-height: 1.6; color: #333;">
+<div style="max-width: 600px; margin: 0 auto; padding: 20px;">
+<p>{}</p>
+{}
+<p>{}</p>
+</div></body></html>"#,
+            t("email.greeting", locale, &args),
+            t("email.survey.body", locale, &args),
+            t("email.signoff", locale, &args),
+        );
+
+        match email_sender
+            .send_email(vec![email], &subject, &body, None)
+            .await
+        {
+            Ok(_) => {
+                sqlx::query("UPDATE candidate_surveys SET sent_at = datetime('now') WHERE id = ?")
+                    .bind(&survey.id)
+                    .execute(pool)
+                    .await?;
+                sent += 1;
+            }
+            Err(e) => {
+                error!(error = %e, survey_id = %survey.id, "Failed to send candidate survey email");
+            }
+        }
+    }
+
+    if sent > 0 {
+        info!(sent = sent, "Sent candidate satisfaction surveys");
+    }
+    Ok(sent)
+}
+
+/// Send surveys every 15 minutes unless `nps_surveys_enabled` is `false`
+pub fn start_survey_task(
+    pool: SqlitePool,
+    settings_service: Arc<SettingsService>,
+    email_sender: Arc<dyn EmailSender>,
+    frontend_url: String,
+) {
+    tokio::spawn(async move {
+        loop {
+            if !crate::common::shutdown::sleep(std::time::Duration::from_secs(
+                SURVEY_INTERVAL_SECONDS,
+            ))
+            .await
+            {
+                break;
+            }
+            let _running = crate::common::shutdown::track();
+
+            let enabled = settings_service
+                .get_setting("nps_surveys_enabled")
+                .await
+                .ok()
+                .flatten()
+                .map(|v| v != "false")
+                .unwrap_or(true);
+            if !enabled {
+                continue;
+            }
+            let delay_hours = settings_service
+                .get_setting("nps_survey_delay_hours")
+                .await
+                .ok()
+                .flatten()
+                .and_then(|v| v.parse::<i64>().ok())
+                .unwrap_or(DEFAULT_SURVEY_DELAY_HOURS);
+
+            if let Err(e) =
+                dispatch_surveys(&pool, email_sender.as_ref(), &frontend_url, delay_hours).await
+            {
+                debug!(error = %e, "Skipped candidate survey dispatch");
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_net_promoter_score() {
+        assert_eq!(net_promoter_score(0, 0, 0), None);
+        assert_eq!(net_promoter_score(5, 3, 2), Some(30.0));
+        assert_eq!(net_promoter_score(0, 0, 4), Some(-100.0));
+        assert_eq!(net_promoter_score(1, 1, 1), Some(0.0));
+        assert_eq!(net_promoter_score(2, 1, 0), Some(66.7));
+    }
+
+    #[test]
+    fn test_with_scores_computes_response_rate() {
+        let breakdown = with_scores(NpsBreakdown {
+            id: Some("J_1".to_string()),
+            label: Some("Line Cook".to_string()),
+            sent: 8,
+            responses: 3,
+            promoters: 2,
+            passives: 0,
+            detractors: 1,
+            nps: None,
+            response_rate: None,
+        });
+        assert_eq!(breakdown.nps, Some(33.3));
+        assert_eq!(breakdown.response_rate, Some(37.5));
+    }
+}
@@ -1,6 +1,7 @@
 // src/services/video.rs
 use crate::candidates::models::{VideoMetadata, VideoSubmission, VideoUploadResponse};
-use crate::services::aws::{AWSError, AWSService};
+use crate::services::aws::AWSError;
+use crate::services::ObjectStore;
 use sqlx::SqlitePool;
 use std::sync::Arc;
 use thiserror::Error;
@@ -45,12 +46,12 @@ const SUPPORTED_FORMATS: &[&str] = &[
 #[derive(Debug)]
 pub struct VideoService {
     db: SqlitePool,
-    aws_service: Arc<AWSService>,
+    object_store: Arc<dyn ObjectStore>,
 }
 
 impl VideoService {
-    pub fn new(db: SqlitePool, aws_service: Arc<AWSService>) -> Self {
-        Self { db, aws_service }
+    pub fn new(db: SqlitePool, object_store: Arc<dyn ObjectStore>) -> Self {
+        Self { db, object_store }
     }
 
     /// Validate video file before upload
@@ -106,7 +107,7 @@ impl VideoService {
         );
 
         let s3_url = self
-            .aws_service
+            .object_store
             .upload_file(file_data, &s3_key, mime_type)
             .await?;
 
@@ -208,7 +209,7 @@ impl VideoService {
                 .ok_or_else(|| VideoError::InvalidData("Invalid S3 URL format".to_string()))?;
 
             // Delete from S3
-            self.aws_service.delete_file(s3_key).await?;
+            self.object_store.delete_file(s3_key).await?;
         }
 
         // Delete from database
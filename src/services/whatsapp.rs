@@ -0,0 +1,447 @@
+// src/services/whatsapp.rs
+//! WhatsApp Business messages for interview confirmations and reminders
+//!
+//! Messages go out through the WhatsApp Business Cloud API as approved templates, since
+//! WhatsApp only allows templates to open a conversation. Which template each event uses
+//! lives in the `whatsapp_templates` setting; an event without a template is not sent.
+//! Every template receives three body parameters, in this order: the candidate's name,
+//! the job title and the interview time in the candidate's time zone.
+//!
+//! Only candidates who opted in from their profile, and only to a verified phone number,
+//! are messaged.
+
+use reqwest::Client;
+use serde_json::{json, Value};
+use sqlx::SqlitePool;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+use crate::admin::models::{WhatsAppMessage, WhatsAppTemplate};
+use crate::common::timezone::{display_stored, user_timezone, DEFAULT_TIMEZONE};
+use crate::services::pii;
+use crate::services::{notification_preferences, SettingsService};
+
+/// Events a template can be configured for
+pub const WHATSAPP_EVENTS: &[&str] = &["interview_confirmation", "interview_reminder"];
+
+/// Delivery log statuses
+pub const WHATSAPP_STATUSES: &[&str] = &["sent", "failed"];
+
+/// How far ahead of an interview the reminder goes out
+pub const WHATSAPP_REMINDER_HOURS: i64 = 24;
+
+const GRAPH_API_VERSION: &str = "v19.0";
+
+const REMINDER_CHECK_INTERVAL_SECS: u64 = 15 * 60;
+
+/// Template per event, as stored in the `whatsapp_templates` setting
+pub type WhatsAppTemplates = BTreeMap<String, WhatsAppTemplate>;
+
+/// Check templates before they are saved: known events, and names and language codes
+/// WhatsApp would accept
+pub fn validate_templates(templates: &WhatsAppTemplates) -> Result<(), String> {
+    for (event, template) in templates {
+        if !WHATSAPP_EVENTS.contains(&event.as_str()) {
+            return Err(format!(
+                "Unknown event '{}'; expected one of {}",
+                event,
+                WHATSAPP_EVENTS.join(", ")
+            ));
+        }
+        let name_ok = !template.name.is_empty()
+            && template.name.len() <= 512
+            && template
+                .name
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+        if !name_ok {
+            return Err(format!(
+                "Template name for '{}' must be lowercase letters, digits and underscores",
+                event
+            ));
+        }
+        let language_ok = (2..=6).contains(&template.language.len())
+            && template
+                .language
+                .chars()
+                .all(|c| c.is_ascii_alphabetic() || c == '_');
+        if !language_ok {
+            return Err(format!(
+                "Template language for '{}' must be a code such as en or en_US",
+                event
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// WhatsApp wants the number without the leading `+`
+fn whatsapp_number(phone: &str) -> String {
+    phone.trim_start_matches('+').to_string()
+}
+
+/// The Cloud API request body for a template message
+fn template_payload(phone: &str, template: &WhatsAppTemplate, parameters: &[&str]) -> Value {
+    let parameters: Vec<Value> = parameters
+        .iter()
+        .map(|text| json!({ "type": "text", "text": text }))
+        .collect();
+    json!({
+        "messaging_product": "whatsapp",
+        "to": whatsapp_number(phone),
+        "type": "template",
+        "template": {
+            "name": template.name,
+            "language": { "code": template.language },
+            "components": [{ "type": "body", "parameters": parameters }],
+        },
+    })
+}
+
+/// Sends template messages through the WhatsApp Business Cloud API
+#[derive(Debug)]
+pub struct WhatsAppService {
+    settings_service: Arc<SettingsService>,
+    http: Client,
+}
+
+impl WhatsAppService {
+    pub fn new(settings_service: Arc<SettingsService>, http: Client) -> Self {
+        Self {
+            settings_service,
+            http,
+        }
+    }
+
+    /// Whether the phone number ID and access token are both set
+    pub async fn is_configured(&self) -> bool {
+        self.credentials().await.is_ok()
+    }
+
+    async fn credentials(&self) -> Result<(String, String), String> {
+        let settings = self
+            .settings_service
+            .get_settings(&["whatsapp_phone_number_id", "whatsapp_access_token"])
+            .await
+            .map_err(|e| e.to_string())?;
+        let setting = |key: &str| {
+            settings
+                .get(key)
+                .cloned()
+                .flatten()
+                .filter(|v| !v.trim().is_empty())
+                .ok_or_else(|| format!("{} is not configured", key))
+        };
+        Ok((
+            setting("whatsapp_phone_number_id")?,
+            setting("whatsapp_access_token")?,
+        ))
+    }
+
+    /// Templates configured per event
+    pub async fn templates(&self) -> WhatsAppTemplates {
+        self.settings_service
+            .get_setting("whatsapp_templates")
+            .await
+            .ok()
+            .flatten()
+            .and_then(|raw| serde_json::from_str(&raw).ok())
+            .unwrap_or_default()
+    }
+
+    pub async fn save_templates(
+        &self,
+        templates: &WhatsAppTemplates,
+        updated_by: &str,
+    ) -> Result<(), String> {
+        validate_templates(templates)?;
+        let raw = serde_json::to_string(templates).map_err(|e| e.to_string())?;
+        self.settings_service
+            .set_setting("whatsapp_templates", &raw, false, Some(updated_by))
+            .await
+            .map_err(|e| e.to_string())
+    }
+
+    /// Send a template message; returns WhatsApp's message ID
+    pub async fn send_template(
+        &self,
+        phone: &str,
+        template: &WhatsAppTemplate,
+        parameters: &[&str],
+    ) -> Result<Option<String>, String> {
+        let (phone_number_id, access_token) = self.credentials().await?;
+
+        let response = self
+            .http
+            .post(format!(
+                "https://graph.facebook.com/{}/{}/messages",
+                GRAPH_API_VERSION, phone_number_id
+            ))
+            .bearer_auth(access_token)
+            .json(&template_payload(phone, template, parameters))
+            .send()
+            .await
+            .map_err(|e| format!("WhatsApp request failed: {}", e))?;
+
+        let status = response.status();
+        let body: Value = response.json().await.unwrap_or(Value::Null);
+        if !status.is_success() {
+            return Err(format!(
+                "WhatsApp returned {}: {}",
+                status,
+                body["error"]["message"].as_str().unwrap_or("unknown error")
+            ));
+        }
+        Ok(body["messages"][0]["id"].as_str().map(str::to_string))
+    }
+}
+
+/// Record an opt-in or opt-out; the opt-in time is kept when opting out
+pub async fn set_opt_in(
+    pool: &SqlitePool,
+    user_id: &str,
+    opted_in: bool,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        r#"
+        INSERT INTO whatsapp_opt_ins (user_id, opted_in, opted_in_at, opted_out_at)
+        VALUES (?1, ?2,
+                CASE WHEN ?2 THEN datetime('now') END,
+                CASE WHEN ?2 THEN NULL ELSE datetime('now') END)
+        ON CONFLICT(user_id) DO UPDATE SET
+            opted_in = excluded.opted_in,
+            opted_in_at = CASE WHEN excluded.opted_in AND NOT whatsapp_opt_ins.opted_in
+                               THEN datetime('now') ELSE whatsapp_opt_ins.opted_in_at END,
+            opted_out_at = CASE WHEN excluded.opted_in THEN whatsapp_opt_ins.opted_out_at
+                                WHEN whatsapp_opt_ins.opted_in THEN datetime('now')
+                                ELSE whatsapp_opt_ins.opted_out_at END
+        "#,
+    )
+    .bind(user_id)
+    .bind(opted_in)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct InterviewMessage {
+    id: String,
+    candidate_id: String,
+    scheduled_date: String,
+    candidate_name: Option<String>,
+    job_title: Option<String>,
+}
+
+/// Send the event's template about an interview to its candidate, if they opted in and a
+/// template is configured. Returns whether a message went out.
+pub async fn notify_interview(
+    pool: &SqlitePool,
+    whatsapp: &WhatsAppService,
+    event: &str,
+    interview_id: &str,
+) -> Result<bool, sqlx::Error> {
+    let Some(template) = whatsapp.templates().await.remove(event) else {
+        return Ok(false);
+    };
+    let Some(interview) = sqlx::query_as::<_, InterviewMessage>(
+        r#"
+        SELECT i.id, i.candidate_id, i.scheduled_date, u.name AS candidate_name,
+               j.title AS job_title
+        FROM interviews i
+        JOIN users u ON u.id = i.candidate_id
+        LEFT JOIN jobs j ON j.id = i.job_id
+        WHERE i.id = ?
+        "#,
+    )
+    .bind(interview_id)
+    .fetch_optional(pool)
+    .await?
+    else {
+        return Ok(false);
+    };
+
+    let prefs = notification_preferences::whatsapp(pool, &interview.candidate_id).await?;
+    let Some(phone) = prefs.phone.as_deref().filter(|_| prefs.opted_in) else {
+        return Ok(false);
+    };
+
+    let zone = user_timezone(pool, &interview.candidate_id)
+        .await
+        .unwrap_or_else(|| DEFAULT_TIMEZONE.to_string());
+    let when = display_stored(&interview.scheduled_date, &zone);
+    let parameters = [
+        interview.candidate_name.as_deref().unwrap_or("there"),
+        interview.job_title.as_deref().unwrap_or("your application"),
+        when.as_str(),
+    ];
+
+    let result = whatsapp.send_template(phone, &template, &parameters).await;
+    let (status, message_id, error) = match &result {
+        Ok(message_id) => ("sent", message_id.as_deref(), None),
+        Err(e) => {
+            warn!(error = %e, event = %event, interview_id = %interview_id, "WhatsApp message failed");
+            ("failed", None, Some(e.as_str()))
+        }
+    };
+    sqlx::query(
+        r#"
+        INSERT INTO whatsapp_messages
+            (id, user_id, phone, event, reference_id, template, status, provider_message_id, error)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+        "#,
+    )
+    .bind(Uuid::new_v4().to_string())
+    .bind(&interview.candidate_id)
+    .bind(pii::seal(phone).map_err(pii::db_error)?)
+    .bind(event)
+    .bind(&interview.id)
+    .bind(&template.name)
+    .bind(status)
+    .bind(message_id)
+    .bind(error)
+    .execute(pool)
+    .await?;
+
+    Ok(result.is_ok())
+}
+
+/// Remind candidates of interviews starting within a day. Each interview is handled once,
+/// whether the message went out or failed.
+pub async fn send_due_reminders(
+    pool: &SqlitePool,
+    whatsapp: &WhatsAppService,
+) -> Result<usize, sqlx::Error> {
+    let due: Vec<String> = sqlx::query_scalar(
+        r#"
+        SELECT i.id FROM interviews i
+        JOIN whatsapp_opt_ins o ON o.user_id = i.candidate_id AND o.opted_in = 1
+        WHERE COALESCE(i.status, 'scheduled') = 'scheduled'
+          AND datetime(i.scheduled_date) > datetime('now')
+          AND datetime(i.scheduled_date) <= datetime('now', ?)
+          AND NOT EXISTS (
+              SELECT 1 FROM whatsapp_messages m
+              WHERE m.event = 'interview_reminder' AND m.reference_id = i.id
+          )
+        "#,
+    )
+    .bind(format!("+{} hours", WHATSAPP_REMINDER_HOURS))
+    .fetch_all(pool)
+    .await?;
+
+    let mut sent = 0;
+    for interview_id in due {
+        if notify_interview(pool, whatsapp, "interview_reminder", &interview_id).await? {
+            sent += 1;
+        }
+    }
+    Ok(sent)
+}
+
+/// The message log, newest first
+pub async fn list_messages(
+    pool: &SqlitePool,
+    user_id: Option<&str>,
+    event: Option<&str>,
+    status: Option<&str>,
+    limit: i64,
+) -> Result<Vec<WhatsAppMessage>, sqlx::Error> {
+    sqlx::query_as::<_, WhatsAppMessage>(
+        r#"
+        SELECT * FROM whatsapp_messages
+        WHERE (?1 IS NULL OR user_id = ?1)
+          AND (?2 IS NULL OR event = ?2)
+          AND (?3 IS NULL OR status = ?3)
+        ORDER BY created_at DESC
+        LIMIT ?4
+        "#,
+    )
+    .bind(user_id)
+    .bind(event)
+    .bind(status)
+    .bind(limit)
+    .fetch_all(pool)
+    .await
+}
+
+/// Periodically send interview reminders while WhatsApp is configured
+pub fn start_whatsapp_reminder_task(pool: SqlitePool, whatsapp_service: Arc<WhatsAppService>) {
+    tokio::spawn(async move {
+        loop {
+            if !crate::common::shutdown::sleep(std::time::Duration::from_secs(
+                REMINDER_CHECK_INTERVAL_SECS,
+            ))
+            .await
+            {
+                break;
+            }
+            let _running = crate::common::shutdown::track();
+
+            if !whatsapp_service.is_configured().await {
+                continue;
+            }
+            match send_due_reminders(&pool, &whatsapp_service).await {
+                Ok(0) => {}
+                Ok(sent) => info!(sent, "WhatsApp reminders sent"),
+                Err(e) => debug!(error = %e, "Skipped WhatsApp reminder run"),
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn template(name: &str, language: &str) -> WhatsAppTemplate {
+        WhatsAppTemplate {
+            name: name.to_string(),
+            language: language.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_validate_templates() {
+        let mut templates = WhatsAppTemplates::new();
+        templates.insert(
+            "interview_reminder".to_string(),
+            template("interview_reminder_v2", "en_US"),
+        );
+        assert!(validate_templates(&templates).is_ok());
+
+        templates.insert(
+            "interview_confirmation".to_string(),
+            template("Interview Confirmed", "en"),
+        );
+        assert!(validate_templates(&templates).is_err());
+
+        let mut unknown = WhatsAppTemplates::new();
+        unknown.insert("offer".to_string(), template("offer", "en"));
+        assert!(validate_templates(&unknown).is_err());
+
+        let mut bad_language = WhatsAppTemplates::new();
+        bad_language.insert(
+            "interview_reminder".to_string(),
+            template("reminder", "english-us"),
+        );
+        assert!(validate_templates(&bad_language).is_err());
+    }
+
+    #[test]
+    fn test_template_payload() {
+        let payload = template_payload(
+            "+14155550123",
+            &template("interview_reminder", "en_US"),
+            &["Ada", "Engineer", "Mon, Mar 2"],
+        );
+        assert_eq!(payload["to"], "14155550123");
+        assert_eq!(payload["template"]["language"]["code"], "en_US");
+        let parameters = payload["template"]["components"][0]["parameters"]
+            .as_array()
+            .unwrap();
+        assert_eq!(parameters.len(), 3);
+        assert_eq!(parameters[1]["text"], "Engineer");
+    }
+}
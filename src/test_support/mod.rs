@@ -0,0 +1,484 @@
+// Test harness for handler-level integration tests
+//
+// `TestApp::new()` builds the router `main` serves, with every middleware layer, over a
+// private in-memory SQLite database with the migrations applied. AWS, OpenAI and Google are
+// replaced by fakes that answer deterministically and record what they were asked to do, so
+// a test can drive a whole flow over HTTP and then check the emails and calendar events it
+// produced. Requests go straight to the router; no port is opened.
+
+// Not every test reads everything the fakes record
+#![allow(dead_code)]
+
+pub mod schema;
+
+use axum::body::Body;
+use axum::http::{header, Method, Request, StatusCode};
+use axum::Router;
+use serde_json::Value;
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use tokio::sync::RwLock;
+use tower::ServiceExt;
+use uuid::Uuid;
+
+use crate::common::config::Config;
+use crate::common::dev_mode::DevModeConfig;
+use crate::common::AppState;
+use crate::services::aws::{AWSError, EmailAttachment};
+use crate::services::google::{
+    CalendarEvent, CalendarEventResponse, ExternalCalendarEvent, GoogleError,
+};
+use crate::services::openai::{OpenAIError, TextGenerationPurpose};
+use crate::services::{
+    AWSService, CalendarClient, EmailSender, GoogleService, LlmClient, ObjectStore, OpenAIService,
+    PDFService, RateLimitService, SettingsService, SmsService, WhatsAppService,
+};
+
+/// Email of the admin every `TestApp` is configured with
+pub const ADMIN_EMAIL: &str = "admin@test.example.com";
+
+const JWT_SECRET: &str = "test-secret-that-is-long-enough-for-config";
+
+/// An email handed to the fake AWS
+#[derive(Debug, Clone)]
+pub struct SentEmail {
+    pub to: Vec<String>,
+    pub subject: String,
+    pub body: String,
+    pub attachments: Vec<String>,
+}
+
+/// Stands in for S3 and SES, keeping files in memory and recording emails
+#[derive(Debug, Default)]
+pub struct FakeAws {
+    pub emails: Mutex<Vec<SentEmail>>,
+    pub files: Mutex<HashMap<String, Vec<u8>>>,
+}
+
+#[async_trait::async_trait]
+impl ObjectStore for FakeAws {
+    async fn upload_file(
+        &self,
+        file_data: Vec<u8>,
+        file_name: &str,
+        _content_type: &str,
+    ) -> Result<String, AWSError> {
+        self.files
+            .lock()
+            .unwrap()
+            .insert(file_name.to_string(), file_data);
+        Ok(file_name.to_string())
+    }
+
+    async fn download_file(&self, key: &str) -> Result<Vec<u8>, AWSError> {
+        self.files
+            .lock()
+            .unwrap()
+            .get(key)
+            .cloned()
+            .ok_or_else(|| AWSError::S3Error(format!("NoSuchKey: {}", key)))
+    }
+
+    async fn delete_file(&self, key: &str) -> Result<(), AWSError> {
+        self.files.lock().unwrap().remove(key);
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl EmailSender for FakeAws {
+    async fn send_email(
+        &self,
+        to: Vec<String>,
+        subject: &str,
+        body: &str,
+        attachments: Option<Vec<EmailAttachment>>,
+    ) -> Result<(), AWSError> {
+        self.emails.lock().unwrap().push(SentEmail {
+            to,
+            subject: subject.to_string(),
+            body: body.to_string(),
+            attachments: attachments
+                .unwrap_or_default()
+                .into_iter()
+                .map(|a| a.filename)
+                .collect(),
+        });
+        Ok(())
+    }
+}
+
+impl FakeAws {
+    /// Emails sent to `address` so far
+    pub fn emails_to(&self, address: &str) -> Vec<SentEmail> {
+        self.emails
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|email| email.to.iter().any(|to| to.eq_ignore_ascii_case(address)))
+            .cloned()
+            .collect()
+    }
+}
+
+/// Stands in for OpenAI: every prompt gets the same canned reply and nothing is flagged
+#[derive(Debug, Default)]
+pub struct FakeOpenAI {
+    pub prompts: Mutex<Vec<String>>,
+}
+
+/// What `FakeOpenAI` answers every text generation request with
+pub const GENERATED_TEXT: &str = "Generated text";
+
+#[async_trait::async_trait]
+impl LlmClient for FakeOpenAI {
+    async fn generate_text(
+        &self,
+        _purpose: TextGenerationPurpose,
+        prompt: &str,
+        _context: Option<Value>,
+    ) -> Result<String, OpenAIError> {
+        self.prompts.lock().unwrap().push(prompt.to_string());
+        Ok(GENERATED_TEXT.to_string())
+    }
+
+    async fn moderate(&self, _input: &str) -> Result<Vec<String>, OpenAIError> {
+        Ok(Vec::new())
+    }
+}
+
+/// Stands in for Google Calendar, numbering events in the order they are created
+#[derive(Debug, Default)]
+pub struct FakeGoogle {
+    pub events: Mutex<Vec<CalendarEvent>>,
+    /// While set, creating an event fails as if Google were down
+    pub unavailable: AtomicBool,
+}
+
+#[async_trait::async_trait]
+impl CalendarClient for FakeGoogle {
+    async fn create_calendar_event(
+        &self,
+        event: CalendarEvent,
+    ) -> Result<CalendarEventResponse, GoogleError> {
+        if self.unavailable.load(Ordering::SeqCst) {
+            return Err(GoogleError::Unavailable("HTTP 503: backend error".to_string()));
+        }
+        let mut events = self.events.lock().unwrap();
+        let id = format!("event-{}", events.len() + 1);
+        let hangout_link = event
+            .create_meet_link
+            .then(|| format!("https://meet.example.com/{}", id));
+        events.push(event);
+        Ok(CalendarEventResponse {
+            html_link: format!("https://calendar.example.com/{}", id),
+            id,
+            hangout_link,
+        })
+    }
+
+    async fn get_calendar_event(
+        &self,
+        event_id: &str,
+    ) -> Result<Option<ExternalCalendarEvent>, GoogleError> {
+        let events = self.events.lock().unwrap();
+        let index = event_id
+            .strip_prefix("event-")
+            .and_then(|n| n.parse::<usize>().ok())
+            .and_then(|n| n.checked_sub(1));
+        Ok(index
+            .and_then(|i| events.get(i))
+            .map(|event| ExternalCalendarEvent {
+                id: event_id.to_string(),
+                start: Some(event.start),
+                end: Some(event.end),
+                updated: None,
+            }))
+    }
+}
+
+/// A response body, parsed as JSON when it is JSON
+pub struct TestResponse {
+    pub status: StatusCode,
+    pub body: Value,
+}
+
+/// The full application over an in-memory database, with fake external services
+pub struct TestApp {
+    pub router: Router,
+    pub state: AppState,
+    pub aws: Arc<FakeAws>,
+    pub openai: Arc<FakeOpenAI>,
+    pub google: Arc<FakeGoogle>,
+    files_dir: PathBuf,
+}
+
+impl TestApp {
+    pub async fn new() -> Self {
+        let name = Uuid::new_v4().simple().to_string();
+        let files_dir = std::env::temp_dir().join(format!("job_api_test_{}", name));
+        let dir = |sub: &str| files_dir.join(sub).display().to_string();
+        let vars: HashMap<&str, String> = HashMap::from([
+            ("JWT_SECRET", JWT_SECRET.to_string()),
+            ("ADMIN_EMAILS", ADMIN_EMAIL.to_string()),
+            (
+                "DATABASE_URL",
+                format!("sqlite:file:{}?mode=memory&cache=shared", name),
+            ),
+            ("RESUMES_DIR", dir("resumes")),
+            ("DOCUMENTS_DIR", dir("documents")),
+            ("UPLOAD_SESSIONS_DIR", dir("uploads/sessions")),
+            ("AVATARS_DIR", dir("uploads/avatars")),
+            ("LOGOS_DIR", dir("uploads/logos")),
+        ]);
+        let dev_mode = DevModeConfig {
+            enabled: false,
+            user_email: String::new(),
+            user_name: String::new(),
+            user_is_admin: false,
+        };
+        let config = Config::from_vars(|key| vars.get(key).cloned(), dev_mode)
+            .expect("test configuration is valid");
+        for path in [
+            &config.resumes_dir,
+            &config.documents_dir,
+            &config.upload_sessions_dir,
+            &config.avatars_dir,
+            &config.logos_dir,
+            &files_dir.join("job-images/logos"),
+            &files_dir.join("job-images/jobs"),
+        ] {
+            std::fs::create_dir_all(path).unwrap();
+        }
+
+        // The named in-memory database lives as long as one connection to it stays open
+        let pool = SqlitePoolOptions::new()
+            .min_connections(1)
+            .idle_timeout(None)
+            .max_lifetime(None)
+            .connect_with(SqliteConnectOptions::from_str(&config.database_url).unwrap())
+            .await
+            .unwrap();
+        crate::common::migrations::run_migrations(&pool, false)
+            .await
+            .unwrap();
+        let jwt_keys = crate::auth::keys::JwtKeys::default();
+        crate::auth::keys::init(&pool, &jwt_keys).await.unwrap();
+
+        let settings_service = Arc::new(SettingsService::new(pool.clone()));
+        settings_service
+            .set_setting("rate_limit_enabled", "false", false, None)
+            .await
+            .unwrap();
+
+        let aws = Arc::new(FakeAws::default());
+        let openai = Arc::new(FakeOpenAI::default());
+        let google = Arc::new(FakeGoogle::default());
+        // The concrete services stay unconfigured, so anything that bypasses the provider
+        // traits fails instead of reaching a real provider
+        let aws_service = Arc::new(AWSService::new(settings_service.clone()));
+        let openai_service = Arc::new(OpenAIService::new(settings_service.clone(), pool.clone()));
+        let google_service = Arc::new(GoogleService::new(settings_service.clone()));
+        let http = reqwest::Client::new();
+        let rate_limit_service = Arc::new(RateLimitService::new(settings_service.clone()));
+
+        let state = AppState {
+            db: pool.clone(),
+            db_read: pool.clone(),
+            resumes_dir: config.resumes_dir.clone(),
+            documents_dir: config.documents_dir.clone(),
+            upload_sessions_dir: config.upload_sessions_dir.clone(),
+            avatars_dir: config.avatars_dir.clone(),
+            logos_dir: config.logos_dir.clone(),
+            job_images_logos_dir: files_dir.join("job-images/logos"),
+            job_images_jobs_dir: files_dir.join("job-images/jobs"),
+            http: http.clone(),
+            jwt_secret: config.jwt_secret.clone(),
+            jwt_keys,
+            google_client_id: None,
+            apple_client_ids: Vec::new(),
+            apple_keys: crate::auth::apple::AppleKeyCache::default(),
+            openai_api_key: None,
+            openai_model: config.openai_model.clone(),
+            admin_emails: config.admin_emails.clone(),
+            dev_mode: config.dev_mode.clone(),
+            settings_service: settings_service.clone(),
+            openai_service,
+            aws_service: aws_service.clone(),
+            google_service,
+            email: aws.clone(),
+            object_store: aws.clone(),
+            llm: openai.clone(),
+            calendar: google.clone(),
+            rate_limit_service: rate_limit_service.clone(),
+            pdf_service: Arc::new(PDFService::new(
+                pool.clone(),
+                settings_service.clone(),
+                aws_service.clone(),
+            )),
+            sms_service: Arc::new(SmsService::new(
+                settings_service.clone(),
+                aws_service,
+                http.clone(),
+            )),
+            whatsapp_service: Arc::new(WhatsAppService::new(settings_service, http)),
+            connection_manager: crate::messages::services::ConnectionManager::new(),
+            feed_cache: crate::jobs::services::FeedCache::default(),
+            similar_jobs_cache: crate::jobs::services::SimilarJobsCache::default(),
+            job_editors: crate::jobs::services::JobEditors::default(),
+            config: Arc::new(config.clone()),
+        };
+
+        let router = crate::app_router(
+            Arc::new(RwLock::new(state.clone())),
+            rate_limit_service,
+            &config.cors_origins,
+        );
+
+        Self {
+            router,
+            state,
+            aws,
+            openai,
+            google,
+            files_dir,
+        }
+    }
+
+    /// Add a user and return their ID
+    pub async fn create_user(&self, email: &str, name: &str) -> String {
+        let id = Uuid::new_v4().to_string();
+        sqlx::query("INSERT INTO users (id, email, name) VALUES (?, ?, ?)")
+            .bind(&id)
+            .bind(email)
+            .bind(name)
+            .execute(&self.state.db)
+            .await
+            .unwrap();
+        id
+    }
+
+    /// Add the configured admin and return their ID
+    pub async fn create_admin(&self) -> String {
+        self.create_user(ADMIN_EMAIL, "Test Admin").await
+    }
+
+    /// Add an active job open for applications and return its ID
+    pub async fn create_job(&self, title: &str) -> String {
+        let id = Uuid::new_v4().to_string();
+        sqlx::query(
+            r#"INSERT INTO jobs (id, title, description, location, company, job_type, status, published_at)
+            VALUES (?, ?, ?, 'Remote', 'Test Co', 'full-time', 'active', datetime('now'))"#,
+        )
+        .bind(&id)
+        .bind(title)
+        .bind(format!("{} at Test Co", title))
+        .execute(&self.state.db)
+        .await
+        .unwrap();
+        id
+    }
+
+    /// A session token for `user_id`, as sign-in would issue
+    pub async fn token_for(&self, user_id: &str) -> String {
+        self.state
+            .jwt_keys
+            .sign(&crate::auth::keys::session_claims(user_id))
+            .await
+            .unwrap()
+    }
+
+    /// Send a request through the full middleware stack
+    pub async fn request(
+        &self,
+        method: Method,
+        uri: &str,
+        token: Option<&str>,
+        body: Option<Value>,
+    ) -> TestResponse {
+        let mut builder = Request::builder().method(method).uri(uri);
+        if let Some(token) = token {
+            builder = builder.header(header::AUTHORIZATION, format!("Bearer {}", token));
+        }
+        let request = match body {
+            Some(body) => builder
+                .header(header::CONTENT_TYPE, "application/json")
+                .body(Body::from(body.to_string())),
+            None => builder.body(Body::empty()),
+        }
+        .unwrap();
+        self.send(request).await
+    }
+
+    /// Send a multipart form; each part is a field name, an optional file name and its bytes
+    pub async fn post_multipart(
+        &self,
+        uri: &str,
+        token: &str,
+        parts: &[(&str, Option<&str>, &[u8])],
+    ) -> TestResponse {
+        const BOUNDARY: &str = "test-multipart-boundary";
+        let mut body = Vec::new();
+        for (name, filename, data) in parts {
+            body.extend_from_slice(format!("--{}\r\n", BOUNDARY).as_bytes());
+            let disposition = match filename {
+                Some(filename) => format!(
+                    "Content-Disposition: form-data; name=\"{}\"; filename=\"{}\"\r\n\r\n",
+                    name, filename
+                ),
+                None => format!("Content-Disposition: form-data; name=\"{}\"\r\n\r\n", name),
+            };
+            body.extend_from_slice(disposition.as_bytes());
+            body.extend_from_slice(data);
+            body.extend_from_slice(b"\r\n");
+        }
+        body.extend_from_slice(format!("--{}--\r\n", BOUNDARY).as_bytes());
+
+        let request = Request::builder()
+            .method(Method::POST)
+            .uri(uri)
+            .header(header::AUTHORIZATION, format!("Bearer {}", token))
+            .header(
+                header::CONTENT_TYPE,
+                format!("multipart/form-data; boundary={}", BOUNDARY),
+            )
+            .body(Body::from(body))
+            .unwrap();
+        self.send(request).await
+    }
+
+    async fn send(&self, request: Request<Body>) -> TestResponse {
+        let response = self.router.clone().oneshot(request).await.unwrap();
+        let status = response.status();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body = serde_json::from_slice(&bytes)
+            .unwrap_or_else(|_| Value::String(String::from_utf8_lossy(&bytes).into_owned()));
+        TestResponse { status, body }
+    }
+
+    pub async fn get(&self, uri: &str, token: &str) -> TestResponse {
+        self.request(Method::GET, uri, Some(token), None).await
+    }
+
+    pub async fn post(&self, uri: &str, token: &str, body: Value) -> TestResponse {
+        self.request(Method::POST, uri, Some(token), Some(body))
+            .await
+    }
+
+    pub async fn patch(&self, uri: &str, token: &str, body: Value) -> TestResponse {
+        self.request(Method::PATCH, uri, Some(token), Some(body))
+            .await
+    }
+}
+
+impl Drop for TestApp {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.files_dir);
+    }
+}
@@ -0,0 +1,163 @@
+// JSON schema snapshots for API responses
+//
+// `assert_compatible` derives a schema (field names and JSON types) from a real response and
+// checks it against the snapshot committed under `src/test_support/snapshots/`. Removing or
+// renaming a field, or changing its type, fails the test; new fields pass. Run the tests with
+// UPDATE_SNAPSHOTS=1 to rewrite the snapshots after an intended change, and commit the result.
+
+use serde_json::{json, Map, Value};
+use std::path::PathBuf;
+
+fn snapshot_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("src/test_support/snapshots")
+        .join(format!("{}.schema.json", name))
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(n) if n.is_f64() => "number",
+        Value::Number(_) => "integer",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+/// The schema of `value`: its JSON type, the fields of objects and the items of arrays
+pub fn schema_of(value: &Value) -> Value {
+    match value {
+        Value::Object(fields) => {
+            let properties: Map<String, Value> = fields
+                .iter()
+                .map(|(key, field)| (key.clone(), schema_of(field)))
+                .collect();
+            json!({
+                "type": "object",
+                "properties": properties,
+                "required": fields.keys().collect::<Vec<_>>(),
+            })
+        }
+        Value::Array(items) => match items.first() {
+            Some(item) => json!({ "type": "array", "items": schema_of(item) }),
+            None => json!({ "type": "array" }),
+        },
+        other => json!({ "type": type_name(other) }),
+    }
+}
+
+/// Whether a field typed `old` in the snapshot may now be typed `new`. Null only says the
+/// sample had no value, and integers are numbers.
+fn compatible_types(old: &str, new: &str) -> bool {
+    old == new || old == "null" || new == "null" || (old == "number" && new == "integer")
+}
+
+/// Changes from `old` to `new` that would break a client relying on `old`
+pub fn breaking_changes(old: &Value, new: &Value, path: &str) -> Vec<String> {
+    let old_type = old["type"].as_str().unwrap_or("null");
+    let new_type = new["type"].as_str().unwrap_or("null");
+    if !compatible_types(old_type, new_type) {
+        return vec![format!(
+            "`{}` changed from {} to {}",
+            path, old_type, new_type
+        )];
+    }
+
+    let mut changes = Vec::new();
+    if let (Some(old_fields), Some(new_fields)) =
+        (old["properties"].as_object(), new["properties"].as_object())
+    {
+        for (key, old_field) in old_fields {
+            let field_path = format!("{}.{}", path, key);
+            match new_fields.get(key) {
+                Some(new_field) => {
+                    changes.extend(breaking_changes(old_field, new_field, &field_path))
+                }
+                None => changes.push(format!("`{}` was removed", field_path)),
+            }
+        }
+    }
+    if let (Some(old_items), Some(new_items)) = (old.get("items"), new.get("items")) {
+        changes.extend(breaking_changes(
+            old_items,
+            new_items,
+            &format!("{}[]", path),
+        ));
+    }
+    changes
+}
+
+/// Check `response` against the `name` snapshot, writing the snapshot when it doesn't exist
+/// yet or UPDATE_SNAPSHOTS is set
+pub fn assert_compatible(name: &str, response: &Value) {
+    let path = snapshot_path(name);
+    let schema = schema_of(response);
+    let pretty = serde_json::to_string_pretty(&schema).unwrap() + "\n";
+
+    let update = std::env::var("UPDATE_SNAPSHOTS").is_ok_and(|v| v == "1" || v == "true");
+    let existing = std::fs::read_to_string(&path).ok();
+    let Some(existing) = existing.filter(|_| !update) else {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, &pretty).unwrap();
+        return;
+    };
+
+    let snapshot: Value = serde_json::from_str(&existing)
+        .unwrap_or_else(|e| panic!("{} is not valid JSON: {}", path.display(), e));
+    let changes = breaking_changes(&snapshot, &schema, name);
+    assert!(
+        changes.is_empty(),
+        "{} response is no longer compatible with {}:\n  - {}\nIf the change is intended, \
+         rerun with UPDATE_SNAPSHOTS=1 and commit the updated snapshot.",
+        name,
+        path.display(),
+        changes.join("\n  - ")
+    );
+    if existing != pretty {
+        eprintln!(
+            "{} response has fields the snapshot lacks; rerun with UPDATE_SNAPSHOTS=1 to record them",
+            name
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_breaking_changes() {
+        let old = schema_of(&json!({
+            "id": "A1",
+            "score": 1.5,
+            "notes": null,
+            "tags": [{ "name": "x" }],
+        }));
+
+        let added = schema_of(&json!({
+            "id": "A1",
+            "score": 2,
+            "notes": "now set",
+            "tags": [{ "name": "x", "color": "red" }],
+            "extra": true,
+        }));
+        assert!(breaking_changes(&old, &added, "r").is_empty());
+
+        let broken = schema_of(&json!({
+            "identifier": "A1",
+            "score": "high",
+            "notes": null,
+            "tags": [{ "label": "x" }],
+        }));
+        assert_eq!(
+            breaking_changes(&old, &broken, "r"),
+            vec![
+                "`r.id` was removed",
+                "`r.score` changed from number to string",
+                "`r.tags[].name` was removed",
+            ]
+        );
+    }
+}